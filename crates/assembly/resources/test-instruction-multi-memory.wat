@@ -0,0 +1,32 @@
+(module
+    (type $f (func (param i32) (result i32)))
+    (import "env" "memory" (memory $m0 1))
+    (memory $m1 1 2)
+    (table $t0 1 funcref)
+    (table $t1 2 funcref)
+    (func $load (param i32) (result i32)
+        (i32.load $m1 offset=4 (local.get 0))
+        (i32.store $m0 (local.get 0) (i32.const 1))
+        (i64.load8_u 1 offset=8 align=1 (local.get 0))
+        (drop)
+    )
+    (func (param i32)
+        memory.size $m1
+        memory.grow $m1
+        drop
+        i32.const 0
+        i32.const 0
+        i32.const 4
+        memory.copy $m0 $m1
+        local.get 0
+        v128.load8_lane $m1 offset=1 3
+        drop
+        local.get 0
+        v128.load32_lane 2
+        drop
+        i32.const 0
+        call_indirect $t1 (type $f)
+        drop
+    )
+    (export "memory1" (memory $m1))
+)
@@ -0,0 +1,35 @@
+(module
+    (memory 1)
+    (func (param v128) (result v128)
+        (v128.load offset=16 (i32.const 0))
+        (v128.const i32x4 1 2 3 4)
+        (i32x4.add)
+        (i8x16.shuffle 0 1 2 3 4 5 6 7 16 17 18 19 20 21 22 23 (local.get 0))
+        (i16x8.extract_lane_s 7)
+        (i16x8.splat)
+        (v128.load32_lane align=1 3 (i32.const 8))
+    )
+)
+
+;; 0x001b | 3d          | size of function
+;; 0x001c | 00          | 0 local blocks
+;; 0x001d | 41 00       | I32Const { value: 0 }
+;; 0x001f | fd 00 04 10 | V128Load { memarg: MemArg { align: 4, offset: 16 } }
+;; 0x0023 | fd 0c 01 00 | V128Const { value: V128(..) }
+;;        | 00 00 02 00
+;;        | 00 00 03 00
+;;        | 00 00 04 00
+;;        | 00 00
+;; 0x0035 | fd ae 01    | I32x4Add
+;; 0x0038 | 20 00       | LocalGet { local_index: 0 }
+;; 0x003a | fd 0d 00 01 | I8x16Shuffle { lanes: [0, 1, 2, 3, 4, 5, 6, 7, 16, 17, 18, 19, 20, 21, 22, 23] }
+;;        | 02 03 04 05
+;;        | 06 07 10 11
+;;        | 12 13 14 15
+;;        | 16 17
+;; 0x004c | fd 18 07    | I16x8ExtractLaneS { lane: 7 }
+;; 0x004f | fd 10       | I16x8Splat
+;; 0x0051 | 41 08       | I32Const { value: 8 }
+;; 0x0053 | fd 56 00 00 | V128Load32Lane { memarg: MemArg { align: 0, offset: 0 }, lane: 3 }
+;;        | 03
+;; 0x0058 | 0b          | End
@@ -0,0 +1,3660 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # WebAssembly 文本格式汇编器
+//!
+//! <https://webassembly.github.io/spec/core/text/index.html>
+//!
+//! 把 WebAssembly 文本格式（`.wat`）转换为语法树（`anvm_ast::ast::Module`），
+//! 语法树可以再使用 `anvm_binary_encoder` 转换为二进制格式。
+//!
+//! 支持：
+//!
+//! - 平铺（flat）和折叠（folded）两种形式的指令，比如
+//!   `local.get 0 i32.const 1 i32.add` 以及 `(i32.add (local.get 0) (i32.const 1))`；
+//! - 使用 `$name` 标识符引用各种项目、局部变量以及结构块的标签，
+//!   标识符会被写入到模块的 `name` 自定义段；
+//! - 内联的导入和导出，比如 `(func $f (export "f") ...)`、`(memory (import "env" "mem") 1)`；
+//! - 内联的类型，比如 `(func (param i32) (result i32) ...)`，
+//!   如果类型表里不存在相同签名的类型，会在类型表的末尾追加一个新类型；
+//! - 内联的表元素和内存数据，比如 `(table funcref (elem $f0 $f1))`、`(memory (data "abc"))`；
+//! - 引用类型，以及主动（active）、被动（passive）和声明（declarative）三种元素段；
+//! - `v128` 类型以及固定宽度 SIMD 指令，比如 `v128.const i32x4 1 2 3 4`、`i8x16.shuffle 0 1 ...`；
+//! - 异常处理的标签以及指令，比如 `(tag $e (param i32))`、`try_table (catch $e 0)`；
+//! - `(module quote "...")` 形式的模块。
+//!
+//! 目前 VM 尚不支持的内容，比如被动的数据段等，
+//! 会产生 `AssemblyError::UnsupportedError` 错误。
+//!
+//! 汇编分两遍进行：
+//!
+//! 1. 第一遍只收集各个项目的索引和名称（类型项则直接解析），
+//!    这样才能在定义之前引用后面的项目，比如 `call $f` 出现在 `$f` 的定义之前；
+//! 2. 第二遍按照文本的次序生成各个项目。
+
+use std::collections::HashMap;
+
+use anvm_ast::{
+    ast::{
+        CodeItem, CustomItem, DataItem, DataMode, ElementItem, ElementItems, ElementMode,
+        ExportDescriptor, ExportItem, FunctionIndexAndBlockLabelsPair,
+        FunctionIndexAndLocalVariableNamesPair, FunctionType, GlobalItem, GlobalType,
+        ImportDescriptor, ImportItem, IndexNamePair, Limit, LocalGroup, MemoryType, Module,
+        NameCollection, TableType, TagType, TypeItem,
+    },
+    instruction::{BlockType, CatchClause, Instruction, MemoryArgument},
+    types::{RefType, ValueType},
+};
+
+use crate::{
+    error::AssemblyError,
+    number_literal,
+    s_expression::{self, SExpression},
+};
+
+/// 内存页面的大小，用于计算内联数据的内存块的页面数
+const MEMORY_PAGE_SIZE: usize = 65536;
+
+/// 汇编文本格式的源码
+///
+/// 源码可以是一个 `(module ...)` 表达式，也可以省略 `(module ...)`，
+/// 直接书写模块的各个字段，比如 `(func ...) (export ...)`。
+pub fn assemble(source: &str) -> Result<Module, AssemblyError> {
+    let expressions = s_expression::parse(source)?;
+
+    match expressions.as_slice() {
+        [expression] if expression.get_keyword() == Some("module") => assemble_module(expression),
+        _ => assemble_fields(&expressions),
+    }
+}
+
+/// 汇编一个 `(module ...)` 表达式
+pub fn assemble_module(expression: &SExpression) -> Result<Module, AssemblyError> {
+    if expression.get_keyword() != Some("module") {
+        return Err(syntax_error(expression, "expected a module"));
+    }
+
+    let items = expression.as_list().unwrap();
+    let mut position = 1;
+
+    // 模块的标识符（`$id`）不影响模块的内容，忽略之
+    take_id(items, &mut position);
+
+    match items.get(position).and_then(|item| item.as_symbol()) {
+        Some("quote") => {
+            let mut source = String::new();
+            for item in &items[position + 1..] {
+                let text = item
+                    .as_text()
+                    .ok_or_else(|| syntax_error(item, "expected a string"))?;
+                let fragment = std::str::from_utf8(text)
+                    .map_err(|_| syntax_error(item, "malformed UTF-8 encoding"))?;
+                source.push_str(fragment);
+                source.push(' ');
+            }
+
+            let expressions = s_expression::parse(&source)?;
+            assemble_fields(&expressions)
+        }
+        Some("binary") => Err(syntax_error(
+            expression,
+            "binary module is not supported by the assembler",
+        )),
+        _ => assemble_fields(&items[position..]),
+    }
+}
+
+fn assemble_fields(fields: &[SExpression]) -> Result<Module, AssemblyError> {
+    let mut assembler = Assembler::new();
+
+    for field in fields {
+        assembler.declare_field(field)?;
+    }
+
+    for field in fields {
+        assembler.define_field(field)?;
+    }
+
+    Ok(assembler.finish())
+}
+
+fn syntax_error(expression: &SExpression, message: &str) -> AssemblyError {
+    AssemblyError::SyntaxError(expression.get_line(), message.to_string())
+}
+
+fn unsupported_error(expression: &SExpression, description: &str) -> AssemblyError {
+    AssemblyError::UnsupportedError(
+        expression.get_line(),
+        format!("{} is not supported", description),
+    )
+}
+
+/// 某一类项目（比如函数、表、局部变量等）的索引空间
+#[derive(Default)]
+struct IndexSpace {
+    count: u32,
+    indices: HashMap<String, u32>,
+    names: Vec<IndexNamePair>,
+}
+
+impl IndexSpace {
+    /// 分配一个新索引，参数 option_id 为不含 `$` 前缀的标识符，
+    /// 参数 option_name 为名称注解 `(@name "...")` 的内容。
+    ///
+    /// 项目的名称（即写入 `name` 段的名称）优先使用名称注解，其次使用标识符。
+    fn declare(
+        &mut self,
+        option_id: Option<&str>,
+        option_name: Option<String>,
+        expression: &SExpression,
+    ) -> Result<u32, AssemblyError> {
+        let index = self.count;
+
+        if let Some(id) = option_id {
+            if self.indices.insert(id.to_string(), index).is_some() {
+                return Err(syntax_error(
+                    expression,
+                    &format!("duplicate identifier \"${}\"", id),
+                ));
+            }
+        }
+
+        if let Some(name) = option_name.or_else(|| option_id.map(|id| id.to_string())) {
+            self.names.push(IndexNamePair { index, name });
+        }
+
+        self.count += 1;
+        Ok(index)
+    }
+
+    /// 解析索引，索引可以是数字或者 `$id`
+    fn resolve(&self, expression: &SExpression) -> Result<u32, AssemblyError> {
+        let symbol = expression
+            .as_symbol()
+            .ok_or_else(|| syntax_error(expression, "expected an index"))?;
+
+        if let Some(id) = symbol.strip_prefix('$') {
+            self.indices.get(id).copied().ok_or_else(|| {
+                syntax_error(expression, &format!("unknown identifier \"{}\"", symbol))
+            })
+        } else {
+            number_literal::parse_u32(symbol)
+                .ok_or_else(|| syntax_error(expression, &format!("invalid index \"{}\"", symbol)))
+        }
+    }
+}
+
+/// 函数体（或者常量表达式）的汇编上下文
+#[derive(Default)]
+struct FunctionContext {
+    local_variables: IndexSpace,
+
+    /// 当前所在的各层结构块的标签，最内层的结构块位于末尾
+    labels: Vec<Option<String>>,
+
+    /// 下一个结构块的索引
+    next_block_index: u32,
+    block_labels: Vec<IndexNamePair>,
+
+    instructions: Vec<Instruction>,
+}
+
+impl FunctionContext {
+    fn enter_block(&mut self, option_label: Option<&str>) -> u32 {
+        let block_index = self.next_block_index;
+        self.next_block_index += 1;
+
+        if let Some(label) = option_label {
+            self.block_labels.push(IndexNamePair {
+                index: block_index,
+                name: label.to_string(),
+            });
+        }
+
+        self.labels
+            .push(option_label.map(|label| label.to_string()));
+        block_index
+    }
+
+    fn resolve_label(&self, expression: &SExpression) -> Result<u32, AssemblyError> {
+        let symbol = expression
+            .as_symbol()
+            .ok_or_else(|| syntax_error(expression, "expected a label"))?;
+
+        if let Some(id) = symbol.strip_prefix('$') {
+            self.labels
+                .iter()
+                .rev()
+                .position(|option_label| option_label.as_deref() == Some(id))
+                .map(|depth| depth as u32)
+                .ok_or_else(|| syntax_error(expression, &format!("unknown label \"{}\"", symbol)))
+        } else {
+            number_literal::parse_u32(symbol)
+                .ok_or_else(|| syntax_error(expression, &format!("invalid label \"{}\"", symbol)))
+        }
+    }
+}
+
+/// 函数的类型，可以是类型表里的类型索引，也可以是内联的参数和返回值列表，
+/// 或者两者兼有。
+#[derive(Default)]
+struct TypeUse {
+    option_type_index: Option<u32>,
+    params: Vec<(/* id */ Option<String>, ValueType)>,
+    results: Vec<ValueType>,
+}
+
+impl TypeUse {
+    fn is_inline_empty(&self) -> bool {
+        self.params.is_empty() && self.results.is_empty()
+    }
+
+    fn to_function_type(&self) -> FunctionType {
+        FunctionType {
+            params: self
+                .params
+                .iter()
+                .map(|(_, value_type)| value_type.clone())
+                .collect(),
+            results: self.results.clone(),
+        }
+    }
+}
+
+struct Assembler {
+    module: Module,
+
+    types: IndexSpace,
+    functions: IndexSpace,
+    tables: IndexSpace,
+    memory_blocks: IndexSpace,
+    global_variables: IndexSpace,
+    tags: IndexSpace,
+    elements: IndexSpace,
+    datas: IndexSpace,
+
+    /// 是否已经出现过函数、表、内存块、全局变量或者标签的定义（即非导入项），
+    /// 导入项必须位于所有定义之前。
+    has_definition: bool,
+
+    local_variable_names: Vec<FunctionIndexAndLocalVariableNamesPair>,
+    block_labels: Vec<FunctionIndexAndBlockLabelsPair>,
+}
+
+impl Assembler {
+    fn new() -> Self {
+        Assembler {
+            module: Module {
+                custom_items: vec![],
+                type_items: vec![],
+                import_items: vec![],
+                internal_function_to_type_index_list: vec![],
+                tables: vec![],
+                memory_blocks: vec![],
+                tag_items: vec![],
+                global_items: vec![],
+                export_items: vec![],
+                start_function_index: None,
+                element_items: vec![],
+                code_items: vec![],
+                data_items: vec![],
+                data_count: None,
+            },
+            types: IndexSpace::default(),
+            functions: IndexSpace::default(),
+            tables: IndexSpace::default(),
+            memory_blocks: IndexSpace::default(),
+            global_variables: IndexSpace::default(),
+            tags: IndexSpace::default(),
+            elements: IndexSpace::default(),
+            datas: IndexSpace::default(),
+            has_definition: false,
+            local_variable_names: vec![],
+            block_labels: vec![],
+        }
+    }
+
+    fn finish(mut self) -> Module {
+        // 指令 `memory.init` 和 `data.drop` 需要 `数据条目段`
+        let is_data_count_required = self.module.code_items.iter().any(|code_item| {
+            code_item.instruction_items.iter().any(|instruction| {
+                matches!(
+                    instruction,
+                    Instruction::MemoryInit(_, _) | Instruction::DataDrop(_)
+                )
+            })
+        });
+
+        if is_data_count_required {
+            self.module.data_count = Some(self.module.data_items.len() as u32);
+        }
+
+        // 名称子段按照其 id 的次序排列
+        let name_collections = vec![
+            NameCollection::FunctionNames(self.functions.names),
+            NameCollection::LocalVariableNamesPairList(self.local_variable_names),
+            NameCollection::BlockLabelsPairList(self.block_labels),
+            NameCollection::TypeNames(self.types.names),
+            NameCollection::TableNames(self.tables.names),
+            NameCollection::MemoryBlockNames(self.memory_blocks.names),
+            NameCollection::GlobalVariableNames(self.global_variables.names),
+            NameCollection::ElementNames(self.elements.names),
+            NameCollection::DataNames(self.datas.names),
+            NameCollection::TagNames(self.tags.names),
+        ]
+        .into_iter()
+        .filter(|name_collection| match name_collection {
+            NameCollection::FunctionNames(names)
+            | NameCollection::TypeNames(names)
+            | NameCollection::TableNames(names)
+            | NameCollection::MemoryBlockNames(names)
+            | NameCollection::GlobalVariableNames(names)
+            | NameCollection::ElementNames(names)
+            | NameCollection::DataNames(names)
+            | NameCollection::TagNames(names) => !names.is_empty(),
+            NameCollection::LocalVariableNamesPairList(pairs) => !pairs.is_empty(),
+            NameCollection::BlockLabelsPairList(pairs) => !pairs.is_empty(),
+        })
+        .collect::<Vec<NameCollection>>();
+
+        if !name_collections.is_empty() {
+            self.module
+                .custom_items
+                .push(CustomItem::NameCollections(name_collections));
+        }
+
+        self.module
+    }
+
+    // 第一遍
+
+    fn declare_field(&mut self, field: &SExpression) -> Result<(), AssemblyError> {
+        let keyword = field
+            .get_keyword()
+            .ok_or_else(|| syntax_error(field, "expected a module field"))?;
+        let items = field.as_list().unwrap();
+
+        match keyword {
+            "type" => self.declare_type(field, items),
+            "import" => {
+                let descriptor = items
+                    .get(3)
+                    .ok_or_else(|| syntax_error(field, "expected an import descriptor"))?;
+                let descriptor_items = descriptor
+                    .as_list()
+                    .ok_or_else(|| syntax_error(descriptor, "expected an import descriptor"))?;
+                let mut position = 1;
+                let (option_id, option_name) = take_field_id(descriptor_items, &mut position)?;
+                self.declare_import(descriptor, option_id, option_name)
+            }
+            "func" | "table" | "memory" | "global" | "tag" => {
+                let mut position = 1;
+                let (option_id, option_name) = take_field_id(items, &mut position)?;
+                skip_inline_exports(items, &mut position);
+
+                let is_import =
+                    items.get(position).and_then(|item| item.get_keyword()) == Some("import");
+
+                if is_import {
+                    return self.declare_import(field, option_id, option_name);
+                }
+
+                self.has_definition = true;
+
+                match keyword {
+                    "func" => {
+                        self.functions.declare(option_id, option_name, field)?;
+                    }
+                    "table" => {
+                        self.tables.declare(option_id, option_name, field)?;
+
+                        // 内联的表元素 `(table funcref (elem ...))` 会产生一个匿名的元素项
+                        if items[position..]
+                            .iter()
+                            .any(|item| item.get_keyword() == Some("elem"))
+                        {
+                            self.elements.declare(None, None, field)?;
+                        }
+                    }
+                    "memory" => {
+                        self.memory_blocks.declare(option_id, option_name, field)?;
+
+                        // 内联的数据 `(memory (data ...))` 会产生一个匿名的数据项
+                        if items[position..]
+                            .iter()
+                            .any(|item| item.get_keyword() == Some("data"))
+                        {
+                            self.datas.declare(None, None, field)?;
+                        }
+                    }
+                    "global" => {
+                        self.global_variables
+                            .declare(option_id, option_name, field)?;
+                    }
+                    _ => {
+                        self.tags.declare(option_id, option_name, field)?;
+                    }
+                }
+
+                Ok(())
+            }
+            "elem" => {
+                let mut position = 1;
+                let (option_id, option_name) = take_field_id(items, &mut position)?;
+                self.elements.declare(option_id, option_name, field)?;
+                Ok(())
+            }
+            "data" => {
+                let mut position = 1;
+                let (option_id, option_name) = take_field_id(items, &mut position)?;
+                self.datas.declare(option_id, option_name, field)?;
+                Ok(())
+            }
+            "export" | "start" => Ok(()),
+            _ => Err(syntax_error(
+                field,
+                &format!("unknown module field \"{}\"", keyword),
+            )),
+        }
+    }
+
+    fn declare_type(
+        &mut self,
+        field: &SExpression,
+        items: &[SExpression],
+    ) -> Result<(), AssemblyError> {
+        // (type $id? (func (param ...)* (result ...)*))
+        let mut position = 1;
+        let (option_id, option_name) = take_field_id(items, &mut position)?;
+
+        let function_expression = items
+            .get(position)
+            .filter(|item| item.get_keyword() == Some("func"))
+            .ok_or_else(|| syntax_error(field, "expected a function type"))?;
+
+        let function_items = function_expression.as_list().unwrap();
+        let mut function_position = 1;
+        let type_use = parse_inline_type_use(function_items, &mut function_position)?;
+        expect_end(function_items, function_position)?;
+        expect_end(items, position + 1)?;
+
+        self.types.declare(option_id, option_name, field)?;
+        self.module
+            .type_items
+            .push(TypeItem::FunctionType(type_use.to_function_type()));
+
+        Ok(())
+    }
+
+    fn declare_import(
+        &mut self,
+        expression: &SExpression,
+        option_id: Option<&str>,
+        option_name: Option<String>,
+    ) -> Result<(), AssemblyError> {
+        if self.has_definition {
+            return Err(syntax_error(
+                expression,
+                "import must occur before all function, table, memory and global definitions",
+            ));
+        }
+
+        match expression.get_keyword() {
+            Some("func") => self.functions.declare(option_id, option_name, expression)?,
+            Some("table") => self.tables.declare(option_id, option_name, expression)?,
+            Some("memory") => self
+                .memory_blocks
+                .declare(option_id, option_name, expression)?,
+            Some("global") => self
+                .global_variables
+                .declare(option_id, option_name, expression)?,
+            Some("tag") => self.tags.declare(option_id, option_name, expression)?,
+            _ => return Err(syntax_error(expression, "unknown import descriptor")),
+        };
+
+        Ok(())
+    }
+
+    // 第二遍
+
+    fn define_field(&mut self, field: &SExpression) -> Result<(), AssemblyError> {
+        let keyword = field.get_keyword().unwrap();
+        let items = field.as_list().unwrap();
+
+        match keyword {
+            "type" => Ok(()),
+            "import" => {
+                // (import "module" "name" (func|table|memory|global|tag $id? ...))
+                let module_name = get_name(field, items, 1)?;
+                let item_name = get_name(field, items, 2)?;
+                let descriptor = &items[3];
+                let descriptor_items = descriptor.as_list().unwrap();
+
+                let mut position = 1;
+                take_id(descriptor_items, &mut position);
+                self.define_import(
+                    descriptor,
+                    descriptor_items,
+                    position,
+                    module_name,
+                    item_name,
+                )?;
+                expect_end(items, 4)
+            }
+            "func" => self.define_function(field, items),
+            "table" => self.define_table(field, items),
+            "memory" => self.define_memory_block(field, items),
+            "global" => self.define_global(field, items),
+            "tag" => self.define_tag(field, items),
+            "export" => {
+                // (export "name" (func|table|memory|global|tag index))
+                let name = get_name(field, items, 1)?;
+                let descriptor = items
+                    .get(2)
+                    .ok_or_else(|| syntax_error(field, "expected an export descriptor"))?;
+                let descriptor_items = descriptor
+                    .as_list()
+                    .ok_or_else(|| syntax_error(descriptor, "expected an export descriptor"))?;
+
+                let index_expression = descriptor_items
+                    .get(1)
+                    .ok_or_else(|| syntax_error(descriptor, "expected an index"))?;
+                expect_end(descriptor_items, 2)?;
+                expect_end(items, 3)?;
+
+                let export_descriptor = match descriptor.get_keyword() {
+                    Some("func") => {
+                        ExportDescriptor::FunctionIndex(self.functions.resolve(index_expression)?)
+                    }
+                    Some("table") => {
+                        ExportDescriptor::TableIndex(self.tables.resolve(index_expression)?)
+                    }
+                    Some("memory") => ExportDescriptor::MemoryBlockIndex(
+                        self.memory_blocks.resolve(index_expression)?,
+                    ),
+                    Some("global") => ExportDescriptor::GlobalItemIndex(
+                        self.global_variables.resolve(index_expression)?,
+                    ),
+                    Some("tag") => ExportDescriptor::TagIndex(self.tags.resolve(index_expression)?),
+                    _ => return Err(syntax_error(descriptor, "unknown export descriptor")),
+                };
+
+                self.module.export_items.push(ExportItem {
+                    name,
+                    export_descriptor,
+                });
+                Ok(())
+            }
+            "start" => {
+                // (start index)
+                let index_expression = items
+                    .get(1)
+                    .ok_or_else(|| syntax_error(field, "expected a function index"))?;
+                expect_end(items, 2)?;
+
+                if self.module.start_function_index.is_some() {
+                    return Err(syntax_error(field, "multiple start sections"));
+                }
+
+                self.module.start_function_index = Some(self.functions.resolve(index_expression)?);
+                Ok(())
+            }
+            "elem" => self.define_element(field, items),
+            "data" => self.define_data(field, items),
+            _ => unreachable!(),
+        }
+    }
+
+    /// 生成导入项，参数 position 指向描述符（比如 `(func $id? ...)`）里的标识符之后的位置
+    fn define_import(
+        &mut self,
+        descriptor: &SExpression,
+        descriptor_items: &[SExpression],
+        position: usize,
+        module_name: String,
+        item_name: String,
+    ) -> Result<(), AssemblyError> {
+        let mut position = position;
+
+        let import_descriptor = match descriptor.get_keyword() {
+            Some("func") => {
+                let type_use = self.parse_type_use(descriptor_items, &mut position)?;
+                let type_index = self.resolve_type_use(descriptor, &type_use)?;
+                ImportDescriptor::FunctionTypeIndex(type_index)
+            }
+            Some("table") => ImportDescriptor::TableType(parse_table_type(
+                descriptor,
+                descriptor_items,
+                &mut position,
+            )?),
+            Some("memory") => ImportDescriptor::MemoryType(parse_memory_type(
+                descriptor,
+                descriptor_items,
+                &mut position,
+            )?),
+            Some("global") => ImportDescriptor::GlobalType(parse_global_type(
+                descriptor,
+                descriptor_items,
+                &mut position,
+            )?),
+            Some("tag") => {
+                let type_use = self.parse_type_use(descriptor_items, &mut position)?;
+                let type_index = self.resolve_type_use(descriptor, &type_use)?;
+                ImportDescriptor::TagType(TagType { type_index })
+            }
+            _ => unreachable!(),
+        };
+
+        expect_end(descriptor_items, position)?;
+
+        self.module.import_items.push(ImportItem {
+            module_name,
+            item_name,
+            import_descriptor,
+        });
+
+        Ok(())
+    }
+
+    /// 处理 `func`、`table`、`memory`、`global` 和 `tag` 字段的标识符、内联导出以及内联导入，
+    ///
+    /// 如果字段是内联导入，则生成导入项并返回 None，否则返回字段的索引。
+    fn define_inline_exports_and_import(
+        &mut self,
+        field: &SExpression,
+        items: &[SExpression],
+        position: &mut usize,
+    ) -> Result<Option<u32>, AssemblyError> {
+        let keyword = field.get_keyword().unwrap();
+
+        // 因为各个项目在第一遍已经按照相同的次序分配过索引，所以当前项目的索引
+        // 就是已生成的同类导入项和定义项的数量之和。
+        let imported_count = self
+            .module
+            .import_items
+            .iter()
+            .filter(|import_item| {
+                matches!(
+                    (keyword, &import_item.import_descriptor),
+                    ("func", ImportDescriptor::FunctionTypeIndex(_))
+                        | ("table", ImportDescriptor::TableType(_))
+                        | ("memory", ImportDescriptor::MemoryType(_))
+                        | ("global", ImportDescriptor::GlobalType(_))
+                        | ("tag", ImportDescriptor::TagType(_))
+                )
+            })
+            .count();
+
+        let defined_count = match keyword {
+            "func" => self.module.code_items.len(),
+            "table" => self.module.tables.len(),
+            "memory" => self.module.memory_blocks.len(),
+            "global" => self.module.global_items.len(),
+            _ => self.module.tag_items.len(),
+        };
+
+        let index = (imported_count + defined_count) as u32;
+
+        take_id(items, position);
+
+        while let Some(item) = items.get(*position) {
+            if item.get_keyword() != Some("export") {
+                break;
+            }
+
+            let export_items = item.as_list().unwrap();
+            let name = get_name(item, export_items, 1)?;
+            expect_end(export_items, 2)?;
+
+            let export_descriptor = match keyword {
+                "func" => ExportDescriptor::FunctionIndex(index),
+                "table" => ExportDescriptor::TableIndex(index),
+                "memory" => ExportDescriptor::MemoryBlockIndex(index),
+                "global" => ExportDescriptor::GlobalItemIndex(index),
+                _ => ExportDescriptor::TagIndex(index),
+            };
+
+            self.module.export_items.push(ExportItem {
+                name,
+                export_descriptor,
+            });
+
+            *position += 1;
+        }
+
+        if let Some(item) = items.get(*position) {
+            if item.get_keyword() == Some("import") {
+                // (func $id? (export "...")* (import "module" "name") typeuse)
+                let import_items = item.as_list().unwrap();
+                let module_name = get_name(item, import_items, 1)?;
+                let item_name = get_name(item, import_items, 2)?;
+                expect_end(import_items, 3)?;
+
+                self.define_import(field, items, *position + 1, module_name, item_name)?;
+                return Ok(None);
+            }
+        }
+
+        Ok(Some(index))
+    }
+
+    fn define_function(
+        &mut self,
+        field: &SExpression,
+        items: &[SExpression],
+    ) -> Result<(), AssemblyError> {
+        // (func $id? (export "...")* typeuse (local ...)* instruction*)
+        let mut position = 1;
+        let function_index =
+            match self.define_inline_exports_and_import(field, items, &mut position)? {
+                Some(index) => index,
+                None => return Ok(()),
+            };
+
+        let type_use = self.parse_type_use(items, &mut position)?;
+        let type_index = self.resolve_type_use(field, &type_use)?;
+
+        let mut context = FunctionContext::default();
+
+        // 参数
+        if type_use.params.is_empty() {
+            // 使用类型索引的函数，参数没有名称
+            let TypeItem::FunctionType(function_type) =
+                &self.module.type_items[type_index as usize];
+            for _ in 0..function_type.params.len() {
+                context.local_variables.declare(None, None, field)?;
+            }
+        } else {
+            for (option_id, _) in &type_use.params {
+                context
+                    .local_variables
+                    .declare(option_id.as_deref(), None, field)?;
+            }
+        }
+
+        // 局部变量
+        let mut local_groups: Vec<LocalGroup> = vec![];
+
+        while let Some(item) = items.get(position) {
+            if item.get_keyword() != Some("local") {
+                break;
+            }
+
+            for (option_id, value_type) in parse_value_type_declaration(item)? {
+                context
+                    .local_variables
+                    .declare(option_id.as_deref(), None, item)?;
+
+                // 连续的相同类型的局部变量合并为一组
+                match local_groups.last_mut() {
+                    Some(local_group) if local_group.value_type == value_type => {
+                        local_group.variable_count += 1;
+                    }
+                    _ => local_groups.push(LocalGroup {
+                        variable_count: 1,
+                        value_type,
+                    }),
+                }
+            }
+
+            position += 1;
+        }
+
+        // 指令
+        self.parse_instructions(&mut context, &items[position..])?;
+
+        if !context.labels.is_empty() {
+            return Err(syntax_error(field, "missing \"end\" instruction"));
+        }
+
+        let mut instruction_items = context.instructions;
+        instruction_items.push(Instruction::End);
+
+        self.module
+            .internal_function_to_type_index_list
+            .push(type_index);
+        self.module.code_items.push(CodeItem {
+            local_groups,
+            instruction_items,
+        });
+
+        if !context.local_variables.names.is_empty() {
+            self.local_variable_names
+                .push(FunctionIndexAndLocalVariableNamesPair {
+                    function_index,
+                    local_variable_names: context.local_variables.names,
+                });
+        }
+
+        if !context.block_labels.is_empty() {
+            self.block_labels.push(FunctionIndexAndBlockLabelsPair {
+                function_index,
+                block_labels: context.block_labels,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn define_table(
+        &mut self,
+        field: &SExpression,
+        items: &[SExpression],
+    ) -> Result<(), AssemblyError> {
+        // (table $id? (export "...")* limits reftype)
+        // (table $id? (export "...")* reftype (elem index*))
+        // (table $id? (export "...")* reftype (elem elemexpr*))
+        let mut position = 1;
+        let table_index =
+            match self.define_inline_exports_and_import(field, items, &mut position)? {
+                Some(index) => index,
+                None => return Ok(()),
+            };
+
+        let is_inline_element =
+            items.get(position + 1).and_then(|item| item.get_keyword()) == Some("elem");
+
+        if is_inline_element {
+            let element_type = parse_reference_type(field, items, &mut position)?;
+
+            let element_expression = &items[position];
+            let element_items = &element_expression.as_list().unwrap()[1..];
+            expect_end(items, position + 1)?;
+
+            // 内联的表元素要么全部是函数索引，要么全部是元素表达式
+            let items = if element_items.iter().all(is_index) {
+                ElementItems::FunctionIndices(self.resolve_function_indices(element_items)?)
+            } else {
+                ElementItems::Expressions(self.parse_element_expressions(field, element_items)?)
+            };
+
+            let count = match &items {
+                ElementItems::FunctionIndices(function_indices) => function_indices.len(),
+                ElementItems::Expressions(expressions) => expressions.len(),
+            } as u64;
+
+            self.module.tables.push(TableType {
+                element_type,
+                limit: Limit::Range(count, count),
+            });
+            self.module.element_items.push(ElementItem {
+                mode: ElementMode::Active {
+                    table_index,
+                    offset_instruction_items: vec![Instruction::I32Const(0), Instruction::End],
+                },
+                element_type,
+                items,
+            });
+        } else {
+            let table_type = parse_table_type(field, items, &mut position)?;
+            expect_end(items, position)?;
+            self.module.tables.push(table_type);
+        }
+
+        Ok(())
+    }
+
+    fn define_memory_block(
+        &mut self,
+        field: &SExpression,
+        items: &[SExpression],
+    ) -> Result<(), AssemblyError> {
+        // (memory $id? (export "...")* limits)
+        // (memory $id? (export "...")* (data "..."*))
+        let mut position = 1;
+        let memory_block_index =
+            match self.define_inline_exports_and_import(field, items, &mut position)? {
+                Some(index) => index,
+                None => return Ok(()),
+            };
+
+        // 内联数据之前也可以指定内存地址的类型，比如 `(memory i64 (data "..."))`
+        let mut data_position = position;
+        let memory64 = parse_address_type(items, &mut data_position);
+
+        let option_data_expression = items
+            .get(data_position)
+            .filter(|item| item.get_keyword() == Some("data"));
+
+        if let Some(data_expression) = option_data_expression {
+            let data_items = data_expression.as_list().unwrap();
+            let data = parse_data_strings(&data_items[1..])?;
+            expect_end(items, data_position + 1)?;
+
+            let page_count = data.len().div_ceil(MEMORY_PAGE_SIZE) as u64;
+            self.module.memory_blocks.push(MemoryType {
+                limit: Limit::Range(page_count, page_count),
+                shared: false,
+                memory64,
+            });
+
+            let offset_instruction = if memory64 {
+                Instruction::I64Const(0)
+            } else {
+                Instruction::I32Const(0)
+            };
+
+            self.module.data_items.push(DataItem {
+                mode: DataMode::Active {
+                    memory_block_index,
+                    offset_instruction_items: vec![offset_instruction, Instruction::End],
+                },
+                data,
+            });
+        } else {
+            let memory_type = parse_memory_type(field, items, &mut position)?;
+            expect_end(items, position)?;
+            self.module.memory_blocks.push(memory_type);
+        }
+
+        Ok(())
+    }
+
+    fn define_global(
+        &mut self,
+        field: &SExpression,
+        items: &[SExpression],
+    ) -> Result<(), AssemblyError> {
+        // (global $id? (export "...")* globaltype instruction*)
+        let mut position = 1;
+        if self
+            .define_inline_exports_and_import(field, items, &mut position)?
+            .is_none()
+        {
+            return Ok(());
+        }
+
+        let global_type = parse_global_type(field, items, &mut position)?;
+        let initialize_instruction_items =
+            self.parse_constant_expression(field, &items[position..])?;
+
+        self.module.global_items.push(GlobalItem {
+            global_type,
+            initialize_instruction_items,
+        });
+
+        Ok(())
+    }
+
+    fn define_tag(
+        &mut self,
+        field: &SExpression,
+        items: &[SExpression],
+    ) -> Result<(), AssemblyError> {
+        // (tag $id? (export "...")* typeuse)
+        let mut position = 1;
+        if self
+            .define_inline_exports_and_import(field, items, &mut position)?
+            .is_none()
+        {
+            return Ok(());
+        }
+
+        let type_use = self.parse_type_use(items, &mut position)?;
+        expect_end(items, position)?;
+
+        let type_index = self.resolve_type_use(field, &type_use)?;
+        self.module.tag_items.push(TagType { type_index });
+
+        Ok(())
+    }
+
+    fn define_element(
+        &mut self,
+        field: &SExpression,
+        items: &[SExpression],
+    ) -> Result<(), AssemblyError> {
+        // 被动段：
+        // (elem $id? elemlist)
+        //
+        // 声明段：
+        // (elem $id? declare elemlist)
+        //
+        // 主动段：
+        // (elem $id? (table index)? (offset instruction*) elemlist)
+        // (elem $id? index? (offset instruction*) index*)
+        // (elem $id? (i32.const 0) index*)
+        //
+        // 其中 elemlist 是 `func index*` 或者 `reftype elemexpr*`，
+        // elemexpr 是 `(item instruction*)` 或者单独一个折叠形式的指令。
+        let mut position = 1;
+        take_id(items, &mut position);
+
+        let mode = match items.get(position) {
+            Some(item) if item.as_symbol() == Some("declare") => {
+                position += 1;
+                ElementMode::Declarative
+            }
+            Some(item) if item.as_list().is_some() || is_index(item) => {
+                let table_index = match items.get(position) {
+                    Some(item) if item.get_keyword() == Some("table") => {
+                        let table_items = item.as_list().unwrap();
+                        let index_expression = table_items
+                            .get(1)
+                            .ok_or_else(|| syntax_error(item, "expected a table index"))?;
+                        expect_end(table_items, 2)?;
+                        position += 1;
+                        self.tables.resolve(index_expression)?
+                    }
+                    Some(item) if is_index(item) => {
+                        position += 1;
+                        self.tables.resolve(item)?
+                    }
+                    _ => 0,
+                };
+
+                let offset_instruction_items =
+                    self.parse_offset(field, items, &mut position, "element")?;
+
+                ElementMode::Active {
+                    table_index,
+                    offset_instruction_items,
+                }
+            }
+            _ => ElementMode::Passive,
+        };
+
+        let (element_type, element_items) = match items
+            .get(position)
+            .and_then(|item| item.as_symbol())
+        {
+            Some("func") => (
+                RefType::FuncRef,
+                ElementItems::FunctionIndices(
+                    self.resolve_function_indices(&items[position + 1..])?,
+                ),
+            ),
+            Some("funcref") | Some("externref") => {
+                let element_type = parse_reference_type(field, items, &mut position)?;
+                (
+                    element_type,
+                    ElementItems::Expressions(
+                        self.parse_element_expressions(field, &items[position..])?,
+                    ),
+                )
+            }
+            // 省略了 `func` 的函数索引列表
+            _ => (
+                RefType::FuncRef,
+                ElementItems::FunctionIndices(self.resolve_function_indices(&items[position..])?),
+            ),
+        };
+
+        self.module.element_items.push(ElementItem {
+            mode,
+            element_type,
+            items: element_items,
+        });
+
+        Ok(())
+    }
+
+    fn resolve_function_indices(&self, items: &[SExpression]) -> Result<Vec<u32>, AssemblyError> {
+        items
+            .iter()
+            .map(|item| {
+                if item.as_list().is_some() {
+                    Err(syntax_error(item, "expected a function index"))
+                } else {
+                    self.functions.resolve(item)
+                }
+            })
+            .collect()
+    }
+
+    /// 解析元素表达式列表，每个元素表达式是 `(item instruction*)` 或者单独一个折叠形式的指令
+    fn parse_element_expressions(
+        &mut self,
+        field: &SExpression,
+        items: &[SExpression],
+    ) -> Result<Vec<Vec<Instruction>>, AssemblyError> {
+        items
+            .iter()
+            .map(|item| match item.get_keyword() {
+                Some("item") => {
+                    let expression_items = item.as_list().unwrap();
+                    self.parse_constant_expression(item, &expression_items[1..])
+                }
+                Some(_) => self.parse_constant_expression(field, std::slice::from_ref(item)),
+                None => Err(syntax_error(item, "expected an element expression")),
+            })
+            .collect()
+    }
+
+    fn define_data(
+        &mut self,
+        field: &SExpression,
+        items: &[SExpression],
+    ) -> Result<(), AssemblyError> {
+        // 被动段：
+        // (data $id? "..."*)
+        //
+        // 主动段：
+        // (data $id? (memory index)? (offset instruction*) "..."*)
+        // (data $id? index? (offset instruction*) "..."*)
+        // (data $id? (i32.const 0) "..."*)
+        let mut position = 1;
+        take_id(items, &mut position);
+
+        let mode = match items.get(position) {
+            Some(item) if item.as_list().is_some() || is_index(item) => {
+                let memory_block_index = match items.get(position) {
+                    Some(item) if item.get_keyword() == Some("memory") => {
+                        let memory_items = item.as_list().unwrap();
+                        let index_expression = memory_items
+                            .get(1)
+                            .ok_or_else(|| syntax_error(item, "expected a memory index"))?;
+                        expect_end(memory_items, 2)?;
+                        position += 1;
+                        self.memory_blocks.resolve(index_expression)?
+                    }
+                    Some(item) if is_index(item) => {
+                        position += 1;
+                        self.memory_blocks.resolve(item)?
+                    }
+                    _ => 0,
+                };
+
+                let offset_instruction_items =
+                    self.parse_offset(field, items, &mut position, "data")?;
+
+                DataMode::Active {
+                    memory_block_index,
+                    offset_instruction_items,
+                }
+            }
+            _ => DataMode::Passive,
+        };
+
+        let data = parse_data_strings(&items[position..])?;
+        self.module.data_items.push(DataItem { mode, data });
+
+        Ok(())
+    }
+
+    /// 解析主动模式的元素项和数据项的偏移值，`(offset instruction*)` 或者单独一个折叠形式的指令
+    fn parse_offset(
+        &mut self,
+        field: &SExpression,
+        items: &[SExpression],
+        position: &mut usize,
+        segment_name: &str,
+    ) -> Result<Vec<Instruction>, AssemblyError> {
+        let offset_expression = match items.get(*position) {
+            Some(item) if item.as_list().is_some() => item,
+            _ => {
+                return Err(syntax_error(
+                    field,
+                    &format!(
+                        "expected an offset expression for the {} segment",
+                        segment_name
+                    ),
+                ));
+            }
+        };
+
+        *position += 1;
+
+        if offset_expression.get_keyword() == Some("offset") {
+            let offset_items = offset_expression.as_list().unwrap();
+            self.parse_constant_expression(offset_expression, &offset_items[1..])
+        } else {
+            self.parse_constant_expression(field, std::slice::from_ref(offset_expression))
+        }
+    }
+
+    fn parse_constant_expression(
+        &mut self,
+        expression: &SExpression,
+        items: &[SExpression],
+    ) -> Result<Vec<Instruction>, AssemblyError> {
+        let mut context = FunctionContext::default();
+        self.parse_instructions(&mut context, items)?;
+
+        if context.instructions.is_empty() {
+            return Err(syntax_error(expression, "expected a constant expression"));
+        }
+
+        let mut instructions = context.instructions;
+        instructions.push(Instruction::End);
+        Ok(instructions)
+    }
+
+    // 类型
+
+    /// 解析 `(type index)? (param ...)* (result ...)*`
+    fn parse_type_use(
+        &self,
+        items: &[SExpression],
+        position: &mut usize,
+    ) -> Result<TypeUse, AssemblyError> {
+        let mut option_type_index: Option<u32> = None;
+
+        if let Some(item) = items.get(*position) {
+            if item.get_keyword() == Some("type") {
+                let type_items = item.as_list().unwrap();
+                let index_expression = type_items
+                    .get(1)
+                    .ok_or_else(|| syntax_error(item, "expected a type index"))?;
+                expect_end(type_items, 2)?;
+
+                option_type_index = Some(self.types.resolve(index_expression)?);
+                *position += 1;
+            }
+        }
+
+        let mut type_use = parse_inline_type_use(items, position)?;
+        type_use.option_type_index = option_type_index;
+        Ok(type_use)
+    }
+
+    /// 获取 TypeUse 对应的类型索引
+    ///
+    /// 如果没有指定类型索引，则使用类型表里第一个签名相同的类型，
+    /// 如果不存在签名相同的类型，则在类型表的末尾追加一个新类型。
+    fn resolve_type_use(
+        &mut self,
+        expression: &SExpression,
+        type_use: &TypeUse,
+    ) -> Result<u32, AssemblyError> {
+        let function_type = type_use.to_function_type();
+
+        if let Some(type_index) = type_use.option_type_index {
+            let TypeItem::FunctionType(existing_function_type) = self
+                .module
+                .type_items
+                .get(type_index as usize)
+                .ok_or_else(|| syntax_error(expression, "unknown type"))?;
+
+            if !type_use.is_inline_empty() && existing_function_type != &function_type {
+                return Err(syntax_error(expression, "inline function type mismatch"));
+            }
+
+            return Ok(type_index);
+        }
+
+        let option_type_index = self.module.type_items.iter().position(
+            |TypeItem::FunctionType(existing_function_type)| {
+                existing_function_type == &function_type
+            },
+        );
+
+        match option_type_index {
+            Some(type_index) => Ok(type_index as u32),
+            None => {
+                let type_index = self.types.declare(None, None, expression)?;
+                self.module
+                    .type_items
+                    .push(TypeItem::FunctionType(function_type));
+                Ok(type_index)
+            }
+        }
+    }
+
+    /// 解析结构块的类型
+    ///
+    /// 没有参数并且返回值不超过一个的结构块使用内置的类型，
+    /// 其余的使用类型表里的类型。
+    fn parse_block_type(
+        &mut self,
+        expression: &SExpression,
+        items: &[SExpression],
+        position: &mut usize,
+    ) -> Result<BlockType, AssemblyError> {
+        let type_use = self.parse_type_use(items, position)?;
+
+        if type_use.option_type_index.is_none() && type_use.params.is_empty() {
+            match type_use.results.as_slice() {
+                [] => return Ok(BlockType::ResultEmpty),
+                [ValueType::I32] => return Ok(BlockType::ResultI32),
+                [ValueType::I64] => return Ok(BlockType::ResultI64),
+                [ValueType::F32] => return Ok(BlockType::ResultF32),
+                [ValueType::F64] => return Ok(BlockType::ResultF64),
+                [ValueType::V128] => return Ok(BlockType::ResultV128),
+                [ValueType::FuncRef] => return Ok(BlockType::ResultFuncRef),
+                [ValueType::ExternRef] => return Ok(BlockType::ResultExternRef),
+                [ValueType::ExnRef] => return Ok(BlockType::ResultExnRef),
+                _ => {}
+            }
+        }
+
+        let type_index = self.resolve_type_use(expression, &type_use)?;
+        Ok(BlockType::TypeIndex(type_index))
+    }
+
+    // 指令
+
+    /// 解析一系列平铺或者折叠形式的指令
+    fn parse_instructions(
+        &mut self,
+        context: &mut FunctionContext,
+        items: &[SExpression],
+    ) -> Result<(), AssemblyError> {
+        let mut position = 0;
+
+        while let Some(item) = items.get(position) {
+            position += 1;
+
+            match item {
+                SExpression::List(_, _) => self.parse_folded_instruction(context, item)?,
+                SExpression::Symbol(keyword, _) => {
+                    self.parse_flat_instruction(context, keyword, item, items, &mut position)?
+                }
+                SExpression::Text(_, _) => {
+                    return Err(syntax_error(item, "expected an instruction"));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn parse_flat_instruction(
+        &mut self,
+        context: &mut FunctionContext,
+        keyword: &str,
+        keyword_expression: &SExpression,
+        items: &[SExpression],
+        position: &mut usize,
+    ) -> Result<(), AssemblyError> {
+        match keyword {
+            "block" | "loop" | "if" => {
+                let option_label = take_id(items, position);
+                let block_type = self.parse_block_type(keyword_expression, items, position)?;
+                let block_index = context.enter_block(option_label);
+
+                context.instructions.push(match keyword {
+                    "block" => Instruction::Block(block_type, block_index),
+                    "loop" => Instruction::Loop(block_type, block_index),
+                    _ => Instruction::If(block_type, block_index),
+                });
+            }
+            "try_table" => {
+                let option_label = take_id(items, position);
+                let block_type = self.parse_block_type(keyword_expression, items, position)?;
+                let catch_clauses = self.parse_catch_clauses(context, items, position)?;
+                let block_index = context.enter_block(option_label);
+
+                context.instructions.push(Instruction::TryTable(
+                    block_type,
+                    catch_clauses,
+                    block_index,
+                ));
+            }
+            "else" => {
+                check_closing_label(context, keyword_expression, items, position)?;
+                context.instructions.push(Instruction::Else);
+            }
+            "end" => {
+                check_closing_label(context, keyword_expression, items, position)?;
+                context.labels.pop();
+                context.instructions.push(Instruction::End);
+            }
+            _ => {
+                let instruction =
+                    self.parse_plain_instruction(context, keyword_expression, items, position)?;
+                context.instructions.push(instruction);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn parse_folded_instruction(
+        &mut self,
+        context: &mut FunctionContext,
+        expression: &SExpression,
+    ) -> Result<(), AssemblyError> {
+        let items = expression.as_list().unwrap();
+        let keyword = expression
+            .get_keyword()
+            .ok_or_else(|| syntax_error(expression, "expected an instruction"))?;
+
+        let mut position = 1;
+
+        match keyword {
+            "block" | "loop" => {
+                // (block $label? blocktype instruction*)
+                let option_label = take_id(items, &mut position);
+                let block_type = self.parse_block_type(expression, items, &mut position)?;
+                let block_index = context.enter_block(option_label);
+
+                context.instructions.push(if keyword == "block" {
+                    Instruction::Block(block_type, block_index)
+                } else {
+                    Instruction::Loop(block_type, block_index)
+                });
+
+                self.parse_instructions(context, &items[position..])?;
+
+                context.labels.pop();
+                context.instructions.push(Instruction::End);
+            }
+            "try_table" => {
+                // (try_table $label? blocktype catch* instruction*)
+                let option_label = take_id(items, &mut position);
+                let block_type = self.parse_block_type(expression, items, &mut position)?;
+                let catch_clauses = self.parse_catch_clauses(context, items, &mut position)?;
+                let block_index = context.enter_block(option_label);
+
+                context.instructions.push(Instruction::TryTable(
+                    block_type,
+                    catch_clauses,
+                    block_index,
+                ));
+
+                self.parse_instructions(context, &items[position..])?;
+
+                context.labels.pop();
+                context.instructions.push(Instruction::End);
+            }
+            "if" => {
+                // (if $label? blocktype folded_instruction* (then instruction*) (else instruction*)?)
+                let option_label = take_id(items, &mut position);
+                let block_type = self.parse_block_type(expression, items, &mut position)?;
+
+                // 条件
+                while let Some(item) = items.get(position) {
+                    if item.get_keyword() == Some("then") {
+                        break;
+                    }
+
+                    if item.as_list().is_none() {
+                        return Err(syntax_error(item, "expected a folded instruction"));
+                    }
+
+                    self.parse_folded_instruction(context, item)?;
+                    position += 1;
+                }
+
+                let block_index = context.enter_block(option_label);
+                context
+                    .instructions
+                    .push(Instruction::If(block_type, block_index));
+
+                let then_expression = items
+                    .get(position)
+                    .ok_or_else(|| syntax_error(expression, "expected \"then\""))?;
+                self.parse_instructions(context, &then_expression.as_list().unwrap()[1..])?;
+                position += 1;
+
+                if let Some(else_expression) = items.get(position) {
+                    if else_expression.get_keyword() != Some("else") {
+                        return Err(syntax_error(else_expression, "expected \"else\""));
+                    }
+
+                    context.instructions.push(Instruction::Else);
+                    self.parse_instructions(context, &else_expression.as_list().unwrap()[1..])?;
+                    position += 1;
+                }
+
+                expect_end(items, position)?;
+
+                context.labels.pop();
+                context.instructions.push(Instruction::End);
+            }
+            _ => {
+                // (plain_instruction immediate* folded_instruction*)
+                let instruction =
+                    self.parse_plain_instruction(context, &items[0], items, &mut position)?;
+
+                for item in &items[position..] {
+                    if item.as_list().is_none() {
+                        return Err(syntax_error(item, "expected a folded instruction"));
+                    }
+                    self.parse_folded_instruction(context, item)?;
+                }
+
+                context.instructions.push(instruction);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 解析 `try_table` 指令的捕获子句 `(catch tag label)`、`(catch_ref tag label)`、
+    /// `(catch_all label)` 以及 `(catch_all_ref label)`
+    ///
+    /// 注意子句的标签是相对于 `try_table` 结构块的外层而言的，所以需要在进入结构块之前解析。
+    fn parse_catch_clauses(
+        &self,
+        context: &FunctionContext,
+        items: &[SExpression],
+        position: &mut usize,
+    ) -> Result<Vec<CatchClause>, AssemblyError> {
+        let mut catch_clauses: Vec<CatchClause> = vec![];
+
+        while let Some(item) = items.get(*position) {
+            let keyword = match item.get_keyword() {
+                Some(keyword @ ("catch" | "catch_ref" | "catch_all" | "catch_all_ref")) => keyword,
+                _ => break,
+            };
+
+            let catch_items = item.as_list().unwrap();
+            let mut catch_position = 1;
+
+            let catch_clause = match keyword {
+                "catch" | "catch_ref" => {
+                    let tag_expression = take_item(item, catch_items, &mut catch_position)?;
+                    let label_expression = take_item(item, catch_items, &mut catch_position)?;
+                    let tag_index = self.tags.resolve(tag_expression)?;
+                    let relative_depth = context.resolve_label(label_expression)?;
+
+                    if keyword == "catch" {
+                        CatchClause::Catch(tag_index, relative_depth)
+                    } else {
+                        CatchClause::CatchRef(tag_index, relative_depth)
+                    }
+                }
+                _ => {
+                    let label_expression = take_item(item, catch_items, &mut catch_position)?;
+                    let relative_depth = context.resolve_label(label_expression)?;
+
+                    if keyword == "catch_all" {
+                        CatchClause::CatchAll(relative_depth)
+                    } else {
+                        CatchClause::CatchAllRef(relative_depth)
+                    }
+                }
+            };
+
+            expect_end(catch_items, catch_position)?;
+            catch_clauses.push(catch_clause);
+            *position += 1;
+        }
+
+        Ok(catch_clauses)
+    }
+
+    /// 解析普通指令（即除了结构块指令之外的指令）以及它的立即数
+    fn parse_plain_instruction(
+        &mut self,
+        context: &mut FunctionContext,
+        keyword_expression: &SExpression,
+        items: &[SExpression],
+        position: &mut usize,
+    ) -> Result<Instruction, AssemblyError> {
+        let keyword = keyword_expression
+            .as_symbol()
+            .ok_or_else(|| syntax_error(keyword_expression, "expected an instruction"))?;
+
+        if let Some(instruction) = get_instruction_without_immediate(keyword) {
+            return Ok(instruction);
+        }
+
+        let instruction = match keyword {
+            "br" => Instruction::Br(context.resolve_label(take_item(
+                keyword_expression,
+                items,
+                position,
+            )?)?),
+            "br_if" => Instruction::BrIf(context.resolve_label(take_item(
+                keyword_expression,
+                items,
+                position,
+            )?)?),
+            "br_table" => {
+                let mut relative_depths: Vec<u32> = vec![];
+                while let Some(item) = items.get(*position).filter(|item| is_index(item)) {
+                    relative_depths.push(context.resolve_label(item)?);
+                    *position += 1;
+                }
+
+                let default_relative_depth = relative_depths
+                    .pop()
+                    .ok_or_else(|| syntax_error(keyword_expression, "expected a label"))?;
+                Instruction::BrTable(relative_depths, default_relative_depth)
+            }
+            "call" => Instruction::Call(self.functions.resolve(take_item(
+                keyword_expression,
+                items,
+                position,
+            )?)?),
+            "return_call" => Instruction::ReturnCall(self.functions.resolve(take_item(
+                keyword_expression,
+                items,
+                position,
+            )?)?),
+            "call_indirect" | "return_call_indirect" => {
+                let table_index = self.take_optional_index(IndexKind::Table, items, position)?;
+                let type_use = self.parse_type_use(items, position)?;
+                if type_use
+                    .params
+                    .iter()
+                    .any(|(option_id, _)| option_id.is_some())
+                {
+                    return Err(syntax_error(
+                        keyword_expression,
+                        &format!("unexpected parameter name in \"{}\"", keyword),
+                    ));
+                }
+                let type_index = self.resolve_type_use(keyword_expression, &type_use)?;
+                if keyword == "call_indirect" {
+                    Instruction::CallIndirect(type_index, table_index)
+                } else {
+                    Instruction::ReturnCallIndirect(type_index, table_index)
+                }
+            }
+            "throw" => Instruction::Throw(self.tags.resolve(take_item(
+                keyword_expression,
+                items,
+                position,
+            )?)?),
+            "select" => {
+                // select (result valtype)*
+                let start_position = *position;
+                let mut value_types: Vec<ValueType> = vec![];
+                while let Some(item) = items
+                    .get(*position)
+                    .filter(|item| item.get_keyword() == Some("result"))
+                {
+                    for result_item in &item.as_list().unwrap()[1..] {
+                        value_types.push(parse_value_type(result_item)?);
+                    }
+                    *position += 1;
+                }
+
+                if *position == start_position {
+                    Instruction::Select
+                } else {
+                    Instruction::SelectTyped(value_types)
+                }
+            }
+
+            "local.get" => Instruction::LocalGet(context.local_variables.resolve(take_item(
+                keyword_expression,
+                items,
+                position,
+            )?)?),
+            "local.set" => Instruction::LocalSet(context.local_variables.resolve(take_item(
+                keyword_expression,
+                items,
+                position,
+            )?)?),
+            "local.tee" => Instruction::LocalTee(context.local_variables.resolve(take_item(
+                keyword_expression,
+                items,
+                position,
+            )?)?),
+            "global.get" => Instruction::GlobalGet(self.global_variables.resolve(take_item(
+                keyword_expression,
+                items,
+                position,
+            )?)?),
+            "global.set" => Instruction::GlobalSet(self.global_variables.resolve(take_item(
+                keyword_expression,
+                items,
+                position,
+            )?)?),
+
+            "i32.load" => Instruction::I32Load(self.take_memory_argument(items, position, 2)?),
+            "i64.load" => Instruction::I64Load(self.take_memory_argument(items, position, 3)?),
+            "f32.load" => Instruction::F32Load(self.take_memory_argument(items, position, 2)?),
+            "f64.load" => Instruction::F64Load(self.take_memory_argument(items, position, 3)?),
+            "i32.load8_s" => Instruction::I32Load8S(self.take_memory_argument(items, position, 0)?),
+            "i32.load8_u" => Instruction::I32Load8U(self.take_memory_argument(items, position, 0)?),
+            "i32.load16_s" => {
+                Instruction::I32Load16S(self.take_memory_argument(items, position, 1)?)
+            }
+            "i32.load16_u" => {
+                Instruction::I32Load16U(self.take_memory_argument(items, position, 1)?)
+            }
+            "i64.load8_s" => Instruction::I64Load8S(self.take_memory_argument(items, position, 0)?),
+            "i64.load8_u" => Instruction::I64Load8U(self.take_memory_argument(items, position, 0)?),
+            "i64.load16_s" => {
+                Instruction::I64Load16S(self.take_memory_argument(items, position, 1)?)
+            }
+            "i64.load16_u" => {
+                Instruction::I64Load16U(self.take_memory_argument(items, position, 1)?)
+            }
+            "i64.load32_s" => {
+                Instruction::I64Load32S(self.take_memory_argument(items, position, 2)?)
+            }
+            "i64.load32_u" => {
+                Instruction::I64Load32U(self.take_memory_argument(items, position, 2)?)
+            }
+            "i32.store" => Instruction::I32Store(self.take_memory_argument(items, position, 2)?),
+            "i64.store" => Instruction::I64Store(self.take_memory_argument(items, position, 3)?),
+            "f32.store" => Instruction::F32Store(self.take_memory_argument(items, position, 2)?),
+            "f64.store" => Instruction::F64Store(self.take_memory_argument(items, position, 3)?),
+            "i32.store8" => Instruction::I32Store8(self.take_memory_argument(items, position, 0)?),
+            "i32.store16" => {
+                Instruction::I32Store16(self.take_memory_argument(items, position, 1)?)
+            }
+            "i64.store8" => Instruction::I64Store8(self.take_memory_argument(items, position, 0)?),
+            "i64.store16" => {
+                Instruction::I64Store16(self.take_memory_argument(items, position, 1)?)
+            }
+            "i64.store32" => {
+                Instruction::I64Store32(self.take_memory_argument(items, position, 2)?)
+            }
+
+            "memory.size" => Instruction::MemorySize(self.take_optional_index(
+                IndexKind::MemoryBlock,
+                items,
+                position,
+            )?),
+            "memory.grow" => Instruction::MemoryGrow(self.take_optional_index(
+                IndexKind::MemoryBlock,
+                items,
+                position,
+            )?),
+            "memory.fill" => Instruction::MemoryFill(self.take_optional_index(
+                IndexKind::MemoryBlock,
+                items,
+                position,
+            )?),
+            "memory.copy" => {
+                let first = self.take_optional_index(IndexKind::MemoryBlock, items, position)?;
+                let second = self.take_optional_index(IndexKind::MemoryBlock, items, position)?;
+                Instruction::MemoryCopy(first, second)
+            }
+            "memory.init" => {
+                // memory.init memory_block_index? data_index
+                let first = take_item(keyword_expression, items, position)?;
+                match items.get(*position).filter(|item| is_index(item)) {
+                    Some(second) => {
+                        *position += 1;
+                        Instruction::MemoryInit(
+                            self.datas.resolve(second)?,
+                            self.memory_blocks.resolve(first)?,
+                        )
+                    }
+                    None => Instruction::MemoryInit(self.datas.resolve(first)?, 0),
+                }
+            }
+            "data.drop" => Instruction::DataDrop(self.datas.resolve(take_item(
+                keyword_expression,
+                items,
+                position,
+            )?)?),
+
+            "table.get" => Instruction::TableGet(self.take_optional_index(
+                IndexKind::Table,
+                items,
+                position,
+            )?),
+            "table.set" => Instruction::TableSet(self.take_optional_index(
+                IndexKind::Table,
+                items,
+                position,
+            )?),
+            "table.grow" => Instruction::TableGrow(self.take_optional_index(
+                IndexKind::Table,
+                items,
+                position,
+            )?),
+            "table.size" => Instruction::TableSize(self.take_optional_index(
+                IndexKind::Table,
+                items,
+                position,
+            )?),
+            "table.fill" => Instruction::TableFill(self.take_optional_index(
+                IndexKind::Table,
+                items,
+                position,
+            )?),
+            "table.copy" => {
+                let first = self.take_optional_index(IndexKind::Table, items, position)?;
+                let second = self.take_optional_index(IndexKind::Table, items, position)?;
+                Instruction::TableCopy(first, second)
+            }
+            "table.init" => {
+                // table.init table_index? element_index
+                let first = take_item(keyword_expression, items, position)?;
+                match items.get(*position).filter(|item| is_index(item)) {
+                    Some(second) => {
+                        *position += 1;
+                        Instruction::TableInit(
+                            self.elements.resolve(second)?,
+                            self.tables.resolve(first)?,
+                        )
+                    }
+                    None => Instruction::TableInit(self.elements.resolve(first)?, 0),
+                }
+            }
+            "elem.drop" => Instruction::ElementDrop(self.elements.resolve(take_item(
+                keyword_expression,
+                items,
+                position,
+            )?)?),
+
+            "ref.null" => Instruction::RefNull(parse_heap_type(take_item(
+                keyword_expression,
+                items,
+                position,
+            )?)?),
+            "ref.func" => Instruction::RefFunc(self.functions.resolve(take_item(
+                keyword_expression,
+                items,
+                position,
+            )?)?),
+
+            "i32.const" => {
+                let item = take_item(keyword_expression, items, position)?;
+                Instruction::I32Const(parse_number(item, number_literal::parse_i32)?)
+            }
+            "i64.const" => {
+                let item = take_item(keyword_expression, items, position)?;
+                Instruction::I64Const(parse_number(item, number_literal::parse_i64)?)
+            }
+            "f32.const" => {
+                let item = take_item(keyword_expression, items, position)?;
+                Instruction::F32Const(parse_number(item, number_literal::parse_f32)?)
+            }
+            "f64.const" => {
+                let item = take_item(keyword_expression, items, position)?;
+                Instruction::F64Const(parse_number(item, number_literal::parse_f64)?)
+            }
+
+            "v128.load" => Instruction::V128Load(self.take_memory_argument(items, position, 4)?),
+            "v128.load8x8_s" => {
+                Instruction::V128Load8x8S(self.take_memory_argument(items, position, 3)?)
+            }
+            "v128.load8x8_u" => {
+                Instruction::V128Load8x8U(self.take_memory_argument(items, position, 3)?)
+            }
+            "v128.load16x4_s" => {
+                Instruction::V128Load16x4S(self.take_memory_argument(items, position, 3)?)
+            }
+            "v128.load16x4_u" => {
+                Instruction::V128Load16x4U(self.take_memory_argument(items, position, 3)?)
+            }
+            "v128.load32x2_s" => {
+                Instruction::V128Load32x2S(self.take_memory_argument(items, position, 3)?)
+            }
+            "v128.load32x2_u" => {
+                Instruction::V128Load32x2U(self.take_memory_argument(items, position, 3)?)
+            }
+            "v128.load8_splat" => {
+                Instruction::V128Load8Splat(self.take_memory_argument(items, position, 0)?)
+            }
+            "v128.load16_splat" => {
+                Instruction::V128Load16Splat(self.take_memory_argument(items, position, 1)?)
+            }
+            "v128.load32_splat" => {
+                Instruction::V128Load32Splat(self.take_memory_argument(items, position, 2)?)
+            }
+            "v128.load64_splat" => {
+                Instruction::V128Load64Splat(self.take_memory_argument(items, position, 3)?)
+            }
+            "v128.store" => Instruction::V128Store(self.take_memory_argument(items, position, 4)?),
+            "v128.const" => {
+                Instruction::V128Const(take_v128_constant(keyword_expression, items, position)?)
+            }
+            "i8x16.shuffle" => {
+                let mut lane_indices = [0u8; 16];
+                for lane_index in lane_indices.iter_mut() {
+                    *lane_index = take_lane_index(keyword_expression, items, position)?;
+                }
+                Instruction::I8x16Shuffle(lane_indices)
+            }
+            "i8x16.extract_lane_s" => Instruction::I8x16ExtractLaneS(take_lane_index(
+                keyword_expression,
+                items,
+                position,
+            )?),
+            "i8x16.extract_lane_u" => Instruction::I8x16ExtractLaneU(take_lane_index(
+                keyword_expression,
+                items,
+                position,
+            )?),
+            "i8x16.replace_lane" => {
+                Instruction::I8x16ReplaceLane(take_lane_index(keyword_expression, items, position)?)
+            }
+            "i16x8.extract_lane_s" => Instruction::I16x8ExtractLaneS(take_lane_index(
+                keyword_expression,
+                items,
+                position,
+            )?),
+            "i16x8.extract_lane_u" => Instruction::I16x8ExtractLaneU(take_lane_index(
+                keyword_expression,
+                items,
+                position,
+            )?),
+            "i16x8.replace_lane" => {
+                Instruction::I16x8ReplaceLane(take_lane_index(keyword_expression, items, position)?)
+            }
+            "i32x4.extract_lane" => {
+                Instruction::I32x4ExtractLane(take_lane_index(keyword_expression, items, position)?)
+            }
+            "i32x4.replace_lane" => {
+                Instruction::I32x4ReplaceLane(take_lane_index(keyword_expression, items, position)?)
+            }
+            "i64x2.extract_lane" => {
+                Instruction::I64x2ExtractLane(take_lane_index(keyword_expression, items, position)?)
+            }
+            "i64x2.replace_lane" => {
+                Instruction::I64x2ReplaceLane(take_lane_index(keyword_expression, items, position)?)
+            }
+            "f32x4.extract_lane" => {
+                Instruction::F32x4ExtractLane(take_lane_index(keyword_expression, items, position)?)
+            }
+            "f32x4.replace_lane" => {
+                Instruction::F32x4ReplaceLane(take_lane_index(keyword_expression, items, position)?)
+            }
+            "f64x2.extract_lane" => {
+                Instruction::F64x2ExtractLane(take_lane_index(keyword_expression, items, position)?)
+            }
+            "f64x2.replace_lane" => {
+                Instruction::F64x2ReplaceLane(take_lane_index(keyword_expression, items, position)?)
+            }
+            "v128.load8_lane" => {
+                let memory_argument = self.take_lane_memory_argument(items, position, 0)?;
+                let lane_index = take_lane_index(keyword_expression, items, position)?;
+                Instruction::V128Load8Lane(memory_argument, lane_index)
+            }
+            "v128.load16_lane" => {
+                let memory_argument = self.take_lane_memory_argument(items, position, 1)?;
+                let lane_index = take_lane_index(keyword_expression, items, position)?;
+                Instruction::V128Load16Lane(memory_argument, lane_index)
+            }
+            "v128.load32_lane" => {
+                let memory_argument = self.take_lane_memory_argument(items, position, 2)?;
+                let lane_index = take_lane_index(keyword_expression, items, position)?;
+                Instruction::V128Load32Lane(memory_argument, lane_index)
+            }
+            "v128.load64_lane" => {
+                let memory_argument = self.take_lane_memory_argument(items, position, 3)?;
+                let lane_index = take_lane_index(keyword_expression, items, position)?;
+                Instruction::V128Load64Lane(memory_argument, lane_index)
+            }
+            "v128.store8_lane" => {
+                let memory_argument = self.take_lane_memory_argument(items, position, 0)?;
+                let lane_index = take_lane_index(keyword_expression, items, position)?;
+                Instruction::V128Store8Lane(memory_argument, lane_index)
+            }
+            "v128.store16_lane" => {
+                let memory_argument = self.take_lane_memory_argument(items, position, 1)?;
+                let lane_index = take_lane_index(keyword_expression, items, position)?;
+                Instruction::V128Store16Lane(memory_argument, lane_index)
+            }
+            "v128.store32_lane" => {
+                let memory_argument = self.take_lane_memory_argument(items, position, 2)?;
+                let lane_index = take_lane_index(keyword_expression, items, position)?;
+                Instruction::V128Store32Lane(memory_argument, lane_index)
+            }
+            "v128.store64_lane" => {
+                let memory_argument = self.take_lane_memory_argument(items, position, 3)?;
+                let lane_index = take_lane_index(keyword_expression, items, position)?;
+                Instruction::V128Store64Lane(memory_argument, lane_index)
+            }
+            "v128.load32_zero" => {
+                Instruction::V128Load32Zero(self.take_memory_argument(items, position, 2)?)
+            }
+            "v128.load64_zero" => {
+                Instruction::V128Load64Zero(self.take_memory_argument(items, position, 3)?)
+            }
+
+            "memory.atomic.notify" => {
+                Instruction::MemoryAtomicNotify(self.take_memory_argument(items, position, 2)?)
+            }
+            "memory.atomic.wait32" => {
+                Instruction::MemoryAtomicWait32(self.take_memory_argument(items, position, 2)?)
+            }
+            "memory.atomic.wait64" => {
+                Instruction::MemoryAtomicWait64(self.take_memory_argument(items, position, 3)?)
+            }
+            "i32.atomic.load" => {
+                Instruction::I32AtomicLoad(self.take_memory_argument(items, position, 2)?)
+            }
+            "i64.atomic.load" => {
+                Instruction::I64AtomicLoad(self.take_memory_argument(items, position, 3)?)
+            }
+            "i32.atomic.load8_u" => {
+                Instruction::I32AtomicLoad8U(self.take_memory_argument(items, position, 0)?)
+            }
+            "i32.atomic.load16_u" => {
+                Instruction::I32AtomicLoad16U(self.take_memory_argument(items, position, 1)?)
+            }
+            "i64.atomic.load8_u" => {
+                Instruction::I64AtomicLoad8U(self.take_memory_argument(items, position, 0)?)
+            }
+            "i64.atomic.load16_u" => {
+                Instruction::I64AtomicLoad16U(self.take_memory_argument(items, position, 1)?)
+            }
+            "i64.atomic.load32_u" => {
+                Instruction::I64AtomicLoad32U(self.take_memory_argument(items, position, 2)?)
+            }
+            "i32.atomic.store" => {
+                Instruction::I32AtomicStore(self.take_memory_argument(items, position, 2)?)
+            }
+            "i64.atomic.store" => {
+                Instruction::I64AtomicStore(self.take_memory_argument(items, position, 3)?)
+            }
+            "i32.atomic.store8" => {
+                Instruction::I32AtomicStore8(self.take_memory_argument(items, position, 0)?)
+            }
+            "i32.atomic.store16" => {
+                Instruction::I32AtomicStore16(self.take_memory_argument(items, position, 1)?)
+            }
+            "i64.atomic.store8" => {
+                Instruction::I64AtomicStore8(self.take_memory_argument(items, position, 0)?)
+            }
+            "i64.atomic.store16" => {
+                Instruction::I64AtomicStore16(self.take_memory_argument(items, position, 1)?)
+            }
+            "i64.atomic.store32" => {
+                Instruction::I64AtomicStore32(self.take_memory_argument(items, position, 2)?)
+            }
+            "i32.atomic.rmw.add" => {
+                Instruction::I32AtomicRmwAdd(self.take_memory_argument(items, position, 2)?)
+            }
+            "i64.atomic.rmw.add" => {
+                Instruction::I64AtomicRmwAdd(self.take_memory_argument(items, position, 3)?)
+            }
+            "i32.atomic.rmw8.add_u" => {
+                Instruction::I32AtomicRmw8AddU(self.take_memory_argument(items, position, 0)?)
+            }
+            "i32.atomic.rmw16.add_u" => {
+                Instruction::I32AtomicRmw16AddU(self.take_memory_argument(items, position, 1)?)
+            }
+            "i64.atomic.rmw8.add_u" => {
+                Instruction::I64AtomicRmw8AddU(self.take_memory_argument(items, position, 0)?)
+            }
+            "i64.atomic.rmw16.add_u" => {
+                Instruction::I64AtomicRmw16AddU(self.take_memory_argument(items, position, 1)?)
+            }
+            "i64.atomic.rmw32.add_u" => {
+                Instruction::I64AtomicRmw32AddU(self.take_memory_argument(items, position, 2)?)
+            }
+            "i32.atomic.rmw.sub" => {
+                Instruction::I32AtomicRmwSub(self.take_memory_argument(items, position, 2)?)
+            }
+            "i64.atomic.rmw.sub" => {
+                Instruction::I64AtomicRmwSub(self.take_memory_argument(items, position, 3)?)
+            }
+            "i32.atomic.rmw8.sub_u" => {
+                Instruction::I32AtomicRmw8SubU(self.take_memory_argument(items, position, 0)?)
+            }
+            "i32.atomic.rmw16.sub_u" => {
+                Instruction::I32AtomicRmw16SubU(self.take_memory_argument(items, position, 1)?)
+            }
+            "i64.atomic.rmw8.sub_u" => {
+                Instruction::I64AtomicRmw8SubU(self.take_memory_argument(items, position, 0)?)
+            }
+            "i64.atomic.rmw16.sub_u" => {
+                Instruction::I64AtomicRmw16SubU(self.take_memory_argument(items, position, 1)?)
+            }
+            "i64.atomic.rmw32.sub_u" => {
+                Instruction::I64AtomicRmw32SubU(self.take_memory_argument(items, position, 2)?)
+            }
+            "i32.atomic.rmw.and" => {
+                Instruction::I32AtomicRmwAnd(self.take_memory_argument(items, position, 2)?)
+            }
+            "i64.atomic.rmw.and" => {
+                Instruction::I64AtomicRmwAnd(self.take_memory_argument(items, position, 3)?)
+            }
+            "i32.atomic.rmw8.and_u" => {
+                Instruction::I32AtomicRmw8AndU(self.take_memory_argument(items, position, 0)?)
+            }
+            "i32.atomic.rmw16.and_u" => {
+                Instruction::I32AtomicRmw16AndU(self.take_memory_argument(items, position, 1)?)
+            }
+            "i64.atomic.rmw8.and_u" => {
+                Instruction::I64AtomicRmw8AndU(self.take_memory_argument(items, position, 0)?)
+            }
+            "i64.atomic.rmw16.and_u" => {
+                Instruction::I64AtomicRmw16AndU(self.take_memory_argument(items, position, 1)?)
+            }
+            "i64.atomic.rmw32.and_u" => {
+                Instruction::I64AtomicRmw32AndU(self.take_memory_argument(items, position, 2)?)
+            }
+            "i32.atomic.rmw.or" => {
+                Instruction::I32AtomicRmwOr(self.take_memory_argument(items, position, 2)?)
+            }
+            "i64.atomic.rmw.or" => {
+                Instruction::I64AtomicRmwOr(self.take_memory_argument(items, position, 3)?)
+            }
+            "i32.atomic.rmw8.or_u" => {
+                Instruction::I32AtomicRmw8OrU(self.take_memory_argument(items, position, 0)?)
+            }
+            "i32.atomic.rmw16.or_u" => {
+                Instruction::I32AtomicRmw16OrU(self.take_memory_argument(items, position, 1)?)
+            }
+            "i64.atomic.rmw8.or_u" => {
+                Instruction::I64AtomicRmw8OrU(self.take_memory_argument(items, position, 0)?)
+            }
+            "i64.atomic.rmw16.or_u" => {
+                Instruction::I64AtomicRmw16OrU(self.take_memory_argument(items, position, 1)?)
+            }
+            "i64.atomic.rmw32.or_u" => {
+                Instruction::I64AtomicRmw32OrU(self.take_memory_argument(items, position, 2)?)
+            }
+            "i32.atomic.rmw.xor" => {
+                Instruction::I32AtomicRmwXor(self.take_memory_argument(items, position, 2)?)
+            }
+            "i64.atomic.rmw.xor" => {
+                Instruction::I64AtomicRmwXor(self.take_memory_argument(items, position, 3)?)
+            }
+            "i32.atomic.rmw8.xor_u" => {
+                Instruction::I32AtomicRmw8XorU(self.take_memory_argument(items, position, 0)?)
+            }
+            "i32.atomic.rmw16.xor_u" => {
+                Instruction::I32AtomicRmw16XorU(self.take_memory_argument(items, position, 1)?)
+            }
+            "i64.atomic.rmw8.xor_u" => {
+                Instruction::I64AtomicRmw8XorU(self.take_memory_argument(items, position, 0)?)
+            }
+            "i64.atomic.rmw16.xor_u" => {
+                Instruction::I64AtomicRmw16XorU(self.take_memory_argument(items, position, 1)?)
+            }
+            "i64.atomic.rmw32.xor_u" => {
+                Instruction::I64AtomicRmw32XorU(self.take_memory_argument(items, position, 2)?)
+            }
+            "i32.atomic.rmw.xchg" => {
+                Instruction::I32AtomicRmwXchg(self.take_memory_argument(items, position, 2)?)
+            }
+            "i64.atomic.rmw.xchg" => {
+                Instruction::I64AtomicRmwXchg(self.take_memory_argument(items, position, 3)?)
+            }
+            "i32.atomic.rmw8.xchg_u" => {
+                Instruction::I32AtomicRmw8XchgU(self.take_memory_argument(items, position, 0)?)
+            }
+            "i32.atomic.rmw16.xchg_u" => {
+                Instruction::I32AtomicRmw16XchgU(self.take_memory_argument(items, position, 1)?)
+            }
+            "i64.atomic.rmw8.xchg_u" => {
+                Instruction::I64AtomicRmw8XchgU(self.take_memory_argument(items, position, 0)?)
+            }
+            "i64.atomic.rmw16.xchg_u" => {
+                Instruction::I64AtomicRmw16XchgU(self.take_memory_argument(items, position, 1)?)
+            }
+            "i64.atomic.rmw32.xchg_u" => {
+                Instruction::I64AtomicRmw32XchgU(self.take_memory_argument(items, position, 2)?)
+            }
+            "i32.atomic.rmw.cmpxchg" => {
+                Instruction::I32AtomicRmwCmpxchg(self.take_memory_argument(items, position, 2)?)
+            }
+            "i64.atomic.rmw.cmpxchg" => {
+                Instruction::I64AtomicRmwCmpxchg(self.take_memory_argument(items, position, 3)?)
+            }
+            "i32.atomic.rmw8.cmpxchg_u" => {
+                Instruction::I32AtomicRmw8CmpxchgU(self.take_memory_argument(items, position, 0)?)
+            }
+            "i32.atomic.rmw16.cmpxchg_u" => {
+                Instruction::I32AtomicRmw16CmpxchgU(self.take_memory_argument(items, position, 1)?)
+            }
+            "i64.atomic.rmw8.cmpxchg_u" => {
+                Instruction::I64AtomicRmw8CmpxchgU(self.take_memory_argument(items, position, 0)?)
+            }
+            "i64.atomic.rmw16.cmpxchg_u" => {
+                Instruction::I64AtomicRmw16CmpxchgU(self.take_memory_argument(items, position, 1)?)
+            }
+            "i64.atomic.rmw32.cmpxchg_u" => {
+                Instruction::I64AtomicRmw32CmpxchgU(self.take_memory_argument(items, position, 2)?)
+            }
+
+            "block" | "loop" | "if" | "try_table" | "else" | "end" | "then" => {
+                return Err(syntax_error(
+                    keyword_expression,
+                    &format!("unexpected \"{}\"", keyword),
+                ))
+            }
+            _ => {
+                return Err(syntax_error(
+                    keyword_expression,
+                    &format!("unknown instruction \"{}\"", keyword),
+                ))
+            }
+        };
+
+        Ok(instruction)
+    }
+
+    /// 读取可选的索引，如果省略了索引，则返回 0
+    fn take_optional_index(
+        &self,
+        index_kind: IndexKind,
+        items: &[SExpression],
+        position: &mut usize,
+    ) -> Result<u32, AssemblyError> {
+        match items.get(*position).filter(|item| is_index(item)) {
+            Some(item) => {
+                *position += 1;
+                match index_kind {
+                    IndexKind::Table => self.tables.resolve(item),
+                    IndexKind::MemoryBlock => self.memory_blocks.resolve(item),
+                }
+            }
+            None => Ok(0),
+        }
+    }
+
+    /// 解析内存类指令的参数 `memory_block_index? offset=N? align=N?`
+    fn take_memory_argument(
+        &self,
+        items: &[SExpression],
+        position: &mut usize,
+        natural_align: u32,
+    ) -> Result<MemoryArgument, AssemblyError> {
+        let memory_block_index =
+            self.take_optional_index(IndexKind::MemoryBlock, items, position)?;
+        take_offset_and_align(items, position, natural_align, memory_block_index)
+    }
+
+    /// 解析 `v128.load*_lane` 和 `v128.store*_lane` 指令的内存参数
+    ///
+    /// 这些指令的内存块索引和通道索引都是数字，所以只有当索引之后还跟着
+    /// 其他参数（内存参数或者通道索引）时，该索引才是内存块索引。
+    fn take_lane_memory_argument(
+        &self,
+        items: &[SExpression],
+        position: &mut usize,
+        natural_align: u32,
+    ) -> Result<MemoryArgument, AssemblyError> {
+        let has_memory_block_index = items.get(*position + 1).is_some_and(|item| {
+            is_index(item)
+                || item
+                    .as_symbol()
+                    .is_some_and(|text| text.starts_with("offset=") || text.starts_with("align="))
+        });
+
+        let memory_block_index = if has_memory_block_index {
+            self.take_optional_index(IndexKind::MemoryBlock, items, position)?
+        } else {
+            0
+        };
+        take_offset_and_align(items, position, natural_align, memory_block_index)
+    }
+}
+
+enum IndexKind {
+    Table,
+    MemoryBlock,
+}
+
+/// 读取可选的标识符 `$id`，返回不含 `$` 前缀的标识符
+///
+/// 标识符之后的名称注解 `(@name "...")` 会被跳过。
+fn take_id<'a>(items: &'a [SExpression], position: &mut usize) -> Option<&'a str> {
+    let option_id = items
+        .get(*position)
+        .and_then(|item| item.as_symbol())
+        .and_then(|symbol| symbol.strip_prefix('$'));
+
+    if option_id.is_some() {
+        *position += 1;
+    }
+
+    if items.get(*position).and_then(|item| item.get_keyword()) == Some("@name") {
+        *position += 1;
+    }
+
+    option_id
+}
+
+/// 读取模块字段的可选标识符 `$id` 以及可选的名称注解 `(@name "...")`
+///
+/// 当项目的名称无法使用标识符表示时（比如名称含有空格，或者多个项目的名称相同），
+/// 反汇编工具（比如 `wasm-tools`）会使用名称注解记录项目的名称。
+fn take_field_id<'a>(
+    items: &'a [SExpression],
+    position: &mut usize,
+) -> Result<(Option<&'a str>, Option<String>), AssemblyError> {
+    let option_id = items
+        .get(*position)
+        .and_then(|item| item.as_symbol())
+        .and_then(|symbol| symbol.strip_prefix('$'));
+
+    if option_id.is_some() {
+        *position += 1;
+    }
+
+    let mut option_name: Option<String> = None;
+
+    if let Some(item) = items.get(*position) {
+        if item.get_keyword() == Some("@name") {
+            let annotation_items = item.as_list().unwrap();
+            option_name = Some(get_name(item, annotation_items, 1)?);
+            expect_end(annotation_items, 2)?;
+            *position += 1;
+        }
+    }
+
+    Ok((option_id, option_name))
+}
+
+fn take_item<'a>(
+    expression: &SExpression,
+    items: &'a [SExpression],
+    position: &mut usize,
+) -> Result<&'a SExpression, AssemblyError> {
+    let item = items
+        .get(*position)
+        .ok_or_else(|| syntax_error(expression, "missing immediate argument"))?;
+
+    *position += 1;
+    Ok(item)
+}
+
+/// 判断表达式是否索引，即数字或者 `$id`
+fn is_index(expression: &SExpression) -> bool {
+    match expression.as_symbol() {
+        Some(symbol) => symbol.starts_with('$') || symbol.starts_with(|c: char| c.is_ascii_digit()),
+        None => false,
+    }
+}
+
+fn skip_inline_exports(items: &[SExpression], position: &mut usize) {
+    while items.get(*position).and_then(|item| item.get_keyword()) == Some("export") {
+        *position += 1;
+    }
+}
+
+fn expect_end(items: &[SExpression], position: usize) -> Result<(), AssemblyError> {
+    match items.get(position) {
+        Some(item) => Err(syntax_error(item, "unexpected token")),
+        None => Ok(()),
+    }
+}
+
+/// 结构块的 `else` 和 `end` 指令后面可以跟随标签，该标签必须跟结构块的标签一致
+fn check_closing_label(
+    context: &FunctionContext,
+    keyword_expression: &SExpression,
+    items: &[SExpression],
+    position: &mut usize,
+) -> Result<(), AssemblyError> {
+    let option_current_label = match context.labels.last() {
+        Some(option_label) => option_label,
+        None => {
+            return Err(syntax_error(
+                keyword_expression,
+                &format!("unexpected \"{}\"", keyword_expression.as_symbol().unwrap()),
+            ))
+        }
+    };
+
+    if let Some(label) = take_id(items, position) {
+        if option_current_label.as_deref() != Some(label) {
+            return Err(syntax_error(keyword_expression, "mismatching label"));
+        }
+    }
+
+    Ok(())
+}
+
+fn get_name(
+    expression: &SExpression,
+    items: &[SExpression],
+    index: usize,
+) -> Result<String, AssemblyError> {
+    let item = items
+        .get(index)
+        .ok_or_else(|| syntax_error(expression, "expected a name"))?;
+    let bytes = item
+        .as_text()
+        .ok_or_else(|| syntax_error(item, "expected a name"))?;
+
+    String::from_utf8(bytes.to_vec()).map_err(|_| syntax_error(item, "malformed UTF-8 encoding"))
+}
+
+fn parse_number<T>(
+    expression: &SExpression,
+    parse: fn(&str) -> Option<T>,
+) -> Result<T, AssemblyError> {
+    let symbol = expression
+        .as_symbol()
+        .ok_or_else(|| syntax_error(expression, "expected a number"))?;
+
+    parse(symbol).ok_or_else(|| syntax_error(expression, &format!("invalid number \"{}\"", symbol)))
+}
+
+fn parse_value_type(expression: &SExpression) -> Result<ValueType, AssemblyError> {
+    match expression.as_symbol() {
+        Some("i32") => Ok(ValueType::I32),
+        Some("i64") => Ok(ValueType::I64),
+        Some("f32") => Ok(ValueType::F32),
+        Some("f64") => Ok(ValueType::F64),
+        Some("funcref") => Ok(ValueType::FuncRef),
+        Some("externref") => Ok(ValueType::ExternRef),
+        Some("exnref") => Ok(ValueType::ExnRef),
+        Some("v128") => Ok(ValueType::V128),
+        _ => Err(syntax_error(expression, "expected a value type")),
+    }
+}
+
+/// 解析 `(param $id valtype)`、`(param valtype*)`、`(local ...)` 以及 `(result valtype*)`
+fn parse_value_type_declaration(
+    expression: &SExpression,
+) -> Result<Vec<(/* id */ Option<String>, ValueType)>, AssemblyError> {
+    let items = expression.as_list().unwrap();
+    let mut position = 1;
+
+    match take_id(items, &mut position) {
+        Some(id) => {
+            let value_type_expression = items
+                .get(position)
+                .ok_or_else(|| syntax_error(expression, "expected a value type"))?;
+            let value_type = parse_value_type(value_type_expression)?;
+            expect_end(items, position + 1)?;
+            Ok(vec![(Some(id.to_string()), value_type)])
+        }
+        None => items[position..]
+            .iter()
+            .map(|item| parse_value_type(item).map(|value_type| (None, value_type)))
+            .collect(),
+    }
+}
+
+/// 解析 `(param ...)* (result ...)*`
+fn parse_inline_type_use(
+    items: &[SExpression],
+    position: &mut usize,
+) -> Result<TypeUse, AssemblyError> {
+    let mut type_use = TypeUse::default();
+
+    while let Some(item) = items.get(*position) {
+        if item.get_keyword() != Some("param") {
+            break;
+        }
+        type_use.params.extend(parse_value_type_declaration(item)?);
+        *position += 1;
+    }
+
+    while let Some(item) = items.get(*position) {
+        if item.get_keyword() != Some("result") {
+            break;
+        }
+
+        let result_items = item.as_list().unwrap();
+        for result_item in &result_items[1..] {
+            type_use.results.push(parse_value_type(result_item)?);
+        }
+        *position += 1;
+    }
+
+    Ok(type_use)
+}
+
+/// 解析 limit 值 `min max?`
+///
+/// 只有 64 位内存块的 limit 值可以超出 u32 的范围。
+fn parse_limit(
+    expression: &SExpression,
+    items: &[SExpression],
+    position: &mut usize,
+    is_64: bool,
+) -> Result<Limit, AssemblyError> {
+    let parse_bound = |item: &SExpression| {
+        if is_64 {
+            parse_number(item, number_literal::parse_u64)
+        } else {
+            parse_number(item, number_literal::parse_u32).map(|value| value as u64)
+        }
+    };
+
+    let min_expression = items
+        .get(*position)
+        .ok_or_else(|| syntax_error(expression, "expected a limit"))?;
+
+    let min = parse_bound(min_expression)?;
+    *position += 1;
+
+    match items.get(*position).and_then(|item| item.as_symbol()) {
+        Some(symbol) if symbol.starts_with(|c: char| c.is_ascii_digit()) => {
+            let max = parse_bound(&items[*position])?;
+            *position += 1;
+            Ok(Limit::Range(min, max))
+        }
+        _ => Ok(Limit::AtLeast(min)),
+    }
+}
+
+/// 解析可选的内存地址类型 `i32` 或者 `i64`，返回是否 64 位内存块
+fn parse_address_type(items: &[SExpression], position: &mut usize) -> bool {
+    match items.get(*position).and_then(|item| item.as_symbol()) {
+        Some("i64") => {
+            *position += 1;
+            true
+        }
+        Some("i32") => {
+            *position += 1;
+            false
+        }
+        _ => false,
+    }
+}
+
+/// 解析内存块的类型 `addrtype? limits shared?`
+fn parse_memory_type(
+    expression: &SExpression,
+    items: &[SExpression],
+    position: &mut usize,
+) -> Result<MemoryType, AssemblyError> {
+    let memory64 = parse_address_type(items, position);
+    let limit = parse_limit(expression, items, position, memory64)?;
+
+    let shared = items.get(*position).and_then(|item| item.as_symbol()) == Some("shared");
+    if shared {
+        *position += 1;
+    }
+
+    Ok(MemoryType {
+        limit,
+        shared,
+        memory64,
+    })
+}
+
+fn parse_reference_type(
+    expression: &SExpression,
+    items: &[SExpression],
+    position: &mut usize,
+) -> Result<RefType, AssemblyError> {
+    let item = items
+        .get(*position)
+        .ok_or_else(|| syntax_error(expression, "expected a reference type"))?;
+
+    let ref_type = match item.as_symbol() {
+        // `anyfunc` 是 `funcref` 的旧名称
+        Some("funcref") | Some("anyfunc") => RefType::FuncRef,
+        Some("externref") => RefType::ExternRef,
+        Some("exnref") => RefType::ExnRef,
+        _ => return Err(syntax_error(item, "expected a reference type")),
+    };
+
+    *position += 1;
+    Ok(ref_type)
+}
+
+/// 解析堆类型 `func`、`extern` 或者 `exn`，比如 `ref.null func`
+fn parse_heap_type(expression: &SExpression) -> Result<RefType, AssemblyError> {
+    match expression.as_symbol() {
+        Some("func") => Ok(RefType::FuncRef),
+        Some("extern") => Ok(RefType::ExternRef),
+        Some("exn") => Ok(RefType::ExnRef),
+        _ => Err(syntax_error(expression, "expected a heap type")),
+    }
+}
+
+/// 解析表的类型 `limits reftype`
+fn parse_table_type(
+    expression: &SExpression,
+    items: &[SExpression],
+    position: &mut usize,
+) -> Result<TableType, AssemblyError> {
+    if let Some(item) = items
+        .get(*position)
+        .filter(|item| item.as_symbol() == Some("i64"))
+    {
+        return Err(unsupported_error(item, "\"i64\" table"));
+    }
+
+    let limit = parse_limit(expression, items, position, false)?;
+    let element_type = parse_reference_type(expression, items, position)?;
+    Ok(TableType {
+        element_type,
+        limit,
+    })
+}
+
+/// 解析全局变量的类型 `valtype` 或者 `(mut valtype)`
+fn parse_global_type(
+    expression: &SExpression,
+    items: &[SExpression],
+    position: &mut usize,
+) -> Result<GlobalType, AssemblyError> {
+    let item = items
+        .get(*position)
+        .ok_or_else(|| syntax_error(expression, "expected a global type"))?;
+    *position += 1;
+
+    if item.get_keyword() == Some("mut") {
+        let mut_items = item.as_list().unwrap();
+        let value_type_expression = mut_items
+            .get(1)
+            .ok_or_else(|| syntax_error(item, "expected a value type"))?;
+        expect_end(mut_items, 2)?;
+
+        Ok(GlobalType {
+            value_type: parse_value_type(value_type_expression)?,
+            mutable: true,
+        })
+    } else {
+        Ok(GlobalType {
+            value_type: parse_value_type(item)?,
+            mutable: false,
+        })
+    }
+}
+
+fn parse_data_strings(items: &[SExpression]) -> Result<Vec<u8>, AssemblyError> {
+    let mut data: Vec<u8> = vec![];
+
+    for item in items {
+        let bytes = item
+            .as_text()
+            .ok_or_else(|| syntax_error(item, "expected a string"))?;
+        data.extend_from_slice(bytes);
+    }
+
+    Ok(data)
+}
+
+/// 解析内存类指令的参数 `offset=N? align=N?`
+///
+/// 参数 natural_align 是指令的自然对齐（以 2 为底的对数），当省略 `align` 时使用。
+fn take_offset_and_align(
+    items: &[SExpression],
+    position: &mut usize,
+    natural_align: u32,
+    memory_block_index: u32,
+) -> Result<MemoryArgument, AssemblyError> {
+    let mut offset: u64 = 0;
+    let mut align = natural_align;
+
+    if let Some(item) = items.get(*position) {
+        if let Some(text) = item.as_symbol().and_then(|s| s.strip_prefix("offset=")) {
+            offset = number_literal::parse_u64(text)
+                .ok_or_else(|| syntax_error(item, "invalid memory offset"))?;
+            *position += 1;
+        }
+    }
+
+    if let Some(item) = items.get(*position) {
+        if let Some(text) = item.as_symbol().and_then(|s| s.strip_prefix("align=")) {
+            let bytes = number_literal::parse_u32(text)
+                .filter(|bytes| bytes.is_power_of_two())
+                .ok_or_else(|| syntax_error(item, "alignment must be a power of two"))?;
+            align = bytes.trailing_zeros();
+            *position += 1;
+        }
+    }
+
+    Ok(MemoryArgument {
+        align,
+        offset,
+        memory_block_index,
+    })
+}
+
+/// 读取通道（lane）的索引，索引是一个 8 位的无符号整数
+///
+/// 索引是否超出向量的通道数由验证器检查。
+fn take_lane_index(
+    expression: &SExpression,
+    items: &[SExpression],
+    position: &mut usize,
+) -> Result<u8, AssemblyError> {
+    let item = take_item(expression, items, position)?;
+    parse_number(item, |text| {
+        number_literal::parse_u32(text).and_then(|value| u8::try_from(value).ok())
+    })
+}
+
+/// 把一个通道的字面量解析为小端格式的字节
+type LaneParser = fn(&str) -> Option<Vec<u8>>;
+
+/// 解析 `v128.const` 指令的参数 `shape lane*`
+///
+/// shape 可以是 `i8x16`、`i16x8`、`i32x4`、`i64x2`、`f32x4` 以及 `f64x2`，
+/// 通道值的数量由 shape 决定，比如 `v128.const i32x4 1 2 3 4`。
+fn take_v128_constant(
+    expression: &SExpression,
+    items: &[SExpression],
+    position: &mut usize,
+) -> Result<u128, AssemblyError> {
+    let shape_expression = take_item(expression, items, position)?;
+
+    let (lane_count, parse_lane): (usize, LaneParser) = match shape_expression.as_symbol() {
+        Some("i8x16") => (16, |text| {
+            number_literal::parse_i8(text).map(|value| vec![value])
+        }),
+        Some("i16x8") => (8, |text| {
+            number_literal::parse_i16(text).map(|value| value.to_le_bytes().to_vec())
+        }),
+        Some("i32x4") => (4, |text| {
+            number_literal::parse_i32(text).map(|value| value.to_le_bytes().to_vec())
+        }),
+        Some("i64x2") => (2, |text| {
+            number_literal::parse_i64(text).map(|value| value.to_le_bytes().to_vec())
+        }),
+        Some("f32x4") => (4, |text| {
+            number_literal::parse_f32(text).map(|value| value.to_le_bytes().to_vec())
+        }),
+        Some("f64x2") => (2, |text| {
+            number_literal::parse_f64(text).map(|value| value.to_le_bytes().to_vec())
+        }),
+        _ => return Err(syntax_error(shape_expression, "expected a vector shape")),
+    };
+
+    let mut bytes: Vec<u8> = Vec::with_capacity(16);
+    for _ in 0..lane_count {
+        let item = take_item(expression, items, position)?;
+        bytes.extend(parse_number(item, parse_lane)?);
+    }
+
+    Ok(u128::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+/// 获取没有立即数的指令
+fn get_instruction_without_immediate(keyword: &str) -> Option<Instruction> {
+    let instruction = match keyword {
+        "unreachable" => Instruction::Unreachable,
+        "nop" => Instruction::Nop,
+        "return" => Instruction::Return,
+        "drop" => Instruction::Drop,
+        "throw_ref" => Instruction::ThrowRef,
+        "ref.is_null" => Instruction::RefIsNull,
+
+        "i32.eqz" => Instruction::I32Eqz,
+        "i32.eq" => Instruction::I32Eq,
+        "i32.ne" => Instruction::I32Ne,
+        "i32.lt_s" => Instruction::I32LtS,
+        "i32.lt_u" => Instruction::I32LtU,
+        "i32.gt_s" => Instruction::I32GtS,
+        "i32.gt_u" => Instruction::I32GtU,
+        "i32.le_s" => Instruction::I32LeS,
+        "i32.le_u" => Instruction::I32LeU,
+        "i32.ge_s" => Instruction::I32GeS,
+        "i32.ge_u" => Instruction::I32GeU,
+
+        "i64.eqz" => Instruction::I64Eqz,
+        "i64.eq" => Instruction::I64Eq,
+        "i64.ne" => Instruction::I64Ne,
+        "i64.lt_s" => Instruction::I64LtS,
+        "i64.lt_u" => Instruction::I64LtU,
+        "i64.gt_s" => Instruction::I64GtS,
+        "i64.gt_u" => Instruction::I64GtU,
+        "i64.le_s" => Instruction::I64LeS,
+        "i64.le_u" => Instruction::I64LeU,
+        "i64.ge_s" => Instruction::I64GeS,
+        "i64.ge_u" => Instruction::I64GeU,
+
+        "f32.eq" => Instruction::F32Eq,
+        "f32.ne" => Instruction::F32Ne,
+        "f32.lt" => Instruction::F32Lt,
+        "f32.gt" => Instruction::F32Gt,
+        "f32.le" => Instruction::F32Le,
+        "f32.ge" => Instruction::F32Ge,
+
+        "f64.eq" => Instruction::F64Eq,
+        "f64.ne" => Instruction::F64Ne,
+        "f64.lt" => Instruction::F64Lt,
+        "f64.gt" => Instruction::F64Gt,
+        "f64.le" => Instruction::F64Le,
+        "f64.ge" => Instruction::F64Ge,
+
+        "i32.clz" => Instruction::I32Clz,
+        "i32.ctz" => Instruction::I32Ctz,
+        "i32.popcnt" => Instruction::I32PopCnt,
+
+        "i32.add" => Instruction::I32Add,
+        "i32.sub" => Instruction::I32Sub,
+        "i32.mul" => Instruction::I32Mul,
+        "i32.div_s" => Instruction::I32DivS,
+        "i32.div_u" => Instruction::I32DivU,
+        "i32.rem_s" => Instruction::I32RemS,
+        "i32.rem_u" => Instruction::I32RemU,
+        "i32.and" => Instruction::I32And,
+        "i32.or" => Instruction::I32Or,
+        "i32.xor" => Instruction::I32Xor,
+        "i32.shl" => Instruction::I32Shl,
+        "i32.shr_s" => Instruction::I32ShrS,
+        "i32.shr_u" => Instruction::I32ShrU,
+        "i32.rotl" => Instruction::I32Rotl,
+        "i32.rotr" => Instruction::I32Rotr,
+
+        "i64.clz" => Instruction::I64Clz,
+        "i64.ctz" => Instruction::I64Ctz,
+        "i64.popcnt" => Instruction::I64PopCnt,
+
+        "i64.add" => Instruction::I64Add,
+        "i64.sub" => Instruction::I64Sub,
+        "i64.mul" => Instruction::I64Mul,
+        "i64.div_s" => Instruction::I64DivS,
+        "i64.div_u" => Instruction::I64DivU,
+        "i64.rem_s" => Instruction::I64RemS,
+        "i64.rem_u" => Instruction::I64RemU,
+        "i64.and" => Instruction::I64And,
+        "i64.or" => Instruction::I64Or,
+        "i64.xor" => Instruction::I64Xor,
+        "i64.shl" => Instruction::I64Shl,
+        "i64.shr_s" => Instruction::I64ShrS,
+        "i64.shr_u" => Instruction::I64ShrU,
+        "i64.rotl" => Instruction::I64Rotl,
+        "i64.rotr" => Instruction::I64Rotr,
+
+        "f32.abs" => Instruction::F32Abs,
+        "f32.neg" => Instruction::F32Neg,
+        "f32.ceil" => Instruction::F32Ceil,
+        "f32.floor" => Instruction::F32Floor,
+        "f32.trunc" => Instruction::F32Trunc,
+        "f32.nearest" => Instruction::F32Nearest,
+        "f32.sqrt" => Instruction::F32Sqrt,
+
+        "f32.add" => Instruction::F32Add,
+        "f32.sub" => Instruction::F32Sub,
+        "f32.mul" => Instruction::F32Mul,
+        "f32.div" => Instruction::F32Div,
+        "f32.min" => Instruction::F32Min,
+        "f32.max" => Instruction::F32Max,
+        "f32.copysign" => Instruction::F32CopySign,
+
+        "f64.abs" => Instruction::F64Abs,
+        "f64.neg" => Instruction::F64Neg,
+        "f64.ceil" => Instruction::F64Ceil,
+        "f64.floor" => Instruction::F64Floor,
+        "f64.trunc" => Instruction::F64Trunc,
+        "f64.nearest" => Instruction::F64Nearest,
+        "f64.sqrt" => Instruction::F64Sqrt,
+
+        "f64.add" => Instruction::F64Add,
+        "f64.sub" => Instruction::F64Sub,
+        "f64.mul" => Instruction::F64Mul,
+        "f64.div" => Instruction::F64Div,
+        "f64.min" => Instruction::F64Min,
+        "f64.max" => Instruction::F64Max,
+        "f64.copysign" => Instruction::F64CopySign,
+
+        "i32.wrap_i64" => Instruction::I32WrapI64,
+        "i32.trunc_f32_s" => Instruction::I32TruncF32S,
+        "i32.trunc_f32_u" => Instruction::I32TruncF32U,
+        "i32.trunc_f64_s" => Instruction::I32TruncF64S,
+        "i32.trunc_f64_u" => Instruction::I32TruncF64U,
+        "i64.extend_i32_s" => Instruction::I64ExtendI32S,
+        "i64.extend_i32_u" => Instruction::I64ExtendI32U,
+        "i64.trunc_f32_s" => Instruction::I64TruncF32S,
+        "i64.trunc_f32_u" => Instruction::I64TruncF32U,
+        "i64.trunc_f64_s" => Instruction::I64TruncF64S,
+        "i64.trunc_f64_u" => Instruction::I64TruncF64U,
+        "f32.convert_i32_s" => Instruction::F32ConvertI32S,
+        "f32.convert_i32_u" => Instruction::F32ConvertI32U,
+        "f32.convert_i64_s" => Instruction::F32ConvertI64S,
+        "f32.convert_i64_u" => Instruction::F32ConvertI64U,
+        "f32.demote_f64" => Instruction::F32DemoteF64,
+        "f64.convert_i32_s" => Instruction::F64ConvertI32S,
+        "f64.convert_i32_u" => Instruction::F64ConvertI32U,
+        "f64.convert_i64_s" => Instruction::F64ConvertI64S,
+        "f64.convert_i64_u" => Instruction::F64ConvertI64U,
+        "f64.promote_f32" => Instruction::F64PromoteF32,
+        "i32.reinterpret_f32" => Instruction::I32ReinterpretF32,
+        "i64.reinterpret_f64" => Instruction::I64ReinterpretF64,
+        "f32.reinterpret_i32" => Instruction::F32ReinterpretI32,
+        "f64.reinterpret_i64" => Instruction::F64ReinterpretI64,
+
+        "i32.extend8_s" => Instruction::I32Extend8S,
+        "i32.extend16_s" => Instruction::I32Extend16S,
+        "i64.extend8_s" => Instruction::I64Extend8S,
+        "i64.extend16_s" => Instruction::I64Extend16S,
+        "i64.extend32_s" => Instruction::I64Extend32S,
+
+        "i32.trunc_sat_f32_s" => Instruction::I32TruncSatF32S,
+        "i32.trunc_sat_f32_u" => Instruction::I32TruncSatF32U,
+        "i32.trunc_sat_f64_s" => Instruction::I32TruncSatF64S,
+        "i32.trunc_sat_f64_u" => Instruction::I32TruncSatF64U,
+        "i64.trunc_sat_f32_s" => Instruction::I64TruncSatF32S,
+        "i64.trunc_sat_f32_u" => Instruction::I64TruncSatF32U,
+        "i64.trunc_sat_f64_s" => Instruction::I64TruncSatF64S,
+        "i64.trunc_sat_f64_u" => Instruction::I64TruncSatF64U,
+
+        "i8x16.swizzle" => Instruction::I8x16Swizzle,
+        "i8x16.splat" => Instruction::I8x16Splat,
+        "i16x8.splat" => Instruction::I16x8Splat,
+        "i32x4.splat" => Instruction::I32x4Splat,
+        "i64x2.splat" => Instruction::I64x2Splat,
+        "f32x4.splat" => Instruction::F32x4Splat,
+        "f64x2.splat" => Instruction::F64x2Splat,
+        "i8x16.eq" => Instruction::I8x16Eq,
+        "i8x16.ne" => Instruction::I8x16Ne,
+        "i8x16.lt_s" => Instruction::I8x16LtS,
+        "i8x16.lt_u" => Instruction::I8x16LtU,
+        "i8x16.gt_s" => Instruction::I8x16GtS,
+        "i8x16.gt_u" => Instruction::I8x16GtU,
+        "i8x16.le_s" => Instruction::I8x16LeS,
+        "i8x16.le_u" => Instruction::I8x16LeU,
+        "i8x16.ge_s" => Instruction::I8x16GeS,
+        "i8x16.ge_u" => Instruction::I8x16GeU,
+        "i16x8.eq" => Instruction::I16x8Eq,
+        "i16x8.ne" => Instruction::I16x8Ne,
+        "i16x8.lt_s" => Instruction::I16x8LtS,
+        "i16x8.lt_u" => Instruction::I16x8LtU,
+        "i16x8.gt_s" => Instruction::I16x8GtS,
+        "i16x8.gt_u" => Instruction::I16x8GtU,
+        "i16x8.le_s" => Instruction::I16x8LeS,
+        "i16x8.le_u" => Instruction::I16x8LeU,
+        "i16x8.ge_s" => Instruction::I16x8GeS,
+        "i16x8.ge_u" => Instruction::I16x8GeU,
+        "i32x4.eq" => Instruction::I32x4Eq,
+        "i32x4.ne" => Instruction::I32x4Ne,
+        "i32x4.lt_s" => Instruction::I32x4LtS,
+        "i32x4.lt_u" => Instruction::I32x4LtU,
+        "i32x4.gt_s" => Instruction::I32x4GtS,
+        "i32x4.gt_u" => Instruction::I32x4GtU,
+        "i32x4.le_s" => Instruction::I32x4LeS,
+        "i32x4.le_u" => Instruction::I32x4LeU,
+        "i32x4.ge_s" => Instruction::I32x4GeS,
+        "i32x4.ge_u" => Instruction::I32x4GeU,
+        "f32x4.eq" => Instruction::F32x4Eq,
+        "f32x4.ne" => Instruction::F32x4Ne,
+        "f32x4.lt" => Instruction::F32x4Lt,
+        "f32x4.gt" => Instruction::F32x4Gt,
+        "f32x4.le" => Instruction::F32x4Le,
+        "f32x4.ge" => Instruction::F32x4Ge,
+        "f64x2.eq" => Instruction::F64x2Eq,
+        "f64x2.ne" => Instruction::F64x2Ne,
+        "f64x2.lt" => Instruction::F64x2Lt,
+        "f64x2.gt" => Instruction::F64x2Gt,
+        "f64x2.le" => Instruction::F64x2Le,
+        "f64x2.ge" => Instruction::F64x2Ge,
+        "v128.not" => Instruction::V128Not,
+        "v128.and" => Instruction::V128And,
+        "v128.andnot" => Instruction::V128Andnot,
+        "v128.or" => Instruction::V128Or,
+        "v128.xor" => Instruction::V128Xor,
+        "v128.bitselect" => Instruction::V128Bitselect,
+        "v128.any_true" => Instruction::V128AnyTrue,
+        "f32x4.demote_f64x2_zero" => Instruction::F32x4DemoteF64x2Zero,
+        "f64x2.promote_low_f32x4" => Instruction::F64x2PromoteLowF32x4,
+        "i8x16.abs" => Instruction::I8x16Abs,
+        "i8x16.neg" => Instruction::I8x16Neg,
+        "i8x16.popcnt" => Instruction::I8x16Popcnt,
+        "i8x16.all_true" => Instruction::I8x16AllTrue,
+        "i8x16.bitmask" => Instruction::I8x16Bitmask,
+        "i8x16.narrow_i16x8_s" => Instruction::I8x16NarrowI16x8S,
+        "i8x16.narrow_i16x8_u" => Instruction::I8x16NarrowI16x8U,
+        "f32x4.ceil" => Instruction::F32x4Ceil,
+        "f32x4.floor" => Instruction::F32x4Floor,
+        "f32x4.trunc" => Instruction::F32x4Trunc,
+        "f32x4.nearest" => Instruction::F32x4Nearest,
+        "i8x16.shl" => Instruction::I8x16Shl,
+        "i8x16.shr_s" => Instruction::I8x16ShrS,
+        "i8x16.shr_u" => Instruction::I8x16ShrU,
+        "i8x16.add" => Instruction::I8x16Add,
+        "i8x16.add_sat_s" => Instruction::I8x16AddSatS,
+        "i8x16.add_sat_u" => Instruction::I8x16AddSatU,
+        "i8x16.sub" => Instruction::I8x16Sub,
+        "i8x16.sub_sat_s" => Instruction::I8x16SubSatS,
+        "i8x16.sub_sat_u" => Instruction::I8x16SubSatU,
+        "f64x2.ceil" => Instruction::F64x2Ceil,
+        "f64x2.floor" => Instruction::F64x2Floor,
+        "i8x16.min_s" => Instruction::I8x16MinS,
+        "i8x16.min_u" => Instruction::I8x16MinU,
+        "i8x16.max_s" => Instruction::I8x16MaxS,
+        "i8x16.max_u" => Instruction::I8x16MaxU,
+        "f64x2.trunc" => Instruction::F64x2Trunc,
+        "i8x16.avgr_u" => Instruction::I8x16AvgrU,
+        "i16x8.extadd_pairwise_i8x16_s" => Instruction::I16x8ExtaddPairwiseI8x16S,
+        "i16x8.extadd_pairwise_i8x16_u" => Instruction::I16x8ExtaddPairwiseI8x16U,
+        "i32x4.extadd_pairwise_i16x8_s" => Instruction::I32x4ExtaddPairwiseI16x8S,
+        "i32x4.extadd_pairwise_i16x8_u" => Instruction::I32x4ExtaddPairwiseI16x8U,
+        "i16x8.abs" => Instruction::I16x8Abs,
+        "i16x8.neg" => Instruction::I16x8Neg,
+        "i16x8.q15mulr_sat_s" => Instruction::I16x8Q15mulrSatS,
+        "i16x8.all_true" => Instruction::I16x8AllTrue,
+        "i16x8.bitmask" => Instruction::I16x8Bitmask,
+        "i16x8.narrow_i32x4_s" => Instruction::I16x8NarrowI32x4S,
+        "i16x8.narrow_i32x4_u" => Instruction::I16x8NarrowI32x4U,
+        "i16x8.extend_low_i8x16_s" => Instruction::I16x8ExtendLowI8x16S,
+        "i16x8.extend_high_i8x16_s" => Instruction::I16x8ExtendHighI8x16S,
+        "i16x8.extend_low_i8x16_u" => Instruction::I16x8ExtendLowI8x16U,
+        "i16x8.extend_high_i8x16_u" => Instruction::I16x8ExtendHighI8x16U,
+        "i16x8.shl" => Instruction::I16x8Shl,
+        "i16x8.shr_s" => Instruction::I16x8ShrS,
+        "i16x8.shr_u" => Instruction::I16x8ShrU,
+        "i16x8.add" => Instruction::I16x8Add,
+        "i16x8.add_sat_s" => Instruction::I16x8AddSatS,
+        "i16x8.add_sat_u" => Instruction::I16x8AddSatU,
+        "i16x8.sub" => Instruction::I16x8Sub,
+        "i16x8.sub_sat_s" => Instruction::I16x8SubSatS,
+        "i16x8.sub_sat_u" => Instruction::I16x8SubSatU,
+        "f64x2.nearest" => Instruction::F64x2Nearest,
+        "i16x8.mul" => Instruction::I16x8Mul,
+        "i16x8.min_s" => Instruction::I16x8MinS,
+        "i16x8.min_u" => Instruction::I16x8MinU,
+        "i16x8.max_s" => Instruction::I16x8MaxS,
+        "i16x8.max_u" => Instruction::I16x8MaxU,
+        "i16x8.avgr_u" => Instruction::I16x8AvgrU,
+        "i16x8.extmul_low_i8x16_s" => Instruction::I16x8ExtmulLowI8x16S,
+        "i16x8.extmul_high_i8x16_s" => Instruction::I16x8ExtmulHighI8x16S,
+        "i16x8.extmul_low_i8x16_u" => Instruction::I16x8ExtmulLowI8x16U,
+        "i16x8.extmul_high_i8x16_u" => Instruction::I16x8ExtmulHighI8x16U,
+        "i32x4.abs" => Instruction::I32x4Abs,
+        "i32x4.neg" => Instruction::I32x4Neg,
+        "i32x4.all_true" => Instruction::I32x4AllTrue,
+        "i32x4.bitmask" => Instruction::I32x4Bitmask,
+        "i32x4.extend_low_i16x8_s" => Instruction::I32x4ExtendLowI16x8S,
+        "i32x4.extend_high_i16x8_s" => Instruction::I32x4ExtendHighI16x8S,
+        "i32x4.extend_low_i16x8_u" => Instruction::I32x4ExtendLowI16x8U,
+        "i32x4.extend_high_i16x8_u" => Instruction::I32x4ExtendHighI16x8U,
+        "i32x4.shl" => Instruction::I32x4Shl,
+        "i32x4.shr_s" => Instruction::I32x4ShrS,
+        "i32x4.shr_u" => Instruction::I32x4ShrU,
+        "i32x4.add" => Instruction::I32x4Add,
+        "i32x4.sub" => Instruction::I32x4Sub,
+        "i32x4.mul" => Instruction::I32x4Mul,
+        "i32x4.min_s" => Instruction::I32x4MinS,
+        "i32x4.min_u" => Instruction::I32x4MinU,
+        "i32x4.max_s" => Instruction::I32x4MaxS,
+        "i32x4.max_u" => Instruction::I32x4MaxU,
+        "i32x4.dot_i16x8_s" => Instruction::I32x4DotI16x8S,
+        "i32x4.extmul_low_i16x8_s" => Instruction::I32x4ExtmulLowI16x8S,
+        "i32x4.extmul_high_i16x8_s" => Instruction::I32x4ExtmulHighI16x8S,
+        "i32x4.extmul_low_i16x8_u" => Instruction::I32x4ExtmulLowI16x8U,
+        "i32x4.extmul_high_i16x8_u" => Instruction::I32x4ExtmulHighI16x8U,
+        "i64x2.abs" => Instruction::I64x2Abs,
+        "i64x2.neg" => Instruction::I64x2Neg,
+        "i64x2.all_true" => Instruction::I64x2AllTrue,
+        "i64x2.bitmask" => Instruction::I64x2Bitmask,
+        "i64x2.extend_low_i32x4_s" => Instruction::I64x2ExtendLowI32x4S,
+        "i64x2.extend_high_i32x4_s" => Instruction::I64x2ExtendHighI32x4S,
+        "i64x2.extend_low_i32x4_u" => Instruction::I64x2ExtendLowI32x4U,
+        "i64x2.extend_high_i32x4_u" => Instruction::I64x2ExtendHighI32x4U,
+        "i64x2.shl" => Instruction::I64x2Shl,
+        "i64x2.shr_s" => Instruction::I64x2ShrS,
+        "i64x2.shr_u" => Instruction::I64x2ShrU,
+        "i64x2.add" => Instruction::I64x2Add,
+        "i64x2.sub" => Instruction::I64x2Sub,
+        "i64x2.mul" => Instruction::I64x2Mul,
+        "i64x2.eq" => Instruction::I64x2Eq,
+        "i64x2.ne" => Instruction::I64x2Ne,
+        "i64x2.lt_s" => Instruction::I64x2LtS,
+        "i64x2.gt_s" => Instruction::I64x2GtS,
+        "i64x2.le_s" => Instruction::I64x2LeS,
+        "i64x2.ge_s" => Instruction::I64x2GeS,
+        "i64x2.extmul_low_i32x4_s" => Instruction::I64x2ExtmulLowI32x4S,
+        "i64x2.extmul_high_i32x4_s" => Instruction::I64x2ExtmulHighI32x4S,
+        "i64x2.extmul_low_i32x4_u" => Instruction::I64x2ExtmulLowI32x4U,
+        "i64x2.extmul_high_i32x4_u" => Instruction::I64x2ExtmulHighI32x4U,
+        "f32x4.abs" => Instruction::F32x4Abs,
+        "f32x4.neg" => Instruction::F32x4Neg,
+        "f32x4.sqrt" => Instruction::F32x4Sqrt,
+        "f32x4.add" => Instruction::F32x4Add,
+        "f32x4.sub" => Instruction::F32x4Sub,
+        "f32x4.mul" => Instruction::F32x4Mul,
+        "f32x4.div" => Instruction::F32x4Div,
+        "f32x4.min" => Instruction::F32x4Min,
+        "f32x4.max" => Instruction::F32x4Max,
+        "f32x4.pmin" => Instruction::F32x4Pmin,
+        "f32x4.pmax" => Instruction::F32x4Pmax,
+        "f64x2.abs" => Instruction::F64x2Abs,
+        "f64x2.neg" => Instruction::F64x2Neg,
+        "f64x2.sqrt" => Instruction::F64x2Sqrt,
+        "f64x2.add" => Instruction::F64x2Add,
+        "f64x2.sub" => Instruction::F64x2Sub,
+        "f64x2.mul" => Instruction::F64x2Mul,
+        "f64x2.div" => Instruction::F64x2Div,
+        "f64x2.min" => Instruction::F64x2Min,
+        "f64x2.max" => Instruction::F64x2Max,
+        "f64x2.pmin" => Instruction::F64x2Pmin,
+        "f64x2.pmax" => Instruction::F64x2Pmax,
+        "i32x4.trunc_sat_f32x4_s" => Instruction::I32x4TruncSatF32x4S,
+        "i32x4.trunc_sat_f32x4_u" => Instruction::I32x4TruncSatF32x4U,
+        "f32x4.convert_i32x4_s" => Instruction::F32x4ConvertI32x4S,
+        "f32x4.convert_i32x4_u" => Instruction::F32x4ConvertI32x4U,
+        "i32x4.trunc_sat_f64x2_s_zero" => Instruction::I32x4TruncSatF64x2SZero,
+        "i32x4.trunc_sat_f64x2_u_zero" => Instruction::I32x4TruncSatF64x2UZero,
+        "f64x2.convert_low_i32x4_s" => Instruction::F64x2ConvertLowI32x4S,
+        "f64x2.convert_low_i32x4_u" => Instruction::F64x2ConvertLowI32x4U,
+
+        "atomic.fence" => Instruction::AtomicFence,
+
+        _ => return None,
+    };
+
+    Some(instruction)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf};
+
+    use anvm_ast::{
+        ast::{
+            CodeItem, CustomItem, DataItem, DataMode, ElementItem, ElementItems, ElementMode,
+            ExportDescriptor, ExportItem, FunctionIndexAndBlockLabelsPair,
+            FunctionIndexAndLocalVariableNamesPair, FunctionType, ImportDescriptor, ImportItem,
+            IndexNamePair, Limit, Module, NameCollection, TableType, TypeItem,
+        },
+        instruction::{BlockType, Instruction},
+        types::{RefType, ValueType},
+    };
+    use anvm_binary_encoder::encoder;
+    use anvm_binary_parser::parser;
+    use anvm_disassembly::disassembler::module_to_text;
+    use pretty_assertions::assert_eq;
+
+    use super::assemble;
+    use crate::error::AssemblyError;
+
+    // 辅助方法
+    fn get_test_resource_path() -> PathBuf {
+        let mut path_buf = env::current_dir().unwrap();
+
+        // 使用 `cargo test` 测试时，
+        // `env::current_dir()` 函数获得的当前目录为
+        // `./xiaoxuan-vm/crates/assembly`；
+        //
+        // 但如果使用 vscode 的源码编辑框里面的 `debug` 按钮开始调试，
+        // `env::current_dir()` 函数获得的当前目录为
+        // `./xiaoxuan-vm`。
+        //
+        // 下面语句用于处理这种情况。
+
+        if !path_buf.ends_with("assembly") {
+            path_buf.push("crates");
+            path_buf.push("assembly");
+        }
+        path_buf.join("resources")
+    }
+
+    fn get_test_binary_resource(filename: &str) -> Vec<u8> {
+        let fullname_buf = get_test_resource_path().join(filename);
+        let fullname = fullname_buf.to_str().unwrap();
+        fs::read(fullname)
+            .unwrap_or_else(|_| panic!("failed to read the specified binary file: {}", fullname))
+    }
+
+    /// 解析二进制模块，并移除文本格式无法表示的自定义段（即除了 `name` 段之外的自定义段）
+    fn get_test_ast_module(filename: &str) -> Module {
+        let bytes = get_test_binary_resource(filename);
+        let mut module = parser::parse(&bytes).unwrap();
+        module
+            .custom_items
+            .retain(|custom_item| matches!(custom_item, CustomItem::NameCollections(_)));
+        module
+    }
+
+    fn get_test_text_resource(filename: &str) -> String {
+        let bytes = get_test_binary_resource(filename);
+        String::from_utf8(bytes).unwrap()
+    }
+
+    /// 汇编文本格式的测试资源，检查结果跟对应的二进制模块是否一致，
+    /// 同时检查汇编结果能否经过二进制编码和解析之后保持不变。
+    fn test_assemble_resource(name: &str) {
+        let text = get_test_text_resource(&format!("{}.wat", name));
+        let module = assemble(&text).unwrap();
+        assert_eq!(module, get_test_ast_module(&format!("{}.wasm", name)));
+
+        let bytes = encoder::encode(&module);
+        assert_eq!(parser::parse(&bytes).unwrap(), module);
+    }
+
+    /// 检查反汇编器输出的文本能否汇编为相同的模块
+    fn test_reassemble_resource(name: &str) {
+        let module = get_test_ast_module(&format!("{}.wasm", name));
+        let text = module_to_text(&module);
+        assert_eq!(assemble(&text).unwrap(), module);
+    }
+
+    fn function_type(params: Vec<ValueType>, results: Vec<ValueType>) -> TypeItem {
+        TypeItem::FunctionType(FunctionType { params, results })
+    }
+
+    #[test]
+    fn test_assemble_instructions() {
+        test_assemble_resource("test-instruction-branch");
+        test_assemble_resource("test-instruction-call");
+        test_assemble_resource("test-instruction-const");
+        test_assemble_resource("test-instruction-flow-control");
+        test_assemble_resource("test-instruction-memory");
+        test_assemble_resource("test-instruction-memory64");
+        test_assemble_resource("test-extended-const");
+        test_assemble_resource("test-instruction-multi-memory");
+        test_assemble_resource("test-instruction-simd");
+        test_assemble_resource("test-instruction-atomic");
+        test_assemble_resource("test-instruction-tail-call");
+        test_assemble_resource("test-instruction-exception");
+        test_assemble_resource("test-instruction-variable");
+    }
+
+    #[test]
+    fn test_assemble_sections() {
+        test_assemble_resource("test-section-1");
+        test_assemble_resource("test-section-2");
+        test_assemble_resource("test-section-general");
+    }
+
+    #[test]
+    fn test_reassemble_disassembled_text() {
+        test_reassemble_resource("test-instruction-branch");
+        test_reassemble_resource("test-instruction-memory");
+        test_reassemble_resource("test-instruction-memory64");
+        test_reassemble_resource("test-extended-const");
+        test_reassemble_resource("test-instruction-multi-memory");
+        test_reassemble_resource("test-instruction-simd");
+        test_reassemble_resource("test-instruction-atomic");
+        test_reassemble_resource("test-instruction-tail-call");
+        test_reassemble_resource("test-instruction-exception");
+        test_reassemble_resource("test-section-1");
+        test_reassemble_resource("test-section-2");
+        test_reassemble_resource("test-section-custom");
+        test_reassemble_resource("test-section-general");
+    }
+
+    /// 所有 crate 的测试资源里的模块经过反汇编之后都应该能重新汇编为相同的模块
+    #[test]
+    fn test_reassemble_all_resources() {
+        let crates_path = get_test_resource_path().join("..").join("..");
+        let mut file_paths = fs::read_dir(&crates_path)
+            .unwrap()
+            .map(|entry| entry.unwrap().path().join("resources"))
+            .filter(|resource_path| resource_path.is_dir())
+            .flat_map(|resource_path| fs::read_dir(resource_path).unwrap())
+            .map(|entry| entry.unwrap().path())
+            .filter(|file_path| file_path.extension().map_or(false, |ext| ext == "wasm"))
+            .collect::<Vec<PathBuf>>();
+        file_paths.sort();
+
+        let mut failures = vec![];
+        for file_path in &file_paths {
+            let bytes = fs::read(file_path).unwrap();
+
+            // 跳过非核心模块（比如组件）
+            let mut module = match parser::parse(&bytes) {
+                Ok(module) => module,
+                Err(_) => continue,
+            };
+            module
+                .custom_items
+                .retain(|custom_item| matches!(custom_item, CustomItem::NameCollections(_)));
+
+            // 文本格式无法表示数据计数段，汇编器只在使用了
+            // `memory.init` 或者 `data.drop` 指令时才生成
+            let is_data_count_required = module.code_items.iter().any(|code_item| {
+                code_item.instruction_items.iter().any(|instruction| {
+                    matches!(
+                        instruction,
+                        Instruction::MemoryInit(_, _) | Instruction::DataDrop(_)
+                    )
+                })
+            });
+            if !is_data_count_required {
+                module.data_count = None;
+            }
+
+            let text = module_to_text(&module);
+            match assemble(&text) {
+                Ok(assembled_module) if assembled_module == module => {}
+                Ok(_) => failures.push(format!("{}: module mismatch", file_path.display())),
+                Err(e) => failures.push(format!("{}: {}", file_path.display(), e)),
+            }
+        }
+
+        assert!(file_paths.len() > 100);
+        assert_eq!(failures, Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_assemble_flat_and_folded() {
+        let flat = assemble(
+            r#"
+            (module
+                (func $f (param $a i32) (result i32)
+                    block $outer (result i32)
+                        local.get $a
+                        if $inner (result i32)
+                            i32.const 1
+                            br $outer
+                        else
+                            i32.const 2
+                        end $inner
+                    end
+                )
+            )"#,
+        )
+        .unwrap();
+
+        let folded = assemble(
+            r#"
+            (module
+                (func $f (param $a i32) (result i32)
+                    (block $outer (result i32)
+                        (if $inner (result i32) (local.get $a)
+                            (then (br $outer (i32.const 1)))
+                            (else (i32.const 2))
+                        )
+                    )
+                )
+            )"#,
+        )
+        .unwrap();
+
+        assert_eq!(flat, folded);
+        assert_eq!(
+            flat.code_items,
+            vec![CodeItem {
+                local_groups: vec![],
+                instruction_items: vec![
+                    Instruction::Block(BlockType::ResultI32, 0),
+                    Instruction::LocalGet(0),
+                    Instruction::If(BlockType::ResultI32, 1),
+                    Instruction::I32Const(1),
+                    Instruction::Br(1),
+                    Instruction::Else,
+                    Instruction::I32Const(2),
+                    Instruction::End,
+                    Instruction::End,
+                    Instruction::End,
+                ]
+            }]
+        );
+        assert_eq!(
+            flat.custom_items,
+            vec![CustomItem::NameCollections(vec![
+                NameCollection::FunctionNames(vec![IndexNamePair {
+                    index: 0,
+                    name: "f".to_string()
+                }]),
+                NameCollection::LocalVariableNamesPairList(vec![
+                    FunctionIndexAndLocalVariableNamesPair {
+                        function_index: 0,
+                        local_variable_names: vec![IndexNamePair {
+                            index: 0,
+                            name: "a".to_string()
+                        }]
+                    }
+                ]),
+                NameCollection::BlockLabelsPairList(vec![FunctionIndexAndBlockLabelsPair {
+                    function_index: 0,
+                    block_labels: vec![
+                        IndexNamePair {
+                            index: 0,
+                            name: "outer".to_string()
+                        },
+                        IndexNamePair {
+                            index: 1,
+                            name: "inner".to_string()
+                        }
+                    ]
+                }]),
+            ])]
+        );
+    }
+
+    #[test]
+    fn test_assemble_inline_type_and_export() {
+        let module = assemble(
+            r#"
+            (type $t0 (func (param i32)))
+            (func $log (export "log") (import "env" "log") (param i32))
+            (func (export "main") (export "start") (result i64)
+                (block (param i64) (result i64) (i64.const 1))
+                (call_indirect (param i32) (i32.const 0) (i32.const 1))
+            )
+            (table 1 funcref)
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            module.type_items,
+            vec![
+                function_type(vec![ValueType::I32], vec![]),
+                function_type(vec![], vec![ValueType::I64]),
+                function_type(vec![ValueType::I64], vec![ValueType::I64]),
+            ]
+        );
+        assert_eq!(
+            module.import_items,
+            vec![ImportItem {
+                module_name: "env".to_string(),
+                item_name: "log".to_string(),
+                import_descriptor: ImportDescriptor::FunctionTypeIndex(0)
+            }]
+        );
+        assert_eq!(module.internal_function_to_type_index_list, vec![1]);
+        assert_eq!(
+            module.export_items,
+            vec![
+                ExportItem {
+                    name: "log".to_string(),
+                    export_descriptor: ExportDescriptor::FunctionIndex(0)
+                },
+                ExportItem {
+                    name: "main".to_string(),
+                    export_descriptor: ExportDescriptor::FunctionIndex(1)
+                },
+                ExportItem {
+                    name: "start".to_string(),
+                    export_descriptor: ExportDescriptor::FunctionIndex(1)
+                },
+            ]
+        );
+        assert_eq!(
+            module.code_items[0].instruction_items,
+            vec![
+                Instruction::Block(BlockType::TypeIndex(2), 0),
+                Instruction::I64Const(1),
+                Instruction::End,
+                Instruction::I32Const(0),
+                Instruction::I32Const(1),
+                Instruction::CallIndirect(0, 0),
+                Instruction::End,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_assemble_reference_types() {
+        let module = assemble(
+            r#"
+            (module
+                (table $t 2 externref)
+                (func $f (param externref) (result funcref)
+                    (if (result funcref) (ref.is_null (local.get 0))
+                        (then (ref.func $f))
+                        (else (select (result funcref)
+                            (ref.null func)
+                            (ref.func $f)
+                            (ref.is_null (table.get $t (i32.const 0)))))
+                    )
+                )
+                (elem $e func $f)
+                (elem declare func $f)
+                (elem (table $t) (i32.const 1) externref (item ref.null extern))
+            )"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            module.tables,
+            vec![TableType {
+                element_type: RefType::ExternRef,
+                limit: Limit::AtLeast(2)
+            }]
+        );
+        assert_eq!(
+            module.element_items,
+            vec![
+                ElementItem {
+                    mode: ElementMode::Passive,
+                    element_type: RefType::FuncRef,
+                    items: ElementItems::FunctionIndices(vec![0])
+                },
+                ElementItem {
+                    mode: ElementMode::Declarative,
+                    element_type: RefType::FuncRef,
+                    items: ElementItems::FunctionIndices(vec![0])
+                },
+                ElementItem {
+                    mode: ElementMode::Active {
+                        table_index: 0,
+                        offset_instruction_items: vec![Instruction::I32Const(1), Instruction::End]
+                    },
+                    element_type: RefType::ExternRef,
+                    items: ElementItems::Expressions(vec![vec![
+                        Instruction::RefNull(RefType::ExternRef),
+                        Instruction::End
+                    ]])
+                },
+            ]
+        );
+        assert_eq!(
+            module.code_items[0].instruction_items,
+            vec![
+                Instruction::LocalGet(0),
+                Instruction::RefIsNull,
+                Instruction::If(BlockType::ResultFuncRef, 0),
+                Instruction::RefFunc(0),
+                Instruction::Else,
+                Instruction::RefNull(RefType::FuncRef),
+                Instruction::RefFunc(0),
+                Instruction::I32Const(0),
+                Instruction::TableGet(0),
+                Instruction::RefIsNull,
+                Instruction::SelectTyped(vec![ValueType::FuncRef]),
+                Instruction::End,
+                Instruction::End,
+            ]
+        );
+
+        // 经过二进制格式往返之后，语法树应该保持不变
+        let binary = encoder::encode(&module);
+        assert_eq!(parser::parse(&binary).unwrap(), module);
+
+        // 内联的元素表达式
+        let module =
+            assemble("(table funcref (elem (item ref.func 0) (ref.null func))) (func)").unwrap();
+
+        assert_eq!(
+            module.element_items,
+            vec![ElementItem {
+                mode: ElementMode::Active {
+                    table_index: 0,
+                    offset_instruction_items: vec![Instruction::I32Const(0), Instruction::End]
+                },
+                element_type: RefType::FuncRef,
+                items: ElementItems::Expressions(vec![
+                    vec![Instruction::RefFunc(0), Instruction::End],
+                    vec![Instruction::RefNull(RefType::FuncRef), Instruction::End]
+                ])
+            }]
+        );
+    }
+
+    #[test]
+    fn test_assemble_data_segments() {
+        let module = assemble(
+            r#"
+            (module
+                (memory 1)
+                (memory $m 1)
+                (data $d "ab" "c")
+                (data (memory $m) (offset (i32.const 8)) "d")
+                (data 1 (i32.const 16) "e")
+                (func
+                    (memory.init $d (i32.const 0) (i32.const 0) (i32.const 3))
+                    (data.drop $d)
+                )
+            )"#,
+        )
+        .unwrap();
+
+        let active = |offset: i32, data: u8| DataItem {
+            mode: DataMode::Active {
+                memory_block_index: 1,
+                offset_instruction_items: vec![Instruction::I32Const(offset), Instruction::End],
+            },
+            data: vec![data],
+        };
+
+        assert_eq!(
+            module.data_items,
+            vec![
+                DataItem {
+                    mode: DataMode::Passive,
+                    data: vec![b'a', b'b', b'c']
+                },
+                active(8, b'd'),
+                active(16, b'e'),
+            ]
+        );
+        assert_eq!(module.data_count, Some(3));
+    }
+
+    #[test]
+    fn test_assemble_vector_constants() {
+        let module = assemble(
+            r#"
+            (func (result v128)
+                (v128.const i8x16 -1 0 1 2 3 4 5 6 7 8 9 10 11 12 13 0xff)
+                (v128.const i16x8 -32768 1 2 3 4 5 6 65535)
+                (v128.const i64x2 -1 0x1234)
+                (v128.const f32x4 1.0 -0.0 inf nan)
+                (v128.const f64x2 -1.5 0x1p-1)
+                (drop) (drop) (drop) (drop)
+            )"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            module.code_items[0].instruction_items[..5],
+            [
+                Instruction::V128Const(0xff0d_0c0b_0a09_0807_0605_0403_0201_00ff),
+                Instruction::V128Const(0xffff_0006_0005_0004_0003_0002_0001_8000),
+                Instruction::V128Const(0x0000_0000_0000_1234_ffff_ffff_ffff_ffff),
+                Instruction::V128Const(0x7fc0_0000_7f80_0000_8000_0000_3f80_0000),
+                Instruction::V128Const(0x3fe0_0000_0000_0000_bff8_0000_0000_0000),
+            ]
+        );
+
+        // 通道的数量以及数值的范围必须跟形状一致
+        assert!(assemble("(func (v128.const i32x4 1 2 3) (drop))").is_err());
+        assert!(
+            assemble("(func (v128.const i8x16 256 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0) (drop))").is_err()
+        );
+        assert!(assemble("(func (v128.const i16x8 -32769 0 0 0 0 0 0 0) (drop))").is_err());
+    }
+
+    #[test]
+    fn test_assemble_quote_module() {
+        assert_eq!(
+            assemble(r#"(module quote "(func (result i32)" "i32.const 0xff)")"#).unwrap(),
+            assemble("(module (func (result i32) i32.const 255))").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_assemble_error() {
+        assert_eq!(
+            assemble("(module (func call $missing))"),
+            Err(AssemblyError::SyntaxError(
+                1,
+                "unknown identifier \"$missing\"".to_string()
+            ))
+        );
+        assert_eq!(
+            assemble("(module (func br $l))"),
+            Err(AssemblyError::SyntaxError(
+                1,
+                "unknown label \"$l\"".to_string()
+            ))
+        );
+        assert_eq!(
+            assemble("(module\n(func block))"),
+            Err(AssemblyError::SyntaxError(
+                2,
+                "missing \"end\" instruction".to_string()
+            ))
+        );
+        assert_eq!(
+            assemble("(module (func i32.const 4294967296))"),
+            Err(AssemblyError::SyntaxError(
+                1,
+                "invalid number \"4294967296\"".to_string()
+            ))
+        );
+        assert_eq!(
+            assemble("(module (func) (import \"m\" \"f\" (func)))"),
+            Err(AssemblyError::SyntaxError(
+                1,
+                "import must occur before all function, table, memory and global definitions"
+                    .to_string()
+            ))
+        );
+        assert_eq!(
+            assemble("(module (memory 1) (data 0 \"abc\"))"),
+            Err(AssemblyError::SyntaxError(
+                1,
+                "expected an offset expression for the data segment".to_string()
+            ))
+        );
+    }
+}
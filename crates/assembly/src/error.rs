@@ -0,0 +1,36 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::fmt::Display;
+
+/// 汇编（即把文本格式转换为 `ast::Module`）的错误
+#[derive(Debug, PartialEq, Clone)]
+pub enum AssemblyError {
+    /// 词法错误，比如字符串缺少结束的引号、括号不配对等
+    LexicalError(/* line */ usize, /* message */ String),
+
+    /// 语法错误，比如未知的指令、数值格式不正确、找不到标识符所对应的项目等
+    SyntaxError(/* line */ usize, /* message */ String),
+
+    /// 文本合乎格式，但含有汇编器或者 VM 尚不支持的内容，比如被动的数据段
+    UnsupportedError(/* line */ usize, /* message */ String),
+}
+
+impl Display for AssemblyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AssemblyError::LexicalError(line, message) => {
+                write!(f, "lexical error at line {}: {}", line, message)
+            }
+            AssemblyError::SyntaxError(line, message) => {
+                write!(f, "syntax error at line {}: {}", line, message)
+            }
+            AssemblyError::UnsupportedError(line, message) => {
+                write!(f, "unsupported content at line {}: {}", line, message)
+            }
+        }
+    }
+}
@@ -4,3 +4,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+pub mod assembler;
+pub mod error;
+pub mod number_literal;
+pub mod s_expression;
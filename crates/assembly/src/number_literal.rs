@@ -0,0 +1,462 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 数字字面量
+//!
+//! <https://webassembly.github.io/spec/core/text/values.html>
+//!
+//! 整数：
+//!
+//! - 十进制，比如 `123`、`-45`、`1_000_000`；
+//! - 十六进制，比如 `0xff`、`-0x80`、`0xdead_beef`。
+//!
+//! 整数指令的立即数既可以写成有符号数，也可以写成无符号数，比如 `i32.const 0xffffffff`
+//! 和 `i32.const -1` 是相同的。
+//!
+//! 浮点数：
+//!
+//! - 十进制，比如 `1.5`、`-0.25`、`1e10`、`6.02E+23`；
+//! - 十六进制，比如 `0x1p-1`、`0x1.8p3`、`-0x0.1`；
+//! - 无穷，`inf` 和 `-inf`；
+//! - NaN，`nan`（规范的 NaN，canonical NaN）以及 `nan:0x200000`（指定了有效数位的 NaN）。
+//!
+//! 除了十六进制的前缀 `0x`，数字之间还可以插入下划线 `_` 作为分隔符，比如 `0x1_0000`。
+//!
+//! 为了能把反汇编器（使用 Rust 的 `Display` 特性格式化浮点数）的输出重新汇编，
+//! 浮点数还接受 `NaN` 这种写法。
+
+/// f32 的有效数位（不含隐藏的最高位）的数量
+const F32_SIGNIFICAND_BITS: u32 = 23;
+/// f32 的指数位的数量
+const F32_EXPONENT_BITS: u32 = 8;
+
+/// f64 的有效数位（不含隐藏的最高位）的数量
+const F64_SIGNIFICAND_BITS: u32 = 52;
+/// f64 的指数位的数量
+const F64_EXPONENT_BITS: u32 = 11;
+
+/// 解析无符号 32 位整数，比如各种索引值、内存块和表的 limit 值等
+pub fn parse_u32(text: &str) -> Option<u32> {
+    parse_unsigned(text).and_then(|value| u32::try_from(value).ok())
+}
+
+/// 解析无符号 64 位整数，比如 64 位内存块的 limit 值以及内存参数的 offset 值
+pub fn parse_u64(text: &str) -> Option<u64> {
+    parse_unsigned(text).map(|value| value as u64)
+}
+
+/// 解析 8 位整数（比如 `v128.const i8x16 ...` 的通道值），数值的范围是 [-2^7, 2^8)，
+/// 结果为数值的补码
+pub fn parse_i8(text: &str) -> Option<u8> {
+    parse_narrow_integer(text, 8).map(|value| value as u8)
+}
+
+/// 解析 16 位整数（比如 `v128.const i16x8 ...` 的通道值），数值的范围是 [-2^15, 2^16)，
+/// 结果为数值的补码
+pub fn parse_i16(text: &str) -> Option<u16> {
+    parse_narrow_integer(text, 16).map(|value| value as u16)
+}
+
+/// 解析 i32 整数，数值的范围是 [-2^31, 2^32)，超出 i32 范围的无符号数会被转换为补码
+pub fn parse_i32(text: &str) -> Option<i32> {
+    let (is_negative, value) = parse_signed(text)?;
+    if is_negative {
+        if value <= 1 << 31 {
+            Some((value as i64).wrapping_neg() as i32)
+        } else {
+            None
+        }
+    } else {
+        u32::try_from(value).ok().map(|v| v as i32)
+    }
+}
+
+/// 解析 i64 整数，数值的范围是 [-2^63, 2^64)，超出 i64 范围的无符号数会被转换为补码
+pub fn parse_i64(text: &str) -> Option<i64> {
+    let (is_negative, value) = parse_signed(text)?;
+    if is_negative {
+        if value <= 1 << 63 {
+            Some((value as i128).wrapping_neg() as i64)
+        } else {
+            None
+        }
+    } else {
+        u64::try_from(value).ok().map(|v| v as i64)
+    }
+}
+
+pub fn parse_f32(text: &str) -> Option<f32> {
+    let (is_negative, body) = split_sign(text);
+
+    let value = if let Some(digits) = body.strip_prefix("0x") {
+        let bits = parse_hex_float(digits, F32_SIGNIFICAND_BITS, F32_EXPONENT_BITS)?;
+        f32::from_bits(bits as u32)
+    } else if let Some(bits) = parse_special_float(body, F32_SIGNIFICAND_BITS, F32_EXPONENT_BITS) {
+        f32::from_bits(bits as u32)
+    } else {
+        let value = remove_underscores(body, is_decimal_float_char)?
+            .parse::<f32>()
+            .ok()?;
+
+        // 十进制数值超出 f32 的范围
+        if value.is_infinite() {
+            return None;
+        }
+        value
+    };
+
+    Some(if is_negative { -value } else { value })
+}
+
+pub fn parse_f64(text: &str) -> Option<f64> {
+    let (is_negative, body) = split_sign(text);
+
+    let value = if let Some(digits) = body.strip_prefix("0x") {
+        let bits = parse_hex_float(digits, F64_SIGNIFICAND_BITS, F64_EXPONENT_BITS)?;
+        f64::from_bits(bits)
+    } else if let Some(bits) = parse_special_float(body, F64_SIGNIFICAND_BITS, F64_EXPONENT_BITS) {
+        f64::from_bits(bits)
+    } else {
+        let value = remove_underscores(body, is_decimal_float_char)?
+            .parse::<f64>()
+            .ok()?;
+
+        // 十进制数值超出 f64 的范围
+        if value.is_infinite() {
+            return None;
+        }
+        value
+    };
+
+    Some(if is_negative { -value } else { value })
+}
+
+/// 解析不超过 32 位的整数，返回数值的补码（只有低 `bits` 位有效）
+fn parse_narrow_integer(text: &str, bits: u32) -> Option<u32> {
+    let (is_negative, value) = parse_signed(text)?;
+    if is_negative {
+        if value <= 1 << (bits - 1) {
+            Some((value as u32).wrapping_neg())
+        } else {
+            None
+        }
+    } else if value < 1 << bits {
+        Some(value as u32)
+    } else {
+        None
+    }
+}
+
+fn split_sign(text: &str) -> (/* is_negative */ bool, &str) {
+    if let Some(body) = text.strip_prefix('-') {
+        (true, body)
+    } else if let Some(body) = text.strip_prefix('+') {
+        (false, body)
+    } else {
+        (false, text)
+    }
+}
+
+fn parse_signed(text: &str) -> Option<(/* is_negative */ bool, u128)> {
+    let (is_negative, body) = split_sign(text);
+    parse_unsigned(body).map(|value| (is_negative, value))
+}
+
+/// 解析不带符号的整数，结果不超过 u64 的范围
+fn parse_unsigned(text: &str) -> Option<u128> {
+    let (radix, digits) = if let Some(digits) = text.strip_prefix("0x") {
+        (16, remove_underscores(digits, |c| c.is_ascii_hexdigit())?)
+    } else {
+        (10, remove_underscores(text, |c| c.is_ascii_digit())?)
+    };
+
+    let value = u128::from_str_radix(&digits, radix).ok()?;
+    if value > u64::MAX as u128 {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+/// 移除数字之间的下划线
+///
+/// 下划线只能出现在两个数字之间，另外数字必须以有效的字符开始。
+fn remove_underscores(text: &str, is_valid_char: fn(char) -> bool) -> Option<String> {
+    let chars: Vec<char> = text.chars().collect();
+
+    match chars.first() {
+        Some(c) if c.is_ascii_hexdigit() => {}
+        _ => return None,
+    }
+
+    let mut result = String::with_capacity(chars.len());
+
+    for (index, c) in chars.iter().enumerate() {
+        if *c == '_' {
+            let is_between_digits = index > 0
+                && index + 1 < chars.len()
+                && chars[index - 1].is_ascii_hexdigit()
+                && chars[index + 1].is_ascii_hexdigit();
+            if !is_between_digits {
+                return None;
+            }
+        } else if is_valid_char(*c) {
+            result.push(*c);
+        } else {
+            return None;
+        }
+    }
+
+    Some(result)
+}
+
+fn is_decimal_float_char(c: char) -> bool {
+    c.is_ascii_digit() || c == '.' || c == 'e' || c == 'E' || c == '+' || c == '-'
+}
+
+/// 解析 `inf`、`nan` 以及 `nan:0x...`，返回浮点数（不含符号位）的二进制位
+fn parse_special_float(text: &str, significand_bits: u32, exponent_bits: u32) -> Option<u64> {
+    let exponent_mask = ((1u64 << exponent_bits) - 1) << significand_bits;
+
+    match text {
+        "inf" => Some(exponent_mask),
+        "nan" | "NaN" => Some(exponent_mask | (1u64 << (significand_bits - 1))),
+        _ => {
+            let payload_text = text.strip_prefix("nan:0x")?;
+            let payload_digits = remove_underscores(payload_text, |c| c.is_ascii_hexdigit())?;
+            let payload = u64::from_str_radix(&payload_digits, 16).ok()?;
+
+            if payload == 0 || payload >= 1u64 << significand_bits {
+                None
+            } else {
+                Some(exponent_mask | payload)
+            }
+        }
+    }
+}
+
+/// 解析十六进制浮点数（不含符号以及前缀 `0x`），返回浮点数（不含符号位）的二进制位
+///
+/// 十六进制浮点数的格式为 `hexnum(.hexfrac?)?([pP][+-]?num)?`，其中指数部分是以 2 为底的十进制数。
+///
+/// 先把所有十六进制数字累积为一个整数 m，数值即为 m * 2^e，然后按照
+/// "向最近的偶数舍入" 的规则把 m 截取为目标类型的有效数位。
+fn parse_hex_float(text: &str, significand_bits: u32, exponent_bits: u32) -> Option<u64> {
+    let (mantissa_text, exponent_text) = match text.find(['p', 'P']) {
+        Some(position) => (&text[..position], Some(&text[position + 1..])),
+        None => (text, None),
+    };
+
+    let (integer_text, fraction_text) = match mantissa_text.find('.') {
+        Some(position) => (&mantissa_text[..position], &mantissa_text[position + 1..]),
+        None => (mantissa_text, ""),
+    };
+
+    let integer_digits = remove_underscores(integer_text, |c| c.is_ascii_hexdigit())?;
+    let fraction_digits = if fraction_text.is_empty() {
+        String::new()
+    } else {
+        remove_underscores(fraction_text, |c| c.is_ascii_hexdigit())?
+    };
+
+    let mut exponent: i64 = match exponent_text {
+        Some(exponent_text) => {
+            let (is_negative, body) = split_sign(exponent_text);
+            let digits = remove_underscores(body, |c| c.is_ascii_digit())?;
+
+            // 指数的绝对值过大时，结果必定是 0 或者溢出，这里限制其范围以避免整数溢出
+            let value = digits.parse::<i64>().unwrap_or(i64::MAX).min(1 << 20);
+            if is_negative {
+                -value
+            } else {
+                value
+            }
+        }
+        None => 0,
+    };
+
+    // 累积有效数字，当超出 u128 能容纳的范围时，
+    // 剩余的数字只记录其是否为 0（即 sticky 位），用于舍入。
+    let mut mantissa: u128 = 0;
+    let mut is_sticky = false;
+
+    for c in integer_digits.chars() {
+        let digit = c.to_digit(16).unwrap() as u128;
+        if mantissa >> 120 == 0 {
+            mantissa = (mantissa << 4) | digit;
+        } else {
+            exponent += 4;
+            is_sticky |= digit != 0;
+        }
+    }
+
+    for c in fraction_digits.chars() {
+        let digit = c.to_digit(16).unwrap() as u128;
+        if mantissa >> 120 == 0 {
+            mantissa = (mantissa << 4) | digit;
+            exponent -= 4;
+        } else {
+            is_sticky |= digit != 0;
+        }
+    }
+
+    if is_sticky {
+        mantissa = (mantissa << 1) | 1;
+        exponent -= 1;
+    }
+
+    if mantissa == 0 {
+        return Some(0);
+    }
+
+    let bias = (1i64 << (exponent_bits - 1)) - 1;
+    let min_exponent = 1 - bias;
+    let max_biased_exponent = (1i64 << exponent_bits) - 1;
+
+    // 最高位 `1` 的指数
+    let top_bit_position = 127 - mantissa.leading_zeros() as i64;
+    let top_exponent = top_bit_position + exponent;
+
+    // 保留下来的最低位的指数，对于非规格化数（subnormal），最低位的指数是固定的
+    let is_normal = top_exponent >= min_exponent;
+    let lowest_exponent = if is_normal {
+        top_exponent - significand_bits as i64
+    } else {
+        min_exponent - significand_bits as i64
+    };
+
+    let shift = lowest_exponent - exponent;
+    let mut significand: u128 = if shift <= 0 {
+        mantissa << (-shift)
+    } else if shift >= 128 {
+        0
+    } else {
+        let quotient = mantissa >> shift;
+        let remainder = mantissa & ((1u128 << shift) - 1);
+        let half = 1u128 << (shift - 1);
+        if remainder > half || (remainder == half && quotient & 1 == 1) {
+            quotient + 1
+        } else {
+            quotient
+        }
+    };
+
+    if significand == 0 {
+        return Some(0);
+    }
+
+    let biased_exponent = if is_normal {
+        let mut top_exponent = top_exponent;
+        if significand == 1u128 << (significand_bits + 1) {
+            // 舍入导致进位
+            significand >>= 1;
+            top_exponent += 1;
+        }
+        top_exponent + bias
+    } else if significand >= 1u128 << significand_bits {
+        // 舍入之后变成了规格化数
+        1
+    } else {
+        0
+    };
+
+    if biased_exponent >= max_biased_exponent {
+        // 超出浮点数的范围
+        return None;
+    }
+
+    let significand_mask = (1u64 << significand_bits) - 1;
+    Some(((biased_exponent as u64) << significand_bits) | (significand as u64 & significand_mask))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        parse_f32, parse_f64, parse_i16, parse_i32, parse_i64, parse_i8, parse_u32, parse_u64,
+    };
+
+    #[test]
+    fn test_parse_integer() {
+        assert_eq!(parse_u32("0"), Some(0));
+        assert_eq!(parse_u32("1_000"), Some(1000));
+        assert_eq!(parse_u32("0xff"), Some(255));
+        assert_eq!(parse_u32("0xffff_ffff"), Some(u32::MAX));
+        assert_eq!(parse_u32("0x1_0000_0000"), None);
+        assert_eq!(parse_u32("-1"), None);
+        assert_eq!(parse_u32("1__0"), None);
+        assert_eq!(parse_u32("_1"), None);
+        assert_eq!(parse_u32("$a"), None);
+
+        assert_eq!(parse_u64("0x1_0000_0000"), Some(1 << 32));
+        assert_eq!(parse_u64("0xffff_ffff_ffff_ffff"), Some(u64::MAX));
+        assert_eq!(parse_u64("0x1_0000_0000_0000_0000"), None);
+
+        assert_eq!(parse_i32("-1"), Some(-1));
+        assert_eq!(parse_i32("+7"), Some(7));
+        assert_eq!(parse_i32("0xffffffff"), Some(-1));
+        assert_eq!(parse_i32("-0x80000000"), Some(i32::MIN));
+        assert_eq!(parse_i32("4294967296"), None);
+        assert_eq!(parse_i32("-2147483649"), None);
+
+        assert_eq!(parse_i8("-128"), Some(0x80));
+        assert_eq!(parse_i8("255"), Some(0xff));
+        assert_eq!(parse_i8("256"), None);
+        assert_eq!(parse_i8("-129"), None);
+        assert_eq!(parse_i16("-1"), Some(0xffff));
+        assert_eq!(parse_i16("0x1_0000"), None);
+        assert_eq!(parse_i16("0xffffffff"), None);
+
+        assert_eq!(parse_i64("-9223372036854775808"), Some(i64::MIN));
+        assert_eq!(parse_i64("0xffffffffffffffff"), Some(-1));
+        assert_eq!(parse_i64("18446744073709551616"), None);
+    }
+
+    #[test]
+    fn test_parse_float() {
+        assert_eq!(parse_f32("1.5"), Some(1.5));
+        assert_eq!(parse_f32("-0.25"), Some(-0.25));
+        assert_eq!(parse_f32("1e3"), Some(1000.0));
+        assert_eq!(parse_f32("1_000.5"), Some(1000.5));
+        assert_eq!(parse_f32("3"), Some(3.0));
+        assert_eq!(parse_f32("1e39"), None);
+        assert_eq!(parse_f32(".5"), None);
+
+        assert_eq!(parse_f32("0x1p-1"), Some(0.5));
+        assert_eq!(parse_f32("0x1.8p3"), Some(12.0));
+        assert_eq!(parse_f32("-0x10"), Some(-16.0));
+        assert_eq!(parse_f32("0x1p-149"), Some(f32::from_bits(1)));
+        assert_eq!(parse_f32("0x1.fffffep127"), Some(f32::MAX));
+        assert_eq!(parse_f32("0x1p128"), None);
+
+        // 舍入
+        assert_eq!(parse_f32("0x1.000001p0"), Some(1.0));
+        assert_eq!(parse_f32("0x1.000003p0"), Some(f32::from_bits(0x3f80_0002)));
+        assert_eq!(parse_f64("0x1.00000000000008p0"), Some(1.0));
+        assert_eq!(
+            parse_f64("0x1.00000000000018p0"),
+            Some(f64::from_bits(0x3ff0_0000_0000_0002))
+        );
+
+        assert_eq!(parse_f32("inf"), Some(f32::INFINITY));
+        assert_eq!(parse_f32("-inf"), Some(f32::NEG_INFINITY));
+        assert_eq!(parse_f32("nan").map(|v| v.to_bits()), Some(0x7fc0_0000));
+        assert_eq!(parse_f32("NaN").map(|v| v.to_bits()), Some(0x7fc0_0000));
+        assert_eq!(parse_f32("-nan").map(|v| v.to_bits()), Some(0xffc0_0000));
+        assert_eq!(parse_f32("nan:0x1").map(|v| v.to_bits()), Some(0x7f80_0001));
+        assert_eq!(parse_f32("nan:0x0"), None);
+        assert_eq!(parse_f32("nan:0x800000"), None);
+
+        assert_eq!(parse_f64("0.1"), Some(0.1));
+        assert_eq!(
+            parse_f64("-0x1.921fb54442d18p1"),
+            Some(-std::f64::consts::PI)
+        );
+        assert_eq!(
+            parse_f64("nan:0x4_0000_0000_0000").map(|v| v.to_bits()),
+            Some(0x7ff4_0000_0000_0000)
+        );
+    }
+}
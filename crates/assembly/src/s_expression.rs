@@ -0,0 +1,363 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # S-Expression 读取器
+//!
+//! WebAssembly 文本格式以及测试脚本（`.wast`）都是以 S-Expression 书写的，
+//! 读取器只负责把源码转换为列表、符号和字符串三种节点，不理解节点的含义。
+//!
+//! - 列表：`(` 和 `)` 包围的零个或多个节点；
+//! - 符号：关键字、标识符（`$name`）、数字等，即连续的非空白、非括号、非引号字符；
+//! - 字符串：`"` 包围的文本，支持 `\t`、`\n`、`\r`、`\"`、`\'`、`\\`、`\hh`（两位十六进制数）
+//!   以及 `\u{hhhh}` 等转义，字符串的内容是字节序列，不一定是有效的 UTF-8 文本。
+//!
+//! 注释有两种，行注释 `;; ...` 以及块注释 `(; ... ;)`，块注释可以嵌套。
+
+use crate::error::AssemblyError;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum SExpression {
+    List(Vec<SExpression>, /* line */ usize),
+    Symbol(String, /* line */ usize),
+    Text(Vec<u8>, /* line */ usize),
+}
+
+impl SExpression {
+    pub fn get_line(&self) -> usize {
+        match self {
+            SExpression::List(_, line) => *line,
+            SExpression::Symbol(_, line) => *line,
+            SExpression::Text(_, line) => *line,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[SExpression]> {
+        match self {
+            SExpression::List(items, _) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn as_symbol(&self) -> Option<&str> {
+        match self {
+            SExpression::Symbol(symbol, _) => Some(symbol),
+            _ => None,
+        }
+    }
+
+    pub fn as_text(&self) -> Option<&[u8]> {
+        match self {
+            SExpression::Text(bytes, _) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// 获取列表的第一个符号，比如 `(module ...)` 的 `module`
+    pub fn get_keyword(&self) -> Option<&str> {
+        self.as_list()
+            .and_then(|items| items.first())
+            .and_then(|item| item.as_symbol())
+    }
+}
+
+pub fn parse(source: &str) -> Result<Vec<SExpression>, AssemblyError> {
+    let mut reader = Reader {
+        chars: source.chars().collect(),
+        position: 0,
+        line: 1,
+    };
+
+    let mut expressions: Vec<SExpression> = vec![];
+
+    loop {
+        reader.skip_whitespaces_and_comments()?;
+        match reader.peek() {
+            None => break,
+            Some(')') => {
+                return Err(AssemblyError::LexicalError(
+                    reader.line,
+                    "unexpected \")\"".to_string(),
+                ))
+            }
+            Some(_) => expressions.push(reader.read_expression()?),
+        }
+    }
+
+    Ok(expressions)
+}
+
+struct Reader {
+    chars: Vec<char>,
+    position: usize,
+    line: usize,
+}
+
+impl Reader {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn peek_next(&self) -> Option<char> {
+        self.chars.get(self.position + 1).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let option_char = self.peek();
+        if let Some(c) = option_char {
+            self.position += 1;
+            if c == '\n' {
+                self.line += 1;
+            }
+        }
+        option_char
+    }
+
+    fn skip_whitespaces_and_comments(&mut self) -> Result<(), AssemblyError> {
+        loop {
+            match (self.peek(), self.peek_next()) {
+                (Some(c), _) if c.is_whitespace() => {
+                    self.next();
+                }
+                (Some(';'), Some(';')) => {
+                    // 行注释
+                    while let Some(c) = self.next() {
+                        if c == '\n' {
+                            break;
+                        }
+                    }
+                }
+                (Some('('), Some(';')) => self.skip_block_comment()?,
+                _ => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn skip_block_comment(&mut self) -> Result<(), AssemblyError> {
+        let start_line = self.line;
+
+        // 跳过 `(;`
+        self.next();
+        self.next();
+
+        let mut depth = 1;
+        while depth > 0 {
+            match (self.next(), self.peek()) {
+                (Some('('), Some(';')) => {
+                    self.next();
+                    depth += 1;
+                }
+                (Some(';'), Some(')')) => {
+                    self.next();
+                    depth -= 1;
+                }
+                (Some(_), _) => {}
+                (None, _) => {
+                    return Err(AssemblyError::LexicalError(
+                        start_line,
+                        "unterminated block comment".to_string(),
+                    ))
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read_expression(&mut self) -> Result<SExpression, AssemblyError> {
+        match self.peek() {
+            Some('(') => self.read_list(),
+            Some('"') => self.read_text(),
+            _ => self.read_symbol(),
+        }
+    }
+
+    fn read_list(&mut self) -> Result<SExpression, AssemblyError> {
+        let start_line = self.line;
+
+        // 跳过 `(`
+        self.next();
+
+        let mut items: Vec<SExpression> = vec![];
+
+        loop {
+            self.skip_whitespaces_and_comments()?;
+            match self.peek() {
+                Some(')') => {
+                    self.next();
+                    break;
+                }
+                Some(_) => items.push(self.read_expression()?),
+                None => {
+                    return Err(AssemblyError::LexicalError(
+                        start_line,
+                        "missing \")\"".to_string(),
+                    ))
+                }
+            }
+        }
+
+        Ok(SExpression::List(items, start_line))
+    }
+
+    fn read_symbol(&mut self) -> Result<SExpression, AssemblyError> {
+        let start_line = self.line;
+        let mut symbol = String::new();
+
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || c == '(' || c == ')' || c == '"' || c == ';' {
+                break;
+            }
+            symbol.push(c);
+            self.next();
+        }
+
+        if symbol.is_empty() {
+            return Err(AssemblyError::LexicalError(
+                start_line,
+                format!("unexpected char \"{}\"", self.peek().unwrap_or(' ')),
+            ));
+        }
+
+        Ok(SExpression::Symbol(symbol, start_line))
+    }
+
+    fn read_text(&mut self) -> Result<SExpression, AssemblyError> {
+        let start_line = self.line;
+
+        // 跳过 `"`
+        self.next();
+
+        let mut bytes: Vec<u8> = vec![];
+
+        loop {
+            match self.next() {
+                Some('"') => break,
+                Some('\\') => self.read_escape(&mut bytes)?,
+                Some(c) => {
+                    let mut buffer = [0u8; 4];
+                    bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+                }
+                None => {
+                    return Err(AssemblyError::LexicalError(
+                        start_line,
+                        "unterminated string".to_string(),
+                    ))
+                }
+            }
+        }
+
+        Ok(SExpression::Text(bytes, start_line))
+    }
+
+    fn read_escape(&mut self, bytes: &mut Vec<u8>) -> Result<(), AssemblyError> {
+        let line = self.line;
+        let invalid_escape = || AssemblyError::LexicalError(line, "invalid escape".to_string());
+
+        match self.next() {
+            Some('t') => bytes.push(b'\t'),
+            Some('n') => bytes.push(b'\n'),
+            Some('r') => bytes.push(b'\r'),
+            Some('"') => bytes.push(b'"'),
+            Some('\'') => bytes.push(b'\''),
+            Some('\\') => bytes.push(b'\\'),
+            Some('u') => {
+                if self.next() != Some('{') {
+                    return Err(invalid_escape());
+                }
+
+                let mut hex = String::new();
+                loop {
+                    match self.next() {
+                        Some('}') => break,
+                        Some(c) if c.is_ascii_hexdigit() || c == '_' => {
+                            if c != '_' {
+                                hex.push(c)
+                            }
+                        }
+                        _ => return Err(invalid_escape()),
+                    }
+                }
+
+                let c = u32::from_str_radix(&hex, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(invalid_escape)?;
+
+                let mut buffer = [0u8; 4];
+                bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+            }
+            Some(high) if high.is_ascii_hexdigit() => match self.next() {
+                Some(low) if low.is_ascii_hexdigit() => {
+                    let value = (high.to_digit(16).unwrap() << 4) | low.to_digit(16).unwrap();
+                    bytes.push(value as u8);
+                }
+                _ => return Err(invalid_escape()),
+            },
+            _ => return Err(invalid_escape()),
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{parse, SExpression};
+    use crate::error::AssemblyError;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse("(module $m (func))").unwrap(),
+            vec![SExpression::List(
+                vec![
+                    SExpression::Symbol("module".to_string(), 1),
+                    SExpression::Symbol("$m".to_string(), 1),
+                    SExpression::List(vec![SExpression::Symbol("func".to_string(), 1)], 1)
+                ],
+                1
+            )]
+        );
+
+        assert_eq!(
+            parse(";; comment\n(; block (; nested ;) ;)\n(a)\n(b)").unwrap(),
+            vec![
+                SExpression::List(vec![SExpression::Symbol("a".to_string(), 3)], 3),
+                SExpression::List(vec![SExpression::Symbol("b".to_string(), 4)], 4)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_text() {
+        assert_eq!(
+            parse(r#""\00asm\t\"\u{4e2d}""#).unwrap(),
+            vec![SExpression::Text(
+                vec![0x00, b'a', b's', b'm', b'\t', b'"', 0xe4, 0xb8, 0xad],
+                1
+            )]
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            parse("(a\n(b)"),
+            Err(AssemblyError::LexicalError(1, "missing \")\"".to_string()))
+        );
+        assert_eq!(
+            parse("\n\"abc"),
+            Err(AssemblyError::LexicalError(
+                2,
+                "unterminated string".to_string()
+            ))
+        );
+    }
+}
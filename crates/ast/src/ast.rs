@@ -4,7 +4,10 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use crate::{instruction::Instruction, types::ValueType};
+use crate::{
+    instruction::Instruction,
+    types::{RefType, ValueType},
+};
 
 /// # 模块
 ///
@@ -77,6 +80,10 @@ pub struct Module {
     /// 目前只支持声明或导入 1 项内存块
     pub memory_blocks: Vec<MemoryType>,
 
+    /// 异常标签列表，（section id 13，由异常处理提案引入）
+    /// 在二进制格式里，标签段位于内存段和全局段之间
+    pub tag_items: Vec<TagType>,
+
     /// 全局变量列表，（section id 6）
     pub global_items: Vec<GlobalItem>,
 
@@ -98,7 +105,12 @@ pub struct Module {
     /// 内存的初始化数据，（section id 11）
     pub data_items: Vec<DataItem>,
 
-    // pub data_count: u32,
+    /// 数据项的数量，（section id 12）
+    ///
+    /// 该段是可选的，只有当函数里含有 `memory.init` 或者 `data.drop` 指令时才是必须的，
+    /// 但有些编译器（比如启用了 bulk memory 特性的 LLVM）总是会输出该段。
+    /// 记录该段是否存在，以便编码器能输出跟原模块一致的二进制数据。
+    pub data_count: Option<u32>,
 }
 
 /// # 自定义项
@@ -126,9 +138,66 @@ pub struct Module {
 #[derive(Debug, PartialEq, Clone)]
 pub enum CustomItem {
     NameCollections(Vec<NameCollection>),
+    DynamicLinking(DynamicLinking),
     Other(String, Vec<u8>), // params: (name, data)
 }
 
+/// # 动态链接信息
+///
+/// 即名称为 `dylink.0` 的自定义段，由共享库（使用 `-shared` 参数编译的模块）
+/// 以及位置无关的主模块（使用 `-pie` 参数编译的模块）携带，用于说明模块所需的
+/// 内存空间和表空间，以及模块所依赖的其他共享库。
+///
+/// 段的内容由若干子段组成：
+///
+/// dylink_section = subsection{*}
+/// subsection = subsection_type:u8 + content_length:u32 + content
+///
+/// https://github.com/WebAssembly/tool-conventions/blob/main/DynamicLinking.md
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct DynamicLinking {
+    /// 子段类型 1（WASM_DYLINK_MEM_INFO）
+    pub memory_info: Option<DynamicLinkingMemoryInfo>,
+
+    /// 子段类型 2（WASM_DYLINK_NEEDED），所依赖的共享库的名称列表
+    pub needed: Vec<String>,
+
+    /// 子段类型 3（WASM_DYLINK_EXPORT_INFO），导出符号的附加信息
+    pub export_infos: Vec<DynamicLinkingExportInfo>,
+
+    /// 子段类型 4（WASM_DYLINK_IMPORT_INFO），导入符号的附加信息
+    pub import_infos: Vec<DynamicLinkingImportInfo>,
+
+    /// 子段类型 5（WASM_DYLINK_RUNTIME_PATH），共享库的搜索路径列表
+    pub runtime_paths: Vec<String>,
+}
+
+/// 模块所需的内存空间和表空间
+///
+/// 对齐值以 2 的幂表示，比如 memory_alignment 为 2 表示按 4 字节对齐。
+#[derive(Debug, PartialEq, Clone)]
+pub struct DynamicLinkingMemoryInfo {
+    pub memory_size: u32,
+    pub memory_alignment: u32,
+    pub table_size: u32,
+    pub table_alignment: u32,
+}
+
+/// 符号的标志位（flags）同 `linking` 段的符号标志，比如 0x1 表示弱符号（WASM_SYMBOL_BINDING_WEAK），
+/// 0x100 表示线程局部存储符号（WASM_SYMBOL_TLS）
+#[derive(Debug, PartialEq, Clone)]
+pub struct DynamicLinkingExportInfo {
+    pub name: String,
+    pub flags: u32,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct DynamicLinkingImportInfo {
+    pub module_name: String,
+    pub item_name: String,
+    pub flags: u32,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum NameCollection {
     TypeNames(Vec<IndexNamePair>),
@@ -140,6 +209,7 @@ pub enum NameCollection {
     TableNames(Vec<IndexNamePair>),
     ElementNames(Vec<IndexNamePair>),
     DataNames(Vec<IndexNamePair>),
+    TagNames(Vec<IndexNamePair>),
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -259,6 +329,7 @@ pub enum ImportDescriptor {
     TableType(TableType),   // 导入表
     MemoryType(MemoryType), // 导入内存块
     GlobalType(GlobalType), // 导入全局变量
+    TagType(TagType),       // 导入异常标签
 }
 
 /// # 补充 A：函数（列表）段
@@ -312,8 +383,8 @@ pub enum ImportDescriptor {
 ///
 /// # 表段
 ///
-/// `表` 用于存储 `元素项`，目前 `元素项` 只支持函数引用（函数索引），
-/// `表` 和 `元素项` 用于列出一组函数的索引，然后在执行 `call_indirect` 指令时，
+/// `表` 用于存储引用类型的值（函数引用 `funcref` 或者宿主引用 `externref`），
+/// 对于函数引用表，`表` 和 `元素项` 用于列出一组函数的索引，然后在执行 `call_indirect` 指令时，
 /// 根据栈顶的操作数获取该列表中的一个函数，从而实现 `动态` 选择被调用的函数。
 ///
 /// `动态函数调用` 相当于高级语言里的 `函数指针`（或者数据类型为 `函数` 的参数）
@@ -321,9 +392,9 @@ pub enum ImportDescriptor {
 /// ## 二进制格式
 ///
 /// table_section = 0x04 + content_length:u32 + <table_type> // 目前一个模块仅支持声明一个表项
-/// table_type = 0x70 + limits
-///              ^
-///              |--- 0x70 表示该表项存储的是 funcref
+/// table_type = ref_type:byte + limits
+///               ^
+///               |--- 0x70 表示该表项存储的是 funcref，0x6F 表示 externref
 ///
 /// 表项仅用于说明表的容量，真正的内容（即函数索引列表）被存储在元素项里，
 /// 元素段存储的是表的初始化数据。
@@ -332,7 +403,7 @@ pub enum ImportDescriptor {
 ///
 /// (func $f1)
 /// (func $f2)
-/// (table 1 10 funcref)                    ;; 表的类型暂时只能是 `funcref`
+/// (table 1 10 funcref)                    ;; 表的类型可以是 `funcref` 或者 `externref`
 /// (elem (offset (i32.const 1)) $f1 $f2)   ;; 元素项的偏移值需要使用 `const`表达式
 ///
 /// 元素项也可以内联到表段里：
@@ -343,6 +414,7 @@ pub enum ImportDescriptor {
 ///
 #[derive(Debug, PartialEq, Clone)]
 pub struct TableType {
+    pub element_type: RefType,
     pub limit: Limit,
 }
 
@@ -361,6 +433,13 @@ pub struct TableType {
 /// 当 tag == 0 时，表示省略了上限，只有 min 值
 /// 当 tag == 1 时，表示同时指出了 min 值和 max 值
 ///
+/// 对于内存块，tag 的第 2 位（即 0x02）表示共享内存块，即 tag 的值为 2 或者 3，
+/// tag 的第 3 位（即 0x04）表示 64 位内存块，此时 min 和 max 都是 u64，
+/// 见 `MemoryType`。
+///
+/// 因为 64 位内存块（由 memory64 提案引入）的页面数有可能超出 u32 的范围，
+/// 所以 Limit 使用 u64 储存 min 和 max 值。
+///
 /// 示例：
 ///
 /// 00 01       ;; tag == 0，min 值为 1，省略了 max 值（所以 max 值对应的字节序列也不会有）
@@ -368,12 +447,12 @@ pub struct TableType {
 ///
 #[derive(Debug, PartialEq, Clone)]
 pub enum Limit {
-    Range(u32, u32),
-    AtLeast(u32),
+    Range(u64, u64),
+    AtLeast(u64),
 }
 
 impl Limit {
-    pub fn new_by_min(min: u32) -> Self {
+    pub fn new_by_min(min: u64) -> Self {
         Limit::AtLeast(min)
     }
 
@@ -381,11 +460,11 @@ impl Limit {
     ///
     /// 跟常见程序语言里的 range 不一样，min 和 max 的值都是 `包括的`（`included`），
     /// 比如 Range(0, 10) 表示从 0 到 10 一共 11 个数字，因为数字 `10` 是 `包括的`。
-    pub fn new_by_range(min: u32, max: u32) -> Self {
+    pub fn new_by_range(min: u64, max: u64) -> Self {
         Limit::Range(min, max)
     }
 
-    pub fn get_min(&self) -> u32 {
+    pub fn get_min(&self) -> u64 {
         match *self {
             Limit::AtLeast(min) => min,
             Limit::Range(min, _) => min,
@@ -406,9 +485,17 @@ impl Limit {
 /// memory_section = 0x05 + content_length:u32 + <memory_type> // 目前一个模块仅支持声明一个内存块
 /// memory_type = limits
 ///
+/// limits 的 tag 为 2 或者 3 时表示共享内存块（`shared memory`），
+/// 共享内存块可以被多个线程同时访问，它必须指定 max 值。
+///
+/// limits 的 tag 的第 3 位（0x04）表示 64 位内存块（由 memory64 提案引入），
+/// 64 位内存块使用 i64 作为内存地址，所以容量可以超过 4GiB。
+///
 /// ## 文本格式
 ///
 /// (memory 1 16)                           ;; 指定 limit 值，即 min 和 max
+/// (memory 1 16 shared)                    ;; 共享内存块
+/// (memory i64 1 16)                       ;; 64 位内存块
 /// (data (offset (i32.const 10)) "foo")    ;; 数据偏移量需要使用 `const` 表达式
 /// (data (offset (i32.const 20)) "bar")
 ///
@@ -426,6 +513,56 @@ impl Limit {
 #[derive(Debug, PartialEq, Clone)]
 pub struct MemoryType {
     pub limit: Limit,
+
+    /// 是否共享内存块（由线程提案引入）
+    pub shared: bool,
+
+    /// 是否 64 位内存块（由 memory64 提案引入）
+    pub memory64: bool,
+}
+
+impl MemoryType {
+    /// 内存地址的数据类型，32 位内存块为 i32，64 位内存块为 i64
+    ///
+    /// 内存读写指令的地址、`memory.size` 和 `memory.grow` 的页面数以及
+    /// 数据段的偏移值都使用这个类型。
+    pub fn get_address_type(&self) -> ValueType {
+        if self.memory64 {
+            ValueType::I64
+        } else {
+            ValueType::I32
+        }
+    }
+}
+
+/// # 异常标签
+///
+/// 标签段里所存储的数据（由异常处理提案引入）
+///
+/// 异常标签用于区分不同种类的异常，标签的类型是一个函数类型，其中参数表示
+/// 异常所携带的数据（即 `throw` 指令从操作数栈弹出的数值），返回值必须为空。
+///
+/// # 标签段
+///
+/// ## 二进制格式
+///
+/// tag_section = 0x0d + content_length:u32 + <tag_type>
+/// tag_type = attribute:byte + type_index:u32
+///
+/// attribute 目前只能是 0，表示异常。
+///
+/// ## 文本格式
+///
+/// (tag $e0 (param i32))                   ;; 定义标签
+/// (import "env" "e1" (tag $e1 (param i64))) ;; 导入标签
+/// (export "e0" (tag $e0))                 ;; 导出标签
+/// (func
+///     (throw $e0 (i32.const 1))
+/// )
+///
+#[derive(Debug, PartialEq, Clone)]
+pub struct TagType {
+    pub type_index: u32,
 }
 
 /// # 全局变量项
@@ -517,6 +654,7 @@ pub enum ExportDescriptor {
     TableIndex(u32),       // 导出表，参数为表的索引
     MemoryBlockIndex(u32), // 导出内存块，参数为内存块索引
     GlobalItemIndex(u32),  // 导出全局变量，参数为全局变量项目的索引
+    TagIndex(u32),         // 导出异常标签，参数为标签的索引
 }
 
 /// # 补充 B：起始函数索引段
@@ -536,7 +674,12 @@ pub enum ExportDescriptor {
 
 /// # 元素项
 ///
-/// 元素项的内容目前只能是函数的索引。
+/// 元素项的内容是一组引用类型的值，可以是函数的索引，也可以是求值结果为引用的常量表达式。
+///
+/// 元素项有 3 种模式：
+/// - 主动（active）：实例化模块时，元素被复制到指定的表的指定位置，之后该元素项被丢弃；
+/// - 被动（passive）：元素项不会自动复制到表，需要使用 `table.init` 指令复制；
+/// - 声明（declarative）：只用于声明 `ref.func` 指令可以引用的函数，实例化之后即被丢弃。
 ///
 /// # 元素段
 ///
@@ -545,17 +688,30 @@ pub enum ExportDescriptor {
 /// ## 二进制格式
 ///
 /// element_section = 0x09 + content_length:u32 + <element_item>
-/// element_item = table_index:u32 + offset_expression + <function_index>
+/// element_item = flags:u32 + ...
+///
+/// flags 的第 0 位表示被动或者声明模式（否则为主动模式），第 1 位表示（主动模式下）
+/// 有显式的表索引，或者（非主动模式下）为声明模式，第 2 位表示元素使用表达式列表表示，
+/// 所以一共有 8 种格式：
+///
+/// 0: offset_expression + <function_index>                                     ;; 主动，表 0，funcref
+/// 1: elem_kind:byte + <function_index>                                        ;; 被动
+/// 2: table_index:u32 + offset_expression + elem_kind:byte + <function_index>  ;; 主动
+/// 3: elem_kind:byte + <function_index>                                        ;; 声明
+/// 4: offset_expression + <expression>                                         ;; 主动，表 0，funcref
+/// 5: ref_type:byte + <expression>                                             ;; 被动
+/// 6: table_index:u32 + offset_expression + ref_type:byte + <expression>       ;; 主动
+/// 7: ref_type:byte + <expression>                                             ;; 声明
+///
+/// elem_kind 目前只能是 0x00，表示 funcref。
 /// offset_expression = byte{*} + 0x0B  // 表达式（指令列表）以 0x0B 结尾
 ///
-/// 元素段里的每个项目的内容由 3 部分组成：
-/// 1. 表的索引，因为目前一个模块只支持一张表，所以它的值恒等于 0；
-/// 2. 表内偏移量，是一个常量表达式；
-/// 3. 函数索引值列表
-///
 /// ## 文本格式
 ///
-/// (elem (offset (i32.const 1)) $f1 $f2)   ;; 元素项的偏移值需要使用（const）表达式
+/// (elem (offset (i32.const 1)) $f1 $f2)                   ;; 元素项的偏移值需要使用（const）表达式
+/// (elem (table $t) (offset (i32.const 1)) func $f1 $f2)   ;; 指定表
+/// (elem funcref (ref.func $f1) (ref.null func))           ;; 被动元素项，元素使用表达式表示
+/// (elem declare func $f1 $f2)                             ;; 声明元素项
 ///
 /// 元素项也可以内联到表段里：
 ///
@@ -563,16 +719,52 @@ pub enum ExportDescriptor {
 ///
 #[derive(Debug, PartialEq, Clone)]
 pub struct ElementItem {
-    /// 表索引，目前 WebAssembly 标准只支持 0
-    pub table_index: u32,
+    /// 元素项的模式
+    pub mode: ElementMode,
+
+    /// 元素的类型
+    pub element_type: RefType,
+
+    /// 元素列表
+    pub items: ElementItems,
+}
 
-    /// 偏移值表达式（指令列表）
-    pub offset_instruction_items: Vec<Instruction>,
+#[derive(Debug, PartialEq, Clone)]
+pub enum ElementMode {
+    Active {
+        /// 表索引
+        table_index: u32,
+
+        /// 偏移值表达式（指令列表）
+        offset_instruction_items: Vec<Instruction>,
+    },
+    Passive,
+    Declarative,
+}
 
+#[derive(Debug, PartialEq, Clone)]
+pub enum ElementItems {
     /// 函数索引列表
-    /// function_indices 这个列表会从指定的偏移值开始，把一系列函数的索引紧密排列，
+    /// 这个列表会从指定的偏移值开始，把一系列函数的索引紧密排列，
     /// 但这一组函数之间并没有必然的关联，只是恰好排列在一起而已。
-    pub function_indices: Vec<u32>,
+    FunctionIndices(Vec<u32>),
+
+    /// 常量表达式列表，每个表达式的求值结果是一个引用，
+    /// 比如 `ref.func 1` 或者 `ref.null extern`
+    Expressions(Vec<Vec<Instruction>>),
+}
+
+impl ElementItems {
+    pub fn len(&self) -> usize {
+        match self {
+            ElementItems::FunctionIndices(function_indices) => function_indices.len(),
+            ElementItems::Expressions(expressions) => expressions.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 /// # 代码项
@@ -677,21 +869,27 @@ pub struct LocalGroup {
 ///
 /// 数据段存储着内存的初始化数据
 ///
+/// 数据项有两种模式：
+/// - 主动（active）：实例化模块时，数据被复制到指定的内存块的指定位置，之后该数据项被丢弃；
+/// - 被动（passive）：数据不会自动复制到内存块，需要使用 `memory.init` 指令复制。
+///
 /// # 数据段
 ///
 /// ## 二进制格式
 ///
 /// data_section = 0x0b + content_length:u32 + <data_item>
-/// data_item = memory_block_index:u32 + offset_expression + data:byte{*}
-/// offset_expression = = byte{*} + 0x0B  // 表达式（指令列表）以 0x0B 结尾
+/// data_item = flags:u32 + ...
+///
+/// flags 一共有 3 种取值：
 ///
-/// 数据段每一项由 3 部分组成：
-/// 1. 内存块索引，因为目前一个模块只能有一块内存，所以这个值恒等于 0
-/// 2. 内存偏移值，一个常量表达式
-/// 3. 初始数据
+/// 0: offset_expression + data:byte{*}                           ;; 主动，内存块 0
+/// 1: data:byte{*}                                               ;; 被动
+/// 2: memory_block_index:u32 + offset_expression + data:byte{*}  ;; 主动
+///
+/// offset_expression = byte{*} + 0x0B  // 表达式（指令列表）以 0x0B 结尾
 ///
 /// 数据项示例：
-/// - 00                ;; 内存块索引
+/// - 00                ;; flags，主动模式，内存块 0
 /// - 41 80 80 c0 00    ;; 偏移值表达式开始，指令是 i32.const(0x41) 0x100000
 /// - 0b                ;; 偏移值表达式结束标记
 /// - 0e                ;; 内容长度 14 字节（0x0e）
@@ -702,6 +900,10 @@ pub struct LocalGroup {
 ///
 /// ## 文本格式
 ///
+/// (data (offset (i32.const 1)) "abc")                 ;; 数据项的偏移值需要使用（const）表达式
+/// (data (memory $m) (offset (i32.const 1)) "abc")     ;; 指定内存块
+/// (data "abc")                                        ;; 被动数据项
+///
 /// 初始数据使用字符串的形式指定，内容可以是
 /// - 单一字符："abc文字"（字符将会以 utf-8 形式编码）
 /// - 十六进制 byte: "\de\ad\be\ef\00"
@@ -709,16 +911,25 @@ pub struct LocalGroup {
 ///
 #[derive(Debug, PartialEq, Clone)]
 pub struct DataItem {
-    /// 内存块索引，目前 WebAssembly 标准只支持 0
-    pub memory_block_index: u32,
-
-    /// 偏移值表达式（指令列表）
-    pub offset_instruction_items: Vec<Instruction>,
+    /// 数据项的模式
+    pub mode: DataMode,
 
     /// 内容
     pub data: Vec<u8>,
 }
 
+#[derive(Debug, PartialEq, Clone)]
+pub enum DataMode {
+    Active {
+        /// 内存块索引
+        memory_block_index: u32,
+
+        /// 偏移值表达式（指令列表）
+        offset_instruction_items: Vec<Instruction>,
+    },
+    Passive,
+}
+
 // /// 指令项
 // ///
 // /// 指令项包括了指令本身（类型和参数）以及指令的位置等信息
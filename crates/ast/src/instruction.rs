@@ -4,6 +4,8 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+use crate::types::{RefType, ValueType};
+
 /// # WebAssembly 指令列表
 ///
 /// <https://webassembly.github.io/spec/core/syntax/instructions.html>
@@ -52,8 +54,20 @@ pub enum Instruction {
     Call(u32),              // params: (function_index)
     CallIndirect(u32, u32), // params: (type_index, table_index)
 
+    // 尾调用（tail call）指令
+    ReturnCall(u32),              // params: (function_index)
+    ReturnCallIndirect(u32, u32), // params: (type_index, table_index)
+
+    // 异常处理（exception handling）指令
+    Throw(u32), // params: (tag_index)
+    ThrowRef,
+
+    // params: (block_type, catch_clauses, block_label_index)
+    TryTable(BlockType, Vec<CatchClause>, u32),
+
     Drop,
     Select,
+    SelectTyped(Vec<ValueType>), // params: (result_types)，目前只允许一个类型
 
     LocalGet(u32),  // params: (local_variable_index)
     LocalSet(u32),  // params: (local_variable_index)
@@ -89,18 +103,22 @@ pub enum Instruction {
     MemoryGrow(u32),      // params: (memory_block_index)
     MemoryInit(u32, u32), // params: (data_index, memory_block_index)
     DataDrop(u32),        // params: (data_index)
-    MemoryCopy(u32, u32), // params: (source_memory_block_index, dest_memory_block_index)
+    MemoryCopy(u32, u32), // params: (dest_memory_block_index, source_memory_block_index)
     MemoryFill(u32),      // params: (memory_block_index)
 
     TableGet(u32),       // params: (table_index)
     TableSet(u32),       // params: (table_index)
     TableInit(u32, u32), // params: (element_index, table_index)
     ElementDrop(u32),    // params: (element_index)
-    TableCopy(u32, u32), // params: (source_table_index, dest_table_index)
+    TableCopy(u32, u32), // params: (dest_table_index, source_table_index)
     TableGrow(u32),      // params: (table_index)
     TableSize(u32),      // params: (table_index)
     TableFill(u32),      // params: (table_index)
 
+    RefNull(RefType), // params: (ref_type)
+    RefIsNull,
+    RefFunc(u32), // params: (function_index)
+
     I32Const(i32), // params: (immediate_number_value)
     I64Const(i64), // params: (immediate_number_value)
     F32Const(f32), // params: (immediate_number_value)
@@ -256,26 +274,373 @@ pub enum Instruction {
     I64TruncSatF32U,
     I64TruncSatF64S,
     I64TruncSatF64U,
+
+    // 固定宽度 SIMD 指令
+    V128Load(MemoryArgument),
+    V128Load8x8S(MemoryArgument),
+    V128Load8x8U(MemoryArgument),
+    V128Load16x4S(MemoryArgument),
+    V128Load16x4U(MemoryArgument),
+    V128Load32x2S(MemoryArgument),
+    V128Load32x2U(MemoryArgument),
+    V128Load8Splat(MemoryArgument),
+    V128Load16Splat(MemoryArgument),
+    V128Load32Splat(MemoryArgument),
+    V128Load64Splat(MemoryArgument),
+    V128Store(MemoryArgument),
+
+    V128Const(u128), // params: (immediate_value)
+
+    I8x16Shuffle([u8; 16]), // params: (lane_indices)
+    I8x16Swizzle,
+    I8x16Splat,
+    I16x8Splat,
+    I32x4Splat,
+    I64x2Splat,
+    F32x4Splat,
+    F64x2Splat,
+    I8x16ExtractLaneS(u8), // params: (lane_index)
+    I8x16ExtractLaneU(u8), // params: (lane_index)
+    I8x16ReplaceLane(u8),  // params: (lane_index)
+    I16x8ExtractLaneS(u8), // params: (lane_index)
+    I16x8ExtractLaneU(u8), // params: (lane_index)
+    I16x8ReplaceLane(u8),  // params: (lane_index)
+    I32x4ExtractLane(u8),  // params: (lane_index)
+    I32x4ReplaceLane(u8),  // params: (lane_index)
+    I64x2ExtractLane(u8),  // params: (lane_index)
+    I64x2ReplaceLane(u8),  // params: (lane_index)
+    F32x4ExtractLane(u8),  // params: (lane_index)
+    F32x4ReplaceLane(u8),  // params: (lane_index)
+    F64x2ExtractLane(u8),  // params: (lane_index)
+    F64x2ReplaceLane(u8),  // params: (lane_index)
+
+    I8x16Eq,
+    I8x16Ne,
+    I8x16LtS,
+    I8x16LtU,
+    I8x16GtS,
+    I8x16GtU,
+    I8x16LeS,
+    I8x16LeU,
+    I8x16GeS,
+    I8x16GeU,
+    I16x8Eq,
+    I16x8Ne,
+    I16x8LtS,
+    I16x8LtU,
+    I16x8GtS,
+    I16x8GtU,
+    I16x8LeS,
+    I16x8LeU,
+    I16x8GeS,
+    I16x8GeU,
+    I32x4Eq,
+    I32x4Ne,
+    I32x4LtS,
+    I32x4LtU,
+    I32x4GtS,
+    I32x4GtU,
+    I32x4LeS,
+    I32x4LeU,
+    I32x4GeS,
+    I32x4GeU,
+    F32x4Eq,
+    F32x4Ne,
+    F32x4Lt,
+    F32x4Gt,
+    F32x4Le,
+    F32x4Ge,
+    F64x2Eq,
+    F64x2Ne,
+    F64x2Lt,
+    F64x2Gt,
+    F64x2Le,
+    F64x2Ge,
+
+    V128Not,
+    V128And,
+    V128Andnot,
+    V128Or,
+    V128Xor,
+    V128Bitselect,
+    V128AnyTrue,
+
+    V128Load8Lane(MemoryArgument, u8), // params: (memory_argument, lane_index)
+    V128Load16Lane(MemoryArgument, u8), // params: (memory_argument, lane_index)
+    V128Load32Lane(MemoryArgument, u8), // params: (memory_argument, lane_index)
+    V128Load64Lane(MemoryArgument, u8), // params: (memory_argument, lane_index)
+    V128Store8Lane(MemoryArgument, u8), // params: (memory_argument, lane_index)
+    V128Store16Lane(MemoryArgument, u8), // params: (memory_argument, lane_index)
+    V128Store32Lane(MemoryArgument, u8), // params: (memory_argument, lane_index)
+    V128Store64Lane(MemoryArgument, u8), // params: (memory_argument, lane_index)
+    V128Load32Zero(MemoryArgument),
+    V128Load64Zero(MemoryArgument),
+
+    F32x4DemoteF64x2Zero,
+    F64x2PromoteLowF32x4,
+    I8x16Abs,
+    I8x16Neg,
+    I8x16Popcnt,
+    I8x16AllTrue,
+    I8x16Bitmask,
+    I8x16NarrowI16x8S,
+    I8x16NarrowI16x8U,
+    F32x4Ceil,
+    F32x4Floor,
+    F32x4Trunc,
+    F32x4Nearest,
+    I8x16Shl,
+    I8x16ShrS,
+    I8x16ShrU,
+    I8x16Add,
+    I8x16AddSatS,
+    I8x16AddSatU,
+    I8x16Sub,
+    I8x16SubSatS,
+    I8x16SubSatU,
+    F64x2Ceil,
+    F64x2Floor,
+    I8x16MinS,
+    I8x16MinU,
+    I8x16MaxS,
+    I8x16MaxU,
+    F64x2Trunc,
+    I8x16AvgrU,
+    I16x8ExtaddPairwiseI8x16S,
+    I16x8ExtaddPairwiseI8x16U,
+    I32x4ExtaddPairwiseI16x8S,
+    I32x4ExtaddPairwiseI16x8U,
+    I16x8Abs,
+    I16x8Neg,
+    I16x8Q15mulrSatS,
+    I16x8AllTrue,
+    I16x8Bitmask,
+    I16x8NarrowI32x4S,
+    I16x8NarrowI32x4U,
+    I16x8ExtendLowI8x16S,
+    I16x8ExtendHighI8x16S,
+    I16x8ExtendLowI8x16U,
+    I16x8ExtendHighI8x16U,
+    I16x8Shl,
+    I16x8ShrS,
+    I16x8ShrU,
+    I16x8Add,
+    I16x8AddSatS,
+    I16x8AddSatU,
+    I16x8Sub,
+    I16x8SubSatS,
+    I16x8SubSatU,
+    F64x2Nearest,
+    I16x8Mul,
+    I16x8MinS,
+    I16x8MinU,
+    I16x8MaxS,
+    I16x8MaxU,
+    I16x8AvgrU,
+    I16x8ExtmulLowI8x16S,
+    I16x8ExtmulHighI8x16S,
+    I16x8ExtmulLowI8x16U,
+    I16x8ExtmulHighI8x16U,
+    I32x4Abs,
+    I32x4Neg,
+    I32x4AllTrue,
+    I32x4Bitmask,
+    I32x4ExtendLowI16x8S,
+    I32x4ExtendHighI16x8S,
+    I32x4ExtendLowI16x8U,
+    I32x4ExtendHighI16x8U,
+    I32x4Shl,
+    I32x4ShrS,
+    I32x4ShrU,
+    I32x4Add,
+    I32x4Sub,
+    I32x4Mul,
+    I32x4MinS,
+    I32x4MinU,
+    I32x4MaxS,
+    I32x4MaxU,
+    I32x4DotI16x8S,
+    I32x4ExtmulLowI16x8S,
+    I32x4ExtmulHighI16x8S,
+    I32x4ExtmulLowI16x8U,
+    I32x4ExtmulHighI16x8U,
+    I64x2Abs,
+    I64x2Neg,
+    I64x2AllTrue,
+    I64x2Bitmask,
+    I64x2ExtendLowI32x4S,
+    I64x2ExtendHighI32x4S,
+    I64x2ExtendLowI32x4U,
+    I64x2ExtendHighI32x4U,
+    I64x2Shl,
+    I64x2ShrS,
+    I64x2ShrU,
+    I64x2Add,
+    I64x2Sub,
+    I64x2Mul,
+    I64x2Eq,
+    I64x2Ne,
+    I64x2LtS,
+    I64x2GtS,
+    I64x2LeS,
+    I64x2GeS,
+    I64x2ExtmulLowI32x4S,
+    I64x2ExtmulHighI32x4S,
+    I64x2ExtmulLowI32x4U,
+    I64x2ExtmulHighI32x4U,
+    F32x4Abs,
+    F32x4Neg,
+    F32x4Sqrt,
+    F32x4Add,
+    F32x4Sub,
+    F32x4Mul,
+    F32x4Div,
+    F32x4Min,
+    F32x4Max,
+    F32x4Pmin,
+    F32x4Pmax,
+    F64x2Abs,
+    F64x2Neg,
+    F64x2Sqrt,
+    F64x2Add,
+    F64x2Sub,
+    F64x2Mul,
+    F64x2Div,
+    F64x2Min,
+    F64x2Max,
+    F64x2Pmin,
+    F64x2Pmax,
+    I32x4TruncSatF32x4S,
+    I32x4TruncSatF32x4U,
+    F32x4ConvertI32x4S,
+    F32x4ConvertI32x4U,
+    I32x4TruncSatF64x2SZero,
+    I32x4TruncSatF64x2UZero,
+    F64x2ConvertLowI32x4S,
+    F64x2ConvertLowI32x4U,
+    // 原子内存访问指令
+    MemoryAtomicNotify(MemoryArgument),
+    MemoryAtomicWait32(MemoryArgument),
+    MemoryAtomicWait64(MemoryArgument),
+    AtomicFence,
+    I32AtomicLoad(MemoryArgument),
+    I64AtomicLoad(MemoryArgument),
+    I32AtomicLoad8U(MemoryArgument),
+    I32AtomicLoad16U(MemoryArgument),
+    I64AtomicLoad8U(MemoryArgument),
+    I64AtomicLoad16U(MemoryArgument),
+    I64AtomicLoad32U(MemoryArgument),
+    I32AtomicStore(MemoryArgument),
+    I64AtomicStore(MemoryArgument),
+    I32AtomicStore8(MemoryArgument),
+    I32AtomicStore16(MemoryArgument),
+    I64AtomicStore8(MemoryArgument),
+    I64AtomicStore16(MemoryArgument),
+    I64AtomicStore32(MemoryArgument),
+    I32AtomicRmwAdd(MemoryArgument),
+    I64AtomicRmwAdd(MemoryArgument),
+    I32AtomicRmw8AddU(MemoryArgument),
+    I32AtomicRmw16AddU(MemoryArgument),
+    I64AtomicRmw8AddU(MemoryArgument),
+    I64AtomicRmw16AddU(MemoryArgument),
+    I64AtomicRmw32AddU(MemoryArgument),
+    I32AtomicRmwSub(MemoryArgument),
+    I64AtomicRmwSub(MemoryArgument),
+    I32AtomicRmw8SubU(MemoryArgument),
+    I32AtomicRmw16SubU(MemoryArgument),
+    I64AtomicRmw8SubU(MemoryArgument),
+    I64AtomicRmw16SubU(MemoryArgument),
+    I64AtomicRmw32SubU(MemoryArgument),
+    I32AtomicRmwAnd(MemoryArgument),
+    I64AtomicRmwAnd(MemoryArgument),
+    I32AtomicRmw8AndU(MemoryArgument),
+    I32AtomicRmw16AndU(MemoryArgument),
+    I64AtomicRmw8AndU(MemoryArgument),
+    I64AtomicRmw16AndU(MemoryArgument),
+    I64AtomicRmw32AndU(MemoryArgument),
+    I32AtomicRmwOr(MemoryArgument),
+    I64AtomicRmwOr(MemoryArgument),
+    I32AtomicRmw8OrU(MemoryArgument),
+    I32AtomicRmw16OrU(MemoryArgument),
+    I64AtomicRmw8OrU(MemoryArgument),
+    I64AtomicRmw16OrU(MemoryArgument),
+    I64AtomicRmw32OrU(MemoryArgument),
+    I32AtomicRmwXor(MemoryArgument),
+    I64AtomicRmwXor(MemoryArgument),
+    I32AtomicRmw8XorU(MemoryArgument),
+    I32AtomicRmw16XorU(MemoryArgument),
+    I64AtomicRmw8XorU(MemoryArgument),
+    I64AtomicRmw16XorU(MemoryArgument),
+    I64AtomicRmw32XorU(MemoryArgument),
+    I32AtomicRmwXchg(MemoryArgument),
+    I64AtomicRmwXchg(MemoryArgument),
+    I32AtomicRmw8XchgU(MemoryArgument),
+    I32AtomicRmw16XchgU(MemoryArgument),
+    I64AtomicRmw8XchgU(MemoryArgument),
+    I64AtomicRmw16XchgU(MemoryArgument),
+    I64AtomicRmw32XchgU(MemoryArgument),
+    I32AtomicRmwCmpxchg(MemoryArgument),
+    I64AtomicRmwCmpxchg(MemoryArgument),
+    I32AtomicRmw8CmpxchgU(MemoryArgument),
+    I32AtomicRmw16CmpxchgU(MemoryArgument),
+    I64AtomicRmw8CmpxchgU(MemoryArgument),
+    I64AtomicRmw16CmpxchgU(MemoryArgument),
+    I64AtomicRmw32CmpxchgU(MemoryArgument),
 }
 
 /// 流程控制结构块（比如 if/block/loop）跟函数类似
 /// 也可以有参数和返回值，除了可以跟函数一样共享 `类型段`（`Type Secion`）所
-/// 定义的类型，还有内置的 5 种无参数的类型：
+/// 定义的类型，还有内置的 9 种无参数的类型：
 /// - () -> i32
 /// - () -> i64
 /// - () -> f32
 /// - () -> f64
+/// - () -> v128
+/// - () -> funcref
+/// - () -> externref
+/// - () -> exnref
 /// - () -> ()
 #[derive(Debug, PartialEq, Clone)]
 pub enum BlockType {
-    ResultI32, //
-    ResultI64, //
-    ResultF32, //
-    ResultF64, //
+    ResultI32,       //
+    ResultI64,       //
+    ResultF32,       //
+    ResultF64,       //
+    ResultV128,      //
+    ResultFuncRef,   //
+    ResultExternRef, //
+    ResultExnRef,    //
     ResultEmpty,
     TypeIndex(u32),
 }
 
+/// `try_table` 指令的异常捕获子句
+///
+/// 当 `try_table` 结构块内部（包括被调用的函数）抛出异常时，按顺序检查各个子句，
+/// 第一个匹配的子句会跳转到指定的目标结构块（label），就像 `br` 指令一样。
+///
+/// - catch：捕获指定标签的异常，并将异常所携带的数据压入操作数栈；
+/// - catch_ref：同 catch，另外还会将异常的引用（exnref）压入操作数栈；
+/// - catch_all：捕获所有异常，不压入任何数据；
+/// - catch_all_ref：捕获所有异常，并将异常的引用（exnref）压入操作数栈。
+///
+/// 二进制格式
+///
+/// catch = 0x00 + tag_index:u32 + relative_depth:u32
+/// catch_ref = 0x01 + tag_index:u32 + relative_depth:u32
+/// catch_all = 0x02 + relative_depth:u32
+/// catch_all_ref = 0x03 + relative_depth:u32
+///
+/// 注意，子句的 relative_depth 是相对于 `try_table` 结构块外层而言的，即
+/// 0 表示 `try_table` 的直接外层结构块。
+#[derive(Debug, PartialEq, Clone)]
+pub enum CatchClause {
+    Catch(u32, u32),    // params: (tag_index, relative_depth)
+    CatchRef(u32, u32), // params: (tag_index, relative_depth)
+    CatchAll(u32),      // params: (relative_depth)
+    CatchAllRef(u32),   // params: (relative_depth)
+}
+
 /// 内存类指令的参数
 ///
 /// 参数存在于 load 和 store 指令的立即数里
@@ -283,9 +648,14 @@ pub enum BlockType {
 ///
 /// i32.load align:uint32 offset:uint32
 ///
+/// 多内存块提案之后，如果 align 的第 6 位为 1，则 align 之后还跟着内存块索引：
+///
+/// i32.load (align | 0x40):uint32 memory_block_index:uint32 offset:uint32
+///
 /// 文本格式
 ///
 /// (i32.load offset=0 align=2)
+/// (i32.load 1 offset=0 align=2) ;; 访问第 1 个内存块
 ///
 /// 对于文本格式，必须先写 offset 再写 align，且可以省略 `align` 值，
 /// 对于 i32.load/i32.store，默认对齐 4 个字节
@@ -296,14 +666,18 @@ pub enum BlockType {
 /// - offset 偏移值
 ///   加载（以及存储）指令都会从操作数栈弹出一个 i32 类型的整数，让它与指令的立即数 offset 相加，得到
 ///   实际的内存地址，即：有效地址 = offset + popUint32()
+///   对于 64 位内存块，地址是 i64 类型的整数，offset 也可以是 u64，所以这里使用 u64 储存 offset。
 ///
 /// - align 地址对齐字节数量的对数，表示对齐一个 ”以 2 为底，以 align 为指数“ 的字节数，
 ///   比如 align = 1 时，表示对齐 2^1 = 2 个字节
 ///   比如 align = 2 时，表示对齐 2^2 = 4 个字节
 ///   align 只起提示作用，用于帮助编译器优化机器代码，对实际执行没有影响（对于 wasm 解析器，可以忽略这个值）
 ///   文本格式里 `align` 的值就是字节数，比如文本格式的 8 对应二进制格式的 3 (2^3)。
+///
+/// - memory_block_index 内存块的索引，对于只有一个内存块的模块，该值为 0
 #[derive(Debug, PartialEq, Clone)]
 pub struct MemoryArgument {
     pub align: u32, // 这里记录的是跟二进制格式里所存储的一致的数值，也就是指数。
-    pub offset: u32,
+    pub offset: u64,
+    pub memory_block_index: u32,
 }
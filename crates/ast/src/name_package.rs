@@ -16,6 +16,9 @@ use crate::ast::{CustomItem, Module, NameCollection};
 pub struct NamePackage {
     pub type_names: HashMap<u32, String>,
     pub function_names: HashMap<u32, String>,
+
+    /// 因为名称重复而无法作为标识符使用的函数名称
+    pub duplicate_function_names: HashMap<u32, String>,
     pub local_variable_names_map: HashMap<u32, HashMap<u32, String>>,
     pub block_labels_map: HashMap<u32, HashMap<u32, String>>,
     pub global_variable_names: HashMap<u32, String>,
@@ -23,6 +26,7 @@ pub struct NamePackage {
     pub table_names: HashMap<u32, String>,
     pub element_names: HashMap<u32, String>,
     pub data_names: HashMap<u32, String>,
+    pub tag_names: HashMap<u32, String>,
 }
 
 impl NamePackage {
@@ -32,6 +36,7 @@ impl NamePackage {
         Self {
             type_names: get_type_names(&name_collections),
             function_names: get_function_names(&name_collections),
+            duplicate_function_names: get_duplicate_function_names(&name_collections),
             local_variable_names_map: get_local_variable_names_map(&name_collections),
             block_labels_map: get_block_lables_map(&name_collections),
             global_variable_names: get_global_variable_names(&name_collections),
@@ -39,6 +44,7 @@ impl NamePackage {
             table_names: get_table_names(&name_collections),
             element_names: get_element_names(&name_collections),
             data_names: get_data_names(&name_collections),
+            tag_names: get_tag_names(&name_collections),
         }
     }
 
@@ -50,6 +56,13 @@ impl NamePackage {
         self.function_names.get(function_index)
     }
 
+    /// 获取因为名称重复而被移除的函数名称
+    ///
+    /// 反汇编时这种名称使用名称注解 `(@name "...")` 表示。
+    pub fn get_duplicate_function_name(&self, function_index: &u32) -> Option<&String> {
+        self.duplicate_function_names.get(function_index)
+    }
+
     pub fn get_local_variable_name(
         &self,
         function_index: &u32, // 这个函数索引的值是 "包括导入和内部函数范围之内" 的索引值
@@ -89,6 +102,10 @@ impl NamePackage {
     pub fn get_data_name(&self, data_index: &u32) -> Option<&String> {
         self.data_names.get(data_index)
     }
+
+    pub fn get_tag_name(&self, tag_index: &u32) -> Option<&String> {
+        self.tag_names.get(tag_index)
+    }
 }
 
 pub fn get_type_names(name_collections: &[NameCollection]) -> HashMap<u32, String> {
@@ -135,6 +152,22 @@ pub fn get_function_names(name_collections: &[NameCollection]) -> HashMap<u32, S
         .collect::<HashMap<u32, String>>()
 }
 
+/// 获取 `get_function_names` 因为名称重复而移除的函数名称
+pub fn get_duplicate_function_names(name_collections: &[NameCollection]) -> HashMap<u32, String> {
+    let function_names = get_function_names(name_collections);
+
+    name_collections
+        .iter()
+        .filter_map(|item| match item {
+            NameCollection::FunctionNames(pairs) => Some(pairs),
+            _ => None,
+        })
+        .flatten()
+        .filter(|item| !function_names.contains_key(&item.index))
+        .map(|item| (item.index, item.name.to_owned()))
+        .collect::<HashMap<u32, String>>()
+}
+
 pub fn get_local_variable_names_map(
     name_collections: &[NameCollection],
 ) -> HashMap<u32, HashMap<u32, String>> {
@@ -239,6 +272,18 @@ pub fn get_data_names(name_collections: &[NameCollection]) -> HashMap<u32, Strin
         .collect::<HashMap<u32, String>>()
 }
 
+pub fn get_tag_names(name_collections: &[NameCollection]) -> HashMap<u32, String> {
+    name_collections
+        .iter()
+        .filter_map(|item| match item {
+            NameCollection::TagNames(pairs) => Some(pairs),
+            _ => None,
+        })
+        .flatten()
+        .map(|item| (item.index, item.name.to_owned()))
+        .collect::<HashMap<u32, String>>()
+}
+
 fn get_module_name_collections(module: &Module) -> Vec<NameCollection> {
     module
         .custom_items
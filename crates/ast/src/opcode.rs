@@ -18,6 +18,8 @@ pub const BLOCK: u8 = 0x02;
 pub const LOOP: u8 = 0x03;
 pub const IF: u8 = 0x04;
 pub const ELSE: u8 = 0x05;
+pub const THROW: u8 = 0x08;
+pub const THROW_REF: u8 = 0x0A;
 pub const END: u8 = 0x0B;
 pub const BR: u8 = 0x0C;
 pub const BR_IF: u8 = 0x0D;
@@ -25,11 +27,18 @@ pub const BR_TABLE: u8 = 0x0E;
 pub const RETURN: u8 = 0x0F;
 pub const CALL: u8 = 0x10;
 pub const CALL_INDIRECT: u8 = 0x11;
+pub const RETURN_CALL: u8 = 0x12;
+pub const RETURN_CALL_INDIRECT: u8 = 0x13;
 //
 // ### 操作数类（参数，parametric）指令
 //
 pub const DROP: u8 = 0x1A;
 pub const SELECT: u8 = 0x1B;
+pub const SELECT_TYPED: u8 = 0x1C;
+//
+// ### 异常处理指令
+//
+pub const TRY_TABLE: u8 = 0x1F;
 //
 // ### 变量类指令
 //
@@ -206,6 +215,12 @@ pub const I32_EXTEND16_S: u8 = 0xC1;
 pub const I64_EXTEND8_S: u8 = 0xC2;
 pub const I64_EXTEND16_S: u8 = 0xC3;
 pub const I64_EXTEND32_S: u8 = 0xC4;
+//
+// ### 引用类指令
+//
+pub const REF_NULL: u8 = 0xD0;
+pub const REF_IS_NULL: u8 = 0xD1;
+pub const REF_FUNC: u8 = 0xD2;
 
 // ## 扩展指令
 //
@@ -241,3 +256,355 @@ pub const I64_TRUNC_SAT_F64_U: u32 = 7;
 
 // ## 扩展指令码 0xFC
 pub const EXTENSION_0XFC: u8 = 0xFC;
+
+// ## 扩展指令码 0xFD
+//
+// 固定宽度 SIMD（fixed-width SIMD）提案引入的指令，
+// 指令码 0xFD 之后是一个 u32 类型（使用 LEB128 编码）的子操作码。
+// 注意子操作码的数值跟 WASM 规范文档一样使用十进制。
+pub const EXTENSION_0XFD: u8 = 0xFD;
+//
+// ### SIMD 内存类指令
+//
+pub const V128_LOAD: u32 = 0;
+pub const V128_LOAD8X8_S: u32 = 1;
+pub const V128_LOAD8X8_U: u32 = 2;
+pub const V128_LOAD16X4_S: u32 = 3;
+pub const V128_LOAD16X4_U: u32 = 4;
+pub const V128_LOAD32X2_S: u32 = 5;
+pub const V128_LOAD32X2_U: u32 = 6;
+pub const V128_LOAD8_SPLAT: u32 = 7;
+pub const V128_LOAD16_SPLAT: u32 = 8;
+pub const V128_LOAD32_SPLAT: u32 = 9;
+pub const V128_LOAD64_SPLAT: u32 = 10;
+pub const V128_STORE: u32 = 11;
+//
+// ### SIMD 常量指令
+//
+pub const V128_CONST: u32 = 12;
+//
+// ### SIMD 通道（lane）操作指令
+//
+pub const I8X16_SHUFFLE: u32 = 13;
+pub const I8X16_SWIZZLE: u32 = 14;
+pub const I8X16_SPLAT: u32 = 15;
+pub const I16X8_SPLAT: u32 = 16;
+pub const I32X4_SPLAT: u32 = 17;
+pub const I64X2_SPLAT: u32 = 18;
+pub const F32X4_SPLAT: u32 = 19;
+pub const F64X2_SPLAT: u32 = 20;
+pub const I8X16_EXTRACT_LANE_S: u32 = 21;
+pub const I8X16_EXTRACT_LANE_U: u32 = 22;
+pub const I8X16_REPLACE_LANE: u32 = 23;
+pub const I16X8_EXTRACT_LANE_S: u32 = 24;
+pub const I16X8_EXTRACT_LANE_U: u32 = 25;
+pub const I16X8_REPLACE_LANE: u32 = 26;
+pub const I32X4_EXTRACT_LANE: u32 = 27;
+pub const I32X4_REPLACE_LANE: u32 = 28;
+pub const I64X2_EXTRACT_LANE: u32 = 29;
+pub const I64X2_REPLACE_LANE: u32 = 30;
+pub const F32X4_EXTRACT_LANE: u32 = 31;
+pub const F32X4_REPLACE_LANE: u32 = 32;
+pub const F64X2_EXTRACT_LANE: u32 = 33;
+pub const F64X2_REPLACE_LANE: u32 = 34;
+//
+// ### SIMD 比较指令
+//
+pub const I8X16_EQ: u32 = 35;
+pub const I8X16_NE: u32 = 36;
+pub const I8X16_LT_S: u32 = 37;
+pub const I8X16_LT_U: u32 = 38;
+pub const I8X16_GT_S: u32 = 39;
+pub const I8X16_GT_U: u32 = 40;
+pub const I8X16_LE_S: u32 = 41;
+pub const I8X16_LE_U: u32 = 42;
+pub const I8X16_GE_S: u32 = 43;
+pub const I8X16_GE_U: u32 = 44;
+pub const I16X8_EQ: u32 = 45;
+pub const I16X8_NE: u32 = 46;
+pub const I16X8_LT_S: u32 = 47;
+pub const I16X8_LT_U: u32 = 48;
+pub const I16X8_GT_S: u32 = 49;
+pub const I16X8_GT_U: u32 = 50;
+pub const I16X8_LE_S: u32 = 51;
+pub const I16X8_LE_U: u32 = 52;
+pub const I16X8_GE_S: u32 = 53;
+pub const I16X8_GE_U: u32 = 54;
+pub const I32X4_EQ: u32 = 55;
+pub const I32X4_NE: u32 = 56;
+pub const I32X4_LT_S: u32 = 57;
+pub const I32X4_LT_U: u32 = 58;
+pub const I32X4_GT_S: u32 = 59;
+pub const I32X4_GT_U: u32 = 60;
+pub const I32X4_LE_S: u32 = 61;
+pub const I32X4_LE_U: u32 = 62;
+pub const I32X4_GE_S: u32 = 63;
+pub const I32X4_GE_U: u32 = 64;
+pub const F32X4_EQ: u32 = 65;
+pub const F32X4_NE: u32 = 66;
+pub const F32X4_LT: u32 = 67;
+pub const F32X4_GT: u32 = 68;
+pub const F32X4_LE: u32 = 69;
+pub const F32X4_GE: u32 = 70;
+pub const F64X2_EQ: u32 = 71;
+pub const F64X2_NE: u32 = 72;
+pub const F64X2_LT: u32 = 73;
+pub const F64X2_GT: u32 = 74;
+pub const F64X2_LE: u32 = 75;
+pub const F64X2_GE: u32 = 76;
+//
+// ### SIMD 位运算指令
+//
+pub const V128_NOT: u32 = 77;
+pub const V128_AND: u32 = 78;
+pub const V128_ANDNOT: u32 = 79;
+pub const V128_OR: u32 = 80;
+pub const V128_XOR: u32 = 81;
+pub const V128_BITSELECT: u32 = 82;
+pub const V128_ANY_TRUE: u32 = 83;
+//
+// ### SIMD 通道读写以及补零加载指令
+//
+pub const V128_LOAD8_LANE: u32 = 84;
+pub const V128_LOAD16_LANE: u32 = 85;
+pub const V128_LOAD32_LANE: u32 = 86;
+pub const V128_LOAD64_LANE: u32 = 87;
+pub const V128_STORE8_LANE: u32 = 88;
+pub const V128_STORE16_LANE: u32 = 89;
+pub const V128_STORE32_LANE: u32 = 90;
+pub const V128_STORE64_LANE: u32 = 91;
+pub const V128_LOAD32_ZERO: u32 = 92;
+pub const V128_LOAD64_ZERO: u32 = 93;
+//
+// ### SIMD 数值运算以及转换指令
+//
+pub const F32X4_DEMOTE_F64X2_ZERO: u32 = 94;
+pub const F64X2_PROMOTE_LOW_F32X4: u32 = 95;
+pub const I8X16_ABS: u32 = 96;
+pub const I8X16_NEG: u32 = 97;
+pub const I8X16_POPCNT: u32 = 98;
+pub const I8X16_ALL_TRUE: u32 = 99;
+pub const I8X16_BITMASK: u32 = 100;
+pub const I8X16_NARROW_I16X8_S: u32 = 101;
+pub const I8X16_NARROW_I16X8_U: u32 = 102;
+pub const F32X4_CEIL: u32 = 103;
+pub const F32X4_FLOOR: u32 = 104;
+pub const F32X4_TRUNC: u32 = 105;
+pub const F32X4_NEAREST: u32 = 106;
+pub const I8X16_SHL: u32 = 107;
+pub const I8X16_SHR_S: u32 = 108;
+pub const I8X16_SHR_U: u32 = 109;
+pub const I8X16_ADD: u32 = 110;
+pub const I8X16_ADD_SAT_S: u32 = 111;
+pub const I8X16_ADD_SAT_U: u32 = 112;
+pub const I8X16_SUB: u32 = 113;
+pub const I8X16_SUB_SAT_S: u32 = 114;
+pub const I8X16_SUB_SAT_U: u32 = 115;
+pub const F64X2_CEIL: u32 = 116;
+pub const F64X2_FLOOR: u32 = 117;
+pub const I8X16_MIN_S: u32 = 118;
+pub const I8X16_MIN_U: u32 = 119;
+pub const I8X16_MAX_S: u32 = 120;
+pub const I8X16_MAX_U: u32 = 121;
+pub const F64X2_TRUNC: u32 = 122;
+pub const I8X16_AVGR_U: u32 = 123;
+pub const I16X8_EXTADD_PAIRWISE_I8X16_S: u32 = 124;
+pub const I16X8_EXTADD_PAIRWISE_I8X16_U: u32 = 125;
+pub const I32X4_EXTADD_PAIRWISE_I16X8_S: u32 = 126;
+pub const I32X4_EXTADD_PAIRWISE_I16X8_U: u32 = 127;
+pub const I16X8_ABS: u32 = 128;
+pub const I16X8_NEG: u32 = 129;
+pub const I16X8_Q15MULR_SAT_S: u32 = 130;
+pub const I16X8_ALL_TRUE: u32 = 131;
+pub const I16X8_BITMASK: u32 = 132;
+pub const I16X8_NARROW_I32X4_S: u32 = 133;
+pub const I16X8_NARROW_I32X4_U: u32 = 134;
+pub const I16X8_EXTEND_LOW_I8X16_S: u32 = 135;
+pub const I16X8_EXTEND_HIGH_I8X16_S: u32 = 136;
+pub const I16X8_EXTEND_LOW_I8X16_U: u32 = 137;
+pub const I16X8_EXTEND_HIGH_I8X16_U: u32 = 138;
+pub const I16X8_SHL: u32 = 139;
+pub const I16X8_SHR_S: u32 = 140;
+pub const I16X8_SHR_U: u32 = 141;
+pub const I16X8_ADD: u32 = 142;
+pub const I16X8_ADD_SAT_S: u32 = 143;
+pub const I16X8_ADD_SAT_U: u32 = 144;
+pub const I16X8_SUB: u32 = 145;
+pub const I16X8_SUB_SAT_S: u32 = 146;
+pub const I16X8_SUB_SAT_U: u32 = 147;
+pub const F64X2_NEAREST: u32 = 148;
+pub const I16X8_MUL: u32 = 149;
+pub const I16X8_MIN_S: u32 = 150;
+pub const I16X8_MIN_U: u32 = 151;
+pub const I16X8_MAX_S: u32 = 152;
+pub const I16X8_MAX_U: u32 = 153;
+pub const I16X8_AVGR_U: u32 = 155;
+pub const I16X8_EXTMUL_LOW_I8X16_S: u32 = 156;
+pub const I16X8_EXTMUL_HIGH_I8X16_S: u32 = 157;
+pub const I16X8_EXTMUL_LOW_I8X16_U: u32 = 158;
+pub const I16X8_EXTMUL_HIGH_I8X16_U: u32 = 159;
+pub const I32X4_ABS: u32 = 160;
+pub const I32X4_NEG: u32 = 161;
+pub const I32X4_ALL_TRUE: u32 = 163;
+pub const I32X4_BITMASK: u32 = 164;
+pub const I32X4_EXTEND_LOW_I16X8_S: u32 = 167;
+pub const I32X4_EXTEND_HIGH_I16X8_S: u32 = 168;
+pub const I32X4_EXTEND_LOW_I16X8_U: u32 = 169;
+pub const I32X4_EXTEND_HIGH_I16X8_U: u32 = 170;
+pub const I32X4_SHL: u32 = 171;
+pub const I32X4_SHR_S: u32 = 172;
+pub const I32X4_SHR_U: u32 = 173;
+pub const I32X4_ADD: u32 = 174;
+pub const I32X4_SUB: u32 = 177;
+pub const I32X4_MUL: u32 = 181;
+pub const I32X4_MIN_S: u32 = 182;
+pub const I32X4_MIN_U: u32 = 183;
+pub const I32X4_MAX_S: u32 = 184;
+pub const I32X4_MAX_U: u32 = 185;
+pub const I32X4_DOT_I16X8_S: u32 = 186;
+pub const I32X4_EXTMUL_LOW_I16X8_S: u32 = 188;
+pub const I32X4_EXTMUL_HIGH_I16X8_S: u32 = 189;
+pub const I32X4_EXTMUL_LOW_I16X8_U: u32 = 190;
+pub const I32X4_EXTMUL_HIGH_I16X8_U: u32 = 191;
+pub const I64X2_ABS: u32 = 192;
+pub const I64X2_NEG: u32 = 193;
+pub const I64X2_ALL_TRUE: u32 = 195;
+pub const I64X2_BITMASK: u32 = 196;
+pub const I64X2_EXTEND_LOW_I32X4_S: u32 = 199;
+pub const I64X2_EXTEND_HIGH_I32X4_S: u32 = 200;
+pub const I64X2_EXTEND_LOW_I32X4_U: u32 = 201;
+pub const I64X2_EXTEND_HIGH_I32X4_U: u32 = 202;
+pub const I64X2_SHL: u32 = 203;
+pub const I64X2_SHR_S: u32 = 204;
+pub const I64X2_SHR_U: u32 = 205;
+pub const I64X2_ADD: u32 = 206;
+pub const I64X2_SUB: u32 = 209;
+pub const I64X2_MUL: u32 = 213;
+pub const I64X2_EQ: u32 = 214;
+pub const I64X2_NE: u32 = 215;
+pub const I64X2_LT_S: u32 = 216;
+pub const I64X2_GT_S: u32 = 217;
+pub const I64X2_LE_S: u32 = 218;
+pub const I64X2_GE_S: u32 = 219;
+pub const I64X2_EXTMUL_LOW_I32X4_S: u32 = 220;
+pub const I64X2_EXTMUL_HIGH_I32X4_S: u32 = 221;
+pub const I64X2_EXTMUL_LOW_I32X4_U: u32 = 222;
+pub const I64X2_EXTMUL_HIGH_I32X4_U: u32 = 223;
+pub const F32X4_ABS: u32 = 224;
+pub const F32X4_NEG: u32 = 225;
+pub const F32X4_SQRT: u32 = 227;
+pub const F32X4_ADD: u32 = 228;
+pub const F32X4_SUB: u32 = 229;
+pub const F32X4_MUL: u32 = 230;
+pub const F32X4_DIV: u32 = 231;
+pub const F32X4_MIN: u32 = 232;
+pub const F32X4_MAX: u32 = 233;
+pub const F32X4_PMIN: u32 = 234;
+pub const F32X4_PMAX: u32 = 235;
+pub const F64X2_ABS: u32 = 236;
+pub const F64X2_NEG: u32 = 237;
+pub const F64X2_SQRT: u32 = 239;
+pub const F64X2_ADD: u32 = 240;
+pub const F64X2_SUB: u32 = 241;
+pub const F64X2_MUL: u32 = 242;
+pub const F64X2_DIV: u32 = 243;
+pub const F64X2_MIN: u32 = 244;
+pub const F64X2_MAX: u32 = 245;
+pub const F64X2_PMIN: u32 = 246;
+pub const F64X2_PMAX: u32 = 247;
+pub const I32X4_TRUNC_SAT_F32X4_S: u32 = 248;
+pub const I32X4_TRUNC_SAT_F32X4_U: u32 = 249;
+pub const F32X4_CONVERT_I32X4_S: u32 = 250;
+pub const F32X4_CONVERT_I32X4_U: u32 = 251;
+pub const I32X4_TRUNC_SAT_F64X2_S_ZERO: u32 = 252;
+pub const I32X4_TRUNC_SAT_F64X2_U_ZERO: u32 = 253;
+pub const F64X2_CONVERT_LOW_I32X4_S: u32 = 254;
+pub const F64X2_CONVERT_LOW_I32X4_U: u32 = 255;
+
+// ## 扩展指令码 0xFE
+//
+// 线程（threads）提案引入的原子内存访问指令，
+// 指令码 0xFE 之后是一个 u32 类型（使用 LEB128 编码）的子操作码。
+pub const EXTENSION_0XFE: u8 = 0xFE;
+//
+// ### 等待、唤醒以及内存屏障指令
+//
+pub const MEMORY_ATOMIC_NOTIFY: u32 = 0;
+pub const MEMORY_ATOMIC_WAIT32: u32 = 1;
+pub const MEMORY_ATOMIC_WAIT64: u32 = 2;
+pub const ATOMIC_FENCE: u32 = 3;
+//
+// ### 原子加载指令
+//
+pub const I32_ATOMIC_LOAD: u32 = 16;
+pub const I64_ATOMIC_LOAD: u32 = 17;
+pub const I32_ATOMIC_LOAD8_U: u32 = 18;
+pub const I32_ATOMIC_LOAD16_U: u32 = 19;
+pub const I64_ATOMIC_LOAD8_U: u32 = 20;
+pub const I64_ATOMIC_LOAD16_U: u32 = 21;
+pub const I64_ATOMIC_LOAD32_U: u32 = 22;
+//
+// ### 原子存储指令
+//
+pub const I32_ATOMIC_STORE: u32 = 23;
+pub const I64_ATOMIC_STORE: u32 = 24;
+pub const I32_ATOMIC_STORE8: u32 = 25;
+pub const I32_ATOMIC_STORE16: u32 = 26;
+pub const I64_ATOMIC_STORE8: u32 = 27;
+pub const I64_ATOMIC_STORE16: u32 = 28;
+pub const I64_ATOMIC_STORE32: u32 = 29;
+//
+// ### 原子读-改-写（read-modify-write）指令
+//
+pub const I32_ATOMIC_RMW_ADD: u32 = 30;
+pub const I64_ATOMIC_RMW_ADD: u32 = 31;
+pub const I32_ATOMIC_RMW8_ADD_U: u32 = 32;
+pub const I32_ATOMIC_RMW16_ADD_U: u32 = 33;
+pub const I64_ATOMIC_RMW8_ADD_U: u32 = 34;
+pub const I64_ATOMIC_RMW16_ADD_U: u32 = 35;
+pub const I64_ATOMIC_RMW32_ADD_U: u32 = 36;
+pub const I32_ATOMIC_RMW_SUB: u32 = 37;
+pub const I64_ATOMIC_RMW_SUB: u32 = 38;
+pub const I32_ATOMIC_RMW8_SUB_U: u32 = 39;
+pub const I32_ATOMIC_RMW16_SUB_U: u32 = 40;
+pub const I64_ATOMIC_RMW8_SUB_U: u32 = 41;
+pub const I64_ATOMIC_RMW16_SUB_U: u32 = 42;
+pub const I64_ATOMIC_RMW32_SUB_U: u32 = 43;
+pub const I32_ATOMIC_RMW_AND: u32 = 44;
+pub const I64_ATOMIC_RMW_AND: u32 = 45;
+pub const I32_ATOMIC_RMW8_AND_U: u32 = 46;
+pub const I32_ATOMIC_RMW16_AND_U: u32 = 47;
+pub const I64_ATOMIC_RMW8_AND_U: u32 = 48;
+pub const I64_ATOMIC_RMW16_AND_U: u32 = 49;
+pub const I64_ATOMIC_RMW32_AND_U: u32 = 50;
+pub const I32_ATOMIC_RMW_OR: u32 = 51;
+pub const I64_ATOMIC_RMW_OR: u32 = 52;
+pub const I32_ATOMIC_RMW8_OR_U: u32 = 53;
+pub const I32_ATOMIC_RMW16_OR_U: u32 = 54;
+pub const I64_ATOMIC_RMW8_OR_U: u32 = 55;
+pub const I64_ATOMIC_RMW16_OR_U: u32 = 56;
+pub const I64_ATOMIC_RMW32_OR_U: u32 = 57;
+pub const I32_ATOMIC_RMW_XOR: u32 = 58;
+pub const I64_ATOMIC_RMW_XOR: u32 = 59;
+pub const I32_ATOMIC_RMW8_XOR_U: u32 = 60;
+pub const I32_ATOMIC_RMW16_XOR_U: u32 = 61;
+pub const I64_ATOMIC_RMW8_XOR_U: u32 = 62;
+pub const I64_ATOMIC_RMW16_XOR_U: u32 = 63;
+pub const I64_ATOMIC_RMW32_XOR_U: u32 = 64;
+pub const I32_ATOMIC_RMW_XCHG: u32 = 65;
+pub const I64_ATOMIC_RMW_XCHG: u32 = 66;
+pub const I32_ATOMIC_RMW8_XCHG_U: u32 = 67;
+pub const I32_ATOMIC_RMW16_XCHG_U: u32 = 68;
+pub const I64_ATOMIC_RMW8_XCHG_U: u32 = 69;
+pub const I64_ATOMIC_RMW16_XCHG_U: u32 = 70;
+pub const I64_ATOMIC_RMW32_XCHG_U: u32 = 71;
+//
+// ### 原子比较并交换指令
+//
+pub const I32_ATOMIC_RMW_CMPXCHG: u32 = 72;
+pub const I64_ATOMIC_RMW_CMPXCHG: u32 = 73;
+pub const I32_ATOMIC_RMW8_CMPXCHG_U: u32 = 74;
+pub const I32_ATOMIC_RMW16_CMPXCHG_U: u32 = 75;
+pub const I64_ATOMIC_RMW8_CMPXCHG_U: u32 = 76;
+pub const I64_ATOMIC_RMW16_CMPXCHG_U: u32 = 77;
+pub const I64_ATOMIC_RMW32_CMPXCHG_U: u32 = 78;
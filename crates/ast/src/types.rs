@@ -10,14 +10,30 @@ use std::fmt::Display;
 ///
 /// <https://webassembly.github.io/spec/core/syntax/types.html>
 ///
-/// WebAssembly 的数值只支持 4 种基本数据类型
-/// i32, i64, f32, f64
+/// WebAssembly 的数值有 4 种基本数据类型：i32, i64, f32, f64，
+/// 1 种向量类型：v128（由 `固定宽度 SIMD`（`fixed-width SIMD`）提案引入），
+/// 以及 3 种引用类型：funcref, externref（由 `引用类型`（`reference types`）提案引入）
+/// 和 exnref（由 `异常处理`（`exception handling`）提案引入）。
 #[derive(Debug, PartialEq, Clone)]
 pub enum ValueType {
     I32,
     I64,
     F32,
     F64,
+    V128,
+    FuncRef,
+    ExternRef,
+    ExnRef,
+}
+
+impl ValueType {
+    /// 是否为引用类型
+    pub fn is_ref(&self) -> bool {
+        matches!(
+            self,
+            ValueType::FuncRef | ValueType::ExternRef | ValueType::ExnRef
+        )
+    }
 }
 
 impl Display for ValueType {
@@ -27,6 +43,45 @@ impl Display for ValueType {
             ValueType::I64 => write!(f, "i64"),
             ValueType::F32 => write!(f, "f32"),
             ValueType::F64 => write!(f, "f64"),
+            ValueType::V128 => write!(f, "v128"),
+            ValueType::FuncRef => write!(f, "funcref"),
+            ValueType::ExternRef => write!(f, "externref"),
+            ValueType::ExnRef => write!(f, "exnref"),
+        }
+    }
+}
+
+/// # 引用类型
+///
+/// <https://webassembly.github.io/spec/core/syntax/types.html#reference-types>
+///
+/// 表的元素、元素段的元素以及 `ref.null` 指令的参数只能是引用类型。
+/// - funcref 是函数的引用，即函数的索引；
+/// - externref 是宿主（host）提供的不透明（opaque）引用，VM 不关心它的内容；
+/// - exnref 是异常的引用，由 `try_table` 指令的 `catch_ref` 和 `catch_all_ref` 子句产生。
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum RefType {
+    FuncRef,
+    ExternRef,
+    ExnRef,
+}
+
+impl From<RefType> for ValueType {
+    fn from(ref_type: RefType) -> Self {
+        match ref_type {
+            RefType::FuncRef => ValueType::FuncRef,
+            RefType::ExternRef => ValueType::ExternRef,
+            RefType::ExnRef => ValueType::ExnRef,
+        }
+    }
+}
+
+impl Display for RefType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RefType::FuncRef => write!(f, "funcref"),
+            RefType::ExternRef => write!(f, "externref"),
+            RefType::ExnRef => write!(f, "exnref"),
         }
     }
 }
@@ -36,12 +91,24 @@ impl Display for ValueType {
 /// 部分指令会明确表明需要将整数解析为无符号整数（unsigned integer）进行运算，
 /// 比如 `lt_u` 和 `gt_u` 等，而 `Value` 仅包含了有符号的整数，
 /// 所以进行无符号运算时，需要先转换再运算。
+///
+/// 向量（v128）的值使用 u128 存储，各个通道（lane）按小端格式排列，
+/// 即第 0 个通道位于最低端。
+///
+/// 引用类型的值为 `None` 时表示空引用（`ref.null`）：
+/// - FuncRef 的值是函数在其所在模块里的索引；
+/// - ExternRef 的值由宿主决定，比如宿主对象列表里的索引，VM 只负责传递；
+/// - ExnRef 的值是异常在 VM 异常列表里的索引。
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Value {
     I32(i32),
     I64(i64),
     F32(f32),
     F64(f64),
+    V128(u128),
+    FuncRef(Option<u32>),
+    ExternRef(Option<u32>),
+    ExnRef(Option<u32>),
 }
 
 impl Value {
@@ -52,6 +119,35 @@ impl Value {
             Self::I64(_) => ValueType::I64,
             Self::F32(_) => ValueType::F32,
             Self::F64(_) => ValueType::F64,
+            Self::V128(_) => ValueType::V128,
+            Self::FuncRef(_) => ValueType::FuncRef,
+            Self::ExternRef(_) => ValueType::ExternRef,
+            Self::ExnRef(_) => ValueType::ExnRef,
+        }
+    }
+
+    /// 获取指定数据类型的默认值，即局部变量以及表的空槽的初始值
+    ///
+    /// 数字类型的默认值为 0，引用类型的默认值为空引用。
+    pub fn default_of(value_type: &ValueType) -> Self {
+        match value_type {
+            ValueType::I32 => Self::I32(0),
+            ValueType::I64 => Self::I64(0),
+            ValueType::F32 => Self::F32(0.0),
+            ValueType::F64 => Self::F64(0.0),
+            ValueType::V128 => Self::V128(0),
+            ValueType::FuncRef => Self::FuncRef(None),
+            ValueType::ExternRef => Self::ExternRef(None),
+            ValueType::ExnRef => Self::ExnRef(None),
+        }
+    }
+
+    /// 创建指定引用类型的空引用
+    pub fn new_null_ref(ref_type: RefType) -> Self {
+        match ref_type {
+            RefType::FuncRef => Self::FuncRef(None),
+            RefType::ExternRef => Self::ExternRef(None),
+            RefType::ExnRef => Self::ExnRef(None),
         }
     }
 }
@@ -63,6 +159,20 @@ impl Display for Value {
             Value::I64(v) => write!(f, "{}", v),
             Value::F32(v) => write!(f, "{}", v),
             Value::F64(v) => write!(f, "{}", v),
+            Value::V128(v) => write!(
+                f,
+                "v128.const i32x4 0x{:08x} 0x{:08x} 0x{:08x} 0x{:08x}",
+                *v as u32,
+                (v >> 32) as u32,
+                (v >> 64) as u32,
+                (v >> 96) as u32
+            ),
+            Value::FuncRef(Some(v)) => write!(f, "ref.func {}", v),
+            Value::FuncRef(None) => write!(f, "ref.null func"),
+            Value::ExternRef(Some(v)) => write!(f, "ref.extern {}", v),
+            Value::ExternRef(None) => write!(f, "ref.null extern"),
+            Value::ExnRef(Some(v)) => write!(f, "ref.exn {}", v),
+            Value::ExnRef(None) => write!(f, "ref.null exn"),
         }
     }
 }
@@ -0,0 +1,232 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::{env, fs, path::Path, process, time::Duration};
+
+use anvm_benches::{
+    report::{compare, format_json, parse_baseline},
+    runner::{measure_workload, Measurement},
+    workload::get_workloads,
+};
+use anvm_engine::vm::InterpreterKind;
+
+/// 默认的重复运行次数
+const DEFAULT_REPEAT_COUNT: usize = 5;
+
+/// 默认的性能退化阈值（百分比）
+const DEFAULT_THRESHOLD_PERCENT: f64 = 10.0;
+
+struct Options {
+    interpreter_name: String,
+    repeat_count: usize,
+    option_output_file: Option<String>,
+    option_baseline_file: Option<String>,
+    threshold_percent: f64,
+    workload_names: Vec<String>,
+}
+
+/// 运行基准测试，例如：
+///
+/// `$ cargo run --release --bin anvm-bench -- --output bench.json`
+///
+/// 指定基准数据时，如果有任何阶段比基准数据慢了超过阈值，程序的退出码为 1。
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let options = match parse_options(&args) {
+        Some(options) => options,
+        None => {
+            print_usage();
+            return;
+        }
+    };
+
+    let interpreter_kind = match options.interpreter_name.as_str() {
+        "stack" => InterpreterKind::Stack,
+        "register" => InterpreterKind::Register,
+        #[cfg(feature = "jit")]
+        "jit" => InterpreterKind::Jit,
+        _ => {
+            println!("unsupported interpreter: {}", options.interpreter_name);
+            process::exit(1);
+        }
+    };
+
+    // 项目的模块路径相对于 `crates` 目录
+    let crates_path = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+
+    let workloads = get_workloads()
+        .into_iter()
+        .filter(|workload| {
+            options.workload_names.is_empty()
+                || options
+                    .workload_names
+                    .iter()
+                    .any(|name| name == workload.name)
+        })
+        .collect::<Vec<_>>();
+
+    println!(
+        "{:<20} {:>14} {:>14} {:>14} {:>14} {:>14}",
+        "workload", "parse", "link_decode", "instantiate", "pool_reuse", "execute"
+    );
+
+    let mut measurements: Vec<Measurement> = vec![];
+
+    for workload in &workloads {
+        let measurement = match measure_workload(
+            workload,
+            crates_path,
+            interpreter_kind,
+            options.repeat_count,
+        ) {
+            Ok(measurement) => measurement,
+            Err(e) => {
+                println!("{}", e);
+                process::exit(1);
+            }
+        };
+
+        let phase_texts = measurement
+            .get_phase_durations()
+            .iter()
+            .map(|option_duration| match option_duration {
+                Some(duration) => format_duration(duration),
+                None => "-".to_string(),
+            })
+            .collect::<Vec<String>>();
+
+        println!(
+            "{:<20} {:>14} {:>14} {:>14} {:>14} {:>14}",
+            measurement.name,
+            phase_texts[0],
+            phase_texts[1],
+            phase_texts[2],
+            phase_texts[3],
+            phase_texts[4]
+        );
+
+        measurements.push(measurement);
+    }
+
+    if let Some(output_file) = &options.option_output_file {
+        let text = format_json(
+            &options.interpreter_name,
+            options.repeat_count,
+            &measurements,
+        );
+
+        if let Err(e) = fs::write(output_file, text) {
+            println!("failed to write file \"{}\": {}", output_file, e);
+            process::exit(1);
+        }
+    }
+
+    if let Some(baseline_file) = &options.option_baseline_file {
+        let text = match fs::read_to_string(baseline_file) {
+            Ok(text) => text,
+            Err(e) => {
+                println!("failed to read file \"{}\": {}", baseline_file, e);
+                process::exit(1);
+            }
+        };
+
+        let comparisons = compare(&measurements, &parse_baseline(&text));
+
+        println!();
+        println!(
+            "{:<20} {:<12} {:>14} {:>14} {:>10}",
+            "workload", "phase", "baseline", "current", "change"
+        );
+
+        let mut is_regressed = false;
+
+        // 变慢超过阈值的阶段以 `!` 标记
+        for comparison in &comparisons {
+            let change_percent = comparison.get_change_percent();
+            let is_phase_regressed = change_percent > options.threshold_percent;
+            if is_phase_regressed {
+                is_regressed = true;
+            }
+
+            println!(
+                "{:<20} {:<12} {:>14} {:>14} {:>+9.1}%{}",
+                comparison.name,
+                comparison.phase,
+                format_duration(&Duration::from_nanos(comparison.baseline_ns)),
+                format_duration(&Duration::from_nanos(comparison.current_ns)),
+                change_percent,
+                if is_phase_regressed { " !" } else { "" }
+            );
+        }
+
+        if is_regressed {
+            process::exit(1);
+        }
+    }
+}
+
+fn parse_options(args: &[String]) -> Option<Options> {
+    let mut options = Options {
+        interpreter_name: "stack".to_string(),
+        repeat_count: DEFAULT_REPEAT_COUNT,
+        option_output_file: None,
+        option_baseline_file: None,
+        threshold_percent: DEFAULT_THRESHOLD_PERCENT,
+        workload_names: vec![],
+    };
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--interpreter" => options.interpreter_name = iter.next()?.clone(),
+            "--repeat" => options.repeat_count = iter.next()?.parse::<usize>().ok()?,
+            "--output" => options.option_output_file = Some(iter.next()?.clone()),
+            "--baseline" => options.option_baseline_file = Some(iter.next()?.clone()),
+            "--threshold" => options.threshold_percent = iter.next()?.parse::<f64>().ok()?,
+            _ if arg.starts_with("--") => return None,
+            _ => options.workload_names.push(arg.clone()),
+        }
+    }
+
+    Some(options)
+}
+
+fn print_usage() {
+    println!(
+        "\
+Usage:
+
+    $ anvm-bench [options] [workload_name...]
+
+Options:
+
+    --interpreter <name>    stack (default), register or jit
+    --repeat <count>        number of runs per workload, default 5
+    --output <file>         write the measurements to the file in JSON
+    --baseline <file>       compare with the measurements in the file
+    --threshold <percent>   regression threshold for --baseline, default 10
+
+e.g.
+
+    $ anvm-bench --output before.json
+    $ anvm-bench --baseline before.json --output after.json
+"
+    );
+}
+
+fn format_duration(duration: &Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{} ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.2} us", nanos as f64 / 1_000.0)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2} ms", nanos as f64 / 1_000_000.0)
+    } else {
+        format!("{:.2} s", nanos as f64 / 1_000_000_000.0)
+    }
+}
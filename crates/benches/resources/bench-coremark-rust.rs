@@ -0,0 +1,424 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! 参照 CoreMark 编写的整数运算基准测试，跟 CoreMark 一样由链表、矩阵、状态机
+//! 以及 CRC 校验四部分组成。不同于 `bench-integer.wat`，本模块由 rustc 编译而成，
+//! 以反映真实编译器生成的指令组合（栈指针、局部变量的内存读写、跳转表等）。
+//!
+//! 编译方法：
+//!
+//! `$ rustc bench-coremark-rust.rs --target wasm32-unknown-unknown -C lto -O -C strip=symbols --crate-type=cdylib -o bench-coremark-rust.wasm`
+//!
+//! 其中 `-C strip=symbols` 用于去除 "name" 自定义段（目前的解析器不支持其中的模块名称子段）。
+
+#![no_std]
+
+use core::panic::PanicInfo;
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    core::arch::wasm32::unreachable()
+}
+
+/// 执行指定次数的迭代，每次迭代使用不同的种子，返回所有结果的 CRC 校验值
+#[no_mangle]
+pub extern "C" fn run(iterations: i32) -> i32 {
+    let mut crc = 0;
+    for iteration in 0..iterations as u32 {
+        let seed = (iteration as u16).wrapping_mul(0x1f3).wrapping_add(0x66);
+        crc = crcu16(bench_list(seed), crc);
+        crc = crcu16(bench_matrix(seed), crc);
+        crc = crcu16(bench_state(seed), crc);
+    }
+    crc as i32
+}
+
+// CRC 校验
+
+fn crcu8(data: u8, crc: u16) -> u16 {
+    let mut data = data;
+    let mut crc = crc;
+    for _ in 0..8 {
+        let carry = ((data & 1) as u16) ^ (crc & 1);
+        data >>= 1;
+        if carry == 1 {
+            crc ^= 0x4002;
+            crc = (crc >> 1) | 0x8000;
+        } else {
+            crc >>= 1;
+        }
+    }
+    crc
+}
+
+fn crcu16(value: u16, crc: u16) -> u16 {
+    crcu8((value >> 8) as u8, crcu8(value as u8, crc))
+}
+
+// 链表
+
+const LIST_LENGTH: usize = 64;
+
+/// 链表结束标记
+const NIL: usize = usize::MAX;
+
+#[derive(Clone, Copy)]
+struct ListNode {
+    next: usize,
+    data: i16,
+    index: i16,
+}
+
+fn bench_list(seed: u16) -> u16 {
+    let mut nodes = [ListNode {
+        next: NIL,
+        data: 0,
+        index: 0,
+    }; LIST_LENGTH];
+
+    for (index, node) in nodes.iter_mut().enumerate() {
+        node.next = if index + 1 < LIST_LENGTH { index + 1 } else { NIL };
+        node.data = ((seed as usize ^ (index * 0x3d)) & 0xff) as i16;
+        node.index = index as i16;
+    }
+
+    let mut head = 0;
+    let mut crc = 0;
+    let mut found: u16 = 0;
+    let mut missed: u16 = 0;
+
+    // 查找以及反转
+    for k in 0..16u16 {
+        let data = (seed.wrapping_mul(k).wrapping_add(k) & 0xff) as i16;
+        match find_data(&nodes, head, data) {
+            Some(node) => {
+                found += 1;
+                crc = crcu16(nodes[node].index as u16, crc);
+            }
+            None => missed += 1,
+        }
+        head = reverse_list(&mut nodes, head);
+    }
+
+    // 按数值排序，然后再按原来的顺序排序
+    head = sort_list(&mut nodes, head, false);
+    crc = crc_list(&nodes, head, false, crc);
+
+    head = sort_list(&mut nodes, head, true);
+    crc = crc_list(&nodes, head, true, crc);
+
+    crcu16(found, crcu16(missed, crc))
+}
+
+fn find_data(nodes: &[ListNode], head: usize, data: i16) -> Option<usize> {
+    let mut node = head;
+    while node != NIL {
+        if nodes[node].data == data {
+            return Some(node);
+        }
+        node = nodes[node].next;
+    }
+    None
+}
+
+fn reverse_list(nodes: &mut [ListNode], head: usize) -> usize {
+    let mut node = head;
+    let mut reversed = NIL;
+    while node != NIL {
+        let next = nodes[node].next;
+        nodes[node].next = reversed;
+        reversed = node;
+        node = next;
+    }
+    reversed
+}
+
+fn get_key(node: &ListNode, by_index: bool) -> i16 {
+    if by_index {
+        node.index
+    } else {
+        node.data
+    }
+}
+
+/// 链表的归并排序（跟 CoreMark 的 `core_list_mergesort` 相同的算法）
+fn sort_list(nodes: &mut [ListNode], head: usize, by_index: bool) -> usize {
+    let mut head = head;
+    let mut insize = 1;
+
+    loop {
+        let mut p = head;
+        let mut tail = NIL;
+        let mut merge_count = 0;
+        head = NIL;
+
+        while p != NIL {
+            merge_count += 1;
+
+            let mut q = p;
+            let mut psize = 0;
+            while psize < insize && q != NIL {
+                psize += 1;
+                q = nodes[q].next;
+            }
+
+            let mut qsize = insize;
+            while psize > 0 || (qsize > 0 && q != NIL) {
+                let take_p = if psize == 0 {
+                    false
+                } else if qsize == 0 || q == NIL {
+                    true
+                } else {
+                    get_key(&nodes[p], by_index) <= get_key(&nodes[q], by_index)
+                };
+
+                let node = if take_p {
+                    let node = p;
+                    p = nodes[p].next;
+                    psize -= 1;
+                    node
+                } else {
+                    let node = q;
+                    q = nodes[q].next;
+                    qsize -= 1;
+                    node
+                };
+
+                if tail == NIL {
+                    head = node;
+                } else {
+                    nodes[tail].next = node;
+                }
+                tail = node;
+            }
+
+            p = q;
+        }
+
+        nodes[tail].next = NIL;
+
+        if merge_count <= 1 {
+            return head;
+        }
+        insize *= 2;
+    }
+}
+
+fn crc_list(nodes: &[ListNode], head: usize, by_index: bool, crc: u16) -> u16 {
+    let mut crc = crc;
+    let mut node = head;
+    while node != NIL {
+        crc = crcu16(get_key(&nodes[node], by_index) as u16, crc);
+        node = nodes[node].next;
+    }
+    crc
+}
+
+// 矩阵
+
+const MATRIX_SIZE: usize = 16;
+const MATRIX_LENGTH: usize = MATRIX_SIZE * MATRIX_SIZE;
+
+fn bench_matrix(seed: u16) -> u16 {
+    let mut a = [0i16; MATRIX_LENGTH];
+    let mut b = [0i16; MATRIX_LENGTH];
+    let mut c = [0i32; MATRIX_LENGTH];
+
+    let mut order: u32 = 1;
+    for index in 0..MATRIX_LENGTH {
+        let value = ((seed as u32).wrapping_mul(order) & 0xffff) + order;
+        b[index] = (value & 0xff) as i16;
+        a[index] = ((value + order) & 0xff) as i16;
+        order += 1;
+    }
+
+    let value = (seed | 0xf000) as i16;
+    let clip = (value as i32) << 4;
+    let mut crc = 0;
+
+    // 加上常数
+    for item in a.iter_mut() {
+        *item = item.wrapping_add(value);
+    }
+
+    // 乘以常数
+    for (dest, item) in c.iter_mut().zip(a.iter()) {
+        *dest = *item as i32 * value as i32;
+    }
+    crc = crcu16(sum_matrix(&c, clip) as u16, crc);
+
+    // 乘以向量（矩阵 b 的第一行）
+    for row in 0..MATRIX_SIZE {
+        let mut sum = 0i32;
+        for column in 0..MATRIX_SIZE {
+            sum = sum.wrapping_add(a[row * MATRIX_SIZE + column] as i32 * b[column] as i32);
+        }
+        c[row] = sum;
+    }
+    crc = crcu16(sum_matrix(&c[..MATRIX_SIZE], clip) as u16, crc);
+
+    // 乘以矩阵
+    multiply_matrix(&a, &b, &mut c, false);
+    crc = crcu16(sum_matrix(&c, clip) as u16, crc);
+
+    // 乘以矩阵，并从乘积中提取部分位
+    multiply_matrix(&a, &b, &mut c, true);
+    crc = crcu16(sum_matrix(&c, clip) as u16, crc);
+
+    // 恢复矩阵 a
+    for item in a.iter_mut() {
+        *item = item.wrapping_sub(value);
+    }
+    crcu16(a[0] as u16 ^ a[MATRIX_LENGTH - 1] as u16, crc)
+}
+
+fn multiply_matrix(a: &[i16], b: &[i16], c: &mut [i32], bit_extract: bool) {
+    for row in 0..MATRIX_SIZE {
+        for column in 0..MATRIX_SIZE {
+            let mut sum = 0i32;
+            for k in 0..MATRIX_SIZE {
+                let product = a[row * MATRIX_SIZE + k] as i32 * b[k * MATRIX_SIZE + column] as i32;
+                sum = sum.wrapping_add(if bit_extract {
+                    ((product >> 2) & 0x0f) * ((product >> 5) & 0x7f)
+                } else {
+                    product
+                });
+            }
+            c[row * MATRIX_SIZE + column] = sum;
+        }
+    }
+}
+
+fn sum_matrix(c: &[i32], clip: i32) -> i16 {
+    let mut total = 0i32;
+    let mut previous = 0i32;
+    let mut result = 0i16;
+
+    for &current in c {
+        total = total.wrapping_add(current);
+        if total > clip {
+            result = result.wrapping_add(10);
+            total = 0;
+        } else if current > previous {
+            result = result.wrapping_add(1);
+        }
+        previous = current;
+    }
+
+    result
+}
+
+// 状态机
+
+const STATE_INPUT_LENGTH: usize = 256;
+
+const PATTERNS: [&[u8]; 16] = [
+    // 整数
+    b"5012", b"1234", b"-874", b"+122",
+    // 浮点数
+    b"35.54400", b".1234500", b"-110.700", b"+0.64400",
+    // 科学计数法
+    b"5.500e+3", b"-.123e-2", b"-87e+832", b"+0.6e-12",
+    // 无效的数字
+    b"T0.3e-1F", b"-T.T++Tq", b"1T3.4e4z", b"34.0e-T^",
+];
+
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    Start,
+    Invalid,
+    Sign,
+    Int,
+    Float,
+    Exponent,
+    ExponentSign,
+    Scientific,
+}
+
+const STATE_COUNT: usize = 8;
+
+fn bench_state(seed: u16) -> u16 {
+    let mut input = [b','; STATE_INPUT_LENGTH];
+
+    // 根据种子选择数字的模式，以逗号分隔
+    let mut length = 0;
+    let mut pattern_seed = seed as usize;
+    loop {
+        pattern_seed = (pattern_seed * 5 + 3) & 0xffff;
+        let pattern = PATTERNS[(pattern_seed >> 4) & 0x0f];
+        if length + pattern.len() + 1 > STATE_INPUT_LENGTH {
+            break;
+        }
+        input[length..length + pattern.len()].copy_from_slice(pattern);
+        length += pattern.len() + 1;
+    }
+
+    let mut final_counts = [0u16; STATE_COUNT];
+    let mut transition_counts = [0u16; STATE_COUNT];
+    scan_input(&input, &mut final_counts, &mut transition_counts);
+
+    // 改动部分字节然后再次扫描
+    for index in (0..STATE_INPUT_LENGTH).step_by(((seed & 0x7) + 1) as usize) {
+        if input[index] != b',' {
+            input[index] ^= (seed & 0xff) as u8;
+        }
+    }
+    scan_input(&input, &mut final_counts, &mut transition_counts);
+
+    let mut crc = 0;
+    for index in 0..STATE_COUNT {
+        crc = crcu16(final_counts[index], crc);
+        crc = crcu16(transition_counts[index], crc);
+    }
+    crc
+}
+
+fn scan_input(input: &[u8], final_counts: &mut [u16], transition_counts: &mut [u16]) {
+    let mut position = 0;
+    while position < input.len() {
+        let state = next_token(input, &mut position, transition_counts);
+        final_counts[state as usize] += 1;
+    }
+}
+
+/// 识别一个数字，position 移到逗号之后
+fn next_token(input: &[u8], position: &mut usize, transition_counts: &mut [u16]) -> State {
+    let mut state = State::Start;
+
+    while *position < input.len() {
+        let c = input[*position];
+        *position += 1;
+
+        if c == b',' {
+            break;
+        }
+
+        let is_digit = c.is_ascii_digit();
+        let is_sign = c == b'+' || c == b'-';
+        let is_exponent = c == b'e' || c == b'E';
+
+        let next_state = match state {
+            State::Start | State::Sign if is_digit => State::Int,
+            State::Start if is_sign => State::Sign,
+            State::Start | State::Sign | State::Int if c == b'.' => State::Float,
+            State::Int if is_digit => State::Int,
+            State::Int | State::Float if is_exponent => State::Exponent,
+            State::Float if is_digit => State::Float,
+            State::Exponent if is_sign => State::ExponentSign,
+            State::Exponent | State::ExponentSign | State::Scientific if is_digit => {
+                State::Scientific
+            }
+            _ => State::Invalid,
+        };
+
+        if next_state != state {
+            transition_counts[next_state as usize] += 1;
+        }
+        state = next_state;
+    }
+
+    state
+}
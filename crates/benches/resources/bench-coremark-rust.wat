@@ -0,0 +1,4182 @@
+(module
+    (type (;0;) (func (param i32 i32 i32)))
+    (type (;1;) (func (param i32 i32 i32 i32)))
+    (type (;2;) (func (param i32 i32) (result i32)))
+    (type (;3;) (func (param i32 i32 i32) (result i32)))
+    (type (;4;) (func (param i32 i32)))
+    (type (;5;) (func (param i32) (result i32)))
+    (type (;6;) (func))
+    (memory (;0;) 17)
+    (global (;0;) (mut i32) i32.const 1048576)
+    (global (;1;) i32 i32.const 1048816)
+    (global (;2;) i32 i32.const 1048816)
+    (export "memory" (memory 0))
+    (export "run" (func 5))
+    (export "__data_end" (global 1))
+    (export "__heap_base" (global 2))
+    (func (;0;) (;type 0;) (param (;0;) i32) (param (;1;) i32) (param (;2;) i32)
+        (local (;3;) i32)
+        (local (;4;) i32)
+        (local (;5;) i32)
+        (local (;6;) i32)
+        (local (;7;) i32)
+        i32.const 0
+        local.set 3
+        block
+            loop
+                i32.const 0
+                local.set 4
+                loop
+                    local.get 3
+                    i32.const 1
+                    i32.add
+                    local.set 5
+                    block
+                        block
+                            block
+                                local.get 0
+                                local.get 3
+                                i32.add
+                                i32.load8_u offset=0 align=1
+                                local.tee 6
+                                i32.const 44
+                                i32.eq
+                                br_if 0
+                                block
+                                    block
+                                        block
+                                            block
+                                                block
+                                                    local.get 6
+                                                    i32.const 101
+                                                    i32.ne
+                                                    br_if 0
+                                                    local.get 4
+                                                    i32.const -3
+                                                    i32.add
+                                                    i32.const 255
+                                                    i32.and
+                                                    i32.const 2
+                                                    i32.lt_u
+                                                    br_if 1
+                                                    i32.const 1
+                                                    local.set 7
+                                                    br 7
+                                                end
+                                                local.get 6
+                                                i32.const -48
+                                                i32.add
+                                                local.set 3
+                                                i32.const 1
+                                                local.set 7
+                                                block
+                                                    block
+                                                        block
+                                                            block
+                                                                block
+                                                                    block
+                                                                        block
+                                                                            block
+                                                                                block
+                                                                                    block
+                                                                                        local.get 4
+                                                                                        i32.const 255
+                                                                                        i32.and
+                                                                                        br_table 0 16 1 5 9 2 3 4 16
+                                                                                    end
+                                                                                    local.get 3
+                                                                                    i32.const 255
+                                                                                    i32.and
+                                                                                    i32.const 10
+                                                                                    i32.ge_u
+                                                                                    br_if 5
+                                                                                    i32.const 3
+                                                                                    local.set 7
+                                                                                    br 15
+                                                                                end
+                                                                                local.get 3
+                                                                                i32.const 255
+                                                                                i32.and
+                                                                                i32.const 10
+                                                                                i32.ge_u
+                                                                                br_if 5
+                                                                                i32.const 3
+                                                                                local.set 7
+                                                                                br 14
+                                                                            end
+                                                                            i32.const 6
+                                                                            local.set 7
+                                                                            local.get 6
+                                                                            i32.const -43
+                                                                            i32.add
+                                                                            br_table 13 9 13 9
+                                                                        end
+                                                                        i32.const 1
+                                                                        local.set 7
+                                                                        local.get 3
+                                                                        i32.const 255
+                                                                        i32.and
+                                                                        i32.const 10
+                                                                        i32.ge_u
+                                                                        br_if 12
+                                                                        br 9
+                                                                    end
+                                                                    i32.const 1
+                                                                    local.set 7
+                                                                    local.get 3
+                                                                    i32.const 255
+                                                                    i32.and
+                                                                    i32.const 10
+                                                                    i32.ge_u
+                                                                    br_if 11
+                                                                    br 8
+                                                                end
+                                                                local.get 6
+                                                                i32.const 46
+                                                                i32.ne
+                                                                br_if 2
+                                                                br 9
+                                                            end
+                                                            i32.const 1
+                                                            local.set 7
+                                                            local.get 6
+                                                            i32.const -43
+                                                            i32.add
+                                                            local.tee 3
+                                                            i32.const 255
+                                                            i32.and
+                                                            i32.const 4
+                                                            i32.ge_u
+                                                            br_if 9
+                                                            i32.const 67240194
+                                                            local.get 3
+                                                            i32.const 3
+                                                            i32.shl
+                                                            i32.const 248
+                                                            i32.and
+                                                            i32.shr_u
+                                                            local.set 7
+                                                            br 9
+                                                        end
+                                                        i32.const 1
+                                                        local.set 7
+                                                        local.get 6
+                                                        i32.const 46
+                                                        i32.eq
+                                                        br_if 7
+                                                        br 8
+                                                    end
+                                                    local.get 6
+                                                    i32.const 69
+                                                    i32.eq
+                                                    br_if 1
+                                                    i32.const 3
+                                                    i32.const 1
+                                                    local.get 3
+                                                    i32.const 255
+                                                    i32.and
+                                                    i32.const 10
+                                                    i32.lt_u
+                                                    select
+                                                    local.set 7
+                                                    br 7
+                                                end
+                                                local.get 6
+                                                i32.const 69
+                                                i32.ne
+                                                br_if 1
+                                            end
+                                            i32.const 5
+                                            local.set 7
+                                            br 5
+                                        end
+                                        i32.const 4
+                                        i32.const 1
+                                        local.get 3
+                                        i32.const 255
+                                        i32.and
+                                        i32.const 10
+                                        i32.lt_u
+                                        select
+                                        local.set 7
+                                        br 4
+                                    end
+                                    i32.const 1
+                                    local.set 7
+                                    local.get 3
+                                    i32.const 255
+                                    i32.and
+                                    i32.const 9
+                                    i32.gt_u
+                                    br_if 3
+                                end
+                                i32.const 7
+                                local.set 7
+                                br 2
+                            end
+                            local.get 1
+                            local.get 4
+                            i32.const 255
+                            i32.and
+                            i32.const 1
+                            i32.shl
+                            i32.add
+                            local.tee 7
+                            local.get 7
+                            i32.load16_u offset=0 align=2
+                            i32.const 1
+                            i32.add
+                            i32.store16 offset=0 align=2
+                            local.get 3
+                            i32.const 255
+                            i32.lt_u
+                            local.set 7
+                            local.get 5
+                            local.set 3
+                            local.get 7
+                            br_if 3
+                            br 4
+                        end
+                        i32.const 4
+                        local.set 7
+                    end
+                    block
+                        local.get 7
+                        i32.const 255
+                        i32.and
+                        local.tee 3
+                        local.get 4
+                        i32.const 255
+                        i32.and
+                        i32.eq
+                        br_if 0
+                        local.get 2
+                        local.get 3
+                        i32.const 1
+                        i32.shl
+                        i32.add
+                        local.tee 3
+                        local.get 3
+                        i32.load16_u offset=0 align=2
+                        i32.const 1
+                        i32.add
+                        i32.store16 offset=0 align=2
+                    end
+                    local.get 7
+                    local.set 4
+                    local.get 5
+                    local.set 3
+                    local.get 5
+                    i32.const 256
+                    i32.ne
+                    br_if 0
+                end
+            end
+            local.get 1
+            local.get 7
+            i32.const 255
+            i32.and
+            i32.const 1
+            i32.shl
+            i32.add
+            local.tee 7
+            local.get 7
+            i32.load16_u offset=0 align=2
+            i32.const 1
+            i32.add
+            i32.store16 offset=0 align=2
+        end
+    )
+    (func (;1;) (;type 1;) (param (;0;) i32) (param (;1;) i32) (param (;2;) i32) (param (;3;) i32)
+        (local (;4;) i32)
+        (local (;5;) i32)
+        (local (;6;) i32)
+        (local (;7;) i32)
+        (local (;8;) i32)
+        (local (;9;) i32)
+        (local (;10;) i32)
+        (local (;11;) i32)
+        (local (;12;) i32)
+        (local (;13;) i32)
+        (local (;14;) i32)
+        (local (;15;) i32)
+        (local (;16;) i32)
+        (local (;17;) i32)
+        (local (;18;) i32)
+        (local (;19;) i32)
+        (local (;20;) i32)
+        (local (;21;) i32)
+        (local (;22;) i32)
+        (local (;23;) i32)
+        block
+            block
+                block
+                    local.get 3
+                    br_if 0
+                    i32.const 0
+                    local.set 4
+                    br 1
+                end
+                i32.const 0
+                local.set 5
+                loop
+                    local.get 0
+                    local.get 5
+                    i32.const 5
+                    i32.shl
+                    i32.add
+                    local.tee 3
+                    i32.load16_u offset=30 align=2
+                    local.set 6
+                    local.get 3
+                    i32.load16_u offset=28 align=2
+                    local.set 7
+                    local.get 3
+                    i32.load16_u offset=26 align=2
+                    local.set 8
+                    local.get 3
+                    i32.load16_u offset=24 align=2
+                    local.set 9
+                    local.get 3
+                    i32.load16_u offset=22 align=2
+                    local.set 10
+                    local.get 3
+                    i32.load16_u offset=20 align=2
+                    local.set 11
+                    local.get 3
+                    i32.load16_u offset=18 align=2
+                    local.set 12
+                    local.get 3
+                    i32.load16_u offset=16 align=2
+                    local.set 13
+                    local.get 3
+                    i32.load16_u offset=14 align=2
+                    local.set 14
+                    local.get 3
+                    i32.load16_u offset=12 align=2
+                    local.set 15
+                    local.get 3
+                    i32.load16_u offset=10 align=2
+                    local.set 16
+                    local.get 3
+                    i32.load16_u offset=8 align=2
+                    local.set 17
+                    local.get 3
+                    i32.load16_u offset=6 align=2
+                    local.set 18
+                    local.get 3
+                    i32.load16_u offset=4 align=2
+                    local.set 19
+                    local.get 3
+                    i32.load16_u offset=2 align=2
+                    local.set 20
+                    local.get 3
+                    i32.load16_u offset=0 align=2
+                    local.set 4
+                    i32.const -32
+                    local.set 21
+                    local.get 2
+                    local.set 22
+                    loop
+                        local.get 22
+                        local.get 1
+                        local.get 21
+                        i32.add
+                        local.tee 3
+                        i32.const 512
+                        i32.add
+                        i32.load16_u offset=0 align=2
+                        local.get 6
+                        i32.mul
+                        local.tee 23
+                        i32.const 2
+                        i32.shr_u
+                        i32.const 15
+                        i32.and
+                        local.get 23
+                        i32.const 5
+                        i32.shr_u
+                        i32.const 127
+                        i32.and
+                        i32.mul
+                        local.get 3
+                        i32.const 480
+                        i32.add
+                        i32.load16_u offset=0 align=2
+                        local.get 7
+                        i32.mul
+                        local.tee 23
+                        i32.const 2
+                        i32.shr_u
+                        i32.const 15
+                        i32.and
+                        local.get 23
+                        i32.const 5
+                        i32.shr_u
+                        i32.const 127
+                        i32.and
+                        i32.mul
+                        local.get 3
+                        i32.const 448
+                        i32.add
+                        i32.load16_u offset=0 align=2
+                        local.get 8
+                        i32.mul
+                        local.tee 23
+                        i32.const 2
+                        i32.shr_u
+                        i32.const 15
+                        i32.and
+                        local.get 23
+                        i32.const 5
+                        i32.shr_u
+                        i32.const 127
+                        i32.and
+                        i32.mul
+                        local.get 3
+                        i32.const 416
+                        i32.add
+                        i32.load16_u offset=0 align=2
+                        local.get 9
+                        i32.mul
+                        local.tee 23
+                        i32.const 2
+                        i32.shr_u
+                        i32.const 15
+                        i32.and
+                        local.get 23
+                        i32.const 5
+                        i32.shr_u
+                        i32.const 127
+                        i32.and
+                        i32.mul
+                        local.get 3
+                        i32.const 384
+                        i32.add
+                        i32.load16_u offset=0 align=2
+                        local.get 10
+                        i32.mul
+                        local.tee 23
+                        i32.const 2
+                        i32.shr_u
+                        i32.const 15
+                        i32.and
+                        local.get 23
+                        i32.const 5
+                        i32.shr_u
+                        i32.const 127
+                        i32.and
+                        i32.mul
+                        local.get 3
+                        i32.const 352
+                        i32.add
+                        i32.load16_u offset=0 align=2
+                        local.get 11
+                        i32.mul
+                        local.tee 23
+                        i32.const 2
+                        i32.shr_u
+                        i32.const 15
+                        i32.and
+                        local.get 23
+                        i32.const 5
+                        i32.shr_u
+                        i32.const 127
+                        i32.and
+                        i32.mul
+                        local.get 3
+                        i32.const 320
+                        i32.add
+                        i32.load16_u offset=0 align=2
+                        local.get 12
+                        i32.mul
+                        local.tee 23
+                        i32.const 2
+                        i32.shr_u
+                        i32.const 15
+                        i32.and
+                        local.get 23
+                        i32.const 5
+                        i32.shr_u
+                        i32.const 127
+                        i32.and
+                        i32.mul
+                        local.get 3
+                        i32.const 288
+                        i32.add
+                        i32.load16_u offset=0 align=2
+                        local.get 13
+                        i32.mul
+                        local.tee 23
+                        i32.const 2
+                        i32.shr_u
+                        i32.const 15
+                        i32.and
+                        local.get 23
+                        i32.const 5
+                        i32.shr_u
+                        i32.const 127
+                        i32.and
+                        i32.mul
+                        local.get 3
+                        i32.const 256
+                        i32.add
+                        i32.load16_u offset=0 align=2
+                        local.get 14
+                        i32.mul
+                        local.tee 23
+                        i32.const 2
+                        i32.shr_u
+                        i32.const 15
+                        i32.and
+                        local.get 23
+                        i32.const 5
+                        i32.shr_u
+                        i32.const 127
+                        i32.and
+                        i32.mul
+                        local.get 3
+                        i32.const 224
+                        i32.add
+                        i32.load16_u offset=0 align=2
+                        local.get 15
+                        i32.mul
+                        local.tee 23
+                        i32.const 2
+                        i32.shr_u
+                        i32.const 15
+                        i32.and
+                        local.get 23
+                        i32.const 5
+                        i32.shr_u
+                        i32.const 127
+                        i32.and
+                        i32.mul
+                        local.get 3
+                        i32.const 192
+                        i32.add
+                        i32.load16_u offset=0 align=2
+                        local.get 16
+                        i32.mul
+                        local.tee 23
+                        i32.const 2
+                        i32.shr_u
+                        i32.const 15
+                        i32.and
+                        local.get 23
+                        i32.const 5
+                        i32.shr_u
+                        i32.const 127
+                        i32.and
+                        i32.mul
+                        local.get 3
+                        i32.const 160
+                        i32.add
+                        i32.load16_u offset=0 align=2
+                        local.get 17
+                        i32.mul
+                        local.tee 23
+                        i32.const 2
+                        i32.shr_u
+                        i32.const 15
+                        i32.and
+                        local.get 23
+                        i32.const 5
+                        i32.shr_u
+                        i32.const 127
+                        i32.and
+                        i32.mul
+                        local.get 3
+                        i32.const 128
+                        i32.add
+                        i32.load16_u offset=0 align=2
+                        local.get 18
+                        i32.mul
+                        local.tee 23
+                        i32.const 2
+                        i32.shr_u
+                        i32.const 15
+                        i32.and
+                        local.get 23
+                        i32.const 5
+                        i32.shr_u
+                        i32.const 127
+                        i32.and
+                        i32.mul
+                        local.get 3
+                        i32.const 96
+                        i32.add
+                        i32.load16_u offset=0 align=2
+                        local.get 19
+                        i32.mul
+                        local.tee 23
+                        i32.const 2
+                        i32.shr_u
+                        i32.const 15
+                        i32.and
+                        local.get 23
+                        i32.const 5
+                        i32.shr_u
+                        i32.const 127
+                        i32.and
+                        i32.mul
+                        local.get 3
+                        i32.const 64
+                        i32.add
+                        i32.load16_u offset=0 align=2
+                        local.get 20
+                        i32.mul
+                        local.tee 23
+                        i32.const 2
+                        i32.shr_u
+                        i32.const 15
+                        i32.and
+                        local.get 23
+                        i32.const 5
+                        i32.shr_u
+                        i32.const 127
+                        i32.and
+                        i32.mul
+                        local.get 3
+                        i32.const 32
+                        i32.add
+                        i32.load16_u offset=0 align=2
+                        local.get 4
+                        i32.mul
+                        local.tee 3
+                        i32.const 2
+                        i32.shr_u
+                        i32.const 15
+                        i32.and
+                        local.get 3
+                        i32.const 5
+                        i32.shr_u
+                        i32.const 127
+                        i32.and
+                        i32.mul
+                        i32.add
+                        i32.add
+                        i32.add
+                        i32.add
+                        i32.add
+                        i32.add
+                        i32.add
+                        i32.add
+                        i32.add
+                        i32.add
+                        i32.add
+                        i32.add
+                        i32.add
+                        i32.add
+                        i32.add
+                        i32.store offset=0 align=4
+                        local.get 22
+                        i32.const 4
+                        i32.add
+                        local.set 22
+                        local.get 21
+                        i32.const 2
+                        i32.add
+                        local.tee 21
+                        br_if 0
+                    end
+                    local.get 2
+                    i32.const 64
+                    i32.add
+                    local.set 2
+                    local.get 5
+                    i32.const 1
+                    i32.add
+                    local.tee 5
+                    i32.const 16
+                    i32.ne
+                    br_if 0
+                    br 2
+                end
+            end
+            loop
+                local.get 0
+                local.get 4
+                i32.const 5
+                i32.shl
+                i32.add
+                local.tee 3
+                i32.load16_s offset=30 align=2
+                local.set 23
+                local.get 3
+                i32.load16_s offset=28 align=2
+                local.set 6
+                local.get 3
+                i32.load16_s offset=26 align=2
+                local.set 7
+                local.get 3
+                i32.load16_s offset=24 align=2
+                local.set 8
+                local.get 3
+                i32.load16_s offset=22 align=2
+                local.set 9
+                local.get 3
+                i32.load16_s offset=20 align=2
+                local.set 10
+                local.get 3
+                i32.load16_s offset=18 align=2
+                local.set 11
+                local.get 3
+                i32.load16_s offset=16 align=2
+                local.set 12
+                local.get 3
+                i32.load16_s offset=14 align=2
+                local.set 13
+                local.get 3
+                i32.load16_s offset=12 align=2
+                local.set 14
+                local.get 3
+                i32.load16_s offset=10 align=2
+                local.set 15
+                local.get 3
+                i32.load16_s offset=8 align=2
+                local.set 16
+                local.get 3
+                i32.load16_s offset=6 align=2
+                local.set 17
+                local.get 3
+                i32.load16_s offset=4 align=2
+                local.set 18
+                local.get 3
+                i32.load16_s offset=2 align=2
+                local.set 19
+                local.get 3
+                i32.load16_s offset=0 align=2
+                local.set 20
+                local.get 2
+                local.set 21
+                i32.const 0
+                local.set 22
+                loop
+                    local.get 21
+                    local.get 1
+                    local.get 22
+                    i32.add
+                    local.tee 3
+                    i32.const 480
+                    i32.add
+                    i32.load16_s offset=0 align=2
+                    local.get 23
+                    i32.mul
+                    local.get 3
+                    i32.const 448
+                    i32.add
+                    i32.load16_s offset=0 align=2
+                    local.get 6
+                    i32.mul
+                    local.get 3
+                    i32.const 416
+                    i32.add
+                    i32.load16_s offset=0 align=2
+                    local.get 7
+                    i32.mul
+                    local.get 3
+                    i32.const 384
+                    i32.add
+                    i32.load16_s offset=0 align=2
+                    local.get 8
+                    i32.mul
+                    local.get 3
+                    i32.const 352
+                    i32.add
+                    i32.load16_s offset=0 align=2
+                    local.get 9
+                    i32.mul
+                    local.get 3
+                    i32.const 320
+                    i32.add
+                    i32.load16_s offset=0 align=2
+                    local.get 10
+                    i32.mul
+                    local.get 3
+                    i32.const 288
+                    i32.add
+                    i32.load16_s offset=0 align=2
+                    local.get 11
+                    i32.mul
+                    local.get 3
+                    i32.const 256
+                    i32.add
+                    i32.load16_s offset=0 align=2
+                    local.get 12
+                    i32.mul
+                    local.get 3
+                    i32.const 224
+                    i32.add
+                    i32.load16_s offset=0 align=2
+                    local.get 13
+                    i32.mul
+                    local.get 3
+                    i32.const 192
+                    i32.add
+                    i32.load16_s offset=0 align=2
+                    local.get 14
+                    i32.mul
+                    local.get 3
+                    i32.const 160
+                    i32.add
+                    i32.load16_s offset=0 align=2
+                    local.get 15
+                    i32.mul
+                    local.get 3
+                    i32.const 128
+                    i32.add
+                    i32.load16_s offset=0 align=2
+                    local.get 16
+                    i32.mul
+                    local.get 3
+                    i32.const 96
+                    i32.add
+                    i32.load16_s offset=0 align=2
+                    local.get 17
+                    i32.mul
+                    local.get 3
+                    i32.const 64
+                    i32.add
+                    i32.load16_s offset=0 align=2
+                    local.get 18
+                    i32.mul
+                    local.get 3
+                    i32.const 32
+                    i32.add
+                    i32.load16_s offset=0 align=2
+                    local.get 19
+                    i32.mul
+                    local.get 3
+                    i32.load16_s offset=0 align=2
+                    local.get 20
+                    i32.mul
+                    i32.add
+                    i32.add
+                    i32.add
+                    i32.add
+                    i32.add
+                    i32.add
+                    i32.add
+                    i32.add
+                    i32.add
+                    i32.add
+                    i32.add
+                    i32.add
+                    i32.add
+                    i32.add
+                    i32.add
+                    i32.store offset=0 align=4
+                    local.get 21
+                    i32.const 4
+                    i32.add
+                    local.set 21
+                    local.get 22
+                    i32.const 2
+                    i32.add
+                    local.tee 22
+                    i32.const 32
+                    i32.ne
+                    br_if 0
+                end
+                local.get 2
+                i32.const 64
+                i32.add
+                local.set 2
+                local.get 4
+                i32.const 1
+                i32.add
+                local.tee 4
+                i32.const 16
+                i32.ne
+                br_if 0
+            end
+        end
+    )
+    (func (;2;) (;type 2;) (param (;0;) i32) (param (;1;) i32) (result i32)
+        (local (;2;) i32)
+        local.get 1
+        i32.const 65534
+        i32.and
+        i32.const 1
+        i32.shr_u
+        local.tee 2
+        i32.const -24575
+        i32.xor
+        local.get 2
+        local.get 1
+        local.get 0
+        i32.xor
+        i32.const 1
+        i32.and
+        select
+        local.tee 1
+        i32.const 1
+        i32.shr_u
+        i32.const 32767
+        i32.and
+        local.tee 2
+        local.get 2
+        i32.const -24575
+        i32.xor
+        local.get 1
+        i32.const 1
+        i32.and
+        local.get 0
+        i32.const 1
+        i32.shr_u
+        i32.const 1
+        i32.and
+        i32.eq
+        select
+        local.tee 1
+        i32.const 1
+        i32.shr_u
+        i32.const 32767
+        i32.and
+        local.tee 2
+        local.get 2
+        i32.const -24575
+        i32.xor
+        local.get 1
+        i32.const 1
+        i32.and
+        local.get 0
+        i32.const 2
+        i32.shr_u
+        i32.const 1
+        i32.and
+        i32.eq
+        select
+        local.tee 1
+        i32.const 1
+        i32.shr_u
+        i32.const 32767
+        i32.and
+        local.tee 2
+        local.get 2
+        i32.const -24575
+        i32.xor
+        local.get 1
+        i32.const 1
+        i32.and
+        local.get 0
+        i32.const 3
+        i32.shr_u
+        i32.const 1
+        i32.and
+        i32.eq
+        select
+        local.tee 1
+        i32.const 1
+        i32.shr_u
+        i32.const 32767
+        i32.and
+        local.tee 2
+        local.get 2
+        i32.const -24575
+        i32.xor
+        local.get 1
+        i32.const 1
+        i32.and
+        local.get 0
+        i32.const 4
+        i32.shr_u
+        i32.const 1
+        i32.and
+        i32.eq
+        select
+        local.tee 1
+        i32.const 1
+        i32.shr_u
+        i32.const 32767
+        i32.and
+        local.tee 2
+        local.get 2
+        i32.const -24575
+        i32.xor
+        local.get 1
+        i32.const 1
+        i32.and
+        local.get 0
+        i32.const 5
+        i32.shr_u
+        i32.const 1
+        i32.and
+        i32.eq
+        select
+        local.tee 1
+        i32.const 1
+        i32.shr_u
+        i32.const 32767
+        i32.and
+        local.tee 2
+        local.get 2
+        i32.const -24575
+        i32.xor
+        local.get 1
+        i32.const 1
+        i32.and
+        local.get 0
+        i32.const 6
+        i32.shr_u
+        i32.const 1
+        i32.and
+        i32.eq
+        select
+        local.tee 1
+        i32.const 1
+        i32.shr_u
+        i32.const 32767
+        i32.and
+        local.tee 2
+        local.get 2
+        i32.const -24575
+        i32.xor
+        local.get 1
+        i32.const 1
+        i32.and
+        local.get 0
+        i32.const 128
+        i32.and
+        i32.const 7
+        i32.shr_u
+        i32.eq
+        select
+        local.tee 1
+        i32.const 1
+        i32.shr_u
+        i32.const 32767
+        i32.and
+        local.tee 2
+        i32.const -24575
+        i32.xor
+        local.get 2
+        local.get 1
+        local.get 0
+        i32.const 8
+        i32.shr_u
+        i32.xor
+        i32.const 1
+        i32.and
+        select
+        local.tee 1
+        i32.const 1
+        i32.shr_u
+        i32.const 32767
+        i32.and
+        local.tee 2
+        i32.const -24575
+        i32.xor
+        local.get 2
+        local.get 1
+        local.get 0
+        i32.const 9
+        i32.shr_u
+        i32.xor
+        i32.const 1
+        i32.and
+        select
+        local.tee 1
+        i32.const 1
+        i32.shr_u
+        i32.const 32767
+        i32.and
+        local.tee 2
+        i32.const -24575
+        i32.xor
+        local.get 2
+        local.get 1
+        local.get 0
+        i32.const 10
+        i32.shr_u
+        i32.xor
+        i32.const 1
+        i32.and
+        select
+        local.tee 1
+        i32.const 1
+        i32.shr_u
+        i32.const 32767
+        i32.and
+        local.tee 2
+        i32.const -24575
+        i32.xor
+        local.get 2
+        local.get 1
+        local.get 0
+        i32.const 11
+        i32.shr_u
+        i32.xor
+        i32.const 1
+        i32.and
+        select
+        local.tee 1
+        i32.const 1
+        i32.shr_u
+        i32.const 32767
+        i32.and
+        local.tee 2
+        i32.const -24575
+        i32.xor
+        local.get 2
+        local.get 1
+        local.get 0
+        i32.const 12
+        i32.shr_u
+        i32.xor
+        i32.const 1
+        i32.and
+        select
+        local.tee 1
+        i32.const 1
+        i32.shr_u
+        i32.const 32767
+        i32.and
+        local.tee 2
+        i32.const -24575
+        i32.xor
+        local.get 2
+        local.get 1
+        local.get 0
+        i32.const 13
+        i32.shr_u
+        i32.xor
+        i32.const 1
+        i32.and
+        select
+        local.tee 1
+        i32.const 1
+        i32.shr_u
+        i32.const 32767
+        i32.and
+        local.tee 2
+        i32.const -24575
+        i32.xor
+        local.get 2
+        local.get 1
+        local.get 0
+        i32.const 14
+        i32.shr_u
+        i32.xor
+        i32.const 1
+        i32.and
+        select
+        local.tee 1
+        i32.const 1
+        i32.shr_u
+        i32.const 32767
+        i32.and
+        local.tee 2
+        local.get 2
+        i32.const -24575
+        i32.xor
+        local.get 1
+        i32.const 1
+        i32.and
+        local.get 0
+        i32.const 32768
+        i32.and
+        i32.const 15
+        i32.shr_u
+        i32.eq
+        select
+    )
+    (func (;3;) (;type 3;) (param (;0;) i32) (param (;1;) i32) (param (;2;) i32) (result i32)
+        (local (;3;) i32)
+        (local (;4;) i32)
+        (local (;5;) i32)
+        (local (;6;) i32)
+        (local (;7;) i32)
+        (local (;8;) i32)
+        (local (;9;) i32)
+        (local (;10;) i32)
+        (local (;11;) i32)
+        i32.const 1
+        local.set 3
+        block
+            loop
+                local.get 1
+                i32.const -1
+                i32.eq
+                br_if 1
+                i32.const 0
+                local.set 4
+                i32.const -1
+                local.set 5
+                i32.const -1
+                local.set 6
+                loop
+                    local.get 1
+                    local.set 7
+                    i32.const 0
+                    local.set 8
+                    block
+                        block
+                            block
+                                local.get 3
+                                i32.const 1
+                                i32.lt_s
+                                br_if 0
+                                i32.const 0
+                                local.set 8
+                                local.get 7
+                                local.set 1
+                                loop
+                                    local.get 1
+                                    i32.const 64
+                                    i32.ge_u
+                                    br_if 2
+                                    local.get 0
+                                    local.get 1
+                                    i32.const 3
+                                    i32.shl
+                                    i32.add
+                                    i32.load offset=0 align=4
+                                    local.set 1
+                                    local.get 8
+                                    i32.const 1
+                                    i32.add
+                                    local.tee 8
+                                    local.get 3
+                                    i32.ge_s
+                                    br_if 1
+                                    local.get 1
+                                    i32.const -1
+                                    i32.ne
+                                    br_if 0
+                                end
+                            end
+                            local.get 4
+                            i32.const 1
+                            i32.add
+                            local.set 4
+                            local.get 3
+                            local.set 9
+                            loop
+                                block
+                                    block
+                                        block
+                                            block
+                                                block
+                                                    block
+                                                        block
+                                                            block
+                                                                block
+                                                                    block
+                                                                        local.get 8
+                                                                        i32.const 0
+                                                                        i32.gt_s
+                                                                        br_if 0
+                                                                        local.get 9
+                                                                        i32.const 1
+                                                                        i32.lt_s
+                                                                        br_if 12
+                                                                        local.get 1
+                                                                        i32.const -1
+                                                                        i32.eq
+                                                                        br_if 12
+                                                                        local.get 8
+                                                                        i32.eqz
+                                                                        br_if 1
+                                                                    end
+                                                                    local.get 9
+                                                                    i32.eqz
+                                                                    br_if 2
+                                                                    local.get 1
+                                                                    i32.const -1
+                                                                    i32.eq
+                                                                    br_if 2
+                                                                    local.get 7
+                                                                    i32.const 64
+                                                                    i32.ge_u
+                                                                    br_if 5
+                                                                    block
+                                                                        block
+                                                                            block
+                                                                                local.get 2
+                                                                                br_if 0
+                                                                                local.get 1
+                                                                                i32.const 64
+                                                                                i32.ge_u
+                                                                                br_if 9
+                                                                                i32.const 4
+                                                                                local.set 10
+                                                                                br 1
+                                                                            end
+                                                                            local.get 1
+                                                                            i32.const 64
+                                                                            i32.ge_u
+                                                                            br_if 1
+                                                                            i32.const 6
+                                                                            local.set 10
+                                                                        end
+                                                                        local.get 0
+                                                                        local.get 7
+                                                                        i32.const 3
+                                                                        i32.shl
+                                                                        i32.add
+                                                                        local.get 10
+                                                                        i32.add
+                                                                        i32.load16_s offset=0 align=2
+                                                                        local.get 0
+                                                                        local.get 1
+                                                                        i32.const 3
+                                                                        i32.shl
+                                                                        i32.add
+                                                                        local.get 10
+                                                                        i32.add
+                                                                        i32.load16_s offset=0 align=2
+                                                                        i32.gt_s
+                                                                        br_if 2
+                                                                        br 4
+                                                                    end
+                                                                    local.get 1
+                                                                    i32.const 64
+                                                                    call 4
+                                                                    unreachable
+                                                                end
+                                                                local.get 1
+                                                                i32.const 64
+                                                                i32.ge_u
+                                                                br_if 6
+                                                            end
+                                                            local.get 9
+                                                            i32.const -1
+                                                            i32.add
+                                                            local.set 9
+                                                            local.get 0
+                                                            local.get 1
+                                                            i32.const 3
+                                                            i32.shl
+                                                            i32.add
+                                                            i32.load offset=0 align=4
+                                                            local.set 11
+                                                            local.get 1
+                                                            local.set 10
+                                                            br 6
+                                                        end
+                                                        local.get 7
+                                                        i32.const 64
+                                                        i32.ge_u
+                                                        br_if 1
+                                                    end
+                                                    local.get 8
+                                                    i32.const -1
+                                                    i32.add
+                                                    local.set 8
+                                                    local.get 7
+                                                    local.set 10
+                                                    local.get 1
+                                                    local.set 11
+                                                    local.get 0
+                                                    local.get 7
+                                                    i32.const 3
+                                                    i32.shl
+                                                    i32.add
+                                                    i32.load offset=0 align=4
+                                                    local.set 7
+                                                    br 4
+                                                end
+                                                local.get 7
+                                                i32.const 64
+                                                call 4
+                                                unreachable
+                                            end
+                                            local.get 7
+                                            i32.const 64
+                                            call 4
+                                            unreachable
+                                        end
+                                        local.get 1
+                                        i32.const 64
+                                        call 4
+                                        unreachable
+                                    end
+                                    local.get 1
+                                    i32.const 64
+                                    call 4
+                                    unreachable
+                                end
+                                block
+                                    local.get 6
+                                    i32.const -1
+                                    i32.eq
+                                    br_if 0
+                                    local.get 0
+                                    local.get 6
+                                    i32.const 3
+                                    i32.shl
+                                    i32.add
+                                    local.get 10
+                                    i32.store offset=0 align=4
+                                    local.get 11
+                                    local.set 1
+                                    local.get 10
+                                    local.set 6
+                                    br 1
+                                end
+                                local.get 11
+                                local.set 1
+                                local.get 10
+                                local.set 6
+                                local.get 10
+                                local.set 5
+                                br 0
+                            end
+                        end
+                        local.get 1
+                        i32.const 64
+                        call 4
+                        unreachable
+                    end
+                    local.get 1
+                    i32.const -1
+                    i32.ne
+                    br_if 0
+                end
+                local.get 6
+                i32.const 64
+                i32.ge_u
+                br_if 1
+                local.get 0
+                local.get 6
+                i32.const 3
+                i32.shl
+                i32.add
+                i32.const -1
+                i32.store offset=0 align=4
+                local.get 3
+                i32.const 1
+                i32.shl
+                local.set 3
+                local.get 5
+                local.set 1
+                local.get 4
+                i32.const 2
+                i32.ge_s
+                br_if 0
+            end
+            local.get 5
+            return
+        end
+        i32.const -1
+        i32.const 64
+        call 4
+        unreachable
+    )
+    (func (;4;) (;type 4;) (param (;0;) i32) (param (;1;) i32)
+        call 7
+        unreachable
+    )
+    (func (;5;) (;type 5;) (param (;0;) i32) (result i32)
+        (local (;1;) i32)
+        (local (;2;) i32)
+        (local (;3;) i32)
+        (local (;4;) i32)
+        (local (;5;) i32)
+        (local (;6;) i32)
+        (local (;7;) i32)
+        (local (;8;) i32)
+        (local (;9;) i32)
+        (local (;10;) i32)
+        (local (;11;) i32)
+        (local (;12;) i32)
+        (local (;13;) i32)
+        (local (;14;) i32)
+        (local (;15;) i32)
+        (local (;16;) i32)
+        (local (;17;) i32)
+        (local (;18;) i32)
+        (local (;19;) i32)
+        (local (;20;) i32)
+        (local (;21;) i32)
+        (local (;22;) i32)
+        (local (;23;) i32)
+        (local (;24;) i32)
+        (local (;25;) i32)
+        (local (;26;) i32)
+        (local (;27;) i32)
+        (local (;28;) i32)
+        (local (;29;) i32)
+        (local (;30;) i32)
+        (local (;31;) i32)
+        (local (;32;) i32)
+        (local (;33;) i32)
+        (local (;34;) i32)
+        (local (;35;) i32)
+        (local (;36;) i32)
+        (local (;37;) i32)
+        global.get 0
+        i32.const 2048
+        i32.sub
+        local.tee 1
+        global.set 0
+        block
+            block
+                local.get 0
+                br_if 0
+                i32.const 0
+                local.set 2
+                br 1
+            end
+            i32.const 204
+            local.set 3
+            i32.const 102
+            local.set 4
+            i32.const 0
+            local.set 5
+            i32.const 0
+            local.set 6
+            block
+                block
+                    block
+                        block
+                            block
+                                loop
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1528 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1520 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1512 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1504 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1496 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1488 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1480 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1472 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1464 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1456 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1448 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1440 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1432 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1424 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1416 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1408 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1400 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1392 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1384 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1376 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1368 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1360 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1352 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1344 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1336 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1328 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1320 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1312 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1304 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1296 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1288 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1280 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1272 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1264 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1256 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1248 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1240 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1232 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1224 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1216 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1208 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1200 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1192 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1184 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1176 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1168 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1160 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1152 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1144 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1136 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1128 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1120 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1112 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1104 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1096 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1088 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1080 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1072 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1064 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1056 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1048 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1040 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1032 align=4
+                                    local.get 1
+                                    i64.const 4294967295
+                                    i64.store offset=1024 align=4
+                                    local.get 6
+                                    i32.const 499
+                                    i32.mul
+                                    local.tee 7
+                                    i32.const 102
+                                    i32.add
+                                    local.set 8
+                                    i32.const 1
+                                    local.set 2
+                                    i32.const 0
+                                    local.set 9
+                                    loop
+                                        local.get 1
+                                        i32.const 1024
+                                        i32.add
+                                        local.get 9
+                                        i32.add
+                                        local.tee 10
+                                        local.get 2
+                                        i32.store offset=0 align=4
+                                        local.get 10
+                                        i32.const 14
+                                        i32.add
+                                        local.get 2
+                                        i32.store16 offset=0 align=2
+                                        local.get 10
+                                        i32.const 8
+                                        i32.add
+                                        local.get 2
+                                        i32.const 1
+                                        i32.add
+                                        i32.const -1
+                                        local.get 2
+                                        i32.const 63
+                                        i32.lt_u
+                                        select
+                                        i32.store offset=0 align=4
+                                        local.get 10
+                                        i32.const 6
+                                        i32.add
+                                        local.get 2
+                                        i32.const -1
+                                        i32.add
+                                        i32.store16 offset=0 align=2
+                                        local.get 10
+                                        i32.const 12
+                                        i32.add
+                                        local.get 2
+                                        i32.const 61
+                                        i32.mul
+                                        local.tee 11
+                                        local.get 8
+                                        i32.xor
+                                        i32.const 255
+                                        i32.and
+                                        i32.store16 offset=0 align=2
+                                        local.get 10
+                                        i32.const 4
+                                        i32.add
+                                        local.get 11
+                                        i32.const -61
+                                        i32.add
+                                        local.get 8
+                                        i32.xor
+                                        i32.const 255
+                                        i32.and
+                                        i32.store16 offset=0 align=2
+                                        local.get 2
+                                        i32.const 2
+                                        i32.add
+                                        local.set 2
+                                        local.get 9
+                                        i32.const 16
+                                        i32.add
+                                        local.tee 9
+                                        i32.const 512
+                                        i32.ne
+                                        br_if 0
+                                    end
+                                    local.get 7
+                                    i32.const 103
+                                    i32.add
+                                    local.set 12
+                                    local.get 6
+                                    i32.const 1
+                                    i32.add
+                                    local.set 6
+                                    i32.const 0
+                                    local.set 13
+                                    i32.const 0
+                                    local.set 2
+                                    i32.const 0
+                                    local.set 14
+                                    i32.const 0
+                                    local.set 15
+                                    i32.const 0
+                                    local.set 7
+                                    loop
+                                        local.get 13
+                                        i32.const 1
+                                        i32.add
+                                        local.set 16
+                                        local.get 13
+                                        local.get 12
+                                        i32.mul
+                                        i32.const 255
+                                        i32.and
+                                        local.set 9
+                                        local.get 2
+                                        local.set 10
+                                        block
+                                            block
+                                                block
+                                                    loop
+                                                        local.get 10
+                                                        i32.const 63
+                                                        i32.gt_u
+                                                        br_if 2
+                                                        local.get 1
+                                                        i32.const 1024
+                                                        i32.add
+                                                        local.get 10
+                                                        i32.const 3
+                                                        i32.shl
+                                                        i32.add
+                                                        local.tee 10
+                                                        i32.load16_u offset=4 align=2
+                                                        local.get 9
+                                                        i32.eq
+                                                        br_if 1
+                                                        local.get 10
+                                                        i32.load offset=0 align=4
+                                                        local.tee 10
+                                                        i32.const -1
+                                                        i32.ne
+                                                        br_if 0
+                                                    end
+                                                    local.get 14
+                                                    i32.const 1
+                                                    i32.add
+                                                    local.set 14
+                                                    br 2
+                                                end
+                                                local.get 7
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 9
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 9
+                                                local.get 10
+                                                i32.load16_u offset=6 align=2
+                                                local.tee 10
+                                                local.get 7
+                                                i32.xor
+                                                i32.const 1
+                                                i32.and
+                                                select
+                                                local.tee 9
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                local.get 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 9
+                                                i32.const 1
+                                                i32.and
+                                                local.get 10
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 1
+                                                i32.and
+                                                i32.eq
+                                                select
+                                                local.tee 9
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                local.get 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 9
+                                                i32.const 1
+                                                i32.and
+                                                local.get 10
+                                                i32.const 2
+                                                i32.shr_u
+                                                i32.const 1
+                                                i32.and
+                                                i32.eq
+                                                select
+                                                local.tee 9
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                local.get 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 9
+                                                i32.const 1
+                                                i32.and
+                                                local.get 10
+                                                i32.const 3
+                                                i32.shr_u
+                                                i32.const 1
+                                                i32.and
+                                                i32.eq
+                                                select
+                                                local.tee 9
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                local.get 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 9
+                                                i32.const 1
+                                                i32.and
+                                                local.get 10
+                                                i32.const 4
+                                                i32.shr_u
+                                                i32.const 1
+                                                i32.and
+                                                i32.eq
+                                                select
+                                                local.tee 9
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                local.get 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 9
+                                                i32.const 1
+                                                i32.and
+                                                local.get 10
+                                                i32.const 5
+                                                i32.shr_u
+                                                i32.const 1
+                                                i32.and
+                                                i32.eq
+                                                select
+                                                local.tee 9
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                local.get 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 9
+                                                i32.const 1
+                                                i32.and
+                                                local.get 10
+                                                i32.const 6
+                                                i32.shr_u
+                                                i32.const 1
+                                                i32.and
+                                                i32.eq
+                                                select
+                                                local.tee 9
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                local.get 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 9
+                                                i32.const 1
+                                                i32.and
+                                                local.get 10
+                                                i32.const 128
+                                                i32.and
+                                                i32.const 7
+                                                i32.shr_u
+                                                i32.eq
+                                                select
+                                                local.tee 9
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 11
+                                                local.get 9
+                                                local.get 10
+                                                i32.const 8
+                                                i32.shr_u
+                                                i32.xor
+                                                i32.const 1
+                                                i32.and
+                                                select
+                                                local.tee 9
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 11
+                                                local.get 9
+                                                local.get 10
+                                                i32.const 9
+                                                i32.shr_u
+                                                i32.xor
+                                                i32.const 1
+                                                i32.and
+                                                select
+                                                local.tee 9
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 11
+                                                local.get 9
+                                                local.get 10
+                                                i32.const 10
+                                                i32.shr_u
+                                                i32.xor
+                                                i32.const 1
+                                                i32.and
+                                                select
+                                                local.tee 9
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 11
+                                                local.get 9
+                                                local.get 10
+                                                i32.const 11
+                                                i32.shr_u
+                                                i32.xor
+                                                i32.const 1
+                                                i32.and
+                                                select
+                                                local.tee 9
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 11
+                                                local.get 9
+                                                local.get 10
+                                                i32.const 12
+                                                i32.shr_u
+                                                i32.xor
+                                                i32.const 1
+                                                i32.and
+                                                select
+                                                local.tee 9
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 11
+                                                local.get 9
+                                                local.get 10
+                                                i32.const 13
+                                                i32.shr_u
+                                                i32.xor
+                                                i32.const 1
+                                                i32.and
+                                                select
+                                                local.tee 9
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 11
+                                                local.get 9
+                                                local.get 10
+                                                i32.const 14
+                                                i32.shr_u
+                                                i32.xor
+                                                i32.const 1
+                                                i32.and
+                                                select
+                                                local.tee 9
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                local.get 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 9
+                                                i32.const 1
+                                                i32.and
+                                                local.get 10
+                                                i32.const 15
+                                                i32.shr_u
+                                                i32.eq
+                                                select
+                                                local.set 7
+                                                local.get 15
+                                                i32.const 1
+                                                i32.add
+                                                local.set 15
+                                                br 1
+                                            end
+                                            local.get 10
+                                            i32.const 64
+                                            call 4
+                                            unreachable
+                                        end
+                                        i32.const -1
+                                        local.set 9
+                                        loop
+                                            local.get 2
+                                            local.tee 10
+                                            i32.const 64
+                                            i32.ge_u
+                                            br_if 4
+                                            local.get 1
+                                            i32.const 1024
+                                            i32.add
+                                            local.get 10
+                                            i32.const 3
+                                            i32.shl
+                                            i32.add
+                                            local.tee 11
+                                            i32.load offset=0 align=4
+                                            local.set 2
+                                            local.get 11
+                                            local.get 9
+                                            i32.store offset=0 align=4
+                                            local.get 10
+                                            local.set 9
+                                            local.get 2
+                                            i32.const -1
+                                            i32.ne
+                                            br_if 0
+                                        end
+                                        local.get 13
+                                        i32.const 65535
+                                        i32.and
+                                        local.set 9
+                                        local.get 16
+                                        local.set 13
+                                        local.get 10
+                                        local.set 2
+                                        local.get 9
+                                        i32.const 15
+                                        i32.lt_u
+                                        br_if 0
+                                    end
+                                    block
+                                        block
+                                            local.get 1
+                                            i32.const 1024
+                                            i32.add
+                                            local.get 10
+                                            i32.const 0
+                                            call 3
+                                            local.tee 13
+                                            i32.const -1
+                                            i32.ne
+                                            br_if 0
+                                            i32.const -1
+                                            local.set 13
+                                            br 1
+                                        end
+                                        local.get 13
+                                        local.set 2
+                                        loop
+                                            local.get 2
+                                            i32.const 63
+                                            i32.gt_u
+                                            br_if 3
+                                            local.get 7
+                                            i32.const 1
+                                            i32.shr_u
+                                            i32.const 32767
+                                            i32.and
+                                            local.tee 10
+                                            i32.const -24575
+                                            i32.xor
+                                            local.get 10
+                                            local.get 1
+                                            i32.const 1024
+                                            i32.add
+                                            local.get 2
+                                            i32.const 3
+                                            i32.shl
+                                            i32.add
+                                            local.tee 9
+                                            i32.load16_u offset=4 align=2
+                                            local.tee 2
+                                            local.get 7
+                                            i32.xor
+                                            i32.const 1
+                                            i32.and
+                                            select
+                                            local.tee 10
+                                            i32.const 1
+                                            i32.shr_u
+                                            i32.const 32767
+                                            i32.and
+                                            local.tee 11
+                                            local.get 11
+                                            i32.const -24575
+                                            i32.xor
+                                            local.get 10
+                                            i32.const 1
+                                            i32.and
+                                            local.get 2
+                                            i32.const 1
+                                            i32.shr_u
+                                            i32.const 1
+                                            i32.and
+                                            i32.eq
+                                            select
+                                            local.tee 10
+                                            i32.const 1
+                                            i32.shr_u
+                                            i32.const 32767
+                                            i32.and
+                                            local.tee 11
+                                            local.get 11
+                                            i32.const -24575
+                                            i32.xor
+                                            local.get 10
+                                            i32.const 1
+                                            i32.and
+                                            local.get 2
+                                            i32.const 2
+                                            i32.shr_u
+                                            i32.const 1
+                                            i32.and
+                                            i32.eq
+                                            select
+                                            local.tee 10
+                                            i32.const 1
+                                            i32.shr_u
+                                            i32.const 32767
+                                            i32.and
+                                            local.tee 11
+                                            local.get 11
+                                            i32.const -24575
+                                            i32.xor
+                                            local.get 10
+                                            i32.const 1
+                                            i32.and
+                                            local.get 2
+                                            i32.const 3
+                                            i32.shr_u
+                                            i32.const 1
+                                            i32.and
+                                            i32.eq
+                                            select
+                                            local.tee 10
+                                            i32.const 1
+                                            i32.shr_u
+                                            i32.const 32767
+                                            i32.and
+                                            local.tee 11
+                                            local.get 11
+                                            i32.const -24575
+                                            i32.xor
+                                            local.get 10
+                                            i32.const 1
+                                            i32.and
+                                            local.get 2
+                                            i32.const 4
+                                            i32.shr_u
+                                            i32.const 1
+                                            i32.and
+                                            i32.eq
+                                            select
+                                            local.tee 10
+                                            i32.const 1
+                                            i32.shr_u
+                                            i32.const 32767
+                                            i32.and
+                                            local.tee 11
+                                            local.get 11
+                                            i32.const -24575
+                                            i32.xor
+                                            local.get 10
+                                            i32.const 1
+                                            i32.and
+                                            local.get 2
+                                            i32.const 5
+                                            i32.shr_u
+                                            i32.const 1
+                                            i32.and
+                                            i32.eq
+                                            select
+                                            local.tee 10
+                                            i32.const 1
+                                            i32.shr_u
+                                            i32.const 32767
+                                            i32.and
+                                            local.tee 11
+                                            local.get 11
+                                            i32.const -24575
+                                            i32.xor
+                                            local.get 10
+                                            i32.const 1
+                                            i32.and
+                                            local.get 2
+                                            i32.const 6
+                                            i32.shr_u
+                                            i32.const 1
+                                            i32.and
+                                            i32.eq
+                                            select
+                                            local.tee 10
+                                            i32.const 1
+                                            i32.shr_u
+                                            i32.const 32767
+                                            i32.and
+                                            local.tee 11
+                                            local.get 11
+                                            i32.const -24575
+                                            i32.xor
+                                            local.get 10
+                                            i32.const 1
+                                            i32.and
+                                            local.get 2
+                                            i32.const 128
+                                            i32.and
+                                            i32.const 7
+                                            i32.shr_u
+                                            i32.eq
+                                            select
+                                            local.tee 10
+                                            i32.const 1
+                                            i32.shr_u
+                                            i32.const 32767
+                                            i32.and
+                                            local.tee 11
+                                            i32.const -24575
+                                            i32.xor
+                                            local.get 11
+                                            local.get 10
+                                            local.get 2
+                                            i32.const 8
+                                            i32.shr_u
+                                            i32.xor
+                                            i32.const 1
+                                            i32.and
+                                            select
+                                            local.tee 10
+                                            i32.const 1
+                                            i32.shr_u
+                                            i32.const 32767
+                                            i32.and
+                                            local.tee 11
+                                            i32.const -24575
+                                            i32.xor
+                                            local.get 11
+                                            local.get 10
+                                            local.get 2
+                                            i32.const 9
+                                            i32.shr_u
+                                            i32.xor
+                                            i32.const 1
+                                            i32.and
+                                            select
+                                            local.tee 10
+                                            i32.const 1
+                                            i32.shr_u
+                                            i32.const 32767
+                                            i32.and
+                                            local.tee 11
+                                            i32.const -24575
+                                            i32.xor
+                                            local.get 11
+                                            local.get 10
+                                            local.get 2
+                                            i32.const 10
+                                            i32.shr_u
+                                            i32.xor
+                                            i32.const 1
+                                            i32.and
+                                            select
+                                            local.tee 10
+                                            i32.const 1
+                                            i32.shr_u
+                                            i32.const 32767
+                                            i32.and
+                                            local.tee 11
+                                            i32.const -24575
+                                            i32.xor
+                                            local.get 11
+                                            local.get 10
+                                            local.get 2
+                                            i32.const 11
+                                            i32.shr_u
+                                            i32.xor
+                                            i32.const 1
+                                            i32.and
+                                            select
+                                            local.tee 10
+                                            i32.const 1
+                                            i32.shr_u
+                                            i32.const 32767
+                                            i32.and
+                                            local.tee 11
+                                            i32.const -24575
+                                            i32.xor
+                                            local.get 11
+                                            local.get 10
+                                            local.get 2
+                                            i32.const 12
+                                            i32.shr_u
+                                            i32.xor
+                                            i32.const 1
+                                            i32.and
+                                            select
+                                            local.tee 10
+                                            i32.const 1
+                                            i32.shr_u
+                                            i32.const 32767
+                                            i32.and
+                                            local.tee 11
+                                            i32.const -24575
+                                            i32.xor
+                                            local.get 11
+                                            local.get 10
+                                            local.get 2
+                                            i32.const 13
+                                            i32.shr_u
+                                            i32.xor
+                                            i32.const 1
+                                            i32.and
+                                            select
+                                            local.tee 10
+                                            i32.const 1
+                                            i32.shr_u
+                                            i32.const 32767
+                                            i32.and
+                                            local.tee 11
+                                            i32.const -24575
+                                            i32.xor
+                                            local.get 11
+                                            local.get 10
+                                            local.get 2
+                                            i32.const 14
+                                            i32.shr_u
+                                            i32.xor
+                                            i32.const 1
+                                            i32.and
+                                            select
+                                            local.tee 10
+                                            i32.const 1
+                                            i32.shr_u
+                                            i32.const 32767
+                                            i32.and
+                                            local.tee 11
+                                            local.get 11
+                                            i32.const -24575
+                                            i32.xor
+                                            local.get 10
+                                            i32.const 1
+                                            i32.and
+                                            local.get 2
+                                            i32.const 15
+                                            i32.shr_u
+                                            i32.eq
+                                            select
+                                            local.set 7
+                                            local.get 9
+                                            i32.load offset=0 align=4
+                                            local.tee 2
+                                            i32.const -1
+                                            i32.ne
+                                            br_if 0
+                                        end
+                                    end
+                                    block
+                                        block
+                                            local.get 1
+                                            i32.const 1024
+                                            i32.add
+                                            local.get 13
+                                            i32.const 1
+                                            call 3
+                                            local.tee 2
+                                            i32.const -1
+                                            i32.eq
+                                            br_if 0
+                                            loop
+                                                local.get 2
+                                                i32.const 63
+                                                i32.gt_u
+                                                br_if 2
+                                                local.get 7
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 10
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 10
+                                                local.get 1
+                                                i32.const 1024
+                                                i32.add
+                                                local.get 2
+                                                i32.const 3
+                                                i32.shl
+                                                i32.add
+                                                local.tee 9
+                                                i32.load16_u offset=6 align=2
+                                                local.tee 2
+                                                local.get 7
+                                                i32.xor
+                                                i32.const 1
+                                                i32.and
+                                                select
+                                                local.tee 10
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                local.get 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 10
+                                                i32.const 1
+                                                i32.and
+                                                local.get 2
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 1
+                                                i32.and
+                                                i32.eq
+                                                select
+                                                local.tee 10
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                local.get 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 10
+                                                i32.const 1
+                                                i32.and
+                                                local.get 2
+                                                i32.const 2
+                                                i32.shr_u
+                                                i32.const 1
+                                                i32.and
+                                                i32.eq
+                                                select
+                                                local.tee 10
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                local.get 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 10
+                                                i32.const 1
+                                                i32.and
+                                                local.get 2
+                                                i32.const 3
+                                                i32.shr_u
+                                                i32.const 1
+                                                i32.and
+                                                i32.eq
+                                                select
+                                                local.tee 10
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                local.get 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 10
+                                                i32.const 1
+                                                i32.and
+                                                local.get 2
+                                                i32.const 4
+                                                i32.shr_u
+                                                i32.const 1
+                                                i32.and
+                                                i32.eq
+                                                select
+                                                local.tee 10
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                local.get 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 10
+                                                i32.const 1
+                                                i32.and
+                                                local.get 2
+                                                i32.const 5
+                                                i32.shr_u
+                                                i32.const 1
+                                                i32.and
+                                                i32.eq
+                                                select
+                                                local.tee 10
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                local.get 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 10
+                                                i32.const 1
+                                                i32.and
+                                                local.get 2
+                                                i32.const 6
+                                                i32.shr_u
+                                                i32.const 1
+                                                i32.and
+                                                i32.eq
+                                                select
+                                                local.tee 10
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                local.get 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 10
+                                                i32.const 1
+                                                i32.and
+                                                local.get 2
+                                                i32.const 128
+                                                i32.and
+                                                i32.const 7
+                                                i32.shr_u
+                                                i32.eq
+                                                select
+                                                local.tee 10
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 11
+                                                local.get 10
+                                                local.get 2
+                                                i32.const 8
+                                                i32.shr_u
+                                                i32.xor
+                                                i32.const 1
+                                                i32.and
+                                                select
+                                                local.tee 10
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 11
+                                                local.get 10
+                                                local.get 2
+                                                i32.const 9
+                                                i32.shr_u
+                                                i32.xor
+                                                i32.const 1
+                                                i32.and
+                                                select
+                                                local.tee 10
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 11
+                                                local.get 10
+                                                local.get 2
+                                                i32.const 10
+                                                i32.shr_u
+                                                i32.xor
+                                                i32.const 1
+                                                i32.and
+                                                select
+                                                local.tee 10
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 11
+                                                local.get 10
+                                                local.get 2
+                                                i32.const 11
+                                                i32.shr_u
+                                                i32.xor
+                                                i32.const 1
+                                                i32.and
+                                                select
+                                                local.tee 10
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 11
+                                                local.get 10
+                                                local.get 2
+                                                i32.const 12
+                                                i32.shr_u
+                                                i32.xor
+                                                i32.const 1
+                                                i32.and
+                                                select
+                                                local.tee 10
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 11
+                                                local.get 10
+                                                local.get 2
+                                                i32.const 13
+                                                i32.shr_u
+                                                i32.xor
+                                                i32.const 1
+                                                i32.and
+                                                select
+                                                local.tee 10
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 11
+                                                local.get 10
+                                                local.get 2
+                                                i32.const 14
+                                                i32.shr_u
+                                                i32.xor
+                                                i32.const 1
+                                                i32.and
+                                                select
+                                                local.tee 10
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 32767
+                                                i32.and
+                                                local.tee 11
+                                                local.get 11
+                                                i32.const -24575
+                                                i32.xor
+                                                local.get 10
+                                                i32.const 1
+                                                i32.and
+                                                local.get 2
+                                                i32.const 15
+                                                i32.shr_u
+                                                i32.eq
+                                                select
+                                                local.set 7
+                                                local.get 9
+                                                i32.load offset=0 align=4
+                                                local.tee 2
+                                                i32.const -1
+                                                i32.ne
+                                                br_if 0
+                                            end
+                                        end
+                                        local.get 1
+                                        i32.const 0
+                                        i32.const 512
+                                        memory.fill
+                                        local.get 1
+                                        i32.const 512
+                                        i32.add
+                                        i32.const 0
+                                        i32.const 512
+                                        memory.fill
+                                        local.get 1
+                                        i32.const 1024
+                                        i32.add
+                                        i32.const 0
+                                        i32.const 1024
+                                        memory.fill
+                                        i32.const 4
+                                        local.set 2
+                                        i32.const 2
+                                        local.set 10
+                                        local.get 4
+                                        local.set 9
+                                        local.get 3
+                                        local.set 11
+                                        loop
+                                            local.get 1
+                                            i32.const 512
+                                            i32.add
+                                            local.get 2
+                                            i32.add
+                                            local.tee 13
+                                            i32.const -2
+                                            i32.add
+                                            local.get 10
+                                            local.get 11
+                                            i32.add
+                                            i32.const 255
+                                            i32.and
+                                            i32.store16 offset=0 align=2
+                                            local.get 1
+                                            local.get 2
+                                            i32.add
+                                            local.tee 16
+                                            i32.const -2
+                                            i32.add
+                                            local.get 2
+                                            local.get 11
+                                            i32.add
+                                            i32.const 255
+                                            i32.and
+                                            i32.store16 offset=0 align=2
+                                            local.get 16
+                                            i32.const -4
+                                            i32.add
+                                            local.get 9
+                                            local.get 2
+                                            i32.add
+                                            i32.const -2
+                                            i32.add
+                                            i32.const 255
+                                            i32.and
+                                            i32.store16 offset=0 align=2
+                                            local.get 13
+                                            i32.const -4
+                                            i32.add
+                                            local.get 9
+                                            local.get 10
+                                            i32.add
+                                            i32.const -1
+                                            i32.add
+                                            i32.const 255
+                                            i32.and
+                                            i32.store16 offset=0 align=2
+                                            local.get 9
+                                            local.get 3
+                                            i32.add
+                                            local.set 9
+                                            local.get 10
+                                            i32.const 2
+                                            i32.add
+                                            local.set 10
+                                            local.get 11
+                                            local.get 3
+                                            i32.add
+                                            local.set 11
+                                            local.get 2
+                                            i32.const 4
+                                            i32.add
+                                            local.tee 2
+                                            i32.const 516
+                                            i32.ne
+                                            br_if 0
+                                        end
+                                        local.get 8
+                                        i32.const -4096
+                                        i32.or
+                                        local.set 2
+                                        i32.const 0
+                                        local.set 9
+                                        loop
+                                            local.get 1
+                                            local.get 9
+                                            i32.add
+                                            local.tee 10
+                                            local.get 10
+                                            i32.load16_u offset=0 align=2
+                                            local.get 2
+                                            i32.add
+                                            i32.store16 offset=0 align=2
+                                            local.get 10
+                                            i32.const 2
+                                            i32.add
+                                            local.tee 11
+                                            local.get 11
+                                            i32.load16_u offset=0 align=2
+                                            local.get 2
+                                            i32.add
+                                            i32.store16 offset=0 align=2
+                                            local.get 10
+                                            i32.const 4
+                                            i32.add
+                                            local.tee 11
+                                            local.get 11
+                                            i32.load16_u offset=0 align=2
+                                            local.get 2
+                                            i32.add
+                                            i32.store16 offset=0 align=2
+                                            local.get 10
+                                            i32.const 6
+                                            i32.add
+                                            local.tee 11
+                                            local.get 11
+                                            i32.load16_u offset=0 align=2
+                                            local.get 2
+                                            i32.add
+                                            i32.store16 offset=0 align=2
+                                            local.get 10
+                                            i32.const 8
+                                            i32.add
+                                            local.tee 11
+                                            local.get 11
+                                            i32.load16_u offset=0 align=2
+                                            local.get 2
+                                            i32.add
+                                            i32.store16 offset=0 align=2
+                                            local.get 10
+                                            i32.const 10
+                                            i32.add
+                                            local.tee 11
+                                            local.get 11
+                                            i32.load16_u offset=0 align=2
+                                            local.get 2
+                                            i32.add
+                                            i32.store16 offset=0 align=2
+                                            local.get 10
+                                            i32.const 12
+                                            i32.add
+                                            local.tee 11
+                                            local.get 11
+                                            i32.load16_u offset=0 align=2
+                                            local.get 2
+                                            i32.add
+                                            i32.store16 offset=0 align=2
+                                            local.get 10
+                                            i32.const 14
+                                            i32.add
+                                            local.tee 10
+                                            local.get 10
+                                            i32.load16_u offset=0 align=2
+                                            local.get 2
+                                            i32.add
+                                            i32.store16 offset=0 align=2
+                                            local.get 9
+                                            i32.const 16
+                                            i32.add
+                                            local.tee 9
+                                            i32.const 512
+                                            i32.ne
+                                            br_if 0
+                                        end
+                                        i32.const 0
+                                        local.set 11
+                                        i32.const 0
+                                        local.set 13
+                                        loop
+                                            local.get 1
+                                            i32.const 1024
+                                            i32.add
+                                            local.get 13
+                                            i32.add
+                                            local.tee 10
+                                            local.get 1
+                                            local.get 11
+                                            i32.add
+                                            local.tee 9
+                                            i32.load16_s offset=0 align=2
+                                            local.get 2
+                                            i32.mul
+                                            i32.store offset=0 align=4
+                                            local.get 10
+                                            i32.const 4
+                                            i32.add
+                                            local.get 9
+                                            i32.const 2
+                                            i32.add
+                                            i32.load16_s offset=0 align=2
+                                            local.get 2
+                                            i32.mul
+                                            i32.store offset=0 align=4
+                                            local.get 10
+                                            i32.const 8
+                                            i32.add
+                                            local.get 9
+                                            i32.const 4
+                                            i32.add
+                                            i32.load16_s offset=0 align=2
+                                            local.get 2
+                                            i32.mul
+                                            i32.store offset=0 align=4
+                                            local.get 10
+                                            i32.const 12
+                                            i32.add
+                                            local.get 9
+                                            i32.const 6
+                                            i32.add
+                                            i32.load16_s offset=0 align=2
+                                            local.get 2
+                                            i32.mul
+                                            i32.store offset=0 align=4
+                                            local.get 13
+                                            i32.const 16
+                                            i32.add
+                                            local.set 13
+                                            local.get 11
+                                            i32.const 8
+                                            i32.add
+                                            local.tee 11
+                                            i32.const 512
+                                            i32.ne
+                                            br_if 0
+                                        end
+                                        local.get 2
+                                        i32.const 4
+                                        i32.shl
+                                        local.set 9
+                                        i32.const 0
+                                        local.set 12
+                                        i32.const 0
+                                        local.set 16
+                                        i32.const 0
+                                        local.set 17
+                                        i32.const 0
+                                        local.set 10
+                                        loop
+                                            i32.const 0
+                                            local.get 1
+                                            i32.const 1024
+                                            i32.add
+                                            local.get 10
+                                            i32.add
+                                            local.tee 13
+                                            i32.const 4
+                                            i32.add
+                                            i32.load offset=0 align=4
+                                            local.tee 11
+                                            i32.const 0
+                                            local.get 13
+                                            i32.load offset=0 align=4
+                                            local.tee 13
+                                            local.get 17
+                                            i32.add
+                                            local.tee 17
+                                            local.get 17
+                                            local.get 9
+                                            i32.gt_s
+                                            local.tee 18
+                                            select
+                                            i32.add
+                                            local.tee 17
+                                            local.get 17
+                                            local.get 9
+                                            i32.gt_s
+                                            local.tee 19
+                                            select
+                                            local.set 17
+                                            i32.const 10
+                                            local.get 11
+                                            local.get 13
+                                            i32.gt_s
+                                            local.get 19
+                                            select
+                                            i32.const 10
+                                            local.get 13
+                                            local.get 12
+                                            i32.gt_s
+                                            local.get 18
+                                            select
+                                            local.get 16
+                                            i32.add
+                                            i32.add
+                                            local.set 16
+                                            local.get 11
+                                            local.set 12
+                                            local.get 10
+                                            i32.const 8
+                                            i32.add
+                                            local.tee 10
+                                            i32.const 1024
+                                            i32.ne
+                                            br_if 0
+                                        end
+                                        local.get 1
+                                        i32.const 1024
+                                        i32.add
+                                        local.set 11
+                                        i32.const 0
+                                        local.set 13
+                                        local.get 1
+                                        i32.load16_s offset=542 align=2
+                                        local.set 12
+                                        local.get 1
+                                        i32.load16_s offset=540 align=2
+                                        local.set 17
+                                        local.get 1
+                                        i32.load16_s offset=538 align=2
+                                        local.set 18
+                                        local.get 1
+                                        i32.load16_s offset=536 align=2
+                                        local.set 19
+                                        local.get 1
+                                        i32.load16_s offset=534 align=2
+                                        local.set 20
+                                        local.get 1
+                                        i32.load16_s offset=532 align=2
+                                        local.set 21
+                                        local.get 1
+                                        i32.load16_s offset=530 align=2
+                                        local.set 22
+                                        local.get 1
+                                        i32.load16_s offset=528 align=2
+                                        local.set 23
+                                        local.get 1
+                                        i32.load16_s offset=526 align=2
+                                        local.set 24
+                                        local.get 1
+                                        i32.load16_s offset=524 align=2
+                                        local.set 25
+                                        local.get 1
+                                        i32.load16_s offset=522 align=2
+                                        local.set 26
+                                        local.get 1
+                                        i32.load16_s offset=520 align=2
+                                        local.set 27
+                                        local.get 1
+                                        i32.load16_s offset=518 align=2
+                                        local.set 28
+                                        local.get 1
+                                        i32.load16_s offset=516 align=2
+                                        local.set 29
+                                        local.get 1
+                                        i32.load16_s offset=514 align=2
+                                        local.set 30
+                                        local.get 1
+                                        i32.load16_s offset=512 align=2
+                                        local.set 31
+                                        loop
+                                            local.get 11
+                                            local.get 1
+                                            local.get 13
+                                            i32.add
+                                            local.tee 10
+                                            i32.const 2
+                                            i32.add
+                                            i32.load16_s offset=0 align=2
+                                            local.get 30
+                                            i32.mul
+                                            local.get 10
+                                            i32.load16_s offset=0 align=2
+                                            local.get 31
+                                            i32.mul
+                                            i32.add
+                                            local.get 10
+                                            i32.const 4
+                                            i32.add
+                                            i32.load16_s offset=0 align=2
+                                            local.get 29
+                                            i32.mul
+                                            i32.add
+                                            local.get 10
+                                            i32.const 6
+                                            i32.add
+                                            i32.load16_s offset=0 align=2
+                                            local.get 28
+                                            i32.mul
+                                            i32.add
+                                            local.get 10
+                                            i32.const 8
+                                            i32.add
+                                            i32.load16_s offset=0 align=2
+                                            local.get 27
+                                            i32.mul
+                                            i32.add
+                                            local.get 10
+                                            i32.const 10
+                                            i32.add
+                                            i32.load16_s offset=0 align=2
+                                            local.get 26
+                                            i32.mul
+                                            i32.add
+                                            local.get 10
+                                            i32.const 12
+                                            i32.add
+                                            i32.load16_s offset=0 align=2
+                                            local.get 25
+                                            i32.mul
+                                            i32.add
+                                            local.get 10
+                                            i32.const 14
+                                            i32.add
+                                            i32.load16_s offset=0 align=2
+                                            local.get 24
+                                            i32.mul
+                                            i32.add
+                                            local.get 10
+                                            i32.const 16
+                                            i32.add
+                                            i32.load16_s offset=0 align=2
+                                            local.get 23
+                                            i32.mul
+                                            i32.add
+                                            local.get 10
+                                            i32.const 18
+                                            i32.add
+                                            i32.load16_s offset=0 align=2
+                                            local.get 22
+                                            i32.mul
+                                            i32.add
+                                            local.get 10
+                                            i32.const 20
+                                            i32.add
+                                            i32.load16_s offset=0 align=2
+                                            local.get 21
+                                            i32.mul
+                                            i32.add
+                                            local.get 10
+                                            i32.const 22
+                                            i32.add
+                                            i32.load16_s offset=0 align=2
+                                            local.get 20
+                                            i32.mul
+                                            i32.add
+                                            local.get 10
+                                            i32.const 24
+                                            i32.add
+                                            i32.load16_s offset=0 align=2
+                                            local.get 19
+                                            i32.mul
+                                            i32.add
+                                            local.get 10
+                                            i32.const 26
+                                            i32.add
+                                            i32.load16_s offset=0 align=2
+                                            local.get 18
+                                            i32.mul
+                                            i32.add
+                                            local.get 10
+                                            i32.const 28
+                                            i32.add
+                                            i32.load16_s offset=0 align=2
+                                            local.get 17
+                                            i32.mul
+                                            i32.add
+                                            local.get 10
+                                            i32.const 30
+                                            i32.add
+                                            i32.load16_s offset=0 align=2
+                                            local.get 12
+                                            i32.mul
+                                            i32.add
+                                            i32.store offset=0 align=4
+                                            local.get 11
+                                            i32.const 4
+                                            i32.add
+                                            local.set 11
+                                            local.get 13
+                                            i32.const 32
+                                            i32.add
+                                            local.tee 13
+                                            i32.const 512
+                                            i32.ne
+                                            br_if 0
+                                        end
+                                        local.get 1
+                                        i32.load offset=1084 align=4
+                                        local.set 32
+                                        local.get 1
+                                        i32.load offset=1080 align=4
+                                        local.set 23
+                                        local.get 1
+                                        i32.load offset=1076 align=4
+                                        local.set 24
+                                        local.get 1
+                                        i32.load offset=1072 align=4
+                                        local.set 25
+                                        local.get 1
+                                        i32.load offset=1068 align=4
+                                        local.set 26
+                                        local.get 1
+                                        i32.load offset=1064 align=4
+                                        local.set 27
+                                        local.get 1
+                                        i32.load offset=1060 align=4
+                                        local.set 28
+                                        local.get 1
+                                        i32.load offset=1056 align=4
+                                        local.set 29
+                                        local.get 1
+                                        i32.load offset=1052 align=4
+                                        local.set 30
+                                        local.get 1
+                                        i32.load offset=1048 align=4
+                                        local.set 31
+                                        local.get 1
+                                        i32.load offset=1044 align=4
+                                        local.set 33
+                                        local.get 1
+                                        i32.load offset=1040 align=4
+                                        local.set 34
+                                        local.get 1
+                                        i32.load offset=1036 align=4
+                                        local.set 35
+                                        local.get 1
+                                        i32.load offset=1032 align=4
+                                        local.set 36
+                                        local.get 1
+                                        i32.load offset=1028 align=4
+                                        local.set 37
+                                        local.get 1
+                                        i32.load offset=1024 align=4
+                                        local.set 22
+                                        i32.const 0
+                                        local.set 17
+                                        local.get 1
+                                        local.get 1
+                                        i32.const 512
+                                        i32.add
+                                        local.get 1
+                                        i32.const 1024
+                                        i32.add
+                                        i32.const 0
+                                        call 1
+                                        i32.const 0
+                                        local.set 12
+                                        i32.const 0
+                                        local.set 18
+                                        i32.const 0
+                                        local.set 10
+                                        loop
+                                            i32.const 0
+                                            local.get 1
+                                            i32.const 1024
+                                            i32.add
+                                            local.get 10
+                                            i32.add
+                                            local.tee 13
+                                            i32.const 4
+                                            i32.add
+                                            i32.load offset=0 align=4
+                                            local.tee 11
+                                            i32.const 0
+                                            local.get 13
+                                            i32.load offset=0 align=4
+                                            local.tee 13
+                                            local.get 18
+                                            i32.add
+                                            local.tee 18
+                                            local.get 18
+                                            local.get 9
+                                            i32.gt_s
+                                            local.tee 19
+                                            select
+                                            i32.add
+                                            local.tee 18
+                                            local.get 18
+                                            local.get 9
+                                            i32.gt_s
+                                            local.tee 20
+                                            select
+                                            local.set 18
+                                            i32.const 10
+                                            local.get 11
+                                            local.get 13
+                                            i32.gt_s
+                                            local.get 20
+                                            select
+                                            i32.const 10
+                                            local.get 13
+                                            local.get 17
+                                            i32.gt_s
+                                            local.get 19
+                                            select
+                                            local.get 12
+                                            i32.add
+                                            i32.add
+                                            local.set 12
+                                            local.get 11
+                                            local.set 17
+                                            local.get 10
+                                            i32.const 8
+                                            i32.add
+                                            local.tee 10
+                                            i32.const 1024
+                                            i32.ne
+                                            br_if 0
+                                        end
+                                        local.get 1
+                                        local.get 1
+                                        i32.const 512
+                                        i32.add
+                                        local.get 1
+                                        i32.const 1024
+                                        i32.add
+                                        i32.const 1
+                                        call 1
+                                        i32.const 0
+                                        local.set 18
+                                        i32.const 0
+                                        local.set 17
+                                        i32.const 0
+                                        local.set 19
+                                        i32.const 0
+                                        local.set 10
+                                        loop
+                                            i32.const 0
+                                            local.get 1
+                                            i32.const 1024
+                                            i32.add
+                                            local.get 10
+                                            i32.add
+                                            local.tee 13
+                                            i32.const 4
+                                            i32.add
+                                            i32.load offset=0 align=4
+                                            local.tee 11
+                                            i32.const 0
+                                            local.get 13
+                                            i32.load offset=0 align=4
+                                            local.tee 13
+                                            local.get 19
+                                            i32.add
+                                            local.tee 19
+                                            local.get 19
+                                            local.get 9
+                                            i32.gt_s
+                                            local.tee 20
+                                            select
+                                            i32.add
+                                            local.tee 19
+                                            local.get 19
+                                            local.get 9
+                                            i32.gt_s
+                                            local.tee 21
+                                            select
+                                            local.set 19
+                                            i32.const 10
+                                            local.get 11
+                                            local.get 13
+                                            i32.gt_s
+                                            local.get 21
+                                            select
+                                            i32.const 10
+                                            local.get 13
+                                            local.get 18
+                                            i32.gt_s
+                                            local.get 20
+                                            select
+                                            local.get 17
+                                            i32.add
+                                            i32.add
+                                            local.set 17
+                                            local.get 11
+                                            local.set 18
+                                            local.get 10
+                                            i32.const 8
+                                            i32.add
+                                            local.tee 10
+                                            i32.const 1024
+                                            i32.ne
+                                            br_if 0
+                                        end
+                                        i32.const 0
+                                        local.set 11
+                                        loop
+                                            local.get 1
+                                            local.get 11
+                                            i32.add
+                                            local.tee 10
+                                            local.get 10
+                                            i32.load16_u offset=0 align=2
+                                            local.get 2
+                                            i32.sub
+                                            i32.store16 offset=0 align=2
+                                            local.get 10
+                                            i32.const 2
+                                            i32.add
+                                            local.tee 13
+                                            local.get 13
+                                            i32.load16_u offset=0 align=2
+                                            local.get 2
+                                            i32.sub
+                                            i32.store16 offset=0 align=2
+                                            local.get 10
+                                            i32.const 4
+                                            i32.add
+                                            local.tee 13
+                                            local.get 13
+                                            i32.load16_u offset=0 align=2
+                                            local.get 2
+                                            i32.sub
+                                            i32.store16 offset=0 align=2
+                                            local.get 10
+                                            i32.const 6
+                                            i32.add
+                                            local.tee 13
+                                            local.get 13
+                                            i32.load16_u offset=0 align=2
+                                            local.get 2
+                                            i32.sub
+                                            i32.store16 offset=0 align=2
+                                            local.get 10
+                                            i32.const 8
+                                            i32.add
+                                            local.tee 13
+                                            local.get 13
+                                            i32.load16_u offset=0 align=2
+                                            local.get 2
+                                            i32.sub
+                                            i32.store16 offset=0 align=2
+                                            local.get 10
+                                            i32.const 10
+                                            i32.add
+                                            local.tee 13
+                                            local.get 13
+                                            i32.load16_u offset=0 align=2
+                                            local.get 2
+                                            i32.sub
+                                            i32.store16 offset=0 align=2
+                                            local.get 10
+                                            i32.const 12
+                                            i32.add
+                                            local.tee 13
+                                            local.get 13
+                                            i32.load16_u offset=0 align=2
+                                            local.get 2
+                                            i32.sub
+                                            i32.store16 offset=0 align=2
+                                            local.get 10
+                                            i32.const 14
+                                            i32.add
+                                            local.tee 10
+                                            local.get 10
+                                            i32.load16_u offset=0 align=2
+                                            local.get 2
+                                            i32.sub
+                                            i32.store16 offset=0 align=2
+                                            local.get 11
+                                            i32.const 16
+                                            i32.add
+                                            local.tee 11
+                                            i32.const 512
+                                            i32.ne
+                                            br_if 0
+                                        end
+                                        local.get 15
+                                        local.get 14
+                                        local.get 7
+                                        call 2
+                                        call 2
+                                        local.get 5
+                                        call 2
+                                        local.set 2
+                                        local.get 1
+                                        i32.load16_u offset=510 align=2
+                                        local.get 1
+                                        i32.load16_u offset=0 align=2
+                                        i32.xor
+                                        local.get 17
+                                        local.get 12
+                                        i32.const 10
+                                        local.get 37
+                                        local.get 22
+                                        i32.gt_s
+                                        i32.const 0
+                                        local.get 22
+                                        local.get 22
+                                        local.get 9
+                                        i32.gt_s
+                                        local.tee 10
+                                        select
+                                        local.get 37
+                                        i32.add
+                                        local.tee 11
+                                        local.get 9
+                                        i32.gt_s
+                                        local.tee 7
+                                        select
+                                        i32.const 10
+                                        local.get 22
+                                        i32.const 0
+                                        i32.gt_s
+                                        local.get 10
+                                        select
+                                        i32.add
+                                        i32.const 10
+                                        local.get 36
+                                        local.get 37
+                                        i32.gt_s
+                                        i32.const 0
+                                        local.get 11
+                                        local.get 7
+                                        select
+                                        local.get 36
+                                        i32.add
+                                        local.tee 10
+                                        local.get 9
+                                        i32.gt_s
+                                        local.tee 11
+                                        select
+                                        i32.add
+                                        i32.const 10
+                                        local.get 35
+                                        local.get 36
+                                        i32.gt_s
+                                        i32.const 0
+                                        local.get 10
+                                        local.get 11
+                                        select
+                                        local.get 35
+                                        i32.add
+                                        local.tee 10
+                                        local.get 9
+                                        i32.gt_s
+                                        local.tee 11
+                                        select
+                                        i32.add
+                                        i32.const 10
+                                        local.get 34
+                                        local.get 35
+                                        i32.gt_s
+                                        i32.const 0
+                                        local.get 10
+                                        local.get 11
+                                        select
+                                        local.get 34
+                                        i32.add
+                                        local.tee 10
+                                        local.get 9
+                                        i32.gt_s
+                                        local.tee 11
+                                        select
+                                        i32.add
+                                        i32.const 10
+                                        local.get 33
+                                        local.get 34
+                                        i32.gt_s
+                                        i32.const 0
+                                        local.get 10
+                                        local.get 11
+                                        select
+                                        local.get 33
+                                        i32.add
+                                        local.tee 10
+                                        local.get 9
+                                        i32.gt_s
+                                        local.tee 11
+                                        select
+                                        i32.add
+                                        i32.const 10
+                                        local.get 31
+                                        local.get 33
+                                        i32.gt_s
+                                        i32.const 0
+                                        local.get 10
+                                        local.get 11
+                                        select
+                                        local.get 31
+                                        i32.add
+                                        local.tee 10
+                                        local.get 9
+                                        i32.gt_s
+                                        local.tee 11
+                                        select
+                                        i32.add
+                                        i32.const 10
+                                        local.get 30
+                                        local.get 31
+                                        i32.gt_s
+                                        i32.const 0
+                                        local.get 10
+                                        local.get 11
+                                        select
+                                        local.get 30
+                                        i32.add
+                                        local.tee 10
+                                        local.get 9
+                                        i32.gt_s
+                                        local.tee 11
+                                        select
+                                        i32.add
+                                        i32.const 10
+                                        local.get 29
+                                        local.get 30
+                                        i32.gt_s
+                                        i32.const 0
+                                        local.get 10
+                                        local.get 11
+                                        select
+                                        local.get 29
+                                        i32.add
+                                        local.tee 10
+                                        local.get 9
+                                        i32.gt_s
+                                        local.tee 11
+                                        select
+                                        i32.add
+                                        i32.const 10
+                                        local.get 28
+                                        local.get 29
+                                        i32.gt_s
+                                        i32.const 0
+                                        local.get 10
+                                        local.get 11
+                                        select
+                                        local.get 28
+                                        i32.add
+                                        local.tee 10
+                                        local.get 9
+                                        i32.gt_s
+                                        local.tee 11
+                                        select
+                                        i32.add
+                                        i32.const 10
+                                        local.get 27
+                                        local.get 28
+                                        i32.gt_s
+                                        i32.const 0
+                                        local.get 10
+                                        local.get 11
+                                        select
+                                        local.get 27
+                                        i32.add
+                                        local.tee 10
+                                        local.get 9
+                                        i32.gt_s
+                                        local.tee 11
+                                        select
+                                        i32.add
+                                        i32.const 10
+                                        local.get 26
+                                        local.get 27
+                                        i32.gt_s
+                                        i32.const 0
+                                        local.get 10
+                                        local.get 11
+                                        select
+                                        local.get 26
+                                        i32.add
+                                        local.tee 10
+                                        local.get 9
+                                        i32.gt_s
+                                        local.tee 11
+                                        select
+                                        i32.add
+                                        i32.const 10
+                                        local.get 25
+                                        local.get 26
+                                        i32.gt_s
+                                        i32.const 0
+                                        local.get 10
+                                        local.get 11
+                                        select
+                                        local.get 25
+                                        i32.add
+                                        local.tee 10
+                                        local.get 9
+                                        i32.gt_s
+                                        local.tee 11
+                                        select
+                                        i32.add
+                                        i32.const 10
+                                        local.get 24
+                                        local.get 25
+                                        i32.gt_s
+                                        i32.const 0
+                                        local.get 10
+                                        local.get 11
+                                        select
+                                        local.get 24
+                                        i32.add
+                                        local.tee 10
+                                        local.get 9
+                                        i32.gt_s
+                                        local.tee 11
+                                        select
+                                        i32.add
+                                        i32.const 10
+                                        local.get 23
+                                        local.get 24
+                                        i32.gt_s
+                                        i32.const 0
+                                        local.get 10
+                                        local.get 11
+                                        select
+                                        local.get 23
+                                        i32.add
+                                        local.tee 10
+                                        local.get 9
+                                        i32.gt_s
+                                        local.tee 11
+                                        select
+                                        i32.add
+                                        i32.const 10
+                                        local.get 32
+                                        local.get 23
+                                        i32.gt_s
+                                        i32.const 0
+                                        local.get 10
+                                        local.get 11
+                                        select
+                                        local.get 32
+                                        i32.add
+                                        local.get 9
+                                        i32.gt_s
+                                        select
+                                        i32.add
+                                        local.get 16
+                                        i32.const 0
+                                        call 2
+                                        call 2
+                                        call 2
+                                        call 2
+                                        call 2
+                                        local.get 2
+                                        call 2
+                                        local.set 13
+                                        local.get 1
+                                        i32.const 1024
+                                        i32.add
+                                        i32.const 44
+                                        i32.const 256
+                                        memory.fill
+                                        block
+                                            local.get 8
+                                            i32.const 65535
+                                            i32.and
+                                            i32.const 5
+                                            i32.mul
+                                            i32.const 3
+                                            i32.add
+                                            local.tee 11
+                                            i32.const 1
+                                            i32.shr_u
+                                            i32.const 120
+                                            i32.and
+                                            local.tee 2
+                                            i32.load offset=1048692 align=4
+                                            local.tee 9
+                                            i32.const -256
+                                            i32.add
+                                            i32.const -257
+                                            i32.lt_u
+                                            br_if 0
+                                            local.get 2
+                                            i32.const 1048688
+                                            i32.add
+                                            local.set 7
+                                            i32.const 0
+                                            local.set 2
+                                            local.get 9
+                                            local.set 10
+                                            loop
+                                                local.get 9
+                                                i32.const 256
+                                                i32.gt_u
+                                                br_if 6
+                                                local.get 9
+                                                local.get 2
+                                                i32.lt_u
+                                                br_if 6
+                                                block
+                                                    local.get 10
+                                                    i32.eqz
+                                                    br_if 0
+                                                    local.get 1
+                                                    i32.const 1024
+                                                    i32.add
+                                                    local.get 2
+                                                    i32.add
+                                                    local.get 7
+                                                    i32.load offset=0 align=4
+                                                    local.get 10
+                                                    memory.copy
+                                                end
+                                                local.get 10
+                                                local.get 2
+                                                i32.add
+                                                local.set 2
+                                                local.get 11
+                                                i32.const 5
+                                                i32.mul
+                                                i32.const 3
+                                                i32.add
+                                                local.tee 11
+                                                i32.const 1
+                                                i32.shr_u
+                                                i32.const 120
+                                                i32.and
+                                                local.tee 10
+                                                i32.const 1048688
+                                                i32.add
+                                                local.set 7
+                                                local.get 10
+                                                i32.load offset=1048692 align=4
+                                                local.tee 10
+                                                local.get 2
+                                                i32.const 1
+                                                i32.add
+                                                local.tee 2
+                                                i32.add
+                                                local.tee 9
+                                                i32.const -256
+                                                i32.add
+                                                i32.const -257
+                                                i32.ge_u
+                                                br_if 0
+                                            end
+                                        end
+                                        local.get 1
+                                        i64.const 0
+                                        i64.store offset=8 align=8
+                                        local.get 1
+                                        i64.const 0
+                                        i64.store offset=0 align=8
+                                        local.get 1
+                                        i64.const 0
+                                        i64.store offset=520 align=8
+                                        local.get 1
+                                        i64.const 0
+                                        i64.store offset=512 align=8
+                                        i32.const 256
+                                        local.get 8
+                                        i32.const 7
+                                        i32.and
+                                        i32.const 1
+                                        i32.add
+                                        local.tee 7
+                                        i32.div_u
+                                        local.tee 2
+                                        local.get 7
+                                        i32.mul
+                                        i32.const 256
+                                        i32.ne
+                                        local.get 2
+                                        i32.add
+                                        local.set 10
+                                        local.get 1
+                                        i32.const 1024
+                                        i32.add
+                                        local.get 1
+                                        local.get 1
+                                        i32.const 512
+                                        i32.add
+                                        call 0
+                                        i32.const 0
+                                        local.set 2
+                                        loop
+                                            local.get 2
+                                            i32.const 255
+                                            i32.gt_u
+                                            br_if 6
+                                            block
+                                                local.get 1
+                                                i32.const 1024
+                                                i32.add
+                                                local.get 2
+                                                i32.add
+                                                local.tee 9
+                                                i32.load8_u offset=0 align=1
+                                                local.tee 11
+                                                i32.const 44
+                                                i32.eq
+                                                br_if 0
+                                                local.get 9
+                                                local.get 11
+                                                local.get 8
+                                                i32.xor
+                                                i32.store8 offset=0 align=1
+                                            end
+                                            local.get 2
+                                            local.get 7
+                                            i32.add
+                                            local.set 2
+                                            local.get 10
+                                            i32.const -1
+                                            i32.add
+                                            local.tee 10
+                                            br_if 0
+                                        end
+                                        local.get 4
+                                        i32.const 499
+                                        i32.add
+                                        local.set 4
+                                        local.get 3
+                                        i32.const 998
+                                        i32.add
+                                        local.set 3
+                                        local.get 1
+                                        i32.const 1024
+                                        i32.add
+                                        local.get 1
+                                        local.get 1
+                                        i32.const 512
+                                        i32.add
+                                        call 0
+                                        local.get 1
+                                        i32.load16_u offset=526 align=2
+                                        local.get 1
+                                        i32.load16_u offset=14 align=2
+                                        local.get 1
+                                        i32.load16_u offset=524 align=2
+                                        local.get 1
+                                        i32.load16_u offset=12 align=2
+                                        local.get 1
+                                        i32.load16_u offset=522 align=2
+                                        local.get 1
+                                        i32.load16_u offset=10 align=2
+                                        local.get 1
+                                        i32.load16_u offset=520 align=2
+                                        local.get 1
+                                        i32.load16_u offset=8 align=2
+                                        local.get 1
+                                        i32.load16_u offset=518 align=2
+                                        local.get 1
+                                        i32.load16_u offset=6 align=2
+                                        local.get 1
+                                        i32.load16_u offset=516 align=2
+                                        local.get 1
+                                        i32.load16_u offset=4 align=2
+                                        local.get 1
+                                        i32.load16_u offset=514 align=2
+                                        local.get 1
+                                        i32.load16_u offset=2 align=2
+                                        local.get 1
+                                        i32.load16_u offset=512 align=2
+                                        local.get 1
+                                        i32.load16_u offset=0 align=2
+                                        i32.const 0
+                                        call 2
+                                        call 2
+                                        call 2
+                                        call 2
+                                        call 2
+                                        call 2
+                                        call 2
+                                        call 2
+                                        call 2
+                                        call 2
+                                        call 2
+                                        call 2
+                                        call 2
+                                        call 2
+                                        call 2
+                                        call 2
+                                        local.get 13
+                                        call 2
+                                        local.set 5
+                                        local.get 6
+                                        local.get 0
+                                        i32.eq
+                                        br_if 6
+                                        br 1
+                                    end
+                                end
+                                local.get 2
+                                i32.const 64
+                                call 4
+                                unreachable
+                            end
+                            local.get 2
+                            i32.const 64
+                            call 4
+                            unreachable
+                        end
+                        local.get 10
+                        i32.const 64
+                        call 4
+                        unreachable
+                    end
+                    local.get 2
+                    local.get 9
+                    call 6
+                    unreachable
+                end
+                local.get 2
+                i32.const 256
+                call 4
+                unreachable
+            end
+            local.get 5
+            i32.const 65535
+            i32.and
+            local.set 2
+        end
+        local.get 1
+        i32.const 2048
+        i32.add
+        global.set 0
+        local.get 2
+    )
+    (func (;6;) (;type 4;) (param (;0;) i32) (param (;1;) i32)
+        call 7
+        unreachable
+    )
+    (func (;7;) (;type 6;)
+        unreachable
+    )
+    (data (;0;) (offset (i32.const 1048576)) "\35\30\31\32\31\32\33\34\2d\38\37\34\2b\31\32\32\33\35\2e\35\34\34\30\30\2e\31\32\33\34\35\30\30\2d\31\31\30\2e\37\30\30\2b\30\2e\36\34\34\30\30\35\2e\35\30\30\65\2b\33\2d\2e\31\32\33\65\2d\32\2d\38\37\65\2b\38\33\32\2b\30\2e\36\65\2d\31\32\54\30\2e\33\65\2d\31\46\2d\54\2e\54\2b\2b\54\71\31\54\33\2e\34\65\34\7a\33\34\2e\30\65\2d\54\5e\00\00\10\00\04\00\00\00\04\00\10\00\04\00\00\00\08\00\10\00\04\00\00\00\0c\00\10\00\04\00\00\00\10\00\10\00\08\00\00\00\18\00\10\00\08\00\00\00\20\00\10\00\08\00\00\00\28\00\10\00\08\00\00\00\30\00\10\00\08\00\00\00\38\00\10\00\08\00\00\00\40\00\10\00\08\00\00\00\48\00\10\00\08\00\00\00\50\00\10\00\08\00\00\00\58\00\10\00\08\00\00\00\60\00\10\00\08\00\00\00\68\00\10\00\08\00\00\00")
+)
\ No newline at end of file
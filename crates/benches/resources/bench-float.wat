@@ -0,0 +1,104 @@
+(module
+    ;; 浮点数运算基准测试
+
+    ;; 0
+    ;; 计算 size x size 个采样点的 Mandelbrot 集合，
+    ;; 返回迭代 64 次之后仍未发散的点的数量
+    (func $mandelbrot (export "mandelbrot") (param $size i32) (result i32)
+        (local $x i32)
+        (local $y i32)
+        (local $iteration i32)
+        (local $count i32)
+        (local $scale f64)
+        (local $cr f64)
+        (local $ci f64)
+        (local $zr f64)
+        (local $zi f64)
+        (local $zr2 f64)
+        (local $zi2 f64)
+        (local.set $scale (f64.div (f64.const 1) (f64.convert_i32_u (local.get $size))))
+        (block $row_done
+            (loop $row_next
+                (br_if $row_done (i32.ge_u (local.get $y) (local.get $size)))
+                (local.set $ci
+                    (f64.sub
+                        (f64.mul
+                            (f64.mul (f64.convert_i32_u (local.get $y)) (local.get $scale))
+                            (f64.const 2))
+                        (f64.const 1)))
+                (local.set $x (i32.const 0))
+                (block $column_done
+                    (loop $column_next
+                        (br_if $column_done (i32.ge_u (local.get $x) (local.get $size)))
+                        (local.set $cr
+                            (f64.sub
+                                (f64.mul
+                                    (f64.mul (f64.convert_i32_u (local.get $x)) (local.get $scale))
+                                    (f64.const 3))
+                                (f64.const 2)))
+                        (local.set $zr (f64.const 0))
+                        (local.set $zi (f64.const 0))
+                        (local.set $zr2 (f64.const 0))
+                        (local.set $zi2 (f64.const 0))
+                        (local.set $iteration (i32.const 0))
+                        (block $escaped
+                            (loop $iterate
+                                (br_if $escaped
+                                    (f64.gt
+                                        (f64.add (local.get $zr2) (local.get $zi2))
+                                        (f64.const 4)))
+                                (local.set $zi
+                                    (f64.add
+                                        (f64.mul
+                                            (f64.mul (local.get $zr) (local.get $zi))
+                                            (f64.const 2))
+                                        (local.get $ci)))
+                                (local.set $zr
+                                    (f64.add
+                                        (f64.sub (local.get $zr2) (local.get $zi2))
+                                        (local.get $cr)))
+                                (local.set $zr2 (f64.mul (local.get $zr) (local.get $zr)))
+                                (local.set $zi2 (f64.mul (local.get $zi) (local.get $zi)))
+                                (local.set $iteration
+                                    (i32.add (local.get $iteration) (i32.const 1)))
+                                (br_if $iterate (i32.lt_u (local.get $iteration) (i32.const 64)))
+                            )
+                        )
+                        (if (i32.eq (local.get $iteration) (i32.const 64))
+                            (then
+                                (local.set $count (i32.add (local.get $count) (i32.const 1)))
+                            )
+                        )
+                        (local.set $x (i32.add (local.get $x) (i32.const 1)))
+                        (br $column_next)
+                    )
+                )
+                (local.set $y (i32.add (local.get $y) (i32.const 1)))
+                (br $row_next)
+            )
+        )
+        (local.get $count)
+    )
+
+    ;; 1
+    ;; 计算级数 sqrt(1)/1 + sqrt(2)/2 + ... + sqrt(n)/n，
+    ;; 其中每一项先以 f32 计算平方根再转换为 f64
+    (func $series (export "series") (param $n i32) (result f64)
+        (local $i i32)
+        (local $term f64)
+        (local $acc f64)
+        (block $done
+            (loop $next
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br_if $done (i32.gt_u (local.get $i) (local.get $n)))
+                (local.set $term
+                    (f64.div
+                        (f64.promote_f32 (f32.sqrt (f32.convert_i32_u (local.get $i))))
+                        (f64.convert_i32_u (local.get $i))))
+                (local.set $acc (f64.add (local.get $acc) (local.get $term)))
+                (br $next)
+            )
+        )
+        (local.get $acc)
+    )
+)
@@ -0,0 +1,285 @@
+(module
+    ;; 整数运算基准测试，参照 CoreMark 的几种工作负载：
+    ;; 矩阵乘法、链表反转与遍历、状态机以及 CRC 校验。
+    ;; 各项运算的中间结果都不会溢出 i32，当轮数 n 不超过 2^16 时。
+    ;;
+    ;; 内存布局：
+    ;; - 0:    矩阵 A，16 x 16 个 i32
+    ;; - 1024: 矩阵 B
+    ;; - 2048: 矩阵 C = A x B
+    ;; - 4096: 链表的 64 个节点，每个节点为 (next: i32, value: i32)
+    (memory 1)
+
+    ;; 0
+    ;; 执行 n 轮，每轮的各项结果累加到 CRC 校验值，返回最终的校验值
+    (func $run (export "run") (param $n i32) (result i32)
+        (local $i i32)
+        (local $crc i32)
+        (block $done
+            (loop $next
+                (br_if $done (i32.ge_u (local.get $i) (local.get $n)))
+                (local.set $crc (call $crc16 (call $matrix (local.get $i)) (local.get $crc)))
+                (local.set $crc (call $crc16 (call $list (local.get $i)) (local.get $crc)))
+                (local.set $crc (call $crc16 (call $state (local.get $i)) (local.get $crc)))
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br $next)
+            )
+        )
+        (local.get $crc)
+    )
+
+    ;; 1
+    ;; 按位计算 8 位数据的 CRC-16
+    (func $crc8 (param $data i32) (param $crc i32) (result i32)
+        (local $i i32)
+        (local $carry i32)
+        (loop $next
+            (local.set $carry
+                (i32.and (i32.xor (local.get $data) (local.get $crc)) (i32.const 1)))
+            (local.set $data (i32.shr_u (local.get $data) (i32.const 1)))
+            (if (local.get $carry)
+                (then
+                    (local.set $crc (i32.xor (local.get $crc) (i32.const 0x4002)))
+                )
+            )
+            (local.set $crc (i32.shr_u (local.get $crc) (i32.const 1)))
+            (if (local.get $carry)
+                (then
+                    (local.set $crc (i32.or (local.get $crc) (i32.const 0x8000)))
+                )
+            )
+            (local.set $i (i32.add (local.get $i) (i32.const 1)))
+            (br_if $next (i32.lt_u (local.get $i) (i32.const 8)))
+        )
+        (local.get $crc)
+    )
+
+    ;; 2
+    ;; 计算 16 位数据的 CRC-16
+    (func $crc16 (param $value i32) (param $crc i32) (result i32)
+        (call $crc8
+            (i32.and (i32.shr_u (local.get $value) (i32.const 8)) (i32.const 0xff))
+            (call $crc8 (i32.and (local.get $value) (i32.const 0xff)) (local.get $crc))
+        )
+    )
+
+    ;; 3
+    ;; 初始化矩阵 A 和 B，计算 C = A x B，返回 C 的所有元素之和
+    (func $matrix (param $seed i32) (result i32)
+        (local $i i32)
+        (local $j i32)
+        (local $k i32)
+        (local $acc i32)
+        (local $sum i32)
+
+        ;; 初始化
+        (block $init_done
+            (loop $init_next
+                (br_if $init_done (i32.ge_u (local.get $i) (i32.const 256)))
+                (i32.store
+                    (i32.shl (local.get $i) (i32.const 2))
+                    (i32.and
+                        (i32.add (local.get $seed) (i32.mul (local.get $i) (i32.const 7)))
+                        (i32.const 0xff)
+                    )
+                )
+                (i32.store offset=1024
+                    (i32.shl (local.get $i) (i32.const 2))
+                    (i32.and
+                        (i32.xor (local.get $seed) (i32.mul (local.get $i) (i32.const 13)))
+                        (i32.const 0xff)
+                    )
+                )
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br $init_next)
+            )
+        )
+
+        ;; 乘法
+        (local.set $i (i32.const 0))
+        (block $row_done
+            (loop $row_next
+                (br_if $row_done (i32.ge_u (local.get $i) (i32.const 16)))
+                (local.set $j (i32.const 0))
+                (block $column_done
+                    (loop $column_next
+                        (br_if $column_done (i32.ge_u (local.get $j) (i32.const 16)))
+                        (local.set $k (i32.const 0))
+                        (local.set $acc (i32.const 0))
+                        (block $dot_done
+                            (loop $dot_next
+                                (br_if $dot_done (i32.ge_u (local.get $k) (i32.const 16)))
+                                (local.set $acc
+                                    (i32.add
+                                        (local.get $acc)
+                                        (i32.mul
+                                            (i32.load
+                                                (i32.shl
+                                                    (i32.add
+                                                        (i32.shl (local.get $i) (i32.const 4))
+                                                        (local.get $k))
+                                                    (i32.const 2)))
+                                            (i32.load offset=1024
+                                                (i32.shl
+                                                    (i32.add
+                                                        (i32.shl (local.get $k) (i32.const 4))
+                                                        (local.get $j))
+                                                    (i32.const 2)))
+                                        )
+                                    )
+                                )
+                                (local.set $k (i32.add (local.get $k) (i32.const 1)))
+                                (br $dot_next)
+                            )
+                        )
+                        (i32.store offset=2048
+                            (i32.shl
+                                (i32.add (i32.shl (local.get $i) (i32.const 4)) (local.get $j))
+                                (i32.const 2))
+                            (local.get $acc)
+                        )
+                        (local.set $sum (i32.add (local.get $sum) (local.get $acc)))
+                        (local.set $j (i32.add (local.get $j) (i32.const 1)))
+                        (br $column_next)
+                    )
+                )
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br $row_next)
+            )
+        )
+        (local.get $sum)
+    )
+
+    ;; 4
+    ;; 构建链表，将其反转，然后遍历并累加节点的值
+    (func $list (param $seed i32) (result i32)
+        (local $i i32)
+        (local $node i32)
+        (local $previous i32)
+        (local $next i32)
+        (local $acc i32)
+
+        ;; 构建链表，最后一个节点的 next 为 0
+        (block $build_done
+            (loop $build_next
+                (br_if $build_done (i32.ge_u (local.get $i) (i32.const 64)))
+                (local.set $node
+                    (i32.add (i32.const 4096) (i32.shl (local.get $i) (i32.const 3))))
+                (i32.store
+                    (local.get $node)
+                    (select
+                        (i32.add (local.get $node) (i32.const 8))
+                        (i32.const 0)
+                        (i32.lt_u (local.get $i) (i32.const 63))
+                    )
+                )
+                (i32.store offset=4
+                    (local.get $node)
+                    (i32.and
+                        (i32.add
+                            (i32.mul (local.get $seed) (i32.const 31))
+                            (i32.mul (local.get $i) (i32.const 17)))
+                        (i32.const 0xffff)
+                    )
+                )
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br $build_next)
+            )
+        )
+
+        ;; 反转
+        (local.set $node (i32.const 4096))
+        (block $reverse_done
+            (loop $reverse_next
+                (br_if $reverse_done (i32.eqz (local.get $node)))
+                (local.set $next (i32.load (local.get $node)))
+                (i32.store (local.get $node) (local.get $previous))
+                (local.set $previous (local.get $node))
+                (local.set $node (local.get $next))
+                (br $reverse_next)
+            )
+        )
+
+        ;; 遍历，结果跟节点的顺序有关
+        (local.set $node (local.get $previous))
+        (block $walk_done
+            (loop $walk_next
+                (br_if $walk_done (i32.eqz (local.get $node)))
+                (local.set $acc
+                    (i32.and
+                        (i32.add
+                            (i32.mul (local.get $acc) (i32.const 3))
+                            (i32.load offset=4 (local.get $node))
+                        )
+                        (i32.const 0xffff)
+                    )
+                )
+                (local.set $node (i32.load (local.get $node)))
+                (br $walk_next)
+            )
+        )
+        (local.get $acc)
+    )
+
+    ;; 5
+    ;; 使用线性同余法（模 2^15）生成 256 个输入符号（0~3），驱动一个 4 个状态的状态机，
+    ;; 返回状态转换的次数和最终状态的组合
+    (func $state (param $seed i32) (result i32)
+        (local $i i32)
+        (local $x i32)
+        (local $symbol i32)
+        (local $state i32)
+        (local $transitions i32)
+        (local $next_state i32)
+        (local.set $x (i32.and (local.get $seed) (i32.const 0x7fff)))
+        (block $done
+            (loop $next
+                (br_if $done (i32.ge_u (local.get $i) (i32.const 256)))
+                (local.set $x
+                    (i32.and
+                        (i32.add
+                            (i32.mul (local.get $x) (i32.const 1103))
+                            (i32.const 12345))
+                        (i32.const 0x7fff)))
+                (local.set $symbol
+                    (i32.and (i32.shr_u (local.get $x) (i32.const 8)) (i32.const 3)))
+                (local.set $next_state
+                    (block $transit (result i32)
+                        (block $s3
+                            (block $s2
+                                (block $s1
+                                    (block $s0
+                                        (br_table $s0 $s1 $s2 $s3 (local.get $state))
+                                    )
+                                    ;; 状态 0：符号 0 进入状态 1，其余进入状态 2
+                                    (br $transit
+                                        (select (i32.const 1) (i32.const 2)
+                                            (i32.eqz (local.get $symbol))))
+                                )
+                                ;; 状态 1：符号 1 进入状态 3，其余返回状态 0
+                                (br $transit
+                                    (select (i32.const 3) (i32.const 0)
+                                        (i32.eq (local.get $symbol) (i32.const 1))))
+                            )
+                            ;; 状态 2：进入跟符号相同的状态
+                            (br $transit (local.get $symbol))
+                        )
+                        ;; 状态 3：符号 3 保持不变，其余返回状态 0
+                        (select (i32.const 3) (i32.const 0)
+                            (i32.eq (local.get $symbol) (i32.const 3)))
+                    )
+                )
+                (if (i32.ne (local.get $next_state) (local.get $state))
+                    (then
+                        (local.set $transitions
+                            (i32.add (local.get $transitions) (i32.const 1)))
+                    )
+                )
+                (local.set $state (local.get $next_state))
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br $next)
+            )
+        )
+        (i32.or (i32.shl (local.get $transitions) (i32.const 2)) (local.get $state))
+    )
+)
@@ -0,0 +1,96 @@
+(module
+    ;; 内存复制基准测试
+    ;;
+    ;; 分别以字节和 i64 为单位把一段内存复制到另一个位置，
+    ;; 复制使用 load/store 指令的循环实现。
+    (memory 4)
+
+    ;; 0
+    ;; 以 i64 为单位复制 length 个字节 n 次，length 应为 8 的倍数，且不超过 128KiB，
+    ;; 返回目标区域的校验和
+    (func $copy_i64 (export "copy_i64") (param $length i32) (param $n i32) (result i64)
+        (local $i i32)
+        (local $offset i32)
+        (call $fill (local.get $length))
+        (block $done
+            (loop $next
+                (br_if $done (i32.ge_u (local.get $i) (local.get $n)))
+                (local.set $offset (i32.const 0))
+                (block $copy_done
+                    (loop $copy_next
+                        (br_if $copy_done (i32.ge_u (local.get $offset) (local.get $length)))
+                        (i64.store offset=131072
+                            (local.get $offset)
+                            (i64.load (local.get $offset)))
+                        (local.set $offset (i32.add (local.get $offset) (i32.const 8)))
+                        (br $copy_next)
+                    )
+                )
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br $next)
+            )
+        )
+        (call $checksum (local.get $length))
+    )
+
+    ;; 1
+    ;; 以字节为单位复制 length 个字节 n 次，length 不超过 128KiB，
+    ;; 返回目标区域的校验和
+    (func $copy_i8 (export "copy_i8") (param $length i32) (param $n i32) (result i64)
+        (local $i i32)
+        (local $offset i32)
+        (call $fill (local.get $length))
+        (block $done
+            (loop $next
+                (br_if $done (i32.ge_u (local.get $i) (local.get $n)))
+                (local.set $offset (i32.const 0))
+                (block $copy_done
+                    (loop $copy_next
+                        (br_if $copy_done (i32.ge_u (local.get $offset) (local.get $length)))
+                        (i32.store8 offset=131072
+                            (local.get $offset)
+                            (i32.load8_u (local.get $offset)))
+                        (local.set $offset (i32.add (local.get $offset) (i32.const 1)))
+                        (br $copy_next)
+                    )
+                )
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br $next)
+            )
+        )
+        (call $checksum (local.get $length))
+    )
+
+    ;; 2
+    ;; 以字节的序号填充源区域
+    (func $fill (param $length i32)
+        (local $offset i32)
+        (block $done
+            (loop $next
+                (br_if $done (i32.ge_u (local.get $offset) (local.get $length)))
+                (i32.store8 (local.get $offset) (local.get $offset))
+                (local.set $offset (i32.add (local.get $offset) (i32.const 1)))
+                (br $next)
+            )
+        )
+    )
+
+    ;; 3
+    ;; 按字节累加目标区域
+    (func $checksum (param $length i32) (result i64)
+        (local $offset i32)
+        (local $acc i64)
+        (block $done
+            (loop $next
+                (br_if $done (i32.ge_u (local.get $offset) (local.get $length)))
+                (local.set $acc
+                    (i64.add
+                        (local.get $acc)
+                        (i64.load8_u offset=131072 (local.get $offset))))
+                (local.set $offset (i32.add (local.get $offset) (i32.const 1)))
+                (br $next)
+            )
+        )
+        (local.get $acc)
+    )
+)
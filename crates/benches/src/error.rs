@@ -0,0 +1,42 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::fmt::Display;
+
+/// 基准测试项目的错误
+#[derive(Debug, PartialEq, Clone)]
+pub enum BenchError {
+    /// 读取模块文件失败
+    ReadFileError(/* path */ String, /* message */ String),
+
+    /// 解析模块失败
+    ParseError(/* path */ String, /* message */ String),
+
+    /// 链接、实例化或者执行函数失败
+    EngineError(/* workload name */ String, /* message */ String),
+
+    /// 多次执行同一个项目的结果不一致
+    ResultMismatch(/* workload name */ String),
+}
+
+impl Display for BenchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BenchError::ReadFileError(path, message) => {
+                write!(f, "failed to read file \"{}\": {}", path, message)
+            }
+            BenchError::ParseError(path, message) => {
+                write!(f, "failed to parse module \"{}\": {}", path, message)
+            }
+            BenchError::EngineError(name, message) => {
+                write!(f, "workload \"{}\" failed: {}", name, message)
+            }
+            BenchError::ResultMismatch(name) => {
+                write!(f, "workload \"{}\" returned different results", name)
+            }
+        }
+    }
+}
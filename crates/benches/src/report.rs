@@ -0,0 +1,280 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 测量结果的报告
+//!
+//! 测量结果以 JSON 格式保存，每个项目占一行，以便使用 `diff` 比较不同提交的结果，例如：
+//!
+//! ```json
+//! {
+//!   "interpreter": "stack",
+//!   "repeat_count": 5,
+//!   "workloads": [
+//!     {"name": "sum", "module": "engine/resources/test-block-loop.wasm", "parse_ns": 6630, "link_decode_ns": 5020, "instantiate_ns": 9490, "pool_reuse_ns": 1260, "execute_ns": 195750000, "results": ["i32:-1124226208"]},
+//!     {"name": "wasi_stderr_c", "module": "native-wasi/resources/test-stderr.wasm", "parse_ns": 241540, "link_decode_ns": 323530, "instantiate_ns": 489150, "pool_reuse_ns": 5830, "execute_ns": null, "results": []}
+//!   ]
+//! }
+//! ```
+//!
+//! 时间的单位是纳秒，返回值的格式为 `类型:数值`。
+//!
+//! 读取基准数据时只识别本模块生成的格式（每个项目占一行），并非通用的 JSON 解析器。
+//! 基准数据里缺少的阶段（比如由旧版本生成的数据没有 `pool_reuse_ns`）不参与比较。
+
+use std::time::Duration;
+
+use anvm_ast::types::Value;
+
+use crate::runner::{Measurement, PHASE_NAMES};
+
+/// 基准数据里的一个项目
+#[derive(Debug, PartialEq, Clone)]
+pub struct BaselineItem {
+    pub name: String,
+
+    /// 各阶段的时间（纳秒），顺序跟 `PHASE_NAMES` 一致
+    pub phases: [Option<u64>; 5],
+}
+
+/// 一个项目的一个阶段的测量结果跟基准数据的比较
+#[derive(Debug, PartialEq, Clone)]
+pub struct Comparison {
+    pub name: String,
+    pub phase: &'static str,
+    pub baseline_ns: u64,
+    pub current_ns: u64,
+}
+
+impl Comparison {
+    /// 相对于基准数据的变化百分比，正数表示变慢
+    pub fn get_change_percent(&self) -> f64 {
+        (self.current_ns as f64 - self.baseline_ns as f64) / (self.baseline_ns.max(1) as f64)
+            * 100.0
+    }
+}
+
+pub fn format_json(
+    interpreter_name: &str,
+    repeat_count: usize,
+    measurements: &[Measurement],
+) -> String {
+    let workload_lines = measurements
+        .iter()
+        .map(|measurement| {
+            let phase_texts = measurement
+                .get_phase_durations()
+                .iter()
+                .zip(PHASE_NAMES)
+                .map(|(option_duration, phase_name)| {
+                    format!(
+                        "\"{}_ns\": {}",
+                        phase_name,
+                        match option_duration {
+                            Some(duration) => duration.as_nanos().to_string(),
+                            None => "null".to_string(),
+                        }
+                    )
+                })
+                .collect::<Vec<String>>();
+
+            let result_texts = measurement
+                .results
+                .iter()
+                .map(|value| format!("\"{}\"", format_value(value)))
+                .collect::<Vec<String>>();
+
+            format!(
+                "    {{\"name\": \"{}\", \"module\": \"{}\", {}, \"results\": [{}]}}",
+                measurement.name,
+                measurement.path,
+                phase_texts.join(", "),
+                result_texts.join(", ")
+            )
+        })
+        .collect::<Vec<String>>();
+
+    format!(
+        "{{\n  \"interpreter\": \"{}\",\n  \"repeat_count\": {},\n  \"workloads\": [\n{}\n  ]\n}}\n",
+        interpreter_name,
+        repeat_count,
+        workload_lines.join(",\n")
+    )
+}
+
+fn format_value(value: &Value) -> String {
+    format!("{}:{}", value.get_type(), value)
+}
+
+/// 读取由 `format_json` 生成的基准数据
+pub fn parse_baseline(text: &str) -> Vec<BaselineItem> {
+    text.lines()
+        .filter_map(|line| {
+            let name = get_string_field(line, "name")?;
+            let mut phases = [None; 5];
+            for (phase, phase_name) in phases.iter_mut().zip(PHASE_NAMES) {
+                *phase = get_number_field(line, &format!("{}_ns", phase_name));
+            }
+
+            Some(BaselineItem { name, phases })
+        })
+        .collect::<Vec<BaselineItem>>()
+}
+
+fn get_field_text<'a>(line: &'a str, field_name: &str) -> Option<&'a str> {
+    let key = format!("\"{}\": ", field_name);
+    line.find(&key).map(|index| &line[index + key.len()..])
+}
+
+fn get_string_field(line: &str, field_name: &str) -> Option<String> {
+    let text = get_field_text(line, field_name)?.strip_prefix('"')?;
+    text.find('"').map(|end| text[..end].to_string())
+}
+
+fn get_number_field(line: &str, field_name: &str) -> Option<u64> {
+    let text = get_field_text(line, field_name)?;
+    let end = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    text[..end].parse::<u64>().ok()
+}
+
+/// 比较测量结果和基准数据
+///
+/// 只比较两者都存在（并且都有时间）的项目和阶段。
+pub fn compare(measurements: &[Measurement], baseline_items: &[BaselineItem]) -> Vec<Comparison> {
+    let mut comparisons = vec![];
+
+    for measurement in measurements {
+        let baseline_item = match baseline_items
+            .iter()
+            .find(|item| item.name == measurement.name)
+        {
+            Some(item) => item,
+            None => continue,
+        };
+
+        for ((option_duration, option_baseline_ns), phase_name) in measurement
+            .get_phase_durations()
+            .iter()
+            .zip(baseline_item.phases)
+            .zip(PHASE_NAMES)
+        {
+            if let (Some(duration), Some(baseline_ns)) = (option_duration, option_baseline_ns) {
+                comparisons.push(Comparison {
+                    name: measurement.name.clone(),
+                    phase: phase_name,
+                    baseline_ns,
+                    current_ns: get_nanos(duration),
+                });
+            }
+        }
+    }
+
+    comparisons
+}
+
+fn get_nanos(duration: &Duration) -> u64 {
+    duration.as_nanos() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use anvm_ast::types::Value;
+    use pretty_assertions::assert_eq;
+
+    use crate::runner::Measurement;
+
+    use super::{compare, format_json, parse_baseline, BaselineItem, Comparison};
+
+    fn create_measurement(
+        name: &str,
+        nanos: u64,
+        option_execute_nanos: Option<u64>,
+    ) -> Measurement {
+        Measurement {
+            name: name.to_string(),
+            path: format!("benches/resources/{}.wasm", name),
+            parse: Duration::from_nanos(nanos),
+            link_decode: Duration::from_nanos(nanos * 2),
+            instantiate: Duration::from_nanos(nanos * 3),
+            pool_reuse: Duration::from_nanos(nanos / 2),
+            option_execute: option_execute_nanos.map(Duration::from_nanos),
+            results: match option_execute_nanos {
+                Some(_) => vec![Value::I32(-1), Value::F64(1.5)],
+                None => vec![],
+            },
+        }
+    }
+
+    #[test]
+    fn test_format_json() {
+        let measurements = vec![
+            create_measurement("a", 10, Some(400)),
+            create_measurement("b", 20, None),
+        ];
+
+        assert_eq!(
+            format_json("stack", 5, &measurements),
+            r#"{
+  "interpreter": "stack",
+  "repeat_count": 5,
+  "workloads": [
+    {"name": "a", "module": "benches/resources/a.wasm", "parse_ns": 10, "link_decode_ns": 20, "instantiate_ns": 30, "pool_reuse_ns": 5, "execute_ns": 400, "results": ["i32:-1", "f64:1.5"]},
+    {"name": "b", "module": "benches/resources/b.wasm", "parse_ns": 20, "link_decode_ns": 40, "instantiate_ns": 60, "pool_reuse_ns": 10, "execute_ns": null, "results": []}
+  ]
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_parse_baseline_and_compare() {
+        let text = format_json(
+            "stack",
+            5,
+            &[
+                create_measurement("a", 10, Some(400)),
+                create_measurement("b", 20, None),
+            ],
+        );
+
+        let baseline_items = parse_baseline(&text);
+        assert_eq!(
+            baseline_items,
+            vec![
+                BaselineItem {
+                    name: "a".to_string(),
+                    phases: [Some(10), Some(20), Some(30), Some(5), Some(400)]
+                },
+                BaselineItem {
+                    name: "b".to_string(),
+                    phases: [Some(20), Some(40), Some(60), Some(10), None]
+                }
+            ]
+        );
+
+        // 基准数据只有项目 a，项目 c 被忽略，项目 a 的 execute 阶段变慢了
+        let measurements = vec![
+            create_measurement("a", 10, Some(500)),
+            create_measurement("c", 10, Some(500)),
+        ];
+        let comparisons = compare(&measurements, &baseline_items[..1]);
+        assert_eq!(comparisons.len(), 5);
+        assert_eq!(
+            comparisons[4],
+            Comparison {
+                name: "a".to_string(),
+                phase: "execute",
+                baseline_ns: 400,
+                current_ns: 500
+            }
+        );
+        assert_eq!(comparisons[4].get_change_percent(), 25.0);
+        assert_eq!(comparisons[0].get_change_percent(), 0.0);
+    }
+}
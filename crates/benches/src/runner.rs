@@ -0,0 +1,392 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 基准测试的运行器
+//!
+//! 每个项目分别测量以下五个阶段所需的时间：
+//!
+//! - `parse`，把二进制模块解析为 AST；
+//! - `link_decode`，链接模块的函数并把函数的指令解码为 VM 的指令；
+//! - `instantiate`，创建 VM 实例，其中包括链接和解码，以及初始化内存块、表、全局变量等；
+//! - `pool_reuse`，从实例池（见 `anvm_engine::instance_pool`）取出实例，写入内存，
+//!   然后归还实例，即实例池代替 `instantiate` 提供一个干净实例所需的时间；
+//! - `execute`，调用指定的函数。
+//!
+//! 每个项目重复运行若干次，每次都重新解析模块和创建实例，每个阶段取其中最短的时间，
+//! 以减少系统的其它活动对测量结果的影响。
+//!
+//! 执行阶段的函数可能依赖整数的溢出回绕，应该以 release 模式编译运行基准测试。
+
+use std::{
+    fs,
+    hint::black_box,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use anvm_ast::types::Value;
+use anvm_binary_parser::parser;
+use anvm_engine::{
+    instance::{create_instance_with_interpreter, link_and_decode_modules},
+    instance_pool::InstancePool,
+    object::NamedAstModule,
+    vm::InterpreterKind,
+};
+
+use crate::{error::BenchError, workload::Workload};
+
+/// 解析、链接、实例化以及实例池阶段所需的时间很短，容易受到干扰，
+/// 所以每次运行项目时这几个阶段都重复多次
+const SHORT_PHASE_REPEAT_COUNT: usize = 20;
+
+/// 阶段的名称，顺序跟 `Measurement::get_phase_durations` 返回的列表一致
+pub const PHASE_NAMES: [&str; 5] = [
+    "parse",
+    "link_decode",
+    "instantiate",
+    "pool_reuse",
+    "execute",
+];
+
+/// 一个项目的测量结果
+#[derive(Debug, PartialEq, Clone)]
+pub struct Measurement {
+    pub name: String,
+    pub path: String,
+    pub parse: Duration,
+    pub link_decode: Duration,
+    pub instantiate: Duration,
+    pub pool_reuse: Duration,
+
+    /// 只测量解析、链接和实例化阶段的项目为 None
+    pub option_execute: Option<Duration>,
+
+    /// 函数的返回值
+    pub results: Vec<Value>,
+}
+
+impl Measurement {
+    pub fn get_phase_durations(&self) -> [Option<Duration>; 5] {
+        [
+            Some(self.parse),
+            Some(self.link_decode),
+            Some(self.instantiate),
+            Some(self.pool_reuse),
+            self.option_execute,
+        ]
+    }
+}
+
+/// 运行一个项目
+///
+/// crates_path 是 `crates` 目录的路径，repeat_count 是重复运行的次数。
+pub fn measure_workload(
+    workload: &Workload,
+    crates_path: &Path,
+    interpreter_kind: InterpreterKind,
+    repeat_count: usize,
+) -> Result<Measurement, BenchError> {
+    let path_buf = crates_path.join(workload.path);
+    let bytes = fs::read(&path_buf)
+        .map_err(|e| BenchError::ReadFileError(workload.path.to_string(), e.to_string()))?;
+
+    let map_engine_error =
+        |message: String| BenchError::EngineError(workload.name.to_string(), message);
+
+    let mut measurement = Measurement {
+        name: workload.name.to_string(),
+        path: workload.path.to_string(),
+        parse: Duration::MAX,
+        link_decode: Duration::MAX,
+        instantiate: Duration::MAX,
+        pool_reuse: Duration::MAX,
+        option_execute: None,
+        results: vec![],
+    };
+
+    for repeat_index in 0..repeat_count.max(1) {
+        let mut option_vm = None;
+        let mut option_named_ast_modules = None;
+
+        for _ in 0..SHORT_PHASE_REPEAT_COUNT {
+            let start = Instant::now();
+            let ast_module = parser::parse(black_box(&bytes))
+                .map_err(|e| BenchError::ParseError(workload.path.to_string(), e.to_string()))?;
+            measurement.parse = measurement.parse.min(start.elapsed());
+
+            let named_ast_modules = vec![NamedAstModule::new(workload.name, ast_module)];
+
+            let native_modules = (workload.native_modules_factory)();
+            let start = Instant::now();
+            link_and_decode_modules(&native_modules, &named_ast_modules)
+                .map_err(|e| map_engine_error(e.to_string()))?;
+            measurement.link_decode = measurement.link_decode.min(start.elapsed());
+
+            let native_modules = (workload.native_modules_factory)();
+            let start = Instant::now();
+            let vm = create_instance_with_interpreter(
+                native_modules,
+                &named_ast_modules,
+                interpreter_kind,
+            )
+            .map_err(|e| map_engine_error(e.to_string()))?;
+            measurement.instantiate = measurement.instantiate.min(start.elapsed());
+
+            option_vm = Some(vm);
+            option_named_ast_modules = Some(named_ast_modules);
+        }
+
+        let named_ast_modules = option_named_ast_modules.unwrap();
+        let mut pool = InstancePool::new(
+            &named_ast_modules,
+            workload.native_modules_factory,
+            interpreter_kind,
+        )
+        .map_err(|e| map_engine_error(e.to_string()))?;
+
+        for _ in 0..SHORT_PHASE_REPEAT_COUNT {
+            let start = Instant::now();
+            let mut vm = pool.acquire();
+
+            // 写入内存使得归还时需要重置页面
+            if let Some(memory_block) = vm.resource.memory_blocks.first_mut() {
+                if memory_block.get_size_in_bytes() >= 4 {
+                    memory_block.write_i32(0, 0x1234_5678);
+                }
+            }
+
+            pool.release(black_box(vm));
+            measurement.pool_reuse = measurement.pool_reuse.min(start.elapsed());
+        }
+
+        let mut vm = option_vm.unwrap();
+
+        if let Some(function_index) = workload.option_function_index {
+            let start = Instant::now();
+            let results = vm
+                .eval_function_by_index(0, function_index, &workload.args)
+                .map_err(|e| map_engine_error(e.to_string()))?;
+            let duration = start.elapsed();
+
+            measurement.option_execute = Some(match measurement.option_execute {
+                Some(last) => last.min(duration),
+                None => duration,
+            });
+
+            if repeat_index == 0 {
+                measurement.results = results;
+            } else if results != measurement.results {
+                return Err(BenchError::ResultMismatch(workload.name.to_string()));
+            }
+        }
+    }
+
+    Ok(measurement)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use anvm_ast::types::Value;
+    use anvm_engine::vm::InterpreterKind;
+    use pretty_assertions::assert_eq;
+
+    use crate::workload::{get_workloads, Workload};
+
+    use super::measure_workload;
+
+    fn get_crates_path() -> &'static Path {
+        Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+    }
+
+    fn measure_results(path: &'static str, function_index: usize, args: Vec<Value>) -> Vec<Value> {
+        let workload = Workload {
+            name: "test",
+            path,
+            native_modules_factory: Vec::new,
+            option_function_index: Some(function_index),
+            args,
+        };
+
+        let mut results_list = vec![];
+        for interpreter_kind in [InterpreterKind::Stack, InterpreterKind::Register] {
+            let measurement =
+                measure_workload(&workload, get_crates_path(), interpreter_kind, 2).unwrap();
+            results_list.push(measurement.results);
+        }
+
+        assert_eq!(results_list[0], results_list[1]);
+        results_list.remove(0)
+    }
+
+    // 整数运算模块的参考实现
+    fn run_integer_reference(n: u32) -> u32 {
+        let crc8 = |mut data: u32, mut crc: u32| {
+            for _ in 0..8 {
+                let carry = (data ^ crc) & 1;
+                data >>= 1;
+                if carry != 0 {
+                    crc ^= 0x4002;
+                }
+                crc >>= 1;
+                if carry != 0 {
+                    crc |= 0x8000;
+                }
+            }
+            crc
+        };
+        let crc16 = |value: u32, crc: u32| crc8((value >> 8) & 0xff, crc8(value & 0xff, crc));
+
+        let matrix = |seed: u32| {
+            let a = (0..256u32)
+                .map(|i| (seed + i * 7) & 0xff)
+                .collect::<Vec<u32>>();
+            let b = (0..256u32)
+                .map(|i| (seed ^ (i * 13)) & 0xff)
+                .collect::<Vec<u32>>();
+            let mut sum = 0u32;
+            for i in 0..16 {
+                for j in 0..16 {
+                    for k in 0..16 {
+                        sum += a[i * 16 + k] * b[k * 16 + j];
+                    }
+                }
+            }
+            sum
+        };
+
+        let list = |seed: u32| {
+            (0..64u32).rev().fold(0u32, |acc, k| {
+                (acc * 3 + ((seed * 31 + k * 17) & 0xffff)) & 0xffff
+            })
+        };
+
+        let state = |seed: u32| {
+            let mut x = seed & 0x7fff;
+            let mut state = 0;
+            let mut transitions = 0;
+            for _ in 0..256 {
+                x = (x * 1103 + 12345) & 0x7fff;
+                let symbol = (x >> 8) & 3;
+                let next_state = match state {
+                    0 => {
+                        if symbol == 0 {
+                            1
+                        } else {
+                            2
+                        }
+                    }
+                    1 => {
+                        if symbol == 1 {
+                            3
+                        } else {
+                            0
+                        }
+                    }
+                    2 => symbol,
+                    _ => {
+                        if symbol == 3 {
+                            3
+                        } else {
+                            0
+                        }
+                    }
+                };
+                if next_state != state {
+                    transitions += 1;
+                }
+                state = next_state;
+            }
+            (transitions << 2) | state
+        };
+
+        (0..n).fold(0, |crc, i| {
+            let crc = crc16(matrix(i), crc);
+            let crc = crc16(list(i), crc);
+            crc16(state(i), crc)
+        })
+    }
+
+    #[test]
+    fn test_integer() {
+        assert_eq!(
+            measure_results(
+                "benches/resources/bench-integer.wasm",
+                0,
+                vec![Value::I32(3)]
+            ),
+            vec![Value::I32(run_integer_reference(3) as i32)]
+        );
+    }
+
+    #[test]
+    fn test_coremark_rust() {
+        // 期望值来自以本机为目标编译运行同一个源文件（去掉 `no_std` 及 `panic_handler`）的结果，
+        // 函数 5 是导出函数 `run`
+        assert_eq!(
+            measure_results(
+                "benches/resources/bench-coremark-rust.wasm",
+                5,
+                vec![Value::I32(3)]
+            ),
+            vec![Value::I32(48140)]
+        );
+    }
+
+    #[test]
+    fn test_float() {
+        assert_eq!(
+            measure_results("benches/resources/bench-float.wasm", 0, vec![Value::I32(4)]),
+            vec![Value::I32(8)]
+        );
+
+        let expected = (1..=100)
+            .map(|i| ((i as f32).sqrt() as f64) / (i as f64))
+            .sum::<f64>();
+        assert_eq!(
+            measure_results(
+                "benches/resources/bench-float.wasm",
+                1,
+                vec![Value::I32(100)]
+            ),
+            vec![Value::F64(expected)]
+        );
+    }
+
+    #[test]
+    fn test_memory_copy() {
+        // 0 + 1 + ... + 255，共 4 组
+        let expected = vec![Value::I64(255 * 256 / 2 * 4)];
+        assert_eq!(
+            measure_results(
+                "benches/resources/bench-memory-copy.wasm",
+                0,
+                vec![Value::I32(1024), Value::I32(2)]
+            ),
+            expected
+        );
+        assert_eq!(
+            measure_results(
+                "benches/resources/bench-memory-copy.wasm",
+                1,
+                vec![Value::I32(1024), Value::I32(2)]
+            ),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_workload_modules() {
+        // 所有项目的模块都能解析、链接和实例化，这里不执行函数
+        for mut workload in get_workloads() {
+            workload.option_function_index = None;
+            let measurement =
+                measure_workload(&workload, get_crates_path(), InterpreterKind::Stack, 1).unwrap();
+            assert_eq!(measurement.option_execute, None);
+        }
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 基准测试项目
+//!
+//! 项目的模块包括：
+//!
+//! - `engine/resources` 和 `native-wasi/resources` 目录里已有的测试模块；
+//! - `benches/resources` 目录里专为基准测试编写的模块，分别是整数运算
+//!   （参照 CoreMark 的矩阵乘法、链表、状态机以及 CRC 校验）、浮点数运算以及内存复制；
+//! - `benches/resources` 目录里由 Rust 编译而成的 CoreMark 风格的模块（`bench-coremark-rust`），
+//!   用于反映真实编译器生成的代码的性能。
+//!
+//! 修改 `benches/resources` 里的 `.wat` 或者 `.rs` 文件之后需要重新编译对应的 `.wasm` 文件
+//! （编译方法见源文件的开头）。
+//! 修改项目的参数会使测量结果失去可比性，修改之后应该重新生成基准数据。
+
+use anvm_ast::types::Value;
+use anvm_engine::native_module::NativeModule;
+use anvm_native_wasi::{wasi::new_wasi_module, wasi_module_context::WASIModuleContext};
+
+pub struct Workload {
+    pub name: &'static str,
+
+    /// 模块文件的路径，相对于 `crates` 目录
+    pub path: &'static str,
+
+    /// 创建模块依赖的本地模块
+    pub native_modules_factory: fn() -> Vec<NativeModule>,
+
+    /// 执行阶段调用的函数，为 None 时只测量解析、链接和实例化阶段
+    pub option_function_index: Option<usize>,
+    pub args: Vec<Value>,
+}
+
+impl Workload {
+    fn new(
+        name: &'static str,
+        path: &'static str,
+        option_function_index: Option<usize>,
+        args: Vec<Value>,
+    ) -> Self {
+        Self {
+            name,
+            path,
+            native_modules_factory: Vec::new,
+            option_function_index,
+            args,
+        }
+    }
+}
+
+fn create_wasi_native_modules() -> Vec<NativeModule> {
+    vec![new_wasi_module(WASIModuleContext::new_minimal())]
+}
+
+pub fn get_workloads() -> Vec<Workload> {
+    vec![
+        Workload::new(
+            "sum",
+            "engine/resources/test-block-loop.wasm",
+            Some(0),
+            vec![Value::I32(3_000_000)],
+        ),
+        Workload::new(
+            "count_primes",
+            "engine/resources/test-block-loop.wasm",
+            Some(1),
+            vec![Value::I32(200_000)],
+        ),
+        Workload::new(
+            "drop_extra",
+            "engine/resources/test-block-loop.wasm",
+            Some(3),
+            vec![Value::I32(1_000_000)],
+        ),
+        Workload::new(
+            "tail_call",
+            "engine/resources/test-function-tail-call.wasm",
+            Some(0),
+            vec![Value::I32(1_000_000)],
+        ),
+        Workload::new(
+            "instance_pool",
+            "engine/resources/test-instance-pool.wasm",
+            Some(0),
+            vec![],
+        ),
+        Workload::new(
+            "lib_rust",
+            "engine/resources/test-lib-rust.wasm",
+            None,
+            vec![],
+        ),
+        Workload {
+            name: "wasi_stderr_c",
+            path: "native-wasi/resources/test-stderr.wasm",
+            native_modules_factory: create_wasi_native_modules,
+            option_function_index: None,
+            args: vec![],
+        },
+        Workload::new(
+            "integer",
+            "benches/resources/bench-integer.wasm",
+            Some(0),
+            vec![Value::I32(200)],
+        ),
+        Workload::new(
+            "coremark_rust",
+            "benches/resources/bench-coremark-rust.wasm",
+            // 导出函数 `run` 的索引
+            Some(5),
+            vec![Value::I32(100)],
+        ),
+        Workload::new(
+            "float_mandelbrot",
+            "benches/resources/bench-float.wasm",
+            Some(0),
+            vec![Value::I32(200)],
+        ),
+        Workload::new(
+            "float_series",
+            "benches/resources/bench-float.wasm",
+            Some(1),
+            vec![Value::I32(1_000_000)],
+        ),
+        Workload::new(
+            "memory_copy_i64",
+            "benches/resources/bench-memory-copy.wasm",
+            Some(0),
+            vec![Value::I32(65536), Value::I32(100)],
+        ),
+        Workload::new(
+            "memory_copy_i8",
+            "benches/resources/bench-memory-copy.wasm",
+            Some(1),
+            vec![Value::I32(65536), Value::I32(20)],
+        ),
+    ]
+}
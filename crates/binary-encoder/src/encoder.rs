@@ -0,0 +1,2177 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 二进制格式的编码器
+//!
+//! 把 `ast::Module` 编码为二进制格式的模块，编码的过程是解析器
+//! （`anvm_binary_parser::parser`）的逆过程，各个段的二进制格式请参阅解析器里的说明。
+//!
+//! 编码器按照 section id 从小到大的顺序输出各个段，内容为空的段会被省略，
+//! 自定义段（包括 `name` 段以及其他自定义段）按照原有的次序放在所有段的后面。
+//!
+//! 当 `Module.data_count` 有值时，编码器会输出 `数据条目段`（data count section）。
+//!
+//! 对于整数，`encode` 总是使用最短的 LEB128 编码。对于含有非最短编码的模块（比如
+//! 链接器为了方便重定位而填充了整数的编码长度），可以使用 `encode_with_layout`，
+//! 根据解析器（`parser::parse_with_layout`）记录的编码布局重现原模块的编码。
+
+use anvm_ast::{
+    ast::{
+        CodeItem, CustomItem, DataItem, DataMode, DynamicLinking, ElementItem, ElementItems,
+        ElementMode, ExportDescriptor, ExportItem, GlobalItem, GlobalType, ImportDescriptor,
+        ImportItem, IndexNamePair, Limit, MemoryType, Module, NameCollection, TableType, TagType,
+        TypeItem,
+    },
+    instruction::{BlockType, CatchClause, Instruction, MemoryArgument},
+    opcode,
+    types::{RefType, ValueType},
+};
+use anvm_binary_parser::{layout::BinaryLayout, types};
+
+use crate::leb128encoder;
+
+pub fn encode(module: &Module) -> Vec<u8> {
+    encode_with_layout(module, &BinaryLayout::default())
+}
+
+/// 按照编码布局编码模块
+///
+/// 对于未经修改的模块，编码结果跟原模块完全一致。
+pub fn encode_with_layout(module: &Module, layout: &BinaryLayout) -> Vec<u8> {
+    let mut bytes: Vec<u8> = vec![];
+
+    // 写入幻数和版本号
+    bytes.extend_from_slice(&types::MAGIC_NUMBER.to_le_bytes());
+    bytes.extend_from_slice(&types::VERSION.to_le_bytes());
+
+    encode_sections(module, layout, &mut bytes);
+
+    bytes
+}
+
+/// # 编码 module 的各个段
+///
+/// section = section_id:u8 + byte_length:u32 + section_data
+fn encode_sections(module: &Module, layout: &BinaryLayout, bytes: &mut Vec<u8>) {
+    // 动态链接信息 `dylink.0` 必须是模块的第一个段
+    // https://github.com/WebAssembly/tool-conventions/blob/main/DynamicLinking.md
+    let (dynamic_linking_items, other_custom_items): (Vec<_>, Vec<_>) = module
+        .custom_items
+        .iter()
+        .enumerate()
+        .partition(|(_, custom_item)| matches!(custom_item, CustomItem::DynamicLinking(_)));
+
+    for (custom_item_index, custom_item) in dynamic_linking_items {
+        write_custom_section(bytes, custom_item_index, custom_item, layout);
+    }
+
+    if !module.type_items.is_empty() {
+        write_section(
+            bytes,
+            types::SECTION_TYPE_ID,
+            encode_type_section(&module.type_items),
+            layout,
+        );
+    }
+
+    if !module.import_items.is_empty() {
+        write_section(
+            bytes,
+            types::SECTION_IMPORT_ID,
+            encode_import_section(&module.import_items),
+            layout,
+        );
+    }
+
+    if !module.internal_function_to_type_index_list.is_empty() {
+        let mut content: Vec<u8> = vec![];
+        write_u32_vec(&mut content, &module.internal_function_to_type_index_list);
+        write_section(bytes, types::SECTION_FUNCTION_ID, content, layout);
+    }
+
+    if !module.tables.is_empty() {
+        write_section(
+            bytes,
+            types::SECTION_TABLE_ID,
+            encode_table_section(&module.tables),
+            layout,
+        );
+    }
+
+    if !module.memory_blocks.is_empty() {
+        write_section(
+            bytes,
+            types::SECTION_MEMORY_ID,
+            encode_memory_section(&module.memory_blocks),
+            layout,
+        );
+    }
+
+    // `标签段` 虽然 id 是 13，但需要位于内存段和全局段之间
+    if !module.tag_items.is_empty() {
+        write_section(
+            bytes,
+            types::SECTION_TAG_ID,
+            encode_tag_section(&module.tag_items),
+            layout,
+        );
+    }
+
+    if !module.global_items.is_empty() {
+        write_section(
+            bytes,
+            types::SECTION_GLOBAL_ID,
+            encode_global_section(&module.global_items),
+            layout,
+        );
+    }
+
+    if !module.export_items.is_empty() {
+        write_section(
+            bytes,
+            types::SECTION_EXPORT_ID,
+            encode_export_section(&module.export_items),
+            layout,
+        );
+    }
+
+    if let Some(start_function_index) = module.start_function_index {
+        let mut content: Vec<u8> = vec![];
+        write_u32(&mut content, start_function_index);
+        write_section(bytes, types::SECTION_START_ID, content, layout);
+    }
+
+    if !module.element_items.is_empty() {
+        write_section(
+            bytes,
+            types::SECTION_ELEMENT_ID,
+            encode_element_section(&module.element_items),
+            layout,
+        );
+    }
+
+    // `数据条目段` 虽然 id 是 12，但需要位于代码段之前
+    if let Some(data_count) = module.data_count {
+        let mut content: Vec<u8> = vec![];
+        write_u32(&mut content, data_count);
+        write_section(bytes, types::SECTION_DATA_COUNT_ID, content, layout);
+    }
+
+    if !module.code_items.is_empty() {
+        write_section(
+            bytes,
+            types::SECTION_CODE_ID,
+            encode_function_code_section(&module.code_items, layout),
+            layout,
+        );
+    }
+
+    if !module.data_items.is_empty() {
+        write_section(
+            bytes,
+            types::SECTION_DATA_ID,
+            encode_data_section(&module.data_items),
+            layout,
+        );
+    }
+
+    for (custom_item_index, custom_item) in other_custom_items {
+        write_custom_section(bytes, custom_item_index, custom_item, layout);
+    }
+}
+
+/// section = section_id:u8 + byte_length:u32 + section_data
+fn write_section(bytes: &mut Vec<u8>, section_id: u8, content: Vec<u8>, layout: &BinaryLayout) {
+    bytes.push(section_id);
+    let length_width = layout.section_length_widths.get(&section_id);
+    write_length_and_content(bytes, content, length_width);
+}
+
+fn write_custom_section(
+    bytes: &mut Vec<u8>,
+    custom_item_index: usize,
+    custom_item: &CustomItem,
+    layout: &BinaryLayout,
+) {
+    bytes.push(types::SECTION_CUSTOM_ID);
+    let length_width = layout.custom_section_length_widths.get(&custom_item_index);
+    write_length_and_content(bytes, encode_custom_section(custom_item), length_width);
+}
+
+/// 写入内容的长度以及内容，如果指定了长度的编码宽度，则把长度填充至该宽度
+fn write_length_and_content(bytes: &mut Vec<u8>, content: Vec<u8>, length_width: Option<&usize>) {
+    match length_width {
+        Some(width) => bytes.extend(leb128encoder::encode_u32_with_width(
+            content.len() as u32,
+            *width,
+        )),
+        None => write_u32(bytes, content.len() as u32),
+    }
+    bytes.extend(content);
+}
+
+/// # 编码 `自定义段`
+///
+/// custom_section = section_id:u8 + section_length:u32 + name + section_content
+///
+/// `name` 为 "name" 的自定义段由 NameCollection 列表编码而得，
+/// 其他自定义段（比如 "producers"、"linking" 等）则原样写入其内容。
+fn encode_custom_section(custom_item: &CustomItem) -> Vec<u8> {
+    let mut content: Vec<u8> = vec![];
+
+    match custom_item {
+        CustomItem::NameCollections(name_collections) => {
+            write_string(&mut content, "name");
+
+            for name_collection in name_collections {
+                let (kind, item_data) = encode_name_collection(name_collection);
+                content.push(kind);
+                write_u32(&mut content, item_data.len() as u32);
+                content.extend(item_data);
+            }
+        }
+        CustomItem::DynamicLinking(dynamic_linking) => {
+            write_string(&mut content, "dylink.0");
+            content.extend(encode_dynamic_linking(dynamic_linking));
+        }
+        CustomItem::Other(name, data) => {
+            write_string(&mut content, name);
+            content.extend_from_slice(data);
+        }
+    }
+
+    content
+}
+
+/// dylink_section = subsection{*}
+/// subsection = subsection_type:u8 + content_length:u32 + content
+///
+/// 内容为空的子段会被省略
+fn encode_dynamic_linking(dynamic_linking: &DynamicLinking) -> Vec<u8> {
+    let mut bytes: Vec<u8> = vec![];
+
+    if let Some(memory_info) = &dynamic_linking.memory_info {
+        let mut item_data: Vec<u8> = vec![];
+        write_u32(&mut item_data, memory_info.memory_size);
+        write_u32(&mut item_data, memory_info.memory_alignment);
+        write_u32(&mut item_data, memory_info.table_size);
+        write_u32(&mut item_data, memory_info.table_alignment);
+        write_subsection(&mut bytes, types::DYLINK_SUBSECTION_MEM_INFO, item_data);
+    }
+
+    if !dynamic_linking.needed.is_empty() {
+        let mut item_data: Vec<u8> = vec![];
+        write_strings(&mut item_data, &dynamic_linking.needed);
+        write_subsection(&mut bytes, types::DYLINK_SUBSECTION_NEEDED, item_data);
+    }
+
+    if !dynamic_linking.export_infos.is_empty() {
+        let mut item_data: Vec<u8> = vec![];
+        write_u32(&mut item_data, dynamic_linking.export_infos.len() as u32);
+        for export_info in &dynamic_linking.export_infos {
+            write_string(&mut item_data, &export_info.name);
+            write_u32(&mut item_data, export_info.flags);
+        }
+        write_subsection(&mut bytes, types::DYLINK_SUBSECTION_EXPORT_INFO, item_data);
+    }
+
+    if !dynamic_linking.import_infos.is_empty() {
+        let mut item_data: Vec<u8> = vec![];
+        write_u32(&mut item_data, dynamic_linking.import_infos.len() as u32);
+        for import_info in &dynamic_linking.import_infos {
+            write_string(&mut item_data, &import_info.module_name);
+            write_string(&mut item_data, &import_info.item_name);
+            write_u32(&mut item_data, import_info.flags);
+        }
+        write_subsection(&mut bytes, types::DYLINK_SUBSECTION_IMPORT_INFO, item_data);
+    }
+
+    if !dynamic_linking.runtime_paths.is_empty() {
+        let mut item_data: Vec<u8> = vec![];
+        write_strings(&mut item_data, &dynamic_linking.runtime_paths);
+        write_subsection(&mut bytes, types::DYLINK_SUBSECTION_RUNTIME_PATH, item_data);
+    }
+
+    bytes
+}
+
+fn write_subsection(bytes: &mut Vec<u8>, subsection_type: u8, item_data: Vec<u8>) {
+    bytes.push(subsection_type);
+    write_u32(bytes, item_data.len() as u32);
+    bytes.extend(item_data);
+}
+
+/// name_collection_item = kind:u8 + content_length:u32 + <type_name_item|function_name_item|global_name_item|...>
+fn encode_name_collection(name_collection: &NameCollection) -> (u8, Vec<u8>) {
+    let mut item_data: Vec<u8> = vec![];
+
+    let kind = match name_collection {
+        NameCollection::TypeNames(index_name_pairs) => {
+            write_index_name_pairs(&mut item_data, index_name_pairs);
+            types::NAME_COLLECTION_KIND_TYPE_NAMES
+        }
+        NameCollection::FunctionNames(index_name_pairs) => {
+            write_index_name_pairs(&mut item_data, index_name_pairs);
+            types::NAME_COLLECTION_KIND_FUNCTION_NAMES
+        }
+        NameCollection::LocalVariableNamesPairList(names_pairs) => {
+            write_u32(&mut item_data, names_pairs.len() as u32);
+            for names_pair in names_pairs {
+                write_u32(&mut item_data, names_pair.function_index);
+                write_index_name_pairs(&mut item_data, &names_pair.local_variable_names);
+            }
+            types::NAME_COLLECTION_KIND_FUNCTION_LOCAL_VARIABLE_NAMES
+        }
+        NameCollection::BlockLabelsPairList(labels_pairs) => {
+            write_u32(&mut item_data, labels_pairs.len() as u32);
+            for labels_pair in labels_pairs {
+                write_u32(&mut item_data, labels_pair.function_index);
+                write_index_name_pairs(&mut item_data, &labels_pair.block_labels);
+            }
+            types::NAME_COLLECTION_KIND_FUNCTION_BLOCK_LABELS
+        }
+        NameCollection::GlobalVariableNames(index_name_pairs) => {
+            write_index_name_pairs(&mut item_data, index_name_pairs);
+            types::NAME_COLLECTION_KIND_GLOBAL_VARIABLE_NAMES
+        }
+        NameCollection::MemoryBlockNames(index_name_pairs) => {
+            write_index_name_pairs(&mut item_data, index_name_pairs);
+            types::NAME_COLLECTION_KIND_MEMORY_BLOCK_NAMES
+        }
+        NameCollection::TableNames(index_name_pairs) => {
+            write_index_name_pairs(&mut item_data, index_name_pairs);
+            types::NAME_COLLECTION_KIND_TABLE_NAMES
+        }
+        NameCollection::ElementNames(index_name_pairs) => {
+            write_index_name_pairs(&mut item_data, index_name_pairs);
+            types::NAME_COLLECTION_KIND_ELEMENT_NAMES
+        }
+        NameCollection::DataNames(index_name_pairs) => {
+            write_index_name_pairs(&mut item_data, index_name_pairs);
+            types::NAME_COLLECTION_KIND_DATA_NAMES
+        }
+        NameCollection::TagNames(index_name_pairs) => {
+            write_index_name_pairs(&mut item_data, index_name_pairs);
+            types::NAME_COLLECTION_KIND_TAG_NAMES
+        }
+    };
+
+    (kind, item_data)
+}
+
+fn write_index_name_pairs(bytes: &mut Vec<u8>, index_name_pairs: &[IndexNamePair]) {
+    write_u32(bytes, index_name_pairs.len() as u32);
+    for index_name_pair in index_name_pairs {
+        write_u32(bytes, index_name_pair.index);
+        write_string(bytes, &index_name_pair.name);
+    }
+}
+
+/// # 编码 `类型段`
+///
+/// type_section = 0x01:byte + content_length:u32 + <function_type>
+/// function_type = 0x60 + <value_type> + <value_type>
+fn encode_type_section(type_items: &[TypeItem]) -> Vec<u8> {
+    let mut content: Vec<u8> = vec![];
+    write_u32(&mut content, type_items.len() as u32);
+
+    for type_item in type_items {
+        let TypeItem::FunctionType(function_type) = type_item;
+        content.push(types::FUNCTION_TYPE_TAG);
+        write_value_types(&mut content, &function_type.params);
+        write_value_types(&mut content, &function_type.results);
+    }
+
+    content
+}
+
+/// <value_type> = items_count:u32 + item{*}
+fn write_value_types(bytes: &mut Vec<u8>, value_types: &[ValueType]) {
+    write_u32(bytes, value_types.len() as u32);
+    for value_type in value_types {
+        write_value_type(bytes, value_type);
+    }
+}
+
+fn write_value_type(bytes: &mut Vec<u8>, value_type: &ValueType) {
+    let tag = match value_type {
+        ValueType::I32 => types::VALUE_TYPE_TAG_I32,
+        ValueType::I64 => types::VALUE_TYPE_TAG_I64,
+        ValueType::F32 => types::VALUE_TYPE_TAG_F32,
+        ValueType::F64 => types::VALUE_TYPE_TAG_F64,
+        ValueType::V128 => types::VALUE_TYPE_TAG_V128,
+        ValueType::FuncRef => types::VALUE_TYPE_TAG_FUNC_REF,
+        ValueType::ExternRef => types::VALUE_TYPE_TAG_EXTERN_REF,
+        ValueType::ExnRef => types::VALUE_TYPE_TAG_EXN_REF,
+    };
+    bytes.push(tag);
+}
+
+fn write_ref_type(bytes: &mut Vec<u8>, ref_type: &RefType) {
+    let tag = match ref_type {
+        RefType::FuncRef => types::VALUE_TYPE_TAG_FUNC_REF,
+        RefType::ExternRef => types::VALUE_TYPE_TAG_EXTERN_REF,
+        RefType::ExnRef => types::VALUE_TYPE_TAG_EXN_REF,
+    };
+    bytes.push(tag);
+}
+
+/// # 编码导入段
+///
+/// import_section = 0x02 + content_length:u32 + <import_item>
+/// import_item = module_name:string + member_name:string + import_descriptor
+/// import_description = tag:byte + (type_index | table_type | memory_type | global_type | tag_type)
+fn encode_import_section(import_items: &[ImportItem]) -> Vec<u8> {
+    let mut content: Vec<u8> = vec![];
+    write_u32(&mut content, import_items.len() as u32);
+
+    for import_item in import_items {
+        write_string(&mut content, &import_item.module_name);
+        write_string(&mut content, &import_item.item_name);
+
+        match &import_item.import_descriptor {
+            ImportDescriptor::FunctionTypeIndex(type_index) => {
+                content.push(types::IMPORT_TAG_FUNCTION);
+                write_u32(&mut content, *type_index);
+            }
+            ImportDescriptor::TableType(table_type) => {
+                content.push(types::IMPORT_TAG_TABLE);
+                write_table_type(&mut content, table_type);
+            }
+            ImportDescriptor::MemoryType(memory_type) => {
+                content.push(types::IMPORT_TAG_MEMORY);
+                write_memory_type(&mut content, memory_type);
+            }
+            ImportDescriptor::GlobalType(global_type) => {
+                content.push(types::IMPORT_TAG_GLOBAL);
+                write_global_type(&mut content, global_type);
+            }
+            ImportDescriptor::TagType(tag_type) => {
+                content.push(types::IMPORT_TAG_TAG);
+                write_tag_type(&mut content, tag_type);
+            }
+        }
+    }
+
+    content
+}
+
+/// table_type = ref_type:byte + limits
+fn write_table_type(bytes: &mut Vec<u8>, table_type: &TableType) {
+    write_ref_type(bytes, &table_type.element_type);
+    write_limit(bytes, &table_type.limit);
+}
+
+/// memory_type = limits
+///
+/// 共享内存块的 limits tag 会设置 0x02 位，64 位内存块的 limits tag 会设置 0x04 位
+fn write_memory_type(bytes: &mut Vec<u8>, memory_type: &MemoryType) {
+    let start = bytes.len();
+    write_limit(bytes, &memory_type.limit);
+    if memory_type.shared {
+        bytes[start] |= 0x02;
+    }
+    if memory_type.memory64 {
+        bytes[start] |= 0x04;
+    }
+}
+
+/// global_type = val_type:byte + mut:byte
+fn write_global_type(bytes: &mut Vec<u8>, global_type: &GlobalType) {
+    write_value_type(bytes, &global_type.value_type);
+    if global_type.mutable {
+        bytes.push(types::GLOBAL_VARIABLE_TAG_MUTABLE);
+    } else {
+        bytes.push(types::GLOBAL_VARIABLE_TAG_IMMUTABLE);
+    }
+}
+
+/// tag_type = attribute:byte + type_index:u32
+fn write_tag_type(bytes: &mut Vec<u8>, tag_type: &TagType) {
+    bytes.push(types::TAG_ATTRIBUTE_EXCEPTION);
+    write_u32(bytes, tag_type.type_index);
+}
+
+/// limits = tag:byte + min:u32 + max:u32
+///
+/// 64 位内存块的 min 和 max 是 u64，因为数值在 u32 范围之内时 u32 和 u64 的
+/// leb128 编码是相同的，所以这里统一按照 u64 写入。
+fn write_limit(bytes: &mut Vec<u8>, limit: &Limit) {
+    match limit {
+        Limit::AtLeast(min) => {
+            bytes.push(0);
+            write_u64(bytes, *min);
+        }
+        Limit::Range(min, max) => {
+            bytes.push(1);
+            write_u64(bytes, *min);
+            write_u64(bytes, *max);
+        }
+    }
+}
+
+/// # 编码表段
+///
+/// table_section = 0x04 + content_length:u32 + <table_type>
+fn encode_table_section(tables: &[TableType]) -> Vec<u8> {
+    let mut content: Vec<u8> = vec![];
+    write_u32(&mut content, tables.len() as u32);
+    for table_type in tables {
+        write_table_type(&mut content, table_type);
+    }
+    content
+}
+
+/// # 编码内存段
+///
+/// memory_section = 0x05 + content_length:u32 + <memory_type>
+fn encode_memory_section(memory_blocks: &[MemoryType]) -> Vec<u8> {
+    let mut content: Vec<u8> = vec![];
+    write_u32(&mut content, memory_blocks.len() as u32);
+    for memory_type in memory_blocks {
+        write_memory_type(&mut content, memory_type);
+    }
+    content
+}
+
+/// # 编码标签段
+///
+/// tag_section = 0x0d + content_length:u32 + <tag_type>
+fn encode_tag_section(tag_items: &[TagType]) -> Vec<u8> {
+    let mut content: Vec<u8> = vec![];
+    write_u32(&mut content, tag_items.len() as u32);
+    for tag_type in tag_items {
+        write_tag_type(&mut content, tag_type);
+    }
+    content
+}
+
+/// # 编码全局（变量）段
+///
+/// global_section = 0x06 + content_length:u32 + <global_item>
+/// global_item = global_type + initialize_expression
+fn encode_global_section(global_items: &[GlobalItem]) -> Vec<u8> {
+    let mut content: Vec<u8> = vec![];
+    write_u32(&mut content, global_items.len() as u32);
+    for global_item in global_items {
+        write_global_type(&mut content, &global_item.global_type);
+        write_instructions(&mut content, &global_item.initialize_instruction_items);
+    }
+    content
+}
+
+/// # 编码导出段
+///
+/// export_section = 0x07 + content_length:u32 + <export_item>
+/// export_item = name:string + export_descriptor
+/// export_descriptor = tag:byte + (function_index | table_index | memory_block_index
+///                     | global_item_index | tag_index)
+fn encode_export_section(export_items: &[ExportItem]) -> Vec<u8> {
+    let mut content: Vec<u8> = vec![];
+    write_u32(&mut content, export_items.len() as u32);
+
+    for export_item in export_items {
+        write_string(&mut content, &export_item.name);
+
+        let (tag, index) = match export_item.export_descriptor {
+            ExportDescriptor::FunctionIndex(index) => (types::EXPORT_TAG_FUNCTION, index),
+            ExportDescriptor::TableIndex(index) => (types::EXPORT_TAG_TABLE, index),
+            ExportDescriptor::MemoryBlockIndex(index) => (types::EXPORT_TAG_MEM, index),
+            ExportDescriptor::GlobalItemIndex(index) => (types::EXPORT_TAG_GLOBAL, index),
+            ExportDescriptor::TagIndex(index) => (types::EXPORT_TAG_TAG, index),
+        };
+
+        content.push(tag);
+        write_u32(&mut content, index);
+    }
+
+    content
+}
+
+/// # 编码元素段
+///
+/// element_section = 0x09 + content_length:u32 + <element_item>
+/// element_item = flags:u32 + ...
+///
+/// 编码器总是选择最短的格式：
+/// 对于表 0 且类型为 funcref 的主动元素项，使用省略了表索引和元素类型的格式 0 或者 4。
+fn encode_element_section(element_items: &[ElementItem]) -> Vec<u8> {
+    let mut content: Vec<u8> = vec![];
+    write_u32(&mut content, element_items.len() as u32);
+
+    for element_item in element_items {
+        let uses_expressions = matches!(element_item.items, ElementItems::Expressions(_));
+        let expressions_flag: u32 = if uses_expressions { 0b100 } else { 0 };
+
+        let has_element_type = match &element_item.mode {
+            ElementMode::Active {
+                table_index,
+                offset_instruction_items,
+            } => {
+                if *table_index == 0 && element_item.element_type == RefType::FuncRef {
+                    write_u32(&mut content, expressions_flag);
+                    write_instructions(&mut content, offset_instruction_items);
+                    false
+                } else {
+                    write_u32(&mut content, expressions_flag | 0b010);
+                    write_u32(&mut content, *table_index);
+                    write_instructions(&mut content, offset_instruction_items);
+                    true
+                }
+            }
+            ElementMode::Passive => {
+                write_u32(&mut content, expressions_flag | 0b001);
+                true
+            }
+            ElementMode::Declarative => {
+                write_u32(&mut content, expressions_flag | 0b011);
+                true
+            }
+        };
+
+        match &element_item.items {
+            ElementItems::FunctionIndices(function_indices) => {
+                if has_element_type {
+                    content.push(types::ELEMENT_KIND_FUNC_REF);
+                }
+                write_u32_vec(&mut content, function_indices);
+            }
+            ElementItems::Expressions(expressions) => {
+                if has_element_type {
+                    write_ref_type(&mut content, &element_item.element_type);
+                }
+                write_u32(&mut content, expressions.len() as u32);
+                for expression in expressions {
+                    write_instructions(&mut content, expression);
+                }
+            }
+        }
+    }
+
+    content
+}
+
+/// # 编码代码段
+///
+/// code_section = 0x0a + content_length:u32 + <code_item>
+/// code_item = code_content_length:u32 + <local_group> + expression
+/// local_group = local_variable_count:u32 + value_type:byte
+fn encode_function_code_section(code_items: &[CodeItem], layout: &BinaryLayout) -> Vec<u8> {
+    let mut content: Vec<u8> = vec![];
+    write_u32(&mut content, code_items.len() as u32);
+
+    for (function_index, code_item) in code_items.iter().enumerate() {
+        let mut code_data: Vec<u8> = vec![];
+
+        write_u32(&mut code_data, code_item.local_groups.len() as u32);
+        for local_group in &code_item.local_groups {
+            write_u32(&mut code_data, local_group.variable_count);
+            write_value_type(&mut code_data, &local_group.value_type);
+        }
+
+        for (instruction_index, instruction) in code_item.instruction_items.iter().enumerate() {
+            // 如果指令跟原模块的相同，则使用原模块里（含有非最短编码整数的）的原始编码
+            match layout
+                .padded_instructions
+                .get(&(function_index, instruction_index))
+            {
+                Some((original_instruction, original_bytes))
+                    if original_instruction == instruction =>
+                {
+                    code_data.extend_from_slice(original_bytes);
+                }
+                _ => write_instruction(&mut code_data, instruction),
+            }
+        }
+
+        let length_width = layout.code_length_widths.get(&function_index);
+        write_length_and_content(&mut content, code_data, length_width);
+    }
+
+    content
+}
+
+/// # 编码数据段
+///
+/// data_section = 0x0b + content_length:u32 + <data_item>
+/// data_item = flags:u32 + ...
+///
+/// 0: offset_expression + data:byte{*}                           ;; 主动，内存块 0
+/// 1: data:byte{*}                                               ;; 被动
+/// 2: memory_block_index:u32 + offset_expression + data:byte{*}  ;; 主动
+fn encode_data_section(data_items: &[DataItem]) -> Vec<u8> {
+    let mut content: Vec<u8> = vec![];
+    write_u32(&mut content, data_items.len() as u32);
+
+    for data_item in data_items {
+        match &data_item.mode {
+            DataMode::Active {
+                memory_block_index,
+                offset_instruction_items,
+            } => {
+                if *memory_block_index == 0 {
+                    write_u32(&mut content, 0);
+                } else {
+                    write_u32(&mut content, 2);
+                    write_u32(&mut content, *memory_block_index);
+                }
+                write_instructions(&mut content, offset_instruction_items);
+            }
+            DataMode::Passive => {
+                write_u32(&mut content, 1);
+            }
+        }
+
+        write_u32(&mut content, data_item.data.len() as u32);
+        content.extend_from_slice(&data_item.data);
+    }
+
+    content
+}
+
+/// 编码指令序列
+///
+/// 指令序列（函数主体以及常量表达式）本身已经包含了结尾的 `end` 指令
+fn write_instructions(bytes: &mut Vec<u8>, instructions: &[Instruction]) {
+    for instruction in instructions {
+        write_instruction(bytes, instruction);
+    }
+}
+
+/// # 编码一个指令
+///
+/// instruction = opcode:byte + immediate_arguments
+fn write_instruction(bytes: &mut Vec<u8>, instruction: &Instruction) {
+    match instruction {
+        // 控制指令
+        Instruction::Block(block_type, _) => {
+            bytes.push(opcode::BLOCK);
+            write_block_type(bytes, block_type);
+        }
+        Instruction::Loop(block_type, _) => {
+            bytes.push(opcode::LOOP);
+            write_block_type(bytes, block_type);
+        }
+        Instruction::If(block_type, _) => {
+            bytes.push(opcode::IF);
+            write_block_type(bytes, block_type);
+        }
+        Instruction::Br(relative_depth) => {
+            bytes.push(opcode::BR);
+            write_u32(bytes, *relative_depth);
+        }
+        Instruction::BrIf(relative_depth) => {
+            bytes.push(opcode::BR_IF);
+            write_u32(bytes, *relative_depth);
+        }
+        Instruction::BrTable(relative_depths, default_relative_depth) => {
+            bytes.push(opcode::BR_TABLE);
+            write_u32_vec(bytes, relative_depths);
+            write_u32(bytes, *default_relative_depth);
+        }
+        Instruction::Call(function_index) => {
+            bytes.push(opcode::CALL);
+            write_u32(bytes, *function_index);
+        }
+        Instruction::CallIndirect(type_index, table_index) => {
+            bytes.push(opcode::CALL_INDIRECT);
+            write_u32(bytes, *type_index);
+            write_u32(bytes, *table_index);
+        }
+        Instruction::ReturnCall(function_index) => {
+            bytes.push(opcode::RETURN_CALL);
+            write_u32(bytes, *function_index);
+        }
+        Instruction::ReturnCallIndirect(type_index, table_index) => {
+            bytes.push(opcode::RETURN_CALL_INDIRECT);
+            write_u32(bytes, *type_index);
+            write_u32(bytes, *table_index);
+        }
+        Instruction::Throw(tag_index) => {
+            bytes.push(opcode::THROW);
+            write_u32(bytes, *tag_index);
+        }
+        Instruction::ThrowRef => bytes.push(opcode::THROW_REF),
+        Instruction::TryTable(block_type, catch_clauses, _) => {
+            bytes.push(opcode::TRY_TABLE);
+            write_block_type(bytes, block_type);
+            write_catch_clauses(bytes, catch_clauses);
+        }
+
+        // 变量指令
+        Instruction::LocalGet(index) => write_opcode_and_index(bytes, opcode::LOCAL_GET, *index),
+        Instruction::LocalSet(index) => write_opcode_and_index(bytes, opcode::LOCAL_SET, *index),
+        Instruction::LocalTee(index) => write_opcode_and_index(bytes, opcode::LOCAL_TEE, *index),
+        Instruction::GlobalGet(index) => write_opcode_and_index(bytes, opcode::GLOBAL_GET, *index),
+        Instruction::GlobalSet(index) => write_opcode_and_index(bytes, opcode::GLOBAL_SET, *index),
+
+        // 内存指令
+        Instruction::I32Load(argument) => write_memory_access(bytes, opcode::I32_LOAD, argument),
+        Instruction::I64Load(argument) => write_memory_access(bytes, opcode::I64_LOAD, argument),
+        Instruction::F32Load(argument) => write_memory_access(bytes, opcode::F32_LOAD, argument),
+        Instruction::F64Load(argument) => write_memory_access(bytes, opcode::F64_LOAD, argument),
+        Instruction::I32Load8S(argument) => {
+            write_memory_access(bytes, opcode::I32_LOAD8_S, argument)
+        }
+        Instruction::I32Load8U(argument) => {
+            write_memory_access(bytes, opcode::I32_LOAD8_U, argument)
+        }
+        Instruction::I32Load16S(argument) => {
+            write_memory_access(bytes, opcode::I32_LOAD16_S, argument)
+        }
+        Instruction::I32Load16U(argument) => {
+            write_memory_access(bytes, opcode::I32_LOAD16_U, argument)
+        }
+        Instruction::I64Load8S(argument) => {
+            write_memory_access(bytes, opcode::I64_LOAD8_S, argument)
+        }
+        Instruction::I64Load8U(argument) => {
+            write_memory_access(bytes, opcode::I64_LOAD8_U, argument)
+        }
+        Instruction::I64Load16S(argument) => {
+            write_memory_access(bytes, opcode::I64_LOAD16_S, argument)
+        }
+        Instruction::I64Load16U(argument) => {
+            write_memory_access(bytes, opcode::I64_LOAD16_U, argument)
+        }
+        Instruction::I64Load32S(argument) => {
+            write_memory_access(bytes, opcode::I64_LOAD32_S, argument)
+        }
+        Instruction::I64Load32U(argument) => {
+            write_memory_access(bytes, opcode::I64_LOAD32_U, argument)
+        }
+        Instruction::I32Store(argument) => write_memory_access(bytes, opcode::I32_STORE, argument),
+        Instruction::I64Store(argument) => write_memory_access(bytes, opcode::I64_STORE, argument),
+        Instruction::F32Store(argument) => write_memory_access(bytes, opcode::F32_STORE, argument),
+        Instruction::F64Store(argument) => write_memory_access(bytes, opcode::F64_STORE, argument),
+        Instruction::I32Store8(argument) => {
+            write_memory_access(bytes, opcode::I32_STORE8, argument)
+        }
+        Instruction::I32Store16(argument) => {
+            write_memory_access(bytes, opcode::I32_STORE16, argument)
+        }
+        Instruction::I64Store8(argument) => {
+            write_memory_access(bytes, opcode::I64_STORE8, argument)
+        }
+        Instruction::I64Store16(argument) => {
+            write_memory_access(bytes, opcode::I64_STORE16, argument)
+        }
+        Instruction::I64Store32(argument) => {
+            write_memory_access(bytes, opcode::I64_STORE32, argument)
+        }
+        Instruction::MemorySize(memory_block_index) => {
+            write_opcode_and_index(bytes, opcode::MEMORY_SIZE, *memory_block_index)
+        }
+        Instruction::MemoryGrow(memory_block_index) => {
+            write_opcode_and_index(bytes, opcode::MEMORY_GROW, *memory_block_index)
+        }
+
+        // 表指令
+        Instruction::TableGet(table_index) => {
+            write_opcode_and_index(bytes, opcode::TABLE_GET, *table_index)
+        }
+        Instruction::TableSet(table_index) => {
+            write_opcode_and_index(bytes, opcode::TABLE_SET, *table_index)
+        }
+
+        // 常量指令
+        Instruction::I32Const(number) => {
+            bytes.push(opcode::I32_CONST);
+            write_i32(bytes, *number);
+        }
+        Instruction::I64Const(number) => {
+            bytes.push(opcode::I64_CONST);
+            write_i64(bytes, *number);
+        }
+        Instruction::F32Const(number) => {
+            bytes.push(opcode::F32_CONST);
+            bytes.extend_from_slice(&number.to_le_bytes());
+        }
+        Instruction::F64Const(number) => {
+            bytes.push(opcode::F64_CONST);
+            bytes.extend_from_slice(&number.to_le_bytes());
+        }
+
+        // 扩展指令
+        Instruction::I32TruncSatF32S => write_extension_opcode(bytes, opcode::I32_TRUNC_SAT_F32_S),
+        Instruction::I32TruncSatF32U => write_extension_opcode(bytes, opcode::I32_TRUNC_SAT_F32_U),
+        Instruction::I32TruncSatF64S => write_extension_opcode(bytes, opcode::I32_TRUNC_SAT_F64_S),
+        Instruction::I32TruncSatF64U => write_extension_opcode(bytes, opcode::I32_TRUNC_SAT_F64_U),
+        Instruction::I64TruncSatF32S => write_extension_opcode(bytes, opcode::I64_TRUNC_SAT_F32_S),
+        Instruction::I64TruncSatF32U => write_extension_opcode(bytes, opcode::I64_TRUNC_SAT_F32_U),
+        Instruction::I64TruncSatF64S => write_extension_opcode(bytes, opcode::I64_TRUNC_SAT_F64_S),
+        Instruction::I64TruncSatF64U => write_extension_opcode(bytes, opcode::I64_TRUNC_SAT_F64_U),
+        Instruction::MemoryInit(data_index, memory_block_index) => {
+            write_extension_opcode(bytes, opcode::MEMORY_INIT);
+            write_u32(bytes, *data_index);
+            write_u32(bytes, *memory_block_index);
+        }
+        Instruction::DataDrop(data_index) => {
+            write_extension_opcode(bytes, opcode::DATA_DROP);
+            write_u32(bytes, *data_index);
+        }
+        Instruction::MemoryCopy(dest_memory_block_index, source_memory_block_index) => {
+            write_extension_opcode(bytes, opcode::MEMORY_COPY);
+            write_u32(bytes, *dest_memory_block_index);
+            write_u32(bytes, *source_memory_block_index);
+        }
+        Instruction::MemoryFill(memory_block_index) => {
+            write_extension_opcode(bytes, opcode::MEMORY_FILL);
+            write_u32(bytes, *memory_block_index);
+        }
+        Instruction::TableInit(element_index, table_index) => {
+            write_extension_opcode(bytes, opcode::TABLE_INIT);
+            write_u32(bytes, *element_index);
+            write_u32(bytes, *table_index);
+        }
+        Instruction::ElementDrop(element_index) => {
+            write_extension_opcode(bytes, opcode::ELEMENT_DROP);
+            write_u32(bytes, *element_index);
+        }
+        Instruction::TableCopy(dest_table_index, source_table_index) => {
+            write_extension_opcode(bytes, opcode::TABLE_COPY);
+            write_u32(bytes, *dest_table_index);
+            write_u32(bytes, *source_table_index);
+        }
+        Instruction::TableGrow(table_index) => {
+            write_extension_opcode(bytes, opcode::TABLE_GROW);
+            write_u32(bytes, *table_index);
+        }
+        Instruction::TableSize(table_index) => {
+            write_extension_opcode(bytes, opcode::TABLE_SIZE);
+            write_u32(bytes, *table_index);
+        }
+        Instruction::TableFill(table_index) => {
+            write_extension_opcode(bytes, opcode::TABLE_FILL);
+            write_u32(bytes, *table_index);
+        }
+
+        // 无立即数的指令
+        Instruction::Unreachable => bytes.push(opcode::UNREACHABLE),
+        Instruction::Nop => bytes.push(opcode::NOP),
+        Instruction::Else => bytes.push(opcode::ELSE),
+        Instruction::End => bytes.push(opcode::END),
+        Instruction::Return => bytes.push(opcode::RETURN),
+        Instruction::Drop => bytes.push(opcode::DROP),
+        Instruction::Select => bytes.push(opcode::SELECT),
+        Instruction::SelectTyped(value_types) => {
+            bytes.push(opcode::SELECT_TYPED);
+            write_value_types(bytes, value_types);
+        }
+        Instruction::RefNull(ref_type) => {
+            bytes.push(opcode::REF_NULL);
+            write_ref_type(bytes, ref_type);
+        }
+        Instruction::RefIsNull => bytes.push(opcode::REF_IS_NULL),
+        Instruction::RefFunc(function_index) => {
+            write_opcode_and_index(bytes, opcode::REF_FUNC, *function_index)
+        }
+        Instruction::I32Eqz => bytes.push(opcode::I32_EQZ),
+        Instruction::I32Eq => bytes.push(opcode::I32_EQ),
+        Instruction::I32Ne => bytes.push(opcode::I32_NE),
+        Instruction::I32LtS => bytes.push(opcode::I32_LT_S),
+        Instruction::I32LtU => bytes.push(opcode::I32_LT_U),
+        Instruction::I32GtS => bytes.push(opcode::I32_GT_S),
+        Instruction::I32GtU => bytes.push(opcode::I32_GT_U),
+        Instruction::I32LeS => bytes.push(opcode::I32_LE_S),
+        Instruction::I32LeU => bytes.push(opcode::I32_LE_U),
+        Instruction::I32GeS => bytes.push(opcode::I32_GE_S),
+        Instruction::I32GeU => bytes.push(opcode::I32_GE_U),
+        Instruction::I64Eqz => bytes.push(opcode::I64_EQZ),
+        Instruction::I64Eq => bytes.push(opcode::I64_EQ),
+        Instruction::I64Ne => bytes.push(opcode::I64_NE),
+        Instruction::I64LtS => bytes.push(opcode::I64_LT_S),
+        Instruction::I64LtU => bytes.push(opcode::I64_LT_U),
+        Instruction::I64GtS => bytes.push(opcode::I64_GT_S),
+        Instruction::I64GtU => bytes.push(opcode::I64_GT_U),
+        Instruction::I64LeS => bytes.push(opcode::I64_LE_S),
+        Instruction::I64LeU => bytes.push(opcode::I64_LE_U),
+        Instruction::I64GeS => bytes.push(opcode::I64_GE_S),
+        Instruction::I64GeU => bytes.push(opcode::I64_GE_U),
+        Instruction::F32Eq => bytes.push(opcode::F32_EQ),
+        Instruction::F32Ne => bytes.push(opcode::F32_NE),
+        Instruction::F32Lt => bytes.push(opcode::F32_LT),
+        Instruction::F32Gt => bytes.push(opcode::F32_GT),
+        Instruction::F32Le => bytes.push(opcode::F32_LE),
+        Instruction::F32Ge => bytes.push(opcode::F32_GE),
+        Instruction::F64Eq => bytes.push(opcode::F64_EQ),
+        Instruction::F64Ne => bytes.push(opcode::F64_NE),
+        Instruction::F64Lt => bytes.push(opcode::F64_LT),
+        Instruction::F64Gt => bytes.push(opcode::F64_GT),
+        Instruction::F64Le => bytes.push(opcode::F64_LE),
+        Instruction::F64Ge => bytes.push(opcode::F64_GE),
+        Instruction::I32Clz => bytes.push(opcode::I32_CLZ),
+        Instruction::I32Ctz => bytes.push(opcode::I32_CTZ),
+        Instruction::I32PopCnt => bytes.push(opcode::I32_POP_CNT),
+        Instruction::I32Add => bytes.push(opcode::I32_ADD),
+        Instruction::I32Sub => bytes.push(opcode::I32_SUB),
+        Instruction::I32Mul => bytes.push(opcode::I32_MUL),
+        Instruction::I32DivS => bytes.push(opcode::I32_DIV_S),
+        Instruction::I32DivU => bytes.push(opcode::I32_DIV_U),
+        Instruction::I32RemS => bytes.push(opcode::I32_REM_S),
+        Instruction::I32RemU => bytes.push(opcode::I32_REM_U),
+        Instruction::I32And => bytes.push(opcode::I32_AND),
+        Instruction::I32Or => bytes.push(opcode::I32_OR),
+        Instruction::I32Xor => bytes.push(opcode::I32_XOR),
+        Instruction::I32Shl => bytes.push(opcode::I32_SHL),
+        Instruction::I32ShrS => bytes.push(opcode::I32_SHR_S),
+        Instruction::I32ShrU => bytes.push(opcode::I32_SHR_U),
+        Instruction::I32Rotl => bytes.push(opcode::I32_ROTL),
+        Instruction::I32Rotr => bytes.push(opcode::I32_ROTR),
+        Instruction::I64Clz => bytes.push(opcode::I64_CLZ),
+        Instruction::I64Ctz => bytes.push(opcode::I64_CTZ),
+        Instruction::I64PopCnt => bytes.push(opcode::I64_POP_CNT),
+        Instruction::I64Add => bytes.push(opcode::I64_ADD),
+        Instruction::I64Sub => bytes.push(opcode::I64_SUB),
+        Instruction::I64Mul => bytes.push(opcode::I64_MUL),
+        Instruction::I64DivS => bytes.push(opcode::I64_DIV_S),
+        Instruction::I64DivU => bytes.push(opcode::I64_DIV_U),
+        Instruction::I64RemS => bytes.push(opcode::I64_REM_S),
+        Instruction::I64RemU => bytes.push(opcode::I64_REM_U),
+        Instruction::I64And => bytes.push(opcode::I64_AND),
+        Instruction::I64Or => bytes.push(opcode::I64_OR),
+        Instruction::I64Xor => bytes.push(opcode::I64_XOR),
+        Instruction::I64Shl => bytes.push(opcode::I64_SHL),
+        Instruction::I64ShrS => bytes.push(opcode::I64_SHR_S),
+        Instruction::I64ShrU => bytes.push(opcode::I64_SHR_U),
+        Instruction::I64Rotl => bytes.push(opcode::I64_ROTL),
+        Instruction::I64Rotr => bytes.push(opcode::I64_ROTR),
+        Instruction::F32Abs => bytes.push(opcode::F32_ABS),
+        Instruction::F32Neg => bytes.push(opcode::F32_NEG),
+        Instruction::F32Ceil => bytes.push(opcode::F32_CEIL),
+        Instruction::F32Floor => bytes.push(opcode::F32_FLOOR),
+        Instruction::F32Trunc => bytes.push(opcode::F32_TRUNC),
+        Instruction::F32Nearest => bytes.push(opcode::F32_NEAREST),
+        Instruction::F32Sqrt => bytes.push(opcode::F32_SQRT),
+        Instruction::F32Add => bytes.push(opcode::F32_ADD),
+        Instruction::F32Sub => bytes.push(opcode::F32_SUB),
+        Instruction::F32Mul => bytes.push(opcode::F32_MUL),
+        Instruction::F32Div => bytes.push(opcode::F32_DIV),
+        Instruction::F32Min => bytes.push(opcode::F32_MIN),
+        Instruction::F32Max => bytes.push(opcode::F32_MAX),
+        Instruction::F32CopySign => bytes.push(opcode::F32_COPY_SIGN),
+        Instruction::F64Abs => bytes.push(opcode::F64_ABS),
+        Instruction::F64Neg => bytes.push(opcode::F64_NEG),
+        Instruction::F64Ceil => bytes.push(opcode::F64_CEIL),
+        Instruction::F64Floor => bytes.push(opcode::F64_FLOOR),
+        Instruction::F64Trunc => bytes.push(opcode::F64_TRUNC),
+        Instruction::F64Nearest => bytes.push(opcode::F64_NEAREST),
+        Instruction::F64Sqrt => bytes.push(opcode::F64_SQRT),
+        Instruction::F64Add => bytes.push(opcode::F64_ADD),
+        Instruction::F64Sub => bytes.push(opcode::F64_SUB),
+        Instruction::F64Mul => bytes.push(opcode::F64_MUL),
+        Instruction::F64Div => bytes.push(opcode::F64_DIV),
+        Instruction::F64Min => bytes.push(opcode::F64_MIN),
+        Instruction::F64Max => bytes.push(opcode::F64_MAX),
+        Instruction::F64CopySign => bytes.push(opcode::F64_COPY_SIGN),
+        Instruction::I32WrapI64 => bytes.push(opcode::I32_WRAP_I64),
+        Instruction::I32TruncF32S => bytes.push(opcode::I32_TRUNC_F32_S),
+        Instruction::I32TruncF32U => bytes.push(opcode::I32_TRUNC_F32_U),
+        Instruction::I32TruncF64S => bytes.push(opcode::I32_TRUNC_F64_S),
+        Instruction::I32TruncF64U => bytes.push(opcode::I32_TRUNC_F64_U),
+        Instruction::I64ExtendI32S => bytes.push(opcode::I64_EXTEND_I32_S),
+        Instruction::I64ExtendI32U => bytes.push(opcode::I64_EXTEND_I32_U),
+        Instruction::I64TruncF32S => bytes.push(opcode::I64_TRUNC_F32_S),
+        Instruction::I64TruncF32U => bytes.push(opcode::I64_TRUNC_F32_U),
+        Instruction::I64TruncF64S => bytes.push(opcode::I64_TRUNC_F64_S),
+        Instruction::I64TruncF64U => bytes.push(opcode::I64_TRUNC_F64_U),
+        Instruction::F32ConvertI32S => bytes.push(opcode::F32_CONVERT_I32_S),
+        Instruction::F32ConvertI32U => bytes.push(opcode::F32_CONVERT_I32_U),
+        Instruction::F32ConvertI64S => bytes.push(opcode::F32_CONVERT_I64_S),
+        Instruction::F32ConvertI64U => bytes.push(opcode::F32_CONVERT_I64_U),
+        Instruction::F32DemoteF64 => bytes.push(opcode::F32_DEMOTE_F64),
+        Instruction::F64ConvertI32S => bytes.push(opcode::F64_CONVERT_I32_S),
+        Instruction::F64ConvertI32U => bytes.push(opcode::F64_CONVERT_I32_U),
+        Instruction::F64ConvertI64S => bytes.push(opcode::F64_CONVERT_I64_S),
+        Instruction::F64ConvertI64U => bytes.push(opcode::F64_CONVERT_I64_U),
+        Instruction::F64PromoteF32 => bytes.push(opcode::F64_PROMOTE_F32),
+        Instruction::I32ReinterpretF32 => bytes.push(opcode::I32_REINTERPRET_F32),
+        Instruction::I64ReinterpretF64 => bytes.push(opcode::I64_REINTERPRET_F64),
+        Instruction::F32ReinterpretI32 => bytes.push(opcode::F32_REINTERPRET_I32),
+        Instruction::F64ReinterpretI64 => bytes.push(opcode::F64_REINTERPRET_I64),
+        Instruction::I32Extend8S => bytes.push(opcode::I32_EXTEND8_S),
+        Instruction::I32Extend16S => bytes.push(opcode::I32_EXTEND16_S),
+        Instruction::I64Extend8S => bytes.push(opcode::I64_EXTEND8_S),
+        Instruction::I64Extend16S => bytes.push(opcode::I64_EXTEND16_S),
+        Instruction::I64Extend32S => bytes.push(opcode::I64_EXTEND32_S),
+
+        // 固定宽度 SIMD 指令
+        Instruction::V128Load(argument) => {
+            write_simd_opcode(bytes, opcode::V128_LOAD);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::V128Load8x8S(argument) => {
+            write_simd_opcode(bytes, opcode::V128_LOAD8X8_S);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::V128Load8x8U(argument) => {
+            write_simd_opcode(bytes, opcode::V128_LOAD8X8_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::V128Load16x4S(argument) => {
+            write_simd_opcode(bytes, opcode::V128_LOAD16X4_S);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::V128Load16x4U(argument) => {
+            write_simd_opcode(bytes, opcode::V128_LOAD16X4_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::V128Load32x2S(argument) => {
+            write_simd_opcode(bytes, opcode::V128_LOAD32X2_S);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::V128Load32x2U(argument) => {
+            write_simd_opcode(bytes, opcode::V128_LOAD32X2_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::V128Load8Splat(argument) => {
+            write_simd_opcode(bytes, opcode::V128_LOAD8_SPLAT);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::V128Load16Splat(argument) => {
+            write_simd_opcode(bytes, opcode::V128_LOAD16_SPLAT);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::V128Load32Splat(argument) => {
+            write_simd_opcode(bytes, opcode::V128_LOAD32_SPLAT);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::V128Load64Splat(argument) => {
+            write_simd_opcode(bytes, opcode::V128_LOAD64_SPLAT);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::V128Store(argument) => {
+            write_simd_opcode(bytes, opcode::V128_STORE);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::V128Const(value) => {
+            write_simd_opcode(bytes, opcode::V128_CONST);
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        Instruction::I8x16Shuffle(lane_indices) => {
+            write_simd_opcode(bytes, opcode::I8X16_SHUFFLE);
+            bytes.extend_from_slice(lane_indices);
+        }
+        Instruction::I8x16Swizzle => write_simd_opcode(bytes, opcode::I8X16_SWIZZLE),
+        Instruction::I8x16Splat => write_simd_opcode(bytes, opcode::I8X16_SPLAT),
+        Instruction::I16x8Splat => write_simd_opcode(bytes, opcode::I16X8_SPLAT),
+        Instruction::I32x4Splat => write_simd_opcode(bytes, opcode::I32X4_SPLAT),
+        Instruction::I64x2Splat => write_simd_opcode(bytes, opcode::I64X2_SPLAT),
+        Instruction::F32x4Splat => write_simd_opcode(bytes, opcode::F32X4_SPLAT),
+        Instruction::F64x2Splat => write_simd_opcode(bytes, opcode::F64X2_SPLAT),
+        Instruction::I8x16ExtractLaneS(lane_index) => {
+            write_simd_opcode(bytes, opcode::I8X16_EXTRACT_LANE_S);
+            bytes.push(*lane_index);
+        }
+        Instruction::I8x16ExtractLaneU(lane_index) => {
+            write_simd_opcode(bytes, opcode::I8X16_EXTRACT_LANE_U);
+            bytes.push(*lane_index);
+        }
+        Instruction::I8x16ReplaceLane(lane_index) => {
+            write_simd_opcode(bytes, opcode::I8X16_REPLACE_LANE);
+            bytes.push(*lane_index);
+        }
+        Instruction::I16x8ExtractLaneS(lane_index) => {
+            write_simd_opcode(bytes, opcode::I16X8_EXTRACT_LANE_S);
+            bytes.push(*lane_index);
+        }
+        Instruction::I16x8ExtractLaneU(lane_index) => {
+            write_simd_opcode(bytes, opcode::I16X8_EXTRACT_LANE_U);
+            bytes.push(*lane_index);
+        }
+        Instruction::I16x8ReplaceLane(lane_index) => {
+            write_simd_opcode(bytes, opcode::I16X8_REPLACE_LANE);
+            bytes.push(*lane_index);
+        }
+        Instruction::I32x4ExtractLane(lane_index) => {
+            write_simd_opcode(bytes, opcode::I32X4_EXTRACT_LANE);
+            bytes.push(*lane_index);
+        }
+        Instruction::I32x4ReplaceLane(lane_index) => {
+            write_simd_opcode(bytes, opcode::I32X4_REPLACE_LANE);
+            bytes.push(*lane_index);
+        }
+        Instruction::I64x2ExtractLane(lane_index) => {
+            write_simd_opcode(bytes, opcode::I64X2_EXTRACT_LANE);
+            bytes.push(*lane_index);
+        }
+        Instruction::I64x2ReplaceLane(lane_index) => {
+            write_simd_opcode(bytes, opcode::I64X2_REPLACE_LANE);
+            bytes.push(*lane_index);
+        }
+        Instruction::F32x4ExtractLane(lane_index) => {
+            write_simd_opcode(bytes, opcode::F32X4_EXTRACT_LANE);
+            bytes.push(*lane_index);
+        }
+        Instruction::F32x4ReplaceLane(lane_index) => {
+            write_simd_opcode(bytes, opcode::F32X4_REPLACE_LANE);
+            bytes.push(*lane_index);
+        }
+        Instruction::F64x2ExtractLane(lane_index) => {
+            write_simd_opcode(bytes, opcode::F64X2_EXTRACT_LANE);
+            bytes.push(*lane_index);
+        }
+        Instruction::F64x2ReplaceLane(lane_index) => {
+            write_simd_opcode(bytes, opcode::F64X2_REPLACE_LANE);
+            bytes.push(*lane_index);
+        }
+        Instruction::I8x16Eq => write_simd_opcode(bytes, opcode::I8X16_EQ),
+        Instruction::I8x16Ne => write_simd_opcode(bytes, opcode::I8X16_NE),
+        Instruction::I8x16LtS => write_simd_opcode(bytes, opcode::I8X16_LT_S),
+        Instruction::I8x16LtU => write_simd_opcode(bytes, opcode::I8X16_LT_U),
+        Instruction::I8x16GtS => write_simd_opcode(bytes, opcode::I8X16_GT_S),
+        Instruction::I8x16GtU => write_simd_opcode(bytes, opcode::I8X16_GT_U),
+        Instruction::I8x16LeS => write_simd_opcode(bytes, opcode::I8X16_LE_S),
+        Instruction::I8x16LeU => write_simd_opcode(bytes, opcode::I8X16_LE_U),
+        Instruction::I8x16GeS => write_simd_opcode(bytes, opcode::I8X16_GE_S),
+        Instruction::I8x16GeU => write_simd_opcode(bytes, opcode::I8X16_GE_U),
+        Instruction::I16x8Eq => write_simd_opcode(bytes, opcode::I16X8_EQ),
+        Instruction::I16x8Ne => write_simd_opcode(bytes, opcode::I16X8_NE),
+        Instruction::I16x8LtS => write_simd_opcode(bytes, opcode::I16X8_LT_S),
+        Instruction::I16x8LtU => write_simd_opcode(bytes, opcode::I16X8_LT_U),
+        Instruction::I16x8GtS => write_simd_opcode(bytes, opcode::I16X8_GT_S),
+        Instruction::I16x8GtU => write_simd_opcode(bytes, opcode::I16X8_GT_U),
+        Instruction::I16x8LeS => write_simd_opcode(bytes, opcode::I16X8_LE_S),
+        Instruction::I16x8LeU => write_simd_opcode(bytes, opcode::I16X8_LE_U),
+        Instruction::I16x8GeS => write_simd_opcode(bytes, opcode::I16X8_GE_S),
+        Instruction::I16x8GeU => write_simd_opcode(bytes, opcode::I16X8_GE_U),
+        Instruction::I32x4Eq => write_simd_opcode(bytes, opcode::I32X4_EQ),
+        Instruction::I32x4Ne => write_simd_opcode(bytes, opcode::I32X4_NE),
+        Instruction::I32x4LtS => write_simd_opcode(bytes, opcode::I32X4_LT_S),
+        Instruction::I32x4LtU => write_simd_opcode(bytes, opcode::I32X4_LT_U),
+        Instruction::I32x4GtS => write_simd_opcode(bytes, opcode::I32X4_GT_S),
+        Instruction::I32x4GtU => write_simd_opcode(bytes, opcode::I32X4_GT_U),
+        Instruction::I32x4LeS => write_simd_opcode(bytes, opcode::I32X4_LE_S),
+        Instruction::I32x4LeU => write_simd_opcode(bytes, opcode::I32X4_LE_U),
+        Instruction::I32x4GeS => write_simd_opcode(bytes, opcode::I32X4_GE_S),
+        Instruction::I32x4GeU => write_simd_opcode(bytes, opcode::I32X4_GE_U),
+        Instruction::F32x4Eq => write_simd_opcode(bytes, opcode::F32X4_EQ),
+        Instruction::F32x4Ne => write_simd_opcode(bytes, opcode::F32X4_NE),
+        Instruction::F32x4Lt => write_simd_opcode(bytes, opcode::F32X4_LT),
+        Instruction::F32x4Gt => write_simd_opcode(bytes, opcode::F32X4_GT),
+        Instruction::F32x4Le => write_simd_opcode(bytes, opcode::F32X4_LE),
+        Instruction::F32x4Ge => write_simd_opcode(bytes, opcode::F32X4_GE),
+        Instruction::F64x2Eq => write_simd_opcode(bytes, opcode::F64X2_EQ),
+        Instruction::F64x2Ne => write_simd_opcode(bytes, opcode::F64X2_NE),
+        Instruction::F64x2Lt => write_simd_opcode(bytes, opcode::F64X2_LT),
+        Instruction::F64x2Gt => write_simd_opcode(bytes, opcode::F64X2_GT),
+        Instruction::F64x2Le => write_simd_opcode(bytes, opcode::F64X2_LE),
+        Instruction::F64x2Ge => write_simd_opcode(bytes, opcode::F64X2_GE),
+        Instruction::V128Not => write_simd_opcode(bytes, opcode::V128_NOT),
+        Instruction::V128And => write_simd_opcode(bytes, opcode::V128_AND),
+        Instruction::V128Andnot => write_simd_opcode(bytes, opcode::V128_ANDNOT),
+        Instruction::V128Or => write_simd_opcode(bytes, opcode::V128_OR),
+        Instruction::V128Xor => write_simd_opcode(bytes, opcode::V128_XOR),
+        Instruction::V128Bitselect => write_simd_opcode(bytes, opcode::V128_BITSELECT),
+        Instruction::V128AnyTrue => write_simd_opcode(bytes, opcode::V128_ANY_TRUE),
+        Instruction::V128Load8Lane(argument, lane_index) => {
+            write_simd_opcode(bytes, opcode::V128_LOAD8_LANE);
+            write_memory_argument(bytes, argument);
+            bytes.push(*lane_index);
+        }
+        Instruction::V128Load16Lane(argument, lane_index) => {
+            write_simd_opcode(bytes, opcode::V128_LOAD16_LANE);
+            write_memory_argument(bytes, argument);
+            bytes.push(*lane_index);
+        }
+        Instruction::V128Load32Lane(argument, lane_index) => {
+            write_simd_opcode(bytes, opcode::V128_LOAD32_LANE);
+            write_memory_argument(bytes, argument);
+            bytes.push(*lane_index);
+        }
+        Instruction::V128Load64Lane(argument, lane_index) => {
+            write_simd_opcode(bytes, opcode::V128_LOAD64_LANE);
+            write_memory_argument(bytes, argument);
+            bytes.push(*lane_index);
+        }
+        Instruction::V128Store8Lane(argument, lane_index) => {
+            write_simd_opcode(bytes, opcode::V128_STORE8_LANE);
+            write_memory_argument(bytes, argument);
+            bytes.push(*lane_index);
+        }
+        Instruction::V128Store16Lane(argument, lane_index) => {
+            write_simd_opcode(bytes, opcode::V128_STORE16_LANE);
+            write_memory_argument(bytes, argument);
+            bytes.push(*lane_index);
+        }
+        Instruction::V128Store32Lane(argument, lane_index) => {
+            write_simd_opcode(bytes, opcode::V128_STORE32_LANE);
+            write_memory_argument(bytes, argument);
+            bytes.push(*lane_index);
+        }
+        Instruction::V128Store64Lane(argument, lane_index) => {
+            write_simd_opcode(bytes, opcode::V128_STORE64_LANE);
+            write_memory_argument(bytes, argument);
+            bytes.push(*lane_index);
+        }
+        Instruction::V128Load32Zero(argument) => {
+            write_simd_opcode(bytes, opcode::V128_LOAD32_ZERO);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::V128Load64Zero(argument) => {
+            write_simd_opcode(bytes, opcode::V128_LOAD64_ZERO);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::F32x4DemoteF64x2Zero => {
+            write_simd_opcode(bytes, opcode::F32X4_DEMOTE_F64X2_ZERO)
+        }
+        Instruction::F64x2PromoteLowF32x4 => {
+            write_simd_opcode(bytes, opcode::F64X2_PROMOTE_LOW_F32X4)
+        }
+        Instruction::I8x16Abs => write_simd_opcode(bytes, opcode::I8X16_ABS),
+        Instruction::I8x16Neg => write_simd_opcode(bytes, opcode::I8X16_NEG),
+        Instruction::I8x16Popcnt => write_simd_opcode(bytes, opcode::I8X16_POPCNT),
+        Instruction::I8x16AllTrue => write_simd_opcode(bytes, opcode::I8X16_ALL_TRUE),
+        Instruction::I8x16Bitmask => write_simd_opcode(bytes, opcode::I8X16_BITMASK),
+        Instruction::I8x16NarrowI16x8S => write_simd_opcode(bytes, opcode::I8X16_NARROW_I16X8_S),
+        Instruction::I8x16NarrowI16x8U => write_simd_opcode(bytes, opcode::I8X16_NARROW_I16X8_U),
+        Instruction::F32x4Ceil => write_simd_opcode(bytes, opcode::F32X4_CEIL),
+        Instruction::F32x4Floor => write_simd_opcode(bytes, opcode::F32X4_FLOOR),
+        Instruction::F32x4Trunc => write_simd_opcode(bytes, opcode::F32X4_TRUNC),
+        Instruction::F32x4Nearest => write_simd_opcode(bytes, opcode::F32X4_NEAREST),
+        Instruction::I8x16Shl => write_simd_opcode(bytes, opcode::I8X16_SHL),
+        Instruction::I8x16ShrS => write_simd_opcode(bytes, opcode::I8X16_SHR_S),
+        Instruction::I8x16ShrU => write_simd_opcode(bytes, opcode::I8X16_SHR_U),
+        Instruction::I8x16Add => write_simd_opcode(bytes, opcode::I8X16_ADD),
+        Instruction::I8x16AddSatS => write_simd_opcode(bytes, opcode::I8X16_ADD_SAT_S),
+        Instruction::I8x16AddSatU => write_simd_opcode(bytes, opcode::I8X16_ADD_SAT_U),
+        Instruction::I8x16Sub => write_simd_opcode(bytes, opcode::I8X16_SUB),
+        Instruction::I8x16SubSatS => write_simd_opcode(bytes, opcode::I8X16_SUB_SAT_S),
+        Instruction::I8x16SubSatU => write_simd_opcode(bytes, opcode::I8X16_SUB_SAT_U),
+        Instruction::F64x2Ceil => write_simd_opcode(bytes, opcode::F64X2_CEIL),
+        Instruction::F64x2Floor => write_simd_opcode(bytes, opcode::F64X2_FLOOR),
+        Instruction::I8x16MinS => write_simd_opcode(bytes, opcode::I8X16_MIN_S),
+        Instruction::I8x16MinU => write_simd_opcode(bytes, opcode::I8X16_MIN_U),
+        Instruction::I8x16MaxS => write_simd_opcode(bytes, opcode::I8X16_MAX_S),
+        Instruction::I8x16MaxU => write_simd_opcode(bytes, opcode::I8X16_MAX_U),
+        Instruction::F64x2Trunc => write_simd_opcode(bytes, opcode::F64X2_TRUNC),
+        Instruction::I8x16AvgrU => write_simd_opcode(bytes, opcode::I8X16_AVGR_U),
+        Instruction::I16x8ExtaddPairwiseI8x16S => {
+            write_simd_opcode(bytes, opcode::I16X8_EXTADD_PAIRWISE_I8X16_S)
+        }
+        Instruction::I16x8ExtaddPairwiseI8x16U => {
+            write_simd_opcode(bytes, opcode::I16X8_EXTADD_PAIRWISE_I8X16_U)
+        }
+        Instruction::I32x4ExtaddPairwiseI16x8S => {
+            write_simd_opcode(bytes, opcode::I32X4_EXTADD_PAIRWISE_I16X8_S)
+        }
+        Instruction::I32x4ExtaddPairwiseI16x8U => {
+            write_simd_opcode(bytes, opcode::I32X4_EXTADD_PAIRWISE_I16X8_U)
+        }
+        Instruction::I16x8Abs => write_simd_opcode(bytes, opcode::I16X8_ABS),
+        Instruction::I16x8Neg => write_simd_opcode(bytes, opcode::I16X8_NEG),
+        Instruction::I16x8Q15mulrSatS => write_simd_opcode(bytes, opcode::I16X8_Q15MULR_SAT_S),
+        Instruction::I16x8AllTrue => write_simd_opcode(bytes, opcode::I16X8_ALL_TRUE),
+        Instruction::I16x8Bitmask => write_simd_opcode(bytes, opcode::I16X8_BITMASK),
+        Instruction::I16x8NarrowI32x4S => write_simd_opcode(bytes, opcode::I16X8_NARROW_I32X4_S),
+        Instruction::I16x8NarrowI32x4U => write_simd_opcode(bytes, opcode::I16X8_NARROW_I32X4_U),
+        Instruction::I16x8ExtendLowI8x16S => {
+            write_simd_opcode(bytes, opcode::I16X8_EXTEND_LOW_I8X16_S)
+        }
+        Instruction::I16x8ExtendHighI8x16S => {
+            write_simd_opcode(bytes, opcode::I16X8_EXTEND_HIGH_I8X16_S)
+        }
+        Instruction::I16x8ExtendLowI8x16U => {
+            write_simd_opcode(bytes, opcode::I16X8_EXTEND_LOW_I8X16_U)
+        }
+        Instruction::I16x8ExtendHighI8x16U => {
+            write_simd_opcode(bytes, opcode::I16X8_EXTEND_HIGH_I8X16_U)
+        }
+        Instruction::I16x8Shl => write_simd_opcode(bytes, opcode::I16X8_SHL),
+        Instruction::I16x8ShrS => write_simd_opcode(bytes, opcode::I16X8_SHR_S),
+        Instruction::I16x8ShrU => write_simd_opcode(bytes, opcode::I16X8_SHR_U),
+        Instruction::I16x8Add => write_simd_opcode(bytes, opcode::I16X8_ADD),
+        Instruction::I16x8AddSatS => write_simd_opcode(bytes, opcode::I16X8_ADD_SAT_S),
+        Instruction::I16x8AddSatU => write_simd_opcode(bytes, opcode::I16X8_ADD_SAT_U),
+        Instruction::I16x8Sub => write_simd_opcode(bytes, opcode::I16X8_SUB),
+        Instruction::I16x8SubSatS => write_simd_opcode(bytes, opcode::I16X8_SUB_SAT_S),
+        Instruction::I16x8SubSatU => write_simd_opcode(bytes, opcode::I16X8_SUB_SAT_U),
+        Instruction::F64x2Nearest => write_simd_opcode(bytes, opcode::F64X2_NEAREST),
+        Instruction::I16x8Mul => write_simd_opcode(bytes, opcode::I16X8_MUL),
+        Instruction::I16x8MinS => write_simd_opcode(bytes, opcode::I16X8_MIN_S),
+        Instruction::I16x8MinU => write_simd_opcode(bytes, opcode::I16X8_MIN_U),
+        Instruction::I16x8MaxS => write_simd_opcode(bytes, opcode::I16X8_MAX_S),
+        Instruction::I16x8MaxU => write_simd_opcode(bytes, opcode::I16X8_MAX_U),
+        Instruction::I16x8AvgrU => write_simd_opcode(bytes, opcode::I16X8_AVGR_U),
+        Instruction::I16x8ExtmulLowI8x16S => {
+            write_simd_opcode(bytes, opcode::I16X8_EXTMUL_LOW_I8X16_S)
+        }
+        Instruction::I16x8ExtmulHighI8x16S => {
+            write_simd_opcode(bytes, opcode::I16X8_EXTMUL_HIGH_I8X16_S)
+        }
+        Instruction::I16x8ExtmulLowI8x16U => {
+            write_simd_opcode(bytes, opcode::I16X8_EXTMUL_LOW_I8X16_U)
+        }
+        Instruction::I16x8ExtmulHighI8x16U => {
+            write_simd_opcode(bytes, opcode::I16X8_EXTMUL_HIGH_I8X16_U)
+        }
+        Instruction::I32x4Abs => write_simd_opcode(bytes, opcode::I32X4_ABS),
+        Instruction::I32x4Neg => write_simd_opcode(bytes, opcode::I32X4_NEG),
+        Instruction::I32x4AllTrue => write_simd_opcode(bytes, opcode::I32X4_ALL_TRUE),
+        Instruction::I32x4Bitmask => write_simd_opcode(bytes, opcode::I32X4_BITMASK),
+        Instruction::I32x4ExtendLowI16x8S => {
+            write_simd_opcode(bytes, opcode::I32X4_EXTEND_LOW_I16X8_S)
+        }
+        Instruction::I32x4ExtendHighI16x8S => {
+            write_simd_opcode(bytes, opcode::I32X4_EXTEND_HIGH_I16X8_S)
+        }
+        Instruction::I32x4ExtendLowI16x8U => {
+            write_simd_opcode(bytes, opcode::I32X4_EXTEND_LOW_I16X8_U)
+        }
+        Instruction::I32x4ExtendHighI16x8U => {
+            write_simd_opcode(bytes, opcode::I32X4_EXTEND_HIGH_I16X8_U)
+        }
+        Instruction::I32x4Shl => write_simd_opcode(bytes, opcode::I32X4_SHL),
+        Instruction::I32x4ShrS => write_simd_opcode(bytes, opcode::I32X4_SHR_S),
+        Instruction::I32x4ShrU => write_simd_opcode(bytes, opcode::I32X4_SHR_U),
+        Instruction::I32x4Add => write_simd_opcode(bytes, opcode::I32X4_ADD),
+        Instruction::I32x4Sub => write_simd_opcode(bytes, opcode::I32X4_SUB),
+        Instruction::I32x4Mul => write_simd_opcode(bytes, opcode::I32X4_MUL),
+        Instruction::I32x4MinS => write_simd_opcode(bytes, opcode::I32X4_MIN_S),
+        Instruction::I32x4MinU => write_simd_opcode(bytes, opcode::I32X4_MIN_U),
+        Instruction::I32x4MaxS => write_simd_opcode(bytes, opcode::I32X4_MAX_S),
+        Instruction::I32x4MaxU => write_simd_opcode(bytes, opcode::I32X4_MAX_U),
+        Instruction::I32x4DotI16x8S => write_simd_opcode(bytes, opcode::I32X4_DOT_I16X8_S),
+        Instruction::I32x4ExtmulLowI16x8S => {
+            write_simd_opcode(bytes, opcode::I32X4_EXTMUL_LOW_I16X8_S)
+        }
+        Instruction::I32x4ExtmulHighI16x8S => {
+            write_simd_opcode(bytes, opcode::I32X4_EXTMUL_HIGH_I16X8_S)
+        }
+        Instruction::I32x4ExtmulLowI16x8U => {
+            write_simd_opcode(bytes, opcode::I32X4_EXTMUL_LOW_I16X8_U)
+        }
+        Instruction::I32x4ExtmulHighI16x8U => {
+            write_simd_opcode(bytes, opcode::I32X4_EXTMUL_HIGH_I16X8_U)
+        }
+        Instruction::I64x2Abs => write_simd_opcode(bytes, opcode::I64X2_ABS),
+        Instruction::I64x2Neg => write_simd_opcode(bytes, opcode::I64X2_NEG),
+        Instruction::I64x2AllTrue => write_simd_opcode(bytes, opcode::I64X2_ALL_TRUE),
+        Instruction::I64x2Bitmask => write_simd_opcode(bytes, opcode::I64X2_BITMASK),
+        Instruction::I64x2ExtendLowI32x4S => {
+            write_simd_opcode(bytes, opcode::I64X2_EXTEND_LOW_I32X4_S)
+        }
+        Instruction::I64x2ExtendHighI32x4S => {
+            write_simd_opcode(bytes, opcode::I64X2_EXTEND_HIGH_I32X4_S)
+        }
+        Instruction::I64x2ExtendLowI32x4U => {
+            write_simd_opcode(bytes, opcode::I64X2_EXTEND_LOW_I32X4_U)
+        }
+        Instruction::I64x2ExtendHighI32x4U => {
+            write_simd_opcode(bytes, opcode::I64X2_EXTEND_HIGH_I32X4_U)
+        }
+        Instruction::I64x2Shl => write_simd_opcode(bytes, opcode::I64X2_SHL),
+        Instruction::I64x2ShrS => write_simd_opcode(bytes, opcode::I64X2_SHR_S),
+        Instruction::I64x2ShrU => write_simd_opcode(bytes, opcode::I64X2_SHR_U),
+        Instruction::I64x2Add => write_simd_opcode(bytes, opcode::I64X2_ADD),
+        Instruction::I64x2Sub => write_simd_opcode(bytes, opcode::I64X2_SUB),
+        Instruction::I64x2Mul => write_simd_opcode(bytes, opcode::I64X2_MUL),
+        Instruction::I64x2Eq => write_simd_opcode(bytes, opcode::I64X2_EQ),
+        Instruction::I64x2Ne => write_simd_opcode(bytes, opcode::I64X2_NE),
+        Instruction::I64x2LtS => write_simd_opcode(bytes, opcode::I64X2_LT_S),
+        Instruction::I64x2GtS => write_simd_opcode(bytes, opcode::I64X2_GT_S),
+        Instruction::I64x2LeS => write_simd_opcode(bytes, opcode::I64X2_LE_S),
+        Instruction::I64x2GeS => write_simd_opcode(bytes, opcode::I64X2_GE_S),
+        Instruction::I64x2ExtmulLowI32x4S => {
+            write_simd_opcode(bytes, opcode::I64X2_EXTMUL_LOW_I32X4_S)
+        }
+        Instruction::I64x2ExtmulHighI32x4S => {
+            write_simd_opcode(bytes, opcode::I64X2_EXTMUL_HIGH_I32X4_S)
+        }
+        Instruction::I64x2ExtmulLowI32x4U => {
+            write_simd_opcode(bytes, opcode::I64X2_EXTMUL_LOW_I32X4_U)
+        }
+        Instruction::I64x2ExtmulHighI32x4U => {
+            write_simd_opcode(bytes, opcode::I64X2_EXTMUL_HIGH_I32X4_U)
+        }
+        Instruction::F32x4Abs => write_simd_opcode(bytes, opcode::F32X4_ABS),
+        Instruction::F32x4Neg => write_simd_opcode(bytes, opcode::F32X4_NEG),
+        Instruction::F32x4Sqrt => write_simd_opcode(bytes, opcode::F32X4_SQRT),
+        Instruction::F32x4Add => write_simd_opcode(bytes, opcode::F32X4_ADD),
+        Instruction::F32x4Sub => write_simd_opcode(bytes, opcode::F32X4_SUB),
+        Instruction::F32x4Mul => write_simd_opcode(bytes, opcode::F32X4_MUL),
+        Instruction::F32x4Div => write_simd_opcode(bytes, opcode::F32X4_DIV),
+        Instruction::F32x4Min => write_simd_opcode(bytes, opcode::F32X4_MIN),
+        Instruction::F32x4Max => write_simd_opcode(bytes, opcode::F32X4_MAX),
+        Instruction::F32x4Pmin => write_simd_opcode(bytes, opcode::F32X4_PMIN),
+        Instruction::F32x4Pmax => write_simd_opcode(bytes, opcode::F32X4_PMAX),
+        Instruction::F64x2Abs => write_simd_opcode(bytes, opcode::F64X2_ABS),
+        Instruction::F64x2Neg => write_simd_opcode(bytes, opcode::F64X2_NEG),
+        Instruction::F64x2Sqrt => write_simd_opcode(bytes, opcode::F64X2_SQRT),
+        Instruction::F64x2Add => write_simd_opcode(bytes, opcode::F64X2_ADD),
+        Instruction::F64x2Sub => write_simd_opcode(bytes, opcode::F64X2_SUB),
+        Instruction::F64x2Mul => write_simd_opcode(bytes, opcode::F64X2_MUL),
+        Instruction::F64x2Div => write_simd_opcode(bytes, opcode::F64X2_DIV),
+        Instruction::F64x2Min => write_simd_opcode(bytes, opcode::F64X2_MIN),
+        Instruction::F64x2Max => write_simd_opcode(bytes, opcode::F64X2_MAX),
+        Instruction::F64x2Pmin => write_simd_opcode(bytes, opcode::F64X2_PMIN),
+        Instruction::F64x2Pmax => write_simd_opcode(bytes, opcode::F64X2_PMAX),
+        Instruction::I32x4TruncSatF32x4S => {
+            write_simd_opcode(bytes, opcode::I32X4_TRUNC_SAT_F32X4_S)
+        }
+        Instruction::I32x4TruncSatF32x4U => {
+            write_simd_opcode(bytes, opcode::I32X4_TRUNC_SAT_F32X4_U)
+        }
+        Instruction::F32x4ConvertI32x4S => write_simd_opcode(bytes, opcode::F32X4_CONVERT_I32X4_S),
+        Instruction::F32x4ConvertI32x4U => write_simd_opcode(bytes, opcode::F32X4_CONVERT_I32X4_U),
+        Instruction::I32x4TruncSatF64x2SZero => {
+            write_simd_opcode(bytes, opcode::I32X4_TRUNC_SAT_F64X2_S_ZERO)
+        }
+        Instruction::I32x4TruncSatF64x2UZero => {
+            write_simd_opcode(bytes, opcode::I32X4_TRUNC_SAT_F64X2_U_ZERO)
+        }
+        Instruction::F64x2ConvertLowI32x4S => {
+            write_simd_opcode(bytes, opcode::F64X2_CONVERT_LOW_I32X4_S)
+        }
+        Instruction::F64x2ConvertLowI32x4U => {
+            write_simd_opcode(bytes, opcode::F64X2_CONVERT_LOW_I32X4_U)
+        }
+
+        // 原子内存访问指令
+        Instruction::MemoryAtomicNotify(argument) => {
+            write_atomic_opcode(bytes, opcode::MEMORY_ATOMIC_NOTIFY);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::MemoryAtomicWait32(argument) => {
+            write_atomic_opcode(bytes, opcode::MEMORY_ATOMIC_WAIT32);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::MemoryAtomicWait64(argument) => {
+            write_atomic_opcode(bytes, opcode::MEMORY_ATOMIC_WAIT64);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::AtomicFence => {
+            write_atomic_opcode(bytes, opcode::ATOMIC_FENCE);
+            bytes.push(0x00);
+        }
+        Instruction::I32AtomicLoad(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_LOAD);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicLoad(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_LOAD);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicLoad8U(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_LOAD8_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicLoad16U(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_LOAD16_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicLoad8U(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_LOAD8_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicLoad16U(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_LOAD16_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicLoad32U(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_LOAD32_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicStore(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_STORE);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicStore(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_STORE);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicStore8(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_STORE8);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicStore16(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_STORE16);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicStore8(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_STORE8);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicStore16(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_STORE16);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicStore32(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_STORE32);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicRmwAdd(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_RMW_ADD);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmwAdd(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW_ADD);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicRmw8AddU(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_RMW8_ADD_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicRmw16AddU(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_RMW16_ADD_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmw8AddU(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW8_ADD_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmw16AddU(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW16_ADD_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmw32AddU(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW32_ADD_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicRmwSub(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_RMW_SUB);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmwSub(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW_SUB);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicRmw8SubU(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_RMW8_SUB_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicRmw16SubU(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_RMW16_SUB_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmw8SubU(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW8_SUB_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmw16SubU(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW16_SUB_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmw32SubU(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW32_SUB_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicRmwAnd(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_RMW_AND);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmwAnd(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW_AND);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicRmw8AndU(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_RMW8_AND_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicRmw16AndU(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_RMW16_AND_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmw8AndU(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW8_AND_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmw16AndU(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW16_AND_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmw32AndU(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW32_AND_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicRmwOr(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_RMW_OR);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmwOr(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW_OR);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicRmw8OrU(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_RMW8_OR_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicRmw16OrU(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_RMW16_OR_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmw8OrU(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW8_OR_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmw16OrU(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW16_OR_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmw32OrU(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW32_OR_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicRmwXor(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_RMW_XOR);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmwXor(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW_XOR);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicRmw8XorU(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_RMW8_XOR_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicRmw16XorU(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_RMW16_XOR_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmw8XorU(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW8_XOR_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmw16XorU(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW16_XOR_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmw32XorU(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW32_XOR_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicRmwXchg(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_RMW_XCHG);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmwXchg(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW_XCHG);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicRmw8XchgU(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_RMW8_XCHG_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicRmw16XchgU(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_RMW16_XCHG_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmw8XchgU(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW8_XCHG_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmw16XchgU(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW16_XCHG_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmw32XchgU(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW32_XCHG_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicRmwCmpxchg(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_RMW_CMPXCHG);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmwCmpxchg(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW_CMPXCHG);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicRmw8CmpxchgU(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_RMW8_CMPXCHG_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I32AtomicRmw16CmpxchgU(argument) => {
+            write_atomic_opcode(bytes, opcode::I32_ATOMIC_RMW16_CMPXCHG_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmw8CmpxchgU(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW8_CMPXCHG_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmw16CmpxchgU(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW16_CMPXCHG_U);
+            write_memory_argument(bytes, argument);
+        }
+        Instruction::I64AtomicRmw32CmpxchgU(argument) => {
+            write_atomic_opcode(bytes, opcode::I64_ATOMIC_RMW32_CMPXCHG_U);
+            write_memory_argument(bytes, argument);
+        }
+    }
+}
+
+/// block_type = (signed) i32
+fn write_block_type(bytes: &mut Vec<u8>, block_type: &BlockType) {
+    let value = match block_type {
+        BlockType::ResultI32 => types::BLOCK_TYPE_I32,
+        BlockType::ResultI64 => types::BLOCK_TYPE_I64,
+        BlockType::ResultF32 => types::BLOCK_TYPE_F32,
+        BlockType::ResultF64 => types::BLOCK_TYPE_F64,
+        BlockType::ResultV128 => types::BLOCK_TYPE_V128,
+        BlockType::ResultFuncRef => types::BLOCK_TYPE_FUNC_REF,
+        BlockType::ResultExternRef => types::BLOCK_TYPE_EXTERN_REF,
+        BlockType::ResultExnRef => types::BLOCK_TYPE_EXN_REF,
+        BlockType::ResultEmpty => types::BLOCK_TYPE_EMPTY,
+        BlockType::TypeIndex(type_index) => *type_index as i32,
+    };
+    write_i32(bytes, value);
+}
+
+/// <catch> = items_count:u32 + catch{*}
+fn write_catch_clauses(bytes: &mut Vec<u8>, catch_clauses: &[CatchClause]) {
+    write_u32(bytes, catch_clauses.len() as u32);
+    for catch_clause in catch_clauses {
+        match catch_clause {
+            CatchClause::Catch(tag_index, relative_depth) => {
+                bytes.push(types::CATCH_KIND_CATCH);
+                write_u32(bytes, *tag_index);
+                write_u32(bytes, *relative_depth);
+            }
+            CatchClause::CatchRef(tag_index, relative_depth) => {
+                bytes.push(types::CATCH_KIND_CATCH_REF);
+                write_u32(bytes, *tag_index);
+                write_u32(bytes, *relative_depth);
+            }
+            CatchClause::CatchAll(relative_depth) => {
+                bytes.push(types::CATCH_KIND_CATCH_ALL);
+                write_u32(bytes, *relative_depth);
+            }
+            CatchClause::CatchAllRef(relative_depth) => {
+                bytes.push(types::CATCH_KIND_CATCH_ALL_REF);
+                write_u32(bytes, *relative_depth);
+            }
+        }
+    }
+}
+
+fn write_opcode_and_index(bytes: &mut Vec<u8>, opcode: u8, index: u32) {
+    bytes.push(opcode);
+    write_u32(bytes, index);
+}
+
+/// memory_load_and_store_argument = align:u32 + offset:u32
+///                                | (align | 0x40):u32 + memory_block_index:u32 + offset:u32
+fn write_memory_access(bytes: &mut Vec<u8>, opcode: u8, memory_argument: &MemoryArgument) {
+    bytes.push(opcode);
+    write_memory_argument(bytes, memory_argument);
+}
+
+fn write_memory_argument(bytes: &mut Vec<u8>, memory_argument: &MemoryArgument) {
+    if memory_argument.memory_block_index == 0 {
+        write_u32(bytes, memory_argument.align);
+    } else {
+        write_u32(bytes, memory_argument.align | 0x40);
+        write_u32(bytes, memory_argument.memory_block_index);
+    }
+    write_u64(bytes, memory_argument.offset);
+}
+
+/// extension_instruction = 0xfc + extension_code:u32 + immediate_arguments
+fn write_extension_opcode(bytes: &mut Vec<u8>, extension_code: u32) {
+    bytes.push(opcode::EXTENSION_0XFC);
+    write_u32(bytes, extension_code);
+}
+
+/// simd_instruction = 0xfd + sub_opcode:u32 + immediate_arguments
+fn write_simd_opcode(bytes: &mut Vec<u8>, sub_opcode: u32) {
+    bytes.push(opcode::EXTENSION_0XFD);
+    write_u32(bytes, sub_opcode);
+}
+
+/// atomic_instruction = 0xfe + sub_opcode:u32 + immediate_arguments
+fn write_atomic_opcode(bytes: &mut Vec<u8>, sub_opcode: u32) {
+    bytes.push(opcode::EXTENSION_0XFE);
+    write_u32(bytes, sub_opcode);
+}
+
+// 辅助函数
+
+/// 写入变长（leb128 编码的）u32
+fn write_u32(bytes: &mut Vec<u8>, value: u32) {
+    bytes.extend(leb128encoder::encode_u32(value));
+}
+
+/// 写入变长（leb128 编码的）u64
+fn write_u64(bytes: &mut Vec<u8>, value: u64) {
+    bytes.extend(leb128encoder::encode_u64(value));
+}
+
+/// 写入变长（leb128 编码的）i32
+fn write_i32(bytes: &mut Vec<u8>, value: i32) {
+    bytes.extend(leb128encoder::encode_i32(value));
+}
+
+/// 写入变长（leb128 编码的）i64
+fn write_i64(bytes: &mut Vec<u8>, value: i64) {
+    bytes.extend(leb128encoder::encode_i64(value));
+}
+
+/// 写入如下结构的 u32 数组
+/// `length:u32 + u32{*}`
+fn write_u32_vec(bytes: &mut Vec<u8>, values: &[u32]) {
+    write_u32(bytes, values.len() as u32);
+    for value in values {
+        write_u32(bytes, *value);
+    }
+}
+
+/// 写入如下结构的字符串数组
+/// `length:u32 + string{*}`
+fn write_strings(bytes: &mut Vec<u8>, values: &[String]) {
+    write_u32(bytes, values.len() as u32);
+    for value in values {
+        write_string(bytes, value);
+    }
+}
+
+/// 写入字符串
+/// `length:u32 + byte{*}`
+fn write_string(bytes: &mut Vec<u8>, value: &str) {
+    write_u32(bytes, value.len() as u32);
+    bytes.extend_from_slice(value.as_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use anvm_ast::{
+        ast::{
+            CodeItem, CustomItem, DynamicLinking, DynamicLinkingExportInfo,
+            DynamicLinkingImportInfo, DynamicLinkingMemoryInfo, ExportDescriptor, ExportItem,
+            FunctionType, IndexNamePair, LocalGroup, Module, NameCollection, TypeItem,
+        },
+        instruction::{BlockType, Instruction},
+        types::ValueType,
+    };
+    use anvm_binary_parser::parser;
+    use pretty_assertions::assert_eq;
+
+    use super::{encode, encode_with_layout};
+
+    // 辅助方法
+    fn get_test_binary_resource(filename: &str) -> Vec<u8> {
+        let mut path_buf = env::current_dir().unwrap();
+
+        // 使用 `cargo test` 测试时，
+        // `env::current_dir()` 函数获得的当前目录为
+        // `./xiaoxuan-vm/crates/binary-encoder`；
+        //
+        // 但如果使用 vscode 的源码编辑框里面的 `debug` 按钮开始调试，
+        // `env::current_dir()` 函数获得的当前目录为
+        // `./xiaoxuan-vm`。
+        //
+        // 下面语句用于处理这种情况。
+
+        if !path_buf.ends_with("binary-encoder") {
+            path_buf.push("crates");
+            path_buf.push("binary-encoder");
+        }
+        let fullname_buf = path_buf.join("resources").join(filename);
+        let fullname = fullname_buf.to_str().unwrap();
+        fs::read(fullname)
+            .unwrap_or_else(|_| panic!("failed to read the specified binary file: {}", fullname))
+    }
+
+    /// 解析二进制模块再编码，检查结果是否跟原模块的二进制数据完全一致
+    fn test_round_trip_resource(filename: &str) {
+        let bytes = get_test_binary_resource(filename);
+        let module = parser::parse(&bytes).unwrap();
+        assert_eq!(encode(&module), bytes);
+    }
+
+    fn create_test_module() -> Module {
+        Module {
+            custom_items: vec![CustomItem::NameCollections(vec![
+                NameCollection::FunctionNames(vec![IndexNamePair {
+                    index: 0,
+                    name: "inc".to_string(),
+                }]),
+            ])],
+            type_items: vec![TypeItem::FunctionType(FunctionType {
+                params: vec![ValueType::I32],
+                results: vec![ValueType::I32],
+            })],
+            import_items: vec![],
+            internal_function_to_type_index_list: vec![0],
+            tables: vec![],
+            memory_blocks: vec![],
+            tag_items: vec![],
+            global_items: vec![],
+            export_items: vec![ExportItem {
+                name: "inc".to_string(),
+                export_descriptor: ExportDescriptor::FunctionIndex(0),
+            }],
+            start_function_index: None,
+            element_items: vec![],
+            code_items: vec![CodeItem {
+                local_groups: vec![LocalGroup {
+                    variable_count: 1,
+                    value_type: ValueType::I64,
+                }],
+                instruction_items: vec![
+                    Instruction::Block(BlockType::ResultI32, 0),
+                    Instruction::LocalGet(0),
+                    Instruction::I32Const(-1),
+                    Instruction::I32Sub,
+                    Instruction::End,
+                    Instruction::End,
+                ],
+            }],
+            data_items: vec![],
+            data_count: None,
+        }
+    }
+
+    #[test]
+    fn test_encode() {
+        let module = create_test_module();
+        let bytes = encode(&module);
+
+        assert_eq!(
+            bytes,
+            vec![
+                0x00, 0x61, 0x73, 0x6d, // magic number
+                0x01, 0x00, 0x00, 0x00, // version
+                0x01, 0x06, 0x01, 0x60, 0x01, 0x7f, 0x01, 0x7f, // type section
+                0x03, 0x02, 0x01, 0x00, // function section
+                0x07, 0x07, 0x01, 0x03, 0x69, 0x6e, 0x63, 0x00, 0x00, // export section
+                0x0a, 0x0e, 0x01, 0x0c, 0x01, 0x01, 0x7e, // code section, locals
+                0x02, 0x7f, 0x20, 0x00, 0x41, 0x7f, 0x6b, 0x0b, 0x0b, // instructions
+                0x00, 0x0d, 0x04, 0x6e, 0x61, 0x6d, 0x65, // custom section "name"
+                0x01, 0x06, 0x01, 0x00, 0x03, 0x69, 0x6e, 0x63, // function names
+            ]
+        );
+
+        assert_eq!(parser::parse(&bytes).unwrap(), module);
+    }
+
+    #[test]
+    fn test_encode_custom_section() {
+        let mut module = create_test_module();
+        module.custom_items.push(CustomItem::Other(
+            "producers".to_string(),
+            vec![0x01, 0x02, 0x03],
+        ));
+
+        let bytes = encode(&module);
+        assert_eq!(
+            bytes[bytes.len() - 15..],
+            [
+                0x00, 0x0d, // custom section
+                0x09, 0x70, 0x72, 0x6f, 0x64, 0x75, 0x63, 0x65, 0x72, 0x73, // "producers"
+                0x01, 0x02, 0x03, // payload
+            ]
+        );
+        assert_eq!(parser::parse(&bytes).unwrap(), module);
+    }
+
+    #[test]
+    fn test_encode_dynamic_linking_section() {
+        let mut module = create_test_module();
+        module.custom_items.insert(
+            0,
+            CustomItem::DynamicLinking(DynamicLinking {
+                memory_info: Some(DynamicLinkingMemoryInfo {
+                    memory_size: 16,
+                    memory_alignment: 2,
+                    table_size: 1,
+                    table_alignment: 0,
+                }),
+                needed: vec!["libm.so".to_string()],
+                export_infos: vec![DynamicLinkingExportInfo {
+                    name: "counter".to_string(),
+                    flags: 0x100,
+                }],
+                import_infos: vec![DynamicLinkingImportInfo {
+                    module_name: "env".to_string(),
+                    item_name: "log".to_string(),
+                    flags: 0x1,
+                }],
+                runtime_paths: vec![],
+            }),
+        );
+
+        // `dylink.0` 段紧跟在模块头部之后
+        let bytes = encode(&module);
+        assert_eq!(
+            bytes[8..61],
+            [
+                0x00, 0x33, // custom section
+                0x08, 0x64, 0x79, 0x6c, 0x69, 0x6e, 0x6b, 0x2e, 0x30, // "dylink.0"
+                0x01, 0x04, 0x10, 0x02, 0x01, 0x00, // mem info
+                0x02, 0x09, 0x01, 0x07, 0x6c, 0x69, 0x62, 0x6d, 0x2e, 0x73, 0x6f, // needed
+                0x03, 0x0b, 0x01, 0x07, 0x63, 0x6f, 0x75, 0x6e, 0x74, 0x65, 0x72, 0x80,
+                0x02, // export info
+                0x04, 0x0a, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x03, 0x6c, 0x6f, 0x67,
+                0x01, // import info
+            ]
+        );
+        assert_eq!(parser::parse(&bytes).unwrap(), module);
+    }
+
+    #[test]
+    fn test_round_trip_instructions() {
+        test_round_trip_resource("test-instruction-branch.wasm");
+        test_round_trip_resource("test-instruction-call.wasm");
+        test_round_trip_resource("test-instruction-const.wasm");
+        test_round_trip_resource("test-instruction-exception.wasm");
+        test_round_trip_resource("test-instruction-flow-control.wasm");
+        test_round_trip_resource("test-instruction-memory.wasm");
+        test_round_trip_resource("test-instruction-memory64.wasm");
+        test_round_trip_resource("test-extended-const.wasm");
+        test_round_trip_resource("test-instruction-multi-memory.wasm");
+        test_round_trip_resource("test-instruction-variable.wasm");
+    }
+
+    #[test]
+    fn test_round_trip_sections() {
+        test_round_trip_resource("test-section-2.wasm");
+        test_round_trip_resource("test-section-custom.wasm");
+        test_round_trip_resource("test-section-general.wasm");
+
+        // 含有 `数据条目段` 以及 "producers"、"target_features" 等自定义段
+        test_round_trip_resource("test-stdout-write-c.wasm");
+
+        // 含有 `dylink.0` 自定义段
+        test_round_trip_resource("test-dylink-lib.wasm");
+        test_round_trip_resource("test-dylink-main.wasm");
+    }
+
+    #[test]
+    fn test_round_trip_padded_leb128() {
+        // 目标文件（object file）里段的长度使用了填充至 5 个字节的 LEB128 编码，
+        // 链接结果 `hello-world-rust.wasm` 里的函数索引等需要重定位的整数也是如此。
+        for filename in ["test-section-1.wasm", "hello-world-rust.wasm"] {
+            let bytes = get_test_binary_resource(filename);
+            let (module, layout) = parser::parse_with_layout(&bytes).unwrap();
+            assert_eq!(encode_with_layout(&module, &layout), bytes);
+
+            // 不使用编码布局时输出的是最短编码
+            let encoded_bytes = encode(&module);
+            assert!(encoded_bytes.len() < bytes.len());
+            assert_eq!(parser::parse(&encoded_bytes).unwrap(), module);
+        }
+    }
+
+    #[test]
+    fn test_encode_with_modified_layout() {
+        let bytes = get_test_binary_resource("hello-world-rust.wasm");
+        let (mut module, layout) = parser::parse_with_layout(&bytes).unwrap();
+
+        // 修改过的指令不再使用原始编码
+        let (function_index, instruction_index) = *layout
+            .padded_instructions
+            .keys()
+            .find(|(_, instruction_index)| *instruction_index > 0)
+            .unwrap();
+        module.code_items[function_index]
+            .instruction_items
+            .insert(instruction_index, Instruction::Nop);
+
+        let encoded_bytes = encode_with_layout(&module, &layout);
+        assert_eq!(parser::parse(&encoded_bytes).unwrap(), module);
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+// # leb128 整数编码
+//
+// 编码过程是解码过程（`anvm_binary_parser::leb128decoder`）的逆过程：
+//
+// 从整数的低位开始，每次取 7 位作为一个字节的低 7 位，如果还有剩余的有效位，
+// 则把该字节的最高位（第 7 位，从 0 开始数）设置为 1，表示还有后续的内容。
+//
+// ## 有符号整数
+//
+// 对于有符号整数，需要使用算术右移（即高端补上符号位）取出每一个 7 位，
+// 当剩余的数值全部是符号位（即 0 或者 -1），并且当前字节的符号位（第 6 位）跟
+// 数值的符号一致时，编码结束。
+//
+// 编码器默认输出最短的字节序列，如果需要重现原模块的非最短编码，
+// 可以使用 `encode_u32_with_width` 把编码填充至指定的宽度。
+
+const CONTINUE_BIT: u8 = 0b1000_0000;
+const SIGN_BIT: u8 = 0b0100_0000;
+const LOW_BITS_MASK: u64 = 0b0111_1111;
+
+/// 编码无符号 64 位整型
+pub fn encode_u64(value: u64) -> Vec<u8> {
+    let mut bytes: Vec<u8> = vec![];
+    let mut remains = value;
+
+    loop {
+        let byte = (remains & LOW_BITS_MASK) as u8;
+        remains >>= 7;
+
+        if remains == 0 {
+            bytes.push(byte);
+            break;
+        }
+
+        bytes.push(byte | CONTINUE_BIT);
+    }
+
+    bytes
+}
+
+/// 编码有符号 64 位整型
+pub fn encode_i64(value: i64) -> Vec<u8> {
+    let mut bytes: Vec<u8> = vec![];
+    let mut remains = value;
+
+    loop {
+        let byte = (remains as u64 & LOW_BITS_MASK) as u8;
+
+        // 算术右移，高端补上符号位
+        remains >>= 7;
+
+        let is_finished =
+            (remains == 0 && byte & SIGN_BIT == 0) || (remains == -1 && byte & SIGN_BIT != 0);
+
+        if is_finished {
+            bytes.push(byte);
+            break;
+        }
+
+        bytes.push(byte | CONTINUE_BIT);
+    }
+
+    bytes
+}
+
+/// 编码无符号 32 位整型
+pub fn encode_u32(value: u32) -> Vec<u8> {
+    encode_u64(value as u64)
+}
+
+/// 编码有符号 32 位整型
+pub fn encode_i32(value: i32) -> Vec<u8> {
+    encode_i64(value as i64)
+}
+
+/// 编码无符号 32 位整型，并填充至指定的宽度（字节数）
+///
+/// 填充的方法是给最后一个字节加上后续标记，然后补上若干个 0x80，最后以 0x00 结尾。
+/// 如果最短编码已经达到或者超过指定的宽度，则返回最短编码。
+pub fn encode_u32_with_width(value: u32, width: usize) -> Vec<u8> {
+    let mut bytes = encode_u32(value);
+
+    if bytes.len() < width {
+        *bytes.last_mut().unwrap() |= CONTINUE_BIT;
+        bytes.resize(width - 1, CONTINUE_BIT);
+        bytes.push(0);
+    }
+
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode_i32, encode_i64, encode_u32, encode_u32_with_width, encode_u64};
+
+    #[test]
+    fn test_encode_u64() {
+        assert_eq!(encode_u64(0), vec![0x00]);
+        assert_eq!(encode_u64(1), vec![0x01]);
+        assert_eq!(encode_u64(127), vec![0x7f]);
+        assert_eq!(encode_u64(128), vec![0x80, 0x01]);
+        assert_eq!(encode_u64(624485), vec![0xe5, 0x8e, 0x26]);
+        assert_eq!(
+            encode_u64(u64::MAX),
+            vec![0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]
+        );
+    }
+
+    #[test]
+    fn test_encode_i64() {
+        assert_eq!(encode_i64(0), vec![0x00]);
+        assert_eq!(encode_i64(1), vec![0x01]);
+        assert_eq!(encode_i64(-1), vec![0x7f]);
+        assert_eq!(encode_i64(63), vec![0x3f]);
+        assert_eq!(encode_i64(64), vec![0xc0, 0x00]);
+        assert_eq!(encode_i64(-64), vec![0x40]);
+        assert_eq!(encode_i64(-65), vec![0xbf, 0x7f]);
+        assert_eq!(encode_i64(-123456), vec![0xc0, 0xbb, 0x78]);
+        assert_eq!(
+            encode_i64(i64::MAX),
+            vec![0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00]
+        );
+        assert_eq!(
+            encode_i64(i64::MIN),
+            vec![0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7f]
+        );
+    }
+
+    #[test]
+    fn test_encode_u32() {
+        assert_eq!(encode_u32(0), vec![0x00]);
+        assert_eq!(encode_u32(128), vec![0x80, 0x01]);
+        assert_eq!(encode_u32(0x100000), vec![0x80, 0x80, 0x40]);
+        assert_eq!(encode_u32(u32::MAX), vec![0xff, 0xff, 0xff, 0xff, 0x0f]);
+    }
+
+    #[test]
+    fn test_encode_i32() {
+        assert_eq!(encode_i32(0), vec![0x00]);
+        assert_eq!(encode_i32(-1), vec![0x7f]);
+        assert_eq!(encode_i32(0x100000), vec![0x80, 0x80, 0xc0, 0x00]);
+        assert_eq!(encode_i32(i32::MAX), vec![0xff, 0xff, 0xff, 0xff, 0x07]);
+        assert_eq!(encode_i32(i32::MIN), vec![0x80, 0x80, 0x80, 0x80, 0x78]);
+    }
+
+    #[test]
+    fn test_encode_u32_with_width() {
+        assert_eq!(encode_u32_with_width(0, 1), vec![0x00]);
+        assert_eq!(
+            encode_u32_with_width(0, 5),
+            vec![0x80, 0x80, 0x80, 0x80, 0x00]
+        );
+        assert_eq!(
+            encode_u32_with_width(76, 5),
+            vec![0xcc, 0x80, 0x80, 0x80, 0x00]
+        );
+        assert_eq!(encode_u32_with_width(128, 3), vec![0x80, 0x81, 0x00]);
+        assert_eq!(encode_u32_with_width(128, 1), vec![0x80, 0x01]);
+    }
+}
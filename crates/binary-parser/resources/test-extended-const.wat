@@ -0,0 +1,9 @@
+(module
+    (import "env" "__memory_base" (global $memory_base i32))
+    (memory 1)
+    (table 2 funcref)
+    (global $g0 i64 (i64.mul (i64.const 6) (i64.sub (i64.const 10) (i64.const 3))))
+    (data (i32.add (global.get $memory_base) (i32.const 8)) "foo")
+    (elem (offset (i32.mul (i32.const 1) (i32.const 1))) func $f)
+    (func $f)
+)
@@ -0,0 +1,26 @@
+(module
+    (type $t (func (param i32)))
+    (import "env" "error" (tag $error (param i32)))
+    (tag $empty)
+    (tag $pair (export "pair") (param i32 i64))
+    (func $f (param i32) (result i32)
+        (block $outer (result i32)
+            (try_table $inner (result i32) (catch $error $outer)
+                (throw $error (local.get 0))
+            )
+        )
+    )
+    (func (result exnref)
+        block $b (result exnref)
+            try_table (catch_all_ref $b)
+                throw $empty
+            end
+            ref.null exn
+        end
+    )
+    (func (param exnref)
+        local.get 0
+        throw_ref
+    )
+    (export "error" (tag $error))
+)
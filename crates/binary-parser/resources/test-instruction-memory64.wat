@@ -0,0 +1,14 @@
+(module
+    (import "env" "memory" (memory $m0 i64 1))
+    (memory $m1 i64 1 0x1_0000_0000)
+    (memory $m2 i32 1 2)
+    (func $load (param i64) (result i64)
+        (i64.store $m1 offset=0x1_0000_0000 (local.get 0) (i64.const 1))
+        (i64.load $m1 offset=8 (local.get 0))
+    )
+    (func (result i64)
+        memory.size $m1
+        memory.grow $m0
+    )
+    (data (i64.const 0x10) "foo")
+)
@@ -52,10 +52,7 @@ pub enum Unsupported {
     UnsupportedTypeTag(/* tag */ u8),
     UnsupportedValueTag(/* tag */ u8),
     UnsupportedImportTag(/* tag */ u8),
-    UnsupportedTableTag(/* tag */ u8),
-
-    UnsupportMultipleTable,
-    UnsupportMultipleMemoryBlock,
+    UnsupportedRefTypeTag(/* tag */ u8),
 
     UnsupportedInstructionOpcode(/* opcode */ u8),
     UnsupportedInstructionExtensionCode(/* opcode */ u8, /* extension_code */ u32),
@@ -97,14 +94,8 @@ impl Display for Unsupported {
             Unsupported::UnsupportedImportTag(tag) => {
                 write!(f, "unsupported import tag: {}", tag)
             }
-            Unsupported::UnsupportedTableTag(tag) => {
-                write!(f, "unsupported table tag: {}", tag)
-            }
-            Unsupported::UnsupportMultipleTable => {
-                write!(f, "only one table is allowed in a module")
-            }
-            Unsupported::UnsupportMultipleMemoryBlock => {
-                write!(f, "only one memory block is allowed in a module")
+            Unsupported::UnsupportedRefTypeTag(tag) => {
+                write!(f, "unsupported reference type tag: {}", tag)
             }
             Unsupported::UnsupportedInstructionOpcode(opcode) => {
                 write!(f, "unsupported instruction opcode: {}", opcode)
@@ -127,8 +118,16 @@ pub enum SyntaxError {
     InvalidGlobalTag(u8),
     InvalidLimitTag(u8),
     InvalidCustomNameSectionTag(u8),
+    InvalidDynamicLinkingSubsectionType(u8),
     InvalidBlockType(i32),
     InvalidConstantExpressionInstruction(Instruction),
+    InvalidElementFlags(u32),
+    InvalidDataFlags(u32),
+    InvalidElementKind(u8),
+    InvalidSelectTypeCount(u32),
+    InvalidAtomicFenceFlag(u8),
+    InvalidTagAttribute(u8),
+    InvalidCatchKind(u8),
 }
 
 impl Display for SyntaxError {
@@ -153,9 +152,49 @@ impl Display for SyntaxError {
             SyntaxError::InvalidCustomNameSectionTag(tag) => {
                 write!(f, "invalid custom section \"name\" tag: {}", tag)
             }
+            SyntaxError::InvalidDynamicLinkingSubsectionType(subsection_type) => {
+                write!(
+                    f,
+                    "invalid custom section \"dylink.0\" subsection type: {}",
+                    subsection_type
+                )
+            }
             SyntaxError::InvalidBlockType(value) => {
                 write!(f, "invalid block type: {}", value)
             }
+            SyntaxError::InvalidElementFlags(flags) => {
+                write!(f, "invalid element segment flags: {}", flags)
+            }
+            SyntaxError::InvalidDataFlags(flags) => {
+                write!(f, "invalid data segment flags: {}", flags)
+            }
+            SyntaxError::InvalidElementKind(kind) => {
+                write!(f, "invalid element kind: {}", kind)
+            }
+            SyntaxError::InvalidSelectTypeCount(count) => {
+                write!(
+                    f,
+                    "invalid number of result types for typed select, expected: 1, actual: {}",
+                    count
+                )
+            }
+            SyntaxError::InvalidAtomicFenceFlag(flag) => {
+                write!(
+                    f,
+                    "invalid atomic fence flag, expected: 0, actual: {}",
+                    flag
+                )
+            }
+            SyntaxError::InvalidTagAttribute(attribute) => {
+                write!(
+                    f,
+                    "invalid tag attribute, expected: 0, actual: {}",
+                    attribute
+                )
+            }
+            SyntaxError::InvalidCatchKind(kind) => {
+                write!(f, "invalid catch clause kind: {}", kind)
+            }
         }
     }
 }
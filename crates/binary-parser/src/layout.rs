@@ -0,0 +1,81 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+// # 二进制模块的编码布局
+//
+// 同一个整数可以有多种长度的 leb128 编码，比如目标文件（object file）里段的长度，
+// 以及（未压缩重定位信息的）链接结果里函数索引、内存地址等需要重定位的整数，
+// 都会被填充（padding）至 5 个字节。
+//
+// 语法树只保存整数的数值，所以解析器另外记录原模块里非最短编码的位置，
+// 编码器根据这些信息重现原模块的编码，使得未经修改的模块能够逐字节一致地往返
+// （即 `解析 -> 编码` 之后跟原模块相同）。
+
+use std::collections::HashMap;
+
+use anvm_ast::instruction::Instruction;
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct BinaryLayout {
+    /// 非自定义段的内容长度的编码宽度（字节数），(段 id, 宽度)
+    pub section_length_widths: HashMap<u8, usize>,
+
+    /// 自定义段的内容长度的编码宽度，(自定义项的索引, 宽度)
+    pub custom_section_length_widths: HashMap<usize, usize>,
+
+    /// 函数主体的长度的编码宽度，(内部函数的索引, 宽度)
+    pub code_length_widths: HashMap<usize, usize>,
+
+    /// 含有非最短编码的整数的指令，(内部函数的索引, 指令的索引) -> (指令, 原始编码)
+    ///
+    /// 只有语法树里对应位置的指令跟记录的指令相同时，编码器才会使用原始编码。
+    pub padded_instructions: HashMap<(usize, usize), (Instruction, Vec<u8>)>,
+}
+
+/// 无符号整数的 leb128 最短编码的宽度
+pub fn get_leb128_width(value: u64) -> usize {
+    let bit_count = (u64::BITS - value.leading_zeros()).max(1) as usize;
+    bit_count.div_ceil(7)
+}
+
+/// 检查一段编码里是否可能含有非最短编码的 leb128 整数
+///
+/// 非最短编码的最后一个字节是多余的：无符号整数以及非负有符号整数的最后一个字节是 0x00，
+/// 负有符号整数的最后一个字节是 0x7f（且前一个字节的符号位为 1）。
+/// 这里不区分整数和其他字节，所以判断结果是保守的，即有可能把最短编码也当作非最短编码，
+/// 但不会遗漏非最短编码。
+pub fn has_padded_leb128(bytes: &[u8]) -> bool {
+    bytes.windows(2).any(|pair| {
+        let (byte, next_byte) = (pair[0], pair[1]);
+        byte & 0x80 != 0 && (next_byte == 0x00 || (next_byte == 0x7f && byte & 0x40 != 0))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_leb128_width, has_padded_leb128};
+
+    #[test]
+    fn test_get_leb128_width() {
+        assert_eq!(get_leb128_width(0), 1);
+        assert_eq!(get_leb128_width(127), 1);
+        assert_eq!(get_leb128_width(128), 2);
+        assert_eq!(get_leb128_width(u32::MAX as u64), 5);
+        assert_eq!(get_leb128_width(u64::MAX), 10);
+    }
+
+    #[test]
+    fn test_has_padded_leb128() {
+        // call 76
+        assert!(!has_padded_leb128(&[0x10, 0x4c]));
+        // call 76，填充至 5 个字节
+        assert!(has_padded_leb128(&[0x10, 0xcc, 0x80, 0x80, 0x80, 0x00]));
+        // i32.const -1，填充至 5 个字节
+        assert!(has_padded_leb128(&[0x41, 0xff, 0xff, 0xff, 0xff, 0x7f]));
+        // i32.const -65
+        assert!(!has_padded_leb128(&[0x41, 0xbf, 0x7f]));
+    }
+}
@@ -6,5 +6,6 @@
 
 pub mod types;
 pub mod error;
+pub mod layout;
 pub mod leb128decoder;
 pub mod parser;
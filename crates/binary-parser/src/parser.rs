@@ -7,29 +7,40 @@ use std::vec;
 
 use anvm_ast::{
     ast::{
-        CodeItem, CustomItem, DataItem, ElementItem, ExportDescriptor, ExportItem,
-        FunctionIndexAndBlockLabelsPair, FunctionIndexAndLocalVariableNamesPair, FunctionType,
-        GlobalItem, GlobalType, ImportDescriptor, ImportItem, IndexNamePair, Limit, LocalGroup,
-        MemoryType, Module, NameCollection, TableType, TypeItem,
+        CodeItem, CustomItem, DataItem, DataMode, DynamicLinking, DynamicLinkingExportInfo,
+        DynamicLinkingImportInfo, DynamicLinkingMemoryInfo, ElementItem, ElementItems, ElementMode,
+        ExportDescriptor, ExportItem, FunctionIndexAndBlockLabelsPair,
+        FunctionIndexAndLocalVariableNamesPair, FunctionType, GlobalItem, GlobalType,
+        ImportDescriptor, ImportItem, IndexNamePair, Limit, LocalGroup, MemoryType, Module,
+        NameCollection, TableType, TagType, TypeItem,
     },
-    instruction::{BlockType, Instruction, MemoryArgument},
+    instruction::{BlockType, CatchClause, Instruction, MemoryArgument},
     opcode,
-    types::ValueType,
+    types::{RefType, ValueType},
 };
 
 use crate::{
     error::{ParseError, SyntaxError, Unsupported},
+    layout::{get_leb128_width, has_padded_leb128, BinaryLayout},
     leb128decoder, types,
 };
 
 pub fn parse(source: &[u8]) -> Result<Module, ParseError> {
+    let (module, _) = parse_module(source)?;
+    Ok(module)
+}
+
+/// 解析二进制模块，同时返回模块的编码布局
+///
+/// 编码布局用于编码器重现原模块的编码，详细请见 `layout` 模块。
+pub fn parse_with_layout(source: &[u8]) -> Result<(Module, BinaryLayout), ParseError> {
     parse_module(source)
 }
 
 /// # 解析二进制 module
 ///
 /// module = magic_number:u32 + version:u32 + <section>
-fn parse_module(source: &[u8]) -> Result<Module, ParseError> {
+fn parse_module(source: &[u8]) -> Result<(Module, BinaryLayout), ParseError> {
     let mut remains = source;
 
     // 读取幻数
@@ -62,7 +73,7 @@ fn parse_module(source: &[u8]) -> Result<Module, ParseError> {
 ///
 /// `自定义段` 的出现顺序不固定，而且可以出现多次，其他段的出现顺序是固定的
 /// （按照 section id 从小到大排列）且不重复。
-fn parse_sections(source: &[u8]) -> Result<Module, ParseError> {
+fn parse_sections(source: &[u8]) -> Result<(Module, BinaryLayout), ParseError> {
     let mut module = Module {
         custom_items: vec![],
         type_items: vec![],
@@ -70,15 +81,17 @@ fn parse_sections(source: &[u8]) -> Result<Module, ParseError> {
         internal_function_to_type_index_list: vec![],
         tables: vec![],
         memory_blocks: vec![],
+        tag_items: vec![],
         global_items: vec![],
         export_items: vec![],
         start_function_index: None,
         element_items: vec![],
         code_items: vec![],
         data_items: vec![],
+        data_count: None,
     };
 
-    let mut option_module_data_count: Option<u32> = None;
+    let mut layout = BinaryLayout::default();
     let mut remains = source;
 
     // 一个模块里的段的数量是未知的，所以需要不断地消耗（consume）段数据，直到
@@ -92,11 +105,21 @@ fn parse_sections(source: &[u8]) -> Result<Module, ParseError> {
         let (section_id, post_section_id) = read_byte(remains)?;
         let (content_length, post_content_length) = read_u32(post_section_id)?;
         let (section_data, post_section_data) =
-            post_content_length.split_at(content_length as usize);
+            read_bytes(post_content_length, content_length as usize)?;
         remains = post_section_data;
 
+        // 记录非最短编码的段内容长度
+        let length_width = post_section_id.len() - post_content_length.len();
+        let is_padded = length_width > get_leb128_width(content_length as u64);
+
         match section_id {
             types::SECTION_CUSTOM_ID => {
+                if is_padded {
+                    layout
+                        .custom_section_length_widths
+                        .insert(module.custom_items.len(), length_width);
+                }
+
                 let custom_item = parse_custom_section(section_data)?;
                 module.custom_items.push(custom_item);
             }
@@ -130,13 +153,13 @@ fn parse_sections(source: &[u8]) -> Result<Module, ParseError> {
                 module.element_items = parse_element_section(section_data)?;
             }
             types::SECTION_CODE_ID => {
-                module.code_items = parse_function_code_section(section_data)?;
+                module.code_items = parse_function_code_section(section_data, &mut layout)?;
             }
             types::SECTION_DATA_ID => {
                 module.data_items = parse_data_section(section_data)?;
 
                 // 验证 data_items 的条目数量
-                if let Some(module_data_count) = option_module_data_count {
+                if let Some(module_data_count) = module.data_count {
                     if module.data_items.len() != module_data_count as usize {
                         return Err(ParseError::Invalid);
                     }
@@ -144,7 +167,10 @@ fn parse_sections(source: &[u8]) -> Result<Module, ParseError> {
             }
             types::SECTION_DATA_COUNT_ID => {
                 let data_count = parse_data_count_section(section_data)?;
-                option_module_data_count = Some(data_count);
+                module.data_count = Some(data_count);
+            }
+            types::SECTION_TAG_ID => {
+                module.tag_items = parse_tag_section(section_data)?;
             }
             _ => {
                 return Err(ParseError::SyntaxError(SyntaxError::InvalidSectionId(
@@ -152,9 +178,15 @@ fn parse_sections(source: &[u8]) -> Result<Module, ParseError> {
                 )))
             }
         }
+
+        if is_padded && section_id != types::SECTION_CUSTOM_ID {
+            layout
+                .section_length_widths
+                .insert(section_id, length_width);
+        }
     }
 
-    Ok(module)
+    Ok((module, layout))
 }
 
 /// # 解析 `自定义段`
@@ -196,6 +228,9 @@ fn parse_sections(source: &[u8]) -> Result<Module, ParseError> {
 /// - 5，项内容为 table name item
 /// - 6，项内容为 memory name item
 /// - 7，项内容为 global name item
+/// - 8，项内容为 element name item
+/// - 9，项内容为 data name item
+/// - 11，项内容为 tag name item
 ///
 /// 二进制格式均为
 ///
@@ -219,13 +254,18 @@ fn parse_sections(source: &[u8]) -> Result<Module, ParseError> {
 /// 当 kind 为 3 时，项内容为 <function_block_label_item>，结构跟 <function_local_name_item>
 /// 一致，这里不再赘述。
 ///
+/// 当 custom section 的 name 值为 "dylink.0" 时，段的内容为动态链接信息，
+/// 详细见 `continue_parse_dynamic_linking`。
 fn parse_custom_section(source: &[u8]) -> Result<CustomItem, ParseError> {
     let (name, post_name) = read_string(source)?;
 
-    // 目前只解析 `name` 为 "name" 的 custom_section
+    // 目前只解析 `name` 为 "name" 以及 "dylink.0" 的 custom_section
     let custom_item = if name == "name" {
         let name_collection_items = continue_parse_name_collection_items(post_name)?;
         CustomItem::NameCollections(name_collection_items)
+    } else if name == "dylink.0" {
+        let dynamic_linking = continue_parse_dynamic_linking(post_name)?;
+        CustomItem::DynamicLinking(dynamic_linking)
     } else {
         CustomItem::Other(name, post_name.to_vec())
     };
@@ -233,6 +273,97 @@ fn parse_custom_section(source: &[u8]) -> Result<CustomItem, ParseError> {
     Ok(custom_item)
 }
 
+/// 解析动态链接信息
+///
+/// dylink_section = subsection{*}
+/// subsection = subsection_type:u8 + content_length:u32 + content
+///
+/// - 类型 1，mem_info = memory_size:u32 + memory_alignment:u32 + table_size:u32 + table_alignment:u32
+/// - 类型 2，needed = <string>
+/// - 类型 3，export_info = <name:string + flags:u32>
+/// - 类型 4，import_info = <module_name:string + item_name:string + flags:u32>
+/// - 类型 5，runtime_path = <string>
+fn continue_parse_dynamic_linking(source: &[u8]) -> Result<DynamicLinking, ParseError> {
+    let mut remains = source;
+    let mut dynamic_linking = DynamicLinking::default();
+
+    while !remains.is_empty() {
+        let (subsection_type, post_subsection_type) = read_byte(remains)?;
+        let (content_length, post_content_length) = read_u32(post_subsection_type)?;
+        let (content, post_content) = read_bytes(post_content_length, content_length as usize)?;
+        remains = post_content;
+
+        match subsection_type {
+            types::DYLINK_SUBSECTION_MEM_INFO => {
+                let (memory_size, post_memory_size) = read_u32(content)?;
+                let (memory_alignment, post_memory_alignment) = read_u32(post_memory_size)?;
+                let (table_size, post_table_size) = read_u32(post_memory_alignment)?;
+                let (table_alignment, _) = read_u32(post_table_size)?;
+                dynamic_linking.memory_info = Some(DynamicLinkingMemoryInfo {
+                    memory_size,
+                    memory_alignment,
+                    table_size,
+                    table_alignment,
+                });
+            }
+            types::DYLINK_SUBSECTION_NEEDED => {
+                dynamic_linking.needed = continue_parse_strings(content)?;
+            }
+            types::DYLINK_SUBSECTION_EXPORT_INFO => {
+                let (item_count, post_item_count) = read_u32(content)?;
+                let mut item_remains = post_item_count;
+                for _ in 0..item_count {
+                    let (name, post_name) = read_string(item_remains)?;
+                    let (flags, post_flags) = read_u32(post_name)?;
+                    dynamic_linking
+                        .export_infos
+                        .push(DynamicLinkingExportInfo { name, flags });
+                    item_remains = post_flags;
+                }
+            }
+            types::DYLINK_SUBSECTION_IMPORT_INFO => {
+                let (item_count, post_item_count) = read_u32(content)?;
+                let mut item_remains = post_item_count;
+                for _ in 0..item_count {
+                    let (module_name, post_module_name) = read_string(item_remains)?;
+                    let (item_name, post_item_name) = read_string(post_module_name)?;
+                    let (flags, post_flags) = read_u32(post_item_name)?;
+                    dynamic_linking.import_infos.push(DynamicLinkingImportInfo {
+                        module_name,
+                        item_name,
+                        flags,
+                    });
+                    item_remains = post_flags;
+                }
+            }
+            types::DYLINK_SUBSECTION_RUNTIME_PATH => {
+                dynamic_linking.runtime_paths = continue_parse_strings(content)?;
+            }
+            _ => {
+                return Err(ParseError::SyntaxError(
+                    SyntaxError::InvalidDynamicLinkingSubsectionType(subsection_type),
+                ));
+            }
+        }
+    }
+
+    Ok(dynamic_linking)
+}
+
+fn continue_parse_strings(source: &[u8]) -> Result<Vec<String>, ParseError> {
+    let (item_count, post_item_count) = read_u32(source)?;
+    let mut strings = new_list_with_capacity::<String>(item_count, post_item_count);
+
+    let mut remains = post_item_count;
+    for _ in 0..item_count {
+        let (string, post_string) = read_string(remains)?;
+        strings.push(string);
+        remains = post_string;
+    }
+
+    Ok(strings)
+}
+
 fn continue_parse_name_collection_items(source: &[u8]) -> Result<Vec<NameCollection>, ParseError> {
     let mut remains = source;
 
@@ -247,7 +378,7 @@ fn continue_parse_name_collection_items(source: &[u8]) -> Result<Vec<NameCollect
 
         let (kind, post_kind) = read_byte(remains)?;
         let (content_length, post_content_length) = read_u32(post_kind)?;
-        let (item_data, post_item_data) = post_content_length.split_at(content_length as usize);
+        let (item_data, post_item_data) = read_bytes(post_content_length, content_length as usize)?;
         remains = post_item_data;
 
         let name_collection_item = match kind {
@@ -288,6 +419,10 @@ fn continue_parse_name_collection_items(source: &[u8]) -> Result<Vec<NameCollect
                 let (index_name_pairs, _) = continue_parse_index_name_pairs(item_data)?;
                 NameCollection::DataNames(index_name_pairs)
             }
+            types::NAME_COLLECTION_KIND_TAG_NAMES => {
+                let (index_name_pairs, _) = continue_parse_index_name_pairs(item_data)?;
+                NameCollection::TagNames(index_name_pairs)
+            }
             _ => {
                 return Err(ParseError::SyntaxError(
                     SyntaxError::InvalidCustomNameSectionTag(kind),
@@ -305,7 +440,7 @@ fn continue_parse_index_name_pairs(
     source: &[u8],
 ) -> Result<(Vec<IndexNamePair>, &[u8]), ParseError> {
     let (item_count, post_item_count) = read_u32(source)?;
-    let mut index_name_pairs = Vec::<IndexNamePair>::with_capacity(item_count as usize);
+    let mut index_name_pairs = new_list_with_capacity::<IndexNamePair>(item_count, post_item_count);
 
     let mut remains = post_item_count;
     for _ in 0..item_count {
@@ -328,8 +463,10 @@ fn continue_parse_local_variable_names_pairs(
     source: &[u8],
 ) -> Result<Vec<FunctionIndexAndLocalVariableNamesPair>, ParseError> {
     let (item_count, post_item_count) = read_u32(source)?;
-    let mut names_pairs =
-        Vec::<FunctionIndexAndLocalVariableNamesPair>::with_capacity(item_count as usize);
+    let mut names_pairs = new_list_with_capacity::<FunctionIndexAndLocalVariableNamesPair>(
+        item_count,
+        post_item_count,
+    );
 
     let mut remains = post_item_count;
     for _ in 0..item_count {
@@ -366,7 +503,7 @@ fn continue_parse_block_labels_pairs(
 ) -> Result<Vec<FunctionIndexAndBlockLabelsPair>, ParseError> {
     let (item_count, post_item_count) = read_u32(source)?;
     let mut labels_pairs =
-        Vec::<FunctionIndexAndBlockLabelsPair>::with_capacity(item_count as usize);
+        new_list_with_capacity::<FunctionIndexAndBlockLabelsPair>(item_count, post_item_count);
 
     let mut remains = post_item_count;
     for _ in 0..item_count {
@@ -408,7 +545,7 @@ fn parse_type_section(source: &[u8]) -> Result<Vec<TypeItem>, ParseError> {
     let (item_count, post_item_count) = read_u32(source)?;
 
     let mut remains = post_item_count;
-    let mut type_items = Vec::<TypeItem>::with_capacity(item_count as usize);
+    let mut type_items = new_list_with_capacity::<TypeItem>(item_count, post_item_count);
     for _ in 0..item_count {
         let (type_item, post_type_item) = continue_parse_type_item(remains)?;
         type_items.push(type_item);
@@ -449,7 +586,7 @@ fn continue_parse_value_types(source: &[u8]) -> Result<(Vec<ValueType>, &[u8]),
     let (item_count, post_item_count) = read_u32(source)?;
 
     let mut remains = post_item_count;
-    let mut value_types = Vec::<ValueType>::with_capacity(item_count as usize);
+    let mut value_types = new_list_with_capacity::<ValueType>(item_count, post_item_count);
     for _ in 0..item_count {
         let (value_type, post_value_type) = continue_parse_value_type(remains)?;
         value_types.push(value_type);
@@ -467,6 +604,10 @@ fn continue_parse_value_type(source: &[u8]) -> Result<(ValueType, &[u8]), ParseE
         types::VALUE_TYPE_TAG_I64 => ValueType::I64,
         types::VALUE_TYPE_TAG_F32 => ValueType::F32,
         types::VALUE_TYPE_TAG_F64 => ValueType::F64,
+        types::VALUE_TYPE_TAG_V128 => ValueType::V128,
+        types::VALUE_TYPE_TAG_FUNC_REF => ValueType::FuncRef,
+        types::VALUE_TYPE_TAG_EXTERN_REF => ValueType::ExternRef,
+        types::VALUE_TYPE_TAG_EXN_REF => ValueType::ExnRef,
         _ => {
             return Err(ParseError::Unsupported(Unsupported::UnsupportedValueTag(
                 tag,
@@ -481,12 +622,12 @@ fn continue_parse_value_type(source: &[u8]) -> Result<(ValueType, &[u8]), ParseE
 ///
 /// import_section = 0x02 + content_length:u32 + <import_item>
 /// import_item = module_name:string + member_name:string + import_descriptor
-/// import_description = tag:byte + (type_index | table_type | memory_type | global_type)
+/// import_description = tag:byte + (type_index | table_type | memory_type | global_type | tag_type)
 fn parse_import_section(source: &[u8]) -> Result<Vec<ImportItem>, ParseError> {
     let (item_count, post_item_count) = read_u32(source)?;
 
     let mut remains = post_item_count;
-    let mut import_items = Vec::<ImportItem>::with_capacity(item_count as usize);
+    let mut import_items = new_list_with_capacity::<ImportItem>(item_count, post_item_count);
 
     for _ in 0..item_count {
         let (import_item, post_import_item) = continue_parse_import_item(remains)?;
@@ -502,7 +643,7 @@ fn parse_import_section(source: &[u8]) -> Result<Vec<ImportItem>, ParseError> {
 }
 
 /// import_item = module_name:string + member_name:string + import_descriptor
-/// import_description = tag:byte + (type_index | table_type | memory_type | global_type)
+/// import_description = tag:byte + (type_index | table_type | memory_type | global_type | tag_type)
 fn continue_parse_import_item(source: &[u8]) -> Result<(ImportItem, &[u8]), ParseError> {
     let (module_name, post_module_name) = read_string(source)?;
     let (item_name, post_item_name) = read_string(post_module_name)?;
@@ -531,6 +672,11 @@ fn continue_parse_import_item(source: &[u8]) -> Result<(ImportItem, &[u8]), Pars
             remains = post_global_type;
             ImportDescriptor::GlobalType(global_type)
         }
+        types::IMPORT_TAG_TAG => {
+            let (tag_type, post_tag_type) = continue_parse_tag_type(remains)?;
+            remains = post_tag_type;
+            ImportDescriptor::TagType(tag_type)
+        }
         _ => {
             return Err(ParseError::Unsupported(Unsupported::UnsupportedImportTag(
                 tag,
@@ -547,25 +693,81 @@ fn continue_parse_import_item(source: &[u8]) -> Result<(ImportItem, &[u8]), Pars
     Ok((import_item, remains))
 }
 
-/// table_type = 0x70 + limits
-///              ^
-///              |--- 0x70 表示该表项存储的是 funcref
+/// table_type = ref_type:byte + limits
+///               ^
+///               |--- 0x70 表示该表项存储的是 funcref，0x6F 表示 externref
 fn continue_parse_table_type(source: &[u8]) -> Result<(TableType, &[u8]), ParseError> {
+    let (element_type, post_element_type) = continue_parse_ref_type(source)?;
+    let (limit, post_limit) = continue_parse_limit(post_element_type)?;
+    Ok((
+        TableType {
+            element_type,
+            limit,
+        },
+        post_limit,
+    ))
+}
+
+/// ref_type = 0x70 | 0x6F | 0x69
+fn continue_parse_ref_type(source: &[u8]) -> Result<(RefType, &[u8]), ParseError> {
     let (tag, post_tag) = read_byte(source)?;
-    if tag != types::TABLE_TYPE_TAG_FUNC_REF {
-        Err(ParseError::Unsupported(Unsupported::UnsupportedTableTag(
-            tag,
-        )))
-    } else {
-        let (limit, post_limit) = continue_parse_limit(post_tag)?;
-        Ok((TableType { limit: limit }, post_limit))
-    }
+    let ref_type = match tag {
+        types::VALUE_TYPE_TAG_FUNC_REF => RefType::FuncRef,
+        types::VALUE_TYPE_TAG_EXTERN_REF => RefType::ExternRef,
+        types::VALUE_TYPE_TAG_EXN_REF => RefType::ExnRef,
+        _ => {
+            return Err(ParseError::Unsupported(Unsupported::UnsupportedRefTypeTag(
+                tag,
+            )));
+        }
+    };
+
+    Ok((ref_type, post_tag))
 }
 
 /// memory_type = limits
+///
+/// 内存块的 limits 的 tag 还可以是 2 或者 3，即 tag 的第 2 位（0x02）表示共享内存块，
+/// tag 的第 3 位（0x04）表示 64 位内存块，此时 min 和 max 都是 u64，
+/// 其余的位跟表的 limits 的 tag 相同。
 fn continue_parse_memory_type(source: &[u8]) -> Result<(MemoryType, &[u8]), ParseError> {
-    let (limit, post_limit) = continue_parse_limit(source)?;
-    Ok((MemoryType { limit: limit }, post_limit))
+    let (tag, post_tag) = read_byte(source)?;
+    if tag > 7 {
+        return Err(ParseError::SyntaxError(SyntaxError::InvalidLimitTag(tag)));
+    }
+
+    let shared = tag & 0x02 != 0;
+    let memory64 = tag & 0x04 != 0;
+
+    let (limit, post_limit) = if memory64 {
+        continue_parse_limit64_content(tag & 0x01, post_tag)?
+    } else {
+        continue_parse_limit_content(tag & 0x01, post_tag)?
+    };
+
+    Ok((
+        MemoryType {
+            limit,
+            shared,
+            memory64,
+        },
+        post_limit,
+    ))
+}
+
+/// tag_type = attribute:byte + type_index:u32
+///
+/// attribute 目前只能是 0，表示异常
+fn continue_parse_tag_type(source: &[u8]) -> Result<(TagType, &[u8]), ParseError> {
+    let (attribute, post_attribute) = read_byte(source)?;
+    if attribute != types::TAG_ATTRIBUTE_EXCEPTION {
+        return Err(ParseError::SyntaxError(SyntaxError::InvalidTagAttribute(
+            attribute,
+        )));
+    }
+
+    let (type_index, post_type_index) = read_u32(post_attribute)?;
+    Ok((TagType { type_index }, post_type_index))
 }
 
 /// global_type = val_type:byte + mut:byte
@@ -599,12 +801,31 @@ fn continue_parse_global_type(source: &[u8]) -> Result<(GlobalType, &[u8]), Pars
 /// 当 tag == 1 时，表示同时指出了 min 值和 max 值
 fn continue_parse_limit(source: &[u8]) -> Result<(Limit, &[u8]), ParseError> {
     let (tag, post_tag) = read_byte(source)?;
-    let (min, post_min) = read_u32(post_tag)?;
+    continue_parse_limit_content(tag, post_tag)
+}
+
+/// 根据 limits 的 tag 解析 min 值以及 max 值
+fn continue_parse_limit_content(tag: u8, source: &[u8]) -> Result<(Limit, &[u8]), ParseError> {
+    let (min, post_min) = read_u32(source)?;
 
     // 仅当 tag == 1 时，才有 max 数据
     match tag {
         1 => {
             let (max, post_max) = read_u32(post_min)?;
+            Ok((Limit::Range(min as u64, max as u64), post_max))
+        }
+        0 => Ok((Limit::AtLeast(min as u64), post_min)),
+        _ => Err(ParseError::SyntaxError(SyntaxError::InvalidLimitTag(tag))),
+    }
+}
+
+/// 根据 limits 的 tag 解析 64 位内存块的 min 值以及 max 值
+fn continue_parse_limit64_content(tag: u8, source: &[u8]) -> Result<(Limit, &[u8]), ParseError> {
+    let (min, post_min) = read_u64(source)?;
+
+    match tag {
+        1 => {
+            let (max, post_max) = read_u64(post_min)?;
             Ok((Limit::Range(min, max), post_max))
         }
         0 => Ok((Limit::AtLeast(min), post_min)),
@@ -628,23 +849,17 @@ fn parse_function_list_section(source: &[u8]) -> Result<Vec<u32>, ParseError> {
 
 /// # 解析表段
 ///
-/// table_section = 0x04 + content_length:u32 + <table_type> // 目前一个模块仅支持声明一个表项
+/// table_section = 0x04 + content_length:u32 + <table_type>
 /// table_type = 0x70 + limits
 ///              ^
 ///              |--- 0x70 表示该表项存储的是 funcref
 ///
-/// 注意，如果一个模块已经导入了一张表，而在表段再次定义一个表，
-/// 则应该视为错误，因为目前 WebAssembly 只允许一张表，
-/// 但在语法解析阶段不抛出错误，留到运行再抛出。
+/// 一个模块可以声明多张表，表的索引从导入的表开始计算。
 fn parse_table_section(source: &[u8]) -> Result<Vec<TableType>, ParseError> {
     let (item_count, post_item_count) = read_u32(source)?;
 
-    if item_count > 1 {
-        return Err(ParseError::Unsupported(Unsupported::UnsupportMultipleTable));
-    }
-
     let mut remains = post_item_count;
-    let mut table_types = Vec::<TableType>::with_capacity(item_count as usize);
+    let mut table_types = new_list_with_capacity::<TableType>(item_count, post_item_count);
 
     for _ in 0..item_count {
         let (table_type, post_table_type) = continue_parse_table_type(remains)?;
@@ -661,24 +876,15 @@ fn parse_table_section(source: &[u8]) -> Result<Vec<TableType>, ParseError> {
 
 /// # 解析内存段
 ///
-/// memory_section = 0x05 + content_length:u32 + <memory_type> // 目前一个模块仅支持声明一个内存块
+/// memory_section = 0x05 + content_length:u32 + <memory_type>
 /// memory_type = limits
 ///
-/// 注意，如果一个模块已经导入了一个内存块，而在内存段再次定义一个内存块，
-/// 则应该视为错误，因为目前 WebAssembly 只允许一个内存块。
-///
-/// 但在语法解析阶段不抛出错误，留到运行再抛出。
+/// 一个模块可以声明多个内存块（多内存块提案），内存块的索引从导入的内存块开始计算。
 fn parse_memory_section(source: &[u8]) -> Result<Vec<MemoryType>, ParseError> {
     let (item_count, post_item_count) = read_u32(source)?;
 
-    if item_count > 1 {
-        return Err(ParseError::Unsupported(
-            Unsupported::UnsupportMultipleMemoryBlock,
-        ));
-    }
-
     let mut remains = post_item_count;
-    let mut memory_types = Vec::<MemoryType>::with_capacity(item_count as usize);
+    let mut memory_types = new_list_with_capacity::<MemoryType>(item_count, post_item_count);
 
     for _ in 0..item_count {
         let (memory_type, post_memory_type) = continue_parse_memory_type(remains)?;
@@ -693,6 +899,31 @@ fn parse_memory_section(source: &[u8]) -> Result<Vec<MemoryType>, ParseError> {
     }
 }
 
+/// # 解析标签段
+///
+/// tag_section = 0x0d + content_length:u32 + <tag_type>
+/// tag_type = attribute:byte + type_index:u32
+///
+/// 注意，虽然标签段的 id 是 13，但在二进制文件里它位于内存段和全局段之间。
+fn parse_tag_section(source: &[u8]) -> Result<Vec<TagType>, ParseError> {
+    let (item_count, post_item_count) = read_u32(source)?;
+
+    let mut remains = post_item_count;
+    let mut tag_items = new_list_with_capacity::<TagType>(item_count, post_item_count);
+
+    for _ in 0..item_count {
+        let (tag_type, post_tag_type) = continue_parse_tag_type(remains)?;
+        tag_items.push(tag_type);
+        remains = post_tag_type;
+    }
+
+    if !remains.is_empty() {
+        Err(ParseError::UnexpectedData("tag".to_string(), None))
+    } else {
+        Ok(tag_items)
+    }
+}
+
 /// # 解析全局（变量）段
 ///
 /// global_section = 0x06 + content_length:u32 + <global_item>
@@ -704,7 +935,7 @@ fn parse_global_section(source: &[u8]) -> Result<Vec<GlobalItem>, ParseError> {
     let (item_count, post_item_count) = read_u32(source)?;
 
     let mut remains = post_item_count;
-    let mut global_items = Vec::<GlobalItem>::with_capacity(item_count as usize);
+    let mut global_items = new_list_with_capacity::<GlobalItem>(item_count, post_item_count);
 
     for _ in 0..item_count {
         let (global_type, post_global_type) = continue_parse_global_type(remains)?;
@@ -834,8 +1065,52 @@ fn continue_parse_instruction_item(
             remains = post_table_index;
             Instruction::CallIndirect(type_index, table_index)
         }
+        opcode::RETURN_CALL => {
+            // return_call = opcode_return_call + function_index:u32
+            let (function_index, post_index) = read_u32(remains)?;
+            remains = post_index;
+            Instruction::ReturnCall(function_index)
+        }
+        opcode::RETURN_CALL_INDIRECT => {
+            // return_call_indirect = opcode_return_call_indirect + type_index:u32 + table_index:u32
+            let (type_index, post_type_index) = read_u32(remains)?;
+            let (table_index, post_table_index) = read_u32(post_type_index)?;
+            remains = post_table_index;
+            Instruction::ReturnCallIndirect(type_index, table_index)
+        }
+        opcode::THROW => {
+            // throw = opcode_throw + tag_index:u32
+            let (tag_index, post_tag_index) = read_u32(remains)?;
+            remains = post_tag_index;
+            Instruction::Throw(tag_index)
+        }
+        opcode::THROW_REF => Instruction::ThrowRef,
+        opcode::TRY_TABLE => {
+            // try_table = opcode_try_table + block_type:i32 + <catch> + instructions + opcode_end
+            let (block_type_value, post_block_type) = read_i32(remains)?;
+            let (catch_clauses, post_catch_clauses) =
+                continue_parse_catch_clauses(post_block_type)?;
+            remains = post_catch_clauses;
+
+            let block_type = get_block_type(block_type_value)?;
+            Instruction::TryTable(block_type, catch_clauses, block_index)
+        }
         opcode::DROP => Instruction::Drop,
         opcode::SELECT => Instruction::Select,
+        opcode::SELECT_TYPED => {
+            // select_typed = opcode_select_typed + <value_type>
+            // 目前规范只允许一个类型
+            let (value_types, post_value_types) = continue_parse_value_types(remains)?;
+            remains = post_value_types;
+
+            if value_types.len() != 1 {
+                return Err(ParseError::SyntaxError(
+                    SyntaxError::InvalidSelectTypeCount(value_types.len() as u32),
+                ));
+            }
+
+            Instruction::SelectTyped(value_types)
+        }
 
         // 变量指令
         opcode::LOCAL_GET => {
@@ -1189,6 +1464,20 @@ fn continue_parse_instruction_item(
         opcode::I64_EXTEND8_S => Instruction::I64Extend8S,
         opcode::I64_EXTEND16_S => Instruction::I64Extend16S,
         opcode::I64_EXTEND32_S => Instruction::I64Extend32S,
+        // 引用指令
+        opcode::REF_NULL => {
+            // ref.null = opcode_ref_null + ref_type:byte
+            let (ref_type, post_ref_type) = continue_parse_ref_type(remains)?;
+            remains = post_ref_type;
+            Instruction::RefNull(ref_type)
+        }
+        opcode::REF_IS_NULL => Instruction::RefIsNull,
+        opcode::REF_FUNC => {
+            // ref.func = opcode_ref_func + function_index:u32
+            let (function_index, post_function_index) = read_u32(remains)?;
+            remains = post_function_index;
+            Instruction::RefFunc(function_index)
+        }
         opcode::EXTENSION_0XFC => {
             let (sub_opcode, post_sub_opcode) = read_u32(remains)?;
             let (extension_instruction, post_extension) = continue_parse_extension_instructions(
@@ -1199,6 +1488,20 @@ fn continue_parse_instruction_item(
             remains = post_extension;
             extension_instruction
         }
+        opcode::EXTENSION_0XFD => {
+            let (sub_opcode, post_sub_opcode) = read_u32(remains)?;
+            let (simd_instruction, post_simd_instruction) =
+                continue_parse_simd_instructions(sub_opcode, post_sub_opcode)?;
+            remains = post_simd_instruction;
+            simd_instruction
+        }
+        opcode::EXTENSION_0XFE => {
+            let (sub_opcode, post_sub_opcode) = read_u32(remains)?;
+            let (atomic_instruction, post_atomic_instruction) =
+                continue_parse_atomic_instructions(sub_opcode, post_sub_opcode)?;
+            remains = post_atomic_instruction;
+            atomic_instruction
+        }
         _ => {
             return Err(ParseError::Unsupported(
                 Unsupported::UnsupportedInstructionOpcode(opcode),
@@ -1206,11 +1509,12 @@ fn continue_parse_instruction_item(
         }
     };
 
-    // 如果指令是 `block`，`loop` 和 `if`，则让 `block_index` 递增
+    // 如果指令是 `block`，`loop`，`if` 和 `try_table`，则让 `block_index` 递增
     let next_block_index = match instruction {
-        Instruction::Block(_, _) | Instruction::Loop(_, _) | Instruction::If(_, _) => {
-            block_index + 1
-        }
+        Instruction::Block(_, _)
+        | Instruction::Loop(_, _)
+        | Instruction::If(_, _)
+        | Instruction::TryTable(_, _, _) => block_index + 1,
         _ => block_index,
     };
 
@@ -1248,10 +1552,11 @@ fn continue_parse_extension_instructions(
             Instruction::DataDrop(data_index)
         }
         opcode::MEMORY_COPY => {
-            let (source_memory_block_index, post_source_memory_block_index) = read_u32(remains)?;
-            let (dest_memory_block_index, post_dest_memory_block_index) = read_u32(post_source_memory_block_index)?;
-            remains = post_dest_memory_block_index;
-            Instruction::MemoryCopy(source_memory_block_index, dest_memory_block_index)
+            let (dest_memory_block_index, post_dest_memory_block_index) = read_u32(remains)?;
+            let (source_memory_block_index, post_source_memory_block_index) =
+                read_u32(post_dest_memory_block_index)?;
+            remains = post_source_memory_block_index;
+            Instruction::MemoryCopy(dest_memory_block_index, source_memory_block_index)
         }
         opcode::MEMORY_FILL => {
             let (memory_block_index, post_memory_block_index) = read_u32(remains)?;
@@ -1272,10 +1577,10 @@ fn continue_parse_extension_instructions(
             Instruction::ElementDrop(element_index)
         }
         opcode::TABLE_COPY => {
-            let (source_table_index, post_source_table_index) = read_u32(remains)?;
-            let (dest_table_index, post_dest_table_index) = read_u32(post_source_table_index)?;
-            remains = post_dest_table_index;
-            Instruction::TableCopy(source_table_index, dest_table_index)
+            let (dest_table_index, post_dest_table_index) = read_u32(remains)?;
+            let (source_table_index, post_source_table_index) = read_u32(post_dest_table_index)?;
+            remains = post_source_table_index;
+            Instruction::TableCopy(dest_table_index, source_table_index)
         }
         opcode::TABLE_GROW => {
             let (table_index, post_table_index) = read_u32(remains)?;
@@ -1303,6 +1608,565 @@ fn continue_parse_extension_instructions(
     Ok((instruction, remains))
 }
 
+/// 解析 0xFD 前缀的固定宽度 SIMD 指令
+///
+/// simd_instruction = 0xFD + sub_opcode:u32 + immediate
+///
+/// 立即数有以下几种：
+///
+/// - 内存类指令：memory_argument，`v128.loadN_lane` 和 `v128.storeN_lane` 后面还有 lane:byte
+/// - `v128.const`：16 个字节（小端序）的 i128
+/// - `i8x16.shuffle`：16 个字节，每个字节为一个通道的索引
+/// - 通道读写指令（`extract_lane`，`replace_lane`）：lane:byte
+fn continue_parse_simd_instructions(
+    sub_opcode: u32,
+    source: &[u8],
+) -> Result<(Instruction, &[u8]), ParseError> {
+    let mut remains = source;
+
+    let instruction = match sub_opcode {
+        // 内存类指令
+        opcode::V128_LOAD => {
+            let (memory_argument, post_memory_argument) =
+                continue_parse_memory_load_and_store_argument(remains)?;
+            remains = post_memory_argument;
+            Instruction::V128Load(memory_argument)
+        }
+        opcode::V128_LOAD8X8_S => {
+            let (memory_argument, post_memory_argument) =
+                continue_parse_memory_load_and_store_argument(remains)?;
+            remains = post_memory_argument;
+            Instruction::V128Load8x8S(memory_argument)
+        }
+        opcode::V128_LOAD8X8_U => {
+            let (memory_argument, post_memory_argument) =
+                continue_parse_memory_load_and_store_argument(remains)?;
+            remains = post_memory_argument;
+            Instruction::V128Load8x8U(memory_argument)
+        }
+        opcode::V128_LOAD16X4_S => {
+            let (memory_argument, post_memory_argument) =
+                continue_parse_memory_load_and_store_argument(remains)?;
+            remains = post_memory_argument;
+            Instruction::V128Load16x4S(memory_argument)
+        }
+        opcode::V128_LOAD16X4_U => {
+            let (memory_argument, post_memory_argument) =
+                continue_parse_memory_load_and_store_argument(remains)?;
+            remains = post_memory_argument;
+            Instruction::V128Load16x4U(memory_argument)
+        }
+        opcode::V128_LOAD32X2_S => {
+            let (memory_argument, post_memory_argument) =
+                continue_parse_memory_load_and_store_argument(remains)?;
+            remains = post_memory_argument;
+            Instruction::V128Load32x2S(memory_argument)
+        }
+        opcode::V128_LOAD32X2_U => {
+            let (memory_argument, post_memory_argument) =
+                continue_parse_memory_load_and_store_argument(remains)?;
+            remains = post_memory_argument;
+            Instruction::V128Load32x2U(memory_argument)
+        }
+        opcode::V128_LOAD8_SPLAT => {
+            let (memory_argument, post_memory_argument) =
+                continue_parse_memory_load_and_store_argument(remains)?;
+            remains = post_memory_argument;
+            Instruction::V128Load8Splat(memory_argument)
+        }
+        opcode::V128_LOAD16_SPLAT => {
+            let (memory_argument, post_memory_argument) =
+                continue_parse_memory_load_and_store_argument(remains)?;
+            remains = post_memory_argument;
+            Instruction::V128Load16Splat(memory_argument)
+        }
+        opcode::V128_LOAD32_SPLAT => {
+            let (memory_argument, post_memory_argument) =
+                continue_parse_memory_load_and_store_argument(remains)?;
+            remains = post_memory_argument;
+            Instruction::V128Load32Splat(memory_argument)
+        }
+        opcode::V128_LOAD64_SPLAT => {
+            let (memory_argument, post_memory_argument) =
+                continue_parse_memory_load_and_store_argument(remains)?;
+            remains = post_memory_argument;
+            Instruction::V128Load64Splat(memory_argument)
+        }
+        opcode::V128_STORE => {
+            let (memory_argument, post_memory_argument) =
+                continue_parse_memory_load_and_store_argument(remains)?;
+            remains = post_memory_argument;
+            Instruction::V128Store(memory_argument)
+        }
+        opcode::V128_LOAD8_LANE => {
+            let (memory_argument, post_memory_argument) =
+                continue_parse_memory_load_and_store_argument(remains)?;
+            let (lane_index, post_lane_index) = read_byte(post_memory_argument)?;
+            remains = post_lane_index;
+            Instruction::V128Load8Lane(memory_argument, lane_index)
+        }
+        opcode::V128_LOAD16_LANE => {
+            let (memory_argument, post_memory_argument) =
+                continue_parse_memory_load_and_store_argument(remains)?;
+            let (lane_index, post_lane_index) = read_byte(post_memory_argument)?;
+            remains = post_lane_index;
+            Instruction::V128Load16Lane(memory_argument, lane_index)
+        }
+        opcode::V128_LOAD32_LANE => {
+            let (memory_argument, post_memory_argument) =
+                continue_parse_memory_load_and_store_argument(remains)?;
+            let (lane_index, post_lane_index) = read_byte(post_memory_argument)?;
+            remains = post_lane_index;
+            Instruction::V128Load32Lane(memory_argument, lane_index)
+        }
+        opcode::V128_LOAD64_LANE => {
+            let (memory_argument, post_memory_argument) =
+                continue_parse_memory_load_and_store_argument(remains)?;
+            let (lane_index, post_lane_index) = read_byte(post_memory_argument)?;
+            remains = post_lane_index;
+            Instruction::V128Load64Lane(memory_argument, lane_index)
+        }
+        opcode::V128_STORE8_LANE => {
+            let (memory_argument, post_memory_argument) =
+                continue_parse_memory_load_and_store_argument(remains)?;
+            let (lane_index, post_lane_index) = read_byte(post_memory_argument)?;
+            remains = post_lane_index;
+            Instruction::V128Store8Lane(memory_argument, lane_index)
+        }
+        opcode::V128_STORE16_LANE => {
+            let (memory_argument, post_memory_argument) =
+                continue_parse_memory_load_and_store_argument(remains)?;
+            let (lane_index, post_lane_index) = read_byte(post_memory_argument)?;
+            remains = post_lane_index;
+            Instruction::V128Store16Lane(memory_argument, lane_index)
+        }
+        opcode::V128_STORE32_LANE => {
+            let (memory_argument, post_memory_argument) =
+                continue_parse_memory_load_and_store_argument(remains)?;
+            let (lane_index, post_lane_index) = read_byte(post_memory_argument)?;
+            remains = post_lane_index;
+            Instruction::V128Store32Lane(memory_argument, lane_index)
+        }
+        opcode::V128_STORE64_LANE => {
+            let (memory_argument, post_memory_argument) =
+                continue_parse_memory_load_and_store_argument(remains)?;
+            let (lane_index, post_lane_index) = read_byte(post_memory_argument)?;
+            remains = post_lane_index;
+            Instruction::V128Store64Lane(memory_argument, lane_index)
+        }
+        opcode::V128_LOAD32_ZERO => {
+            let (memory_argument, post_memory_argument) =
+                continue_parse_memory_load_and_store_argument(remains)?;
+            remains = post_memory_argument;
+            Instruction::V128Load32Zero(memory_argument)
+        }
+        opcode::V128_LOAD64_ZERO => {
+            let (memory_argument, post_memory_argument) =
+                continue_parse_memory_load_and_store_argument(remains)?;
+            remains = post_memory_argument;
+            Instruction::V128Load64Zero(memory_argument)
+        }
+
+        // 常量以及洗牌指令
+        opcode::V128_CONST => {
+            let (bytes, post_bytes) = read_bytes(remains, 16)?;
+            remains = post_bytes;
+            Instruction::V128Const(u128::from_le_bytes(bytes.try_into().unwrap()))
+        }
+        opcode::I8X16_SHUFFLE => {
+            let (bytes, post_bytes) = read_bytes(remains, 16)?;
+            remains = post_bytes;
+            Instruction::I8x16Shuffle(bytes.try_into().unwrap())
+        }
+
+        // 通道（lane）读写指令
+        opcode::I8X16_EXTRACT_LANE_S => {
+            let (lane_index, post_lane_index) = read_byte(remains)?;
+            remains = post_lane_index;
+            Instruction::I8x16ExtractLaneS(lane_index)
+        }
+        opcode::I8X16_EXTRACT_LANE_U => {
+            let (lane_index, post_lane_index) = read_byte(remains)?;
+            remains = post_lane_index;
+            Instruction::I8x16ExtractLaneU(lane_index)
+        }
+        opcode::I8X16_REPLACE_LANE => {
+            let (lane_index, post_lane_index) = read_byte(remains)?;
+            remains = post_lane_index;
+            Instruction::I8x16ReplaceLane(lane_index)
+        }
+        opcode::I16X8_EXTRACT_LANE_S => {
+            let (lane_index, post_lane_index) = read_byte(remains)?;
+            remains = post_lane_index;
+            Instruction::I16x8ExtractLaneS(lane_index)
+        }
+        opcode::I16X8_EXTRACT_LANE_U => {
+            let (lane_index, post_lane_index) = read_byte(remains)?;
+            remains = post_lane_index;
+            Instruction::I16x8ExtractLaneU(lane_index)
+        }
+        opcode::I16X8_REPLACE_LANE => {
+            let (lane_index, post_lane_index) = read_byte(remains)?;
+            remains = post_lane_index;
+            Instruction::I16x8ReplaceLane(lane_index)
+        }
+        opcode::I32X4_EXTRACT_LANE => {
+            let (lane_index, post_lane_index) = read_byte(remains)?;
+            remains = post_lane_index;
+            Instruction::I32x4ExtractLane(lane_index)
+        }
+        opcode::I32X4_REPLACE_LANE => {
+            let (lane_index, post_lane_index) = read_byte(remains)?;
+            remains = post_lane_index;
+            Instruction::I32x4ReplaceLane(lane_index)
+        }
+        opcode::I64X2_EXTRACT_LANE => {
+            let (lane_index, post_lane_index) = read_byte(remains)?;
+            remains = post_lane_index;
+            Instruction::I64x2ExtractLane(lane_index)
+        }
+        opcode::I64X2_REPLACE_LANE => {
+            let (lane_index, post_lane_index) = read_byte(remains)?;
+            remains = post_lane_index;
+            Instruction::I64x2ReplaceLane(lane_index)
+        }
+        opcode::F32X4_EXTRACT_LANE => {
+            let (lane_index, post_lane_index) = read_byte(remains)?;
+            remains = post_lane_index;
+            Instruction::F32x4ExtractLane(lane_index)
+        }
+        opcode::F32X4_REPLACE_LANE => {
+            let (lane_index, post_lane_index) = read_byte(remains)?;
+            remains = post_lane_index;
+            Instruction::F32x4ReplaceLane(lane_index)
+        }
+        opcode::F64X2_EXTRACT_LANE => {
+            let (lane_index, post_lane_index) = read_byte(remains)?;
+            remains = post_lane_index;
+            Instruction::F64x2ExtractLane(lane_index)
+        }
+        opcode::F64X2_REPLACE_LANE => {
+            let (lane_index, post_lane_index) = read_byte(remains)?;
+            remains = post_lane_index;
+            Instruction::F64x2ReplaceLane(lane_index)
+        }
+
+        // 不带立即数的指令
+        opcode::I8X16_SWIZZLE => Instruction::I8x16Swizzle,
+        opcode::I8X16_SPLAT => Instruction::I8x16Splat,
+        opcode::I16X8_SPLAT => Instruction::I16x8Splat,
+        opcode::I32X4_SPLAT => Instruction::I32x4Splat,
+        opcode::I64X2_SPLAT => Instruction::I64x2Splat,
+        opcode::F32X4_SPLAT => Instruction::F32x4Splat,
+        opcode::F64X2_SPLAT => Instruction::F64x2Splat,
+        opcode::I8X16_EQ => Instruction::I8x16Eq,
+        opcode::I8X16_NE => Instruction::I8x16Ne,
+        opcode::I8X16_LT_S => Instruction::I8x16LtS,
+        opcode::I8X16_LT_U => Instruction::I8x16LtU,
+        opcode::I8X16_GT_S => Instruction::I8x16GtS,
+        opcode::I8X16_GT_U => Instruction::I8x16GtU,
+        opcode::I8X16_LE_S => Instruction::I8x16LeS,
+        opcode::I8X16_LE_U => Instruction::I8x16LeU,
+        opcode::I8X16_GE_S => Instruction::I8x16GeS,
+        opcode::I8X16_GE_U => Instruction::I8x16GeU,
+        opcode::I16X8_EQ => Instruction::I16x8Eq,
+        opcode::I16X8_NE => Instruction::I16x8Ne,
+        opcode::I16X8_LT_S => Instruction::I16x8LtS,
+        opcode::I16X8_LT_U => Instruction::I16x8LtU,
+        opcode::I16X8_GT_S => Instruction::I16x8GtS,
+        opcode::I16X8_GT_U => Instruction::I16x8GtU,
+        opcode::I16X8_LE_S => Instruction::I16x8LeS,
+        opcode::I16X8_LE_U => Instruction::I16x8LeU,
+        opcode::I16X8_GE_S => Instruction::I16x8GeS,
+        opcode::I16X8_GE_U => Instruction::I16x8GeU,
+        opcode::I32X4_EQ => Instruction::I32x4Eq,
+        opcode::I32X4_NE => Instruction::I32x4Ne,
+        opcode::I32X4_LT_S => Instruction::I32x4LtS,
+        opcode::I32X4_LT_U => Instruction::I32x4LtU,
+        opcode::I32X4_GT_S => Instruction::I32x4GtS,
+        opcode::I32X4_GT_U => Instruction::I32x4GtU,
+        opcode::I32X4_LE_S => Instruction::I32x4LeS,
+        opcode::I32X4_LE_U => Instruction::I32x4LeU,
+        opcode::I32X4_GE_S => Instruction::I32x4GeS,
+        opcode::I32X4_GE_U => Instruction::I32x4GeU,
+        opcode::F32X4_EQ => Instruction::F32x4Eq,
+        opcode::F32X4_NE => Instruction::F32x4Ne,
+        opcode::F32X4_LT => Instruction::F32x4Lt,
+        opcode::F32X4_GT => Instruction::F32x4Gt,
+        opcode::F32X4_LE => Instruction::F32x4Le,
+        opcode::F32X4_GE => Instruction::F32x4Ge,
+        opcode::F64X2_EQ => Instruction::F64x2Eq,
+        opcode::F64X2_NE => Instruction::F64x2Ne,
+        opcode::F64X2_LT => Instruction::F64x2Lt,
+        opcode::F64X2_GT => Instruction::F64x2Gt,
+        opcode::F64X2_LE => Instruction::F64x2Le,
+        opcode::F64X2_GE => Instruction::F64x2Ge,
+        opcode::V128_NOT => Instruction::V128Not,
+        opcode::V128_AND => Instruction::V128And,
+        opcode::V128_ANDNOT => Instruction::V128Andnot,
+        opcode::V128_OR => Instruction::V128Or,
+        opcode::V128_XOR => Instruction::V128Xor,
+        opcode::V128_BITSELECT => Instruction::V128Bitselect,
+        opcode::V128_ANY_TRUE => Instruction::V128AnyTrue,
+        opcode::F32X4_DEMOTE_F64X2_ZERO => Instruction::F32x4DemoteF64x2Zero,
+        opcode::F64X2_PROMOTE_LOW_F32X4 => Instruction::F64x2PromoteLowF32x4,
+        opcode::I8X16_ABS => Instruction::I8x16Abs,
+        opcode::I8X16_NEG => Instruction::I8x16Neg,
+        opcode::I8X16_POPCNT => Instruction::I8x16Popcnt,
+        opcode::I8X16_ALL_TRUE => Instruction::I8x16AllTrue,
+        opcode::I8X16_BITMASK => Instruction::I8x16Bitmask,
+        opcode::I8X16_NARROW_I16X8_S => Instruction::I8x16NarrowI16x8S,
+        opcode::I8X16_NARROW_I16X8_U => Instruction::I8x16NarrowI16x8U,
+        opcode::F32X4_CEIL => Instruction::F32x4Ceil,
+        opcode::F32X4_FLOOR => Instruction::F32x4Floor,
+        opcode::F32X4_TRUNC => Instruction::F32x4Trunc,
+        opcode::F32X4_NEAREST => Instruction::F32x4Nearest,
+        opcode::I8X16_SHL => Instruction::I8x16Shl,
+        opcode::I8X16_SHR_S => Instruction::I8x16ShrS,
+        opcode::I8X16_SHR_U => Instruction::I8x16ShrU,
+        opcode::I8X16_ADD => Instruction::I8x16Add,
+        opcode::I8X16_ADD_SAT_S => Instruction::I8x16AddSatS,
+        opcode::I8X16_ADD_SAT_U => Instruction::I8x16AddSatU,
+        opcode::I8X16_SUB => Instruction::I8x16Sub,
+        opcode::I8X16_SUB_SAT_S => Instruction::I8x16SubSatS,
+        opcode::I8X16_SUB_SAT_U => Instruction::I8x16SubSatU,
+        opcode::F64X2_CEIL => Instruction::F64x2Ceil,
+        opcode::F64X2_FLOOR => Instruction::F64x2Floor,
+        opcode::I8X16_MIN_S => Instruction::I8x16MinS,
+        opcode::I8X16_MIN_U => Instruction::I8x16MinU,
+        opcode::I8X16_MAX_S => Instruction::I8x16MaxS,
+        opcode::I8X16_MAX_U => Instruction::I8x16MaxU,
+        opcode::F64X2_TRUNC => Instruction::F64x2Trunc,
+        opcode::I8X16_AVGR_U => Instruction::I8x16AvgrU,
+        opcode::I16X8_EXTADD_PAIRWISE_I8X16_S => Instruction::I16x8ExtaddPairwiseI8x16S,
+        opcode::I16X8_EXTADD_PAIRWISE_I8X16_U => Instruction::I16x8ExtaddPairwiseI8x16U,
+        opcode::I32X4_EXTADD_PAIRWISE_I16X8_S => Instruction::I32x4ExtaddPairwiseI16x8S,
+        opcode::I32X4_EXTADD_PAIRWISE_I16X8_U => Instruction::I32x4ExtaddPairwiseI16x8U,
+        opcode::I16X8_ABS => Instruction::I16x8Abs,
+        opcode::I16X8_NEG => Instruction::I16x8Neg,
+        opcode::I16X8_Q15MULR_SAT_S => Instruction::I16x8Q15mulrSatS,
+        opcode::I16X8_ALL_TRUE => Instruction::I16x8AllTrue,
+        opcode::I16X8_BITMASK => Instruction::I16x8Bitmask,
+        opcode::I16X8_NARROW_I32X4_S => Instruction::I16x8NarrowI32x4S,
+        opcode::I16X8_NARROW_I32X4_U => Instruction::I16x8NarrowI32x4U,
+        opcode::I16X8_EXTEND_LOW_I8X16_S => Instruction::I16x8ExtendLowI8x16S,
+        opcode::I16X8_EXTEND_HIGH_I8X16_S => Instruction::I16x8ExtendHighI8x16S,
+        opcode::I16X8_EXTEND_LOW_I8X16_U => Instruction::I16x8ExtendLowI8x16U,
+        opcode::I16X8_EXTEND_HIGH_I8X16_U => Instruction::I16x8ExtendHighI8x16U,
+        opcode::I16X8_SHL => Instruction::I16x8Shl,
+        opcode::I16X8_SHR_S => Instruction::I16x8ShrS,
+        opcode::I16X8_SHR_U => Instruction::I16x8ShrU,
+        opcode::I16X8_ADD => Instruction::I16x8Add,
+        opcode::I16X8_ADD_SAT_S => Instruction::I16x8AddSatS,
+        opcode::I16X8_ADD_SAT_U => Instruction::I16x8AddSatU,
+        opcode::I16X8_SUB => Instruction::I16x8Sub,
+        opcode::I16X8_SUB_SAT_S => Instruction::I16x8SubSatS,
+        opcode::I16X8_SUB_SAT_U => Instruction::I16x8SubSatU,
+        opcode::F64X2_NEAREST => Instruction::F64x2Nearest,
+        opcode::I16X8_MUL => Instruction::I16x8Mul,
+        opcode::I16X8_MIN_S => Instruction::I16x8MinS,
+        opcode::I16X8_MIN_U => Instruction::I16x8MinU,
+        opcode::I16X8_MAX_S => Instruction::I16x8MaxS,
+        opcode::I16X8_MAX_U => Instruction::I16x8MaxU,
+        opcode::I16X8_AVGR_U => Instruction::I16x8AvgrU,
+        opcode::I16X8_EXTMUL_LOW_I8X16_S => Instruction::I16x8ExtmulLowI8x16S,
+        opcode::I16X8_EXTMUL_HIGH_I8X16_S => Instruction::I16x8ExtmulHighI8x16S,
+        opcode::I16X8_EXTMUL_LOW_I8X16_U => Instruction::I16x8ExtmulLowI8x16U,
+        opcode::I16X8_EXTMUL_HIGH_I8X16_U => Instruction::I16x8ExtmulHighI8x16U,
+        opcode::I32X4_ABS => Instruction::I32x4Abs,
+        opcode::I32X4_NEG => Instruction::I32x4Neg,
+        opcode::I32X4_ALL_TRUE => Instruction::I32x4AllTrue,
+        opcode::I32X4_BITMASK => Instruction::I32x4Bitmask,
+        opcode::I32X4_EXTEND_LOW_I16X8_S => Instruction::I32x4ExtendLowI16x8S,
+        opcode::I32X4_EXTEND_HIGH_I16X8_S => Instruction::I32x4ExtendHighI16x8S,
+        opcode::I32X4_EXTEND_LOW_I16X8_U => Instruction::I32x4ExtendLowI16x8U,
+        opcode::I32X4_EXTEND_HIGH_I16X8_U => Instruction::I32x4ExtendHighI16x8U,
+        opcode::I32X4_SHL => Instruction::I32x4Shl,
+        opcode::I32X4_SHR_S => Instruction::I32x4ShrS,
+        opcode::I32X4_SHR_U => Instruction::I32x4ShrU,
+        opcode::I32X4_ADD => Instruction::I32x4Add,
+        opcode::I32X4_SUB => Instruction::I32x4Sub,
+        opcode::I32X4_MUL => Instruction::I32x4Mul,
+        opcode::I32X4_MIN_S => Instruction::I32x4MinS,
+        opcode::I32X4_MIN_U => Instruction::I32x4MinU,
+        opcode::I32X4_MAX_S => Instruction::I32x4MaxS,
+        opcode::I32X4_MAX_U => Instruction::I32x4MaxU,
+        opcode::I32X4_DOT_I16X8_S => Instruction::I32x4DotI16x8S,
+        opcode::I32X4_EXTMUL_LOW_I16X8_S => Instruction::I32x4ExtmulLowI16x8S,
+        opcode::I32X4_EXTMUL_HIGH_I16X8_S => Instruction::I32x4ExtmulHighI16x8S,
+        opcode::I32X4_EXTMUL_LOW_I16X8_U => Instruction::I32x4ExtmulLowI16x8U,
+        opcode::I32X4_EXTMUL_HIGH_I16X8_U => Instruction::I32x4ExtmulHighI16x8U,
+        opcode::I64X2_ABS => Instruction::I64x2Abs,
+        opcode::I64X2_NEG => Instruction::I64x2Neg,
+        opcode::I64X2_ALL_TRUE => Instruction::I64x2AllTrue,
+        opcode::I64X2_BITMASK => Instruction::I64x2Bitmask,
+        opcode::I64X2_EXTEND_LOW_I32X4_S => Instruction::I64x2ExtendLowI32x4S,
+        opcode::I64X2_EXTEND_HIGH_I32X4_S => Instruction::I64x2ExtendHighI32x4S,
+        opcode::I64X2_EXTEND_LOW_I32X4_U => Instruction::I64x2ExtendLowI32x4U,
+        opcode::I64X2_EXTEND_HIGH_I32X4_U => Instruction::I64x2ExtendHighI32x4U,
+        opcode::I64X2_SHL => Instruction::I64x2Shl,
+        opcode::I64X2_SHR_S => Instruction::I64x2ShrS,
+        opcode::I64X2_SHR_U => Instruction::I64x2ShrU,
+        opcode::I64X2_ADD => Instruction::I64x2Add,
+        opcode::I64X2_SUB => Instruction::I64x2Sub,
+        opcode::I64X2_MUL => Instruction::I64x2Mul,
+        opcode::I64X2_EQ => Instruction::I64x2Eq,
+        opcode::I64X2_NE => Instruction::I64x2Ne,
+        opcode::I64X2_LT_S => Instruction::I64x2LtS,
+        opcode::I64X2_GT_S => Instruction::I64x2GtS,
+        opcode::I64X2_LE_S => Instruction::I64x2LeS,
+        opcode::I64X2_GE_S => Instruction::I64x2GeS,
+        opcode::I64X2_EXTMUL_LOW_I32X4_S => Instruction::I64x2ExtmulLowI32x4S,
+        opcode::I64X2_EXTMUL_HIGH_I32X4_S => Instruction::I64x2ExtmulHighI32x4S,
+        opcode::I64X2_EXTMUL_LOW_I32X4_U => Instruction::I64x2ExtmulLowI32x4U,
+        opcode::I64X2_EXTMUL_HIGH_I32X4_U => Instruction::I64x2ExtmulHighI32x4U,
+        opcode::F32X4_ABS => Instruction::F32x4Abs,
+        opcode::F32X4_NEG => Instruction::F32x4Neg,
+        opcode::F32X4_SQRT => Instruction::F32x4Sqrt,
+        opcode::F32X4_ADD => Instruction::F32x4Add,
+        opcode::F32X4_SUB => Instruction::F32x4Sub,
+        opcode::F32X4_MUL => Instruction::F32x4Mul,
+        opcode::F32X4_DIV => Instruction::F32x4Div,
+        opcode::F32X4_MIN => Instruction::F32x4Min,
+        opcode::F32X4_MAX => Instruction::F32x4Max,
+        opcode::F32X4_PMIN => Instruction::F32x4Pmin,
+        opcode::F32X4_PMAX => Instruction::F32x4Pmax,
+        opcode::F64X2_ABS => Instruction::F64x2Abs,
+        opcode::F64X2_NEG => Instruction::F64x2Neg,
+        opcode::F64X2_SQRT => Instruction::F64x2Sqrt,
+        opcode::F64X2_ADD => Instruction::F64x2Add,
+        opcode::F64X2_SUB => Instruction::F64x2Sub,
+        opcode::F64X2_MUL => Instruction::F64x2Mul,
+        opcode::F64X2_DIV => Instruction::F64x2Div,
+        opcode::F64X2_MIN => Instruction::F64x2Min,
+        opcode::F64X2_MAX => Instruction::F64x2Max,
+        opcode::F64X2_PMIN => Instruction::F64x2Pmin,
+        opcode::F64X2_PMAX => Instruction::F64x2Pmax,
+        opcode::I32X4_TRUNC_SAT_F32X4_S => Instruction::I32x4TruncSatF32x4S,
+        opcode::I32X4_TRUNC_SAT_F32X4_U => Instruction::I32x4TruncSatF32x4U,
+        opcode::F32X4_CONVERT_I32X4_S => Instruction::F32x4ConvertI32x4S,
+        opcode::F32X4_CONVERT_I32X4_U => Instruction::F32x4ConvertI32x4U,
+        opcode::I32X4_TRUNC_SAT_F64X2_S_ZERO => Instruction::I32x4TruncSatF64x2SZero,
+        opcode::I32X4_TRUNC_SAT_F64X2_U_ZERO => Instruction::I32x4TruncSatF64x2UZero,
+        opcode::F64X2_CONVERT_LOW_I32X4_S => Instruction::F64x2ConvertLowI32x4S,
+        opcode::F64X2_CONVERT_LOW_I32X4_U => Instruction::F64x2ConvertLowI32x4U,
+
+        _ => {
+            return Err(ParseError::Unsupported(
+                Unsupported::UnsupportedInstructionExtensionCode(
+                    opcode::EXTENSION_0XFD,
+                    sub_opcode,
+                ),
+            ));
+        }
+    };
+
+    Ok((instruction, remains))
+}
+
+/// 解析 0xFE 前缀的原子内存访问指令
+///
+/// atomic_instruction = 0xfe + sub_opcode:u32 + memory_argument
+/// atomic_fence = 0xfe + 0x03 + 0x00
+fn continue_parse_atomic_instructions(
+    sub_opcode: u32,
+    source: &[u8],
+) -> Result<(Instruction, &[u8]), ParseError> {
+    if sub_opcode == opcode::ATOMIC_FENCE {
+        // 目前只有一种内存顺序（sequentially consistent），其标记必须为 0
+        let (flag, post_flag) = read_byte(source)?;
+        if flag != 0 {
+            return Err(ParseError::SyntaxError(
+                SyntaxError::InvalidAtomicFenceFlag(flag),
+            ));
+        }
+        return Ok((Instruction::AtomicFence, post_flag));
+    }
+
+    // 除了 `atomic.fence` 之外，其余的原子指令都带有内存参数
+    let (memory_argument, post_memory_argument) =
+        continue_parse_memory_load_and_store_argument(source)?;
+
+    let instruction = match sub_opcode {
+        opcode::MEMORY_ATOMIC_NOTIFY => Instruction::MemoryAtomicNotify(memory_argument),
+        opcode::MEMORY_ATOMIC_WAIT32 => Instruction::MemoryAtomicWait32(memory_argument),
+        opcode::MEMORY_ATOMIC_WAIT64 => Instruction::MemoryAtomicWait64(memory_argument),
+        opcode::I32_ATOMIC_LOAD => Instruction::I32AtomicLoad(memory_argument),
+        opcode::I64_ATOMIC_LOAD => Instruction::I64AtomicLoad(memory_argument),
+        opcode::I32_ATOMIC_LOAD8_U => Instruction::I32AtomicLoad8U(memory_argument),
+        opcode::I32_ATOMIC_LOAD16_U => Instruction::I32AtomicLoad16U(memory_argument),
+        opcode::I64_ATOMIC_LOAD8_U => Instruction::I64AtomicLoad8U(memory_argument),
+        opcode::I64_ATOMIC_LOAD16_U => Instruction::I64AtomicLoad16U(memory_argument),
+        opcode::I64_ATOMIC_LOAD32_U => Instruction::I64AtomicLoad32U(memory_argument),
+        opcode::I32_ATOMIC_STORE => Instruction::I32AtomicStore(memory_argument),
+        opcode::I64_ATOMIC_STORE => Instruction::I64AtomicStore(memory_argument),
+        opcode::I32_ATOMIC_STORE8 => Instruction::I32AtomicStore8(memory_argument),
+        opcode::I32_ATOMIC_STORE16 => Instruction::I32AtomicStore16(memory_argument),
+        opcode::I64_ATOMIC_STORE8 => Instruction::I64AtomicStore8(memory_argument),
+        opcode::I64_ATOMIC_STORE16 => Instruction::I64AtomicStore16(memory_argument),
+        opcode::I64_ATOMIC_STORE32 => Instruction::I64AtomicStore32(memory_argument),
+        opcode::I32_ATOMIC_RMW_ADD => Instruction::I32AtomicRmwAdd(memory_argument),
+        opcode::I64_ATOMIC_RMW_ADD => Instruction::I64AtomicRmwAdd(memory_argument),
+        opcode::I32_ATOMIC_RMW8_ADD_U => Instruction::I32AtomicRmw8AddU(memory_argument),
+        opcode::I32_ATOMIC_RMW16_ADD_U => Instruction::I32AtomicRmw16AddU(memory_argument),
+        opcode::I64_ATOMIC_RMW8_ADD_U => Instruction::I64AtomicRmw8AddU(memory_argument),
+        opcode::I64_ATOMIC_RMW16_ADD_U => Instruction::I64AtomicRmw16AddU(memory_argument),
+        opcode::I64_ATOMIC_RMW32_ADD_U => Instruction::I64AtomicRmw32AddU(memory_argument),
+        opcode::I32_ATOMIC_RMW_SUB => Instruction::I32AtomicRmwSub(memory_argument),
+        opcode::I64_ATOMIC_RMW_SUB => Instruction::I64AtomicRmwSub(memory_argument),
+        opcode::I32_ATOMIC_RMW8_SUB_U => Instruction::I32AtomicRmw8SubU(memory_argument),
+        opcode::I32_ATOMIC_RMW16_SUB_U => Instruction::I32AtomicRmw16SubU(memory_argument),
+        opcode::I64_ATOMIC_RMW8_SUB_U => Instruction::I64AtomicRmw8SubU(memory_argument),
+        opcode::I64_ATOMIC_RMW16_SUB_U => Instruction::I64AtomicRmw16SubU(memory_argument),
+        opcode::I64_ATOMIC_RMW32_SUB_U => Instruction::I64AtomicRmw32SubU(memory_argument),
+        opcode::I32_ATOMIC_RMW_AND => Instruction::I32AtomicRmwAnd(memory_argument),
+        opcode::I64_ATOMIC_RMW_AND => Instruction::I64AtomicRmwAnd(memory_argument),
+        opcode::I32_ATOMIC_RMW8_AND_U => Instruction::I32AtomicRmw8AndU(memory_argument),
+        opcode::I32_ATOMIC_RMW16_AND_U => Instruction::I32AtomicRmw16AndU(memory_argument),
+        opcode::I64_ATOMIC_RMW8_AND_U => Instruction::I64AtomicRmw8AndU(memory_argument),
+        opcode::I64_ATOMIC_RMW16_AND_U => Instruction::I64AtomicRmw16AndU(memory_argument),
+        opcode::I64_ATOMIC_RMW32_AND_U => Instruction::I64AtomicRmw32AndU(memory_argument),
+        opcode::I32_ATOMIC_RMW_OR => Instruction::I32AtomicRmwOr(memory_argument),
+        opcode::I64_ATOMIC_RMW_OR => Instruction::I64AtomicRmwOr(memory_argument),
+        opcode::I32_ATOMIC_RMW8_OR_U => Instruction::I32AtomicRmw8OrU(memory_argument),
+        opcode::I32_ATOMIC_RMW16_OR_U => Instruction::I32AtomicRmw16OrU(memory_argument),
+        opcode::I64_ATOMIC_RMW8_OR_U => Instruction::I64AtomicRmw8OrU(memory_argument),
+        opcode::I64_ATOMIC_RMW16_OR_U => Instruction::I64AtomicRmw16OrU(memory_argument),
+        opcode::I64_ATOMIC_RMW32_OR_U => Instruction::I64AtomicRmw32OrU(memory_argument),
+        opcode::I32_ATOMIC_RMW_XOR => Instruction::I32AtomicRmwXor(memory_argument),
+        opcode::I64_ATOMIC_RMW_XOR => Instruction::I64AtomicRmwXor(memory_argument),
+        opcode::I32_ATOMIC_RMW8_XOR_U => Instruction::I32AtomicRmw8XorU(memory_argument),
+        opcode::I32_ATOMIC_RMW16_XOR_U => Instruction::I32AtomicRmw16XorU(memory_argument),
+        opcode::I64_ATOMIC_RMW8_XOR_U => Instruction::I64AtomicRmw8XorU(memory_argument),
+        opcode::I64_ATOMIC_RMW16_XOR_U => Instruction::I64AtomicRmw16XorU(memory_argument),
+        opcode::I64_ATOMIC_RMW32_XOR_U => Instruction::I64AtomicRmw32XorU(memory_argument),
+        opcode::I32_ATOMIC_RMW_XCHG => Instruction::I32AtomicRmwXchg(memory_argument),
+        opcode::I64_ATOMIC_RMW_XCHG => Instruction::I64AtomicRmwXchg(memory_argument),
+        opcode::I32_ATOMIC_RMW8_XCHG_U => Instruction::I32AtomicRmw8XchgU(memory_argument),
+        opcode::I32_ATOMIC_RMW16_XCHG_U => Instruction::I32AtomicRmw16XchgU(memory_argument),
+        opcode::I64_ATOMIC_RMW8_XCHG_U => Instruction::I64AtomicRmw8XchgU(memory_argument),
+        opcode::I64_ATOMIC_RMW16_XCHG_U => Instruction::I64AtomicRmw16XchgU(memory_argument),
+        opcode::I64_ATOMIC_RMW32_XCHG_U => Instruction::I64AtomicRmw32XchgU(memory_argument),
+        opcode::I32_ATOMIC_RMW_CMPXCHG => Instruction::I32AtomicRmwCmpxchg(memory_argument),
+        opcode::I64_ATOMIC_RMW_CMPXCHG => Instruction::I64AtomicRmwCmpxchg(memory_argument),
+        opcode::I32_ATOMIC_RMW8_CMPXCHG_U => Instruction::I32AtomicRmw8CmpxchgU(memory_argument),
+        opcode::I32_ATOMIC_RMW16_CMPXCHG_U => Instruction::I32AtomicRmw16CmpxchgU(memory_argument),
+        opcode::I64_ATOMIC_RMW8_CMPXCHG_U => Instruction::I64AtomicRmw8CmpxchgU(memory_argument),
+        opcode::I64_ATOMIC_RMW16_CMPXCHG_U => Instruction::I64AtomicRmw16CmpxchgU(memory_argument),
+        opcode::I64_ATOMIC_RMW32_CMPXCHG_U => Instruction::I64AtomicRmw32CmpxchgU(memory_argument),
+        _ => {
+            return Err(ParseError::Unsupported(
+                Unsupported::UnsupportedInstructionExtensionCode(
+                    opcode::EXTENSION_0XFE,
+                    sub_opcode,
+                ),
+            ));
+        }
+    };
+
+    Ok((instruction, post_memory_argument))
+}
+
 /// 将流程控制结构块的返回类型转换为函数返回类型
 /// 因为在解析器的实现时，流程控制结构块将会当作一种简化的函数来处理
 ///
@@ -1313,6 +2177,10 @@ fn get_block_type(value: i32) -> Result<BlockType, ParseError> {
         types::BLOCK_TYPE_I64 => Ok(BlockType::ResultI64),
         types::BLOCK_TYPE_F32 => Ok(BlockType::ResultF32),
         types::BLOCK_TYPE_F64 => Ok(BlockType::ResultF64),
+        types::BLOCK_TYPE_V128 => Ok(BlockType::ResultV128),
+        types::BLOCK_TYPE_FUNC_REF => Ok(BlockType::ResultFuncRef),
+        types::BLOCK_TYPE_EXTERN_REF => Ok(BlockType::ResultExternRef),
+        types::BLOCK_TYPE_EXN_REF => Ok(BlockType::ResultExnRef),
         types::BLOCK_TYPE_EMPTY => Ok(BlockType::ResultEmpty),
         _ if value >= 0 => Ok(BlockType::TypeIndex(value as u32)),
         _ => Err(ParseError::SyntaxError(SyntaxError::InvalidBlockType(
@@ -1321,7 +2189,55 @@ fn get_block_type(value: i32) -> Result<BlockType, ParseError> {
     }
 }
 
-/// 解析内存数据加载和存储指令的参数
+/// 解析 `try_table` 指令的捕获子句列表
+///
+/// <catch> = items_count:u32 + catch{*}
+/// catch = 0x00 + tag_index:u32 + relative_depth:u32   // catch
+///       | 0x01 + tag_index:u32 + relative_depth:u32   // catch_ref
+///       | 0x02 + relative_depth:u32                   // catch_all
+///       | 0x03 + relative_depth:u32                   // catch_all_ref
+fn continue_parse_catch_clauses(source: &[u8]) -> Result<(Vec<CatchClause>, &[u8]), ParseError> {
+    let (item_count, post_item_count) = read_u32(source)?;
+
+    let mut remains = post_item_count;
+    let mut catch_clauses = new_list_with_capacity::<CatchClause>(item_count, post_item_count);
+
+    for _ in 0..item_count {
+        let (kind, post_kind) = read_byte(remains)?;
+        let catch_clause = match kind {
+            types::CATCH_KIND_CATCH | types::CATCH_KIND_CATCH_REF => {
+                let (tag_index, post_tag_index) = read_u32(post_kind)?;
+                let (relative_depth, post_relative_depth) = read_u32(post_tag_index)?;
+                remains = post_relative_depth;
+
+                if kind == types::CATCH_KIND_CATCH {
+                    CatchClause::Catch(tag_index, relative_depth)
+                } else {
+                    CatchClause::CatchRef(tag_index, relative_depth)
+                }
+            }
+            types::CATCH_KIND_CATCH_ALL | types::CATCH_KIND_CATCH_ALL_REF => {
+                let (relative_depth, post_relative_depth) = read_u32(post_kind)?;
+                remains = post_relative_depth;
+
+                if kind == types::CATCH_KIND_CATCH_ALL {
+                    CatchClause::CatchAll(relative_depth)
+                } else {
+                    CatchClause::CatchAllRef(relative_depth)
+                }
+            }
+            _ => {
+                return Err(ParseError::SyntaxError(SyntaxError::InvalidCatchKind(kind)));
+            }
+        };
+
+        catch_clauses.push(catch_clause);
+    }
+
+    Ok((catch_clauses, remains))
+}
+
+/// 解析内存数据加载和存储指令的参数
 ///
 /// 参数有两个：
 ///
@@ -1336,7 +2252,13 @@ fn get_block_type(value: i32) -> Result<BlockType, ParseError> {
 ///
 /// 二进制格式：
 ///
-/// memory_load_and_store_argument = align:u32 + offset:u32
+/// memory_load_and_store_argument = align:u32 + offset:u64
+///                                | (align | 0x40):u32 + memory_block_index:u32 + offset:u64
+///
+/// 即 align 的第 6 位为 1 时，表示之后跟着内存块的索引（多内存块提案）。
+///
+/// 64 位内存块（memory64 提案）的 offset 可以超出 u32 的范围，所以这里总是按照 u64 读取，
+/// 32 位内存块的 offset 是否超出范围由验证器检查。
 ///
 /// 文本格式：
 ///
@@ -1348,9 +2270,21 @@ fn get_block_type(value: i32) -> Result<BlockType, ParseError> {
 fn continue_parse_memory_load_and_store_argument(
     source: &[u8],
 ) -> Result<(MemoryArgument, &[u8]), ParseError> {
-    let (align, post_align) = read_u32(source)?;
-    let (offset, post_offset) = read_u32(post_align)?;
-    let memory_argument = MemoryArgument { align, offset };
+    let (flags, post_flags) = read_u32(source)?;
+
+    let (align, memory_block_index, post_align) = if flags & 0x40 != 0 {
+        let (memory_block_index, post_memory_block_index) = read_u32(post_flags)?;
+        (flags & !0x40, memory_block_index, post_memory_block_index)
+    } else {
+        (flags, 0, post_flags)
+    };
+
+    let (offset, post_offset) = read_u64(post_align)?;
+    let memory_argument = MemoryArgument {
+        align,
+        offset,
+        memory_block_index,
+    };
     Ok((memory_argument, post_offset))
 }
 
@@ -1358,12 +2292,13 @@ fn continue_parse_memory_load_and_store_argument(
 ///
 /// export_section = 0x07 + content_length:u32 + <export_item>
 /// export_item = name:string + export_descriptor
-/// export_descriptor = tag:byte + (function_index | table_index | memory_block_index | global_item_index)
+/// export_descriptor = tag:byte + (function_index | table_index | memory_block_index
+///                     | global_item_index | tag_index)
 fn parse_export_section(source: &[u8]) -> Result<Vec<ExportItem>, ParseError> {
     let (item_count, post_item_count) = read_u32(source)?;
 
     let mut remains = post_item_count;
-    let mut export_items = Vec::<ExportItem>::with_capacity(item_count as usize);
+    let mut export_items = new_list_with_capacity::<ExportItem>(item_count, post_item_count);
 
     for _ in 0..item_count {
         let (export_item, post_export_item) = continue_parse_export_item(remains)?;
@@ -1379,7 +2314,8 @@ fn parse_export_section(source: &[u8]) -> Result<Vec<ExportItem>, ParseError> {
 }
 
 /// export_item = name:string + export_descriptor
-/// export_descriptor = tag:byte + (function_index | table_index | memory_block_index | global_item_index)
+/// export_descriptor = tag:byte + (function_index | table_index | memory_block_index
+///                     | global_item_index | tag_index)
 fn continue_parse_export_item(source: &[u8]) -> Result<(ExportItem, &[u8]), ParseError> {
     let (name, post_name) = read_string(source)?;
 
@@ -1391,6 +2327,7 @@ fn continue_parse_export_item(source: &[u8]) -> Result<(ExportItem, &[u8]), Pars
         types::EXPORT_TAG_TABLE => ExportDescriptor::TableIndex(index),
         types::EXPORT_TAG_MEM => ExportDescriptor::MemoryBlockIndex(index),
         types::EXPORT_TAG_GLOBAL => ExportDescriptor::GlobalItemIndex(index),
+        types::EXPORT_TAG_TAG => ExportDescriptor::TagIndex(index),
         _ => {
             return Err(ParseError::Unsupported(Unsupported::UnsupportedExportTag(
                 tag,
@@ -1421,13 +2358,14 @@ fn parse_start_function_section(source: &[u8]) -> Result<u32, ParseError> {
 /// # 解析元素段
 ///
 /// element_section = 0x09 + content_length:u32 + <element_item>
-/// element_item = table_index:u32 + offset_expression + <function_index>
-/// offset_expression = byte{*} + 0x0B  // 表达式（指令列表）以 0x0B 结尾
+/// element_item = flags:u32 + ...
+///
+/// 元素项一共有 8 种格式，详细请见 `anvm_ast::ast::ElementItem`
 fn parse_element_section(source: &[u8]) -> Result<Vec<ElementItem>, ParseError> {
     let (item_count, post_item_count) = read_u32(source)?;
 
     let mut remains = post_item_count;
-    let mut element_items = Vec::<ElementItem>::with_capacity(item_count as usize);
+    let mut element_items = new_list_with_capacity::<ElementItem>(item_count, post_item_count);
 
     for _ in 0..item_count {
         let (element_item, post_element_item) = continue_parse_element_item(remains)?;
@@ -1442,20 +2380,104 @@ fn parse_element_section(source: &[u8]) -> Result<Vec<ElementItem>, ParseError>
     }
 }
 
-/// element_item = table_index:u32 + offset_expression + <function_index>
-/// offset_expression = byte{*} + 0x0B  // 表达式（指令列表）以 0x0B 结尾
+/// element_item = flags:u32 + ...
+///
+/// - 第 0 位为 1 表示被动或者声明模式，为 0 表示主动模式；
+/// - 第 1 位在主动模式下表示有显式的表索引，在其他模式下表示声明模式；
+/// - 第 2 位表示元素使用常量表达式列表（否则为函数索引列表）。
+///
+/// 对于函数索引列表，显式表索引的主动模式以及非主动模式带有 elem_kind，
+/// 对于常量表达式列表，显式表索引的主动模式以及非主动模式带有 ref_type。
 fn continue_parse_element_item(source: &[u8]) -> Result<(ElementItem, &[u8]), ParseError> {
-    let (table_index, post_index) = read_u32(source)?;
-    let (offset_instruction_items, post_instruction_items) = continue_parse_expression(post_index)?;
-    let (function_indices, post_indices) = read_u32_vec(post_instruction_items)?;
+    let (flags, post_flags) = read_u32(source)?;
+    if flags > 7 {
+        return Err(ParseError::SyntaxError(SyntaxError::InvalidElementFlags(
+            flags,
+        )));
+    }
+
+    let is_non_active = flags & 0b001 != 0;
+    let has_table_index_or_is_declarative = flags & 0b010 != 0;
+    let uses_expressions = flags & 0b100 != 0;
+
+    let mut remains = post_flags;
+
+    let mode = if is_non_active {
+        if has_table_index_or_is_declarative {
+            ElementMode::Declarative
+        } else {
+            ElementMode::Passive
+        }
+    } else {
+        let table_index = if has_table_index_or_is_declarative {
+            let (table_index, post_table_index) = read_u32(remains)?;
+            remains = post_table_index;
+            table_index
+        } else {
+            0
+        };
+
+        let (offset_instruction_items, post_instruction_items) =
+            continue_parse_expression(remains)?;
+        remains = post_instruction_items;
+
+        ElementMode::Active {
+            table_index,
+            offset_instruction_items,
+        }
+    };
+
+    // 仅 flags 为 0 和 4 时省略了元素的类型，此时类型为 funcref
+    let has_element_type = is_non_active || has_table_index_or_is_declarative;
+
+    let (element_type, items) = if uses_expressions {
+        let element_type = if has_element_type {
+            let (ref_type, post_ref_type) = continue_parse_ref_type(remains)?;
+            remains = post_ref_type;
+            ref_type
+        } else {
+            RefType::FuncRef
+        };
+
+        let (expression_count, post_expression_count) = read_u32(remains)?;
+        remains = post_expression_count;
+
+        let mut expressions: Vec<Vec<Instruction>> =
+            new_list_with_capacity(expression_count, post_expression_count);
+        for _ in 0..expression_count {
+            let (expression, post_expression) = continue_parse_expression(remains)?;
+            expressions.push(expression);
+            remains = post_expression;
+        }
+
+        (element_type, ElementItems::Expressions(expressions))
+    } else {
+        if has_element_type {
+            let (element_kind, post_element_kind) = read_byte(remains)?;
+            if element_kind != types::ELEMENT_KIND_FUNC_REF {
+                return Err(ParseError::SyntaxError(SyntaxError::InvalidElementKind(
+                    element_kind,
+                )));
+            }
+            remains = post_element_kind;
+        }
+
+        let (function_indices, post_indices) = read_u32_vec(remains)?;
+        remains = post_indices;
+
+        (
+            RefType::FuncRef,
+            ElementItems::FunctionIndices(function_indices),
+        )
+    };
 
     let element_item = ElementItem {
-        table_index: table_index,
-        offset_instruction_items,
-        function_indices,
+        mode,
+        element_type,
+        items,
     };
 
-    Ok((element_item, post_indices))
+    Ok((element_item, remains))
 }
 
 /// # 解析代码段
@@ -1466,14 +2488,18 @@ fn continue_parse_element_item(source: &[u8]) -> Result<(ElementItem, &[u8]), Pa
 /// expression = byte{*} + 0x0B  // 表达式（指令列表）以 0x0B 结尾
 ///
 /// code_content_length 表示该项目的内容总大小，包括了局部变量声明列表以及指令序列，指令序列结尾的 0x0B。
-fn parse_function_code_section(source: &[u8]) -> Result<Vec<CodeItem>, ParseError> {
+fn parse_function_code_section(
+    source: &[u8],
+    layout: &mut BinaryLayout,
+) -> Result<Vec<CodeItem>, ParseError> {
     let (item_count, post_item_count) = read_u32(source)?;
 
     let mut remains = post_item_count;
-    let mut code_items = Vec::<CodeItem>::with_capacity(item_count as usize);
+    let mut code_items = new_list_with_capacity::<CodeItem>(item_count, post_item_count);
 
-    for _ in 0..item_count {
-        let (code_item, post_code_item) = continue_parse_code_item(remains)?;
+    for function_index in 0..item_count as usize {
+        let (code_item, post_code_item) =
+            continue_parse_code_item(remains, function_index, layout)?;
         code_items.push(code_item);
         remains = post_code_item;
     }
@@ -1488,14 +2514,26 @@ fn parse_function_code_section(source: &[u8]) -> Result<Vec<CodeItem>, ParseErro
 /// code_item = code_content_length:u32 + <local_group> + expression
 /// local_group = local_variable_count:u32 + value_type:byte
 /// expression = byte{*} + 0x0B  // 表达式（指令列表）以 0x0B 结尾
-fn continue_parse_code_item(source: &[u8]) -> Result<(CodeItem, &[u8]), ParseError> {
+fn continue_parse_code_item<'a>(
+    source: &'a [u8],
+    function_index: usize,
+    layout: &mut BinaryLayout,
+) -> Result<(CodeItem, &'a [u8]), ParseError> {
     // code_content_length 表示该项目的内容总大小，包括了局部变量声明列表以及指令序列，指令序列结尾的 0x0B。
     let (code_content_length, post_code_content_length) = read_u32(source)?;
     let (code_data, post_code_data) =
-        post_code_content_length.split_at(code_content_length as usize);
+        read_bytes(post_code_content_length, code_content_length as usize)?;
+
+    let length_width = source.len() - post_code_content_length.len();
+    if length_width > get_leb128_width(code_content_length as u64) {
+        layout
+            .code_length_widths
+            .insert(function_index, length_width);
+    }
 
     let (local_groups, post_local_groups) = continue_parse_local_groups(code_data)?;
-    let instruction_items = continue_parse_instruction_items(post_local_groups)?;
+    let instruction_items =
+        continue_parse_instruction_items(post_local_groups, function_index, layout)?;
     let code_item = CodeItem {
         local_groups,
         instruction_items,
@@ -1508,7 +2546,8 @@ fn continue_parse_local_groups(source: &[u8]) -> Result<(Vec<LocalGroup>, &[u8])
     let (local_group_count, post_local_group_count) = read_u32(source)?;
 
     let mut remains = post_local_group_count;
-    let mut local_groups = Vec::<LocalGroup>::with_capacity(local_group_count as usize);
+    let mut local_groups =
+        new_list_with_capacity::<LocalGroup>(local_group_count, post_local_group_count);
 
     for _ in 0..local_group_count {
         let (variable_count, post_variable_count) = read_u32(remains)?;
@@ -1525,7 +2564,11 @@ fn continue_parse_local_groups(source: &[u8]) -> Result<(Vec<LocalGroup>, &[u8])
     Ok((local_groups, remains))
 }
 
-fn continue_parse_instruction_items(source: &[u8]) -> Result<Vec<Instruction>, ParseError> {
+fn continue_parse_instruction_items(
+    source: &[u8],
+    function_index: usize,
+    layout: &mut BinaryLayout,
+) -> Result<Vec<Instruction>, ParseError> {
     let mut instruction_items: Vec<Instruction> = vec![];
     let mut remains = source;
 
@@ -1539,6 +2582,16 @@ fn continue_parse_instruction_items(source: &[u8]) -> Result<Vec<Instruction>, P
 
         let (instruction_item, post_instruction, next_block_index) =
             continue_parse_instruction_item(remains, block_index)?;
+
+        // 保留含有非最短编码的整数的指令的原始编码
+        let instruction_bytes = &remains[..remains.len() - post_instruction.len()];
+        if has_padded_leb128(instruction_bytes) {
+            layout.padded_instructions.insert(
+                (function_index, instruction_items.len()),
+                (instruction_item.clone(), instruction_bytes.to_vec()),
+            );
+        }
+
         instruction_items.push(instruction_item);
 
         block_index = next_block_index;
@@ -1557,13 +2610,12 @@ fn continue_parse_instruction_items(source: &[u8]) -> Result<Vec<Instruction>, P
 /// # 解析数据段
 ///
 /// data_section = 0x0b + content_length:u32 + <data_item>
-/// data_item = memory_block_index:u32 + offset_expression + data:byte{*}
-/// offset_expression = = byte{*} + 0x0B  // 表达式（指令列表）以 0x0B 结尾
+/// data_item = flags:u32 + ...
 fn parse_data_section(source: &[u8]) -> Result<Vec<DataItem>, ParseError> {
     let (item_count, post_item_count) = read_u32(source)?;
 
     let mut remains = post_item_count;
-    let mut data_items = Vec::<DataItem>::with_capacity(item_count as usize);
+    let mut data_items = new_list_with_capacity::<DataItem>(item_count, post_item_count);
 
     for _ in 0..item_count {
         let (data_item, post_data_item) = continue_parse_data_item(remains)?;
@@ -1578,19 +2630,44 @@ fn parse_data_section(source: &[u8]) -> Result<Vec<DataItem>, ParseError> {
     }
 }
 
-/// data_item = memory_block_index:u32 + offset_expression + data:byte{*}
-/// offset_expression = = byte{*} + 0x0B  // 表达式（指令列表）以 0x0B 结尾
+/// data_item = flags:u32 + ...
+///
+/// 0: offset_expression + data:byte{*}                           ;; 主动，内存块 0
+/// 1: data:byte{*}                                               ;; 被动
+/// 2: memory_block_index:u32 + offset_expression + data:byte{*}  ;; 主动
+///
+/// offset_expression = byte{*} + 0x0B  // 表达式（指令列表）以 0x0B 结尾
 fn continue_parse_data_item(source: &[u8]) -> Result<(DataItem, &[u8]), ParseError> {
-    let (memory_block_index, post_index) = read_u32(source)?;
-    let (offset_instruction_items, post_instruction_items) = continue_parse_expression(post_index)?;
-    let (data, post_data) = read_byte_vec(post_instruction_items)?;
-
-    let data_item = DataItem {
-        memory_block_index,
-        offset_instruction_items,
-        data,
+    let (flags, post_flags) = read_u32(source)?;
+
+    let (mode, post_mode) = match flags {
+        0 | 2 => {
+            let (memory_block_index, post_index) = if flags == 2 {
+                read_u32(post_flags)?
+            } else {
+                (0, post_flags)
+            };
+
+            let (offset_instruction_items, post_instruction_items) =
+                continue_parse_expression(post_index)?;
+
+            let mode = DataMode::Active {
+                memory_block_index,
+                offset_instruction_items,
+            };
+            (mode, post_instruction_items)
+        }
+        1 => (DataMode::Passive, post_flags),
+        _ => {
+            return Err(ParseError::SyntaxError(SyntaxError::InvalidDataFlags(
+                flags,
+            )))
+        }
     };
 
+    let (data, post_data) = read_byte_vec(post_mode)?;
+    let data_item = DataItem { mode, data };
+
     Ok((data_item, post_data))
 }
 
@@ -1636,6 +2713,14 @@ fn read_fixed_u32(source: &[u8]) -> Result<(u32, &[u8]), ParseError> {
 /// 注：
 /// 大部分指令使用 unsigned int，但有些使用 signed int，
 /// 比如 const 指令的立即数和 block type
+/// 创建指定容量的列表
+///
+/// 元素的数量读取自模块的二进制内容，而每个元素至少占一个字节，所以容量不超过剩余的字节数，
+/// 以免一个很小的（损坏或者恶意的）模块声明很大的元素数量，令解析器预先分配大量的内存。
+fn new_list_with_capacity<T>(item_count: u32, remains: &[u8]) -> Vec<T> {
+    Vec::with_capacity((item_count as usize).min(remains.len()))
+}
+
 fn read_u32(source: &[u8]) -> Result<(u32, &[u8]), ParseError> {
     match leb128decoder::decode_u32(source) {
         Ok((value, length)) => Ok((value, &source[length..])),
@@ -1663,8 +2748,7 @@ fn read_i64(source: &[u8]) -> Result<(i64, &[u8]), ParseError> {
     }
 }
 
-/// 读取变长（leb128 编码的）i64
-#[allow(dead_code)]
+/// 读取变长（leb128 编码的）u64
 fn read_u64(source: &[u8]) -> Result<(u64, &[u8]), ParseError> {
     match leb128decoder::decode_u64(source) {
         Ok((value, length)) => Ok((value, &source[length..])),
@@ -1726,16 +2810,19 @@ mod tests {
 
     use anvm_ast::{
         ast::{
-            CodeItem, CustomItem, DataItem, ElementItem, ExportDescriptor, ExportItem,
-            FunctionIndexAndBlockLabelsPair, FunctionIndexAndLocalVariableNamesPair, FunctionType,
-            GlobalItem, GlobalType, ImportDescriptor, ImportItem, IndexNamePair, Limit, LocalGroup,
-            MemoryType, Module, NameCollection, TableType, TypeItem,
+            CodeItem, CustomItem, DataItem, DataMode, ElementItem, ElementItems, ElementMode,
+            ExportDescriptor, ExportItem, FunctionIndexAndBlockLabelsPair,
+            FunctionIndexAndLocalVariableNamesPair, FunctionType, GlobalItem, GlobalType,
+            ImportDescriptor, ImportItem, IndexNamePair, Limit, LocalGroup, MemoryType, Module,
+            NameCollection, TableType, TagType, TypeItem,
         },
-        instruction::{BlockType, Instruction, MemoryArgument},
-        types::ValueType,
+        instruction::{BlockType, CatchClause, Instruction, MemoryArgument},
+        types::{RefType, ValueType},
     };
 
-    use super::parse;
+    use crate::error::{ParseError, SyntaxError, Unsupported};
+
+    use super::{continue_parse_instruction_item, parse};
     use pretty_assertions::assert_eq;
 
     // 辅助方法
@@ -1799,11 +2886,14 @@ mod tests {
                 item_name: "__linear_memory".to_string(),
                 import_descriptor: ImportDescriptor::MemoryType(MemoryType {
                     limit: Limit::AtLeast(0),
+                    shared: false,
+                    memory64: false,
                 }),
             }],
             internal_function_to_type_index_list: vec![0],
             tables: vec![],
             memory_blocks: vec![],
+            tag_items: vec![],
             global_items: vec![],
             export_items: vec![],
             start_function_index: None,
@@ -1822,6 +2912,7 @@ mod tests {
                 ],
             }],
             data_items: vec![],
+            data_count: None,
         };
         assert_eq!(expected, remove_unsupported_custom_section(module));
     }
@@ -1875,7 +2966,10 @@ mod tests {
             tables: vec![],
             memory_blocks: vec![MemoryType {
                 limit: Limit::AtLeast(16),
+                shared: false,
+                memory64: false,
             }],
+            tag_items: vec![],
             global_items: vec![
                 GlobalItem {
                     global_type: GlobalType {
@@ -1974,6 +3068,7 @@ mod tests {
                 },
             ],
             data_items: vec![],
+            data_count: None,
         };
         assert_eq!(expected, remove_unsupported_custom_section(module));
     }
@@ -2034,11 +3129,15 @@ mod tests {
             ],
             internal_function_to_type_index_list: vec![1, 1],
             tables: vec![TableType {
+                element_type: RefType::FuncRef,
                 limit: Limit::Range(2, 4),
             }],
             memory_blocks: vec![MemoryType {
                 limit: Limit::Range(1, 8),
+                shared: false,
+                memory64: false,
             }],
+            tag_items: vec![],
             global_items: vec![],
             export_items: vec![
                 ExportItem {
@@ -2053,14 +3152,20 @@ mod tests {
             start_function_index: Some(3),
             element_items: vec![
                 ElementItem {
-                    table_index: 0,
-                    offset_instruction_items: vec![Instruction::I32Const(1), Instruction::End],
-                    function_indices: vec![2],
+                    mode: ElementMode::Active {
+                        table_index: 0,
+                        offset_instruction_items: vec![Instruction::I32Const(1), Instruction::End],
+                    },
+                    element_type: RefType::FuncRef,
+                    items: ElementItems::FunctionIndices(vec![2]),
                 },
                 ElementItem {
-                    table_index: 0,
-                    offset_instruction_items: vec![Instruction::I32Const(3), Instruction::End],
-                    function_indices: vec![3],
+                    mode: ElementMode::Active {
+                        table_index: 0,
+                        offset_instruction_items: vec![Instruction::I32Const(3), Instruction::End],
+                    },
+                    element_type: RefType::FuncRef,
+                    items: ElementItems::FunctionIndices(vec![3]),
                 },
             ],
             code_items: vec![
@@ -2070,6 +3175,7 @@ mod tests {
                         Instruction::I32Load(MemoryArgument {
                             align: 2,
                             offset: 100,
+                            memory_block_index: 0,
                         }),
                         Instruction::Call(0),
                         Instruction::Call(3),
@@ -2082,10 +3188,12 @@ mod tests {
                         Instruction::I32Load(MemoryArgument {
                             align: 3,
                             offset: 200,
+                            memory_block_index: 0,
                         }),
                         Instruction::I64Load(MemoryArgument {
                             align: 3,
                             offset: 400,
+                            memory_block_index: 0,
                         }),
                         Instruction::End,
                     ],
@@ -2093,16 +3201,27 @@ mod tests {
             ],
             data_items: vec![
                 DataItem {
-                    memory_block_index: 0,
-                    offset_instruction_items: vec![Instruction::I32Const(100), Instruction::End],
+                    mode: DataMode::Active {
+                        memory_block_index: 0,
+                        offset_instruction_items: vec![
+                            Instruction::I32Const(100),
+                            Instruction::End,
+                        ],
+                    },
                     data: vec![104, 101, 108, 108, 111],
                 },
                 DataItem {
-                    memory_block_index: 0,
-                    offset_instruction_items: vec![Instruction::I32Const(200), Instruction::End],
+                    mode: DataMode::Active {
+                        memory_block_index: 0,
+                        offset_instruction_items: vec![
+                            Instruction::I32Const(200),
+                            Instruction::End,
+                        ],
+                    },
                     data: vec![80, 96, 112],
                 },
             ],
+            data_count: None,
         };
         assert_eq!(expected, remove_unsupported_custom_section(module));
     }
@@ -2251,24 +3370,34 @@ mod tests {
             }],
             internal_function_to_type_index_list: vec![2, 0],
             tables: vec![TableType {
+                element_type: RefType::FuncRef,
                 limit: Limit::Range(2, 4),
             }],
             memory_blocks: vec![MemoryType {
                 limit: Limit::Range(1, 2),
+                shared: false,
+                memory64: false,
             }],
+            tag_items: vec![],
             global_items: vec![],
             export_items: vec![],
             start_function_index: None,
             element_items: vec![
                 ElementItem {
-                    table_index: 0,
-                    offset_instruction_items: vec![Instruction::I32Const(1), Instruction::End],
-                    function_indices: vec![1],
+                    mode: ElementMode::Active {
+                        table_index: 0,
+                        offset_instruction_items: vec![Instruction::I32Const(1), Instruction::End],
+                    },
+                    element_type: RefType::FuncRef,
+                    items: ElementItems::FunctionIndices(vec![1]),
                 },
                 ElementItem {
-                    table_index: 0,
-                    offset_instruction_items: vec![Instruction::I32Const(3), Instruction::End],
-                    function_indices: vec![2],
+                    mode: ElementMode::Active {
+                        table_index: 0,
+                        offset_instruction_items: vec![Instruction::I32Const(3), Instruction::End],
+                    },
+                    element_type: RefType::FuncRef,
+                    items: ElementItems::FunctionIndices(vec![2]),
                 },
             ],
             code_items: vec![
@@ -2322,20 +3451,198 @@ mod tests {
             ],
             data_items: vec![
                 DataItem {
-                    memory_block_index: 0,
-                    offset_instruction_items: vec![Instruction::I32Const(10), Instruction::End],
+                    mode: DataMode::Active {
+                        memory_block_index: 0,
+                        offset_instruction_items: vec![Instruction::I32Const(10), Instruction::End],
+                    },
                     data: vec![102, 111, 111],
                 },
                 DataItem {
-                    memory_block_index: 0,
-                    offset_instruction_items: vec![Instruction::I32Const(20), Instruction::End],
+                    mode: DataMode::Active {
+                        memory_block_index: 0,
+                        offset_instruction_items: vec![Instruction::I32Const(20), Instruction::End],
+                    },
                     data: vec![98, 97, 114],
                 },
             ],
+            data_count: None,
         };
         assert_eq!(expected, remove_unsupported_custom_section(module));
     }
 
+    #[test]
+    fn test_parse_module_section_element() {
+        // 元素段的 8 种编码（标志 0 到 7），每个段只有一个元素
+        let binary = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // 魔数和版本
+            0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // 类型段
+            0x03, 0x02, 0x01, 0x00, // 函数段
+            0x04, 0x04, 0x01, 0x70, 0x00, 0x04, // 表段
+            0x09, 0x35, 0x08, // 元素段
+            0x00, 0x41, 0x00, 0x0b, 0x01, 0x00, // 0: 主动，函数索引
+            0x01, 0x00, 0x01, 0x00, // 1: 被动，函数索引
+            0x02, 0x00, 0x41, 0x01, 0x0b, 0x00, 0x01, 0x00, // 2: 主动，指定表，函数索引
+            0x03, 0x00, 0x01, 0x00, // 3: 声明，函数索引
+            0x04, 0x41, 0x02, 0x0b, 0x01, 0xd2, 0x00, 0x0b, // 4: 主动，表达式
+            0x05, 0x6f, 0x01, 0xd0, 0x6f, 0x0b, // 5: 被动，表达式
+            0x06, 0x00, 0x41, 0x03, 0x0b, 0x70, 0x01, 0xd0, 0x70, 0x0b, // 6: 主动，指定表，表达式
+            0x07, 0x70, 0x01, 0xd2, 0x00, 0x0b, // 7: 声明，表达式
+            0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b, // 代码段
+        ];
+
+        let active = |offset: i32| ElementMode::Active {
+            table_index: 0,
+            offset_instruction_items: vec![Instruction::I32Const(offset), Instruction::End],
+        };
+        let function_indices = ElementItems::FunctionIndices(vec![0]);
+
+        let module = parse(&binary).unwrap();
+        assert_eq!(
+            module.element_items,
+            vec![
+                ElementItem {
+                    mode: active(0),
+                    element_type: RefType::FuncRef,
+                    items: function_indices.clone(),
+                },
+                ElementItem {
+                    mode: ElementMode::Passive,
+                    element_type: RefType::FuncRef,
+                    items: function_indices.clone(),
+                },
+                ElementItem {
+                    mode: active(1),
+                    element_type: RefType::FuncRef,
+                    items: function_indices.clone(),
+                },
+                ElementItem {
+                    mode: ElementMode::Declarative,
+                    element_type: RefType::FuncRef,
+                    items: function_indices,
+                },
+                ElementItem {
+                    mode: active(2),
+                    element_type: RefType::FuncRef,
+                    items: ElementItems::Expressions(vec![vec![
+                        Instruction::RefFunc(0),
+                        Instruction::End
+                    ]]),
+                },
+                ElementItem {
+                    mode: ElementMode::Passive,
+                    element_type: RefType::ExternRef,
+                    items: ElementItems::Expressions(vec![vec![
+                        Instruction::RefNull(RefType::ExternRef),
+                        Instruction::End
+                    ]]),
+                },
+                ElementItem {
+                    mode: active(3),
+                    element_type: RefType::FuncRef,
+                    items: ElementItems::Expressions(vec![vec![
+                        Instruction::RefNull(RefType::FuncRef),
+                        Instruction::End
+                    ]]),
+                },
+                ElementItem {
+                    mode: ElementMode::Declarative,
+                    element_type: RefType::FuncRef,
+                    items: ElementItems::Expressions(vec![vec![
+                        Instruction::RefFunc(0),
+                        Instruction::End
+                    ]]),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_module_section_data() {
+        // 数据段的 3 种编码（标志 0 到 2）
+        let binary = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // 魔数和版本
+            0x05, 0x05, 0x02, 0x00, 0x01, 0x00, 0x01, // 内存段
+            0x0b, 0x13, 0x03, // 数据段
+            0x00, 0x41, 0x00, 0x0b, 0x01, 0x61, // 0: 主动，内存块 0
+            0x02, 0x01, 0x41, 0x00, 0x0b, 0x02, 0x61, 0x62, // 2: 主动，指定内存块
+            0x01, 0x02, 0x61, 0x62, // 1: 被动
+        ];
+
+        let module = parse(&binary).unwrap();
+        assert_eq!(
+            module.data_items,
+            vec![
+                DataItem {
+                    mode: DataMode::Active {
+                        memory_block_index: 0,
+                        offset_instruction_items: vec![Instruction::I32Const(0), Instruction::End],
+                    },
+                    data: vec![0x61],
+                },
+                DataItem {
+                    mode: DataMode::Active {
+                        memory_block_index: 1,
+                        offset_instruction_items: vec![Instruction::I32Const(0), Instruction::End],
+                    },
+                    data: vec![0x61, 0x62],
+                },
+                DataItem {
+                    mode: DataMode::Passive,
+                    data: vec![0x61, 0x62],
+                },
+            ]
+        );
+
+        // 无效的标志
+        assert_eq!(
+            parse(&[
+                0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // 魔数和版本
+                0x0b, 0x05, 0x01, 0x03, 0x02, 0x61, 0x62, // 数据段
+            ]),
+            Err(ParseError::SyntaxError(SyntaxError::InvalidDataFlags(3)))
+        );
+    }
+
+    #[test]
+    fn test_parse_truncated_content() {
+        // 段的内容长度超出了剩余的数据
+        assert_eq!(
+            parse(&[0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x05, 0x01, 0x60, 0x00]),
+            Err(ParseError::UnexpectedEnd)
+        );
+
+        // 代码项的内容长度超出了代码段的数据
+        assert_eq!(
+            parse(&[
+                0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // 头部
+                0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // 类型段
+                0x03, 0x02, 0x01, 0x00, // 函数段
+                0x0a, 0x04, 0x01, 0x05, 0x00, 0x0b, // 代码段
+            ]),
+            Err(ParseError::UnexpectedEnd)
+        );
+
+        // 项目的数量远大于剩余的数据，解析器不应该按照声明的数量预先分配内存
+        assert_eq!(
+            parse(&[
+                0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // 头部
+                0x01, 0x05, 0xff, 0xff, 0xff, 0xff, 0x0f, // 类型段，声明了 2^32 - 1 个类型
+            ]),
+            Err(ParseError::UnexpectedEnd)
+        );
+
+        // 局部变量组的数量远大于剩余的数据
+        assert_eq!(
+            parse(&[
+                0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // 头部
+                0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // 类型段
+                0x03, 0x02, 0x01, 0x00, // 函数段
+                0x0a, 0x08, 0x01, 0x06, 0xff, 0xff, 0xff, 0xff, 0x0f, 0x0b, // 代码段
+            ]),
+            Err(ParseError::UnexpectedEnd)
+        );
+    }
+
     #[test]
     fn test_parse_instruction_const() {
         let binary = get_test_binary_resource("test-instruction-const.wasm");
@@ -2421,15 +3728,19 @@ mod tests {
         assert_eq!(
             module.memory_blocks,
             vec![MemoryType {
-                limit: Limit::Range(1, 8)
+                limit: Limit::Range(1, 8),
+                shared: false,
+                memory64: false
             }]
         );
 
         assert_eq!(
             module.data_items,
             vec![DataItem {
-                memory_block_index: 0,
-                offset_instruction_items: vec![Instruction::I32Const(100), Instruction::End],
+                mode: DataMode::Active {
+                    memory_block_index: 0,
+                    offset_instruction_items: vec![Instruction::I32Const(100), Instruction::End],
+                },
                 data: vec!['h' as u8, 'e' as u8, 'l' as u8, 'l' as u8, 'o' as u8]
             }]
         );
@@ -2443,11 +3754,13 @@ mod tests {
                     Instruction::I32Const(2),
                     Instruction::I32Load(MemoryArgument {
                         align: 2,
-                        offset: 100
+                        offset: 100,
+                        memory_block_index: 0
                     }),
                     Instruction::I32Store(MemoryArgument {
                         align: 2,
-                        offset: 100
+                        offset: 100,
+                        memory_block_index: 0
                     }),
                     Instruction::MemorySize(0),
                     Instruction::Drop,
@@ -2506,6 +3819,7 @@ mod tests {
         assert_eq!(
             m0.tables,
             vec![TableType {
+                element_type: RefType::FuncRef,
                 limit: Limit::Range(3, 3)
             }]
         );
@@ -2513,9 +3827,12 @@ mod tests {
         assert_eq!(
             m0.element_items,
             vec![ElementItem {
-                table_index: 0,
-                offset_instruction_items: vec![Instruction::I32Const(0), Instruction::End],
-                function_indices: vec![1, 1, 1]
+                mode: ElementMode::Active {
+                    table_index: 0,
+                    offset_instruction_items: vec![Instruction::I32Const(0), Instruction::End],
+                },
+                element_type: RefType::FuncRef,
+                items: ElementItems::FunctionIndices(vec![1, 1, 1]),
             }]
         );
 
@@ -2605,4 +3922,386 @@ mod tests {
             ]
         )
     }
+
+    #[test]
+    fn test_parse_instruction_simd() {
+        let binary = get_test_binary_resource("test-instruction-simd.wasm");
+        let module = parse(&binary).unwrap();
+        assert_eq!(
+            module.type_items[0],
+            TypeItem::FunctionType(FunctionType {
+                params: vec![ValueType::V128],
+                results: vec![ValueType::V128]
+            })
+        );
+        assert_eq!(
+            module.code_items[0],
+            CodeItem {
+                local_groups: vec![],
+                instruction_items: vec![
+                    Instruction::I32Const(0),
+                    Instruction::V128Load(MemoryArgument {
+                        align: 4,
+                        offset: 16,
+                        memory_block_index: 0
+                    }),
+                    Instruction::V128Const(0x0000_0004_0000_0003_0000_0002_0000_0001),
+                    Instruction::I32x4Add,
+                    Instruction::LocalGet(0),
+                    Instruction::I8x16Shuffle([
+                        0, 1, 2, 3, 4, 5, 6, 7, 16, 17, 18, 19, 20, 21, 22, 23
+                    ]),
+                    Instruction::I16x8ExtractLaneS(7),
+                    Instruction::I16x8Splat,
+                    Instruction::I32Const(8),
+                    Instruction::V128Load32Lane(
+                        MemoryArgument {
+                            align: 0,
+                            offset: 0,
+                            memory_block_index: 0
+                        },
+                        3
+                    ),
+                    Instruction::End
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_instruction_atomic() {
+        let binary = get_test_binary_resource("test-instruction-atomic.wasm");
+        let module = parse(&binary).unwrap();
+        assert_eq!(
+            module.memory_blocks,
+            vec![MemoryType {
+                limit: Limit::Range(1, 1),
+                shared: true,
+                memory64: false
+            }]
+        );
+        assert_eq!(
+            module.code_items[0],
+            CodeItem {
+                local_groups: vec![],
+                instruction_items: vec![
+                    Instruction::LocalGet(0),
+                    Instruction::I32Const(1),
+                    Instruction::I32AtomicRmwAdd(MemoryArgument {
+                        align: 2,
+                        offset: 4,
+                        memory_block_index: 0
+                    }),
+                    Instruction::I32Const(8),
+                    Instruction::I64AtomicLoad8U(MemoryArgument {
+                        align: 0,
+                        offset: 0,
+                        memory_block_index: 0
+                    }),
+                    Instruction::Drop,
+                    Instruction::I32Const(0),
+                    Instruction::I32Const(0),
+                    Instruction::I64Const(-1),
+                    Instruction::MemoryAtomicWait32(MemoryArgument {
+                        align: 2,
+                        offset: 0,
+                        memory_block_index: 0
+                    }),
+                    Instruction::I32Add,
+                    Instruction::AtomicFence,
+                    Instruction::End
+                ]
+            }
+        );
+
+        // 原子指令的扩展码不存在
+        assert_eq!(
+            continue_parse_instruction_item(&[0xfe, 0x4f, 0x02, 0x00], 0).map(|item| item.0),
+            Err(ParseError::Unsupported(
+                Unsupported::UnsupportedInstructionExtensionCode(0xfe, 0x4f)
+            ))
+        );
+
+        // atomic.fence 的标记必须为 0
+        assert_eq!(
+            continue_parse_instruction_item(&[0xfe, 0x03, 0x01], 0).map(|item| item.0),
+            Err(ParseError::SyntaxError(
+                SyntaxError::InvalidAtomicFenceFlag(1)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_instruction_tail_call() {
+        let binary = get_test_binary_resource("test-instruction-tail-call.wasm");
+        let module = parse(&binary).unwrap();
+        assert_eq!(
+            module.code_items,
+            vec![
+                CodeItem {
+                    local_groups: vec![],
+                    instruction_items: vec![
+                        Instruction::LocalGet(0),
+                        Instruction::ReturnCall(0),
+                        Instruction::End
+                    ]
+                },
+                CodeItem {
+                    local_groups: vec![],
+                    instruction_items: vec![
+                        Instruction::Block(BlockType::ResultI32, 0),
+                        Instruction::LocalGet(0),
+                        Instruction::I32Const(0),
+                        Instruction::ReturnCallIndirect(0, 0),
+                        Instruction::End,
+                        Instruction::End
+                    ]
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_instruction_exception() {
+        let binary = get_test_binary_resource("test-instruction-exception.wasm");
+        let module = parse(&binary).unwrap();
+
+        assert_eq!(
+            module.import_items[0].import_descriptor,
+            ImportDescriptor::TagType(TagType { type_index: 0 })
+        );
+        assert_eq!(
+            module.tag_items,
+            vec![TagType { type_index: 1 }, TagType { type_index: 2 }]
+        );
+        assert_eq!(
+            module.export_items,
+            vec![
+                ExportItem {
+                    name: "pair".to_string(),
+                    export_descriptor: ExportDescriptor::TagIndex(2)
+                },
+                ExportItem {
+                    name: "error".to_string(),
+                    export_descriptor: ExportDescriptor::TagIndex(0)
+                }
+            ]
+        );
+
+        assert_eq!(
+            module.code_items,
+            vec![
+                CodeItem {
+                    local_groups: vec![],
+                    instruction_items: vec![
+                        Instruction::Block(BlockType::ResultI32, 0),
+                        Instruction::TryTable(
+                            BlockType::ResultI32,
+                            vec![CatchClause::Catch(0, 0)],
+                            1
+                        ),
+                        Instruction::LocalGet(0),
+                        Instruction::Throw(0),
+                        Instruction::End,
+                        Instruction::End,
+                        Instruction::End
+                    ]
+                },
+                CodeItem {
+                    local_groups: vec![],
+                    instruction_items: vec![
+                        Instruction::Block(BlockType::ResultExnRef, 0),
+                        Instruction::TryTable(
+                            BlockType::ResultEmpty,
+                            vec![CatchClause::CatchAllRef(0)],
+                            1
+                        ),
+                        Instruction::Throw(1),
+                        Instruction::End,
+                        Instruction::RefNull(RefType::ExnRef),
+                        Instruction::End,
+                        Instruction::End
+                    ]
+                },
+                CodeItem {
+                    local_groups: vec![],
+                    instruction_items: vec![
+                        Instruction::LocalGet(0),
+                        Instruction::ThrowRef,
+                        Instruction::End
+                    ]
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_instruction_multi_memory() {
+        let binary = get_test_binary_resource("test-instruction-multi-memory.wasm");
+        let module = parse(&binary).unwrap();
+
+        assert_eq!(
+            module.memory_blocks,
+            vec![MemoryType {
+                limit: Limit::Range(1, 2),
+                shared: false,
+                memory64: false
+            }]
+        );
+        assert_eq!(module.tables.len(), 2);
+
+        assert_eq!(
+            module.code_items[0].instruction_items,
+            vec![
+                Instruction::LocalGet(0),
+                Instruction::I32Load(MemoryArgument {
+                    align: 2,
+                    offset: 4,
+                    memory_block_index: 1
+                }),
+                Instruction::LocalGet(0),
+                Instruction::I32Const(1),
+                Instruction::I32Store(MemoryArgument {
+                    align: 2,
+                    offset: 0,
+                    memory_block_index: 0
+                }),
+                Instruction::LocalGet(0),
+                Instruction::I64Load8U(MemoryArgument {
+                    align: 0,
+                    offset: 8,
+                    memory_block_index: 1
+                }),
+                Instruction::Drop,
+                Instruction::End
+            ]
+        );
+
+        assert_eq!(
+            module.code_items[1].instruction_items[..4],
+            [
+                Instruction::MemorySize(1),
+                Instruction::MemoryGrow(1),
+                Instruction::Drop,
+                Instruction::I32Const(0)
+            ]
+        );
+        assert_eq!(
+            module.code_items[1].instruction_items[6..9],
+            [
+                Instruction::MemoryCopy(0, 1),
+                Instruction::LocalGet(0),
+                Instruction::V128Load8Lane(
+                    MemoryArgument {
+                        align: 0,
+                        offset: 1,
+                        memory_block_index: 1
+                    },
+                    3
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_instruction_memory64() {
+        let binary = get_test_binary_resource("test-instruction-memory64.wasm");
+        let module = parse(&binary).unwrap();
+
+        assert_eq!(
+            module.import_items[0].import_descriptor,
+            ImportDescriptor::MemoryType(MemoryType {
+                limit: Limit::AtLeast(1),
+                shared: false,
+                memory64: true
+            })
+        );
+        assert_eq!(
+            module.memory_blocks,
+            vec![
+                MemoryType {
+                    limit: Limit::Range(1, 0x1_0000_0000),
+                    shared: false,
+                    memory64: true
+                },
+                MemoryType {
+                    limit: Limit::Range(1, 2),
+                    shared: false,
+                    memory64: false
+                }
+            ]
+        );
+
+        assert_eq!(
+            module.code_items[0].instruction_items,
+            vec![
+                Instruction::LocalGet(0),
+                Instruction::I64Const(1),
+                Instruction::I64Store(MemoryArgument {
+                    align: 3,
+                    offset: 0x1_0000_0000,
+                    memory_block_index: 1
+                }),
+                Instruction::LocalGet(0),
+                Instruction::I64Load(MemoryArgument {
+                    align: 3,
+                    offset: 8,
+                    memory_block_index: 1
+                }),
+                Instruction::End
+            ]
+        );
+
+        assert_eq!(
+            module.data_items[0].mode,
+            DataMode::Active {
+                memory_block_index: 0,
+                offset_instruction_items: vec![Instruction::I64Const(0x10), Instruction::End]
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_extended_const() {
+        let binary = get_test_binary_resource("test-extended-const.wasm");
+        let module = parse(&binary).unwrap();
+
+        assert_eq!(
+            module.global_items[0].initialize_instruction_items,
+            vec![
+                Instruction::I64Const(6),
+                Instruction::I64Const(10),
+                Instruction::I64Const(3),
+                Instruction::I64Sub,
+                Instruction::I64Mul,
+                Instruction::End
+            ]
+        );
+
+        assert_eq!(
+            module.data_items[0].mode,
+            DataMode::Active {
+                memory_block_index: 0,
+                offset_instruction_items: vec![
+                    Instruction::GlobalGet(0),
+                    Instruction::I32Const(8),
+                    Instruction::I32Add,
+                    Instruction::End
+                ]
+            }
+        );
+
+        assert_eq!(
+            module.element_items[0].mode,
+            ElementMode::Active {
+                table_index: 0,
+                offset_instruction_items: vec![
+                    Instruction::I32Const(1),
+                    Instruction::I32Const(1),
+                    Instruction::I32Mul,
+                    Instruction::End
+                ]
+            }
+        );
+    }
 }
@@ -26,6 +26,7 @@ pub const SECTION_ELEMENT_ID: u8 = 9;
 pub const SECTION_CODE_ID: u8 = 10;
 pub const SECTION_DATA_ID: u8 = 11;
 pub const SECTION_DATA_COUNT_ID: u8 = 12;
+pub const SECTION_TAG_ID: u8 = 13;
 
 /// 在 `函数类型段` 里的 `类型项` 的 tag 值目前只能是 `0x60`
 pub const FUNCTION_TYPE_TAG: u8 = 0x60;
@@ -35,15 +36,29 @@ pub const VALUE_TYPE_TAG_I32: u8 = 0x7f; // i32
 pub const VALUE_TYPE_TAG_I64: u8 = 0x7E; // i64
 pub const VALUE_TYPE_TAG_F32: u8 = 0x7D; // f32
 pub const VALUE_TYPE_TAG_F64: u8 = 0x7C; // f64
+pub const VALUE_TYPE_TAG_V128: u8 = 0x7B; // v128
+pub const VALUE_TYPE_TAG_FUNC_REF: u8 = 0x70; // funcref
+pub const VALUE_TYPE_TAG_EXTERN_REF: u8 = 0x6F; // externref
+pub const VALUE_TYPE_TAG_EXN_REF: u8 = 0x69; // exnref
 
 /// 导入项描述 tag
 pub const IMPORT_TAG_FUNCTION: u8 = 0;
 pub const IMPORT_TAG_TABLE: u8 = 1;
 pub const IMPORT_TAG_MEMORY: u8 = 2;
 pub const IMPORT_TAG_GLOBAL: u8 = 3;
+pub const IMPORT_TAG_TAG: u8 = 4;
 
-/// 表项的 tag，目前只支持 func_ref
-pub const TABLE_TYPE_TAG_FUNC_REF: u8 = 0x70;
+/// 元素项（flags 为 1、2、3 时）的元素种类 tag，目前只有 0x00，表示 funcref
+pub const ELEMENT_KIND_FUNC_REF: u8 = 0x00;
+
+/// 异常标签的属性，目前只有 0，表示异常
+pub const TAG_ATTRIBUTE_EXCEPTION: u8 = 0;
+
+/// `try_table` 指令的捕获子句的种类
+pub const CATCH_KIND_CATCH: u8 = 0x00;
+pub const CATCH_KIND_CATCH_REF: u8 = 0x01;
+pub const CATCH_KIND_CATCH_ALL: u8 = 0x02;
+pub const CATCH_KIND_CATCH_ALL_REF: u8 = 0x03;
 
 /// 全局变量的可变性 tag，0 == 常量
 pub const GLOBAL_VARIABLE_TAG_IMMUTABLE: u8 = 0;
@@ -58,12 +73,17 @@ pub const BLOCK_TYPE_I32: i32 = -1; // 返回 i32
 pub const BLOCK_TYPE_I64: i32 = -2; // 返回 i64
 pub const BLOCK_TYPE_F32: i32 = -3; // 返回 f32
 pub const BLOCK_TYPE_F64: i32 = -4; // 返回 f64
+pub const BLOCK_TYPE_V128: i32 = -5; // 返回 v128
+pub const BLOCK_TYPE_FUNC_REF: i32 = -16; // 返回 funcref
+pub const BLOCK_TYPE_EXTERN_REF: i32 = -17; // 返回 externref
+pub const BLOCK_TYPE_EXN_REF: i32 = -23; // 返回 exnref
 pub const BLOCK_TYPE_EMPTY: i32 = -64; // 无返回
 
 pub const EXPORT_TAG_FUNCTION: u8 = 0;
 pub const EXPORT_TAG_TABLE: u8 = 1;
 pub const EXPORT_TAG_MEM: u8 = 2;
 pub const EXPORT_TAG_GLOBAL: u8 = 3;
+pub const EXPORT_TAG_TAG: u8 = 4;
 
 pub const NAME_COLLECTION_KIND_FUNCTION_NAMES: u8 = 0x01;
 pub const NAME_COLLECTION_KIND_FUNCTION_LOCAL_VARIABLE_NAMES: u8 = 0x02;
@@ -74,3 +94,10 @@ pub const NAME_COLLECTION_KIND_MEMORY_BLOCK_NAMES: u8 = 0x06;
 pub const NAME_COLLECTION_KIND_GLOBAL_VARIABLE_NAMES: u8 = 0x07;
 pub const NAME_COLLECTION_KIND_ELEMENT_NAMES: u8 = 0x08;
 pub const NAME_COLLECTION_KIND_DATA_NAMES: u8 = 0x09;
+pub const NAME_COLLECTION_KIND_TAG_NAMES: u8 = 0x0b;
+
+pub const DYLINK_SUBSECTION_MEM_INFO: u8 = 1;
+pub const DYLINK_SUBSECTION_NEEDED: u8 = 2;
+pub const DYLINK_SUBSECTION_EXPORT_INFO: u8 = 3;
+pub const DYLINK_SUBSECTION_IMPORT_INFO: u8 = 4;
+pub const DYLINK_SUBSECTION_RUNTIME_PATH: u8 = 5;
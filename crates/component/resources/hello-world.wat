@@ -0,0 +1,98 @@
+;; WASI 0.2 命令组件，向标准输出打印 "Hello, world!"
+(component
+  (type $io-error-type (instance
+    (export "error" (type (sub resource)))
+  ))
+  (import "wasi:io/error@0.2.0" (instance $io-error (type $io-error-type)))
+  (alias export $io-error "error" (type $error))
+
+  (type $io-streams-type (instance
+    (alias outer 1 $error (type $error-outer))
+    (export "error" (type $error-local (eq $error-outer)))
+    (type $stream-error (variant
+      (case "last-operation-failed" (own $error-local))
+      (case "closed")
+    ))
+    (export "stream-error" (type $stream-error-local (eq $stream-error)))
+    (export "output-stream" (type $output-stream (sub resource)))
+    (type $write-result (result (error $stream-error-local)))
+    (type $write-type (func
+      (param "self" (borrow $output-stream))
+      (param "contents" (list u8))
+      (result $write-result)
+    ))
+    (export "[method]output-stream.blocking-write-and-flush" (func (type $write-type)))
+  ))
+  (import "wasi:io/streams@0.2.0" (instance $io-streams (type $io-streams-type)))
+  (alias export $io-streams "output-stream" (type $output-stream))
+
+  (type $cli-stdout-type (instance
+    (alias outer 1 $output-stream (type $output-stream-outer))
+    (export "output-stream" (type $output-stream-local (eq $output-stream-outer)))
+    (type $get-stdout-type (func (result (own $output-stream-local))))
+    (export "get-stdout" (func (type $get-stdout-type)))
+  ))
+  (import "wasi:cli/stdout@0.2.0" (instance $cli-stdout (type $cli-stdout-type)))
+
+  ;; 提供内存块以及内存分配函数的核心模块
+  (core module
+    (memory (export "memory") 1)
+    (global $heap (mut i32) (i32.const 1024))
+    (func (export "cabi_realloc")
+      (param $original_ptr i32) (param $original_size i32)
+      (param $align i32) (param $new_size i32) (result i32)
+      (local $ptr i32)
+      ;; ptr = (heap + align - 1) & ~(align - 1)
+      (local.set $ptr
+        (i32.and
+          (i32.add (global.get $heap) (i32.sub (local.get $align) (i32.const 1)))
+          (i32.xor (i32.sub (local.get $align) (i32.const 1)) (i32.const -1))))
+      (global.set $heap (i32.add (local.get $ptr) (local.get $new_size)))
+      (local.get $ptr)
+    )
+  )
+  (core instance $libc (instantiate 0))
+  (alias core export $libc "memory" (core memory $memory))
+  (alias core export $libc "cabi_realloc" (core func $realloc))
+
+  (alias export $io-streams "[method]output-stream.blocking-write-and-flush"
+    (func $blocking-write-and-flush))
+  (core func $blocking-write-and-flush-lowered
+    (canon lower (func $blocking-write-and-flush) (memory $memory) (realloc $realloc)))
+  (core func $drop-output-stream (canon resource.drop $output-stream))
+  (alias export $cli-stdout "get-stdout" (func $get-stdout))
+  (core func $get-stdout-lowered (canon lower (func $get-stdout)))
+
+  (core module
+    (import "libc" "memory" (memory 1))
+    (import "streams" "blocking-write-and-flush" (func $write (param i32 i32 i32 i32)))
+    (import "streams" "drop-output-stream" (func $drop (param i32)))
+    (import "stdout" "get-stdout" (func $get_stdout (result i32)))
+    (data (i32.const 16) "Hello, world!\n")
+    (func (export "run") (result i32)
+      (local $stream i32)
+      (local.set $stream (call $get_stdout))
+      ;; 返回值 `result<_, stream-error>` 存放在地址 0
+      (call $write (local.get $stream) (i32.const 16) (i32.const 14) (i32.const 0))
+      (call $drop (local.get $stream))
+      ;; 写入失败时返回 1，即 `result::error`
+      (i32.load8_u (i32.const 0))
+    )
+  )
+  (core instance $main (instantiate 1
+    (with "libc" (instance $libc))
+    (with "streams" (instance
+      (export "blocking-write-and-flush" (func $blocking-write-and-flush-lowered))
+      (export "drop-output-stream" (func $drop-output-stream))
+    ))
+    (with "stdout" (instance
+      (export "get-stdout" (func $get-stdout-lowered))
+    ))
+  ))
+
+  (type $run-type (func (result (result))))
+  (alias core export $main "run" (core func $run-core))
+  (func $run (type $run-type) (canon lift (core func $run-core)))
+  (instance $run-instance (export "run" (func $run)))
+  (export "wasi:cli/run@0.2.0" (instance $run-instance))
+)
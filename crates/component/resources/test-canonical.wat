@@ -0,0 +1,85 @@
+;; 测试规范 ABI 的提升以及降低
+(component
+  (core module
+    (memory (export "memory") 1)
+    (global $heap (mut i32) (i32.const 1024))
+    (data (i32.const 32) "division by zero")
+
+    (func (export "cabi_realloc")
+      (param $original_ptr i32) (param $original_size i32)
+      (param $align i32) (param $new_size i32) (result i32)
+      (local $ptr i32)
+      (local.set $ptr
+        (i32.and
+          (i32.add (global.get $heap) (i32.sub (local.get $align) (i32.const 1)))
+          (i32.xor (i32.sub (local.get $align) (i32.const 1)) (i32.const -1))))
+      (global.set $heap (i32.add (local.get $ptr) (local.get $new_size)))
+      (local.get $ptr)
+    )
+
+    ;; 返回值（字符串的地址以及长度）存放在地址 0
+    (func (export "echo") (param $ptr i32) (param $len i32) (result i32)
+      (i32.store (i32.const 0) (local.get $ptr))
+      (i32.store (i32.const 4) (local.get $len))
+      (i32.const 0)
+    )
+
+    (func (export "add") (param $x i32) (param $y i32) (result i32)
+      (i32.add (local.get $x) (local.get $y))
+    )
+
+    ;; 返回值 `result<u32, string>` 存放在地址 0
+    (func (export "checked-div") (param $a i32) (param $b i32) (result i32)
+      (if (i32.eqz (local.get $b))
+        (then
+          (i32.store8 (i32.const 0) (i32.const 1))
+          (i32.store (i32.const 4) (i32.const 32))
+          (i32.store (i32.const 8) (i32.const 16)))
+        (else
+          (i32.store8 (i32.const 0) (i32.const 0))
+          (i32.store (i32.const 4) (i32.div_u (local.get $a) (local.get $b)))))
+      (i32.const 0)
+    )
+
+    (func (export "sum") (param $ptr i32) (param $len i32) (result i32)
+      (local $total i32)
+      (block $exit
+        (loop $next
+          (br_if $exit (i32.eqz (local.get $len)))
+          (local.set $total (i32.add (local.get $total) (i32.load (local.get $ptr))))
+          (local.set $ptr (i32.add (local.get $ptr) (i32.const 4)))
+          (local.set $len (i32.sub (local.get $len) (i32.const 1)))
+          (br $next)))
+      (local.get $total)
+    )
+  )
+  (core instance $main (instantiate 0))
+  (alias core export $main "memory" (core memory $memory))
+  (alias core export $main "cabi_realloc" (core func $realloc))
+
+  (type $echo-type (func (param "s" string) (result string)))
+  (alias core export $main "echo" (core func $echo-core))
+  (func $echo (type $echo-type)
+    (canon lift (core func $echo-core) (memory $memory) (realloc $realloc) string-encoding=utf8))
+  (export "echo" (func $echo))
+
+  (type $point (record (field "x" u32) (field "y" u32)))
+  (export $point-export "point" (type $point))
+  (type $add-type (func (param "p" $point-export) (result u32)))
+  (alias core export $main "add" (core func $add-core))
+  (func $add (type $add-type) (canon lift (core func $add-core)))
+  (export "add" (func $add))
+
+  (type $div-result (result u32 (error string)))
+  (type $checked-div-type (func (param "a" u32) (param "b" u32) (result $div-result)))
+  (alias core export $main "checked-div" (core func $checked-div-core))
+  (func $checked-div (type $checked-div-type)
+    (canon lift (core func $checked-div-core) (memory $memory) (realloc $realloc)))
+  (export "checked-div" (func $checked-div))
+
+  (type $sum-type (func (param "items" (list u32)) (result u32)))
+  (alias core export $main "sum" (core func $sum-core))
+  (func $sum (type $sum-type)
+    (canon lift (core func $sum-core) (memory $memory) (realloc $realloc)))
+  (export "sum" (func $sum))
+)
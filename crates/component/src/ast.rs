@@ -0,0 +1,293 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 组件（component）的 AST
+//!
+//! 组件二进制格式的各个段可以交错出现，而且可以重复出现，
+//! 各个索引空间（比如函数索引、类型索引、实例索引等）的编号依赖于
+//! 段里各个项目出现的先后次序，所以这里按照二进制里的原始次序保存各个定义项，
+//! 而不是像核心模块（core module）那样按段分类保存。
+//!
+//! https://github.com/WebAssembly/component-model/blob/main/design/mvp/Binary.md
+
+use anvm_ast::{ast, types};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Component {
+    pub definitions: Vec<Definition>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Definition {
+    CoreModule(Box<ast::Module>),
+    CoreInstance(CoreInstance),
+
+    /// 核心类型（core type）
+    ///
+    /// 目前仅用于占用核心类型的索引，其内容被忽略。
+    CoreType,
+
+    Component(Component),
+    Instance(Instance),
+    Alias(Alias),
+    Type(TypeDefinition),
+    Canon(Canon),
+    Start(Start),
+    Import(Import),
+    Export(Export),
+}
+
+/// 核心项目的种类
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CoreSort {
+    Function, // 0x00
+    Table,    // 0x01
+    Memory,   // 0x02
+    Global,   // 0x03
+    Tag,      // 0x04
+    Type,     // 0x10
+    Module,   // 0x11
+    Instance, // 0x12
+}
+
+/// 组件项目的种类
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Sort {
+    Core(CoreSort), // 0x00 + core_sort
+    Function,       // 0x01
+    Value,          // 0x02
+    Type,           // 0x03
+    Component,      // 0x04
+    Instance,       // 0x05
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum CoreInstance {
+    /// 实例化一个核心模块
+    ///
+    /// 实参的名称对应着模块导入项的模块名称（module name）
+    Instantiate {
+        module_index: u32,
+        arguments: Vec<CoreInstantiationArgument>,
+    },
+
+    /// 由已存在的核心项目组合而成的实例
+    FromExports(Vec<CoreInlineExport>),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CoreInstantiationArgument {
+    pub name: String,
+    pub instance_index: u32,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CoreInlineExport {
+    pub name: String,
+    pub sort: CoreSort,
+    pub index: u32,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Instance {
+    /// 实例化一个组件
+    Instantiate {
+        component_index: u32,
+        arguments: Vec<InstantiationArgument>,
+    },
+
+    /// 由已存在的组件项目组合而成的实例
+    FromExports(Vec<InlineExport>),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct InstantiationArgument {
+    pub name: String,
+    pub sort: Sort,
+    pub index: u32,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct InlineExport {
+    pub name: String,
+    pub sort: Sort,
+    pub index: u32,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Alias {
+    /// 组件实例的导出项
+    InstanceExport {
+        sort: Sort,
+        instance_index: u32,
+        name: String,
+    },
+
+    /// 核心实例的导出项
+    CoreInstanceExport {
+        sort: CoreSort,
+        instance_index: u32,
+        name: String,
+    },
+
+    /// 外层组件（或者外层组件类型）的项目
+    ///
+    /// count 为向外的层数，0 表示当前层
+    Outer { sort: Sort, count: u32, index: u32 },
+}
+
+/// 基本数据类型
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PrimitiveType {
+    Bool,   // 0x7f
+    S8,     // 0x7e
+    U8,     // 0x7d
+    S16,    // 0x7c
+    U16,    // 0x7b
+    S32,    // 0x7a
+    U32,    // 0x79
+    S64,    // 0x78
+    U64,    // 0x77
+    F32,    // 0x76
+    F64,    // 0x75
+    Char,   // 0x74
+    String, // 0x73
+}
+
+/// 组件函数的参数、记录的字段等所使用的数据类型
+///
+/// 要么是基本数据类型，要么是类型索引
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ValueType {
+    Primitive(PrimitiveType),
+    TypeIndex(u32),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Case {
+    pub name: String,
+    pub value_type: Option<ValueType>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum DefinedType {
+    Primitive(PrimitiveType),
+    Record(Vec<(String, ValueType)>),
+    Variant(Vec<Case>),
+    List(ValueType),
+    Tuple(Vec<ValueType>),
+    Flags(Vec<String>),
+    Enum(Vec<String>),
+    Option(ValueType),
+    Result {
+        ok: Option<ValueType>,
+        error: Option<ValueType>,
+    },
+    Own(/* type_index */ u32),
+    Borrow(/* type_index */ u32),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct FunctionType {
+    pub params: Vec<(String, ValueType)>,
+    pub result: Option<ValueType>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum TypeDefinition {
+    Defined(DefinedType),
+    Function(FunctionType),
+    Component(Vec<TypeDeclaration>),
+    Instance(Vec<TypeDeclaration>),
+
+    /// 资源类型
+    ///
+    /// representation 目前只能是 i32
+    Resource {
+        representation: types::ValueType,
+        destructor: Option<u32>,
+    },
+}
+
+/// 组件类型以及实例类型的声明项
+///
+/// 注意只有组件类型才有 `Import` 声明项
+#[derive(Debug, PartialEq, Clone)]
+pub enum TypeDeclaration {
+    CoreType,
+    Type(TypeDefinition),
+    Alias(Alias),
+    Import(Import),
+    Export(Import),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TypeBound {
+    Eq(u32),
+    SubResource,
+}
+
+/// 导入项或者导出项的类型
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TypeRef {
+    Module(u32),
+    Function(u32),
+    Value(ValueType),
+    Type(TypeBound),
+    Instance(u32),
+    Component(u32),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CanonicalOption {
+    Utf8,
+    Utf16,
+    CompactUtf16,
+    Memory(u32),
+    Realloc(u32),
+    PostReturn(u32),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Canon {
+    /// 把核心函数提升（lift）为组件函数
+    Lift {
+        core_function_index: u32,
+        options: Vec<CanonicalOption>,
+        type_index: u32,
+    },
+
+    /// 把组件函数降低（lower）为核心函数
+    Lower {
+        function_index: u32,
+        options: Vec<CanonicalOption>,
+    },
+
+    ResourceNew(/* type_index */ u32),
+    ResourceDrop(/* type_index */ u32),
+    ResourceRep(/* type_index */ u32),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Start {
+    pub function_index: u32,
+    pub arguments: Vec<u32>,
+    pub result_count: u32,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Import {
+    pub name: String,
+    pub type_ref: TypeRef,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Export {
+    pub name: String,
+    pub sort: Sort,
+    pub index: u32,
+    pub type_ref: Option<TypeRef>,
+}
@@ -0,0 +1,1594 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 规范 ABI（canonical ABI）
+//!
+//! 规范 ABI 定义了组件数值（component value）跟核心数值以及线性内存之间的转换规则：
+//!
+//! - 提升（lift）：把核心数值（以及核心数值所指向的线性内存里的数据）转换为组件数值；
+//! - 降低（lower）：把组件数值转换为核心数值，字符串以及列表等数据会被写入线性内存，
+//!   所需的内存空间通过模块导出的 `realloc` 函数来分配。
+//!
+//! 目前字符串仅支持 UTF-8 编码。
+//!
+//! https://github.com/WebAssembly/component-model/blob/main/design/mvp/CanonicalABI.md
+
+use anvm_ast::{
+    ast::FunctionType,
+    types::{Value, ValueType},
+};
+
+use crate::{
+    error::ComponentError,
+    types::{ComponentValue, InterfaceFunctionType, InterfaceType, ResourceTypeId},
+};
+
+/// 直接使用核心数值传递的参数（展平之后）的最大数量，超过这个数量的参数
+/// 会被存放在线性内存里，然后只传递一个指针。
+pub const MAX_FLAT_PARAMS: usize = 16;
+
+/// 直接使用核心数值传递的返回值（展平之后）的最大数量
+pub const MAX_FLAT_RESULTS: usize = 1;
+
+/// 规范 ABI 访问线性内存的接口
+///
+/// 地址以及长度超出内存范围时应该返回 `ComponentError::MemoryAccessOutOfRange`。
+pub trait LinearMemory {
+    fn read(&mut self, address: u32, length: u32) -> Result<Vec<u8>, ComponentError>;
+    fn write(&mut self, address: u32, data: &[u8]) -> Result<(), ComponentError>;
+
+    /// 调用模块导出的 `realloc(original_ptr, original_size, alignment, new_size) -> ptr` 函数
+    fn realloc(
+        &mut self,
+        original_ptr: u32,
+        original_size: u32,
+        alignment: u32,
+        new_size: u32,
+    ) -> Result<u32, ComponentError>;
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct HandleEntry {
+    pub resource_type_id: ResourceTypeId,
+    pub representation: u32,
+
+    /// 是否拥有资源，即 own 句柄，否则为 borrow 句柄
+    pub own: bool,
+}
+
+/// 资源句柄表
+///
+/// 组件里的核心代码使用句柄（i32 整数）来表示资源，句柄为句柄表的索引，
+/// 其中 0 号句柄保留不用。
+///
+/// 注：规范要求 borrow 句柄在函数调用结束时自动释放，目前尚未实现，
+/// borrow 句柄需要由核心代码主动调用 `resource.drop` 来释放。
+#[derive(Debug, PartialEq, Clone)]
+pub struct HandleTable {
+    entries: Vec<Option<HandleEntry>>,
+}
+
+impl HandleTable {
+    pub fn new() -> Self {
+        Self {
+            entries: vec![None],
+        }
+    }
+
+    /// 添加句柄，优先复用已经被释放的句柄
+    pub fn insert(&mut self, entry: HandleEntry) -> u32 {
+        let option_free_index = self
+            .entries
+            .iter()
+            .skip(1)
+            .position(|item| item.is_none())
+            .map(|index| index + 1);
+
+        match option_free_index {
+            Some(index) => {
+                self.entries[index] = Some(entry);
+                index as u32
+            }
+            None => {
+                self.entries.push(Some(entry));
+                (self.entries.len() - 1) as u32
+            }
+        }
+    }
+
+    pub fn get(&self, handle: u32) -> Result<&HandleEntry, ComponentError> {
+        match self.entries.get(handle as usize) {
+            Some(Some(entry)) => Ok(entry),
+            _ => Err(ComponentError::InvalidHandle(handle)),
+        }
+    }
+
+    pub fn remove(&mut self, handle: u32) -> Result<HandleEntry, ComponentError> {
+        match self.entries.get_mut(handle as usize) {
+            Some(item @ Some(_)) => Ok(item.take().unwrap()),
+            _ => Err(ComponentError::InvalidHandle(handle)),
+        }
+    }
+}
+
+impl Default for HandleTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 提升和降低数值时所需的上下文
+pub struct CallContext<'a> {
+    pub memory: &'a mut dyn LinearMemory,
+    pub handles: &'a mut HandleTable,
+}
+
+impl<'a> CallContext<'a> {
+    pub fn new(memory: &'a mut dyn LinearMemory, handles: &'a mut HandleTable) -> Self {
+        Self { memory, handles }
+    }
+}
+
+// 数据的对齐以及大小
+
+pub fn alignment(interface_type: &InterfaceType) -> u32 {
+    match interface_type {
+        InterfaceType::Bool | InterfaceType::S8 | InterfaceType::U8 => 1,
+        InterfaceType::S16 | InterfaceType::U16 => 2,
+        InterfaceType::S32 | InterfaceType::U32 | InterfaceType::F32 | InterfaceType::Char => 4,
+        InterfaceType::S64 | InterfaceType::U64 | InterfaceType::F64 => 8,
+        InterfaceType::String | InterfaceType::List(_) => 4,
+        InterfaceType::Record(_) | InterfaceType::Tuple(_) => {
+            get_record_field_types(interface_type)
+                .iter()
+                .map(|field_type| alignment(field_type))
+                .max()
+                .unwrap_or(1)
+        }
+        InterfaceType::Variant(_)
+        | InterfaceType::Enum(_)
+        | InterfaceType::Option(_)
+        | InterfaceType::Result { .. } => {
+            let case_types = get_variant_case_types(interface_type);
+            let discriminant_size = get_discriminant_size(case_types.len());
+            discriminant_size.max(get_max_case_alignment(&case_types))
+        }
+        InterfaceType::Flags(names) => match get_flags_size(names.len()) {
+            0 | 1 => 1,
+            2 => 2,
+            _ => 4,
+        },
+        InterfaceType::Own(_) | InterfaceType::Borrow(_) => 4,
+    }
+}
+
+pub fn size(interface_type: &InterfaceType) -> u32 {
+    match interface_type {
+        InterfaceType::Bool | InterfaceType::S8 | InterfaceType::U8 => 1,
+        InterfaceType::S16 | InterfaceType::U16 => 2,
+        InterfaceType::S32 | InterfaceType::U32 | InterfaceType::F32 | InterfaceType::Char => 4,
+        InterfaceType::S64 | InterfaceType::U64 | InterfaceType::F64 => 8,
+        InterfaceType::String | InterfaceType::List(_) => 8,
+        InterfaceType::Record(_) | InterfaceType::Tuple(_) => {
+            let mut record_size = 0;
+            for field_type in get_record_field_types(interface_type) {
+                record_size = align_to(record_size, alignment(field_type));
+                record_size += size(field_type);
+            }
+            align_to(record_size, alignment(interface_type))
+        }
+        InterfaceType::Variant(_)
+        | InterfaceType::Enum(_)
+        | InterfaceType::Option(_)
+        | InterfaceType::Result { .. } => {
+            let case_types = get_variant_case_types(interface_type);
+            let mut variant_size = get_discriminant_size(case_types.len());
+            variant_size = align_to(variant_size, get_max_case_alignment(&case_types));
+            variant_size += case_types
+                .iter()
+                .map(|case_type| case_type.map_or(0, size))
+                .max()
+                .unwrap_or(0);
+            align_to(variant_size, alignment(interface_type))
+        }
+        InterfaceType::Flags(names) => get_flags_size(names.len()),
+        InterfaceType::Own(_) | InterfaceType::Borrow(_) => 4,
+    }
+}
+
+fn align_to(offset: u32, alignment: u32) -> u32 {
+    offset.div_ceil(alignment) * alignment
+}
+
+fn get_discriminant_size(case_count: usize) -> u32 {
+    if case_count <= 0x100 {
+        1
+    } else if case_count <= 0x10000 {
+        2
+    } else {
+        4
+    }
+}
+
+fn get_max_case_alignment(case_types: &[Option<&InterfaceType>]) -> u32 {
+    case_types
+        .iter()
+        .map(|case_type| case_type.map_or(1, alignment))
+        .max()
+        .unwrap_or(1)
+}
+
+fn get_flags_size(flag_count: usize) -> u32 {
+    if flag_count == 0 {
+        0
+    } else if flag_count <= 8 {
+        1
+    } else if flag_count <= 16 {
+        2
+    } else {
+        4 * (flag_count as u32).div_ceil(32)
+    }
+}
+
+/// 记录（record）以及元组（tuple）的各个字段的类型
+fn get_record_field_types(interface_type: &InterfaceType) -> Vec<&InterfaceType> {
+    match interface_type {
+        InterfaceType::Record(fields) => fields.iter().map(|(_, field_type)| field_type).collect(),
+        InterfaceType::Tuple(field_types) => field_types.iter().collect(),
+        _ => unreachable!(),
+    }
+}
+
+/// 变体（variant）以及枚举（enum）、可选值（option）、结果（result）的各个分支的类型
+///
+/// 后三者在规范 ABI 里都被当作变体处理。
+fn get_variant_case_types(interface_type: &InterfaceType) -> Vec<Option<&InterfaceType>> {
+    match interface_type {
+        InterfaceType::Variant(cases) => cases
+            .iter()
+            .map(|(_, case_type)| case_type.as_ref())
+            .collect(),
+        InterfaceType::Enum(names) => vec![None; names.len()],
+        InterfaceType::Option(value_type) => vec![None, Some(value_type)],
+        InterfaceType::Result { ok, error } => vec![ok.as_deref(), error.as_deref()],
+        _ => unreachable!(),
+    }
+}
+
+// 类型的展平（flatten）
+
+pub fn flatten_type(interface_type: &InterfaceType) -> Vec<ValueType> {
+    match interface_type {
+        InterfaceType::Bool
+        | InterfaceType::S8
+        | InterfaceType::U8
+        | InterfaceType::S16
+        | InterfaceType::U16
+        | InterfaceType::S32
+        | InterfaceType::U32
+        | InterfaceType::Char
+        | InterfaceType::Own(_)
+        | InterfaceType::Borrow(_) => vec![ValueType::I32],
+        InterfaceType::S64 | InterfaceType::U64 => vec![ValueType::I64],
+        InterfaceType::F32 => vec![ValueType::F32],
+        InterfaceType::F64 => vec![ValueType::F64],
+        InterfaceType::String | InterfaceType::List(_) => vec![ValueType::I32, ValueType::I32],
+        InterfaceType::Record(_) | InterfaceType::Tuple(_) => {
+            get_record_field_types(interface_type)
+                .iter()
+                .flat_map(|field_type| flatten_type(field_type))
+                .collect()
+        }
+        InterfaceType::Variant(_)
+        | InterfaceType::Enum(_)
+        | InterfaceType::Option(_)
+        | InterfaceType::Result { .. } => {
+            let case_types = get_variant_case_types(interface_type);
+            let mut flat_types = vec![ValueType::I32];
+            flat_types.extend(flatten_variant_payload(&case_types));
+            flat_types
+        }
+        InterfaceType::Flags(names) => vec![ValueType::I32; names.len().div_ceil(32)],
+    }
+}
+
+/// 合并变体各个分支的载荷（payload）的展平类型
+fn flatten_variant_payload(case_types: &[Option<&InterfaceType>]) -> Vec<ValueType> {
+    let mut flat_types: Vec<ValueType> = vec![];
+
+    for case_type in case_types.iter().flatten() {
+        for (index, flat_type) in flatten_type(case_type).into_iter().enumerate() {
+            if index < flat_types.len() {
+                flat_types[index] = join_flat_type(&flat_types[index], &flat_type);
+            } else {
+                flat_types.push(flat_type);
+            }
+        }
+    }
+
+    flat_types
+}
+
+fn join_flat_type(left: &ValueType, right: &ValueType) -> ValueType {
+    match (left, right) {
+        _ if left == right => left.clone(),
+        (ValueType::I32, ValueType::F32) | (ValueType::F32, ValueType::I32) => ValueType::I32,
+        _ => ValueType::I64,
+    }
+}
+
+/// 计算组件函数所对应的核心函数的类型
+///
+/// - is_lower 为 true 时，表示把组件函数降低为核心函数，当返回值需要通过内存传递时，
+///   由调用者（核心代码）分配内存并在最后一个参数里传入指针；
+/// - 否则表示核心函数被提升为组件函数，当返回值需要通过内存传递时，
+///   由被调用者（核心代码）返回指针。
+pub fn flatten_function_type(
+    function_type: &InterfaceFunctionType,
+    is_lower: bool,
+) -> FunctionType {
+    let mut params = function_type
+        .params
+        .iter()
+        .flat_map(|(_, param_type)| flatten_type(param_type))
+        .collect::<Vec<ValueType>>();
+
+    if params.len() > MAX_FLAT_PARAMS {
+        params = vec![ValueType::I32];
+    }
+
+    let mut results = function_type.result.as_ref().map_or(vec![], flatten_type);
+
+    if results.len() > MAX_FLAT_RESULTS {
+        if is_lower {
+            params.push(ValueType::I32);
+            results = vec![];
+        } else {
+            results = vec![ValueType::I32];
+        }
+    }
+
+    FunctionType { params, results }
+}
+
+// 从线性内存加载数值
+
+fn checked_offset(address: u32, offset: u32) -> Result<u32, ComponentError> {
+    address
+        .checked_add(offset)
+        .ok_or(ComponentError::MemoryAccessOutOfRange(address, offset))
+}
+
+fn check_alignment(address: u32, alignment: u32) -> Result<(), ComponentError> {
+    if !address.is_multiple_of(alignment) {
+        Err(ComponentError::UnalignedPointer(address, alignment))
+    } else {
+        Ok(())
+    }
+}
+
+fn load_int(context: &mut CallContext, address: u32, length: u32) -> Result<u64, ComponentError> {
+    let bytes = context.memory.read(address, length)?;
+    let mut buffer = [0u8; 8];
+    buffer[..bytes.len()].copy_from_slice(&bytes);
+    Ok(u64::from_le_bytes(buffer))
+}
+
+fn store_int(
+    context: &mut CallContext,
+    value: u64,
+    address: u32,
+    length: u32,
+) -> Result<(), ComponentError> {
+    let bytes = value.to_le_bytes();
+    context.memory.write(address, &bytes[..length as usize])
+}
+
+fn convert_u32_to_char(value: u32) -> Result<char, ComponentError> {
+    char::from_u32(value).ok_or(ComponentError::InvalidChar(value))
+}
+
+/// 从线性内存的指定位置加载数值
+pub fn load(
+    context: &mut CallContext,
+    interface_type: &InterfaceType,
+    address: u32,
+) -> Result<ComponentValue, ComponentError> {
+    let value = match interface_type {
+        InterfaceType::Bool => ComponentValue::Bool(load_int(context, address, 1)? != 0),
+        InterfaceType::S8 => ComponentValue::S8(load_int(context, address, 1)? as i8),
+        InterfaceType::U8 => ComponentValue::U8(load_int(context, address, 1)? as u8),
+        InterfaceType::S16 => ComponentValue::S16(load_int(context, address, 2)? as i16),
+        InterfaceType::U16 => ComponentValue::U16(load_int(context, address, 2)? as u16),
+        InterfaceType::S32 => ComponentValue::S32(load_int(context, address, 4)? as i32),
+        InterfaceType::U32 => ComponentValue::U32(load_int(context, address, 4)? as u32),
+        InterfaceType::S64 => ComponentValue::S64(load_int(context, address, 8)? as i64),
+        InterfaceType::U64 => ComponentValue::U64(load_int(context, address, 8)?),
+        InterfaceType::F32 => {
+            ComponentValue::F32(f32::from_bits(load_int(context, address, 4)? as u32))
+        }
+        InterfaceType::F64 => ComponentValue::F64(f64::from_bits(load_int(context, address, 8)?)),
+        InterfaceType::Char => {
+            ComponentValue::Char(convert_u32_to_char(load_int(context, address, 4)? as u32)?)
+        }
+        InterfaceType::String => {
+            let data_address = load_int(context, address, 4)? as u32;
+            let length = load_int(context, checked_offset(address, 4)?, 4)? as u32;
+            ComponentValue::String(load_string_from_range(context, data_address, length)?)
+        }
+        InterfaceType::List(element_type) => {
+            let data_address = load_int(context, address, 4)? as u32;
+            let length = load_int(context, checked_offset(address, 4)?, 4)? as u32;
+            ComponentValue::List(load_list_from_range(
+                context,
+                element_type,
+                data_address,
+                length,
+            )?)
+        }
+        InterfaceType::Record(fields) => {
+            let values = load_record(context, interface_type, address)?;
+            ComponentValue::Record(
+                fields
+                    .iter()
+                    .map(|(name, _)| name.to_owned())
+                    .zip(values)
+                    .collect(),
+            )
+        }
+        InterfaceType::Tuple(_) => {
+            ComponentValue::Tuple(load_record(context, interface_type, address)?)
+        }
+        InterfaceType::Variant(_)
+        | InterfaceType::Enum(_)
+        | InterfaceType::Option(_)
+        | InterfaceType::Result { .. } => {
+            let case_types = get_variant_case_types(interface_type);
+            let discriminant_size = get_discriminant_size(case_types.len());
+            let case_index = load_int(context, address, discriminant_size)? as u32;
+
+            let case_type = case_types
+                .get(case_index as usize)
+                .ok_or(ComponentError::InvalidDiscriminant(case_index))?;
+
+            let payload_offset = align_to(discriminant_size, get_max_case_alignment(&case_types));
+
+            let payload = match case_type {
+                Some(t) => Some(load(context, t, checked_offset(address, payload_offset)?)?),
+                None => None,
+            };
+
+            build_variant_value(interface_type, case_index as usize, payload)
+        }
+        InterfaceType::Flags(names) => {
+            let flags_size = get_flags_size(names.len());
+            let mut bits: Vec<u32> = vec![];
+            if flags_size <= 4 {
+                bits.push(load_int(context, address, flags_size)? as u32);
+            } else {
+                for offset in (0..flags_size).step_by(4) {
+                    bits.push(load_int(context, checked_offset(address, offset)?, 4)? as u32);
+                }
+            }
+            build_flags_value(names, &bits)
+        }
+        InterfaceType::Own(resource_type_id) => {
+            let handle = load_int(context, address, 4)? as u32;
+            ComponentValue::Own(lift_own(context, *resource_type_id, handle)?)
+        }
+        InterfaceType::Borrow(resource_type_id) => {
+            let handle = load_int(context, address, 4)? as u32;
+            ComponentValue::Borrow(lift_borrow(context, *resource_type_id, handle)?)
+        }
+    };
+
+    Ok(value)
+}
+
+fn load_record(
+    context: &mut CallContext,
+    interface_type: &InterfaceType,
+    address: u32,
+) -> Result<Vec<ComponentValue>, ComponentError> {
+    let mut values: Vec<ComponentValue> = vec![];
+    let mut offset = 0;
+
+    for field_type in get_record_field_types(interface_type) {
+        offset = align_to(offset, alignment(field_type));
+        values.push(load(context, field_type, checked_offset(address, offset)?)?);
+        offset += size(field_type);
+    }
+
+    Ok(values)
+}
+
+fn load_string_from_range(
+    context: &mut CallContext,
+    address: u32,
+    length: u32,
+) -> Result<String, ComponentError> {
+    let bytes = context.memory.read(address, length)?;
+    String::from_utf8(bytes).map_err(|_| ComponentError::InvalidUtf8String)
+}
+
+fn load_list_from_range(
+    context: &mut CallContext,
+    element_type: &InterfaceType,
+    address: u32,
+    length: u32,
+) -> Result<Vec<ComponentValue>, ComponentError> {
+    check_alignment(address, alignment(element_type))?;
+
+    let element_size = size(element_type);
+    let byte_length = length
+        .checked_mul(element_size)
+        .ok_or(ComponentError::MemoryAccessOutOfRange(address, u32::MAX))?;
+
+    // 先检查整个列表的范围，以免在长度非法时做大量的无用功
+    checked_offset(address, byte_length)?;
+    if byte_length > 0 {
+        context
+            .memory
+            .read(address + byte_length - 1, 1)
+            .map_err(|_| ComponentError::MemoryAccessOutOfRange(address, byte_length))?;
+    }
+
+    (0..length)
+        .map(|index| load(context, element_type, address + index * element_size))
+        .collect()
+}
+
+fn build_variant_value(
+    interface_type: &InterfaceType,
+    case_index: usize,
+    payload: Option<ComponentValue>,
+) -> ComponentValue {
+    let boxed_payload = payload.map(Box::new);
+
+    match interface_type {
+        InterfaceType::Variant(cases) => {
+            ComponentValue::Variant(cases[case_index].0.to_owned(), boxed_payload)
+        }
+        InterfaceType::Enum(names) => ComponentValue::Enum(names[case_index].to_owned()),
+        InterfaceType::Option(_) => ComponentValue::Option(boxed_payload),
+        InterfaceType::Result { .. } => {
+            if case_index == 0 {
+                ComponentValue::Result(Ok(boxed_payload))
+            } else {
+                ComponentValue::Result(Err(boxed_payload))
+            }
+        }
+        _ => unreachable!(),
+    }
+}
+
+/// 获取变体数值的分支索引以及载荷
+fn get_variant_case<'a>(
+    interface_type: &InterfaceType,
+    value: &'a ComponentValue,
+) -> Result<(usize, Option<&'a ComponentValue>), ComponentError> {
+    let (case_index, payload) = match (interface_type, value) {
+        (InterfaceType::Variant(cases), ComponentValue::Variant(case_name, payload)) => {
+            let case_index = cases
+                .iter()
+                .position(|(name, _)| name == case_name)
+                .ok_or_else(|| {
+                    ComponentError::ValueTypeMismatch(format!("unknown case \"{}\"", case_name))
+                })?;
+            (case_index, payload.as_deref())
+        }
+        (InterfaceType::Enum(names), ComponentValue::Enum(case_name)) => {
+            let case_index = names
+                .iter()
+                .position(|name| name == case_name)
+                .ok_or_else(|| {
+                    ComponentError::ValueTypeMismatch(format!("unknown case \"{}\"", case_name))
+                })?;
+            (case_index, None)
+        }
+        (InterfaceType::Option(_), ComponentValue::Option(payload)) => match payload {
+            None => (0, None),
+            Some(v) => (1, Some(v.as_ref())),
+        },
+        (InterfaceType::Result { .. }, ComponentValue::Result(result)) => match result {
+            Ok(payload) => (0, payload.as_deref()),
+            Err(payload) => (1, payload.as_deref()),
+        },
+        _ => return Err(make_value_type_mismatch_error(interface_type, value)),
+    };
+
+    // 核对载荷的有无
+    let case_types = get_variant_case_types(interface_type);
+    if case_types[case_index].is_some() != payload.is_some() {
+        return Err(make_value_type_mismatch_error(interface_type, value));
+    }
+
+    Ok((case_index, payload))
+}
+
+fn build_flags_value(names: &[String], bits: &[u32]) -> ComponentValue {
+    // 超出标志数量的位将被忽略
+    let set_names = names
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| bits[index / 32] & (1 << (index % 32)) != 0)
+        .map(|(_, name)| name.to_owned())
+        .collect::<Vec<String>>();
+    ComponentValue::Flags(set_names)
+}
+
+fn get_flags_bits(names: &[String], set_names: &[String]) -> Result<Vec<u32>, ComponentError> {
+    let mut bits = vec![0u32; names.len().div_ceil(32)];
+    for set_name in set_names {
+        let index = names
+            .iter()
+            .position(|name| name == set_name)
+            .ok_or_else(|| {
+                ComponentError::ValueTypeMismatch(format!("unknown flag \"{}\"", set_name))
+            })?;
+        bits[index / 32] |= 1 << (index % 32);
+    }
+    Ok(bits)
+}
+
+fn lift_own(
+    context: &mut CallContext,
+    resource_type_id: ResourceTypeId,
+    handle: u32,
+) -> Result<u32, ComponentError> {
+    let entry = context.handles.get(handle)?;
+    if entry.resource_type_id != resource_type_id || !entry.own {
+        return Err(ComponentError::InvalidHandle(handle));
+    }
+
+    // 转移资源的所有权
+    let entry = context.handles.remove(handle)?;
+    Ok(entry.representation)
+}
+
+fn lift_borrow(
+    context: &mut CallContext,
+    resource_type_id: ResourceTypeId,
+    handle: u32,
+) -> Result<u32, ComponentError> {
+    let entry = context.handles.get(handle)?;
+    if entry.resource_type_id != resource_type_id {
+        return Err(ComponentError::InvalidHandle(handle));
+    }
+    Ok(entry.representation)
+}
+
+fn lower_handle(
+    context: &mut CallContext,
+    resource_type_id: ResourceTypeId,
+    representation: u32,
+    own: bool,
+) -> u32 {
+    context.handles.insert(HandleEntry {
+        resource_type_id,
+        representation,
+        own,
+    })
+}
+
+fn make_value_type_mismatch_error(
+    interface_type: &InterfaceType,
+    value: &ComponentValue,
+) -> ComponentError {
+    ComponentError::ValueTypeMismatch(format!(
+        "expected type: {:?}, actual value: {:?}",
+        interface_type, value
+    ))
+}
+
+// 把数值存储到线性内存
+
+/// 把数值存储到线性内存的指定位置
+pub fn store(
+    context: &mut CallContext,
+    value: &ComponentValue,
+    interface_type: &InterfaceType,
+    address: u32,
+) -> Result<(), ComponentError> {
+    match (interface_type, value) {
+        (InterfaceType::Bool, ComponentValue::Bool(v)) => store_int(context, *v as u64, address, 1),
+        (InterfaceType::S8, ComponentValue::S8(v)) => store_int(context, *v as u64, address, 1),
+        (InterfaceType::U8, ComponentValue::U8(v)) => store_int(context, *v as u64, address, 1),
+        (InterfaceType::S16, ComponentValue::S16(v)) => store_int(context, *v as u64, address, 2),
+        (InterfaceType::U16, ComponentValue::U16(v)) => store_int(context, *v as u64, address, 2),
+        (InterfaceType::S32, ComponentValue::S32(v)) => store_int(context, *v as u64, address, 4),
+        (InterfaceType::U32, ComponentValue::U32(v)) => store_int(context, *v as u64, address, 4),
+        (InterfaceType::S64, ComponentValue::S64(v)) => store_int(context, *v as u64, address, 8),
+        (InterfaceType::U64, ComponentValue::U64(v)) => store_int(context, *v, address, 8),
+        (InterfaceType::F32, ComponentValue::F32(v)) => {
+            store_int(context, v.to_bits() as u64, address, 4)
+        }
+        (InterfaceType::F64, ComponentValue::F64(v)) => store_int(context, v.to_bits(), address, 8),
+        (InterfaceType::Char, ComponentValue::Char(v)) => store_int(context, *v as u64, address, 4),
+        (InterfaceType::String, ComponentValue::String(s)) => {
+            let (data_address, length) = store_string_into_range(context, s)?;
+            store_int(context, data_address as u64, address, 4)?;
+            store_int(context, length as u64, checked_offset(address, 4)?, 4)
+        }
+        (InterfaceType::List(element_type), ComponentValue::List(values)) => {
+            let (data_address, length) = store_list_into_range(context, values, element_type)?;
+            store_int(context, data_address as u64, address, 4)?;
+            store_int(context, length as u64, checked_offset(address, 4)?, 4)
+        }
+        (InterfaceType::Record(_), ComponentValue::Record(_))
+        | (InterfaceType::Tuple(_), ComponentValue::Tuple(_)) => {
+            let field_values = get_record_field_values(interface_type, value)?;
+            let mut offset = 0;
+
+            for (field_value, field_type) in field_values
+                .into_iter()
+                .zip(get_record_field_types(interface_type))
+            {
+                offset = align_to(offset, alignment(field_type));
+                store(
+                    context,
+                    field_value,
+                    field_type,
+                    checked_offset(address, offset)?,
+                )?;
+                offset += size(field_type);
+            }
+
+            Ok(())
+        }
+        (InterfaceType::Variant(_), _)
+        | (InterfaceType::Enum(_), _)
+        | (InterfaceType::Option(_), _)
+        | (InterfaceType::Result { .. }, _) => {
+            let (case_index, payload) = get_variant_case(interface_type, value)?;
+            let case_types = get_variant_case_types(interface_type);
+            let discriminant_size = get_discriminant_size(case_types.len());
+            store_int(context, case_index as u64, address, discriminant_size)?;
+
+            if let (Some(case_type), Some(payload_value)) = (case_types[case_index], payload) {
+                let payload_offset =
+                    align_to(discriminant_size, get_max_case_alignment(&case_types));
+                store(
+                    context,
+                    payload_value,
+                    case_type,
+                    checked_offset(address, payload_offset)?,
+                )?;
+            }
+
+            Ok(())
+        }
+        (InterfaceType::Flags(names), ComponentValue::Flags(set_names)) => {
+            let bits = get_flags_bits(names, set_names)?;
+            let flags_size = get_flags_size(names.len());
+            if flags_size <= 4 {
+                if flags_size > 0 {
+                    store_int(context, bits[0] as u64, address, flags_size)?;
+                }
+            } else {
+                for (index, item) in bits.iter().enumerate() {
+                    let item_address = checked_offset(address, index as u32 * 4)?;
+                    store_int(context, *item as u64, item_address, 4)?;
+                }
+            }
+            Ok(())
+        }
+        (InterfaceType::Own(resource_type_id), ComponentValue::Own(representation)) => {
+            let handle = lower_handle(context, *resource_type_id, *representation, true);
+            store_int(context, handle as u64, address, 4)
+        }
+        (InterfaceType::Borrow(resource_type_id), ComponentValue::Borrow(representation)) => {
+            let handle = lower_handle(context, *resource_type_id, *representation, false);
+            store_int(context, handle as u64, address, 4)
+        }
+        _ => Err(make_value_type_mismatch_error(interface_type, value)),
+    }
+}
+
+/// 获取记录（record）以及元组（tuple）的各个字段的值，同时核对字段的数量以及名称
+fn get_record_field_values<'a>(
+    interface_type: &InterfaceType,
+    value: &'a ComponentValue,
+) -> Result<Vec<&'a ComponentValue>, ComponentError> {
+    let field_values = match (interface_type, value) {
+        (InterfaceType::Record(fields), ComponentValue::Record(field_values))
+            if fields.len() == field_values.len()
+                && fields
+                    .iter()
+                    .zip(field_values.iter())
+                    .all(|((name, _), (value_name, _))| name == value_name) =>
+        {
+            field_values.iter().map(|(_, v)| v).collect()
+        }
+        (InterfaceType::Tuple(field_types), ComponentValue::Tuple(field_values))
+            if field_types.len() == field_values.len() =>
+        {
+            field_values.iter().collect()
+        }
+        _ => return Err(make_value_type_mismatch_error(interface_type, value)),
+    };
+
+    Ok(field_values)
+}
+
+fn store_string_into_range(
+    context: &mut CallContext,
+    s: &str,
+) -> Result<(u32, u32), ComponentError> {
+    let bytes = s.as_bytes();
+    let length = bytes.len() as u32;
+    let address = context.memory.realloc(0, 0, 1, length)?;
+    context.memory.write(address, bytes)?;
+    Ok((address, length))
+}
+
+fn store_list_into_range(
+    context: &mut CallContext,
+    values: &[ComponentValue],
+    element_type: &InterfaceType,
+) -> Result<(u32, u32), ComponentError> {
+    let element_size = size(element_type);
+    let element_alignment = alignment(element_type);
+    let length = values.len() as u32;
+    let byte_length = length
+        .checked_mul(element_size)
+        .ok_or(ComponentError::MemoryAccessOutOfRange(0, u32::MAX))?;
+
+    let address = context
+        .memory
+        .realloc(0, 0, element_alignment, byte_length)?;
+    check_alignment(address, element_alignment)?;
+
+    for (index, value) in values.iter().enumerate() {
+        let element_address = checked_offset(address, index as u32 * element_size)?;
+        store(context, value, element_type, element_address)?;
+    }
+
+    Ok((address, length))
+}
+
+// 展平数值的提升和降低
+
+/// 展平的核心数值序列
+pub struct FlatValues<'a> {
+    values: &'a [Value],
+    position: usize,
+}
+
+impl<'a> FlatValues<'a> {
+    pub fn new(values: &'a [Value]) -> Self {
+        Self {
+            values,
+            position: 0,
+        }
+    }
+
+    fn next(&mut self, expected_type: &ValueType) -> Result<Value, ComponentError> {
+        match self.values.get(self.position) {
+            Some(value) if &value.get_type() == expected_type => {
+                self.position += 1;
+                Ok(*value)
+            }
+            option_value => Err(ComponentError::ValueTypeMismatch(format!(
+                "expected core value type: {:?}, actual value: {:?}",
+                expected_type, option_value
+            ))),
+        }
+    }
+
+    fn next_i32(&mut self) -> Result<i32, ComponentError> {
+        match self.next(&ValueType::I32)? {
+            Value::I32(v) => Ok(v),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// 把变体的载荷从合并之后的展平类型转换为分支本身的展平类型
+fn coerce_value_down(value: Value, expected_type: &ValueType) -> Value {
+    match (value, expected_type) {
+        (Value::I32(v), ValueType::F32) => Value::F32(f32::from_bits(v as u32)),
+        (Value::I64(v), ValueType::I32) => Value::I32(v as i32),
+        (Value::I64(v), ValueType::F32) => Value::F32(f32::from_bits(v as u32)),
+        (Value::I64(v), ValueType::F64) => Value::F64(f64::from_bits(v as u64)),
+        (v, _) => v,
+    }
+}
+
+/// 把变体的载荷从分支本身的展平类型转换为合并之后的展平类型
+fn coerce_value_up(value: Value, expected_type: &ValueType) -> Value {
+    match (value, expected_type) {
+        (Value::F32(v), ValueType::I32) => Value::I32(v.to_bits() as i32),
+        (Value::I32(v), ValueType::I64) => Value::I64(v as u32 as i64),
+        (Value::F32(v), ValueType::I64) => Value::I64(v.to_bits() as i64),
+        (Value::F64(v), ValueType::I64) => Value::I64(v.to_bits() as i64),
+        (v, _) => v,
+    }
+}
+
+fn get_zero_value(value_type: &ValueType) -> Value {
+    match value_type {
+        ValueType::I64 => Value::I64(0),
+        ValueType::F32 => Value::F32(0.0),
+        ValueType::F64 => Value::F64(0.0),
+        _ => Value::I32(0),
+    }
+}
+
+/// 从展平的核心数值序列提升一个组件数值
+pub fn lift_flat(
+    context: &mut CallContext,
+    interface_type: &InterfaceType,
+    values: &mut FlatValues,
+) -> Result<ComponentValue, ComponentError> {
+    let value = match interface_type {
+        InterfaceType::Bool => ComponentValue::Bool(values.next_i32()? != 0),
+        InterfaceType::S8 => ComponentValue::S8(values.next_i32()? as i8),
+        InterfaceType::U8 => ComponentValue::U8(values.next_i32()? as u8),
+        InterfaceType::S16 => ComponentValue::S16(values.next_i32()? as i16),
+        InterfaceType::U16 => ComponentValue::U16(values.next_i32()? as u16),
+        InterfaceType::S32 => ComponentValue::S32(values.next_i32()?),
+        InterfaceType::U32 => ComponentValue::U32(values.next_i32()? as u32),
+        InterfaceType::S64 | InterfaceType::U64 => match values.next(&ValueType::I64)? {
+            Value::I64(v) if interface_type == &InterfaceType::S64 => ComponentValue::S64(v),
+            Value::I64(v) => ComponentValue::U64(v as u64),
+            _ => unreachable!(),
+        },
+        InterfaceType::F32 => match values.next(&ValueType::F32)? {
+            Value::F32(v) => ComponentValue::F32(v),
+            _ => unreachable!(),
+        },
+        InterfaceType::F64 => match values.next(&ValueType::F64)? {
+            Value::F64(v) => ComponentValue::F64(v),
+            _ => unreachable!(),
+        },
+        InterfaceType::Char => {
+            ComponentValue::Char(convert_u32_to_char(values.next_i32()? as u32)?)
+        }
+        InterfaceType::String => {
+            let address = values.next_i32()? as u32;
+            let length = values.next_i32()? as u32;
+            ComponentValue::String(load_string_from_range(context, address, length)?)
+        }
+        InterfaceType::List(element_type) => {
+            let address = values.next_i32()? as u32;
+            let length = values.next_i32()? as u32;
+            ComponentValue::List(load_list_from_range(
+                context,
+                element_type,
+                address,
+                length,
+            )?)
+        }
+        InterfaceType::Record(fields) => {
+            let mut field_values: Vec<(String, ComponentValue)> = vec![];
+            for (name, field_type) in fields {
+                field_values.push((name.to_owned(), lift_flat(context, field_type, values)?));
+            }
+            ComponentValue::Record(field_values)
+        }
+        InterfaceType::Tuple(field_types) => {
+            let mut field_values: Vec<ComponentValue> = vec![];
+            for field_type in field_types {
+                field_values.push(lift_flat(context, field_type, values)?);
+            }
+            ComponentValue::Tuple(field_values)
+        }
+        InterfaceType::Variant(_)
+        | InterfaceType::Enum(_)
+        | InterfaceType::Option(_)
+        | InterfaceType::Result { .. } => {
+            let case_types = get_variant_case_types(interface_type);
+            let case_index = values.next_i32()? as u32;
+
+            let case_type = case_types
+                .get(case_index as usize)
+                .ok_or(ComponentError::InvalidDiscriminant(case_index))?;
+
+            // 先读取所有载荷数值，然后转换为当前分支的展平类型
+            let payload_types = flatten_variant_payload(&case_types);
+            let mut payload_values: Vec<Value> = vec![];
+            for payload_type in &payload_types {
+                payload_values.push(values.next(payload_type)?);
+            }
+
+            let payload = match case_type {
+                Some(t) => {
+                    let case_values = flatten_type(t)
+                        .iter()
+                        .zip(payload_values)
+                        .map(|(case_value_type, v)| coerce_value_down(v, case_value_type))
+                        .collect::<Vec<Value>>();
+                    Some(lift_flat(context, t, &mut FlatValues::new(&case_values))?)
+                }
+                None => None,
+            };
+
+            build_variant_value(interface_type, case_index as usize, payload)
+        }
+        InterfaceType::Flags(names) => {
+            let mut bits: Vec<u32> = vec![];
+            for _ in 0..names.len().div_ceil(32) {
+                bits.push(values.next_i32()? as u32);
+            }
+            build_flags_value(names, &bits)
+        }
+        InterfaceType::Own(resource_type_id) => {
+            let handle = values.next_i32()? as u32;
+            ComponentValue::Own(lift_own(context, *resource_type_id, handle)?)
+        }
+        InterfaceType::Borrow(resource_type_id) => {
+            let handle = values.next_i32()? as u32;
+            ComponentValue::Borrow(lift_borrow(context, *resource_type_id, handle)?)
+        }
+    };
+
+    Ok(value)
+}
+
+/// 把一个组件数值降低为展平的核心数值序列
+pub fn lower_flat(
+    context: &mut CallContext,
+    value: &ComponentValue,
+    interface_type: &InterfaceType,
+) -> Result<Vec<Value>, ComponentError> {
+    let values = match (interface_type, value) {
+        (InterfaceType::Bool, ComponentValue::Bool(v)) => vec![Value::I32(*v as i32)],
+        (InterfaceType::S8, ComponentValue::S8(v)) => vec![Value::I32(*v as i32)],
+        (InterfaceType::U8, ComponentValue::U8(v)) => vec![Value::I32(*v as i32)],
+        (InterfaceType::S16, ComponentValue::S16(v)) => vec![Value::I32(*v as i32)],
+        (InterfaceType::U16, ComponentValue::U16(v)) => vec![Value::I32(*v as i32)],
+        (InterfaceType::S32, ComponentValue::S32(v)) => vec![Value::I32(*v)],
+        (InterfaceType::U32, ComponentValue::U32(v)) => vec![Value::I32(*v as i32)],
+        (InterfaceType::S64, ComponentValue::S64(v)) => vec![Value::I64(*v)],
+        (InterfaceType::U64, ComponentValue::U64(v)) => vec![Value::I64(*v as i64)],
+        (InterfaceType::F32, ComponentValue::F32(v)) => vec![Value::F32(*v)],
+        (InterfaceType::F64, ComponentValue::F64(v)) => vec![Value::F64(*v)],
+        (InterfaceType::Char, ComponentValue::Char(v)) => vec![Value::I32(*v as i32)],
+        (InterfaceType::String, ComponentValue::String(s)) => {
+            let (address, length) = store_string_into_range(context, s)?;
+            vec![Value::I32(address as i32), Value::I32(length as i32)]
+        }
+        (InterfaceType::List(element_type), ComponentValue::List(element_values)) => {
+            let (address, length) = store_list_into_range(context, element_values, element_type)?;
+            vec![Value::I32(address as i32), Value::I32(length as i32)]
+        }
+        (InterfaceType::Record(_), ComponentValue::Record(_))
+        | (InterfaceType::Tuple(_), ComponentValue::Tuple(_)) => {
+            let field_values = get_record_field_values(interface_type, value)?;
+            let mut values: Vec<Value> = vec![];
+            for (field_value, field_type) in field_values
+                .into_iter()
+                .zip(get_record_field_types(interface_type))
+            {
+                values.extend(lower_flat(context, field_value, field_type)?);
+            }
+            values
+        }
+        (InterfaceType::Variant(_), _)
+        | (InterfaceType::Enum(_), _)
+        | (InterfaceType::Option(_), _)
+        | (InterfaceType::Result { .. }, _) => {
+            let (case_index, payload) = get_variant_case(interface_type, value)?;
+            let case_types = get_variant_case_types(interface_type);
+            let payload_types = flatten_variant_payload(&case_types);
+
+            let case_values = match (case_types[case_index], payload) {
+                (Some(case_type), Some(payload_value)) => {
+                    lower_flat(context, payload_value, case_type)?
+                }
+                _ => vec![],
+            };
+
+            let mut values = vec![Value::I32(case_index as i32)];
+            for (index, payload_type) in payload_types.iter().enumerate() {
+                let payload_value = match case_values.get(index) {
+                    Some(v) => coerce_value_up(*v, payload_type),
+                    None => get_zero_value(payload_type),
+                };
+                values.push(payload_value);
+            }
+            values
+        }
+        (InterfaceType::Flags(names), ComponentValue::Flags(set_names)) => {
+            get_flags_bits(names, set_names)?
+                .into_iter()
+                .map(|item| Value::I32(item as i32))
+                .collect()
+        }
+        (InterfaceType::Own(resource_type_id), ComponentValue::Own(representation)) => {
+            let handle = lower_handle(context, *resource_type_id, *representation, true);
+            vec![Value::I32(handle as i32)]
+        }
+        (InterfaceType::Borrow(resource_type_id), ComponentValue::Borrow(representation)) => {
+            let handle = lower_handle(context, *resource_type_id, *representation, false);
+            vec![Value::I32(handle as i32)]
+        }
+        _ => return Err(make_value_type_mismatch_error(interface_type, value)),
+    };
+
+    Ok(values)
+}
+
+/// 提升一组数值（函数的参数或者返回值）
+///
+/// 当展平之后的核心数值数量超过 max_flat 时，核心数值只有一个指针，
+/// 数值以元组的形式存放在线性内存里。
+pub fn lift_values(
+    context: &mut CallContext,
+    interface_types: &[InterfaceType],
+    values: &[Value],
+    max_flat: usize,
+) -> Result<Vec<ComponentValue>, ComponentError> {
+    let flat_count: usize = interface_types.iter().map(|t| flatten_type(t).len()).sum();
+
+    if flat_count > max_flat {
+        let tuple_type = InterfaceType::Tuple(interface_types.to_vec());
+        let address = FlatValues::new(values).next_i32()? as u32;
+        check_alignment(address, alignment(&tuple_type))?;
+
+        match load(context, &tuple_type, address)? {
+            ComponentValue::Tuple(component_values) => Ok(component_values),
+            _ => unreachable!(),
+        }
+    } else {
+        let mut flat_values = FlatValues::new(values);
+        let mut component_values: Vec<ComponentValue> = vec![];
+        for interface_type in interface_types {
+            component_values.push(lift_flat(context, interface_type, &mut flat_values)?);
+        }
+        Ok(component_values)
+    }
+}
+
+/// 降低一组数值（函数的参数或者返回值）
+///
+/// 当展平之后的核心数值数量超过 max_flat 时，数值以元组的形式存放在线性内存里，
+/// 如果指定了 option_address，则存放在该位置，并返回空列表；
+/// 否则通过 `realloc` 分配内存，并返回指向该内存的指针。
+pub fn lower_values(
+    context: &mut CallContext,
+    component_values: &[ComponentValue],
+    interface_types: &[InterfaceType],
+    max_flat: usize,
+    option_address: Option<u32>,
+) -> Result<Vec<Value>, ComponentError> {
+    if component_values.len() != interface_types.len() {
+        return Err(ComponentError::ValueTypeMismatch(format!(
+            "expected {} values, actual {}",
+            interface_types.len(),
+            component_values.len()
+        )));
+    }
+
+    let flat_count: usize = interface_types.iter().map(|t| flatten_type(t).len()).sum();
+
+    if flat_count > max_flat {
+        let tuple_type = InterfaceType::Tuple(interface_types.to_vec());
+        let tuple_value = ComponentValue::Tuple(component_values.to_vec());
+
+        match option_address {
+            Some(address) => {
+                check_alignment(address, alignment(&tuple_type))?;
+                store(context, &tuple_value, &tuple_type, address)?;
+                Ok(vec![])
+            }
+            None => {
+                let tuple_alignment = alignment(&tuple_type);
+                let address = context
+                    .memory
+                    .realloc(0, 0, tuple_alignment, size(&tuple_type))?;
+                check_alignment(address, tuple_alignment)?;
+                store(context, &tuple_value, &tuple_type, address)?;
+                Ok(vec![Value::I32(address as i32)])
+            }
+        }
+    } else {
+        let mut values: Vec<Value> = vec![];
+        for (component_value, interface_type) in component_values.iter().zip(interface_types) {
+            values.extend(lower_flat(context, component_value, interface_type)?);
+        }
+        Ok(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anvm_ast::types::{Value, ValueType};
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        error::ComponentError,
+        types::{ComponentValue, InterfaceFunctionType, InterfaceType},
+    };
+
+    use super::{
+        alignment, flatten_function_type, flatten_type, lift_flat, lift_values, load, lower_flat,
+        lower_values, size, store, CallContext, FlatValues, HandleTable, LinearMemory,
+        MAX_FLAT_PARAMS, MAX_FLAT_RESULTS,
+    };
+
+    /// 使用字节数组模拟线性内存，`realloc` 为简单的递增分配
+    struct TestMemory {
+        data: Vec<u8>,
+        heap_address: u32,
+    }
+
+    impl TestMemory {
+        fn new() -> Self {
+            Self {
+                data: vec![0; 1024],
+                heap_address: 256,
+            }
+        }
+    }
+
+    impl LinearMemory for TestMemory {
+        fn read(&mut self, address: u32, length: u32) -> Result<Vec<u8>, ComponentError> {
+            let start = address as usize;
+            let end = start + length as usize;
+            if end > self.data.len() {
+                return Err(ComponentError::MemoryAccessOutOfRange(address, length));
+            }
+            Ok(self.data[start..end].to_vec())
+        }
+
+        fn write(&mut self, address: u32, data: &[u8]) -> Result<(), ComponentError> {
+            let start = address as usize;
+            let end = start + data.len();
+            if end > self.data.len() {
+                return Err(ComponentError::MemoryAccessOutOfRange(
+                    address,
+                    data.len() as u32,
+                ));
+            }
+            self.data[start..end].copy_from_slice(data);
+            Ok(())
+        }
+
+        fn realloc(
+            &mut self,
+            _original_ptr: u32,
+            _original_size: u32,
+            alignment: u32,
+            new_size: u32,
+        ) -> Result<u32, ComponentError> {
+            let address = self.heap_address.div_ceil(alignment) * alignment;
+            self.heap_address = address + new_size;
+            Ok(address)
+        }
+    }
+
+    fn get_test_type() -> InterfaceType {
+        // record {
+        //     name: string,
+        //     scores: list<u16>,
+        //     shape: variant { circle(f32), rect(tuple<u64, s8>), none },
+        //     status: result<char, option<bool>>,
+        //     tags: flags { a, b, c },
+        // }
+        InterfaceType::Record(vec![
+            ("name".to_owned(), InterfaceType::String),
+            (
+                "scores".to_owned(),
+                InterfaceType::List(Box::new(InterfaceType::U16)),
+            ),
+            (
+                "shape".to_owned(),
+                InterfaceType::Variant(vec![
+                    ("circle".to_owned(), Some(InterfaceType::F32)),
+                    (
+                        "rect".to_owned(),
+                        Some(InterfaceType::Tuple(vec![
+                            InterfaceType::U64,
+                            InterfaceType::S8,
+                        ])),
+                    ),
+                    ("none".to_owned(), None),
+                ]),
+            ),
+            (
+                "status".to_owned(),
+                InterfaceType::Result {
+                    ok: Some(Box::new(InterfaceType::Char)),
+                    error: Some(Box::new(InterfaceType::Option(Box::new(
+                        InterfaceType::Bool,
+                    )))),
+                },
+            ),
+            (
+                "tags".to_owned(),
+                InterfaceType::Flags(vec!["a".to_owned(), "b".to_owned(), "c".to_owned()]),
+            ),
+        ])
+    }
+
+    fn get_test_values() -> Vec<ComponentValue> {
+        let shapes = vec![
+            ComponentValue::Variant(
+                "circle".to_owned(),
+                Some(Box::new(ComponentValue::F32(1.5))),
+            ),
+            ComponentValue::Variant(
+                "rect".to_owned(),
+                Some(Box::new(ComponentValue::Tuple(vec![
+                    ComponentValue::U64(0x1_0000_0001),
+                    ComponentValue::S8(-3),
+                ]))),
+            ),
+            ComponentValue::Variant("none".to_owned(), None),
+        ];
+
+        let statuses = vec![
+            ComponentValue::Result(Ok(Some(Box::new(ComponentValue::Char('中'))))),
+            ComponentValue::Result(Err(Some(Box::new(ComponentValue::Option(Some(Box::new(
+                ComponentValue::Bool(true),
+            ))))))),
+        ];
+
+        shapes
+            .into_iter()
+            .zip(statuses.into_iter().cycle())
+            .map(|(shape, status)| {
+                ComponentValue::Record(vec![
+                    (
+                        "name".to_owned(),
+                        ComponentValue::String("hello 世界".to_owned()),
+                    ),
+                    (
+                        "scores".to_owned(),
+                        ComponentValue::List(vec![
+                            ComponentValue::U16(1),
+                            ComponentValue::U16(65535),
+                        ]),
+                    ),
+                    ("shape".to_owned(), shape),
+                    ("status".to_owned(), status),
+                    (
+                        "tags".to_owned(),
+                        ComponentValue::Flags(vec!["a".to_owned(), "c".to_owned()]),
+                    ),
+                ])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_layout() {
+        let test_type = get_test_type();
+
+        // name: 0..8, scores: 8..16, shape: 16..40（载荷按照 tuple 的 8 字节对齐），
+        // status: 40..48, tags: 48..49，最后按照 8 字节对齐
+        assert_eq!(alignment(&test_type), 8);
+        assert_eq!(size(&test_type), 56);
+
+        assert_eq!(
+            flatten_type(&test_type),
+            vec![
+                ValueType::I32, // name
+                ValueType::I32,
+                ValueType::I32, // scores
+                ValueType::I32,
+                ValueType::I32, // shape discriminant
+                ValueType::I64, // join(f32, u64)
+                ValueType::I32, // s8
+                ValueType::I32, // status discriminant
+                ValueType::I32, // join(char, option discriminant)
+                ValueType::I32, // bool
+                ValueType::I32, // tags
+            ]
+        );
+
+        assert_eq!(size(&InterfaceType::Flags(vec!["x".to_owned(); 40])), 8);
+        assert_eq!(
+            flatten_type(&InterfaceType::Flags(vec!["x".to_owned(); 40])),
+            vec![ValueType::I32, ValueType::I32]
+        );
+    }
+
+    #[test]
+    fn test_flatten_function_type() {
+        let function_type = InterfaceFunctionType {
+            params: vec![("s".to_owned(), InterfaceType::String)],
+            result: Some(InterfaceType::String),
+        };
+
+        // 降低：返回值通过调用者传入的指针返回
+        let lower_type = flatten_function_type(&function_type, true);
+        assert_eq!(
+            lower_type.params,
+            vec![ValueType::I32, ValueType::I32, ValueType::I32]
+        );
+        assert_eq!(lower_type.results, vec![]);
+
+        // 提升：被调用者返回指针
+        let lift_type = flatten_function_type(&function_type, false);
+        assert_eq!(lift_type.params, vec![ValueType::I32, ValueType::I32]);
+        assert_eq!(lift_type.results, vec![ValueType::I32]);
+
+        // 参数过多
+        let function_type = InterfaceFunctionType {
+            params: vec![("p".to_owned(), InterfaceType::U64); MAX_FLAT_PARAMS + 1],
+            result: None,
+        };
+        let lift_type = flatten_function_type(&function_type, false);
+        assert_eq!(lift_type.params, vec![ValueType::I32]);
+        assert_eq!(lift_type.results, vec![]);
+    }
+
+    #[test]
+    fn test_store_and_load() {
+        let test_type = get_test_type();
+
+        for value in get_test_values() {
+            let mut memory = TestMemory::new();
+            let mut handles = HandleTable::new();
+            let mut context = CallContext::new(&mut memory, &mut handles);
+
+            store(&mut context, &value, &test_type, 8).unwrap();
+            assert_eq!(load(&mut context, &test_type, 8).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_lower_and_lift_flat() {
+        let test_type = get_test_type();
+
+        for value in get_test_values() {
+            let mut memory = TestMemory::new();
+            let mut handles = HandleTable::new();
+            let mut context = CallContext::new(&mut memory, &mut handles);
+
+            let values = lower_flat(&mut context, &value, &test_type).unwrap();
+            assert_eq!(
+                values
+                    .iter()
+                    .map(|v| v.get_type())
+                    .collect::<Vec<ValueType>>(),
+                flatten_type(&test_type)
+            );
+
+            let mut flat_values = FlatValues::new(&values);
+            assert_eq!(
+                lift_flat(&mut context, &test_type, &mut flat_values).unwrap(),
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_lower_and_lift_values() {
+        let mut memory = TestMemory::new();
+        let mut handles = HandleTable::new();
+        let mut context = CallContext::new(&mut memory, &mut handles);
+
+        // 返回值超过 MAX_FLAT_RESULTS，存放在指定的位置
+        let types = vec![InterfaceType::String];
+        let values = vec![ComponentValue::String("abc".to_owned())];
+        assert_eq!(
+            lower_values(&mut context, &values, &types, MAX_FLAT_RESULTS, Some(16)).unwrap(),
+            vec![]
+        );
+        assert_eq!(
+            lift_values(&mut context, &types, &[Value::I32(16)], MAX_FLAT_RESULTS).unwrap(),
+            values
+        );
+
+        // 参数超过 MAX_FLAT_PARAMS，通过 realloc 分配内存
+        let types = vec![InterfaceType::U32; MAX_FLAT_PARAMS + 1];
+        let values = (0..=MAX_FLAT_PARAMS as u32)
+            .map(ComponentValue::U32)
+            .collect::<Vec<ComponentValue>>();
+        let core_values =
+            lower_values(&mut context, &values, &types, MAX_FLAT_PARAMS, None).unwrap();
+        assert_eq!(core_values.len(), 1);
+        assert_eq!(
+            lift_values(&mut context, &types, &core_values, MAX_FLAT_PARAMS).unwrap(),
+            values
+        );
+    }
+
+    #[test]
+    fn test_resource_handles() {
+        let mut memory = TestMemory::new();
+        let mut handles = HandleTable::new();
+        let mut context = CallContext::new(&mut memory, &mut handles);
+
+        let own_type = InterfaceType::Own(3);
+        let borrow_type = InterfaceType::Borrow(3);
+
+        let values = lower_flat(&mut context, &ComponentValue::Own(100), &own_type).unwrap();
+        assert_eq!(values, vec![Value::I32(1)]);
+
+        // borrow 不会转移所有权
+        let mut flat_values = FlatValues::new(&values);
+        assert_eq!(
+            lift_flat(&mut context, &borrow_type, &mut flat_values).unwrap(),
+            ComponentValue::Borrow(100)
+        );
+
+        // 资源类型不一致
+        let mut flat_values = FlatValues::new(&values);
+        assert!(matches!(
+            lift_flat(&mut context, &InterfaceType::Own(4), &mut flat_values),
+            Err(ComponentError::InvalidHandle(1))
+        ));
+
+        // own 会转移所有权，之后句柄失效
+        let mut flat_values = FlatValues::new(&values);
+        assert_eq!(
+            lift_flat(&mut context, &own_type, &mut flat_values).unwrap(),
+            ComponentValue::Own(100)
+        );
+        let mut flat_values = FlatValues::new(&values);
+        assert!(matches!(
+            lift_flat(&mut context, &own_type, &mut flat_values),
+            Err(ComponentError::InvalidHandle(1))
+        ));
+    }
+
+    #[test]
+    fn test_lift_errors() {
+        let mut memory = TestMemory::new();
+        let mut handles = HandleTable::new();
+        let mut context = CallContext::new(&mut memory, &mut handles);
+
+        assert!(matches!(
+            lift_flat(
+                &mut context,
+                &InterfaceType::Char,
+                &mut FlatValues::new(&[Value::I32(0xd800)])
+            ),
+            Err(ComponentError::InvalidChar(0xd800))
+        ));
+
+        assert!(matches!(
+            lift_flat(
+                &mut context,
+                &InterfaceType::Enum(vec!["a".to_owned(), "b".to_owned()]),
+                &mut FlatValues::new(&[Value::I32(2)])
+            ),
+            Err(ComponentError::InvalidDiscriminant(2))
+        ));
+
+        context.memory.write(0, &[0xff, 0xfe]).unwrap();
+        assert!(matches!(
+            lift_flat(
+                &mut context,
+                &InterfaceType::String,
+                &mut FlatValues::new(&[Value::I32(0), Value::I32(2)])
+            ),
+            Err(ComponentError::InvalidUtf8String)
+        ));
+
+        assert!(matches!(
+            lift_flat(
+                &mut context,
+                &InterfaceType::List(Box::new(InterfaceType::U32)),
+                &mut FlatValues::new(&[Value::I32(2), Value::I32(1)])
+            ),
+            Err(ComponentError::UnalignedPointer(2, 4))
+        ));
+
+        assert!(matches!(
+            lift_flat(
+                &mut context,
+                &InterfaceType::String,
+                &mut FlatValues::new(&[Value::I32(1000), Value::I32(100)])
+            ),
+            Err(ComponentError::MemoryAccessOutOfRange(1000, 100))
+        ));
+
+        assert!(matches!(
+            lift_flat(
+                &mut context,
+                &InterfaceType::U32,
+                &mut FlatValues::new(&[Value::I64(1)])
+            ),
+            Err(ComponentError::ValueTypeMismatch(_))
+        ));
+    }
+}
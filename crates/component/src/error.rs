@@ -0,0 +1,168 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::{
+    any::Any,
+    fmt::{Debug, Display},
+};
+
+use anvm_binary_parser::error as core_error;
+use anvm_engine::error::{EngineError, InternalError, NativeError, NativeTerminate};
+
+/// 解析组件二进制时的错误
+#[derive(Debug, PartialEq, Clone)]
+pub enum ParseError {
+    UnsupportedFormat(/* magic_number */ u32),
+
+    /// 版本号以及层（layer）号
+    ///
+    /// 核心模块的层号为 0，组件的层号为 1
+    UnsupportedVersion(/* version */ u16, /* layer */ u16),
+
+    InvalidSectionId(u8),
+    InvalidTag(/* item_name */ String, /* tag */ u8),
+
+    /// 规范里存在但暂时未实现的功能，比如异步函数以及非 i32 的资源表示类型
+    Unsupported(String),
+
+    DecodingError,
+    UnexpectedEnd,
+
+    /// 内嵌的核心模块解析错误
+    CoreModule(core_error::ParseError),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::UnsupportedFormat(magic_number) => {
+                write!(
+                    f,
+                    "unknown binary file magic number: 0x{:08x}",
+                    magic_number
+                )
+            }
+            ParseError::UnsupportedVersion(version, layer) => {
+                write!(
+                    f,
+                    "unsupported component version: {}, layer: {}",
+                    version, layer
+                )
+            }
+            ParseError::InvalidSectionId(id) => write!(f, "invalid section id: {}", id),
+            ParseError::InvalidTag(item_name, tag) => {
+                write!(f, "invalid {} tag: 0x{:02x}", item_name, tag)
+            }
+            ParseError::Unsupported(message) => write!(f, "unsupported {}", message),
+            ParseError::DecodingError => write!(f, "decoding error"),
+            ParseError::UnexpectedEnd => write!(f, "unexpected end"),
+            ParseError::CoreModule(e) => write!(f, "core module error: {}", e),
+        }
+    }
+}
+
+/// 组件实例化以及运行时的错误
+#[derive(Debug)]
+pub enum ComponentError {
+    /// 宿主未提供组件所需的导入实例，或者导入实例缺少所需的项目
+    ImportNotFound(/* import_name */ String),
+    ImportItemNotFound(/* import_name */ String, /* item_name */ String),
+
+    ExportNotFound(/* export_name */ String),
+
+    /// 组件的结构不符合规范，比如索引超出范围，或者项目的种类不正确
+    InvalidComponent(String),
+
+    /// 规范里存在但暂时未实现的功能，比如嵌套组件的实例化，以及 UTF-16 字符串编码
+    Unsupported(String),
+
+    /// 调用组件函数时，实参的数量或者数据类型跟函数的类型不一致
+    ValueTypeMismatch(String),
+
+    // 以下是规范 ABI（canonical ABI）在提升（lift）和降低（lower）数值时的错误，
+    // 根据规范，这些错误都会导致陷入（trap）。
+    MemoryAccessOutOfRange(/* address */ u32, /* length */ u32),
+    UnalignedPointer(/* address */ u32, /* alignment */ u32),
+    InvalidUtf8String,
+    InvalidChar(u32),
+    InvalidDiscriminant(u32),
+    InvalidHandle(u32),
+    MissingMemoryOption,
+    MissingReallocOption,
+
+    Engine(EngineError),
+}
+
+impl Display for ComponentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComponentError::ImportNotFound(name) => write!(f, "import \"{}\" not found", name),
+            ComponentError::ImportItemNotFound(import_name, item_name) => {
+                write!(
+                    f,
+                    "item \"{}\" of import \"{}\" not found",
+                    item_name, import_name
+                )
+            }
+            ComponentError::ExportNotFound(name) => write!(f, "export \"{}\" not found", name),
+            ComponentError::InvalidComponent(message) => {
+                write!(f, "invalid component: {}", message)
+            }
+            ComponentError::Unsupported(message) => write!(f, "unsupported {}", message),
+            ComponentError::ValueTypeMismatch(message) => {
+                write!(f, "value type mismatch: {}", message)
+            }
+            ComponentError::MemoryAccessOutOfRange(address, length) => {
+                write!(
+                    f,
+                    "memory access out of range, address: {}, length: {}",
+                    address, length
+                )
+            }
+            ComponentError::UnalignedPointer(address, alignment) => {
+                write!(
+                    f,
+                    "unaligned pointer, address: {}, alignment: {}",
+                    address, alignment
+                )
+            }
+            ComponentError::InvalidUtf8String => write!(f, "invalid UTF-8 string"),
+            ComponentError::InvalidChar(value) => write!(f, "invalid char: 0x{:x}", value),
+            ComponentError::InvalidDiscriminant(value) => {
+                write!(f, "invalid discriminant: {}", value)
+            }
+            ComponentError::InvalidHandle(handle) => write!(f, "invalid handle: {}", handle),
+            ComponentError::MissingMemoryOption => write!(f, "canonical option memory missing"),
+            ComponentError::MissingReallocOption => {
+                write!(f, "canonical option realloc missing")
+            }
+            ComponentError::Engine(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl InternalError for ComponentError {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl ComponentError {
+    /// 转换为本地函数的错误，以便从本地函数里返回
+    ///
+    /// 如果错误本身是由本地函数（重入调用核心函数时）产生的，则原样返回。
+    pub fn to_native_terminate(self, module_name: &str) -> NativeTerminate {
+        match self {
+            ComponentError::Engine(EngineError::NativeTerminate(native_terminate)) => {
+                native_terminate
+            }
+            _ => NativeTerminate {
+                module_name: module_name.to_owned(),
+                native_error: NativeError::Internal(Box::new(self)),
+            },
+        }
+    }
+}
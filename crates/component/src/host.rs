@@ -0,0 +1,94 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 宿主实例
+//!
+//! 宿主实例用于满足组件的实例导入项（比如 `wasi:cli/stdout@0.2.0`），
+//! 宿主实例的函数（宿主函数）接收以及返回的都是组件数值，
+//! 组件数值跟核心数值之间的转换由规范 ABI 负责。
+//!
+//! 宿主函数并不声明自身的类型，实参以及返回值的类型以组件导入项里声明的类型为准。
+
+use anvm_engine::{error::NativeTerminate, native_module::ModuleContext};
+
+use crate::types::ComponentValue;
+
+/// 宿主函数
+///
+/// 第一个参数是宿主的上下文（即实例化组件时传入的 host_context）
+pub type HostFunction = fn(
+    host_context: &mut dyn ModuleContext,
+    arguments: &[ComponentValue],
+) -> Result<Option<ComponentValue>, NativeTerminate>;
+
+/// 宿主资源的析构函数，参数 representation 为资源的表示值
+pub type HostResourceDestructor =
+    fn(host_context: &mut dyn ModuleContext, representation: u32) -> Result<(), NativeTerminate>;
+
+pub struct HostInstance {
+    /// 实例的名称，比如 `wasi:cli/stdout`
+    ///
+    /// 匹配组件的导入项时忽略名称的版本号部分（即 `@0.2.0`）
+    pub name: String,
+    pub functions: Vec<(String, HostFunction)>,
+    pub resources: Vec<(String, Option<HostResourceDestructor>)>,
+}
+
+impl HostInstance {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            functions: vec![],
+            resources: vec![],
+        }
+    }
+
+    pub fn add_function(&mut self, name: &str, host_function: HostFunction) {
+        self.functions.push((name.to_owned(), host_function));
+    }
+
+    pub fn add_resource(&mut self, name: &str, destructor: Option<HostResourceDestructor>) {
+        self.resources.push((name.to_owned(), destructor));
+    }
+
+    pub fn find_function(&self, name: &str) -> Option<HostFunction> {
+        self.functions
+            .iter()
+            .find(|(function_name, _)| function_name == name)
+            .map(|(_, host_function)| *host_function)
+    }
+
+    pub fn find_resource(&self, name: &str) -> Option<Option<HostResourceDestructor>> {
+        self.resources
+            .iter()
+            .find(|(resource_name, _)| resource_name == name)
+            .map(|(_, destructor)| *destructor)
+    }
+}
+
+/// 获取接口名称不包括版本号的部分
+///
+/// 比如 `wasi:cli/stdout@0.2.0` 返回 `wasi:cli/stdout`
+pub fn get_unversioned_name(name: &str) -> &str {
+    match name.find('@') {
+        Some(index) => &name[..index],
+        None => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get_unversioned_name;
+
+    #[test]
+    fn test_get_unversioned_name() {
+        assert_eq!(
+            get_unversioned_name("wasi:cli/stdout@0.2.0"),
+            "wasi:cli/stdout"
+        );
+        assert_eq!(get_unversioned_name("wasi:cli/stdout"), "wasi:cli/stdout");
+    }
+}
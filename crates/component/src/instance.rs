@@ -0,0 +1,1688 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 组件的实例化
+//!
+//! 组件本身并不包含可执行的代码，它由若干核心模块以及把它们连接起来的
+//! 定义项（核心实例、别名、规范函数等）组成，实例化一个组件的过程如下：
+//!
+//! 1. 按照定义项的原始次序建立各个索引空间；
+//! 2. 每个核心模块的实例化（`instantiate`）都会产生一个具有唯一名称的 `NamedAstModule`，
+//!    模块的导入项被改写为指向实参实例所对应的模块或者项目；
+//! 3. 每个降低（`canon lower`）以及 `resource.new/drop/rep` 函数都会产生一个只有
+//!    一个函数的本地模块，由本地函数负责规范 ABI 的转换以及调用宿主函数；
+//! 4. 使用引擎创建 VM 实例，然后解析规范选项（内存块、realloc 函数等）在 VM 里的位置，
+//!    最后按次序执行各个核心模块的 start 函数。
+//!
+//! 目前只支持实例化最外层的组件，不支持实例化内嵌的组件。
+
+use std::{cell::RefCell, rc::Rc};
+
+use anvm_ast::{
+    ast as core_ast,
+    types::{Value, ValueType},
+};
+use anvm_engine::{
+    error::{EngineError, NativeTerminate},
+    instance::{create_instance, find_ast_module_export_function},
+    native_module::{ModuleContext, NativeModule},
+    object::NamedAstModule,
+    vm::VM,
+};
+
+use crate::{
+    ast::{
+        Alias, Canon, CanonicalOption, Component, CoreInstance, CoreSort, DefinedType, Definition,
+        Export, FunctionType, Import, Instance, PrimitiveType, Sort, TypeBound, TypeDeclaration,
+        TypeDefinition, TypeRef, ValueType as AstValueType,
+    },
+    canonical_abi::{
+        flatten_function_type, flatten_type, lift_values, lower_values, CallContext, HandleEntry,
+        HandleTable, LinearMemory, MAX_FLAT_PARAMS, MAX_FLAT_RESULTS,
+    },
+    error::ComponentError,
+    host::{get_unversioned_name, HostFunction, HostInstance, HostResourceDestructor},
+    types::{ComponentValue, InterfaceFunctionType, InterfaceType, ResourceTypeId},
+};
+
+/// 资源类型
+pub struct ResourceType {
+    pub name: String,
+    pub host_destructor: Option<HostResourceDestructor>,
+
+    /// 组件内部定义的资源类型的析构函数，值为 (vm_module_index, function_index)
+    pub core_destructor: Option<(usize, usize)>,
+}
+
+/// 组件实例的共享状态
+///
+/// 组件实例以及各个降低函数的本地模块共享同一个上下文。
+pub struct ComponentContext {
+    pub host_context: Box<dyn ModuleContext>,
+    pub handles: HandleTable,
+    pub resource_types: Vec<ResourceType>,
+}
+
+/// 核心项目（函数、表、内存块、全局变量）的位置
+///
+/// 即核心模块实例的名称，以及项目在该模块里的导出名称（或者本地模块的函数名称）。
+#[derive(Debug, PartialEq, Clone)]
+struct CoreItemRef {
+    module_name: String,
+    export_name: String,
+}
+
+enum CoreInstanceItem {
+    Module(/* module_name */ String),
+    Exports(Vec<(String, CoreSort, CoreItemRef)>),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum ComponentType {
+    Value(InterfaceType),
+    Function(InterfaceFunctionType),
+    Resource(ResourceTypeId),
+    Instance(Vec<(String, InstanceTypeExport)>),
+    Component,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum InstanceTypeExport {
+    Function(InterfaceFunctionType),
+    Type(ComponentType),
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+struct CanonicalOptions {
+    memory: Option<CoreItemRef>,
+    realloc: Option<CoreItemRef>,
+    post_return: Option<CoreItemRef>,
+}
+
+/// 规范选项在 VM 里的位置
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+struct ResolvedOptions {
+    /// 内存块实例的索引
+    memory_index: Option<usize>,
+
+    /// (vm_module_index, function_index)
+    realloc: Option<(usize, usize)>,
+    post_return: Option<(usize, usize)>,
+}
+
+#[derive(Clone)]
+enum FunctionItem {
+    Host {
+        host_function: HostFunction,
+        function_type: InterfaceFunctionType,
+    },
+    Lifted {
+        core_function: CoreItemRef,
+        function_type: InterfaceFunctionType,
+        options: CanonicalOptions,
+    },
+}
+
+#[derive(Clone)]
+enum InstanceExportItem {
+    Function(FunctionItem),
+    Type(ComponentType),
+    Instance(/* instance_index */ usize),
+}
+
+/// 被提升的核心函数，即组件的导出函数
+struct LiftedFunction {
+    name: String,
+    function_type: InterfaceFunctionType,
+    vm_module_index: usize,
+    function_index: usize,
+    options: ResolvedOptions,
+}
+
+enum LoweredFunctionKind {
+    Host {
+        host_function: HostFunction,
+        function_type: InterfaceFunctionType,
+    },
+    ResourceNew(ResourceTypeId),
+    ResourceDrop(ResourceTypeId),
+    ResourceRep(ResourceTypeId),
+}
+
+/// 降低函数的本地模块的上下文
+struct LoweredFunctionContext {
+    kind: Rc<LoweredFunctionKind>,
+    canonical_options: CanonicalOptions,
+    options: ResolvedOptions,
+    component_context: Rc<RefCell<ComponentContext>>,
+}
+
+impl ModuleContext for LoweredFunctionContext {
+    fn as_any(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+const LOWERED_FUNCTION_NAME: &str = "function";
+
+pub struct ComponentInstance {
+    pub vm: VM,
+    pub context: Rc<RefCell<ComponentContext>>,
+    lifted_functions: Vec<LiftedFunction>,
+}
+
+impl ComponentInstance {
+    /// 导出函数的名称列表
+    ///
+    /// 导出实例的函数的名称为 `实例名称#函数名称`，比如 `wasi:cli/run@0.2.0#run`。
+    pub fn get_export_function_names(&self) -> Vec<String> {
+        self.lifted_functions
+            .iter()
+            .map(|item| item.name.clone())
+            .collect()
+    }
+
+    /// 调用组件的导出函数
+    pub fn call(
+        &mut self,
+        name: &str,
+        arguments: &[ComponentValue],
+    ) -> Result<Option<ComponentValue>, ComponentError> {
+        let lifted_function = self
+            .lifted_functions
+            .iter()
+            .find(|item| item.name == name)
+            .ok_or_else(|| ComponentError::ExportNotFound(name.to_owned()))?;
+
+        let param_types = lifted_function
+            .function_type
+            .params
+            .iter()
+            .map(|(_, param_type)| param_type.clone())
+            .collect::<Vec<InterfaceType>>();
+        let result_types = lifted_function
+            .function_type
+            .result
+            .iter()
+            .cloned()
+            .collect::<Vec<InterfaceType>>();
+
+        let options = lifted_function.options;
+        let vm_module_index = lifted_function.vm_module_index;
+        let function_index = lifted_function.function_index;
+
+        let core_arguments = {
+            let mut component_context = self.context.borrow_mut();
+            let mut memory = VMLinearMemory::new(&mut self.vm, &options);
+            let mut call_context = CallContext::new(&mut memory, &mut component_context.handles);
+            lower_values(
+                &mut call_context,
+                arguments,
+                &param_types,
+                MAX_FLAT_PARAMS,
+                None,
+            )?
+        };
+
+        let core_results = self
+            .vm
+            .eval_function_by_index(vm_module_index, function_index, &core_arguments)
+            .map_err(ComponentError::Engine)?;
+
+        let mut results = {
+            let mut component_context = self.context.borrow_mut();
+            let mut memory = VMLinearMemory::new(&mut self.vm, &options);
+            let mut call_context = CallContext::new(&mut memory, &mut component_context.handles);
+            lift_values(
+                &mut call_context,
+                &result_types,
+                &core_results,
+                MAX_FLAT_RESULTS,
+            )?
+        };
+
+        if let Some((post_return_vm_module_index, post_return_function_index)) = options.post_return
+        {
+            self.vm
+                .eval_function_by_index(
+                    post_return_vm_module_index,
+                    post_return_function_index,
+                    &core_results,
+                )
+                .map_err(ComponentError::Engine)?;
+        }
+
+        Ok(results.pop())
+    }
+}
+
+/// 实例化组件
+///
+/// host_instances 用于满足组件的实例导入项，host_context 是宿主函数的上下文。
+pub fn instantiate(
+    component: &Component,
+    host_instances: &[HostInstance],
+    host_context: Box<dyn ModuleContext>,
+) -> Result<ComponentInstance, ComponentError> {
+    let component_context = Rc::new(RefCell::new(ComponentContext {
+        host_context,
+        handles: HandleTable::new(),
+        resource_types: vec![],
+    }));
+
+    let mut instantiator = Instantiator::new(host_instances, Rc::clone(&component_context));
+    for definition in &component.definitions {
+        instantiator.process_definition(definition)?;
+    }
+
+    instantiator.finish()
+}
+
+struct Instantiator<'a> {
+    host_instances: &'a [HostInstance],
+    component_context: Rc<RefCell<ComponentContext>>,
+
+    named_ast_modules: Vec<NamedAstModule>,
+    native_modules: Vec<NativeModule>,
+
+    // 核心项目的索引空间
+    core_modules: Vec<core_ast::Module>,
+    core_instances: Vec<CoreInstanceItem>,
+    core_functions: Vec<CoreItemRef>,
+    core_tables: Vec<CoreItemRef>,
+    core_memories: Vec<CoreItemRef>,
+    core_globals: Vec<CoreItemRef>,
+    core_type_count: usize,
+
+    // 组件项目的索引空间
+    //
+    // 类型的索引空间是一个栈，栈顶为当前的作用域，组件类型以及实例类型的声明项
+    // 会在新的作用域里解析，`alias outer` 根据层数访问外层作用域。
+    type_scopes: Vec<Vec<ComponentType>>,
+    functions: Vec<FunctionItem>,
+    instances: Vec<Vec<(String, InstanceExportItem)>>,
+    component_count: usize,
+
+    /// 导出函数的名称以及函数
+    export_functions: Vec<(String, FunctionItem)>,
+
+    /// 组件内部定义的资源类型的析构函数
+    resource_destructors: Vec<(ResourceTypeId, CoreItemRef)>,
+
+    /// 已经跟宿主实例绑定的资源类型
+    ///
+    /// 实例类型可以通过 `(eq ...)` 重新导出其他实例的资源类型，
+    /// 这些资源类型只在首次导入时绑定宿主的析构函数。
+    host_resource_types: Vec<ResourceTypeId>,
+}
+
+impl<'a> Instantiator<'a> {
+    fn new(
+        host_instances: &'a [HostInstance],
+        component_context: Rc<RefCell<ComponentContext>>,
+    ) -> Self {
+        Self {
+            host_instances,
+            component_context,
+            named_ast_modules: vec![],
+            native_modules: vec![],
+            core_modules: vec![],
+            core_instances: vec![],
+            core_functions: vec![],
+            core_tables: vec![],
+            core_memories: vec![],
+            core_globals: vec![],
+            core_type_count: 0,
+            type_scopes: vec![vec![]],
+            functions: vec![],
+            instances: vec![],
+            component_count: 0,
+            export_functions: vec![],
+            resource_destructors: vec![],
+            host_resource_types: vec![],
+        }
+    }
+
+    fn process_definition(&mut self, definition: &Definition) -> Result<(), ComponentError> {
+        match definition {
+            Definition::CoreModule(module) => self.core_modules.push(*module.clone()),
+            Definition::CoreInstance(core_instance) => self.process_core_instance(core_instance)?,
+            Definition::CoreType => self.core_type_count += 1,
+            Definition::Component(_) => self.component_count += 1,
+            Definition::Instance(instance) => self.process_instance(instance)?,
+            Definition::Alias(alias) => self.process_alias(alias)?,
+            Definition::Type(type_definition) => {
+                let component_type = self.resolve_type_definition(type_definition)?;
+                self.get_current_types().push(component_type);
+            }
+            Definition::Canon(canon) => self.process_canon(canon)?,
+            Definition::Start(_) => {
+                return Err(ComponentError::Unsupported(
+                    "component start function".to_owned(),
+                ))
+            }
+            Definition::Import(import) => self.process_import(import)?,
+            Definition::Export(export) => self.process_export(export)?,
+        }
+
+        Ok(())
+    }
+
+    fn process_core_instance(
+        &mut self,
+        core_instance: &CoreInstance,
+    ) -> Result<(), ComponentError> {
+        let item = match core_instance {
+            CoreInstance::Instantiate {
+                module_index,
+                arguments,
+            } => {
+                let mut module =
+                    get_item(&self.core_modules, *module_index, "core module")?.clone();
+
+                // 改写模块的导入项，使之指向实参实例所对应的模块或者项目
+                for import_item in &mut module.import_items {
+                    let argument = arguments
+                        .iter()
+                        .find(|argument| argument.name == import_item.module_name)
+                        .ok_or_else(|| {
+                            ComponentError::InvalidComponent(format!(
+                                "missing instantiation argument \"{}\"",
+                                import_item.module_name
+                            ))
+                        })?;
+
+                    let core_item_ref = self.get_core_instance_export(
+                        argument.instance_index,
+                        &import_item.item_name,
+                        None,
+                    )?;
+
+                    import_item.module_name = core_item_ref.module_name;
+                    import_item.item_name = core_item_ref.export_name;
+                }
+
+                let module_name = format!("core-instance-{}", self.core_instances.len());
+                self.named_ast_modules
+                    .push(NamedAstModule::new(&module_name, module));
+
+                CoreInstanceItem::Module(module_name)
+            }
+            CoreInstance::FromExports(exports) => {
+                let mut items: Vec<(String, CoreSort, CoreItemRef)> = vec![];
+                for export in exports {
+                    let core_item_ref = self.get_core_item(export.sort, export.index)?.clone();
+                    items.push((export.name.clone(), export.sort, core_item_ref));
+                }
+                CoreInstanceItem::Exports(items)
+            }
+        };
+
+        self.core_instances.push(item);
+        Ok(())
+    }
+
+    /// 获取核心实例的导出项
+    ///
+    /// 当 option_sort 为 None 时不检查导出项的种类
+    fn get_core_instance_export(
+        &self,
+        instance_index: u32,
+        name: &str,
+        option_sort: Option<CoreSort>,
+    ) -> Result<CoreItemRef, ComponentError> {
+        match get_item(&self.core_instances, instance_index, "core instance")? {
+            CoreInstanceItem::Module(module_name) => Ok(CoreItemRef {
+                module_name: module_name.clone(),
+                export_name: name.to_owned(),
+            }),
+            CoreInstanceItem::Exports(items) => items
+                .iter()
+                .find(|(export_name, sort, _)| {
+                    export_name == name && option_sort.is_none_or(|s| &s == sort)
+                })
+                .map(|(_, _, core_item_ref)| core_item_ref.clone())
+                .ok_or_else(|| {
+                    ComponentError::InvalidComponent(format!(
+                        "core instance {} has no export \"{}\"",
+                        instance_index, name
+                    ))
+                }),
+        }
+    }
+
+    fn get_core_item_space(
+        &mut self,
+        sort: CoreSort,
+    ) -> Result<&mut Vec<CoreItemRef>, ComponentError> {
+        match sort {
+            CoreSort::Function => Ok(&mut self.core_functions),
+            CoreSort::Table => Ok(&mut self.core_tables),
+            CoreSort::Memory => Ok(&mut self.core_memories),
+            CoreSort::Global => Ok(&mut self.core_globals),
+            _ => Err(ComponentError::Unsupported(format!("core sort {:?}", sort))),
+        }
+    }
+
+    fn get_core_item(
+        &mut self,
+        sort: CoreSort,
+        index: u32,
+    ) -> Result<&CoreItemRef, ComponentError> {
+        let items = self.get_core_item_space(sort)?;
+        get_item(items, index, "core item")
+    }
+
+    fn process_instance(&mut self, instance: &Instance) -> Result<(), ComponentError> {
+        match instance {
+            Instance::Instantiate { .. } => Err(ComponentError::Unsupported(
+                "nested component instantiation".to_owned(),
+            )),
+            Instance::FromExports(exports) => {
+                let mut items: Vec<(String, InstanceExportItem)> = vec![];
+                for export in exports {
+                    let item = self.get_instance_export_item(export.sort, export.index)?;
+                    items.push((export.name.clone(), item));
+                }
+                self.instances.push(items);
+                Ok(())
+            }
+        }
+    }
+
+    fn get_instance_export_item(
+        &mut self,
+        sort: Sort,
+        index: u32,
+    ) -> Result<InstanceExportItem, ComponentError> {
+        match sort {
+            Sort::Function => {
+                let function_item = get_item(&self.functions, index, "function")?.clone();
+                Ok(InstanceExportItem::Function(function_item))
+            }
+            Sort::Type => {
+                let component_type = get_item(self.get_current_types(), index, "type")?.clone();
+                Ok(InstanceExportItem::Type(component_type))
+            }
+            Sort::Instance => {
+                get_item(&self.instances, index, "instance")?;
+                Ok(InstanceExportItem::Instance(index as usize))
+            }
+            _ => Err(ComponentError::Unsupported(format!(
+                "instance export {:?}",
+                sort
+            ))),
+        }
+    }
+
+    fn process_alias(&mut self, alias: &Alias) -> Result<(), ComponentError> {
+        match alias {
+            Alias::InstanceExport {
+                sort,
+                instance_index,
+                name,
+            } => {
+                let items = get_item(&self.instances, *instance_index, "instance")?;
+                let item = items
+                    .iter()
+                    .find(|(export_name, _)| export_name == name)
+                    .map(|(_, item)| item.clone())
+                    .ok_or_else(|| {
+                        ComponentError::InvalidComponent(format!(
+                            "instance {} has no export \"{}\"",
+                            instance_index, name
+                        ))
+                    })?;
+
+                match (sort, item) {
+                    (Sort::Function, InstanceExportItem::Function(function_item)) => {
+                        self.functions.push(function_item);
+                    }
+                    (Sort::Type, InstanceExportItem::Type(component_type)) => {
+                        self.get_current_types().push(component_type);
+                    }
+                    (Sort::Instance, InstanceExportItem::Instance(index)) => {
+                        let instance_item = self.instances[index].clone();
+                        self.instances.push(instance_item);
+                    }
+                    _ => {
+                        return Err(ComponentError::InvalidComponent(format!(
+                            "export \"{}\" of instance {} is not {:?}",
+                            name, instance_index, sort
+                        )))
+                    }
+                }
+            }
+            Alias::CoreInstanceExport {
+                sort,
+                instance_index,
+                name,
+            } => {
+                let core_item_ref =
+                    self.get_core_instance_export(*instance_index, name, Some(*sort))?;
+                self.get_core_item_space(*sort)?.push(core_item_ref);
+            }
+            Alias::Outer { sort, count, index } => {
+                let component_type = self.get_outer_type(*sort, *count, *index)?;
+                self.get_current_types().push(component_type);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_outer_type(
+        &self,
+        sort: Sort,
+        count: u32,
+        index: u32,
+    ) -> Result<ComponentType, ComponentError> {
+        if sort != Sort::Type {
+            return Err(ComponentError::Unsupported(format!(
+                "outer alias {:?}",
+                sort
+            )));
+        }
+
+        let depth = self.type_scopes.len();
+        if count as usize >= depth {
+            return Err(ComponentError::Unsupported(
+                "outer alias to enclosing component".to_owned(),
+            ));
+        }
+
+        let types = &self.type_scopes[depth - 1 - count as usize];
+        Ok(get_item(types, index, "type")?.clone())
+    }
+
+    fn get_current_types(&mut self) -> &mut Vec<ComponentType> {
+        self.type_scopes.last_mut().unwrap()
+    }
+
+    fn get_type(&mut self, type_index: u32) -> Result<&ComponentType, ComponentError> {
+        get_item(self.get_current_types(), type_index, "type")
+    }
+
+    fn add_resource_type(&mut self, name: &str) -> ResourceTypeId {
+        let mut component_context = self.component_context.borrow_mut();
+        component_context.resource_types.push(ResourceType {
+            name: name.to_owned(),
+            host_destructor: None,
+            core_destructor: None,
+        });
+        component_context.resource_types.len() - 1
+    }
+
+    fn resolve_type_definition(
+        &mut self,
+        type_definition: &TypeDefinition,
+    ) -> Result<ComponentType, ComponentError> {
+        let component_type = match type_definition {
+            TypeDefinition::Defined(defined_type) => {
+                ComponentType::Value(self.resolve_defined_type(defined_type)?)
+            }
+            TypeDefinition::Function(function_type) => {
+                ComponentType::Function(self.resolve_function_type(function_type)?)
+            }
+            TypeDefinition::Resource { destructor, .. } => {
+                let resource_type_id = self.add_resource_type("");
+                if let Some(function_index) = destructor {
+                    let core_item_ref =
+                        get_item(&self.core_functions, *function_index, "core function")?.clone();
+                    self.resource_destructors
+                        .push((resource_type_id, core_item_ref));
+                }
+                ComponentType::Resource(resource_type_id)
+            }
+            TypeDefinition::Instance(declarations) => {
+                ComponentType::Instance(self.resolve_instance_type(declarations)?)
+            }
+            TypeDefinition::Component(_) => ComponentType::Component,
+        };
+
+        Ok(component_type)
+    }
+
+    /// 解析实例类型的声明项
+    ///
+    /// 以 `(sub resource)` 形式声明的资源类型在这里分配新的资源类型编号。
+    fn resolve_instance_type(
+        &mut self,
+        declarations: &[TypeDeclaration],
+    ) -> Result<Vec<(String, InstanceTypeExport)>, ComponentError> {
+        self.type_scopes.push(vec![]);
+        let result = self.resolve_instance_type_declarations(declarations);
+        self.type_scopes.pop();
+        result
+    }
+
+    fn resolve_instance_type_declarations(
+        &mut self,
+        declarations: &[TypeDeclaration],
+    ) -> Result<Vec<(String, InstanceTypeExport)>, ComponentError> {
+        let mut exports: Vec<(String, InstanceTypeExport)> = vec![];
+
+        for declaration in declarations {
+            match declaration {
+                TypeDeclaration::Type(type_definition) => {
+                    let component_type = self.resolve_type_definition(type_definition)?;
+                    self.get_current_types().push(component_type);
+                }
+                TypeDeclaration::Alias(Alias::Outer { sort, count, index }) => {
+                    let component_type = self.get_outer_type(*sort, *count, *index)?;
+                    self.get_current_types().push(component_type);
+                }
+                TypeDeclaration::Export(Import { name, type_ref }) => match type_ref {
+                    TypeRef::Function(type_index) => match self.get_type(*type_index)? {
+                        ComponentType::Function(function_type) => {
+                            let export = InstanceTypeExport::Function(function_type.clone());
+                            exports.push((name.clone(), export));
+                        }
+                        _ => return Err(make_not_function_type_error(*type_index)),
+                    },
+                    TypeRef::Type(type_bound) => {
+                        let component_type = match type_bound {
+                            TypeBound::Eq(type_index) => self.get_type(*type_index)?.clone(),
+                            TypeBound::SubResource => {
+                                ComponentType::Resource(self.add_resource_type(name))
+                            }
+                        };
+                        self.get_current_types().push(component_type.clone());
+                        exports.push((name.clone(), InstanceTypeExport::Type(component_type)));
+                    }
+                    _ => {
+                        return Err(ComponentError::Unsupported(format!(
+                            "instance type export {:?}",
+                            type_ref
+                        )))
+                    }
+                },
+                _ => {
+                    return Err(ComponentError::Unsupported(format!(
+                        "instance type declaration {:?}",
+                        declaration
+                    )))
+                }
+            }
+        }
+
+        Ok(exports)
+    }
+
+    fn resolve_function_type(
+        &mut self,
+        function_type: &FunctionType,
+    ) -> Result<InterfaceFunctionType, ComponentError> {
+        let mut params: Vec<(String, InterfaceType)> = vec![];
+        for (name, value_type) in &function_type.params {
+            params.push((name.clone(), self.resolve_value_type(value_type)?));
+        }
+
+        let result = match &function_type.result {
+            Some(value_type) => Some(self.resolve_value_type(value_type)?),
+            None => None,
+        };
+
+        Ok(InterfaceFunctionType { params, result })
+    }
+
+    fn resolve_value_type(
+        &mut self,
+        value_type: &AstValueType,
+    ) -> Result<InterfaceType, ComponentError> {
+        match value_type {
+            AstValueType::Primitive(primitive_type) => Ok(resolve_primitive_type(primitive_type)),
+            AstValueType::TypeIndex(type_index) => match self.get_type(*type_index)? {
+                ComponentType::Value(interface_type) => Ok(interface_type.clone()),
+                _ => Err(ComponentError::InvalidComponent(format!(
+                    "type {} is not a value type",
+                    type_index
+                ))),
+            },
+        }
+    }
+
+    fn resolve_option_value_type(
+        &mut self,
+        option_value_type: &Option<AstValueType>,
+    ) -> Result<Option<Box<InterfaceType>>, ComponentError> {
+        match option_value_type {
+            Some(value_type) => Ok(Some(Box::new(self.resolve_value_type(value_type)?))),
+            None => Ok(None),
+        }
+    }
+
+    fn resolve_defined_type(
+        &mut self,
+        defined_type: &DefinedType,
+    ) -> Result<InterfaceType, ComponentError> {
+        let interface_type = match defined_type {
+            DefinedType::Primitive(primitive_type) => resolve_primitive_type(primitive_type),
+            DefinedType::Record(fields) => {
+                let mut items: Vec<(String, InterfaceType)> = vec![];
+                for (name, value_type) in fields {
+                    items.push((name.clone(), self.resolve_value_type(value_type)?));
+                }
+                InterfaceType::Record(items)
+            }
+            DefinedType::Variant(cases) => {
+                let mut items: Vec<(String, Option<InterfaceType>)> = vec![];
+                for case in cases {
+                    let case_type = self
+                        .resolve_option_value_type(&case.value_type)?
+                        .map(|t| *t);
+                    items.push((case.name.clone(), case_type));
+                }
+                InterfaceType::Variant(items)
+            }
+            DefinedType::List(value_type) => {
+                InterfaceType::List(Box::new(self.resolve_value_type(value_type)?))
+            }
+            DefinedType::Tuple(value_types) => {
+                let mut items: Vec<InterfaceType> = vec![];
+                for value_type in value_types {
+                    items.push(self.resolve_value_type(value_type)?);
+                }
+                InterfaceType::Tuple(items)
+            }
+            DefinedType::Flags(names) => InterfaceType::Flags(names.clone()),
+            DefinedType::Enum(names) => InterfaceType::Enum(names.clone()),
+            DefinedType::Option(value_type) => {
+                InterfaceType::Option(Box::new(self.resolve_value_type(value_type)?))
+            }
+            DefinedType::Result { ok, error } => InterfaceType::Result {
+                ok: self.resolve_option_value_type(ok)?,
+                error: self.resolve_option_value_type(error)?,
+            },
+            DefinedType::Own(type_index) => {
+                InterfaceType::Own(self.get_resource_type_id(*type_index)?)
+            }
+            DefinedType::Borrow(type_index) => {
+                InterfaceType::Borrow(self.get_resource_type_id(*type_index)?)
+            }
+        };
+
+        Ok(interface_type)
+    }
+
+    fn get_resource_type_id(&mut self, type_index: u32) -> Result<ResourceTypeId, ComponentError> {
+        match self.get_type(type_index)? {
+            ComponentType::Resource(resource_type_id) => Ok(*resource_type_id),
+            _ => Err(ComponentError::InvalidComponent(format!(
+                "type {} is not a resource type",
+                type_index
+            ))),
+        }
+    }
+
+    fn resolve_canonical_options(
+        &self,
+        options: &[CanonicalOption],
+    ) -> Result<CanonicalOptions, ComponentError> {
+        let mut canonical_options = CanonicalOptions::default();
+
+        for option in options {
+            match option {
+                CanonicalOption::Utf8 => {}
+                CanonicalOption::Utf16 | CanonicalOption::CompactUtf16 => {
+                    return Err(ComponentError::Unsupported(format!(
+                        "string encoding {:?}",
+                        option
+                    )))
+                }
+                CanonicalOption::Memory(index) => {
+                    canonical_options.memory =
+                        Some(get_item(&self.core_memories, *index, "core memory")?.clone());
+                }
+                CanonicalOption::Realloc(index) => {
+                    canonical_options.realloc =
+                        Some(get_item(&self.core_functions, *index, "core function")?.clone());
+                }
+                CanonicalOption::PostReturn(index) => {
+                    canonical_options.post_return =
+                        Some(get_item(&self.core_functions, *index, "core function")?.clone());
+                }
+            }
+        }
+
+        Ok(canonical_options)
+    }
+
+    fn process_canon(&mut self, canon: &Canon) -> Result<(), ComponentError> {
+        match canon {
+            Canon::Lift {
+                core_function_index,
+                options,
+                type_index,
+            } => {
+                let core_function =
+                    get_item(&self.core_functions, *core_function_index, "core function")?.clone();
+                let function_type = match self.get_type(*type_index)? {
+                    ComponentType::Function(function_type) => function_type.clone(),
+                    _ => return Err(make_not_function_type_error(*type_index)),
+                };
+                let options = self.resolve_canonical_options(options)?;
+
+                self.functions.push(FunctionItem::Lifted {
+                    core_function,
+                    function_type,
+                    options,
+                });
+            }
+            Canon::Lower {
+                function_index,
+                options,
+            } => {
+                let (host_function, function_type) =
+                    match get_item(&self.functions, *function_index, "function")? {
+                        FunctionItem::Host {
+                            host_function,
+                            function_type,
+                        } => (*host_function, function_type.clone()),
+                        FunctionItem::Lifted { .. } => {
+                            return Err(ComponentError::Unsupported(
+                                "lowering a lifted function".to_owned(),
+                            ))
+                        }
+                    };
+
+                let core_function_type = flatten_function_type(&function_type, true);
+                let options = self.resolve_canonical_options(options)?;
+
+                self.add_lowered_function(
+                    LoweredFunctionKind::Host {
+                        host_function,
+                        function_type,
+                    },
+                    core_function_type.params,
+                    core_function_type.results,
+                    options,
+                );
+            }
+            Canon::ResourceNew(type_index) => {
+                let resource_type_id = self.get_resource_type_id(*type_index)?;
+                self.add_lowered_function(
+                    LoweredFunctionKind::ResourceNew(resource_type_id),
+                    vec![ValueType::I32],
+                    vec![ValueType::I32],
+                    CanonicalOptions::default(),
+                );
+            }
+            Canon::ResourceDrop(type_index) => {
+                let resource_type_id = self.get_resource_type_id(*type_index)?;
+                self.add_lowered_function(
+                    LoweredFunctionKind::ResourceDrop(resource_type_id),
+                    vec![ValueType::I32],
+                    vec![],
+                    CanonicalOptions::default(),
+                );
+            }
+            Canon::ResourceRep(type_index) => {
+                let resource_type_id = self.get_resource_type_id(*type_index)?;
+                self.add_lowered_function(
+                    LoweredFunctionKind::ResourceRep(resource_type_id),
+                    vec![ValueType::I32],
+                    vec![ValueType::I32],
+                    CanonicalOptions::default(),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 为降低函数创建一个本地模块，并把函数添加到核心函数的索引空间
+    fn add_lowered_function(
+        &mut self,
+        kind: LoweredFunctionKind,
+        params: Vec<ValueType>,
+        results: Vec<ValueType>,
+        canonical_options: CanonicalOptions,
+    ) {
+        let module_name = format!("canon-function-{}", self.native_modules.len());
+        let module_context = LoweredFunctionContext {
+            kind: Rc::new(kind),
+            canonical_options,
+            options: ResolvedOptions::default(),
+            component_context: Rc::clone(&self.component_context),
+        };
+
+        let mut native_module = NativeModule::new(&module_name, Box::new(module_context));
+        native_module.add_native_function(
+            LOWERED_FUNCTION_NAME,
+            params,
+            vec![],
+            results,
+            call_lowered_function,
+        );
+
+        self.native_modules.push(native_module);
+        self.core_functions.push(CoreItemRef {
+            module_name,
+            export_name: LOWERED_FUNCTION_NAME.to_owned(),
+        });
+    }
+
+    fn process_import(&mut self, import: &Import) -> Result<(), ComponentError> {
+        match import.type_ref {
+            TypeRef::Instance(type_index) => {
+                let type_exports = match self.get_type(type_index)? {
+                    ComponentType::Instance(type_exports) => type_exports.clone(),
+                    _ => {
+                        return Err(ComponentError::InvalidComponent(format!(
+                            "type {} is not an instance type",
+                            type_index
+                        )))
+                    }
+                };
+
+                let host_instance = self
+                    .host_instances
+                    .iter()
+                    .find(|item| {
+                        get_unversioned_name(&item.name) == get_unversioned_name(&import.name)
+                    })
+                    .ok_or_else(|| ComponentError::ImportNotFound(import.name.clone()))?;
+
+                let mut items: Vec<(String, InstanceExportItem)> = vec![];
+
+                for (name, type_export) in type_exports {
+                    let item = match type_export {
+                        InstanceTypeExport::Function(function_type) => {
+                            let host_function =
+                                host_instance.find_function(&name).ok_or_else(|| {
+                                    ComponentError::ImportItemNotFound(
+                                        import.name.clone(),
+                                        name.clone(),
+                                    )
+                                })?;
+
+                            InstanceExportItem::Function(FunctionItem::Host {
+                                host_function,
+                                function_type,
+                            })
+                        }
+                        InstanceTypeExport::Type(component_type) => {
+                            // 由宿主提供的资源类型，需要登记其析构函数
+                            if let ComponentType::Resource(resource_type_id) = component_type {
+                                if !self.host_resource_types.contains(&resource_type_id) {
+                                    let host_destructor =
+                                        host_instance.find_resource(&name).ok_or_else(|| {
+                                            ComponentError::ImportItemNotFound(
+                                                import.name.clone(),
+                                                name.clone(),
+                                            )
+                                        })?;
+
+                                    let mut component_context = self.component_context.borrow_mut();
+                                    component_context.resource_types[resource_type_id]
+                                        .host_destructor = host_destructor;
+                                    self.host_resource_types.push(resource_type_id);
+                                }
+                            }
+                            InstanceExportItem::Type(component_type)
+                        }
+                    };
+
+                    items.push((name, item));
+                }
+
+                self.instances.push(items);
+                Ok(())
+            }
+            TypeRef::Type(TypeBound::Eq(type_index)) => {
+                let component_type = self.get_type(type_index)?.clone();
+                self.get_current_types().push(component_type);
+                Ok(())
+            }
+            _ => Err(ComponentError::Unsupported(format!(
+                "import \"{}\" of {:?}",
+                import.name, import.type_ref
+            ))),
+        }
+    }
+
+    fn process_export(&mut self, export: &Export) -> Result<(), ComponentError> {
+        match export.sort {
+            Sort::Function => {
+                let function_item = get_item(&self.functions, export.index, "function")?.clone();
+                self.export_functions
+                    .push((export.name.clone(), function_item.clone()));
+                self.functions.push(function_item);
+            }
+            Sort::Instance => {
+                let items = get_item(&self.instances, export.index, "instance")?.clone();
+                for (name, item) in &items {
+                    if let InstanceExportItem::Function(function_item) = item {
+                        self.export_functions
+                            .push((format!("{}#{}", export.name, name), function_item.clone()));
+                    }
+                }
+                self.instances.push(items);
+            }
+            Sort::Type => {
+                let component_type = self.get_type(export.index)?.clone();
+                self.get_current_types().push(component_type);
+            }
+            _ => {
+                return Err(ComponentError::Unsupported(format!(
+                    "export \"{}\" of {:?}",
+                    export.name, export.sort
+                )))
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 创建 VM 实例，解析各个规范选项在 VM 里的位置，然后执行核心模块的 start 函数
+    fn finish(self) -> Result<ComponentInstance, ComponentError> {
+        let Instantiator {
+            component_context,
+            named_ast_modules,
+            native_modules,
+            export_functions,
+            resource_destructors,
+            ..
+        } = self;
+
+        let mut vm =
+            create_instance(native_modules, &named_ast_modules).map_err(ComponentError::Engine)?;
+
+        let resolver = CoreItemResolver {
+            named_ast_modules: &named_ast_modules,
+            memory_indexes_list: vm
+                .resource
+                .vm_modules
+                .iter()
+                .map(|vm_module| vm_module.memory_indexes.clone())
+                .collect(),
+        };
+
+        // 解析降低函数的规范选项
+        for native_module in &mut vm.resource.native_modules {
+            let module_context = native_module
+                .module_context
+                .as_any()
+                .downcast_mut::<LoweredFunctionContext>()
+                .unwrap();
+            module_context.options = resolver.resolve_options(&module_context.canonical_options)?;
+        }
+
+        // 解析资源类型的析构函数
+        for (resource_type_id, core_item_ref) in &resource_destructors {
+            let location = resolver.resolve_function(core_item_ref)?;
+            component_context.borrow_mut().resource_types[*resource_type_id].core_destructor =
+                Some(location);
+        }
+
+        let mut lifted_functions: Vec<LiftedFunction> = vec![];
+        for (name, function_item) in export_functions {
+            match &function_item {
+                FunctionItem::Lifted {
+                    core_function,
+                    function_type,
+                    options,
+                } => {
+                    let (vm_module_index, function_index) =
+                        resolver.resolve_function(core_function)?;
+                    lifted_functions.push(LiftedFunction {
+                        name,
+                        function_type: function_type.clone(),
+                        vm_module_index,
+                        function_index,
+                        options: resolver.resolve_options(options)?,
+                    });
+                }
+                FunctionItem::Host { .. } => {
+                    return Err(ComponentError::Unsupported(format!(
+                        "re-exporting imported function \"{}\"",
+                        name
+                    )))
+                }
+            }
+        }
+
+        // 按照实例化的次序执行核心模块的 start 函数
+        for (vm_module_index, named_ast_module) in named_ast_modules.iter().enumerate() {
+            if let Some(function_index) = named_ast_module.module.start_function_index {
+                vm.eval_function_by_index(vm_module_index, function_index as usize, &[])
+                    .map_err(ComponentError::Engine)?;
+            }
+        }
+
+        Ok(ComponentInstance {
+            vm,
+            context: component_context,
+            lifted_functions,
+        })
+    }
+}
+
+/// 根据核心项目的位置（模块名称以及导出名称）查找项目在 VM 里的位置
+struct CoreItemResolver<'b> {
+    named_ast_modules: &'b [NamedAstModule],
+    memory_indexes_list: Vec<Vec<usize>>,
+}
+
+impl<'b> CoreItemResolver<'b> {
+    fn get_module_index(&self, core_item_ref: &CoreItemRef) -> Result<usize, ComponentError> {
+        self.named_ast_modules
+            .iter()
+            .position(|item| item.name == core_item_ref.module_name)
+            .ok_or_else(|| {
+                // 只有降低函数（位于本地模块）不在核心模块列表里
+                ComponentError::Unsupported(format!(
+                    "using lowered function \"{}\" as canonical option or export",
+                    core_item_ref.module_name
+                ))
+            })
+    }
+
+    fn make_export_not_found_error(core_item_ref: &CoreItemRef) -> ComponentError {
+        ComponentError::InvalidComponent(format!(
+            "core instance \"{}\" has no export \"{}\"",
+            core_item_ref.module_name, core_item_ref.export_name
+        ))
+    }
+
+    /// 返回 (vm_module_index, function_index)
+    fn resolve_function(
+        &self,
+        core_item_ref: &CoreItemRef,
+    ) -> Result<(usize, usize), ComponentError> {
+        let module_index = self.get_module_index(core_item_ref)?;
+        let function_index = find_ast_module_export_function(
+            &self.named_ast_modules[module_index].module,
+            &core_item_ref.export_name,
+        )
+        .ok_or_else(|| Self::make_export_not_found_error(core_item_ref))?;
+        Ok((module_index, function_index as usize))
+    }
+
+    /// 返回内存块实例的索引
+    fn resolve_memory(&self, core_item_ref: &CoreItemRef) -> Result<usize, ComponentError> {
+        let module_index = self.get_module_index(core_item_ref)?;
+        let memory_index = self.named_ast_modules[module_index]
+            .module
+            .export_items
+            .iter()
+            .find_map(|item| match item.export_descriptor {
+                core_ast::ExportDescriptor::MemoryBlockIndex(index)
+                    if item.name == core_item_ref.export_name =>
+                {
+                    Some(index)
+                }
+                _ => None,
+            })
+            .ok_or_else(|| Self::make_export_not_found_error(core_item_ref))?;
+        Ok(self.memory_indexes_list[module_index][memory_index as usize])
+    }
+
+    fn resolve_options(
+        &self,
+        canonical_options: &CanonicalOptions,
+    ) -> Result<ResolvedOptions, ComponentError> {
+        let memory_index = match &canonical_options.memory {
+            Some(core_item_ref) => Some(self.resolve_memory(core_item_ref)?),
+            None => None,
+        };
+        let realloc = match &canonical_options.realloc {
+            Some(core_item_ref) => Some(self.resolve_function(core_item_ref)?),
+            None => None,
+        };
+        let post_return = match &canonical_options.post_return {
+            Some(core_item_ref) => Some(self.resolve_function(core_item_ref)?),
+            None => None,
+        };
+
+        Ok(ResolvedOptions {
+            memory_index,
+            realloc,
+            post_return,
+        })
+    }
+}
+
+/// 通过 VM 访问规范选项所指定的内存块以及 realloc 函数
+struct VMLinearMemory<'b> {
+    vm: &'b mut VM,
+    options: &'b ResolvedOptions,
+}
+
+impl<'b> VMLinearMemory<'b> {
+    fn new(vm: &'b mut VM, options: &'b ResolvedOptions) -> Self {
+        Self { vm, options }
+    }
+
+    fn get_memory_index(&self, address: u32, length: usize) -> Result<usize, ComponentError> {
+        let memory_index = self
+            .options
+            .memory_index
+            .ok_or(ComponentError::MissingMemoryOption)?;
+
+        let memory_size = self.vm.resource.memory_blocks[memory_index].get_size_in_bytes();
+        if address as usize + length > memory_size {
+            return Err(ComponentError::MemoryAccessOutOfRange(
+                address,
+                length as u32,
+            ));
+        }
+
+        Ok(memory_index)
+    }
+}
+
+impl<'b> LinearMemory for VMLinearMemory<'b> {
+    fn read(&mut self, address: u32, length: u32) -> Result<Vec<u8>, ComponentError> {
+        let memory_index = self.get_memory_index(address, length as usize)?;
+        Ok(self.vm.resource.memory_blocks[memory_index]
+            .read_bytes(address as usize, length as usize))
+    }
+
+    fn write(&mut self, address: u32, data: &[u8]) -> Result<(), ComponentError> {
+        let memory_index = self.get_memory_index(address, data.len())?;
+        self.vm.resource.memory_blocks[memory_index].write_bytes(address as usize, data);
+        Ok(())
+    }
+
+    fn realloc(
+        &mut self,
+        original_ptr: u32,
+        original_size: u32,
+        alignment: u32,
+        new_size: u32,
+    ) -> Result<u32, ComponentError> {
+        let (vm_module_index, function_index) = self
+            .options
+            .realloc
+            .ok_or(ComponentError::MissingReallocOption)?;
+
+        let arguments = vec![
+            Value::I32(original_ptr as i32),
+            Value::I32(original_size as i32),
+            Value::I32(alignment as i32),
+            Value::I32(new_size as i32),
+        ];
+
+        // realloc 有可能在降低函数（即本地函数）执行期间被调用，所以需要使用重入的方式
+        let results = self
+            .vm
+            .eval_function_reentrant(vm_module_index, function_index, &arguments)
+            .map_err(ComponentError::Engine)?;
+
+        match results.first() {
+            Some(Value::I32(address)) => Ok(*address as u32),
+            _ => Err(ComponentError::ValueTypeMismatch(
+                "realloc should return an i32 value".to_owned(),
+            )),
+        }
+    }
+}
+
+/// 降低函数的本地函数实现
+fn call_lowered_function(
+    vm: &mut VM,
+    native_module_index: usize,
+    arguments: &[Value],
+) -> Result<Vec<Value>, NativeTerminate> {
+    let native_module = &mut vm.resource.native_modules[native_module_index];
+    let module_name = native_module.name.clone();
+    let module_context = native_module
+        .module_context
+        .as_any()
+        .downcast_mut::<LoweredFunctionContext>()
+        .unwrap();
+
+    let kind = Rc::clone(&module_context.kind);
+    let options = module_context.options;
+    let component_context = Rc::clone(&module_context.component_context);
+
+    invoke_lowered_function(vm, &kind, &options, &component_context, arguments)
+        .map_err(|e| e.to_native_terminate(&module_name))
+}
+
+fn invoke_lowered_function(
+    vm: &mut VM,
+    kind: &LoweredFunctionKind,
+    options: &ResolvedOptions,
+    component_context: &Rc<RefCell<ComponentContext>>,
+    arguments: &[Value],
+) -> Result<Vec<Value>, ComponentError> {
+    match kind {
+        LoweredFunctionKind::Host {
+            host_function,
+            function_type,
+        } => call_host_function(
+            vm,
+            component_context,
+            options,
+            *host_function,
+            function_type,
+            arguments,
+        ),
+        LoweredFunctionKind::ResourceNew(resource_type_id) => {
+            let representation = get_i32_argument(arguments)?;
+            let handle = component_context.borrow_mut().handles.insert(HandleEntry {
+                resource_type_id: *resource_type_id,
+                representation,
+                own: true,
+            });
+            Ok(vec![Value::I32(handle as i32)])
+        }
+        LoweredFunctionKind::ResourceDrop(resource_type_id) => {
+            drop_resource(vm, component_context, *resource_type_id, arguments)
+        }
+        LoweredFunctionKind::ResourceRep(resource_type_id) => {
+            let handle = get_i32_argument(arguments)?;
+            let component_context_ref = component_context.borrow();
+            let entry = component_context_ref.handles.get(handle)?;
+            if entry.resource_type_id != *resource_type_id {
+                Err(ComponentError::InvalidHandle(handle))
+            } else {
+                Ok(vec![Value::I32(entry.representation as i32)])
+            }
+        }
+    }
+}
+
+fn get_i32_argument(arguments: &[Value]) -> Result<u32, ComponentError> {
+    match arguments.first() {
+        Some(Value::I32(value)) => Ok(*value as u32),
+        _ => Err(ComponentError::ValueTypeMismatch(format!(
+            "expected an i32 argument, actual: {:?}",
+            arguments
+        ))),
+    }
+}
+
+fn call_host_function(
+    vm: &mut VM,
+    component_context: &Rc<RefCell<ComponentContext>>,
+    options: &ResolvedOptions,
+    host_function: HostFunction,
+    function_type: &InterfaceFunctionType,
+    arguments: &[Value],
+) -> Result<Vec<Value>, ComponentError> {
+    let param_types = function_type
+        .params
+        .iter()
+        .map(|(_, param_type)| param_type.clone())
+        .collect::<Vec<InterfaceType>>();
+    let result_types = function_type
+        .result
+        .iter()
+        .cloned()
+        .collect::<Vec<InterfaceType>>();
+
+    // 当返回值需要通过内存传递时，最后一个实参是存放返回值的地址
+    let is_result_in_memory = result_types
+        .iter()
+        .any(|result_type| flatten_type(result_type).len() > MAX_FLAT_RESULTS);
+
+    let (param_values, option_result_address) = if is_result_in_memory {
+        let (last, rest) = arguments.split_last().ok_or_else(|| {
+            ComponentError::ValueTypeMismatch("missing result address".to_owned())
+        })?;
+        (rest, Some(get_i32_argument(std::slice::from_ref(last))?))
+    } else {
+        (arguments, None)
+    };
+
+    let params = {
+        let mut component_context_ref = component_context.borrow_mut();
+        let mut memory = VMLinearMemory::new(vm, options);
+        let mut call_context = CallContext::new(&mut memory, &mut component_context_ref.handles);
+        lift_values(
+            &mut call_context,
+            &param_types,
+            param_values,
+            MAX_FLAT_PARAMS,
+        )?
+    };
+
+    let option_result = {
+        let mut component_context_ref = component_context.borrow_mut();
+        host_function(component_context_ref.host_context.as_mut(), &params)
+            .map_err(|e| ComponentError::Engine(EngineError::NativeTerminate(e)))?
+    };
+
+    let results = option_result.into_iter().collect::<Vec<ComponentValue>>();
+
+    let mut component_context_ref = component_context.borrow_mut();
+    let mut memory = VMLinearMemory::new(vm, options);
+    let mut call_context = CallContext::new(&mut memory, &mut component_context_ref.handles);
+    lower_values(
+        &mut call_context,
+        &results,
+        &result_types,
+        MAX_FLAT_RESULTS,
+        option_result_address,
+    )
+}
+
+fn drop_resource(
+    vm: &mut VM,
+    component_context: &Rc<RefCell<ComponentContext>>,
+    resource_type_id: ResourceTypeId,
+    arguments: &[Value],
+) -> Result<Vec<Value>, ComponentError> {
+    let handle = get_i32_argument(arguments)?;
+
+    let (entry, host_destructor, core_destructor) = {
+        let mut component_context_ref = component_context.borrow_mut();
+        if component_context_ref.handles.get(handle)?.resource_type_id != resource_type_id {
+            return Err(ComponentError::InvalidHandle(handle));
+        }
+
+        let entry = component_context_ref.handles.remove(handle)?;
+        let resource_type = &component_context_ref.resource_types[resource_type_id];
+        (
+            entry,
+            resource_type.host_destructor,
+            resource_type.core_destructor,
+        )
+    };
+
+    // 丢弃 borrow 句柄不会析构资源
+    if entry.own {
+        if let Some(destructor) = host_destructor {
+            let mut component_context_ref = component_context.borrow_mut();
+            destructor(
+                component_context_ref.host_context.as_mut(),
+                entry.representation,
+            )
+            .map_err(|e| ComponentError::Engine(EngineError::NativeTerminate(e)))?;
+        }
+
+        if let Some((vm_module_index, function_index)) = core_destructor {
+            vm.eval_function_reentrant(
+                vm_module_index,
+                function_index,
+                &[Value::I32(entry.representation as i32)],
+            )
+            .map_err(ComponentError::Engine)?;
+        }
+    }
+
+    Ok(vec![])
+}
+
+fn get_item<'b, T>(items: &'b [T], index: u32, item_name: &str) -> Result<&'b T, ComponentError> {
+    items.get(index as usize).ok_or_else(|| {
+        ComponentError::InvalidComponent(format!("{} index {} out of range", item_name, index))
+    })
+}
+
+fn make_not_function_type_error(type_index: u32) -> ComponentError {
+    ComponentError::InvalidComponent(format!("type {} is not a function type", type_index))
+}
+
+fn resolve_primitive_type(primitive_type: &PrimitiveType) -> InterfaceType {
+    match primitive_type {
+        PrimitiveType::Bool => InterfaceType::Bool,
+        PrimitiveType::S8 => InterfaceType::S8,
+        PrimitiveType::U8 => InterfaceType::U8,
+        PrimitiveType::S16 => InterfaceType::S16,
+        PrimitiveType::U16 => InterfaceType::U16,
+        PrimitiveType::S32 => InterfaceType::S32,
+        PrimitiveType::U32 => InterfaceType::U32,
+        PrimitiveType::S64 => InterfaceType::S64,
+        PrimitiveType::U64 => InterfaceType::U64,
+        PrimitiveType::F32 => InterfaceType::F32,
+        PrimitiveType::F64 => InterfaceType::F64,
+        PrimitiveType::Char => InterfaceType::Char,
+        PrimitiveType::String => InterfaceType::String,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use anvm_engine::native_module::ModuleContext;
+
+    use crate::{
+        error::ComponentError,
+        parser::{is_component, parse},
+        types::ComponentValue,
+    };
+
+    use super::{instantiate, ComponentInstance};
+    use pretty_assertions::assert_eq;
+
+    struct EmptyContext;
+
+    impl ModuleContext for EmptyContext {
+        fn as_any(&mut self) -> &mut dyn std::any::Any {
+            self
+        }
+    }
+
+    // 辅助方法
+    fn get_test_binary_resource(filename: &str) -> Vec<u8> {
+        let mut path_buf = env::current_dir().unwrap();
+        if !path_buf.ends_with("component") {
+            path_buf.push("crates");
+            path_buf.push("component");
+        }
+        let fullname_buf = path_buf.join("resources").join(filename);
+        let fullname = fullname_buf.to_str().unwrap();
+        fs::read(fullname)
+            .unwrap_or_else(|_| panic!("failed to read the specified binary file: {}", fullname))
+    }
+
+    fn get_test_component_instance(filename: &str) -> ComponentInstance {
+        let bytes = get_test_binary_resource(filename);
+        assert!(is_component(&bytes));
+
+        let component = parse(&bytes).unwrap();
+        instantiate(&component, &[], Box::new(EmptyContext)).unwrap()
+    }
+
+    #[test]
+    fn test_export_function_names() {
+        let component_instance = get_test_component_instance("test-canonical.wasm");
+        assert_eq!(
+            component_instance.get_export_function_names(),
+            vec!["echo", "add", "checked-div", "sum"]
+        );
+    }
+
+    #[test]
+    fn test_call_string_and_record() {
+        let mut component_instance = get_test_component_instance("test-canonical.wasm");
+
+        assert_eq!(
+            component_instance
+                .call("echo", &[ComponentValue::String("你好, world".to_owned())])
+                .unwrap(),
+            Some(ComponentValue::String("你好, world".to_owned()))
+        );
+
+        assert_eq!(
+            component_instance
+                .call(
+                    "add",
+                    &[ComponentValue::Record(vec![
+                        ("x".to_owned(), ComponentValue::U32(11)),
+                        ("y".to_owned(), ComponentValue::U32(22)),
+                    ])]
+                )
+                .unwrap(),
+            Some(ComponentValue::U32(33))
+        );
+    }
+
+    #[test]
+    fn test_call_result_and_list() {
+        let mut component_instance = get_test_component_instance("test-canonical.wasm");
+
+        assert_eq!(
+            component_instance
+                .call(
+                    "checked-div",
+                    &[ComponentValue::U32(20), ComponentValue::U32(3)]
+                )
+                .unwrap(),
+            Some(ComponentValue::Result(Ok(Some(Box::new(
+                ComponentValue::U32(6)
+            )))))
+        );
+
+        assert_eq!(
+            component_instance
+                .call(
+                    "checked-div",
+                    &[ComponentValue::U32(20), ComponentValue::U32(0)]
+                )
+                .unwrap(),
+            Some(ComponentValue::Result(Err(Some(Box::new(
+                ComponentValue::String("division by zero".to_owned())
+            )))))
+        );
+
+        assert_eq!(
+            component_instance
+                .call(
+                    "sum",
+                    &[ComponentValue::List(vec![
+                        ComponentValue::U32(1),
+                        ComponentValue::U32(2),
+                        ComponentValue::U32(3),
+                        ComponentValue::U32(4),
+                    ])]
+                )
+                .unwrap(),
+            Some(ComponentValue::U32(10))
+        );
+    }
+
+    #[test]
+    fn test_call_errors() {
+        let mut component_instance = get_test_component_instance("test-canonical.wasm");
+
+        assert!(matches!(
+            component_instance.call("not-exist", &[]),
+            Err(ComponentError::ExportNotFound(_))
+        ));
+
+        assert!(matches!(
+            component_instance.call("add", &[ComponentValue::U32(1)]),
+            Err(ComponentError::ValueTypeMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn test_missing_import() {
+        let bytes = get_test_binary_resource("hello-world.wasm");
+        let component = parse(&bytes).unwrap();
+        assert!(matches!(
+            instantiate(&component, &[], Box::new(EmptyContext)),
+            Err(ComponentError::ImportNotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,15 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+pub mod ast;
+pub mod error;
+pub mod parser;
+pub mod types;
+
+pub mod canonical_abi;
+pub mod host;
+pub mod instance;
+pub mod wasi;
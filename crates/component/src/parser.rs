@@ -0,0 +1,789 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 解析组件二进制
+//!
+//! component = magic_number:u32 + version:u16 + layer:u16 + <section>
+//!
+//! 组件的幻数跟核心模块的一样，版本号为 0x0d，层号为 1（核心模块的层号为 0）。
+
+use anvm_ast::types as core_types;
+use anvm_binary_parser::{leb128decoder, parser as core_parser};
+
+use crate::{
+    ast::{
+        Alias, Canon, CanonicalOption, Case, Component, CoreInlineExport, CoreInstance,
+        CoreInstantiationArgument, CoreSort, DefinedType, Definition, Export, FunctionType, Import,
+        InlineExport, Instance, InstantiationArgument, PrimitiveType, Sort, Start, TypeBound,
+        TypeDeclaration, TypeDefinition, TypeRef, ValueType,
+    },
+    error::ParseError,
+};
+
+const MAGIC_NUMBER: u32 = 0x6d736100; // `\0asm`
+const VERSION: u16 = 0x0d;
+const LAYER: u16 = 0x01;
+
+const SECTION_ID_CUSTOM: u8 = 0;
+const SECTION_ID_CORE_MODULE: u8 = 1;
+const SECTION_ID_CORE_INSTANCE: u8 = 2;
+const SECTION_ID_CORE_TYPE: u8 = 3;
+const SECTION_ID_COMPONENT: u8 = 4;
+const SECTION_ID_INSTANCE: u8 = 5;
+const SECTION_ID_ALIAS: u8 = 6;
+const SECTION_ID_TYPE: u8 = 7;
+const SECTION_ID_CANON: u8 = 8;
+const SECTION_ID_START: u8 = 9;
+const SECTION_ID_IMPORT: u8 = 10;
+const SECTION_ID_EXPORT: u8 = 11;
+
+pub fn parse(source: &[u8]) -> Result<Component, ParseError> {
+    let (magic_number, post_magic_number) = read_fixed_u32(source)?;
+    if magic_number != MAGIC_NUMBER {
+        return Err(ParseError::UnsupportedFormat(magic_number));
+    }
+
+    let (version, post_version) = read_fixed_u16(post_magic_number)?;
+    let (layer, post_layer) = read_fixed_u16(post_version)?;
+    if version != VERSION || layer != LAYER {
+        return Err(ParseError::UnsupportedVersion(version, layer));
+    }
+
+    parse_sections(post_layer)
+}
+
+/// 判断二进制数据是否为组件（而不是核心模块）
+pub fn is_component(source: &[u8]) -> bool {
+    source.len() >= 8 && source[4..8] == [0x0d, 0x00, 0x01, 0x00]
+}
+
+/// section = section_id:u8 + byte_length:u32 + section_data
+///
+/// 跟核心模块不同，组件的各个段可以以任意次序出现，而且可以重复出现。
+fn parse_sections(source: &[u8]) -> Result<Component, ParseError> {
+    let mut definitions: Vec<Definition> = vec![];
+    let mut remains = source;
+
+    while !remains.is_empty() {
+        let (section_id, post_section_id) = read_byte(remains)?;
+        let (section_length, post_section_length) = read_u32(post_section_id)?;
+        let (section_data, post_section_data) =
+            read_bytes(post_section_length, section_length as usize)?;
+
+        match section_id {
+            SECTION_ID_CUSTOM => {
+                // 忽略自定义段
+            }
+            SECTION_ID_CORE_MODULE => {
+                let module = core_parser::parse(section_data).map_err(ParseError::CoreModule)?;
+                definitions.push(Definition::CoreModule(Box::new(module)));
+            }
+            SECTION_ID_CORE_INSTANCE => {
+                let items = parse_items(section_data, continue_parse_core_instance)?;
+                definitions.extend(items.into_iter().map(Definition::CoreInstance));
+            }
+            SECTION_ID_CORE_TYPE => {
+                // 核心类型仅用于类型检查，这里只需保留其数量以便计算索引
+                let (item_count, _) = read_u32(section_data)?;
+                definitions.extend((0..item_count).map(|_| Definition::CoreType));
+            }
+            SECTION_ID_COMPONENT => {
+                let component = parse(section_data)?;
+                definitions.push(Definition::Component(component));
+            }
+            SECTION_ID_INSTANCE => {
+                let items = parse_items(section_data, continue_parse_instance)?;
+                definitions.extend(items.into_iter().map(Definition::Instance));
+            }
+            SECTION_ID_ALIAS => {
+                let items = parse_items(section_data, continue_parse_alias)?;
+                definitions.extend(items.into_iter().map(Definition::Alias));
+            }
+            SECTION_ID_TYPE => {
+                let items = parse_items(section_data, continue_parse_type_definition)?;
+                definitions.extend(items.into_iter().map(Definition::Type));
+            }
+            SECTION_ID_CANON => {
+                let items = parse_items(section_data, continue_parse_canon)?;
+                definitions.extend(items.into_iter().map(Definition::Canon));
+            }
+            SECTION_ID_START => {
+                let (start, _) = continue_parse_start(section_data)?;
+                definitions.push(Definition::Start(start));
+            }
+            SECTION_ID_IMPORT => {
+                let items = parse_items(section_data, continue_parse_import)?;
+                definitions.extend(items.into_iter().map(Definition::Import));
+            }
+            SECTION_ID_EXPORT => {
+                let items = parse_items(section_data, continue_parse_export)?;
+                definitions.extend(items.into_iter().map(Definition::Export));
+            }
+            _ => return Err(ParseError::InvalidSectionId(section_id)),
+        }
+
+        remains = post_section_data;
+    }
+
+    Ok(Component { definitions })
+}
+
+/// 读取如下结构的数组
+/// `length:u32 + item{*}`
+fn parse_items<T, F>(source: &[u8], continue_parse_item: F) -> Result<Vec<T>, ParseError>
+where
+    F: Fn(&[u8]) -> Result<(T, &[u8]), ParseError>,
+{
+    let (items, _) = continue_parse_items(source, continue_parse_item)?;
+    Ok(items)
+}
+
+fn continue_parse_items<T, F>(
+    source: &[u8],
+    continue_parse_item: F,
+) -> Result<(Vec<T>, &[u8]), ParseError>
+where
+    F: Fn(&[u8]) -> Result<(T, &[u8]), ParseError>,
+{
+    let (item_count, post_item_count) = read_u32(source)?;
+    let mut remains = post_item_count;
+    let mut items: Vec<T> = vec![];
+
+    for _ in 0..item_count {
+        let (item, post_item) = continue_parse_item(remains)?;
+        items.push(item);
+        remains = post_item;
+    }
+
+    Ok((items, remains))
+}
+
+/// core_instance = 0x00 module_index:u32 vec(name:string 0x12 instance_index:u32)
+///               | 0x01 vec(name:string core_sort:byte index:u32)
+fn continue_parse_core_instance(source: &[u8]) -> Result<(CoreInstance, &[u8]), ParseError> {
+    let (tag, post_tag) = read_byte(source)?;
+    match tag {
+        0x00 => {
+            let (module_index, post_module_index) = read_u32(post_tag)?;
+            let (arguments, remains) = continue_parse_items(post_module_index, |s| {
+                let (name, post_name) = read_string(s)?;
+                let (sort_tag, post_sort_tag) = read_byte(post_name)?;
+                if sort_tag != 0x12 {
+                    return Err(ParseError::InvalidTag(
+                        "core instantiation argument".to_owned(),
+                        sort_tag,
+                    ));
+                }
+                let (instance_index, post_instance_index) = read_u32(post_sort_tag)?;
+                Ok((
+                    CoreInstantiationArgument {
+                        name,
+                        instance_index,
+                    },
+                    post_instance_index,
+                ))
+            })?;
+
+            Ok((
+                CoreInstance::Instantiate {
+                    module_index,
+                    arguments,
+                },
+                remains,
+            ))
+        }
+        0x01 => {
+            let (exports, remains) = continue_parse_items(post_tag, |s| {
+                let (name, post_name) = read_string(s)?;
+                let (sort, post_sort) = continue_parse_core_sort(post_name)?;
+                let (index, post_index) = read_u32(post_sort)?;
+                Ok((CoreInlineExport { name, sort, index }, post_index))
+            })?;
+            Ok((CoreInstance::FromExports(exports), remains))
+        }
+        _ => Err(ParseError::InvalidTag("core instance".to_owned(), tag)),
+    }
+}
+
+fn continue_parse_core_sort(source: &[u8]) -> Result<(CoreSort, &[u8]), ParseError> {
+    let (tag, post_tag) = read_byte(source)?;
+    let sort = match tag {
+        0x00 => CoreSort::Function,
+        0x01 => CoreSort::Table,
+        0x02 => CoreSort::Memory,
+        0x03 => CoreSort::Global,
+        0x04 => CoreSort::Tag,
+        0x10 => CoreSort::Type,
+        0x11 => CoreSort::Module,
+        0x12 => CoreSort::Instance,
+        _ => return Err(ParseError::InvalidTag("core sort".to_owned(), tag)),
+    };
+    Ok((sort, post_tag))
+}
+
+/// sort = 0x00 core_sort | 0x01 func | 0x02 value | 0x03 type | 0x04 component | 0x05 instance
+fn continue_parse_sort(source: &[u8]) -> Result<(Sort, &[u8]), ParseError> {
+    let (tag, post_tag) = read_byte(source)?;
+    match tag {
+        0x00 => {
+            let (core_sort, post_core_sort) = continue_parse_core_sort(post_tag)?;
+            Ok((Sort::Core(core_sort), post_core_sort))
+        }
+        0x01 => Ok((Sort::Function, post_tag)),
+        0x02 => Ok((Sort::Value, post_tag)),
+        0x03 => Ok((Sort::Type, post_tag)),
+        0x04 => Ok((Sort::Component, post_tag)),
+        0x05 => Ok((Sort::Instance, post_tag)),
+        _ => Err(ParseError::InvalidTag("sort".to_owned(), tag)),
+    }
+}
+
+/// instance = 0x00 component_index:u32 vec(name:string sort index:u32)
+///          | 0x01 vec(export_name sort index:u32)
+fn continue_parse_instance(source: &[u8]) -> Result<(Instance, &[u8]), ParseError> {
+    let (tag, post_tag) = read_byte(source)?;
+    match tag {
+        0x00 => {
+            let (component_index, post_component_index) = read_u32(post_tag)?;
+            let (arguments, remains) = continue_parse_items(post_component_index, |s| {
+                let (name, post_name) = read_string(s)?;
+                let (sort, post_sort) = continue_parse_sort(post_name)?;
+                let (index, post_index) = read_u32(post_sort)?;
+                Ok((InstantiationArgument { name, sort, index }, post_index))
+            })?;
+            Ok((
+                Instance::Instantiate {
+                    component_index,
+                    arguments,
+                },
+                remains,
+            ))
+        }
+        0x01 => {
+            let (exports, remains) = continue_parse_items(post_tag, |s| {
+                let (name, post_name) = continue_parse_extern_name(s)?;
+                let (sort, post_sort) = continue_parse_sort(post_name)?;
+                let (index, post_index) = read_u32(post_sort)?;
+                Ok((InlineExport { name, sort, index }, post_index))
+            })?;
+            Ok((Instance::FromExports(exports), remains))
+        }
+        _ => Err(ParseError::InvalidTag("instance".to_owned(), tag)),
+    }
+}
+
+/// 导入名称以及导出名称
+///
+/// extern_name = (0x00 | 0x01) name:string
+///
+/// 前缀 0x01 是旧版本的格式，用于表示 `a:b/c` 形式的接口名称，这里一律忽略前缀。
+fn continue_parse_extern_name(source: &[u8]) -> Result<(String, &[u8]), ParseError> {
+    let (tag, post_tag) = read_byte(source)?;
+    match tag {
+        0x00 | 0x01 => read_string(post_tag),
+        _ => Err(ParseError::InvalidTag("extern name".to_owned(), tag)),
+    }
+}
+
+/// alias = sort target
+///
+/// target = 0x00 instance_index:u32 name:string       ;; 组件实例的导出项
+///        | 0x01 instance_index:u32 name:string       ;; 核心实例的导出项
+///        | 0x02 count:u32 index:u32                  ;; 外层项目
+fn continue_parse_alias(source: &[u8]) -> Result<(Alias, &[u8]), ParseError> {
+    let (sort, post_sort) = continue_parse_sort(source)?;
+    let (tag, post_tag) = read_byte(post_sort)?;
+
+    match tag {
+        0x00 => {
+            let (instance_index, post_instance_index) = read_u32(post_tag)?;
+            let (name, post_name) = read_string(post_instance_index)?;
+            Ok((
+                Alias::InstanceExport {
+                    sort,
+                    instance_index,
+                    name,
+                },
+                post_name,
+            ))
+        }
+        0x01 => {
+            let core_sort = match sort {
+                Sort::Core(core_sort) => core_sort,
+                _ => return Err(ParseError::InvalidTag("core alias sort".to_owned(), tag)),
+            };
+            let (instance_index, post_instance_index) = read_u32(post_tag)?;
+            let (name, post_name) = read_string(post_instance_index)?;
+            Ok((
+                Alias::CoreInstanceExport {
+                    sort: core_sort,
+                    instance_index,
+                    name,
+                },
+                post_name,
+            ))
+        }
+        0x02 => {
+            let (count, post_count) = read_u32(post_tag)?;
+            let (index, post_index) = read_u32(post_count)?;
+            Ok((Alias::Outer { sort, count, index }, post_index))
+        }
+        _ => Err(ParseError::InvalidTag("alias target".to_owned(), tag)),
+    }
+}
+
+fn get_primitive_type(tag: u8) -> Option<PrimitiveType> {
+    let primitive_type = match tag {
+        0x7f => PrimitiveType::Bool,
+        0x7e => PrimitiveType::S8,
+        0x7d => PrimitiveType::U8,
+        0x7c => PrimitiveType::S16,
+        0x7b => PrimitiveType::U16,
+        0x7a => PrimitiveType::S32,
+        0x79 => PrimitiveType::U32,
+        0x78 => PrimitiveType::S64,
+        0x77 => PrimitiveType::U64,
+        0x76 => PrimitiveType::F32,
+        0x75 => PrimitiveType::F64,
+        0x74 => PrimitiveType::Char,
+        0x73 => PrimitiveType::String,
+        _ => return None,
+    };
+    Some(primitive_type)
+}
+
+/// value_type = primitive_type | type_index:s33
+fn continue_parse_value_type(source: &[u8]) -> Result<(ValueType, &[u8]), ParseError> {
+    let (tag, post_tag) = read_byte(source)?;
+    if let Some(primitive_type) = get_primitive_type(tag) {
+        return Ok((ValueType::Primitive(primitive_type), post_tag));
+    }
+
+    let (type_index, post_type_index) = read_s33(source)?;
+    if type_index < 0 || type_index > u32::MAX as i64 {
+        return Err(ParseError::InvalidTag("value type".to_owned(), tag));
+    }
+
+    Ok((ValueType::TypeIndex(type_index as u32), post_type_index))
+}
+
+/// option_value_type = 0x00 | 0x01 value_type
+fn continue_parse_option_value_type(
+    source: &[u8],
+) -> Result<(Option<ValueType>, &[u8]), ParseError> {
+    let (tag, post_tag) = read_byte(source)?;
+    match tag {
+        0x00 => Ok((None, post_tag)),
+        0x01 => {
+            let (value_type, post_value_type) = continue_parse_value_type(post_tag)?;
+            Ok((Some(value_type), post_value_type))
+        }
+        _ => Err(ParseError::InvalidTag(
+            "optional value type".to_owned(),
+            tag,
+        )),
+    }
+}
+
+fn continue_parse_named_value_type(
+    source: &[u8],
+) -> Result<((String, ValueType), &[u8]), ParseError> {
+    let (name, post_name) = read_string(source)?;
+    let (value_type, post_value_type) = continue_parse_value_type(post_name)?;
+    Ok(((name, value_type), post_value_type))
+}
+
+fn continue_parse_type_definition(source: &[u8]) -> Result<(TypeDefinition, &[u8]), ParseError> {
+    let (tag, post_tag) = read_byte(source)?;
+
+    match tag {
+        0x3f => {
+            let (representation_tag, post_representation) = read_byte(post_tag)?;
+            if representation_tag != 0x7f {
+                return Err(ParseError::Unsupported(
+                    "resource representation type".to_owned(),
+                ));
+            }
+
+            let (destructor_tag, post_destructor_tag) = read_byte(post_representation)?;
+            let (destructor, remains) = match destructor_tag {
+                0x00 => (None, post_destructor_tag),
+                0x01 => {
+                    let (function_index, post_function_index) = read_u32(post_destructor_tag)?;
+                    (Some(function_index), post_function_index)
+                }
+                _ => {
+                    return Err(ParseError::InvalidTag(
+                        "resource destructor".to_owned(),
+                        destructor_tag,
+                    ))
+                }
+            };
+
+            Ok((
+                TypeDefinition::Resource {
+                    representation: core_types::ValueType::I32,
+                    destructor,
+                },
+                remains,
+            ))
+        }
+        0x40 => {
+            let (function_type, remains) = continue_parse_function_type(post_tag)?;
+            Ok((TypeDefinition::Function(function_type), remains))
+        }
+        0x43 => Err(ParseError::Unsupported("async function type".to_owned())),
+        0x41 => {
+            let (declarations, remains) =
+                continue_parse_items(post_tag, |s| continue_parse_type_declaration(s, true))?;
+            Ok((TypeDefinition::Component(declarations), remains))
+        }
+        0x42 => {
+            let (declarations, remains) =
+                continue_parse_items(post_tag, |s| continue_parse_type_declaration(s, false))?;
+            Ok((TypeDefinition::Instance(declarations), remains))
+        }
+        _ => {
+            let (defined_type, remains) = continue_parse_defined_type(tag, post_tag)?;
+            Ok((TypeDefinition::Defined(defined_type), remains))
+        }
+    }
+}
+
+/// function_type = vec(name:string value_type) result
+///
+/// result = 0x00 value_type | 0x01 0x00
+fn continue_parse_function_type(source: &[u8]) -> Result<(FunctionType, &[u8]), ParseError> {
+    let (params, post_params) = continue_parse_items(source, continue_parse_named_value_type)?;
+    let (tag, post_tag) = read_byte(post_params)?;
+
+    let (result, remains) = match tag {
+        0x00 => {
+            let (value_type, post_value_type) = continue_parse_value_type(post_tag)?;
+            (Some(value_type), post_value_type)
+        }
+        0x01 => {
+            let (count, post_count) = read_byte(post_tag)?;
+            if count != 0x00 {
+                return Err(ParseError::InvalidTag("function results".to_owned(), count));
+            }
+            (None, post_count)
+        }
+        _ => return Err(ParseError::InvalidTag("function result".to_owned(), tag)),
+    };
+
+    Ok((FunctionType { params, result }, remains))
+}
+
+fn continue_parse_defined_type(tag: u8, source: &[u8]) -> Result<(DefinedType, &[u8]), ParseError> {
+    if let Some(primitive_type) = get_primitive_type(tag) {
+        return Ok((DefinedType::Primitive(primitive_type), source));
+    }
+
+    match tag {
+        0x72 => {
+            let (fields, remains) = continue_parse_items(source, continue_parse_named_value_type)?;
+            Ok((DefinedType::Record(fields), remains))
+        }
+        0x71 => {
+            let (cases, remains) = continue_parse_items(source, |s| {
+                let (name, post_name) = read_string(s)?;
+                let (value_type, post_value_type) = continue_parse_option_value_type(post_name)?;
+                let (refines, post_refines) = read_byte(post_value_type)?;
+                if refines != 0x00 {
+                    return Err(ParseError::InvalidTag("variant case".to_owned(), refines));
+                }
+                Ok((Case { name, value_type }, post_refines))
+            })?;
+            Ok((DefinedType::Variant(cases), remains))
+        }
+        0x70 => {
+            let (value_type, remains) = continue_parse_value_type(source)?;
+            Ok((DefinedType::List(value_type), remains))
+        }
+        0x6f => {
+            let (value_types, remains) = continue_parse_items(source, continue_parse_value_type)?;
+            Ok((DefinedType::Tuple(value_types), remains))
+        }
+        0x6e => {
+            let (names, remains) = continue_parse_items(source, read_string)?;
+            Ok((DefinedType::Flags(names), remains))
+        }
+        0x6d => {
+            let (names, remains) = continue_parse_items(source, read_string)?;
+            Ok((DefinedType::Enum(names), remains))
+        }
+        0x6b => {
+            let (value_type, remains) = continue_parse_value_type(source)?;
+            Ok((DefinedType::Option(value_type), remains))
+        }
+        0x6a => {
+            let (ok, post_ok) = continue_parse_option_value_type(source)?;
+            let (error, post_error) = continue_parse_option_value_type(post_ok)?;
+            Ok((DefinedType::Result { ok, error }, post_error))
+        }
+        0x69 => {
+            let (type_index, remains) = read_u32(source)?;
+            Ok((DefinedType::Own(type_index), remains))
+        }
+        0x68 => {
+            let (type_index, remains) = read_u32(source)?;
+            Ok((DefinedType::Borrow(type_index), remains))
+        }
+        _ => Err(ParseError::InvalidTag("defined type".to_owned(), tag)),
+    }
+}
+
+/// 组件类型以及实例类型的声明项
+///
+/// declaration = 0x00 core_type | 0x01 type | 0x02 alias | 0x03 import | 0x04 export
+fn continue_parse_type_declaration(
+    source: &[u8],
+    allow_import: bool,
+) -> Result<(TypeDeclaration, &[u8]), ParseError> {
+    let (tag, post_tag) = read_byte(source)?;
+    match tag {
+        0x00 => Err(ParseError::Unsupported("core type declaration".to_owned())),
+        0x01 => {
+            let (type_definition, remains) = continue_parse_type_definition(post_tag)?;
+            Ok((TypeDeclaration::Type(type_definition), remains))
+        }
+        0x02 => {
+            let (alias, remains) = continue_parse_alias(post_tag)?;
+            Ok((TypeDeclaration::Alias(alias), remains))
+        }
+        0x03 if allow_import => {
+            let (import, remains) = continue_parse_import(post_tag)?;
+            Ok((TypeDeclaration::Import(import), remains))
+        }
+        0x04 => {
+            let (export, remains) = continue_parse_import(post_tag)?;
+            Ok((TypeDeclaration::Export(export), remains))
+        }
+        _ => Err(ParseError::InvalidTag("type declaration".to_owned(), tag)),
+    }
+}
+
+/// type_ref = 0x00 0x11 type_index:u32  ;; core module
+///          | 0x01 type_index:u32       ;; func
+///          | 0x02 value_type           ;; value
+///          | 0x03 type_bound           ;; type
+///          | 0x04 type_index:u32       ;; component
+///          | 0x05 type_index:u32       ;; instance
+fn continue_parse_type_ref(source: &[u8]) -> Result<(TypeRef, &[u8]), ParseError> {
+    let (sort, post_sort) = continue_parse_sort(source)?;
+    match sort {
+        Sort::Core(CoreSort::Module) => {
+            let (type_index, remains) = read_u32(post_sort)?;
+            Ok((TypeRef::Module(type_index), remains))
+        }
+        Sort::Function => {
+            let (type_index, remains) = read_u32(post_sort)?;
+            Ok((TypeRef::Function(type_index), remains))
+        }
+        Sort::Value => {
+            let (value_type, remains) = continue_parse_value_type(post_sort)?;
+            Ok((TypeRef::Value(value_type), remains))
+        }
+        Sort::Type => {
+            let (tag, post_tag) = read_byte(post_sort)?;
+            match tag {
+                0x00 => {
+                    let (type_index, remains) = read_u32(post_tag)?;
+                    Ok((TypeRef::Type(TypeBound::Eq(type_index)), remains))
+                }
+                0x01 => Ok((TypeRef::Type(TypeBound::SubResource), post_tag)),
+                _ => Err(ParseError::InvalidTag("type bound".to_owned(), tag)),
+            }
+        }
+        Sort::Component => {
+            let (type_index, remains) = read_u32(post_sort)?;
+            Ok((TypeRef::Component(type_index), remains))
+        }
+        Sort::Instance => {
+            let (type_index, remains) = read_u32(post_sort)?;
+            Ok((TypeRef::Instance(type_index), remains))
+        }
+        Sort::Core(_) => Err(ParseError::InvalidTag("type ref".to_owned(), source[1])),
+    }
+}
+
+/// import = import_name type_ref
+fn continue_parse_import(source: &[u8]) -> Result<(Import, &[u8]), ParseError> {
+    let (name, post_name) = continue_parse_extern_name(source)?;
+    let (type_ref, post_type_ref) = continue_parse_type_ref(post_name)?;
+    Ok((Import { name, type_ref }, post_type_ref))
+}
+
+/// export = export_name sort index:u32 (0x00 | 0x01 type_ref)
+fn continue_parse_export(source: &[u8]) -> Result<(Export, &[u8]), ParseError> {
+    let (name, post_name) = continue_parse_extern_name(source)?;
+    let (sort, post_sort) = continue_parse_sort(post_name)?;
+    let (index, post_index) = read_u32(post_sort)?;
+    let (tag, post_tag) = read_byte(post_index)?;
+
+    let (type_ref, remains) = match tag {
+        0x00 => (None, post_tag),
+        0x01 => {
+            let (type_ref, post_type_ref) = continue_parse_type_ref(post_tag)?;
+            (Some(type_ref), post_type_ref)
+        }
+        _ => return Err(ParseError::InvalidTag("export type".to_owned(), tag)),
+    };
+
+    Ok((
+        Export {
+            name,
+            sort,
+            index,
+            type_ref,
+        },
+        remains,
+    ))
+}
+
+/// canon = 0x00 0x00 core_function_index:u32 options type_index:u32  ;; lift
+///       | 0x01 0x00 function_index:u32 options                     ;; lower
+///       | 0x02 type_index:u32                                      ;; resource.new
+///       | 0x03 type_index:u32                                      ;; resource.drop
+///       | 0x04 type_index:u32                                      ;; resource.rep
+fn continue_parse_canon(source: &[u8]) -> Result<(Canon, &[u8]), ParseError> {
+    let (tag, post_tag) = read_byte(source)?;
+    match tag {
+        0x00 | 0x01 => {
+            let (sub_tag, post_sub_tag) = read_byte(post_tag)?;
+            if sub_tag != 0x00 {
+                return Err(ParseError::InvalidTag("canon".to_owned(), sub_tag));
+            }
+
+            let (function_index, post_function_index) = read_u32(post_sub_tag)?;
+            let (options, post_options) =
+                continue_parse_items(post_function_index, continue_parse_canonical_option)?;
+
+            if tag == 0x00 {
+                let (type_index, post_type_index) = read_u32(post_options)?;
+                Ok((
+                    Canon::Lift {
+                        core_function_index: function_index,
+                        options,
+                        type_index,
+                    },
+                    post_type_index,
+                ))
+            } else {
+                Ok((
+                    Canon::Lower {
+                        function_index,
+                        options,
+                    },
+                    post_options,
+                ))
+            }
+        }
+        0x02 => {
+            let (type_index, remains) = read_u32(post_tag)?;
+            Ok((Canon::ResourceNew(type_index), remains))
+        }
+        0x03 => {
+            let (type_index, remains) = read_u32(post_tag)?;
+            Ok((Canon::ResourceDrop(type_index), remains))
+        }
+        0x04 => {
+            let (type_index, remains) = read_u32(post_tag)?;
+            Ok((Canon::ResourceRep(type_index), remains))
+        }
+        _ => Err(ParseError::Unsupported(format!(
+            "canonical function 0x{:02x}",
+            tag
+        ))),
+    }
+}
+
+fn continue_parse_canonical_option(source: &[u8]) -> Result<(CanonicalOption, &[u8]), ParseError> {
+    let (tag, post_tag) = read_byte(source)?;
+    match tag {
+        0x00 => Ok((CanonicalOption::Utf8, post_tag)),
+        0x01 => Ok((CanonicalOption::Utf16, post_tag)),
+        0x02 => Ok((CanonicalOption::CompactUtf16, post_tag)),
+        0x03..=0x05 => {
+            let (index, remains) = read_u32(post_tag)?;
+            let option = match tag {
+                0x03 => CanonicalOption::Memory(index),
+                0x04 => CanonicalOption::Realloc(index),
+                _ => CanonicalOption::PostReturn(index),
+            };
+            Ok((option, remains))
+        }
+        _ => Err(ParseError::Unsupported(format!(
+            "canonical option 0x{:02x}",
+            tag
+        ))),
+    }
+}
+
+/// start = function_index:u32 vec(value_index:u32) result_count:u32
+fn continue_parse_start(source: &[u8]) -> Result<(Start, &[u8]), ParseError> {
+    let (function_index, post_function_index) = read_u32(source)?;
+    let (arguments, post_arguments) = continue_parse_items(post_function_index, read_u32)?;
+    let (result_count, remains) = read_u32(post_arguments)?;
+    Ok((
+        Start {
+            function_index,
+            arguments,
+            result_count,
+        },
+        remains,
+    ))
+}
+
+fn read_byte(source: &[u8]) -> Result<(u8, &[u8]), ParseError> {
+    match source.split_first() {
+        Some((first, rest)) => Ok((*first, rest)),
+        None => Err(ParseError::UnexpectedEnd),
+    }
+}
+
+fn read_bytes(source: &[u8], length: usize) -> Result<(&[u8], &[u8]), ParseError> {
+    if length > source.len() {
+        Err(ParseError::UnexpectedEnd)
+    } else {
+        Ok(source.split_at(length))
+    }
+}
+
+fn read_fixed_u16(source: &[u8]) -> Result<(u16, &[u8]), ParseError> {
+    let (bytes, remains) = read_bytes(source, 2)?;
+    Ok((u16::from_le_bytes(bytes.try_into().unwrap()), remains))
+}
+
+fn read_fixed_u32(source: &[u8]) -> Result<(u32, &[u8]), ParseError> {
+    let (bytes, remains) = read_bytes(source, 4)?;
+    Ok((u32::from_le_bytes(bytes.try_into().unwrap()), remains))
+}
+
+fn read_u32(source: &[u8]) -> Result<(u32, &[u8]), ParseError> {
+    match leb128decoder::decode_u32(source) {
+        Ok((value, length)) => Ok((value, &source[length..])),
+        _ => Err(ParseError::DecodingError),
+    }
+}
+
+/// 读取变长（leb128 编码的）s33，用 i64 表示
+fn read_s33(source: &[u8]) -> Result<(i64, &[u8]), ParseError> {
+    match leb128decoder::decode_i64(source) {
+        Ok((value, length)) => Ok((value, &source[length..])),
+        _ => Err(ParseError::DecodingError),
+    }
+}
+
+fn read_string(source: &[u8]) -> Result<(String, &[u8]), ParseError> {
+    let (length, post_length) = read_u32(source)?;
+    let (bytes, remains) = read_bytes(post_length, length as usize)?;
+    match String::from_utf8(bytes.to_vec()) {
+        Ok(s) => Ok((s, remains)),
+        Err(_) => Err(ParseError::DecodingError),
+    }
+}
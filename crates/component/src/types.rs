@@ -0,0 +1,87 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 接口类型以及组件数值
+//!
+//! AST 里的数据类型使用类型索引来引用其他类型，而且索引的含义依赖于所在的组件
+//! （或者组件类型）的作用域，为了便于实现规范 ABI，实例化组件时会把它们
+//! 解析为完整的接口类型（interface type）。
+
+/// 资源类型的编号
+///
+/// 每当实例化一个资源类型定义（或者导入一个资源类型）时，都会分配一个新的编号，
+/// 编号不同的资源类型是不同的类型。
+pub type ResourceTypeId = usize;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum InterfaceType {
+    Bool,
+    S8,
+    U8,
+    S16,
+    U16,
+    S32,
+    U32,
+    S64,
+    U64,
+    F32,
+    F64,
+    Char,
+    String,
+    List(Box<InterfaceType>),
+    Record(Vec<(String, InterfaceType)>),
+    Tuple(Vec<InterfaceType>),
+    Variant(Vec<(String, Option<InterfaceType>)>),
+    Enum(Vec<String>),
+    Flags(Vec<String>),
+    Option(Box<InterfaceType>),
+    Result {
+        ok: Option<Box<InterfaceType>>,
+        error: Option<Box<InterfaceType>>,
+    },
+    Own(ResourceTypeId),
+    Borrow(ResourceTypeId),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct InterfaceFunctionType {
+    pub params: Vec<(String, InterfaceType)>,
+    pub result: Option<InterfaceType>,
+}
+
+/// 组件数值，即组件函数的实参以及返回值
+///
+/// 资源句柄（own 以及 borrow）在宿主这一端使用资源的表示值（representation），
+/// 规范 ABI 负责在表示值和组件实例的句柄（handle）之间进行转换。
+#[derive(Debug, PartialEq, Clone)]
+pub enum ComponentValue {
+    Bool(bool),
+    S8(i8),
+    U8(u8),
+    S16(i16),
+    U16(u16),
+    S32(i32),
+    U32(u32),
+    S64(i64),
+    U64(u64),
+    F32(f32),
+    F64(f64),
+    Char(char),
+    String(String),
+    List(Vec<ComponentValue>),
+    Record(Vec<(String, ComponentValue)>),
+    Tuple(Vec<ComponentValue>),
+    Variant(/* case_name */ String, Option<Box<ComponentValue>>),
+    Enum(/* case_name */ String),
+
+    /// 被设置的标志的名称
+    Flags(Vec<String>),
+
+    Option(Option<Box<ComponentValue>>),
+    Result(Result<Option<Box<ComponentValue>>, Option<Box<ComponentValue>>>),
+    Own(/* representation */ u32),
+    Borrow(/* representation */ u32),
+}
@@ -0,0 +1,337 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # WASI 0.2（preview 2）
+//!
+//! 目前只实现了运行命令（command）组件所需的最小接口集合：
+//!
+//! - `wasi:cli/environment`
+//! - `wasi:cli/exit`
+//! - `wasi:cli/stdout`
+//! - `wasi:cli/stderr`
+//! - `wasi:io/error`
+//! - `wasi:io/streams` 的输出流
+//!
+//! 输出流资源的表示值即流的编号，1 为标准输出，2 为标准错误。
+
+use std::{
+    cell::RefCell,
+    io::{self, Write},
+    rc::Rc,
+};
+
+use anvm_engine::{
+    error::{EngineError, NativeError, NativeTerminate},
+    native_module::ModuleContext,
+};
+
+use crate::{
+    error::ComponentError, host::HostInstance, instance::ComponentInstance, types::ComponentValue,
+};
+
+/// 命令组件的入口函数的导出名称
+pub const RUN_FUNCTION_NAME: &str = "wasi:cli/run@0.2.0#run";
+
+const STDOUT_STREAM: u32 = 1;
+const STDERR_STREAM: u32 = 2;
+
+/// 每次可写入的最大字节数，即 `check-write` 的返回值
+const WRITE_BUDGET: u64 = 4096;
+
+pub struct WASIContext {
+    pub arguments: Vec<String>,
+    pub environments: Vec<(String, String)>,
+    pub stdout: Rc<RefCell<dyn Write>>,
+    pub stderr: Rc<RefCell<dyn Write>>,
+}
+
+impl ModuleContext for WASIContext {
+    fn as_any(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl WASIContext {
+    pub fn new(
+        arguments: Vec<String>,
+        environments: Vec<(String, String)>,
+        stdout: Rc<RefCell<dyn Write>>,
+        stderr: Rc<RefCell<dyn Write>>,
+    ) -> Self {
+        Self {
+            arguments,
+            environments,
+            stdout,
+            stderr,
+        }
+    }
+
+    pub fn new_minimal() -> Self {
+        Self {
+            arguments: vec![],
+            environments: vec![],
+            stdout: Rc::new(RefCell::new(io::sink())),
+            stderr: Rc::new(RefCell::new(io::sink())),
+        }
+    }
+}
+
+/// 创建 WASI 的宿主实例列表
+pub fn get_wasi_host_instances() -> Vec<HostInstance> {
+    let mut io_error = HostInstance::new("wasi:io/error");
+    io_error.add_resource("error", None);
+
+    let mut io_streams = HostInstance::new("wasi:io/streams");
+    io_streams.add_resource("input-stream", None);
+    io_streams.add_resource("output-stream", None);
+    io_streams.add_function(
+        "[method]output-stream.check-write",
+        output_stream_check_write,
+    );
+    io_streams.add_function("[method]output-stream.write", output_stream_write);
+    io_streams.add_function(
+        "[method]output-stream.blocking-write-and-flush",
+        output_stream_write,
+    );
+    io_streams.add_function("[method]output-stream.flush", output_stream_flush);
+    io_streams.add_function("[method]output-stream.blocking-flush", output_stream_flush);
+
+    let mut cli_stdout = HostInstance::new("wasi:cli/stdout");
+    cli_stdout.add_function("get-stdout", get_stdout);
+
+    let mut cli_stderr = HostInstance::new("wasi:cli/stderr");
+    cli_stderr.add_function("get-stderr", get_stderr);
+
+    let mut cli_exit = HostInstance::new("wasi:cli/exit");
+    cli_exit.add_function("exit", exit);
+
+    let mut cli_environment = HostInstance::new("wasi:cli/environment");
+    cli_environment.add_function("get-arguments", get_arguments);
+    cli_environment.add_function("get-environment", get_environment);
+
+    vec![
+        io_error,
+        io_streams,
+        cli_stdout,
+        cli_stderr,
+        cli_exit,
+        cli_environment,
+    ]
+}
+
+/// 运行命令组件
+///
+/// 调用组件导出的 `wasi:cli/run` 接口的 `run` 函数，返回退出码。
+pub fn run_command(component_instance: &mut ComponentInstance) -> Result<i32, ComponentError> {
+    match component_instance.call(RUN_FUNCTION_NAME, &[]) {
+        Ok(Some(ComponentValue::Result(Ok(_)))) => Ok(0),
+        Ok(Some(ComponentValue::Result(Err(_)))) => Ok(1),
+        Ok(value) => Err(ComponentError::ValueTypeMismatch(format!(
+            "expected a result value, actual: {:?}",
+            value
+        ))),
+        Err(ComponentError::Engine(EngineError::NativeTerminate(NativeTerminate {
+            native_error: NativeError::Exit(exit_code),
+            ..
+        }))) => Ok(exit_code),
+        Err(e) => Err(e),
+    }
+}
+
+fn get_wasi_context(host_context: &mut dyn ModuleContext) -> &mut WASIContext {
+    host_context
+        .as_any()
+        .downcast_mut::<WASIContext>()
+        .expect("the host context should be WASIContext")
+}
+
+fn get_output_stream(
+    host_context: &mut dyn ModuleContext,
+    arguments: &[ComponentValue],
+) -> Option<Rc<RefCell<dyn Write>>> {
+    let context = get_wasi_context(host_context);
+    match arguments.first() {
+        Some(ComponentValue::Borrow(STDOUT_STREAM)) => Some(Rc::clone(&context.stdout)),
+        Some(ComponentValue::Borrow(STDERR_STREAM)) => Some(Rc::clone(&context.stderr)),
+        _ => None,
+    }
+}
+
+/// 构造 `result<_, stream-error>` 的错误值，目前只有 `closed` 一种错误
+fn stream_error_closed() -> ComponentValue {
+    ComponentValue::Result(Err(Some(Box::new(ComponentValue::Variant(
+        "closed".to_owned(),
+        None,
+    )))))
+}
+
+fn output_stream_check_write(
+    host_context: &mut dyn ModuleContext,
+    arguments: &[ComponentValue],
+) -> Result<Option<ComponentValue>, NativeTerminate> {
+    let result = match get_output_stream(host_context, arguments) {
+        Some(_) => ComponentValue::Result(Ok(Some(Box::new(ComponentValue::U64(WRITE_BUDGET))))),
+        None => stream_error_closed(),
+    };
+    Ok(Some(result))
+}
+
+fn output_stream_write(
+    host_context: &mut dyn ModuleContext,
+    arguments: &[ComponentValue],
+) -> Result<Option<ComponentValue>, NativeTerminate> {
+    let bytes = match arguments.get(1) {
+        Some(ComponentValue::List(items)) => items
+            .iter()
+            .filter_map(|item| match item {
+                ComponentValue::U8(value) => Some(*value),
+                _ => None,
+            })
+            .collect::<Vec<u8>>(),
+        _ => return Ok(Some(stream_error_closed())),
+    };
+
+    let result = match get_output_stream(host_context, arguments) {
+        Some(stream) => {
+            let mut writer = stream.borrow_mut();
+            match writer.write_all(&bytes).and_then(|_| writer.flush()) {
+                Ok(_) => ComponentValue::Result(Ok(None)),
+                Err(_) => stream_error_closed(),
+            }
+        }
+        None => stream_error_closed(),
+    };
+    Ok(Some(result))
+}
+
+fn output_stream_flush(
+    host_context: &mut dyn ModuleContext,
+    arguments: &[ComponentValue],
+) -> Result<Option<ComponentValue>, NativeTerminate> {
+    let result = match get_output_stream(host_context, arguments) {
+        Some(stream) => match stream.borrow_mut().flush() {
+            Ok(_) => ComponentValue::Result(Ok(None)),
+            Err(_) => stream_error_closed(),
+        },
+        None => stream_error_closed(),
+    };
+    Ok(Some(result))
+}
+
+fn get_stdout(
+    _host_context: &mut dyn ModuleContext,
+    _arguments: &[ComponentValue],
+) -> Result<Option<ComponentValue>, NativeTerminate> {
+    Ok(Some(ComponentValue::Own(STDOUT_STREAM)))
+}
+
+fn get_stderr(
+    _host_context: &mut dyn ModuleContext,
+    _arguments: &[ComponentValue],
+) -> Result<Option<ComponentValue>, NativeTerminate> {
+    Ok(Some(ComponentValue::Own(STDERR_STREAM)))
+}
+
+fn exit(
+    _host_context: &mut dyn ModuleContext,
+    arguments: &[ComponentValue],
+) -> Result<Option<ComponentValue>, NativeTerminate> {
+    let exit_code = match arguments.first() {
+        Some(ComponentValue::Result(Ok(_))) => 0,
+        _ => 1,
+    };
+
+    Err(NativeTerminate {
+        module_name: "wasi:cli/exit".to_owned(),
+        native_error: NativeError::Exit(exit_code),
+    })
+}
+
+fn get_arguments(
+    host_context: &mut dyn ModuleContext,
+    _arguments: &[ComponentValue],
+) -> Result<Option<ComponentValue>, NativeTerminate> {
+    let context = get_wasi_context(host_context);
+    let items = context
+        .arguments
+        .iter()
+        .map(|argument| ComponentValue::String(argument.clone()))
+        .collect::<Vec<ComponentValue>>();
+    Ok(Some(ComponentValue::List(items)))
+}
+
+fn get_environment(
+    host_context: &mut dyn ModuleContext,
+    _arguments: &[ComponentValue],
+) -> Result<Option<ComponentValue>, NativeTerminate> {
+    let context = get_wasi_context(host_context);
+    let items = context
+        .environments
+        .iter()
+        .map(|(name, value)| {
+            ComponentValue::Tuple(vec![
+                ComponentValue::String(name.clone()),
+                ComponentValue::String(value.clone()),
+            ])
+        })
+        .collect::<Vec<ComponentValue>>();
+    Ok(Some(ComponentValue::List(items)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, env, fs, io::Write, rc::Rc};
+
+    use crate::{instance::instantiate, parser::parse};
+
+    use super::{get_wasi_host_instances, run_command, WASIContext};
+    use pretty_assertions::assert_eq;
+
+    // 辅助方法
+    fn get_test_binary_resource(filename: &str) -> Vec<u8> {
+        let mut path_buf = env::current_dir().unwrap();
+        if !path_buf.ends_with("component") {
+            path_buf.push("crates");
+            path_buf.push("component");
+        }
+        let fullname_buf = path_buf.join("resources").join(filename);
+        let fullname = fullname_buf.to_str().unwrap();
+        fs::read(fullname)
+            .unwrap_or_else(|_| panic!("failed to read the specified binary file: {}", fullname))
+    }
+
+    #[test]
+    fn test_hello_world() {
+        let bytes = get_test_binary_resource("hello-world.wasm");
+        let component = parse(&bytes).unwrap();
+
+        let stdout: Rc<RefCell<Vec<u8>>> = Rc::new(RefCell::new(vec![]));
+        let stderr: Rc<RefCell<Vec<u8>>> = Rc::new(RefCell::new(vec![]));
+        let wasi_context = WASIContext::new(
+            vec![],
+            vec![],
+            Rc::clone(&stdout) as Rc<RefCell<dyn Write>>,
+            Rc::clone(&stderr) as Rc<RefCell<dyn Write>>,
+        );
+
+        let mut component_instance = instantiate(
+            &component,
+            &get_wasi_host_instances(),
+            Box::new(wasi_context),
+        )
+        .unwrap();
+
+        assert_eq!(run_command(&mut component_instance).unwrap(), 0);
+        assert_eq!(
+            String::from_utf8(stdout.borrow().clone()).unwrap(),
+            "Hello, world!\n"
+        );
+        assert!(stderr.borrow().is_empty());
+
+        // 输出流资源已经被丢弃
+        assert!(component_instance.context.borrow().handles.get(1).is_err());
+    }
+}
@@ -26,8 +26,14 @@ pub fn module_to_text(module: &Module) -> String {
     );
 
     // 转换 import 段
-    let (import_lines, function_index, table_index, memory_block_index, global_variable_index) =
-        format_import_items(&module.import_items, &name_package);
+    let (
+        import_lines,
+        function_index,
+        table_index,
+        memory_block_index,
+        global_variable_index,
+        tag_index,
+    ) = format_import_items(&module.import_items, &name_package);
 
     lines.extend(import_lines);
 
@@ -51,6 +57,15 @@ pub fn module_to_text(module: &Module) -> String {
             }),
     );
 
+    // 转换 tag 段
+    lines.extend(
+        module
+            .tag_items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| item.to_text(&name_package, Some(index as u32 + tag_index))),
+    );
+
     // 转换 global 段
     lines.extend(module.global_items.iter().enumerate().map(|(index, item)| {
         item.to_text(&name_package, Some(index as u32 + global_variable_index))
@@ -6,11 +6,13 @@
 
 use anvm_ast::{
     ast::{
-        CodeItem, DataItem, ElementItem, ExportItem, GlobalItem, ImportDescriptor, ImportItem,
-        Limit, MemoryType, Module, TableType, TypeItem,
+        CodeItem, DataItem, DataMode, ElementItem, ElementItems, ElementMode, ExportItem,
+        GlobalItem, ImportDescriptor, ImportItem, Limit, MemoryType, Module, TableType, TagType,
+        TypeItem,
     },
-    instruction::{BlockType, Instruction, MemoryArgument},
+    instruction::{BlockType, CatchClause, Instruction, MemoryArgument},
     name_package::NamePackage,
+    types::{RefType, Value},
 };
 use std::fmt::Write;
 
@@ -104,7 +106,7 @@ impl TextFormat for TableType {
     ) -> std::fmt::Result {
         // 示例：
         // (table (;0;) 1 funcref)
-        // (table $name 1 8 funcref)
+        // (table $name 1 8 externref)
         let mut text_fragments: Vec<String> = vec![];
 
         text_fragments.push("(table".to_string());
@@ -118,10 +120,10 @@ impl TextFormat for TableType {
 
         match self.limit {
             Limit::AtLeast(min) => {
-                text_fragments.push(format!("{} funcref)", min));
+                text_fragments.push(format!("{} {})", min, self.element_type));
             }
             Limit::Range(min, max) => {
-                text_fragments.push(format!("{} {} funcref)", min, max));
+                text_fragments.push(format!("{} {} {})", min, max, self.element_type));
             }
         }
 
@@ -141,6 +143,8 @@ impl TextFormat for MemoryType {
         // 示例：
         // (memory (;0;) 1)
         // (memory $name 1 8)
+        // (memory $name 1 8 shared)
+        // (memory $name i64 1 8)
         let mut text_fragments: Vec<String> = vec![];
 
         text_fragments.push("(memory".to_string());
@@ -152,15 +156,55 @@ impl TextFormat for MemoryType {
             text_fragments.push(format!("(;{};)", memory_block_index));
         }
 
+        if self.memory64 {
+            text_fragments.push("i64".to_string());
+        }
+
         match self.limit {
             Limit::AtLeast(min) => {
-                text_fragments.push(format!("{})", min));
+                text_fragments.push(format!("{}", min));
             }
             Limit::Range(min, max) => {
-                text_fragments.push(format!("{} {})", min, max));
+                text_fragments.push(format!("{} {}", min, max));
             }
         }
 
+        if self.shared {
+            text_fragments.push("shared".to_string());
+        }
+
+        write!(f, "{})", text_fragments.join(" "))
+    }
+}
+
+/// 在 ast::Module 当中直接使用 TagType 作为 TagItem，
+/// 所以这里 TagType 转换为 text 实际上是 TagItem 的文本。
+impl TextFormat for TagType {
+    fn text(
+        &self,
+        name_package: &NamePackage,
+        option_item_index: Option<u32>,
+        f: &mut String,
+    ) -> std::fmt::Result {
+        // 示例：
+        // (tag (;0;) (type 0))
+        // (tag $name (type $ft0))
+        let mut text_fragments: Vec<String> = vec![];
+        text_fragments.push("(tag".to_string());
+
+        let tag_index = option_item_index.unwrap();
+        if let Some(tag_name) = name_package.get_tag_name(&tag_index) {
+            text_fragments.push(format!("${}", tag_name));
+        } else {
+            text_fragments.push(format!("(;{};)", tag_index));
+        }
+
+        if let Some(type_name) = name_package.get_type_name(&self.type_index) {
+            text_fragments.push(format!("(type ${}))", type_name));
+        } else {
+            text_fragments.push(format!("(type {}))", self.type_index));
+        }
+
         write!(f, "{}", text_fragments.join(" "))
     }
 }
@@ -214,6 +258,7 @@ pub fn format_import_items(
     /* table_index */ u32,
     /* memory_block_index */ u32,
     /* global_variable_index */ u32,
+    /* tag_index */ u32,
 ) {
     // 示例
     // (import "env" "putc" (func $fputc (type $ft0)))
@@ -222,11 +267,13 @@ pub fn format_import_items(
     // (import "env" "mem0" (memory $name 1 8))
     // (import "env" "g1" (global $g1 i32))
     // (import "env" "g2" (global $g2 (mut i32)))
+    // (import "env" "e0" (tag (;0;) (type 1)))
 
     let mut function_index: u32 = 0;
     let mut table_index: u32 = 0;
     let mut memory_block_index: u32 = 0;
     let mut global_variable_index: u32 = 0;
+    let mut tag_index: u32 = 0;
 
     let lines = import_items
         .iter()
@@ -330,6 +377,22 @@ pub fn format_import_items(
                     global_variable_index += 1; // 累加 global_variable_index
                     text_fragments.join(" ")
                 }
+                ImportDescriptor::TagType(tag_type) => {
+                    // 示例
+                    // (import "env" "e0" (tag (;0;) (type 1)))
+                    let mut text_fragments: Vec<String> = vec![];
+
+                    text_fragments.push("(import".to_string());
+                    text_fragments.push(format!("\"{}\" \"{}\"", module_name, item_name));
+
+                    text_fragments.push(format!(
+                        "{})",
+                        tag_type.to_text(name_package, Some(tag_index))
+                    ));
+
+                    tag_index += 1; // 累加 tag_index
+                    text_fragments.join(" ")
+                }
             }
         })
         .collect::<Vec<String>>();
@@ -340,6 +403,7 @@ pub fn format_import_items(
         table_index,
         memory_block_index,
         global_variable_index,
+        tag_index,
     )
 }
 
@@ -369,6 +433,10 @@ impl TextFormat for BlockType {
             BlockType::ResultI64 => write!(f, "(result i64)"),
             BlockType::ResultF32 => write!(f, "(result f32)"),
             BlockType::ResultF64 => write!(f, "(result f64)"),
+            BlockType::ResultV128 => write!(f, "(result v128)"),
+            BlockType::ResultFuncRef => write!(f, "(result funcref)"),
+            BlockType::ResultExternRef => write!(f, "(result externref)"),
+            BlockType::ResultExnRef => write!(f, "(result exnref)"),
             BlockType::ResultEmpty => write!(f, ""),
 
             // 来自类型表的类型
@@ -393,6 +461,11 @@ impl TextFormat for MemoryArgument {
         // 示例
         // i32.load offset=100 align=4
         //          ^----------------^
+        // i32.load 1 offset=100 align=4
+        //          ^------------------^
+        if self.memory_block_index != 0 {
+            write!(f, "{} ", self.memory_block_index)?;
+        }
         write!(f, "offset={} align={}", self.offset, 2i32.pow(self.align))
     }
 }
@@ -494,17 +567,79 @@ impl TextFormat for Instruction {
                     write!(f, "call {}", function_index)
                 }
             }
-            Instruction::CallIndirect(type_index, _table_index) => {
-                // table_index 暂时用不上
-                if let Some(type_name) = name_package.get_type_name(type_index) {
-                    write!(f, "call_indirect (type ${})", type_name)
+            Instruction::CallIndirect(type_index, table_index) => write!(
+                f,
+                "call_indirect {}",
+                format_call_indirect_arguments(name_package, type_index, table_index)
+            ),
+            Instruction::ReturnCall(function_index) => {
+                if let Some(function_name) = name_package.get_function_name(function_index) {
+                    write!(f, "return_call ${}", function_name)
                 } else {
-                    write!(f, "call_indirect (type {})", type_index)
+                    write!(f, "return_call {}", function_index)
+                }
+            }
+            Instruction::ReturnCallIndirect(type_index, table_index) => write!(
+                f,
+                "return_call_indirect {}",
+                format_call_indirect_arguments(name_package, type_index, table_index)
+            ),
+            Instruction::Throw(tag_index) => {
+                write!(f, "throw {}", format_tag_index(name_package, tag_index))
+            }
+            Instruction::ThrowRef => write!(f, "throw_ref"),
+            Instruction::TryTable(block_type, catch_clauses, block_index) => {
+                let mut text_fragments: Vec<String> = vec![];
+                text_fragments.push("try_table".to_string());
+
+                if let Some(function_index) = option_item_index {
+                    let option_block_label =
+                        name_package.get_block_lable(&function_index, block_index);
+                    if let Some(block_label) = option_block_label {
+                        text_fragments.push(format!("${}", block_label));
+                    }
                 }
+
+                let block_type_text = block_type.to_text(name_package, None);
+                if !block_type_text.is_empty() {
+                    text_fragments.push(block_type_text);
+                }
+
+                text_fragments.extend(catch_clauses.iter().map(
+                    |catch_clause| match catch_clause {
+                        CatchClause::Catch(tag_index, relative_depth) => format!(
+                            "(catch {} {})",
+                            format_tag_index(name_package, tag_index),
+                            relative_depth
+                        ),
+                        CatchClause::CatchRef(tag_index, relative_depth) => format!(
+                            "(catch_ref {} {})",
+                            format_tag_index(name_package, tag_index),
+                            relative_depth
+                        ),
+                        CatchClause::CatchAll(relative_depth) => {
+                            format!("(catch_all {})", relative_depth)
+                        }
+                        CatchClause::CatchAllRef(relative_depth) => {
+                            format!("(catch_all_ref {})", relative_depth)
+                        }
+                    },
+                ));
+
+                write!(f, "{}", text_fragments.join(" "))
             }
 
             Instruction::Drop => write!(f, "drop"),
             Instruction::Select => write!(f, "select"),
+            Instruction::SelectTyped(value_types) => write!(
+                f,
+                "select (result {})",
+                value_types
+                    .iter()
+                    .map(|value_type| value_type.to_string())
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            ),
 
             Instruction::LocalGet(local_variable_index) => {
                 let option_variable_name = {
@@ -685,31 +820,59 @@ impl TextFormat for Instruction {
                 "i64.store32 {}",
                 memory_argument.to_text(name_package, option_item_index)
             ),
-            Instruction::MemorySize(_memory_block_index) => write!(f, "memory.size"), // memory_block_index 暂时用不上
-            Instruction::MemoryGrow(_memory_block_index) => write!(f, "memory.grow"), // memory_block_index 暂时用不上
-            Instruction::MemoryInit(data_index, _memory_block_index) => {
-                write!(f, "memory.init {}", data_index)
-            } // memory_block_index 暂时用不上
+            Instruction::MemorySize(memory_block_index) => {
+                write!(f, "memory.size{}", format_memory_block_index(memory_block_index))
+            }
+            Instruction::MemoryGrow(memory_block_index) => {
+                write!(f, "memory.grow{}", format_memory_block_index(memory_block_index))
+            }
+            Instruction::MemoryInit(data_index, memory_block_index) => write!(
+                f,
+                "memory.init{} {}",
+                format_memory_block_index(memory_block_index),
+                data_index
+            ),
             Instruction::DataDrop(data_index) => write!(f, "data.drop {}", data_index),
-            Instruction::MemoryCopy(_source_memory_block_index, _dest_memory_block_index) => {
-                // memory_block_index 暂时用不上
-                write!(f, "memory.copy")
+            Instruction::MemoryCopy(dest_memory_block_index, source_memory_block_index) => {
+                // 两个内存块索引都为 0 时省略
+                if *dest_memory_block_index == 0 && *source_memory_block_index == 0 {
+                    write!(f, "memory.copy")
+                } else {
+                    write!(
+                        f,
+                        "memory.copy {} {}",
+                        dest_memory_block_index, source_memory_block_index
+                    )
+                }
+            }
+            Instruction::MemoryFill(memory_block_index) => {
+                write!(f, "memory.fill{}", format_memory_block_index(memory_block_index))
             }
-            Instruction::MemoryFill(_memory_block_index) => write!(f, "memory.fill"), // memory_block_index 暂时用不上
 
             Instruction::TableGet(table_index) => write!(f, "table.get {}", table_index),
             Instruction::TableSet(table_index) => write!(f, "table.set {}", table_index),
             Instruction::TableInit(element_index, table_index) => {
-                write!(f, "table.init {} {}", element_index, table_index)
+                // 文本格式的表索引位于元素索引之前，跟二进制格式的次序相反
+                write!(f, "table.init {} {}", table_index, element_index)
             }
             Instruction::ElementDrop(element_index) => write!(f, "elem.drop {}", element_index),
-            Instruction::TableCopy(source_table_index, dest_table_index) => {
-                write!(f, "table.copy {} {}", source_table_index, dest_table_index)
+            Instruction::TableCopy(dest_table_index, source_table_index) => {
+                write!(f, "table.copy {} {}", dest_table_index, source_table_index)
             }
             Instruction::TableGrow(table_index) => write!(f, "table.grow {}", table_index),
             Instruction::TableSize(table_index) => write!(f, "table.size {}", table_index),
             Instruction::TableFill(table_index) => write!(f, "table.fill {}", table_index),
 
+            Instruction::RefNull(ref_type) => write!(f, "ref.null {}", format_heap_type(ref_type)),
+            Instruction::RefIsNull => write!(f, "ref.is_null"),
+            Instruction::RefFunc(function_index) => {
+                if let Some(function_name) = name_package.get_function_name(function_index) {
+                    write!(f, "ref.func ${}", function_name)
+                } else {
+                    write!(f, "ref.func {}", function_index)
+                }
+            }
+
             Instruction::I32Const(immediate_number) => write!(f, "i32.const {}", immediate_number),
             Instruction::I64Const(immediate_number) => write!(f, "i64.const {}", immediate_number),
             Instruction::F32Const(immediate_number) => write!(f, "f32.const {}", immediate_number),
@@ -861,8 +1024,705 @@ impl TextFormat for Instruction {
             Instruction::I32TruncSatF64U => write!(f, "i32.trunc_sat_f64_u"),
             Instruction::I64TruncSatF32S => write!(f, "i64.trunc_sat_f32_s"),
             Instruction::I64TruncSatF32U => write!(f, "i64.trunc_sat_f32_u"),
-            Instruction::I64TruncSatF64S => write!(f, "i64.trunc_sat_64_s"),
-            Instruction::I64TruncSatF64U => write!(f, "i64.trunc_sat_64_u"),
+            Instruction::I64TruncSatF64S => write!(f, "i64.trunc_sat_f64_s"),
+            Instruction::I64TruncSatF64U => write!(f, "i64.trunc_sat_f64_u"),
+
+            Instruction::V128Load(memory_argument) => write!(
+                f,
+                "v128.load {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::V128Load8x8S(memory_argument) => write!(
+                f,
+                "v128.load8x8_s {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::V128Load8x8U(memory_argument) => write!(
+                f,
+                "v128.load8x8_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::V128Load16x4S(memory_argument) => write!(
+                f,
+                "v128.load16x4_s {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::V128Load16x4U(memory_argument) => write!(
+                f,
+                "v128.load16x4_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::V128Load32x2S(memory_argument) => write!(
+                f,
+                "v128.load32x2_s {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::V128Load32x2U(memory_argument) => write!(
+                f,
+                "v128.load32x2_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::V128Load8Splat(memory_argument) => write!(
+                f,
+                "v128.load8_splat {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::V128Load16Splat(memory_argument) => write!(
+                f,
+                "v128.load16_splat {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::V128Load32Splat(memory_argument) => write!(
+                f,
+                "v128.load32_splat {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::V128Load64Splat(memory_argument) => write!(
+                f,
+                "v128.load64_splat {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::V128Store(memory_argument) => write!(
+                f,
+                "v128.store {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::V128Const(value) => write!(f, "{}", Value::V128(*value)),
+            Instruction::I8x16Shuffle(lane_indices) => {
+                let lane_texts: Vec<String> =
+                    lane_indices.iter().map(|index| index.to_string()).collect();
+                write!(f, "i8x16.shuffle {}", lane_texts.join(" "))
+            }
+            Instruction::I8x16Swizzle => write!(f, "i8x16.swizzle"),
+            Instruction::I8x16Splat => write!(f, "i8x16.splat"),
+            Instruction::I16x8Splat => write!(f, "i16x8.splat"),
+            Instruction::I32x4Splat => write!(f, "i32x4.splat"),
+            Instruction::I64x2Splat => write!(f, "i64x2.splat"),
+            Instruction::F32x4Splat => write!(f, "f32x4.splat"),
+            Instruction::F64x2Splat => write!(f, "f64x2.splat"),
+            Instruction::I8x16ExtractLaneS(lane_index) => {
+                write!(f, "i8x16.extract_lane_s {}", lane_index)
+            }
+            Instruction::I8x16ExtractLaneU(lane_index) => {
+                write!(f, "i8x16.extract_lane_u {}", lane_index)
+            }
+            Instruction::I8x16ReplaceLane(lane_index) => {
+                write!(f, "i8x16.replace_lane {}", lane_index)
+            }
+            Instruction::I16x8ExtractLaneS(lane_index) => {
+                write!(f, "i16x8.extract_lane_s {}", lane_index)
+            }
+            Instruction::I16x8ExtractLaneU(lane_index) => {
+                write!(f, "i16x8.extract_lane_u {}", lane_index)
+            }
+            Instruction::I16x8ReplaceLane(lane_index) => {
+                write!(f, "i16x8.replace_lane {}", lane_index)
+            }
+            Instruction::I32x4ExtractLane(lane_index) => {
+                write!(f, "i32x4.extract_lane {}", lane_index)
+            }
+            Instruction::I32x4ReplaceLane(lane_index) => {
+                write!(f, "i32x4.replace_lane {}", lane_index)
+            }
+            Instruction::I64x2ExtractLane(lane_index) => {
+                write!(f, "i64x2.extract_lane {}", lane_index)
+            }
+            Instruction::I64x2ReplaceLane(lane_index) => {
+                write!(f, "i64x2.replace_lane {}", lane_index)
+            }
+            Instruction::F32x4ExtractLane(lane_index) => {
+                write!(f, "f32x4.extract_lane {}", lane_index)
+            }
+            Instruction::F32x4ReplaceLane(lane_index) => {
+                write!(f, "f32x4.replace_lane {}", lane_index)
+            }
+            Instruction::F64x2ExtractLane(lane_index) => {
+                write!(f, "f64x2.extract_lane {}", lane_index)
+            }
+            Instruction::F64x2ReplaceLane(lane_index) => {
+                write!(f, "f64x2.replace_lane {}", lane_index)
+            }
+            Instruction::I8x16Eq => write!(f, "i8x16.eq"),
+            Instruction::I8x16Ne => write!(f, "i8x16.ne"),
+            Instruction::I8x16LtS => write!(f, "i8x16.lt_s"),
+            Instruction::I8x16LtU => write!(f, "i8x16.lt_u"),
+            Instruction::I8x16GtS => write!(f, "i8x16.gt_s"),
+            Instruction::I8x16GtU => write!(f, "i8x16.gt_u"),
+            Instruction::I8x16LeS => write!(f, "i8x16.le_s"),
+            Instruction::I8x16LeU => write!(f, "i8x16.le_u"),
+            Instruction::I8x16GeS => write!(f, "i8x16.ge_s"),
+            Instruction::I8x16GeU => write!(f, "i8x16.ge_u"),
+            Instruction::I16x8Eq => write!(f, "i16x8.eq"),
+            Instruction::I16x8Ne => write!(f, "i16x8.ne"),
+            Instruction::I16x8LtS => write!(f, "i16x8.lt_s"),
+            Instruction::I16x8LtU => write!(f, "i16x8.lt_u"),
+            Instruction::I16x8GtS => write!(f, "i16x8.gt_s"),
+            Instruction::I16x8GtU => write!(f, "i16x8.gt_u"),
+            Instruction::I16x8LeS => write!(f, "i16x8.le_s"),
+            Instruction::I16x8LeU => write!(f, "i16x8.le_u"),
+            Instruction::I16x8GeS => write!(f, "i16x8.ge_s"),
+            Instruction::I16x8GeU => write!(f, "i16x8.ge_u"),
+            Instruction::I32x4Eq => write!(f, "i32x4.eq"),
+            Instruction::I32x4Ne => write!(f, "i32x4.ne"),
+            Instruction::I32x4LtS => write!(f, "i32x4.lt_s"),
+            Instruction::I32x4LtU => write!(f, "i32x4.lt_u"),
+            Instruction::I32x4GtS => write!(f, "i32x4.gt_s"),
+            Instruction::I32x4GtU => write!(f, "i32x4.gt_u"),
+            Instruction::I32x4LeS => write!(f, "i32x4.le_s"),
+            Instruction::I32x4LeU => write!(f, "i32x4.le_u"),
+            Instruction::I32x4GeS => write!(f, "i32x4.ge_s"),
+            Instruction::I32x4GeU => write!(f, "i32x4.ge_u"),
+            Instruction::F32x4Eq => write!(f, "f32x4.eq"),
+            Instruction::F32x4Ne => write!(f, "f32x4.ne"),
+            Instruction::F32x4Lt => write!(f, "f32x4.lt"),
+            Instruction::F32x4Gt => write!(f, "f32x4.gt"),
+            Instruction::F32x4Le => write!(f, "f32x4.le"),
+            Instruction::F32x4Ge => write!(f, "f32x4.ge"),
+            Instruction::F64x2Eq => write!(f, "f64x2.eq"),
+            Instruction::F64x2Ne => write!(f, "f64x2.ne"),
+            Instruction::F64x2Lt => write!(f, "f64x2.lt"),
+            Instruction::F64x2Gt => write!(f, "f64x2.gt"),
+            Instruction::F64x2Le => write!(f, "f64x2.le"),
+            Instruction::F64x2Ge => write!(f, "f64x2.ge"),
+            Instruction::V128Not => write!(f, "v128.not"),
+            Instruction::V128And => write!(f, "v128.and"),
+            Instruction::V128Andnot => write!(f, "v128.andnot"),
+            Instruction::V128Or => write!(f, "v128.or"),
+            Instruction::V128Xor => write!(f, "v128.xor"),
+            Instruction::V128Bitselect => write!(f, "v128.bitselect"),
+            Instruction::V128AnyTrue => write!(f, "v128.any_true"),
+            Instruction::V128Load8Lane(memory_argument, lane_index) => write!(
+                f,
+                "v128.load8_lane {} {}",
+                memory_argument.to_text(name_package, option_item_index),
+                lane_index
+            ),
+            Instruction::V128Load16Lane(memory_argument, lane_index) => write!(
+                f,
+                "v128.load16_lane {} {}",
+                memory_argument.to_text(name_package, option_item_index),
+                lane_index
+            ),
+            Instruction::V128Load32Lane(memory_argument, lane_index) => write!(
+                f,
+                "v128.load32_lane {} {}",
+                memory_argument.to_text(name_package, option_item_index),
+                lane_index
+            ),
+            Instruction::V128Load64Lane(memory_argument, lane_index) => write!(
+                f,
+                "v128.load64_lane {} {}",
+                memory_argument.to_text(name_package, option_item_index),
+                lane_index
+            ),
+            Instruction::V128Store8Lane(memory_argument, lane_index) => write!(
+                f,
+                "v128.store8_lane {} {}",
+                memory_argument.to_text(name_package, option_item_index),
+                lane_index
+            ),
+            Instruction::V128Store16Lane(memory_argument, lane_index) => write!(
+                f,
+                "v128.store16_lane {} {}",
+                memory_argument.to_text(name_package, option_item_index),
+                lane_index
+            ),
+            Instruction::V128Store32Lane(memory_argument, lane_index) => write!(
+                f,
+                "v128.store32_lane {} {}",
+                memory_argument.to_text(name_package, option_item_index),
+                lane_index
+            ),
+            Instruction::V128Store64Lane(memory_argument, lane_index) => write!(
+                f,
+                "v128.store64_lane {} {}",
+                memory_argument.to_text(name_package, option_item_index),
+                lane_index
+            ),
+            Instruction::V128Load32Zero(memory_argument) => write!(
+                f,
+                "v128.load32_zero {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::V128Load64Zero(memory_argument) => write!(
+                f,
+                "v128.load64_zero {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::F32x4DemoteF64x2Zero => write!(f, "f32x4.demote_f64x2_zero"),
+            Instruction::F64x2PromoteLowF32x4 => write!(f, "f64x2.promote_low_f32x4"),
+            Instruction::I8x16Abs => write!(f, "i8x16.abs"),
+            Instruction::I8x16Neg => write!(f, "i8x16.neg"),
+            Instruction::I8x16Popcnt => write!(f, "i8x16.popcnt"),
+            Instruction::I8x16AllTrue => write!(f, "i8x16.all_true"),
+            Instruction::I8x16Bitmask => write!(f, "i8x16.bitmask"),
+            Instruction::I8x16NarrowI16x8S => write!(f, "i8x16.narrow_i16x8_s"),
+            Instruction::I8x16NarrowI16x8U => write!(f, "i8x16.narrow_i16x8_u"),
+            Instruction::F32x4Ceil => write!(f, "f32x4.ceil"),
+            Instruction::F32x4Floor => write!(f, "f32x4.floor"),
+            Instruction::F32x4Trunc => write!(f, "f32x4.trunc"),
+            Instruction::F32x4Nearest => write!(f, "f32x4.nearest"),
+            Instruction::I8x16Shl => write!(f, "i8x16.shl"),
+            Instruction::I8x16ShrS => write!(f, "i8x16.shr_s"),
+            Instruction::I8x16ShrU => write!(f, "i8x16.shr_u"),
+            Instruction::I8x16Add => write!(f, "i8x16.add"),
+            Instruction::I8x16AddSatS => write!(f, "i8x16.add_sat_s"),
+            Instruction::I8x16AddSatU => write!(f, "i8x16.add_sat_u"),
+            Instruction::I8x16Sub => write!(f, "i8x16.sub"),
+            Instruction::I8x16SubSatS => write!(f, "i8x16.sub_sat_s"),
+            Instruction::I8x16SubSatU => write!(f, "i8x16.sub_sat_u"),
+            Instruction::F64x2Ceil => write!(f, "f64x2.ceil"),
+            Instruction::F64x2Floor => write!(f, "f64x2.floor"),
+            Instruction::I8x16MinS => write!(f, "i8x16.min_s"),
+            Instruction::I8x16MinU => write!(f, "i8x16.min_u"),
+            Instruction::I8x16MaxS => write!(f, "i8x16.max_s"),
+            Instruction::I8x16MaxU => write!(f, "i8x16.max_u"),
+            Instruction::F64x2Trunc => write!(f, "f64x2.trunc"),
+            Instruction::I8x16AvgrU => write!(f, "i8x16.avgr_u"),
+            Instruction::I16x8ExtaddPairwiseI8x16S => write!(f, "i16x8.extadd_pairwise_i8x16_s"),
+            Instruction::I16x8ExtaddPairwiseI8x16U => write!(f, "i16x8.extadd_pairwise_i8x16_u"),
+            Instruction::I32x4ExtaddPairwiseI16x8S => write!(f, "i32x4.extadd_pairwise_i16x8_s"),
+            Instruction::I32x4ExtaddPairwiseI16x8U => write!(f, "i32x4.extadd_pairwise_i16x8_u"),
+            Instruction::I16x8Abs => write!(f, "i16x8.abs"),
+            Instruction::I16x8Neg => write!(f, "i16x8.neg"),
+            Instruction::I16x8Q15mulrSatS => write!(f, "i16x8.q15mulr_sat_s"),
+            Instruction::I16x8AllTrue => write!(f, "i16x8.all_true"),
+            Instruction::I16x8Bitmask => write!(f, "i16x8.bitmask"),
+            Instruction::I16x8NarrowI32x4S => write!(f, "i16x8.narrow_i32x4_s"),
+            Instruction::I16x8NarrowI32x4U => write!(f, "i16x8.narrow_i32x4_u"),
+            Instruction::I16x8ExtendLowI8x16S => write!(f, "i16x8.extend_low_i8x16_s"),
+            Instruction::I16x8ExtendHighI8x16S => write!(f, "i16x8.extend_high_i8x16_s"),
+            Instruction::I16x8ExtendLowI8x16U => write!(f, "i16x8.extend_low_i8x16_u"),
+            Instruction::I16x8ExtendHighI8x16U => write!(f, "i16x8.extend_high_i8x16_u"),
+            Instruction::I16x8Shl => write!(f, "i16x8.shl"),
+            Instruction::I16x8ShrS => write!(f, "i16x8.shr_s"),
+            Instruction::I16x8ShrU => write!(f, "i16x8.shr_u"),
+            Instruction::I16x8Add => write!(f, "i16x8.add"),
+            Instruction::I16x8AddSatS => write!(f, "i16x8.add_sat_s"),
+            Instruction::I16x8AddSatU => write!(f, "i16x8.add_sat_u"),
+            Instruction::I16x8Sub => write!(f, "i16x8.sub"),
+            Instruction::I16x8SubSatS => write!(f, "i16x8.sub_sat_s"),
+            Instruction::I16x8SubSatU => write!(f, "i16x8.sub_sat_u"),
+            Instruction::F64x2Nearest => write!(f, "f64x2.nearest"),
+            Instruction::I16x8Mul => write!(f, "i16x8.mul"),
+            Instruction::I16x8MinS => write!(f, "i16x8.min_s"),
+            Instruction::I16x8MinU => write!(f, "i16x8.min_u"),
+            Instruction::I16x8MaxS => write!(f, "i16x8.max_s"),
+            Instruction::I16x8MaxU => write!(f, "i16x8.max_u"),
+            Instruction::I16x8AvgrU => write!(f, "i16x8.avgr_u"),
+            Instruction::I16x8ExtmulLowI8x16S => write!(f, "i16x8.extmul_low_i8x16_s"),
+            Instruction::I16x8ExtmulHighI8x16S => write!(f, "i16x8.extmul_high_i8x16_s"),
+            Instruction::I16x8ExtmulLowI8x16U => write!(f, "i16x8.extmul_low_i8x16_u"),
+            Instruction::I16x8ExtmulHighI8x16U => write!(f, "i16x8.extmul_high_i8x16_u"),
+            Instruction::I32x4Abs => write!(f, "i32x4.abs"),
+            Instruction::I32x4Neg => write!(f, "i32x4.neg"),
+            Instruction::I32x4AllTrue => write!(f, "i32x4.all_true"),
+            Instruction::I32x4Bitmask => write!(f, "i32x4.bitmask"),
+            Instruction::I32x4ExtendLowI16x8S => write!(f, "i32x4.extend_low_i16x8_s"),
+            Instruction::I32x4ExtendHighI16x8S => write!(f, "i32x4.extend_high_i16x8_s"),
+            Instruction::I32x4ExtendLowI16x8U => write!(f, "i32x4.extend_low_i16x8_u"),
+            Instruction::I32x4ExtendHighI16x8U => write!(f, "i32x4.extend_high_i16x8_u"),
+            Instruction::I32x4Shl => write!(f, "i32x4.shl"),
+            Instruction::I32x4ShrS => write!(f, "i32x4.shr_s"),
+            Instruction::I32x4ShrU => write!(f, "i32x4.shr_u"),
+            Instruction::I32x4Add => write!(f, "i32x4.add"),
+            Instruction::I32x4Sub => write!(f, "i32x4.sub"),
+            Instruction::I32x4Mul => write!(f, "i32x4.mul"),
+            Instruction::I32x4MinS => write!(f, "i32x4.min_s"),
+            Instruction::I32x4MinU => write!(f, "i32x4.min_u"),
+            Instruction::I32x4MaxS => write!(f, "i32x4.max_s"),
+            Instruction::I32x4MaxU => write!(f, "i32x4.max_u"),
+            Instruction::I32x4DotI16x8S => write!(f, "i32x4.dot_i16x8_s"),
+            Instruction::I32x4ExtmulLowI16x8S => write!(f, "i32x4.extmul_low_i16x8_s"),
+            Instruction::I32x4ExtmulHighI16x8S => write!(f, "i32x4.extmul_high_i16x8_s"),
+            Instruction::I32x4ExtmulLowI16x8U => write!(f, "i32x4.extmul_low_i16x8_u"),
+            Instruction::I32x4ExtmulHighI16x8U => write!(f, "i32x4.extmul_high_i16x8_u"),
+            Instruction::I64x2Abs => write!(f, "i64x2.abs"),
+            Instruction::I64x2Neg => write!(f, "i64x2.neg"),
+            Instruction::I64x2AllTrue => write!(f, "i64x2.all_true"),
+            Instruction::I64x2Bitmask => write!(f, "i64x2.bitmask"),
+            Instruction::I64x2ExtendLowI32x4S => write!(f, "i64x2.extend_low_i32x4_s"),
+            Instruction::I64x2ExtendHighI32x4S => write!(f, "i64x2.extend_high_i32x4_s"),
+            Instruction::I64x2ExtendLowI32x4U => write!(f, "i64x2.extend_low_i32x4_u"),
+            Instruction::I64x2ExtendHighI32x4U => write!(f, "i64x2.extend_high_i32x4_u"),
+            Instruction::I64x2Shl => write!(f, "i64x2.shl"),
+            Instruction::I64x2ShrS => write!(f, "i64x2.shr_s"),
+            Instruction::I64x2ShrU => write!(f, "i64x2.shr_u"),
+            Instruction::I64x2Add => write!(f, "i64x2.add"),
+            Instruction::I64x2Sub => write!(f, "i64x2.sub"),
+            Instruction::I64x2Mul => write!(f, "i64x2.mul"),
+            Instruction::I64x2Eq => write!(f, "i64x2.eq"),
+            Instruction::I64x2Ne => write!(f, "i64x2.ne"),
+            Instruction::I64x2LtS => write!(f, "i64x2.lt_s"),
+            Instruction::I64x2GtS => write!(f, "i64x2.gt_s"),
+            Instruction::I64x2LeS => write!(f, "i64x2.le_s"),
+            Instruction::I64x2GeS => write!(f, "i64x2.ge_s"),
+            Instruction::I64x2ExtmulLowI32x4S => write!(f, "i64x2.extmul_low_i32x4_s"),
+            Instruction::I64x2ExtmulHighI32x4S => write!(f, "i64x2.extmul_high_i32x4_s"),
+            Instruction::I64x2ExtmulLowI32x4U => write!(f, "i64x2.extmul_low_i32x4_u"),
+            Instruction::I64x2ExtmulHighI32x4U => write!(f, "i64x2.extmul_high_i32x4_u"),
+            Instruction::F32x4Abs => write!(f, "f32x4.abs"),
+            Instruction::F32x4Neg => write!(f, "f32x4.neg"),
+            Instruction::F32x4Sqrt => write!(f, "f32x4.sqrt"),
+            Instruction::F32x4Add => write!(f, "f32x4.add"),
+            Instruction::F32x4Sub => write!(f, "f32x4.sub"),
+            Instruction::F32x4Mul => write!(f, "f32x4.mul"),
+            Instruction::F32x4Div => write!(f, "f32x4.div"),
+            Instruction::F32x4Min => write!(f, "f32x4.min"),
+            Instruction::F32x4Max => write!(f, "f32x4.max"),
+            Instruction::F32x4Pmin => write!(f, "f32x4.pmin"),
+            Instruction::F32x4Pmax => write!(f, "f32x4.pmax"),
+            Instruction::F64x2Abs => write!(f, "f64x2.abs"),
+            Instruction::F64x2Neg => write!(f, "f64x2.neg"),
+            Instruction::F64x2Sqrt => write!(f, "f64x2.sqrt"),
+            Instruction::F64x2Add => write!(f, "f64x2.add"),
+            Instruction::F64x2Sub => write!(f, "f64x2.sub"),
+            Instruction::F64x2Mul => write!(f, "f64x2.mul"),
+            Instruction::F64x2Div => write!(f, "f64x2.div"),
+            Instruction::F64x2Min => write!(f, "f64x2.min"),
+            Instruction::F64x2Max => write!(f, "f64x2.max"),
+            Instruction::F64x2Pmin => write!(f, "f64x2.pmin"),
+            Instruction::F64x2Pmax => write!(f, "f64x2.pmax"),
+            Instruction::I32x4TruncSatF32x4S => write!(f, "i32x4.trunc_sat_f32x4_s"),
+            Instruction::I32x4TruncSatF32x4U => write!(f, "i32x4.trunc_sat_f32x4_u"),
+            Instruction::F32x4ConvertI32x4S => write!(f, "f32x4.convert_i32x4_s"),
+            Instruction::F32x4ConvertI32x4U => write!(f, "f32x4.convert_i32x4_u"),
+            Instruction::I32x4TruncSatF64x2SZero => write!(f, "i32x4.trunc_sat_f64x2_s_zero"),
+            Instruction::I32x4TruncSatF64x2UZero => write!(f, "i32x4.trunc_sat_f64x2_u_zero"),
+            Instruction::F64x2ConvertLowI32x4S => write!(f, "f64x2.convert_low_i32x4_s"),
+            Instruction::F64x2ConvertLowI32x4U => write!(f, "f64x2.convert_low_i32x4_u"),
+
+            Instruction::MemoryAtomicNotify(memory_argument) => write!(
+                f,
+                "memory.atomic.notify {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::MemoryAtomicWait32(memory_argument) => write!(
+                f,
+                "memory.atomic.wait32 {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::MemoryAtomicWait64(memory_argument) => write!(
+                f,
+                "memory.atomic.wait64 {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::AtomicFence => write!(f, "atomic.fence"),
+            Instruction::I32AtomicLoad(memory_argument) => write!(
+                f,
+                "i32.atomic.load {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicLoad(memory_argument) => write!(
+                f,
+                "i64.atomic.load {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicLoad8U(memory_argument) => write!(
+                f,
+                "i32.atomic.load8_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicLoad16U(memory_argument) => write!(
+                f,
+                "i32.atomic.load16_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicLoad8U(memory_argument) => write!(
+                f,
+                "i64.atomic.load8_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicLoad16U(memory_argument) => write!(
+                f,
+                "i64.atomic.load16_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicLoad32U(memory_argument) => write!(
+                f,
+                "i64.atomic.load32_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicStore(memory_argument) => write!(
+                f,
+                "i32.atomic.store {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicStore(memory_argument) => write!(
+                f,
+                "i64.atomic.store {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicStore8(memory_argument) => write!(
+                f,
+                "i32.atomic.store8 {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicStore16(memory_argument) => write!(
+                f,
+                "i32.atomic.store16 {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicStore8(memory_argument) => write!(
+                f,
+                "i64.atomic.store8 {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicStore16(memory_argument) => write!(
+                f,
+                "i64.atomic.store16 {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicStore32(memory_argument) => write!(
+                f,
+                "i64.atomic.store32 {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicRmwAdd(memory_argument) => write!(
+                f,
+                "i32.atomic.rmw.add {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmwAdd(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw.add {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicRmw8AddU(memory_argument) => write!(
+                f,
+                "i32.atomic.rmw8.add_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicRmw16AddU(memory_argument) => write!(
+                f,
+                "i32.atomic.rmw16.add_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmw8AddU(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw8.add_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmw16AddU(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw16.add_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmw32AddU(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw32.add_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicRmwSub(memory_argument) => write!(
+                f,
+                "i32.atomic.rmw.sub {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmwSub(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw.sub {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicRmw8SubU(memory_argument) => write!(
+                f,
+                "i32.atomic.rmw8.sub_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicRmw16SubU(memory_argument) => write!(
+                f,
+                "i32.atomic.rmw16.sub_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmw8SubU(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw8.sub_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmw16SubU(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw16.sub_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmw32SubU(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw32.sub_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicRmwAnd(memory_argument) => write!(
+                f,
+                "i32.atomic.rmw.and {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmwAnd(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw.and {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicRmw8AndU(memory_argument) => write!(
+                f,
+                "i32.atomic.rmw8.and_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicRmw16AndU(memory_argument) => write!(
+                f,
+                "i32.atomic.rmw16.and_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmw8AndU(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw8.and_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmw16AndU(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw16.and_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmw32AndU(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw32.and_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicRmwOr(memory_argument) => write!(
+                f,
+                "i32.atomic.rmw.or {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmwOr(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw.or {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicRmw8OrU(memory_argument) => write!(
+                f,
+                "i32.atomic.rmw8.or_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicRmw16OrU(memory_argument) => write!(
+                f,
+                "i32.atomic.rmw16.or_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmw8OrU(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw8.or_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmw16OrU(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw16.or_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmw32OrU(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw32.or_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicRmwXor(memory_argument) => write!(
+                f,
+                "i32.atomic.rmw.xor {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmwXor(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw.xor {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicRmw8XorU(memory_argument) => write!(
+                f,
+                "i32.atomic.rmw8.xor_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicRmw16XorU(memory_argument) => write!(
+                f,
+                "i32.atomic.rmw16.xor_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmw8XorU(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw8.xor_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmw16XorU(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw16.xor_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmw32XorU(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw32.xor_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicRmwXchg(memory_argument) => write!(
+                f,
+                "i32.atomic.rmw.xchg {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmwXchg(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw.xchg {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicRmw8XchgU(memory_argument) => write!(
+                f,
+                "i32.atomic.rmw8.xchg_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicRmw16XchgU(memory_argument) => write!(
+                f,
+                "i32.atomic.rmw16.xchg_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmw8XchgU(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw8.xchg_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmw16XchgU(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw16.xchg_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmw32XchgU(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw32.xchg_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicRmwCmpxchg(memory_argument) => write!(
+                f,
+                "i32.atomic.rmw.cmpxchg {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmwCmpxchg(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw.cmpxchg {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicRmw8CmpxchgU(memory_argument) => write!(
+                f,
+                "i32.atomic.rmw8.cmpxchg_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I32AtomicRmw16CmpxchgU(memory_argument) => write!(
+                f,
+                "i32.atomic.rmw16.cmpxchg_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmw8CmpxchgU(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw8.cmpxchg_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmw16CmpxchgU(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw16.cmpxchg_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
+            Instruction::I64AtomicRmw32CmpxchgU(memory_argument) => write!(
+                f,
+                "i64.atomic.rmw32.cmpxchg_u {}",
+                memory_argument.to_text(name_package, option_item_index)
+            ),
         }
     }
 }
@@ -959,6 +1819,11 @@ pub fn format_function_item(
         first_line_text_fragments.push(format!("${}", function_name));
     } else {
         first_line_text_fragments.push(format!("(;{};)", function_index));
+
+        // 重复的函数名称无法作为标识符，使用名称注解保留
+        if let Some(function_name) = name_package.get_duplicate_function_name(&function_index) {
+            first_line_text_fragments.push(format!("(@name \"{}\")", function_name));
+        }
     }
 
     first_line_text_fragments.push(inline_type_text_fragments.join(" ")); // 添加 type 文本到 first line 文本
@@ -1002,7 +1867,10 @@ pub fn format_function_item(
     for instruction in &instruction_items[0..instruction_items.len() - 1] {
         // 维护结构块的层级
         let indent_level = match instruction {
-            Instruction::Block(_, _) | Instruction::Loop(_, _) | Instruction::If(_, _) => {
+            Instruction::Block(_, _)
+            | Instruction::Loop(_, _)
+            | Instruction::If(_, _)
+            | Instruction::TryTable(_, _, _) => {
                 block_level += 1;
                 block_level - 1
             }
@@ -1096,6 +1964,9 @@ impl TextFormat for ExportItem {
                     format!("(global {})", global_variable_index)
                 }
             }
+            anvm_ast::ast::ExportDescriptor::TagIndex(tag_index) => {
+                format!("(tag {})", format_tag_index(name_package, &tag_index))
+            }
         };
 
         write!(f, "(export \"{}\" {})", self.name, tail)
@@ -1112,6 +1983,10 @@ impl TextFormat for ElementItem {
         // 示例
         // (elem $elem_one (offset (i32.const 1)) $func0 $func1)
         // (elem (;0;) (offset (i32.const 3)) 2 3 4)
+        // (elem (;1;) (table 1) (offset (i32.const 0)) func 2 3)
+        // (elem (;2;) func $func0 $func1)
+        // (elem (;3;) declare func 2)
+        // (elem (;4;) externref (item ref.null extern))
 
         let mut text_fragments: Vec<String> = vec![];
 
@@ -1125,26 +2000,51 @@ impl TextFormat for ElementItem {
             text_fragments.push(format!("(;{};)", element_index));
         }
 
-        let offset_text = format!(
-            "(offset ({}))",
-            format_constant_expression(&self.offset_instruction_items)
-        );
-        text_fragments.push(offset_text);
+        // 仅表 0 的 funcref 函数索引列表可以使用省略 `func` 关键字的简写形式
+        let mut is_abbreviated = false;
 
-        let function_indices_text = self
-            .function_indices
-            .iter()
-            .map(
-                |function_index| match name_package.get_function_name(function_index) {
-                    Some(function_name) => format!("${}", function_name),
-                    None => function_index.to_string(),
-                },
-            )
-            .collect::<Vec<String>>()
-            .join(" ");
-        text_fragments.push(format!("{})", function_indices_text));
+        match &self.mode {
+            ElementMode::Active {
+                table_index,
+                offset_instruction_items,
+            } => {
+                if *table_index != 0 {
+                    text_fragments.push(format!("(table {})", table_index));
+                } else {
+                    is_abbreviated = true;
+                }
 
-        write!(f, "{}", text_fragments.join(" "))
+                text_fragments.push(format_offset_expression(offset_instruction_items));
+            }
+            ElementMode::Passive => {}
+            ElementMode::Declarative => {
+                text_fragments.push("declare".to_string());
+            }
+        }
+
+        match &self.items {
+            ElementItems::FunctionIndices(function_indices) => {
+                if !is_abbreviated {
+                    text_fragments.push("func".to_string());
+                }
+
+                text_fragments.extend(function_indices.iter().map(|function_index| {
+                    match name_package.get_function_name(function_index) {
+                        Some(function_name) => format!("${}", function_name),
+                        None => function_index.to_string(),
+                    }
+                }));
+            }
+            ElementItems::Expressions(expressions) => {
+                text_fragments.push(self.element_type.to_string());
+
+                text_fragments.extend(expressions.iter().map(|expression| {
+                    format!("(item {})", format_constant_expression(expression))
+                }));
+            }
+        }
+
+        write!(f, "{})", text_fragments.join(" "))
     }
 }
 
@@ -1158,6 +2058,8 @@ impl TextFormat for DataItem {
         // 示例
         // (data $name (offset (i32.const 10)) "\11\22\33")
         // (data (;1;) (offset (i32.const 20)) "\aa\bb\cc")
+        // (data (;2;) (memory 1) (offset (i32.const 0)) "\dd")
+        // (data (;3;) "\ee")
 
         let mut text_fragments: Vec<String> = vec![];
 
@@ -1171,13 +2073,19 @@ impl TextFormat for DataItem {
             text_fragments.push(format!("(;{};)", data_index));
         }
 
-        // text_fragments.push(self.memory_block_index.to_string());
+        match &self.mode {
+            DataMode::Active {
+                memory_block_index,
+                offset_instruction_items,
+            } => {
+                if *memory_block_index != 0 {
+                    text_fragments.push(format!("(memory {})", memory_block_index));
+                }
 
-        let offset_text = format!(
-            "(offset ({}))",
-            format_constant_expression(&self.offset_instruction_items)
-        );
-        text_fragments.push(offset_text);
+                text_fragments.push(format_offset_expression(offset_instruction_items));
+            }
+            DataMode::Passive => {}
+        }
 
         let bytes_text = self
             .data
@@ -1191,29 +2099,113 @@ impl TextFormat for DataItem {
     }
 }
 
+/// 常量表达式的平铺形式，比如 `i32.const 1`、`global.get 0 i32.const 8 i32.add`
 pub fn format_constant_expression(instructions: &[Instruction]) -> String {
-    let first_instruction = instructions.first().unwrap();
-    match first_instruction {
+    instructions
+        .iter()
+        .filter(|instruction| !matches!(instruction, Instruction::End))
+        .map(format_constant_instruction)
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// 偏移值常量表达式，每一条指令都使用括号包围，
+/// 比如 `(offset (i32.const 1))`、`(offset (global.get 0) (i32.const 8) (i32.add))`
+fn format_offset_expression(instructions: &[Instruction]) -> String {
+    let instruction_texts = instructions
+        .iter()
+        .filter(|instruction| !matches!(instruction, Instruction::End))
+        .map(|instruction| format!("({})", format_constant_instruction(instruction)))
+        .collect::<Vec<String>>();
+
+    format!("(offset {})", instruction_texts.join(" "))
+}
+
+/// 常量表达式里的指令
+///
+/// 除了 `t.const`、`ref.null`、`ref.func` 和 `global.get` 指令之外，
+/// extended-const 提案还允许整数的加、减、乘指令。
+fn format_constant_instruction(instruction: &Instruction) -> String {
+    match instruction {
         Instruction::I32Const(value) => format!("i32.const {}", value),
         Instruction::I64Const(value) => format!("i64.const {}", value),
         Instruction::F32Const(value) => format!("f32.const {}", value),
         Instruction::F64Const(value) => format!("f64.const {}", value),
+        Instruction::V128Const(value) => format!("{}", Value::V128(*value)),
+        Instruction::GlobalGet(global_variable_index) => {
+            format!("global.get {}", global_variable_index)
+        }
+        Instruction::RefNull(ref_type) => format!("ref.null {}", format_heap_type(ref_type)),
+        Instruction::RefFunc(function_index) => format!("ref.func {}", function_index),
+        Instruction::I32Add => "i32.add".to_string(),
+        Instruction::I32Sub => "i32.sub".to_string(),
+        Instruction::I32Mul => "i32.mul".to_string(),
+        Instruction::I64Add => "i64.add".to_string(),
+        Instruction::I64Sub => "i64.sub".to_string(),
+        Instruction::I64Mul => "i64.mul".to_string(),
         _ => panic!("unsupported constant expression instruction"),
     }
 }
 
+/// 标签的索引，有名称时使用 `$name` 的形式
+fn format_tag_index(name_package: &NamePackage, tag_index: &u32) -> String {
+    if let Some(tag_name) = name_package.get_tag_name(tag_index) {
+        format!("${}", tag_name)
+    } else {
+        tag_index.to_string()
+    }
+}
+
+/// 内存类指令的内存块索引，索引为 0 时省略，否则在前面加一个空格
+fn format_memory_block_index(memory_block_index: &u32) -> String {
+    if *memory_block_index == 0 {
+        String::new()
+    } else {
+        format!(" {}", memory_block_index)
+    }
+}
+
+/// `call_indirect` 和 `return_call_indirect` 指令的参数，表索引为 0 时省略
+fn format_call_indirect_arguments(
+    name_package: &NamePackage,
+    type_index: &u32,
+    table_index: &u32,
+) -> String {
+    let type_text = if let Some(type_name) = name_package.get_type_name(type_index) {
+        format!("(type ${})", type_name)
+    } else {
+        format!("(type {})", type_index)
+    };
+
+    if *table_index == 0 {
+        type_text
+    } else {
+        format!("{} {}", table_index, type_text)
+    }
+}
+
+/// `ref.null` 指令的参数是 `堆类型`（heap type），即引用类型去掉 `ref` 后缀
+fn format_heap_type(ref_type: &RefType) -> &'static str {
+    match ref_type {
+        RefType::FuncRef => "func",
+        RefType::ExternRef => "extern",
+        RefType::ExnRef => "exn",
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use anvm_ast::{
         ast::{
-            CodeItem, CustomItem, DataItem, ElementItem, ExportDescriptor, ExportItem,
-            FunctionIndexAndBlockLabelsPair, FunctionIndexAndLocalVariableNamesPair, FunctionType,
-            GlobalItem, GlobalType, ImportDescriptor, ImportItem, IndexNamePair, Limit, LocalGroup,
-            MemoryType, Module, NameCollection, TableType, TypeItem,
+            CodeItem, CustomItem, DataItem, DataMode, ElementItem, ElementItems, ElementMode,
+            ExportDescriptor, ExportItem, FunctionIndexAndBlockLabelsPair,
+            FunctionIndexAndLocalVariableNamesPair, FunctionType, GlobalItem, GlobalType,
+            ImportDescriptor, ImportItem, IndexNamePair, Limit, LocalGroup, MemoryType, Module,
+            NameCollection, TableType, TagType, TypeItem,
         },
-        instruction::{BlockType, Instruction, MemoryArgument},
+        instruction::{BlockType, CatchClause, Instruction, MemoryArgument},
         name_package::NamePackage,
-        types::ValueType,
+        types::{RefType, ValueType},
     };
     use pretty_assertions::assert_eq;
 
@@ -1252,12 +2244,14 @@ mod tests {
             internal_function_to_type_index_list: vec![],
             tables: vec![],
             memory_blocks: vec![],
+            tag_items: vec![],
             global_items: vec![],
             export_items: vec![],
             start_function_index: None,
             element_items: vec![],
             code_items: vec![],
             data_items: vec![],
+            data_count: None,
         };
 
         let name_package = NamePackage::new(&module);
@@ -1297,19 +2291,27 @@ mod tests {
             internal_function_to_type_index_list: vec![],
             tables: vec![
                 TableType {
+                    element_type: RefType::FuncRef,
                     limit: Limit::Range(1, 8),
                 },
                 TableType {
+                    element_type: RefType::FuncRef,
                     limit: Limit::AtLeast(4),
                 },
+                TableType {
+                    element_type: RefType::ExternRef,
+                    limit: Limit::AtLeast(2),
+                },
             ],
             memory_blocks: vec![],
+            tag_items: vec![],
             global_items: vec![],
             export_items: vec![],
             start_function_index: None,
             element_items: vec![],
             code_items: vec![],
             data_items: vec![],
+            data_count: None,
         };
 
         let name_package = NamePackage::new(&module);
@@ -1323,6 +2325,11 @@ mod tests {
             module.tables[1].to_text(&name_package, Some(1)),
             "(table (;1;) 4 funcref)"
         );
+
+        assert_eq!(
+            module.tables[2].to_text(&name_package, Some(2)),
+            "(table (;2;) 2 externref)"
+        );
     }
 
     #[test]
@@ -1341,17 +2348,28 @@ mod tests {
             memory_blocks: vec![
                 MemoryType {
                     limit: Limit::Range(1, 8),
+                    shared: false,
+                    memory64: false,
                 },
                 MemoryType {
                     limit: Limit::AtLeast(4),
+                    shared: false,
+                    memory64: false,
+                },
+                MemoryType {
+                    limit: Limit::Range(1, 0x1_0000_0000),
+                    shared: false,
+                    memory64: true,
                 },
             ],
+            tag_items: vec![],
             global_items: vec![],
             export_items: vec![],
             start_function_index: None,
             element_items: vec![],
             code_items: vec![],
             data_items: vec![],
+            data_count: None,
         };
 
         let name_package = NamePackage::new(&module);
@@ -1365,6 +2383,11 @@ mod tests {
             module.memory_blocks[1].to_text(&name_package, Some(1)),
             "(memory (;1;) 4)"
         );
+
+        assert_eq!(
+            module.memory_blocks[2].to_text(&name_package, Some(2)),
+            "(memory (;2;) i64 1 4294967296)"
+        );
     }
 
     #[test]
@@ -1381,6 +2404,7 @@ mod tests {
             internal_function_to_type_index_list: vec![],
             tables: vec![],
             memory_blocks: vec![],
+            tag_items: vec![],
             global_items: vec![
                 GlobalItem {
                     global_type: GlobalType {
@@ -1402,12 +2426,25 @@ mod tests {
                         Instruction::End,
                     ],
                 },
+                GlobalItem {
+                    global_type: GlobalType {
+                        value_type: ValueType::I32,
+                        mutable: false,
+                    },
+                    initialize_instruction_items: vec![
+                        Instruction::I32Const(16),
+                        Instruction::I32Const(4),
+                        Instruction::I32Mul,
+                        Instruction::End,
+                    ],
+                },
             ],
             export_items: vec![],
             start_function_index: None,
             element_items: vec![],
             code_items: vec![],
             data_items: vec![],
+            data_count: None,
         };
 
         let name_package = NamePackage::new(&module);
@@ -1421,6 +2458,11 @@ mod tests {
             module.global_items[1].to_text(&name_package, Some(1)),
             "(global (;1;) i64 i64.const 2000)"
         );
+
+        assert_eq!(
+            module.global_items[2].to_text(&name_package, Some(2)),
+            "(global (;2;) i32 i32.const 16 i32.const 4 i32.mul)"
+        );
     }
 
     #[test]
@@ -1484,6 +2526,7 @@ mod tests {
                     module_name: "share".to_string(),
                     item_name: "main_table".to_string(),
                     import_descriptor: ImportDescriptor::TableType(TableType {
+                        element_type: RefType::FuncRef,
                         limit: Limit::Range(1, 4),
                     }),
                 },
@@ -1492,6 +2535,7 @@ mod tests {
                     module_name: "share".to_string(),
                     item_name: "minor_table".to_string(),
                     import_descriptor: ImportDescriptor::TableType(TableType {
+                        element_type: RefType::FuncRef,
                         limit: Limit::AtLeast(8),
                     }),
                 },
@@ -1501,6 +2545,8 @@ mod tests {
                     item_name: "main_memory".to_string(),
                     import_descriptor: ImportDescriptor::MemoryType(MemoryType {
                         limit: Limit::Range(1, 2),
+                        shared: false,
+                        memory64: false,
                     }),
                 },
                 ImportItem {
@@ -1509,6 +2555,8 @@ mod tests {
                     item_name: "minor_memory".to_string(),
                     import_descriptor: ImportDescriptor::MemoryType(MemoryType {
                         limit: Limit::AtLeast(6),
+                        shared: false,
+                        memory64: false,
                     }),
                 },
                 ImportItem {
@@ -1529,22 +2577,36 @@ mod tests {
                         value_type: ValueType::I64,
                     }),
                 },
+                ImportItem {
+                    // (import "common" "error" (tag (;0;) (type $typ0)))
+                    module_name: "common".to_string(),
+                    item_name: "error".to_string(),
+                    import_descriptor: ImportDescriptor::TagType(TagType { type_index: 0 }),
+                },
             ],
             internal_function_to_type_index_list: vec![],
             tables: vec![],
             memory_blocks: vec![],
+            tag_items: vec![],
             global_items: vec![],
             export_items: vec![],
             start_function_index: None,
             element_items: vec![],
             code_items: vec![],
             data_items: vec![],
+            data_count: None,
         };
 
         let name_package = NamePackage::new(&module);
 
-        let (lines, function_index, table_index, memory_block_index, global_variable_index) =
-            format_import_items(&module.import_items, &name_package);
+        let (
+            lines,
+            function_index,
+            table_index,
+            memory_block_index,
+            global_variable_index,
+            tag_index,
+        ) = format_import_items(&module.import_items, &name_package);
 
         let expected: Vec<&str> = vec![
             "(import \"env\" \"putc\" (func $func0 (type $typ0)))",
@@ -1555,6 +2617,7 @@ mod tests {
             "(import \"share\" \"minor_memory\" (memory (;1;) 6))",
             "(import \"common\" \"heap_pointer\" (global $g0 (mut i32)))",
             "(import \"common\" \"stack_pointer\" (global (;1;) i64))",
+            "(import \"common\" \"error\" (tag (;0;) (type $typ0)))",
         ];
 
         assert_eq!(lines, expected);
@@ -1563,6 +2626,7 @@ mod tests {
         assert_eq!(table_index, 2);
         assert_eq!(memory_block_index, 2);
         assert_eq!(global_variable_index, 2);
+        assert_eq!(tag_index, 1);
     }
 
     #[test]
@@ -1603,18 +2667,24 @@ mod tests {
                     index: 1,
                     name: "g1".to_string(),
                 }]),
+                NameCollection::TagNames(vec![IndexNamePair {
+                    index: 1,
+                    name: "e1".to_string(),
+                }]),
             ])],
             type_items: vec![],
             import_items: vec![],
             internal_function_to_type_index_list: vec![],
             tables: vec![],
             memory_blocks: vec![],
+            tag_items: vec![],
             global_items: vec![],
             export_items: vec![],
             start_function_index: None,
             element_items: vec![],
             code_items: vec![],
             data_items: vec![],
+            data_count: None,
         };
 
         let name_package = NamePackage::new(&module);
@@ -1641,6 +2711,17 @@ mod tests {
             Instruction::Call(1), // function 1 有名称 $f1
             Instruction::CallIndirect(0, 0),
             Instruction::CallIndirect(1, 0), // type 1 有名称 $t1
+            Instruction::ReturnCall(1),      // function 1 有名称 $f1
+            Instruction::ReturnCallIndirect(0, 0),
+            Instruction::Throw(0),
+            Instruction::ThrowRef,
+            Instruction::TryTable(BlockType::ResultI32, vec![CatchClause::Catch(0, 0)], 0),
+            Instruction::TryTable(
+                BlockType::ResultEmpty,
+                vec![CatchClause::CatchRef(1, 0), CatchClause::CatchAllRef(1)],
+                2,
+            ), // function 2 的 block 2 有名称 $b2，tag 1 有名称 $e1
+            Instruction::TryTable(BlockType::ResultEmpty, vec![CatchClause::CatchAll(0)], 0),
             Instruction::LocalGet(0),
             Instruction::LocalSet(1), // function 2 的 local 1 有名称 $l1
             Instruction::LocalTee(2), // function 2 的 local 2 有名称 $l2
@@ -1649,17 +2730,34 @@ mod tests {
             Instruction::I32Load(MemoryArgument {
                 offset: 100,
                 align: 2,
+                memory_block_index: 0,
             }),
             Instruction::I64Load(MemoryArgument {
                 offset: 200,
                 align: 3,
+                memory_block_index: 0,
+            }),
+            Instruction::I32Load(MemoryArgument {
+                offset: 0,
+                align: 2,
+                memory_block_index: 1,
             }),
             Instruction::MemorySize(0),
             Instruction::MemoryGrow(0),
+            Instruction::MemorySize(1),
+            Instruction::MemoryCopy(1, 0),
+            Instruction::CallIndirect(0, 1),
             Instruction::I32Const(100),
             Instruction::I64Const(200),
             Instruction::F32Const(2.414),
             Instruction::F64Const(1.618),
+            Instruction::SelectTyped(vec![ValueType::ExternRef]),
+            Instruction::RefNull(RefType::FuncRef),
+            Instruction::RefNull(RefType::ExternRef),
+            Instruction::RefIsNull,
+            Instruction::RefFunc(0),
+            Instruction::RefFunc(1), // function 1 有名称 $f1
+            Instruction::Block(BlockType::ResultFuncRef, 3),
         ];
 
         let expected: Vec<&str> = vec![
@@ -1683,6 +2781,13 @@ mod tests {
             "call $f1",
             "call_indirect (type 0)",
             "call_indirect (type $t1)",
+            "return_call $f1",
+            "return_call_indirect (type 0)",
+            "throw 0",
+            "throw_ref",
+            "try_table (result i32) (catch 0 0)",
+            "try_table $b2 (catch_ref $e1 0) (catch_all_ref 1)",
+            "try_table (catch_all 0)",
             "local.get 0",
             "local.set $l1",
             "local.tee $l2",
@@ -1690,12 +2795,23 @@ mod tests {
             "global.set $g1",
             "i32.load offset=100 align=4",
             "i64.load offset=200 align=8",
+            "i32.load 1 offset=0 align=4",
             "memory.size",
             "memory.grow",
+            "memory.size 1",
+            "memory.copy 1 0",
+            "call_indirect 1 (type 0)",
             "i32.const 100",
             "i64.const 200",
             "f32.const 2.414",
             "f64.const 1.618",
+            "select (result externref)",
+            "ref.null func",
+            "ref.null extern",
+            "ref.is_null",
+            "ref.func 0",
+            "ref.func $f1",
+            "block (result funcref)",
         ];
 
         let actual = instructions
@@ -1771,6 +2887,7 @@ mod tests {
             internal_function_to_type_index_list: vec![0, 1, 2, 3],
             tables: vec![],
             memory_blocks: vec![],
+            tag_items: vec![],
             global_items: vec![],
             export_items: vec![],
             start_function_index: None,
@@ -1822,6 +2939,7 @@ mod tests {
                 },
             ],
             data_items: vec![],
+            data_count: None,
         };
 
         let name_package = NamePackage::new(&module);
@@ -1930,6 +3048,7 @@ mod tests {
             internal_function_to_type_index_list: vec![],
             tables: vec![],
             memory_blocks: vec![],
+            tag_items: vec![],
             global_items: vec![],
             export_items: vec![
                 ExportItem {
@@ -1969,6 +3088,7 @@ mod tests {
             element_items: vec![],
             code_items: vec![],
             data_items: vec![],
+            data_count: None,
         };
 
         let name_package = NamePackage::new(&module);
@@ -2036,23 +3156,68 @@ mod tests {
             internal_function_to_type_index_list: vec![],
             tables: vec![],
             memory_blocks: vec![],
+            tag_items: vec![],
             global_items: vec![],
             export_items: vec![],
             start_function_index: None,
             element_items: vec![
                 ElementItem {
-                    table_index: 0,
-                    offset_instruction_items: vec![Instruction::I32Const(10), Instruction::End],
-                    function_indices: vec![0, 1, 2, 3],
+                    mode: ElementMode::Active {
+                        table_index: 0,
+                        offset_instruction_items: vec![Instruction::I32Const(10), Instruction::End],
+                    },
+                    element_type: RefType::FuncRef,
+                    items: ElementItems::FunctionIndices(vec![0, 1, 2, 3]),
+                },
+                ElementItem {
+                    mode: ElementMode::Active {
+                        table_index: 0,
+                        offset_instruction_items: vec![Instruction::I32Const(20), Instruction::End],
+                    },
+                    element_type: RefType::FuncRef,
+                    items: ElementItems::FunctionIndices(vec![4]),
+                },
+                ElementItem {
+                    mode: ElementMode::Active {
+                        table_index: 1,
+                        offset_instruction_items: vec![Instruction::I32Const(0), Instruction::End],
+                    },
+                    element_type: RefType::FuncRef,
+                    items: ElementItems::FunctionIndices(vec![0]),
+                },
+                ElementItem {
+                    mode: ElementMode::Passive,
+                    element_type: RefType::FuncRef,
+                    items: ElementItems::FunctionIndices(vec![1, 2]),
                 },
                 ElementItem {
-                    table_index: 0,
-                    offset_instruction_items: vec![Instruction::I32Const(20), Instruction::End],
-                    function_indices: vec![4],
+                    mode: ElementMode::Declarative,
+                    element_type: RefType::FuncRef,
+                    items: ElementItems::FunctionIndices(vec![3]),
+                },
+                ElementItem {
+                    mode: ElementMode::Passive,
+                    element_type: RefType::ExternRef,
+                    items: ElementItems::Expressions(vec![vec![
+                        Instruction::RefNull(RefType::ExternRef),
+                        Instruction::End,
+                    ]]),
+                },
+                ElementItem {
+                    mode: ElementMode::Active {
+                        table_index: 0,
+                        offset_instruction_items: vec![Instruction::I32Const(5), Instruction::End],
+                    },
+                    element_type: RefType::FuncRef,
+                    items: ElementItems::Expressions(vec![
+                        vec![Instruction::RefFunc(1), Instruction::End],
+                        vec![Instruction::RefNull(RefType::FuncRef), Instruction::End],
+                    ]),
                 },
             ],
             code_items: vec![],
             data_items: vec![],
+            data_count: None,
         };
 
         let name_package = NamePackage::new(&module);
@@ -2066,6 +3231,31 @@ mod tests {
             module.element_items[1].to_text(&name_package, Some(1)),
             "(elem (;1;) (offset (i32.const 20)) 4)"
         );
+
+        assert_eq!(
+            module.element_items[2].to_text(&name_package, Some(2)),
+            "(elem (;2;) (table 1) (offset (i32.const 0)) func $func0)"
+        );
+
+        assert_eq!(
+            module.element_items[3].to_text(&name_package, Some(3)),
+            "(elem (;3;) func $func1 2)"
+        );
+
+        assert_eq!(
+            module.element_items[4].to_text(&name_package, Some(4)),
+            "(elem (;4;) declare func 3)"
+        );
+
+        assert_eq!(
+            module.element_items[5].to_text(&name_package, Some(5)),
+            "(elem (;5;) externref (item ref.null extern))"
+        );
+
+        assert_eq!(
+            module.element_items[6].to_text(&name_package, Some(6)),
+            "(elem (;6;) (offset (i32.const 5)) funcref (item ref.func 1) (item ref.null func))"
+        );
     }
 
     #[test]
@@ -2082,6 +3272,7 @@ mod tests {
             internal_function_to_type_index_list: vec![],
             tables: vec![],
             memory_blocks: vec![],
+            tag_items: vec![],
             global_items: vec![],
             export_items: vec![],
             start_function_index: None,
@@ -2089,16 +3280,44 @@ mod tests {
             code_items: vec![],
             data_items: vec![
                 DataItem {
-                    memory_block_index: 0,
-                    offset_instruction_items: vec![Instruction::I32Const(10), Instruction::End],
+                    mode: DataMode::Active {
+                        memory_block_index: 0,
+                        offset_instruction_items: vec![Instruction::I32Const(10), Instruction::End],
+                    },
                     data: vec![0x11, 0x22, 0x33],
                 },
                 DataItem {
-                    memory_block_index: 0,
-                    offset_instruction_items: vec![Instruction::I32Const(20), Instruction::End],
+                    mode: DataMode::Active {
+                        memory_block_index: 0,
+                        offset_instruction_items: vec![Instruction::I32Const(20), Instruction::End],
+                    },
                     data: vec![0xaa, 0x0b, 0x09],
                 },
+                DataItem {
+                    mode: DataMode::Active {
+                        memory_block_index: 0,
+                        offset_instruction_items: vec![
+                            Instruction::GlobalGet(0),
+                            Instruction::I32Const(8),
+                            Instruction::I32Add,
+                            Instruction::End,
+                        ],
+                    },
+                    data: vec![0x01],
+                },
+                DataItem {
+                    mode: DataMode::Active {
+                        memory_block_index: 1,
+                        offset_instruction_items: vec![Instruction::I32Const(0), Instruction::End],
+                    },
+                    data: vec![0xdd],
+                },
+                DataItem {
+                    mode: DataMode::Passive,
+                    data: vec![0xee],
+                },
             ],
+            data_count: None,
         };
 
         let name_package = NamePackage::new(&module);
@@ -2112,5 +3331,20 @@ mod tests {
             module.data_items[1].to_text(&name_package, Some(1)),
             "(data (;1;) (offset (i32.const 20)) \"\\aa\\0b\\09\")"
         );
+
+        assert_eq!(
+            module.data_items[2].to_text(&name_package, Some(2)),
+            "(data (;2;) (offset (global.get 0) (i32.const 8) (i32.add)) \"\\01\")"
+        );
+
+        assert_eq!(
+            module.data_items[3].to_text(&name_package, Some(3)),
+            "(data (;3;) (memory 1) (offset (i32.const 0)) \"\\dd\")"
+        );
+
+        assert_eq!(
+            module.data_items[4].to_text(&name_package, Some(4)),
+            "(data (;4;) \"\\ee\")"
+        );
     }
 }
@@ -0,0 +1,15 @@
+(module
+    (memory 1)
+
+    ;; 原子指令同样可以作用于普通内存块
+    (func $rmw_or (result i32 i32)
+        (i32.atomic.store (i32.const 0) (i32.const 0x0f))
+        (i32.atomic.rmw.or (i32.const 0) (i32.const 0xf0))
+        (i32.atomic.load (i32.const 0))
+    )
+
+    ;; 普通内存块不能等待
+    (func $wait (result i32)
+        (memory.atomic.wait32 (i32.const 0) (i32.const 0) (i64.const -1))
+    )
+)
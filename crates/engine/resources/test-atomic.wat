@@ -0,0 +1,67 @@
+(module
+    (memory 1 1 shared)
+    (data (i32.const 0) "\01\00\00\00\00\00\00\00\ff\ff\ff\ff\ff\ff\ff\ff")
+
+    ;; 原子加载，窄于操作数类型的加载指令进行零扩展
+    (func $load (result i32 i64 i32 i64)
+        (i32.atomic.load (i32.const 0))
+        (i64.atomic.load (i32.const 8))
+        (i32.atomic.load8_u (i32.const 8))
+        (i64.atomic.load32_u (i32.const 8))
+    )
+
+    ;; 原子存储，然后以普通指令读回
+    (func $store (param $value i64) (result i64 i32)
+        (i64.atomic.store (i32.const 16) (local.get $value))
+        (i32.atomic.store16 (i32.const 24) (i32.const 0x12345678))
+        (i64.load (i32.const 16))
+        (i32.load (i32.const 24))
+    )
+
+    ;; 读取-修改-写入，返回旧值以及新值
+    (func $rmw_add (param $value i32) (result i32 i32)
+        (i32.atomic.rmw.add (i32.const 32) (local.get $value))
+        (i32.atomic.load (i32.const 32))
+    )
+
+    ;; 窄宽度的 rmw 会截断结果
+    (func $rmw8_sub (result i32 i32)
+        (i32.atomic.store8 (i32.const 40) (i32.const 1))
+        (i32.atomic.rmw8.sub_u (i32.const 40) (i32.const 2))
+        (i32.atomic.load8_u (i32.const 40))
+    )
+
+    ;; 交换
+    (func $rmw_xchg (param $value i64) (result i64 i64)
+        (i64.atomic.store (i32.const 48) (i64.const 7))
+        (i64.atomic.rmw.xchg (i32.const 48) (local.get $value))
+        (i64.atomic.load (i32.const 48))
+    )
+
+    ;; 比较并交换，返回旧值以及新值
+    (func $cmpxchg (param $expected i32) (param $replacement i32) (result i32 i32)
+        (i32.atomic.store (i32.const 56) (i32.const 10))
+        (i32.atomic.rmw.cmpxchg (i32.const 56) (local.get $expected) (local.get $replacement))
+        (i32.atomic.load (i32.const 56))
+    )
+
+    ;; 没有线程等待时唤醒的数量为 0
+    (func $notify (result i32)
+        (memory.atomic.notify (i32.const 0) (i32.const 1))
+    )
+
+    ;; 数值不相等时立即返回 1，相等时等待直到超时并返回 2
+    (func $wait (param $expected i32) (result i32)
+        (memory.atomic.wait32 (i32.const 0) (local.get $expected) (i64.const 1000))
+    )
+
+    ;; 地址未对齐
+    (func $unaligned (result i32)
+        (i32.atomic.load (i32.const 2))
+    )
+
+    ;; 内存屏障
+    (func $fence
+        (atomic.fence)
+    )
+)
@@ -1,19 +1,23 @@
 (module
     ;; 测试 br_table
     (func (param i32) (result i32)
-        (block
-            (block
-                (block
+        ;; br_table 的各个目标的类型必须一致，所以结构块都返回 i32
+        (block (result i32)
+            (block (result i32)
+                (block (result i32)
                     (i32.const 55)
                     (local.get 0)
                     (br_table 0 1 2 3)
                 ) ;; <-- 0
+                (drop)
                 (i32.const 22)
                 (return)
             ) ;; <-- 1
+            (drop)
             (i32.const 33)
             (return)
         ) ;; <-- 2
+        (drop)
         (i32.const 44)
     ) ;; <-- 3, 4...
 )
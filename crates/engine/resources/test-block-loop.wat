@@ -0,0 +1,80 @@
+(module
+    ;; 测试 loop 和 br_if 指令
+    ;; 计算 1 + 2 + ... + n
+    (func $sum (param $n i32) (result i32)
+        (local $i i32)
+        (local $acc i32)
+        (loop $next
+            (local.set $i (i32.add (local.get $i) (i32.const 1)))
+            (local.set $acc (i32.add (local.get $acc) (local.get $i)))
+            (br_if $next (i32.lt_u (local.get $i) (local.get $n)))
+        )
+        (local.get $acc)
+    )
+
+    ;; 测试嵌套的 loop/block 以及跨层跳转
+    ;; 统计小于 n 的素数的数量
+    (func $count_primes (param $n i32) (result i32)
+        (local $i i32)
+        (local $j i32)
+        (local $count i32)
+        (local.set $i (i32.const 2))
+        (block $done
+            (loop $outer
+                (br_if $done (i32.ge_u (local.get $i) (local.get $n)))
+                (local.set $j (i32.const 2))
+                (block $composite
+                    (loop $inner
+                        (br_if $composite
+                            (i32.gt_u (i32.mul (local.get $j) (local.get $j)) (local.get $i)))
+                        (if (i32.eqz (i32.rem_u (local.get $i) (local.get $j)))
+                            (then
+                                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                                (br $outer)
+                            )
+                        )
+                        (local.set $j (i32.add (local.get $j) (i32.const 1)))
+                        (br $inner)
+                    )
+                )
+                (local.set $count (i32.add (local.get $count) (i32.const 1)))
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br $outer)
+            )
+        )
+        (local.get $count)
+    )
+
+    ;; 测试带参数和返回值的 loop 结构块
+    ;; 累加值保存在操作数栈上，计算 n 的阶乘（模 2^32）
+    (func $factorial (param $n i32) (result i32)
+        (i32.const 1)
+        (loop $next (param i32) (result i32)
+            (i32.mul (local.get $n))
+            (local.tee $n (i32.sub (local.get $n) (i32.const 1)))
+            (br_if $next (i32.gt_s (i32.const 1)))
+        )
+    )
+
+    ;; 测试跳出结构块时丢弃多余的操作数
+    ;; 跳转时操作数栈上留有多余的操作数，跳转之后只保留目标结构块的返回值
+    (func $drop_extra (param $n i32) (result i32)
+        (local $acc i32)
+        (loop $next
+            (local.set $acc
+                (i32.add
+                    (local.get $acc)
+                    (block $b (result i32)
+                        (i32.const 100)
+                        (i32.const 200)
+                        (local.get $n)
+                        (br_table $b $b (i32.and (local.get $n) (i32.const 1)))
+                    )
+                )
+            )
+            (local.tee $n (i32.sub (local.get $n) (i32.const 1)))
+            (br_if $next)
+        )
+        (local.get $acc)
+    )
+)
@@ -9,26 +9,25 @@
     ;; 测试 return 指令
     (func $1 (result i32)
         (i32.const 1)
-        (block
+        (block (result i32)
             (i32.const 2) ;; got
             (return)
-            (i32.const 3)
         )
-        (i32.const 10)
+        (drop)
     )
 
     ;; 测试 return 指令
     (func $2 (result i32)
         (i32.const 1)
-        (block
+        (block (result i32)
             (i32.const 2)
-            (block
+            (block (result i32)
                 (i32.const 3) ;; got
                 (return)
-                (i32.const 4)
             )
+            (drop)
         )
-        (i32.const 10)
+        (drop)
     )
 
     ;; 测试 br 指令
@@ -37,8 +36,8 @@
         (block
             (i32.const 2)
             (br 0)
-            (i32.const 3)
         ) ;; <-- br to here
+        (drop)
         (i32.const 4) ;; got
     )
 
@@ -48,8 +47,8 @@
         (block
             (i32.const 2) ;; got
             (br 1)
-            (i32.const 3)
         )
+        (drop)
         (i32.const 4)
     ) ;; <-- br to here
 
@@ -63,7 +62,6 @@
                 (block
                     (i32.const 4)
                     (br 0)
-                    (i32.const 5)
                 ) ;; <-- br to here
                 (i32.const 11) ;; got
                 (return)
@@ -71,6 +69,7 @@
             (i32.const 12)
             (return)
         )
+        (drop)
         (i32.const 13)
     )
 
@@ -84,7 +83,6 @@
                 (block
                     (i32.const 4)
                     (br 1)
-                    (i32.const 5)
                 )
                 (i32.const 11)
                 (return)
@@ -92,6 +90,7 @@
             (i32.const 12)  ;; got
             (return)
         )
+        (drop)
         (i32.const 13)
     )
 
@@ -105,7 +104,6 @@
                 (block
                     (i32.const 4)
                     (br 2)
-                    (i32.const 5)
                 )
                 (i32.const 11)
                 (return)
@@ -113,6 +111,7 @@
             (i32.const 12)
             (return)
         ) ;; <-- br to here
+        (drop)
         (i32.const 13) ;; got
     )
 
@@ -159,6 +158,8 @@
             (i32.wrap_i64)
             (br 1)
         )
+        (drop)
+        (drop)
         (i32.const 99)
     )
 )
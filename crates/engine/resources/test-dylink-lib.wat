@@ -0,0 +1,53 @@
+;; 共享库 `libcounter.so`
+;;
+;; 数据段（相对于 __memory_base）：
+;; - 偏移 0：指向 counter 的指针，由 __wasm_apply_data_relocs 重定位
+;; - 偏移 4：counter，初始值为 100，__wasm_call_ctors 会将其加 1
+(module
+    (@dylink.0
+        (mem-info (memory 8 2) (table 1 0))
+    )
+    (import "env" "memory" (memory 1))
+    (import "env" "__indirect_function_table" (table 1 funcref))
+    (import "env" "__memory_base" (global $memory_base i32))
+    (import "env" "__table_base" (global $table_base i32))
+    (import "env" "__stack_pointer" (global $stack_pointer (mut i32)))
+    (import "env" "host_triple" (func $host_triple (param i32) (result i32)))
+
+    (global (export "counter_pointer") i32 (i32.const 0))
+    (global (export "counter") i32 (i32.const 4))
+
+    (data (global.get $memory_base) "\00\00\00\00\64\00\00\00")
+    (elem (global.get $table_base) func $double)
+
+    (func $add (export "add") (param i32 i32) (result i32)
+        (i32.add (local.get 0) (local.get 1))
+    )
+
+    ;; 内部函数，只能通过函数指针调用
+    (func $double (param i32) (result i32)
+        (i32.mul (local.get 0) (i32.const 2))
+    )
+
+    (func (export "get_double_pointer") (result i32)
+        (global.get $table_base)
+    )
+
+    (func (export "triple") (param i32) (result i32)
+        (call $host_triple (local.get 0))
+    )
+
+    (func (export "__wasm_apply_data_relocs")
+        (i32.store
+            (global.get $memory_base)
+            (i32.add (global.get $memory_base) (i32.const 4)))
+    )
+
+    (func (export "__wasm_call_ctors")
+        (i32.store
+            (i32.add (global.get $memory_base) (i32.const 4))
+            (i32.add
+                (i32.load (i32.add (global.get $memory_base) (i32.const 4)))
+                (i32.const 1)))
+    )
+)
@@ -0,0 +1,61 @@
+;; 依赖共享库 `libcounter.so` 的主模块
+(module
+    (@dylink.0
+        (mem-info (memory 4 2) (table 0 0))
+        (needed "libcounter.so")
+    )
+    (type $unary (func (param i32) (result i32)))
+    (type $binary (func (param i32 i32) (result i32)))
+
+    (import "env" "memory" (memory 1))
+    (import "env" "__indirect_function_table" (table 1 funcref))
+    (import "env" "__memory_base" (global $memory_base i32))
+    (import "env" "__table_base" (global $table_base i32))
+    (import "env" "__stack_pointer" (global $stack_pointer (mut i32)))
+    (import "env" "add" (func $add (type $binary)))
+    (import "env" "get_double_pointer" (func $get_double_pointer (result i32)))
+    (import "env" "triple" (func $triple (type $unary)))
+    (import "GOT.func" "add" (global $add_pointer (mut i32)))
+    (import "GOT.mem" "counter" (global $counter_address (mut i32)))
+    (import "GOT.mem" "counter_pointer" (global $counter_pointer_address (mut i32)))
+
+    (data (global.get $memory_base) "\07\00\00\00")
+
+    (func (export "call_add") (param i32 i32) (result i32)
+        (call $add (local.get 0) (local.get 1))
+    )
+
+    (func (export "call_add_by_pointer") (param i32 i32) (result i32)
+        (call_indirect (type $binary)
+            (local.get 0) (local.get 1) (global.get $add_pointer))
+    )
+
+    (func (export "call_double_by_pointer") (param i32) (result i32)
+        (call_indirect (type $unary)
+            (local.get 0) (call $get_double_pointer))
+    )
+
+    (func (export "call_triple") (param i32) (result i32)
+        (call $triple (local.get 0))
+    )
+
+    (func (export "read_counter") (result i32)
+        (i32.load (global.get $counter_address))
+    )
+
+    (func (export "read_counter_by_pointer") (result i32)
+        (i32.load (i32.load (global.get $counter_pointer_address)))
+    )
+
+    (func (export "read_local_data") (result i32)
+        (i32.load (global.get $memory_base))
+    )
+
+    (func (export "get_memory_base") (result i32)
+        (global.get $memory_base)
+    )
+
+    (func (export "get_stack_pointer") (result i32)
+        (global.get $stack_pointer)
+    )
+)
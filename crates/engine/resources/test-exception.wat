@@ -0,0 +1,77 @@
+(module
+    (type $fail_type (func (param i32)))
+    (import "env" "error" (tag $error (param i32)))
+    (import "env" "fail" (func $fail (type $fail_type)))
+
+    (tag $value (param i32 i64))
+    (tag $empty)
+
+    ;; 捕获异常并取出异常携带的数值
+    (func $catch_value (param i32) (result i32 i64)
+        (block $handler (result i32 i64)
+            (try_table (catch $value $handler)
+                (throw $value (local.get 0) (i64.const 100)))
+            (i32.const -1)
+            (i64.const -1)))
+
+    ;; 捕获在被调用的函数里抛出的异常
+    (func $throw_empty (param i32) (result i32)
+        (block
+            (if (local.get 0)
+                (then (throw $empty))))
+        (i32.const 10))
+
+    (func $catch_from_callee (param i32) (result i32)
+        (block $handler
+            (try_table (result i32) (catch_all $handler)
+                (block (result i32)
+                    (call $throw_empty (local.get 0))))
+            (return))
+        (i32.const 20))
+
+    ;; 捕获异常引用，然后重新抛出
+    (func $rethrow (param i32) (result i32)
+        (block $outer (result i32 i64)
+            (try_table (catch $value $outer)
+                (block $inner (result exnref)
+                    (try_table (catch_all_ref $inner)
+                        (throw $value (local.get 0) (i64.const 200)))
+                    (unreachable))
+                (throw_ref))
+            (unreachable))
+        (drop))
+
+    ;; 捕获异常之后跳转到 loop 结构块的开头
+    (func $retry (result i32)
+        (local $count i32)
+        (loop $again
+            (local.set $count (i32.add (local.get $count) (i32.const 1)))
+            (try_table (catch $empty $again)
+                (if (i32.lt_u (local.get $count) (i32.const 3))
+                    (then (throw $empty)))))
+        (local.get $count))
+
+    ;; 未被捕获的异常
+    (func $uncaught (param i32) (result i32)
+        (block $handler
+            (try_table (catch $empty $handler)
+                (throw $error (local.get 0))))
+        (i32.const 0))
+
+    ;; 捕获本地函数抛出的异常
+    (func $catch_native (param i32) (result i32)
+        (block $handler (result i32)
+            (try_table (catch $error $handler)
+                (call $fail (local.get 0)))
+            (i32.const 0)))
+
+    ;; 多次抛出并捕获异常
+    (func $retry_many (param i32) (result i32)
+        (local $count i32)
+        (loop $again
+            (local.set $count (i32.add (local.get $count) (i32.const 1)))
+            (try_table (catch_all $again)
+                (if (i32.lt_u (local.get $count) (local.get 0))
+                    (then (throw $empty)))))
+        (local.get $count))
+)
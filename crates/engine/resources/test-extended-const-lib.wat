@@ -0,0 +1,4 @@
+(module
+    (global (export "memory_base") i32 (i32.const 16))
+    (global (export "table_base") i32 (i32.const 2))
+)
@@ -0,0 +1,29 @@
+(module
+    (import "lib" "memory_base" (global $memory_base i32))
+    (import "lib" "table_base" (global $table_base i32))
+
+    (memory 1)
+    (table 4 funcref)
+
+    ;; 全局变量的初始值可以使用算术指令以及导入的全局变量
+    (global $g0 i32 (i32.add (global.get $memory_base) (i32.const 4)))
+    (global $g1 i64 (i64.mul (i64.const 6) (i64.sub (i64.const 10) (i64.const 3))))
+
+    ;; 数据段和元素段的偏移值
+    (data (i32.add (global.get $memory_base) (i32.const 8)) "\0a\0b")
+    (elem (offset (i32.add (global.get $table_base) (i32.const 1))) func $f)
+
+    (func $f (result i32)
+        (i32.const 42))
+
+    (func (result i32 i64)
+        (global.get $g0)
+        (global.get $g1))
+
+    (func (result i32 i32)
+        (i32.load8_u (i32.const 24))
+        (i32.load8_u (i32.const 25)))
+
+    (func (result i32)
+        (call_indirect (result i32) (i32.const 3)))
+)
@@ -0,0 +1,8 @@
+(module
+    ;; 无限递归
+    (func $0 (param i32) (result i32)
+        (i32.add
+            (local.get 0)
+            (call $0 (i32.const 1)))
+    )
+)
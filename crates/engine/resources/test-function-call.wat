@@ -3,6 +3,8 @@
     (func $0 (result i32)
         (i32.const 1)
         (call $return_8)
+        (drop)
+        (drop)
         (i32.const 2)
     )
 
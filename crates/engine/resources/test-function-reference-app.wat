@@ -0,0 +1,39 @@
+(module
+    (type $return_i32 (func (result i32)))
+    (import "lib" "get_ref" (func $get_ref (result funcref)))
+
+    (table $t0 1 funcref)
+    (global $g0 (mut funcref) (ref.null func))
+
+    (elem declare func $g)
+
+    ;; 跟 lib 模块的 $f 在各自模块里的索引相同
+    (func $g (type $return_i32)
+        (i32.const 222))
+
+    ;; 通过表调用其他模块的函数的引用
+    (func (result i32)
+        (table.set $t0 (i32.const 0) (call $get_ref))
+        (call_indirect $t0 (type $return_i32) (i32.const 0)))
+
+    ;; 通过全局变量传递其他模块的函数的引用
+    (func (result i32)
+        (global.set $g0 (call $get_ref))
+        (table.set $t0 (i32.const 0) (global.get $g0))
+        (call_indirect $t0 (type $return_i32) (i32.const 0)))
+
+    ;; 调用本模块的函数
+    (func (result i32)
+        (table.set $t0 (i32.const 0) (ref.func $g))
+        (call_indirect $t0 (type $return_i32) (i32.const 0)))
+
+    ;; 把函数引用返回给宿主
+    (func (result funcref)
+        (table.set $t0 (i32.const 0) (call $get_ref))
+        (table.get $t0 (i32.const 0)))
+
+    ;; 通过宿主传入的函数引用调用函数
+    (func (param funcref) (result i32)
+        (table.set $t0 (i32.const 0) (local.get 0))
+        (call_indirect $t0 (type $return_i32) (i32.const 0)))
+)
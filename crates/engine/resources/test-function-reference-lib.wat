@@ -0,0 +1,12 @@
+(module
+    (type $return_i32 (func (result i32)))
+
+    (elem declare func $f)
+
+    ;; 返回本模块函数的引用
+    (func $get_ref (export "get_ref") (result funcref)
+        (ref.func $f))
+
+    (func $f (type $return_i32)
+        (i32.const 111))
+)
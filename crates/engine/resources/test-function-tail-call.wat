@@ -0,0 +1,52 @@
+(module
+    (type $i32_to_i32 (func (param i32) (result i32)))
+
+    (table 2 funcref)
+    (elem (i32.const 0) $is_even $is_odd)
+
+    ;; 相互递归的尾调用，递归深度远超调用栈的容量
+    (func $is_even (param $n i32) (result i32)
+        (if (result i32)
+            (i32.eqz (local.get $n))
+            (then (i32.const 1))
+            (else (return_call $is_odd (i32.sub (local.get $n) (i32.const 1))))
+        )
+    )
+
+    (func $is_odd (param $n i32) (result i32)
+        (if (result i32)
+            (i32.eqz (local.get $n))
+            (then (i32.const 0))
+            (else (return_call $is_even (i32.sub (local.get $n) (i32.const 1))))
+        )
+    )
+
+    ;; 在结构块内尾调用，同时检查局部变量在每次调用时都被重新初始化
+    (func $factorial (param $n i64) (param $acc i64) (result i64)
+        (local $visited i32)
+        (if (local.get $visited)
+            (then unreachable)
+        )
+        (local.set $visited (i32.const 1))
+
+        (block $done
+            (br_if $done (i64.eqz (local.get $n)))
+            (return_call $factorial
+                (i64.sub (local.get $n) (i64.const 1))
+                (i64.mul (local.get $acc) (local.get $n)))
+        )
+        (local.get $acc)
+    )
+
+    ;; 间接尾调用
+    (func $dispatch (param $index i32) (param $n i32) (result i32)
+        (return_call_indirect (type $i32_to_i32) (local.get $n) (local.get $index))
+    )
+
+    ;; 尾调用的目标函数返回到当前函数的调用者
+    (func $is_even_plus_100 (param $n i32) (result i32)
+        (i32.add
+            (call $is_even (local.get $n))
+            (i32.const 100))
+    )
+)
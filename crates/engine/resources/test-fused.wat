@@ -0,0 +1,38 @@
+(module
+    ;; 测试超级指令
+    ;; `local.get; local.get; i32.add`
+    (func $add (param $a i32) (param $b i32) (result i32)
+        (i32.add (local.get $a) (local.get $b))
+    )
+
+    ;; 计算 0 + 1 + ... + (n - 1)，当 n 为负数时返回 -1
+    ;; `local.get; i32.const; i32.add; local.set` 以及 `i32.const; i32.lt_s; br_if`
+    (func $sum (param $n i32) (result i32)
+        (local $i i32)
+        (local $acc i32)
+        (br_if 0 (i32.const -1) (i32.lt_s (local.get $n) (i32.const 0)))
+        (drop)
+        (block $done
+            (loop $next
+                (br_if $done (i32.ge_s (local.get $i) (local.get $n)))
+                (local.set $acc (i32.add (local.get $acc) (local.get $i)))
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br $next)
+            )
+        )
+        (local.get $acc)
+    )
+
+    ;; 计算 0 + 1 + ... + 9
+    ;; 超级指令 `i32.const; i32.lt_s; br_if` 跳转到 loop 结构块
+    (func $sum_to_ten (result i32)
+        (local $i i32)
+        (local $acc i32)
+        (loop $next
+            (local.set $acc (i32.add (local.get $acc) (local.get $i)))
+            (local.set $i (i32.add (local.get $i) (i32.const 1)))
+            (br_if $next (i32.lt_s (local.get $i) (i32.const 10)))
+        )
+        (local.get $acc)
+    )
+)
@@ -0,0 +1,32 @@
+(module
+    (table 1 funcref)
+    (elem (i32.const 0) $get_counter)
+    (memory 1 4)
+    (data (i32.const 0) "\0a\00\00\00")
+    (global $counter (mut i32) (i32.const 100))
+
+    ;; 返回内存的第一个 i32 整数、全局变量、内存块页面数以及表的大小，
+    ;; 然后修改它们
+    (func $touch (result i32 i32 i32 i32)
+        (i32.load (i32.const 0))
+        (global.get $counter)
+        (memory.size)
+        (table.size 0)
+
+        (i32.store (i32.const 0) (i32.const 99))
+        (global.set $counter (i32.const 200))
+        (drop (memory.grow (i32.const 1)))
+        (i32.store (i32.const 65536) (i32.const 77))
+        (drop (table.grow 0 (ref.null func) (i32.const 1)))
+    )
+
+    (func $get_counter (result i32)
+        (global.get $counter)
+    )
+
+    ;; 修改内存之后陷入
+    (func $trap
+        (i32.store (i32.const 0) (i32.const 55))
+        (unreachable)
+    )
+)
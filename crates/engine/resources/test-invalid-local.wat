@@ -0,0 +1,6 @@
+(module
+    ;; 未能通过验证的模块：局部变量的索引超出范围
+    (func $f0
+        (local.set 100000 (i32.const 0))
+    )
+)
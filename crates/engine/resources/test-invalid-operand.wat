@@ -0,0 +1,6 @@
+(module
+    ;; 未能通过验证的模块：操作数的类型不匹配
+    (func $f0 (result i32)
+        (i32.add (f32.const 1.0) (i64.const 2))
+    )
+)
@@ -0,0 +1,140 @@
+(module
+    ;; 测试 JIT 编译器
+    (memory 1)
+
+    ;; 0
+    (func $i32_div_s (param $a i32) (param $b i32) (result i32)
+        (i32.div_s (local.get $a) (local.get $b))
+    )
+
+    ;; 1
+    (func $i32_rem_s (param $a i32) (param $b i32) (result i32)
+        (i32.rem_s (local.get $a) (local.get $b))
+    )
+
+    ;; 2
+    (func $i64_div_u (param $a i64) (param $b i64) (result i64)
+        (i64.div_u (local.get $a) (local.get $b))
+    )
+
+    ;; 3
+    (func $i64_rem_s (param $a i64) (param $b i64) (result i64)
+        (i64.rem_s (local.get $a) (local.get $b))
+    )
+
+    ;; 4
+    ;; 写入 i64 然后按不同的宽度读取，返回各个结果之和
+    (func $store_load (param $address i32) (param $value i64) (result i64)
+        (i64.store offset=8 (local.get $address) (local.get $value))
+        (i64.add
+            (i64.add
+                (i64.load offset=8 (local.get $address))
+                (i64.load8_s offset=8 (local.get $address))
+            )
+            (i64.add
+                (i64.extend_i32_u (i32.load16_u offset=8 (local.get $address)))
+                (i64.load32_s offset=12 (local.get $address))
+            )
+        )
+    )
+
+    ;; 5
+    ;; 按 f32 写入再读取，然后跟自身比较，NaN 不等于自身
+    (func $float_eq (param $value f32) (result i32)
+        (f32.store (i32.const 0) (local.get $value))
+        (f32.eq (f32.load (i32.const 0)) (local.get $value))
+    )
+
+    ;; 6
+    ;; 把 0..n 的平方写入内存，然后求和
+    (func $sum_squares (param $n i32) (result i64)
+        (local $i i32)
+        (local $acc i64)
+        (block $write_done
+            (loop $write_next
+                (br_if $write_done (i32.ge_u (local.get $i) (local.get $n)))
+                (i64.store
+                    (i32.shl (local.get $i) (i32.const 3))
+                    (i64.mul (i64.extend_i32_u (local.get $i)) (i64.extend_i32_u (local.get $i)))
+                )
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br $write_next)
+            )
+        )
+        (local.set $i (i32.const 0))
+        (block $read_done
+            (loop $read_next
+                (br_if $read_done (i32.ge_u (local.get $i) (local.get $n)))
+                (local.set $acc
+                    (i64.add (local.get $acc) (i64.load (i32.shl (local.get $i) (i32.const 3))))
+                )
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br $read_next)
+            )
+        )
+        (local.get $acc)
+    )
+
+    ;; 7
+    ;; 按索引跳到不同的结构块，并把操作数带到目标结构块
+    (func $branch_table (param $index i32) (param $value i32) (result i32)
+        (block $default (result i32)
+            (block $two (result i32)
+                (block $one (result i32)
+                    (block $zero (result i32)
+                        ;; 多余的操作数，使得跳转时需要把结果复制到结构块的开始位置
+                        (i32.const 7)
+                        (i32.mul (local.get $value) (i32.const 3))
+                        (local.get $index)
+                        (br_table $zero $one $two $default)
+                    )
+                    (i32.const 1)
+                    (i32.add)
+                    (br $default)
+                )
+                (i32.const 2)
+                (i32.add)
+                (br $default)
+            )
+            (i32.const 3)
+            (i32.add)
+        )
+    )
+
+    ;; 8
+    ;; 带到目标结构块的操作数是 v128 数值
+    (func $branch_table_v128 (param $index i32) (result i64)
+        (block $one (result v128)
+            (block $zero (result v128)
+                (i32.const 7)
+                (v128.const i64x2 1 2)
+                (local.get $index)
+                (br_table $zero $one)
+            )
+            (v128.const i64x2 10 20)
+            (i64x2.add)
+        )
+        (i64x2.extract_lane 1)
+    )
+
+    ;; 9
+    ;; 在循环里反复执行 br_table
+    (func $branch_table_loop (param $n i32) (result i32)
+        (local $i i32)
+        (local $acc i32)
+        (block $done
+            (loop $next
+                (br_if $done (i32.ge_u (local.get $i) (local.get $n)))
+                (local.set $acc
+                    (i32.add
+                        (local.get $acc)
+                        (call $branch_table (i32.rem_u (local.get $i) (i32.const 5)) (local.get $i))
+                    )
+                )
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br $next)
+            )
+        )
+        (local.get $acc)
+    )
+)
@@ -0,0 +1,35 @@
+(module
+    (memory $m0 1)
+    (memory $m1 i64 1)
+
+    (data (i32.const 0) "hello")
+
+    ;; memory.fill，填充值只取低 8 位
+    (func (result i32 i32)
+        (memory.fill (i32.const 10) (i32.const 0x1ab) (i32.const 4))
+        (i32.load (i32.const 10))
+        (i32.load8_u (i32.const 14)))
+
+    ;; memory.copy，源区间和目标区间重叠
+    (func (result i32 i32)
+        (memory.copy (i32.const 2) (i32.const 0) (i32.const 5))
+        (i32.load (i32.const 0))
+        (i32.load (i32.const 4)))
+
+    ;; 从 32 位内存块复制到 64 位内存块，以及填充 64 位内存块
+    (func (result i32 i32)
+        (memory.copy $m1 $m0 (i64.const 100) (i32.const 0) (i32.const 4))
+        (memory.fill $m1 (i64.const 8) (i32.const 7) (i64.const 2))
+        (i32.load $m1 (i64.const 100))
+        (i32.load16_u $m1 (i64.const 8)))
+
+    ;; 越界的 memory.fill
+    (func (param i32) (result i32)
+        (memory.fill (i32.const 65535) (i32.const 1) (local.get 0))
+        (i32.load8_u (i32.const 65535)))
+
+    ;; 主动模式的数据段在实例化之后已被丢弃
+    (func (param i32)
+        (memory.init 0 (i32.const 0) (i32.const 0) (local.get 0))
+        (data.drop 0))
+)
@@ -0,0 +1,19 @@
+(module
+    ;; 没有指定最大页面数的 64 位内存块
+    (memory $m0 i64 1)
+
+    ;; 增长内存块，返回 memory.grow 的结果以及增长之后的页面数
+    (func (param i64) (result i64 i64)
+        (memory.grow (local.get 0))
+        (memory.size)
+    )
+
+    ;; 写入最后一个字节然后读取
+    (func (result i32)
+        (i32.store8
+            (i64.sub (i64.mul (memory.size) (i64.const 65536)) (i64.const 1))
+            (i32.const 0x7f))
+        (i32.load8_u
+            (i64.sub (i64.mul (memory.size) (i64.const 65536)) (i64.const 1)))
+    )
+)
@@ -0,0 +1,23 @@
+(module
+    (memory $m0 i64 1 4)
+    (data (i64.const 0x10) "\01\02\03\04")
+
+    ;; 使用 i64 地址读取数据段的内容
+    (func (result i32 i32)
+        (i32.load8_u (i64.const 0x10))
+        (i32.load16_u offset=2 (i64.const 0x10)))
+
+    ;; 写入然后读取
+    (func (param i64 i64) (result i64)
+        (i64.store (local.get 0) (local.get 1))
+        (i64.load (local.get 0)))
+
+    ;; 内存块的页面数是 i64
+    (func (param i64) (result i64 i64)
+        (memory.grow (local.get 0))
+        (memory.size))
+
+    ;; 偏移值超出 4GiB
+    (func (result i32)
+        (i32.load offset=0x1_0000_0000 (i64.const 0)))
+)
@@ -0,0 +1,9 @@
+(module
+    ;; 导入的内存块排在模块内定义的内存块之前
+    (import "lib" "memory1" (memory $imported 1 3))
+    (memory $own 1)
+
+    (func (result i32 i32)
+        (i32.load8_u $imported (i32.const 2))
+        (i32.load8_u $own (i32.const 2)))
+)
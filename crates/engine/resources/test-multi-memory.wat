@@ -0,0 +1,47 @@
+(module
+    (type $i32_to_i32 (func (param i32) (result i32)))
+
+    (memory $m0 1)
+    (memory $m1 1 3)
+    (export "memory1" (memory $m1))
+
+    (data (i32.const 0) "\0a\0b")
+    (data (memory $m1) (offset (i32.const 0)) "\01\02\03\04")
+
+    (table $t0 2 funcref)
+    (table $t1 2 funcref)
+
+    (elem (table $t1) (offset (i32.const 0)) func $inc $double)
+
+    (func $inc (type $i32_to_i32)
+        (i32.add (local.get 0) (i32.const 1)))
+
+    (func $double (type $i32_to_i32)
+        (i32.mul (local.get 0) (i32.const 2)))
+
+    ;; 分别读取两个内存块
+    (func (result i32 i32)
+        (i32.load8_u $m0 (i32.const 1))
+        (i32.load8_u $m1 (i32.const 1)))
+
+    ;; 写入第二个内存块，第一个内存块不受影响
+    (func (param i32) (result i32 i32)
+        (i32.store $m1 (i32.const 8) (local.get 0))
+        (i32.load $m1 (i32.const 8))
+        (i32.load $m0 (i32.const 8)))
+
+    ;; 第二个内存块的页面数
+    (func (result i32 i32 i32)
+        (memory.grow $m1 (i32.const 2))
+        (memory.size $m1)
+        (memory.size $m0))
+
+    ;; 通过第二张表间接调用函数
+    (func (param i32 i32) (result i32)
+        (call_indirect $t1 (type $i32_to_i32) (local.get 1) (local.get 0)))
+
+    ;; 把第二张表的元素复制到第一张表，然后通过第一张表间接调用函数
+    (func (param i32) (result i32)
+        (table.copy $t0 $t1 (i32.const 0) (i32.const 0) (i32.const 2))
+        (call_indirect $t0 (type $i32_to_i32) (local.get 0) (i32.const 1)))
+)
@@ -109,4 +109,40 @@
         (i32.const 2)   ;; 先弹，rhs
         (i32.rotr)      ;; 0b00 + （29个'1'） + 0
     )
+
+    ;; 溢出时回绕
+
+    (func $f15 (result i32 i32 i32)
+        (i32.add (i32.const 0x7fffffff) (i32.const 1))      ;; -2147483648
+        (i32.sub (i32.const -2147483648) (i32.const 1))     ;; 2147483647
+        (i32.mul (i32.const 0x10000) (i32.const 0x10001))   ;; 0x10000
+    )
+
+    (func $f16 (result i64 i64 i64)
+        (i64.add (i64.const 0x7fffffffffffffff) (i64.const 1))
+        (i64.sub (i64.const -9223372036854775808) (i64.const 1))
+        (i64.mul (i64.const 0x100000000) (i64.const 0x100000001))
+    )
+
+    ;; 移位的位数按数据宽度取模
+
+    (func $f17 (result i32 i32 i64)
+        (i32.shl (i32.const 1) (i32.const 33))              ;; 2
+        (i32.shr_u (i32.const -1) (i32.const 60))           ;; 0b1111
+        (i64.shr_s (i64.const -16) (i64.const 66))          ;; -4
+    )
+
+    ;; 浮点数的 min/max 以及 copysign
+
+    (func $f18 (param $a f32) (param $b f32) (result f32)
+        (f32.min (local.get $a) (local.get $b))
+    )
+
+    (func $f19 (param $a f64) (param $b f64) (result f64)
+        (f64.max (local.get $a) (local.get $b))
+    )
+
+    (func $f20 (param $a f32) (param $b f32) (result f32)
+        (f32.copysign (local.get $a) (local.get $b))
+    )
 )
@@ -53,5 +53,25 @@
         (f32.convert_i32_u)
     )
 
+    ;; 截断的陷阱
+
+    (func $f9 (param $value f32) (result i32)
+        (i32.trunc_f32_s (local.get $value))
+    )
+
+    (func $f10 (param $value f64) (result i64)
+        (i64.trunc_f64_u (local.get $value))
+    )
+
+    ;; 饱和截断
+
+    (func $f11 (param $value f32) (result i32)
+        (i32.trunc_sat_f32_s (local.get $value))
+    )
+
+    (func $f12 (param $value f64) (result i64)
+        (i64.trunc_sat_f64_u (local.get $value))
+    )
+
     ;; todo 其他测试
 )
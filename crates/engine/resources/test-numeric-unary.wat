@@ -76,4 +76,10 @@
         (f32.const 25.0)
         (f32.sqrt)          ;; 5.0
     )
+
+    ;; 就近取整，只有恰好位于两个整数中间时才是 "5 奇进偶不进"
+
+    (func $f14 (param $value f64) (result f64)
+        (f64.nearest (local.get $value))
+    )
 )
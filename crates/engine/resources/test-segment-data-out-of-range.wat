@@ -0,0 +1,10 @@
+(module
+    (table 2 funcref)
+    (memory 1)
+    (elem (i32.const 0) $f0)
+    (data (i32.const 0) "\11\22")
+    (data (i32.const 65534) "\33\44\55")
+    (func $f0 (result i32)
+        (i32.const 0)
+    )
+)
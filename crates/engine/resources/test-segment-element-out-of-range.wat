@@ -0,0 +1,9 @@
+(module
+    (table 2 funcref)
+    (memory 1)
+    (elem (i32.const -1) $f0)
+    (data (i32.const 0) "\11\22")
+    (func $f0 (result i32)
+        (i32.const 0)
+    )
+)
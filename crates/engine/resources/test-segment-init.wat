@@ -0,0 +1,21 @@
+(module
+    (type $ft (func (result i32)))
+    (table 4 funcref)
+    (memory 1)
+    (elem (i32.const 1) $f10 $f20)
+    (data (i32.const 0) "\11\22\33\44")
+    (data (i32.const 65532) "\55\66\77\88")
+    (func $f10 (result i32)
+        (i32.const 10)
+    )
+    (func $f20 (result i32)
+        (i32.const 20)
+    )
+    (func $f2 (param $index i32) (result i32)
+        (call_indirect (type $ft) (local.get $index))
+    )
+    (func $f3 (result i32 i32)
+        (i32.load (i32.const 0))
+        (i32.load (i32.const 65532))
+    )
+)
@@ -0,0 +1,54 @@
+(module
+    (memory 1)
+    (data (i32.const 0) "\00\01\02\03\04\05\06\07\08\09\0a\0b\0c\0d\0e\0f")
+
+    ;; 逐通道的整数加法
+    (func $i32x4_add (param $a v128) (param $b v128) (result v128)
+        (i32x4.add (local.get $a) (local.get $b))
+    )
+
+    ;; 饱和加法
+    (func $i8x16_add_sat_s (param $a v128) (param $b v128) (result v128)
+        (i8x16.add_sat_s (local.get $a) (local.get $b))
+    )
+
+    ;; 浮点数的最小值，NaN 会传播
+    (func $f32x4_min (param $a v128) (param $b v128) (result v128)
+        (f32x4.min (local.get $a) (local.get $b))
+    )
+
+    ;; 洗牌，结果为 a 的通道 0~7 与 b 的通道 0~7 交替排列
+    (func $shuffle (param $a v128) (param $b v128) (result v128)
+        (i8x16.shuffle 0 16 1 17 2 18 3 19 4 20 5 21 6 22 7 23 (local.get $a) (local.get $b))
+    )
+
+    ;; splat 之后读写通道
+    (func $splat_and_lane (param $x i32) (result i32 i32)
+        (i16x8.extract_lane_s 0 (i16x8.splat (local.get $x)))
+        (i32x4.extract_lane 3
+            (i32x4.replace_lane 3 (v128.const i32x4 1 2 3 4) (local.get $x)))
+    )
+
+    ;; 从内存加载之后，按位比较以及测试
+    (func $load_and_test (param $address i32) (result i32 i32)
+        (i8x16.bitmask
+            (i8x16.gt_u (v128.load (local.get $address)) (v128.const i8x16 7 7 7 7 7 7 7 7 7 7 7 7 7 7 7 7)))
+        (v128.any_true (v128.load8_splat (local.get $address)))
+    )
+
+    ;; 移位以及扩展
+    (func $shift_and_extend (param $a v128) (param $count i32) (result v128)
+        (i32x4.extend_low_i16x8_s (i16x8.shl (local.get $a) (local.get $count)))
+    )
+
+    ;; 把通道写入内存，然后以 i32 读回
+    (func $store_lane (param $a v128) (result i32)
+        (v128.store32_lane 2 (i32.const 100) (local.get $a))
+        (i32.load (i32.const 100))
+    )
+
+    ;; 越界访问
+    (func $load_out_of_range
+        (drop (v128.load (i32.const 65530)))
+    )
+)
@@ -0,0 +1,63 @@
+(module
+    (type $type_ret_i32 (func (result i32)))
+
+    (table $t 4 10 funcref)
+
+    ;; 被动元素段，需要使用 table.init 写入表
+    (elem $e0 func $f10 $f20)
+
+    ;; 声明元素段，只用于声明可以被 ref.func 引用的函数
+    (elem declare func $f10 $f20)
+
+    (func $f10 (result i32)
+        (i32.const 10)
+    )
+
+    (func $f20 (result i32)
+        (i32.const 20)
+    )
+
+    ;; 把元素段 $e0 写入到表的 [1, 3)，然后调用指定槽的函数
+    (func $init_and_call (param $index i32) (result i32)
+        (table.init $e0 (i32.const 1) (i32.const 0) (i32.const 2))
+        (call_indirect (type $type_ret_i32) (local.get $index))
+    )
+
+    ;; 元素段被丢弃之后，长度为 0，table.init 应该访问越界
+    (func $drop_and_init
+        (elem.drop $e0)
+        (table.init $e0 (i32.const 0) (i32.const 0) (i32.const 1))
+    )
+
+    ;; 增加表的槽数，返回旧的槽数以及新的槽数
+    (func $grow (param $count i32) (result i32 i32)
+        (table.grow $t (ref.func $f10) (local.get $count))
+        (table.size $t)
+    )
+
+    ;; 先把指定的槽设置为函数引用，再设置为 null，返回两次读取时 ref.is_null 的结果
+    (func $set_and_get (param $index i32) (result i32 i32)
+        (table.set $t (local.get $index) (ref.func $f20))
+        (ref.is_null (table.get $t (local.get $index)))
+        (table.set $t (local.get $index) (ref.null func))
+        (ref.is_null (table.get $t (local.get $index)))
+    )
+
+    ;; 把 [0, 2) 填充为 $f20，再复制到 [2, 4)，然后调用槽 3 的函数
+    (func $fill_and_copy (result i32)
+        (table.fill $t (i32.const 0) (ref.func $f20) (i32.const 2))
+        (table.copy $t $t (i32.const 2) (i32.const 0) (i32.const 2))
+        (call_indirect (type $type_ret_i32) (i32.const 3))
+    )
+
+    ;; 外部引用由宿主提供，原样返回，同时返回它是否为 null
+    (func $extern_ref (param $ref externref) (result externref i32)
+        (local.get $ref)
+        (ref.is_null (local.get $ref))
+    )
+
+    ;; 带类型的 select 指令
+    (func $select_ref (param $cond i32) (result funcref)
+        (select (result funcref) (ref.func $f10) (ref.null func) (local.get $cond))
+    )
+)
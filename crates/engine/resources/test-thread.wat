@@ -0,0 +1,25 @@
+(module
+    (memory 1 1 shared)
+
+    ;; 新线程的入口函数
+    ;; 把 start_arg 累加到地址 0，然后累加地址 4 的完成计数并唤醒等待者
+    (func (export "wasi_thread_start") (param $tid i32) (param $start_arg i32)
+        (drop (i32.atomic.rmw.add (i32.const 0) (local.get $start_arg)))
+        (drop (i32.atomic.rmw.add (i32.const 4) (i32.const 1)))
+        (drop (memory.atomic.notify (i32.const 4) (i32.const -1)))
+    )
+
+    ;; 等待直到完成计数达到 $count，然后返回累加的结果
+    (func (export "wait_for") (param $count i32) (result i32)
+        (local $finished i32)
+        (block $done
+            (loop $again
+                (local.set $finished (i32.atomic.load (i32.const 4)))
+                (br_if $done (i32.ge_u (local.get $finished) (local.get $count)))
+                (drop (memory.atomic.wait32 (i32.const 4) (local.get $finished) (i64.const -1)))
+                (br $again)
+            )
+        )
+        (i32.atomic.load (i32.const 0))
+    )
+)
@@ -0,0 +1,1051 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 字节码
+//!
+//! 解码器输出的 `object::Instruction` 包裹着完整的 `anvm_ast::instruction::Instruction`，
+//! 体积较大，而且执行每一条指令都需要经过两层 `match`。为了提高执行速度，
+//! 创建 VM 模块时会把指令列表再编译为一种紧凑的内部字节码：
+//!
+//! - 每个字节码（`Opcode`）只占 16 个字节，并且实现了 `Copy`，执行时直接复制而无需克隆指令；
+//! - 不带参数的顺序执行指令（比如数值运算指令）预先解析为对应的指令处理函数；
+//! - 局部变量的索引即局部变量槽相对于当前调用帧开始位置（fp）的偏移值；
+//! - 全局变量以及内存块的索引预先转换为它们在 VM 实例列表里的索引；
+//! - 跳转目标、函数调用目标以及内存读写参数等较大的数据保存在字节码的附属列表里，
+//!   字节码只记录它们在列表里的索引。
+//!
+//! 其余不常用的指令（比如表、SIMD、原子指令以及异常处理指令等）编译为 `Opcode::Fallback`，
+//! 执行时回退到 `interpreter::exec_instruction` 执行原指令。
+//!
+//! ## 地址映射
+//!
+//! 字节码跟指令列表是一一对应的，即字节码的地址跟原指令的地址相同，
+//! 所以跳转地址、函数的开始地址等都无需转换，调试器也可以通过 VM 的当前地址（pc）
+//! 获取原指令（见 `VMModule::get_instruction` 方法）。
+//!
+//! ## 超级指令
+//!
+//! 解码器识别出的超级指令（见 `decoder` 模块）编译为超级字节码，保存在另一份字节码列表
+//! `fused_opcodes` 里：超级字节码位于被融合的第一条原指令的地址，执行之后直接跳到
+//! 被融合的最后一条原指令的下一条指令，其余位置的字节码跟 `opcodes` 的相同。
+//!
+//! 连续执行（`VM::recur_without_break`）时使用 `fused_opcodes`，
+//! 单步执行（`VM::step`）时使用 `opcodes`，所以单步执行仍然会逐条执行原指令。
+
+use anvm_ast::{
+    instruction::{Instruction, MemoryArgument},
+    types::ValueType,
+};
+
+use crate::{
+    error::EngineError,
+    ins_atomic, ins_memory,
+    ins_memory::MemoryAccess,
+    ins_numeric_binary, ins_numeric_comparsion, ins_numeric_convert, ins_numeric_eqz,
+    ins_numeric_unary, ins_parametric, ins_reference, ins_simd,
+    object::{self, BranchTarget, Control, FusedInstruction, FusedItem},
+    vm::VM,
+};
+
+/// 不带参数的顺序执行指令的处理函数
+pub type SequenceFunction = fn(&mut VM) -> Result<(), EngineError>;
+
+/// 内存加载和存储指令的处理函数
+pub type MemoryFunction = fn(&mut VM, &MemoryAccess) -> Result<(), EngineError>;
+
+/// 字节码
+#[derive(Debug, Clone, Copy)]
+pub enum Opcode {
+    /// 不需要执行任何操作的指令
+    ///
+    /// 比如 `block/loop 指令`、结构块的 `end 指令` 以及 `nop 指令`。
+    Nop,
+
+    /// 不带参数的顺序执行指令，参数是预先解析的指令处理函数
+    Sequence(SequenceFunction),
+
+    I32Const(i32),
+    I64Const(i64),
+    F32Const(f32),
+    F64Const(f64),
+
+    /// 读取局部变量，参数是局部变量槽相对于当前调用帧开始位置的偏移值
+    ///
+    /// 局部变量指令直接复制栈槽，所以只用于非 v128 类型的局部变量，
+    /// v128 类型的局部变量的读写指令会回退到执行原指令。
+    LocalGet(u32),
+
+    /// 写入局部变量，参数是局部变量槽相对于当前调用帧开始位置的偏移值
+    LocalSet(u32),
+
+    /// 写入局部变量并保留栈顶的操作数，参数是局部变量槽相对于当前调用帧开始位置的偏移值
+    LocalTee(u32),
+
+    /// 读取全局变量，参数是全局变量在 VM 全局变量实例列表里的索引
+    GlobalGet(u32),
+
+    /// 内存加载或存储指令
+    ///
+    /// 参数是指令处理函数以及预先解析的内存读写参数在 `memory_accesses` 列表里的索引
+    Memory(MemoryFunction, u32),
+
+    /// 无条件跳转，原 `else 指令`，参数是目标地址
+    Jump(u32),
+
+    /// 当原栈顶的数值等于 0 时跳转，否则继续执行下一条指令
+    ///
+    /// 原 `if 指令`，参数是 else 结构块的开始位置，或者 if 结构块之后的位置。
+    JumpWhenEqZero(u32),
+
+    /// 原 `br/return 指令`，参数是跳转项在 `break_items` 列表里的索引
+    Break(u32),
+
+    /// 原 `br_if 指令`，参数是跳转项在 `break_items` 列表里的索引
+    BreakWhenNotEqZero(u32),
+
+    /// 调用（普通）函数，参数是调用项在 `call_items` 列表里的索引
+    Call(u32),
+
+    /// 回退到执行原指令
+    Fallback,
+
+    /// 超级字节码 `local.get a; local.get b; i32.add`，参数是两个局部变量槽的偏移值
+    LocalGetLocalGetI32Add(u32, u32),
+
+    /// 超级字节码 `local.get a; i32.const c; i32.add; local.set b`
+    ///
+    /// 参数分别是源局部变量槽的偏移值、常量以及目标局部变量槽的偏移值。
+    LocalGetI32ConstI32AddLocalSet(u32, i32, u32),
+
+    /// 超级字节码 `i32.const c; i32.lt_s; br_if`
+    ///
+    /// 参数分别是常量以及跳转项在 `break_items` 列表里的索引。
+    I32ConstI32LtSBreakWhenNotEqZero(i32, u32),
+}
+
+/// 跳转项
+#[derive(Debug, PartialEq, Clone)]
+pub struct BreakItem {
+    /// 指令所在的结构块索引，仅用于出错信息
+    pub option_block_index: Option<usize>,
+    pub branch_target: BranchTarget,
+}
+
+/// 函数调用项
+///
+/// 函数调用的目标已经由解码器预先解析，各成员的含义跟 `Control::Call` 的一样。
+#[derive(Debug, PartialEq, Clone)]
+pub struct CallItem {
+    pub vm_module_index: usize,
+    pub type_index: usize,
+    pub function_index: usize,
+    pub internal_function_index: usize,
+    pub address: usize,
+}
+
+/// 一个模块的字节码
+#[derive(Debug, Clone)]
+pub struct Bytecode {
+    /// 字节码列表，跟模块的指令列表一一对应
+    pub opcodes: Vec<Opcode>,
+
+    /// 包含超级字节码的字节码列表，长度跟 `opcodes` 的相同
+    pub fused_opcodes: Vec<Opcode>,
+
+    /// 预先解析的内存读写参数列表
+    pub memory_accesses: Vec<MemoryAccess>,
+
+    /// 跳转项列表
+    pub break_items: Vec<BreakItem>,
+
+    /// 函数调用项列表
+    pub call_items: Vec<CallItem>,
+}
+
+impl Bytecode {
+    /// 编译一个模块的指令列表
+    ///
+    /// 参数 memory_indexes 和 global_variable_indexes 分别是当前模块的内存块和
+    /// 全局变量在 VM 实例列表里的索引。
+    ///
+    /// 参数 function_local_variable_types_list 是当前模块每个内部函数的开始地址，
+    /// 以及函数的局部变量（包括参数）的数据类型列表，按开始地址从小到大排列。
+    pub fn compile(
+        instructions: &[object::Instruction],
+        memory_indexes: &[usize],
+        global_variable_indexes: &[usize],
+        function_local_variable_types_list: &[(usize, Vec<ValueType>)],
+    ) -> Self {
+        let mut bytecode = Bytecode {
+            opcodes: Vec::with_capacity(instructions.len()),
+            fused_opcodes: vec![],
+            memory_accesses: vec![],
+            break_items: vec![],
+            call_items: vec![],
+        };
+
+        let mut function_local_variable_types_iter =
+            function_local_variable_types_list.iter().peekable();
+        let mut local_variable_types: &[ValueType] = &[];
+
+        for (address, instruction) in instructions.iter().enumerate() {
+            // 进入下一个函数
+            while let Some((_, types)) = function_local_variable_types_iter
+                .next_if(|(start_address, _)| *start_address <= address)
+            {
+                local_variable_types = types;
+            }
+
+            let opcode = match instruction {
+                object::Instruction::Sequence(instruction) => bytecode.compile_sequence(
+                    instruction,
+                    memory_indexes,
+                    global_variable_indexes,
+                    local_variable_types,
+                ),
+                object::Instruction::Control(control) => bytecode.compile_control(control),
+            };
+            bytecode.opcodes.push(opcode);
+        }
+
+        bytecode.fused_opcodes = bytecode.opcodes.clone();
+        bytecode
+    }
+
+    /// 编译超级指令，并写入到 `fused_opcodes` 列表
+    ///
+    /// 参数 function_local_variable_types_list 跟 `compile` 方法的一样。
+    /// 读写 v128 类型局部变量的超级指令不会被编译，执行时仍然逐条执行原指令。
+    pub fn compile_fused_items(
+        &mut self,
+        fused_items: &[FusedItem],
+        function_local_variable_types_list: &[(usize, Vec<ValueType>)],
+    ) {
+        for fused_item in fused_items {
+            // 超级指令所在的函数，即开始地址不大于超级指令地址的最后一个函数
+            let function_count = function_local_variable_types_list
+                .partition_point(|(start_address, _)| *start_address <= fused_item.address);
+            let local_variable_types: &[ValueType] = match function_count {
+                0 => &[],
+                _ => &function_local_variable_types_list[function_count - 1].1,
+            };
+
+            let option_opcode = match &fused_item.fused_instruction {
+                FusedInstruction::LocalGetLocalGetI32Add {
+                    left_index,
+                    right_index,
+                } => (is_slot_local(local_variable_types, *left_index)
+                    && is_slot_local(local_variable_types, *right_index))
+                .then_some(Opcode::LocalGetLocalGetI32Add(*left_index, *right_index)),
+                FusedInstruction::LocalGetI32ConstI32AddLocalSet {
+                    source_index,
+                    value,
+                    dest_index,
+                } => (is_slot_local(local_variable_types, *source_index)
+                    && is_slot_local(local_variable_types, *dest_index))
+                .then_some(Opcode::LocalGetI32ConstI32AddLocalSet(
+                    *source_index,
+                    *value,
+                    *dest_index,
+                )),
+                FusedInstruction::I32ConstI32LtSBreakWhenNotEqZero {
+                    value,
+                    option_block_index,
+                    branch_target,
+                } => Some(Opcode::I32ConstI32LtSBreakWhenNotEqZero(
+                    *value,
+                    self.add_break_item(*option_block_index, branch_target),
+                )),
+            };
+
+            if let Some(opcode) = option_opcode {
+                self.fused_opcodes[fused_item.address] = opcode;
+            }
+        }
+    }
+
+    fn compile_sequence(
+        &mut self,
+        instruction: &Instruction,
+        memory_indexes: &[usize],
+        global_variable_indexes: &[usize],
+        local_variable_types: &[ValueType],
+    ) -> Opcode {
+        let mut memory =
+            |function: MemoryFunction, memory_args: &MemoryArgument| match memory_indexes
+                .get(memory_args.memory_block_index as usize)
+            {
+                Some(instance_memory_block_index) => {
+                    self.memory_accesses.push(MemoryAccess::new(
+                        *instance_memory_block_index,
+                        memory_args.offset,
+                    ));
+                    Opcode::Memory(function, (self.memory_accesses.len() - 1) as u32)
+                }
+                None => Opcode::Fallback,
+            };
+
+        match instruction {
+            // 常量指令
+            Instruction::I32Const(value) => Opcode::I32Const(*value),
+            Instruction::I64Const(value) => Opcode::I64Const(*value),
+            Instruction::F32Const(value) => Opcode::F32Const(*value),
+            Instruction::F64Const(value) => Opcode::F64Const(*value),
+
+            // 操作数（参数，parametric）指令
+            Instruction::Drop => Opcode::Sequence(ins_parametric::drop),
+            Instruction::Select => Opcode::Sequence(ins_parametric::select),
+
+            // 零值测试指令
+            Instruction::I32Eqz => Opcode::Sequence(ins_numeric_eqz::i32_eqz),
+            Instruction::I64Eqz => Opcode::Sequence(ins_numeric_eqz::i64_eqz),
+
+            // 数值比较指令
+            Instruction::I32Eq => Opcode::Sequence(ins_numeric_comparsion::i32_eq),
+            Instruction::I32Ne => Opcode::Sequence(ins_numeric_comparsion::i32_ne),
+            Instruction::I32LtS => Opcode::Sequence(ins_numeric_comparsion::i32_lt_s),
+            Instruction::I32LtU => Opcode::Sequence(ins_numeric_comparsion::i32_lt_u),
+            Instruction::I32GtS => Opcode::Sequence(ins_numeric_comparsion::i32_gt_s),
+            Instruction::I32GtU => Opcode::Sequence(ins_numeric_comparsion::i32_gt_u),
+            Instruction::I32LeS => Opcode::Sequence(ins_numeric_comparsion::i32_le_s),
+            Instruction::I32LeU => Opcode::Sequence(ins_numeric_comparsion::i32_le_u),
+            Instruction::I32GeS => Opcode::Sequence(ins_numeric_comparsion::i32_ge_s),
+            Instruction::I32GeU => Opcode::Sequence(ins_numeric_comparsion::i32_ge_u),
+
+            Instruction::I64Eq => Opcode::Sequence(ins_numeric_comparsion::i64_eq),
+            Instruction::I64Ne => Opcode::Sequence(ins_numeric_comparsion::i64_ne),
+            Instruction::I64LtS => Opcode::Sequence(ins_numeric_comparsion::i64_lt_s),
+            Instruction::I64LtU => Opcode::Sequence(ins_numeric_comparsion::i64_lt_u),
+            Instruction::I64GtS => Opcode::Sequence(ins_numeric_comparsion::i64_gt_s),
+            Instruction::I64GtU => Opcode::Sequence(ins_numeric_comparsion::i64_gt_u),
+            Instruction::I64LeS => Opcode::Sequence(ins_numeric_comparsion::i64_le_s),
+            Instruction::I64LeU => Opcode::Sequence(ins_numeric_comparsion::i64_le_u),
+            Instruction::I64GeS => Opcode::Sequence(ins_numeric_comparsion::i64_ge_s),
+            Instruction::I64GeU => Opcode::Sequence(ins_numeric_comparsion::i64_ge_u),
+
+            Instruction::F32Eq => Opcode::Sequence(ins_numeric_comparsion::f32_eq),
+            Instruction::F32Ne => Opcode::Sequence(ins_numeric_comparsion::f32_ne),
+            Instruction::F32Lt => Opcode::Sequence(ins_numeric_comparsion::f32_lt),
+            Instruction::F32Gt => Opcode::Sequence(ins_numeric_comparsion::f32_gt),
+            Instruction::F32Le => Opcode::Sequence(ins_numeric_comparsion::f32_le),
+            Instruction::F32Ge => Opcode::Sequence(ins_numeric_comparsion::f32_ge),
+
+            Instruction::F64Eq => Opcode::Sequence(ins_numeric_comparsion::f64_eq),
+            Instruction::F64Ne => Opcode::Sequence(ins_numeric_comparsion::f64_ne),
+            Instruction::F64Lt => Opcode::Sequence(ins_numeric_comparsion::f64_lt),
+            Instruction::F64Gt => Opcode::Sequence(ins_numeric_comparsion::f64_gt),
+            Instruction::F64Le => Opcode::Sequence(ins_numeric_comparsion::f64_le),
+            Instruction::F64Ge => Opcode::Sequence(ins_numeric_comparsion::f64_ge),
+
+            // 一元运算
+            Instruction::I32Clz => Opcode::Sequence(ins_numeric_unary::i32_clz),
+            Instruction::I32Ctz => Opcode::Sequence(ins_numeric_unary::i32_ctz),
+            Instruction::I32PopCnt => Opcode::Sequence(ins_numeric_unary::i32_popcnt),
+
+            Instruction::I64Clz => Opcode::Sequence(ins_numeric_unary::i64_clz),
+            Instruction::I64Ctz => Opcode::Sequence(ins_numeric_unary::i64_ctz),
+            Instruction::I64PopCnt => Opcode::Sequence(ins_numeric_unary::i64_popcnt),
+
+            Instruction::F32Abs => Opcode::Sequence(ins_numeric_unary::f32_abs),
+            Instruction::F32Neg => Opcode::Sequence(ins_numeric_unary::f32_neg),
+            Instruction::F32Ceil => Opcode::Sequence(ins_numeric_unary::f32_ceil),
+            Instruction::F32Floor => Opcode::Sequence(ins_numeric_unary::f32_floor),
+            Instruction::F32Trunc => Opcode::Sequence(ins_numeric_unary::f32_trunc),
+            Instruction::F32Nearest => Opcode::Sequence(ins_numeric_unary::f32_nearest),
+            Instruction::F32Sqrt => Opcode::Sequence(ins_numeric_unary::f32_sqrt),
+
+            Instruction::F64Abs => Opcode::Sequence(ins_numeric_unary::f64_abs),
+            Instruction::F64Neg => Opcode::Sequence(ins_numeric_unary::f64_neg),
+            Instruction::F64Ceil => Opcode::Sequence(ins_numeric_unary::f64_ceil),
+            Instruction::F64Floor => Opcode::Sequence(ins_numeric_unary::f64_floor),
+            Instruction::F64Trunc => Opcode::Sequence(ins_numeric_unary::f64_trunc),
+            Instruction::F64Nearest => Opcode::Sequence(ins_numeric_unary::f64_nearest),
+            Instruction::F64Sqrt => Opcode::Sequence(ins_numeric_unary::f64_sqrt),
+
+            // 二元运算
+            Instruction::I32Add => Opcode::Sequence(ins_numeric_binary::i32_add),
+            Instruction::I32Sub => Opcode::Sequence(ins_numeric_binary::i32_sub),
+            Instruction::I32Mul => Opcode::Sequence(ins_numeric_binary::i32_mul),
+            Instruction::I32DivS => Opcode::Sequence(ins_numeric_binary::i32_div_s),
+            Instruction::I32DivU => Opcode::Sequence(ins_numeric_binary::i32_div_u),
+            Instruction::I32RemS => Opcode::Sequence(ins_numeric_binary::i32_rem_s),
+            Instruction::I32RemU => Opcode::Sequence(ins_numeric_binary::i32_rem_u),
+            Instruction::I32And => Opcode::Sequence(ins_numeric_binary::i32_and),
+            Instruction::I32Or => Opcode::Sequence(ins_numeric_binary::i32_or),
+            Instruction::I32Xor => Opcode::Sequence(ins_numeric_binary::i32_xor),
+            Instruction::I32Shl => Opcode::Sequence(ins_numeric_binary::i32_shl),
+            Instruction::I32ShrS => Opcode::Sequence(ins_numeric_binary::i32_shr_s),
+            Instruction::I32ShrU => Opcode::Sequence(ins_numeric_binary::i32_shr_u),
+            Instruction::I32Rotl => Opcode::Sequence(ins_numeric_binary::i32_rotl),
+            Instruction::I32Rotr => Opcode::Sequence(ins_numeric_binary::i32_rotr),
+
+            Instruction::I64Add => Opcode::Sequence(ins_numeric_binary::i64_add),
+            Instruction::I64Sub => Opcode::Sequence(ins_numeric_binary::i64_sub),
+            Instruction::I64Mul => Opcode::Sequence(ins_numeric_binary::i64_mul),
+            Instruction::I64DivS => Opcode::Sequence(ins_numeric_binary::i64_div_s),
+            Instruction::I64DivU => Opcode::Sequence(ins_numeric_binary::i64_div_u),
+            Instruction::I64RemS => Opcode::Sequence(ins_numeric_binary::i64_rem_s),
+            Instruction::I64RemU => Opcode::Sequence(ins_numeric_binary::i64_rem_u),
+            Instruction::I64And => Opcode::Sequence(ins_numeric_binary::i64_and),
+            Instruction::I64Or => Opcode::Sequence(ins_numeric_binary::i64_or),
+            Instruction::I64Xor => Opcode::Sequence(ins_numeric_binary::i64_xor),
+            Instruction::I64Shl => Opcode::Sequence(ins_numeric_binary::i64_shl),
+            Instruction::I64ShrS => Opcode::Sequence(ins_numeric_binary::i64_shr_s),
+            Instruction::I64ShrU => Opcode::Sequence(ins_numeric_binary::i64_shr_u),
+            Instruction::I64Rotl => Opcode::Sequence(ins_numeric_binary::i64_rotl),
+            Instruction::I64Rotr => Opcode::Sequence(ins_numeric_binary::i64_rotr),
+
+            Instruction::F32Add => Opcode::Sequence(ins_numeric_binary::f32_add),
+            Instruction::F32Sub => Opcode::Sequence(ins_numeric_binary::f32_sub),
+            Instruction::F32Mul => Opcode::Sequence(ins_numeric_binary::f32_mul),
+            Instruction::F32Div => Opcode::Sequence(ins_numeric_binary::f32_div),
+            Instruction::F32Min => Opcode::Sequence(ins_numeric_binary::f32_min),
+            Instruction::F32Max => Opcode::Sequence(ins_numeric_binary::f32_max),
+            Instruction::F32CopySign => Opcode::Sequence(ins_numeric_binary::f32_copysign),
+
+            Instruction::F64Add => Opcode::Sequence(ins_numeric_binary::f64_add),
+            Instruction::F64Sub => Opcode::Sequence(ins_numeric_binary::f64_sub),
+            Instruction::F64Mul => Opcode::Sequence(ins_numeric_binary::f64_mul),
+            Instruction::F64Div => Opcode::Sequence(ins_numeric_binary::f64_div),
+            Instruction::F64Min => Opcode::Sequence(ins_numeric_binary::f64_min),
+            Instruction::F64Max => Opcode::Sequence(ins_numeric_binary::f64_max),
+            Instruction::F64CopySign => Opcode::Sequence(ins_numeric_binary::f64_copysign),
+
+            // 类型转换指令
+            Instruction::I32WrapI64 => Opcode::Sequence(ins_numeric_convert::i32_wrap_i64),
+
+            Instruction::I32Extend8S => Opcode::Sequence(ins_numeric_convert::i32_extend8_s),
+            Instruction::I32Extend16S => Opcode::Sequence(ins_numeric_convert::i32_extend16_s),
+            Instruction::I64ExtendI32S => Opcode::Sequence(ins_numeric_convert::i64_extend_i32_s),
+            Instruction::I64ExtendI32U => Opcode::Sequence(ins_numeric_convert::i64_extend_i32_u),
+            Instruction::I64Extend8S => Opcode::Sequence(ins_numeric_convert::i64_extend8_s),
+            Instruction::I64Extend16S => Opcode::Sequence(ins_numeric_convert::i64_extend16_s),
+            Instruction::I64Extend32S => Opcode::Sequence(ins_numeric_convert::i64_extend32_s),
+
+            Instruction::I32TruncF32S => Opcode::Sequence(ins_numeric_convert::i32_trunc_f32_s),
+            Instruction::I32TruncF32U => Opcode::Sequence(ins_numeric_convert::i32_trunc_f32_u),
+            Instruction::I64TruncF32S => Opcode::Sequence(ins_numeric_convert::i64_trunc_f32_s),
+            Instruction::I64TruncF32U => Opcode::Sequence(ins_numeric_convert::i64_trunc_f32_u),
+            Instruction::I32TruncF64S => Opcode::Sequence(ins_numeric_convert::i32_trunc_f64_s),
+            Instruction::I32TruncF64U => Opcode::Sequence(ins_numeric_convert::i32_trunc_f64_u),
+            Instruction::I64TruncF64S => Opcode::Sequence(ins_numeric_convert::i64_trunc_f64_s),
+            Instruction::I64TruncF64U => Opcode::Sequence(ins_numeric_convert::i64_trunc_f64_u),
+
+            Instruction::I32TruncSatF32S => {
+                Opcode::Sequence(ins_numeric_convert::i32_trunc_sat_f32_s)
+            }
+            Instruction::I32TruncSatF32U => {
+                Opcode::Sequence(ins_numeric_convert::i32_trunc_sat_f32_u)
+            }
+            Instruction::I32TruncSatF64S => {
+                Opcode::Sequence(ins_numeric_convert::i32_trunc_sat_f64_s)
+            }
+            Instruction::I32TruncSatF64U => {
+                Opcode::Sequence(ins_numeric_convert::i32_trunc_sat_f64_u)
+            }
+            Instruction::I64TruncSatF32S => {
+                Opcode::Sequence(ins_numeric_convert::i64_trunc_sat_f32_s)
+            }
+            Instruction::I64TruncSatF32U => {
+                Opcode::Sequence(ins_numeric_convert::i64_trunc_sat_f32_u)
+            }
+            Instruction::I64TruncSatF64S => {
+                Opcode::Sequence(ins_numeric_convert::i64_trunc_sat_f64_s)
+            }
+            Instruction::I64TruncSatF64U => {
+                Opcode::Sequence(ins_numeric_convert::i64_trunc_sat_f64_u)
+            }
+
+            Instruction::F32ConvertI32S => Opcode::Sequence(ins_numeric_convert::f32_convert_i32_s),
+            Instruction::F32ConvertI32U => Opcode::Sequence(ins_numeric_convert::f32_convert_i32_u),
+            Instruction::F64ConvertI32S => Opcode::Sequence(ins_numeric_convert::f64_convert_i32_s),
+            Instruction::F64ConvertI32U => Opcode::Sequence(ins_numeric_convert::f64_convert_i32_u),
+            Instruction::F32ConvertI64S => Opcode::Sequence(ins_numeric_convert::f32_convert_i64_s),
+            Instruction::F32ConvertI64U => Opcode::Sequence(ins_numeric_convert::f32_convert_i64_u),
+            Instruction::F64ConvertI64S => Opcode::Sequence(ins_numeric_convert::f64_convert_i64_s),
+            Instruction::F64ConvertI64U => Opcode::Sequence(ins_numeric_convert::f64_convert_i64_u),
+
+            Instruction::F32DemoteF64 => Opcode::Sequence(ins_numeric_convert::f32_demote_f64_s),
+            Instruction::F64PromoteF32 => Opcode::Sequence(ins_numeric_convert::f64_promote_f32),
+
+            Instruction::I32ReinterpretF32 => {
+                Opcode::Sequence(ins_numeric_convert::i32_reinterpret_f32)
+            }
+            Instruction::I64ReinterpretF64 => {
+                Opcode::Sequence(ins_numeric_convert::i64_reinterpret_f64)
+            }
+            Instruction::F32ReinterpretI32 => {
+                Opcode::Sequence(ins_numeric_convert::f32_reinterpret_i32)
+            }
+            Instruction::F64ReinterpretI64 => {
+                Opcode::Sequence(ins_numeric_convert::f64_reinterpret_i64)
+            }
+
+            // 变量指令
+            Instruction::LocalGet(index) if is_slot_local(local_variable_types, *index) => {
+                Opcode::LocalGet(*index)
+            }
+            Instruction::LocalSet(index) if is_slot_local(local_variable_types, *index) => {
+                Opcode::LocalSet(*index)
+            }
+            Instruction::LocalTee(index) if is_slot_local(local_variable_types, *index) => {
+                Opcode::LocalTee(*index)
+            }
+            Instruction::GlobalGet(index) => match global_variable_indexes.get(*index as usize) {
+                Some(instance_global_variable_index) => {
+                    Opcode::GlobalGet(*instance_global_variable_index as u32)
+                }
+                None => Opcode::Fallback,
+            },
+
+            // 内存指令
+            Instruction::I32Load(memory_args) => memory(ins_memory::i32_load, memory_args),
+            Instruction::I32Load16S(memory_args) => memory(ins_memory::i32_load16_s, memory_args),
+            Instruction::I32Load16U(memory_args) => memory(ins_memory::i32_load16_u, memory_args),
+            Instruction::I32Load8S(memory_args) => memory(ins_memory::i32_load8_s, memory_args),
+            Instruction::I32Load8U(memory_args) => memory(ins_memory::i32_load8_u, memory_args),
+
+            Instruction::I64Load(memory_args) => memory(ins_memory::i64_load, memory_args),
+            Instruction::I64Load32S(memory_args) => memory(ins_memory::i64_load32_s, memory_args),
+            Instruction::I64Load32U(memory_args) => memory(ins_memory::i64_load32_u, memory_args),
+            Instruction::I64Load16S(memory_args) => memory(ins_memory::i64_load16_s, memory_args),
+            Instruction::I64Load16U(memory_args) => memory(ins_memory::i64_load16_u, memory_args),
+            Instruction::I64Load8S(memory_args) => memory(ins_memory::i64_load8_s, memory_args),
+            Instruction::I64Load8U(memory_args) => memory(ins_memory::i64_load8_u, memory_args),
+
+            Instruction::F32Load(memory_args) => memory(ins_memory::f32_load, memory_args),
+            Instruction::F64Load(memory_args) => memory(ins_memory::f64_load, memory_args),
+
+            Instruction::I32Store(memory_args) => memory(ins_memory::i32_store, memory_args),
+            Instruction::I32Store16(memory_args) => memory(ins_memory::i32_store_16, memory_args),
+            Instruction::I32Store8(memory_args) => memory(ins_memory::i32_store_8, memory_args),
+            Instruction::I64Store(memory_args) => memory(ins_memory::i64_store, memory_args),
+            Instruction::I64Store32(memory_args) => memory(ins_memory::i64_store_32, memory_args),
+            Instruction::I64Store16(memory_args) => memory(ins_memory::i64_store_16, memory_args),
+            Instruction::I64Store8(memory_args) => memory(ins_memory::i64_store_8, memory_args),
+
+            Instruction::F32Store(memory_args) => memory(ins_memory::f32_store, memory_args),
+            Instruction::F64Store(memory_args) => memory(ins_memory::f64_store, memory_args),
+
+            // 引用指令
+            Instruction::RefIsNull => Opcode::Sequence(ins_reference::ref_is_null),
+
+            // 固定宽度 SIMD 指令
+            Instruction::I8x16Swizzle => Opcode::Sequence(ins_simd::i8x16_swizzle),
+            Instruction::I8x16Splat => Opcode::Sequence(ins_simd::i8x16_splat),
+            Instruction::I16x8Splat => Opcode::Sequence(ins_simd::i16x8_splat),
+            Instruction::I32x4Splat => Opcode::Sequence(ins_simd::i32x4_splat),
+            Instruction::I64x2Splat => Opcode::Sequence(ins_simd::i64x2_splat),
+            Instruction::F32x4Splat => Opcode::Sequence(ins_simd::f32x4_splat),
+            Instruction::F64x2Splat => Opcode::Sequence(ins_simd::f64x2_splat),
+            Instruction::I8x16Eq => Opcode::Sequence(ins_simd::i8x16_eq),
+            Instruction::I8x16Ne => Opcode::Sequence(ins_simd::i8x16_ne),
+            Instruction::I8x16LtS => Opcode::Sequence(ins_simd::i8x16_lt_s),
+            Instruction::I8x16LtU => Opcode::Sequence(ins_simd::i8x16_lt_u),
+            Instruction::I8x16GtS => Opcode::Sequence(ins_simd::i8x16_gt_s),
+            Instruction::I8x16GtU => Opcode::Sequence(ins_simd::i8x16_gt_u),
+            Instruction::I8x16LeS => Opcode::Sequence(ins_simd::i8x16_le_s),
+            Instruction::I8x16LeU => Opcode::Sequence(ins_simd::i8x16_le_u),
+            Instruction::I8x16GeS => Opcode::Sequence(ins_simd::i8x16_ge_s),
+            Instruction::I8x16GeU => Opcode::Sequence(ins_simd::i8x16_ge_u),
+            Instruction::I16x8Eq => Opcode::Sequence(ins_simd::i16x8_eq),
+            Instruction::I16x8Ne => Opcode::Sequence(ins_simd::i16x8_ne),
+            Instruction::I16x8LtS => Opcode::Sequence(ins_simd::i16x8_lt_s),
+            Instruction::I16x8LtU => Opcode::Sequence(ins_simd::i16x8_lt_u),
+            Instruction::I16x8GtS => Opcode::Sequence(ins_simd::i16x8_gt_s),
+            Instruction::I16x8GtU => Opcode::Sequence(ins_simd::i16x8_gt_u),
+            Instruction::I16x8LeS => Opcode::Sequence(ins_simd::i16x8_le_s),
+            Instruction::I16x8LeU => Opcode::Sequence(ins_simd::i16x8_le_u),
+            Instruction::I16x8GeS => Opcode::Sequence(ins_simd::i16x8_ge_s),
+            Instruction::I16x8GeU => Opcode::Sequence(ins_simd::i16x8_ge_u),
+            Instruction::I32x4Eq => Opcode::Sequence(ins_simd::i32x4_eq),
+            Instruction::I32x4Ne => Opcode::Sequence(ins_simd::i32x4_ne),
+            Instruction::I32x4LtS => Opcode::Sequence(ins_simd::i32x4_lt_s),
+            Instruction::I32x4LtU => Opcode::Sequence(ins_simd::i32x4_lt_u),
+            Instruction::I32x4GtS => Opcode::Sequence(ins_simd::i32x4_gt_s),
+            Instruction::I32x4GtU => Opcode::Sequence(ins_simd::i32x4_gt_u),
+            Instruction::I32x4LeS => Opcode::Sequence(ins_simd::i32x4_le_s),
+            Instruction::I32x4LeU => Opcode::Sequence(ins_simd::i32x4_le_u),
+            Instruction::I32x4GeS => Opcode::Sequence(ins_simd::i32x4_ge_s),
+            Instruction::I32x4GeU => Opcode::Sequence(ins_simd::i32x4_ge_u),
+            Instruction::F32x4Eq => Opcode::Sequence(ins_simd::f32x4_eq),
+            Instruction::F32x4Ne => Opcode::Sequence(ins_simd::f32x4_ne),
+            Instruction::F32x4Lt => Opcode::Sequence(ins_simd::f32x4_lt),
+            Instruction::F32x4Gt => Opcode::Sequence(ins_simd::f32x4_gt),
+            Instruction::F32x4Le => Opcode::Sequence(ins_simd::f32x4_le),
+            Instruction::F32x4Ge => Opcode::Sequence(ins_simd::f32x4_ge),
+            Instruction::F64x2Eq => Opcode::Sequence(ins_simd::f64x2_eq),
+            Instruction::F64x2Ne => Opcode::Sequence(ins_simd::f64x2_ne),
+            Instruction::F64x2Lt => Opcode::Sequence(ins_simd::f64x2_lt),
+            Instruction::F64x2Gt => Opcode::Sequence(ins_simd::f64x2_gt),
+            Instruction::F64x2Le => Opcode::Sequence(ins_simd::f64x2_le),
+            Instruction::F64x2Ge => Opcode::Sequence(ins_simd::f64x2_ge),
+            Instruction::V128Not => Opcode::Sequence(ins_simd::v128_not),
+            Instruction::V128And => Opcode::Sequence(ins_simd::v128_and),
+            Instruction::V128Andnot => Opcode::Sequence(ins_simd::v128_andnot),
+            Instruction::V128Or => Opcode::Sequence(ins_simd::v128_or),
+            Instruction::V128Xor => Opcode::Sequence(ins_simd::v128_xor),
+            Instruction::V128Bitselect => Opcode::Sequence(ins_simd::v128_bitselect),
+            Instruction::V128AnyTrue => Opcode::Sequence(ins_simd::v128_any_true),
+            Instruction::F32x4DemoteF64x2Zero => {
+                Opcode::Sequence(ins_simd::f32x4_demote_f64x2_zero)
+            }
+            Instruction::F64x2PromoteLowF32x4 => {
+                Opcode::Sequence(ins_simd::f64x2_promote_low_f32x4)
+            }
+            Instruction::I8x16Abs => Opcode::Sequence(ins_simd::i8x16_abs),
+            Instruction::I8x16Neg => Opcode::Sequence(ins_simd::i8x16_neg),
+            Instruction::I8x16Popcnt => Opcode::Sequence(ins_simd::i8x16_popcnt),
+            Instruction::I8x16AllTrue => Opcode::Sequence(ins_simd::i8x16_all_true),
+            Instruction::I8x16Bitmask => Opcode::Sequence(ins_simd::i8x16_bitmask),
+            Instruction::I8x16NarrowI16x8S => Opcode::Sequence(ins_simd::i8x16_narrow_i16x8_s),
+            Instruction::I8x16NarrowI16x8U => Opcode::Sequence(ins_simd::i8x16_narrow_i16x8_u),
+            Instruction::F32x4Ceil => Opcode::Sequence(ins_simd::f32x4_ceil),
+            Instruction::F32x4Floor => Opcode::Sequence(ins_simd::f32x4_floor),
+            Instruction::F32x4Trunc => Opcode::Sequence(ins_simd::f32x4_trunc),
+            Instruction::F32x4Nearest => Opcode::Sequence(ins_simd::f32x4_nearest),
+            Instruction::I8x16Shl => Opcode::Sequence(ins_simd::i8x16_shl),
+            Instruction::I8x16ShrS => Opcode::Sequence(ins_simd::i8x16_shr_s),
+            Instruction::I8x16ShrU => Opcode::Sequence(ins_simd::i8x16_shr_u),
+            Instruction::I8x16Add => Opcode::Sequence(ins_simd::i8x16_add),
+            Instruction::I8x16AddSatS => Opcode::Sequence(ins_simd::i8x16_add_sat_s),
+            Instruction::I8x16AddSatU => Opcode::Sequence(ins_simd::i8x16_add_sat_u),
+            Instruction::I8x16Sub => Opcode::Sequence(ins_simd::i8x16_sub),
+            Instruction::I8x16SubSatS => Opcode::Sequence(ins_simd::i8x16_sub_sat_s),
+            Instruction::I8x16SubSatU => Opcode::Sequence(ins_simd::i8x16_sub_sat_u),
+            Instruction::F64x2Ceil => Opcode::Sequence(ins_simd::f64x2_ceil),
+            Instruction::F64x2Floor => Opcode::Sequence(ins_simd::f64x2_floor),
+            Instruction::I8x16MinS => Opcode::Sequence(ins_simd::i8x16_min_s),
+            Instruction::I8x16MinU => Opcode::Sequence(ins_simd::i8x16_min_u),
+            Instruction::I8x16MaxS => Opcode::Sequence(ins_simd::i8x16_max_s),
+            Instruction::I8x16MaxU => Opcode::Sequence(ins_simd::i8x16_max_u),
+            Instruction::F64x2Trunc => Opcode::Sequence(ins_simd::f64x2_trunc),
+            Instruction::I8x16AvgrU => Opcode::Sequence(ins_simd::i8x16_avgr_u),
+            Instruction::I16x8ExtaddPairwiseI8x16S => {
+                Opcode::Sequence(ins_simd::i16x8_extadd_pairwise_i8x16_s)
+            }
+            Instruction::I16x8ExtaddPairwiseI8x16U => {
+                Opcode::Sequence(ins_simd::i16x8_extadd_pairwise_i8x16_u)
+            }
+            Instruction::I32x4ExtaddPairwiseI16x8S => {
+                Opcode::Sequence(ins_simd::i32x4_extadd_pairwise_i16x8_s)
+            }
+            Instruction::I32x4ExtaddPairwiseI16x8U => {
+                Opcode::Sequence(ins_simd::i32x4_extadd_pairwise_i16x8_u)
+            }
+            Instruction::I16x8Abs => Opcode::Sequence(ins_simd::i16x8_abs),
+            Instruction::I16x8Neg => Opcode::Sequence(ins_simd::i16x8_neg),
+            Instruction::I16x8Q15mulrSatS => Opcode::Sequence(ins_simd::i16x8_q15mulr_sat_s),
+            Instruction::I16x8AllTrue => Opcode::Sequence(ins_simd::i16x8_all_true),
+            Instruction::I16x8Bitmask => Opcode::Sequence(ins_simd::i16x8_bitmask),
+            Instruction::I16x8NarrowI32x4S => Opcode::Sequence(ins_simd::i16x8_narrow_i32x4_s),
+            Instruction::I16x8NarrowI32x4U => Opcode::Sequence(ins_simd::i16x8_narrow_i32x4_u),
+            Instruction::I16x8ExtendLowI8x16S => {
+                Opcode::Sequence(ins_simd::i16x8_extend_low_i8x16_s)
+            }
+            Instruction::I16x8ExtendHighI8x16S => {
+                Opcode::Sequence(ins_simd::i16x8_extend_high_i8x16_s)
+            }
+            Instruction::I16x8ExtendLowI8x16U => {
+                Opcode::Sequence(ins_simd::i16x8_extend_low_i8x16_u)
+            }
+            Instruction::I16x8ExtendHighI8x16U => {
+                Opcode::Sequence(ins_simd::i16x8_extend_high_i8x16_u)
+            }
+            Instruction::I16x8Shl => Opcode::Sequence(ins_simd::i16x8_shl),
+            Instruction::I16x8ShrS => Opcode::Sequence(ins_simd::i16x8_shr_s),
+            Instruction::I16x8ShrU => Opcode::Sequence(ins_simd::i16x8_shr_u),
+            Instruction::I16x8Add => Opcode::Sequence(ins_simd::i16x8_add),
+            Instruction::I16x8AddSatS => Opcode::Sequence(ins_simd::i16x8_add_sat_s),
+            Instruction::I16x8AddSatU => Opcode::Sequence(ins_simd::i16x8_add_sat_u),
+            Instruction::I16x8Sub => Opcode::Sequence(ins_simd::i16x8_sub),
+            Instruction::I16x8SubSatS => Opcode::Sequence(ins_simd::i16x8_sub_sat_s),
+            Instruction::I16x8SubSatU => Opcode::Sequence(ins_simd::i16x8_sub_sat_u),
+            Instruction::F64x2Nearest => Opcode::Sequence(ins_simd::f64x2_nearest),
+            Instruction::I16x8Mul => Opcode::Sequence(ins_simd::i16x8_mul),
+            Instruction::I16x8MinS => Opcode::Sequence(ins_simd::i16x8_min_s),
+            Instruction::I16x8MinU => Opcode::Sequence(ins_simd::i16x8_min_u),
+            Instruction::I16x8MaxS => Opcode::Sequence(ins_simd::i16x8_max_s),
+            Instruction::I16x8MaxU => Opcode::Sequence(ins_simd::i16x8_max_u),
+            Instruction::I16x8AvgrU => Opcode::Sequence(ins_simd::i16x8_avgr_u),
+            Instruction::I16x8ExtmulLowI8x16S => {
+                Opcode::Sequence(ins_simd::i16x8_extmul_low_i8x16_s)
+            }
+            Instruction::I16x8ExtmulHighI8x16S => {
+                Opcode::Sequence(ins_simd::i16x8_extmul_high_i8x16_s)
+            }
+            Instruction::I16x8ExtmulLowI8x16U => {
+                Opcode::Sequence(ins_simd::i16x8_extmul_low_i8x16_u)
+            }
+            Instruction::I16x8ExtmulHighI8x16U => {
+                Opcode::Sequence(ins_simd::i16x8_extmul_high_i8x16_u)
+            }
+            Instruction::I32x4Abs => Opcode::Sequence(ins_simd::i32x4_abs),
+            Instruction::I32x4Neg => Opcode::Sequence(ins_simd::i32x4_neg),
+            Instruction::I32x4AllTrue => Opcode::Sequence(ins_simd::i32x4_all_true),
+            Instruction::I32x4Bitmask => Opcode::Sequence(ins_simd::i32x4_bitmask),
+            Instruction::I32x4ExtendLowI16x8S => {
+                Opcode::Sequence(ins_simd::i32x4_extend_low_i16x8_s)
+            }
+            Instruction::I32x4ExtendHighI16x8S => {
+                Opcode::Sequence(ins_simd::i32x4_extend_high_i16x8_s)
+            }
+            Instruction::I32x4ExtendLowI16x8U => {
+                Opcode::Sequence(ins_simd::i32x4_extend_low_i16x8_u)
+            }
+            Instruction::I32x4ExtendHighI16x8U => {
+                Opcode::Sequence(ins_simd::i32x4_extend_high_i16x8_u)
+            }
+            Instruction::I32x4Shl => Opcode::Sequence(ins_simd::i32x4_shl),
+            Instruction::I32x4ShrS => Opcode::Sequence(ins_simd::i32x4_shr_s),
+            Instruction::I32x4ShrU => Opcode::Sequence(ins_simd::i32x4_shr_u),
+            Instruction::I32x4Add => Opcode::Sequence(ins_simd::i32x4_add),
+            Instruction::I32x4Sub => Opcode::Sequence(ins_simd::i32x4_sub),
+            Instruction::I32x4Mul => Opcode::Sequence(ins_simd::i32x4_mul),
+            Instruction::I32x4MinS => Opcode::Sequence(ins_simd::i32x4_min_s),
+            Instruction::I32x4MinU => Opcode::Sequence(ins_simd::i32x4_min_u),
+            Instruction::I32x4MaxS => Opcode::Sequence(ins_simd::i32x4_max_s),
+            Instruction::I32x4MaxU => Opcode::Sequence(ins_simd::i32x4_max_u),
+            Instruction::I32x4DotI16x8S => Opcode::Sequence(ins_simd::i32x4_dot_i16x8_s),
+            Instruction::I32x4ExtmulLowI16x8S => {
+                Opcode::Sequence(ins_simd::i32x4_extmul_low_i16x8_s)
+            }
+            Instruction::I32x4ExtmulHighI16x8S => {
+                Opcode::Sequence(ins_simd::i32x4_extmul_high_i16x8_s)
+            }
+            Instruction::I32x4ExtmulLowI16x8U => {
+                Opcode::Sequence(ins_simd::i32x4_extmul_low_i16x8_u)
+            }
+            Instruction::I32x4ExtmulHighI16x8U => {
+                Opcode::Sequence(ins_simd::i32x4_extmul_high_i16x8_u)
+            }
+            Instruction::I64x2Abs => Opcode::Sequence(ins_simd::i64x2_abs),
+            Instruction::I64x2Neg => Opcode::Sequence(ins_simd::i64x2_neg),
+            Instruction::I64x2AllTrue => Opcode::Sequence(ins_simd::i64x2_all_true),
+            Instruction::I64x2Bitmask => Opcode::Sequence(ins_simd::i64x2_bitmask),
+            Instruction::I64x2ExtendLowI32x4S => {
+                Opcode::Sequence(ins_simd::i64x2_extend_low_i32x4_s)
+            }
+            Instruction::I64x2ExtendHighI32x4S => {
+                Opcode::Sequence(ins_simd::i64x2_extend_high_i32x4_s)
+            }
+            Instruction::I64x2ExtendLowI32x4U => {
+                Opcode::Sequence(ins_simd::i64x2_extend_low_i32x4_u)
+            }
+            Instruction::I64x2ExtendHighI32x4U => {
+                Opcode::Sequence(ins_simd::i64x2_extend_high_i32x4_u)
+            }
+            Instruction::I64x2Shl => Opcode::Sequence(ins_simd::i64x2_shl),
+            Instruction::I64x2ShrS => Opcode::Sequence(ins_simd::i64x2_shr_s),
+            Instruction::I64x2ShrU => Opcode::Sequence(ins_simd::i64x2_shr_u),
+            Instruction::I64x2Add => Opcode::Sequence(ins_simd::i64x2_add),
+            Instruction::I64x2Sub => Opcode::Sequence(ins_simd::i64x2_sub),
+            Instruction::I64x2Mul => Opcode::Sequence(ins_simd::i64x2_mul),
+            Instruction::I64x2Eq => Opcode::Sequence(ins_simd::i64x2_eq),
+            Instruction::I64x2Ne => Opcode::Sequence(ins_simd::i64x2_ne),
+            Instruction::I64x2LtS => Opcode::Sequence(ins_simd::i64x2_lt_s),
+            Instruction::I64x2GtS => Opcode::Sequence(ins_simd::i64x2_gt_s),
+            Instruction::I64x2LeS => Opcode::Sequence(ins_simd::i64x2_le_s),
+            Instruction::I64x2GeS => Opcode::Sequence(ins_simd::i64x2_ge_s),
+            Instruction::I64x2ExtmulLowI32x4S => {
+                Opcode::Sequence(ins_simd::i64x2_extmul_low_i32x4_s)
+            }
+            Instruction::I64x2ExtmulHighI32x4S => {
+                Opcode::Sequence(ins_simd::i64x2_extmul_high_i32x4_s)
+            }
+            Instruction::I64x2ExtmulLowI32x4U => {
+                Opcode::Sequence(ins_simd::i64x2_extmul_low_i32x4_u)
+            }
+            Instruction::I64x2ExtmulHighI32x4U => {
+                Opcode::Sequence(ins_simd::i64x2_extmul_high_i32x4_u)
+            }
+            Instruction::F32x4Abs => Opcode::Sequence(ins_simd::f32x4_abs),
+            Instruction::F32x4Neg => Opcode::Sequence(ins_simd::f32x4_neg),
+            Instruction::F32x4Sqrt => Opcode::Sequence(ins_simd::f32x4_sqrt),
+            Instruction::F32x4Add => Opcode::Sequence(ins_simd::f32x4_add),
+            Instruction::F32x4Sub => Opcode::Sequence(ins_simd::f32x4_sub),
+            Instruction::F32x4Mul => Opcode::Sequence(ins_simd::f32x4_mul),
+            Instruction::F32x4Div => Opcode::Sequence(ins_simd::f32x4_div),
+            Instruction::F32x4Min => Opcode::Sequence(ins_simd::f32x4_min),
+            Instruction::F32x4Max => Opcode::Sequence(ins_simd::f32x4_max),
+            Instruction::F32x4Pmin => Opcode::Sequence(ins_simd::f32x4_pmin),
+            Instruction::F32x4Pmax => Opcode::Sequence(ins_simd::f32x4_pmax),
+            Instruction::F64x2Abs => Opcode::Sequence(ins_simd::f64x2_abs),
+            Instruction::F64x2Neg => Opcode::Sequence(ins_simd::f64x2_neg),
+            Instruction::F64x2Sqrt => Opcode::Sequence(ins_simd::f64x2_sqrt),
+            Instruction::F64x2Add => Opcode::Sequence(ins_simd::f64x2_add),
+            Instruction::F64x2Sub => Opcode::Sequence(ins_simd::f64x2_sub),
+            Instruction::F64x2Mul => Opcode::Sequence(ins_simd::f64x2_mul),
+            Instruction::F64x2Div => Opcode::Sequence(ins_simd::f64x2_div),
+            Instruction::F64x2Min => Opcode::Sequence(ins_simd::f64x2_min),
+            Instruction::F64x2Max => Opcode::Sequence(ins_simd::f64x2_max),
+            Instruction::F64x2Pmin => Opcode::Sequence(ins_simd::f64x2_pmin),
+            Instruction::F64x2Pmax => Opcode::Sequence(ins_simd::f64x2_pmax),
+            Instruction::I32x4TruncSatF32x4S => Opcode::Sequence(ins_simd::i32x4_trunc_sat_f32x4_s),
+            Instruction::I32x4TruncSatF32x4U => Opcode::Sequence(ins_simd::i32x4_trunc_sat_f32x4_u),
+            Instruction::F32x4ConvertI32x4S => Opcode::Sequence(ins_simd::f32x4_convert_i32x4_s),
+            Instruction::F32x4ConvertI32x4U => Opcode::Sequence(ins_simd::f32x4_convert_i32x4_u),
+            Instruction::I32x4TruncSatF64x2SZero => {
+                Opcode::Sequence(ins_simd::i32x4_trunc_sat_f64x2_s_zero)
+            }
+            Instruction::I32x4TruncSatF64x2UZero => {
+                Opcode::Sequence(ins_simd::i32x4_trunc_sat_f64x2_u_zero)
+            }
+            Instruction::F64x2ConvertLowI32x4S => {
+                Opcode::Sequence(ins_simd::f64x2_convert_low_i32x4_s)
+            }
+            Instruction::F64x2ConvertLowI32x4U => {
+                Opcode::Sequence(ins_simd::f64x2_convert_low_i32x4_u)
+            }
+
+            // 原子内存访问指令
+            Instruction::AtomicFence => Opcode::Sequence(ins_atomic::atomic_fence),
+
+            // 其余指令回退到执行原指令
+            _ => Opcode::Fallback,
+        }
+    }
+
+    fn compile_control(&mut self, control: &Control) -> Opcode {
+        match control {
+            Control::Block { .. } | Control::End(Some(_)) | Control::Nop => Opcode::Nop,
+            Control::BlockAndJumpWhenEqZero {
+                option_alternate_address,
+                end_address,
+                ..
+            } => {
+                // 跳到 else 结构块的第一条指令，或者跳出 if 结构块
+                let address = match option_alternate_address {
+                    Some(alternate_address) => alternate_address + 1,
+                    None => end_address + 1,
+                };
+                Opcode::JumpWhenEqZero(address as u32)
+            }
+            Control::JumpWithinBlock(address) => Opcode::Jump(*address as u32),
+            Control::Break {
+                option_block_index,
+                branch_target,
+            } => Opcode::Break(self.add_break_item(*option_block_index, branch_target)),
+            Control::BreakWhenNotEqZero {
+                option_block_index,
+                branch_target,
+            } => {
+                Opcode::BreakWhenNotEqZero(self.add_break_item(*option_block_index, branch_target))
+            }
+            Control::Call {
+                vm_module_index,
+                type_index,
+                function_index,
+                internal_function_index,
+                address,
+            } => {
+                self.call_items.push(CallItem {
+                    vm_module_index: *vm_module_index,
+                    type_index: *type_index,
+                    function_index: *function_index,
+                    internal_function_index: *internal_function_index,
+                    address: *address,
+                });
+                Opcode::Call((self.call_items.len() - 1) as u32)
+            }
+            _ => Opcode::Fallback,
+        }
+    }
+
+    fn add_break_item(
+        &mut self,
+        option_block_index: Option<usize>,
+        branch_target: &BranchTarget,
+    ) -> u32 {
+        self.break_items.push(BreakItem {
+            option_block_index,
+            branch_target: branch_target.clone(),
+        });
+        (self.break_items.len() - 1) as u32
+    }
+}
+
+/// 局部变量是否可以直接复制栈槽来读写，即数据类型已知并且不是 v128
+fn is_slot_local(local_variable_types: &[ValueType], index: u32) -> bool {
+    matches!(
+        local_variable_types.get(index as usize),
+        Some(value_type) if *value_type != ValueType::V128
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::size_of;
+
+    use anvm_ast::{
+        instruction::{BlockType, Instruction, MemoryArgument},
+        types::ValueType,
+    };
+
+    use super::{Bytecode, Opcode};
+    use crate::{
+        ins_memory::MemoryAccess,
+        object::{self, BranchTarget, Control, FusedInstruction, FusedItem},
+    };
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_opcode_size() {
+        assert_eq!(size_of::<Opcode>(), 16);
+    }
+
+    #[test]
+    fn test_compile() {
+        let branch_target = BranchTarget {
+            relative_depth: 0,
+            stack_height: 0,
+            arity: 0,
+            address: 2,
+        };
+
+        let instructions = vec![
+            object::Instruction::Control(Control::Block {
+                block_type: BlockType::ResultEmpty,
+                block_index: 0,
+                end_address: 9,
+            }), // #00
+            object::Instruction::Sequence(Instruction::LocalGet(1)), // #01
+            object::Instruction::Sequence(Instruction::I32Const(7)), // #02
+            object::Instruction::Sequence(Instruction::I32Add),      // #03
+            object::Instruction::Sequence(Instruction::I32Load(MemoryArgument {
+                align: 2,
+                offset: 16,
+                memory_block_index: 1,
+            })), // #04
+            object::Instruction::Sequence(Instruction::GlobalGet(1)), // #05
+            object::Instruction::Control(Control::BreakWhenNotEqZero {
+                option_block_index: Some(0),
+                branch_target: branch_target.clone(),
+            }), // #06
+            object::Instruction::Sequence(Instruction::TableSize(0)), // #07
+            object::Instruction::Control(Control::JumpWithinBlock(9)), // #08
+            object::Instruction::Control(Control::End(Some(0))),     // #09
+            object::Instruction::Control(Control::End(None)),        // #10
+        ];
+
+        let bytecode = Bytecode::compile(
+            &instructions,
+            &[3, 5],
+            &[7, 11],
+            &[(0, vec![ValueType::I32, ValueType::I32])],
+        );
+        let opcodes = &bytecode.opcodes;
+
+        assert_eq!(opcodes.len(), instructions.len());
+        assert!(matches!(opcodes[0], Opcode::Nop));
+        assert!(matches!(opcodes[1], Opcode::LocalGet(1)));
+        assert!(matches!(opcodes[2], Opcode::I32Const(7)));
+        assert!(matches!(opcodes[3], Opcode::Sequence(_)));
+        assert!(matches!(opcodes[4], Opcode::Memory(_, 0)));
+        assert!(matches!(opcodes[5], Opcode::GlobalGet(11)));
+        assert!(matches!(opcodes[6], Opcode::BreakWhenNotEqZero(0)));
+        assert!(matches!(opcodes[7], Opcode::Fallback));
+        assert!(matches!(opcodes[8], Opcode::Jump(9)));
+        assert!(matches!(opcodes[9], Opcode::Nop));
+        assert!(matches!(opcodes[10], Opcode::Fallback));
+
+        // 内存块的索引以及全局变量的索引都已转换为 VM 实例列表里的索引
+        assert_eq!(bytecode.memory_accesses, vec![MemoryAccess::new(5, 16)]);
+        assert_eq!(bytecode.break_items.len(), 1);
+        assert_eq!(bytecode.break_items[0].option_block_index, Some(0));
+        assert_eq!(bytecode.break_items[0].branch_target, branch_target);
+    }
+
+    #[test]
+    fn test_compile_local_variables() {
+        let instructions = vec![
+            object::Instruction::Sequence(Instruction::LocalGet(0)), // #00
+            object::Instruction::Sequence(Instruction::LocalSet(1)), // #01
+            object::Instruction::Control(Control::End(None)),        // #02
+            object::Instruction::Sequence(Instruction::LocalTee(0)), // #03
+            object::Instruction::Sequence(Instruction::LocalGet(1)), // #04
+            object::Instruction::Control(Control::End(None)),        // #05
+        ];
+
+        let bytecode = Bytecode::compile(
+            &instructions,
+            &[],
+            &[],
+            &[
+                (0, vec![ValueType::I64, ValueType::V128]),
+                (3, vec![ValueType::V128, ValueType::F32]),
+            ],
+        );
+        let opcodes = &bytecode.opcodes;
+
+        // v128 类型的局部变量回退到执行原指令
+        assert!(matches!(opcodes[0], Opcode::LocalGet(0)));
+        assert!(matches!(opcodes[1], Opcode::Fallback));
+        assert!(matches!(opcodes[3], Opcode::Fallback));
+        assert!(matches!(opcodes[4], Opcode::LocalGet(1)));
+    }
+
+    #[test]
+    fn test_compile_fused_items() {
+        let instructions = vec![
+            object::Instruction::Sequence(Instruction::LocalGet(0)), // #00
+            object::Instruction::Sequence(Instruction::LocalGet(1)), // #01
+            object::Instruction::Sequence(Instruction::I32Add),      // #02
+            object::Instruction::Control(Control::End(None)),        // #03
+            object::Instruction::Sequence(Instruction::LocalGet(0)), // #04
+            object::Instruction::Sequence(Instruction::I32Const(1)), // #05
+            object::Instruction::Sequence(Instruction::I32Add),      // #06
+            object::Instruction::Sequence(Instruction::LocalSet(1)), // #07
+            object::Instruction::Control(Control::End(None)),        // #08
+        ];
+
+        let fused_items = vec![
+            FusedItem {
+                address: 0,
+                length: 3,
+                fused_instruction: FusedInstruction::LocalGetLocalGetI32Add {
+                    left_index: 0,
+                    right_index: 1,
+                },
+            },
+            FusedItem {
+                address: 4,
+                length: 4,
+                fused_instruction: FusedInstruction::LocalGetI32ConstI32AddLocalSet {
+                    source_index: 0,
+                    value: 1,
+                    dest_index: 1,
+                },
+            },
+        ];
+
+        let function_local_variable_types_list = vec![
+            (0, vec![ValueType::I32, ValueType::I32]),
+            (4, vec![ValueType::I32, ValueType::V128]),
+        ];
+
+        let mut bytecode =
+            Bytecode::compile(&instructions, &[], &[], &function_local_variable_types_list);
+        bytecode.compile_fused_items(&fused_items, &function_local_variable_types_list);
+
+        let opcodes = &bytecode.opcodes;
+        let fused_opcodes = &bytecode.fused_opcodes;
+
+        // 原字节码列表保持不变
+        assert_eq!(fused_opcodes.len(), opcodes.len());
+        assert!(matches!(opcodes[0], Opcode::LocalGet(0)));
+
+        assert!(matches!(
+            fused_opcodes[0],
+            Opcode::LocalGetLocalGetI32Add(0, 1)
+        ));
+        assert!(matches!(fused_opcodes[1], Opcode::LocalGet(1)));
+
+        // 读写 v128 类型局部变量的超级指令不会被编译
+        assert!(matches!(fused_opcodes[4], Opcode::LocalGet(0)));
+    }
+}
@@ -22,40 +22,74 @@
 //!     新增加一个专门跟 `if 指令` 对应的 `block_jump_eq_zero 控制指令`；
 //!   * 有时 `if 指令` 的结构里不一定存在 `else 指令`，这时相当于在 `end 指令` 前有一个隐藏的 `else 指令`，
 //!     为了简化起见，如果 `if 指令` 的结构里不存在 `else 指令`，则直接让跳转目标为 `end 指令` 所在的位置。
-//! - `else 指令` 转换为 `jump 控制指令`，跳转目标为 if 结构块当中 `end 指令` 所在的位置；
-//! - `br 指令`
-//!   * 对于原 block/if 结构块，转换为 `jump 控制指令`，跳转目标为原始结构块的 `end 指令` 所在的位置，
-//!   * 对于原 loop 结构块，转换为 `recur 控制指令`，跳转目标为原始结构块的开始位置；
+//! - `else 指令` 转换为 `jump 控制指令`，跳转目标为 if 结构块当中 `end 指令` 的下一个指令；
+//! - `br 指令` 转换为 `jump 控制指令`
+//!   * 对于原 block/if 结构块，跳转目标为原始结构块的 `end 指令` 的下一个指令，
+//!   * 对于原 loop 结构块，跳转目标为原始结构块 `loop 指令` 的下一个指令；
 //! - `br_if 指令` 转换为 `jump_not_eq_zero 控制指令`；
 //! - `return 指令` 转换为 `jump 控制指令`，跳转目标为函数的最后一条指令（即 `end 指令`）所在的位置；
+//!
+//! ## 结构块的栈高度
+//!
+//! 结构块不创建栈帧，结构块内的操作数跟函数的操作数位于同一个调用帧的运算操作数段。
+//! 解码时根据每个指令的栈效应，静态地计算出每个结构块在进入时的栈高度（不包括结构块的参数），
+//! 跳转指令只需保留栈顶若干个操作数（block/if 结构块为返回值，loop 结构块为参数），
+//! 把操作数栈截断到目标结构块的栈高度，然后跳到目标地址即可。
+//!
+//! WebAssembly 的验证规则保证了同一位置的栈高度是确定的，所以静态计算的结果跟运行时一致。
 //! - `call 指令`：
 //!   * 对于目标为模块内的函数，转为 `call_internal 控制指令`；
 //!   * 对于目标为模块外的函数，转为 `call_external 控制指令`；
 //!   * 对于目标为本地的函数（native function），转为 `call_native 控制指令`；
 //! - `end 指令` 转为 `return 控制指令`；
+//! - `try_table 指令` 转为 `try_table 控制指令`，各个 catch 子句的跳转目标跟 `br 指令` 一样
+//!   预先计算好；
+//! - `throw 指令` 和 `throw_ref 指令` 分别转为 `throw 控制指令` 和 `throw_ref 控制指令`；
 //!
 //! 控制指令列表
 //!
 //! - block (block_type, end_addr)
 //! - block_jump_eq_zero (block_type, alternate_addr, end_addr)
-//! - jump (relative_depth, address)
-//! - jump_not_eq_zero (relative_depth, address)
-//! - recur (relative_depth, address)
-//! - recur_not_eq_zero (relative_depth, address)
-//! - branch ([branch_target::jump(relative_depth, address)], branch_target::recur(relative_depth, address))
+//! - jump (branch_target(stack_height, arity, address))
+//! - jump_not_eq_zero (branch_target(stack_height, arity, address))
+//! - branch ([branch_target], default_branch_target)
 //! - call_internal (type_index, function_index, address)
 //! - call_external (module_index, type_index, function_index, address)
 //! - call_native (module_index, type_index, function_index)
 //! - dynamic_call (type_index, table_index)
 //! - return
+//!
+//! ## 超级指令
+//!
+//! 编译器经常生成一些固定的指令序列，比如 `local.get; local.get; i32.add`，
+//! 逐条执行时每条指令都需要分派一次。解码器会识别这些序列并生成对应的超级指令
+//! （`object::FusedInstruction`），执行时一次完成整个序列。
+//!
+//! 超级指令不替换指令列表里的原指令，而是以 `object::FusedItem` 的形式单独列出，
+//! 并记录被融合的原指令的开始地址和数量。所以指令列表仍然跟原始指令一一对应，
+//! 单步执行以及断点仍然可以定位到每一条原指令。
+//!
+//! 被融合的序列除了第一条指令，其余指令的前一条指令都是顺序指令，而所有跳转目标
+//! （结构块 `end 指令`、`loop 指令`、`else 指令` 的下一条指令，以及函数调用的返回地址）
+//! 的前一条指令都是控制指令，所以不会有跳转目标落在被融合的序列的中间。
 
-use anvm_ast::{ast::ImportDescriptor, instruction};
+use anvm_ast::{
+    ast::{self, ImportDescriptor, TypeItem},
+    instruction::{self, BlockType, CatchClause},
+};
 
 use crate::{
     error::{EngineError, OutOfRange, Unsupported},
-    object::{BlockItem, BranchTarget, Control, FunctionItem, Instruction, NamedAstModule},
+    object::{
+        BlockItem, BranchTarget, CatchItem, Control, FunctionItem, FusedInstruction, FusedItem,
+        Instruction, NamedAstModule,
+    },
+    stack_effect::get_stack_effect,
 };
 
+/// 解码的结果，即每个模块的指令列表，以及每个模块的超级指令项列表
+pub type DecodedInstructions = (Vec<Vec<Instruction>>, Vec<Vec<FusedItem>>);
+
 /// 将 AST 模块当中的函数指令序列编译为虚拟机能直接解析运行的指令
 ///
 /// 具体来说，原始指令（即 WebAssembly 指令）当中部分指令包含有一定的流程控制逻辑，
@@ -73,12 +107,13 @@ use crate::{
 pub fn decode(
     named_ast_modules: &[NamedAstModule],
     function_items_list: &Vec<Vec<FunctionItem>>,
-) -> Result<Vec<Vec<Instruction>>, EngineError> {
+) -> Result<DecodedInstructions, EngineError> {
     // 第 3 步：
     // - 将 AST 的 Instruction 转换为虚拟机可直接解析运行的 Instruction
     // - 合并一个模块里的所有内部函数的指令序列
 
     let mut instructions_list: Vec<Vec<Instruction>> = vec![];
+    let mut fused_items_list: Vec<Vec<FusedItem>> = vec![];
 
     // 转换一个模块
     for (ast_module_index, named_ast_module) in named_ast_modules.iter().enumerate() {
@@ -99,6 +134,18 @@ pub fn decode(
 
         let function_items = &function_items_list[ast_module_index];
 
+        // 所有函数（包括导入函数）的类型索引，用于计算函数调用指令的栈效应
+        let module = &named_ast_module.module;
+        let function_type_indexes = module
+            .import_items
+            .iter()
+            .filter_map(|item| match item.import_descriptor {
+                ImportDescriptor::FunctionTypeIndex(type_index) => Some(type_index),
+                _ => None,
+            })
+            .chain(module.internal_function_to_type_index_list.iter().copied())
+            .collect::<Vec<u32>>();
+
         // 转换一个函数
         for (internal_function_index, code_item) in
             named_ast_module.module.code_items.iter().enumerate()
@@ -119,7 +166,19 @@ pub fn decode(
                 _ => unreachable!("should be normal \"function\" item"),
             };
 
-            let mut block_index_stack: Vec<usize> = vec![];
+            // 函数本层的跳转目标，即 `return 指令` 的跳转目标
+            let function_type_index = function_type_indexes[function_index];
+            let function_branch_target = BranchTarget {
+                relative_depth: 0,
+                stack_height: 0,
+                arity: get_function_type_counts(module, function_type_index).1,
+                address: function_end_address,
+            };
+
+            let mut block_contexts: Vec<BlockContext> = vec![];
+
+            // 当前位置的运算操作数的数量，即相对于调用帧运算操作数段开始位置的栈高度
+            let mut stack_height: usize = 0;
 
             let function_original_instructions = &code_item.instruction_items;
 
@@ -127,7 +186,6 @@ pub fn decode(
                 let instruction = match function_original_instruction {
                     instruction::Instruction::Block(block_type, block_index) => {
                         let block_index_usize = *block_index as usize;
-                        block_index_stack.push(block_index_usize);
 
                         // 获取 block 结构块当中的 `end 指令` 所在的位置
                         let block_item = &block_items[block_index_usize];
@@ -141,6 +199,20 @@ pub fn decode(
                         } else {
                             unreachable!("should be \"block\" structure")
                         };
+
+                        // 跳到 block 结构块时，携带的是结构块的返回值，
+                        // 跳转目标为结构块 `end 指令` 的下一个指令
+                        let (params_count, results_count) =
+                            get_block_type_counts(module, block_type);
+                        block_contexts.push(BlockContext {
+                            block_index: block_index_usize,
+                            stack_height: stack_height.saturating_sub(params_count),
+                            params_count,
+                            results_count,
+                            arity: results_count,
+                            address: function_start_address + end_address + 1,
+                        });
+
                         Instruction::Control(Control::Block {
                             block_type: block_type.to_owned(),
                             block_index: block_index_usize,
@@ -149,20 +221,33 @@ pub fn decode(
                     }
                     instruction::Instruction::Loop(block_type, block_index) => {
                         let block_index_usize = *block_index as usize;
-                        block_index_stack.push(block_index_usize);
 
                         // 获取 loop 结构块当中的 `end 指令` 所在的位置
                         let block_item = &block_items[block_index_usize];
-                        let end_address = if let BlockItem::Loop {
+                        let (start_address, end_address) = if let BlockItem::Loop {
                             block_type: _,
-                            start_address: _,
+                            start_address,
                             end_address,
                         } = block_item
                         {
-                            end_address
+                            (start_address, end_address)
                         } else {
                             unreachable!("should be \"loop\" structure")
                         };
+
+                        // 跳到 loop 结构块时，携带的是结构块的参数，
+                        // 跳转目标为 `loop 指令` 的下一个指令
+                        let (params_count, results_count) =
+                            get_block_type_counts(module, block_type);
+                        block_contexts.push(BlockContext {
+                            block_index: block_index_usize,
+                            stack_height: stack_height.saturating_sub(params_count),
+                            params_count,
+                            results_count,
+                            arity: params_count,
+                            address: function_start_address + start_address + 1,
+                        });
+
                         Instruction::Control(Control::Block {
                             block_type: block_type.to_owned(),
                             block_index: block_index_usize,
@@ -171,7 +256,6 @@ pub fn decode(
                     }
                     instruction::Instruction::If(block_type, block_index) => {
                         let block_index_usize = *block_index as usize;
-                        block_index_stack.push(block_index_usize);
 
                         // 获取 if 结构块当中的 `else 指令` 所在的位置
                         let block_item = &block_items[block_index_usize];
@@ -187,6 +271,20 @@ pub fn decode(
                             unreachable!("should be \"if\" structure")
                         };
 
+                        // `if 指令` 先弹出一个操作数作为判断条件
+                        stack_height = stack_height.saturating_sub(1);
+
+                        let (params_count, results_count) =
+                            get_block_type_counts(module, block_type);
+                        block_contexts.push(BlockContext {
+                            block_index: block_index_usize,
+                            stack_height: stack_height.saturating_sub(params_count),
+                            params_count,
+                            results_count,
+                            arity: results_count,
+                            address: function_start_address + end_address + 1,
+                        });
+
                         let map_alternate_address = option_alternate_address
                             .map(|address| function_start_address + address);
 
@@ -197,119 +295,169 @@ pub fn decode(
                             end_address: function_start_address + end_address,
                         })
                     }
-                    instruction::Instruction::Else => {
-                        let block_index = block_index_stack.last().unwrap();
-
-                        // 获取 if 结构块当中的 `end 指令` 所在的位置
+                    instruction::Instruction::TryTable(block_type, catch_clauses, block_index) => {
                         let block_index_usize = *block_index as usize;
-                        let block_item = &block_items[block_index_usize];
 
-                        let end_address = if let BlockItem::If {
+                        // 获取 try_table 结构块当中的 `end 指令` 所在的位置
+                        let block_item = &block_items[block_index_usize];
+                        let end_address = if let BlockItem::Block {
                             block_type: _,
                             start_address: _,
                             end_address,
-                            alternate_address: _,
                         } = block_item
                         {
                             end_address
                         } else {
-                            unreachable!("should be \"if\" structure")
+                            unreachable!("should be \"try_table\" structure")
                         };
-                        Instruction::Control(Control::JumpWithinBlock(function_start_address + end_address))
+
+                        // catch 子句里的相对深度不包括 try_table 结构块本身，
+                        // 所以需要在压入 try_table 结构块之前计算跳转目标
+                        let mut catch_items: Vec<CatchItem> = vec![];
+                        for catch_clause in catch_clauses {
+                            let (option_tag_index, is_ref, relative_depth) = match catch_clause {
+                                CatchClause::Catch(tag_index, relative_depth) => {
+                                    (Some(*tag_index as usize), false, relative_depth)
+                                }
+                                CatchClause::CatchRef(tag_index, relative_depth) => {
+                                    (Some(*tag_index as usize), true, relative_depth)
+                                }
+                                CatchClause::CatchAll(relative_depth) => {
+                                    (None, false, relative_depth)
+                                }
+                                CatchClause::CatchAllRef(relative_depth) => {
+                                    (None, true, relative_depth)
+                                }
+                            };
+
+                            let branch_target = get_branch_target(
+                                &function_branch_target,
+                                &block_contexts,
+                                *relative_depth as usize,
+                            )?;
+
+                            catch_items.push(CatchItem {
+                                option_tag_index,
+                                is_ref,
+                                branch_target,
+                            });
+                        }
+
+                        let (params_count, results_count) =
+                            get_block_type_counts(module, block_type);
+                        block_contexts.push(BlockContext {
+                            block_index: block_index_usize,
+                            stack_height: stack_height.saturating_sub(params_count),
+                            params_count,
+                            results_count,
+                            arity: results_count,
+                            address: function_start_address + end_address + 1,
+                        });
+
+                        Instruction::Control(Control::TryTable {
+                            block_type: block_type.to_owned(),
+                            block_index: block_index_usize,
+                            end_address: function_start_address + end_address,
+                            catch_items,
+                        })
+                    }
+                    instruction::Instruction::Throw(_) | instruction::Instruction::ThrowRef => {
+                        stack_height = get_unreachable_stack_height(&block_contexts);
+
+                        if let instruction::Instruction::Throw(tag_index) =
+                            function_original_instruction
+                        {
+                            Instruction::Control(Control::Throw(*tag_index as usize))
+                        } else {
+                            Instruction::Control(Control::ThrowRef)
+                        }
+                    }
+                    instruction::Instruction::Else => {
+                        let block_context = block_contexts.last().unwrap();
+
+                        // 进入 else 分支时，栈高度恢复为进入 if 结构块时的高度
+                        stack_height = block_context.stack_height + block_context.params_count;
+
+                        // 跳到 if 结构块 `end 指令` 的下一个指令
+                        Instruction::Control(Control::JumpWithinBlock(block_context.address))
                     }
                     instruction::Instruction::Br(relative_depth) => {
-                        let result = get_branch_target(
-                            function_start_address,
-                            function_end_address,
-                            &block_items,
-                            &block_index_stack,
+                        let branch_target = get_branch_target(
+                            &function_branch_target,
+                            &block_contexts,
                             *relative_depth as usize,
                         )?;
 
-                        match result {
-                            BranchTarget::Break(relative_depth, address) => {
-                                let option_block_index = block_index_stack.last().map(|v| *v);
-                                Instruction::Control(Control::Break {
-                                    option_block_index,
-                                    relative_depth,
-                                    address,
-                                })
-                            }
-                            BranchTarget::Recur(relative_depth, address) => {
-                                let block_index = block_index_stack.last().map(|v| *v).unwrap();
-                                Instruction::Control(Control::Recur {
-                                    block_index,
-                                    relative_depth,
-                                    address,
-                                })
-                            }
-                        }
+                        let option_block_index = block_contexts.last().map(|c| c.block_index);
+                        stack_height = get_unreachable_stack_height(&block_contexts);
+
+                        Instruction::Control(Control::Break {
+                            option_block_index,
+                            branch_target,
+                        })
                     }
                     instruction::Instruction::BrIf(relative_depth) => {
-                        let result = get_branch_target(
-                            function_start_address,
-                            function_end_address,
-                            &block_items,
-                            &block_index_stack,
+                        let branch_target = get_branch_target(
+                            &function_branch_target,
+                            &block_contexts,
                             *relative_depth as usize,
                         )?;
 
-                        match result {
-                            BranchTarget::Break(relative_depth, address) => {
-                                let option_block_index = block_index_stack.last().map(|v| *v);
-                                Instruction::Control(Control::BreakWhenNotEqZero {
-                                    option_block_index,
-                                    relative_depth,
-                                    address,
-                                })
-                            }
-                            BranchTarget::Recur(relative_depth, address) => {
-                                let block_index = block_index_stack.last().map(|v| *v).unwrap();
-                                Instruction::Control(Control::RecurWhenNotEqZero {
-                                    block_index,
-                                    relative_depth,
-                                    address,
-                                })
-                            }
-                        }
+                        let option_block_index = block_contexts.last().map(|c| c.block_index);
+                        stack_height = stack_height.saturating_sub(1);
+
+                        Instruction::Control(Control::BreakWhenNotEqZero {
+                            option_block_index,
+                            branch_target,
+                        })
                     }
                     instruction::Instruction::BrTable(relative_depths, default_relative_depth) => {
-                        let mut depths = relative_depths.to_owned();
-                        depths.push(*default_relative_depth);
-
-                        let mut targets: Vec<BranchTarget> = vec![];
-                        for depth in depths {
-                            let target = get_branch_target(
-                                function_start_address,
-                                function_end_address,
-                                &block_items,
-                                &block_index_stack,
-                                depth as usize,
+                        let mut branch_targets: Vec<BranchTarget> = vec![];
+                        for relative_depth in relative_depths {
+                            let branch_target = get_branch_target(
+                                &function_branch_target,
+                                &block_contexts,
+                                *relative_depth as usize,
                             )?;
-                            targets.push(target)
+                            branch_targets.push(branch_target)
                         }
 
-                        let option_block_index = block_index_stack.last().map(|v| *v);
-                        let default_branch_target = targets.last().unwrap();
-                        let branch_targets = &targets[0..targets.len() - 1];
+                        let default_branch_target = get_branch_target(
+                            &function_branch_target,
+                            &block_contexts,
+                            *default_relative_depth as usize,
+                        )?;
+
+                        let option_block_index = block_contexts.last().map(|c| c.block_index);
+                        stack_height = get_unreachable_stack_height(&block_contexts);
 
                         Instruction::Control(Control::Branch {
                             option_block_index,
-                            branch_targets: branch_targets.to_owned(),
-                            default_branch_target: default_branch_target.to_owned(),
+                            branch_targets,
+                            default_branch_target,
                         })
                     }
                     instruction::Instruction::Return => {
-                        let option_block_index = block_index_stack.last().map(|v| *v);
-                        let relative_depth = block_index_stack.len();
-                        let end_address = function_original_instructions.len() - 1;
+                        let option_block_index = block_contexts.last().map(|c| c.block_index);
+                        let branch_target = BranchTarget {
+                            relative_depth: block_contexts.len(),
+                            ..function_branch_target.clone()
+                        };
+
+                        stack_height = get_unreachable_stack_height(&block_contexts);
+
                         Instruction::Control(Control::Break {
                             option_block_index,
-                            relative_depth,
-                            address: function_start_address + end_address,
+                            branch_target,
                         })
                     }
                     instruction::Instruction::Call(function_index) => {
+                        let (params_count, results_count) = get_function_type_counts(
+                            module,
+                            function_type_indexes[*function_index as usize],
+                        );
+                        stack_height = stack_height.saturating_sub(params_count) + results_count;
+
                         // 获取函数的位置信息
                         let function_item =
                             &function_items_list[ast_module_index][*function_index as usize];
@@ -342,39 +490,188 @@ pub fn decode(
                         }
                     }
                     instruction::Instruction::CallIndirect(type_index, table_index) => {
+                        // 除了函数的参数，还需要弹出一个操作数作为表格元素的索引
+                        let (params_count, results_count) =
+                            get_function_type_counts(module, *type_index);
+                        stack_height =
+                            stack_height.saturating_sub(params_count + 1) + results_count;
+
                         Instruction::Control(Control::CallIndirect {
                             type_index: *type_index as usize,
                             table_index: *table_index as usize,
                         })
                     }
+                    instruction::Instruction::ReturnCall(function_index) => {
+                        stack_height = get_unreachable_stack_height(&block_contexts);
+
+                        let function_item =
+                            &function_items_list[ast_module_index][*function_index as usize];
+
+                        match function_item {
+                            FunctionItem::Normal {
+                                vm_module_index,
+                                type_index,
+                                function_index,
+                                internal_function_index,
+                                start_address,
+                                end_address: _,
+                                block_items: _,
+                            } => Instruction::Control(Control::ReturnCall {
+                                vm_module_index: *vm_module_index,
+                                type_index: *type_index,
+                                function_index: *function_index,
+                                internal_function_index: *internal_function_index,
+                                address: *start_address,
+                            }),
+                            FunctionItem::Native {
+                                native_module_index,
+                                type_index,
+                                function_index,
+                            } => Instruction::Control(Control::ReturnCallNative {
+                                native_module_index: *native_module_index,
+                                type_index: *type_index,
+                                function_index: *function_index,
+                            }),
+                        }
+                    }
+                    instruction::Instruction::ReturnCallIndirect(type_index, table_index) => {
+                        stack_height = get_unreachable_stack_height(&block_contexts);
+
+                        Instruction::Control(Control::ReturnCallIndirect {
+                            type_index: *type_index as usize,
+                            table_index: *table_index as usize,
+                        })
+                    }
                     instruction::Instruction::End => {
                         // 函数的指令序列最后一个指令，即 `end 指令` 不属于结构块，所以需要排除
                         // 结构块栈已经弹空的情况
-                        let option_block_index = block_index_stack.pop();
+                        let option_block_index = block_contexts.pop().map(|block_context| {
+                            stack_height =
+                                block_context.stack_height + block_context.results_count;
+                            block_context.block_index
+                        });
                         Instruction::Control(Control::End(option_block_index))
                     }
                     instruction::Instruction::Unreachable => {
+                        stack_height = get_unreachable_stack_height(&block_contexts);
                         Instruction::Control(Control::Unreachable)
                     }
                     instruction::Instruction::Nop => Instruction::Control(Control::Nop),
-                    _ => Instruction::Sequence(function_original_instruction.to_owned()), // 其他指令归类为 `顺序指令`，
+                    _ => {
+                        // 其他指令归类为 `顺序指令`
+                        let (pops, pushes) = get_stack_effect(function_original_instruction);
+                        stack_height = stack_height.saturating_sub(pops) + pushes;
+                        Instruction::Sequence(function_original_instruction.to_owned())
+                    }
                 };
 
                 instructions.push(instruction);
             }
         }
 
+        fused_items_list.push(fuse_instructions(&instructions));
         instructions_list.push(instructions);
     }
 
-    Ok(instructions_list)
+    Ok((instructions_list, fused_items_list))
+}
+
+/// 识别指令列表里可以融合为超级指令的指令序列
+///
+/// 从前往后匹配，已被融合的指令不会再参与其他序列的匹配。
+pub fn fuse_instructions(instructions: &[Instruction]) -> Vec<FusedItem> {
+    let mut fused_items: Vec<FusedItem> = vec![];
+    let mut address: usize = 0;
+
+    while address < instructions.len() {
+        match match_fused_instruction(&instructions[address..]) {
+            Some((length, fused_instruction)) => {
+                fused_items.push(FusedItem {
+                    address,
+                    length,
+                    fused_instruction,
+                });
+                address += length;
+            }
+            None => {
+                address += 1;
+            }
+        }
+    }
+
+    fused_items
+}
+
+/// 尝试以指令切片的第一条指令作为开始匹配超级指令
+///
+/// 返回被融合的指令的数量以及超级指令。
+fn match_fused_instruction(instructions: &[Instruction]) -> Option<(usize, FusedInstruction)> {
+    use instruction::Instruction::{I32Add, I32Const, I32LtS, LocalGet, LocalSet};
+
+    let sequence = |offset: usize| match instructions.get(offset) {
+        Some(Instruction::Sequence(instruction)) => Some(instruction),
+        _ => None,
+    };
+
+    match (sequence(0), sequence(1), sequence(2)) {
+        (Some(LocalGet(left_index)), Some(LocalGet(right_index)), Some(I32Add)) => Some((
+            3,
+            FusedInstruction::LocalGetLocalGetI32Add {
+                left_index: *left_index,
+                right_index: *right_index,
+            },
+        )),
+        (Some(LocalGet(source_index)), Some(I32Const(value)), Some(I32Add)) => match sequence(3) {
+            Some(LocalSet(dest_index)) => Some((
+                4,
+                FusedInstruction::LocalGetI32ConstI32AddLocalSet {
+                    source_index: *source_index,
+                    value: *value,
+                    dest_index: *dest_index,
+                },
+            )),
+            _ => None,
+        },
+        (Some(I32Const(value)), Some(I32LtS), None) => match instructions.get(2) {
+            Some(Instruction::Control(Control::BreakWhenNotEqZero {
+                option_block_index,
+                branch_target,
+            })) => Some((
+                3,
+                FusedInstruction::I32ConstI32LtSBreakWhenNotEqZero {
+                    value: *value,
+                    option_block_index: *option_block_index,
+                    branch_target: branch_target.clone(),
+                },
+            )),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// 解码过程中的结构块信息
+///
+/// 用于静态分析结构块的栈高度，以及计算跳转指令的目标
+struct BlockContext {
+    block_index: usize,
+
+    /// 进入结构块时（不包括结构块的参数）运算操作数的数量
+    stack_height: usize,
+
+    params_count: usize,
+    results_count: usize,
+
+    /// 跳到此结构块时需要携带的操作数的数量
+    arity: usize,
+
+    /// 跳到此结构块时的目标地址
+    address: usize,
 }
 
 fn get_branch_target(
-    function_start_address: usize,
-    function_end_address: usize,
-    block_items: &[BlockItem],
-    block_index_stack: &[usize],
+    function_branch_target: &BranchTarget,
+    block_contexts: &[BlockContext],
     relative_depth: usize,
 ) -> Result<BranchTarget, EngineError> {
     // target_level 为目标层的层级，函数本层的层级为 0，第一层 block 的层级为 1，比如
@@ -385,53 +682,71 @@ fn get_branch_target(
     //  |   |     <--- level 1
     //  |-- end
     //
-    let target_level = block_index_stack.len() - relative_depth;
-
-    if (target_level as isize) < 0 {
+    if relative_depth > block_contexts.len() {
         // 目标层级超出了范围
         return Err(EngineError::OutOfRange(
-            OutOfRange::BlockRelativeDepthOutOfRange(relative_depth, block_index_stack.len()),
+            OutOfRange::BlockRelativeDepthOutOfRange(relative_depth, block_contexts.len()),
         ));
     }
 
+    let target_level = block_contexts.len() - relative_depth;
+
     if target_level == 0 {
         // 跳到函数本层了
         // 目标位置应该是函数的最后一个指令，即 `end 指令` 所在的位置
-
-        Ok(BranchTarget::Break(relative_depth, function_end_address))
+        Ok(BranchTarget {
+            relative_depth,
+            ..function_branch_target.clone()
+        })
     } else {
-        let target_block_index = block_index_stack[target_level - 1];
-
-        // 获取目标层的位置信息
-        let block_item = &block_items[target_block_index];
-
-        match block_item {
-            BlockItem::Loop {
-                block_type: _,
-                start_address,
-                end_address: _,
-            } => Ok(BranchTarget::Recur(
-                relative_depth,
-                function_start_address + *start_address,
-            )),
-            BlockItem::Block {
-                block_type: _,
-                start_address: _,
-                end_address,
-            } => Ok(BranchTarget::Break(
-                relative_depth,
-                function_start_address + *end_address,
-            )),
-            BlockItem::If {
-                block_type: _,
-                start_address: _,
-                end_address,
-                alternate_address: _,
-            } => Ok(BranchTarget::Break(
-                relative_depth,
-                function_start_address + *end_address,
-            )),
+        let block_context = &block_contexts[target_level - 1];
+        Ok(BranchTarget {
+            relative_depth,
+            stack_height: block_context.stack_height,
+            arity: block_context.arity,
+            address: block_context.address,
+        })
+    }
+}
+
+/// 获取执行 `br`、`return`、`unreachable` 等无条件跳转指令之后的栈高度
+///
+/// 这些指令之后直到结构块结束（或者 `else 指令`）之前的指令都不会被执行，
+/// 在 WebAssembly 的验证规则里，这部分指令的操作数栈是 "多态" 的，
+/// 这里简单地把栈高度恢复为进入当前结构块时的高度。
+fn get_unreachable_stack_height(block_contexts: &[BlockContext]) -> usize {
+    block_contexts
+        .last()
+        .map(|block_context| block_context.stack_height)
+        .unwrap_or(0)
+}
+
+/// 获取函数类型的参数和返回值的数量
+///
+/// 解码器并不验证模块，如果类型不存在，则当作没有参数和返回值，
+/// 执行到相应的指令时虚拟机会再作检查。
+fn get_function_type_counts(module: &ast::Module, type_index: u32) -> (usize, usize) {
+    match module.type_items.get(type_index as usize) {
+        Some(TypeItem::FunctionType(function_type)) => {
+            (function_type.params.len(), function_type.results.len())
         }
+        None => (0, 0),
+    }
+}
+
+/// 获取结构块的参数和返回值的数量
+fn get_block_type_counts(module: &ast::Module, block_type: &BlockType) -> (usize, usize) {
+    match block_type {
+        BlockType::ResultEmpty => (0, 0),
+        BlockType::ResultI32
+        | BlockType::ResultI64
+        | BlockType::ResultF32
+        | BlockType::ResultF64
+        | BlockType::ResultV128
+        | BlockType::ResultFuncRef
+        | BlockType::ResultExternRef
+        | BlockType::ResultExnRef => (0, 1),
+        BlockType::TypeIndex(type_index) => get_function_type_counts(module, *type_index),
     }
 }
 
@@ -450,11 +765,14 @@ fn get_branch_target(
 /// - ref.null
 /// - ref.func x
 /// - global.get x （目标只允许是导入的 global item）
+/// - i32.add、i32.sub、i32.mul、i64.add、i64.sub、i64.mul（由 extended-const 提案引入）
 ///
 /// 详细见：
 /// https://webassembly.github.io/spec/core/valid/instructions.html#constant-expressions
+/// https://github.com/WebAssembly/extended-const/blob/main/proposals/extended-const/Overview.md
 ///
-/// 目前这里只支持 `t.const 指令`
+/// 动态链接的工具链常用 `(i32.add (global.get $__memory_base) (i32.const offset))`
+/// 这种形式的常量表达式作为数据段的偏移值。
 pub fn decode_constant_expression(
     original_instructions: &[instruction::Instruction],
 ) -> Result<Vec<instruction::Instruction>, EngineError> {
@@ -466,6 +784,16 @@ pub fn decode_constant_expression(
             | instruction::Instruction::I64Const(_)
             | instruction::Instruction::F32Const(_)
             | instruction::Instruction::F64Const(_)
+            | instruction::Instruction::V128Const(_)
+            | instruction::Instruction::RefNull(_)
+            | instruction::Instruction::RefFunc(_)
+            | instruction::Instruction::GlobalGet(_)
+            | instruction::Instruction::I32Add
+            | instruction::Instruction::I32Sub
+            | instruction::Instruction::I32Mul
+            | instruction::Instruction::I64Add
+            | instruction::Instruction::I64Sub
+            | instruction::Instruction::I64Mul
             | instruction::Instruction::End => inst.to_owned(),
             _ => {
                 return Err(EngineError::Unsupported(
@@ -482,12 +810,12 @@ pub fn decode_constant_expression(
 
 #[cfg(test)]
 mod tests {
-    use super::{decode, NamedAstModule};
+    use super::{decode, fuse_instructions, NamedAstModule};
     use crate::{
         error::{EngineError, NativeTerminate},
         linker,
         native_module::{EmptyModuleContext, NativeModule},
-        object::{BranchTarget, Control, FunctionItem, Instruction},
+        object::{BranchTarget, Control, FunctionItem, FusedInstruction, FusedItem, Instruction},
         vm::VM,
     };
     use anvm_ast::{
@@ -517,13 +845,16 @@ mod tests {
                 internal_function_to_type_index_list: function_list,
                 tables: vec![],
                 memory_blocks: vec![],
+                tag_items: vec![],
                 global_items: vec![],
                 export_items,
                 start_function_index: None,
                 element_items: vec![],
                 code_items,
                 data_items: vec![],
+                data_count: None,
             },
+            option_content_hash: None,
         }
     }
 
@@ -585,6 +916,7 @@ mod tests {
         let function_items_list: Vec<Vec<FunctionItem>> =
             linker::link_functions(native_modules, named_ast_modules)?;
         decode(named_ast_modules, &function_items_list)
+            .map(|(instructions_list, _)| instructions_list)
     }
 
     #[test]
@@ -862,43 +1194,71 @@ mod tests {
             }), // #04 - block 0
             Instruction::Control(Control::Break {
                 option_block_index: Some(0),
-                relative_depth: 0,
-                address: 28,
+                branch_target: BranchTarget {
+                    relative_depth: 0,
+                    stack_height: 2,
+                    arity: 0,
+                    address: 29,
+                },
             }), // #05
             Instruction::Control(Control::Break {
                 option_block_index: Some(0),
-                relative_depth: 1,
-                address: 35,
+                branch_target: BranchTarget {
+                    relative_depth: 1,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 35,
+                },
             }), // #06
             Instruction::Control(Control::Break {
                 option_block_index: Some(0),
-                relative_depth: 1,
-                address: 35,
+                branch_target: BranchTarget {
+                    relative_depth: 1,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 35,
+                },
             }), // #07
             Instruction::Control(Control::Block {
                 block_type: BlockType::ResultEmpty,
                 block_index: 1,
                 end_address: 24,
             }), // #08 - block 1 - loop
-            Instruction::Control(Control::Recur {
-                block_index: 1,
-                relative_depth: 0,
-                address: 8,
+            Instruction::Control(Control::Break {
+                option_block_index: Some(1),
+                branch_target: BranchTarget {
+                    relative_depth: 0,
+                    stack_height: 2,
+                    arity: 0,
+                    address: 9,
+                },
             }), // #09
             Instruction::Control(Control::Break {
                 option_block_index: Some(1),
-                relative_depth: 1,
-                address: 28,
+                branch_target: BranchTarget {
+                    relative_depth: 1,
+                    stack_height: 2,
+                    arity: 0,
+                    address: 29,
+                },
             }), // #10
             Instruction::Control(Control::Break {
                 option_block_index: Some(1),
-                relative_depth: 2,
-                address: 35,
+                branch_target: BranchTarget {
+                    relative_depth: 2,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 35,
+                },
             }), // #11
             Instruction::Control(Control::Break {
                 option_block_index: Some(1),
-                relative_depth: 2,
-                address: 35,
+                branch_target: BranchTarget {
+                    relative_depth: 2,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 35,
+                },
             }), // #12
             Instruction::Control(Control::Block {
                 block_type: BlockType::ResultEmpty,
@@ -907,78 +1267,134 @@ mod tests {
             }), // #13 - block 2
             Instruction::Control(Control::Break {
                 option_block_index: Some(2),
-                relative_depth: 0,
-                address: 19,
+                branch_target: BranchTarget {
+                    relative_depth: 0,
+                    stack_height: 2,
+                    arity: 0,
+                    address: 20,
+                },
             }), // #14
-            Instruction::Control(Control::Recur {
-                block_index: 2,
-                relative_depth: 1,
-                address: 8,
+            Instruction::Control(Control::Break {
+                option_block_index: Some(2),
+                branch_target: BranchTarget {
+                    relative_depth: 1,
+                    stack_height: 2,
+                    arity: 0,
+                    address: 9,
+                },
             }), // #15
             Instruction::Control(Control::Break {
                 option_block_index: Some(2),
-                relative_depth: 2,
-                address: 28,
+                branch_target: BranchTarget {
+                    relative_depth: 2,
+                    stack_height: 2,
+                    arity: 0,
+                    address: 29,
+                },
             }), // #16
             Instruction::Control(Control::Break {
                 option_block_index: Some(2),
-                relative_depth: 3,
-                address: 35,
+                branch_target: BranchTarget {
+                    relative_depth: 3,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 35,
+                },
             }), // #17
             Instruction::Control(Control::Break {
                 option_block_index: Some(2),
-                relative_depth: 3,
-                address: 35,
+                branch_target: BranchTarget {
+                    relative_depth: 3,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 35,
+                },
             }), // #18
             Instruction::Control(Control::End(Some(2))),                  // #19 - block 2 end
-            Instruction::Control(Control::Recur {
-                block_index: 1,
-                relative_depth: 0,
-                address: 8,
+            Instruction::Control(Control::Break {
+                option_block_index: Some(1),
+                branch_target: BranchTarget {
+                    relative_depth: 0,
+                    stack_height: 2,
+                    arity: 0,
+                    address: 9,
+                },
             }), // #20
             Instruction::Control(Control::Break {
                 option_block_index: Some(1),
-                relative_depth: 1,
-                address: 28,
+                branch_target: BranchTarget {
+                    relative_depth: 1,
+                    stack_height: 2,
+                    arity: 0,
+                    address: 29,
+                },
             }), // #21
             Instruction::Control(Control::Break {
                 option_block_index: Some(1),
-                relative_depth: 2,
-                address: 35,
+                branch_target: BranchTarget {
+                    relative_depth: 2,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 35,
+                },
             }), // #22
             Instruction::Control(Control::Break {
                 option_block_index: Some(1),
-                relative_depth: 2,
-                address: 35,
+                branch_target: BranchTarget {
+                    relative_depth: 2,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 35,
+                },
             }), // #23
             Instruction::Control(Control::End(Some(1))),                  // #24 - block 1 end
             Instruction::Control(Control::Break {
                 option_block_index: Some(0),
-                relative_depth: 0,
-                address: 28,
+                branch_target: BranchTarget {
+                    relative_depth: 0,
+                    stack_height: 2,
+                    arity: 0,
+                    address: 29,
+                },
             }), // #25
             Instruction::Control(Control::Break {
                 option_block_index: Some(0),
-                relative_depth: 1,
-                address: 35,
+                branch_target: BranchTarget {
+                    relative_depth: 1,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 35,
+                },
             }), // #26
             Instruction::Control(Control::Break {
                 option_block_index: Some(0),
-                relative_depth: 1,
-                address: 35,
+                branch_target: BranchTarget {
+                    relative_depth: 1,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 35,
+                },
             }), // #27
             Instruction::Control(Control::End(Some(0))),                  // #28 - block 0 end
             Instruction::Sequence(instruction::Instruction::I32Const(2)), // #29
             Instruction::Sequence(instruction::Instruction::I32Const(3)), // #30
             Instruction::Control(Control::Break {
                 option_block_index: None,
-                relative_depth: 0,
-                address: 35,
+                branch_target: BranchTarget {
+                    relative_depth: 0,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 35,
+                },
             }), // #31
             Instruction::Control(Control::Break {
                 option_block_index: None,
-                relative_depth: 0,
-                address: 35,
+                branch_target: BranchTarget {
+                    relative_depth: 0,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 35,
+                },
             }), // #32
             Instruction::Sequence(instruction::Instruction::I32Const(4)), // #33
             Instruction::Sequence(instruction::Instruction::I32Const(5)), // #34
@@ -1010,18 +1426,30 @@ mod tests {
             }), // #43 - block 3
             Instruction::Control(Control::Break {
                 option_block_index: Some(3),
-                relative_depth: 0,
-                address: 77,
+                branch_target: BranchTarget {
+                    relative_depth: 0,
+                    stack_height: 2,
+                    arity: 0,
+                    address: 78,
+                },
             }), // #44
             Instruction::Control(Control::Break {
                 option_block_index: Some(3),
-                relative_depth: 1,
-                address: 80,
+                branch_target: BranchTarget {
+                    relative_depth: 1,
+                    stack_height: 2,
+                    arity: 0,
+                    address: 81,
+                },
             }), // #45
             Instruction::Control(Control::Break {
                 option_block_index: Some(3),
-                relative_depth: 2,
-                address: 85,
+                branch_target: BranchTarget {
+                    relative_depth: 2,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 85,
+                },
             }), // #46
             Instruction::Control(Control::BlockAndJumpWhenEqZero {
                 block_type: BlockType::ResultEmpty,
@@ -1031,23 +1459,39 @@ mod tests {
             }), // #47 - block 4 if
             Instruction::Control(Control::Break {
                 option_block_index: Some(4),
-                relative_depth: 0,
-                address: 73,
+                branch_target: BranchTarget {
+                    relative_depth: 0,
+                    stack_height: 1,
+                    arity: 0,
+                    address: 74,
+                },
             }), // #48
             Instruction::Control(Control::Break {
                 option_block_index: Some(4),
-                relative_depth: 1,
-                address: 77,
+                branch_target: BranchTarget {
+                    relative_depth: 1,
+                    stack_height: 2,
+                    arity: 0,
+                    address: 78,
+                },
             }), // #49
             Instruction::Control(Control::Break {
                 option_block_index: Some(4),
-                relative_depth: 2,
-                address: 80,
+                branch_target: BranchTarget {
+                    relative_depth: 2,
+                    stack_height: 2,
+                    arity: 0,
+                    address: 81,
+                },
             }), // #50
             Instruction::Control(Control::Break {
                 option_block_index: Some(4),
-                relative_depth: 3,
-                address: 85,
+                branch_target: BranchTarget {
+                    relative_depth: 3,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 85,
+                },
             }), // #51
             Instruction::Control(Control::Block {
                 block_type: BlockType::ResultEmpty,
@@ -1056,55 +1500,95 @@ mod tests {
             }), // #52 - block 5
             Instruction::Control(Control::Break {
                 option_block_index: Some(5),
-                relative_depth: 0,
-                address: 59,
+                branch_target: BranchTarget {
+                    relative_depth: 0,
+                    stack_height: 1,
+                    arity: 0,
+                    address: 60,
+                },
             }), // #53
             Instruction::Control(Control::Break {
                 option_block_index: Some(5),
-                relative_depth: 1,
-                address: 73,
+                branch_target: BranchTarget {
+                    relative_depth: 1,
+                    stack_height: 1,
+                    arity: 0,
+                    address: 74,
+                },
             }), // #54
             Instruction::Control(Control::Break {
                 option_block_index: Some(5),
-                relative_depth: 2,
-                address: 77,
+                branch_target: BranchTarget {
+                    relative_depth: 2,
+                    stack_height: 2,
+                    arity: 0,
+                    address: 78,
+                },
             }), // #55
             Instruction::Control(Control::Break {
                 option_block_index: Some(5),
-                relative_depth: 3,
-                address: 80,
+                branch_target: BranchTarget {
+                    relative_depth: 3,
+                    stack_height: 2,
+                    arity: 0,
+                    address: 81,
+                },
             }), // #56
             Instruction::Control(Control::Break {
                 option_block_index: Some(5),
-                relative_depth: 4,
-                address: 85,
+                branch_target: BranchTarget {
+                    relative_depth: 4,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 85,
+                },
             }), // #57 - jump to function end
             Instruction::Control(Control::Break {
                 option_block_index: Some(5),
-                relative_depth: 4,
-                address: 85,
+                branch_target: BranchTarget {
+                    relative_depth: 4,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 85,
+                },
             }), // #58
             Instruction::Control(Control::End(Some(5))),                  // #59 - block 5 end
-            Instruction::Control(Control::JumpWithinBlock(73)),                      // #60 - else
+            Instruction::Control(Control::JumpWithinBlock(74)),                      // #60 - else
             Instruction::Control(Control::Break {
                 option_block_index: Some(4),
-                relative_depth: 0,
-                address: 73,
+                branch_target: BranchTarget {
+                    relative_depth: 0,
+                    stack_height: 1,
+                    arity: 0,
+                    address: 74,
+                },
             }), // #61
             Instruction::Control(Control::Break {
                 option_block_index: Some(4),
-                relative_depth: 1,
-                address: 77,
+                branch_target: BranchTarget {
+                    relative_depth: 1,
+                    stack_height: 2,
+                    arity: 0,
+                    address: 78,
+                },
             }), // #62
             Instruction::Control(Control::Break {
                 option_block_index: Some(4),
-                relative_depth: 2,
-                address: 80,
+                branch_target: BranchTarget {
+                    relative_depth: 2,
+                    stack_height: 2,
+                    arity: 0,
+                    address: 81,
+                },
             }), // #63
             Instruction::Control(Control::Break {
                 option_block_index: Some(4),
-                relative_depth: 3,
-                address: 85,
+                branch_target: BranchTarget {
+                    relative_depth: 3,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 85,
+                },
             }), // #64
             Instruction::Control(Control::Block {
                 block_type: BlockType::ResultEmpty,
@@ -1113,74 +1597,126 @@ mod tests {
             }), // #65 - block 6
             Instruction::Control(Control::Break {
                 option_block_index: Some(6),
-                relative_depth: 0,
-                address: 72,
+                branch_target: BranchTarget {
+                    relative_depth: 0,
+                    stack_height: 1,
+                    arity: 0,
+                    address: 73,
+                },
             }), // #66
             Instruction::Control(Control::Break {
                 option_block_index: Some(6),
-                relative_depth: 1,
-                address: 73,
+                branch_target: BranchTarget {
+                    relative_depth: 1,
+                    stack_height: 1,
+                    arity: 0,
+                    address: 74,
+                },
             }), // #67
             Instruction::Control(Control::Break {
                 option_block_index: Some(6),
-                relative_depth: 2,
-                address: 77,
+                branch_target: BranchTarget {
+                    relative_depth: 2,
+                    stack_height: 2,
+                    arity: 0,
+                    address: 78,
+                },
             }), // #68
             Instruction::Control(Control::Break {
                 option_block_index: Some(6),
-                relative_depth: 3,
-                address: 80,
+                branch_target: BranchTarget {
+                    relative_depth: 3,
+                    stack_height: 2,
+                    arity: 0,
+                    address: 81,
+                },
             }), // #69
             Instruction::Control(Control::Break {
                 option_block_index: Some(6),
-                relative_depth: 4,
-                address: 85,
+                branch_target: BranchTarget {
+                    relative_depth: 4,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 85,
+                },
             }), // #70 - jump to function end
             Instruction::Control(Control::Break {
                 option_block_index: Some(6),
-                relative_depth: 4,
-                address: 85,
+                branch_target: BranchTarget {
+                    relative_depth: 4,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 85,
+                },
             }), // #71
             Instruction::Control(Control::End(Some(6))),                  // #72 - block 6 end
             Instruction::Control(Control::End(Some(4))),                  // #73 // block 4 end
             Instruction::Control(Control::Break {
                 option_block_index: Some(3),
-                relative_depth: 0,
-                address: 77,
+                branch_target: BranchTarget {
+                    relative_depth: 0,
+                    stack_height: 2,
+                    arity: 0,
+                    address: 78,
+                },
             }), // #74
             Instruction::Control(Control::Break {
                 option_block_index: Some(3),
-                relative_depth: 1,
-                address: 80,
+                branch_target: BranchTarget {
+                    relative_depth: 1,
+                    stack_height: 2,
+                    arity: 0,
+                    address: 81,
+                },
             }), // #75
             Instruction::Control(Control::Break {
                 option_block_index: Some(3),
-                relative_depth: 2,
-                address: 85,
+                branch_target: BranchTarget {
+                    relative_depth: 2,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 85,
+                },
             }), // #76
             Instruction::Control(Control::End(Some(3))),                  // #77 // block 3 end
             Instruction::Control(Control::Break {
                 option_block_index: Some(0),
-                relative_depth: 0,
-                address: 80,
+                branch_target: BranchTarget {
+                    relative_depth: 0,
+                    stack_height: 2,
+                    arity: 0,
+                    address: 81,
+                },
             }), // #78
             Instruction::Control(Control::Break {
                 option_block_index: Some(0),
-                relative_depth: 1,
-                address: 85,
+                branch_target: BranchTarget {
+                    relative_depth: 1,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 85,
+                },
             }), // #79
             Instruction::Control(Control::End(Some(0))),                  // #80 // block 0 end
             Instruction::Sequence(instruction::Instruction::I32Const(0)), // #81
             Instruction::Sequence(instruction::Instruction::I32Const(1)), // #82
             Instruction::Control(Control::Break {
                 option_block_index: None,
-                relative_depth: 0,
-                address: 85,
+                branch_target: BranchTarget {
+                    relative_depth: 0,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 85,
+                },
             }), // #83
             Instruction::Control(Control::Break {
                 option_block_index: None,
-                relative_depth: 0,
-                address: 85,
+                branch_target: BranchTarget {
+                    relative_depth: 0,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 85,
+                },
             }), // #84
             Instruction::Control(Control::End(None)),                     // #85
             // function 4
@@ -1194,18 +1730,30 @@ mod tests {
             }), // #88 - block 0
             Instruction::Control(Control::Break {
                 option_block_index: Some(0),
-                relative_depth: 0,
-                address: 101,
+                branch_target: BranchTarget {
+                    relative_depth: 0,
+                    stack_height: 1,
+                    arity: 0,
+                    address: 102,
+                },
             }), // #89
             Instruction::Control(Control::Break {
                 option_block_index: Some(0),
-                relative_depth: 1,
-                address: 104,
+                branch_target: BranchTarget {
+                    relative_depth: 1,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 104,
+                },
             }), // #90
             Instruction::Control(Control::Break {
                 option_block_index: Some(0),
-                relative_depth: 1,
-                address: 104,
+                branch_target: BranchTarget {
+                    relative_depth: 1,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 104,
+                },
             }), // #91
             Instruction::Control(Control::Block {
                 block_type: BlockType::ResultEmpty,
@@ -1214,39 +1762,67 @@ mod tests {
             }), // #92 - block 1
             Instruction::Control(Control::Break {
                 option_block_index: Some(1),
-                relative_depth: 0,
-                address: 97,
+                branch_target: BranchTarget {
+                    relative_depth: 0,
+                    stack_height: 1,
+                    arity: 0,
+                    address: 98,
+                },
             }), // #93
             Instruction::Control(Control::Break {
                 option_block_index: Some(1),
-                relative_depth: 1,
-                address: 101,
+                branch_target: BranchTarget {
+                    relative_depth: 1,
+                    stack_height: 1,
+                    arity: 0,
+                    address: 102,
+                },
             }), // #94
             Instruction::Control(Control::Break {
                 option_block_index: Some(1),
-                relative_depth: 2,
-                address: 104,
+                branch_target: BranchTarget {
+                    relative_depth: 2,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 104,
+                },
             }), // #95
             Instruction::Control(Control::Break {
                 option_block_index: Some(1),
-                relative_depth: 2,
-                address: 104,
+                branch_target: BranchTarget {
+                    relative_depth: 2,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 104,
+                },
             }), // #96
             Instruction::Control(Control::End(Some(1))),                  // #97 - block 1 end
             Instruction::Control(Control::Break {
                 option_block_index: Some(0),
-                relative_depth: 0,
-                address: 101,
+                branch_target: BranchTarget {
+                    relative_depth: 0,
+                    stack_height: 1,
+                    arity: 0,
+                    address: 102,
+                },
             }), // #98
             Instruction::Control(Control::Break {
                 option_block_index: Some(0),
-                relative_depth: 1,
-                address: 104,
+                branch_target: BranchTarget {
+                    relative_depth: 1,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 104,
+                },
             }), // #99
             Instruction::Control(Control::Break {
                 option_block_index: Some(0),
-                relative_depth: 1,
-                address: 104,
+                branch_target: BranchTarget {
+                    relative_depth: 1,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 104,
+                },
             }), // #100
             Instruction::Control(Control::End(Some(0))),                  // #101 - block 0 end
             Instruction::Sequence(instruction::Instruction::I32Const(2)), // #102
@@ -1321,23 +1897,39 @@ mod tests {
             Instruction::Sequence(instruction::Instruction::I32Const(3)), // #06
             Instruction::Control(Control::BreakWhenNotEqZero {
                 option_block_index: Some(2),
-                relative_depth: 0,
-                address: 11,
+                branch_target: BranchTarget {
+                    relative_depth: 0,
+                    stack_height: 3,
+                    arity: 0,
+                    address: 12,
+                },
             }), // #07 jump to `block 2 end`
-            Instruction::Control(Control::RecurWhenNotEqZero {
-                block_index: 2,
-                relative_depth: 1,
-                address: 3,
+            Instruction::Control(Control::BreakWhenNotEqZero {
+                option_block_index: Some(2),
+                branch_target: BranchTarget {
+                    relative_depth: 1,
+                    stack_height: 2,
+                    arity: 0,
+                    address: 4,
+                },
             }), // #08 jump to `block 1 loop`
             Instruction::Control(Control::BreakWhenNotEqZero {
                 option_block_index: Some(2),
-                relative_depth: 2,
-                address: 13,
+                branch_target: BranchTarget {
+                    relative_depth: 2,
+                    stack_height: 1,
+                    arity: 0,
+                    address: 14,
+                },
             }), // #09 jump to `block 0 end`
             Instruction::Control(Control::BreakWhenNotEqZero {
                 option_block_index: Some(2),
-                relative_depth: 3,
-                address: 14,
+                branch_target: BranchTarget {
+                    relative_depth: 3,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 14,
+                },
             }), // #10 jump to `function end`
             Instruction::Control(Control::End(Some(2))),                  // #11 - block 2 end
             Instruction::Control(Control::End(Some(1))),                  // #12 - block 1 end
@@ -1412,11 +2004,31 @@ mod tests {
             Instruction::Control(Control::Branch {
                 option_block_index: Some(2),
                 branch_targets: vec![
-                    BranchTarget::Break(2, 10),
-                    BranchTarget::Break(0, 8),
-                    BranchTarget::Recur(1, 3),
+                    BranchTarget {
+                        relative_depth: 2,
+                        stack_height: 1,
+                        arity: 0,
+                        address: 11,
+                    },
+                    BranchTarget {
+                        relative_depth: 0,
+                        stack_height: 3,
+                        arity: 0,
+                        address: 9,
+                    },
+                    BranchTarget {
+                        relative_depth: 1,
+                        stack_height: 2,
+                        arity: 0,
+                        address: 4,
+                    },
                 ],
-                default_branch_target: BranchTarget::Break(3, 11),
+                default_branch_target: BranchTarget {
+                    relative_depth: 3,
+                    stack_height: 0,
+                    arity: 1,
+                    address: 11,
+                },
             }), // #07
             Instruction::Control(Control::End(Some(2))), // #08 - block 2 end
             Instruction::Control(Control::End(Some(1))), // #09 - block 1 end
@@ -2021,4 +2633,67 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_fuse_instructions() {
+        let branch_target = BranchTarget {
+            relative_depth: 0,
+            stack_height: 0,
+            arity: 0,
+            address: 12,
+        };
+
+        let instructions = vec![
+            Instruction::Sequence(instruction::Instruction::LocalGet(0)), // #00
+            Instruction::Sequence(instruction::Instruction::LocalGet(1)), // #01
+            Instruction::Sequence(instruction::Instruction::LocalGet(2)), // #02
+            Instruction::Sequence(instruction::Instruction::I32Add),      // #03
+            Instruction::Sequence(instruction::Instruction::LocalGet(0)), // #04
+            Instruction::Sequence(instruction::Instruction::I32Const(1)), // #05
+            Instruction::Sequence(instruction::Instruction::I32Add),      // #06
+            Instruction::Sequence(instruction::Instruction::LocalSet(3)), // #07
+            Instruction::Sequence(instruction::Instruction::I32Const(10)), // #08
+            Instruction::Sequence(instruction::Instruction::I32LtS),      // #09
+            Instruction::Control(Control::BreakWhenNotEqZero {
+                option_block_index: Some(0),
+                branch_target: branch_target.clone(),
+            }), // #10
+            Instruction::Sequence(instruction::Instruction::I32Const(10)), // #11
+            Instruction::Sequence(instruction::Instruction::I32LtU),      // #12
+            Instruction::Control(Control::End(None)),                     // #13
+        ];
+
+        assert_eq!(
+            fuse_instructions(&instructions),
+            vec![
+                // #00 不能跟 #01 融合，#01 开始的 3 条指令被融合
+                FusedItem {
+                    address: 1,
+                    length: 3,
+                    fused_instruction: FusedInstruction::LocalGetLocalGetI32Add {
+                        left_index: 1,
+                        right_index: 2
+                    }
+                },
+                FusedItem {
+                    address: 4,
+                    length: 4,
+                    fused_instruction: FusedInstruction::LocalGetI32ConstI32AddLocalSet {
+                        source_index: 0,
+                        value: 1,
+                        dest_index: 3
+                    }
+                },
+                FusedItem {
+                    address: 8,
+                    length: 3,
+                    fused_instruction: FusedInstruction::I32ConstI32LtSBreakWhenNotEqZero {
+                        value: 10,
+                        option_block_index: Some(0),
+                        branch_target
+                    }
+                },
+            ]
+        );
+    }
 }
@@ -0,0 +1,857 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 动态链接
+//!
+//! 按照 WebAssembly 工具约定（tool-conventions）的动态链接规范，加载一个主模块
+//! 以及它所依赖的共享库。
+//!
+//! 共享库（以及位置无关的主模块）携带 `dylink.0` 自定义段，说明了模块所需的内存空间
+//! 和表空间。共享库并不定义自己的内存和表，而是导入 `env` 模块的共享内存和共享表，
+//! 然后通过导入的 `env.__memory_base` 和 `env.__table_base` 两个全局变量得知
+//! 分配给自己的空间的起始位置。
+//!
+//! 模块之间的数据符号和函数符号的地址通过 `GOT.mem` 和 `GOT.func` 两个模块的
+//! 可变全局变量获得（GOT 即 Global Offset Table）。
+//!
+//! 加载器会合成 `env`、`GOT.mem` 和 `GOT.func` 三个 AST 模块，并改写各个模块的导入项，
+//! 使之能够由 `create_instance` 函数直接链接。
+//!
+//! https://github.com/WebAssembly/tool-conventions/blob/main/DynamicLinking.md
+
+use anvm_ast::{
+    ast::{
+        self, CustomItem, DynamicLinking, ElementItem, ElementItems, ElementMode, ExportDescriptor,
+        ExportItem, FunctionType, GlobalItem, GlobalType, ImportDescriptor, ImportItem, Limit,
+        MemoryType, TableType, TypeItem,
+    },
+    instruction::Instruction,
+    types::{RefType, ValueType},
+};
+
+use crate::{
+    error::{EngineError, ObjectNotFound, Overflow, Unsupported},
+    instance::find_ast_module_export_function,
+    native_module::NativeModule,
+    object::NamedAstModule,
+    vm::VM,
+};
+
+pub const ENV_MODULE_NAME: &str = "env";
+pub const GOT_MEM_MODULE_NAME: &str = "GOT.mem";
+pub const GOT_FUNC_MODULE_NAME: &str = "GOT.func";
+
+/// 合成的模块（即 `env`、`GOT.mem` 和 `GOT.func`）的数量，
+/// 它们排在链接结果的最前面。
+pub const SYNTHESIZED_MODULE_COUNT: usize = 3;
+
+const MEMORY_NAME: &str = "memory";
+const TABLE_NAME: &str = "__indirect_function_table";
+const MEMORY_BASE_NAME: &str = "__memory_base";
+const TABLE_BASE_NAME: &str = "__table_base";
+const STACK_POINTER_NAME: &str = "__stack_pointer";
+const HEAP_BASE_NAME: &str = "__heap_base";
+
+const APPLY_DATA_RELOCS_FUNCTION_NAME: &str = "__wasm_apply_data_relocs";
+const CALL_CTORS_FUNCTION_NAME: &str = "__wasm_call_ctors";
+
+/// 内存的前 1024 个字节保留不用，以便空指针（0）及其附近的地址
+/// 不会指向有效的数据。
+const GLOBAL_BASE: u32 = 1024;
+
+/// 栈的大小，栈位于所有模块的数据之后，从高地址向低地址增长
+const STACK_SIZE: u32 = 64 * 1024;
+const STACK_ALIGNMENT: u32 = 16;
+
+const PAGE_SIZE: u32 = 64 * 1024;
+
+/// 每个模块被分配到的内存空间和表空间的起始位置
+struct ModuleLayout {
+    memory_base: u32,
+    table_base: u32,
+}
+
+/// 链接主模块及其共享库
+///
+/// 返回的 AST 模块列表的顺序为：
+/// `[env, GOT.mem, GOT.func, 共享库..., 主模块]`，
+/// 可以直接传给 `create_instance` 函数创建 VM 实例，
+/// 然后调用 `initialize_shared_libraries` 函数执行各模块的初始化函数。
+///
+/// 所有模块都必须携带 `dylink.0` 自定义段，共享库的模块名称即共享库的文件名称，
+/// 比如 `libm.so`，它需跟其他模块的 `dylink.0` 段所列出的依赖的名称一致。
+///
+/// 导入的函数符号（即 `env` 模块的导入函数）按照主模块、共享库（按参数的顺序）、
+/// 本地模块的顺序查找。
+pub fn link_shared_libraries(
+    native_modules: &[NativeModule],
+    main_module: &NamedAstModule,
+    shared_libraries: &[NamedAstModule],
+) -> Result<Vec<NamedAstModule>, EngineError> {
+    // 符号的查找顺序，同时也是分配内存空间和表空间的顺序
+    let mut named_ast_modules: Vec<&NamedAstModule> = vec![main_module];
+    named_ast_modules.extend(shared_libraries.iter());
+
+    let dynamic_linkings = named_ast_modules
+        .iter()
+        .map(|named_ast_module| get_dynamic_linking(named_ast_module))
+        .collect::<Result<Vec<&DynamicLinking>, EngineError>>()?;
+
+    // 检查依赖的共享库是否都已提供
+    for dynamic_linking in &dynamic_linkings {
+        for needed in &dynamic_linking.needed {
+            if !shared_libraries.iter().any(|item| &item.name == needed) {
+                return Err(EngineError::ObjectNotFound(ObjectNotFound::ModuleNotFound(
+                    needed.to_owned(),
+                )));
+            }
+        }
+    }
+
+    // 分配内存空间和表空间
+    //
+    // 表的第 0 个槽保留为空，以便空函数指针（0）不会指向有效的函数。
+    let mut memory_end = GLOBAL_BASE;
+    let mut table_end: u32 = 1;
+    let mut module_layouts: Vec<ModuleLayout> = vec![];
+
+    // `dylink.0` 段的内容来自模块，对齐值以及空间大小都有可能超出地址范围
+    for (named_ast_module, dynamic_linking) in named_ast_modules.iter().zip(&dynamic_linkings) {
+        let (memory_base, table_base) = match &dynamic_linking.memory_info {
+            Some(memory_info) => {
+                let make_overflow_error = || {
+                    EngineError::Overflow(Overflow::DynamicLinkingLayoutOverflow(
+                        named_ast_module.name.clone(),
+                    ))
+                };
+
+                let memory_base = 1u32
+                    .checked_shl(memory_info.memory_alignment)
+                    .and_then(|alignment| align_up(memory_end, alignment))
+                    .ok_or_else(make_overflow_error)?;
+                let table_base = 1u32
+                    .checked_shl(memory_info.table_alignment)
+                    .and_then(|alignment| align_up(table_end, alignment))
+                    .ok_or_else(make_overflow_error)?;
+                memory_end = memory_base
+                    .checked_add(memory_info.memory_size)
+                    .ok_or_else(make_overflow_error)?;
+                table_end = table_base
+                    .checked_add(memory_info.table_size)
+                    .ok_or_else(make_overflow_error)?;
+                (memory_base, table_base)
+            }
+            None => (memory_end, table_end),
+        };
+
+        module_layouts.push(ModuleLayout {
+            memory_base,
+            table_base,
+        });
+    }
+
+    let stack_pointer = align_up(memory_end, STACK_ALIGNMENT)
+        .and_then(|stack_base| stack_base.checked_add(STACK_SIZE))
+        .ok_or(EngineError::Overflow(
+            Overflow::DynamicLinkingLayoutOverflow(main_module.name.clone()),
+        ))?;
+    let page_count = stack_pointer.div_ceil(PAGE_SIZE);
+
+    // 收集 GOT 符号
+    let mut got_mem_symbols: Vec<String> = vec![];
+    let mut got_func_symbols: Vec<String> = vec![];
+
+    for named_ast_module in &named_ast_modules {
+        for import_item in &named_ast_module.module.import_items {
+            let symbols = match import_item.module_name.as_str() {
+                GOT_MEM_MODULE_NAME => &mut got_mem_symbols,
+                GOT_FUNC_MODULE_NAME => &mut got_func_symbols,
+                _ => continue,
+            };
+
+            if !symbols.contains(&import_item.item_name) {
+                symbols.push(import_item.item_name.clone());
+            }
+        }
+    }
+
+    // GOT.func 的槽位于所有模块的表空间之后
+    let got_func_table_base = table_end;
+    table_end = u32::try_from(got_func_symbols.len())
+        .ok()
+        .and_then(|symbol_count| table_end.checked_add(symbol_count))
+        .ok_or(EngineError::Overflow(
+            Overflow::DynamicLinkingLayoutOverflow(main_module.name.clone()),
+        ))?;
+
+    let memory_type = MemoryType {
+        limit: Limit::AtLeast(page_count as u64),
+        shared: false,
+        memory64: false,
+    };
+
+    let table_type = TableType {
+        element_type: RefType::FuncRef,
+        limit: Limit::AtLeast(table_end as u64),
+    };
+
+    let env_module = create_env_module(
+        &named_ast_modules,
+        &module_layouts,
+        &memory_type,
+        &table_type,
+        stack_pointer,
+    );
+
+    let got_mem_module = create_got_mem_module(
+        &named_ast_modules,
+        &module_layouts,
+        stack_pointer,
+        &got_mem_symbols,
+    )?;
+
+    let got_func_module = create_got_func_module(
+        native_modules,
+        &named_ast_modules,
+        &table_type,
+        got_func_table_base,
+        &got_func_symbols,
+    )?;
+
+    let mut linked_modules = vec![env_module, got_mem_module, got_func_module];
+
+    for named_ast_module in shared_libraries.iter().chain([main_module]) {
+        let module = relocate_imports(
+            native_modules,
+            &named_ast_modules,
+            named_ast_module,
+            &memory_type,
+            &table_type,
+        )?;
+        linked_modules.push(NamedAstModule::new(&named_ast_module.name, module));
+    }
+
+    Ok(linked_modules)
+}
+
+/// 执行各个模块的初始化函数
+///
+/// 先共享库（按链接时的顺序）后主模块，依次调用各个模块导出的
+/// `__wasm_apply_data_relocs` 函数（用于重定位数据段里的指针）以及
+/// `__wasm_call_ctors` 函数（用于执行构造函数）。
+///
+/// 参数 named_ast_modules 是 `link_shared_libraries` 函数的返回结果。
+pub fn initialize_shared_libraries(
+    vm: &mut VM,
+    named_ast_modules: &[NamedAstModule],
+) -> Result<(), EngineError> {
+    for (vm_module_index, named_ast_module) in named_ast_modules
+        .iter()
+        .enumerate()
+        .skip(SYNTHESIZED_MODULE_COUNT)
+    {
+        for function_name in [APPLY_DATA_RELOCS_FUNCTION_NAME, CALL_CTORS_FUNCTION_NAME] {
+            if let Some(function_index) =
+                find_ast_module_export_function(&named_ast_module.module, function_name)
+            {
+                vm.eval_function_by_index(vm_module_index, function_index as usize, &[])?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn get_dynamic_linking(named_ast_module: &NamedAstModule) -> Result<&DynamicLinking, EngineError> {
+    named_ast_module
+        .module
+        .custom_items
+        .iter()
+        .find_map(|item| match item {
+            CustomItem::DynamicLinking(dynamic_linking) => Some(dynamic_linking),
+            _ => None,
+        })
+        .ok_or(EngineError::ObjectNotFound(
+            ObjectNotFound::DynamicLinkingSectionNotFound(named_ast_module.name.clone()),
+        ))
+}
+
+/// 向上对齐，结果超出 u32 的范围时返回 None
+fn align_up(value: u32, alignment: u32) -> Option<u32> {
+    value.div_ceil(alignment).checked_mul(alignment)
+}
+
+/// 模块专属的 `__memory_base` 和 `__table_base` 全局变量在 `env` 模块里的导出名称
+fn get_module_base_export_name(module_name: &str, base_name: &str) -> String {
+    format!("{}:{}", module_name, base_name)
+}
+
+fn create_empty_module() -> ast::Module {
+    ast::Module {
+        custom_items: vec![],
+        type_items: vec![],
+        import_items: vec![],
+        internal_function_to_type_index_list: vec![],
+        tables: vec![],
+        memory_blocks: vec![],
+        tag_items: vec![],
+        global_items: vec![],
+        export_items: vec![],
+        start_function_index: None,
+        element_items: vec![],
+        code_items: vec![],
+        data_items: vec![],
+        data_count: None,
+    }
+}
+
+/// 添加一个以 i32 常量为初始值的全局变量，并导出
+fn add_i32_global(module: &mut ast::Module, name: &str, mutable: bool, value: u32) {
+    let global_index = module.global_items.len() as u32;
+
+    module.global_items.push(GlobalItem {
+        global_type: GlobalType {
+            value_type: ValueType::I32,
+            mutable,
+        },
+        initialize_instruction_items: vec![Instruction::I32Const(value as i32), Instruction::End],
+    });
+
+    module.export_items.push(ExportItem {
+        name: name.to_owned(),
+        export_descriptor: ExportDescriptor::GlobalItemIndex(global_index),
+    });
+}
+
+/// 创建 `env` 模块
+///
+/// 该模块定义共享内存、共享表、栈指针，以及每个模块专属的 `__memory_base` 和
+/// `__table_base` 全局变量。
+fn create_env_module(
+    named_ast_modules: &[&NamedAstModule],
+    module_layouts: &[ModuleLayout],
+    memory_type: &MemoryType,
+    table_type: &TableType,
+    stack_pointer: u32,
+) -> NamedAstModule {
+    let mut module = create_empty_module();
+
+    module.memory_blocks.push(memory_type.clone());
+    module.export_items.push(ExportItem {
+        name: MEMORY_NAME.to_owned(),
+        export_descriptor: ExportDescriptor::MemoryBlockIndex(0),
+    });
+
+    module.tables.push(table_type.clone());
+    module.export_items.push(ExportItem {
+        name: TABLE_NAME.to_owned(),
+        export_descriptor: ExportDescriptor::TableIndex(0),
+    });
+
+    add_i32_global(&mut module, STACK_POINTER_NAME, true, stack_pointer);
+    add_i32_global(&mut module, HEAP_BASE_NAME, false, stack_pointer);
+
+    for (named_ast_module, module_layout) in named_ast_modules.iter().zip(module_layouts) {
+        add_i32_global(
+            &mut module,
+            &get_module_base_export_name(&named_ast_module.name, MEMORY_BASE_NAME),
+            false,
+            module_layout.memory_base,
+        );
+        add_i32_global(
+            &mut module,
+            &get_module_base_export_name(&named_ast_module.name, TABLE_BASE_NAME),
+            false,
+            module_layout.table_base,
+        );
+    }
+
+    NamedAstModule::new(ENV_MODULE_NAME, module)
+}
+
+/// 创建 `GOT.mem` 模块
+///
+/// 数据符号是模块导出的（不可变）全局变量，其值是数据相对于该模块 `__memory_base` 的偏移值，
+/// GOT.mem 的全局变量的值则是数据的绝对地址。
+fn create_got_mem_module(
+    named_ast_modules: &[&NamedAstModule],
+    module_layouts: &[ModuleLayout],
+    heap_base: u32,
+    got_mem_symbols: &[String],
+) -> Result<NamedAstModule, EngineError> {
+    let mut module = create_empty_module();
+
+    for symbol in got_mem_symbols {
+        // `__heap_base` 是链接器合成的符号，它没有定义在任何模块里
+        let option_address = if symbol == HEAP_BASE_NAME {
+            Some(heap_base)
+        } else {
+            find_data_symbol_address(named_ast_modules, module_layouts, symbol)?
+        };
+
+        let address = option_address.ok_or(EngineError::ObjectNotFound(
+            ObjectNotFound::SymbolNotFound(GOT_MEM_MODULE_NAME.to_owned(), symbol.to_owned()),
+        ))?;
+
+        add_i32_global(&mut module, symbol, true, address);
+    }
+
+    Ok(NamedAstModule::new(GOT_MEM_MODULE_NAME, module))
+}
+
+fn find_data_symbol_address(
+    named_ast_modules: &[&NamedAstModule],
+    module_layouts: &[ModuleLayout],
+    symbol: &str,
+) -> Result<Option<u32>, EngineError> {
+    for (named_ast_module, module_layout) in named_ast_modules.iter().zip(module_layouts) {
+        let ast_module = &named_ast_module.module;
+
+        let option_global_index =
+            ast_module
+                .export_items
+                .iter()
+                .find_map(|item| match item.export_descriptor {
+                    ExportDescriptor::GlobalItemIndex(index) if item.name == symbol => Some(index),
+                    _ => None,
+                });
+
+        let global_index = match option_global_index {
+            Some(index) => index as usize,
+            None => continue,
+        };
+
+        let import_global_count = ast_module
+            .import_items
+            .iter()
+            .filter(|item| matches!(item.import_descriptor, ImportDescriptor::GlobalType(_)))
+            .count();
+
+        // 导出的数据符号应该是模块内定义的、以 i32 常量为初始值的全局变量
+        let global_item = global_index
+            .checked_sub(import_global_count)
+            .and_then(|index| ast_module.global_items.get(index));
+
+        let offset = match global_item.map(|item| item.initialize_instruction_items.as_slice()) {
+            Some([Instruction::I32Const(offset), ..]) => *offset as u32,
+            Some([instruction, ..]) => {
+                return Err(EngineError::Unsupported(
+                    Unsupported::UnsupportedConstantExpressionInstruction(instruction.clone()),
+                ));
+            }
+            _ => continue,
+        };
+
+        return module_layout
+            .memory_base
+            .checked_add(offset)
+            .map(Some)
+            .ok_or(EngineError::Overflow(
+                Overflow::DynamicLinkingLayoutOverflow(named_ast_module.name.clone()),
+            ));
+    }
+
+    Ok(None)
+}
+
+/// 创建 `GOT.func` 模块
+///
+/// 该模块导入所有被引用的函数，并通过一个主动元素段把它们放置在共享表的末尾，
+/// GOT.func 的全局变量的值即函数在表中的位置（即函数指针）。
+fn create_got_func_module(
+    native_modules: &[NativeModule],
+    named_ast_modules: &[&NamedAstModule],
+    table_type: &TableType,
+    got_func_table_base: u32,
+    got_func_symbols: &[String],
+) -> Result<NamedAstModule, EngineError> {
+    let mut module = create_empty_module();
+
+    module.import_items.push(ImportItem {
+        module_name: ENV_MODULE_NAME.to_owned(),
+        item_name: TABLE_NAME.to_owned(),
+        import_descriptor: ImportDescriptor::TableType(table_type.clone()),
+    });
+
+    for (function_index, symbol) in got_func_symbols.iter().enumerate() {
+        let (module_name, function_type) =
+            find_function_symbol(native_modules, named_ast_modules, symbol).ok_or(
+                EngineError::ObjectNotFound(ObjectNotFound::SymbolNotFound(
+                    GOT_FUNC_MODULE_NAME.to_owned(),
+                    symbol.to_owned(),
+                )),
+            )?;
+
+        let type_index = module.type_items.len() as u32;
+        module
+            .type_items
+            .push(TypeItem::FunctionType(function_type));
+
+        module.import_items.push(ImportItem {
+            module_name,
+            item_name: symbol.to_owned(),
+            import_descriptor: ImportDescriptor::FunctionTypeIndex(type_index),
+        });
+
+        add_i32_global(
+            &mut module,
+            symbol,
+            true,
+            got_func_table_base + function_index as u32,
+        );
+    }
+
+    if !got_func_symbols.is_empty() {
+        module.element_items.push(ElementItem {
+            mode: ElementMode::Active {
+                table_index: 0,
+                offset_instruction_items: vec![
+                    Instruction::I32Const(got_func_table_base as i32),
+                    Instruction::End,
+                ],
+            },
+            element_type: RefType::FuncRef,
+            items: ElementItems::FunctionIndices((0..got_func_symbols.len() as u32).collect()),
+        });
+    }
+
+    Ok(NamedAstModule::new(GOT_FUNC_MODULE_NAME, module))
+}
+
+/// 查找函数符号，返回定义（导出）该函数的模块的名称以及函数的类型
+fn find_function_symbol(
+    native_modules: &[NativeModule],
+    named_ast_modules: &[&NamedAstModule],
+    symbol: &str,
+) -> Option<(String, FunctionType)> {
+    for named_ast_module in named_ast_modules {
+        let ast_module = &named_ast_module.module;
+
+        if let Some(function_index) = find_ast_module_export_function(ast_module, symbol) {
+            let import_function_type_indexes = ast_module
+                .import_items
+                .iter()
+                .filter_map(|item| match item.import_descriptor {
+                    ImportDescriptor::FunctionTypeIndex(type_index) => Some(type_index),
+                    _ => None,
+                })
+                .collect::<Vec<u32>>();
+
+            let function_index = function_index as usize;
+            let type_index = if function_index < import_function_type_indexes.len() {
+                import_function_type_indexes[function_index]
+            } else {
+                ast_module.internal_function_to_type_index_list
+                    [function_index - import_function_type_indexes.len()]
+            };
+
+            let TypeItem::FunctionType(function_type) = &ast_module.type_items[type_index as usize];
+            return Some((named_ast_module.name.clone(), function_type.clone()));
+        }
+    }
+
+    native_modules.iter().find_map(|native_module| {
+        native_module
+            .find_function_index_by_exported_name(symbol)
+            .map(|function_index| {
+                let type_index = native_module.function_to_type_index_list[function_index];
+                (
+                    native_module.name.clone(),
+                    native_module.function_types[type_index].clone(),
+                )
+            })
+    })
+}
+
+/// 改写模块的导入项
+///
+/// - 导入的内存和表的类型改为共享内存和共享表的实际类型；
+/// - `env.__memory_base` 和 `env.__table_base` 改为导入该模块专属的全局变量；
+/// - `env` 模块的导入函数改为从定义该函数的模块导入。
+fn relocate_imports(
+    native_modules: &[NativeModule],
+    named_ast_modules: &[&NamedAstModule],
+    named_ast_module: &NamedAstModule,
+    memory_type: &MemoryType,
+    table_type: &TableType,
+) -> Result<ast::Module, EngineError> {
+    let mut module = named_ast_module.module.clone();
+
+    for import_item in &mut module.import_items {
+        if import_item.module_name != ENV_MODULE_NAME {
+            continue;
+        }
+
+        match &mut import_item.import_descriptor {
+            ImportDescriptor::MemoryType(import_memory_type) => {
+                *import_memory_type = memory_type.clone();
+            }
+            ImportDescriptor::TableType(import_table_type) => {
+                *import_table_type = table_type.clone();
+            }
+            ImportDescriptor::GlobalType(_)
+                if import_item.item_name == MEMORY_BASE_NAME
+                    || import_item.item_name == TABLE_BASE_NAME =>
+            {
+                import_item.item_name =
+                    get_module_base_export_name(&named_ast_module.name, &import_item.item_name);
+            }
+            ImportDescriptor::FunctionTypeIndex(_) => {
+                let (module_name, _) =
+                    find_function_symbol(native_modules, named_ast_modules, &import_item.item_name)
+                        .ok_or(EngineError::ObjectNotFound(ObjectNotFound::SymbolNotFound(
+                            named_ast_module.name.clone(),
+                            import_item.item_name.clone(),
+                        )))?;
+                import_item.module_name = module_name;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(module)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use anvm_ast::{
+        ast::{self, CustomItem, DynamicLinkingMemoryInfo},
+        types::{Value, ValueType},
+    };
+    use anvm_binary_parser::parser;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        error::{EngineError, NativeTerminate, ObjectNotFound, Overflow},
+        instance::{create_instance, find_ast_module_export_function},
+        native_module::{EmptyModuleContext, NativeModule},
+        object::NamedAstModule,
+        vm::VM,
+    };
+
+    use super::{initialize_shared_libraries, link_shared_libraries};
+
+    // 辅助方法
+    fn get_test_binary_resource(filename: &str) -> Vec<u8> {
+        let mut path_buf = env::current_dir().unwrap();
+        if !path_buf.ends_with("engine") {
+            path_buf.push("crates");
+            path_buf.push("engine");
+        }
+        let fullname_buf = path_buf.join("resources").join(filename);
+        let fullname = fullname_buf.to_str().unwrap();
+        fs::read(fullname)
+            .unwrap_or_else(|_| panic!("failed to read the specified binary file: {}", fullname))
+    }
+
+    fn get_test_ast_module(filename: &str) -> ast::Module {
+        let bytes = get_test_binary_resource(filename);
+        parser::parse(&bytes).unwrap()
+    }
+
+    fn native_function_triple(
+        _vm: &mut VM,
+        _native_module_index: usize,
+        params: &[Value],
+    ) -> Result<Vec<Value>, NativeTerminate> {
+        match params[0] {
+            Value::I32(value) => Ok(vec![Value::I32(value * 3)]),
+            _ => panic!("incorrect data type of the native function arguments"),
+        }
+    }
+
+    fn get_native_modules() -> Vec<NativeModule> {
+        let mut native_module = NativeModule::new("host", Box::new(EmptyModuleContext::new()));
+        native_module.add_native_function(
+            "host_triple",
+            vec![ValueType::I32],
+            vec!["value"],
+            vec![ValueType::I32],
+            native_function_triple,
+        );
+        vec![native_module]
+    }
+
+    fn eval_main_function(
+        vm: &mut VM,
+        named_ast_modules: &[NamedAstModule],
+        function_name: &str,
+        arguments: &[Value],
+    ) -> Vec<Value> {
+        let vm_module_index = named_ast_modules.len() - 1;
+        let function_index = find_ast_module_export_function(
+            &named_ast_modules[vm_module_index].module,
+            function_name,
+        )
+        .unwrap();
+        vm.eval_function_by_index(vm_module_index, function_index as usize, arguments)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_link_shared_libraries() {
+        let main_module = NamedAstModule::new("main", get_test_ast_module("test-dylink-main.wasm"));
+        let shared_library =
+            NamedAstModule::new("libcounter.so", get_test_ast_module("test-dylink-lib.wasm"));
+
+        let native_modules = get_native_modules();
+        let named_ast_modules =
+            link_shared_libraries(&native_modules, &main_module, &[shared_library]).unwrap();
+
+        let module_names = named_ast_modules
+            .iter()
+            .map(|item| item.name.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(
+            module_names,
+            vec!["env", "GOT.mem", "GOT.func", "libcounter.so", "main"]
+        );
+
+        let mut vm = create_instance(native_modules, &named_ast_modules).unwrap();
+        initialize_shared_libraries(&mut vm, &named_ast_modules).unwrap();
+
+        let mut eval = |function_name: &str, arguments: &[Value]| {
+            eval_main_function(&mut vm, &named_ast_modules, function_name, arguments)
+        };
+
+        // 主模块的数据位于 1024，共享库的数据紧随其后，栈位于所有数据之后
+        assert_eq!(eval("get_memory_base", &[]), vec![Value::I32(1024)]);
+        assert_eq!(
+            eval("get_stack_pointer", &[]),
+            vec![Value::I32(1040 + 64 * 1024)]
+        );
+        assert_eq!(eval("read_local_data", &[]), vec![Value::I32(7)]);
+
+        // 函数调用
+        assert_eq!(
+            eval("call_add", &[Value::I32(3), Value::I32(4)]),
+            vec![Value::I32(7)]
+        );
+        assert_eq!(eval("call_triple", &[Value::I32(5)]), vec![Value::I32(15)]);
+
+        // 通过 GOT.func 以及共享库返回的函数指针间接调用
+        assert_eq!(
+            eval("call_add_by_pointer", &[Value::I32(10), Value::I32(20)]),
+            vec![Value::I32(30)]
+        );
+        assert_eq!(
+            eval("call_double_by_pointer", &[Value::I32(21)]),
+            vec![Value::I32(42)]
+        );
+
+        // 通过 GOT.mem 访问共享库的数据，数据已经被重定位以及被构造函数修改
+        assert_eq!(eval("read_counter", &[]), vec![Value::I32(101)]);
+        assert_eq!(eval("read_counter_by_pointer", &[]), vec![Value::I32(101)]);
+    }
+
+    #[test]
+    fn test_link_shared_libraries_errors() {
+        let main_module = NamedAstModule::new("main", get_test_ast_module("test-dylink-main.wasm"));
+        let shared_library =
+            NamedAstModule::new("libcounter.so", get_test_ast_module("test-dylink-lib.wasm"));
+
+        // 缺少依赖的共享库
+        assert!(matches!(
+            link_shared_libraries(&get_native_modules(), &main_module, &[]),
+            Err(EngineError::ObjectNotFound(ObjectNotFound::ModuleNotFound(name)))
+                if name == "libcounter.so"
+        ));
+
+        // 缺少符号
+        assert!(matches!(
+            link_shared_libraries(&[], &main_module, std::slice::from_ref(&shared_library)),
+            Err(EngineError::ObjectNotFound(ObjectNotFound::SymbolNotFound(module_name, symbol)))
+                if module_name == "libcounter.so" && symbol == "host_triple"
+        ));
+
+        // 缺少 dylink.0 段
+        let plain_library = NamedAstModule::new(
+            "libcounter.so",
+            get_test_ast_module("test-extended-const-lib.wasm"),
+        );
+        assert!(matches!(
+            link_shared_libraries(&get_native_modules(), &main_module, &[plain_library]),
+            Err(EngineError::ObjectNotFound(ObjectNotFound::DynamicLinkingSectionNotFound(name)))
+                if name == "libcounter.so"
+        ));
+    }
+
+    #[test]
+    fn test_link_shared_libraries_layout_overflow() {
+        let main_module = NamedAstModule::new("main", get_test_ast_module("test-dylink-main.wasm"));
+
+        let link_with_memory_info = |memory_info: DynamicLinkingMemoryInfo| {
+            let mut ast_module = get_test_ast_module("test-dylink-lib.wasm");
+            for custom_item in &mut ast_module.custom_items {
+                if let CustomItem::DynamicLinking(dynamic_linking) = custom_item {
+                    dynamic_linking.memory_info = Some(memory_info.clone());
+                }
+            }
+
+            let shared_library = NamedAstModule::new("libcounter.so", ast_module);
+            link_shared_libraries(&get_native_modules(), &main_module, &[shared_library])
+        };
+
+        let is_overflow_error = |result: Result<Vec<NamedAstModule>, EngineError>| {
+            matches!(
+                result,
+                Err(EngineError::Overflow(Overflow::DynamicLinkingLayoutOverflow(name)))
+                    if name == "libcounter.so"
+            )
+        };
+
+        let memory_info = DynamicLinkingMemoryInfo {
+            memory_size: 16,
+            memory_alignment: 2,
+            table_size: 1,
+            table_alignment: 0,
+        };
+        assert!(link_with_memory_info(memory_info.clone()).is_ok());
+
+        // 对齐值超出 u32 的位数
+        assert!(is_overflow_error(link_with_memory_info(
+            DynamicLinkingMemoryInfo {
+                memory_alignment: 40,
+                ..memory_info.clone()
+            }
+        )));
+        assert!(is_overflow_error(link_with_memory_info(
+            DynamicLinkingMemoryInfo {
+                table_alignment: 32,
+                ..memory_info.clone()
+            }
+        )));
+
+        // 空间大小超出 u32 的范围
+        assert!(is_overflow_error(link_with_memory_info(
+            DynamicLinkingMemoryInfo {
+                memory_size: u32::MAX,
+                ..memory_info.clone()
+            }
+        )));
+        assert!(is_overflow_error(link_with_memory_info(
+            DynamicLinkingMemoryInfo {
+                table_size: u32::MAX,
+                ..memory_info.clone()
+            }
+        )));
+
+        // 所有模块的数据之后已经容纳不下栈
+        assert!(matches!(
+            link_with_memory_info(DynamicLinkingMemoryInfo {
+                memory_size: u32::MAX - 0x1_0000,
+                memory_alignment: 0,
+                table_size: 1,
+                table_alignment: 0,
+            }),
+            Err(EngineError::Overflow(Overflow::DynamicLinkingLayoutOverflow(name)))
+                if name == "main"
+        ));
+    }
+}
@@ -14,6 +14,10 @@ use anvm_ast::{
     types::{Value, ValueType},
 };
 
+use anvm_validator::error::ValidationError;
+
+use crate::vm_exception::VMException;
+
 pub fn make_operand_data_types_mismatch_engine_error(
     instruction_name: &str,
     expected_types: Vec<ValueType>,
@@ -43,6 +47,12 @@ pub enum EngineError {
     TypeMismatch(TypeMismatch),
     InvalidOperation(InvalidOperation),
     NativeTerminate(NativeTerminate),
+
+    /// 模块未能通过验证，引擎只执行通过验证的模块
+    InvalidModule(/* module name */ String, ValidationError),
+
+    /// 没有被任何 `try_table` 结构块捕获的异常
+    UncaughtException(VMException),
 }
 
 impl Display for EngineError {
@@ -55,6 +65,14 @@ impl Display for EngineError {
             EngineError::TypeMismatch(s) => write!(f, "{}", s),
             EngineError::InvalidOperation(s) => write!(f, "{}", s),
             EngineError::NativeTerminate(s) => write!(f, "{}", s),
+            EngineError::InvalidModule(module_name, e) => {
+                write!(f, "invalid module \"{}\": {}", module_name, e)
+            }
+            EngineError::UncaughtException(exception) => write!(
+                f,
+                "uncaught exception, tag #{}, values: {:?}",
+                exception.tag_index, exception.values
+            ),
         }
     }
 }
@@ -80,6 +98,11 @@ pub enum ObjectNotFound {
         /* module name */ String,
         /* global variable name */ String,
     ),
+    TagNotFound(/* module name */ String, /* tag name */ String),
+
+    // 以下几个异常是在动态链接共享库时触发的
+    DynamicLinkingSectionNotFound(/* module name */ String),
+    SymbolNotFound(/* module name */ String, /* symbol name */ String),
 
     // 以下几个异常是在运行程序时触发的
     ElementItemNotFound(
@@ -125,6 +148,21 @@ impl Display for ObjectNotFound {
                 "cannot find function \"{}\" in module \"{}\"",
                 global_variable_name, module_name
             ),
+            ObjectNotFound::TagNotFound(module_name, tag_name) => write!(
+                f,
+                "cannot find tag \"{}\" in module \"{}\"",
+                tag_name, module_name
+            ),
+            ObjectNotFound::DynamicLinkingSectionNotFound(module_name) => write!(
+                f,
+                "cannot find the dynamic linking section \"dylink.0\" in module \"{}\"",
+                module_name
+            ),
+            ObjectNotFound::SymbolNotFound(module_name, symbol_name) => write!(
+                f,
+                "cannot find symbol \"{}\" required by module \"{}\"",
+                symbol_name, module_name
+            ),
             ObjectNotFound::ElementItemNotFound(module_index, table_index, element_index) => {
                 write!(
                     f,
@@ -145,8 +183,15 @@ impl Display for ObjectNotFound {
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Overflow {
-    MemoryPageExceed(/* actual */ u32, /* max allowed */ u32),
+    MemoryPageExceed(/* actual */ u64, /* max allowed */ u64),
+
+    /// 宿主无法分配指定页面数的内存
+    MemoryAllocationFailed(/* page count */ u64),
     TableSizeExceed(/* actual */ u32, /* max allowed */ u32),
+    StackOverflow(/* max allowed */ usize),
+
+    /// 按照模块的 `dylink.0` 段分配内存空间或者表空间时超出了地址范围
+    DynamicLinkingLayoutOverflow(/* module name */ String),
 }
 
 impl Display for Overflow {
@@ -159,6 +204,9 @@ impl Display for Overflow {
                     actual, max
                 )
             }
+            Overflow::MemoryAllocationFailed(page_count) => {
+                write!(f, "failed to allocate {} memory pages", page_count)
+            }
             Overflow::TableSizeExceed(actual, max) => {
                 write!(
                     f,
@@ -166,6 +214,16 @@ impl Display for Overflow {
                     actual, max
                 )
             }
+            Overflow::StackOverflow(max) => {
+                write!(f, "call stack exhausted, maximum stack size {}", max)
+            }
+            Overflow::DynamicLinkingLayoutOverflow(module_name) => {
+                write!(
+                    f,
+                    "the memory or table layout of module \"{}\" exceeds the address range",
+                    module_name
+                )
+            }
         }
     }
 }
@@ -175,11 +233,55 @@ pub enum OutOfRange {
     BlockRelativeDepthOutOfRange(/* relative_depth */ usize, /* max */ usize),
     ElementIndexOutOfRange(/* element index */ usize, /* max */ usize),
 
+    /// 表的批量访问（比如 `table.fill`、`table.copy` 指令）超出了表的范围
+    TableAccessOutOfRange(
+        /* offset */ usize,
+        /* length */ usize,
+        /* table size */ usize,
+    ),
+
+    /// 内存访问（比如内存的加载和存储指令、SIMD 的内存指令）超出了内存块的范围
+    MemoryAccessOutOfRange(
+        /* address */ usize,
+        /* length */ usize,
+        /* memory size */ usize,
+    ),
+
+    /// `table.init` 指令读取的区间超出了元素段的范围
+    ElementSegmentAccessOutOfRange(
+        /* offset */ usize,
+        /* length */ usize,
+        /* segment size */ usize,
+    ),
+
+    /// `memory.init` 指令读取的区间超出了数据段的范围
+    DataSegmentAccessOutOfRange(
+        /* offset */ usize,
+        /* length */ usize,
+        /* segment size */ usize,
+    ),
+
     /// 暂时用不上，仅当支持多表格时才有此异常
     TableIndexOutOfRange(/* table index */ usize, /* max */ usize),
 
     /// 暂时用不上，仅当支持多内存块时才有此异常
     MemoryBlockIndexOutOfRange(/* memory block index */ usize, /* max */ usize),
+
+    // 以下两个异常是在创建实例时（初始化表和内存块）触发的
+    ElementSegmentOutOfRange {
+        module_name: String,
+        element_index: usize,
+        offset: usize,
+        length: usize,
+        table_size: usize,
+    },
+    DataSegmentOutOfRange {
+        module_name: String,
+        data_index: usize,
+        offset: usize,
+        length: usize,
+        memory_size: usize,
+    },
 }
 
 impl Display for OutOfRange {
@@ -195,6 +297,26 @@ impl Display for OutOfRange {
                 "the element index {} is out of range, maximum {}",
                 element_index, max
             ),
+            OutOfRange::TableAccessOutOfRange(offset, length, table_size) => write!(
+                f,
+                "out of bounds table access, offset: {}, length: {}, table size: {}",
+                offset, length, table_size
+            ),
+            OutOfRange::MemoryAccessOutOfRange(address, length, memory_size) => write!(
+                f,
+                "out of bounds memory access, address: {}, length: {}, memory size: {}",
+                address, length, memory_size
+            ),
+            OutOfRange::ElementSegmentAccessOutOfRange(offset, length, segment_size) => write!(
+                f,
+                "out of bounds element segment access, offset: {}, length: {}, segment size: {}",
+                offset, length, segment_size
+            ),
+            OutOfRange::DataSegmentAccessOutOfRange(offset, length, segment_size) => write!(
+                f,
+                "out of bounds data segment access, offset: {}, length: {}, segment size: {}",
+                offset, length, segment_size
+            ),
             OutOfRange::TableIndexOutOfRange(table_index, max) => write!(
                 f,
                 "the table index {} is out of range, maximum {}",
@@ -205,6 +327,28 @@ impl Display for OutOfRange {
                 "the memory block index {} is out of range, maximum {}",
                 memory_block_index, max
             ),
+            OutOfRange::ElementSegmentOutOfRange {
+                module_name,
+                element_index,
+                offset,
+                length,
+                table_size,
+            } => write!(
+                f,
+                "the element segment #{} of module \"{}\" does not fit in the table, offset: {}, length: {}, table size: {}",
+                element_index, module_name, offset, length, table_size
+            ),
+            OutOfRange::DataSegmentOutOfRange {
+                module_name,
+                data_index,
+                offset,
+                length,
+                memory_size,
+            } => write!(
+                f,
+                "the data segment #{} of module \"{}\" does not fit in the memory block, offset: {}, length: {}, memory size: {}",
+                data_index, module_name, offset, length, memory_size
+            ),
         }
     }
 }
@@ -212,12 +356,6 @@ impl Display for OutOfRange {
 #[derive(Debug, PartialEq, Clone)]
 pub enum Unsupported {
     UnsupportedConstantExpressionInstruction(instruction::Instruction),
-
-    /// 当 vm 支持多表格时，需要移除此异常
-    UnsupportedMultipleTable,
-
-    /// 当 vm 支持多内存块时，需要移除此异常
-    UnsupportedMultipleMemoryBlock,
 }
 
 impl Display for Unsupported {
@@ -230,12 +368,6 @@ impl Display for Unsupported {
                     inst
                 )
             }
-            Unsupported::UnsupportedMultipleTable => {
-                write!(f, "does not support multiple tables")
-            }
-            Unsupported::UnsupportedMultipleMemoryBlock => {
-                write!(f, "does not support multiple memory blocks")
-            }
         }
     }
 }
@@ -258,6 +390,7 @@ pub enum TypeMismatch {
     ImportedMemoryBlockTypeMismatch(/* module_name */ String, /* import_name */ String),
     ImportedTableTypeMismatch(/* module_name */ String, /* import_name */ String),
     ImportedFunctionTypeMismatch(/* module_name */ String, /* import_name */ String),
+    ImportedTagTypeMismatch(/* module_name */ String, /* import_name */ String),
 
     SetGlobalVariableValueTypeMismatch(
         /* vm_module_index */ usize,
@@ -287,23 +420,6 @@ pub enum TypeMismatch {
         parameter_type: ValueType,
         value_type: ValueType,
     },
-    BlockCallArgumentTypeMismatch {
-        vm_module_index: usize,
-        function_index: usize,
-        block_index: usize,
-        parameter_index: usize,
-        parameter_type: ValueType,
-        value_type: ValueType,
-    },
-    LoopBlockRecurArgumentTypeMismatch {
-        vm_module_index: usize,
-        function_index: usize,
-        source_block_index: usize,
-        relative_depth: usize,
-        parameter_index: usize,
-        parameter_type: ValueType,
-        valuetype: ValueType,
-    },
 
     SelectInstructionConsequentTypeMismatch(
         /* consequent_value_type */ ValueType,
@@ -316,14 +432,6 @@ pub enum TypeMismatch {
         result_type: ValueType,
         value_type: ValueType,
     },
-    BlockResultTypeMismatch {
-        vm_module_index: usize,
-        function_index: usize,
-        block_index: usize,
-        result_index: usize,
-        result_type: ValueType,
-        value_type: ValueType,
-    },
 }
 
 impl Display for TypeMismatch {
@@ -373,6 +481,13 @@ impl Display for TypeMismatch {
                     global_variable_name, module_name,
                 )
             }
+            TypeMismatch::ImportedTagTypeMismatch(module_name, tag_name) => {
+                write!(
+                    f,
+                    "imported tag \"{}\" (module \"{}\") type does not match",
+                    tag_name, module_name,
+                )
+            }
             TypeMismatch::SetGlobalVariableValueTypeMismatch(
                 vm_module_index,
                 global_variable_index,
@@ -432,42 +547,6 @@ impl Display for TypeMismatch {
                     parameter_type,
                     value_type)
             }
-            TypeMismatch::BlockCallArgumentTypeMismatch {
-                vm_module_index,
-                function_index,
-                block_index,
-                parameter_index,
-                parameter_type,
-                value_type,
-            } => {
-                write!(f,
-                    "failed to enter block {} (function {}, module {}). The data type of parameter {} does not match, expected: {}, actual: {}",
-                    block_index,
-                    function_index,
-                    vm_module_index,
-                    parameter_index,
-                    parameter_type,
-                    value_type)
-            }
-            TypeMismatch::LoopBlockRecurArgumentTypeMismatch {
-                vm_module_index,
-                function_index,
-                source_block_index,
-                relative_depth,
-                parameter_index,
-                parameter_type,
-                valuetype,
-            } => {
-                write!(f,
-                    "failed to recur block {} to relative depth {} (function {}, module {}). The data type of parameter {} does not match, expected: {}, actual: {}",
-                    source_block_index,
-                    relative_depth,
-                    function_index,
-                    vm_module_index,
-                    parameter_index,
-                    parameter_type,
-                    valuetype)
-            }
             TypeMismatch::SelectInstructionConsequentTypeMismatch(
                 consequent_value_type,
                 alternate_value_type,
@@ -492,23 +571,6 @@ impl Display for TypeMismatch {
                     value_type
                 )
             }
-            TypeMismatch::BlockResultTypeMismatch {
-                vm_module_index,
-                function_index,
-                block_index,
-                result_index,
-                result_type,
-                value_type,
-            } => {
-                write!(f,
-                    "failed to return result from block {} (function {}, module {}), The data type of result {} does not match, expected: {}, actual: {}",
-                    block_index,
-                    function_index,
-                    vm_module_index,
-                    result_index,
-                    result_type,
-                    value_type)
-            }
         }
     }
 }
@@ -543,34 +605,12 @@ pub enum InvalidOperation {
         parameters_count: usize,
         operands_count: usize,
     },
-    NotEnoughOperandForBlockCall {
-        vm_module_index: usize,
-        function_index: usize,
-        block_index: usize,
-        parameters_count: usize,
-        operands_count: usize,
-    },
-    NotEnoughOperandForLoopBlockRecur {
-        vm_module_index: usize,
-        function_index: usize,
-        source_block_index: usize,
-        relative_depth: usize,
-        parameters_count: usize,
-        operands_count: usize,
-    },
     NotEnoughOperandForFunctionResult {
         vm_module_index: usize,
         function_index: usize,
         results_count: usize,
         operands_count: usize,
     },
-    NotEnoughOperandForBlockResult {
-        vm_module_index: usize,
-        function_index: usize,
-        block_index: usize,
-        results_count: usize,
-        operands_count: usize,
-    },
     NotEnoughOperandForFunctionBreakToResult {
         vm_module_index: usize,
         function_index: usize,
@@ -586,6 +626,27 @@ pub enum InvalidOperation {
         operands_count: usize,
     },
     Unreachable,
+
+    /// 原子内存访问指令的有效地址没有按照数据的宽度对齐
+    UnalignedAtomicAccess(/* address */ usize, /* length */ usize),
+
+    /// `memory.atomic.wait32` 和 `memory.atomic.wait64` 只能用于共享内存块
+    ExpectedSharedMemory,
+
+    /// `throw_ref` 指令的操作数是空引用
+    NullExceptionReference,
+
+    /// 常量表达式的结果必须是有且只有一个数值
+    IncorrectConstantExpressionResultCount(/* values count */ usize),
+
+    /// 整数除法或者求余的除数为 0
+    IntegerDivideByZero,
+
+    /// 有符号整数除法溢出（即最小值除以 -1），或者浮点数截断之后超出了目标整数的范围
+    IntegerOverflow,
+
+    /// 将 NaN 截断为整数
+    InvalidConversionToInteger,
 }
 
 impl Display for InvalidOperation {
@@ -650,38 +711,6 @@ impl Display for InvalidOperation {
                     parameters_count,
                     operands_count)
             }
-            InvalidOperation::NotEnoughOperandForBlockCall {
-                vm_module_index,
-                function_index,
-                block_index,
-                parameters_count,
-                operands_count,
-            } => {
-                write!(f,
-                    "failed to call block #{} (function #{}, module #{}), not enough operands, expected: {}, actual: {}",
-                    block_index,
-                    function_index,
-                    vm_module_index,
-                    parameters_count,
-                    operands_count)
-            }
-            InvalidOperation::NotEnoughOperandForLoopBlockRecur {
-                vm_module_index,
-                function_index,
-                source_block_index,
-                relative_depth,
-                parameters_count,
-                operands_count,
-            } => {
-                write!(f,
-                    "failed to recur block #{} to relative depth #{} (function #{}, module #{}), not enough operands, expected: {}, actual: {}",
-                    source_block_index,
-                    relative_depth,
-                    function_index,
-                    vm_module_index,
-                    parameters_count,
-                    operands_count)
-            }
             InvalidOperation::NotEnoughOperandForFunctionResult {
                 vm_module_index,
                 function_index,
@@ -695,21 +724,6 @@ impl Display for InvalidOperation {
                     results_count,
                     operands_count)
             }
-            InvalidOperation::NotEnoughOperandForBlockResult {
-                vm_module_index,
-                function_index,
-                block_index,
-                results_count,
-                operands_count,
-            } => {
-                write!(f,
-                    "failed to return result from block #{} (function #{}, module #{}), not enough operands, expected: {}, actual: {}",
-                    block_index,
-                    function_index,
-                    vm_module_index,
-                    results_count,
-                    operands_count)
-            }
             InvalidOperation::NotEnoughOperandForFunctionBreakToResult {
                 vm_module_index,
                 function_index,
@@ -741,6 +755,27 @@ impl Display for InvalidOperation {
                     operands_count)
             }
             InvalidOperation::Unreachable => write!(f, "instruction \"unreachable\" is executed"),
+            InvalidOperation::UnalignedAtomicAccess(address, length) => write!(
+                f,
+                "unaligned atomic memory access, address: {}, length: {}",
+                address, length
+            ),
+            InvalidOperation::ExpectedSharedMemory => {
+                write!(f, "expected shared memory")
+            }
+            InvalidOperation::IntegerDivideByZero => write!(f, "integer divide by zero"),
+            InvalidOperation::IntegerOverflow => write!(f, "integer overflow"),
+            InvalidOperation::InvalidConversionToInteger => {
+                write!(f, "invalid conversion to integer")
+            }
+            InvalidOperation::NullExceptionReference => {
+                write!(f, "the exception reference is null")
+            }
+            InvalidOperation::IncorrectConstantExpressionResultCount(values_count) => write!(
+                f,
+                "the constant expression should result in exactly one value, actual: {}",
+                values_count
+            ),
         }
     }
 }
@@ -755,6 +790,12 @@ pub struct NativeTerminate {
 pub enum NativeError {
     Exit(i32),
     Internal(Box<dyn InternalError>),
+
+    /// 本地函数抛出 WebAssembly 异常
+    ///
+    /// 参数 tag_index 是标签在（抛出异常的）本地模块里的索引，
+    /// 异常可以被 WebAssembly 的 `try_table` 结构块捕获。
+    Exception(/* tag_index */ usize, /* values */ Vec<Value>),
 }
 
 pub trait InternalError: Debug + Display {
@@ -775,6 +816,13 @@ impl Display for NativeTerminate {
                     internal_error.to_string()
                 )
             }
+            NativeError::Exception(tag_index, values) => {
+                write!(
+                    f,
+                    "native module \"{}\" throws exception, tag #{}, values: {:?}",
+                    self.module_name, tag_index, values
+                )
+            }
         }
     }
 }
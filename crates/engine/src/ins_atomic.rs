@@ -0,0 +1,890 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 原子内存访问指令
+//!
+//! 线程提案引入的原子指令都以 0xFE 作为前缀，它们的内存参数跟普通的内存指令一样，
+//! 不过对齐值必须等于数据的自然对齐值，而且有效地址也必须按照数据的宽度对齐，
+//! 否则会触发 “unaligned atomic” 异常。
+//!
+//! 指令大致分为以下几类：
+//!
+//! - 原子加载和存储，比如 `i32.atomic.load`、`i64.atomic.store16`，
+//!   窄于操作数类型的加载指令对数值进行零扩展（zero-extend）；
+//! - 原子的 “读取-修改-写入”，比如 `i32.atomic.rmw.add`、`i64.atomic.rmw8.xchg_u`，
+//!   从操作数栈弹出地址和操作数，把运算结果写入内存，然后压入内存的旧值；
+//! - 原子的比较并交换，比如 `i32.atomic.rmw.cmpxchg`，
+//!   仅当内存的旧值等于期望值（expected）时才写入替换值（replacement），然后压入旧值；
+//! - 等待和唤醒，`memory.atomic.wait32` 和 `memory.atomic.wait64` 让当前线程等待，
+//!   直到被其他线程的 `memory.atomic.notify` 唤醒或者超时，超时值的单位为纳秒，负数表示不会超时；
+//! - 内存屏障 `atomic.fence`。
+//!
+//! 原子指令同样可以作用于普通（非共享）内存块，除了 `memory.atomic.wait32` 和
+//! `memory.atomic.wait64` 会触发 “expected shared memory” 异常，
+//! 以及 `memory.atomic.notify` 总是返回 0。
+//!
+//! https://github.com/WebAssembly/threads/blob/main/proposals/threads/Overview.md
+
+use std::time::Duration;
+
+use anvm_ast::{
+    instruction::MemoryArgument,
+    types::{Value, ValueType},
+};
+
+use crate::{
+    error::{make_operand_data_types_mismatch_engine_error, EngineError, InvalidOperation},
+    ins_memory,
+    vm::VM,
+    vm_memory::VMMemory,
+};
+
+pub fn memory_atomic_notify(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    memory_atomic_notify_op(vm, "memory.atomic.notify", memory_args)
+}
+
+pub fn memory_atomic_wait32(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    memory_atomic_wait_op(vm, "memory.atomic.wait32", memory_args, 4, ValueType::I32)
+}
+
+pub fn memory_atomic_wait64(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    memory_atomic_wait_op(vm, "memory.atomic.wait64", memory_args, 8, ValueType::I64)
+}
+
+/// 目前所有原子指令都是顺序一致的（sequentially consistent），
+/// 内存屏障只需保证本线程前后的内存访问不会被重新排序。
+pub fn atomic_fence(_vm: &mut VM) -> Result<(), EngineError> {
+    std::sync::atomic::fence(std::sync::atomic::Ordering::SeqCst);
+    Ok(())
+}
+
+pub fn i32_atomic_load(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_load_op(vm, "i32.atomic.load", memory_args, 4, ValueType::I32)
+}
+
+pub fn i64_atomic_load(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_load_op(vm, "i64.atomic.load", memory_args, 8, ValueType::I64)
+}
+
+pub fn i32_atomic_load8_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_load_op(vm, "i32.atomic.load8_u", memory_args, 1, ValueType::I32)
+}
+
+pub fn i32_atomic_load16_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_load_op(vm, "i32.atomic.load16_u", memory_args, 2, ValueType::I32)
+}
+
+pub fn i64_atomic_load8_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_load_op(vm, "i64.atomic.load8_u", memory_args, 1, ValueType::I64)
+}
+
+pub fn i64_atomic_load16_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_load_op(vm, "i64.atomic.load16_u", memory_args, 2, ValueType::I64)
+}
+
+pub fn i64_atomic_load32_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_load_op(vm, "i64.atomic.load32_u", memory_args, 4, ValueType::I64)
+}
+
+pub fn i32_atomic_store(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_store_op(vm, "i32.atomic.store", memory_args, 4, ValueType::I32)
+}
+
+pub fn i64_atomic_store(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_store_op(vm, "i64.atomic.store", memory_args, 8, ValueType::I64)
+}
+
+pub fn i32_atomic_store8(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_store_op(vm, "i32.atomic.store8", memory_args, 1, ValueType::I32)
+}
+
+pub fn i32_atomic_store16(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_store_op(vm, "i32.atomic.store16", memory_args, 2, ValueType::I32)
+}
+
+pub fn i64_atomic_store8(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_store_op(vm, "i64.atomic.store8", memory_args, 1, ValueType::I64)
+}
+
+pub fn i64_atomic_store16(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_store_op(vm, "i64.atomic.store16", memory_args, 2, ValueType::I64)
+}
+
+pub fn i64_atomic_store32(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_store_op(vm, "i64.atomic.store32", memory_args, 4, ValueType::I64)
+}
+
+pub fn i32_atomic_rmw_add(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i32.atomic.rmw.add",
+        memory_args,
+        4,
+        ValueType::I32,
+        |old, value| old.wrapping_add(value),
+    )
+}
+
+pub fn i64_atomic_rmw_add(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i64.atomic.rmw.add",
+        memory_args,
+        8,
+        ValueType::I64,
+        |old, value| old.wrapping_add(value),
+    )
+}
+
+pub fn i32_atomic_rmw8_add_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i32.atomic.rmw8.add_u",
+        memory_args,
+        1,
+        ValueType::I32,
+        |old, value| old.wrapping_add(value),
+    )
+}
+
+pub fn i32_atomic_rmw16_add_u(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i32.atomic.rmw16.add_u",
+        memory_args,
+        2,
+        ValueType::I32,
+        |old, value| old.wrapping_add(value),
+    )
+}
+
+pub fn i64_atomic_rmw8_add_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i64.atomic.rmw8.add_u",
+        memory_args,
+        1,
+        ValueType::I64,
+        |old, value| old.wrapping_add(value),
+    )
+}
+
+pub fn i64_atomic_rmw16_add_u(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i64.atomic.rmw16.add_u",
+        memory_args,
+        2,
+        ValueType::I64,
+        |old, value| old.wrapping_add(value),
+    )
+}
+
+pub fn i64_atomic_rmw32_add_u(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i64.atomic.rmw32.add_u",
+        memory_args,
+        4,
+        ValueType::I64,
+        |old, value| old.wrapping_add(value),
+    )
+}
+
+pub fn i32_atomic_rmw_sub(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i32.atomic.rmw.sub",
+        memory_args,
+        4,
+        ValueType::I32,
+        |old, value| old.wrapping_sub(value),
+    )
+}
+
+pub fn i64_atomic_rmw_sub(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i64.atomic.rmw.sub",
+        memory_args,
+        8,
+        ValueType::I64,
+        |old, value| old.wrapping_sub(value),
+    )
+}
+
+pub fn i32_atomic_rmw8_sub_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i32.atomic.rmw8.sub_u",
+        memory_args,
+        1,
+        ValueType::I32,
+        |old, value| old.wrapping_sub(value),
+    )
+}
+
+pub fn i32_atomic_rmw16_sub_u(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i32.atomic.rmw16.sub_u",
+        memory_args,
+        2,
+        ValueType::I32,
+        |old, value| old.wrapping_sub(value),
+    )
+}
+
+pub fn i64_atomic_rmw8_sub_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i64.atomic.rmw8.sub_u",
+        memory_args,
+        1,
+        ValueType::I64,
+        |old, value| old.wrapping_sub(value),
+    )
+}
+
+pub fn i64_atomic_rmw16_sub_u(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i64.atomic.rmw16.sub_u",
+        memory_args,
+        2,
+        ValueType::I64,
+        |old, value| old.wrapping_sub(value),
+    )
+}
+
+pub fn i64_atomic_rmw32_sub_u(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i64.atomic.rmw32.sub_u",
+        memory_args,
+        4,
+        ValueType::I64,
+        |old, value| old.wrapping_sub(value),
+    )
+}
+
+pub fn i32_atomic_rmw_and(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i32.atomic.rmw.and",
+        memory_args,
+        4,
+        ValueType::I32,
+        |old, value| old & value,
+    )
+}
+
+pub fn i64_atomic_rmw_and(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i64.atomic.rmw.and",
+        memory_args,
+        8,
+        ValueType::I64,
+        |old, value| old & value,
+    )
+}
+
+pub fn i32_atomic_rmw8_and_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i32.atomic.rmw8.and_u",
+        memory_args,
+        1,
+        ValueType::I32,
+        |old, value| old & value,
+    )
+}
+
+pub fn i32_atomic_rmw16_and_u(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i32.atomic.rmw16.and_u",
+        memory_args,
+        2,
+        ValueType::I32,
+        |old, value| old & value,
+    )
+}
+
+pub fn i64_atomic_rmw8_and_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i64.atomic.rmw8.and_u",
+        memory_args,
+        1,
+        ValueType::I64,
+        |old, value| old & value,
+    )
+}
+
+pub fn i64_atomic_rmw16_and_u(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i64.atomic.rmw16.and_u",
+        memory_args,
+        2,
+        ValueType::I64,
+        |old, value| old & value,
+    )
+}
+
+pub fn i64_atomic_rmw32_and_u(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i64.atomic.rmw32.and_u",
+        memory_args,
+        4,
+        ValueType::I64,
+        |old, value| old & value,
+    )
+}
+
+pub fn i32_atomic_rmw_or(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i32.atomic.rmw.or",
+        memory_args,
+        4,
+        ValueType::I32,
+        |old, value| old | value,
+    )
+}
+
+pub fn i64_atomic_rmw_or(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i64.atomic.rmw.or",
+        memory_args,
+        8,
+        ValueType::I64,
+        |old, value| old | value,
+    )
+}
+
+pub fn i32_atomic_rmw8_or_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i32.atomic.rmw8.or_u",
+        memory_args,
+        1,
+        ValueType::I32,
+        |old, value| old | value,
+    )
+}
+
+pub fn i32_atomic_rmw16_or_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i32.atomic.rmw16.or_u",
+        memory_args,
+        2,
+        ValueType::I32,
+        |old, value| old | value,
+    )
+}
+
+pub fn i64_atomic_rmw8_or_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i64.atomic.rmw8.or_u",
+        memory_args,
+        1,
+        ValueType::I64,
+        |old, value| old | value,
+    )
+}
+
+pub fn i64_atomic_rmw16_or_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i64.atomic.rmw16.or_u",
+        memory_args,
+        2,
+        ValueType::I64,
+        |old, value| old | value,
+    )
+}
+
+pub fn i64_atomic_rmw32_or_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i64.atomic.rmw32.or_u",
+        memory_args,
+        4,
+        ValueType::I64,
+        |old, value| old | value,
+    )
+}
+
+pub fn i32_atomic_rmw_xor(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i32.atomic.rmw.xor",
+        memory_args,
+        4,
+        ValueType::I32,
+        |old, value| old ^ value,
+    )
+}
+
+pub fn i64_atomic_rmw_xor(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i64.atomic.rmw.xor",
+        memory_args,
+        8,
+        ValueType::I64,
+        |old, value| old ^ value,
+    )
+}
+
+pub fn i32_atomic_rmw8_xor_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i32.atomic.rmw8.xor_u",
+        memory_args,
+        1,
+        ValueType::I32,
+        |old, value| old ^ value,
+    )
+}
+
+pub fn i32_atomic_rmw16_xor_u(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i32.atomic.rmw16.xor_u",
+        memory_args,
+        2,
+        ValueType::I32,
+        |old, value| old ^ value,
+    )
+}
+
+pub fn i64_atomic_rmw8_xor_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i64.atomic.rmw8.xor_u",
+        memory_args,
+        1,
+        ValueType::I64,
+        |old, value| old ^ value,
+    )
+}
+
+pub fn i64_atomic_rmw16_xor_u(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i64.atomic.rmw16.xor_u",
+        memory_args,
+        2,
+        ValueType::I64,
+        |old, value| old ^ value,
+    )
+}
+
+pub fn i64_atomic_rmw32_xor_u(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i64.atomic.rmw32.xor_u",
+        memory_args,
+        4,
+        ValueType::I64,
+        |old, value| old ^ value,
+    )
+}
+
+pub fn i32_atomic_rmw_xchg(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i32.atomic.rmw.xchg",
+        memory_args,
+        4,
+        ValueType::I32,
+        |_, value| value,
+    )
+}
+
+pub fn i64_atomic_rmw_xchg(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i64.atomic.rmw.xchg",
+        memory_args,
+        8,
+        ValueType::I64,
+        |_, value| value,
+    )
+}
+
+pub fn i32_atomic_rmw8_xchg_u(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i32.atomic.rmw8.xchg_u",
+        memory_args,
+        1,
+        ValueType::I32,
+        |_, value| value,
+    )
+}
+
+pub fn i32_atomic_rmw16_xchg_u(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i32.atomic.rmw16.xchg_u",
+        memory_args,
+        2,
+        ValueType::I32,
+        |_, value| value,
+    )
+}
+
+pub fn i64_atomic_rmw8_xchg_u(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i64.atomic.rmw8.xchg_u",
+        memory_args,
+        1,
+        ValueType::I64,
+        |_, value| value,
+    )
+}
+
+pub fn i64_atomic_rmw16_xchg_u(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i64.atomic.rmw16.xchg_u",
+        memory_args,
+        2,
+        ValueType::I64,
+        |_, value| value,
+    )
+}
+
+pub fn i64_atomic_rmw32_xchg_u(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    atomic_rmw_op(
+        vm,
+        "i64.atomic.rmw32.xchg_u",
+        memory_args,
+        4,
+        ValueType::I64,
+        |_, value| value,
+    )
+}
+
+pub fn i32_atomic_rmw_cmpxchg(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    atomic_cmpxchg_op(vm, "i32.atomic.rmw.cmpxchg", memory_args, 4, ValueType::I32)
+}
+
+pub fn i64_atomic_rmw_cmpxchg(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    atomic_cmpxchg_op(vm, "i64.atomic.rmw.cmpxchg", memory_args, 8, ValueType::I64)
+}
+
+pub fn i32_atomic_rmw8_cmpxchg_u(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    atomic_cmpxchg_op(
+        vm,
+        "i32.atomic.rmw8.cmpxchg_u",
+        memory_args,
+        1,
+        ValueType::I32,
+    )
+}
+
+pub fn i32_atomic_rmw16_cmpxchg_u(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    atomic_cmpxchg_op(
+        vm,
+        "i32.atomic.rmw16.cmpxchg_u",
+        memory_args,
+        2,
+        ValueType::I32,
+    )
+}
+
+pub fn i64_atomic_rmw8_cmpxchg_u(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    atomic_cmpxchg_op(
+        vm,
+        "i64.atomic.rmw8.cmpxchg_u",
+        memory_args,
+        1,
+        ValueType::I64,
+    )
+}
+
+pub fn i64_atomic_rmw16_cmpxchg_u(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    atomic_cmpxchg_op(
+        vm,
+        "i64.atomic.rmw16.cmpxchg_u",
+        memory_args,
+        2,
+        ValueType::I64,
+    )
+}
+
+pub fn i64_atomic_rmw32_cmpxchg_u(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    atomic_cmpxchg_op(
+        vm,
+        "i64.atomic.rmw32.cmpxchg_u",
+        memory_args,
+        4,
+        ValueType::I64,
+    )
+}
+
+// 辅助函数
+
+/// 获取当前模块的内存块，内存块的索引由指令的内存参数指定
+fn get_memory_block<'a>(vm: &'a mut VM, memory_args: &MemoryArgument) -> &'a mut VMMemory {
+    let instance_memory_block_index = vm.resource.vm_modules[vm.status.vm_module_index]
+        .memory_indexes[memory_args.memory_block_index as usize];
+    &mut vm.resource.memory_blocks[instance_memory_block_index]
+}
+
+/// 弹出 i32 或者 i64 操作数，并转换为无符号整数
+fn pop_unsigned(
+    vm: &mut VM,
+    instruction_name: &str,
+    value_type: &ValueType,
+) -> Result<u64, EngineError> {
+    let value = vm.stack.pop();
+    match (value_type, &value) {
+        (ValueType::I32, Value::I32(v)) => Ok(*v as u32 as u64),
+        (ValueType::I64, Value::I64(v)) => Ok(*v as u64),
+        _ => Err(make_operand_data_types_mismatch_engine_error(
+            instruction_name,
+            vec![value_type.clone()],
+            vec![&value],
+        )),
+    }
+}
+
+/// 把无符号整数转换为 i32 或者 i64 然后压入操作数栈
+fn push_unsigned(vm: &mut VM, value_type: &ValueType, value: u64) {
+    match value_type {
+        ValueType::I32 => vm.stack.push(Value::I32(value as u32 as i32)),
+        _ => vm.stack.push(Value::I64(value as i64)),
+    }
+}
+
+/// 从操作数栈弹出地址，计算有效地址，并检查访问区间是否越界以及地址是否对齐
+fn pop_effective_address(
+    vm: &mut VM,
+    instruction_name: &str,
+    memory_args: &MemoryArgument,
+    length: usize,
+) -> Result<usize, EngineError> {
+    let effective_address =
+        ins_memory::pop_effective_address(vm, instruction_name, memory_args, length)?;
+
+    if !effective_address.is_multiple_of(length) {
+        return Err(EngineError::InvalidOperation(
+            InvalidOperation::UnalignedAtomicAccess(effective_address, length),
+        ));
+    }
+
+    Ok(effective_address)
+}
+
+/// 截断数值，只保留低 length 个字节
+fn truncate(value: u64, length: usize) -> u64 {
+    if length == 8 {
+        value
+    } else {
+        value & ((1u64 << (length * 8)) - 1)
+    }
+}
+
+fn atomic_load_op(
+    vm: &mut VM,
+    instruction_name: &str,
+    memory_args: &MemoryArgument,
+    length: usize,
+    value_type: ValueType,
+) -> Result<(), EngineError> {
+    let address = pop_effective_address(vm, instruction_name, memory_args, length)?;
+    let value = get_memory_block(vm, memory_args).atomic_load(address, length);
+    push_unsigned(vm, &value_type, value);
+    Ok(())
+}
+
+fn atomic_store_op(
+    vm: &mut VM,
+    instruction_name: &str,
+    memory_args: &MemoryArgument,
+    length: usize,
+    value_type: ValueType,
+) -> Result<(), EngineError> {
+    let value = pop_unsigned(vm, instruction_name, &value_type)?;
+    let address = pop_effective_address(vm, instruction_name, memory_args, length)?;
+    get_memory_block(vm, memory_args).atomic_store(address, length, value);
+    Ok(())
+}
+
+/// “读取-修改-写入” 指令
+///
+/// 参数 op 的第一个参数是内存的旧值，第二个参数是操作数，返回值是新值。
+fn atomic_rmw_op(
+    vm: &mut VM,
+    instruction_name: &str,
+    memory_args: &MemoryArgument,
+    length: usize,
+    value_type: ValueType,
+    op: impl FnOnce(u64, u64) -> u64,
+) -> Result<(), EngineError> {
+    let value = pop_unsigned(vm, instruction_name, &value_type)?;
+    let address = pop_effective_address(vm, instruction_name, memory_args, length)?;
+    let old_value =
+        get_memory_block(vm, memory_args).atomic_rmw(address, length, |old| op(old, value));
+    push_unsigned(vm, &value_type, old_value);
+    Ok(())
+}
+
+/// 比较并交换指令
+///
+/// 期望值会先被截断为 length 个字节再跟内存的旧值比较。
+fn atomic_cmpxchg_op(
+    vm: &mut VM,
+    instruction_name: &str,
+    memory_args: &MemoryArgument,
+    length: usize,
+    value_type: ValueType,
+) -> Result<(), EngineError> {
+    let replacement = pop_unsigned(vm, instruction_name, &value_type)?;
+    let expected = truncate(pop_unsigned(vm, instruction_name, &value_type)?, length);
+    let address = pop_effective_address(vm, instruction_name, memory_args, length)?;
+
+    let old_value = get_memory_block(vm, memory_args).atomic_rmw(address, length, |old| {
+        if old == expected {
+            replacement
+        } else {
+            old
+        }
+    });
+
+    push_unsigned(vm, &value_type, old_value);
+    Ok(())
+}
+
+/// `memory.atomic.wait32` 和 `memory.atomic.wait64`
+///
+/// 操作数依次为地址、期望值以及 i64 类型的超时值（纳秒），
+/// 返回 0（被唤醒）、1（数值不等于期望值）或者 2（超时）。
+fn memory_atomic_wait_op(
+    vm: &mut VM,
+    instruction_name: &str,
+    memory_args: &MemoryArgument,
+    length: usize,
+    value_type: ValueType,
+) -> Result<(), EngineError> {
+    let timeout = pop_unsigned(vm, instruction_name, &ValueType::I64)? as i64;
+    let expected = pop_unsigned(vm, instruction_name, &value_type)?;
+    let address = pop_effective_address(vm, instruction_name, memory_args, length)?;
+
+    let option_timeout = if timeout < 0 {
+        None
+    } else {
+        Some(Duration::from_nanos(timeout as u64))
+    };
+
+    let wait_result = get_memory_block(vm, memory_args)
+        .atomic_wait(address, length, expected, option_timeout)
+        .ok_or(EngineError::InvalidOperation(
+            InvalidOperation::ExpectedSharedMemory,
+        ))?;
+
+    vm.stack.push(Value::I32(wait_result as i32));
+    Ok(())
+}
+
+/// `memory.atomic.notify`
+///
+/// 操作数依次为地址和最多唤醒的线程数量（uint32），返回实际被唤醒的线程数量。
+fn memory_atomic_notify_op(
+    vm: &mut VM,
+    instruction_name: &str,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    let count = pop_unsigned(vm, instruction_name, &ValueType::I32)? as u32;
+    let address = pop_effective_address(vm, instruction_name, memory_args, 4)?;
+    let notified_count = get_memory_block(vm, memory_args).atomic_notify(address, count);
+    vm.stack.push(Value::I32(notified_count as i32));
+    Ok(())
+}
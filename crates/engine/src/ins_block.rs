@@ -48,16 +48,20 @@
 //! br_table 指令先从操作数栈顶弹出一个 uint32 整数，这个数将作为
 //! br_table 后面的整数列表的索引，获取跳转的目标。如果该索引超出了
 //! 列表范围，则跳转目标的 br_table 指令的最末尾一个参数（即默认目标）
+//!
+//! ## 结构块的栈帧
+//!
+//! 进入结构块时不会创建栈帧，结构块的操作数跟函数的操作数位于同一个调用帧，
+//! 跳转指令的目标栈高度以及需要携带的操作数数量均由解码器预先计算好，
+//! 所以跳转只需截断操作数栈然后改变下一个指令的地址即可。
 
 use anvm_ast::{
     instruction::BlockType,
-    types::{check_value_types, Value, ValueType, ValueTypeCheckError},
+    types::{Value, ValueType},
 };
 
 use crate::{
-    error::{
-        make_operand_data_types_mismatch_engine_error, EngineError, InvalidOperation, TypeMismatch,
-    },
+    error::{make_operand_data_types_mismatch_engine_error, EngineError, InvalidOperation},
     ins_control::ControlResult,
     object::BranchTarget,
     vm::VM,
@@ -65,272 +69,51 @@ use crate::{
 };
 
 /// 处理原 `block/loop 指令`
+///
+/// 结构块不创建栈帧，所以进入结构块时不需要任何操作。
 pub fn block(
-    vm: &mut VM,
-    block_type: &BlockType,
-    block_index: usize,
-    end_address: usize,
+    _vm: &mut VM,
+    _block_type: &BlockType,
+    _block_index: usize,
+    _end_address: usize,
 ) -> Result<ControlResult, EngineError> {
-    // 对于 block 结构块
-    // 默认的返回地址（即在中间没有遇到 br/return 指令的情况下，一直执行到 end 指令）应该是
-    // 结构块最后一条指令 `end 指令` 的下一个指令
-    let return_address = end_address + 1;
-
-    // 执行完 `block/loop 指令` 之后的下一个指令应该是当前指令的下一个指令
-    let next_instruction_address = vm.status.address + 1;
-
-    continue_process_block(
-        vm,
-        block_type,
-        block_index,
-        next_instruction_address,
-        return_address,
-    )
+    Ok(ControlResult::Sequence)
 }
 
 /// 处理原 `if 指令`
 pub fn block_and_jump_when_eq_zero(
     vm: &mut VM,
-    block_type: &BlockType,
-    block_index: usize,
+    _block_type: &BlockType,
+    _block_index: usize,
     option_alternate_address: Option<usize>,
     end_address: usize,
 ) -> Result<ControlResult, EngineError> {
-    // 对于 if 结构块
-    // 默认的返回地址（即在中间没有遇到 else/br/return 指令的情况下，一直执行到 end 指令）应该是
-    // 返回地址应该是结构块最后一条指令 `end 指令` 的下一个指令
-    let return_address = end_address + 1;
-
-    let testing = match vm.stack.pop_bool() {
-        Ok(b) => b,
-        Err(v) => {
-            return Err(make_operand_data_types_mismatch_engine_error(
-                "if",
-                vec![ValueType::I32],
-                vec![&v],
-            ))
-        }
-    };
-
     // 执行完 `if 指令` 之后，如果刚才栈顶的数值是：
     //
     // - true：
-    //   则压入一个栈帧，下一个指令应该是 `if 指令` 的下一个指令；
+    //   下一个指令应该是 `if 指令` 的下一个指令；
     // - false：
-    //   - 如果存在 `else 指令` ，则压入一个栈帧，
-    //     跳到 `else 指令` 的下一个指令。
+    //   - 如果存在 `else 指令` ，则跳到 `else 指令` 的下一个指令。
     //     注意不要跳到 `else 指令` 本身，因为 `else 指令` 已经被
     //     转换为 `jump 控制指令`，该指令的效果是直接跳到 if 结构块的结束位置
-    //   - 不存在 `else 指令` 的话，则 **不要压入一个栈帧**，
-    //     并跳到结构块之外，即跳到if 结构块的最后一条 `end 指令` 的下一个指令，
-    //     就当作普通的 jump 指令一样。
-    let push_control_frame = if testing {
-        Ok(vm.status.address + 1)
+    //   - 不存在 `else 指令` 的话，则跳到结构块之外，即跳到 if 结构块的
+    //     最后一条 `end 指令` 的下一个指令。
+    let address = if let Some(alternate_address) = option_alternate_address {
+        alternate_address + 1
     } else {
-        if let Some(alternate_address) = option_alternate_address {
-            Ok(alternate_address + 1)
-        } else {
-            Err(end_address + 1)
-        }
+        end_address + 1
     };
 
-    match push_control_frame {
-        Ok(next_instruction_address) => continue_process_block(
-            vm,
-            block_type,
-            block_index,
-            next_instruction_address,
-            return_address,
-        ),
-        Err(post_block_address) => Ok(ControlResult::JumpWithinBlock(
-            /* address: */ post_block_address,
-        )),
-    }
-}
-
-fn continue_process_block(
-    vm: &mut VM,
-    block_type: &BlockType,
-    block_index: usize,
-    next_instruction_address: usize,
-    return_address: usize,
-) -> Result<ControlResult, EngineError> {
-    let parameter_types = {
-        match block_type {
-            BlockType::ResultEmpty
-            | BlockType::ResultI32
-            | BlockType::ResultI64
-            | BlockType::ResultF32
-            | BlockType::ResultF64 => {
-                vec![]
-            }
-            BlockType::TypeIndex(type_index) => {
-                let vm_module_index = vm.status.vm_module_index;
-                let vm_module = &vm.resource.vm_modules[vm_module_index];
-                let function_type = &vm_module.function_types[*type_index as usize];
-                function_type.params.to_owned()
-            }
-        }
-    };
-
-    let function_index = vm.status.function_index;
-    let vm_module_index = vm.status.vm_module_index;
-
-    // 判断操作数是否足够当前结构块的参数
-    let parameters_count = parameter_types.len();
-    let stack_size = vm.stack.get_size();
-    let operands_count = stack_size - vm.status.base_pointer - INFO_SEGMENT_ITEM_COUNT;
-    if operands_count < parameters_count {
-        return Err(EngineError::InvalidOperation(
-            InvalidOperation::NotEnoughOperandForBlockCall {
-                vm_module_index,
-                function_index,
-                block_index,
-                parameters_count,
-                operands_count,
-            },
-        ));
-    }
-
-    // 从栈弹出数据，作为结构块的参数
-    // 跟普通的函数调用栈帧不同，结构块的栈帧没有自己的局部变量段，所以只能
-    // 把实参先弹出来，在创建了控制帧之后，再压入栈。
-    let arguments = vm.stack.pop_values(parameters_count);
-
-    // 核对实参的数据类型和数量
-    match check_value_types(&arguments, &parameter_types) {
-        Err(ValueTypeCheckError::DataTypeMismatch(parameter_index)) => {
-            return Err(EngineError::TypeMismatch(
-                TypeMismatch::BlockCallArgumentTypeMismatch {
-                    vm_module_index,
-                    function_index,
-                    block_index,
-                    parameter_index,
-                    parameter_type: parameter_types[parameter_index].clone(),
-                    value_type: arguments[parameter_index].get_type(),
-                },
-            ));
-        }
-        _ => {
-            // pass
-        }
-    }
-
-    // 压入调用栈
-    vm.push_control_frame(return_address);
-
-    // 压入实参
-    vm.stack.push_values(&arguments);
-
-    // 返回新的状态信息，让调用者更新虚拟机状态
-    let control_result = ControlResult::PushStackFrame {
-        is_call_frame: false,
-        vm_module_index,
-        function_index,
-        frame_type: block_type.to_owned(),
-        address: next_instruction_address,
-    };
-
-    Ok(control_result)
-}
-
-pub fn jump_within_block(_vm: &mut VM, address: usize) -> Result<ControlResult, EngineError> {
-    Ok(ControlResult::JumpWithinBlock(address))
-}
-
-pub fn process_break(
-    vm: &mut VM,
-    option_block_index: Option<usize>,
-    relative_depth: usize,
-    address: usize,
-) -> Result<ControlResult, EngineError> {
-    if relative_depth == 0 {
-        // 本层的跳转，只需简单地改变下一个指令的地址为结构块的 `end 指令` 位置即可
-        Ok(ControlResult::JumpWithinBlock(address))
-    } else {
-        // 跨层跳转，需要将当前栈帧的操作数作为目标层的返回值，具体步骤：
-        // 1. 根据目标层返回值的数量，暂存当前栈帧相应数量的操作数；
-        // 2. 弹出 `相对深度` 数量的栈帧；
-        // 3. 压入第一步所保留的操作数，作为目标结构块返回值。
-
-        let vm_module_index = vm.status.vm_module_index;
-        let function_index = vm.status.function_index;
-
-        let target_status = vm.get_status_by_relative_depth(relative_depth);
-        let target_frame_type = &target_status.frame_type;
-
-        // 获取目标帧的返回值的数量
-        //
-        // 注意
-        // - 这里不检查目标帧返回值的数据类型，因为当执行到目标结构块的 `end 指令` 时，
-        //   解析器还会进一步检查，所以这里只需获取返回值的数量即可。
-        // - 实际上当执行到结构块的 `end 指令` 时，解析器不单会检查返回值的数据类型，
-        //   还会再次检查作为返回值的操作数的数量，这个检查跟现在的检查是重复的，
-        //   目前这种处理方式主要是为了简化程序的实现。
-        let results_count = {
-            match target_frame_type {
-                BlockType::ResultEmpty => 0,
-                BlockType::ResultI32
-                | BlockType::ResultI64
-                | BlockType::ResultF32
-                | BlockType::ResultF64 => 1,
-                BlockType::TypeIndex(type_index) => {
-                    let vm_module = &vm.resource.vm_modules[vm_module_index];
-                    let function_type = &vm_module.function_types[*type_index as usize];
-                    function_type.results.len()
-                }
-            }
-        };
-
-        // 判断操作数是否足够当前函数或结构块用于返回
-        let stack_size = vm.stack.get_size();
-        let operands_count = stack_size - vm.status.base_pointer - INFO_SEGMENT_ITEM_COUNT;
-        if operands_count < results_count {
-            if let Some(block_index) = option_block_index {
-                return Err(EngineError::InvalidOperation(
-                    InvalidOperation::NotEnoughOperandForBlockBreakToResult {
-                        vm_module_index,
-                        function_index,
-                        source_block_index: block_index,
-                        relative_depth,
-                        results_count,
-                        operands_count,
-                    },
-                ));
-            } else {
-                return Err(EngineError::InvalidOperation(
-                    InvalidOperation::NotEnoughOperandForFunctionBreakToResult {
-                        vm_module_index,
-                        function_index,
-                        results_count,
-                        operands_count,
-                    },
-                ));
-            }
-        }
-
-        // 丢弃指定数量的栈帧
-        vm.pop_frames(relative_depth, results_count);
-
-        Ok(ControlResult::JumpWithinFunction {
-            frame_type: target_status.frame_type,
-            address,
-        })
-    }
+    jump_when_eq_zero(vm, address)
 }
 
-/// 处理原 `br_if` 跳转到 `loop` 结构层的情况
-pub fn process_break_when_not_eq_zero(
-    vm: &mut VM,
-    option_block_index: Option<usize>,
-    relative_depth: usize,
-    address: usize,
-) -> Result<ControlResult, EngineError> {
+/// 当原栈顶的数值等于 0 时跳到指定的地址，否则继续执行下一个指令
+pub fn jump_when_eq_zero(vm: &mut VM, address: usize) -> Result<ControlResult, EngineError> {
     let testing = match vm.stack.pop_bool() {
         Ok(b) => b,
         Err(v) => {
             return Err(make_operand_data_types_mismatch_engine_error(
-                "br_if",
+                "if",
                 vec![ValueType::I32],
                 vec![&v],
             ))
@@ -338,108 +121,69 @@ pub fn process_break_when_not_eq_zero(
     };
 
     if testing {
-        process_break(vm, option_block_index, relative_depth, address)
-    } else {
         Ok(ControlResult::Sequence)
+    } else {
+        Ok(ControlResult::Jump(address))
     }
 }
 
-pub fn recur(
+pub fn jump_within_block(_vm: &mut VM, address: usize) -> Result<ControlResult, EngineError> {
+    Ok(ControlResult::Jump(address))
+}
+
+/// 处理原 `br/return 指令`
+///
+/// 将栈顶 arity 个操作数作为目标结构块的返回值（或者 loop 结构块的参数），
+/// 丢弃这些操作数与目标结构块栈高度之间的操作数，然后跳到目标地址。
+pub fn process_break(
     vm: &mut VM,
-    block_index: usize,
-    relative_depth: usize,
-    address: usize,
+    option_block_index: Option<usize>,
+    branch_target: &BranchTarget,
 ) -> Result<ControlResult, EngineError> {
-    if relative_depth == 0 {
-        // 本层的跳转，只需简单地改变下一个指令的地址为结构块的 `loop 指令` 的下一个指令位置即可
-        Ok(ControlResult::JumpWithinBlock(
-            /* address: */ address + 1,
-        ))
-    } else {
-        // 跨层跳转，需要将当前栈帧的操作数作为目标层的参数，具体步骤：
-        // 1. 根据目标层参数的数量，暂存当前栈帧相应数量的操作数；
-        // 2. 弹出 `相对深度` 数量的栈帧；
-        // 3. 压入第一步所保留的操作数，作为目标结构块的参数。
+    let operand_pointer = vm.status.base_pointer + INFO_SEGMENT_ITEM_COUNT;
+    let target_stack_pointer = operand_pointer + branch_target.stack_height;
 
+    // 判断操作数是否足够目标结构块用于返回
+    let stack_size = vm.stack.get_size();
+    if stack_size < target_stack_pointer + branch_target.arity {
         let vm_module_index = vm.status.vm_module_index;
         let function_index = vm.status.function_index;
+        let operands_count = stack_size - operand_pointer;
 
-        let target_status = vm.get_status_by_relative_depth(relative_depth);
-        let target_frame_type = &target_status.frame_type;
-
-        // 获取目标帧的参数信息
-        let parameter_types = {
-            match target_frame_type {
-                BlockType::ResultEmpty
-                | BlockType::ResultI32
-                | BlockType::ResultI64
-                | BlockType::ResultF32
-                | BlockType::ResultF64 => vec![],
-                BlockType::TypeIndex(type_index) => {
-                    let vm_module = &vm.resource.vm_modules[vm_module_index];
-                    let function_type = &vm_module.function_types[*type_index as usize];
-                    function_type.params.to_owned()
-                }
-            }
-        };
-
-        // 判断操作数是否足够当前结构块用于递归调用
-        let parameters_count = parameter_types.len();
-        let stack_size = vm.stack.get_size();
-        let operands_count = stack_size - vm.status.base_pointer - INFO_SEGMENT_ITEM_COUNT;
-        if operands_count < parameters_count {
+        if let Some(block_index) = option_block_index {
             return Err(EngineError::InvalidOperation(
-                InvalidOperation::NotEnoughOperandForLoopBlockRecur {
+                InvalidOperation::NotEnoughOperandForBlockBreakToResult {
                     vm_module_index,
                     function_index,
                     source_block_index: block_index,
-                    relative_depth,
-                    parameters_count,
+                    relative_depth: branch_target.relative_depth,
+                    results_count: branch_target.arity,
+                    operands_count,
+                },
+            ));
+        } else {
+            return Err(EngineError::InvalidOperation(
+                InvalidOperation::NotEnoughOperandForFunctionBreakToResult {
+                    vm_module_index,
+                    function_index,
+                    results_count: branch_target.arity,
                     operands_count,
                 },
             ));
         }
+    }
 
-        let arguments = vm.stack.peek_values(parameters_count);
-
-        // 核对实参的数据类型和数量
-        match check_value_types(arguments, &parameter_types) {
-            Err(ValueTypeCheckError::LengthMismatch) => {
-                unreachable!("arguments count should be match")
-            }
-            Err(ValueTypeCheckError::DataTypeMismatch(parameter_index)) => {
-                return Err(EngineError::TypeMismatch(
-                    TypeMismatch::LoopBlockRecurArgumentTypeMismatch {
-                        vm_module_index,
-                        function_index,
-                        source_block_index: block_index,
-                        relative_depth,
-                        parameter_index,
-                        parameter_type: parameter_types[parameter_index].clone(),
-                        valuetype: arguments[parameter_index].get_type(),
-                    },
-                ));
-            }
-            _ => {
-                // pass
-            }
-        }
-
-        vm.pop_frames(relative_depth, parameters_count);
+    vm.stack
+        .drop_values_keep_top(target_stack_pointer, branch_target.arity);
 
-        Ok(ControlResult::JumpWithinFunction {
-            frame_type: target_status.frame_type,
-            address: address + 1,
-        })
-    }
+    Ok(ControlResult::Jump(branch_target.address))
 }
 
-/// 处理原 `br_if` 跳转到 `loop` 结构层的情况
-pub fn recur_when_not_eq_zero(
+/// 处理原 `br_if 指令`
+pub fn process_break_when_not_eq_zero(
     vm: &mut VM,
-    block_index: usize,
-    relative_depth: usize,
-    address: usize,
+    option_block_index: Option<usize>,
+    branch_target: &BranchTarget,
 ) -> Result<ControlResult, EngineError> {
     let testing = match vm.stack.pop_bool() {
         Ok(b) => b,
@@ -453,12 +197,13 @@ pub fn recur_when_not_eq_zero(
     };
 
     if testing {
-        recur(vm, block_index, relative_depth, address)
+        process_break(vm, option_block_index, branch_target)
     } else {
         Ok(ControlResult::Sequence)
     }
 }
 
+/// 处理原 `br_table 指令`
 pub fn branch(
     vm: &mut VM,
     option_block_index: Option<usize>,
@@ -477,19 +222,9 @@ pub fn branch(
         ));
     };
 
-    let actual_branch_target = if branch_index < branch_targets.len() {
-        branch_targets[branch_index].clone()
-    } else {
-        default_branch_target.to_owned()
-    };
+    let actual_branch_target = branch_targets
+        .get(branch_index)
+        .unwrap_or(default_branch_target);
 
-    match actual_branch_target {
-        BranchTarget::Break(relative_depth, address) => {
-            process_break(vm, option_block_index, relative_depth, address)
-        }
-        BranchTarget::Recur(relative_depth, address) => {
-            let block_index = option_block_index.unwrap();
-            recur(vm, block_index, relative_depth, address)
-        }
-    }
+    process_break(vm, option_block_index, actual_branch_target)
 }
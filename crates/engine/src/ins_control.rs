@@ -10,7 +10,7 @@
 
 use anvm_ast::{
     instruction::BlockType,
-    types::{check_value_types, ValueType, ValueTypeCheckError},
+    types::{check_types, ValueType, ValueTypeCheckError},
 };
 
 use crate::{
@@ -23,22 +23,20 @@ pub enum ControlResult {
     /// 执行下一句
     Sequence,
 
-    /// 进入一个函数或者一个结构块
+    /// 进入一个函数
     ///
     /// 参数用于更新虚拟机的 pc 值
     PushStackFrame {
-        is_call_frame: bool,
         vm_module_index: usize,
         function_index: usize,
         frame_type: BlockType,
         address: usize,
     },
 
-    /// 从一个函数或者一个结构块跳出
+    /// 从一个函数返回
     ///
     /// 参数用于更新虚拟机的 pc 值
     PopStackFrame {
-        is_call_frame: bool,
         vm_module_index: usize,
         function_index: usize,
         frame_type: BlockType,
@@ -47,14 +45,8 @@ pub enum ControlResult {
 
     /// 函数内跳转
     ///
-    /// 即结构块间的跳转
-    JumpWithinFunction {
-        frame_type: BlockType,
-        address: usize,
-    },
-
-    /// 结构块内跳转
-    JumpWithinBlock(/* address */ usize),
+    /// 包括结构块内以及结构块间的跳转
+    Jump(/* address */ usize),
 
     /// 程序已结束
     ProgramEnd,
@@ -64,16 +56,16 @@ pub fn process_end(
     vm: &mut VM,
     option_block_index: &Option<usize>,
 ) -> Result<ControlResult, EngineError> {
+    // 结构块没有栈帧，结构块的 `end 指令` 不需要任何操作
+    if option_block_index.is_some() {
+        return Ok(ControlResult::Sequence);
+    }
+
     let frame_type = &vm.status.frame_type;
     let vm_module_index = vm.status.vm_module_index;
     let function_index = vm.status.function_index;
 
-    // 如果 fp 和 lp 的值相同，则说明当前是调用帧，否则则是流程控制的结构块帧
-    let frame_pointer = vm.status.frame_pointer;
-    let local_pointer = vm.status.local_pointer;
-    let is_call_frame = frame_pointer == local_pointer;
-
-    // 获取当前帧的返回值类型
+    // 获取当前函数的返回值类型
     let result_types = {
         match frame_type {
             BlockType::ResultEmpty => vec![],
@@ -81,6 +73,10 @@ pub fn process_end(
             BlockType::ResultI64 => vec![ValueType::I64],
             BlockType::ResultF32 => vec![ValueType::F32],
             BlockType::ResultF64 => vec![ValueType::F64],
+            BlockType::ResultV128 => vec![ValueType::V128],
+            BlockType::ResultFuncRef => vec![ValueType::FuncRef],
+            BlockType::ResultExternRef => vec![ValueType::ExternRef],
+            BlockType::ResultExnRef => vec![ValueType::ExnRef],
             BlockType::TypeIndex(type_index) => {
                 let vm_module = &vm.resource.vm_modules[vm_module_index];
                 let function_type = &vm_module.function_types[*type_index as usize];
@@ -89,60 +85,35 @@ pub fn process_end(
         }
     };
 
-    // 判断操作数是否足够当前函数或结构块用于返回
+    // 判断操作数是否足够当前函数用于返回
     let results_count = result_types.len();
     let stack_size = vm.stack.get_size();
     let operands_count = stack_size - vm.status.base_pointer - INFO_SEGMENT_ITEM_COUNT;
     if operands_count < results_count {
-        if let Some(block_index) = option_block_index {
-            return Err(EngineError::InvalidOperation(
-                InvalidOperation::NotEnoughOperandForBlockResult {
-                    vm_module_index,
-                    function_index,
-                    block_index: *block_index,
-                    results_count,
-                    operands_count,
-                },
-            ));
-        } else {
-            return Err(EngineError::InvalidOperation(
-                InvalidOperation::NotEnoughOperandForFunctionResult {
-                    vm_module_index,
-                    function_index,
-                    results_count,
-                    operands_count,
-                },
-            ));
-        }
+        return Err(EngineError::InvalidOperation(
+            InvalidOperation::NotEnoughOperandForFunctionResult {
+                vm_module_index,
+                function_index,
+                results_count,
+                operands_count,
+            },
+        ));
     }
 
     // 判断返回值的数据类型
-    let results = vm.stack.peek_values(results_count);
-    match check_value_types(results, &result_types) {
+    let value_types = vm.stack.peek_types(results_count);
+    match check_types(&result_types, value_types) {
         Err(ValueTypeCheckError::LengthMismatch) => unreachable!("argument count should be match"),
         Err(ValueTypeCheckError::DataTypeMismatch(result_index)) => {
-            if let Some(block_index) = option_block_index {
-                return Err(EngineError::TypeMismatch(
-                    TypeMismatch::BlockResultTypeMismatch {
-                        vm_module_index,
-                        function_index,
-                        block_index: *block_index,
-                        result_index,
-                        result_type: result_types[result_index].clone(),
-                        value_type: results[result_index].get_type(),
-                    },
-                ));
-            } else {
-                return Err(EngineError::TypeMismatch(
-                    TypeMismatch::FunctionResultTypeMismatch {
-                        vm_module_index,
-                        function_index,
-                        result_index,
-                        result_type: result_types[result_index].clone(),
-                        value_type: results[result_index].get_type(),
-                    },
-                ));
-            }
+            return Err(EngineError::TypeMismatch(
+                TypeMismatch::FunctionResultTypeMismatch {
+                    vm_module_index,
+                    function_index,
+                    result_index,
+                    result_type: result_types[result_index].clone(),
+                    value_type: value_types[result_index].clone(),
+                },
+            ));
         }
         _ => {
             // pass
@@ -161,7 +132,6 @@ pub fn process_end(
         Ok(ControlResult::ProgramEnd)
     } else {
         Ok(ControlResult::PopStackFrame {
-            is_call_frame: is_call_frame,
             vm_module_index,
             function_index,
             frame_type,
@@ -0,0 +1,282 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 异常处理指令
+//!
+//! ## try_table 指令
+//!
+//! try_table 结构块跟 block 结构块一样，进入结构块时不需要任何操作，
+//! 不同的是 try_table 指令带有一组 catch 子句：
+//!
+//! - `catch tag label`：捕获指定标签的异常，把异常携带的数值带到目标结构块；
+//! - `catch_ref tag label`：跟 `catch` 一样，不过还会把异常的引用（exnref）带到目标结构块；
+//! - `catch_all label`：捕获所有异常，不携带任何数值；
+//! - `catch_all_ref label`：捕获所有异常，只携带异常的引用。
+//!
+//! ## throw/throw_ref 指令
+//!
+//! 结构块没有栈帧，所以抛出异常时根据指令的位置来查找包含该指令的 try_table 结构块，
+//! 从当前函数开始逐层往外查找：
+//!
+//! - 在当前函数里，从内往外逐个检查包含当前指令的 try_table 结构块，如果有匹配的 catch 子句，
+//!   则把相应的数值压入栈，然后跳转到 catch 子句的目标结构块，
+//!   就像在 try_table 结构块之外执行了 br 指令一样；
+//! - 否则弹出当前函数的调用帧，以调用者的 `call 指令` 作为当前指令，继续检查调用者；
+//! - 如果所有栈帧都已经弹出，则说明异常没有被捕获，返回 `EngineError::UncaughtException`。
+//!
+//! 本地函数也可以通过返回 `NativeError::Exception` 抛出异常，该异常跟 throw 指令
+//! 抛出的异常一样，可以被 WebAssembly 的 try_table 结构块捕获。
+//!
+//! ## 异常对象的释放
+//!
+//! 由 throw 指令（或者本地函数）抛出的异常对象，如果被 `catch` 或者 `catch_all` 子句捕获，
+//! 或者没有被捕获，则不会再被访问，所以会被释放，它在异常列表里的位置留给新的异常对象使用。
+//! 被 `catch_ref` 或者 `catch_all_ref` 子句捕获的异常对象，以及由 throw_ref 指令抛出的
+//! 异常对象，因为异常引用有可能被保存在其他地方，所以不会被释放。
+
+use std::mem;
+
+use anvm_ast::{
+    instruction::BlockType,
+    types::{check_value_types, Value, ValueType},
+};
+
+use crate::{
+    error::{make_operand_data_types_mismatch_engine_error, EngineError, InvalidOperation},
+    ins_block,
+    ins_control::ControlResult,
+    object::{BlockItem, CatchItem, Control, FunctionItem, Instruction},
+    vm::{INITIAL_FRAME_POINTER, VM},
+    vm_exception::VMException,
+    vm_stack::INFO_SEGMENT_ITEM_COUNT,
+};
+
+/// 处理原 `try_table 指令`
+///
+/// 进入 try_table 结构块跟进入 block 结构块一样，catch 子句只在抛出异常时才会用到。
+pub fn try_table(
+    vm: &mut VM,
+    block_type: &BlockType,
+    block_index: usize,
+    end_address: usize,
+) -> Result<ControlResult, EngineError> {
+    ins_block::block(vm, block_type, block_index, end_address)
+}
+
+/// 处理原 `throw 指令`
+pub fn throw(vm: &mut VM, tag_index: usize) -> Result<ControlResult, EngineError> {
+    let vm_module_index = vm.status.vm_module_index;
+    let instance_tag_index = vm.resource.vm_modules[vm_module_index].tag_indexes[tag_index];
+    let parameter_types = vm.resource.tags[instance_tag_index].params.clone();
+
+    // 判断操作数是否足够异常携带的数值
+    let parameters_count = parameter_types.len();
+    let stack_size = vm.stack.get_size();
+    let operands_count = stack_size - vm.status.base_pointer - INFO_SEGMENT_ITEM_COUNT;
+    if operands_count < parameters_count {
+        let operands = vm.stack.peek_values(operands_count);
+        return Err(make_operand_data_types_mismatch_engine_error(
+            "throw",
+            parameter_types,
+            operands.iter().collect::<Vec<&Value>>(),
+        ));
+    }
+
+    let values = vm.stack.pop_values(parameters_count);
+
+    // 核对数值的数据类型
+    if check_value_types(&values, &parameter_types).is_err() {
+        return Err(make_operand_data_types_mismatch_engine_error(
+            "throw",
+            parameter_types,
+            values.iter().collect::<Vec<&Value>>(),
+        ));
+    }
+
+    let exception_index = add_exception(vm, VMException::new(instance_tag_index, values));
+    unwind(vm, exception_index, false)
+}
+
+/// 处理原 `throw_ref 指令`
+pub fn throw_ref(vm: &mut VM) -> Result<ControlResult, EngineError> {
+    let value = vm.stack.pop();
+
+    match value {
+        Value::ExnRef(Some(exception_index)) => unwind(vm, exception_index as usize, true),
+        Value::ExnRef(None) => Err(EngineError::InvalidOperation(
+            InvalidOperation::NullExceptionReference,
+        )),
+        _ => Err(make_operand_data_types_mismatch_engine_error(
+            "throw_ref",
+            vec![ValueType::ExnRef],
+            vec![&value],
+        )),
+    }
+}
+
+/// 抛出本地函数的异常
+///
+/// 参数 tag_index 是标签在本地模块里的索引
+pub fn throw_native_exception(
+    vm: &mut VM,
+    native_module_index: usize,
+    tag_index: usize,
+    values: Vec<Value>,
+) -> Result<ControlResult, EngineError> {
+    let instance_tag_index = vm.get_native_tag_index(native_module_index, tag_index);
+    let exception_index = add_exception(vm, VMException::new(instance_tag_index, values));
+    unwind(vm, exception_index, false)
+}
+
+/// 添加异常对象，优先使用已被释放的位置
+fn add_exception(vm: &mut VM, exception: VMException) -> usize {
+    let resource = &mut vm.resource;
+    match resource.free_exception_indexes.pop() {
+        Some(exception_index) => {
+            resource.exceptions[exception_index] = exception;
+            exception_index
+        }
+        None => {
+            resource.exceptions.push(exception);
+            resource.exceptions.len() - 1
+        }
+    }
+}
+
+/// 释放异常对象，并取出异常对象的内容
+fn remove_exception(vm: &mut VM, exception_index: usize) -> VMException {
+    let resource = &mut vm.resource;
+    let exception = &mut resource.exceptions[exception_index];
+    let removed_exception = VMException::new(exception.tag_index, mem::take(&mut exception.values));
+    resource.free_exception_indexes.push(exception_index);
+    removed_exception
+}
+
+/// 逐层弹出栈帧，直到找到能捕获异常的 try_table 结构块
+///
+/// 参数 is_referenced 表示异常对象是否有可能被异常引用所引用。
+fn unwind(
+    vm: &mut VM,
+    exception_index: usize,
+    is_referenced: bool,
+) -> Result<ControlResult, EngineError> {
+    // 抛出异常的指令的位置，
+    // 对于调用者来说，则是 `call 指令` 的位置
+    let mut address = vm.status.address;
+
+    loop {
+        // 所有栈帧都已经弹出，异常没有被捕获
+        if vm.status.frame_pointer == INITIAL_FRAME_POINTER {
+            let exception = if is_referenced {
+                vm.resource.exceptions[exception_index].clone()
+            } else {
+                remove_exception(vm, exception_index)
+            };
+            return Err(EngineError::UncaughtException(exception));
+        }
+
+        if let Some((block_index, catch_item)) = find_catch_item(vm, exception_index, address) {
+            return catch(vm, exception_index, is_referenced, block_index, &catch_item);
+        }
+
+        let (vm_module_index, function_index, frame_type, return_address) = vm.pop_frame(0);
+
+        let status = &mut vm.status;
+        status.vm_module_index = vm_module_index;
+        status.function_index = function_index;
+        status.frame_type = frame_type;
+
+        // 返回地址是 `call 指令` 的下一个指令
+        address = return_address.wrapping_sub(1);
+    }
+}
+
+/// 在当前函数里从内往外查找包含指定位置的 try_table 结构块，
+/// 并返回第一个能捕获异常的 catch 子句
+fn find_catch_item(vm: &VM, exception_index: usize, address: usize) -> Option<(usize, CatchItem)> {
+    let status = &vm.status;
+    let vm_module = &vm.resource.vm_modules[status.vm_module_index];
+
+    let (function_start_address, block_items) =
+        match &vm_module.function_items[status.function_index] {
+            FunctionItem::Normal {
+                start_address,
+                block_items,
+                ..
+            } => (*start_address, block_items),
+            _ => unreachable!("should be normal \"function\" item"),
+        };
+
+    let exception_tag_index = vm.resource.exceptions[exception_index].tag_index;
+
+    // 结构块按照开始位置排列，所以包含指定位置的结构块当中，
+    // 开始位置越靠后的结构块越靠内层
+    block_items
+        .iter()
+        .enumerate()
+        .rev()
+        .filter_map(|(block_index, block_item)| match block_item {
+            BlockItem::Block {
+                start_address,
+                end_address,
+                ..
+            } if function_start_address + start_address < address
+                && address < function_start_address + end_address =>
+            {
+                match &vm_module.instructions[function_start_address + start_address] {
+                    Instruction::Control(Control::TryTable { catch_items, .. }) => {
+                        Some((block_index, catch_items))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .find_map(|(block_index, catch_items)| {
+            catch_items
+                .iter()
+                .find(|catch_item| match catch_item.option_tag_index {
+                    Some(tag_index) => vm_module.tag_indexes[tag_index] == exception_tag_index,
+                    None => true,
+                })
+                .map(|catch_item| (block_index, catch_item.clone()))
+        })
+}
+
+/// 捕获异常
+///
+/// 压入 catch 子句需要携带的数值之后，相当于在 try_table 结构块之外执行了一次 br 指令。
+fn catch(
+    vm: &mut VM,
+    exception_index: usize,
+    is_referenced: bool,
+    block_index: usize,
+    catch_item: &CatchItem,
+) -> Result<ControlResult, EngineError> {
+    let mut values = if catch_item.is_ref || is_referenced {
+        if catch_item.option_tag_index.is_some() {
+            vm.resource.exceptions[exception_index].values.clone()
+        } else {
+            vec![]
+        }
+    } else {
+        // 异常对象在被 `catch` 或者 `catch_all` 子句捕获之后不会再被访问
+        let exception = remove_exception(vm, exception_index);
+        if catch_item.option_tag_index.is_some() {
+            exception.values
+        } else {
+            vec![]
+        }
+    };
+
+    if catch_item.is_ref {
+        values.push(Value::ExnRef(Some(exception_index as u32)));
+    }
+
+    vm.stack.push_values(&values);
+
+    ins_block::process_break(vm, Some(block_index), &catch_item.branch_target)
+}
@@ -44,6 +44,12 @@
 //!
 //! return 指令相当于 br 指令跳到函数本身一层，比如当 return 出现在一层 block 里，
 //! 作用相当于 `br 1`。
+//!
+//! ## return_call 和 return_call_indirect 指令
+//!
+//! 尾调用指令，格式跟 call 和 call_indirect 指令一样，作用相当于调用目标函数之后
+//! 紧接着执行 return 指令。不同的是尾调用不会压入新的调用帧，而是用目标函数的
+//! 调用帧替换当前函数的调用帧，所以（相互）递归的尾调用不会耗尽调用栈。
 
 //! # 调用函数的过程
 //!
@@ -134,12 +140,13 @@ use anvm_ast::{
 use crate::{
     error::{
         make_operand_data_types_mismatch_engine_error, EngineError, InvalidOperation,
-        ObjectNotFound, TypeMismatch, Unsupported,
+        NativeError, NativeTerminate, ObjectNotFound, Overflow, TypeMismatch,
     },
-    ins_control::ControlResult,
+    ins_control::{self, ControlResult},
+    ins_exception,
     object::FunctionItem,
     vm::VM,
-    vm_stack::INFO_SEGMENT_ITEM_COUNT,
+    vm_stack::{INFO_SEGMENT_ITEM_COUNT, MAX_STACK_SIZE},
 };
 
 pub fn call(
@@ -158,40 +165,13 @@ pub fn call(
         (&function_type.params, local_variable_types.to_owned())
     };
 
-    // 判断操作数是否足够当前函数或结构块用于返回
     let parameters_count = parameter_types.len();
-    let stack_size = vm.stack.get_size();
-    let operands_count = stack_size - vm.status.base_pointer - INFO_SEGMENT_ITEM_COUNT;
-    if operands_count < parameters_count {
-        return Err(EngineError::InvalidOperation(
-            InvalidOperation::NotEnoughOperandForFunctionCall {
-                vm_module_index,
-                function_index,
-                parameters_count,
-                operands_count,
-            },
-        ));
-    }
+    check_arguments(vm, vm_module_index, function_index, parameter_types)?;
 
-    let arguments = vm.stack.peek_values(parameters_count);
-
-    // 核对实参的数据类型和数量
-    match check_value_types(arguments, parameter_types) {
-        Err(ValueTypeCheckError::LengthMismatch) => unreachable!("argument count should be match"),
-        Err(ValueTypeCheckError::DataTypeMismatch(parameter_index)) => {
-            return Err(EngineError::TypeMismatch(
-                TypeMismatch::FunctionCallArgumentTypeMismatch {
-                    vm_module_index,
-                    function_index,
-                    parameter_index,
-                    parameter_type: parameter_types[parameter_index].clone(),
-                    value_type: arguments[parameter_index].get_type(),
-                },
-            ));
-        }
-        _ => {
-            // pass
-        }
+    // 检查栈是否溢出
+    let stack_size = vm.stack.get_size();
+    if stack_size + INFO_SEGMENT_ITEM_COUNT + local_variable_types.len() > MAX_STACK_SIZE {
+        return Err(EngineError::Overflow(Overflow::StackOverflow(MAX_STACK_SIZE)));
     }
 
     // 压入调用栈
@@ -201,7 +181,6 @@ pub fn call(
 
     // 返回新的状态信息，让调用者更新虚拟机状态
     let control_result = ControlResult::PushStackFrame {
-        is_call_frame: true,
         vm_module_index,
         function_index,
         frame_type: BlockType::TypeIndex(type_index as u32),
@@ -211,6 +190,46 @@ pub fn call(
     Ok(control_result)
 }
 
+/// 核对栈顶的实参的数量以及数据类型
+fn check_arguments(
+    vm: &VM,
+    vm_module_index: usize,
+    function_index: usize,
+    parameter_types: &[ValueType],
+) -> Result<(), EngineError> {
+    // 判断操作数是否足够当前函数的调用
+    let parameters_count = parameter_types.len();
+    let stack_size = vm.stack.get_size();
+    let operands_count = stack_size - vm.status.base_pointer - INFO_SEGMENT_ITEM_COUNT;
+    if operands_count < parameters_count {
+        return Err(EngineError::InvalidOperation(
+            InvalidOperation::NotEnoughOperandForFunctionCall {
+                vm_module_index,
+                function_index,
+                parameters_count,
+                operands_count,
+            },
+        ));
+    }
+
+    let value_types = vm.stack.peek_types(parameters_count);
+
+    // 核对实参的数据类型和数量
+    match check_types(parameter_types, value_types) {
+        Err(ValueTypeCheckError::LengthMismatch) => unreachable!("argument count should be match"),
+        Err(ValueTypeCheckError::DataTypeMismatch(parameter_index)) => Err(
+            EngineError::TypeMismatch(TypeMismatch::FunctionCallArgumentTypeMismatch {
+                vm_module_index,
+                function_index,
+                parameter_index,
+                parameter_type: parameter_types[parameter_index].clone(),
+                value_type: value_types[parameter_index].clone(),
+            }),
+        ),
+        _ => Ok(()),
+    }
+}
+
 /// 从模块内部函数调用本地函数的过程。
 ///
 /// 示例：
@@ -297,28 +316,31 @@ pub fn call_native(
             // 执行下一个指令即可。
             Ok(ControlResult::Sequence)
         }
+        Err(NativeTerminate {
+            module_name: _,
+            native_error: NativeError::Exception(tag_index, values),
+        }) => {
+            // 本地函数抛出了异常，从当前栈帧开始查找能捕获该异常的 try_table 结构块
+            ins_exception::throw_native_exception(vm, native_module_index, tag_index, values)
+        }
         Err(e) => Err(EngineError::NativeTerminate(e)),
     }
 }
 
-pub fn call_indirect(
+/// 从表里获取间接调用的目标函数，并核对函数的签名
+fn get_indirect_function_item(
     vm: &mut VM,
+    instruction_name: &str,
     type_index: usize,
     table_index: usize,
-) -> Result<ControlResult, EngineError> {
-    if table_index != 0 {
-        return Err(EngineError::Unsupported(
-            Unsupported::UnsupportedMultipleTable,
-        ));
-    }
-
+) -> Result<FunctionItem, EngineError> {
     let element_index = {
         let element_index_value = vm.stack.pop();
         match element_index_value {
             Value::I32(index) => index as usize,
             _ => {
                 return Err(make_operand_data_types_mismatch_engine_error(
-                    "call_indirect",
+                    instruction_name,
                     vec![ValueType::I32],
                     vec![&element_index_value],
                 ));
@@ -329,12 +351,27 @@ pub fn call_indirect(
     let (/* vm_module_index, function_index, */ function_item, expected_function_type) = {
         let vm_module_index = vm.status.vm_module_index;
         let vm_module = &vm.resource.vm_modules[vm_module_index];
-        let table_index = vm_module.table_index;
-        let table = &vm.resource.tables[table_index];
+        let instance_table_index = vm_module.table_indexes[table_index];
+        let table = &vm.resource.tables[instance_table_index];
 
+        // 空槽的值是 null 引用。
+        // 函数引用指向的函数可能属于其他模块，而期望的函数类型则属于当前模块。
         let element_item = table.get_element(element_index)?;
-        let function_index = match element_item {
-            Some(index) => index as usize,
+        let (owner_vm_module_index, function_index) = match element_item {
+            Value::FuncRef(Some(function_reference)) => {
+                match vm.resolve_function_reference(function_reference) {
+                    Some(location) => location,
+                    None => {
+                        return Err(EngineError::ObjectNotFound(
+                            ObjectNotFound::ElementItemNotFound(
+                                vm_module_index,
+                                table_index,
+                                element_index,
+                            ),
+                        ))
+                    }
+                }
+            }
             _ => {
                 return Err(EngineError::ObjectNotFound(
                     ObjectNotFound::ElementItemNotFound(
@@ -346,7 +383,8 @@ pub fn call_indirect(
             }
         };
 
-        let function_item = &vm_module.function_items[function_index];
+        let owner_vm_module = &vm.resource.vm_modules[owner_vm_module_index];
+        let function_item = &owner_vm_module.function_items[function_index];
         let expected_function_type = &vm_module.function_types[type_index];
 
         (function_item.to_owned(), expected_function_type.to_owned())
@@ -412,6 +450,16 @@ pub fn call_indirect(
         }
     }
 
+    Ok(function_item)
+}
+
+pub fn call_indirect(
+    vm: &mut VM,
+    type_index: usize,
+    table_index: usize,
+) -> Result<ControlResult, EngineError> {
+    let function_item = get_indirect_function_item(vm, "call_indirect", type_index, table_index)?;
+
     // 调用处理函数
     match &function_item {
         FunctionItem::Normal {
@@ -437,3 +485,104 @@ pub fn call_indirect(
         } => call_native(vm, *native_module_index, *type_index, *function_index),
     }
 }
+
+/// 尾调用（普通）函数
+///
+/// 跟 `call` 指令不同的是，尾调用不压入新的调用帧，
+/// 而是用目标函数的调用帧替换当前函数的调用帧。目标函数执行完毕之后，
+/// 会直接返回到当前函数的调用者，所以深度的（相互）递归尾调用不会导致栈溢出。
+pub fn return_call(
+    vm: &mut VM,
+    vm_module_index: usize,
+    type_index: usize,
+    function_index: usize,
+    internal_function_index: usize,
+    address: usize,
+) -> Result<ControlResult, EngineError> {
+    let (parameter_types, local_variable_types) = {
+        let vm_module = &vm.resource.vm_modules[vm_module_index];
+        let function_type = &vm_module.function_types[type_index];
+        let local_variable_types =
+            &vm_module.internal_function_local_variable_types_list[internal_function_index];
+        (
+            function_type.params.to_owned(),
+            local_variable_types.to_owned(),
+        )
+    };
+
+    let parameters_count = parameter_types.len();
+    check_arguments(vm, vm_module_index, function_index, &parameter_types)?;
+
+    // 检查栈是否溢出
+    let frame_size = parameters_count + local_variable_types.len() + INFO_SEGMENT_ITEM_COUNT;
+    if vm.status.frame_pointer + frame_size > MAX_STACK_SIZE {
+        return Err(EngineError::Overflow(Overflow::StackOverflow(
+            MAX_STACK_SIZE,
+        )));
+    }
+
+    // 替换调用帧，返回地址沿用当前调用帧的
+    vm.replace_call_frame(parameters_count, &local_variable_types);
+
+    // 返回新的状态信息，让调用者更新虚拟机状态
+    let control_result = ControlResult::PushStackFrame {
+        vm_module_index,
+        function_index,
+        frame_type: BlockType::TypeIndex(type_index as u32),
+        address,
+    };
+
+    Ok(control_result)
+}
+
+/// 尾调用本地函数
+///
+/// 本地函数不使用调用帧，所以尾调用本地函数相当于先调用本地函数，
+/// 然后再执行 `return` 指令。
+pub fn return_call_native(
+    vm: &mut VM,
+    native_module_index: usize,
+    type_index: usize,
+    function_index: usize,
+) -> Result<ControlResult, EngineError> {
+    match call_native(vm, native_module_index, type_index, function_index)? {
+        // 这里相当于执行函数的最后一条指令（即 `end 指令`）
+        ControlResult::Sequence => ins_control::process_end(vm, &None),
+
+        // 本地函数抛出的异常已经被捕获
+        control_result => Ok(control_result),
+    }
+}
+
+pub fn return_call_indirect(
+    vm: &mut VM,
+    type_index: usize,
+    table_index: usize,
+) -> Result<ControlResult, EngineError> {
+    let function_item =
+        get_indirect_function_item(vm, "return_call_indirect", type_index, table_index)?;
+
+    match &function_item {
+        FunctionItem::Normal {
+            vm_module_index,
+            type_index,
+            function_index,
+            internal_function_index,
+            start_address,
+            end_address: _,
+            block_items: _,
+        } => return_call(
+            vm,
+            *vm_module_index,
+            *type_index,
+            *function_index,
+            *internal_function_index,
+            *start_address,
+        ),
+        FunctionItem::Native {
+            native_module_index,
+            type_index,
+            function_index,
+        } => return_call_native(vm, *native_module_index, *type_index, *function_index),
+    }
+}
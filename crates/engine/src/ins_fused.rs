@@ -0,0 +1,66 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 超级指令
+//!
+//! 由解码器融合而成的超级指令，执行效果跟依次执行被融合的原指令相同。
+//!
+//! - local.get a; local.get b; i32.add
+//! - local.get a; i32.const c; i32.add; local.set b
+//! - i32.const c; i32.lt_s; br_if
+//!
+//! 局部变量的读写直接访问栈槽，所以只能用于非 v128 类型的局部变量。
+
+use anvm_ast::types::ValueType;
+
+use crate::{
+    error::EngineError, ins_block, ins_control::ControlResult, object::BranchTarget, vm::VM,
+};
+
+#[inline]
+fn read_local_i32(vm: &VM, index: u32) -> i32 {
+    let offset = vm.status.local_pointer + (index as usize);
+    vm.stack.get_slot(offset) as u32 as i32
+}
+
+pub fn local_get_local_get_i32_add(
+    vm: &mut VM,
+    left_index: u32,
+    right_index: u32,
+) -> Result<(), EngineError> {
+    let left = read_local_i32(vm, left_index);
+    let right = read_local_i32(vm, right_index);
+    vm.stack.push_i32(left.wrapping_add(right));
+    Ok(())
+}
+
+pub fn local_get_i32_const_i32_add_local_set(
+    vm: &mut VM,
+    source_index: u32,
+    value: i32,
+    dest_index: u32,
+) -> Result<(), EngineError> {
+    let result = read_local_i32(vm, source_index).wrapping_add(value);
+    let offset = vm.status.local_pointer + (dest_index as usize);
+    vm.stack
+        .set_slot(offset, result as u32 as u64, ValueType::I32);
+    Ok(())
+}
+
+pub fn i32_const_i32_lt_s_break_when_not_eq_zero(
+    vm: &mut VM,
+    value: i32,
+    option_block_index: Option<usize>,
+    branch_target: &BranchTarget,
+) -> Result<ControlResult, EngineError> {
+    let left = vm.stack.pop_i32();
+
+    if left < value {
+        ins_block::process_break(vm, option_block_index, branch_target)
+    } else {
+        Ok(ControlResult::Sequence)
+    }
+}
@@ -28,6 +28,9 @@
 //!   After a data segment is dropped its data can no longer be retrieved,
 //!   so the memory used by this segment may be freed.
 //!
+//! 批量操作（fill、copy 以及 init）的地址以及长度的数据类型跟内存块的地址类型一致
+//! （`memory.init` 的数据段位置和长度除外，它们总是 i32），访问越界时不会修改内存的内容。
+//!
 //! https://webassembly.github.io/spec/core/syntax/instructions.html#syntax-instr-memory
 //!
 //! ## 加载指令
@@ -111,584 +114,589 @@
 use anvm_ast::{instruction::MemoryArgument, types::Value, types::ValueType};
 
 use crate::{
-    error::{EngineError, TypeMismatch, Unsupported, make_operand_data_types_mismatch_engine_error},
+    error::{make_operand_data_types_mismatch_engine_error, EngineError, OutOfRange},
     vm::VM,
     vm_memory::VMMemory,
     vm_stack::VMStack,
 };
 
-pub fn memory_size(vm: &mut VM, memory_block_index: u32) -> Result<(), EngineError> {
-    if memory_block_index != 0 {
-        return Err(EngineError::Unsupported(
-            Unsupported::UnsupportedMultipleMemoryBlock,
-        ));
+/// 预先解析的内存读写参数
+///
+/// 指令里的内存块索引是内存块在当前模块里的索引，执行时需要先通过当前模块的
+/// 内存块索引列表转换为内存块实例的索引。编译字节码时预先完成这个转换，
+/// 从而省去每次执行加载或存储指令时的查询过程。
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct MemoryAccess {
+    /// 内存块在 VM 内存块实例列表里的索引
+    pub instance_memory_block_index: usize,
+
+    /// 偏移值
+    pub offset: u64,
+}
+
+impl MemoryAccess {
+    pub fn new(instance_memory_block_index: usize, offset: u64) -> Self {
+        Self {
+            instance_memory_block_index,
+            offset,
+        }
     }
+}
 
-    let instance_memory_block_index =
-        vm.resource.vm_modules[vm.status.vm_module_index].memory_index;
-    let memory_block = &mut vm.resource.memory_blocks[instance_memory_block_index];
+/// 将指令里的内存参数转换为预先解析的内存读写参数
+pub fn resolve_memory_access(vm: &VM, memory_args: &MemoryArgument) -> MemoryAccess {
+    MemoryAccess::new(
+        get_instance_memory_block_index(vm, memory_args.memory_block_index),
+        memory_args.offset,
+    )
+}
+
+pub fn memory_size(vm: &mut VM, memory_block_index: u32) -> Result<(), EngineError> {
+    let memory_block = get_memory_block(vm, memory_block_index);
     let page_count = memory_block.get_page_count();
 
+    // 64 位内存块的页面数是 i64
+    let value = if memory_block.is_memory64() {
+        Value::I64(page_count as i64)
+    } else {
+        Value::I32(page_count as i32)
+    };
+
     let stack = &mut vm.stack;
-    stack.push(Value::I32(page_count as i32));
+    stack.push(value);
 
     Ok(())
 }
 
 pub fn memory_grow(vm: &mut VM, memory_block_index: u32) -> Result<(), EngineError> {
-    if memory_block_index != 0 {
-        return Err(EngineError::Unsupported(
-            Unsupported::UnsupportedMultipleMemoryBlock,
-        ));
-    }
-
-    let stack = &mut vm.stack;
-    let increase_number = stack.pop();
+    let increase_number = vm.stack.pop();
 
-    let instance_memory_block_index =
-        vm.resource.vm_modules[vm.status.vm_module_index].memory_index;
+    let instance_memory_block_index = get_instance_memory_block_index(vm, memory_block_index);
     let memory_block = &mut vm.resource.memory_blocks[instance_memory_block_index];
+    let stack = &mut vm.stack;
 
-    if let Value::I32(value) = increase_number {
-        let result = memory_block.increase_page(value as u32);
-        match result {
-            Ok(previous_page_count) => {
-                stack.push(Value::I32(previous_page_count as i32));
-            }
-            _ => {
-                stack.push(Value::I32(-1));
-            }
+    // 64 位内存块的增加量以及返回值都是 i64
+    let memory64 = memory_block.is_memory64();
+    let increase_page_number = match (memory64, &increase_number) {
+        (false, Value::I32(value)) => *value as u32 as u64,
+        (true, Value::I64(value)) => *value as u64,
+        _ => {
+            return Err(make_operand_data_types_mismatch_engine_error(
+                "memory.grow",
+                vec![memory_block.get_memory_type().get_address_type()],
+                vec![&increase_number],
+            ))
         }
+    };
 
-        Ok(())
+    let result = match memory_block.increase_page(increase_page_number) {
+        Ok(previous_page_count) => previous_page_count as i64,
+        _ => -1,
+    };
+
+    if memory64 {
+        stack.push(Value::I64(result));
     } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "memory.grow",
-            vec![ValueType::I32],
-            vec![&increase_number],
-        ))
+        stack.push(Value::I32(result as i32));
     }
-}
 
-/// 计算有效内存地址，即内存读写指令最终所访问内存的实际地址。
-///
-/// 注意，
-/// 因为指令中的 offset 立即数是 u32，而操作数栈弹出的值也是 i32（实际是 u32），
-/// 所以有效地址是一个 33 位（u32 + u32）的无符号整数，实际的值有可能会超出了 u32 的范围。
-fn get_effective_address(immediate_offset: u32, operand_address: i32) -> usize {
-    (immediate_offset + operand_address as u32) as usize
+    Ok(())
 }
 
-fn get_memory_load_access_meterial<'a>(
-    vm: &'a mut VM,
-    memory_args: &MemoryArgument,
-) -> Result<(&'a mut VMMemory, &'a mut VMStack, usize), Value> {
-    let stack = &mut vm.stack;
-    let instance_memory_block_index =
-        vm.resource.vm_modules[vm.status.vm_module_index].memory_index;
-    let memory_block = &mut vm.resource.memory_blocks[instance_memory_block_index];
+pub fn memory_fill(vm: &mut VM, memory_block_index: u32) -> Result<(), EngineError> {
+    let instance_memory_block_index = get_instance_memory_block_index(vm, memory_block_index);
+    let memory_access = MemoryAccess::new(instance_memory_block_index, 0);
 
-    // MemoryArg 里头的 align 暂时无用
-    let immediate_offset = memory_args.offset;
-    let address_value = stack.pop();
+    let length = pop_address(vm, "memory.fill", &memory_access)?;
+    let value = pop_u32(vm, "memory.fill")?;
+    let address = pop_address(vm, "memory.fill", &memory_access)?;
 
-    if let Value::I32(address) = address_value {
-        let effective_address = get_effective_address(immediate_offset, address);
-        Ok((memory_block, stack, effective_address))
+    let memory_block = &mut vm.resource.memory_blocks[instance_memory_block_index];
+    let address = check_memory_range(memory_block, address, length)?;
+    memory_block.write_bytes(address, &vec![value as u8; length as usize]);
+    Ok(())
+}
+
+pub fn memory_copy(
+    vm: &mut VM,
+    dest_memory_block_index: u32,
+    source_memory_block_index: u32,
+) -> Result<(), EngineError> {
+    let dest_access = MemoryAccess::new(
+        get_instance_memory_block_index(vm, dest_memory_block_index),
+        0,
+    );
+    let source_access = MemoryAccess::new(
+        get_instance_memory_block_index(vm, source_memory_block_index),
+        0,
+    );
+
+    // 当两个内存块其中一个是 32 位内存块时，复制的长度是 i32
+    let memory_blocks = &vm.resource.memory_blocks;
+    let length_access = if memory_blocks[dest_access.instance_memory_block_index].is_memory64() {
+        source_access
     } else {
-        // 参数错误时，返回参数值
-        Err(address_value)
-    }
+        dest_access
+    };
+
+    let length = pop_address(vm, "memory.copy", &length_access)?;
+    let source_address = pop_address(vm, "memory.copy", &source_access)?;
+    let dest_address = pop_address(vm, "memory.copy", &dest_access)?;
+
+    let memory_blocks = &mut vm.resource.memory_blocks;
+    let source_address = check_memory_range(
+        &memory_blocks[source_access.instance_memory_block_index],
+        source_address,
+        length,
+    )?;
+    let dest_address = check_memory_range(
+        &memory_blocks[dest_access.instance_memory_block_index],
+        dest_address,
+        length,
+    )?;
+
+    // 源区间和目标区间允许重叠，所以先读取源区间的数据，再写入目标区间
+    let data = memory_blocks[source_access.instance_memory_block_index]
+        .read_bytes(source_address, length as usize);
+    memory_blocks[dest_access.instance_memory_block_index].write_bytes(dest_address, &data);
+    Ok(())
 }
 
-fn get_memory_store_access_meterial<'a>(
-    vm: &'a mut VM,
-    memory_args: &MemoryArgument,
-) -> Result<(&'a mut VMMemory, usize, Value), (Value, Value)> {
-    let stack = &mut vm.stack;
-    let instance_memory_block_index =
-        vm.resource.vm_modules[vm.status.vm_module_index].memory_index;
-    let memory_block = &mut vm.resource.memory_blocks[instance_memory_block_index];
+pub fn memory_init(
+    vm: &mut VM,
+    data_index: u32,
+    memory_block_index: u32,
+) -> Result<(), EngineError> {
+    let instance_memory_block_index = get_instance_memory_block_index(vm, memory_block_index);
+    let memory_access = MemoryAccess::new(instance_memory_block_index, 0);
+
+    let length = pop_u32(vm, "memory.init")? as usize;
+    let source_offset = pop_u32(vm, "memory.init")? as usize;
+    let address = pop_address(vm, "memory.init", &memory_access)?;
+
+    let resource = &mut vm.resource;
+    let data_segment =
+        &resource.vm_modules[vm.status.vm_module_index].data_segments[data_index as usize];
+
+    let data = match source_offset.checked_add(length) {
+        Some(end) if end <= data_segment.len() => &data_segment[source_offset..end],
+        _ => {
+            return Err(EngineError::OutOfRange(
+                OutOfRange::DataSegmentAccessOutOfRange(source_offset, length, data_segment.len()),
+            ))
+        }
+    };
 
-    // 待储存的数据
-    let data_value = stack.pop();
+    let memory_block = &mut resource.memory_blocks[instance_memory_block_index];
+    let address = check_memory_range(memory_block, address, length as u64)?;
+    memory_block.write_bytes(address, data);
+    Ok(())
+}
 
-    // MemoryArg 里头的 align 暂时无用
-    let immediate_offset = memory_args.offset;
-    let address_value = stack.pop();
+pub fn data_drop(vm: &mut VM, data_index: u32) -> Result<(), EngineError> {
+    let vm_module = &mut vm.resource.vm_modules[vm.status.vm_module_index];
+    vm_module.data_segments[data_index as usize] = vec![];
+    Ok(())
+}
 
-    if let Value::I32(address) = address_value {
-        let effective_address = get_effective_address(immediate_offset, address);
-        Ok((memory_block, effective_address, data_value))
-    } else {
-        // 参数错误时，返回参数值
-        Err((data_value, address_value))
-    }
+/// 获取内存块实例的索引
+///
+/// 参数 memory_block_index 是指令里的内存块索引，即内存块在当前模块里的索引。
+fn get_instance_memory_block_index(vm: &VM, memory_block_index: u32) -> usize {
+    vm.resource.vm_modules[vm.status.vm_module_index].memory_indexes[memory_block_index as usize]
 }
 
-fn make_memory_load_operand_data_type_mismatch_engine_error(
-    instruction_name: &str,
-    address_value: &Value,
-) -> EngineError {
-    EngineError::TypeMismatch(TypeMismatch::OperandDataTypeMismatch(
-        instruction_name.to_owned(),
-        vec![ValueType::I32],
-        vec![address_value.get_type()],
-    ))
+/// 获取当前模块的内存块
+fn get_memory_block(vm: &mut VM, memory_block_index: u32) -> &mut VMMemory {
+    let instance_memory_block_index = get_instance_memory_block_index(vm, memory_block_index);
+    &mut vm.resource.memory_blocks[instance_memory_block_index]
 }
 
-fn make_memory_store_operand_data_type_mismatch_engine_error(
+/// 从操作数栈弹出地址，计算有效内存地址（即内存读写指令最终所访问内存的实际地址），
+/// 并检查访问区间 [address, address + length) 是否越界。
+///
+/// 参数 memory_args 是指令里的内存参数，其中的内存块索引是内存块在当前模块里的索引。
+pub fn pop_effective_address(
+    vm: &mut VM,
     instruction_name: &str,
-    expected_data_type: ValueType,
-    data_value: &Value,
-) -> EngineError {
-    EngineError::TypeMismatch(TypeMismatch::OperandDataTypeMismatch(
-        instruction_name.to_owned(),
-        vec![expected_data_type, ValueType::I32],
-        vec![data_value.get_type(), ValueType::I32],
-    ))
+    memory_args: &MemoryArgument,
+    length: usize,
+) -> Result<usize, EngineError> {
+    let memory_access = resolve_memory_access(vm, memory_args);
+    pop_access_effective_address(vm, instruction_name, &memory_access, length)
 }
 
-fn make_memory_store_operand_data_types_mismatch_engine_error(
+/// 从操作数栈弹出地址，计算有效内存地址，并检查访问区间是否越界。
+///
+/// 32 位内存块的地址是 i32（实际是 u32），64 位内存块的地址是 i64（实际是 u64），
+/// 有效地址 = offset + 地址。
+///
+/// 注意，
+/// 对于 32 位内存块，有效地址是一个 33 位（u32 + u32）的无符号整数，所以这里使用 u64 计算，
+/// 对于 64 位内存块，有效地址有可能超出 u64 的范围，此时也视为越界。
+fn pop_access_effective_address(
+    vm: &mut VM,
     instruction_name: &str,
-    expected_data_type: ValueType,
-    data_value: &Value,
-    address_value: &Value,
-) -> EngineError {
-    EngineError::TypeMismatch(TypeMismatch::OperandDataTypeMismatch(
-        instruction_name.to_owned(),
-        vec![expected_data_type, ValueType::I32],
-        vec![data_value.get_type(), address_value.get_type()],
-    ))
+    memory_access: &MemoryAccess,
+    length: usize,
+) -> Result<usize, EngineError> {
+    let address = pop_address(vm, instruction_name, memory_access)?;
+    let memory_block = &vm.resource.memory_blocks[memory_access.instance_memory_block_index];
+    let memory_size = memory_block.get_size_in_bytes();
+
+    match memory_access.offset.checked_add(address) {
+        Some(effective_address)
+            if effective_address
+                .checked_add(length as u64)
+                .is_some_and(|end| end <= memory_size as u64) =>
+        {
+            Ok(effective_address as usize)
+        }
+        _ => Err(EngineError::OutOfRange(OutOfRange::MemoryAccessOutOfRange(
+            memory_access.offset.saturating_add(address) as usize,
+            length,
+            memory_size,
+        ))),
+    }
 }
 
-// i32 load
-
-pub fn i32_load(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
-    match get_memory_load_access_meterial(vm, memory_args) {
-        Ok((memory_block, stack, address)) => {
-            let value = memory_block.read_i32(address);
-            stack.push(Value::I32(value));
-            Ok(())
-        }
-        Err(address_value) => Err(make_memory_load_operand_data_type_mismatch_engine_error(
-            "i32_load",
-            &address_value,
+/// 从操作数栈弹出地址
+fn pop_address(
+    vm: &mut VM,
+    instruction_name: &str,
+    memory_access: &MemoryAccess,
+) -> Result<u64, EngineError> {
+    let address_value = vm.stack.pop();
+    let memory_block = &vm.resource.memory_blocks[memory_access.instance_memory_block_index];
+
+    match (memory_block.is_memory64(), &address_value) {
+        (false, Value::I32(address)) => Ok(*address as u32 as u64),
+        (true, Value::I64(address)) => Ok(*address as u64),
+        _ => Err(make_operand_data_types_mismatch_engine_error(
+            instruction_name,
+            vec![memory_block.get_memory_type().get_address_type()],
+            vec![&address_value],
         )),
     }
 }
 
-pub fn i32_load16_s(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
-    match get_memory_load_access_meterial(vm, memory_args) {
-        Ok((memory_block, stack, address)) => {
-            let value = memory_block.read_i16(address);
-            stack.push(Value::I32(value as i32));
-            Ok(())
-        }
-        Err(address_value) => Err(make_memory_load_operand_data_type_mismatch_engine_error(
-            "i32_load16_s",
-            &address_value,
-        )),
+/// 检查批量操作访问的区间 [address, address + length) 是否越界
+fn check_memory_range(
+    memory_block: &VMMemory,
+    address: u64,
+    length: u64,
+) -> Result<usize, EngineError> {
+    let memory_size = memory_block.get_size_in_bytes();
+
+    match address.checked_add(length) {
+        Some(end) if end <= memory_size as u64 => Ok(address as usize),
+        _ => Err(EngineError::OutOfRange(OutOfRange::MemoryAccessOutOfRange(
+            address as usize,
+            length as usize,
+            memory_size,
+        ))),
     }
 }
 
-pub fn i32_load16_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
-    match get_memory_load_access_meterial(vm, memory_args) {
-        Ok((memory_block, stack, address)) => {
-            let value = memory_block.read_i16(address);
-            stack.push(Value::I32((value as u16) as i32));
-            Ok(())
-        }
-        Err(address_value) => Err(make_memory_load_operand_data_type_mismatch_engine_error(
-            "i32_load16_u",
-            &address_value,
+/// 弹出一个 i32 操作数，并作为无符号整数（比如填充的值、数据段的位置等）看待
+fn pop_u32(vm: &mut VM, instruction_name: &str) -> Result<u32, EngineError> {
+    let value = vm.stack.pop();
+    match value {
+        Value::I32(v) => Ok(v as u32),
+        _ => Err(make_operand_data_types_mismatch_engine_error(
+            instruction_name,
+            vec![ValueType::I32],
+            vec![&value],
         )),
     }
 }
 
-pub fn i32_load8_s(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
-    match get_memory_load_access_meterial(vm, memory_args) {
-        Ok((memory_block, stack, address)) => {
-            let value = memory_block.read_i8(address);
-            stack.push(Value::I32(value as i32));
-            Ok(())
-        }
-        Err(address_value) => Err(make_memory_load_operand_data_type_mismatch_engine_error(
-            "i32_load8_s",
-            &address_value,
-        )),
-    }
+fn get_memory_load_access_meterial<'a>(
+    vm: &'a mut VM,
+    instruction_name: &str,
+    memory_access: &MemoryAccess,
+    length: usize,
+) -> Result<(&'a mut VMMemory, &'a mut VMStack, usize), EngineError> {
+    // MemoryArg 里头的 align 暂时无用，所以 MemoryAccess 并没有保留 align
+    let effective_address =
+        pop_access_effective_address(vm, instruction_name, memory_access, length)?;
+
+    let stack = &mut vm.stack;
+    let memory_block = &mut vm.resource.memory_blocks[memory_access.instance_memory_block_index];
+
+    Ok((memory_block, stack, effective_address))
 }
 
-pub fn i32_load8_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
-    match get_memory_load_access_meterial(vm, memory_args) {
-        Ok((memory_block, stack, address)) => {
-            let value = memory_block.read_i8(address);
-            stack.push(Value::I32((value as u8) as i32));
-            Ok(())
-        }
-        Err(address_value) => Err(make_memory_load_operand_data_type_mismatch_engine_error(
-            "i32_load8_u",
-            &address_value,
-        )),
-    }
+fn get_memory_store_access_meterial<'a>(
+    vm: &'a mut VM,
+    instruction_name: &str,
+    memory_access: &MemoryAccess,
+    length: usize,
+) -> Result<(&'a mut VMMemory, usize, Value), EngineError> {
+    // 待储存的数据
+    let data_value = vm.stack.pop();
+
+    // MemoryArg 里头的 align 暂时无用，所以 MemoryAccess 并没有保留 align
+    let effective_address =
+        pop_access_effective_address(vm, instruction_name, memory_access, length)?;
+    let memory_block = &mut vm.resource.memory_blocks[memory_access.instance_memory_block_index];
+
+    Ok((memory_block, effective_address, data_value))
+}
+
+// i32 load
+
+pub fn i32_load(vm: &mut VM, memory_access: &MemoryAccess) -> Result<(), EngineError> {
+    let (memory_block, stack, address) =
+        get_memory_load_access_meterial(vm, "i32.load", memory_access, 4)?;
+    let value = memory_block.read_i32(address);
+    stack.push_i32(value);
+    Ok(())
+}
+
+pub fn i32_load16_s(vm: &mut VM, memory_access: &MemoryAccess) -> Result<(), EngineError> {
+    let (memory_block, stack, address) =
+        get_memory_load_access_meterial(vm, "i32.load16_s", memory_access, 2)?;
+    let value = memory_block.read_i16(address);
+    stack.push_i32(value as i32);
+    Ok(())
+}
+
+pub fn i32_load16_u(vm: &mut VM, memory_access: &MemoryAccess) -> Result<(), EngineError> {
+    let (memory_block, stack, address) =
+        get_memory_load_access_meterial(vm, "i32.load16_u", memory_access, 2)?;
+    let value = memory_block.read_i16(address);
+    stack.push_i32((value as u16) as i32);
+    Ok(())
+}
+
+pub fn i32_load8_s(vm: &mut VM, memory_access: &MemoryAccess) -> Result<(), EngineError> {
+    let (memory_block, stack, address) =
+        get_memory_load_access_meterial(vm, "i32.load8_s", memory_access, 1)?;
+    let value = memory_block.read_i8(address);
+    stack.push_i32(value as i32);
+    Ok(())
+}
+
+pub fn i32_load8_u(vm: &mut VM, memory_access: &MemoryAccess) -> Result<(), EngineError> {
+    let (memory_block, stack, address) =
+        get_memory_load_access_meterial(vm, "i32.load8_u", memory_access, 1)?;
+    let value = memory_block.read_i8(address);
+    stack.push_i32((value as u8) as i32);
+    Ok(())
 }
 
 // i64 load
 
-pub fn i64_load(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
-    match get_memory_load_access_meterial(vm, memory_args) {
-        Ok((memory_block, stack, address)) => {
-            let value = memory_block.read_i64(address);
-            stack.push(Value::I64(value));
-            Ok(())
-        }
-        Err(address_value) => Err(make_memory_load_operand_data_type_mismatch_engine_error(
-            "i64_load",
-            &address_value,
-        )),
-    }
+pub fn i64_load(vm: &mut VM, memory_access: &MemoryAccess) -> Result<(), EngineError> {
+    let (memory_block, stack, address) =
+        get_memory_load_access_meterial(vm, "i64.load", memory_access, 8)?;
+    let value = memory_block.read_i64(address);
+    stack.push_i64(value);
+    Ok(())
 }
 
-pub fn i64_load32_s(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
-    match get_memory_load_access_meterial(vm, memory_args) {
-        Ok((memory_block, stack, address)) => {
-            let value = memory_block.read_i32(address);
-            stack.push(Value::I64(value as i64));
-            Ok(())
-        }
-        Err(address_value) => Err(make_memory_load_operand_data_type_mismatch_engine_error(
-            "i64_load32_s",
-            &address_value,
-        )),
-    }
+pub fn i64_load32_s(vm: &mut VM, memory_access: &MemoryAccess) -> Result<(), EngineError> {
+    let (memory_block, stack, address) =
+        get_memory_load_access_meterial(vm, "i64.load32_s", memory_access, 4)?;
+    let value = memory_block.read_i32(address);
+    stack.push_i64(value as i64);
+    Ok(())
 }
 
-pub fn i64_load32_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
-    match get_memory_load_access_meterial(vm, memory_args) {
-        Ok((memory_block, stack, address)) => {
-            let value = memory_block.read_i32(address);
-            stack.push(Value::I64((value as u32) as i64));
-            Ok(())
-        }
-        Err(address_value) => Err(make_memory_load_operand_data_type_mismatch_engine_error(
-            "i64_load32_u",
-            &address_value,
-        )),
-    }
+pub fn i64_load32_u(vm: &mut VM, memory_access: &MemoryAccess) -> Result<(), EngineError> {
+    let (memory_block, stack, address) =
+        get_memory_load_access_meterial(vm, "i64.load32_u", memory_access, 4)?;
+    let value = memory_block.read_i32(address);
+    stack.push_i64((value as u32) as i64);
+    Ok(())
 }
 
-pub fn i64_load16_s(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
-    match get_memory_load_access_meterial(vm, memory_args) {
-        Ok((memory_block, stack, address)) => {
-            let value = memory_block.read_i16(address);
-            stack.push(Value::I64(value as i64));
-            Ok(())
-        }
-        Err(address_value) => Err(make_memory_load_operand_data_type_mismatch_engine_error(
-            "i64_load16_s",
-            &address_value,
-        )),
-    }
+pub fn i64_load16_s(vm: &mut VM, memory_access: &MemoryAccess) -> Result<(), EngineError> {
+    let (memory_block, stack, address) =
+        get_memory_load_access_meterial(vm, "i64.load16_s", memory_access, 2)?;
+    let value = memory_block.read_i16(address);
+    stack.push_i64(value as i64);
+    Ok(())
 }
 
-pub fn i64_load16_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
-    match get_memory_load_access_meterial(vm, memory_args) {
-        Ok((memory_block, stack, address)) => {
-            let value = memory_block.read_i16(address);
-            stack.push(Value::I64((value as u16) as i64));
-            Ok(())
-        }
-        Err(address_value) => Err(make_memory_load_operand_data_type_mismatch_engine_error(
-            "i64_load16_u",
-            &address_value,
-        )),
-    }
+pub fn i64_load16_u(vm: &mut VM, memory_access: &MemoryAccess) -> Result<(), EngineError> {
+    let (memory_block, stack, address) =
+        get_memory_load_access_meterial(vm, "i64.load16_u", memory_access, 2)?;
+    let value = memory_block.read_i16(address);
+    stack.push_i64((value as u16) as i64);
+    Ok(())
 }
 
-pub fn i64_load8_s(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
-    match get_memory_load_access_meterial(vm, memory_args) {
-        Ok((memory_block, stack, address)) => {
-            let value = memory_block.read_i8(address);
-            stack.push(Value::I64(value as i64));
-            Ok(())
-        }
-        Err(address_value) => Err(make_memory_load_operand_data_type_mismatch_engine_error(
-            "i64_load8_s",
-            &address_value,
-        )),
-    }
+pub fn i64_load8_s(vm: &mut VM, memory_access: &MemoryAccess) -> Result<(), EngineError> {
+    let (memory_block, stack, address) =
+        get_memory_load_access_meterial(vm, "i64.load8_s", memory_access, 1)?;
+    let value = memory_block.read_i8(address);
+    stack.push_i64(value as i64);
+    Ok(())
 }
 
-pub fn i64_load8_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
-    match get_memory_load_access_meterial(vm, memory_args) {
-        Ok((memory_block, stack, address)) => {
-            let value = memory_block.read_i8(address);
-            stack.push(Value::I64((value as u8) as i64));
-            Ok(())
-        }
-        Err(address_value) => Err(make_memory_load_operand_data_type_mismatch_engine_error(
-            "i64_load8_u",
-            &address_value,
-        )),
-    }
+pub fn i64_load8_u(vm: &mut VM, memory_access: &MemoryAccess) -> Result<(), EngineError> {
+    let (memory_block, stack, address) =
+        get_memory_load_access_meterial(vm, "i64.load8_u", memory_access, 1)?;
+    let value = memory_block.read_i8(address);
+    stack.push_i64((value as u8) as i64);
+    Ok(())
 }
 
 // float load
 
-pub fn f32_load(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
-    match get_memory_load_access_meterial(vm, memory_args) {
-        Ok((memory_block, stack, address)) => {
-            let value = memory_block.read_f32(address);
-            stack.push(Value::F32(value));
-            Ok(())
-        }
-        Err(address_value) => Err(make_memory_load_operand_data_type_mismatch_engine_error(
-            "f32_load",
-            &address_value,
-        )),
-    }
+pub fn f32_load(vm: &mut VM, memory_access: &MemoryAccess) -> Result<(), EngineError> {
+    let (memory_block, stack, address) =
+        get_memory_load_access_meterial(vm, "f32.load", memory_access, 4)?;
+    let value = memory_block.read_f32(address);
+    stack.push_f32(value);
+    Ok(())
 }
 
-pub fn f64_load(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
-    match get_memory_load_access_meterial(vm, memory_args) {
-        Ok((memory_block, stack, address)) => {
-            let value = memory_block.read_f64(address);
-            stack.push(Value::F64(value));
-            Ok(())
-        }
-        Err(address_value) => Err(make_memory_load_operand_data_type_mismatch_engine_error(
-            "f64_load",
-            &address_value,
-        )),
-    }
+pub fn f64_load(vm: &mut VM, memory_access: &MemoryAccess) -> Result<(), EngineError> {
+    let (memory_block, stack, address) =
+        get_memory_load_access_meterial(vm, "f64.load", memory_access, 8)?;
+    let value = memory_block.read_f64(address);
+    stack.push_f64(value);
+    Ok(())
 }
 
 // i32 store
 
-pub fn i32_store(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
-    match get_memory_store_access_meterial(vm, memory_args) {
-        Ok((memory_block, address, data_value)) => {
-            if let Value::I32(value) = data_value {
-                memory_block.write_i32(address, value);
-                Ok(())
-            } else {
-                Err(make_memory_store_operand_data_type_mismatch_engine_error(
-                    "i32.store",
-                    ValueType::I32,
-                    &data_value,
-                ))
-            }
-        }
-        Err((data_value, address_value)) => {
-            Err(make_memory_store_operand_data_types_mismatch_engine_error(
-                "i32.store",
-                ValueType::I32,
-                &data_value,
-                &address_value,
-            ))
-        }
+pub fn i32_store(vm: &mut VM, memory_access: &MemoryAccess) -> Result<(), EngineError> {
+    let (memory_block, address, data_value) =
+        get_memory_store_access_meterial(vm, "i32.store", memory_access, 4)?;
+    if let Value::I32(value) = data_value {
+        memory_block.write_i32(address, value);
+        Ok(())
+    } else {
+        Err(make_operand_data_types_mismatch_engine_error(
+            "i32.store",
+            vec![ValueType::I32],
+            vec![&data_value],
+        ))
     }
 }
 
-pub fn i32_store_16(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
-    match get_memory_store_access_meterial(vm, memory_args) {
-        Ok((memory_block, address, data_value)) => {
-            if let Value::I32(value) = data_value {
-                memory_block.write_i16(address, value as i16);
-                Ok(())
-            } else {
-                Err(make_memory_store_operand_data_type_mismatch_engine_error(
-                    "i32.store_16",
-                    ValueType::I32,
-                    &data_value,
-                ))
-            }
-        }
-        Err((data_value, address_value)) => {
-            Err(make_memory_store_operand_data_types_mismatch_engine_error(
-                "i32.store_16",
-                ValueType::I32,
-                &data_value,
-                &address_value,
-            ))
-        }
+pub fn i32_store_16(vm: &mut VM, memory_access: &MemoryAccess) -> Result<(), EngineError> {
+    let (memory_block, address, data_value) =
+        get_memory_store_access_meterial(vm, "i32.store16", memory_access, 2)?;
+    if let Value::I32(value) = data_value {
+        memory_block.write_i16(address, value as i16);
+        Ok(())
+    } else {
+        Err(make_operand_data_types_mismatch_engine_error(
+            "i32.store16",
+            vec![ValueType::I32],
+            vec![&data_value],
+        ))
     }
 }
 
-pub fn i32_store_8(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
-    match get_memory_store_access_meterial(vm, memory_args) {
-        Ok((memory_block, address, data_value)) => {
-            if let Value::I32(value) = data_value {
-                memory_block.write_i8(address, value as i8);
-                Ok(())
-            } else {
-                Err(make_memory_store_operand_data_type_mismatch_engine_error(
-                    "i32.store_8",
-                    ValueType::I32,
-                    &data_value,
-                ))
-            }
-        }
-        Err((data_value, address_value)) => {
-            Err(make_memory_store_operand_data_types_mismatch_engine_error(
-                "i32.store_8",
-                ValueType::I32,
-                &data_value,
-                &address_value,
-            ))
-        }
+pub fn i32_store_8(vm: &mut VM, memory_access: &MemoryAccess) -> Result<(), EngineError> {
+    let (memory_block, address, data_value) =
+        get_memory_store_access_meterial(vm, "i32.store8", memory_access, 1)?;
+    if let Value::I32(value) = data_value {
+        memory_block.write_i8(address, value as i8);
+        Ok(())
+    } else {
+        Err(make_operand_data_types_mismatch_engine_error(
+            "i32.store8",
+            vec![ValueType::I32],
+            vec![&data_value],
+        ))
     }
 }
 
-pub fn i64_store(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
-    match get_memory_store_access_meterial(vm, memory_args) {
-        Ok((memory_block, address, data_value)) => {
-            if let Value::I64(value) = data_value {
-                memory_block.write_i64(address, value);
-                Ok(())
-            } else {
-                Err(make_memory_store_operand_data_type_mismatch_engine_error(
-                    "i64.store",
-                    ValueType::I64,
-                    &data_value,
-                ))
-            }
-        }
-        Err((data_value, address_value)) => {
-            Err(make_memory_store_operand_data_types_mismatch_engine_error(
-                "i64.store",
-                ValueType::I64,
-                &data_value,
-                &address_value,
-            ))
-        }
+pub fn i64_store(vm: &mut VM, memory_access: &MemoryAccess) -> Result<(), EngineError> {
+    let (memory_block, address, data_value) =
+        get_memory_store_access_meterial(vm, "i64.store", memory_access, 8)?;
+    if let Value::I64(value) = data_value {
+        memory_block.write_i64(address, value);
+        Ok(())
+    } else {
+        Err(make_operand_data_types_mismatch_engine_error(
+            "i64.store",
+            vec![ValueType::I64],
+            vec![&data_value],
+        ))
     }
 }
 
-pub fn i64_store_32(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
-    match get_memory_store_access_meterial(vm, memory_args) {
-        Ok((memory_block, address, data_value)) => {
-            if let Value::I64(value) = data_value {
-                memory_block.write_i32(address, value as i32);
-                Ok(())
-            } else {
-                Err(make_memory_store_operand_data_type_mismatch_engine_error(
-                    "i64.store_32",
-                    ValueType::I64,
-                    &data_value,
-                ))
-            }
-        }
-        Err((data_value, address_value)) => {
-            Err(make_memory_store_operand_data_types_mismatch_engine_error(
-                "i64.store_32",
-                ValueType::I64,
-                &data_value,
-                &address_value,
-            ))
-        }
+pub fn i64_store_32(vm: &mut VM, memory_access: &MemoryAccess) -> Result<(), EngineError> {
+    let (memory_block, address, data_value) =
+        get_memory_store_access_meterial(vm, "i64.store32", memory_access, 4)?;
+    if let Value::I64(value) = data_value {
+        memory_block.write_i32(address, value as i32);
+        Ok(())
+    } else {
+        Err(make_operand_data_types_mismatch_engine_error(
+            "i64.store32",
+            vec![ValueType::I64],
+            vec![&data_value],
+        ))
     }
 }
 
-pub fn i64_store_16(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
-    match get_memory_store_access_meterial(vm, memory_args) {
-        Ok((memory_block, address, data_value)) => {
-            if let Value::I64(value) = data_value {
-                memory_block.write_i16(address, value as i16);
-                Ok(())
-            } else {
-                Err(make_memory_store_operand_data_type_mismatch_engine_error(
-                    "i64.store_16",
-                    ValueType::I64,
-                    &data_value,
-                ))
-            }
-        }
-        Err((data_value, address_value)) => {
-            Err(make_memory_store_operand_data_types_mismatch_engine_error(
-                "i64.store_16",
-                ValueType::I64,
-                &data_value,
-                &address_value,
-            ))
-        }
+pub fn i64_store_16(vm: &mut VM, memory_access: &MemoryAccess) -> Result<(), EngineError> {
+    let (memory_block, address, data_value) =
+        get_memory_store_access_meterial(vm, "i64.store16", memory_access, 2)?;
+    if let Value::I64(value) = data_value {
+        memory_block.write_i16(address, value as i16);
+        Ok(())
+    } else {
+        Err(make_operand_data_types_mismatch_engine_error(
+            "i64.store16",
+            vec![ValueType::I64],
+            vec![&data_value],
+        ))
     }
 }
 
-pub fn i64_store_8(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
-    match get_memory_store_access_meterial(vm, memory_args) {
-        Ok((memory_block, address, data_value)) => {
-            if let Value::I64(value) = data_value {
-                memory_block.write_i8(address, value as i8);
-                Ok(())
-            } else {
-                Err(make_memory_store_operand_data_type_mismatch_engine_error(
-                    "i64.store_8",
-                    ValueType::I64,
-                    &data_value,
-                ))
-            }
-        }
-        Err((data_value, address_value)) => {
-            Err(make_memory_store_operand_data_types_mismatch_engine_error(
-                "i64.store_8",
-                ValueType::I64,
-                &data_value,
-                &address_value,
-            ))
-        }
+pub fn i64_store_8(vm: &mut VM, memory_access: &MemoryAccess) -> Result<(), EngineError> {
+    let (memory_block, address, data_value) =
+        get_memory_store_access_meterial(vm, "i64.store8", memory_access, 1)?;
+    if let Value::I64(value) = data_value {
+        memory_block.write_i8(address, value as i8);
+        Ok(())
+    } else {
+        Err(make_operand_data_types_mismatch_engine_error(
+            "i64.store8",
+            vec![ValueType::I64],
+            vec![&data_value],
+        ))
     }
 }
 
 // float store
 
-pub fn f32_store(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
-    match get_memory_store_access_meterial(vm, memory_args) {
-        Ok((memory_block, address, data_value)) => {
-            if let Value::F32(value) = data_value {
-                memory_block.write_f32(address, value);
-                Ok(())
-            } else {
-                Err(make_memory_store_operand_data_type_mismatch_engine_error(
-                    "f32.store",
-                    ValueType::F32,
-                    &data_value,
-                ))
-            }
-        }
-        Err((data_value, address_value)) => {
-            Err(make_memory_store_operand_data_types_mismatch_engine_error(
-                "f32.store",
-                ValueType::F32,
-                &data_value,
-                &address_value,
-            ))
-        }
+pub fn f32_store(vm: &mut VM, memory_access: &MemoryAccess) -> Result<(), EngineError> {
+    let (memory_block, address, data_value) =
+        get_memory_store_access_meterial(vm, "f32.store", memory_access, 4)?;
+    if let Value::F32(value) = data_value {
+        memory_block.write_f32(address, value);
+        Ok(())
+    } else {
+        Err(make_operand_data_types_mismatch_engine_error(
+            "f32.store",
+            vec![ValueType::F32],
+            vec![&data_value],
+        ))
     }
 }
 
-pub fn f64_store(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
-    match get_memory_store_access_meterial(vm, memory_args) {
-        Ok((memory_block, address, data_value)) => {
-            if let Value::F64(value) = data_value {
-                memory_block.write_f64(address, value);
-                Ok(())
-            } else {
-                Err(make_memory_store_operand_data_type_mismatch_engine_error(
-                    "f64.store",
-                    ValueType::F64,
-                    &data_value,
-                ))
-            }
-        }
-        Err((data_value, address_value)) => {
-            Err(make_memory_store_operand_data_types_mismatch_engine_error(
-                "f64.store",
-                ValueType::F64,
-                &data_value,
-                &address_value,
-            ))
-        }
+pub fn f64_store(vm: &mut VM, memory_access: &MemoryAccess) -> Result<(), EngineError> {
+    let (memory_block, address, data_value) =
+        get_memory_store_access_meterial(vm, "f64.store", memory_access, 8)?;
+    if let Value::F64(value) = data_value {
+        memory_block.write_f64(address, value);
+        Ok(())
+    } else {
+        Err(make_operand_data_types_mismatch_engine_error(
+            "f64.store",
+            vec![ValueType::F64],
+            vec![&data_value],
+        ))
     }
 }
@@ -104,10 +104,8 @@
 //!
 //! 不改变操作数的比特位，仅重新解释成其他类型
 
-use anvm_ast::types::{Value, ValueType};
-
 use crate::{
-    error::{make_operand_data_types_mismatch_engine_error, EngineError},
+    error::{EngineError, InvalidOperation},
     vm::VM,
 };
 
@@ -115,524 +113,354 @@ use crate::{
 
 pub fn i32_wrap_i64(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::I64(value) = operand {
-        let result = value as i32;
-        stack.push(Value::I32(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "i32.wrap_i64",
-            vec![ValueType::I64],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_i64();
+    let result = value as i32;
+    stack.push_i32(result);
+    Ok(())
 }
 
 // 整数提升
 
 pub fn i32_extend8_s(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::I32(value) = operand {
-        let result = (value as i8) as i32;
-        stack.push(Value::I32(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "i32.extend8_s",
-            vec![ValueType::I32],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_i32();
+    let result = (value as i8) as i32;
+    stack.push_i32(result);
+    Ok(())
 }
 
 pub fn i32_extend16_s(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::I32(value) = operand {
-        let result = (value as i16) as i32;
-        stack.push(Value::I32(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "i32.extend16_s",
-            vec![ValueType::I32],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_i32();
+    let result = (value as i16) as i32;
+    stack.push_i32(result);
+    Ok(())
 }
 
 pub fn i64_extend_i32_s(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::I32(value) = operand {
-        let result = value as i64;
-        stack.push(Value::I64(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "i64.extend_i32_s",
-            vec![ValueType::I32],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_i32();
+    let result = value as i64;
+    stack.push_i64(result);
+    Ok(())
 }
 
 pub fn i64_extend_i32_u(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::I32(value) = operand {
-        let result = (value as u32) as i64;
-        stack.push(Value::I64(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "i64.extend_i32_u",
-            vec![ValueType::I32],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_i32();
+    let result = (value as u32) as i64;
+    stack.push_i64(result);
+    Ok(())
 }
 
 pub fn i64_extend8_s(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::I64(value) = operand {
-        let result = (value as i8) as i64;
-        stack.push(Value::I64(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "i64.extend8_s",
-            vec![ValueType::I64],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_i64();
+    let result = (value as i8) as i64;
+    stack.push_i64(result);
+    Ok(())
 }
 
 pub fn i64_extend16_s(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::I64(value) = operand {
-        let result = (value as i16) as i64;
-        stack.push(Value::I64(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "i64.extend16_s",
-            vec![ValueType::I64],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_i64();
+    let result = (value as i16) as i64;
+    stack.push_i64(result);
+    Ok(())
 }
 
 pub fn i64_extend32_s(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::I64(value) = operand {
-        let result = (value as i32) as i64;
-        stack.push(Value::I64(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "i64.extend32_s",
-            vec![ValueType::I64],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_i64();
+    let result = (value as i32) as i64;
+    stack.push_i64(result);
+    Ok(())
 }
 
 // 浮点数转整数（截断运算）
+//
+// 源数值为 NaN，或者截断之后超出目标整数的范围时陷入。
+// f32 转为 f64 不会损失精度，所以统一按 f64 检查范围。
+
+/// 检查截断之后的数值是否位于 [lower, upper) 之内
+fn check_truncation(value: f64, lower: f64, upper: f64) -> Result<(), EngineError> {
+    if value.is_nan() {
+        return Err(EngineError::InvalidOperation(
+            InvalidOperation::InvalidConversionToInteger,
+        ));
+    }
+
+    let truncated = value.trunc();
+    if truncated < lower || truncated >= upper {
+        return Err(EngineError::InvalidOperation(
+            InvalidOperation::IntegerOverflow,
+        ));
+    }
+
+    Ok(())
+}
+
+const I32_LOWER: f64 = -2147483648.0; // -2^31
+const I32_UPPER: f64 = 2147483648.0; // 2^31
+const U32_UPPER: f64 = 4294967296.0; // 2^32
+const I64_LOWER: f64 = -9223372036854775808.0; // -2^63
+const I64_UPPER: f64 = 9223372036854775808.0; // 2^63
+const U64_UPPER: f64 = 18446744073709551616.0; // 2^64
 
 pub fn i32_trunc_f32_s(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::F32(value) = operand {
-        let result = value as i32;
-        stack.push(Value::I32(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "i32.trunc_f32_s",
-            vec![ValueType::F32],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_f32();
+    check_truncation(value as f64, I32_LOWER, I32_UPPER)?;
+    let result = value as i32;
+    stack.push_i32(result);
+    Ok(())
 }
 
 pub fn i32_trunc_f32_u(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::F32(value) = operand {
-        let result = value as u32;
-        stack.push(Value::I32(result as i32));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "i32.trunc_f32_u",
-            vec![ValueType::F32],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_f32();
+    check_truncation(value as f64, 0.0, U32_UPPER)?;
+    let result = value as u32;
+    stack.push_i32(result as i32);
+    Ok(())
 }
 
 pub fn i64_trunc_f32_s(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::F32(value) = operand {
-        let result = value as i64;
-        stack.push(Value::I64(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "i64.trunc_f32_s",
-            vec![ValueType::F32],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_f32();
+    check_truncation(value as f64, I64_LOWER, I64_UPPER)?;
+    let result = value as i64;
+    stack.push_i64(result);
+    Ok(())
 }
 
 pub fn i64_trunc_f32_u(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::F32(value) = operand {
-        let result = value as u64;
-        stack.push(Value::I64(result as i64));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "i64.trunc_f32_u",
-            vec![ValueType::F32],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_f32();
+    check_truncation(value as f64, 0.0, U64_UPPER)?;
+    let result = value as u64;
+    stack.push_i64(result as i64);
+    Ok(())
 }
 
 pub fn i32_trunc_f64_s(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::F64(value) = operand {
-        let result = value as i32;
-        stack.push(Value::I32(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "i32.trunc_f64_s",
-            vec![ValueType::F64],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_f64();
+    check_truncation(value, I32_LOWER, I32_UPPER)?;
+    let result = value as i32;
+    stack.push_i32(result);
+    Ok(())
 }
 
 pub fn i32_trunc_f64_u(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::F64(value) = operand {
-        let result = value as u32;
-        stack.push(Value::I32(result as i32));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "i32.trunc_f64_u",
-            vec![ValueType::F64],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_f64();
+    check_truncation(value, 0.0, U32_UPPER)?;
+    let result = value as u32;
+    stack.push_i32(result as i32);
+    Ok(())
 }
 
 pub fn i64_trunc_f64_s(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::F64(value) = operand {
-        let result = value as i64;
-        stack.push(Value::I64(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "i64.trunc_f64_s",
-            vec![ValueType::F64],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_f64();
+    check_truncation(value, I64_LOWER, I64_UPPER)?;
+    let result = value as i64;
+    stack.push_i64(result);
+    Ok(())
 }
 
 pub fn i64_trunc_f64_u(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::F64(value) = operand {
-        let result = value as u64;
-        stack.push(Value::I64(result as i64));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "i64.trunc_f64_u",
-            vec![ValueType::F64],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_f64();
+    check_truncation(value, 0.0, U64_UPPER)?;
+    let result = value as u64;
+    stack.push_i64(result as i64);
+    Ok(())
 }
 
 // 饱和截断
+//
+// Rust 的浮点数转整数的 `as` 运算本身就是饱和的，NaN 会被转为 0。
+
+pub fn i32_trunc_sat_f32_s(vm: &mut VM) -> Result<(), EngineError> {
+    let stack = &mut vm.stack;
+    let value = stack.pop_f32();
+    let result = value as i32;
+    stack.push_i32(result);
+    Ok(())
+}
+
+pub fn i32_trunc_sat_f32_u(vm: &mut VM) -> Result<(), EngineError> {
+    let stack = &mut vm.stack;
+    let value = stack.pop_f32();
+    let result = value as u32;
+    stack.push_i32(result as i32);
+    Ok(())
+}
 
-// todo::
+pub fn i32_trunc_sat_f64_s(vm: &mut VM) -> Result<(), EngineError> {
+    let stack = &mut vm.stack;
+    let value = stack.pop_f64();
+    let result = value as i32;
+    stack.push_i32(result);
+    Ok(())
+}
+
+pub fn i32_trunc_sat_f64_u(vm: &mut VM) -> Result<(), EngineError> {
+    let stack = &mut vm.stack;
+    let value = stack.pop_f64();
+    let result = value as u32;
+    stack.push_i32(result as i32);
+    Ok(())
+}
+
+pub fn i64_trunc_sat_f32_s(vm: &mut VM) -> Result<(), EngineError> {
+    let stack = &mut vm.stack;
+    let value = stack.pop_f32();
+    let result = value as i64;
+    stack.push_i64(result);
+    Ok(())
+}
+
+pub fn i64_trunc_sat_f32_u(vm: &mut VM) -> Result<(), EngineError> {
+    let stack = &mut vm.stack;
+    let value = stack.pop_f32();
+    let result = value as u64;
+    stack.push_i64(result as i64);
+    Ok(())
+}
+
+pub fn i64_trunc_sat_f64_s(vm: &mut VM) -> Result<(), EngineError> {
+    let stack = &mut vm.stack;
+    let value = stack.pop_f64();
+    let result = value as i64;
+    stack.push_i64(result);
+    Ok(())
+}
+
+pub fn i64_trunc_sat_f64_u(vm: &mut VM) -> Result<(), EngineError> {
+    let stack = &mut vm.stack;
+    let value = stack.pop_f64();
+    let result = value as u64;
+    stack.push_i64(result as i64);
+    Ok(())
+}
 
 // 整数转浮点数（转换运算）
 
 pub fn f32_convert_i32_s(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::I32(value) = operand {
-        let result = value as f32;
-        stack.push(Value::F32(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "f32.convert_i32_s",
-            vec![ValueType::I32],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_i32();
+    let result = value as f32;
+    stack.push_f32(result);
+    Ok(())
 }
 
 pub fn f32_convert_i32_u(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::I32(value) = operand {
-        let result = (value as u32) as f32;
-        stack.push(Value::F32(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "f32.convert_i32_u",
-            vec![ValueType::I32],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_i32();
+    let result = (value as u32) as f32;
+    stack.push_f32(result);
+    Ok(())
 }
 
 pub fn f64_convert_i32_s(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::I32(value) = operand {
-        let result = value as f64;
-        stack.push(Value::F64(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "f64.convert_i32_s",
-            vec![ValueType::I32],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_i32();
+    let result = value as f64;
+    stack.push_f64(result);
+    Ok(())
 }
 
 pub fn f64_convert_i32_u(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::I32(value) = operand {
-        let result = (value as u32) as f64;
-        stack.push(Value::F64(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "f64.convert_i32_u",
-            vec![ValueType::I32],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_i32();
+    let result = (value as u32) as f64;
+    stack.push_f64(result);
+    Ok(())
 }
 
 pub fn f32_convert_i64_s(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::I64(value) = operand {
-        let result = value as f32;
-        stack.push(Value::F32(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "f32.convert_i64_s",
-            vec![ValueType::I64],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_i64();
+    let result = value as f32;
+    stack.push_f32(result);
+    Ok(())
 }
 
 pub fn f32_convert_i64_u(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::I64(value) = operand {
-        let result = (value as u64) as f32;
-        stack.push(Value::F32(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "f32.convert_i64_u",
-            vec![ValueType::I64],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_i64();
+    let result = (value as u64) as f32;
+    stack.push_f32(result);
+    Ok(())
 }
 
 pub fn f64_convert_i64_s(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::I64(value) = operand {
-        let result = value as f64;
-        stack.push(Value::F64(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "f64.convert_i64_s",
-            vec![ValueType::I64],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_i64();
+    let result = value as f64;
+    stack.push_f64(result);
+    Ok(())
 }
 
 pub fn f64_convert_i64_u(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::I64(value) = operand {
-        let result = (value as u64) as f64;
-        stack.push(Value::F64(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "f64.convert_i64_u",
-            vec![ValueType::I64],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_i64();
+    let result = (value as u64) as f64;
+    stack.push_f64(result);
+    Ok(())
 }
 
 // 浮点数精度调整
 
 pub fn f32_demote_f64_s(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::F64(value) = operand {
-        let result = value as f32;
-        stack.push(Value::F32(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "f32.demote_f64_s",
-            vec![ValueType::F64],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_f64();
+    let result = value as f32;
+    stack.push_f32(result);
+    Ok(())
 }
 
 pub fn f64_promote_f32(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::F32(value) = operand {
-        let result = value as f64;
-        stack.push(Value::F64(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "f64.promote_f32",
-            vec![ValueType::F32],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_f32();
+    let result = value as f64;
+    stack.push_f64(result);
+    Ok(())
 }
 
 // 比特位重新解释
 
 pub fn i32_reinterpret_f32(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::F32(value) = operand {
-        let result = i32::from_le_bytes(value.to_le_bytes());
-        stack.push(Value::I32(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "i32.reinterpret_f32",
-            vec![ValueType::F32],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_f32();
+    let result = i32::from_le_bytes(value.to_le_bytes());
+    stack.push_i32(result);
+    Ok(())
 }
 
 pub fn i64_reinterpret_f64(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::F64(value) = operand {
-        let result = i64::from_le_bytes(value.to_le_bytes());
-        stack.push(Value::I64(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "i64.reinterpret_f64",
-            vec![ValueType::F64],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_f64();
+    let result = i64::from_le_bytes(value.to_le_bytes());
+    stack.push_i64(result);
+    Ok(())
 }
 
 pub fn f32_reinterpret_i32(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::I32(value) = operand {
-        let result = f32::from_le_bytes(value.to_le_bytes());
-        stack.push(Value::F32(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "f32.reinterpret_i32",
-            vec![ValueType::I32],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_i32();
+    let result = f32::from_le_bytes(value.to_le_bytes());
+    stack.push_f32(result);
+    Ok(())
 }
 
 pub fn f64_reinterpret_i64(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let operand = stack.pop();
-
-    if let Value::I64(value) = operand {
-        let result = f64::from_le_bytes(value.to_le_bytes());
-        stack.push(Value::F64(result));
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "f64.reinterpret_i64",
-            vec![ValueType::I64],
-            vec![&operand],
-        ))
-    }
+    let value = stack.pop_i64();
+    let result = f64::from_le_bytes(value.to_le_bytes());
+    stack.push_f64(result);
+    Ok(())
 }
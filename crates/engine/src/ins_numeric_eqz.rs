@@ -12,41 +12,18 @@
 //! i32.eqz
 //! i64.eqz
 
-use anvm_ast::types::{Value, ValueType};
-
-use crate::{
-    error::{make_operand_data_types_mismatch_engine_error, EngineError},
-    vm::VM,
-};
+use crate::{error::EngineError, vm::VM};
 
 pub fn i32_eqz(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let testing = stack.pop();
-
-    if let Value::I32(value) = testing {
-        stack.push_bool(value == 0);
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "i32.eqz",
-            vec![ValueType::I32],
-            vec![&testing],
-        ))
-    }
+    let value = stack.peek_i32();
+    stack.replace_top_bool(value == 0);
+    Ok(())
 }
 
 pub fn i64_eqz(vm: &mut VM) -> Result<(), EngineError> {
     let stack = &mut vm.stack;
-    let testing = stack.pop();
-
-    if let Value::I64(value) = testing {
-        stack.push_bool(value == 0);
-        Ok(())
-    } else {
-        Err(make_operand_data_types_mismatch_engine_error(
-            "i64.eqz",
-            vec![ValueType::I64],
-            vec![&testing],
-        ))
-    }
+    let value = stack.peek_i64();
+    stack.replace_top_bool(value == 0);
+    Ok(())
 }
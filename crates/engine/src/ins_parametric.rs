@@ -22,7 +22,7 @@ use crate::{
 ///
 /// 弹出栈顶的一个操作数并扔掉
 pub fn drop(vm: &mut VM) -> Result<(), EngineError> {
-    vm.stack.pop();
+    vm.stack.pop_slot();
     Ok(())
 }
 
@@ -0,0 +1,57 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 引用指令
+//!
+//! - ref.null ref_type:byte
+//!   压入指定类型的 null 引用
+//!
+//! - ref.is_null
+//!   从操作数栈弹出一个引用值，如果是 null 则压入 1:i32，否则压入 0:i32
+//!
+//! - ref.func func_idx:uint32
+//!   压入指定函数的引用
+//!
+//! 函数引用的值是函数在 VM 实例里的全局索引，即当前模块的函数引用起始值加上
+//! 函数在当前模块里的索引（包括导入函数），所以函数引用在模块之间传递之后
+//! 仍然指向原来的函数。外部引用（externref）的值由宿主（比如本地函数）提供，
+//! VM 不理解它的含义。
+
+use anvm_ast::types::{RefType, Value, ValueType};
+
+use crate::{
+    error::{make_operand_data_types_mismatch_engine_error, EngineError},
+    vm::VM,
+};
+
+pub fn ref_null(vm: &mut VM, ref_type: RefType) -> Result<(), EngineError> {
+    vm.stack.push(Value::new_null_ref(ref_type));
+    Ok(())
+}
+
+pub fn ref_is_null(vm: &mut VM) -> Result<(), EngineError> {
+    let stack = &mut vm.stack;
+    let value = stack.pop();
+
+    match value {
+        Value::FuncRef(option_index) | Value::ExternRef(option_index) => {
+            stack.push(Value::I32(if option_index.is_none() { 1 } else { 0 }));
+            Ok(())
+        }
+        _ => Err(make_operand_data_types_mismatch_engine_error(
+            "ref.is_null",
+            vec![ValueType::FuncRef],
+            vec![&value],
+        )),
+    }
+}
+
+pub fn ref_func(vm: &mut VM, function_index: u32) -> Result<(), EngineError> {
+    let vm_module = &vm.resource.vm_modules[vm.status.vm_module_index];
+    let function_reference = vm_module.function_reference_base + function_index;
+    vm.stack.push(Value::FuncRef(Some(function_reference)));
+    Ok(())
+}
@@ -0,0 +1,1931 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 固定宽度 SIMD 指令
+//!
+//! 128 位的向量（v128）可以按照以下几种形状（shape）解释为多个通道（lane）：
+//!
+//! - i8x16：16 个 8 位整数
+//! - i16x8：8 个 16 位整数
+//! - i32x4：4 个 32 位整数
+//! - i64x2：2 个 64 位整数
+//! - f32x4：4 个 f32
+//! - f64x2：2 个 f64
+//!
+//! 通道按照小端格式排列，即第 0 个通道位于向量的最低端。
+//!
+//! 指令大致分为以下几类：
+//!
+//! - 内存指令，比如 `v128.load`、`v128.load8x8_s`、`v128.load32_zero`、`v128.store16_lane`，
+//!   跟普通的内存指令一样，先从操作数栈弹出 uint32 作为地址，然后加上立即数 offset 得到有效地址，
+//!   `v128.loadN_lane` 和 `v128.storeN_lane` 的向量操作数位于地址之上；
+//! - 通道指令，比如 `i8x16.shuffle`、`i8x16.swizzle`、`i32x4.splat`、`f32x4.extract_lane`、
+//!   `f64x2.replace_lane`；
+//! - 按通道运算的指令，比如 `i16x8.add_sat_s`、`f32x4.min`、`i32x4.lt_u`、`i64x2.shl`，
+//!   比较指令的结果是每个通道全为 1（真）或者全为 0（假）的向量；
+//! - 改变形状的指令，比如 `i16x8.extend_low_i8x16_s`、`i8x16.narrow_i16x8_u`、
+//!   `i32x4.dot_i16x8_s`、`f32x4.demote_f64x2_zero`；
+//! - 按位运算的指令，比如 `v128.and`、`v128.bitselect`、`v128.any_true`。
+//!
+//! 本模块是不依赖平台向量指令的（portable）标量实现：先把向量拆分为通道数组，
+//! 逐个通道运算之后再合并为向量。
+//!
+//! https://webassembly.github.io/spec/core/syntax/instructions.html#vector-instructions
+
+use anvm_ast::{
+    instruction::MemoryArgument,
+    types::{Value, ValueType},
+};
+
+use crate::{
+    error::{make_operand_data_types_mismatch_engine_error, EngineError},
+    ins_memory::pop_effective_address,
+    vm::VM,
+    vm_memory::VMMemory,
+};
+
+pub fn v128_load(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    v128_load_op(vm, "v128.load", memory_args, 16, u128_from_bytes)
+}
+
+pub fn v128_load8x8_s(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    v128_load_op(vm, "v128.load8x8_s", memory_args, 8, |bytes| {
+        convert_lanes::<i8, 16, i16, 8>(u128_from_bytes(bytes), 0, i16::from)
+    })
+}
+
+pub fn v128_load8x8_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    v128_load_op(vm, "v128.load8x8_u", memory_args, 8, |bytes| {
+        convert_lanes::<u8, 16, u16, 8>(u128_from_bytes(bytes), 0, u16::from)
+    })
+}
+
+pub fn v128_load16x4_s(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    v128_load_op(vm, "v128.load16x4_s", memory_args, 8, |bytes| {
+        convert_lanes::<i16, 8, i32, 4>(u128_from_bytes(bytes), 0, i32::from)
+    })
+}
+
+pub fn v128_load16x4_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    v128_load_op(vm, "v128.load16x4_u", memory_args, 8, |bytes| {
+        convert_lanes::<u16, 8, u32, 4>(u128_from_bytes(bytes), 0, u32::from)
+    })
+}
+
+pub fn v128_load32x2_s(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    v128_load_op(vm, "v128.load32x2_s", memory_args, 8, |bytes| {
+        convert_lanes::<i32, 4, i64, 2>(u128_from_bytes(bytes), 0, i64::from)
+    })
+}
+
+pub fn v128_load32x2_u(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    v128_load_op(vm, "v128.load32x2_u", memory_args, 8, |bytes| {
+        convert_lanes::<u32, 4, u64, 2>(u128_from_bytes(bytes), 0, u64::from)
+    })
+}
+
+pub fn v128_load8_splat(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    v128_load_op(vm, "v128.load8_splat", memory_args, 1, |bytes| {
+        from_lanes::<u8, 16>([u8::from_le_slice(bytes); 16])
+    })
+}
+
+pub fn v128_load16_splat(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    v128_load_op(vm, "v128.load16_splat", memory_args, 2, |bytes| {
+        from_lanes::<u16, 8>([u16::from_le_slice(bytes); 8])
+    })
+}
+
+pub fn v128_load32_splat(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    v128_load_op(vm, "v128.load32_splat", memory_args, 4, |bytes| {
+        from_lanes::<u32, 4>([u32::from_le_slice(bytes); 4])
+    })
+}
+
+pub fn v128_load64_splat(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    v128_load_op(vm, "v128.load64_splat", memory_args, 8, |bytes| {
+        from_lanes::<u64, 2>([u64::from_le_slice(bytes); 2])
+    })
+}
+
+pub fn v128_store(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    v128_store_op(vm, "v128.store", memory_args)
+}
+
+pub fn i8x16_shuffle(vm: &mut VM, lane_indices: &[u8; 16]) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.shuffle", |lhs, rhs| {
+        let (left, right) = (lhs.to_le_bytes(), rhs.to_le_bytes());
+        u128::from_le_bytes(std::array::from_fn(|index| {
+            let lane_index = lane_indices[index] as usize;
+            if lane_index < 16 {
+                left[lane_index]
+            } else {
+                right[lane_index - 16]
+            }
+        }))
+    })
+}
+
+pub fn i8x16_swizzle(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.swizzle", |lhs, rhs| {
+        let (lanes, indices) = (lhs.to_le_bytes(), rhs.to_le_bytes());
+        u128::from_le_bytes(std::array::from_fn(|index| {
+            lanes.get(indices[index] as usize).copied().unwrap_or(0)
+        }))
+    })
+}
+
+pub fn i8x16_splat(vm: &mut VM) -> Result<(), EngineError> {
+    let value = pop_i32(vm, "i8x16.splat")?;
+    vm.stack.push(Value::V128(from_lanes([value as i8; 16])));
+    Ok(())
+}
+
+pub fn i16x8_splat(vm: &mut VM) -> Result<(), EngineError> {
+    let value = pop_i32(vm, "i16x8.splat")?;
+    vm.stack.push(Value::V128(from_lanes([value as i16; 8])));
+    Ok(())
+}
+
+pub fn i32x4_splat(vm: &mut VM) -> Result<(), EngineError> {
+    let value = pop_i32(vm, "i32x4.splat")?;
+    vm.stack.push(Value::V128(from_lanes([value; 4])));
+    Ok(())
+}
+
+pub fn i64x2_splat(vm: &mut VM) -> Result<(), EngineError> {
+    let value = pop_i64(vm, "i64x2.splat")?;
+    vm.stack.push(Value::V128(from_lanes([value; 2])));
+    Ok(())
+}
+
+pub fn f32x4_splat(vm: &mut VM) -> Result<(), EngineError> {
+    let value = pop_f32(vm, "f32x4.splat")?;
+    vm.stack.push(Value::V128(from_lanes([value; 4])));
+    Ok(())
+}
+
+pub fn f64x2_splat(vm: &mut VM) -> Result<(), EngineError> {
+    let value = pop_f64(vm, "f64x2.splat")?;
+    vm.stack.push(Value::V128(from_lanes([value; 2])));
+    Ok(())
+}
+
+pub fn i8x16_extract_lane_s(vm: &mut VM, lane_index: u8) -> Result<(), EngineError> {
+    let vector = pop_v128(vm, "i8x16.extract_lane_s")?;
+    let lane = to_lanes::<i8, 16>(vector)[lane_index as usize];
+    vm.stack.push(Value::I32(lane as i32));
+    Ok(())
+}
+
+pub fn i8x16_extract_lane_u(vm: &mut VM, lane_index: u8) -> Result<(), EngineError> {
+    let vector = pop_v128(vm, "i8x16.extract_lane_u")?;
+    let lane = to_lanes::<u8, 16>(vector)[lane_index as usize];
+    vm.stack.push(Value::I32(lane as i32));
+    Ok(())
+}
+
+pub fn i8x16_replace_lane(vm: &mut VM, lane_index: u8) -> Result<(), EngineError> {
+    let value = pop_i32(vm, "i8x16.replace_lane")?;
+    let vector = pop_v128(vm, "i8x16.replace_lane")?;
+    let mut lanes = to_lanes::<i8, 16>(vector);
+    lanes[lane_index as usize] = value as i8;
+    vm.stack.push(Value::V128(from_lanes(lanes)));
+    Ok(())
+}
+
+pub fn i16x8_extract_lane_s(vm: &mut VM, lane_index: u8) -> Result<(), EngineError> {
+    let vector = pop_v128(vm, "i16x8.extract_lane_s")?;
+    let lane = to_lanes::<i16, 8>(vector)[lane_index as usize];
+    vm.stack.push(Value::I32(lane as i32));
+    Ok(())
+}
+
+pub fn i16x8_extract_lane_u(vm: &mut VM, lane_index: u8) -> Result<(), EngineError> {
+    let vector = pop_v128(vm, "i16x8.extract_lane_u")?;
+    let lane = to_lanes::<u16, 8>(vector)[lane_index as usize];
+    vm.stack.push(Value::I32(lane as i32));
+    Ok(())
+}
+
+pub fn i16x8_replace_lane(vm: &mut VM, lane_index: u8) -> Result<(), EngineError> {
+    let value = pop_i32(vm, "i16x8.replace_lane")?;
+    let vector = pop_v128(vm, "i16x8.replace_lane")?;
+    let mut lanes = to_lanes::<i16, 8>(vector);
+    lanes[lane_index as usize] = value as i16;
+    vm.stack.push(Value::V128(from_lanes(lanes)));
+    Ok(())
+}
+
+pub fn i32x4_extract_lane(vm: &mut VM, lane_index: u8) -> Result<(), EngineError> {
+    let vector = pop_v128(vm, "i32x4.extract_lane")?;
+    let lane = to_lanes::<i32, 4>(vector)[lane_index as usize];
+    vm.stack.push(Value::I32(lane));
+    Ok(())
+}
+
+pub fn i32x4_replace_lane(vm: &mut VM, lane_index: u8) -> Result<(), EngineError> {
+    let value = pop_i32(vm, "i32x4.replace_lane")?;
+    let vector = pop_v128(vm, "i32x4.replace_lane")?;
+    let mut lanes = to_lanes::<i32, 4>(vector);
+    lanes[lane_index as usize] = value;
+    vm.stack.push(Value::V128(from_lanes(lanes)));
+    Ok(())
+}
+
+pub fn i64x2_extract_lane(vm: &mut VM, lane_index: u8) -> Result<(), EngineError> {
+    let vector = pop_v128(vm, "i64x2.extract_lane")?;
+    let lane = to_lanes::<i64, 2>(vector)[lane_index as usize];
+    vm.stack.push(Value::I64(lane));
+    Ok(())
+}
+
+pub fn i64x2_replace_lane(vm: &mut VM, lane_index: u8) -> Result<(), EngineError> {
+    let value = pop_i64(vm, "i64x2.replace_lane")?;
+    let vector = pop_v128(vm, "i64x2.replace_lane")?;
+    let mut lanes = to_lanes::<i64, 2>(vector);
+    lanes[lane_index as usize] = value;
+    vm.stack.push(Value::V128(from_lanes(lanes)));
+    Ok(())
+}
+
+pub fn f32x4_extract_lane(vm: &mut VM, lane_index: u8) -> Result<(), EngineError> {
+    let vector = pop_v128(vm, "f32x4.extract_lane")?;
+    let lane = to_lanes::<f32, 4>(vector)[lane_index as usize];
+    vm.stack.push(Value::F32(lane));
+    Ok(())
+}
+
+pub fn f32x4_replace_lane(vm: &mut VM, lane_index: u8) -> Result<(), EngineError> {
+    let value = pop_f32(vm, "f32x4.replace_lane")?;
+    let vector = pop_v128(vm, "f32x4.replace_lane")?;
+    let mut lanes = to_lanes::<f32, 4>(vector);
+    lanes[lane_index as usize] = value;
+    vm.stack.push(Value::V128(from_lanes(lanes)));
+    Ok(())
+}
+
+pub fn f64x2_extract_lane(vm: &mut VM, lane_index: u8) -> Result<(), EngineError> {
+    let vector = pop_v128(vm, "f64x2.extract_lane")?;
+    let lane = to_lanes::<f64, 2>(vector)[lane_index as usize];
+    vm.stack.push(Value::F64(lane));
+    Ok(())
+}
+
+pub fn f64x2_replace_lane(vm: &mut VM, lane_index: u8) -> Result<(), EngineError> {
+    let value = pop_f64(vm, "f64x2.replace_lane")?;
+    let vector = pop_v128(vm, "f64x2.replace_lane")?;
+    let mut lanes = to_lanes::<f64, 2>(vector);
+    lanes[lane_index as usize] = value;
+    vm.stack.push(Value::V128(from_lanes(lanes)));
+    Ok(())
+}
+
+pub fn i8x16_eq(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.eq", |lhs, rhs| {
+        compare_lanes::<i8, 16>(lhs, rhs, |a, b| a == b)
+    })
+}
+
+pub fn i8x16_ne(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.ne", |lhs, rhs| {
+        compare_lanes::<i8, 16>(lhs, rhs, |a, b| a != b)
+    })
+}
+
+pub fn i8x16_lt_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.lt_s", |lhs, rhs| {
+        compare_lanes::<i8, 16>(lhs, rhs, |a, b| a < b)
+    })
+}
+
+pub fn i8x16_lt_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.lt_u", |lhs, rhs| {
+        compare_lanes::<u8, 16>(lhs, rhs, |a, b| a < b)
+    })
+}
+
+pub fn i8x16_gt_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.gt_s", |lhs, rhs| {
+        compare_lanes::<i8, 16>(lhs, rhs, |a, b| a > b)
+    })
+}
+
+pub fn i8x16_gt_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.gt_u", |lhs, rhs| {
+        compare_lanes::<u8, 16>(lhs, rhs, |a, b| a > b)
+    })
+}
+
+pub fn i8x16_le_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.le_s", |lhs, rhs| {
+        compare_lanes::<i8, 16>(lhs, rhs, |a, b| a <= b)
+    })
+}
+
+pub fn i8x16_le_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.le_u", |lhs, rhs| {
+        compare_lanes::<u8, 16>(lhs, rhs, |a, b| a <= b)
+    })
+}
+
+pub fn i8x16_ge_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.ge_s", |lhs, rhs| {
+        compare_lanes::<i8, 16>(lhs, rhs, |a, b| a >= b)
+    })
+}
+
+pub fn i8x16_ge_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.ge_u", |lhs, rhs| {
+        compare_lanes::<u8, 16>(lhs, rhs, |a, b| a >= b)
+    })
+}
+
+pub fn i16x8_eq(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.eq", |lhs, rhs| {
+        compare_lanes::<i16, 8>(lhs, rhs, |a, b| a == b)
+    })
+}
+
+pub fn i16x8_ne(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.ne", |lhs, rhs| {
+        compare_lanes::<i16, 8>(lhs, rhs, |a, b| a != b)
+    })
+}
+
+pub fn i16x8_lt_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.lt_s", |lhs, rhs| {
+        compare_lanes::<i16, 8>(lhs, rhs, |a, b| a < b)
+    })
+}
+
+pub fn i16x8_lt_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.lt_u", |lhs, rhs| {
+        compare_lanes::<u16, 8>(lhs, rhs, |a, b| a < b)
+    })
+}
+
+pub fn i16x8_gt_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.gt_s", |lhs, rhs| {
+        compare_lanes::<i16, 8>(lhs, rhs, |a, b| a > b)
+    })
+}
+
+pub fn i16x8_gt_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.gt_u", |lhs, rhs| {
+        compare_lanes::<u16, 8>(lhs, rhs, |a, b| a > b)
+    })
+}
+
+pub fn i16x8_le_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.le_s", |lhs, rhs| {
+        compare_lanes::<i16, 8>(lhs, rhs, |a, b| a <= b)
+    })
+}
+
+pub fn i16x8_le_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.le_u", |lhs, rhs| {
+        compare_lanes::<u16, 8>(lhs, rhs, |a, b| a <= b)
+    })
+}
+
+pub fn i16x8_ge_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.ge_s", |lhs, rhs| {
+        compare_lanes::<i16, 8>(lhs, rhs, |a, b| a >= b)
+    })
+}
+
+pub fn i16x8_ge_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.ge_u", |lhs, rhs| {
+        compare_lanes::<u16, 8>(lhs, rhs, |a, b| a >= b)
+    })
+}
+
+pub fn i32x4_eq(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i32x4.eq", |lhs, rhs| {
+        compare_lanes::<i32, 4>(lhs, rhs, |a, b| a == b)
+    })
+}
+
+pub fn i32x4_ne(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i32x4.ne", |lhs, rhs| {
+        compare_lanes::<i32, 4>(lhs, rhs, |a, b| a != b)
+    })
+}
+
+pub fn i32x4_lt_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i32x4.lt_s", |lhs, rhs| {
+        compare_lanes::<i32, 4>(lhs, rhs, |a, b| a < b)
+    })
+}
+
+pub fn i32x4_lt_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i32x4.lt_u", |lhs, rhs| {
+        compare_lanes::<u32, 4>(lhs, rhs, |a, b| a < b)
+    })
+}
+
+pub fn i32x4_gt_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i32x4.gt_s", |lhs, rhs| {
+        compare_lanes::<i32, 4>(lhs, rhs, |a, b| a > b)
+    })
+}
+
+pub fn i32x4_gt_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i32x4.gt_u", |lhs, rhs| {
+        compare_lanes::<u32, 4>(lhs, rhs, |a, b| a > b)
+    })
+}
+
+pub fn i32x4_le_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i32x4.le_s", |lhs, rhs| {
+        compare_lanes::<i32, 4>(lhs, rhs, |a, b| a <= b)
+    })
+}
+
+pub fn i32x4_le_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i32x4.le_u", |lhs, rhs| {
+        compare_lanes::<u32, 4>(lhs, rhs, |a, b| a <= b)
+    })
+}
+
+pub fn i32x4_ge_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i32x4.ge_s", |lhs, rhs| {
+        compare_lanes::<i32, 4>(lhs, rhs, |a, b| a >= b)
+    })
+}
+
+pub fn i32x4_ge_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i32x4.ge_u", |lhs, rhs| {
+        compare_lanes::<u32, 4>(lhs, rhs, |a, b| a >= b)
+    })
+}
+
+pub fn f32x4_eq(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f32x4.eq", |lhs, rhs| {
+        compare_lanes::<f32, 4>(lhs, rhs, |a, b| a == b)
+    })
+}
+
+pub fn f32x4_ne(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f32x4.ne", |lhs, rhs| {
+        compare_lanes::<f32, 4>(lhs, rhs, |a, b| a != b)
+    })
+}
+
+pub fn f32x4_lt(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f32x4.lt", |lhs, rhs| {
+        compare_lanes::<f32, 4>(lhs, rhs, |a, b| a < b)
+    })
+}
+
+pub fn f32x4_gt(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f32x4.gt", |lhs, rhs| {
+        compare_lanes::<f32, 4>(lhs, rhs, |a, b| a > b)
+    })
+}
+
+pub fn f32x4_le(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f32x4.le", |lhs, rhs| {
+        compare_lanes::<f32, 4>(lhs, rhs, |a, b| a <= b)
+    })
+}
+
+pub fn f32x4_ge(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f32x4.ge", |lhs, rhs| {
+        compare_lanes::<f32, 4>(lhs, rhs, |a, b| a >= b)
+    })
+}
+
+pub fn f64x2_eq(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f64x2.eq", |lhs, rhs| {
+        compare_lanes::<f64, 2>(lhs, rhs, |a, b| a == b)
+    })
+}
+
+pub fn f64x2_ne(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f64x2.ne", |lhs, rhs| {
+        compare_lanes::<f64, 2>(lhs, rhs, |a, b| a != b)
+    })
+}
+
+pub fn f64x2_lt(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f64x2.lt", |lhs, rhs| {
+        compare_lanes::<f64, 2>(lhs, rhs, |a, b| a < b)
+    })
+}
+
+pub fn f64x2_gt(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f64x2.gt", |lhs, rhs| {
+        compare_lanes::<f64, 2>(lhs, rhs, |a, b| a > b)
+    })
+}
+
+pub fn f64x2_le(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f64x2.le", |lhs, rhs| {
+        compare_lanes::<f64, 2>(lhs, rhs, |a, b| a <= b)
+    })
+}
+
+pub fn f64x2_ge(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f64x2.ge", |lhs, rhs| {
+        compare_lanes::<f64, 2>(lhs, rhs, |a, b| a >= b)
+    })
+}
+
+pub fn v128_not(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "v128.not", |value| !value)
+}
+
+pub fn v128_and(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "v128.and", |lhs, rhs| lhs & rhs)
+}
+
+pub fn v128_andnot(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "v128.andnot", |lhs, rhs| lhs & !rhs)
+}
+
+pub fn v128_or(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "v128.or", |lhs, rhs| lhs | rhs)
+}
+
+pub fn v128_xor(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "v128.xor", |lhs, rhs| lhs ^ rhs)
+}
+
+pub fn v128_bitselect(vm: &mut VM) -> Result<(), EngineError> {
+    let mask = pop_v128(vm, "v128.bitselect")?;
+    let rhs = pop_v128(vm, "v128.bitselect")?;
+    let lhs = pop_v128(vm, "v128.bitselect")?;
+    vm.stack.push(Value::V128((lhs & mask) | (rhs & !mask)));
+    Ok(())
+}
+
+pub fn v128_any_true(vm: &mut VM) -> Result<(), EngineError> {
+    v128_test_op(vm, "v128.any_true", |value| (value != 0) as i32)
+}
+
+pub fn v128_load8_lane(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+    lane_index: u8,
+) -> Result<(), EngineError> {
+    v128_load_lane_op(vm, "v128.load8_lane", memory_args, lane_index, 1)
+}
+
+pub fn v128_load16_lane(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+    lane_index: u8,
+) -> Result<(), EngineError> {
+    v128_load_lane_op(vm, "v128.load16_lane", memory_args, lane_index, 2)
+}
+
+pub fn v128_load32_lane(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+    lane_index: u8,
+) -> Result<(), EngineError> {
+    v128_load_lane_op(vm, "v128.load32_lane", memory_args, lane_index, 4)
+}
+
+pub fn v128_load64_lane(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+    lane_index: u8,
+) -> Result<(), EngineError> {
+    v128_load_lane_op(vm, "v128.load64_lane", memory_args, lane_index, 8)
+}
+
+pub fn v128_store8_lane(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+    lane_index: u8,
+) -> Result<(), EngineError> {
+    v128_store_lane_op(vm, "v128.store8_lane", memory_args, lane_index, 1)
+}
+
+pub fn v128_store16_lane(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+    lane_index: u8,
+) -> Result<(), EngineError> {
+    v128_store_lane_op(vm, "v128.store16_lane", memory_args, lane_index, 2)
+}
+
+pub fn v128_store32_lane(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+    lane_index: u8,
+) -> Result<(), EngineError> {
+    v128_store_lane_op(vm, "v128.store32_lane", memory_args, lane_index, 4)
+}
+
+pub fn v128_store64_lane(
+    vm: &mut VM,
+    memory_args: &MemoryArgument,
+    lane_index: u8,
+) -> Result<(), EngineError> {
+    v128_store_lane_op(vm, "v128.store64_lane", memory_args, lane_index, 8)
+}
+
+pub fn v128_load32_zero(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    v128_load_op(vm, "v128.load32_zero", memory_args, 4, u128_from_bytes)
+}
+
+pub fn v128_load64_zero(vm: &mut VM, memory_args: &MemoryArgument) -> Result<(), EngineError> {
+    v128_load_op(vm, "v128.load64_zero", memory_args, 8, u128_from_bytes)
+}
+
+pub fn f32x4_demote_f64x2_zero(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "f32x4.demote_f64x2_zero", |value| {
+        let lanes = to_lanes::<f64, 2>(value);
+        from_lanes::<f32, 4>([lanes[0] as f32, lanes[1] as f32, 0.0, 0.0])
+    })
+}
+
+pub fn f64x2_promote_low_f32x4(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "f64x2.promote_low_f32x4", |value| {
+        convert_lanes::<f32, 4, f64, 2>(value, 0, f64::from)
+    })
+}
+
+pub fn i8x16_abs(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i8x16.abs", |value| {
+        map_lanes::<i8, 16>(value, i8::wrapping_abs)
+    })
+}
+
+pub fn i8x16_neg(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i8x16.neg", |value| {
+        map_lanes::<i8, 16>(value, i8::wrapping_neg)
+    })
+}
+
+pub fn i8x16_popcnt(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i8x16.popcnt", |value| {
+        map_lanes::<u8, 16>(value, |a| a.count_ones() as u8)
+    })
+}
+
+pub fn i8x16_all_true(vm: &mut VM) -> Result<(), EngineError> {
+    v128_test_op(vm, "i8x16.all_true", |value| {
+        to_lanes::<i8, 16>(value).iter().all(|lane| *lane != 0) as i32
+    })
+}
+
+pub fn i8x16_bitmask(vm: &mut VM) -> Result<(), EngineError> {
+    v128_test_op(vm, "i8x16.bitmask", |value| {
+        to_lanes::<i8, 16>(value)
+            .iter()
+            .enumerate()
+            .fold(0, |mask, (index, lane)| {
+                mask | (((*lane < 0) as i32) << index)
+            })
+    })
+}
+
+pub fn i8x16_narrow_i16x8_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.narrow_i16x8_s", |lhs, rhs| {
+        let (left, right) = (to_lanes::<i16, 8>(lhs), to_lanes::<i16, 8>(rhs));
+        from_lanes::<i8, 16>(std::array::from_fn(|index| {
+            let lane = if index < 8 {
+                left[index]
+            } else {
+                right[index - 8]
+            };
+            lane.clamp(i8::MIN as i16, i8::MAX as i16) as i8
+        }))
+    })
+}
+
+pub fn i8x16_narrow_i16x8_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.narrow_i16x8_u", |lhs, rhs| {
+        let (left, right) = (to_lanes::<i16, 8>(lhs), to_lanes::<i16, 8>(rhs));
+        from_lanes::<u8, 16>(std::array::from_fn(|index| {
+            let lane = if index < 8 {
+                left[index]
+            } else {
+                right[index - 8]
+            };
+            lane.clamp(u8::MIN as i16, u8::MAX as i16) as u8
+        }))
+    })
+}
+
+pub fn f32x4_ceil(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "f32x4.ceil", |value| {
+        map_lanes::<f32, 4>(value, f32::ceil)
+    })
+}
+
+pub fn f32x4_floor(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "f32x4.floor", |value| {
+        map_lanes::<f32, 4>(value, f32::floor)
+    })
+}
+
+pub fn f32x4_trunc(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "f32x4.trunc", |value| {
+        map_lanes::<f32, 4>(value, f32::trunc)
+    })
+}
+
+pub fn f32x4_nearest(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "f32x4.nearest", |value| {
+        map_lanes::<f32, 4>(value, f32::round_ties_even)
+    })
+}
+
+pub fn i8x16_shl(vm: &mut VM) -> Result<(), EngineError> {
+    v128_shift_op(vm, "i8x16.shl", |value, count| {
+        map_lanes::<i8, 16>(value, |a| a.wrapping_shl(count))
+    })
+}
+
+pub fn i8x16_shr_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_shift_op(vm, "i8x16.shr_s", |value, count| {
+        map_lanes::<i8, 16>(value, |a| a.wrapping_shr(count))
+    })
+}
+
+pub fn i8x16_shr_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_shift_op(vm, "i8x16.shr_u", |value, count| {
+        map_lanes::<u8, 16>(value, |a| a.wrapping_shr(count))
+    })
+}
+
+pub fn i8x16_add(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.add", |lhs, rhs| {
+        zip_lanes::<i8, 16>(lhs, rhs, i8::wrapping_add)
+    })
+}
+
+pub fn i8x16_add_sat_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.add_sat_s", |lhs, rhs| {
+        zip_lanes::<i8, 16>(lhs, rhs, i8::saturating_add)
+    })
+}
+
+pub fn i8x16_add_sat_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.add_sat_u", |lhs, rhs| {
+        zip_lanes::<u8, 16>(lhs, rhs, u8::saturating_add)
+    })
+}
+
+pub fn i8x16_sub(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.sub", |lhs, rhs| {
+        zip_lanes::<i8, 16>(lhs, rhs, i8::wrapping_sub)
+    })
+}
+
+pub fn i8x16_sub_sat_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.sub_sat_s", |lhs, rhs| {
+        zip_lanes::<i8, 16>(lhs, rhs, i8::saturating_sub)
+    })
+}
+
+pub fn i8x16_sub_sat_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.sub_sat_u", |lhs, rhs| {
+        zip_lanes::<u8, 16>(lhs, rhs, u8::saturating_sub)
+    })
+}
+
+pub fn f64x2_ceil(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "f64x2.ceil", |value| {
+        map_lanes::<f64, 2>(value, f64::ceil)
+    })
+}
+
+pub fn f64x2_floor(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "f64x2.floor", |value| {
+        map_lanes::<f64, 2>(value, f64::floor)
+    })
+}
+
+pub fn i8x16_min_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.min_s", |lhs, rhs| {
+        zip_lanes::<i8, 16>(lhs, rhs, |a, b| a.min(b))
+    })
+}
+
+pub fn i8x16_min_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.min_u", |lhs, rhs| {
+        zip_lanes::<u8, 16>(lhs, rhs, |a, b| a.min(b))
+    })
+}
+
+pub fn i8x16_max_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.max_s", |lhs, rhs| {
+        zip_lanes::<i8, 16>(lhs, rhs, |a, b| a.max(b))
+    })
+}
+
+pub fn i8x16_max_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.max_u", |lhs, rhs| {
+        zip_lanes::<u8, 16>(lhs, rhs, |a, b| a.max(b))
+    })
+}
+
+pub fn f64x2_trunc(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "f64x2.trunc", |value| {
+        map_lanes::<f64, 2>(value, f64::trunc)
+    })
+}
+
+pub fn i8x16_avgr_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i8x16.avgr_u", |lhs, rhs| {
+        zip_lanes::<u8, 16>(lhs, rhs, |a, b| (a as u16 + b as u16).div_ceil(2) as u8)
+    })
+}
+
+pub fn i16x8_extadd_pairwise_i8x16_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i16x8.extadd_pairwise_i8x16_s", |value| {
+        let lanes = to_lanes::<i8, 16>(value);
+        from_lanes::<i16, 8>(std::array::from_fn(|index| {
+            i16::from(lanes[index * 2]) + i16::from(lanes[index * 2 + 1])
+        }))
+    })
+}
+
+pub fn i16x8_extadd_pairwise_i8x16_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i16x8.extadd_pairwise_i8x16_u", |value| {
+        let lanes = to_lanes::<u8, 16>(value);
+        from_lanes::<u16, 8>(std::array::from_fn(|index| {
+            u16::from(lanes[index * 2]) + u16::from(lanes[index * 2 + 1])
+        }))
+    })
+}
+
+pub fn i32x4_extadd_pairwise_i16x8_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i32x4.extadd_pairwise_i16x8_s", |value| {
+        let lanes = to_lanes::<i16, 8>(value);
+        from_lanes::<i32, 4>(std::array::from_fn(|index| {
+            i32::from(lanes[index * 2]) + i32::from(lanes[index * 2 + 1])
+        }))
+    })
+}
+
+pub fn i32x4_extadd_pairwise_i16x8_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i32x4.extadd_pairwise_i16x8_u", |value| {
+        let lanes = to_lanes::<u16, 8>(value);
+        from_lanes::<u32, 4>(std::array::from_fn(|index| {
+            u32::from(lanes[index * 2]) + u32::from(lanes[index * 2 + 1])
+        }))
+    })
+}
+
+pub fn i16x8_abs(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i16x8.abs", |value| {
+        map_lanes::<i16, 8>(value, i16::wrapping_abs)
+    })
+}
+
+pub fn i16x8_neg(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i16x8.neg", |value| {
+        map_lanes::<i16, 8>(value, i16::wrapping_neg)
+    })
+}
+
+pub fn i16x8_q15mulr_sat_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.q15mulr_sat_s", |lhs, rhs| {
+        zip_lanes::<i16, 8>(lhs, rhs, |a, b| {
+            ((a as i32 * b as i32 + 0x4000) >> 15).clamp(i16::MIN as i32, i16::MAX as i32) as i16
+        })
+    })
+}
+
+pub fn i16x8_all_true(vm: &mut VM) -> Result<(), EngineError> {
+    v128_test_op(vm, "i16x8.all_true", |value| {
+        to_lanes::<i16, 8>(value).iter().all(|lane| *lane != 0) as i32
+    })
+}
+
+pub fn i16x8_bitmask(vm: &mut VM) -> Result<(), EngineError> {
+    v128_test_op(vm, "i16x8.bitmask", |value| {
+        to_lanes::<i16, 8>(value)
+            .iter()
+            .enumerate()
+            .fold(0, |mask, (index, lane)| {
+                mask | (((*lane < 0) as i32) << index)
+            })
+    })
+}
+
+pub fn i16x8_narrow_i32x4_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.narrow_i32x4_s", |lhs, rhs| {
+        let (left, right) = (to_lanes::<i32, 4>(lhs), to_lanes::<i32, 4>(rhs));
+        from_lanes::<i16, 8>(std::array::from_fn(|index| {
+            let lane = if index < 4 {
+                left[index]
+            } else {
+                right[index - 4]
+            };
+            lane.clamp(i16::MIN as i32, i16::MAX as i32) as i16
+        }))
+    })
+}
+
+pub fn i16x8_narrow_i32x4_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.narrow_i32x4_u", |lhs, rhs| {
+        let (left, right) = (to_lanes::<i32, 4>(lhs), to_lanes::<i32, 4>(rhs));
+        from_lanes::<u16, 8>(std::array::from_fn(|index| {
+            let lane = if index < 4 {
+                left[index]
+            } else {
+                right[index - 4]
+            };
+            lane.clamp(u16::MIN as i32, u16::MAX as i32) as u16
+        }))
+    })
+}
+
+pub fn i16x8_extend_low_i8x16_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i16x8.extend_low_i8x16_s", |value| {
+        convert_lanes::<i8, 16, i16, 8>(value, 0, i16::from)
+    })
+}
+
+pub fn i16x8_extend_high_i8x16_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i16x8.extend_high_i8x16_s", |value| {
+        convert_lanes::<i8, 16, i16, 8>(value, 8, i16::from)
+    })
+}
+
+pub fn i16x8_extend_low_i8x16_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i16x8.extend_low_i8x16_u", |value| {
+        convert_lanes::<u8, 16, u16, 8>(value, 0, u16::from)
+    })
+}
+
+pub fn i16x8_extend_high_i8x16_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i16x8.extend_high_i8x16_u", |value| {
+        convert_lanes::<u8, 16, u16, 8>(value, 8, u16::from)
+    })
+}
+
+pub fn i16x8_shl(vm: &mut VM) -> Result<(), EngineError> {
+    v128_shift_op(vm, "i16x8.shl", |value, count| {
+        map_lanes::<i16, 8>(value, |a| a.wrapping_shl(count))
+    })
+}
+
+pub fn i16x8_shr_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_shift_op(vm, "i16x8.shr_s", |value, count| {
+        map_lanes::<i16, 8>(value, |a| a.wrapping_shr(count))
+    })
+}
+
+pub fn i16x8_shr_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_shift_op(vm, "i16x8.shr_u", |value, count| {
+        map_lanes::<u16, 8>(value, |a| a.wrapping_shr(count))
+    })
+}
+
+pub fn i16x8_add(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.add", |lhs, rhs| {
+        zip_lanes::<i16, 8>(lhs, rhs, i16::wrapping_add)
+    })
+}
+
+pub fn i16x8_add_sat_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.add_sat_s", |lhs, rhs| {
+        zip_lanes::<i16, 8>(lhs, rhs, i16::saturating_add)
+    })
+}
+
+pub fn i16x8_add_sat_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.add_sat_u", |lhs, rhs| {
+        zip_lanes::<u16, 8>(lhs, rhs, u16::saturating_add)
+    })
+}
+
+pub fn i16x8_sub(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.sub", |lhs, rhs| {
+        zip_lanes::<i16, 8>(lhs, rhs, i16::wrapping_sub)
+    })
+}
+
+pub fn i16x8_sub_sat_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.sub_sat_s", |lhs, rhs| {
+        zip_lanes::<i16, 8>(lhs, rhs, i16::saturating_sub)
+    })
+}
+
+pub fn i16x8_sub_sat_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.sub_sat_u", |lhs, rhs| {
+        zip_lanes::<u16, 8>(lhs, rhs, u16::saturating_sub)
+    })
+}
+
+pub fn f64x2_nearest(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "f64x2.nearest", |value| {
+        map_lanes::<f64, 2>(value, f64::round_ties_even)
+    })
+}
+
+pub fn i16x8_mul(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.mul", |lhs, rhs| {
+        zip_lanes::<i16, 8>(lhs, rhs, i16::wrapping_mul)
+    })
+}
+
+pub fn i16x8_min_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.min_s", |lhs, rhs| {
+        zip_lanes::<i16, 8>(lhs, rhs, |a, b| a.min(b))
+    })
+}
+
+pub fn i16x8_min_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.min_u", |lhs, rhs| {
+        zip_lanes::<u16, 8>(lhs, rhs, |a, b| a.min(b))
+    })
+}
+
+pub fn i16x8_max_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.max_s", |lhs, rhs| {
+        zip_lanes::<i16, 8>(lhs, rhs, |a, b| a.max(b))
+    })
+}
+
+pub fn i16x8_max_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.max_u", |lhs, rhs| {
+        zip_lanes::<u16, 8>(lhs, rhs, |a, b| a.max(b))
+    })
+}
+
+pub fn i16x8_avgr_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.avgr_u", |lhs, rhs| {
+        zip_lanes::<u16, 8>(lhs, rhs, |a, b| (a as u32 + b as u32).div_ceil(2) as u16)
+    })
+}
+
+pub fn i16x8_extmul_low_i8x16_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.extmul_low_i8x16_s", |lhs, rhs| {
+        zip_lanes::<i16, 8>(
+            convert_lanes::<i8, 16, i16, 8>(lhs, 0, i16::from),
+            convert_lanes::<i8, 16, i16, 8>(rhs, 0, i16::from),
+            i16::wrapping_mul,
+        )
+    })
+}
+
+pub fn i16x8_extmul_high_i8x16_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.extmul_high_i8x16_s", |lhs, rhs| {
+        zip_lanes::<i16, 8>(
+            convert_lanes::<i8, 16, i16, 8>(lhs, 8, i16::from),
+            convert_lanes::<i8, 16, i16, 8>(rhs, 8, i16::from),
+            i16::wrapping_mul,
+        )
+    })
+}
+
+pub fn i16x8_extmul_low_i8x16_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.extmul_low_i8x16_u", |lhs, rhs| {
+        zip_lanes::<u16, 8>(
+            convert_lanes::<u8, 16, u16, 8>(lhs, 0, u16::from),
+            convert_lanes::<u8, 16, u16, 8>(rhs, 0, u16::from),
+            u16::wrapping_mul,
+        )
+    })
+}
+
+pub fn i16x8_extmul_high_i8x16_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i16x8.extmul_high_i8x16_u", |lhs, rhs| {
+        zip_lanes::<u16, 8>(
+            convert_lanes::<u8, 16, u16, 8>(lhs, 8, u16::from),
+            convert_lanes::<u8, 16, u16, 8>(rhs, 8, u16::from),
+            u16::wrapping_mul,
+        )
+    })
+}
+
+pub fn i32x4_abs(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i32x4.abs", |value| {
+        map_lanes::<i32, 4>(value, i32::wrapping_abs)
+    })
+}
+
+pub fn i32x4_neg(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i32x4.neg", |value| {
+        map_lanes::<i32, 4>(value, i32::wrapping_neg)
+    })
+}
+
+pub fn i32x4_all_true(vm: &mut VM) -> Result<(), EngineError> {
+    v128_test_op(vm, "i32x4.all_true", |value| {
+        to_lanes::<i32, 4>(value).iter().all(|lane| *lane != 0) as i32
+    })
+}
+
+pub fn i32x4_bitmask(vm: &mut VM) -> Result<(), EngineError> {
+    v128_test_op(vm, "i32x4.bitmask", |value| {
+        to_lanes::<i32, 4>(value)
+            .iter()
+            .enumerate()
+            .fold(0, |mask, (index, lane)| {
+                mask | (((*lane < 0) as i32) << index)
+            })
+    })
+}
+
+pub fn i32x4_extend_low_i16x8_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i32x4.extend_low_i16x8_s", |value| {
+        convert_lanes::<i16, 8, i32, 4>(value, 0, i32::from)
+    })
+}
+
+pub fn i32x4_extend_high_i16x8_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i32x4.extend_high_i16x8_s", |value| {
+        convert_lanes::<i16, 8, i32, 4>(value, 4, i32::from)
+    })
+}
+
+pub fn i32x4_extend_low_i16x8_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i32x4.extend_low_i16x8_u", |value| {
+        convert_lanes::<u16, 8, u32, 4>(value, 0, u32::from)
+    })
+}
+
+pub fn i32x4_extend_high_i16x8_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i32x4.extend_high_i16x8_u", |value| {
+        convert_lanes::<u16, 8, u32, 4>(value, 4, u32::from)
+    })
+}
+
+pub fn i32x4_shl(vm: &mut VM) -> Result<(), EngineError> {
+    v128_shift_op(vm, "i32x4.shl", |value, count| {
+        map_lanes::<i32, 4>(value, |a| a.wrapping_shl(count))
+    })
+}
+
+pub fn i32x4_shr_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_shift_op(vm, "i32x4.shr_s", |value, count| {
+        map_lanes::<i32, 4>(value, |a| a.wrapping_shr(count))
+    })
+}
+
+pub fn i32x4_shr_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_shift_op(vm, "i32x4.shr_u", |value, count| {
+        map_lanes::<u32, 4>(value, |a| a.wrapping_shr(count))
+    })
+}
+
+pub fn i32x4_add(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i32x4.add", |lhs, rhs| {
+        zip_lanes::<i32, 4>(lhs, rhs, i32::wrapping_add)
+    })
+}
+
+pub fn i32x4_sub(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i32x4.sub", |lhs, rhs| {
+        zip_lanes::<i32, 4>(lhs, rhs, i32::wrapping_sub)
+    })
+}
+
+pub fn i32x4_mul(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i32x4.mul", |lhs, rhs| {
+        zip_lanes::<i32, 4>(lhs, rhs, i32::wrapping_mul)
+    })
+}
+
+pub fn i32x4_min_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i32x4.min_s", |lhs, rhs| {
+        zip_lanes::<i32, 4>(lhs, rhs, |a, b| a.min(b))
+    })
+}
+
+pub fn i32x4_min_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i32x4.min_u", |lhs, rhs| {
+        zip_lanes::<u32, 4>(lhs, rhs, |a, b| a.min(b))
+    })
+}
+
+pub fn i32x4_max_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i32x4.max_s", |lhs, rhs| {
+        zip_lanes::<i32, 4>(lhs, rhs, |a, b| a.max(b))
+    })
+}
+
+pub fn i32x4_max_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i32x4.max_u", |lhs, rhs| {
+        zip_lanes::<u32, 4>(lhs, rhs, |a, b| a.max(b))
+    })
+}
+
+pub fn i32x4_dot_i16x8_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i32x4.dot_i16x8_s", |lhs, rhs| {
+        let (left, right) = (to_lanes::<i16, 8>(lhs), to_lanes::<i16, 8>(rhs));
+        from_lanes::<i32, 4>(std::array::from_fn(|index| {
+            let low = left[index * 2] as i32 * right[index * 2] as i32;
+            let high = left[index * 2 + 1] as i32 * right[index * 2 + 1] as i32;
+            low.wrapping_add(high)
+        }))
+    })
+}
+
+pub fn i32x4_extmul_low_i16x8_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i32x4.extmul_low_i16x8_s", |lhs, rhs| {
+        zip_lanes::<i32, 4>(
+            convert_lanes::<i16, 8, i32, 4>(lhs, 0, i32::from),
+            convert_lanes::<i16, 8, i32, 4>(rhs, 0, i32::from),
+            i32::wrapping_mul,
+        )
+    })
+}
+
+pub fn i32x4_extmul_high_i16x8_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i32x4.extmul_high_i16x8_s", |lhs, rhs| {
+        zip_lanes::<i32, 4>(
+            convert_lanes::<i16, 8, i32, 4>(lhs, 4, i32::from),
+            convert_lanes::<i16, 8, i32, 4>(rhs, 4, i32::from),
+            i32::wrapping_mul,
+        )
+    })
+}
+
+pub fn i32x4_extmul_low_i16x8_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i32x4.extmul_low_i16x8_u", |lhs, rhs| {
+        zip_lanes::<u32, 4>(
+            convert_lanes::<u16, 8, u32, 4>(lhs, 0, u32::from),
+            convert_lanes::<u16, 8, u32, 4>(rhs, 0, u32::from),
+            u32::wrapping_mul,
+        )
+    })
+}
+
+pub fn i32x4_extmul_high_i16x8_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i32x4.extmul_high_i16x8_u", |lhs, rhs| {
+        zip_lanes::<u32, 4>(
+            convert_lanes::<u16, 8, u32, 4>(lhs, 4, u32::from),
+            convert_lanes::<u16, 8, u32, 4>(rhs, 4, u32::from),
+            u32::wrapping_mul,
+        )
+    })
+}
+
+pub fn i64x2_abs(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i64x2.abs", |value| {
+        map_lanes::<i64, 2>(value, i64::wrapping_abs)
+    })
+}
+
+pub fn i64x2_neg(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i64x2.neg", |value| {
+        map_lanes::<i64, 2>(value, i64::wrapping_neg)
+    })
+}
+
+pub fn i64x2_all_true(vm: &mut VM) -> Result<(), EngineError> {
+    v128_test_op(vm, "i64x2.all_true", |value| {
+        to_lanes::<i64, 2>(value).iter().all(|lane| *lane != 0) as i32
+    })
+}
+
+pub fn i64x2_bitmask(vm: &mut VM) -> Result<(), EngineError> {
+    v128_test_op(vm, "i64x2.bitmask", |value| {
+        to_lanes::<i64, 2>(value)
+            .iter()
+            .enumerate()
+            .fold(0, |mask, (index, lane)| {
+                mask | (((*lane < 0) as i32) << index)
+            })
+    })
+}
+
+pub fn i64x2_extend_low_i32x4_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i64x2.extend_low_i32x4_s", |value| {
+        convert_lanes::<i32, 4, i64, 2>(value, 0, i64::from)
+    })
+}
+
+pub fn i64x2_extend_high_i32x4_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i64x2.extend_high_i32x4_s", |value| {
+        convert_lanes::<i32, 4, i64, 2>(value, 2, i64::from)
+    })
+}
+
+pub fn i64x2_extend_low_i32x4_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i64x2.extend_low_i32x4_u", |value| {
+        convert_lanes::<u32, 4, u64, 2>(value, 0, u64::from)
+    })
+}
+
+pub fn i64x2_extend_high_i32x4_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i64x2.extend_high_i32x4_u", |value| {
+        convert_lanes::<u32, 4, u64, 2>(value, 2, u64::from)
+    })
+}
+
+pub fn i64x2_shl(vm: &mut VM) -> Result<(), EngineError> {
+    v128_shift_op(vm, "i64x2.shl", |value, count| {
+        map_lanes::<i64, 2>(value, |a| a.wrapping_shl(count))
+    })
+}
+
+pub fn i64x2_shr_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_shift_op(vm, "i64x2.shr_s", |value, count| {
+        map_lanes::<i64, 2>(value, |a| a.wrapping_shr(count))
+    })
+}
+
+pub fn i64x2_shr_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_shift_op(vm, "i64x2.shr_u", |value, count| {
+        map_lanes::<u64, 2>(value, |a| a.wrapping_shr(count))
+    })
+}
+
+pub fn i64x2_add(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i64x2.add", |lhs, rhs| {
+        zip_lanes::<i64, 2>(lhs, rhs, i64::wrapping_add)
+    })
+}
+
+pub fn i64x2_sub(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i64x2.sub", |lhs, rhs| {
+        zip_lanes::<i64, 2>(lhs, rhs, i64::wrapping_sub)
+    })
+}
+
+pub fn i64x2_mul(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i64x2.mul", |lhs, rhs| {
+        zip_lanes::<i64, 2>(lhs, rhs, i64::wrapping_mul)
+    })
+}
+
+pub fn i64x2_eq(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i64x2.eq", |lhs, rhs| {
+        compare_lanes::<i64, 2>(lhs, rhs, |a, b| a == b)
+    })
+}
+
+pub fn i64x2_ne(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i64x2.ne", |lhs, rhs| {
+        compare_lanes::<i64, 2>(lhs, rhs, |a, b| a != b)
+    })
+}
+
+pub fn i64x2_lt_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i64x2.lt_s", |lhs, rhs| {
+        compare_lanes::<i64, 2>(lhs, rhs, |a, b| a < b)
+    })
+}
+
+pub fn i64x2_gt_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i64x2.gt_s", |lhs, rhs| {
+        compare_lanes::<i64, 2>(lhs, rhs, |a, b| a > b)
+    })
+}
+
+pub fn i64x2_le_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i64x2.le_s", |lhs, rhs| {
+        compare_lanes::<i64, 2>(lhs, rhs, |a, b| a <= b)
+    })
+}
+
+pub fn i64x2_ge_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i64x2.ge_s", |lhs, rhs| {
+        compare_lanes::<i64, 2>(lhs, rhs, |a, b| a >= b)
+    })
+}
+
+pub fn i64x2_extmul_low_i32x4_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i64x2.extmul_low_i32x4_s", |lhs, rhs| {
+        zip_lanes::<i64, 2>(
+            convert_lanes::<i32, 4, i64, 2>(lhs, 0, i64::from),
+            convert_lanes::<i32, 4, i64, 2>(rhs, 0, i64::from),
+            i64::wrapping_mul,
+        )
+    })
+}
+
+pub fn i64x2_extmul_high_i32x4_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i64x2.extmul_high_i32x4_s", |lhs, rhs| {
+        zip_lanes::<i64, 2>(
+            convert_lanes::<i32, 4, i64, 2>(lhs, 2, i64::from),
+            convert_lanes::<i32, 4, i64, 2>(rhs, 2, i64::from),
+            i64::wrapping_mul,
+        )
+    })
+}
+
+pub fn i64x2_extmul_low_i32x4_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i64x2.extmul_low_i32x4_u", |lhs, rhs| {
+        zip_lanes::<u64, 2>(
+            convert_lanes::<u32, 4, u64, 2>(lhs, 0, u64::from),
+            convert_lanes::<u32, 4, u64, 2>(rhs, 0, u64::from),
+            u64::wrapping_mul,
+        )
+    })
+}
+
+pub fn i64x2_extmul_high_i32x4_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "i64x2.extmul_high_i32x4_u", |lhs, rhs| {
+        zip_lanes::<u64, 2>(
+            convert_lanes::<u32, 4, u64, 2>(lhs, 2, u64::from),
+            convert_lanes::<u32, 4, u64, 2>(rhs, 2, u64::from),
+            u64::wrapping_mul,
+        )
+    })
+}
+
+pub fn f32x4_abs(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "f32x4.abs", |value| {
+        map_lanes::<f32, 4>(value, f32::abs)
+    })
+}
+
+pub fn f32x4_neg(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "f32x4.neg", |value| map_lanes::<f32, 4>(value, |a| -a))
+}
+
+pub fn f32x4_sqrt(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "f32x4.sqrt", |value| {
+        map_lanes::<f32, 4>(value, f32::sqrt)
+    })
+}
+
+pub fn f32x4_add(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f32x4.add", |lhs, rhs| {
+        zip_lanes::<f32, 4>(lhs, rhs, |a, b| a + b)
+    })
+}
+
+pub fn f32x4_sub(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f32x4.sub", |lhs, rhs| {
+        zip_lanes::<f32, 4>(lhs, rhs, |a, b| a - b)
+    })
+}
+
+pub fn f32x4_mul(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f32x4.mul", |lhs, rhs| {
+        zip_lanes::<f32, 4>(lhs, rhs, |a, b| a * b)
+    })
+}
+
+pub fn f32x4_div(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f32x4.div", |lhs, rhs| {
+        zip_lanes::<f32, 4>(lhs, rhs, |a, b| a / b)
+    })
+}
+
+pub fn f32x4_min(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f32x4.min", |lhs, rhs| {
+        zip_lanes::<f32, 4>(lhs, rhs, f32_min)
+    })
+}
+
+pub fn f32x4_max(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f32x4.max", |lhs, rhs| {
+        zip_lanes::<f32, 4>(lhs, rhs, f32_max)
+    })
+}
+
+pub fn f32x4_pmin(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f32x4.pmin", |lhs, rhs| {
+        zip_lanes::<f32, 4>(lhs, rhs, |a, b| if b < a { b } else { a })
+    })
+}
+
+pub fn f32x4_pmax(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f32x4.pmax", |lhs, rhs| {
+        zip_lanes::<f32, 4>(lhs, rhs, |a, b| if a < b { b } else { a })
+    })
+}
+
+pub fn f64x2_abs(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "f64x2.abs", |value| {
+        map_lanes::<f64, 2>(value, f64::abs)
+    })
+}
+
+pub fn f64x2_neg(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "f64x2.neg", |value| map_lanes::<f64, 2>(value, |a| -a))
+}
+
+pub fn f64x2_sqrt(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "f64x2.sqrt", |value| {
+        map_lanes::<f64, 2>(value, f64::sqrt)
+    })
+}
+
+pub fn f64x2_add(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f64x2.add", |lhs, rhs| {
+        zip_lanes::<f64, 2>(lhs, rhs, |a, b| a + b)
+    })
+}
+
+pub fn f64x2_sub(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f64x2.sub", |lhs, rhs| {
+        zip_lanes::<f64, 2>(lhs, rhs, |a, b| a - b)
+    })
+}
+
+pub fn f64x2_mul(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f64x2.mul", |lhs, rhs| {
+        zip_lanes::<f64, 2>(lhs, rhs, |a, b| a * b)
+    })
+}
+
+pub fn f64x2_div(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f64x2.div", |lhs, rhs| {
+        zip_lanes::<f64, 2>(lhs, rhs, |a, b| a / b)
+    })
+}
+
+pub fn f64x2_min(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f64x2.min", |lhs, rhs| {
+        zip_lanes::<f64, 2>(lhs, rhs, f64_min)
+    })
+}
+
+pub fn f64x2_max(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f64x2.max", |lhs, rhs| {
+        zip_lanes::<f64, 2>(lhs, rhs, f64_max)
+    })
+}
+
+pub fn f64x2_pmin(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f64x2.pmin", |lhs, rhs| {
+        zip_lanes::<f64, 2>(lhs, rhs, |a, b| if b < a { b } else { a })
+    })
+}
+
+pub fn f64x2_pmax(vm: &mut VM) -> Result<(), EngineError> {
+    v128_binary_op(vm, "f64x2.pmax", |lhs, rhs| {
+        zip_lanes::<f64, 2>(lhs, rhs, |a, b| if a < b { b } else { a })
+    })
+}
+
+pub fn i32x4_trunc_sat_f32x4_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i32x4.trunc_sat_f32x4_s", |value| {
+        convert_lanes::<f32, 4, i32, 4>(value, 0, |a| a as i32)
+    })
+}
+
+pub fn i32x4_trunc_sat_f32x4_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i32x4.trunc_sat_f32x4_u", |value| {
+        convert_lanes::<f32, 4, u32, 4>(value, 0, |a| a as u32)
+    })
+}
+
+pub fn f32x4_convert_i32x4_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "f32x4.convert_i32x4_s", |value| {
+        convert_lanes::<i32, 4, f32, 4>(value, 0, |a| a as f32)
+    })
+}
+
+pub fn f32x4_convert_i32x4_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "f32x4.convert_i32x4_u", |value| {
+        convert_lanes::<u32, 4, f32, 4>(value, 0, |a| a as f32)
+    })
+}
+
+pub fn i32x4_trunc_sat_f64x2_s_zero(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i32x4.trunc_sat_f64x2_s_zero", |value| {
+        let lanes = to_lanes::<f64, 2>(value);
+        from_lanes::<i32, 4>([lanes[0] as i32, lanes[1] as i32, 0, 0])
+    })
+}
+
+pub fn i32x4_trunc_sat_f64x2_u_zero(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "i32x4.trunc_sat_f64x2_u_zero", |value| {
+        let lanes = to_lanes::<f64, 2>(value);
+        from_lanes::<u32, 4>([lanes[0] as u32, lanes[1] as u32, 0, 0])
+    })
+}
+
+pub fn f64x2_convert_low_i32x4_s(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "f64x2.convert_low_i32x4_s", |value| {
+        convert_lanes::<i32, 4, f64, 2>(value, 0, f64::from)
+    })
+}
+
+pub fn f64x2_convert_low_i32x4_u(vm: &mut VM) -> Result<(), EngineError> {
+    v128_unary_op(vm, "f64x2.convert_low_i32x4_u", |value| {
+        convert_lanes::<u32, 4, f64, 2>(value, 0, f64::from)
+    })
+}
+
+/// 向量的通道的数据类型
+trait Lane: Copy + Default + PartialEq + PartialOrd {
+    /// 通道的字节数
+    const SIZE: usize;
+
+    fn from_le_slice(bytes: &[u8]) -> Self;
+    fn write_le_slice(self, bytes: &mut [u8]);
+}
+
+macro_rules! impl_lane {
+    ($($lane_type:ty),*) => {
+        $(
+            impl Lane for $lane_type {
+                const SIZE: usize = std::mem::size_of::<$lane_type>();
+
+                fn from_le_slice(bytes: &[u8]) -> Self {
+                    <$lane_type>::from_le_bytes(bytes.try_into().unwrap())
+                }
+
+                fn write_le_slice(self, bytes: &mut [u8]) {
+                    bytes.copy_from_slice(&self.to_le_bytes())
+                }
+            }
+        )*
+    };
+}
+
+impl_lane!(i8, u8, i16, u16, i32, u32, i64, u64, f32, f64);
+
+/// 把向量拆分为通道数组，N 为通道数
+fn to_lanes<T: Lane, const N: usize>(value: u128) -> [T; N] {
+    let bytes = value.to_le_bytes();
+    std::array::from_fn(|index| T::from_le_slice(&bytes[index * T::SIZE..(index + 1) * T::SIZE]))
+}
+
+/// 把通道数组合并为向量
+fn from_lanes<T: Lane, const N: usize>(lanes: [T; N]) -> u128 {
+    let mut bytes = [0u8; 16];
+    for (index, lane) in lanes.iter().enumerate() {
+        lane.write_le_slice(&mut bytes[index * T::SIZE..(index + 1) * T::SIZE]);
+    }
+    u128::from_le_bytes(bytes)
+}
+
+/// 逐个通道进行一元运算
+fn map_lanes<T: Lane, const N: usize>(value: u128, op: impl Fn(T) -> T) -> u128 {
+    from_lanes(to_lanes::<T, N>(value).map(op))
+}
+
+/// 逐个通道进行二元运算
+fn zip_lanes<T: Lane, const N: usize>(lhs: u128, rhs: u128, op: impl Fn(T, T) -> T) -> u128 {
+    let (left, right) = (to_lanes::<T, N>(lhs), to_lanes::<T, N>(rhs));
+    from_lanes::<T, N>(std::array::from_fn(|index| op(left[index], right[index])))
+}
+
+/// 逐个通道进行比较，结果为真的通道的所有位都设置为 1，否则为 0
+fn compare_lanes<T: Lane, const N: usize>(lhs: u128, rhs: u128, op: impl Fn(T, T) -> bool) -> u128 {
+    let (left, right) = (to_lanes::<T, N>(lhs), to_lanes::<T, N>(rhs));
+    let mut bytes = [0u8; 16];
+    for index in 0..N {
+        if op(left[index], right[index]) {
+            bytes[index * T::SIZE..(index + 1) * T::SIZE].fill(0xff);
+        }
+    }
+    u128::from_le_bytes(bytes)
+}
+
+/// 从第 start 个通道开始，把 M 个通道转换为另一种形状的通道，
+/// 用于实现加宽（extend）以及整数和浮点数之间的转换
+fn convert_lanes<T: Lane, const N: usize, U: Lane, const M: usize>(
+    value: u128,
+    start: usize,
+    op: impl Fn(T) -> U,
+) -> u128 {
+    let lanes = to_lanes::<T, N>(value);
+    from_lanes::<U, M>(std::array::from_fn(|index| op(lanes[start + index])))
+}
+
+/// 把不超过 16 个字节的数据转换为向量，不足的高端字节补 0
+fn u128_from_bytes(bytes: &[u8]) -> u128 {
+    let mut buffer = [0u8; 16];
+    buffer[..bytes.len()].copy_from_slice(bytes);
+    u128::from_le_bytes(buffer)
+}
+
+/// 浮点数的 min 运算
+///
+/// 跟 Rust 的 f32::min() 不同，只要有一个操作数是 NaN，结果就是 NaN，
+/// 另外 -0 小于 +0。
+fn f32_min(lhs: f32, rhs: f32) -> f32 {
+    if lhs.is_nan() || rhs.is_nan() {
+        lhs + rhs
+    } else if lhs == rhs {
+        if lhs.is_sign_negative() {
+            lhs
+        } else {
+            rhs
+        }
+    } else {
+        lhs.min(rhs)
+    }
+}
+
+fn f32_max(lhs: f32, rhs: f32) -> f32 {
+    if lhs.is_nan() || rhs.is_nan() {
+        lhs + rhs
+    } else if lhs == rhs {
+        if lhs.is_sign_positive() {
+            lhs
+        } else {
+            rhs
+        }
+    } else {
+        lhs.max(rhs)
+    }
+}
+
+fn f64_min(lhs: f64, rhs: f64) -> f64 {
+    if lhs.is_nan() || rhs.is_nan() {
+        lhs + rhs
+    } else if lhs == rhs {
+        if lhs.is_sign_negative() {
+            lhs
+        } else {
+            rhs
+        }
+    } else {
+        lhs.min(rhs)
+    }
+}
+
+fn f64_max(lhs: f64, rhs: f64) -> f64 {
+    if lhs.is_nan() || rhs.is_nan() {
+        lhs + rhs
+    } else if lhs == rhs {
+        if lhs.is_sign_positive() {
+            lhs
+        } else {
+            rhs
+        }
+    } else {
+        lhs.max(rhs)
+    }
+}
+
+fn pop_v128(vm: &mut VM, instruction_name: &str) -> Result<u128, EngineError> {
+    let value = vm.stack.pop();
+    match value {
+        Value::V128(v) => Ok(v),
+        _ => Err(make_operand_data_types_mismatch_engine_error(
+            instruction_name,
+            vec![ValueType::V128],
+            vec![&value],
+        )),
+    }
+}
+
+fn pop_i32(vm: &mut VM, instruction_name: &str) -> Result<i32, EngineError> {
+    let value = vm.stack.pop();
+    match value {
+        Value::I32(v) => Ok(v),
+        _ => Err(make_operand_data_types_mismatch_engine_error(
+            instruction_name,
+            vec![ValueType::I32],
+            vec![&value],
+        )),
+    }
+}
+
+fn pop_i64(vm: &mut VM, instruction_name: &str) -> Result<i64, EngineError> {
+    let value = vm.stack.pop();
+    match value {
+        Value::I64(v) => Ok(v),
+        _ => Err(make_operand_data_types_mismatch_engine_error(
+            instruction_name,
+            vec![ValueType::I64],
+            vec![&value],
+        )),
+    }
+}
+
+fn pop_f32(vm: &mut VM, instruction_name: &str) -> Result<f32, EngineError> {
+    let value = vm.stack.pop();
+    match value {
+        Value::F32(v) => Ok(v),
+        _ => Err(make_operand_data_types_mismatch_engine_error(
+            instruction_name,
+            vec![ValueType::F32],
+            vec![&value],
+        )),
+    }
+}
+
+fn pop_f64(vm: &mut VM, instruction_name: &str) -> Result<f64, EngineError> {
+    let value = vm.stack.pop();
+    match value {
+        Value::F64(v) => Ok(v),
+        _ => Err(make_operand_data_types_mismatch_engine_error(
+            instruction_name,
+            vec![ValueType::F64],
+            vec![&value],
+        )),
+    }
+}
+
+fn v128_unary_op(
+    vm: &mut VM,
+    instruction_name: &str,
+    op: impl Fn(u128) -> u128,
+) -> Result<(), EngineError> {
+    let value = pop_v128(vm, instruction_name)?;
+    vm.stack.push(Value::V128(op(value)));
+    Ok(())
+}
+
+fn v128_binary_op(
+    vm: &mut VM,
+    instruction_name: &str,
+    op: impl Fn(u128, u128) -> u128,
+) -> Result<(), EngineError> {
+    let rhs = pop_v128(vm, instruction_name)?;
+    let lhs = pop_v128(vm, instruction_name)?;
+    vm.stack.push(Value::V128(op(lhs, rhs)));
+    Ok(())
+}
+
+/// 移位指令，从操作数栈先弹出 i32 作为移位的位数，再弹出向量
+///
+/// 位数会对通道的位宽取模，比如 `i8x16.shl` 移动 9 位跟移动 1 位的结果是一样的。
+fn v128_shift_op(
+    vm: &mut VM,
+    instruction_name: &str,
+    op: impl Fn(u128, u32) -> u128,
+) -> Result<(), EngineError> {
+    let count = pop_i32(vm, instruction_name)?;
+    let value = pop_v128(vm, instruction_name)?;
+    vm.stack.push(Value::V128(op(value, count as u32)));
+    Ok(())
+}
+
+/// 从向量得到一个 i32 的指令，比如 `v128.any_true`、`i8x16.all_true`、`i8x16.bitmask`
+fn v128_test_op(
+    vm: &mut VM,
+    instruction_name: &str,
+    op: impl Fn(u128) -> i32,
+) -> Result<(), EngineError> {
+    let value = pop_v128(vm, instruction_name)?;
+    vm.stack.push(Value::I32(op(value)));
+    Ok(())
+}
+
+/// 获取当前模块的内存块，内存块的索引由指令的内存参数指定
+fn get_memory_block<'a>(vm: &'a mut VM, memory_args: &MemoryArgument) -> &'a mut VMMemory {
+    let instance_memory_block_index = vm.resource.vm_modules[vm.status.vm_module_index]
+        .memory_indexes[memory_args.memory_block_index as usize];
+    &mut vm.resource.memory_blocks[instance_memory_block_index]
+}
+
+/// 加载 length 个字节，然后转换为向量
+fn v128_load_op(
+    vm: &mut VM,
+    instruction_name: &str,
+    memory_args: &MemoryArgument,
+    length: usize,
+    op: impl Fn(&[u8]) -> u128,
+) -> Result<(), EngineError> {
+    let address = pop_effective_address(vm, instruction_name, memory_args, length)?;
+    let value = op(&get_memory_block(vm, memory_args).read_bytes(address, length));
+    vm.stack.push(Value::V128(value));
+    Ok(())
+}
+
+fn v128_store_op(
+    vm: &mut VM,
+    instruction_name: &str,
+    memory_args: &MemoryArgument,
+) -> Result<(), EngineError> {
+    let value = pop_v128(vm, instruction_name)?;
+    let address = pop_effective_address(vm, instruction_name, memory_args, 16)?;
+    get_memory_block(vm, memory_args).write_bytes(address, &value.to_le_bytes());
+    Ok(())
+}
+
+/// 加载 lane_size 个字节，替换向量的指定通道
+fn v128_load_lane_op(
+    vm: &mut VM,
+    instruction_name: &str,
+    memory_args: &MemoryArgument,
+    lane_index: u8,
+    lane_size: usize,
+) -> Result<(), EngineError> {
+    let value = pop_v128(vm, instruction_name)?;
+    let address = pop_effective_address(vm, instruction_name, memory_args, lane_size)?;
+
+    let mut bytes = value.to_le_bytes();
+    let start = lane_index as usize * lane_size;
+    bytes[start..start + lane_size]
+        .copy_from_slice(&get_memory_block(vm, memory_args).read_bytes(address, lane_size));
+
+    vm.stack.push(Value::V128(u128::from_le_bytes(bytes)));
+    Ok(())
+}
+
+/// 把向量的指定通道（lane_size 个字节）写入内存
+fn v128_store_lane_op(
+    vm: &mut VM,
+    instruction_name: &str,
+    memory_args: &MemoryArgument,
+    lane_index: u8,
+    lane_size: usize,
+) -> Result<(), EngineError> {
+    let value = pop_v128(vm, instruction_name)?;
+    let address = pop_effective_address(vm, instruction_name, memory_args, lane_size)?;
+
+    let bytes = value.to_le_bytes();
+    let start = lane_index as usize * lane_size;
+    get_memory_block(vm, memory_args).write_bytes(address, &bytes[start..start + lane_size]);
+    Ok(())
+}
@@ -0,0 +1,159 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 表指令
+//!
+//! - table.get table_idx:uint32
+//!   从操作数栈弹出 uint32 作为槽的索引，压入该槽的引用值
+//!
+//! - table.set table_idx:uint32
+//!   从操作数栈先弹出引用值，再弹出 uint32 作为槽的索引，把引用值写入该槽
+//!
+//! - table.size table_idx:uint32
+//!   返回指定表的槽数（uint32）
+//!
+//! - table.grow table_idx:uint32
+//!   从操作数栈先弹出 uint32 作为增加量，再弹出新槽的初始值，
+//!   成功则返回旧的槽数，失败（比如超出限制值的 max）则返回 -1:uint32
+//!
+//! - table.fill table_idx:uint32
+//!   从操作数栈依次弹出数量 n、引用值 value 以及开始位置 i，
+//!   把区间 [i, i + n) 的槽都设置为 value
+//!
+//! - table.copy dest_table_idx:uint32 source_table_idx:uint32
+//!   从操作数栈依次弹出数量 n、源位置 s 以及目标位置 d，
+//!   把区间 [s, s + n) 的槽复制到 [d, d + n)，两个区间允许重叠
+//!
+//! - table.init elem_idx:uint32 table_idx:uint32
+//!   从操作数栈依次弹出数量 n、元素段内的位置 s 以及表的位置 d，
+//!   把元素段的区间 [s, s + n) 的引用值复制到表的区间 [d, d + n)
+//!
+//! - elem.drop elem_idx:uint32
+//!   丢弃指定的元素段，丢弃之后元素段的内容为空
+//!
+//! 批量操作（fill、copy 以及 init）在访问越界时不会修改表的内容。
+//!
+//! https://webassembly.github.io/spec/core/syntax/instructions.html#table-instructions
+
+use anvm_ast::types::{Value, ValueType};
+
+use crate::{
+    error::{make_operand_data_types_mismatch_engine_error, EngineError, OutOfRange},
+    vm::VM,
+    vm_table::VMTable,
+};
+
+pub fn table_get(vm: &mut VM, table_index: u32) -> Result<(), EngineError> {
+    let index = pop_u32(vm, "table.get")?;
+    let value = get_table(vm, table_index)?.get_element(index as usize)?;
+
+    vm.stack.push(value);
+    Ok(())
+}
+
+pub fn table_set(vm: &mut VM, table_index: u32) -> Result<(), EngineError> {
+    let value = vm.stack.pop();
+    let index = pop_u32(vm, "table.set")?;
+
+    get_table(vm, table_index)?.set_element(index as usize, value)
+}
+
+pub fn table_size(vm: &mut VM, table_index: u32) -> Result<(), EngineError> {
+    let size = get_table(vm, table_index)?.get_size();
+
+    vm.stack.push(Value::I32(size as i32));
+    Ok(())
+}
+
+pub fn table_grow(vm: &mut VM, table_index: u32) -> Result<(), EngineError> {
+    let increase_number = pop_u32(vm, "table.grow")?;
+    let value = vm.stack.pop();
+
+    let result = get_table(vm, table_index)?.increase_size_with_value(increase_number, value);
+    match result {
+        Ok(previous_size) => vm.stack.push(Value::I32(previous_size as i32)),
+        _ => vm.stack.push(Value::I32(-1)),
+    }
+
+    Ok(())
+}
+
+pub fn table_fill(vm: &mut VM, table_index: u32) -> Result<(), EngineError> {
+    let length = pop_u32(vm, "table.fill")?;
+    let value = vm.stack.pop();
+    let offset = pop_u32(vm, "table.fill")?;
+
+    get_table(vm, table_index)?.fill_elements(offset as usize, length as usize, value)
+}
+
+pub fn table_copy(
+    vm: &mut VM,
+    dest_table_index: u32,
+    source_table_index: u32,
+) -> Result<(), EngineError> {
+    let length = pop_u32(vm, "table.copy")? as usize;
+    let source_offset = pop_u32(vm, "table.copy")? as usize;
+    let dest_offset = pop_u32(vm, "table.copy")? as usize;
+
+    if dest_table_index == source_table_index {
+        return get_table(vm, dest_table_index)?.copy_elements(dest_offset, source_offset, length);
+    }
+
+    // 在不同的表之间复制时，先读取源表的元素，再写入目标表
+    let values = get_table(vm, source_table_index)?.read_elements(source_offset, length)?;
+    get_table(vm, dest_table_index)?.write_elements(dest_offset, &values)
+}
+
+pub fn table_init(vm: &mut VM, element_index: u32, table_index: u32) -> Result<(), EngineError> {
+    let length = pop_u32(vm, "table.init")? as usize;
+    let source_offset = pop_u32(vm, "table.init")? as usize;
+    let dest_offset = pop_u32(vm, "table.init")? as usize;
+
+    let vm_module_index = vm.status.vm_module_index;
+    let vm_module = &vm.resource.vm_modules[vm_module_index];
+    let element_segment = &vm_module.element_segments[element_index as usize];
+
+    let values = match source_offset.checked_add(length) {
+        Some(end) if end <= element_segment.len() => element_segment[source_offset..end].to_vec(),
+        _ => {
+            return Err(EngineError::OutOfRange(
+                OutOfRange::ElementSegmentAccessOutOfRange(
+                    source_offset,
+                    length,
+                    element_segment.len(),
+                ),
+            ))
+        }
+    };
+
+    get_table(vm, table_index)?.write_elements(dest_offset, &values)
+}
+
+pub fn elem_drop(vm: &mut VM, element_index: u32) -> Result<(), EngineError> {
+    let vm_module = &mut vm.resource.vm_modules[vm.status.vm_module_index];
+    vm_module.element_segments[element_index as usize] = vec![];
+    Ok(())
+}
+
+/// 获取当前模块的表
+fn get_table(vm: &mut VM, table_index: u32) -> Result<&mut VMTable, EngineError> {
+    let instance_table_index =
+        vm.resource.vm_modules[vm.status.vm_module_index].table_indexes[table_index as usize];
+    Ok(&mut vm.resource.tables[instance_table_index])
+}
+
+/// 弹出一个 i32 操作数，并作为无符号整数（比如槽的索引、数量等）看待
+fn pop_u32(vm: &mut VM, instruction_name: &str) -> Result<u32, EngineError> {
+    let value = vm.stack.pop();
+    match value {
+        Value::I32(v) => Ok(v as u32),
+        _ => Err(make_operand_data_types_mismatch_engine_error(
+            instruction_name,
+            vec![ValueType::I32],
+            vec![&value],
+        )),
+    }
+}
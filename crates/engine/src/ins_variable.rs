@@ -50,6 +50,27 @@ pub fn local_tee(vm: &mut VM, index: u32) -> Result<(), EngineError> {
     Ok(())
 }
 
+/// 直接复制栈槽的 `local.get 指令`，只能用于非 v128 类型的局部变量
+pub fn local_get_slot(vm: &mut VM, index: u32) -> Result<(), EngineError> {
+    let offset = vm.status.local_pointer + (index as usize);
+    vm.stack.push_from_slot(offset);
+    Ok(())
+}
+
+/// 直接复制栈槽的 `local.set 指令`，只能用于非 v128 类型的局部变量
+pub fn local_set_slot(vm: &mut VM, index: u32) -> Result<(), EngineError> {
+    let offset = vm.status.local_pointer + (index as usize);
+    vm.stack.pop_to_slot(offset);
+    Ok(())
+}
+
+/// 直接复制栈槽的 `local.tee 指令`，只能用于非 v128 类型的局部变量
+pub fn local_tee_slot(vm: &mut VM, index: u32) -> Result<(), EngineError> {
+    let offset = vm.status.local_pointer + (index as usize);
+    vm.stack.copy_top_to_slot(offset);
+    Ok(())
+}
+
 pub fn global_get(vm: &mut VM, index: u32) -> Result<(), EngineError> {
     let instance_global_variable_index =
         vm.resource.vm_modules[vm.status.vm_module_index].global_variable_indexes[index as usize];
@@ -4,50 +4,193 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+use std::rc::Rc;
+
 use anvm_ast::{
-    ast::{self, FunctionType, TypeItem},
+    ast::{self, DataMode, ElementItems, ElementMode, FunctionType, ImportDescriptor, TypeItem},
+    instruction,
     types::{Value, ValueType},
 };
+use anvm_validator::{
+    error::ValidationError,
+    validator::{count_local_variables, validate},
+};
 
 use crate::{
-    decoder::{decode, decode_constant_expression},
-    error::{EngineError, TypeMismatch, Unsupported},
-    linker::{link_functions, link_global_variables, link_memorys, link_tables},
+    decoder::{decode, decode_constant_expression, fuse_instructions, DecodedInstructions},
+    error::{EngineError, OutOfRange, TypeMismatch},
+    linker::{
+        get_function_reference_bases, link_functions, link_global_variables, link_memorys,
+        link_tables, link_tags,
+    },
+    module_cache::{CompiledModules, ModuleCache},
     native_module::NativeModule,
-    object::NamedAstModule,
-    vm::{Resource, Status, VM},
+    object::{FunctionItem, FusedItem, Instruction, NamedAstModule},
+    register::RegisterCode,
+    vm::{InterpreterKind, Resource, Status, VM},
+    vm_memory::VMMemory,
     vm_module::VMModule,
     vm_stack::VMStack,
 };
 
+#[cfg(feature = "jit")]
+use crate::jit::JitCode;
+
 pub fn create_instance(
     native_modules: Vec<NativeModule>,
     named_ast_modules: &[NamedAstModule],
+) -> Result<VM, EngineError> {
+    create_instance_with_interpreter(native_modules, named_ast_modules, InterpreterKind::Stack)
+}
+
+/// 创建 VM 实例，并指定执行函数时使用的解释器
+///
+/// 两种解释器的执行结果相同，寄存器解释器需要在实例化时把各个模块的
+/// 内部函数降低为寄存器中间表示。
+pub fn create_instance_with_interpreter(
+    native_modules: Vec<NativeModule>,
+    named_ast_modules: &[NamedAstModule],
+    interpreter_kind: InterpreterKind,
+) -> Result<VM, EngineError> {
+    let linked_and_decoded = link_and_decode(&native_modules, named_ast_modules, None)?;
+    instantiate(
+        native_modules,
+        named_ast_modules,
+        linked_and_decoded,
+        vec![],
+        interpreter_kind,
+    )
+}
+
+/// 创建 VM 实例，并使用编译模块缓存
+///
+/// 当所有模块都有内容哈希值（见 `NamedAstModule::with_content_hash`）时，
+/// 先尝试从缓存读取链接和解码的结果（此时不再验证模块），缓存不存在时则正常验证、
+/// 链接和解码，然后写入缓存。读写缓存失败并不会影响实例化。
+///
+/// 这个函数用于模块已经解析的情况，如果想连解析也省去，可以先根据模块的二进制内容
+/// 读取缓存（见 `ModuleCache::compute_key_by_content_hashes` 和 `ModuleCache::load`），
+/// 然后使用 `create_instance_with_compiled_modules` 创建实例。
+pub fn create_instance_with_cache(
+    native_modules: Vec<NativeModule>,
+    named_ast_modules: &[NamedAstModule],
+    interpreter_kind: InterpreterKind,
+    module_cache: &ModuleCache,
+) -> Result<VM, EngineError> {
+    let linked_and_decoded =
+        link_and_decode(&native_modules, named_ast_modules, Some(module_cache))?;
+    instantiate(
+        native_modules,
+        named_ast_modules,
+        linked_and_decoded,
+        vec![],
+        interpreter_kind,
+    )
+}
+
+/// 使用从编译模块缓存读取的 AST 模块以及链接和解码的结果创建 VM 实例
+///
+/// 缓存里的模块在写入之前已经通过验证，所以这里不再验证模块，也不再链接和解码。
+/// named_ast_modules 和 compiled_modules 必须是 `ModuleCache::load` 的同一个返回值。
+pub fn create_instance_with_compiled_modules(
+    native_modules: Vec<NativeModule>,
+    named_ast_modules: &[NamedAstModule],
+    compiled_modules: CompiledModules,
+    interpreter_kind: InterpreterKind,
+) -> Result<VM, EngineError> {
+    let (function_items_list, instructions_list) = compiled_modules;
+    let fused_items_list = fuse_instructions_list(&instructions_list);
+    instantiate(
+        native_modules,
+        named_ast_modules,
+        (function_items_list, (instructions_list, fused_items_list)),
+        vec![],
+        interpreter_kind,
+    )
+}
+
+/// 为新线程创建 VM 实例
+///
+/// shared_memory_blocks 是（父线程 VM 实例的）内存块实例列表，其中共享内存块为 Some，
+/// 新实例将会使用这些共享内存块代替同一索引位置的新内存块，并且不再往这些共享内存块
+/// 填充 data 段，而其余的内存块、表以及全局变量等都是新实例独有的。
+pub fn create_thread_instance(
+    native_modules: Vec<NativeModule>,
+    named_ast_modules: &[NamedAstModule],
+    shared_memory_blocks: Vec<Option<VMMemory>>,
+) -> Result<VM, EngineError> {
+    let linked_and_decoded = link_and_decode(&native_modules, named_ast_modules, None)?;
+    instantiate(
+        native_modules,
+        named_ast_modules,
+        linked_and_decoded,
+        shared_memory_blocks,
+        InterpreterKind::Stack,
+    )
+}
+
+/// 只链接和解码模块，不创建 VM 实例
+///
+/// 用于检查模块能否链接，或者单独测量链接和解码阶段所需的时间。
+pub fn link_and_decode_modules(
+    native_modules: &[NativeModule],
+    named_ast_modules: &[NamedAstModule],
+) -> Result<(), EngineError> {
+    link_and_decode(native_modules, named_ast_modules, None).map(|_| ())
+}
+
+/// linked_and_decoded 是链接和解码的结果（见 `link_and_decode`）
+fn instantiate(
+    native_modules: Vec<NativeModule>,
+    named_ast_modules: &[NamedAstModule],
+    linked_and_decoded: (Vec<Vec<FunctionItem>>, DecodedInstructions),
+    shared_memory_blocks: Vec<Option<VMMemory>>,
+    interpreter_kind: InterpreterKind,
 ) -> Result<VM, EngineError> {
     // 获取指令列表
     // 指令列表跟 AST 模块列表是一一对应的，所以无需映射表
-    let mut function_items_list = link_functions(&native_modules, named_ast_modules)?;
-    let mut instructions_list = decode(named_ast_modules, &function_items_list)?;
-
-    // 获取 "表" 实例列表，以及 "AST 模块 - 表" 映射表
-    let (tables, mut module_to_table_index_list) = link_tables(named_ast_modules)?;
+    let (mut function_items_list, (mut instructions_list, mut fused_items_list)) =
+        linked_and_decoded;
+
+    // 获取 "表" 实例列表，以及 "AST 模块 - 表列表" 映射表
+    let (tables, mut module_to_tables_list) = link_tables(named_ast_modules)?;
+
+    // 获取内存块实例列表，以及 "AST 模块 - 内存块列表" 映射表
+    let (mut memory_blocks, mut module_to_memory_blocks_list) = link_memorys(named_ast_modules)?;
+
+    // 使用共享内存块代替新创建的内存块
+    let mut shared_memory_block_indices: Vec<usize> = vec![];
+    for (index, option_memory_block) in shared_memory_blocks.into_iter().enumerate() {
+        if let Some(memory_block) = option_memory_block {
+            if index >= memory_blocks.len() {
+                return Err(EngineError::OutOfRange(
+                    OutOfRange::MemoryBlockIndexOutOfRange(index, memory_blocks.len()),
+                ));
+            }
 
-    // 获取内存块实例列表，以及 "AST 模块 - 内存块" 映射表
-    let (memory_blocks, mut module_to_memory_block_index_list) = link_memorys(named_ast_modules)?;
+            memory_blocks[index] = memory_block;
+            shared_memory_block_indices.push(index);
+        }
+    }
 
     // 获取全局变量实例列表，以及 "AST 模块 - 全局变量列表" 映射表
     let (global_variables, mut module_to_global_variables_list) =
         link_global_variables(named_ast_modules)?;
 
+    // 获取标签实例列表，以及 "AST 模块 - 标签列表" 映射表
+    let (tags, mut module_to_tags_list) = link_tags(&native_modules, named_ast_modules)?;
+
     let ast_module_count = named_ast_modules.len();
     let mut vm_modules: Vec<VMModule> = vec![];
 
     for reverse_index in 0..ast_module_count {
         let function_items = function_items_list.pop().unwrap();
         let instructions = instructions_list.pop().unwrap();
-        let table_index = module_to_table_index_list.pop().unwrap();
-        let memory_index = module_to_memory_block_index_list.pop().unwrap();
+        let fused_items = fused_items_list.pop().unwrap();
+        let table_indexes = module_to_tables_list.pop().unwrap();
+        let memory_indexes = module_to_memory_blocks_list.pop().unwrap();
         let global_variable_indexes = module_to_global_variables_list.pop().unwrap();
+        let tag_indexes = module_to_tags_list.pop().unwrap();
 
         let ast_module_index = ast_module_count - reverse_index - 1;
         let named_ast_module = &named_ast_modules[ast_module_index];
@@ -64,28 +207,56 @@ pub fn create_instance(
             })
             .collect::<Vec<FunctionType>>();
 
+        // 展开各个内部函数的局部变量组
+        //
+        // 通过验证的模块都满足局部变量数量的限制，这里仍然在分配空间之前检查，
+        // 因为从编译模块缓存读取的模块不会再次验证。
+        let imported_function_count = ast_module
+            .import_items
+            .iter()
+            .filter(|item| {
+                matches!(
+                    item.import_descriptor,
+                    ImportDescriptor::FunctionTypeIndex(_)
+                )
+            })
+            .count();
+
         let internal_function_local_variable_types_list = ast_module
             .code_items
             .iter()
-            .map(|item| {
-                item.local_groups
-                    .iter()
-                    .flat_map(|local_group| {
-                        vec![local_group.value_type.clone(); local_group.variable_count as usize]
-                    })
-                    .collect::<Vec<ValueType>>()
+            .enumerate()
+            .map(|(internal_function_index, item)| {
+                let local_count = count_local_variables(&item.local_groups).map_err(|e| {
+                    let function_index = (imported_function_count + internal_function_index) as u32;
+                    EngineError::InvalidModule(
+                        name.clone(),
+                        ValidationError::FunctionError(function_index, 0, e),
+                    )
+                })?;
+
+                let mut local_variable_types = Vec::with_capacity(local_count);
+                for local_group in &item.local_groups {
+                    local_variable_types.extend(vec![
+                        local_group.value_type.clone();
+                        local_group.variable_count as usize
+                    ]);
+                }
+                Ok(local_variable_types)
             })
-            .collect::<Vec<Vec<ValueType>>>();
+            .collect::<Result<Vec<Vec<ValueType>>, EngineError>>()?;
 
         let vm_module = VMModule::new(
             name,
-            table_index,
-            memory_index,
+            table_indexes,
+            memory_indexes,
             global_variable_indexes,
+            tag_indexes,
             function_types,
             internal_function_local_variable_types_list,
             function_items,
             instructions,
+            fused_items,
         );
 
         vm_modules.push(vm_module);
@@ -94,6 +265,13 @@ pub fn create_instance(
     // 因为 vm_modules 的元素是反序添加的，所以这里需要翻转一次
     vm_modules.reverse();
 
+    for (vm_module, function_reference_base) in vm_modules
+        .iter_mut()
+        .zip(get_function_reference_bases(named_ast_modules))
+    {
+        vm_module.function_reference_base = function_reference_base;
+    }
+
     // 构建 VM 实例
 
     let stack = VMStack::new();
@@ -102,6 +280,7 @@ pub fn create_instance(
         memory_blocks,
         tables,
         global_variables,
+        tags,
         native_modules,
         vm_modules,
     );
@@ -112,84 +291,300 @@ pub fn create_instance(
         stack,
         status,
         resource,
+        interpreter_kind,
     };
 
-    // 填充 data 和 element 到 memory 和 table
+    // 降低为寄存器中间表示
+    //
+    // 降低函数调用指令时需要读取被调用函数（可能位于其它模块）的类型，
+    // 所以先降低所有模块，然后再逐个保存到模块实例。
+    if interpreter_kind != InterpreterKind::Stack {
+        let register_codes = (0..vm.resource.vm_modules.len())
+            .map(|vm_module_index| RegisterCode::lower(&vm.resource, vm_module_index))
+            .collect::<Vec<RegisterCode>>();
+
+        for (vm_module, register_code) in vm.resource.vm_modules.iter_mut().zip(register_codes) {
+            vm_module.register_code = Some(Rc::new(register_code));
+        }
+    }
+
+    // 编译为机器码
+    //
+    // 无法分配可执行内存时该模块改由寄存器解释器执行。
+    #[cfg(feature = "jit")]
+    if interpreter_kind == InterpreterKind::Jit {
+        let jit_codes = (0..vm.resource.vm_modules.len())
+            .map(|vm_module_index| {
+                let register_code = vm.resource.vm_modules[vm_module_index]
+                    .register_code
+                    .as_ref()
+                    .unwrap();
+                JitCode::compile(&vm.resource, vm_module_index, register_code).ok()
+            })
+            .collect::<Vec<Option<JitCode>>>();
+
+        for (vm_module, jit_code) in vm.resource.vm_modules.iter_mut().zip(jit_codes) {
+            vm_module.jit_code = jit_code.map(Rc::new);
+        }
+    }
+
+    // 填充 element 和 data 到 table 和 memory
     //
     // 因为 data 和 element 的常量表达式里可能存在引用数据，所以需要先构造了 vm 之后
     // 再对表达式进行求值。
     // https://webassembly.github.io/spec/core/valid/instructions.html#constant-expressions
-    for ast_module_index in 0..ast_module_count {
-        let named_ast_module = &named_ast_modules[ast_module_index];
+    //
+    // 按照规范的实例化步骤：
+    // https://webassembly.github.io/spec/core/exec/modules.html#instantiation
+    //
+    // 1. 先求值所有段的偏移值，并检查每一个段是否完全落在表或者内存块的范围之内，
+    //    只要有一个段越界，则整个实例化失败，且不会对表和内存块作任何修改；
+    // 2. 先填充 element，再填充 data。
+    //
+    // 注意 `start` 函数不在这里执行，而是由调用者（比如 launcher）在实例化成功之后执行。
+    let mut pending_element_items: Vec<(usize, usize, Vec<Value>)> = vec![];
+    let mut pending_data_items: Vec<(usize, usize, &[u8])> = vec![];
+
+    for (ast_module_index, named_ast_module) in named_ast_modules.iter().enumerate() {
         let ast_module = &named_ast_module.module;
 
-        let instance_memory_index = vm.resource.vm_modules[ast_module_index].memory_index;
-        let instance_table_index = vm.resource.vm_modules[ast_module_index].table_index;
+        // 元素段的内容（引用值列表）会一直保存在模块实例里，供 `table.init` 指令使用，
+        // 其中主动模式（active）以及声明模式（declarative）的元素段在实例化之后
+        // 相当于被 `elem.drop` 指令丢弃，即内容为空。
+        let mut element_segments: Vec<Vec<Value>> = vec![];
+
+        for (element_index, element_item) in ast_module.element_items.iter().enumerate() {
+            let values = eval_element_items(&mut vm, ast_module_index, &element_item.items)?;
+
+            let (table_index, offset_instruction_items) = match &element_item.mode {
+                ElementMode::Active {
+                    table_index,
+                    offset_instruction_items,
+                } => (*table_index, offset_instruction_items),
+                ElementMode::Passive => {
+                    element_segments.push(values);
+                    continue;
+                }
+                ElementMode::Declarative => {
+                    element_segments.push(vec![]);
+                    continue;
+                }
+            };
 
-        // 填充 data 到 memory
-        for data_item in &ast_module.data_items {
-            // 内存块索引，目前 WebAssembly 标准只支持 0
-            if data_item.memory_block_index != 0 {
-                return Err(EngineError::Unsupported(
-                    Unsupported::UnsupportedMultipleMemoryBlock,
+            element_segments.push(vec![]);
+
+            let instance_table_index =
+                vm.resource.vm_modules[ast_module_index].table_indexes[table_index as usize];
+
+            let offset = eval_segment_offset(
+                &mut vm,
+                ast_module_index,
+                offset_instruction_items,
+                &ValueType::I32,
+            )?;
+            let length = values.len();
+            let table_size = vm.resource.tables[instance_table_index].get_size() as usize;
+
+            if !is_segment_in_range(offset, length, table_size) {
+                return Err(EngineError::OutOfRange(
+                    OutOfRange::ElementSegmentOutOfRange {
+                        module_name: named_ast_module.name.clone(),
+                        element_index,
+                        offset,
+                        length,
+                        table_size,
+                    },
                 ));
             }
 
-            let offset_instruction_items = &data_item.offset_instruction_items;
-            let constant_expression = decode_constant_expression(offset_instruction_items)?;
-            let offset_value = vm.eval_constant_expression(&constant_expression)?;
-
-            let address = match offset_value {
-                Value::I32(v) => v as usize,
-                _ => {
-                    return Err(EngineError::TypeMismatch(
-                        TypeMismatch::ConstantExpressionValueTypeMismatch(
-                            ValueType::I32,
-                            offset_value.get_type(),
-                        ),
-                    ));
+            pending_element_items.push((instance_table_index, offset, values));
+        }
+
+        vm.resource.vm_modules[ast_module_index].element_segments = element_segments;
+
+        // 数据段的内容会一直保存在模块实例里，供 `memory.init` 指令使用，
+        // 其中主动模式的数据段在实例化之后相当于被 `data.drop` 指令丢弃，即内容为空。
+        let mut data_segments: Vec<Vec<u8>> = vec![];
+
+        for (data_index, data_item) in ast_module.data_items.iter().enumerate() {
+            let (memory_block_index, offset_instruction_items) = match &data_item.mode {
+                DataMode::Active {
+                    memory_block_index,
+                    offset_instruction_items,
+                } => (*memory_block_index, offset_instruction_items),
+                DataMode::Passive => {
+                    data_segments.push(data_item.data.clone());
+                    continue;
                 }
             };
 
-            let data = &data_item.data;
-            vm.resource.memory_blocks[instance_memory_index].write_bytes(address, data);
+            data_segments.push(vec![]);
+
+            let instance_memory_index = vm.resource.vm_modules[ast_module_index].memory_indexes
+                [memory_block_index as usize];
+
+            // 64 位内存块的数据段的偏移值是 i64
+            let memory_block = &vm.resource.memory_blocks[instance_memory_index];
+            let memory_size = memory_block.get_size_in_bytes();
+            let address_type = memory_block.get_memory_type().get_address_type();
+
+            let offset = eval_segment_offset(
+                &mut vm,
+                ast_module_index,
+                offset_instruction_items,
+                &address_type,
+            )?;
+            let length = data_item.data.len();
+
+            if !is_segment_in_range(offset, length, memory_size) {
+                return Err(EngineError::OutOfRange(OutOfRange::DataSegmentOutOfRange {
+                    module_name: named_ast_module.name.clone(),
+                    data_index,
+                    offset,
+                    length,
+                    memory_size,
+                }));
+            }
+
+            pending_data_items.push((instance_memory_index, offset, &data_item.data));
         }
 
-        // 填充 element 到 table
-        for element_item in &ast_module.element_items {
-            // 表索引，目前 WebAssembly 标准只支持 0
-            if element_item.table_index != 0 {
-                return Err(EngineError::Unsupported(
-                    Unsupported::UnsupportedMultipleTable,
-                ));
-            }
+        vm.resource.vm_modules[ast_module_index].data_segments = data_segments;
+    }
 
-            let offset_instruction_items = &element_item.offset_instruction_items;
-            let constant_expression = decode_constant_expression(offset_instruction_items)?;
-            let offset_value = vm.eval_constant_expression(&constant_expression)?;
-
-            let offset = match offset_value {
-                Value::I32(v) => v as usize,
-                _ => {
-                    return Err(EngineError::TypeMismatch(
-                        TypeMismatch::ConstantExpressionValueTypeMismatch(
-                            ValueType::I32,
-                            offset_value.get_type(),
-                        ),
-                    ));
-                }
-            };
+    for (instance_table_index, offset, values) in pending_element_items {
+        vm.resource.tables[instance_table_index].write_elements(offset, &values)?;
+    }
 
-            for (index, function_index) in element_item.function_indices.iter().enumerate() {
-                vm.resource.tables[instance_table_index]
-                    .set_element(offset + index, *function_index)?;
-            }
+    for (instance_memory_index, offset, data) in pending_data_items {
+        // 共享内存块已经由父线程的 VM 实例初始化
+        if shared_memory_block_indices.contains(&instance_memory_index) {
+            continue;
         }
+        vm.resource.memory_blocks[instance_memory_index].write_bytes(offset, data);
     }
 
     Ok(vm)
 }
 
+/// 验证所有模块，然后链接所有模块的函数并解码指令，返回函数项列表以及解码的结果
+///
+/// 解释器、寄存器中间表示以及 JIT 编译器都假定模块已经通过验证（比如操作数的类型、
+/// 局部变量的索引），所以未通过验证的模块在这里就被拒绝。
+///
+/// 如果指定了编译模块缓存，则优先读取缓存，命中缓存时不再验证模块
+/// （缓存里只有通过验证的模块）。
+fn link_and_decode(
+    native_modules: &[NativeModule],
+    named_ast_modules: &[NamedAstModule],
+    option_module_cache: Option<&ModuleCache>,
+) -> Result<(Vec<Vec<FunctionItem>>, DecodedInstructions), EngineError> {
+    let option_cache_and_key = option_module_cache.and_then(|module_cache| {
+        ModuleCache::compute_key(native_modules, named_ast_modules).map(|key| (module_cache, key))
+    });
+
+    if let Some((module_cache, key)) = option_cache_and_key {
+        if let Some((_, (function_items_list, instructions_list))) = module_cache.load(&key) {
+            let fused_items_list = fuse_instructions_list(&instructions_list);
+            return Ok((function_items_list, (instructions_list, fused_items_list)));
+        }
+    }
+
+    for named_ast_module in named_ast_modules {
+        validate(&named_ast_module.module)
+            .map_err(|e| EngineError::InvalidModule(named_ast_module.name.clone(), e))?;
+    }
+
+    let function_items_list = link_functions(native_modules, named_ast_modules)?;
+    let decoded_instructions = decode(named_ast_modules, &function_items_list)?;
+
+    if let Some((module_cache, key)) = option_cache_and_key {
+        // 缓存只用于加快实例化，写入失败（比如缓存目录不可写）时忽略
+        let _ = module_cache.store(
+            &key,
+            named_ast_modules,
+            &function_items_list,
+            &decoded_instructions.0,
+        );
+    }
+
+    Ok((function_items_list, decoded_instructions))
+}
+
+/// 识别（从缓存读取的）各个模块的指令列表里的超级指令项
+fn fuse_instructions_list(instructions_list: &[Vec<Instruction>]) -> Vec<Vec<FusedItem>> {
+    instructions_list
+        .iter()
+        .map(|instructions| fuse_instructions(instructions))
+        .collect()
+}
+
+/// 求值 element 或者 data 段的偏移值
+///
+/// 偏移值是 i32 类型（64 位内存块的数据段的偏移值是 i64 类型），但应该作为
+/// 无符号整数看待，比如 `i32.const -1` 表示 0xffff_ffff 而不是负数。
+///
+/// 偏移值表达式可以读取模块导入的全局变量，比如动态链接模块的
+/// `(i32.add (global.get $__memory_base) (i32.const 16))`。
+fn eval_segment_offset(
+    vm: &mut VM,
+    vm_module_index: usize,
+    offset_instruction_items: &[instruction::Instruction],
+    offset_type: &ValueType,
+) -> Result<usize, EngineError> {
+    let constant_expression = decode_constant_expression(offset_instruction_items)?;
+    let offset_value = vm.eval_constant_expression(vm_module_index, &constant_expression)?;
+
+    match (offset_type, &offset_value) {
+        (ValueType::I32, Value::I32(v)) => Ok(*v as u32 as usize),
+        (ValueType::I64, Value::I64(v)) => Ok(*v as u64 as usize),
+        _ => Err(EngineError::TypeMismatch(
+            TypeMismatch::ConstantExpressionValueTypeMismatch(
+                offset_type.clone(),
+                offset_value.get_type(),
+            ),
+        )),
+    }
+}
+
+/// 求值元素段的引用值列表
+///
+/// 函数索引列表形式的元素段会被转换为函数引用值，表达式列表形式的元素段
+/// 的每一个表达式都是常量表达式（`ref.null` 或者 `ref.func`）。
+fn eval_element_items(
+    vm: &mut VM,
+    vm_module_index: usize,
+    items: &ElementItems,
+) -> Result<Vec<Value>, EngineError> {
+    match items {
+        ElementItems::FunctionIndices(function_indices) => {
+            let function_reference_base =
+                vm.resource.vm_modules[vm_module_index].function_reference_base;
+            Ok(function_indices
+                .iter()
+                .map(|function_index| {
+                    Value::FuncRef(Some(function_reference_base + function_index))
+                })
+                .collect::<Vec<Value>>())
+        }
+        ElementItems::Expressions(expressions) => expressions
+            .iter()
+            .map(|instructions| {
+                let constant_expression = decode_constant_expression(instructions)?;
+                vm.eval_constant_expression(vm_module_index, &constant_expression)
+            })
+            .collect::<Result<Vec<Value>, EngineError>>(),
+    }
+}
+
+/// 检查区间 [offset, offset + length) 是否落在 [0, size) 之内
+fn is_segment_in_range(offset: usize, length: usize, size: usize) -> bool {
+    match offset.checked_add(length) {
+        Some(end) => end <= size,
+        None => false,
+    }
+}
+
 /// 从 named_ast_modules 的最后一个元素开始，寻找 ast module 当中
 /// `start` 段指定的函数或者导出名称为 `_start` 的函数的索引。
 pub fn get_entry_module_and_function_index(
@@ -237,17 +632,28 @@ mod tests {
         types::{Value, ValueType},
     };
     use anvm_binary_parser::parser;
+    use anvm_validator::error::{FunctionError, ValidationError};
 
     use pretty_assertions::assert_eq;
 
     use crate::{
-        error::{EngineError, NativeTerminate},
+        bytecode::Opcode,
+        decoder::decode,
+        error::{
+            EngineError, InvalidOperation, NativeError, NativeTerminate, ObjectNotFound,
+            OutOfRange, Overflow,
+        },
+        linker::link_functions,
         native_module::{EmptyModuleContext, NativeModule},
-        object::NamedAstModule,
-        vm::VM,
+        object::{FunctionItem, FusedInstruction, NamedAstModule},
+        vm::{InterpreterKind, VM},
+        vm_memory::VMMemory,
     };
 
-    use super::{create_instance, find_ast_module_export_function};
+    use super::{
+        create_instance, create_instance_with_compiled_modules, create_instance_with_interpreter,
+        create_thread_instance, find_ast_module_export_function,
+    };
 
     // 辅助方法
     fn get_test_binary_resource(filename: &str) -> Vec<u8> {
@@ -387,6 +793,14 @@ mod tests {
     fn eval_with_multiple_modules(
         function_index: usize,
         args: &[Value],
+    ) -> Result<Vec<Value>, EngineError> {
+        eval_with_multiple_modules_and_interpreter(function_index, args, InterpreterKind::Stack)
+    }
+
+    fn eval_with_multiple_modules_and_interpreter(
+        function_index: usize,
+        args: &[Value],
+        interpreter_kind: InterpreterKind,
     ) -> Result<Vec<Value>, EngineError> {
         let native_module = get_test_native_module();
 
@@ -405,18 +819,33 @@ mod tests {
             get_test_ast_module("test-function-call-caller.wasm"),
         );
 
-        let mut vm = create_instance(
+        let mut vm = create_instance_with_interpreter(
             vec![native_module],
             &vec![
                 named_ast_module_callee,
                 named_ast_module_callee_intermediate,
                 named_ast_module_caller,
             ],
+            interpreter_kind,
         )?;
 
         vm.eval_function_by_index(2, function_index, args)
     }
 
+    fn eval_with_interpreter(
+        filename: &str,
+        function_index: usize,
+        args: &[Value],
+        interpreter_kind: InterpreterKind,
+    ) -> Result<Vec<Value>, EngineError> {
+        let ast_module = get_test_ast_module(filename);
+        let named_ast_module = NamedAstModule::new("test", ast_module);
+        let mut vm =
+            create_instance_with_interpreter(vec![], &[named_ast_module], interpreter_kind)?;
+
+        vm.eval_function_by_index(0, function_index, args)
+    }
+
     fn convert_i32_list(values: &[i32]) -> Vec<Value> {
         values
             .iter()
@@ -666,6 +1095,17 @@ mod tests {
             eval(module_name, 13, &vec![]).unwrap(),
             vec![Value::F32(5.0)]
         );
+
+        for (value, expected) in [(2.55, 3.0), (-2.5, -2.0), (-3.5, -4.0), (4.5, 4.0)] {
+            assert_eq!(
+                eval(module_name, 14, &[Value::F64(value)]).unwrap(),
+                vec![Value::F64(expected)]
+            );
+        }
+        let results = eval(module_name, 14, &[Value::F64(-0.5)]).unwrap();
+        assert!(
+            matches!(results[..], [Value::F64(value)] if value == 0.0 && value.is_sign_negative())
+        );
     }
 
     #[test]
@@ -754,6 +1194,120 @@ mod tests {
                 Value::I32(0b00_11111111_11111111_11111111_111110)
             ]
         );
+
+        // 溢出时回绕
+        assert_eq!(
+            eval(module_name, 15, &vec![]).unwrap(),
+            vec![
+                Value::I32(i32::MIN),
+                Value::I32(i32::MAX),
+                Value::I32(0x10000)
+            ]
+        );
+        assert_eq!(
+            eval(module_name, 16, &vec![]).unwrap(),
+            vec![
+                Value::I64(i64::MIN),
+                Value::I64(i64::MAX),
+                Value::I64(0x1_0000_0000)
+            ]
+        );
+
+        // 移位的位数按数据宽度取模
+        assert_eq!(
+            eval(module_name, 17, &vec![]).unwrap(),
+            vec![Value::I32(2), Value::I32(0b1111), Value::I64(-4)]
+        );
+
+        // 符号零以及 NaN
+        let eval_f32 = |function_index: usize, left: f32, right: f32| {
+            eval(
+                module_name,
+                function_index,
+                &[Value::F32(left), Value::F32(right)],
+            )
+            .unwrap()
+        };
+        let to_bits = |values: Vec<Value>| match values[..] {
+            [Value::F32(value)] => value.to_bits() as u64,
+            [Value::F64(value)] => value.to_bits(),
+            _ => panic!("unexpected results: {:?}", values),
+        };
+
+        assert_eq!(to_bits(eval_f32(18, 0.0, -0.0)), (-0.0f32).to_bits() as u64);
+        assert_eq!(to_bits(eval_f32(18, -0.0, 0.0)), (-0.0f32).to_bits() as u64);
+        assert_eq!(eval_f32(18, 1.0, 2.0), vec![Value::F32(1.0)]);
+        assert!(matches!(eval_f32(18, 1.0, f32::NAN)[..], [Value::F32(value)] if value.is_nan()));
+
+        let max_results = eval(module_name, 19, &[Value::F64(-0.0), Value::F64(0.0)]).unwrap();
+        assert_eq!(to_bits(max_results), 0.0f64.to_bits());
+        let max_results = eval(module_name, 19, &[Value::F64(f64::NAN), Value::F64(1.0)]).unwrap();
+        assert!(matches!(max_results[..], [Value::F64(value)] if value.is_nan()));
+
+        // 结果的绝对值来自 LHS，符号来自 RHS
+        assert_eq!(eval_f32(20, 1.0, -0.0), vec![Value::F32(-1.0)]);
+        assert_eq!(
+            eval_f32(20, f32::NEG_INFINITY, 0.0),
+            vec![Value::F32(f32::INFINITY)]
+        );
+    }
+
+    #[test]
+    fn test_invalid_module() {
+        // 未能通过验证的模块在实例化时被拒绝，而不会在执行时崩溃或者得出错误的结果
+        for module_name in ["test-invalid-operand.wasm", "test-invalid-local.wasm"] {
+            for interpreter_kind in [
+                InterpreterKind::Stack,
+                InterpreterKind::Register,
+                #[cfg(feature = "jit")]
+                InterpreterKind::Jit,
+            ] {
+                let named_ast_module =
+                    NamedAstModule::new("test", get_test_ast_module(module_name));
+                assert!(matches!(
+                    create_instance_with_interpreter(vec![], &[named_ast_module], interpreter_kind),
+                    Err(EngineError::InvalidModule(..))
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn test_too_many_locals() {
+        // 声明了大量局部变量的模块只有几个字节，实例化时应该在分配空间之前被拒绝
+        let mut module = get_test_ast_module("test-local-variable.wasm");
+        module.code_items[0].local_groups = vec![
+            ast::LocalGroup {
+                variable_count: u32::MAX,
+                value_type: ValueType::I64,
+            };
+            2
+        ];
+        let named_ast_modules = [NamedAstModule::new("test", module)];
+
+        assert!(matches!(
+            create_instance(vec![], &named_ast_modules),
+            Err(EngineError::InvalidModule(
+                _,
+                ValidationError::FunctionError(0, 0, FunctionError::TooManyLocals(_))
+            ))
+        ));
+
+        // 不经过验证（比如从编译模块缓存读取）的模块也会被拒绝
+        let function_items_list = link_functions(&[], &named_ast_modules).unwrap();
+        let (instructions_list, _) = decode(&named_ast_modules, &function_items_list).unwrap();
+        assert!(matches!(
+            create_instance_with_compiled_modules(
+                vec![],
+                &named_ast_modules,
+                (function_items_list, instructions_list),
+                InterpreterKind::Stack,
+            ),
+            Err(EngineError::InvalidModule(
+                _,
+                ValidationError::FunctionError(0, 0, FunctionError::TooManyLocals(_))
+            ))
+        ));
     }
 
     #[test]
@@ -784,6 +1338,58 @@ mod tests {
             vec![Value::F32(66.0)]
         );
 
+        // 截断之后超出范围，或者源数值为 NaN 时陷入
+        assert_eq!(
+            eval(module_name, 9, &[Value::F32(-2147483648.0)]).unwrap(),
+            vec![Value::I32(i32::MIN)]
+        );
+        assert!(matches!(
+            eval(module_name, 9, &[Value::F32(2147483648.0)]),
+            Err(EngineError::InvalidOperation(
+                InvalidOperation::IntegerOverflow
+            ))
+        ));
+        assert!(matches!(
+            eval(module_name, 9, &[Value::F32(f32::NAN)]),
+            Err(EngineError::InvalidOperation(
+                InvalidOperation::InvalidConversionToInteger
+            ))
+        ));
+        assert_eq!(
+            eval(module_name, 10, &[Value::F64(-0.9)]).unwrap(),
+            vec![Value::I64(0)]
+        );
+        assert!(matches!(
+            eval(module_name, 10, &[Value::F64(-1.0)]),
+            Err(EngineError::InvalidOperation(
+                InvalidOperation::IntegerOverflow
+            ))
+        ));
+        assert!(matches!(
+            eval(module_name, 10, &[Value::F64(18446744073709551616.0)]),
+            Err(EngineError::InvalidOperation(
+                InvalidOperation::IntegerOverflow
+            ))
+        ));
+
+        // 饱和截断
+        assert_eq!(
+            eval(module_name, 11, &[Value::F32(f32::INFINITY)]).unwrap(),
+            vec![Value::I32(i32::MAX)]
+        );
+        assert_eq!(
+            eval(module_name, 11, &[Value::F32(f32::NAN)]).unwrap(),
+            vec![Value::I32(0)]
+        );
+        assert_eq!(
+            eval(module_name, 12, &[Value::F64(-1.0)]).unwrap(),
+            vec![Value::I64(0)]
+        );
+        assert_eq!(
+            eval(module_name, 12, &[Value::F64(1e20)]).unwrap(),
+            vec![Value::I64(-1)]
+        );
+
         // todo:: 这里仅测试了部分指令
     }
 
@@ -1198,36 +1804,1175 @@ mod tests {
     }
 
     #[test]
-    fn test_lib_c() {
-        let module_name = "test-lib-c.wasm";
+    fn test_block_loop() {
+        let module_name = "test-block-loop.wasm";
 
+        // 测试 loop 和 br_if
         assert_eq!(
-            eval_by_export_function_name(module_name, "add", &vec![Value::I32(22), Value::I32(33)])
-                .unwrap(),
+            eval(module_name, 0, &vec![Value::I32(100)]).unwrap(),
+            vec![Value::I32(5050)]
+        );
+
+        // 测试嵌套结构块的跨层跳转
+        assert_eq!(
+            eval(module_name, 1, &vec![Value::I32(100)]).unwrap(),
+            vec![Value::I32(25)]
+        );
+        assert_eq!(
+            eval(module_name, 1, &vec![Value::I32(1000)]).unwrap(),
+            vec![Value::I32(168)]
+        );
+
+        // 测试带参数和返回值的 loop 结构块
+        assert_eq!(
+            eval(module_name, 2, &vec![Value::I32(1)]).unwrap(),
+            vec![Value::I32(1)]
+        );
+        assert_eq!(
+            eval(module_name, 2, &vec![Value::I32(10)]).unwrap(),
+            vec![Value::I32(3628800)]
+        );
+
+        // 测试跳转时丢弃多余的操作数
+        assert_eq!(
+            eval(module_name, 3, &vec![Value::I32(10)]).unwrap(),
             vec![Value::I32(55)]
         );
+    }
+
+    #[test]
+    fn test_function_call_exhaustion() {
+        let module_name = "test-function-call-exhaustion.wasm";
 
+        match eval(module_name, 0, &vec![Value::I32(1)]) {
+            Err(EngineError::Overflow(Overflow::StackOverflow(_))) => {}
+            _ => panic!("expected a stack overflow error"),
+        }
+    }
+
+    #[test]
+    fn test_function_tail_call() {
+        let module_name = "test-function-tail-call.wasm";
+
+        // 相互递归的尾调用不会导致栈溢出
         assert_eq!(
-            eval_by_export_function_name(module_name, "mul", &vec![Value::I32(22), Value::I32(33)])
-                .unwrap(),
-            vec![Value::I32(726)]
+            eval(module_name, 0, &vec![Value::I32(200_000)]).unwrap(),
+            vec![Value::I32(1)]
+        );
+        assert_eq!(
+            eval(module_name, 1, &vec![Value::I32(200_001)]).unwrap(),
+            vec![Value::I32(1)]
+        );
+        assert_eq!(
+            eval(module_name, 0, &vec![Value::I32(7)]).unwrap(),
+            vec![Value::I32(0)]
+        );
+
+        // 在结构块内尾调用
+        assert_eq!(
+            eval(module_name, 2, &vec![Value::I64(10), Value::I64(1)]).unwrap(),
+            vec![Value::I64(3628800)]
+        );
+
+        // 间接尾调用
+        assert_eq!(
+            eval(module_name, 3, &vec![Value::I32(0), Value::I32(10)]).unwrap(),
+            vec![Value::I32(1)]
+        );
+        assert_eq!(
+            eval(module_name, 3, &vec![Value::I32(1), Value::I32(10)]).unwrap(),
+            vec![Value::I32(0)]
+        );
+
+        // 尾调用的目标函数返回到原函数的调用者
+        assert_eq!(
+            eval(module_name, 4, &vec![Value::I32(1000)]).unwrap(),
+            vec![Value::I32(101)]
         );
     }
 
     #[test]
-    fn test_lib_rust() {
-        let module_name = "test-lib-rust.wasm";
+    fn test_segment_init() {
+        let module_name = "test-segment-init.wasm";
 
         assert_eq!(
-            eval_by_export_function_name(module_name, "add", &vec![Value::I32(22), Value::I32(33)])
-                .unwrap(),
-            vec![Value::I32(55)]
+            eval(module_name, 2, &vec![Value::I32(1)]).unwrap(),
+            vec![Value::I32(10)]
+        );
+        assert_eq!(
+            eval(module_name, 2, &vec![Value::I32(2)]).unwrap(),
+            vec![Value::I32(20)]
+        );
+        assert_eq!(
+            eval(module_name, 3, &vec![]).unwrap(),
+            vec![Value::I32(0x44332211), Value::I32(0x88776655u32 as i32)]
+        );
+    }
+
+    #[test]
+    fn test_segment_data_out_of_range() {
+        let ast_module = get_test_ast_module("test-segment-data-out-of-range.wasm");
+        let named_ast_module = NamedAstModule::new("test", ast_module);
+
+        match create_instance(vec![], &vec![named_ast_module]) {
+            Err(EngineError::OutOfRange(e)) => assert_eq!(
+                e,
+                OutOfRange::DataSegmentOutOfRange {
+                    module_name: "test".to_string(),
+                    data_index: 1,
+                    offset: 65534,
+                    length: 3,
+                    memory_size: 65536
+                }
+            ),
+            _ => panic!("expected a data segment out of range error"),
+        }
+    }
+
+    #[test]
+    fn test_segment_element_out_of_range() {
+        let ast_module = get_test_ast_module("test-segment-element-out-of-range.wasm");
+        let named_ast_module = NamedAstModule::new("test", ast_module);
+
+        // 偏移值 `i32.const -1` 应该被视为无符号整数 0xffff_ffff
+        match create_instance(vec![], &vec![named_ast_module]) {
+            Err(EngineError::OutOfRange(e)) => assert_eq!(
+                e,
+                OutOfRange::ElementSegmentOutOfRange {
+                    module_name: "test".to_string(),
+                    element_index: 0,
+                    offset: 0xffff_ffff,
+                    length: 1,
+                    table_size: 2
+                }
+            ),
+            _ => panic!("expected an element segment out of range error"),
+        }
+    }
+
+    #[test]
+    fn test_table_and_reference() {
+        let module_name = "test-table.wasm";
+
+        // 测试 table.init
+        assert_eq!(
+            eval(module_name, 2, &vec![Value::I32(1)]).unwrap(),
+            vec![Value::I32(10)]
+        );
+        assert_eq!(
+            eval(module_name, 2, &vec![Value::I32(2)]).unwrap(),
+            vec![Value::I32(20)]
         );
+        assert!(eval(module_name, 2, &vec![Value::I32(0)]).is_err());
+
+        // 测试 elem.drop
+        match eval(module_name, 3, &vec![]) {
+            Err(EngineError::OutOfRange(e)) => {
+                assert_eq!(e, OutOfRange::ElementSegmentAccessOutOfRange(0, 1, 0))
+            }
+            _ => panic!("expected an element segment access out of range error"),
+        }
 
+        // 测试 table.grow 和 table.size
         assert_eq!(
-            eval_by_export_function_name(module_name, "mul", &vec![Value::I32(22), Value::I32(33)])
-                .unwrap(),
-            vec![Value::I32(726)]
+            eval(module_name, 4, &vec![Value::I32(2)]).unwrap(),
+            vec![Value::I32(4), Value::I32(6)]
+        );
+        assert_eq!(
+            eval(module_name, 4, &vec![Value::I32(7)]).unwrap(),
+            vec![Value::I32(-1), Value::I32(4)]
+        );
+
+        // 测试 table.set、table.get 和 ref.is_null
+        assert_eq!(
+            eval(module_name, 5, &vec![Value::I32(3)]).unwrap(),
+            vec![Value::I32(0), Value::I32(1)]
+        );
+        assert!(eval(module_name, 5, &vec![Value::I32(4)]).is_err());
+
+        // 测试 table.fill 和 table.copy
+        assert_eq!(
+            eval(module_name, 6, &vec![]).unwrap(),
+            vec![Value::I32(20)]
         );
+
+        // 测试宿主提供的外部引用
+        assert_eq!(
+            eval(module_name, 7, &vec![Value::ExternRef(Some(123))]).unwrap(),
+            vec![Value::ExternRef(Some(123)), Value::I32(0)]
+        );
+        assert_eq!(
+            eval(module_name, 7, &vec![Value::ExternRef(None)]).unwrap(),
+            vec![Value::ExternRef(None), Value::I32(1)]
+        );
+
+        // 测试带类型的 select
+        assert_eq!(
+            eval(module_name, 8, &vec![Value::I32(1)]).unwrap(),
+            vec![Value::FuncRef(Some(0))]
+        );
+        assert_eq!(
+            eval(module_name, 8, &vec![Value::I32(0)]).unwrap(),
+            vec![Value::FuncRef(None)]
+        );
+    }
+
+    #[test]
+    fn test_lib_c() {
+        let module_name = "test-lib-c.wasm";
+
+        assert_eq!(
+            eval_by_export_function_name(module_name, "add", &vec![Value::I32(22), Value::I32(33)])
+                .unwrap(),
+            vec![Value::I32(55)]
+        );
+
+        assert_eq!(
+            eval_by_export_function_name(module_name, "mul", &vec![Value::I32(22), Value::I32(33)])
+                .unwrap(),
+            vec![Value::I32(726)]
+        );
+    }
+
+    #[test]
+    fn test_lib_rust() {
+        let module_name = "test-lib-rust.wasm";
+
+        assert_eq!(
+            eval_by_export_function_name(module_name, "add", &vec![Value::I32(22), Value::I32(33)])
+                .unwrap(),
+            vec![Value::I32(55)]
+        );
+
+        assert_eq!(
+            eval_by_export_function_name(module_name, "mul", &vec![Value::I32(22), Value::I32(33)])
+                .unwrap(),
+            vec![Value::I32(726)]
+        );
+    }
+
+    #[test]
+    fn test_simd() {
+        let module_name = "test-simd.wasm";
+
+        fn from_i32x4(lanes: [i32; 4]) -> Value {
+            let bytes = lanes
+                .iter()
+                .flat_map(|lane| lane.to_le_bytes())
+                .collect::<Vec<u8>>();
+            Value::V128(u128::from_le_bytes(bytes.try_into().unwrap()))
+        }
+
+        fn from_f32x4(lanes: [f32; 4]) -> Value {
+            from_i32x4(lanes.map(|lane| lane.to_bits() as i32))
+        }
+
+        // 逐通道运算
+        assert_eq!(
+            eval(
+                module_name,
+                0,
+                &vec![from_i32x4([1, 2, 3, -1]), from_i32x4([10, 20, 30, 1])]
+            )
+            .unwrap(),
+            vec![from_i32x4([11, 22, 33, 0])]
+        );
+        assert_eq!(
+            eval(
+                module_name,
+                1,
+                &vec![
+                    Value::V128(0x7070_7070_7070_7070_7070_7070_7070_8070),
+                    Value::V128(0x2020_2020_2020_2020_2020_2020_2020_f020)
+                ]
+            )
+            .unwrap(),
+            vec![Value::V128(0x7f7f_7f7f_7f7f_7f7f_7f7f_7f7f_7f7f_807f)]
+        );
+
+        // 浮点数的最小值，-0.0 小于 +0.0，NaN 会传播
+        match eval(
+            module_name,
+            2,
+            &vec![
+                from_f32x4([1.0, -0.0, f32::NAN, 5.0]),
+                from_f32x4([2.0, 0.0, 1.0, -5.0]),
+            ],
+        )
+        .unwrap()[0]
+        {
+            Value::V128(value) => {
+                let bytes = value.to_le_bytes();
+                let lanes = bytes
+                    .chunks(4)
+                    .map(|lane| f32::from_le_bytes(lane.try_into().unwrap()))
+                    .collect::<Vec<f32>>();
+                assert_eq!(lanes[0], 1.0);
+                assert_eq!(lanes[1].to_bits(), (-0.0f32).to_bits());
+                assert!(lanes[2].is_nan());
+                assert_eq!(lanes[3], -5.0);
+            }
+            _ => panic!("expected a v128 value"),
+        }
+
+        // 洗牌
+        assert_eq!(
+            eval(
+                module_name,
+                3,
+                &vec![
+                    Value::V128(0x0f0e_0d0c_0b0a_0908_0706_0504_0302_0100),
+                    Value::V128(0x1f1e_1d1c_1b1a_1918_1716_1514_1312_1110)
+                ]
+            )
+            .unwrap(),
+            vec![Value::V128(0x1707_1606_1505_1404_1303_1202_1101_1000)]
+        );
+
+        // 读写通道
+        assert_eq!(
+            eval(module_name, 4, &vec![Value::I32(0x1234_8000)]).unwrap(),
+            vec![Value::I32(-32768), Value::I32(0x1234_8000)]
+        );
+
+        // 加载、比较以及测试
+        assert_eq!(
+            eval(module_name, 5, &vec![Value::I32(0)]).unwrap(),
+            vec![Value::I32(0xff00), Value::I32(0)]
+        );
+        assert_eq!(
+            eval(module_name, 5, &vec![Value::I32(1)]).unwrap(),
+            vec![Value::I32(0x7f80), Value::I32(1)]
+        );
+
+        // 移位的位数会对通道的宽度取模
+        assert_eq!(
+            eval(
+                module_name,
+                6,
+                &vec![
+                    Value::V128(0x0000_0000_0000_0000_0003_4000_ffff_0001),
+                    Value::I32(17)
+                ]
+            )
+            .unwrap(),
+            vec![from_i32x4([2, -2, -32768, 6])]
+        );
+
+        // 把通道写入内存
+        assert_eq!(
+            eval(module_name, 7, &vec![from_i32x4([1, 2, 0x7654_3210, 4])]).unwrap(),
+            vec![Value::I32(0x7654_3210)]
+        );
+
+        // 越界访问
+        match eval(module_name, 8, &vec![]) {
+            Err(EngineError::OutOfRange(e)) => {
+                assert_eq!(e, OutOfRange::MemoryAccessOutOfRange(65530, 16, 65536))
+            }
+            _ => panic!("expected a memory access out of range error"),
+        }
+    }
+
+    #[test]
+    fn test_atomic() {
+        let module_name = "test-atomic.wasm";
+
+        // 原子加载
+        assert_eq!(
+            eval(module_name, 0, &vec![]).unwrap(),
+            vec![
+                Value::I32(1),
+                Value::I64(-1),
+                Value::I32(0xff),
+                Value::I64(0xffff_ffff)
+            ]
+        );
+
+        // 原子存储
+        assert_eq!(
+            eval(module_name, 1, &vec![Value::I64(0x1122_3344_5566_7788)]).unwrap(),
+            vec![Value::I64(0x1122_3344_5566_7788), Value::I32(0x5678)]
+        );
+
+        // 读取-修改-写入
+        assert_eq!(
+            eval(module_name, 2, &vec![Value::I32(5)]).unwrap(),
+            vec![Value::I32(0), Value::I32(5)]
+        );
+        assert_eq!(
+            eval(module_name, 3, &vec![]).unwrap(),
+            vec![Value::I32(1), Value::I32(0xff)]
+        );
+        assert_eq!(
+            eval(module_name, 4, &vec![Value::I64(9)]).unwrap(),
+            vec![Value::I64(7), Value::I64(9)]
+        );
+
+        // 比较并交换
+        assert_eq!(
+            eval(module_name, 5, &vec![Value::I32(10), Value::I32(20)]).unwrap(),
+            vec![Value::I32(10), Value::I32(20)]
+        );
+        assert_eq!(
+            eval(module_name, 5, &vec![Value::I32(11), Value::I32(20)]).unwrap(),
+            vec![Value::I32(10), Value::I32(10)]
+        );
+
+        // 唤醒以及等待
+        assert_eq!(eval(module_name, 6, &vec![]).unwrap(), vec![Value::I32(0)]);
+        assert_eq!(
+            eval(module_name, 7, &vec![Value::I32(0)]).unwrap(),
+            vec![Value::I32(1)]
+        );
+        assert_eq!(
+            eval(module_name, 7, &vec![Value::I32(1)]).unwrap(),
+            vec![Value::I32(2)]
+        );
+
+        // 地址未对齐
+        match eval(module_name, 8, &vec![]) {
+            Err(EngineError::InvalidOperation(e)) => {
+                assert_eq!(e, InvalidOperation::UnalignedAtomicAccess(2, 4))
+            }
+            _ => panic!("expected an unaligned atomic access error"),
+        }
+
+        // 内存屏障
+        assert_eq!(eval(module_name, 9, &vec![]).unwrap(), vec![]);
+
+        // 普通内存块
+        let unshared_module_name = "test-atomic-unshared.wasm";
+
+        assert_eq!(
+            eval(unshared_module_name, 0, &vec![]).unwrap(),
+            vec![Value::I32(0x0f), Value::I32(0xff)]
+        );
+
+        match eval(unshared_module_name, 1, &vec![]) {
+            Err(EngineError::InvalidOperation(e)) => {
+                assert_eq!(e, InvalidOperation::ExpectedSharedMemory)
+            }
+            _ => panic!("expected an expected shared memory error"),
+        }
+    }
+
+    fn native_function_fail(
+        _vm: &mut VM,
+        _native_module_index: usize,
+        params: &[Value],
+    ) -> Result<Vec<Value>, NativeTerminate> {
+        match params[0] {
+            Value::I32(0) => Ok(vec![]),
+            Value::I32(value) => Err(NativeTerminate {
+                module_name: "env".to_string(),
+                native_error: NativeError::Exception(0, vec![Value::I32(value * 2)]),
+            }),
+            _ => panic!("incorrect data type of the native function arguments"),
+        }
+    }
+
+    #[test]
+    fn test_exception() {
+        let eval_exception = |function_index: usize, args: &[Value]| {
+            let empty_module_context = EmptyModuleContext::new();
+            let mut native_module = NativeModule::new("env", Box::new(empty_module_context));
+            native_module.add_tag("error", vec![ValueType::I32]);
+            native_module.add_native_function(
+                "fail",
+                vec![ValueType::I32],
+                vec!["value"],
+                vec![],
+                native_function_fail,
+            );
+
+            let ast_module = get_test_ast_module("test-exception.wasm");
+            let named_ast_module = NamedAstModule::new("test", ast_module);
+            let mut vm = create_instance(vec![native_module], &vec![named_ast_module]).unwrap();
+            let result = vm.eval_function_by_index(0, function_index, args);
+            (vm, result)
+        };
+
+        // 捕获异常携带的数值
+        assert_eq!(
+            eval_exception(1, &vec![Value::I32(11)]).1.unwrap(),
+            vec![Value::I32(11), Value::I64(100)]
+        );
+
+        // 捕获被调用函数抛出的异常
+        assert_eq!(
+            eval_exception(3, &vec![Value::I32(0)]).1.unwrap(),
+            vec![Value::I32(10)]
+        );
+        assert_eq!(
+            eval_exception(3, &vec![Value::I32(1)]).1.unwrap(),
+            vec![Value::I32(20)]
+        );
+
+        // 通过异常引用重新抛出异常
+        assert_eq!(
+            eval_exception(4, &vec![Value::I32(33)]).1.unwrap(),
+            vec![Value::I32(33)]
+        );
+
+        // 跳转到 loop 结构块的开头
+        assert_eq!(eval_exception(5, &vec![]).1.unwrap(), vec![Value::I32(3)]);
+
+        // 未被捕获的异常
+        let (vm, result) = eval_exception(6, &vec![Value::I32(44)]);
+        match result {
+            Err(EngineError::UncaughtException(exception)) => {
+                assert_eq!(exception.tag_index, vm.get_native_tag_index(0, 0));
+                assert_eq!(exception.values, vec![Value::I32(44)]);
+            }
+            _ => panic!("expected an uncaught exception error"),
+        }
+
+        // 捕获本地函数抛出的异常
+        assert_eq!(
+            eval_exception(7, &vec![Value::I32(0)]).1.unwrap(),
+            vec![Value::I32(0)]
+        );
+        assert_eq!(
+            eval_exception(7, &vec![Value::I32(5)]).1.unwrap(),
+            vec![Value::I32(10)]
+        );
+
+        // 本地函数抛出的未被捕获的异常
+        let (vm, result) = eval_exception(2, &vec![Value::I32(1)]);
+        match result {
+            Err(EngineError::UncaughtException(exception)) => {
+                assert_ne!(exception.tag_index, vm.get_native_tag_index(0, 0));
+                assert_eq!(exception.values, vec![]);
+            }
+            _ => panic!("expected an uncaught exception error"),
+        }
+
+        // 被 catch 子句捕获的异常对象会被释放，异常列表的长度不会一直增长
+        let (vm, result) = eval_exception(8, &vec![Value::I32(1000)]);
+        assert_eq!(result.unwrap(), vec![Value::I32(1000)]);
+        assert_eq!(vm.resource.exceptions.len(), 1);
+    }
+
+    #[test]
+    fn test_memory_bulk() {
+        let module_name = "test-memory-bulk.wasm";
+
+        // 测试 memory.fill
+        assert_eq!(
+            eval(module_name, 0, &vec![]).unwrap(),
+            vec![Value::I32(0xabababab_u32 as i32), Value::I32(0)]
+        );
+
+        // 测试 memory.copy
+        assert_eq!(
+            eval(module_name, 1, &vec![]).unwrap(),
+            vec![Value::I32(0x65686568), Value::I32(0x006f6c6c)]
+        );
+        assert_eq!(
+            eval(module_name, 2, &vec![]).unwrap(),
+            vec![Value::I32(0x6c6c6568), Value::I32(0x0707)]
+        );
+
+        // 测试越界访问
+        assert_eq!(
+            eval(module_name, 3, &vec![Value::I32(1)]).unwrap(),
+            vec![Value::I32(1)]
+        );
+        match eval(module_name, 3, &vec![Value::I32(2)]) {
+            Err(EngineError::OutOfRange(e)) => {
+                assert_eq!(e, OutOfRange::MemoryAccessOutOfRange(65535, 2, 65536))
+            }
+            _ => panic!("expected a memory access out of range error"),
+        }
+
+        // 测试 memory.init 和 data.drop
+        assert_eq!(eval(module_name, 4, &vec![Value::I32(0)]).unwrap(), vec![]);
+        match eval(module_name, 4, &vec![Value::I32(1)]) {
+            Err(EngineError::OutOfRange(e)) => {
+                assert_eq!(e, OutOfRange::DataSegmentAccessOutOfRange(0, 1, 0))
+            }
+            _ => panic!("expected a data segment access out of range error"),
+        }
+    }
+
+    #[test]
+    fn test_multi_memory() {
+        let module_name = "test-multi-memory.wasm";
+
+        // 分别读取两个内存块
+        assert_eq!(
+            eval(module_name, 2, &vec![]).unwrap(),
+            vec![Value::I32(0x0b), Value::I32(0x02)]
+        );
+
+        // 写入第二个内存块
+        assert_eq!(
+            eval(module_name, 3, &vec![Value::I32(123)]).unwrap(),
+            vec![Value::I32(123), Value::I32(0)]
+        );
+
+        // 第二个内存块的页面数
+        assert_eq!(
+            eval(module_name, 4, &vec![]).unwrap(),
+            vec![Value::I32(1), Value::I32(3), Value::I32(1)]
+        );
+
+        // 通过第二张表间接调用函数
+        assert_eq!(
+            eval(module_name, 5, &vec![Value::I32(0), Value::I32(10)]).unwrap(),
+            vec![Value::I32(11)]
+        );
+        assert_eq!(
+            eval(module_name, 5, &vec![Value::I32(1), Value::I32(10)]).unwrap(),
+            vec![Value::I32(20)]
+        );
+
+        // 在两张表之间复制元素
+        assert_eq!(
+            eval(module_name, 6, &vec![Value::I32(7)]).unwrap(),
+            vec![Value::I32(14)]
+        );
+
+        // 导入其他模块的第二个内存块
+        let named_ast_module_lib = NamedAstModule::new("lib", get_test_ast_module(module_name));
+        let named_ast_module_app = NamedAstModule::new(
+            "app",
+            get_test_ast_module("test-multi-memory-import.wasm"),
+        );
+        let mut vm =
+            create_instance(vec![], &vec![named_ast_module_lib, named_ast_module_app]).unwrap();
+
+        assert_eq!(vm.resource.memory_blocks.len(), 3);
+        assert_eq!(vm.resource.vm_modules[1].memory_indexes, vec![1, 2]);
+        assert_eq!(
+            vm.eval_function_by_index(1, 0, &vec![]).unwrap(),
+            vec![Value::I32(0x03), Value::I32(0)]
+        );
+    }
+
+    #[test]
+    fn test_thread_instance_shared_memory_out_of_range() {
+        let named_ast_modules = vec![NamedAstModule::new(
+            "test",
+            get_test_ast_module("test-multi-memory.wasm"),
+        )];
+
+        // 共享内存块的索引超出新实例的内存块数量（2）
+        let shared_memory_blocks = vec![None, None, Some(VMMemory::new_by_min_page(1))];
+
+        match create_thread_instance(vec![], &named_ast_modules, shared_memory_blocks) {
+            Err(EngineError::OutOfRange(OutOfRange::MemoryBlockIndexOutOfRange(2, 2))) => {}
+            _ => panic!("expected a memory block index out of range error"),
+        }
+    }
+
+    #[test]
+    fn test_function_reference_across_modules() {
+        let named_ast_module_lib = NamedAstModule::new(
+            "lib",
+            get_test_ast_module("test-function-reference-lib.wasm"),
+        );
+        let named_ast_module_app = NamedAstModule::new(
+            "app",
+            get_test_ast_module("test-function-reference-app.wasm"),
+        );
+        let mut vm =
+            create_instance(vec![], &[named_ast_module_lib, named_ast_module_app]).unwrap();
+
+        // 函数引用的值是函数在 VM 实例里的全局索引
+        assert_eq!(vm.resource.vm_modules[1].function_reference_base, 2);
+
+        // 通过表或者全局变量传递 lib 模块的函数引用，调用的仍然是 lib 模块的函数
+        assert_eq!(
+            vm.eval_function_by_index(1, 2, &[]).unwrap(),
+            vec![Value::I32(111)]
+        );
+        assert_eq!(
+            vm.eval_function_by_index(1, 3, &[]).unwrap(),
+            vec![Value::I32(111)]
+        );
+        assert_eq!(
+            vm.eval_function_by_index(1, 4, &[]).unwrap(),
+            vec![Value::I32(222)]
+        );
+
+        // 函数引用在宿主和 VM 之间传递
+        assert_eq!(
+            vm.eval_function_by_index(1, 5, &[]).unwrap(),
+            vec![Value::FuncRef(Some(1))]
+        );
+        assert_eq!(
+            vm.eval_function_by_index(1, 6, &[Value::FuncRef(Some(1))])
+                .unwrap(),
+            vec![Value::I32(111)]
+        );
+        assert_eq!(
+            vm.eval_function_by_index(1, 6, &[Value::FuncRef(Some(3))])
+                .unwrap(),
+            vec![Value::I32(222)]
+        );
+        assert!(matches!(
+            vm.eval_function_by_index(1, 6, &[Value::FuncRef(Some(100))]),
+            Err(EngineError::ObjectNotFound(
+                ObjectNotFound::ElementItemNotFound(1, 0, 0)
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_memory64() {
+        let module_name = "test-memory64.wasm";
+
+        // 使用 i64 地址读取数据段的内容
+        assert_eq!(
+            eval(module_name, 0, &vec![]).unwrap(),
+            vec![Value::I32(0x01), Value::I32(0x0403)]
+        );
+
+        // 写入然后读取
+        assert_eq!(
+            eval(
+                module_name,
+                1,
+                &vec![Value::I64(8), Value::I64(0x1122_3344_5566_7788)]
+            )
+            .unwrap(),
+            vec![Value::I64(0x1122_3344_5566_7788)]
+        );
+
+        // memory.grow 和 memory.size
+        assert_eq!(
+            eval(module_name, 2, &vec![Value::I64(2)]).unwrap(),
+            vec![Value::I64(1), Value::I64(3)]
+        );
+        assert_eq!(
+            eval(module_name, 2, &vec![Value::I64(4)]).unwrap(),
+            vec![Value::I64(-1), Value::I64(1)]
+        );
+
+        // 越界访问
+        match eval(module_name, 1, &vec![Value::I64(65532), Value::I64(1)]) {
+            Err(EngineError::OutOfRange(e)) => {
+                assert_eq!(e, OutOfRange::MemoryAccessOutOfRange(65532, 8, 65536))
+            }
+            _ => panic!("expected a memory access out of range error"),
+        }
+
+        match eval(module_name, 3, &vec![]) {
+            Err(EngineError::OutOfRange(e)) => {
+                assert_eq!(
+                    e,
+                    OutOfRange::MemoryAccessOutOfRange(0x1_0000_0000, 4, 65536)
+                )
+            }
+            _ => panic!("expected a memory access out of range error"),
+        }
+    }
+
+    #[test]
+    fn test_memory64_grow() {
+        let module_name = "test-memory64-grow.wasm";
+
+        // 增长的页面数超出宿主的内存时 memory.grow 返回 -1，而不是中止进程
+        assert_eq!(
+            eval(module_name, 0, &vec![Value::I64(1 << 47)]).unwrap(),
+            vec![Value::I64(-1), Value::I64(1)]
+        );
+        assert_eq!(
+            eval(module_name, 0, &vec![Value::I64(1 << 30)]).unwrap(),
+            vec![Value::I64(-1), Value::I64(1)]
+        );
+        assert_eq!(
+            eval(module_name, 0, &vec![Value::I64(-1)]).unwrap(),
+            vec![Value::I64(-1), Value::I64(1)]
+        );
+
+        assert_eq!(
+            eval(module_name, 0, &vec![Value::I64(1)]).unwrap(),
+            vec![Value::I64(1), Value::I64(2)]
+        );
+        assert_eq!(
+            eval(module_name, 1, &vec![]).unwrap(),
+            vec![Value::I32(0x7f)]
+        );
+    }
+
+    #[test]
+    fn test_extended_constant_expression() {
+        let named_ast_module_lib =
+            NamedAstModule::new("lib", get_test_ast_module("test-extended-const-lib.wasm"));
+        let named_ast_module_app =
+            NamedAstModule::new("app", get_test_ast_module("test-extended-const.wasm"));
+        let mut vm =
+            create_instance(vec![], &vec![named_ast_module_lib, named_ast_module_app]).unwrap();
+
+        // 全局变量的初始值
+        assert_eq!(
+            vm.eval_function_by_index(1, 1, &vec![]).unwrap(),
+            vec![Value::I32(20), Value::I64(42)]
+        );
+
+        // 数据段的偏移值是 16 + 8
+        assert_eq!(
+            vm.eval_function_by_index(1, 2, &vec![]).unwrap(),
+            vec![Value::I32(0x0a), Value::I32(0x0b)]
+        );
+
+        // 元素段的偏移值是 2 + 1
+        assert_eq!(
+            vm.eval_function_by_index(1, 3, &vec![]).unwrap(),
+            vec![Value::I32(42)]
+        );
+    }
+
+    #[test]
+    fn test_register_interpreter() {
+        assert_same_as_stack_interpreter(InterpreterKind::Register);
+    }
+
+    #[test]
+    fn test_integer_divide_trap() {
+        // test-jit.wasm 的前 4 个函数分别是
+        // i32.div_s, i32.rem_s, i64.div_u 和 i64.rem_s
+        let module_name = "test-jit.wasm";
+
+        for interpreter_kind in [
+            InterpreterKind::Stack,
+            InterpreterKind::Register,
+            #[cfg(feature = "jit")]
+            InterpreterKind::Jit,
+        ] {
+            let eval = |function_index: usize, args: &[Value]| {
+                eval_with_interpreter(module_name, function_index, args, interpreter_kind)
+            };
+
+            assert_eq!(
+                eval(1, &convert_i32_list(&[i32::MIN, -1])).unwrap(),
+                vec![Value::I32(0)]
+            );
+            assert_eq!(
+                eval(3, &[Value::I64(i64::MIN), Value::I64(-1)]).unwrap(),
+                vec![Value::I64(0)]
+            );
+
+            assert!(matches!(
+                eval(0, &convert_i32_list(&[1, 0])),
+                Err(EngineError::InvalidOperation(
+                    InvalidOperation::IntegerDivideByZero
+                ))
+            ));
+            assert!(matches!(
+                eval(0, &convert_i32_list(&[i32::MIN, -1])),
+                Err(EngineError::InvalidOperation(
+                    InvalidOperation::IntegerOverflow
+                ))
+            ));
+            assert!(matches!(
+                eval(1, &convert_i32_list(&[1, 0])),
+                Err(EngineError::InvalidOperation(
+                    InvalidOperation::IntegerDivideByZero
+                ))
+            ));
+            assert!(matches!(
+                eval(2, &[Value::I64(1), Value::I64(0)]),
+                Err(EngineError::InvalidOperation(
+                    InvalidOperation::IntegerDivideByZero
+                ))
+            ));
+            assert!(matches!(
+                eval(3, &[Value::I64(1), Value::I64(0)]),
+                Err(EngineError::InvalidOperation(
+                    InvalidOperation::IntegerDivideByZero
+                ))
+            ));
+        }
+    }
+
+    #[cfg(feature = "jit")]
+    #[test]
+    fn test_jit() {
+        assert_same_as_stack_interpreter(InterpreterKind::Jit);
+
+        let module_name = "test-jit.wasm";
+        let eval_jit = |function_index: usize, args: &[Value]| {
+            eval_with_interpreter(module_name, function_index, args, InterpreterKind::Jit)
+        };
+
+        let named_ast_module = NamedAstModule::new("test", get_test_ast_module(module_name));
+        let vm =
+            create_instance_with_interpreter(vec![], &[named_ast_module], InterpreterKind::Jit)
+                .unwrap();
+        assert!(vm.resource.vm_modules[0].jit_code.is_some());
+
+        // 整数除法
+        assert_eq!(
+            eval_jit(0, &convert_i32_list(&[-7, 2])).unwrap(),
+            vec![Value::I32(-3)]
+        );
+        assert_eq!(
+            eval_jit(1, &convert_i32_list(&[-7, 2])).unwrap(),
+            vec![Value::I32(-1)]
+        );
+        assert_eq!(
+            eval_jit(1, &convert_i32_list(&[i32::MIN, -1])).unwrap(),
+            vec![Value::I32(0)]
+        );
+        assert_eq!(
+            eval_jit(2, &[Value::I64(-1), Value::I64(2)]).unwrap(),
+            vec![Value::I64(i64::MAX)]
+        );
+        assert_eq!(
+            eval_jit(3, &[Value::I64(i64::MIN), Value::I64(-1)]).unwrap(),
+            vec![Value::I64(0)]
+        );
+
+        // 陷阱
+        assert!(matches!(
+            eval_jit(0, &convert_i32_list(&[1, 0])),
+            Err(EngineError::InvalidOperation(
+                InvalidOperation::IntegerDivideByZero
+            ))
+        ));
+        assert!(matches!(
+            eval_jit(0, &convert_i32_list(&[i32::MIN, -1])),
+            Err(EngineError::InvalidOperation(
+                InvalidOperation::IntegerOverflow
+            ))
+        ));
+        assert!(matches!(
+            eval_jit(2, &[Value::I64(1), Value::I64(0)]),
+            Err(EngineError::InvalidOperation(
+                InvalidOperation::IntegerDivideByZero
+            ))
+        ));
+        assert!(matches!(
+            eval_jit(3, &[Value::I64(1), Value::I64(0)]),
+            Err(EngineError::InvalidOperation(
+                InvalidOperation::IntegerDivideByZero
+            ))
+        ));
+
+        // 内存读写，越界时的出错信息应该跟栈式解释器的相同
+        let assert_same_result = |function_index: usize, args: &[Value]| {
+            let stack_result =
+                eval_with_interpreter(module_name, function_index, args, InterpreterKind::Stack);
+            assert_eq!(
+                format!("{:?}", stack_result),
+                format!("{:?}", eval_jit(function_index, args)),
+                "function {}",
+                function_index
+            );
+        };
+
+        for address in [0, 100, 65520, 65521, 65536] {
+            assert_same_result(4, &[Value::I32(address), Value::I64(-0x1_2345_6789)]);
+        }
+        for value in [0.0, -1.5, f32::NAN, f32::INFINITY] {
+            assert_same_result(5, &[Value::F32(value)]);
+        }
+        assert_same_result(6, &[Value::I32(100)]);
+        assert_same_result(6, &[Value::I32(10_000)]);
+
+        assert_eq!(
+            eval_jit(6, &[Value::I32(100)]).unwrap(),
+            vec![Value::I64(328350)]
+        );
+
+        // 跳转表，包括需要复制 v128 操作数（交回解释器执行）的情况
+        for (index, expected) in [(0, 31), (1, 32), (2, 33), (3, 30), (100, 30), (-1, 30)] {
+            assert_eq!(
+                eval_jit(7, &convert_i32_list(&[index, 10])).unwrap(),
+                vec![Value::I32(expected)]
+            );
+        }
+        for (index, expected) in [(0, 22), (1, 2), (-1, 2)] {
+            assert_eq!(
+                eval_jit(8, &[Value::I32(index)]).unwrap(),
+                vec![Value::I64(expected)]
+            );
+        }
+        assert_same_result(9, &[Value::I32(1000)]);
+    }
+
+    /// 指定的解释器跟栈式解释器的执行结果（包括出错信息）应该完全相同
+    fn assert_same_as_stack_interpreter(interpreter_kind: InterpreterKind) {
+        let assert_same_result = |filename: &str, function_index: usize, args: &[Value]| {
+            let stack_result =
+                eval_with_interpreter(filename, function_index, args, InterpreterKind::Stack);
+            let result = eval_with_interpreter(filename, function_index, args, interpreter_kind);
+            assert_eq!(
+                format!("{:?}", stack_result),
+                format!("{:?}", result),
+                "{} function {}",
+                filename,
+                function_index
+            );
+        };
+
+        // 测试各个模块里的无参数函数
+        let module_names = [
+            "test-block.wasm",
+            "test-block-if.wasm",
+            "test-const.wasm",
+            "test-function-call.wasm",
+            "test-function-call-exhaustion.wasm",
+            "test-global-variable.wasm",
+            "test-local-variable.wasm",
+            "test-memory-bulk.wasm",
+            "test-memory-load.wasm",
+            "test-memory-page.wasm",
+            "test-memory-store.wasm",
+            "test-memory64.wasm",
+            "test-multi-memory.wasm",
+            "test-numeric-binary.wasm",
+            "test-numeric-comparsion.wasm",
+            "test-numeric-convert.wasm",
+            "test-numeric-eqz.wasm",
+            "test-numeric-unary.wasm",
+            "test-parametric.wasm",
+            "test-segment-init.wasm",
+            "test-simd.wasm",
+            "test-table.wasm",
+        ];
+
+        for module_name in module_names {
+            let named_ast_module = NamedAstModule::new("test", get_test_ast_module(module_name));
+            let vm = create_instance(vec![], &[named_ast_module]).unwrap();
+            let vm_module = &vm.resource.vm_modules[0];
+
+            for (function_index, function_item) in vm_module.function_items.iter().enumerate() {
+                if let FunctionItem::Normal { type_index, .. } = function_item {
+                    if vm_module.function_types[*type_index].params.is_empty() {
+                        assert_same_result(module_name, function_index, &[]);
+                    }
+                }
+            }
+        }
+
+        // 测试带参数的函数
+        let module_name = "test-block-loop.wasm";
+        assert_same_result(module_name, 0, &[Value::I32(100)]);
+        assert_same_result(module_name, 1, &[Value::I32(1000)]);
+        assert_same_result(module_name, 2, &[Value::I32(10)]);
+        assert_same_result(module_name, 3, &[Value::I32(10)]);
+
+        let module_name = "test-block-branch-table.wasm";
+        for index in 0..6 {
+            assert_same_result(module_name, 0, &[Value::I32(index)]);
+        }
+
+        assert_same_result("test-block.wasm", 9, &convert_i32_list(&[55, 66]));
+
+        let module_name = "test-function-call.wasm";
+        assert_same_result(module_name, 1, &convert_i32_list(&[55, 66]));
+        assert_same_result(module_name, 2, &convert_i32_list(&[-123]));
+        assert_same_result(module_name, 3, &convert_i32_list(&[-55, 66]));
+
+        let module_name = "test-function-call-indirect.wasm";
+        for selector in 0..4 {
+            assert_same_result(module_name, 0, &convert_i32_list(&[selector, 40, 5]));
+        }
+
+        let module_name = "test-function-tail-call.wasm";
+        assert_same_result(module_name, 0, &[Value::I32(200_000)]);
+        assert_same_result(module_name, 2, &[Value::I64(10), Value::I64(1)]);
+        assert_same_result(module_name, 3, &convert_i32_list(&[1, 10]));
+        assert_same_result(module_name, 4, &[Value::I32(1000)]);
+
+        // 测试跨模块调用以及本地函数调用
+        for function_index in 0..12 {
+            let args = convert_i32_list(&[8, 2]);
+            assert_eq!(
+                format!(
+                    "{:?}",
+                    eval_with_multiple_modules_and_interpreter(
+                        function_index,
+                        &args,
+                        InterpreterKind::Stack
+                    )
+                ),
+                format!(
+                    "{:?}",
+                    eval_with_multiple_modules_and_interpreter(
+                        function_index,
+                        &args,
+                        interpreter_kind
+                    )
+                ),
+                "function {}",
+                function_index
+            );
+        }
+    }
+
+    #[test]
+    fn test_fused_instructions() {
+        let module_name = "test-fused.wasm";
+
+        assert_eq!(
+            eval(module_name, 0, &convert_i32_list(&[3, 4])).unwrap(),
+            vec![Value::I32(7)]
+        );
+        assert_eq!(
+            eval(module_name, 1, &[Value::I32(-5)]).unwrap(),
+            vec![Value::I32(-1)]
+        );
+        assert_eq!(
+            eval(module_name, 1, &[Value::I32(10)]).unwrap(),
+            vec![Value::I32(45)]
+        );
+        assert_eq!(eval(module_name, 2, &[]).unwrap(), vec![Value::I32(45)]);
+
+        let named_ast_module = NamedAstModule::new("test", get_test_ast_module(module_name));
+        let mut vm = create_instance(vec![], &[named_ast_module]).unwrap();
+
+        let vm_module = &vm.resource.vm_modules[0];
+        let fused_names = vm_module
+            .fused_items
+            .iter()
+            .map(|fused_item| match fused_item.fused_instruction {
+                FusedInstruction::LocalGetLocalGetI32Add { .. } => "get_get_add",
+                FusedInstruction::LocalGetI32ConstI32AddLocalSet { .. } => "get_const_add_set",
+                FusedInstruction::I32ConstI32LtSBreakWhenNotEqZero { .. } => "const_lt_br_if",
+            })
+            .collect::<Vec<&str>>();
+        assert_eq!(
+            fused_names,
+            vec![
+                "get_get_add",
+                "const_lt_br_if",
+                "get_get_add",
+                "get_const_add_set",
+                "get_get_add",
+                "get_const_add_set",
+                "const_lt_br_if"
+            ]
+        );
+
+        // 函数 $add 的前 3 条指令被融合为一个超级指令
+        assert!(matches!(
+            vm_module.bytecode.fused_opcodes[0],
+            Opcode::LocalGetLocalGetI32Add(0, 1)
+        ));
+        assert!(matches!(vm_module.bytecode.opcodes[0], Opcode::LocalGet(0)));
+        for address in 0..3 {
+            let fused_item = vm_module.get_fused_item(address).unwrap();
+            assert_eq!((fused_item.address, fused_item.length), (0, 3));
+        }
+        assert!(vm_module.get_fused_item(3).is_none());
+
+        // 单步执行时仍然逐条执行原指令
+        vm.call_function_by_index(0, 0, &convert_i32_list(&[3, 4]))
+            .unwrap();
+        let mut addresses = vec![];
+        loop {
+            addresses.push(vm.status.address);
+            if vm.step().unwrap() {
+                break;
+            }
+        }
+        assert_eq!(addresses, vec![0, 1, 2, 3]);
+        assert_eq!(vm.stack.pop_values(1), vec![Value::I32(7)]);
     }
 }
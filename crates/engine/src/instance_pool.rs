@@ -0,0 +1,347 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 实例池
+//!
+//! 函数即服务（FaaS）之类的场景需要为每个请求提供一个全新的 VM 实例，如果每次都通过
+//! `create_instance` 创建实例，则每次都需要重新链接模块以及复制所有的数据段。
+//!
+//! 实例池只实例化一次模块，并保存实例化之后的模块实例、内存块、表、全局变量以及标签的镜像，
+//! 实例用完之后归还给实例池，实例池把它重置为镜像的状态，然后再交给下一个请求使用。
+//! 没有空闲的实例时，实例池直接从镜像创建新实例，既不需要重新链接，也不需要填充数据段。
+//!
+//! 在 Linux 系统里，实例池的内存块以写时复制的方式映射镜像（见 `VMMemory::new_from_image`），
+//! 重置时只需丢弃请求期间写入过的页面，所以重置的开销跟内存块的容量无关。
+//!
+//! 注意：
+//!
+//! - 本地模块的上下文（比如 WASI 的状态）无法从镜像恢复，所以实例池在创建新实例以及
+//!   归还实例时都调用 `native_modules_factory` 重新创建本地模块；
+//! - 共享内存块可能已经交给其它线程的 VM 实例使用，所以不会被替换为映射镜像的内存块，
+//!   重置时只复制镜像的内容；
+//! - VM 实例不能跨线程使用，多线程的服务可以为每个线程创建一个实例池。
+
+use crate::{
+    error::EngineError,
+    instance::create_instance_with_interpreter,
+    native_module::NativeModule,
+    object::NamedAstModule,
+    vm::{InterpreterKind, Resource, Status, VM},
+    vm_exception::VMTag,
+    vm_global_variable::VMGlobalVariable,
+    vm_memory::{MemoryImage, VMMemory},
+    vm_module::VMModule,
+    vm_stack::VMStack,
+    vm_table::VMTable,
+};
+
+pub struct InstancePool {
+    native_modules_factory: Box<dyn Fn() -> Vec<NativeModule>>,
+    interpreter_kind: InterpreterKind,
+    image: InstanceImage,
+
+    /// 空闲的实例
+    idle_instances: Vec<VM>,
+}
+
+/// 实例刚实例化之后的状态
+struct InstanceImage {
+    memory_images: Vec<MemoryImage>,
+    tables: Vec<VMTable>,
+    global_variables: Vec<VMGlobalVariable>,
+    tags: Vec<VMTag>,
+
+    /// 模块实例，包括解码后的指令、寄存器中间表示、机器码以及元素段和数据段
+    vm_modules: Vec<VMModule>,
+}
+
+impl InstancePool {
+    /// 实例化模块并保存实例的镜像
+    ///
+    /// 参数 native_modules_factory 用于为每个新实例以及每个归还的实例创建本地模块。
+    pub fn new(
+        named_ast_modules: &[NamedAstModule],
+        native_modules_factory: impl Fn() -> Vec<NativeModule> + 'static,
+        interpreter_kind: InterpreterKind,
+    ) -> Result<Self, EngineError> {
+        let mut vm = create_instance_with_interpreter(
+            native_modules_factory(),
+            named_ast_modules,
+            interpreter_kind,
+        )?;
+
+        let image = InstanceImage::capture(&vm);
+        image.remap_memory_blocks(&mut vm);
+
+        Ok(Self {
+            native_modules_factory: Box::new(native_modules_factory),
+            interpreter_kind,
+            image,
+            idle_instances: vec![vm],
+        })
+    }
+
+    /// 取出一个实例，没有空闲的实例时则从镜像创建一个新实例
+    pub fn acquire(&mut self) -> VM {
+        if let Some(vm) = self.idle_instances.pop() {
+            return vm;
+        }
+
+        self.image
+            .instantiate((self.native_modules_factory)(), self.interpreter_kind)
+    }
+
+    /// 归还实例，实例会被重置为刚实例化之后的状态
+    ///
+    /// 实例必须是从当前实例池取出的。
+    pub fn release(&mut self, mut vm: VM) {
+        self.image.restore(&mut vm);
+        vm.resource.native_modules = (self.native_modules_factory)();
+        self.idle_instances.push(vm);
+    }
+
+    /// 空闲的实例的数量
+    pub fn get_idle_count(&self) -> usize {
+        self.idle_instances.len()
+    }
+}
+
+impl InstanceImage {
+    fn capture(vm: &VM) -> Self {
+        let resource = &vm.resource;
+
+        Self {
+            memory_images: resource
+                .memory_blocks
+                .iter()
+                .map(|memory_block| memory_block.create_image())
+                .collect::<Vec<MemoryImage>>(),
+            tables: resource.tables.clone(),
+            global_variables: resource.global_variables.clone(),
+            tags: resource.tags.clone(),
+            vm_modules: resource.vm_modules.clone(),
+        }
+    }
+
+    /// 从镜像创建新实例
+    fn instantiate(
+        &self,
+        native_modules: Vec<NativeModule>,
+        interpreter_kind: InterpreterKind,
+    ) -> VM {
+        let memory_blocks = self
+            .memory_images
+            .iter()
+            .map(VMMemory::new_from_image)
+            .collect::<Vec<VMMemory>>();
+
+        let resource = Resource::new(
+            memory_blocks,
+            self.tables.clone(),
+            self.global_variables.clone(),
+            self.tags.clone(),
+            native_modules,
+            self.vm_modules.clone(),
+        );
+
+        VM {
+            stack: VMStack::new(),
+            status: Status::new(),
+            resource,
+            interpreter_kind,
+        }
+    }
+
+    /// 使用从镜像创建的内存块代替实例原有的（非共享）内存块
+    fn remap_memory_blocks(&self, vm: &mut VM) {
+        for (memory_block, memory_image) in vm
+            .resource
+            .memory_blocks
+            .iter_mut()
+            .zip(&self.memory_images)
+        {
+            if !memory_block.is_shared() {
+                *memory_block = VMMemory::new_from_image(memory_image);
+            }
+        }
+    }
+
+    /// 把实例重置为镜像的状态
+    fn restore(&self, vm: &mut VM) {
+        let resource = &mut vm.resource;
+
+        for (memory_block, memory_image) in
+            resource.memory_blocks.iter_mut().zip(&self.memory_images)
+        {
+            memory_block.reset(memory_image);
+        }
+
+        resource.tables.clone_from(&self.tables);
+        resource.global_variables.clone_from(&self.global_variables);
+        resource.exceptions.clear();
+        resource.free_exception_indexes.clear();
+
+        for (vm_module, image_vm_module) in resource.vm_modules.iter_mut().zip(&self.vm_modules) {
+            vm_module
+                .element_segments
+                .clone_from(&image_vm_module.element_segments);
+            vm_module
+                .data_segments
+                .clone_from(&image_vm_module.data_segments);
+        }
+
+        vm.stack.clear();
+        vm.status = Status::new();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{any::Any, env, fs};
+
+    use anvm_ast::types::Value;
+    use anvm_binary_parser::parser;
+
+    use crate::{
+        native_module::{ModuleContext, NativeModule},
+        object::NamedAstModule,
+        vm::{InterpreterKind, VM},
+    };
+
+    use super::InstancePool;
+
+    // 辅助方法
+    fn get_test_binary_resource(filename: &str) -> Vec<u8> {
+        let mut path_buf = env::current_dir().unwrap();
+        if !path_buf.ends_with("engine") {
+            path_buf.push("crates");
+            path_buf.push("engine");
+        }
+        let fullname_buf = path_buf.join("resources").join(filename);
+        let fullname = fullname_buf.to_str().unwrap();
+        fs::read(fullname)
+            .unwrap_or_else(|_| panic!("failed to read the specified binary file: {}", fullname))
+    }
+
+    fn create_test_instance_pool(interpreter_kind: InterpreterKind) -> InstancePool {
+        let bytes = get_test_binary_resource("test-instance-pool.wasm");
+        let named_ast_module = NamedAstModule::new("test", parser::parse(&bytes).unwrap());
+        InstancePool::new(&[named_ast_module], Vec::new, interpreter_kind).unwrap()
+    }
+
+    #[test]
+    fn test_reuse_instance() {
+        for interpreter_kind in [InterpreterKind::Stack, InterpreterKind::Register] {
+            let mut pool = create_test_instance_pool(interpreter_kind);
+            let initial_results = vec![
+                Value::I32(10),
+                Value::I32(100),
+                Value::I32(1),
+                Value::I32(1),
+            ];
+
+            // 修改了内存、全局变量、内存块页面数以及表的大小的实例，归还之后被重置
+            for _ in 0..3 {
+                let mut vm = pool.acquire();
+                assert_eq!(pool.get_idle_count(), 0);
+                assert_eq!(
+                    vm.eval_function_by_index(0, 0, &[]).unwrap(),
+                    initial_results
+                );
+                assert_eq!(
+                    vm.eval_function_by_index(0, 0, &[]).unwrap(),
+                    vec![
+                        Value::I32(99),
+                        Value::I32(200),
+                        Value::I32(2),
+                        Value::I32(2)
+                    ]
+                );
+                assert_eq!(vm.resource.memory_blocks[0].read_i32(65536), 77);
+
+                pool.release(vm);
+                assert_eq!(pool.get_idle_count(), 1);
+            }
+
+            // 没有空闲的实例时创建新实例
+            let mut vm0 = pool.acquire();
+            let mut vm1 = pool.acquire();
+            assert_eq!(
+                vm0.eval_function_by_index(0, 1, &[]).unwrap(),
+                vec![Value::I32(100)]
+            );
+            vm0.eval_function_by_index(0, 0, &[]).unwrap();
+            assert_eq!(
+                vm1.eval_function_by_index(0, 0, &[]).unwrap(),
+                initial_results
+            );
+
+            pool.release(vm0);
+            pool.release(vm1);
+            assert_eq!(pool.get_idle_count(), 2);
+
+            // 陷入之后的实例也能被重置
+            let mut vm2 = pool.acquire();
+            assert!(vm2.eval_function_by_index(0, 2, &[]).is_err());
+            pool.release(vm2);
+
+            let mut vm3 = pool.acquire();
+            assert_eq!(
+                vm3.eval_function_by_index(0, 0, &[]).unwrap(),
+                initial_results
+            );
+        }
+    }
+
+    struct CounterContext {
+        count: i32,
+    }
+
+    impl ModuleContext for CounterContext {
+        fn as_any(&mut self) -> &mut dyn Any {
+            self
+        }
+    }
+
+    fn get_count(vm: &mut VM) -> &mut i32 {
+        &mut vm.resource.native_modules[0]
+            .module_context
+            .as_any()
+            .downcast_mut::<CounterContext>()
+            .unwrap()
+            .count
+    }
+
+    #[test]
+    fn test_reset_native_module_context() {
+        let bytes = get_test_binary_resource("test-instance-pool.wasm");
+        let named_ast_module = NamedAstModule::new("test", parser::parse(&bytes).unwrap());
+        let mut pool = InstancePool::new(
+            &[named_ast_module],
+            || {
+                vec![NativeModule::new(
+                    "counter",
+                    Box::new(CounterContext { count: 0 }),
+                )]
+            },
+            InterpreterKind::Stack,
+        )
+        .unwrap();
+
+        // 归还的实例以及从镜像创建的实例都使用新的本地模块上下文
+        let mut vm0 = pool.acquire();
+        let mut vm1 = pool.acquire();
+        *get_count(&mut vm0) = 1;
+        *get_count(&mut vm1) = 2;
+        pool.release(vm0);
+        pool.release(vm1);
+
+        for _ in 0..2 {
+            let mut vm = pool.acquire();
+            assert_eq!(*get_count(&mut vm), 0);
+        }
+    }
+}
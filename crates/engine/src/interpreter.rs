@@ -4,19 +4,150 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+use std::rc::Rc;
+
 use anvm_ast::instruction::Instruction;
 
 use crate::{
+    bytecode::Opcode,
     error::EngineError,
-    ins_block, ins_const,
+    ins_atomic, ins_block, ins_const,
     ins_control::{self, ControlResult},
+    ins_exception,
     ins_function::{self},
-    ins_memory, ins_numeric_binary, ins_numeric_comparsion, ins_numeric_convert, ins_numeric_eqz,
-    ins_numeric_unary, ins_parametric, ins_variable,
+    ins_fused, ins_memory, ins_numeric_binary, ins_numeric_comparsion, ins_numeric_convert,
+    ins_numeric_eqz, ins_numeric_unary, ins_parametric, ins_reference, ins_simd, ins_table,
+    ins_variable,
     object::{self, Control},
     vm::VM,
 };
 
+/// 执行一个字节码
+///
+/// 当程序（即第一个被调用的函数）的最后一条指令执行之后，函数返回 true，否则返回 false。
+#[inline]
+pub fn exec_opcode(vm: &mut VM, opcode: Opcode) -> Result<bool, EngineError> {
+    let sequence_result = match opcode {
+        Opcode::Nop => Ok(()),
+        Opcode::Sequence(function) => function(vm),
+
+        Opcode::I32Const(value) => ins_const::i32_const(vm, value),
+        Opcode::I64Const(value) => ins_const::i64_const(vm, value),
+        Opcode::F32Const(value) => ins_const::f32_const(vm, value),
+        Opcode::F64Const(value) => ins_const::f64_const(vm, value),
+
+        // 局部变量的索引即局部变量槽相对于当前调用帧开始位置的偏移值
+        Opcode::LocalGet(offset) => ins_variable::local_get_slot(vm, offset),
+        Opcode::LocalSet(offset) => ins_variable::local_set_slot(vm, offset),
+        Opcode::LocalTee(offset) => ins_variable::local_tee_slot(vm, offset),
+        Opcode::GlobalGet(instance_global_variable_index) => {
+            let value =
+                vm.resource.global_variables[instance_global_variable_index as usize].get_value();
+            vm.stack.push(value);
+            Ok(())
+        }
+
+        Opcode::Memory(function, index) => {
+            let memory_access = vm.resource.vm_modules[vm.status.vm_module_index]
+                .bytecode
+                .memory_accesses[index as usize];
+            function(vm, &memory_access)
+        }
+
+        Opcode::Jump(address) => {
+            vm.status.address = address as usize;
+            return Ok(false);
+        }
+        Opcode::JumpWhenEqZero(address) => {
+            let control_result = ins_block::jump_when_eq_zero(vm, address as usize);
+            return apply_control_result(vm, control_result);
+        }
+        Opcode::Break(index) | Opcode::BreakWhenNotEqZero(index) => {
+            let break_item = vm.resource.vm_modules[vm.status.vm_module_index]
+                .bytecode
+                .break_items[index as usize]
+                .clone();
+            let control_result = if let Opcode::Break(_) = opcode {
+                ins_block::process_break(
+                    vm,
+                    break_item.option_block_index,
+                    &break_item.branch_target,
+                )
+            } else {
+                ins_block::process_break_when_not_eq_zero(
+                    vm,
+                    break_item.option_block_index,
+                    &break_item.branch_target,
+                )
+            };
+            return apply_control_result(vm, control_result);
+        }
+        Opcode::Call(index) => {
+            let call_item = vm.resource.vm_modules[vm.status.vm_module_index]
+                .bytecode
+                .call_items[index as usize]
+                .clone();
+            let control_result = ins_function::call(
+                vm,
+                call_item.vm_module_index,
+                call_item.type_index,
+                call_item.function_index,
+                call_item.internal_function_index,
+                call_item.address,
+            );
+            return apply_control_result(vm, control_result);
+        }
+
+        Opcode::Fallback => {
+            // 指令列表由 Rc 共享，这里只是增加引用计数，并不会复制指令
+            let instructions =
+                Rc::clone(&vm.resource.vm_modules[vm.status.vm_module_index].instructions);
+            return exec_instruction(vm, &instructions[vm.status.address]);
+        }
+
+        // 超级字节码执行之后跳到被融合的最后一条原指令的下一条指令
+        Opcode::LocalGetLocalGetI32Add(left_offset, right_offset) => {
+            ins_fused::local_get_local_get_i32_add(vm, left_offset, right_offset)?;
+            vm.status.address += 3;
+            return Ok(false);
+        }
+        Opcode::LocalGetI32ConstI32AddLocalSet(source_offset, value, dest_offset) => {
+            ins_fused::local_get_i32_const_i32_add_local_set(
+                vm,
+                source_offset,
+                value,
+                dest_offset,
+            )?;
+            vm.status.address += 4;
+            return Ok(false);
+        }
+        Opcode::I32ConstI32LtSBreakWhenNotEqZero(value, index) => {
+            let break_item = vm.resource.vm_modules[vm.status.vm_module_index]
+                .bytecode
+                .break_items[index as usize]
+                .clone();
+
+            // 先让 pc 指向被融合的 `br_if 指令`，不跳转时再由 apply_control_result 移到下一条指令
+            vm.status.address += 2;
+            let control_result = ins_fused::i32_const_i32_lt_s_break_when_not_eq_zero(
+                vm,
+                value,
+                break_item.option_block_index,
+                &break_item.branch_target,
+            );
+            return apply_control_result(vm, control_result);
+        }
+    };
+
+    match sequence_result {
+        Ok(_) => {
+            vm.status.address += 1;
+            Ok(false)
+        }
+        Err(e) => Err(e),
+    }
+}
+
 pub fn exec_instruction(
     vm: &mut VM,
     instruction: &object::Instruction,
@@ -33,6 +164,7 @@ pub fn exec_instruction(
                 // 操作数（参数，parametric）指令
                 Instruction::Drop => ins_parametric::drop(vm),
                 Instruction::Select => ins_parametric::select(vm),
+                Instruction::SelectTyped(_) => ins_parametric::select(vm),
 
                 // 零值测试指令
                 Instruction::I32Eqz => ins_numeric_eqz::i32_eqz(vm),
@@ -169,15 +301,15 @@ pub fn exec_instruction(
                 Instruction::I64TruncF64S => ins_numeric_convert::i64_trunc_f64_s(vm),
                 Instruction::I64TruncF64U => ins_numeric_convert::i64_trunc_f64_u(vm),
 
-                Instruction::I32TruncSatF32S => todo!(),
-                Instruction::I32TruncSatF32U => todo!(),
-                Instruction::I32TruncSatF64S => todo!(),
-                Instruction::I32TruncSatF64U => todo!(),
+                Instruction::I32TruncSatF32S => ins_numeric_convert::i32_trunc_sat_f32_s(vm),
+                Instruction::I32TruncSatF32U => ins_numeric_convert::i32_trunc_sat_f32_u(vm),
+                Instruction::I32TruncSatF64S => ins_numeric_convert::i32_trunc_sat_f64_s(vm),
+                Instruction::I32TruncSatF64U => ins_numeric_convert::i32_trunc_sat_f64_u(vm),
 
-                Instruction::I64TruncSatF32S => todo!(),
-                Instruction::I64TruncSatF32U => todo!(),
-                Instruction::I64TruncSatF64S => todo!(),
-                Instruction::I64TruncSatF64U => todo!(),
+                Instruction::I64TruncSatF32S => ins_numeric_convert::i64_trunc_sat_f32_s(vm),
+                Instruction::I64TruncSatF32U => ins_numeric_convert::i64_trunc_sat_f32_u(vm),
+                Instruction::I64TruncSatF64S => ins_numeric_convert::i64_trunc_sat_f64_s(vm),
+                Instruction::I64TruncSatF64U => ins_numeric_convert::i64_trunc_sat_f64_u(vm),
 
                 Instruction::F32ConvertI32S => ins_numeric_convert::f32_convert_i32_s(vm),
                 Instruction::F32ConvertI32U => ins_numeric_convert::f32_convert_i32_u(vm),
@@ -211,50 +343,651 @@ pub fn exec_instruction(
                     ins_memory::memory_grow(vm, *memory_block_index)
                 }
 
-                Instruction::MemoryInit(data_index, memory_block_index) => todo!(),
-                Instruction::DataDrop(data_index) => todo!(),
-                Instruction::MemoryCopy(source_memory_block_index, dest_memory_block_index) => {
-                    todo!()
+                Instruction::MemoryInit(data_index, memory_block_index) => {
+                    ins_memory::memory_init(vm, *data_index, *memory_block_index)
+                }
+                Instruction::DataDrop(data_index) => ins_memory::data_drop(vm, *data_index),
+                Instruction::MemoryCopy(dest_memory_block_index, source_memory_block_index) => {
+                    ins_memory::memory_copy(
+                        vm,
+                        *dest_memory_block_index,
+                        *source_memory_block_index,
+                    )
+                }
+                Instruction::MemoryFill(memory_block_index) => {
+                    ins_memory::memory_fill(vm, *memory_block_index)
                 }
-                Instruction::MemoryFill(memory_block_index) => todo!(),
 
-                Instruction::I32Load(memory_args) => ins_memory::i32_load(vm, memory_args),
-                Instruction::I32Load16S(memory_args) => ins_memory::i32_load16_s(vm, memory_args),
-                Instruction::I32Load16U(memory_args) => ins_memory::i32_load16_u(vm, memory_args),
-                Instruction::I32Load8S(memory_args) => ins_memory::i32_load8_s(vm, memory_args),
-                Instruction::I32Load8U(memory_args) => ins_memory::i32_load8_u(vm, memory_args),
+                Instruction::I32Load(memory_args) => {
+                    let memory_access = ins_memory::resolve_memory_access(vm, memory_args);
+                    ins_memory::i32_load(vm, &memory_access)
+                }
+                Instruction::I32Load16S(memory_args) => {
+                    let memory_access = ins_memory::resolve_memory_access(vm, memory_args);
+                    ins_memory::i32_load16_s(vm, &memory_access)
+                }
+                Instruction::I32Load16U(memory_args) => {
+                    let memory_access = ins_memory::resolve_memory_access(vm, memory_args);
+                    ins_memory::i32_load16_u(vm, &memory_access)
+                }
+                Instruction::I32Load8S(memory_args) => {
+                    let memory_access = ins_memory::resolve_memory_access(vm, memory_args);
+                    ins_memory::i32_load8_s(vm, &memory_access)
+                }
+                Instruction::I32Load8U(memory_args) => {
+                    let memory_access = ins_memory::resolve_memory_access(vm, memory_args);
+                    ins_memory::i32_load8_u(vm, &memory_access)
+                }
 
-                Instruction::I64Load(memory_args) => ins_memory::i64_load(vm, memory_args),
-                Instruction::I64Load32S(memory_args) => ins_memory::i64_load32_s(vm, memory_args),
-                Instruction::I64Load32U(memory_args) => ins_memory::i64_load32_u(vm, memory_args),
-                Instruction::I64Load16S(memory_args) => ins_memory::i64_load16_s(vm, memory_args),
-                Instruction::I64Load16U(memory_args) => ins_memory::i64_load16_u(vm, memory_args),
-                Instruction::I64Load8S(memory_args) => ins_memory::i64_load8_s(vm, memory_args),
-                Instruction::I64Load8U(memory_args) => ins_memory::i64_load8_u(vm, memory_args),
+                Instruction::I64Load(memory_args) => {
+                    let memory_access = ins_memory::resolve_memory_access(vm, memory_args);
+                    ins_memory::i64_load(vm, &memory_access)
+                }
+                Instruction::I64Load32S(memory_args) => {
+                    let memory_access = ins_memory::resolve_memory_access(vm, memory_args);
+                    ins_memory::i64_load32_s(vm, &memory_access)
+                }
+                Instruction::I64Load32U(memory_args) => {
+                    let memory_access = ins_memory::resolve_memory_access(vm, memory_args);
+                    ins_memory::i64_load32_u(vm, &memory_access)
+                }
+                Instruction::I64Load16S(memory_args) => {
+                    let memory_access = ins_memory::resolve_memory_access(vm, memory_args);
+                    ins_memory::i64_load16_s(vm, &memory_access)
+                }
+                Instruction::I64Load16U(memory_args) => {
+                    let memory_access = ins_memory::resolve_memory_access(vm, memory_args);
+                    ins_memory::i64_load16_u(vm, &memory_access)
+                }
+                Instruction::I64Load8S(memory_args) => {
+                    let memory_access = ins_memory::resolve_memory_access(vm, memory_args);
+                    ins_memory::i64_load8_s(vm, &memory_access)
+                }
+                Instruction::I64Load8U(memory_args) => {
+                    let memory_access = ins_memory::resolve_memory_access(vm, memory_args);
+                    ins_memory::i64_load8_u(vm, &memory_access)
+                }
 
-                Instruction::F32Load(memory_args) => ins_memory::f32_load(vm, memory_args),
-                Instruction::F64Load(memory_args) => ins_memory::f64_load(vm, memory_args),
+                Instruction::F32Load(memory_args) => {
+                    let memory_access = ins_memory::resolve_memory_access(vm, memory_args);
+                    ins_memory::f32_load(vm, &memory_access)
+                }
+                Instruction::F64Load(memory_args) => {
+                    let memory_access = ins_memory::resolve_memory_access(vm, memory_args);
+                    ins_memory::f64_load(vm, &memory_access)
+                }
 
-                Instruction::I32Store(memory_args) => ins_memory::i32_store(vm, memory_args),
-                Instruction::I32Store16(memory_args) => ins_memory::i32_store_16(vm, memory_args),
-                Instruction::I32Store8(memory_args) => ins_memory::i32_store_8(vm, memory_args),
-                Instruction::I64Store(memory_args) => ins_memory::i64_store(vm, memory_args),
-                Instruction::I64Store32(memory_args) => ins_memory::i64_store_32(vm, memory_args),
-                Instruction::I64Store16(memory_args) => ins_memory::i64_store_16(vm, memory_args),
-                Instruction::I64Store8(memory_args) => ins_memory::i64_store_8(vm, memory_args),
+                Instruction::I32Store(memory_args) => {
+                    let memory_access = ins_memory::resolve_memory_access(vm, memory_args);
+                    ins_memory::i32_store(vm, &memory_access)
+                }
+                Instruction::I32Store16(memory_args) => {
+                    let memory_access = ins_memory::resolve_memory_access(vm, memory_args);
+                    ins_memory::i32_store_16(vm, &memory_access)
+                }
+                Instruction::I32Store8(memory_args) => {
+                    let memory_access = ins_memory::resolve_memory_access(vm, memory_args);
+                    ins_memory::i32_store_8(vm, &memory_access)
+                }
+                Instruction::I64Store(memory_args) => {
+                    let memory_access = ins_memory::resolve_memory_access(vm, memory_args);
+                    ins_memory::i64_store(vm, &memory_access)
+                }
+                Instruction::I64Store32(memory_args) => {
+                    let memory_access = ins_memory::resolve_memory_access(vm, memory_args);
+                    ins_memory::i64_store_32(vm, &memory_access)
+                }
+                Instruction::I64Store16(memory_args) => {
+                    let memory_access = ins_memory::resolve_memory_access(vm, memory_args);
+                    ins_memory::i64_store_16(vm, &memory_access)
+                }
+                Instruction::I64Store8(memory_args) => {
+                    let memory_access = ins_memory::resolve_memory_access(vm, memory_args);
+                    ins_memory::i64_store_8(vm, &memory_access)
+                }
 
-                Instruction::F32Store(memory_args) => ins_memory::f32_store(vm, memory_args),
-                Instruction::F64Store(memory_args) => ins_memory::f64_store(vm, memory_args),
+                Instruction::F32Store(memory_args) => {
+                    let memory_access = ins_memory::resolve_memory_access(vm, memory_args);
+                    ins_memory::f32_store(vm, &memory_access)
+                }
+                Instruction::F64Store(memory_args) => {
+                    let memory_access = ins_memory::resolve_memory_access(vm, memory_args);
+                    ins_memory::f64_store(vm, &memory_access)
+                }
 
                 // 表指令
-                Instruction::TableGet(table_index) => todo!(),
-                Instruction::TableSet(table_index) => todo!(),
-                Instruction::TableInit(element_index, table_index) => todo!(),
-                Instruction::ElementDrop(element_index) => todo!(),
-                Instruction::TableCopy(source_table_index, dest_table_index) => todo!(),
-                Instruction::TableGrow(table_index) => todo!(),
-                Instruction::TableSize(table_index) => todo!(),
-                Instruction::TableFill(table_index) => todo!(),
+                Instruction::TableGet(table_index) => ins_table::table_get(vm, *table_index),
+                Instruction::TableSet(table_index) => ins_table::table_set(vm, *table_index),
+                Instruction::TableInit(element_index, table_index) => {
+                    ins_table::table_init(vm, *element_index, *table_index)
+                }
+                Instruction::ElementDrop(element_index) => ins_table::elem_drop(vm, *element_index),
+                Instruction::TableCopy(dest_table_index, source_table_index) => {
+                    ins_table::table_copy(vm, *dest_table_index, *source_table_index)
+                }
+                Instruction::TableGrow(table_index) => ins_table::table_grow(vm, *table_index),
+                Instruction::TableSize(table_index) => ins_table::table_size(vm, *table_index),
+                Instruction::TableFill(table_index) => ins_table::table_fill(vm, *table_index),
+
+                // 引用指令
+                Instruction::RefNull(ref_type) => ins_reference::ref_null(vm, *ref_type),
+                Instruction::RefIsNull => ins_reference::ref_is_null(vm),
+                Instruction::RefFunc(function_index) => {
+                    ins_reference::ref_func(vm, *function_index)
+                }
+
+                // 固定宽度 SIMD 指令
+                Instruction::V128Load(memory_args) => ins_simd::v128_load(vm, memory_args),
+                Instruction::V128Load8x8S(memory_args) => ins_simd::v128_load8x8_s(vm, memory_args),
+                Instruction::V128Load8x8U(memory_args) => ins_simd::v128_load8x8_u(vm, memory_args),
+                Instruction::V128Load16x4S(memory_args) => {
+                    ins_simd::v128_load16x4_s(vm, memory_args)
+                }
+                Instruction::V128Load16x4U(memory_args) => {
+                    ins_simd::v128_load16x4_u(vm, memory_args)
+                }
+                Instruction::V128Load32x2S(memory_args) => {
+                    ins_simd::v128_load32x2_s(vm, memory_args)
+                }
+                Instruction::V128Load32x2U(memory_args) => {
+                    ins_simd::v128_load32x2_u(vm, memory_args)
+                }
+                Instruction::V128Load8Splat(memory_args) => {
+                    ins_simd::v128_load8_splat(vm, memory_args)
+                }
+                Instruction::V128Load16Splat(memory_args) => {
+                    ins_simd::v128_load16_splat(vm, memory_args)
+                }
+                Instruction::V128Load32Splat(memory_args) => {
+                    ins_simd::v128_load32_splat(vm, memory_args)
+                }
+                Instruction::V128Load64Splat(memory_args) => {
+                    ins_simd::v128_load64_splat(vm, memory_args)
+                }
+                Instruction::V128Store(memory_args) => ins_simd::v128_store(vm, memory_args),
+                Instruction::V128Const(value) => ins_const::v128_const(vm, *value),
+                Instruction::I8x16Shuffle(lane_indices) => {
+                    ins_simd::i8x16_shuffle(vm, lane_indices)
+                }
+                Instruction::I8x16Swizzle => ins_simd::i8x16_swizzle(vm),
+                Instruction::I8x16Splat => ins_simd::i8x16_splat(vm),
+                Instruction::I16x8Splat => ins_simd::i16x8_splat(vm),
+                Instruction::I32x4Splat => ins_simd::i32x4_splat(vm),
+                Instruction::I64x2Splat => ins_simd::i64x2_splat(vm),
+                Instruction::F32x4Splat => ins_simd::f32x4_splat(vm),
+                Instruction::F64x2Splat => ins_simd::f64x2_splat(vm),
+                Instruction::I8x16ExtractLaneS(lane_index) => {
+                    ins_simd::i8x16_extract_lane_s(vm, *lane_index)
+                }
+                Instruction::I8x16ExtractLaneU(lane_index) => {
+                    ins_simd::i8x16_extract_lane_u(vm, *lane_index)
+                }
+                Instruction::I8x16ReplaceLane(lane_index) => {
+                    ins_simd::i8x16_replace_lane(vm, *lane_index)
+                }
+                Instruction::I16x8ExtractLaneS(lane_index) => {
+                    ins_simd::i16x8_extract_lane_s(vm, *lane_index)
+                }
+                Instruction::I16x8ExtractLaneU(lane_index) => {
+                    ins_simd::i16x8_extract_lane_u(vm, *lane_index)
+                }
+                Instruction::I16x8ReplaceLane(lane_index) => {
+                    ins_simd::i16x8_replace_lane(vm, *lane_index)
+                }
+                Instruction::I32x4ExtractLane(lane_index) => {
+                    ins_simd::i32x4_extract_lane(vm, *lane_index)
+                }
+                Instruction::I32x4ReplaceLane(lane_index) => {
+                    ins_simd::i32x4_replace_lane(vm, *lane_index)
+                }
+                Instruction::I64x2ExtractLane(lane_index) => {
+                    ins_simd::i64x2_extract_lane(vm, *lane_index)
+                }
+                Instruction::I64x2ReplaceLane(lane_index) => {
+                    ins_simd::i64x2_replace_lane(vm, *lane_index)
+                }
+                Instruction::F32x4ExtractLane(lane_index) => {
+                    ins_simd::f32x4_extract_lane(vm, *lane_index)
+                }
+                Instruction::F32x4ReplaceLane(lane_index) => {
+                    ins_simd::f32x4_replace_lane(vm, *lane_index)
+                }
+                Instruction::F64x2ExtractLane(lane_index) => {
+                    ins_simd::f64x2_extract_lane(vm, *lane_index)
+                }
+                Instruction::F64x2ReplaceLane(lane_index) => {
+                    ins_simd::f64x2_replace_lane(vm, *lane_index)
+                }
+                Instruction::I8x16Eq => ins_simd::i8x16_eq(vm),
+                Instruction::I8x16Ne => ins_simd::i8x16_ne(vm),
+                Instruction::I8x16LtS => ins_simd::i8x16_lt_s(vm),
+                Instruction::I8x16LtU => ins_simd::i8x16_lt_u(vm),
+                Instruction::I8x16GtS => ins_simd::i8x16_gt_s(vm),
+                Instruction::I8x16GtU => ins_simd::i8x16_gt_u(vm),
+                Instruction::I8x16LeS => ins_simd::i8x16_le_s(vm),
+                Instruction::I8x16LeU => ins_simd::i8x16_le_u(vm),
+                Instruction::I8x16GeS => ins_simd::i8x16_ge_s(vm),
+                Instruction::I8x16GeU => ins_simd::i8x16_ge_u(vm),
+                Instruction::I16x8Eq => ins_simd::i16x8_eq(vm),
+                Instruction::I16x8Ne => ins_simd::i16x8_ne(vm),
+                Instruction::I16x8LtS => ins_simd::i16x8_lt_s(vm),
+                Instruction::I16x8LtU => ins_simd::i16x8_lt_u(vm),
+                Instruction::I16x8GtS => ins_simd::i16x8_gt_s(vm),
+                Instruction::I16x8GtU => ins_simd::i16x8_gt_u(vm),
+                Instruction::I16x8LeS => ins_simd::i16x8_le_s(vm),
+                Instruction::I16x8LeU => ins_simd::i16x8_le_u(vm),
+                Instruction::I16x8GeS => ins_simd::i16x8_ge_s(vm),
+                Instruction::I16x8GeU => ins_simd::i16x8_ge_u(vm),
+                Instruction::I32x4Eq => ins_simd::i32x4_eq(vm),
+                Instruction::I32x4Ne => ins_simd::i32x4_ne(vm),
+                Instruction::I32x4LtS => ins_simd::i32x4_lt_s(vm),
+                Instruction::I32x4LtU => ins_simd::i32x4_lt_u(vm),
+                Instruction::I32x4GtS => ins_simd::i32x4_gt_s(vm),
+                Instruction::I32x4GtU => ins_simd::i32x4_gt_u(vm),
+                Instruction::I32x4LeS => ins_simd::i32x4_le_s(vm),
+                Instruction::I32x4LeU => ins_simd::i32x4_le_u(vm),
+                Instruction::I32x4GeS => ins_simd::i32x4_ge_s(vm),
+                Instruction::I32x4GeU => ins_simd::i32x4_ge_u(vm),
+                Instruction::F32x4Eq => ins_simd::f32x4_eq(vm),
+                Instruction::F32x4Ne => ins_simd::f32x4_ne(vm),
+                Instruction::F32x4Lt => ins_simd::f32x4_lt(vm),
+                Instruction::F32x4Gt => ins_simd::f32x4_gt(vm),
+                Instruction::F32x4Le => ins_simd::f32x4_le(vm),
+                Instruction::F32x4Ge => ins_simd::f32x4_ge(vm),
+                Instruction::F64x2Eq => ins_simd::f64x2_eq(vm),
+                Instruction::F64x2Ne => ins_simd::f64x2_ne(vm),
+                Instruction::F64x2Lt => ins_simd::f64x2_lt(vm),
+                Instruction::F64x2Gt => ins_simd::f64x2_gt(vm),
+                Instruction::F64x2Le => ins_simd::f64x2_le(vm),
+                Instruction::F64x2Ge => ins_simd::f64x2_ge(vm),
+                Instruction::V128Not => ins_simd::v128_not(vm),
+                Instruction::V128And => ins_simd::v128_and(vm),
+                Instruction::V128Andnot => ins_simd::v128_andnot(vm),
+                Instruction::V128Or => ins_simd::v128_or(vm),
+                Instruction::V128Xor => ins_simd::v128_xor(vm),
+                Instruction::V128Bitselect => ins_simd::v128_bitselect(vm),
+                Instruction::V128AnyTrue => ins_simd::v128_any_true(vm),
+                Instruction::V128Load8Lane(memory_args, lane_index) => {
+                    ins_simd::v128_load8_lane(vm, memory_args, *lane_index)
+                }
+                Instruction::V128Load16Lane(memory_args, lane_index) => {
+                    ins_simd::v128_load16_lane(vm, memory_args, *lane_index)
+                }
+                Instruction::V128Load32Lane(memory_args, lane_index) => {
+                    ins_simd::v128_load32_lane(vm, memory_args, *lane_index)
+                }
+                Instruction::V128Load64Lane(memory_args, lane_index) => {
+                    ins_simd::v128_load64_lane(vm, memory_args, *lane_index)
+                }
+                Instruction::V128Store8Lane(memory_args, lane_index) => {
+                    ins_simd::v128_store8_lane(vm, memory_args, *lane_index)
+                }
+                Instruction::V128Store16Lane(memory_args, lane_index) => {
+                    ins_simd::v128_store16_lane(vm, memory_args, *lane_index)
+                }
+                Instruction::V128Store32Lane(memory_args, lane_index) => {
+                    ins_simd::v128_store32_lane(vm, memory_args, *lane_index)
+                }
+                Instruction::V128Store64Lane(memory_args, lane_index) => {
+                    ins_simd::v128_store64_lane(vm, memory_args, *lane_index)
+                }
+                Instruction::V128Load32Zero(memory_args) => {
+                    ins_simd::v128_load32_zero(vm, memory_args)
+                }
+                Instruction::V128Load64Zero(memory_args) => {
+                    ins_simd::v128_load64_zero(vm, memory_args)
+                }
+                Instruction::F32x4DemoteF64x2Zero => ins_simd::f32x4_demote_f64x2_zero(vm),
+                Instruction::F64x2PromoteLowF32x4 => ins_simd::f64x2_promote_low_f32x4(vm),
+                Instruction::I8x16Abs => ins_simd::i8x16_abs(vm),
+                Instruction::I8x16Neg => ins_simd::i8x16_neg(vm),
+                Instruction::I8x16Popcnt => ins_simd::i8x16_popcnt(vm),
+                Instruction::I8x16AllTrue => ins_simd::i8x16_all_true(vm),
+                Instruction::I8x16Bitmask => ins_simd::i8x16_bitmask(vm),
+                Instruction::I8x16NarrowI16x8S => ins_simd::i8x16_narrow_i16x8_s(vm),
+                Instruction::I8x16NarrowI16x8U => ins_simd::i8x16_narrow_i16x8_u(vm),
+                Instruction::F32x4Ceil => ins_simd::f32x4_ceil(vm),
+                Instruction::F32x4Floor => ins_simd::f32x4_floor(vm),
+                Instruction::F32x4Trunc => ins_simd::f32x4_trunc(vm),
+                Instruction::F32x4Nearest => ins_simd::f32x4_nearest(vm),
+                Instruction::I8x16Shl => ins_simd::i8x16_shl(vm),
+                Instruction::I8x16ShrS => ins_simd::i8x16_shr_s(vm),
+                Instruction::I8x16ShrU => ins_simd::i8x16_shr_u(vm),
+                Instruction::I8x16Add => ins_simd::i8x16_add(vm),
+                Instruction::I8x16AddSatS => ins_simd::i8x16_add_sat_s(vm),
+                Instruction::I8x16AddSatU => ins_simd::i8x16_add_sat_u(vm),
+                Instruction::I8x16Sub => ins_simd::i8x16_sub(vm),
+                Instruction::I8x16SubSatS => ins_simd::i8x16_sub_sat_s(vm),
+                Instruction::I8x16SubSatU => ins_simd::i8x16_sub_sat_u(vm),
+                Instruction::F64x2Ceil => ins_simd::f64x2_ceil(vm),
+                Instruction::F64x2Floor => ins_simd::f64x2_floor(vm),
+                Instruction::I8x16MinS => ins_simd::i8x16_min_s(vm),
+                Instruction::I8x16MinU => ins_simd::i8x16_min_u(vm),
+                Instruction::I8x16MaxS => ins_simd::i8x16_max_s(vm),
+                Instruction::I8x16MaxU => ins_simd::i8x16_max_u(vm),
+                Instruction::F64x2Trunc => ins_simd::f64x2_trunc(vm),
+                Instruction::I8x16AvgrU => ins_simd::i8x16_avgr_u(vm),
+                Instruction::I16x8ExtaddPairwiseI8x16S => {
+                    ins_simd::i16x8_extadd_pairwise_i8x16_s(vm)
+                }
+                Instruction::I16x8ExtaddPairwiseI8x16U => {
+                    ins_simd::i16x8_extadd_pairwise_i8x16_u(vm)
+                }
+                Instruction::I32x4ExtaddPairwiseI16x8S => {
+                    ins_simd::i32x4_extadd_pairwise_i16x8_s(vm)
+                }
+                Instruction::I32x4ExtaddPairwiseI16x8U => {
+                    ins_simd::i32x4_extadd_pairwise_i16x8_u(vm)
+                }
+                Instruction::I16x8Abs => ins_simd::i16x8_abs(vm),
+                Instruction::I16x8Neg => ins_simd::i16x8_neg(vm),
+                Instruction::I16x8Q15mulrSatS => ins_simd::i16x8_q15mulr_sat_s(vm),
+                Instruction::I16x8AllTrue => ins_simd::i16x8_all_true(vm),
+                Instruction::I16x8Bitmask => ins_simd::i16x8_bitmask(vm),
+                Instruction::I16x8NarrowI32x4S => ins_simd::i16x8_narrow_i32x4_s(vm),
+                Instruction::I16x8NarrowI32x4U => ins_simd::i16x8_narrow_i32x4_u(vm),
+                Instruction::I16x8ExtendLowI8x16S => ins_simd::i16x8_extend_low_i8x16_s(vm),
+                Instruction::I16x8ExtendHighI8x16S => ins_simd::i16x8_extend_high_i8x16_s(vm),
+                Instruction::I16x8ExtendLowI8x16U => ins_simd::i16x8_extend_low_i8x16_u(vm),
+                Instruction::I16x8ExtendHighI8x16U => ins_simd::i16x8_extend_high_i8x16_u(vm),
+                Instruction::I16x8Shl => ins_simd::i16x8_shl(vm),
+                Instruction::I16x8ShrS => ins_simd::i16x8_shr_s(vm),
+                Instruction::I16x8ShrU => ins_simd::i16x8_shr_u(vm),
+                Instruction::I16x8Add => ins_simd::i16x8_add(vm),
+                Instruction::I16x8AddSatS => ins_simd::i16x8_add_sat_s(vm),
+                Instruction::I16x8AddSatU => ins_simd::i16x8_add_sat_u(vm),
+                Instruction::I16x8Sub => ins_simd::i16x8_sub(vm),
+                Instruction::I16x8SubSatS => ins_simd::i16x8_sub_sat_s(vm),
+                Instruction::I16x8SubSatU => ins_simd::i16x8_sub_sat_u(vm),
+                Instruction::F64x2Nearest => ins_simd::f64x2_nearest(vm),
+                Instruction::I16x8Mul => ins_simd::i16x8_mul(vm),
+                Instruction::I16x8MinS => ins_simd::i16x8_min_s(vm),
+                Instruction::I16x8MinU => ins_simd::i16x8_min_u(vm),
+                Instruction::I16x8MaxS => ins_simd::i16x8_max_s(vm),
+                Instruction::I16x8MaxU => ins_simd::i16x8_max_u(vm),
+                Instruction::I16x8AvgrU => ins_simd::i16x8_avgr_u(vm),
+                Instruction::I16x8ExtmulLowI8x16S => ins_simd::i16x8_extmul_low_i8x16_s(vm),
+                Instruction::I16x8ExtmulHighI8x16S => ins_simd::i16x8_extmul_high_i8x16_s(vm),
+                Instruction::I16x8ExtmulLowI8x16U => ins_simd::i16x8_extmul_low_i8x16_u(vm),
+                Instruction::I16x8ExtmulHighI8x16U => ins_simd::i16x8_extmul_high_i8x16_u(vm),
+                Instruction::I32x4Abs => ins_simd::i32x4_abs(vm),
+                Instruction::I32x4Neg => ins_simd::i32x4_neg(vm),
+                Instruction::I32x4AllTrue => ins_simd::i32x4_all_true(vm),
+                Instruction::I32x4Bitmask => ins_simd::i32x4_bitmask(vm),
+                Instruction::I32x4ExtendLowI16x8S => ins_simd::i32x4_extend_low_i16x8_s(vm),
+                Instruction::I32x4ExtendHighI16x8S => ins_simd::i32x4_extend_high_i16x8_s(vm),
+                Instruction::I32x4ExtendLowI16x8U => ins_simd::i32x4_extend_low_i16x8_u(vm),
+                Instruction::I32x4ExtendHighI16x8U => ins_simd::i32x4_extend_high_i16x8_u(vm),
+                Instruction::I32x4Shl => ins_simd::i32x4_shl(vm),
+                Instruction::I32x4ShrS => ins_simd::i32x4_shr_s(vm),
+                Instruction::I32x4ShrU => ins_simd::i32x4_shr_u(vm),
+                Instruction::I32x4Add => ins_simd::i32x4_add(vm),
+                Instruction::I32x4Sub => ins_simd::i32x4_sub(vm),
+                Instruction::I32x4Mul => ins_simd::i32x4_mul(vm),
+                Instruction::I32x4MinS => ins_simd::i32x4_min_s(vm),
+                Instruction::I32x4MinU => ins_simd::i32x4_min_u(vm),
+                Instruction::I32x4MaxS => ins_simd::i32x4_max_s(vm),
+                Instruction::I32x4MaxU => ins_simd::i32x4_max_u(vm),
+                Instruction::I32x4DotI16x8S => ins_simd::i32x4_dot_i16x8_s(vm),
+                Instruction::I32x4ExtmulLowI16x8S => ins_simd::i32x4_extmul_low_i16x8_s(vm),
+                Instruction::I32x4ExtmulHighI16x8S => ins_simd::i32x4_extmul_high_i16x8_s(vm),
+                Instruction::I32x4ExtmulLowI16x8U => ins_simd::i32x4_extmul_low_i16x8_u(vm),
+                Instruction::I32x4ExtmulHighI16x8U => ins_simd::i32x4_extmul_high_i16x8_u(vm),
+                Instruction::I64x2Abs => ins_simd::i64x2_abs(vm),
+                Instruction::I64x2Neg => ins_simd::i64x2_neg(vm),
+                Instruction::I64x2AllTrue => ins_simd::i64x2_all_true(vm),
+                Instruction::I64x2Bitmask => ins_simd::i64x2_bitmask(vm),
+                Instruction::I64x2ExtendLowI32x4S => ins_simd::i64x2_extend_low_i32x4_s(vm),
+                Instruction::I64x2ExtendHighI32x4S => ins_simd::i64x2_extend_high_i32x4_s(vm),
+                Instruction::I64x2ExtendLowI32x4U => ins_simd::i64x2_extend_low_i32x4_u(vm),
+                Instruction::I64x2ExtendHighI32x4U => ins_simd::i64x2_extend_high_i32x4_u(vm),
+                Instruction::I64x2Shl => ins_simd::i64x2_shl(vm),
+                Instruction::I64x2ShrS => ins_simd::i64x2_shr_s(vm),
+                Instruction::I64x2ShrU => ins_simd::i64x2_shr_u(vm),
+                Instruction::I64x2Add => ins_simd::i64x2_add(vm),
+                Instruction::I64x2Sub => ins_simd::i64x2_sub(vm),
+                Instruction::I64x2Mul => ins_simd::i64x2_mul(vm),
+                Instruction::I64x2Eq => ins_simd::i64x2_eq(vm),
+                Instruction::I64x2Ne => ins_simd::i64x2_ne(vm),
+                Instruction::I64x2LtS => ins_simd::i64x2_lt_s(vm),
+                Instruction::I64x2GtS => ins_simd::i64x2_gt_s(vm),
+                Instruction::I64x2LeS => ins_simd::i64x2_le_s(vm),
+                Instruction::I64x2GeS => ins_simd::i64x2_ge_s(vm),
+                Instruction::I64x2ExtmulLowI32x4S => ins_simd::i64x2_extmul_low_i32x4_s(vm),
+                Instruction::I64x2ExtmulHighI32x4S => ins_simd::i64x2_extmul_high_i32x4_s(vm),
+                Instruction::I64x2ExtmulLowI32x4U => ins_simd::i64x2_extmul_low_i32x4_u(vm),
+                Instruction::I64x2ExtmulHighI32x4U => ins_simd::i64x2_extmul_high_i32x4_u(vm),
+                Instruction::F32x4Abs => ins_simd::f32x4_abs(vm),
+                Instruction::F32x4Neg => ins_simd::f32x4_neg(vm),
+                Instruction::F32x4Sqrt => ins_simd::f32x4_sqrt(vm),
+                Instruction::F32x4Add => ins_simd::f32x4_add(vm),
+                Instruction::F32x4Sub => ins_simd::f32x4_sub(vm),
+                Instruction::F32x4Mul => ins_simd::f32x4_mul(vm),
+                Instruction::F32x4Div => ins_simd::f32x4_div(vm),
+                Instruction::F32x4Min => ins_simd::f32x4_min(vm),
+                Instruction::F32x4Max => ins_simd::f32x4_max(vm),
+                Instruction::F32x4Pmin => ins_simd::f32x4_pmin(vm),
+                Instruction::F32x4Pmax => ins_simd::f32x4_pmax(vm),
+                Instruction::F64x2Abs => ins_simd::f64x2_abs(vm),
+                Instruction::F64x2Neg => ins_simd::f64x2_neg(vm),
+                Instruction::F64x2Sqrt => ins_simd::f64x2_sqrt(vm),
+                Instruction::F64x2Add => ins_simd::f64x2_add(vm),
+                Instruction::F64x2Sub => ins_simd::f64x2_sub(vm),
+                Instruction::F64x2Mul => ins_simd::f64x2_mul(vm),
+                Instruction::F64x2Div => ins_simd::f64x2_div(vm),
+                Instruction::F64x2Min => ins_simd::f64x2_min(vm),
+                Instruction::F64x2Max => ins_simd::f64x2_max(vm),
+                Instruction::F64x2Pmin => ins_simd::f64x2_pmin(vm),
+                Instruction::F64x2Pmax => ins_simd::f64x2_pmax(vm),
+                Instruction::I32x4TruncSatF32x4S => ins_simd::i32x4_trunc_sat_f32x4_s(vm),
+                Instruction::I32x4TruncSatF32x4U => ins_simd::i32x4_trunc_sat_f32x4_u(vm),
+                Instruction::F32x4ConvertI32x4S => ins_simd::f32x4_convert_i32x4_s(vm),
+                Instruction::F32x4ConvertI32x4U => ins_simd::f32x4_convert_i32x4_u(vm),
+                Instruction::I32x4TruncSatF64x2SZero => ins_simd::i32x4_trunc_sat_f64x2_s_zero(vm),
+                Instruction::I32x4TruncSatF64x2UZero => ins_simd::i32x4_trunc_sat_f64x2_u_zero(vm),
+                Instruction::F64x2ConvertLowI32x4S => ins_simd::f64x2_convert_low_i32x4_s(vm),
+                Instruction::F64x2ConvertLowI32x4U => ins_simd::f64x2_convert_low_i32x4_u(vm),
+
+                // 原子内存访问指令
+                Instruction::MemoryAtomicNotify(memory_args) => {
+                    ins_atomic::memory_atomic_notify(vm, memory_args)
+                }
+                Instruction::MemoryAtomicWait32(memory_args) => {
+                    ins_atomic::memory_atomic_wait32(vm, memory_args)
+                }
+                Instruction::MemoryAtomicWait64(memory_args) => {
+                    ins_atomic::memory_atomic_wait64(vm, memory_args)
+                }
+                Instruction::AtomicFence => ins_atomic::atomic_fence(vm),
+                Instruction::I32AtomicLoad(memory_args) => {
+                    ins_atomic::i32_atomic_load(vm, memory_args)
+                }
+                Instruction::I64AtomicLoad(memory_args) => {
+                    ins_atomic::i64_atomic_load(vm, memory_args)
+                }
+                Instruction::I32AtomicLoad8U(memory_args) => {
+                    ins_atomic::i32_atomic_load8_u(vm, memory_args)
+                }
+                Instruction::I32AtomicLoad16U(memory_args) => {
+                    ins_atomic::i32_atomic_load16_u(vm, memory_args)
+                }
+                Instruction::I64AtomicLoad8U(memory_args) => {
+                    ins_atomic::i64_atomic_load8_u(vm, memory_args)
+                }
+                Instruction::I64AtomicLoad16U(memory_args) => {
+                    ins_atomic::i64_atomic_load16_u(vm, memory_args)
+                }
+                Instruction::I64AtomicLoad32U(memory_args) => {
+                    ins_atomic::i64_atomic_load32_u(vm, memory_args)
+                }
+                Instruction::I32AtomicStore(memory_args) => {
+                    ins_atomic::i32_atomic_store(vm, memory_args)
+                }
+                Instruction::I64AtomicStore(memory_args) => {
+                    ins_atomic::i64_atomic_store(vm, memory_args)
+                }
+                Instruction::I32AtomicStore8(memory_args) => {
+                    ins_atomic::i32_atomic_store8(vm, memory_args)
+                }
+                Instruction::I32AtomicStore16(memory_args) => {
+                    ins_atomic::i32_atomic_store16(vm, memory_args)
+                }
+                Instruction::I64AtomicStore8(memory_args) => {
+                    ins_atomic::i64_atomic_store8(vm, memory_args)
+                }
+                Instruction::I64AtomicStore16(memory_args) => {
+                    ins_atomic::i64_atomic_store16(vm, memory_args)
+                }
+                Instruction::I64AtomicStore32(memory_args) => {
+                    ins_atomic::i64_atomic_store32(vm, memory_args)
+                }
+                Instruction::I32AtomicRmwAdd(memory_args) => {
+                    ins_atomic::i32_atomic_rmw_add(vm, memory_args)
+                }
+                Instruction::I64AtomicRmwAdd(memory_args) => {
+                    ins_atomic::i64_atomic_rmw_add(vm, memory_args)
+                }
+                Instruction::I32AtomicRmw8AddU(memory_args) => {
+                    ins_atomic::i32_atomic_rmw8_add_u(vm, memory_args)
+                }
+                Instruction::I32AtomicRmw16AddU(memory_args) => {
+                    ins_atomic::i32_atomic_rmw16_add_u(vm, memory_args)
+                }
+                Instruction::I64AtomicRmw8AddU(memory_args) => {
+                    ins_atomic::i64_atomic_rmw8_add_u(vm, memory_args)
+                }
+                Instruction::I64AtomicRmw16AddU(memory_args) => {
+                    ins_atomic::i64_atomic_rmw16_add_u(vm, memory_args)
+                }
+                Instruction::I64AtomicRmw32AddU(memory_args) => {
+                    ins_atomic::i64_atomic_rmw32_add_u(vm, memory_args)
+                }
+                Instruction::I32AtomicRmwSub(memory_args) => {
+                    ins_atomic::i32_atomic_rmw_sub(vm, memory_args)
+                }
+                Instruction::I64AtomicRmwSub(memory_args) => {
+                    ins_atomic::i64_atomic_rmw_sub(vm, memory_args)
+                }
+                Instruction::I32AtomicRmw8SubU(memory_args) => {
+                    ins_atomic::i32_atomic_rmw8_sub_u(vm, memory_args)
+                }
+                Instruction::I32AtomicRmw16SubU(memory_args) => {
+                    ins_atomic::i32_atomic_rmw16_sub_u(vm, memory_args)
+                }
+                Instruction::I64AtomicRmw8SubU(memory_args) => {
+                    ins_atomic::i64_atomic_rmw8_sub_u(vm, memory_args)
+                }
+                Instruction::I64AtomicRmw16SubU(memory_args) => {
+                    ins_atomic::i64_atomic_rmw16_sub_u(vm, memory_args)
+                }
+                Instruction::I64AtomicRmw32SubU(memory_args) => {
+                    ins_atomic::i64_atomic_rmw32_sub_u(vm, memory_args)
+                }
+                Instruction::I32AtomicRmwAnd(memory_args) => {
+                    ins_atomic::i32_atomic_rmw_and(vm, memory_args)
+                }
+                Instruction::I64AtomicRmwAnd(memory_args) => {
+                    ins_atomic::i64_atomic_rmw_and(vm, memory_args)
+                }
+                Instruction::I32AtomicRmw8AndU(memory_args) => {
+                    ins_atomic::i32_atomic_rmw8_and_u(vm, memory_args)
+                }
+                Instruction::I32AtomicRmw16AndU(memory_args) => {
+                    ins_atomic::i32_atomic_rmw16_and_u(vm, memory_args)
+                }
+                Instruction::I64AtomicRmw8AndU(memory_args) => {
+                    ins_atomic::i64_atomic_rmw8_and_u(vm, memory_args)
+                }
+                Instruction::I64AtomicRmw16AndU(memory_args) => {
+                    ins_atomic::i64_atomic_rmw16_and_u(vm, memory_args)
+                }
+                Instruction::I64AtomicRmw32AndU(memory_args) => {
+                    ins_atomic::i64_atomic_rmw32_and_u(vm, memory_args)
+                }
+                Instruction::I32AtomicRmwOr(memory_args) => {
+                    ins_atomic::i32_atomic_rmw_or(vm, memory_args)
+                }
+                Instruction::I64AtomicRmwOr(memory_args) => {
+                    ins_atomic::i64_atomic_rmw_or(vm, memory_args)
+                }
+                Instruction::I32AtomicRmw8OrU(memory_args) => {
+                    ins_atomic::i32_atomic_rmw8_or_u(vm, memory_args)
+                }
+                Instruction::I32AtomicRmw16OrU(memory_args) => {
+                    ins_atomic::i32_atomic_rmw16_or_u(vm, memory_args)
+                }
+                Instruction::I64AtomicRmw8OrU(memory_args) => {
+                    ins_atomic::i64_atomic_rmw8_or_u(vm, memory_args)
+                }
+                Instruction::I64AtomicRmw16OrU(memory_args) => {
+                    ins_atomic::i64_atomic_rmw16_or_u(vm, memory_args)
+                }
+                Instruction::I64AtomicRmw32OrU(memory_args) => {
+                    ins_atomic::i64_atomic_rmw32_or_u(vm, memory_args)
+                }
+                Instruction::I32AtomicRmwXor(memory_args) => {
+                    ins_atomic::i32_atomic_rmw_xor(vm, memory_args)
+                }
+                Instruction::I64AtomicRmwXor(memory_args) => {
+                    ins_atomic::i64_atomic_rmw_xor(vm, memory_args)
+                }
+                Instruction::I32AtomicRmw8XorU(memory_args) => {
+                    ins_atomic::i32_atomic_rmw8_xor_u(vm, memory_args)
+                }
+                Instruction::I32AtomicRmw16XorU(memory_args) => {
+                    ins_atomic::i32_atomic_rmw16_xor_u(vm, memory_args)
+                }
+                Instruction::I64AtomicRmw8XorU(memory_args) => {
+                    ins_atomic::i64_atomic_rmw8_xor_u(vm, memory_args)
+                }
+                Instruction::I64AtomicRmw16XorU(memory_args) => {
+                    ins_atomic::i64_atomic_rmw16_xor_u(vm, memory_args)
+                }
+                Instruction::I64AtomicRmw32XorU(memory_args) => {
+                    ins_atomic::i64_atomic_rmw32_xor_u(vm, memory_args)
+                }
+                Instruction::I32AtomicRmwXchg(memory_args) => {
+                    ins_atomic::i32_atomic_rmw_xchg(vm, memory_args)
+                }
+                Instruction::I64AtomicRmwXchg(memory_args) => {
+                    ins_atomic::i64_atomic_rmw_xchg(vm, memory_args)
+                }
+                Instruction::I32AtomicRmw8XchgU(memory_args) => {
+                    ins_atomic::i32_atomic_rmw8_xchg_u(vm, memory_args)
+                }
+                Instruction::I32AtomicRmw16XchgU(memory_args) => {
+                    ins_atomic::i32_atomic_rmw16_xchg_u(vm, memory_args)
+                }
+                Instruction::I64AtomicRmw8XchgU(memory_args) => {
+                    ins_atomic::i64_atomic_rmw8_xchg_u(vm, memory_args)
+                }
+                Instruction::I64AtomicRmw16XchgU(memory_args) => {
+                    ins_atomic::i64_atomic_rmw16_xchg_u(vm, memory_args)
+                }
+                Instruction::I64AtomicRmw32XchgU(memory_args) => {
+                    ins_atomic::i64_atomic_rmw32_xchg_u(vm, memory_args)
+                }
+                Instruction::I32AtomicRmwCmpxchg(memory_args) => {
+                    ins_atomic::i32_atomic_rmw_cmpxchg(vm, memory_args)
+                }
+                Instruction::I64AtomicRmwCmpxchg(memory_args) => {
+                    ins_atomic::i64_atomic_rmw_cmpxchg(vm, memory_args)
+                }
+                Instruction::I32AtomicRmw8CmpxchgU(memory_args) => {
+                    ins_atomic::i32_atomic_rmw8_cmpxchg_u(vm, memory_args)
+                }
+                Instruction::I32AtomicRmw16CmpxchgU(memory_args) => {
+                    ins_atomic::i32_atomic_rmw16_cmpxchg_u(vm, memory_args)
+                }
+                Instruction::I64AtomicRmw8CmpxchgU(memory_args) => {
+                    ins_atomic::i64_atomic_rmw8_cmpxchg_u(vm, memory_args)
+                }
+                Instruction::I64AtomicRmw16CmpxchgU(memory_args) => {
+                    ins_atomic::i64_atomic_rmw16_cmpxchg_u(vm, memory_args)
+                }
+                Instruction::I64AtomicRmw32CmpxchgU(memory_args) => {
+                    ins_atomic::i64_atomic_rmw32_cmpxchg_u(vm, memory_args)
+                }
 
                 // 其他指令已经被替换成 Instruction::Control，所以
                 // 程序不应该来到这个分支
@@ -307,6 +1040,34 @@ pub fn exec_instruction(
                     type_index,
                     table_index,
                 } => ins_function::call_indirect(vm, *type_index, *table_index),
+                Control::ReturnCall {
+                    vm_module_index,
+                    type_index,
+                    function_index,
+                    internal_function_index,
+                    address,
+                } => ins_function::return_call(
+                    vm,
+                    *vm_module_index,
+                    *type_index,
+                    *function_index,
+                    *internal_function_index,
+                    *address,
+                ),
+                Control::ReturnCallNative {
+                    native_module_index,
+                    type_index,
+                    function_index,
+                } => ins_function::return_call_native(
+                    vm,
+                    *native_module_index,
+                    *type_index,
+                    *function_index,
+                ),
+                Control::ReturnCallIndirect {
+                    type_index,
+                    table_index,
+                } => ins_function::return_call_indirect(vm, *type_index, *table_index),
 
                 // 流程结构控制指令
                 Control::Block {
@@ -326,33 +1087,26 @@ pub fn exec_instruction(
                     *option_alternate_address,
                     *end_address,
                 ),
+                Control::TryTable {
+                    block_type,
+                    block_index,
+                    end_address,
+                    catch_items: _,
+                } => ins_exception::try_table(vm, block_type, *block_index, *end_address),
                 Control::JumpWithinBlock(address) => ins_block::jump_within_block(vm, *address),
 
                 Control::Break {
                     option_block_index,
-                    relative_depth,
-                    address,
-                } => ins_block::process_break(vm, *option_block_index, *relative_depth, *address),
+                    branch_target,
+                } => ins_block::process_break(vm, *option_block_index, branch_target),
                 Control::BreakWhenNotEqZero {
                     option_block_index,
-                    relative_depth,
-                    address,
+                    branch_target,
                 } => ins_block::process_break_when_not_eq_zero(
                     vm,
                     *option_block_index,
-                    *relative_depth,
-                    *address,
+                    branch_target,
                 ),
-                Control::Recur {
-                    block_index,
-                    relative_depth,
-                    address,
-                } => ins_block::recur(vm, *block_index, *relative_depth, *address),
-                Control::RecurWhenNotEqZero {
-                    block_index,
-                    relative_depth,
-                    address,
-                } => ins_block::recur_when_not_eq_zero(vm, *block_index, *relative_depth, *address),
                 Control::Branch {
                     option_block_index,
                     branch_targets,
@@ -363,69 +1117,70 @@ pub fn exec_instruction(
                     branch_targets,
                     default_branch_target,
                 ),
+
+                // 异常处理指令
+                Control::Throw(tag_index) => ins_exception::throw(vm, *tag_index),
+                Control::ThrowRef => ins_exception::throw_ref(vm),
             };
 
-            match control_result {
-                Ok(ControlResult::ProgramEnd) => Ok(true),
-                Ok(ControlResult::Sequence) => {
-                    // 更新虚拟机的 pc 值
-                    let status = &mut vm.status;
-                    status.address += 1;
+            apply_control_result(vm, control_result)
+        }
+    }
+}
 
-                    Ok(false)
-                }
-                Ok(ControlResult::PushStackFrame {
-                    is_call_frame: _,
-                    vm_module_index,
-                    function_index,
-                    frame_type,
-                    address,
-                }) => {
-                    // 更新虚拟机的 pc 值
-                    let status = &mut vm.status;
-                    status.vm_module_index = vm_module_index;
-                    status.function_index = function_index;
-                    status.frame_type = frame_type;
-                    status.address = address;
+/// 根据控制指令的执行结果更新虚拟机的 pc 值
+///
+/// 当程序（即第一个被调用的函数）的最后一条指令执行之后，函数返回 true，否则返回 false。
+fn apply_control_result(
+    vm: &mut VM,
+    control_result: Result<ControlResult, EngineError>,
+) -> Result<bool, EngineError> {
+    match control_result {
+        Ok(ControlResult::ProgramEnd) => Ok(true),
+        Ok(ControlResult::Sequence) => {
+            // 更新虚拟机的 pc 值
+            let status = &mut vm.status;
+            status.address += 1;
 
-                    Ok(false)
-                }
-                Ok(ControlResult::PopStackFrame {
-                    is_call_frame: _,
-                    vm_module_index,
-                    function_index,
-                    frame_type,
-                    address,
-                }) => {
-                    // 更新虚拟机的 pc 值
-                    let status = &mut vm.status;
-                    status.vm_module_index = vm_module_index;
-                    status.function_index = function_index;
-                    status.frame_type = frame_type;
-                    status.address = address;
+            Ok(false)
+        }
+        Ok(ControlResult::PushStackFrame {
+            vm_module_index,
+            function_index,
+            frame_type,
+            address,
+        }) => {
+            // 更新虚拟机的 pc 值
+            let status = &mut vm.status;
+            status.vm_module_index = vm_module_index;
+            status.function_index = function_index;
+            status.frame_type = frame_type;
+            status.address = address;
 
-                    Ok(false)
-                }
-                Ok(ControlResult::JumpWithinFunction {
-                    frame_type,
-                    address,
-                }) => {
-                    // 更新虚拟机的 pc 值
-                    let status = &mut vm.status;
-                    status.frame_type = frame_type;
-                    status.address = address;
+            Ok(false)
+        }
+        Ok(ControlResult::PopStackFrame {
+            vm_module_index,
+            function_index,
+            frame_type,
+            address,
+        }) => {
+            // 更新虚拟机的 pc 值
+            let status = &mut vm.status;
+            status.vm_module_index = vm_module_index;
+            status.function_index = function_index;
+            status.frame_type = frame_type;
+            status.address = address;
 
-                    Ok(false)
-                }
-                Ok(ControlResult::JumpWithinBlock(address)) => {
-                    // 更新虚拟机的 pc 值
-                    let status = &mut vm.status;
-                    status.address = address;
+            Ok(false)
+        }
+        Ok(ControlResult::Jump(address)) => {
+            // 更新虚拟机的 pc 值
+            let status = &mut vm.status;
+            status.address = address;
 
-                    Ok(false)
-                }
-                Err(e) => Err(e),
-            }
+            Ok(false)
         }
+        Err(e) => Err(e),
     }
 }
@@ -0,0 +1,1067 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # x86-64 基线 JIT 编译器
+//!
+//! 启用 `jit` 特性之后，可以使用 `InterpreterKind::Jit` 创建 VM 实例。实例化时
+//! 各个模块的内部函数先由解码后的指令列表降低为寄存器中间表示（见 `register` 模块），
+//! 然后再把寄存器中间表示的每一个操作编译为 x86-64 机器码，存放在通过 mmap 分配的
+//! 可执行内存里。
+//!
+//! ## 编译方式
+//!
+//! 这是一个基线（baseline）编译器：每个操作独立编译，操作的结果总是写回栈槽，
+//! 不在操作之间分配寄存器。所以每个操作的开始位置都可以作为机器码的入口，
+//! 机器码可以在任意一个操作处离开，由解释器执行该操作之后再从下一个操作重新进入。
+//!
+//! 机器码执行期间使用的寄存器：
+//!
+//! ```text
+//! rbx     当前调用帧的栈槽（fp）
+//! r13     当前调用帧的栈槽的类型视图
+//! r8      运算操作数段的栈槽（bp + 信息段大小）
+//! r9      运算操作数段的栈槽的类型视图
+//! r12     JIT 上下文（JitContext）
+//! r14     内存块数据的开始地址
+//! r15     内存块数据的大小
+//! ```
+//!
+//! ## 交回解释器
+//!
+//! 以下情况机器码会记录当前操作的索引然后返回，由寄存器解释器执行该操作：
+//!
+//! - 暂不支持编译的操作，即除了内存读写指令之外的 `Stack` 操作，这些操作会回退到
+//!   解释器执行（`Stack` 操作最终由栈式解释器执行原指令）；
+//! - `Move` 以及 `BranchTable` 需要复制的操作数当中有 v128 数值（v128 数值的高 64 位
+//!   不在栈槽里），机器码先检查所有操作数的类型然后才复制，所以交回时栈槽尚未被修改；
+//! - 内存读写越界，解释器执行该操作时会得出跟栈式解释器相同的 `EngineError`；
+//! - `Exit` 操作，即函数调用（包括调用宿主提供的本地函数）、函数返回、尾调用、抛出异常以及
+//!   `unreachable` 等指令。这些指令交回栈式解释器执行，函数调用返回之后再从调用指令的
+//!   下一个入口重新进入机器码。所以机器码只覆盖函数内部的运算和跳转（包括 `br_table`），
+//!   调用频繁的小函数的大部分时间仍然花在解释器里。
+//!
+//! 整数除以 0 以及有符号整数除法溢出则由机器码直接检查，机器码记录陷阱（trap）的种类之后返回，
+//! 然后转换为相应的 `EngineError`。
+//!
+//! 内存块的数据在内存块增长时可能会被移动，而增长只会发生在解释器里，所以每次进入
+//! 机器码时都会重新读取内存块数据的地址和大小。
+//!
+//! 目前仅支持 x86-64 Linux。
+
+#[cfg(not(all(target_arch = "x86_64", target_os = "linux")))]
+compile_error!("the `jit` feature only supports x86-64 Linux");
+
+use std::{
+    collections::BTreeMap,
+    io,
+    mem::{self, offset_of, size_of},
+    ptr,
+    rc::Rc,
+};
+
+use anvm_ast::{instruction, types::ValueType};
+
+use crate::{
+    error::{EngineError, InvalidOperation},
+    jit_assembler::{
+        Assembler, ExecutableMemory, CC_A, CC_AE, CC_B, CC_BE, CC_E, CC_G, CC_GE, CC_L, CC_LE,
+        CC_NE, CC_NP, CC_P, R12, R13, R14, R15, R8, R9, RAX, RBX, RCX, RDI, RDX, RSI, XMM0,
+    },
+    object::Instruction,
+    register::{
+        BinaryOperands, BranchTableItem, EntryItem, Register, RegisterCode, RegisterOp,
+        UnaryOperands,
+    },
+    register_interpreter,
+    vm::{Resource, VM},
+    vm_stack::INFO_SEGMENT_ITEM_COUNT,
+};
+
+// 类型视图的每一项占一个字节，机器码直接复制字节来写入栈槽的类型
+const _: () = assert!(size_of::<ValueType>() == 1);
+
+/// 机器码执行时的上下文
+#[repr(C)]
+struct JitContext {
+    frame_slots: *mut u64,
+    frame_types: *mut ValueType,
+    operand_slots: *mut u64,
+    operand_types: *mut ValueType,
+    memory_base: *mut u8,
+    memory_size: u64,
+
+    /// 机器码离开时所在的操作的索引
+    op_index: u32,
+
+    /// 机器码离开的原因，`TRAP_NONE` 表示需要交回解释器执行 op_index 所在的操作
+    trap: u32,
+
+    /// 各种数据类型的模板，机器码从这里复制数据类型
+    value_types: [ValueType; 8],
+}
+
+const TRAP_NONE: u32 = 0;
+const TRAP_INTEGER_DIVIDE_BY_ZERO: u32 = 1;
+const TRAP_INTEGER_OVERFLOW: u32 = 2;
+
+/// 机器码的入口函数
+///
+/// 参数分别是 JIT 上下文以及开始执行的操作的机器码地址。
+type EntryFunction = unsafe extern "sysv64" fn(*mut JitContext, *const u8);
+
+/// 一个模块编译而成的机器码
+pub struct JitCode {
+    memory: ExecutableMemory,
+
+    /// 每个操作的机器码相对于可执行内存开始位置的偏移值
+    op_offsets: Vec<u32>,
+
+    /// 机器码直接读写的内存块在 VM 内存块实例列表里的索引
+    ///
+    /// 即模块的第一个内存块，仅当它不是共享内存块并且不是 64 位内存块时才会直接读写。
+    option_instance_memory_block_index: Option<usize>,
+}
+
+impl JitCode {
+    /// 编译一个模块的寄存器中间表示
+    pub fn compile(
+        resource: &Resource,
+        vm_module_index: usize,
+        register_code: &RegisterCode,
+    ) -> io::Result<Self> {
+        let vm_module = &resource.vm_modules[vm_module_index];
+        let option_instance_memory_block_index =
+            vm_module
+                .memory_indexes
+                .first()
+                .copied()
+                .filter(|instance_memory_block_index| {
+                    let memory_block = &resource.memory_blocks[*instance_memory_block_index];
+                    !memory_block.is_shared() && !memory_block.is_memory64()
+                });
+
+        let mut compiler = Compiler {
+            assembler: Assembler::new(),
+            instructions: &vm_module.instructions,
+            branch_tables: &register_code.branch_tables,
+            option_native_memory_block_index: option_instance_memory_block_index.map(|_| 0),
+            op_offsets: Vec::with_capacity(register_code.ops.len()),
+            epilogue_position: 0,
+            jump_patches: vec![],
+            fallback_patches: BTreeMap::new(),
+            trap_patches: BTreeMap::new(),
+        };
+        compiler.compile(&register_code.ops);
+
+        Ok(Self {
+            memory: ExecutableMemory::new(compiler.assembler.get_code())?,
+            op_offsets: compiler.op_offsets,
+            option_instance_memory_block_index,
+        })
+    }
+
+    /// 从指定的操作开始执行机器码，返回机器码离开时所在的操作的索引
+    fn run(
+        &self,
+        vm: &mut VM,
+        frame_pointer: usize,
+        operand_pointer: usize,
+        op_index: usize,
+    ) -> Result<usize, EngineError> {
+        let (memory_base, memory_size) = match self.option_instance_memory_block_index {
+            Some(index) => match vm.resource.memory_blocks[index].get_local_bytes_mut() {
+                Some(bytes) => (bytes.as_mut_ptr(), bytes.len() as u64),
+                None => (ptr::null_mut(), 0),
+            },
+            None => (ptr::null_mut(), 0),
+        };
+
+        let (slots, types) = vm.stack.as_mut_ptrs();
+
+        // 执行机器码期间栈的大小不会改变，所以栈槽的地址是稳定的
+        let mut context = unsafe {
+            JitContext {
+                frame_slots: slots.add(frame_pointer),
+                frame_types: types.add(frame_pointer),
+                operand_slots: slots.add(operand_pointer),
+                operand_types: types.add(operand_pointer),
+                memory_base,
+                memory_size,
+                op_index: 0,
+                trap: TRAP_NONE,
+                value_types: [
+                    ValueType::I32,
+                    ValueType::I64,
+                    ValueType::F32,
+                    ValueType::F64,
+                    ValueType::V128,
+                    ValueType::FuncRef,
+                    ValueType::ExternRef,
+                    ValueType::ExnRef,
+                ],
+            }
+        };
+
+        unsafe {
+            let code = self.memory.as_ptr();
+            let entry: EntryFunction = mem::transmute(code);
+            entry(&mut context, code.add(self.op_offsets[op_index] as usize));
+        }
+
+        match context.trap {
+            TRAP_INTEGER_DIVIDE_BY_ZERO => Err(EngineError::InvalidOperation(
+                InvalidOperation::IntegerDivideByZero,
+            )),
+            TRAP_INTEGER_OVERFLOW => Err(EngineError::InvalidOperation(
+                InvalidOperation::IntegerOverflow,
+            )),
+            _ => Ok(context.op_index as usize),
+        }
+    }
+}
+
+/// 执行剩余的指令，无视断点
+///
+/// 遇到入口时执行机器码，其余指令由栈式解释器执行。
+pub fn recur_without_break(vm: &mut VM) -> Result<(), EngineError> {
+    loop {
+        let option_entry = {
+            let vm_module = &vm.resource.vm_modules[vm.status.vm_module_index];
+            match (&vm_module.register_code, &vm_module.jit_code) {
+                (Some(register_code), Some(jit_code)) => register_code.entries[vm.status.address]
+                    .map(|entry_item| (Rc::clone(register_code), Rc::clone(jit_code), entry_item)),
+                _ => None,
+            }
+        };
+
+        if let Some((register_code, jit_code, entry_item)) = option_entry {
+            exec_jit_code(vm, &register_code, &jit_code, entry_item)?;
+        }
+
+        let is_program_end = vm.step()?;
+        if is_program_end {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// 从指定入口开始执行机器码，直到遇到 `Exit` 操作
+fn exec_jit_code(
+    vm: &mut VM,
+    register_code: &RegisterCode,
+    jit_code: &JitCode,
+    entry_item: EntryItem,
+) -> Result<(), EngineError> {
+    let frame_pointer = vm.status.frame_pointer;
+    let operand_pointer = vm.status.base_pointer + INFO_SEGMENT_ITEM_COUNT;
+    let full_size = operand_pointer + entry_item.max_stack_height as usize;
+
+    vm.stack.set_size(full_size);
+
+    let mut op_index = entry_item.op_index as usize;
+
+    loop {
+        op_index = jit_code.run(vm, frame_pointer, operand_pointer, op_index)?;
+
+        match register_interpreter::exec_register_op(
+            vm,
+            register_code,
+            frame_pointer,
+            operand_pointer,
+            full_size,
+            op_index,
+        )? {
+            Some(next_op_index) => op_index = next_op_index,
+            None => return Ok(()),
+        }
+    }
+}
+
+struct Compiler<'a> {
+    assembler: Assembler,
+    instructions: &'a [Instruction],
+    branch_tables: &'a [Vec<BranchTableItem>],
+
+    /// 机器码直接读写的内存块在模块里的索引
+    option_native_memory_block_index: Option<u32>,
+
+    op_offsets: Vec<u32>,
+    epilogue_position: usize,
+
+    /// 跳转指令的偏移值的位置，以及目标操作的索引
+    jump_patches: Vec<(usize, u32)>,
+
+    /// 交回解释器的跳转指令的偏移值的位置，按操作的索引分组
+    fallback_patches: BTreeMap<u32, Vec<usize>>,
+
+    /// 陷阱的跳转指令的偏移值的位置，按陷阱的种类分组
+    trap_patches: BTreeMap<u32, Vec<usize>>,
+}
+
+impl Compiler<'_> {
+    fn compile(&mut self, ops: &[RegisterOp]) {
+        self.emit_prologue();
+        self.emit_epilogue();
+
+        for (op_index, op) in ops.iter().enumerate() {
+            self.op_offsets.push(self.assembler.get_position() as u32);
+            self.compile_op(op_index as u32, op);
+        }
+
+        // 交回解释器的代码统一放在最后
+        for (op_index, patch_positions) in mem::take(&mut self.fallback_patches) {
+            let position = self.assembler.get_position();
+            for patch_position in patch_positions {
+                self.assembler.patch_rel32(patch_position, position);
+            }
+            self.emit_leave(op_index);
+        }
+
+        for (trap, patch_positions) in mem::take(&mut self.trap_patches) {
+            let position = self.assembler.get_position();
+            for patch_position in patch_positions {
+                self.assembler.patch_rel32(patch_position, position);
+            }
+            self.emit_trap(trap);
+        }
+
+        for (patch_position, target) in mem::take(&mut self.jump_patches) {
+            let position = self.op_offsets[target as usize] as usize;
+            self.assembler.patch_rel32(patch_position, position);
+        }
+    }
+
+    /// 入口函数，位于机器码的开始位置
+    fn emit_prologue(&mut self) {
+        let assembler = &mut self.assembler;
+
+        // push rbx; push r12; push r13; push r14; push r15
+        assembler.emit(&[0x53, 0x41, 0x54, 0x41, 0x55, 0x41, 0x56, 0x41, 0x57]);
+
+        // mov r12, rdi
+        assembler.op_reg(None, true, &[0x89], RDI, R12);
+
+        let load_context = |assembler: &mut Assembler, reg: u8, offset: usize| {
+            assembler.op_mem(None, true, &[0x8b], reg, R12, offset as i32)
+        };
+        load_context(assembler, RBX, offset_of!(JitContext, frame_slots));
+        load_context(assembler, R13, offset_of!(JitContext, frame_types));
+        load_context(assembler, R8, offset_of!(JitContext, operand_slots));
+        load_context(assembler, R9, offset_of!(JitContext, operand_types));
+        load_context(assembler, R14, offset_of!(JitContext, memory_base));
+        load_context(assembler, R15, offset_of!(JitContext, memory_size));
+
+        // jmp rsi
+        assembler.op_reg(None, false, &[0xff], 4, RSI);
+    }
+
+    fn emit_epilogue(&mut self) {
+        self.epilogue_position = self.assembler.get_position();
+
+        // pop r15; pop r14; pop r13; pop r12; pop rbx; ret
+        self.assembler
+            .emit(&[0x41, 0x5f, 0x41, 0x5e, 0x41, 0x5d, 0x41, 0x5c, 0x5b, 0xc3]);
+    }
+
+    /// 记录操作的索引然后离开机器码
+    fn emit_leave(&mut self, op_index: u32) {
+        // mov dword [r12 + op_index], imm32
+        self.assembler.op_mem(
+            None,
+            false,
+            &[0xc7],
+            0,
+            R12,
+            offset_of!(JitContext, op_index) as i32,
+        );
+        self.assembler.emit_i32(op_index as i32);
+
+        let patch_position = self.assembler.jmp();
+        self.assembler
+            .patch_rel32(patch_position, self.epilogue_position);
+    }
+
+    /// 记录陷阱的种类然后离开机器码
+    fn emit_trap(&mut self, trap: u32) {
+        // mov dword [r12 + trap], imm32
+        self.assembler.op_mem(
+            None,
+            false,
+            &[0xc7],
+            0,
+            R12,
+            offset_of!(JitContext, trap) as i32,
+        );
+        self.assembler.emit_i32(trap as i32);
+
+        let patch_position = self.assembler.jmp();
+        self.assembler
+            .patch_rel32(patch_position, self.epilogue_position);
+    }
+
+    /// 当条件成立时触发陷阱
+    fn emit_trap_when(&mut self, condition: u8, trap: u32) {
+        let patch_position = self.assembler.jcc(condition);
+        self.trap_patches
+            .entry(trap)
+            .or_default()
+            .push(patch_position);
+    }
+
+    /// 当条件成立时交回解释器执行指定的操作
+    fn emit_fallback_when(&mut self, condition: u8, op_index: u32) {
+        let patch_position = self.assembler.jcc(condition);
+        self.fallback_patches
+            .entry(op_index)
+            .or_default()
+            .push(patch_position);
+    }
+
+    fn compile_op(&mut self, op_index: u32, op: &RegisterOp) {
+        match op {
+            RegisterOp::Const {
+                dest,
+                value,
+                value_type,
+            } => {
+                self.assembler.mov_imm64(RAX, *value);
+                self.store_slot(*dest, RAX);
+                self.write_type(*dest, value_type);
+            }
+            RegisterOp::Copy(operands) => {
+                self.load_slot(true, RAX, operands.source);
+                self.store_slot(operands.dest, RAX);
+                self.copy_type(operands.dest, operands.source);
+            }
+
+            RegisterOp::I32Eqz(operands) => self.compile_test(false, operands),
+            RegisterOp::I64Eqz(operands) => self.compile_test(true, operands),
+
+            RegisterOp::I32Eq(o) => self.compile_integer_compare(false, o, CC_E),
+            RegisterOp::I32Ne(o) => self.compile_integer_compare(false, o, CC_NE),
+            RegisterOp::I32LtS(o) => self.compile_integer_compare(false, o, CC_L),
+            RegisterOp::I32LtU(o) => self.compile_integer_compare(false, o, CC_B),
+            RegisterOp::I32GtS(o) => self.compile_integer_compare(false, o, CC_G),
+            RegisterOp::I32GtU(o) => self.compile_integer_compare(false, o, CC_A),
+            RegisterOp::I32LeS(o) => self.compile_integer_compare(false, o, CC_LE),
+            RegisterOp::I32LeU(o) => self.compile_integer_compare(false, o, CC_BE),
+            RegisterOp::I32GeS(o) => self.compile_integer_compare(false, o, CC_GE),
+            RegisterOp::I32GeU(o) => self.compile_integer_compare(false, o, CC_AE),
+
+            RegisterOp::I64Eq(o) => self.compile_integer_compare(true, o, CC_E),
+            RegisterOp::I64Ne(o) => self.compile_integer_compare(true, o, CC_NE),
+            RegisterOp::I64LtS(o) => self.compile_integer_compare(true, o, CC_L),
+            RegisterOp::I64LtU(o) => self.compile_integer_compare(true, o, CC_B),
+            RegisterOp::I64GtS(o) => self.compile_integer_compare(true, o, CC_G),
+            RegisterOp::I64GtU(o) => self.compile_integer_compare(true, o, CC_A),
+            RegisterOp::I64LeS(o) => self.compile_integer_compare(true, o, CC_LE),
+            RegisterOp::I64LeU(o) => self.compile_integer_compare(true, o, CC_BE),
+            RegisterOp::I64GeS(o) => self.compile_integer_compare(true, o, CC_GE),
+            RegisterOp::I64GeU(o) => self.compile_integer_compare(true, o, CC_AE),
+
+            RegisterOp::F32Eq(o) => self.compile_float_compare(false, o, FloatCompare::Eq),
+            RegisterOp::F32Ne(o) => self.compile_float_compare(false, o, FloatCompare::Ne),
+            RegisterOp::F32Lt(o) => self.compile_float_compare(false, o, FloatCompare::Lt),
+            RegisterOp::F32Gt(o) => self.compile_float_compare(false, o, FloatCompare::Gt),
+            RegisterOp::F32Le(o) => self.compile_float_compare(false, o, FloatCompare::Le),
+            RegisterOp::F32Ge(o) => self.compile_float_compare(false, o, FloatCompare::Ge),
+
+            RegisterOp::F64Eq(o) => self.compile_float_compare(true, o, FloatCompare::Eq),
+            RegisterOp::F64Ne(o) => self.compile_float_compare(true, o, FloatCompare::Ne),
+            RegisterOp::F64Lt(o) => self.compile_float_compare(true, o, FloatCompare::Lt),
+            RegisterOp::F64Gt(o) => self.compile_float_compare(true, o, FloatCompare::Gt),
+            RegisterOp::F64Le(o) => self.compile_float_compare(true, o, FloatCompare::Le),
+            RegisterOp::F64Ge(o) => self.compile_float_compare(true, o, FloatCompare::Ge),
+
+            RegisterOp::I32Add(o) => self.compile_integer_binary(false, o, &[0x03]),
+            RegisterOp::I32Sub(o) => self.compile_integer_binary(false, o, &[0x2b]),
+            RegisterOp::I32Mul(o) => self.compile_integer_binary(false, o, &[0x0f, 0xaf]),
+            RegisterOp::I32And(o) => self.compile_integer_binary(false, o, &[0x23]),
+            RegisterOp::I32Or(o) => self.compile_integer_binary(false, o, &[0x0b]),
+            RegisterOp::I32Xor(o) => self.compile_integer_binary(false, o, &[0x33]),
+            RegisterOp::I32Shl(o) => self.compile_shift(false, o, 4),
+            RegisterOp::I32ShrS(o) => self.compile_shift(false, o, 7),
+            RegisterOp::I32ShrU(o) => self.compile_shift(false, o, 5),
+            RegisterOp::I32Rotl(o) => self.compile_shift(false, o, 0),
+            RegisterOp::I32Rotr(o) => self.compile_shift(false, o, 1),
+            RegisterOp::I32DivS(o) => self.compile_divide(false, o, true, false),
+            RegisterOp::I32DivU(o) => self.compile_divide(false, o, false, false),
+            RegisterOp::I32RemS(o) => self.compile_divide(false, o, true, true),
+            RegisterOp::I32RemU(o) => self.compile_divide(false, o, false, true),
+
+            RegisterOp::I64Add(o) => self.compile_integer_binary(true, o, &[0x03]),
+            RegisterOp::I64Sub(o) => self.compile_integer_binary(true, o, &[0x2b]),
+            RegisterOp::I64Mul(o) => self.compile_integer_binary(true, o, &[0x0f, 0xaf]),
+            RegisterOp::I64And(o) => self.compile_integer_binary(true, o, &[0x23]),
+            RegisterOp::I64Or(o) => self.compile_integer_binary(true, o, &[0x0b]),
+            RegisterOp::I64Xor(o) => self.compile_integer_binary(true, o, &[0x33]),
+            RegisterOp::I64Shl(o) => self.compile_shift(true, o, 4),
+            RegisterOp::I64ShrS(o) => self.compile_shift(true, o, 7),
+            RegisterOp::I64ShrU(o) => self.compile_shift(true, o, 5),
+            RegisterOp::I64Rotl(o) => self.compile_shift(true, o, 0),
+            RegisterOp::I64Rotr(o) => self.compile_shift(true, o, 1),
+            RegisterOp::I64DivS(o) => self.compile_divide(true, o, true, false),
+            RegisterOp::I64DivU(o) => self.compile_divide(true, o, false, false),
+            RegisterOp::I64RemS(o) => self.compile_divide(true, o, true, true),
+            RegisterOp::I64RemU(o) => self.compile_divide(true, o, false, true),
+
+            RegisterOp::F32Add(o) => self.compile_float_binary(false, o, 0x58),
+            RegisterOp::F32Sub(o) => self.compile_float_binary(false, o, 0x5c),
+            RegisterOp::F32Mul(o) => self.compile_float_binary(false, o, 0x59),
+            RegisterOp::F32Div(o) => self.compile_float_binary(false, o, 0x5e),
+
+            RegisterOp::F64Add(o) => self.compile_float_binary(true, o, 0x58),
+            RegisterOp::F64Sub(o) => self.compile_float_binary(true, o, 0x5c),
+            RegisterOp::F64Mul(o) => self.compile_float_binary(true, o, 0x59),
+            RegisterOp::F64Div(o) => self.compile_float_binary(true, o, 0x5e),
+
+            RegisterOp::Jump(target) => {
+                let patch_position = self.assembler.jmp();
+                self.jump_patches.push((patch_position, *target));
+            }
+            RegisterOp::JumpWhenEqZero { condition, target } => {
+                self.compile_jump_when(*condition, *target, CC_E)
+            }
+            RegisterOp::JumpWhenNotEqZero { condition, target } => {
+                self.compile_jump_when(*condition, *target, CC_NE)
+            }
+
+            RegisterOp::Stack { address, height } => {
+                if !self.compile_memory_access(op_index, *address as usize, *height) {
+                    self.emit_leave(op_index);
+                }
+            }
+
+            RegisterOp::Move {
+                dest,
+                source,
+                count,
+            } => self.compile_move(op_index, *dest, *source, *count),
+            RegisterOp::BranchTable { index, table } => {
+                self.compile_branch_table(op_index, *index, *table)
+            }
+
+            RegisterOp::Exit { .. } => self.emit_leave(op_index),
+        }
+    }
+
+    // 栈槽的读写
+    //
+    // 栈槽的编码方式跟解释器的一致：i32 和 f32 存放在低 32 位（高 32 位为 0），
+    // 而 x86-64 写 32 位寄存器时会把高 32 位清零，所以 32 位的结果也总是写入整个 64 位的栈槽。
+
+    fn load_slot(&mut self, is_64bit: bool, reg: u8, register: Register) {
+        self.assembler.op_mem(
+            None,
+            is_64bit,
+            &[0x8b],
+            reg,
+            RBX,
+            slot_displacement(register),
+        );
+    }
+
+    fn store_slot(&mut self, register: Register, reg: u8) {
+        self.assembler
+            .op_mem(None, true, &[0x89], reg, RBX, slot_displacement(register));
+    }
+
+    /// 写入栈槽的数据类型，会改写 al 寄存器
+    fn write_type(&mut self, register: Register, value_type: &ValueType) {
+        self.write_type_at(R13, register as i32, value_type);
+    }
+
+    fn write_type_at(&mut self, base: u8, displacement: i32, value_type: &ValueType) {
+        let index = match value_type {
+            ValueType::I32 => 0,
+            ValueType::I64 => 1,
+            ValueType::F32 => 2,
+            ValueType::F64 => 3,
+            ValueType::V128 => 4,
+            ValueType::FuncRef => 5,
+            ValueType::ExternRef => 6,
+            ValueType::ExnRef => 7,
+        };
+
+        // mov al, [r12 + value_types + index]
+        self.assembler.op_mem(
+            None,
+            false,
+            &[0x8a],
+            RAX,
+            R12,
+            (offset_of!(JitContext, value_types) + index) as i32,
+        );
+
+        // mov [base + displacement], al
+        self.assembler
+            .op_mem(None, false, &[0x88], RAX, base, displacement);
+    }
+
+    /// 复制栈槽的数据类型，会改写 al 寄存器
+    fn copy_type(&mut self, dest: Register, source: Register) {
+        self.assembler
+            .op_mem(None, false, &[0x8a], RAX, R13, source as i32);
+        self.assembler
+            .op_mem(None, false, &[0x88], RAX, R13, dest as i32);
+    }
+
+    // 运算操作
+
+    fn compile_test(&mut self, is_64bit: bool, operands: &UnaryOperands) {
+        self.load_slot(is_64bit, RAX, operands.source);
+
+        // test rax, rax
+        self.assembler.op_reg(None, is_64bit, &[0x85], RAX, RAX);
+        self.assembler.setcc_eax(CC_E);
+        self.store_slot(operands.dest, RAX);
+        self.write_type(operands.dest, &ValueType::I32);
+    }
+
+    fn compile_integer_compare(&mut self, is_64bit: bool, operands: &BinaryOperands, cc: u8) {
+        self.load_slot(is_64bit, RAX, operands.left);
+
+        // cmp rax, [rbx + right]
+        self.assembler.op_mem(
+            None,
+            is_64bit,
+            &[0x3b],
+            RAX,
+            RBX,
+            slot_displacement(operands.right),
+        );
+        self.assembler.setcc_eax(cc);
+        self.store_slot(operands.dest, RAX);
+        self.write_type(operands.dest, &ValueType::I32);
+    }
+
+    /// 浮点数比较
+    ///
+    /// `ucomiss/ucomisd` 遇到 NaN 时 ZF、PF 和 CF 均为 1，所以 `小于`、`小于等于` 交换操作数之后
+    /// 使用 `a/ae` 条件，`等于` 和 `不等于` 则需要同时检查 PF。
+    fn compile_float_compare(
+        &mut self,
+        is_64bit: bool,
+        operands: &BinaryOperands,
+        compare: FloatCompare,
+    ) {
+        let (first, second) = match compare {
+            FloatCompare::Lt | FloatCompare::Le => (operands.right, operands.left),
+            _ => (operands.left, operands.right),
+        };
+
+        let (load_prefix, compare_prefix) = if is_64bit {
+            (0xf2, Some(0x66))
+        } else {
+            (0xf3, None)
+        };
+
+        // movss/movsd xmm0, [rbx + first]
+        self.assembler.op_mem(
+            Some(load_prefix),
+            false,
+            &[0x0f, 0x10],
+            XMM0,
+            RBX,
+            slot_displacement(first),
+        );
+
+        // ucomiss/ucomisd xmm0, [rbx + second]
+        self.assembler.op_mem(
+            compare_prefix,
+            false,
+            &[0x0f, 0x2e],
+            XMM0,
+            RBX,
+            slot_displacement(second),
+        );
+
+        match compare {
+            FloatCompare::Eq | FloatCompare::Ne => {
+                let (cc, parity_cc, combine) = if compare == FloatCompare::Eq {
+                    (CC_E, CC_NP, 0x20) // and al, cl
+                } else {
+                    (CC_NE, CC_P, 0x08) // or al, cl
+                };
+
+                // setcc al; setcc cl; and/or al, cl; movzx eax, al
+                self.assembler
+                    .op_reg(None, false, &[0x0f, 0x90 | cc], 0, RAX);
+                self.assembler
+                    .op_reg(None, false, &[0x0f, 0x90 | parity_cc], 0, RCX);
+                self.assembler.op_reg(None, false, &[combine], RCX, RAX);
+                self.assembler.op_reg(None, false, &[0x0f, 0xb6], RAX, RAX);
+            }
+            FloatCompare::Lt | FloatCompare::Gt => self.assembler.setcc_eax(CC_A),
+            FloatCompare::Le | FloatCompare::Ge => self.assembler.setcc_eax(CC_AE),
+        }
+
+        self.store_slot(operands.dest, RAX);
+        self.write_type(operands.dest, &ValueType::I32);
+    }
+
+    fn compile_integer_binary(&mut self, is_64bit: bool, operands: &BinaryOperands, opcode: &[u8]) {
+        self.load_slot(is_64bit, RAX, operands.left);
+
+        // op rax, [rbx + right]
+        self.assembler.op_mem(
+            None,
+            is_64bit,
+            opcode,
+            RAX,
+            RBX,
+            slot_displacement(operands.right),
+        );
+        self.store_slot(operands.dest, RAX);
+        self.write_integer_type(is_64bit, operands.dest);
+    }
+
+    /// 移位和旋转
+    ///
+    /// 参数 extension 是 `D3 /n` 指令的 n，移位的位数由 cl 指定，CPU 会按操作数的宽度取模，
+    /// 跟 WebAssembly 的语义一致。
+    fn compile_shift(&mut self, is_64bit: bool, operands: &BinaryOperands, extension: u8) {
+        self.load_slot(is_64bit, RAX, operands.left);
+        self.load_slot(false, RCX, operands.right);
+        self.assembler
+            .op_reg(None, is_64bit, &[0xd3], extension, RAX);
+        self.store_slot(operands.dest, RAX);
+        self.write_integer_type(is_64bit, operands.dest);
+    }
+
+    /// 整数除法和求余
+    ///
+    /// 除数为 0，以及有符号除法溢出（最小值除以 -1）时触发陷阱。
+    /// 需注意有符号求余的最小值除以 -1 并不是溢出，结果为 0，但 `idiv` 指令同样会引发异常，
+    /// 所以直接写入结果 0。
+    fn compile_divide(
+        &mut self,
+        is_64bit: bool,
+        operands: &BinaryOperands,
+        is_signed: bool,
+        is_remainder: bool,
+    ) {
+        self.load_slot(is_64bit, RAX, operands.left);
+        self.load_slot(is_64bit, RCX, operands.right);
+
+        // test rcx, rcx; jz trap
+        self.assembler.op_reg(None, is_64bit, &[0x85], RCX, RCX);
+        self.emit_trap_when(CC_E, TRAP_INTEGER_DIVIDE_BY_ZERO);
+
+        let mut option_done_patch_position = None;
+
+        if is_signed {
+            // cmp rcx, -1; jne divide
+            self.assembler.op_reg(None, is_64bit, &[0x83], 7, RCX);
+            self.assembler.emit(&[0xff]);
+            let patch_position = self.assembler.jcc(CC_NE);
+
+            // cmp rax, MIN; jne divide
+            if is_64bit {
+                self.assembler.mov_imm64(RDX, i64::MIN as u64);
+                self.assembler.op_reg(None, true, &[0x39], RDX, RAX);
+            } else {
+                self.assembler.emit(&[0x3d]);
+                self.assembler.emit_i32(i32::MIN);
+            }
+
+            if is_remainder {
+                let divide_patch_position = self.assembler.jcc(CC_NE);
+
+                // xor edx, edx; jmp done
+                self.assembler.op_reg(None, false, &[0x31], RDX, RDX);
+                option_done_patch_position = Some(self.assembler.jmp());
+
+                let position = self.assembler.get_position();
+                self.assembler.patch_rel32(divide_patch_position, position);
+            } else {
+                self.emit_trap_when(CC_E, TRAP_INTEGER_OVERFLOW);
+            }
+
+            let position = self.assembler.get_position();
+            self.assembler.patch_rel32(patch_position, position);
+
+            // cdq/cqo; idiv rcx
+            if is_64bit {
+                self.assembler.emit(&[0x48, 0x99]);
+            } else {
+                self.assembler.emit(&[0x99]);
+            }
+            self.assembler.op_reg(None, is_64bit, &[0xf7], 7, RCX);
+        } else {
+            // xor edx, edx; div rcx
+            self.assembler.op_reg(None, false, &[0x31], RDX, RDX);
+            self.assembler.op_reg(None, is_64bit, &[0xf7], 6, RCX);
+        }
+
+        if let Some(done_patch_position) = option_done_patch_position {
+            let position = self.assembler.get_position();
+            self.assembler.patch_rel32(done_patch_position, position);
+        }
+
+        // 商在 rax，余数在 rdx
+        self.store_slot(operands.dest, if is_remainder { RDX } else { RAX });
+        self.write_integer_type(is_64bit, operands.dest);
+    }
+
+    /// 浮点数运算，参数 opcode 是 `addss/addsd` 等指令的第二个操作码字节
+    fn compile_float_binary(&mut self, is_64bit: bool, operands: &BinaryOperands, opcode: u8) {
+        let prefix = if is_64bit { 0xf2 } else { 0xf3 };
+
+        // movss/movsd xmm0, [rbx + left]
+        self.assembler.op_mem(
+            Some(prefix),
+            false,
+            &[0x0f, 0x10],
+            XMM0,
+            RBX,
+            slot_displacement(operands.left),
+        );
+
+        // op xmm0, [rbx + right]
+        self.assembler.op_mem(
+            Some(prefix),
+            false,
+            &[0x0f, opcode],
+            XMM0,
+            RBX,
+            slot_displacement(operands.right),
+        );
+
+        if is_64bit {
+            // movsd [rbx + dest], xmm0
+            self.assembler.op_mem(
+                Some(prefix),
+                false,
+                &[0x0f, 0x11],
+                XMM0,
+                RBX,
+                slot_displacement(operands.dest),
+            );
+            self.write_type(operands.dest, &ValueType::F64);
+        } else {
+            // movd eax, xmm0
+            self.assembler
+                .op_reg(Some(0x66), false, &[0x0f, 0x7e], XMM0, RAX);
+            self.store_slot(operands.dest, RAX);
+            self.write_type(operands.dest, &ValueType::F32);
+        }
+    }
+
+    fn compile_jump_when(&mut self, condition: Register, target: u32, cc: u8) {
+        // cmp dword [rbx + condition], 0
+        self.assembler
+            .op_mem(None, false, &[0x83], 7, RBX, slot_displacement(condition));
+        self.assembler.emit(&[0x00]);
+
+        let patch_position = self.assembler.jcc(cc);
+        self.jump_patches.push((patch_position, target));
+    }
+
+    /// 复制一段连续的栈槽（包括类型视图），顺序跟 `VMStack::move_slots` 的相同
+    ///
+    /// 遇到 v128 数值时交回解释器执行指定的操作。
+    fn compile_move(&mut self, op_index: u32, dest: Register, source: Register, count: u32) {
+        // mov al, [r12 + value_types + 4]，即 v128 的类型
+        self.assembler.op_mem(
+            None,
+            false,
+            &[0x8a],
+            RAX,
+            R12,
+            (offset_of!(JitContext, value_types) + 4) as i32,
+        );
+
+        for offset in 0..count {
+            // cmp [r13 + source + offset], al
+            self.assembler
+                .op_mem(None, false, &[0x38], RAX, R13, (source + offset) as i32);
+            self.emit_fallback_when(CC_E, op_index);
+        }
+
+        for offset in 0..count {
+            self.load_slot(true, RAX, source + offset);
+            self.store_slot(dest + offset, RAX);
+            self.copy_type(dest + offset, source + offset);
+        }
+    }
+
+    /// 编译 `BranchTable` 操作
+    ///
+    /// 逐项比较索引，然后跳到相应的项，每一项先复制操作数然后跳到目标操作。
+    fn compile_branch_table(&mut self, op_index: u32, index: Register, table: u32) {
+        let branch_tables = self.branch_tables;
+        let (default_item, items) = branch_tables[table as usize].split_last().unwrap();
+
+        self.load_slot(false, RAX, index);
+
+        let mut item_patch_positions: Vec<usize> = vec![];
+        for item_index in 0..items.len() {
+            // cmp eax, imm32
+            self.assembler.op_reg(None, false, &[0x81], 7, RAX);
+            self.assembler.emit_i32(item_index as i32);
+            item_patch_positions.push(self.assembler.jcc(CC_E));
+        }
+
+        self.compile_branch_table_item(op_index, default_item);
+
+        for (patch_position, item) in item_patch_positions.into_iter().zip(items) {
+            let position = self.assembler.get_position();
+            self.assembler.patch_rel32(patch_position, position);
+            self.compile_branch_table_item(op_index, item);
+        }
+    }
+
+    fn compile_branch_table_item(&mut self, op_index: u32, item: &BranchTableItem) {
+        if item.count > 0 {
+            self.compile_move(op_index, item.dest, item.source, item.count);
+        }
+
+        let patch_position = self.assembler.jmp();
+        self.jump_patches.push((patch_position, item.target));
+    }
+
+    fn write_integer_type(&mut self, is_64bit: bool, register: Register) {
+        let value_type = if is_64bit {
+            ValueType::I64
+        } else {
+            ValueType::I32
+        };
+        self.write_type(register, &value_type);
+    }
+
+    /// 编译内存读写指令
+    ///
+    /// 只支持访问模块第一个内存块的普通读写指令，其余指令返回 false，交回解释器执行。
+    /// 有效地址越界时也交回解释器执行，由解释器得出越界错误。
+    fn compile_memory_access(&mut self, op_index: u32, address: usize, height: u32) -> bool {
+        use instruction::Instruction as I;
+
+        let instruction = match &self.instructions[address] {
+            Instruction::Sequence(instruction) => instruction,
+            _ => return false,
+        };
+
+        // (内存参数, 读写的字节数, 指令编码, 读取的数据类型；写入时为 None)
+        let (memory_args, length, encoding, option_value_type) = match instruction {
+            I::I32Load(m) => (m, 4, MOV32, Some(ValueType::I32)),
+            I::I32Load8S(m) => (m, 1, MOVSX8_32, Some(ValueType::I32)),
+            I::I32Load8U(m) => (m, 1, MOVZX8, Some(ValueType::I32)),
+            I::I32Load16S(m) => (m, 2, MOVSX16_32, Some(ValueType::I32)),
+            I::I32Load16U(m) => (m, 2, MOVZX16, Some(ValueType::I32)),
+            I::I64Load(m) => (m, 8, MOV64, Some(ValueType::I64)),
+            I::I64Load8S(m) => (m, 1, MOVSX8_64, Some(ValueType::I64)),
+            I::I64Load8U(m) => (m, 1, MOVZX8, Some(ValueType::I64)),
+            I::I64Load16S(m) => (m, 2, MOVSX16_64, Some(ValueType::I64)),
+            I::I64Load16U(m) => (m, 2, MOVZX16, Some(ValueType::I64)),
+            I::I64Load32S(m) => (m, 4, MOVSX32_64, Some(ValueType::I64)),
+            I::I64Load32U(m) => (m, 4, MOV32, Some(ValueType::I64)),
+            I::F32Load(m) => (m, 4, MOV32, Some(ValueType::F32)),
+            I::F64Load(m) => (m, 8, MOV64, Some(ValueType::F64)),
+            I::I32Store(m) | I::I64Store32(m) | I::F32Store(m) => (m, 4, STORE32, None),
+            I::I32Store8(m) | I::I64Store8(m) => (m, 1, STORE8, None),
+            I::I32Store16(m) | I::I64Store16(m) => (m, 2, STORE16, None),
+            I::I64Store(m) | I::F64Store(m) => (m, 8, STORE64, None),
+            _ => return false,
+        };
+
+        if Some(memory_args.memory_block_index) != self.option_native_memory_block_index
+            || memory_args.offset > i32::MAX as u64
+        {
+            return false;
+        }
+
+        // 地址位于读取指令的栈顶，或者写入指令的次栈顶
+        let address_operand = match option_value_type {
+            Some(_) => height - 1,
+            None => height - 2,
+        };
+
+        // mov eax, [r8 + address_operand]，即 u32 地址
+        self.assembler.op_mem(
+            None,
+            false,
+            &[0x8b],
+            RAX,
+            R8,
+            slot_displacement(address_operand),
+        );
+
+        // add rax, offset
+        if memory_args.offset != 0 {
+            self.assembler.emit(&[0x48, 0x05]);
+            self.assembler.emit_i32(memory_args.offset as i32);
+        }
+
+        // mov rdx, rax; add rdx, length; cmp rdx, r15; ja fallback
+        self.assembler.op_reg(None, true, &[0x89], RAX, RDX);
+        self.assembler.op_reg(None, true, &[0x83], 0, RDX);
+        self.assembler.emit(&[length]);
+        self.assembler.op_reg(None, true, &[0x39], R15, RDX);
+        self.emit_fallback_when(CC_A, op_index);
+
+        let (prefix, is_64bit, opcode) = encoding;
+
+        match option_value_type {
+            Some(value_type) => {
+                // 读取 [r14 + rax] 到 rax，然后写入地址所在的栈槽
+                self.assembler
+                    .op_mem_index(prefix, is_64bit, opcode, RAX, R14, RAX);
+                self.assembler.op_mem(
+                    None,
+                    true,
+                    &[0x89],
+                    RAX,
+                    R8,
+                    slot_displacement(address_operand),
+                );
+                self.write_type_at(R9, address_operand as i32, &value_type);
+            }
+            None => {
+                // 把栈顶的数值写入 [r14 + rax]
+                self.assembler
+                    .op_mem(None, true, &[0x8b], RCX, R8, slot_displacement(height - 1));
+                self.assembler
+                    .op_mem_index(prefix, is_64bit, opcode, RCX, R14, RAX);
+            }
+        }
+
+        true
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum FloatCompare {
+    Eq,
+    Ne,
+    Lt,
+    Gt,
+    Le,
+    Ge,
+}
+
+/// 内存读写指令的编码，即（强制前缀，是否 64 位，操作码）
+type MemoryEncoding = (Option<u8>, bool, &'static [u8]);
+
+const MOV32: MemoryEncoding = (None, false, &[0x8b]);
+const MOV64: MemoryEncoding = (None, true, &[0x8b]);
+const MOVZX8: MemoryEncoding = (None, false, &[0x0f, 0xb6]);
+const MOVZX16: MemoryEncoding = (None, false, &[0x0f, 0xb7]);
+const MOVSX8_32: MemoryEncoding = (None, false, &[0x0f, 0xbe]);
+const MOVSX16_32: MemoryEncoding = (None, false, &[0x0f, 0xbf]);
+const MOVSX8_64: MemoryEncoding = (None, true, &[0x0f, 0xbe]);
+const MOVSX16_64: MemoryEncoding = (None, true, &[0x0f, 0xbf]);
+const MOVSX32_64: MemoryEncoding = (None, true, &[0x63]);
+const STORE8: MemoryEncoding = (None, false, &[0x88]);
+const STORE16: MemoryEncoding = (Some(0x66), false, &[0x89]);
+const STORE32: MemoryEncoding = (None, false, &[0x89]);
+const STORE64: MemoryEncoding = (None, true, &[0x89]);
+
+/// 寄存器（栈槽）相对于基址的偏移值
+fn slot_displacement(register: Register) -> i32 {
+    (register as usize * size_of::<u64>()) as i32
+}
@@ -0,0 +1,310 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # x86-64 汇编器
+//!
+//! 供 JIT 编译器使用的最小化 x86-64 机器码生成器，只实现了 JIT 编译器用到的
+//! 几种指令编码形式：
+//!
+//! - 寄存器和寄存器：`op reg, rm`，即 ModRM 的 mod 为 11；
+//! - 寄存器和内存：`op reg, [base + disp32]`；
+//! - 寄存器和带索引的内存：`op reg, [base + index]`。
+//!
+//! 以及用于存放机器码的可执行内存（通过 mmap 分配）。
+
+use std::{ffi::c_void, io, ptr};
+
+// 通用寄存器的编号，对于 SSE 指令则是 xmm 寄存器的编号
+pub const RAX: u8 = 0;
+pub const RCX: u8 = 1;
+pub const RDX: u8 = 2;
+pub const RBX: u8 = 3;
+pub const RSI: u8 = 6;
+pub const RDI: u8 = 7;
+pub const R8: u8 = 8;
+pub const R9: u8 = 9;
+pub const R12: u8 = 12;
+pub const R13: u8 = 13;
+pub const R14: u8 = 14;
+pub const R15: u8 = 15;
+
+pub const XMM0: u8 = 0;
+
+// 条件码，用于 `jcc` 和 `setcc` 指令
+pub const CC_B: u8 = 0x2;
+pub const CC_AE: u8 = 0x3;
+pub const CC_E: u8 = 0x4;
+pub const CC_NE: u8 = 0x5;
+pub const CC_BE: u8 = 0x6;
+pub const CC_A: u8 = 0x7;
+pub const CC_P: u8 = 0xa;
+pub const CC_NP: u8 = 0xb;
+pub const CC_L: u8 = 0xc;
+pub const CC_GE: u8 = 0xd;
+pub const CC_LE: u8 = 0xe;
+pub const CC_G: u8 = 0xf;
+
+pub struct Assembler {
+    code: Vec<u8>,
+}
+
+impl Assembler {
+    pub fn new() -> Self {
+        Self { code: vec![] }
+    }
+
+    /// 当前位置，即下一个字节的偏移值
+    pub fn get_position(&self) -> usize {
+        self.code.len()
+    }
+
+    pub fn get_code(&self) -> &[u8] {
+        &self.code
+    }
+
+    pub fn emit(&mut self, bytes: &[u8]) {
+        self.code.extend_from_slice(bytes);
+    }
+
+    pub fn emit_i32(&mut self, value: i32) {
+        self.code.extend_from_slice(&value.to_le_bytes());
+    }
+
+    /// 写入 REX 前缀，当不需要时（即各个位均为 0）则不写入
+    fn emit_rex(&mut self, is_64bit: bool, reg: u8, index: u8, base: u8) {
+        let rex = ((is_64bit as u8) << 3) | ((reg >> 3) << 2) | ((index >> 3) << 1) | (base >> 3);
+        if rex != 0 {
+            self.code.push(0x40 | rex);
+        }
+    }
+
+    /// `op reg, rm`
+    ///
+    /// 参数 prefix 是强制前缀（比如 SSE 指令的 0x66、0xf3、0xf2），需要写在 REX 前缀之前。
+    pub fn op_reg(&mut self, prefix: Option<u8>, is_64bit: bool, opcode: &[u8], reg: u8, rm: u8) {
+        if let Some(byte) = prefix {
+            self.code.push(byte);
+        }
+        self.emit_rex(is_64bit, reg, 0, rm);
+        self.emit(opcode);
+        self.code.push(0xc0 | ((reg & 7) << 3) | (rm & 7));
+    }
+
+    /// `op reg, [base + disp32]`
+    pub fn op_mem(
+        &mut self,
+        prefix: Option<u8>,
+        is_64bit: bool,
+        opcode: &[u8],
+        reg: u8,
+        base: u8,
+        displacement: i32,
+    ) {
+        if let Some(byte) = prefix {
+            self.code.push(byte);
+        }
+        self.emit_rex(is_64bit, reg, 0, base);
+        self.emit(opcode);
+
+        // mod = 10，即 32 位偏移值
+        self.code.push(0x80 | ((reg & 7) << 3) | (base & 7));
+
+        // 以 rsp/r12 作为基址时需要 SIB 字节
+        if base & 7 == 4 {
+            self.code.push(0x24);
+        }
+        self.emit_i32(displacement);
+    }
+
+    /// `op reg, [base + index]`
+    ///
+    /// 基址不能是 rbp/r13，因为它们在 mod 为 00 时表示另外的寻址方式。
+    pub fn op_mem_index(
+        &mut self,
+        prefix: Option<u8>,
+        is_64bit: bool,
+        opcode: &[u8],
+        reg: u8,
+        base: u8,
+        index: u8,
+    ) {
+        if let Some(byte) = prefix {
+            self.code.push(byte);
+        }
+        self.emit_rex(is_64bit, reg, index, base);
+        self.emit(opcode);
+        self.code.push(((reg & 7) << 3) | 0b100);
+        self.code.push(((index & 7) << 3) | (base & 7));
+    }
+
+    /// `mov reg, imm64`
+    pub fn mov_imm64(&mut self, reg: u8, value: u64) {
+        self.emit_rex(true, 0, 0, reg);
+        self.code.push(0xb8 | (reg & 7));
+        self.code.extend_from_slice(&value.to_le_bytes());
+    }
+
+    /// `setcc al` 以及 `movzx eax, al`，即把条件转换为 i32 的 1 或者 0
+    pub fn setcc_eax(&mut self, condition: u8) {
+        self.op_reg(None, false, &[0x0f, 0x90 | condition], 0, RAX);
+        self.op_reg(None, false, &[0x0f, 0xb6], RAX, RAX);
+    }
+
+    /// `jmp rel32`
+    ///
+    /// 返回偏移值的位置，跳转目标需要在确定之后通过 `patch_rel32` 写入。
+    pub fn jmp(&mut self) -> usize {
+        self.code.push(0xe9);
+        self.emit_i32(0);
+        self.code.len() - 4
+    }
+
+    /// `jcc rel32`
+    ///
+    /// 返回偏移值的位置，跳转目标需要在确定之后通过 `patch_rel32` 写入。
+    pub fn jcc(&mut self, condition: u8) -> usize {
+        self.emit(&[0x0f, 0x80 | condition]);
+        self.emit_i32(0);
+        self.code.len() - 4
+    }
+
+    /// 写入跳转指令的偏移值
+    ///
+    /// 偏移值是相对于跳转指令的下一条指令（即偏移值之后）的位置。
+    pub fn patch_rel32(&mut self, patch_position: usize, target: usize) {
+        let relative = target as i64 - (patch_position + 4) as i64;
+        self.code[patch_position..patch_position + 4]
+            .copy_from_slice(&(relative as i32).to_le_bytes());
+    }
+}
+
+const PROT_READ: i32 = 0x1;
+const PROT_WRITE: i32 = 0x2;
+const PROT_EXEC: i32 = 0x4;
+const MAP_PRIVATE: i32 = 0x02;
+const MAP_ANONYMOUS: i32 = 0x20;
+const MAP_FAILED: *mut c_void = !0 as *mut c_void;
+
+extern "C" {
+    fn mmap(
+        addr: *mut c_void,
+        length: usize,
+        prot: i32,
+        flags: i32,
+        fd: i32,
+        offset: i64,
+    ) -> *mut c_void;
+    fn mprotect(addr: *mut c_void, length: usize, prot: i32) -> i32;
+    fn munmap(addr: *mut c_void, length: usize) -> i32;
+}
+
+/// 存放机器码的可执行内存
+///
+/// 先以可读写的方式映射内存并写入机器码，然后再改为可读可执行，
+/// 所以任何时候内存都不会同时可写和可执行。
+pub struct ExecutableMemory {
+    address: *mut u8,
+    length: usize,
+}
+
+impl ExecutableMemory {
+    pub fn new(code: &[u8]) -> io::Result<Self> {
+        let length = code.len().max(1);
+
+        unsafe {
+            let address = mmap(
+                ptr::null_mut(),
+                length,
+                PROT_READ | PROT_WRITE,
+                MAP_PRIVATE | MAP_ANONYMOUS,
+                -1,
+                0,
+            );
+            if address == MAP_FAILED {
+                return Err(io::Error::last_os_error());
+            }
+
+            let memory = Self {
+                address: address as *mut u8,
+                length,
+            };
+
+            ptr::copy_nonoverlapping(code.as_ptr(), memory.address, code.len());
+
+            if mprotect(address, length, PROT_READ | PROT_EXEC) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(memory)
+        }
+    }
+
+    pub fn as_ptr(&self) -> *const u8 {
+        self.address
+    }
+}
+
+impl Drop for ExecutableMemory {
+    fn drop(&mut self) {
+        unsafe {
+            munmap(self.address as *mut c_void, self.length);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Assembler, CC_E, R12, R13, R14, RAX, RBX, RCX};
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_encode() {
+        let mut assembler = Assembler::new();
+
+        // mov rax, [rbx + 16]
+        assembler.op_mem(None, true, &[0x8b], RAX, RBX, 16);
+        // mov al, [r12 + 8]
+        assembler.op_mem(None, false, &[0x8a], RAX, R12, 8);
+        // mov [r13 + 3], al
+        assembler.op_mem(None, false, &[0x88], RAX, R13, 3);
+        // movzx eax, byte [r14 + rax]
+        assembler.op_mem_index(None, false, &[0x0f, 0xb6], RAX, R14, RAX);
+        // shl rax, cl
+        assembler.op_reg(None, true, &[0xd3], 4, RAX);
+        // sete al; movzx eax, al
+        assembler.setcc_eax(CC_E);
+        // add ecx, [rbx + 0]
+        assembler.op_mem(None, false, &[0x03], RCX, RBX, 0);
+
+        assert_eq!(
+            assembler.get_code(),
+            &[
+                0x48, 0x8b, 0x83, 0x10, 0x00, 0x00, 0x00, // mov rax, [rbx + 16]
+                0x41, 0x8a, 0x84, 0x24, 0x08, 0x00, 0x00, 0x00, // mov al, [r12 + 8]
+                0x41, 0x88, 0x85, 0x03, 0x00, 0x00, 0x00, // mov [r13 + 3], al
+                0x41, 0x0f, 0xb6, 0x04, 0x06, // movzx eax, byte [r14 + rax]
+                0x48, 0xd3, 0xe0, // shl rax, cl
+                0x0f, 0x94, 0xc0, 0x0f, 0xb6, 0xc0, // sete al; movzx eax, al
+                0x03, 0x8b, 0x00, 0x00, 0x00, 0x00, // add ecx, [rbx + 0]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_patch_jump() {
+        let mut assembler = Assembler::new();
+        let patch_position = assembler.jmp();
+        assembler.emit(&[0x90, 0x90]);
+        let target = assembler.get_position();
+        assembler.patch_rel32(patch_position, target);
+
+        assert_eq!(
+            assembler.get_code(),
+            &[0xe9, 0x02, 0x00, 0x00, 0x00, 0x90, 0x90]
+        );
+    }
+}
@@ -10,14 +10,27 @@ pub mod object;
 pub mod native_module;
 pub mod vm_stack;
 pub mod vm_memory;
+#[cfg(target_os = "linux")]
+mod vm_memory_mapped;
 pub mod vm_table;
 pub mod vm_global_variable;
+pub mod vm_exception;
 pub mod vm_module;
+pub mod bytecode;
 pub mod interpreter;
+pub mod register;
+pub mod register_interpreter;
+#[cfg(feature = "jit")]
+pub mod jit;
 pub mod vm;
+pub mod module_cache;
+mod module_cache_codec;
 
 mod linker;
 mod decoder;
+mod stack_effect;
+#[cfg(feature = "jit")]
+mod jit_assembler;
 
 mod ins_const;
 mod ins_parametric;
@@ -28,8 +41,17 @@ mod ins_numeric_binary;
 mod ins_numeric_convert;
 mod ins_variable;
 mod ins_memory;
+mod ins_table;
+mod ins_reference;
+mod ins_simd;
+mod ins_atomic;
 mod ins_control;
 mod ins_function;
 mod ins_block;
+mod ins_exception;
+mod ins_fused;
 
-pub mod instance;
\ No newline at end of file
+pub mod instance;
+pub mod instance_pool;
+pub mod dylink;
+pub mod thread;
\ No newline at end of file
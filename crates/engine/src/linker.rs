@@ -6,17 +6,22 @@
 
 use crate::{
     decoder::decode_constant_expression,
-    error::{EngineError, ObjectNotFound, TypeMismatch, Unsupported},
+    error::{EngineError, ObjectNotFound, TypeMismatch},
     native_module::NativeModule,
     object::{BlockItem, FunctionItem, NamedAstModule},
     vm::VM,
+    vm_exception::VMTag,
     vm_global_variable::VMGlobalVariable,
     vm_memory::VMMemory,
     vm_table::VMTable,
 };
 use anvm_ast::{
-    ast::{self, ExportDescriptor, GlobalType, ImportDescriptor, TypeItem},
+    ast::{
+        self, ExportDescriptor, GlobalType, ImportDescriptor, Limit, MemoryType, TableType,
+        TagType, TypeItem,
+    },
     instruction,
+    types::{Value, ValueType},
 };
 
 /// AST 模块的函数的指令序列位置信息
@@ -51,6 +56,36 @@ impl BlockLocation {
     }
 }
 
+/// 计算每个模块的函数引用值的起始值
+///
+/// 函数引用的值是函数在 VM 实例里的全局索引，即所属模块的起始值加上函数在该模块里的
+/// 索引（包括导入函数），所以函数引用在模块之间传递（比如通过函数的返回值、
+/// 共享的表或者全局变量）之后仍然指向原来的函数。
+pub fn get_function_reference_bases(named_ast_modules: &[NamedAstModule]) -> Vec<u32> {
+    let mut function_reference_base: u32 = 0;
+
+    named_ast_modules
+        .iter()
+        .map(|named_ast_module| {
+            let ast_module = &named_ast_module.module;
+            let import_function_count = ast_module
+                .import_items
+                .iter()
+                .filter(|item| {
+                    matches!(
+                        item.import_descriptor,
+                        ImportDescriptor::FunctionTypeIndex(_)
+                    )
+                })
+                .count();
+
+            let base = function_reference_base;
+            function_reference_base += (import_function_count + ast_module.code_items.len()) as u32;
+            base
+        })
+        .collect::<Vec<u32>>()
+}
+
 /// 解决模块间的函数 "导出和导入" 的链接
 ///
 /// 返回各个 AST Module 对应的函数信息列表。
@@ -356,7 +391,9 @@ pub fn get_function_block_items(
 
     for (address, instruction) in instruction_items.iter().enumerate() {
         match instruction {
-            instruction::Instruction::Block(block_type, block_index) => {
+            // `try_table` 结构块的位置信息跟 block 结构块一样
+            instruction::Instruction::Block(block_type, block_index)
+            | instruction::Instruction::TryTable(block_type, _, block_index) => {
                 block_location_stack.push(BlockLocation::new(
                     *block_index as usize,
                     BlockItem::Block {
@@ -511,71 +548,78 @@ fn get_ast_module_function_index_by_export_name(
 
 /// 解决模块间的表链接，并创建相应的表对象。
 ///
-/// 注，对于没有指定表信息的模块，将会创建一个
+/// 注，对于既没有导入表也没有定义表的模块，将会创建一个
 /// 最小值为 0 的表对象
 ///
 /// 返回值当中
 /// - Vec<VMTable> 是虚拟机当中所有实例表的列表
-/// - Vec<usize> 是每个 AST Module 对应的实例表的索引列表，
-///   注：目前 WebAssembly 限制一个 Module 只能有一张表；
+/// - Vec<Vec<usize>> 是每个 AST Module 对应的实例表的索引列表，
+///   注：一个 Module 可以有多张表（导入的表排在前面）；
 ///   存在多个 Module 对应同一张表的情况。
 pub fn link_tables(
     named_ast_modules: &[NamedAstModule],
-) -> Result<(Vec<VMTable>, Vec<usize>), EngineError> {
-    // "AST 模块 - 表格实例的索引" 的临时映射表，
-    // 将元素的初始值设置为 None，以表示该项尚未设置。
-    let mut module_to_table_index_list: Vec<Option<usize>> = vec![None; named_ast_modules.len()];
+) -> Result<(Vec<VMTable>, Vec<Vec<usize>>), EngineError> {
+    // "AST 模块 - 表格实例的索引" 的临时映射表
+    let mut module_to_tables_list: Vec<Vec<Option<usize>>> = vec![];
 
     // 所有实例表
     let mut instance_tables: Vec<VMTable> = vec![];
 
-    // 先创建非导入的表
-    for (ast_module_index, ast_module) in named_ast_modules
-        .iter()
-        .map(|item| &item.module)
-        .enumerate()
-    {
-        // 先检查是否存在导入表
-        let option_import_table_item = ast_module
+    for ast_module in named_ast_modules.iter().map(|item| &item.module) {
+        let mut module_table_map_item: Vec<Option<usize>> = vec![];
+
+        // 先以 None 为值，填充模块的导入表
+        let import_table_count = ast_module
             .import_items
             .iter()
-            .find(|item| matches!(item.import_descriptor, ImportDescriptor::TableType(_)));
-
-        if option_import_table_item == None {
-            // 无导入表，创建新表
+            .filter(|item| matches!(item.import_descriptor, ImportDescriptor::TableType(_)))
+            .count();
 
-            let instance_table = if let Some(first) = ast_module.tables.first() {
-                // 根据定义创建新表
-                VMTable::new(first.clone())
-            } else {
-                // 创建默认表（容量最小值为 0，最大值也是 0，相当于无表）
-                VMTable::new_by_page_range(0, 0)
-            };
+        for _ in 0..import_table_count {
+            module_table_map_item.push(None);
+        }
 
+        // 再根据定义创建新表
+        for table_type in &ast_module.tables {
             let instance_table_index = instance_tables.len();
-            instance_tables.push(instance_table);
+            instance_tables.push(VMTable::new(table_type.clone()));
+            module_table_map_item.push(Some(instance_table_index));
+        }
 
-            module_to_table_index_list[ast_module_index] = Some(instance_table_index);
+        if module_table_map_item.is_empty() {
+            // 创建默认表（容量最小值为 0，最大值也是 0，相当于无表）
+            let instance_table_index = instance_tables.len();
+            instance_tables.push(VMTable::new_by_page_range(0, 0));
+            module_table_map_item.push(Some(instance_table_index));
         }
+
+        module_to_tables_list.push(module_table_map_item);
     }
 
     // 解决导入表格
     for ast_module_index in 0..named_ast_modules.len() {
-        if module_to_table_index_list[ast_module_index] == None {
-            resolve_ast_module_table(
-                named_ast_modules,
-                &instance_tables,
-                &mut module_to_table_index_list,
-                ast_module_index,
-            )?;
+        for module_table_index in 0..module_to_tables_list[ast_module_index].len() {
+            if module_to_tables_list[ast_module_index][module_table_index].is_none() {
+                resolve_ast_module_table(
+                    named_ast_modules,
+                    &instance_tables,
+                    &mut module_to_tables_list,
+                    ast_module_index,
+                    module_table_index,
+                )?;
+            }
         }
     }
 
     // 转换临时映射表
-    let list = module_to_table_index_list
+    let list = module_to_tables_list
         .iter()
-        .map(|item| item.unwrap())
-        .collect::<Vec<usize>>();
+        .map(|item| {
+            item.iter()
+                .map(|sub_item| sub_item.unwrap())
+                .collect::<Vec<usize>>()
+        })
+        .collect::<Vec<Vec<usize>>>();
 
     Ok((instance_tables, list))
 }
@@ -583,22 +627,23 @@ pub fn link_tables(
 fn resolve_ast_module_table(
     named_ast_modules: &[NamedAstModule],
     instance_tables: &Vec<VMTable>,
-    module_table_map: &mut Vec<Option<usize>>,
+    module_table_map: &mut Vec<Vec<Option<usize>>>,
     ast_module_index: usize,
+    module_table_index: usize,
 ) -> Result<usize, EngineError> {
     let ast_module = &named_ast_modules[ast_module_index].module;
 
     let (target_module_name, target_export_item_name, target_table_type) = ast_module
         .import_items
         .iter()
-        .find_map(|item| {
+        .filter_map(|item| {
             if let ImportDescriptor::TableType(table_type) = &item.import_descriptor {
                 Some((&item.module_name, &item.item_name, table_type))
             } else {
                 None
             }
         })
-        .expect("unreachable"); // 仅当 AST Module 声明了一个导入表格才会来到这里，所以不存在找不到导入项的情况
+        .collect::<Vec<(&String, &String, &TableType)>>()[module_table_index];
 
     let (target_ast_module_index, target_ast_module) = named_ast_modules
         .iter()
@@ -609,12 +654,12 @@ fn resolve_ast_module_table(
             target_module_name.to_owned(),
         )))?;
 
-    let target_table_index = target_ast_module
+    let target_module_table_index = target_ast_module
         .export_items
         .iter()
         .find_map(|item| match item.export_descriptor {
             ExportDescriptor::TableIndex(table_index) if &item.name == target_export_item_name => {
-                Some(table_index)
+                Some(table_index as usize)
             }
             _ => None,
         })
@@ -623,13 +668,8 @@ fn resolve_ast_module_table(
             target_export_item_name.to_owned(),
         )))?;
 
-    if target_table_index != 0 {
-        return Err(EngineError::Unsupported(
-            Unsupported::UnsupportedMultipleTable,
-        ));
-    }
-
-    let option_target_instance_table_index = module_table_map[target_ast_module_index];
+    let option_target_instance_table_index =
+        module_table_map[target_ast_module_index][target_module_table_index];
 
     let target_instance_table_index = if let Some(index) = option_target_instance_table_index {
         index
@@ -641,13 +681,22 @@ fn resolve_ast_module_table(
             instance_tables,
             module_table_map,
             target_ast_module_index,
+            target_module_table_index,
         )?
     };
 
     // 检查表格类型
     let instance_table = &instance_tables[target_instance_table_index];
 
-    if instance_table.get_table_type() != target_table_type {
+    let instance_table_type = instance_table.get_table_type();
+
+    if instance_table_type.element_type != target_table_type.element_type
+        || !is_limit_match(
+            instance_table.get_size() as u64,
+            &instance_table_type.limit,
+            &target_table_type.limit,
+        )
+    {
         return Err(EngineError::TypeMismatch(
             TypeMismatch::ImportedTableTypeMismatch(
                 target_module_name.to_owned(),
@@ -657,79 +706,85 @@ fn resolve_ast_module_table(
     }
 
     // 更新映射表
-    module_table_map[ast_module_index] = Some(target_instance_table_index);
+    module_table_map[ast_module_index][module_table_index] = Some(target_instance_table_index);
 
-    Ok(target_ast_module_index)
+    Ok(target_instance_table_index)
 }
 
 /// 解决模块间的内存块链接，并创建相应的内存块对象。
 ///
-/// 注，对于没有指定内存信息的模块，将会创建一个
+/// 注，对于既没有导入内存块也没有定义内存块的模块，将会创建一个
 /// 最小值为 0 的内存块对象
 ///
 /// 返回值当中
 /// - Vec<VMMemory> 是虚拟机当中所有内存块实例的列表
-/// - Vec<usize> 是每个 AST Module 对应的内存块实例的索引列表，
-///   注：目前 WebAssembly 限制一个 Module 只能有一个内存块；
+/// - Vec<Vec<usize>> 是每个 AST Module 对应的内存块实例的索引列表，
+///   注：一个 Module 可以有多个内存块（导入的内存块排在前面）；
 ///   存在多个 Module 对应同一个内存块的情况。
 pub fn link_memorys(
     named_ast_modules: &[NamedAstModule],
-) -> Result<(Vec<VMMemory>, Vec<usize>), EngineError> {
-    // "AST 模块 - 内存块实例的索引" 的临时映射表，
-    // 将元素的初始值设置为 None，以表示该项尚未设置。
-    let mut module_to_memory_block_index_list: Vec<Option<usize>> =
-        vec![None; named_ast_modules.len()];
+) -> Result<(Vec<VMMemory>, Vec<Vec<usize>>), EngineError> {
+    // "AST 模块 - 内存块实例的索引" 的临时映射表
+    let mut module_to_memory_blocks_list: Vec<Vec<Option<usize>>> = vec![];
 
-    // 所有实例表
+    // 所有内存块实例
     let mut instance_memory_blocks: Vec<VMMemory> = vec![];
 
-    // 先创建非导入的内存块实例
-    for (ast_module_index, ast_module) in named_ast_modules
-        .iter()
-        .map(|item| &item.module)
-        .enumerate()
-    {
-        // 先检查是否存在导入内存块
-        let option_import_memory_item = ast_module
+    for ast_module in named_ast_modules.iter().map(|item| &item.module) {
+        let mut module_memory_block_map_item: Vec<Option<usize>> = vec![];
+
+        // 先以 None 为值，填充模块的导入内存块
+        let import_memory_block_count = ast_module
             .import_items
             .iter()
-            .find(|item| matches!(item.import_descriptor, ImportDescriptor::MemoryType(_)));
-
-        if option_import_memory_item == None {
-            // 无导入内存块，创建新内存块
+            .filter(|item| matches!(item.import_descriptor, ImportDescriptor::MemoryType(_)))
+            .count();
 
-            let instance_memory = if let Some(first) = ast_module.memory_blocks.first() {
-                // 根据定义创建新内存块
-                VMMemory::new(first.clone())
-            } else {
-                // 创建默认内存块（容量最小值为 0，最大值也是 0，相当于无内存块定义）
-                VMMemory::new_by_page_range(0, 0)
-            };
+        for _ in 0..import_memory_block_count {
+            module_memory_block_map_item.push(None);
+        }
 
+        // 再根据定义创建新内存块
+        for memory_type in &ast_module.memory_blocks {
             let instance_memory_block_index = instance_memory_blocks.len();
-            instance_memory_blocks.push(instance_memory);
+            instance_memory_blocks.push(VMMemory::new(memory_type.clone()));
+            module_memory_block_map_item.push(Some(instance_memory_block_index));
+        }
 
-            module_to_memory_block_index_list[ast_module_index] = Some(instance_memory_block_index);
+        if module_memory_block_map_item.is_empty() {
+            // 创建默认内存块（容量最小值为 0，最大值也是 0，相当于无内存块定义）
+            let instance_memory_block_index = instance_memory_blocks.len();
+            instance_memory_blocks.push(VMMemory::new_by_page_range(0, 0));
+            module_memory_block_map_item.push(Some(instance_memory_block_index));
         }
+
+        module_to_memory_blocks_list.push(module_memory_block_map_item);
     }
 
     // 解决导入内存块
     for ast_module_index in 0..named_ast_modules.len() {
-        if module_to_memory_block_index_list[ast_module_index] == None {
-            resolve_ast_module_memory_block(
-                named_ast_modules,
-                &instance_memory_blocks,
-                &mut module_to_memory_block_index_list,
-                ast_module_index,
-            )?;
+        for module_memory_block_index in 0..module_to_memory_blocks_list[ast_module_index].len() {
+            if module_to_memory_blocks_list[ast_module_index][module_memory_block_index].is_none() {
+                resolve_ast_module_memory_block(
+                    named_ast_modules,
+                    &instance_memory_blocks,
+                    &mut module_to_memory_blocks_list,
+                    ast_module_index,
+                    module_memory_block_index,
+                )?;
+            }
         }
     }
 
     // 转换临时映射表
-    let list = module_to_memory_block_index_list
+    let list = module_to_memory_blocks_list
         .iter()
-        .map(|item| item.unwrap())
-        .collect::<Vec<usize>>();
+        .map(|item| {
+            item.iter()
+                .map(|sub_item| sub_item.unwrap())
+                .collect::<Vec<usize>>()
+        })
+        .collect::<Vec<Vec<usize>>>();
 
     Ok((instance_memory_blocks, list))
 }
@@ -737,22 +792,23 @@ pub fn link_memorys(
 fn resolve_ast_module_memory_block(
     named_ast_modules: &[NamedAstModule],
     instance_memory_blocks: &Vec<VMMemory>,
-    module_memory_block_map: &mut Vec<Option<usize>>,
+    module_memory_block_map: &mut Vec<Vec<Option<usize>>>,
     ast_module_index: usize,
+    module_memory_block_index: usize,
 ) -> Result<usize, EngineError> {
     let ast_module = &named_ast_modules[ast_module_index].module;
 
     let (target_module_name, target_export_item_name, target_memory_type) = ast_module
         .import_items
         .iter()
-        .find_map(|item| {
+        .filter_map(|item| {
             if let ImportDescriptor::MemoryType(memory_type) = &item.import_descriptor {
                 Some((&item.module_name, &item.item_name, memory_type))
             } else {
                 None
             }
         })
-        .expect("unreachable"); // 仅当 AST Module 声明了一个导入内存块才会来到这里，所以不存在找不到导入项的情况
+        .collect::<Vec<(&String, &String, &MemoryType)>>()[module_memory_block_index];
 
     let (target_ast_module_index, target_ast_module) = named_ast_modules
         .iter()
@@ -763,14 +819,14 @@ fn resolve_ast_module_memory_block(
             target_module_name.to_owned(),
         )))?;
 
-    let target_memory_block_index = target_ast_module
+    let target_module_memory_block_index = target_ast_module
         .export_items
         .iter()
         .find_map(|item| match item.export_descriptor {
             ExportDescriptor::MemoryBlockIndex(memory_block_index)
                 if &item.name == target_export_item_name =>
             {
-                Some(memory_block_index)
+                Some(memory_block_index as usize)
             }
             _ => None,
         })
@@ -781,14 +837,8 @@ fn resolve_ast_module_memory_block(
             ),
         ))?;
 
-    if target_memory_block_index != 0 {
-        return Err(EngineError::Unsupported(
-            Unsupported::UnsupportedMultipleMemoryBlock,
-        ));
-    }
-
     let option_target_instance_memory_block_index =
-        module_memory_block_map[target_ast_module_index];
+        module_memory_block_map[target_ast_module_index][target_module_memory_block_index];
 
     let target_instance_memory_block_index =
         if let Some(index) = option_target_instance_memory_block_index {
@@ -801,13 +851,23 @@ fn resolve_ast_module_memory_block(
                 instance_memory_blocks,
                 module_memory_block_map,
                 target_ast_module_index,
+                target_module_memory_block_index,
             )?
         };
 
     // 检查内存块类型
     let instance_memory_block = &instance_memory_blocks[target_instance_memory_block_index];
 
-    if instance_memory_block.get_memory_type() != target_memory_type {
+    let instance_memory_type = instance_memory_block.get_memory_type();
+
+    if instance_memory_type.shared != target_memory_type.shared
+        || instance_memory_type.memory64 != target_memory_type.memory64
+        || !is_limit_match(
+            instance_memory_block.get_page_count(),
+            &instance_memory_type.limit,
+            &target_memory_type.limit,
+        )
+    {
         return Err(EngineError::TypeMismatch(
             TypeMismatch::ImportedMemoryBlockTypeMismatch(
                 target_module_name.to_owned(),
@@ -817,9 +877,26 @@ fn resolve_ast_module_memory_block(
     }
 
     // 更新映射表
-    module_memory_block_map[ast_module_index] = Some(target_instance_memory_block_index);
+    module_memory_block_map[ast_module_index][module_memory_block_index] =
+        Some(target_instance_memory_block_index);
 
-    Ok(target_ast_module_index)
+    Ok(target_instance_memory_block_index)
+}
+
+/// 检查实际的表或者内存块能否满足导入项所声明的限制值
+///
+/// 实际的大小（即当前的元素数量或者页面数量）不能小于导入项的最小值，
+/// 如果导入项声明了最大值，则实际的限制值也必须有最大值，且不能大于导入项的最大值。
+fn is_limit_match(actual_size: u64, actual_limit: &Limit, expected_limit: &Limit) -> bool {
+    if actual_size < expected_limit.get_min() {
+        return false;
+    }
+
+    match (actual_limit, expected_limit) {
+        (_, Limit::AtLeast(_)) => true,
+        (Limit::Range(_, actual_max), Limit::Range(_, expected_max)) => actual_max <= expected_max,
+        (Limit::AtLeast(_), Limit::Range(_, _)) => false,
+    }
 }
 
 /// 解决模块间的全局变量链接
@@ -854,24 +931,12 @@ pub fn link_global_variables(
         }
 
         // 再创建模块内定义的所有全局变量
+        //
+        // 因为初始化常量表达式可能引用导入的全局变量（`global.get` 指令），
+        // 所以这里先以数据类型的默认值作为初始值，等解决了导入全局变量之后再求值。
         for global_item in &ast_module.global_items {
             let global_type = global_item.global_type.clone();
-
-            // 求值 global_item 的初始化常量表达式
-            let constant_expression =
-                decode_constant_expression(&global_item.initialize_instruction_items)?;
-            let value = VM::get_constant_instruction_value(&constant_expression)?;
-
-            // 检查数据类型是否匹配
-            if value.get_type() != global_type.value_type {
-                return Err(EngineError::TypeMismatch(
-                    TypeMismatch::ConstantExpressionValueTypeMismatch(
-                        global_type.value_type,
-                        value.get_type(),
-                    ),
-                ));
-            }
-
+            let value = Value::default_of(&global_type.value_type);
             let instance_global_variable = VMGlobalVariable::new(global_type, value);
 
             // 创建全局变量实例
@@ -920,6 +985,54 @@ pub fn link_global_variables(
         })
         .collect::<Vec<Vec<usize>>>();
 
+    // 按模块的顺序求值模块内定义的全局变量的初始化常量表达式
+    //
+    // 常量表达式里的 `global.get` 只能引用导入的全局变量，因此被引用的全局变量
+    // 应该位于排在前面的模块里（即已经被求值）。
+    let function_reference_bases = get_function_reference_bases(named_ast_modules);
+
+    for (ast_module_index, ast_module) in named_ast_modules
+        .iter()
+        .map(|item| &item.module)
+        .enumerate()
+    {
+        let global_variable_indexes = &list[ast_module_index];
+        let import_global_variable_count =
+            global_variable_indexes.len() - ast_module.global_items.len();
+
+        for (global_item_index, global_item) in ast_module.global_items.iter().enumerate() {
+            let global_type = global_item.global_type.clone();
+
+            // 求值 global_item 的初始化常量表达式
+            let constant_expression =
+                decode_constant_expression(&global_item.initialize_instruction_items)?;
+            let value = VM::get_constant_instruction_value(
+                &constant_expression,
+                function_reference_bases[ast_module_index],
+                |global_variable_index| {
+                    let instance_global_variable_index =
+                        global_variable_indexes[global_variable_index as usize];
+                    instance_global_variables[instance_global_variable_index].get_value()
+                },
+            )?;
+
+            // 检查数据类型是否匹配
+            if value.get_type() != global_type.value_type {
+                return Err(EngineError::TypeMismatch(
+                    TypeMismatch::ConstantExpressionValueTypeMismatch(
+                        global_type.value_type,
+                        value.get_type(),
+                    ),
+                ));
+            }
+
+            let instance_global_variable_index =
+                global_variable_indexes[import_global_variable_count + global_item_index];
+            instance_global_variables[instance_global_variable_index] =
+                VMGlobalVariable::new(global_type, value);
+        }
+    }
+
     Ok((instance_global_variables, list))
 }
 
@@ -1008,3 +1121,212 @@ fn resolve_ast_module_global_variable(
 
     Ok(target_ast_module_index)
 }
+
+/// 解决模块间的标签链接，并创建相应的标签实例
+///
+/// 本地模块的标签最先创建（按照本地模块的顺序），然后才是各个 AST 模块内部定义的标签，
+/// 所以本地模块的标签实例的索引可以由 `VM::get_native_tag_index` 方法直接计算得出。
+///
+/// 返回值当中
+/// - Vec<VMTag> 是虚拟机当中所有标签实例的列表
+/// - Vec<Vec<usize>> 是每个 AST Module 对应的标签实例的索引列表
+pub fn link_tags(
+    native_modules: &[NativeModule],
+    named_ast_modules: &[NamedAstModule],
+) -> Result<(Vec<VMTag>, Vec<Vec<usize>>), EngineError> {
+    // 所有标签实例
+    let mut instance_tags: Vec<VMTag> = vec![];
+
+    // 创建本地模块的标签
+    let mut native_module_to_tags_list: Vec<Vec<usize>> = vec![];
+
+    for native_module in native_modules {
+        let mut native_module_tag_map_item: Vec<usize> = vec![];
+
+        for type_index in &native_module.tag_to_type_index_list {
+            let params = native_module.function_types[*type_index].params.clone();
+            native_module_tag_map_item.push(instance_tags.len());
+            instance_tags.push(VMTag::new(params));
+        }
+
+        native_module_to_tags_list.push(native_module_tag_map_item);
+    }
+
+    // "AST 模块 - 标签实例的索引" 的临时映射表
+    let mut module_to_tags_list: Vec<Vec<Option<usize>>> = vec![];
+
+    for ast_module in named_ast_modules.iter().map(|item| &item.module) {
+        // 先以 None 为值，填充模块的导入标签
+        let import_tag_count = ast_module
+            .import_items
+            .iter()
+            .filter(|item| matches!(item.import_descriptor, ImportDescriptor::TagType(_)))
+            .count();
+
+        let mut module_tag_map_item: Vec<Option<usize>> = vec![None; import_tag_count];
+
+        // 再创建模块内定义的所有标签
+        for tag_type in &ast_module.tag_items {
+            let params = get_ast_module_tag_params(ast_module, tag_type);
+            module_tag_map_item.push(Some(instance_tags.len()));
+            instance_tags.push(VMTag::new(params));
+        }
+
+        module_to_tags_list.push(module_tag_map_item);
+    }
+
+    // 解决导入标签
+    for ast_module_index in 0..named_ast_modules.len() {
+        for module_tag_index in 0..module_to_tags_list[ast_module_index].len() {
+            if module_to_tags_list[ast_module_index][module_tag_index].is_none() {
+                resolve_ast_module_tag(
+                    native_modules,
+                    named_ast_modules,
+                    &native_module_to_tags_list,
+                    &instance_tags,
+                    &mut module_to_tags_list,
+                    ast_module_index,
+                    module_tag_index,
+                )?;
+            }
+        }
+    }
+
+    // 转换临时映射表
+    let list = module_to_tags_list
+        .iter()
+        .map(|item| {
+            item.iter()
+                .map(|sub_item| sub_item.unwrap())
+                .collect::<Vec<usize>>()
+        })
+        .collect::<Vec<Vec<usize>>>();
+
+    Ok((instance_tags, list))
+}
+
+fn get_ast_module_tag_params(ast_module: &ast::Module, tag_type: &TagType) -> Vec<ValueType> {
+    match &ast_module.type_items[tag_type.type_index as usize] {
+        TypeItem::FunctionType(function_type) => function_type.params.clone(),
+    }
+}
+
+/// 返回目标标签实例的索引
+fn resolve_ast_module_tag(
+    native_modules: &[NativeModule],
+    named_ast_modules: &[NamedAstModule],
+    native_module_to_tags_list: &[Vec<usize>],
+    instance_tags: &[VMTag],
+    module_tag_map: &mut Vec<Vec<Option<usize>>>,
+    ast_module_index: usize,
+    module_tag_index: usize,
+) -> Result<usize, EngineError> {
+    let ast_module = &named_ast_modules[ast_module_index].module;
+
+    let (target_module_name, target_export_item_name, target_tag_type) = ast_module
+        .import_items
+        .iter()
+        .filter_map(|item| {
+            if let ImportDescriptor::TagType(tag_type) = &item.import_descriptor {
+                Some((&item.module_name, &item.item_name, tag_type))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<(&String, &String, &TagType)>>()[module_tag_index];
+
+    let make_tag_not_found_error = || {
+        EngineError::ObjectNotFound(ObjectNotFound::TagNotFound(
+            target_module_name.to_owned(),
+            target_export_item_name.to_owned(),
+        ))
+    };
+
+    let option_native_module_index = native_modules
+        .iter()
+        .position(|item| &item.name == target_module_name);
+
+    let target_instance_tag_index = if let Some(native_module_index) = option_native_module_index
+    {
+        // 导入本地模块的标签
+        let native_module_tag_index = native_modules[native_module_index]
+            .find_tag_index_by_exported_name(target_export_item_name)
+            .ok_or_else(make_tag_not_found_error)?;
+        native_module_to_tags_list[native_module_index][native_module_tag_index]
+    } else {
+        let (target_ast_module_index, target_ast_module) = named_ast_modules
+            .iter()
+            .enumerate()
+            .find(|(_index, item)| &item.name == target_module_name)
+            .map(|(index, item)| (index, &item.module))
+            .ok_or(EngineError::ObjectNotFound(ObjectNotFound::ModuleNotFound(
+                target_module_name.to_owned(),
+            )))?;
+
+        let target_module_tag_index = target_ast_module
+            .export_items
+            .iter()
+            .find_map(|item| match item.export_descriptor {
+                ExportDescriptor::TagIndex(tag_index) if &item.name == target_export_item_name => {
+                    Some(tag_index as usize)
+                }
+                _ => None,
+            })
+            .ok_or_else(make_tag_not_found_error)?;
+
+        match module_tag_map[target_ast_module_index][target_module_tag_index] {
+            Some(index) => index,
+            None => {
+                // 目标标签是模块导入再次导出的，
+                // 需要再次解析一次，直到找到真正的标签实例为止
+                resolve_ast_module_tag(
+                    native_modules,
+                    named_ast_modules,
+                    native_module_to_tags_list,
+                    instance_tags,
+                    module_tag_map,
+                    target_ast_module_index,
+                    target_module_tag_index,
+                )?
+            }
+        }
+    };
+
+    // 检查标签类型
+    if instance_tags[target_instance_tag_index].params
+        != get_ast_module_tag_params(ast_module, target_tag_type)
+    {
+        return Err(EngineError::TypeMismatch(
+            TypeMismatch::ImportedTagTypeMismatch(
+                target_module_name.to_owned(),
+                target_export_item_name.to_owned(),
+            ),
+        ));
+    }
+
+    // 更新映射表
+    module_tag_map[ast_module_index][module_tag_index] = Some(target_instance_tag_index);
+
+    Ok(target_instance_tag_index)
+}
+
+#[cfg(test)]
+mod tests {
+    use anvm_ast::ast::Limit;
+
+    use crate::linker::is_limit_match;
+
+    #[test]
+    fn test_is_limit_match() {
+        // 导入项没有最大值
+        assert!(is_limit_match(1, &Limit::Range(1, 5), &Limit::AtLeast(1)));
+        assert!(is_limit_match(2, &Limit::AtLeast(1), &Limit::AtLeast(2)));
+        assert!(!is_limit_match(1, &Limit::AtLeast(1), &Limit::AtLeast(2)));
+
+        // 导入项有最大值
+        assert!(is_limit_match(1, &Limit::Range(1, 5), &Limit::Range(1, 5)));
+        assert!(is_limit_match(2, &Limit::Range(2, 4), &Limit::Range(1, 5)));
+        assert!(!is_limit_match(1, &Limit::Range(1, 6), &Limit::Range(1, 5)));
+        assert!(!is_limit_match(1, &Limit::AtLeast(1), &Limit::Range(1, 5)));
+    }
+}
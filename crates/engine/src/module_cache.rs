@@ -0,0 +1,804 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 编译模块缓存
+//!
+//! 实例化时需要先解析和验证各个模块，然后链接所有模块的函数（`linker::link_functions`），
+//! 再把函数的指令序列解码为 VM 可以直接执行的指令（`decoder::decode`）。
+//!
+//! 编译模块缓存把这些步骤的结果，即 AST 模块、各个模块的函数项（包括结构块项）以及
+//! 解码后的指令列表，保存到缓存目录。下次使用相同的模块文件时，先根据模块的二进制内容
+//! 计算缓存键，命中缓存则直接读取 AST 模块以及链接和解码的结果，省去解析、验证、
+//! 链接和解码的步骤（见 `anvm_launcher` 以及 `instance::create_instance_with_compiled_modules`）。
+//!
+//! 只有通过验证的模块才会被写入缓存，所以从缓存读取的模块无需再次验证。
+//!
+//! ## 缓存键
+//!
+//! 链接的结果跟所有模块都有关（比如调用其它模块的函数的控制指令含有目标模块的索引），
+//! 所以缓存以整组模块为单位，缓存键是以下内容的 SHA-256 哈希值：
+//!
+//! - 缓存格式的版本号；
+//! - 各个本地模块的名称、函数以及标签（名称和类型）；
+//! - 各个模块的名称以及模块二进制内容的 SHA-256 哈希值（`NamedAstModule::option_content_hash`）。
+//!
+//! 缓存键只跟模块的二进制内容有关，所以可以在解析模块之前计算（见 `compute_key_by_content_hashes`）。
+//! 对于已解析的模块，只要有一个模块缺少内容哈希值，则不使用缓存。
+//!
+//! ## 缓存文件
+//!
+//! 每组模块对应缓存目录里的一个文件 `{缓存键}.anvmc`，文件的开头记录了缓存格式的版本号
+//! 以及缓存键，然后是各个模块的名称、内容哈希值、AST 模块、函数项列表和指令列表，
+//! 文件的末尾是以上所有内容的 SHA-256 校验值。
+//!
+//! 读取时如果版本号或者缓存键跟当前的不一致，或者校验值不正确（比如文件被截断或者损坏），
+//! 则删除该文件并重新编译。因为读取缓存时不再验证模块，所以校验值覆盖了整个文件，
+//! 以免损坏的缓存文件里的索引（比如跳转目标、函数索引以及局部变量的数量）越界。
+//! 注意校验值只能发现意外的损坏，并不能防止恶意的修改，缓存目录应该跟模块文件一样
+//! 只允许可信的用户写入。
+//!
+//! 指令列表里的顺序指令跟 AST 模块里的原指令相同，所以缓存文件只记录控制指令，
+//! 顺序指令在读取时从 AST 模块复制。超级指令项则在读取之后重新识别。
+//!
+//! 缓存文件的编码见 `module_cache_codec` 模块。修改了 AST 或者链接器、解码器的输出
+//! （即 `object` 模块里的数据结构及其含义）时，需要增加 `FORMAT_VERSION` 的值，以令旧的缓存文件失效。
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+};
+
+use anvm_ast::{ast::FunctionType, instruction};
+
+use crate::{
+    module_cache_codec::{invalid_data, value_type_to_u8, Reader, Writer},
+    native_module::NativeModule,
+    object::{FunctionItem, Instruction, NamedAstModule},
+};
+
+/// 缓存格式的版本号
+///
+/// 缓存文件记录的是 AST 模块以及链接器和解码器的输出，所以修改了 AST、`object` 模块里的
+/// 函数项、结构块项、控制指令等数据结构（包括其含义）或者它们的编码时，都必须增加此值。
+/// 注意引擎的版本号（`CARGO_PKG_VERSION`）并不会随着这些修改而改变，所以不能用于判断缓存是否过期。
+pub const FORMAT_VERSION: u32 = 3;
+
+const MAGIC: &[u8; 8] = b"ANVMCACH";
+const FILE_EXTENSION: &str = "anvmc";
+
+/// 链接和解码的结果，即每个模块的函数项列表，以及每个模块的指令列表
+pub type CompiledModules = (Vec<Vec<FunctionItem>>, Vec<Vec<Instruction>>);
+
+/// 模块二进制内容或者缓存键的哈希值（SHA-256）
+pub type ContentHash = [u8; 32];
+
+/// 计算字节序列的 SHA-256 哈希值，用于计算模块二进制内容的哈希值
+pub fn hash_bytes(bytes: &[u8]) -> ContentHash {
+    let mut hasher = Hasher::new();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+/// 编译模块缓存
+pub struct ModuleCache {
+    directory: PathBuf,
+}
+
+impl ModuleCache {
+    /// 使用指定的缓存目录，目录不存在时会在第一次写入缓存时创建
+    pub fn new(directory: &Path) -> Self {
+        Self {
+            directory: directory.to_path_buf(),
+        }
+    }
+
+    pub fn get_directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// 计算一组已解析的模块的缓存键，当有模块缺少内容哈希值时返回 None
+    pub fn compute_key(
+        native_modules: &[NativeModule],
+        named_ast_modules: &[NamedAstModule],
+    ) -> Option<ContentHash> {
+        let named_content_hashes = named_ast_modules
+            .iter()
+            .map(|named_ast_module| {
+                named_ast_module
+                    .option_content_hash
+                    .map(|content_hash| (named_ast_module.name.as_str(), content_hash))
+            })
+            .collect::<Option<Vec<(&str, ContentHash)>>>()?;
+
+        Some(Self::compute_key_by_content_hashes(
+            native_modules,
+            &named_content_hashes,
+        ))
+    }
+
+    /// 根据各个模块的名称以及二进制内容的哈希值计算缓存键
+    ///
+    /// 用于在解析模块之前查找缓存。
+    pub fn compute_key_by_content_hashes(
+        native_modules: &[NativeModule],
+        named_content_hashes: &[(&str, ContentHash)],
+    ) -> ContentHash {
+        let mut hasher = Hasher::new();
+        hasher.write(MAGIC);
+        hasher.write_u64(FORMAT_VERSION as u64);
+
+        hasher.write_u64(native_modules.len() as u64);
+        for native_module in native_modules {
+            hasher.write_str(&native_module.name);
+
+            hasher.write_u64(native_module.function_names.len() as u64);
+            for (function_name, type_index) in native_module
+                .function_names
+                .iter()
+                .zip(&native_module.function_to_type_index_list)
+            {
+                hasher.write_str(function_name);
+                hasher.write_function_type(&native_module.function_types[*type_index]);
+            }
+
+            hasher.write_u64(native_module.tag_names.len() as u64);
+            for (tag_name, type_index) in native_module
+                .tag_names
+                .iter()
+                .zip(&native_module.tag_to_type_index_list)
+            {
+                hasher.write_str(tag_name);
+                hasher.write_function_type(&native_module.function_types[*type_index]);
+            }
+        }
+
+        hasher.write_u64(named_content_hashes.len() as u64);
+        for (name, content_hash) in named_content_hashes {
+            hasher.write_str(name);
+            hasher.write(content_hash);
+        }
+
+        hasher.finish()
+    }
+
+    /// 缓存键所对应的缓存文件的路径
+    pub fn get_entry_path(&self, key: &ContentHash) -> PathBuf {
+        let name = key
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+        self.directory.join(format!("{}.{}", name, FILE_EXTENSION))
+    }
+
+    /// 读取缓存，返回（带有内容哈希值的）AST 模块列表以及链接和解码的结果
+    ///
+    /// 缓存不存在时返回 None；缓存文件的版本不一致或者内容损坏时，删除该文件并返回 None。
+    pub fn load(&self, key: &ContentHash) -> Option<(Vec<NamedAstModule>, CompiledModules)> {
+        let path = self.get_entry_path(key);
+        let bytes = fs::read(&path).ok()?;
+
+        match read_entry(&bytes, key) {
+            Ok(entry) => Some(entry),
+            Err(_) => {
+                // 过期或者损坏的缓存文件
+                let _ = fs::remove_file(&path);
+                None
+            }
+        }
+    }
+
+    /// 写入缓存
+    ///
+    /// 调用者需确保模块已经通过验证。
+    /// 先写入临时文件然后再重命名，以免其它进程读到不完整的缓存文件。
+    pub fn store(
+        &self,
+        key: &ContentHash,
+        named_ast_modules: &[NamedAstModule],
+        function_items_list: &[Vec<FunctionItem>],
+        instructions_list: &[Vec<Instruction>],
+    ) -> io::Result<()> {
+        let bytes = write_entry(
+            key,
+            named_ast_modules,
+            function_items_list,
+            instructions_list,
+        )?;
+
+        fs::create_dir_all(&self.directory)?;
+
+        let path = self.get_entry_path(key);
+        let temp_path = path.with_extension(format!("{}.{}", FILE_EXTENSION, process::id()));
+        fs::write(&temp_path, bytes)?;
+        fs::rename(&temp_path, &path).inspect_err(|_| {
+            let _ = fs::remove_file(&temp_path);
+        })
+    }
+}
+
+fn write_entry(
+    key: &ContentHash,
+    named_ast_modules: &[NamedAstModule],
+    function_items_list: &[Vec<FunctionItem>],
+    instructions_list: &[Vec<Instruction>],
+) -> io::Result<Vec<u8>> {
+    let mut writer = Writer::new();
+
+    writer.write_bytes(MAGIC);
+    writer.write_u32(FORMAT_VERSION);
+    writer.write_bytes(key);
+
+    writer.write_usize(named_ast_modules.len());
+    for ((named_ast_module, function_items), instructions) in named_ast_modules
+        .iter()
+        .zip(function_items_list)
+        .zip(instructions_list)
+    {
+        let content_hash = named_ast_module
+            .option_content_hash
+            .ok_or_else(|| invalid_data("missing content hash"))?;
+
+        writer.write_str(&named_ast_module.name);
+        writer.write_bytes(&content_hash);
+        writer.write_module(&named_ast_module.module);
+
+        writer.write_list(function_items, Writer::write_function_item);
+
+        writer.write_usize(instructions.len());
+        for instruction in instructions {
+            match instruction {
+                Instruction::Sequence(_) => writer.write_u8(0),
+                Instruction::Control(control) => {
+                    writer.write_u8(1);
+                    writer.write_control(control);
+                }
+            }
+        }
+    }
+
+    let checksum = hash_bytes(&writer.bytes);
+    writer.write_bytes(&checksum);
+
+    Ok(writer.bytes)
+}
+
+fn read_entry(
+    bytes: &[u8],
+    key: &ContentHash,
+) -> io::Result<(Vec<NamedAstModule>, CompiledModules)> {
+    // 先检查校验值，再读取内容
+    let content_length = bytes
+        .len()
+        .checked_sub(key.len())
+        .ok_or_else(|| invalid_data("unexpected end of data"))?;
+    let (content, checksum) = bytes.split_at(content_length);
+    if hash_bytes(content) != checksum {
+        return Err(invalid_data("checksum mismatch"));
+    }
+
+    let mut reader = Reader::new(content);
+
+    if reader.read_bytes(MAGIC.len())? != MAGIC
+        || reader.read_u32()? != FORMAT_VERSION
+        || reader.read_bytes(key.len())? != key
+    {
+        return Err(invalid_data("version mismatch"));
+    }
+
+    let module_count = reader.read_length()?;
+
+    let mut named_ast_modules: Vec<NamedAstModule> = vec![];
+    let mut function_items_list: Vec<Vec<FunctionItem>> = vec![];
+    let mut instructions_list: Vec<Vec<Instruction>> = vec![];
+
+    for _ in 0..module_count {
+        let name = reader.read_string()?;
+        let content_hash: ContentHash = reader.read_bytes(32)?.try_into().unwrap();
+        let module = reader.read_module()?;
+
+        let function_items = reader.read_list(Reader::read_function_item)?;
+
+        // 指令列表跟 AST 模块里所有内部函数的指令序列一一对应
+        let original_instructions = module
+            .code_items
+            .iter()
+            .flat_map(|code_item| &code_item.instruction_items)
+            .collect::<Vec<&instruction::Instruction>>();
+
+        if reader.read_usize()? != original_instructions.len() {
+            return Err(invalid_data("instruction count mismatch"));
+        }
+
+        let instructions = original_instructions
+            .into_iter()
+            .map(|original_instruction| match reader.read_u8()? {
+                0 => Ok(Instruction::Sequence(original_instruction.to_owned())),
+                1 => Ok(Instruction::Control(reader.read_control()?)),
+                _ => Err(invalid_data("invalid instruction tag")),
+            })
+            .collect::<io::Result<Vec<Instruction>>>()?;
+
+        named_ast_modules.push(NamedAstModule::new(&name, module).with_content_hash(content_hash));
+        function_items_list.push(function_items);
+        instructions_list.push(instructions);
+    }
+
+    if !reader.is_end() {
+        return Err(invalid_data("unexpected trailing data"));
+    }
+
+    Ok((named_ast_modules, (function_items_list, instructions_list)))
+}
+
+/// SHA-256 哈希计算器
+///
+/// 缓存键用于识别模块，碰撞会导致执行错误的指令，所以使用加密哈希函数而不是 FNV 等
+/// 非加密哈希函数。
+struct Hasher {
+    state: [u32; 8],
+    buffer: [u8; 64],
+    buffer_length: usize,
+    total_length: u64,
+}
+
+impl Hasher {
+    const INITIAL_STATE: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+
+    const ROUND_CONSTANTS: [u32; 64] = [
+        0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4,
+        0xab1c5ed5, 0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe,
+        0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f,
+        0x4a7484aa, 0x5cb0a9dc, 0x76f988da, 0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7,
+        0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc,
+        0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85, 0xa2bfe8a1, 0xa81a664b,
+        0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070, 0x19a4c116,
+        0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+        0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7,
+        0xc67178f2,
+    ];
+
+    fn new() -> Self {
+        Self {
+            state: Self::INITIAL_STATE,
+            buffer: [0; 64],
+            buffer_length: 0,
+            total_length: 0,
+        }
+    }
+
+    fn write(&mut self, mut bytes: &[u8]) {
+        self.total_length = self.total_length.wrapping_add(bytes.len() as u64);
+
+        while !bytes.is_empty() {
+            let length = (64 - self.buffer_length).min(bytes.len());
+            self.buffer[self.buffer_length..self.buffer_length + length]
+                .copy_from_slice(&bytes[..length]);
+            self.buffer_length += length;
+            bytes = &bytes[length..];
+
+            if self.buffer_length == 64 {
+                let block = self.buffer;
+                self.compress(&block);
+                self.buffer_length = 0;
+            }
+        }
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    /// 写入字符串，先写入长度以免不同的字符串组合得出相同的字节序列
+    fn write_str(&mut self, value: &str) {
+        self.write_u64(value.len() as u64);
+        self.write(value.as_bytes());
+    }
+
+    fn write_function_type(&mut self, function_type: &FunctionType) {
+        for value_types in [&function_type.params, &function_type.results] {
+            self.write_u64(value_types.len() as u64);
+            for value_type in value_types {
+                self.write(&[value_type_to_u8(value_type)]);
+            }
+        }
+    }
+
+    fn finish(mut self) -> ContentHash {
+        let bit_length = self.total_length.wrapping_mul(8);
+
+        // 填充：一个 0x80 字节，若干个 0 字节，最后是 64 位大端序的消息长度（以位为单位）
+        self.write(&[0x80]);
+        while self.buffer_length != 56 {
+            self.write(&[0]);
+        }
+        self.write(&bit_length.to_be_bytes());
+
+        let mut hash = [0u8; 32];
+        for (bytes, word) in hash.chunks_exact_mut(4).zip(self.state) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+        hash
+    }
+
+    fn compress(&mut self, block: &[u8; 64]) {
+        let mut words = [0u32; 64];
+        for (word, bytes) in words.iter_mut().zip(block.chunks_exact(4)) {
+            *word = u32::from_be_bytes(bytes.try_into().unwrap());
+        }
+        for index in 16..64 {
+            let s0 = words[index - 15].rotate_right(7)
+                ^ words[index - 15].rotate_right(18)
+                ^ (words[index - 15] >> 3);
+            let s1 = words[index - 2].rotate_right(17)
+                ^ words[index - 2].rotate_right(19)
+                ^ (words[index - 2] >> 10);
+            words[index] = words[index - 16]
+                .wrapping_add(s0)
+                .wrapping_add(words[index - 7])
+                .wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.state;
+        for (round_constant, word) in Self::ROUND_CONSTANTS.iter().zip(words) {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let temp1 = h
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(*round_constant)
+                .wrapping_add(word);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let temp2 = s0.wrapping_add(maj);
+
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(temp1);
+            d = c;
+            c = b;
+            b = a;
+            a = temp1.wrapping_add(temp2);
+        }
+
+        for (state, value) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *state = state.wrapping_add(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf, process};
+
+    use anvm_ast::types::{Value, ValueType};
+    use anvm_binary_parser::parser;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        decoder::decode,
+        error::NativeTerminate,
+        instance::{
+            create_instance, create_instance_with_cache, create_instance_with_compiled_modules,
+        },
+        linker::link_functions,
+        module_cache_codec::{Reader, Writer},
+        native_module::{EmptyModuleContext, NativeModule},
+        object::NamedAstModule,
+        vm::{InterpreterKind, VM},
+    };
+
+    use super::{hash_bytes, ModuleCache};
+
+    // 辅助方法
+    fn get_test_binary_resource(filename: &str) -> Vec<u8> {
+        let mut path_buf = env::current_dir().unwrap();
+        if !path_buf.ends_with("engine") {
+            path_buf.push("crates");
+            path_buf.push("engine");
+        }
+        let fullname_buf = path_buf.join("resources").join(filename);
+        let fullname = fullname_buf.to_str().unwrap();
+        fs::read(fullname)
+            .unwrap_or_else(|_| panic!("failed to read the specified binary file: {}", fullname))
+    }
+
+    fn get_test_resource_filenames() -> Vec<String> {
+        let mut path_buf = env::current_dir().unwrap();
+        if !path_buf.ends_with("engine") {
+            path_buf.push("crates");
+            path_buf.push("engine");
+        }
+        let mut filenames = fs::read_dir(path_buf.join("resources"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .filter(|filename| filename.ends_with(".wasm"))
+            .collect::<Vec<String>>();
+        filenames.sort();
+        filenames
+    }
+
+    fn get_named_ast_module(name: &str, filename: &str) -> NamedAstModule {
+        let bytes = get_test_binary_resource(filename);
+        NamedAstModule::new(name, parser::parse(&bytes).unwrap())
+            .with_content_hash(hash_bytes(&bytes))
+    }
+
+    /// 每个测试使用独立的缓存目录
+    fn create_test_cache_directory(name: &str) -> PathBuf {
+        let directory =
+            env::temp_dir().join(format!("anvm-module-cache-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        directory
+    }
+
+    fn to_hex(hash: &[u8]) -> String {
+        hash.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    #[test]
+    fn test_hash_bytes_nist_vectors() {
+        // NIST 公布的 SHA-256 已知答案测试向量（FIPS 180-2 附录 B 以及 NIST 的示例）
+
+        // 空消息
+        assert_eq!(
+            to_hex(&hash_bytes(b"")),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+
+        // 单个数据块的消息 "abc"
+        assert_eq!(
+            to_hex(&hash_bytes(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+
+        // 448 位的消息，填充之后跨越两个数据块
+        assert_eq!(
+            to_hex(&hash_bytes(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+            )),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+
+        // 一百万个 'a'
+        assert_eq!(
+            to_hex(&hash_bytes(&[b'a'; 1_000_000])),
+            "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"
+        );
+    }
+
+    #[test]
+    fn test_compute_key() {
+        let named_ast_modules = vec![get_named_ast_module("test", "test-block-loop.wasm")];
+        let key = ModuleCache::compute_key(&[], &named_ast_modules).unwrap();
+        assert_eq!(ModuleCache::compute_key(&[], &named_ast_modules), Some(key));
+
+        // 模块的内容、名称以及本地模块均影响缓存键
+        let mut changed_modules = named_ast_modules.clone();
+        changed_modules[0].option_content_hash = Some([0; 32]);
+        assert_ne!(ModuleCache::compute_key(&[], &changed_modules), Some(key));
+
+        let mut renamed_modules = named_ast_modules.clone();
+        renamed_modules[0].name = "other".to_string();
+        assert_ne!(ModuleCache::compute_key(&[], &renamed_modules), Some(key));
+
+        let native_module = NativeModule::new("env", Box::new(EmptyModuleContext::new()));
+        assert_ne!(
+            ModuleCache::compute_key(&[native_module], &named_ast_modules),
+            Some(key)
+        );
+
+        // 缺少内容哈希值时不使用缓存
+        let mut unhashed_modules = named_ast_modules.clone();
+        unhashed_modules[0].option_content_hash = None;
+        assert_eq!(ModuleCache::compute_key(&[], &unhashed_modules), None);
+    }
+
+    fn native_function_fail(
+        _vm: &mut VM,
+        _native_module_index: usize,
+        _params: &[Value],
+    ) -> Result<Vec<Value>, NativeTerminate> {
+        Ok(vec![])
+    }
+
+    fn get_test_native_module() -> NativeModule {
+        let mut native_module = NativeModule::new("env", Box::new(EmptyModuleContext::new()));
+        native_module.add_tag("error", vec![ValueType::I32]);
+        native_module.add_native_function(
+            "fail",
+            vec![ValueType::I32],
+            vec!["value"],
+            vec![],
+            native_function_fail,
+        );
+        native_module
+    }
+
+    #[test]
+    fn test_store_and_load() {
+        let directory = create_test_cache_directory("store");
+        let module_cache = ModuleCache::new(&directory);
+
+        // 包含导入函数、结构块、br_table 以及 try_table 等控制指令
+        let named_ast_modules = vec![
+            get_named_ast_module("m0", "test-function-call-callee.wasm"),
+            get_named_ast_module("m1", "test-block-branch-table.wasm"),
+            get_named_ast_module("m2", "test-exception.wasm"),
+        ];
+
+        let key =
+            ModuleCache::compute_key(&[get_test_native_module()], &named_ast_modules).unwrap();
+        assert_eq!(module_cache.load(&key), None);
+
+        // 第一次实例化时写入缓存
+        create_instance_with_cache(
+            vec![get_test_native_module()],
+            &named_ast_modules,
+            InterpreterKind::Stack,
+            &module_cache,
+        )
+        .unwrap();
+        assert!(module_cache.get_entry_path(&key).exists());
+
+        // 缓存的内容跟 AST 模块以及链接和解码的结果相同
+        let function_items_list =
+            link_functions(&[get_test_native_module()], &named_ast_modules).unwrap();
+        let (instructions_list, _) = decode(&named_ast_modules, &function_items_list).unwrap();
+        assert_eq!(
+            module_cache.load(&key),
+            Some((
+                named_ast_modules.clone(),
+                (function_items_list, instructions_list)
+            ))
+        );
+
+        // 从缓存创建的实例的执行结果跟不使用缓存的相同
+        let mut cached_vm = create_instance_with_cache(
+            vec![get_test_native_module()],
+            &named_ast_modules,
+            InterpreterKind::Stack,
+            &module_cache,
+        )
+        .unwrap();
+        let mut vm = create_instance(vec![get_test_native_module()], &named_ast_modules).unwrap();
+
+        for index in 0..6 {
+            let args = [Value::I32(index)];
+            assert_eq!(
+                cached_vm.eval_function_by_index(1, 0, &args).unwrap(),
+                vm.eval_function_by_index(1, 0, &args).unwrap()
+            );
+        }
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_load_without_parsing() {
+        let directory = create_test_cache_directory("unparsed");
+        let module_cache = ModuleCache::new(&directory);
+
+        let filenames = ["test-function-call-callee.wasm", "test-block-branch-table.wasm"];
+        let named_ast_modules = vec![
+            get_named_ast_module("m0", filenames[0]),
+            get_named_ast_module("m1", filenames[1]),
+        ];
+        create_instance_with_cache(
+            vec![],
+            &named_ast_modules,
+            InterpreterKind::Stack,
+            &module_cache,
+        )
+        .unwrap();
+
+        // 只根据模块的二进制内容计算缓存键，无需解析模块
+        let named_content_hashes = [
+            ("m0", hash_bytes(&get_test_binary_resource(filenames[0]))),
+            ("m1", hash_bytes(&get_test_binary_resource(filenames[1]))),
+        ];
+        let key = ModuleCache::compute_key_by_content_hashes(&[], &named_content_hashes);
+        assert_eq!(ModuleCache::compute_key(&[], &named_ast_modules), Some(key));
+
+        let (cached_modules, compiled_modules) = module_cache.load(&key).unwrap();
+        assert_eq!(cached_modules, named_ast_modules);
+
+        let mut cached_vm = create_instance_with_compiled_modules(
+            vec![],
+            &cached_modules,
+            compiled_modules,
+            InterpreterKind::Stack,
+        )
+        .unwrap();
+        let mut vm = create_instance(vec![], &named_ast_modules).unwrap();
+
+        for index in 0..6 {
+            let args = [Value::I32(index)];
+            assert_eq!(
+                cached_vm.eval_function_by_index(1, 0, &args).unwrap(),
+                vm.eval_function_by_index(1, 0, &args).unwrap()
+            );
+        }
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_encode_and_decode_modules() {
+        // 缓存文件能完整地记录所有测试模块（包括自定义段、SIMD 指令和异常处理指令等）
+        for filename in get_test_resource_filenames() {
+            let module = parser::parse(&get_test_binary_resource(&filename)).unwrap();
+
+            let mut writer = Writer::new();
+            writer.write_module(&module);
+
+            let mut reader = Reader::new(&writer.bytes);
+            assert_eq!(reader.read_module().unwrap(), module, "{}", filename);
+            assert!(reader.is_end());
+        }
+    }
+
+    #[test]
+    fn test_invalidate_stale_entry() {
+        let directory = create_test_cache_directory("stale");
+        let module_cache = ModuleCache::new(&directory);
+
+        let named_ast_modules = vec![get_named_ast_module("test", "test-block-loop.wasm")];
+        let key = ModuleCache::compute_key(&[], &named_ast_modules).unwrap();
+        let path = module_cache.get_entry_path(&key);
+
+        let function_items_list = link_functions(&[], &named_ast_modules).unwrap();
+        let (instructions_list, _) = decode(&named_ast_modules, &function_items_list).unwrap();
+        module_cache
+            .store(
+                &key,
+                &named_ast_modules,
+                &function_items_list,
+                &instructions_list,
+            )
+            .unwrap();
+        let bytes = fs::read(&path).unwrap();
+
+        // 缓存格式的版本号不一致（同时更新校验值）
+        let mut stale_bytes = bytes[..bytes.len() - 32].to_vec();
+        stale_bytes[8] = stale_bytes[8].wrapping_add(1);
+        let checksum = hash_bytes(&stale_bytes);
+        stale_bytes.extend_from_slice(&checksum);
+        fs::write(&path, stale_bytes).unwrap();
+        assert_eq!(module_cache.load(&key), None);
+        assert!(!path.exists());
+
+        // 文件内容不完整
+        fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+        assert_eq!(module_cache.load(&key), None);
+        assert!(!path.exists());
+
+        // 文件内容损坏，比如控制指令里的跳转目标被修改
+        let mut corrupted_bytes = bytes.clone();
+        let middle = corrupted_bytes.len() / 2;
+        corrupted_bytes[middle] ^= 0x01;
+        fs::write(&path, corrupted_bytes).unwrap();
+        assert_eq!(module_cache.load(&key), None);
+        assert!(!path.exists());
+
+        // 过期的缓存被删除之后，实例化时会重新写入缓存
+        create_instance_with_cache(
+            vec![],
+            &named_ast_modules,
+            InterpreterKind::Stack,
+            &module_cache,
+        )
+        .unwrap();
+        assert_eq!(fs::read(&path).unwrap(), bytes);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
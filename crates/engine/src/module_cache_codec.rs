@@ -0,0 +1,2474 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+// # 编译模块缓存文件的编码
+//
+// 缓存文件记录了 AST 模块、函数项以及解码之后的控制指令（见 `module_cache` 模块）。
+//
+// 整数均使用小端序，usize 按 u64 写入，Option 和 Vec 分别先写入一个标记和长度，
+// 枚举先写入一个表示成员的标记。AST 指令的标记是该指令在 `instruction::Instruction`
+// 里的定义顺序，所以增删或者调整指令的顺序时，需要增加缓存格式的版本号。
+
+use std::io::{self, ErrorKind};
+
+use anvm_ast::{
+    ast::{
+        CodeItem, CustomItem, DataItem, DataMode, DynamicLinking, DynamicLinkingExportInfo,
+        DynamicLinkingImportInfo, DynamicLinkingMemoryInfo, ElementItem, ElementItems, ElementMode,
+        ExportDescriptor, ExportItem, FunctionIndexAndBlockLabelsPair,
+        FunctionIndexAndLocalVariableNamesPair, FunctionType, GlobalItem, GlobalType,
+        ImportDescriptor, ImportItem, IndexNamePair, Limit, LocalGroup, MemoryType, Module,
+        NameCollection, TableType, TagType, TypeItem,
+    },
+    instruction::{BlockType, CatchClause, Instruction, MemoryArgument},
+    types::{RefType, ValueType},
+};
+
+use crate::object::{BlockItem, BranchTarget, CatchItem, Control, FunctionItem};
+
+pub fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message)
+}
+
+pub struct Writer {
+    pub bytes: Vec<u8>,
+}
+
+impl Writer {
+    pub fn new() -> Self {
+        Self { bytes: vec![] }
+    }
+
+    pub fn write_u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    pub fn write_u16(&mut self, value: u16) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_u64(&mut self, value: u64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+
+    pub fn write_bool(&mut self, value: bool) {
+        self.write_u8(value as u8);
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    /// 写入长度以及内容
+    pub fn write_byte_list(&mut self, bytes: &[u8]) {
+        self.write_usize(bytes.len());
+        self.write_bytes(bytes);
+    }
+
+    pub fn write_str(&mut self, value: &str) {
+        self.write_byte_list(value.as_bytes());
+    }
+
+    pub fn write_list<T>(&mut self, items: &[T], write_item: fn(&mut Self, &T)) {
+        self.write_usize(items.len());
+        for item in items {
+            write_item(self, item);
+        }
+    }
+
+    fn write_u32_list(&mut self, values: &[u32]) {
+        self.write_list(values, |writer, value| writer.write_u32(*value));
+    }
+
+    fn write_option_u32(&mut self, option_value: Option<u32>) {
+        match option_value {
+            Some(value) => {
+                self.write_u8(1);
+                self.write_u32(value);
+            }
+            None => self.write_u8(0),
+        }
+    }
+
+    fn write_option_usize(&mut self, option_value: Option<usize>) {
+        match option_value {
+            Some(value) => {
+                self.write_u8(1);
+                self.write_usize(value);
+            }
+            None => self.write_u8(0),
+        }
+    }
+
+    fn write_value_type(&mut self, value_type: &ValueType) {
+        self.write_u8(value_type_to_u8(value_type));
+    }
+
+    fn write_value_types(&mut self, value_types: &[ValueType]) {
+        self.write_list(value_types, Self::write_value_type);
+    }
+
+    fn write_ref_type(&mut self, ref_type: &RefType) {
+        match ref_type {
+            RefType::FuncRef => self.write_u8(0),
+            RefType::ExternRef => self.write_u8(1),
+            RefType::ExnRef => self.write_u8(2),
+        }
+    }
+
+    fn write_block_type(&mut self, block_type: &BlockType) {
+        match block_type {
+            BlockType::ResultI32 => self.write_u8(0),
+            BlockType::ResultI64 => self.write_u8(1),
+            BlockType::ResultF32 => self.write_u8(2),
+            BlockType::ResultF64 => self.write_u8(3),
+            BlockType::ResultV128 => self.write_u8(4),
+            BlockType::ResultFuncRef => self.write_u8(5),
+            BlockType::ResultExternRef => self.write_u8(6),
+            BlockType::ResultExnRef => self.write_u8(7),
+            BlockType::ResultEmpty => self.write_u8(8),
+            BlockType::TypeIndex(type_index) => {
+                self.write_u8(9);
+                self.write_u32(*type_index);
+            }
+        }
+    }
+
+    // AST 模块
+
+    pub fn write_module(&mut self, module: &Module) {
+        self.write_list(&module.custom_items, Self::write_custom_item);
+        self.write_list(&module.type_items, |writer, type_item| match type_item {
+            TypeItem::FunctionType(function_type) => writer.write_function_type(function_type),
+        });
+        self.write_list(&module.import_items, Self::write_import_item);
+        self.write_u32_list(&module.internal_function_to_type_index_list);
+        self.write_list(&module.tables, Self::write_table_type);
+        self.write_list(&module.memory_blocks, Self::write_memory_type);
+        self.write_list(&module.tag_items, |writer, tag_type| {
+            writer.write_u32(tag_type.type_index)
+        });
+        self.write_list(&module.global_items, |writer, global_item| {
+            writer.write_global_type(&global_item.global_type);
+            writer.write_instructions(&global_item.initialize_instruction_items);
+        });
+        self.write_list(&module.export_items, Self::write_export_item);
+        self.write_option_u32(module.start_function_index);
+        self.write_list(&module.element_items, Self::write_element_item);
+        self.write_list(&module.code_items, |writer, code_item| {
+            writer.write_list(&code_item.local_groups, |writer, local_group| {
+                writer.write_u32(local_group.variable_count);
+                writer.write_value_type(&local_group.value_type);
+            });
+            writer.write_instructions(&code_item.instruction_items);
+        });
+        self.write_list(&module.data_items, Self::write_data_item);
+        self.write_option_u32(module.data_count);
+    }
+
+    fn write_custom_item(&mut self, custom_item: &CustomItem) {
+        match custom_item {
+            CustomItem::NameCollections(name_collections) => {
+                self.write_u8(0);
+                self.write_list(name_collections, Self::write_name_collection);
+            }
+            CustomItem::DynamicLinking(dynamic_linking) => {
+                self.write_u8(1);
+                self.write_dynamic_linking(dynamic_linking);
+            }
+            CustomItem::Other(name, data) => {
+                self.write_u8(2);
+                self.write_str(name);
+                self.write_byte_list(data);
+            }
+        }
+    }
+
+    fn write_index_name_pairs(&mut self, index_name_pairs: &[IndexNamePair]) {
+        self.write_list(index_name_pairs, |writer, index_name_pair| {
+            writer.write_u32(index_name_pair.index);
+            writer.write_str(&index_name_pair.name);
+        });
+    }
+
+    fn write_name_collection(&mut self, name_collection: &NameCollection) {
+        match name_collection {
+            NameCollection::TypeNames(pairs) => {
+                self.write_u8(0);
+                self.write_index_name_pairs(pairs);
+            }
+            NameCollection::FunctionNames(pairs) => {
+                self.write_u8(1);
+                self.write_index_name_pairs(pairs);
+            }
+            NameCollection::LocalVariableNamesPairList(pairs) => {
+                self.write_u8(2);
+                self.write_list(pairs, |writer, pair| {
+                    writer.write_u32(pair.function_index);
+                    writer.write_index_name_pairs(&pair.local_variable_names);
+                });
+            }
+            NameCollection::BlockLabelsPairList(pairs) => {
+                self.write_u8(3);
+                self.write_list(pairs, |writer, pair| {
+                    writer.write_u32(pair.function_index);
+                    writer.write_index_name_pairs(&pair.block_labels);
+                });
+            }
+            NameCollection::GlobalVariableNames(pairs) => {
+                self.write_u8(4);
+                self.write_index_name_pairs(pairs);
+            }
+            NameCollection::MemoryBlockNames(pairs) => {
+                self.write_u8(5);
+                self.write_index_name_pairs(pairs);
+            }
+            NameCollection::TableNames(pairs) => {
+                self.write_u8(6);
+                self.write_index_name_pairs(pairs);
+            }
+            NameCollection::ElementNames(pairs) => {
+                self.write_u8(7);
+                self.write_index_name_pairs(pairs);
+            }
+            NameCollection::DataNames(pairs) => {
+                self.write_u8(8);
+                self.write_index_name_pairs(pairs);
+            }
+            NameCollection::TagNames(pairs) => {
+                self.write_u8(9);
+                self.write_index_name_pairs(pairs);
+            }
+        }
+    }
+
+    fn write_dynamic_linking(&mut self, dynamic_linking: &DynamicLinking) {
+        match &dynamic_linking.memory_info {
+            Some(memory_info) => {
+                self.write_u8(1);
+                self.write_u32(memory_info.memory_size);
+                self.write_u32(memory_info.memory_alignment);
+                self.write_u32(memory_info.table_size);
+                self.write_u32(memory_info.table_alignment);
+            }
+            None => self.write_u8(0),
+        }
+        self.write_list(&dynamic_linking.needed, |writer, name| {
+            writer.write_str(name)
+        });
+        self.write_list(&dynamic_linking.export_infos, |writer, export_info| {
+            writer.write_str(&export_info.name);
+            writer.write_u32(export_info.flags);
+        });
+        self.write_list(&dynamic_linking.import_infos, |writer, import_info| {
+            writer.write_str(&import_info.module_name);
+            writer.write_str(&import_info.item_name);
+            writer.write_u32(import_info.flags);
+        });
+        self.write_list(&dynamic_linking.runtime_paths, |writer, path| {
+            writer.write_str(path)
+        });
+    }
+
+    fn write_function_type(&mut self, function_type: &FunctionType) {
+        self.write_value_types(&function_type.params);
+        self.write_value_types(&function_type.results);
+    }
+
+    fn write_limit(&mut self, limit: &Limit) {
+        match limit {
+            Limit::Range(min, max) => {
+                self.write_u8(0);
+                self.write_u64(*min);
+                self.write_u64(*max);
+            }
+            Limit::AtLeast(min) => {
+                self.write_u8(1);
+                self.write_u64(*min);
+            }
+        }
+    }
+
+    fn write_table_type(&mut self, table_type: &TableType) {
+        self.write_ref_type(&table_type.element_type);
+        self.write_limit(&table_type.limit);
+    }
+
+    fn write_memory_type(&mut self, memory_type: &MemoryType) {
+        self.write_limit(&memory_type.limit);
+        self.write_bool(memory_type.shared);
+        self.write_bool(memory_type.memory64);
+    }
+
+    fn write_global_type(&mut self, global_type: &GlobalType) {
+        self.write_value_type(&global_type.value_type);
+        self.write_bool(global_type.mutable);
+    }
+
+    fn write_import_item(&mut self, import_item: &ImportItem) {
+        self.write_str(&import_item.module_name);
+        self.write_str(&import_item.item_name);
+        match &import_item.import_descriptor {
+            ImportDescriptor::FunctionTypeIndex(type_index) => {
+                self.write_u8(0);
+                self.write_u32(*type_index);
+            }
+            ImportDescriptor::TableType(table_type) => {
+                self.write_u8(1);
+                self.write_table_type(table_type);
+            }
+            ImportDescriptor::MemoryType(memory_type) => {
+                self.write_u8(2);
+                self.write_memory_type(memory_type);
+            }
+            ImportDescriptor::GlobalType(global_type) => {
+                self.write_u8(3);
+                self.write_global_type(global_type);
+            }
+            ImportDescriptor::TagType(tag_type) => {
+                self.write_u8(4);
+                self.write_u32(tag_type.type_index);
+            }
+        }
+    }
+
+    fn write_export_item(&mut self, export_item: &ExportItem) {
+        self.write_str(&export_item.name);
+        let (tag, index) = match export_item.export_descriptor {
+            ExportDescriptor::FunctionIndex(index) => (0, index),
+            ExportDescriptor::TableIndex(index) => (1, index),
+            ExportDescriptor::MemoryBlockIndex(index) => (2, index),
+            ExportDescriptor::GlobalItemIndex(index) => (3, index),
+            ExportDescriptor::TagIndex(index) => (4, index),
+        };
+        self.write_u8(tag);
+        self.write_u32(index);
+    }
+
+    fn write_element_item(&mut self, element_item: &ElementItem) {
+        match &element_item.mode {
+            ElementMode::Active {
+                table_index,
+                offset_instruction_items,
+            } => {
+                self.write_u8(0);
+                self.write_u32(*table_index);
+                self.write_instructions(offset_instruction_items);
+            }
+            ElementMode::Passive => self.write_u8(1),
+            ElementMode::Declarative => self.write_u8(2),
+        }
+        self.write_ref_type(&element_item.element_type);
+        match &element_item.items {
+            ElementItems::FunctionIndices(function_indices) => {
+                self.write_u8(0);
+                self.write_u32_list(function_indices);
+            }
+            ElementItems::Expressions(expressions) => {
+                self.write_u8(1);
+                self.write_list(expressions, |writer, instructions| {
+                    writer.write_instructions(instructions)
+                });
+            }
+        }
+    }
+
+    fn write_data_item(&mut self, data_item: &DataItem) {
+        match &data_item.mode {
+            DataMode::Active {
+                memory_block_index,
+                offset_instruction_items,
+            } => {
+                self.write_u8(0);
+                self.write_u32(*memory_block_index);
+                self.write_instructions(offset_instruction_items);
+            }
+            DataMode::Passive => self.write_u8(1),
+        }
+        self.write_byte_list(&data_item.data);
+    }
+
+    fn write_memory_argument(&mut self, memory_argument: &MemoryArgument) {
+        self.write_u32(memory_argument.align);
+        self.write_u64(memory_argument.offset);
+        self.write_u32(memory_argument.memory_block_index);
+    }
+
+    fn write_catch_clause(&mut self, catch_clause: &CatchClause) {
+        match catch_clause {
+            CatchClause::Catch(tag_index, relative_depth) => {
+                self.write_u8(0);
+                self.write_u32(*tag_index);
+                self.write_u32(*relative_depth);
+            }
+            CatchClause::CatchRef(tag_index, relative_depth) => {
+                self.write_u8(1);
+                self.write_u32(*tag_index);
+                self.write_u32(*relative_depth);
+            }
+            CatchClause::CatchAll(relative_depth) => {
+                self.write_u8(2);
+                self.write_u32(*relative_depth);
+            }
+            CatchClause::CatchAllRef(relative_depth) => {
+                self.write_u8(3);
+                self.write_u32(*relative_depth);
+            }
+        }
+    }
+
+    fn write_instructions(&mut self, instructions: &[Instruction]) {
+        self.write_list(instructions, Self::write_instruction);
+    }
+
+    /// 写入 AST 指令，先写入指令的标记，然后是指令的参数
+    fn write_instruction(&mut self, instruction: &Instruction) {
+        self.write_u16(get_instruction_tag(instruction));
+
+        match instruction {
+            Instruction::Block(block_type, block_label_index)
+            | Instruction::Loop(block_type, block_label_index)
+            | Instruction::If(block_type, block_label_index) => {
+                self.write_block_type(block_type);
+                self.write_u32(*block_label_index);
+            }
+            Instruction::TryTable(block_type, catch_clauses, block_label_index) => {
+                self.write_block_type(block_type);
+                self.write_usize(catch_clauses.len());
+                for catch_clause in catch_clauses {
+                    self.write_catch_clause(catch_clause);
+                }
+                self.write_u32(*block_label_index);
+            }
+            Instruction::BrTable(relative_depths, default_relative_depth) => {
+                self.write_u32_list(relative_depths);
+                self.write_u32(*default_relative_depth);
+            }
+            Instruction::Br(value)
+            | Instruction::BrIf(value)
+            | Instruction::Call(value)
+            | Instruction::ReturnCall(value)
+            | Instruction::Throw(value)
+            | Instruction::LocalGet(value)
+            | Instruction::LocalSet(value)
+            | Instruction::LocalTee(value)
+            | Instruction::GlobalGet(value)
+            | Instruction::GlobalSet(value)
+            | Instruction::MemorySize(value)
+            | Instruction::MemoryGrow(value)
+            | Instruction::DataDrop(value)
+            | Instruction::MemoryFill(value)
+            | Instruction::TableGet(value)
+            | Instruction::TableSet(value)
+            | Instruction::ElementDrop(value)
+            | Instruction::TableGrow(value)
+            | Instruction::TableSize(value)
+            | Instruction::TableFill(value)
+            | Instruction::RefFunc(value) => self.write_u32(*value),
+            Instruction::CallIndirect(first, second)
+            | Instruction::ReturnCallIndirect(first, second)
+            | Instruction::MemoryInit(first, second)
+            | Instruction::MemoryCopy(first, second)
+            | Instruction::TableInit(first, second)
+            | Instruction::TableCopy(first, second) => {
+                self.write_u32(*first);
+                self.write_u32(*second);
+            }
+            Instruction::SelectTyped(value_types) => self.write_value_types(value_types),
+            Instruction::I32Load(memory_argument)
+            | Instruction::I64Load(memory_argument)
+            | Instruction::F32Load(memory_argument)
+            | Instruction::F64Load(memory_argument)
+            | Instruction::I32Load8S(memory_argument)
+            | Instruction::I32Load8U(memory_argument)
+            | Instruction::I32Load16S(memory_argument)
+            | Instruction::I32Load16U(memory_argument)
+            | Instruction::I64Load8S(memory_argument)
+            | Instruction::I64Load8U(memory_argument)
+            | Instruction::I64Load16S(memory_argument)
+            | Instruction::I64Load16U(memory_argument)
+            | Instruction::I64Load32S(memory_argument)
+            | Instruction::I64Load32U(memory_argument)
+            | Instruction::I32Store(memory_argument)
+            | Instruction::I64Store(memory_argument)
+            | Instruction::F32Store(memory_argument)
+            | Instruction::F64Store(memory_argument)
+            | Instruction::I32Store8(memory_argument)
+            | Instruction::I32Store16(memory_argument)
+            | Instruction::I64Store8(memory_argument)
+            | Instruction::I64Store16(memory_argument)
+            | Instruction::I64Store32(memory_argument)
+            | Instruction::V128Load(memory_argument)
+            | Instruction::V128Load8x8S(memory_argument)
+            | Instruction::V128Load8x8U(memory_argument)
+            | Instruction::V128Load16x4S(memory_argument)
+            | Instruction::V128Load16x4U(memory_argument)
+            | Instruction::V128Load32x2S(memory_argument)
+            | Instruction::V128Load32x2U(memory_argument)
+            | Instruction::V128Load8Splat(memory_argument)
+            | Instruction::V128Load16Splat(memory_argument)
+            | Instruction::V128Load32Splat(memory_argument)
+            | Instruction::V128Load64Splat(memory_argument)
+            | Instruction::V128Store(memory_argument)
+            | Instruction::V128Load32Zero(memory_argument)
+            | Instruction::V128Load64Zero(memory_argument)
+            | Instruction::MemoryAtomicNotify(memory_argument)
+            | Instruction::MemoryAtomicWait32(memory_argument)
+            | Instruction::MemoryAtomicWait64(memory_argument)
+            | Instruction::I32AtomicLoad(memory_argument)
+            | Instruction::I64AtomicLoad(memory_argument)
+            | Instruction::I32AtomicLoad8U(memory_argument)
+            | Instruction::I32AtomicLoad16U(memory_argument)
+            | Instruction::I64AtomicLoad8U(memory_argument)
+            | Instruction::I64AtomicLoad16U(memory_argument)
+            | Instruction::I64AtomicLoad32U(memory_argument)
+            | Instruction::I32AtomicStore(memory_argument)
+            | Instruction::I64AtomicStore(memory_argument)
+            | Instruction::I32AtomicStore8(memory_argument)
+            | Instruction::I32AtomicStore16(memory_argument)
+            | Instruction::I64AtomicStore8(memory_argument)
+            | Instruction::I64AtomicStore16(memory_argument)
+            | Instruction::I64AtomicStore32(memory_argument)
+            | Instruction::I32AtomicRmwAdd(memory_argument)
+            | Instruction::I64AtomicRmwAdd(memory_argument)
+            | Instruction::I32AtomicRmw8AddU(memory_argument)
+            | Instruction::I32AtomicRmw16AddU(memory_argument)
+            | Instruction::I64AtomicRmw8AddU(memory_argument)
+            | Instruction::I64AtomicRmw16AddU(memory_argument)
+            | Instruction::I64AtomicRmw32AddU(memory_argument)
+            | Instruction::I32AtomicRmwSub(memory_argument)
+            | Instruction::I64AtomicRmwSub(memory_argument)
+            | Instruction::I32AtomicRmw8SubU(memory_argument)
+            | Instruction::I32AtomicRmw16SubU(memory_argument)
+            | Instruction::I64AtomicRmw8SubU(memory_argument)
+            | Instruction::I64AtomicRmw16SubU(memory_argument)
+            | Instruction::I64AtomicRmw32SubU(memory_argument)
+            | Instruction::I32AtomicRmwAnd(memory_argument)
+            | Instruction::I64AtomicRmwAnd(memory_argument)
+            | Instruction::I32AtomicRmw8AndU(memory_argument)
+            | Instruction::I32AtomicRmw16AndU(memory_argument)
+            | Instruction::I64AtomicRmw8AndU(memory_argument)
+            | Instruction::I64AtomicRmw16AndU(memory_argument)
+            | Instruction::I64AtomicRmw32AndU(memory_argument)
+            | Instruction::I32AtomicRmwOr(memory_argument)
+            | Instruction::I64AtomicRmwOr(memory_argument)
+            | Instruction::I32AtomicRmw8OrU(memory_argument)
+            | Instruction::I32AtomicRmw16OrU(memory_argument)
+            | Instruction::I64AtomicRmw8OrU(memory_argument)
+            | Instruction::I64AtomicRmw16OrU(memory_argument)
+            | Instruction::I64AtomicRmw32OrU(memory_argument)
+            | Instruction::I32AtomicRmwXor(memory_argument)
+            | Instruction::I64AtomicRmwXor(memory_argument)
+            | Instruction::I32AtomicRmw8XorU(memory_argument)
+            | Instruction::I32AtomicRmw16XorU(memory_argument)
+            | Instruction::I64AtomicRmw8XorU(memory_argument)
+            | Instruction::I64AtomicRmw16XorU(memory_argument)
+            | Instruction::I64AtomicRmw32XorU(memory_argument)
+            | Instruction::I32AtomicRmwXchg(memory_argument)
+            | Instruction::I64AtomicRmwXchg(memory_argument)
+            | Instruction::I32AtomicRmw8XchgU(memory_argument)
+            | Instruction::I32AtomicRmw16XchgU(memory_argument)
+            | Instruction::I64AtomicRmw8XchgU(memory_argument)
+            | Instruction::I64AtomicRmw16XchgU(memory_argument)
+            | Instruction::I64AtomicRmw32XchgU(memory_argument)
+            | Instruction::I32AtomicRmwCmpxchg(memory_argument)
+            | Instruction::I64AtomicRmwCmpxchg(memory_argument)
+            | Instruction::I32AtomicRmw8CmpxchgU(memory_argument)
+            | Instruction::I32AtomicRmw16CmpxchgU(memory_argument)
+            | Instruction::I64AtomicRmw8CmpxchgU(memory_argument)
+            | Instruction::I64AtomicRmw16CmpxchgU(memory_argument)
+            | Instruction::I64AtomicRmw32CmpxchgU(memory_argument) => {
+                self.write_memory_argument(memory_argument)
+            }
+            Instruction::V128Load8Lane(memory_argument, lane_index)
+            | Instruction::V128Load16Lane(memory_argument, lane_index)
+            | Instruction::V128Load32Lane(memory_argument, lane_index)
+            | Instruction::V128Load64Lane(memory_argument, lane_index)
+            | Instruction::V128Store8Lane(memory_argument, lane_index)
+            | Instruction::V128Store16Lane(memory_argument, lane_index)
+            | Instruction::V128Store32Lane(memory_argument, lane_index)
+            | Instruction::V128Store64Lane(memory_argument, lane_index) => {
+                self.write_memory_argument(memory_argument);
+                self.write_u8(*lane_index);
+            }
+            Instruction::RefNull(ref_type) => self.write_ref_type(ref_type),
+            Instruction::I32Const(value) => self.write_u32(*value as u32),
+            Instruction::I64Const(value) => self.write_u64(*value as u64),
+            Instruction::F32Const(value) => self.write_u32(value.to_bits()),
+            Instruction::F64Const(value) => self.write_u64(value.to_bits()),
+            Instruction::V128Const(value) => self.write_bytes(&value.to_le_bytes()),
+            Instruction::I8x16Shuffle(lane_indices) => self.write_bytes(lane_indices),
+            Instruction::I8x16ExtractLaneS(lane_index)
+            | Instruction::I8x16ExtractLaneU(lane_index)
+            | Instruction::I8x16ReplaceLane(lane_index)
+            | Instruction::I16x8ExtractLaneS(lane_index)
+            | Instruction::I16x8ExtractLaneU(lane_index)
+            | Instruction::I16x8ReplaceLane(lane_index)
+            | Instruction::I32x4ExtractLane(lane_index)
+            | Instruction::I32x4ReplaceLane(lane_index)
+            | Instruction::I64x2ExtractLane(lane_index)
+            | Instruction::I64x2ReplaceLane(lane_index)
+            | Instruction::F32x4ExtractLane(lane_index)
+            | Instruction::F32x4ReplaceLane(lane_index)
+            | Instruction::F64x2ExtractLane(lane_index)
+            | Instruction::F64x2ReplaceLane(lane_index) => self.write_u8(*lane_index),
+            // 其余指令没有参数
+            _ => {}
+        }
+    }
+
+    // 链接和解码的结果
+
+    pub fn write_function_item(&mut self, function_item: &FunctionItem) {
+        match function_item {
+            FunctionItem::Native {
+                native_module_index,
+                type_index,
+                function_index,
+            } => {
+                self.write_u8(0);
+                self.write_usize(*native_module_index);
+                self.write_usize(*type_index);
+                self.write_usize(*function_index);
+            }
+            FunctionItem::Normal {
+                vm_module_index,
+                type_index,
+                function_index,
+                internal_function_index,
+                start_address,
+                end_address,
+                block_items,
+            } => {
+                self.write_u8(1);
+                self.write_usize(*vm_module_index);
+                self.write_usize(*type_index);
+                self.write_usize(*function_index);
+                self.write_usize(*internal_function_index);
+                self.write_usize(*start_address);
+                self.write_usize(*end_address);
+
+                self.write_usize(block_items.len());
+                for block_item in block_items {
+                    self.write_block_item(block_item);
+                }
+            }
+        }
+    }
+
+    fn write_block_item(&mut self, block_item: &BlockItem) {
+        match block_item {
+            BlockItem::Block {
+                block_type,
+                start_address,
+                end_address,
+            } => {
+                self.write_u8(0);
+                self.write_block_type(block_type);
+                self.write_usize(*start_address);
+                self.write_usize(*end_address);
+            }
+            BlockItem::Loop {
+                block_type,
+                start_address,
+                end_address,
+            } => {
+                self.write_u8(1);
+                self.write_block_type(block_type);
+                self.write_usize(*start_address);
+                self.write_usize(*end_address);
+            }
+            BlockItem::If {
+                block_type,
+                start_address,
+                end_address,
+                alternate_address,
+            } => {
+                self.write_u8(2);
+                self.write_block_type(block_type);
+                self.write_usize(*start_address);
+                self.write_usize(*end_address);
+                self.write_option_usize(*alternate_address);
+            }
+        }
+    }
+
+    fn write_branch_target(&mut self, branch_target: &BranchTarget) {
+        self.write_usize(branch_target.relative_depth);
+        self.write_usize(branch_target.stack_height);
+        self.write_usize(branch_target.arity);
+        self.write_usize(branch_target.address);
+    }
+
+    pub fn write_control(&mut self, control: &Control) {
+        match control {
+            Control::Block {
+                block_type,
+                block_index,
+                end_address,
+            } => {
+                self.write_u8(0);
+                self.write_block_type(block_type);
+                self.write_usize(*block_index);
+                self.write_usize(*end_address);
+            }
+            Control::BlockAndJumpWhenEqZero {
+                block_type,
+                block_index,
+                option_alternate_address,
+                end_address,
+            } => {
+                self.write_u8(1);
+                self.write_block_type(block_type);
+                self.write_usize(*block_index);
+                self.write_option_usize(*option_alternate_address);
+                self.write_usize(*end_address);
+            }
+            Control::TryTable {
+                block_type,
+                block_index,
+                end_address,
+                catch_items,
+            } => {
+                self.write_u8(2);
+                self.write_block_type(block_type);
+                self.write_usize(*block_index);
+                self.write_usize(*end_address);
+
+                self.write_usize(catch_items.len());
+                for catch_item in catch_items {
+                    self.write_option_usize(catch_item.option_tag_index);
+                    self.write_bool(catch_item.is_ref);
+                    self.write_branch_target(&catch_item.branch_target);
+                }
+            }
+            Control::JumpWithinBlock(address) => {
+                self.write_u8(3);
+                self.write_usize(*address);
+            }
+            Control::Break {
+                option_block_index,
+                branch_target,
+            } => {
+                self.write_u8(4);
+                self.write_option_usize(*option_block_index);
+                self.write_branch_target(branch_target);
+            }
+            Control::BreakWhenNotEqZero {
+                option_block_index,
+                branch_target,
+            } => {
+                self.write_u8(5);
+                self.write_option_usize(*option_block_index);
+                self.write_branch_target(branch_target);
+            }
+            Control::Branch {
+                option_block_index,
+                branch_targets,
+                default_branch_target,
+            } => {
+                self.write_u8(6);
+                self.write_option_usize(*option_block_index);
+
+                self.write_usize(branch_targets.len());
+                for branch_target in branch_targets {
+                    self.write_branch_target(branch_target);
+                }
+                self.write_branch_target(default_branch_target);
+            }
+            Control::Call {
+                vm_module_index,
+                type_index,
+                function_index,
+                internal_function_index,
+                address,
+            } => {
+                self.write_u8(7);
+                self.write_usize(*vm_module_index);
+                self.write_usize(*type_index);
+                self.write_usize(*function_index);
+                self.write_usize(*internal_function_index);
+                self.write_usize(*address);
+            }
+            Control::CallNative {
+                native_module_index,
+                type_index,
+                function_index,
+            } => {
+                self.write_u8(8);
+                self.write_usize(*native_module_index);
+                self.write_usize(*type_index);
+                self.write_usize(*function_index);
+            }
+            Control::CallIndirect {
+                type_index,
+                table_index,
+            } => {
+                self.write_u8(9);
+                self.write_usize(*type_index);
+                self.write_usize(*table_index);
+            }
+            Control::ReturnCall {
+                vm_module_index,
+                type_index,
+                function_index,
+                internal_function_index,
+                address,
+            } => {
+                self.write_u8(10);
+                self.write_usize(*vm_module_index);
+                self.write_usize(*type_index);
+                self.write_usize(*function_index);
+                self.write_usize(*internal_function_index);
+                self.write_usize(*address);
+            }
+            Control::ReturnCallNative {
+                native_module_index,
+                type_index,
+                function_index,
+            } => {
+                self.write_u8(11);
+                self.write_usize(*native_module_index);
+                self.write_usize(*type_index);
+                self.write_usize(*function_index);
+            }
+            Control::ReturnCallIndirect {
+                type_index,
+                table_index,
+            } => {
+                self.write_u8(12);
+                self.write_usize(*type_index);
+                self.write_usize(*table_index);
+            }
+            Control::Throw(tag_index) => {
+                self.write_u8(13);
+                self.write_usize(*tag_index);
+            }
+            Control::ThrowRef => self.write_u8(14),
+            Control::End(option_block_index) => {
+                self.write_u8(15);
+                self.write_option_usize(*option_block_index);
+            }
+            Control::Unreachable => self.write_u8(16),
+            Control::Nop => self.write_u8(17),
+        }
+    }
+}
+
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    pub fn is_end(&self) -> bool {
+        self.position == self.bytes.len()
+    }
+
+    pub fn read_bytes(&mut self, length: usize) -> io::Result<&'a [u8]> {
+        let end = self
+            .position
+            .checked_add(length)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| invalid_data("unexpected end of data"))?;
+        let bytes = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    pub fn read_u8(&mut self) -> io::Result<u8> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    pub fn read_u16(&mut self) -> io::Result<u16> {
+        Ok(u16::from_le_bytes(self.read_bytes(2)?.try_into().unwrap()))
+    }
+
+    pub fn read_u32(&mut self) -> io::Result<u32> {
+        Ok(u32::from_le_bytes(self.read_bytes(4)?.try_into().unwrap()))
+    }
+
+    pub fn read_u64(&mut self) -> io::Result<u64> {
+        Ok(u64::from_le_bytes(self.read_bytes(8)?.try_into().unwrap()))
+    }
+
+    pub fn read_usize(&mut self) -> io::Result<usize> {
+        usize::try_from(self.read_u64()?).map_err(|_| invalid_data("value out of range"))
+    }
+
+    fn read_bool(&mut self) -> io::Result<bool> {
+        match self.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(invalid_data("invalid bool")),
+        }
+    }
+
+    /// 读取列表的长度
+    ///
+    /// 每个元素至少占一个字节，所以长度不会超过剩余的字节数，以免损坏的文件导致分配过多的内存。
+    pub fn read_length(&mut self) -> io::Result<usize> {
+        let length = self.read_usize()?;
+        if length > self.bytes.len() - self.position {
+            return Err(invalid_data("invalid length"));
+        }
+        Ok(length)
+    }
+
+    pub fn read_byte_list(&mut self) -> io::Result<Vec<u8>> {
+        let length = self.read_length()?;
+        Ok(self.read_bytes(length)?.to_vec())
+    }
+
+    pub fn read_string(&mut self) -> io::Result<String> {
+        String::from_utf8(self.read_byte_list()?).map_err(|_| invalid_data("invalid string"))
+    }
+
+    pub fn read_list<T>(
+        &mut self,
+        read_item: fn(&mut Self) -> io::Result<T>,
+    ) -> io::Result<Vec<T>> {
+        let length = self.read_length()?;
+        (0..length).map(|_| read_item(self)).collect()
+    }
+
+    fn read_u32_list(&mut self) -> io::Result<Vec<u32>> {
+        self.read_list(Self::read_u32)
+    }
+
+    fn read_option_u32(&mut self) -> io::Result<Option<u32>> {
+        match self.read_u8()? {
+            0 => Ok(None),
+            1 => Ok(Some(self.read_u32()?)),
+            _ => Err(invalid_data("invalid option tag")),
+        }
+    }
+
+    fn read_option_usize(&mut self) -> io::Result<Option<usize>> {
+        match self.read_u8()? {
+            0 => Ok(None),
+            1 => Ok(Some(self.read_usize()?)),
+            _ => Err(invalid_data("invalid option tag")),
+        }
+    }
+
+    fn read_value_type(&mut self) -> io::Result<ValueType> {
+        let value_type = match self.read_u8()? {
+            0 => ValueType::I32,
+            1 => ValueType::I64,
+            2 => ValueType::F32,
+            3 => ValueType::F64,
+            4 => ValueType::V128,
+            5 => ValueType::FuncRef,
+            6 => ValueType::ExternRef,
+            7 => ValueType::ExnRef,
+            _ => return Err(invalid_data("invalid value type")),
+        };
+        Ok(value_type)
+    }
+
+    fn read_value_types(&mut self) -> io::Result<Vec<ValueType>> {
+        self.read_list(Self::read_value_type)
+    }
+
+    fn read_ref_type(&mut self) -> io::Result<RefType> {
+        let ref_type = match self.read_u8()? {
+            0 => RefType::FuncRef,
+            1 => RefType::ExternRef,
+            2 => RefType::ExnRef,
+            _ => return Err(invalid_data("invalid reference type")),
+        };
+        Ok(ref_type)
+    }
+
+    // AST 模块
+
+    pub fn read_module(&mut self) -> io::Result<Module> {
+        Ok(Module {
+            custom_items: self.read_list(Self::read_custom_item)?,
+            type_items: self
+                .read_list(|reader| Ok(TypeItem::FunctionType(reader.read_function_type()?)))?,
+            import_items: self.read_list(Self::read_import_item)?,
+            internal_function_to_type_index_list: self.read_u32_list()?,
+            tables: self.read_list(Self::read_table_type)?,
+            memory_blocks: self.read_list(Self::read_memory_type)?,
+            tag_items: self.read_list(|reader| {
+                Ok(TagType {
+                    type_index: reader.read_u32()?,
+                })
+            })?,
+            global_items: self.read_list(|reader| {
+                Ok(GlobalItem {
+                    global_type: reader.read_global_type()?,
+                    initialize_instruction_items: reader.read_instructions()?,
+                })
+            })?,
+            export_items: self.read_list(Self::read_export_item)?,
+            start_function_index: self.read_option_u32()?,
+            element_items: self.read_list(Self::read_element_item)?,
+            code_items: self.read_list(|reader| {
+                Ok(CodeItem {
+                    local_groups: reader.read_list(|reader| {
+                        Ok(LocalGroup {
+                            variable_count: reader.read_u32()?,
+                            value_type: reader.read_value_type()?,
+                        })
+                    })?,
+                    instruction_items: reader.read_instructions()?,
+                })
+            })?,
+            data_items: self.read_list(Self::read_data_item)?,
+            data_count: self.read_option_u32()?,
+        })
+    }
+
+    fn read_custom_item(&mut self) -> io::Result<CustomItem> {
+        let custom_item = match self.read_u8()? {
+            0 => CustomItem::NameCollections(self.read_list(Self::read_name_collection)?),
+            1 => CustomItem::DynamicLinking(self.read_dynamic_linking()?),
+            2 => CustomItem::Other(self.read_string()?, self.read_byte_list()?),
+            _ => return Err(invalid_data("invalid custom item")),
+        };
+        Ok(custom_item)
+    }
+
+    fn read_index_name_pairs(&mut self) -> io::Result<Vec<IndexNamePair>> {
+        self.read_list(|reader| {
+            Ok(IndexNamePair {
+                index: reader.read_u32()?,
+                name: reader.read_string()?,
+            })
+        })
+    }
+
+    fn read_name_collection(&mut self) -> io::Result<NameCollection> {
+        let name_collection = match self.read_u8()? {
+            0 => NameCollection::TypeNames(self.read_index_name_pairs()?),
+            1 => NameCollection::FunctionNames(self.read_index_name_pairs()?),
+            2 => NameCollection::LocalVariableNamesPairList(self.read_list(|reader| {
+                Ok(FunctionIndexAndLocalVariableNamesPair {
+                    function_index: reader.read_u32()?,
+                    local_variable_names: reader.read_index_name_pairs()?,
+                })
+            })?),
+            3 => NameCollection::BlockLabelsPairList(self.read_list(|reader| {
+                Ok(FunctionIndexAndBlockLabelsPair {
+                    function_index: reader.read_u32()?,
+                    block_labels: reader.read_index_name_pairs()?,
+                })
+            })?),
+            4 => NameCollection::GlobalVariableNames(self.read_index_name_pairs()?),
+            5 => NameCollection::MemoryBlockNames(self.read_index_name_pairs()?),
+            6 => NameCollection::TableNames(self.read_index_name_pairs()?),
+            7 => NameCollection::ElementNames(self.read_index_name_pairs()?),
+            8 => NameCollection::DataNames(self.read_index_name_pairs()?),
+            9 => NameCollection::TagNames(self.read_index_name_pairs()?),
+            _ => return Err(invalid_data("invalid name collection")),
+        };
+        Ok(name_collection)
+    }
+
+    fn read_dynamic_linking(&mut self) -> io::Result<DynamicLinking> {
+        let memory_info = match self.read_u8()? {
+            0 => None,
+            1 => Some(DynamicLinkingMemoryInfo {
+                memory_size: self.read_u32()?,
+                memory_alignment: self.read_u32()?,
+                table_size: self.read_u32()?,
+                table_alignment: self.read_u32()?,
+            }),
+            _ => return Err(invalid_data("invalid option tag")),
+        };
+        Ok(DynamicLinking {
+            memory_info,
+            needed: self.read_list(Self::read_string)?,
+            export_infos: self.read_list(|reader| {
+                Ok(DynamicLinkingExportInfo {
+                    name: reader.read_string()?,
+                    flags: reader.read_u32()?,
+                })
+            })?,
+            import_infos: self.read_list(|reader| {
+                Ok(DynamicLinkingImportInfo {
+                    module_name: reader.read_string()?,
+                    item_name: reader.read_string()?,
+                    flags: reader.read_u32()?,
+                })
+            })?,
+            runtime_paths: self.read_list(Self::read_string)?,
+        })
+    }
+
+    fn read_function_type(&mut self) -> io::Result<FunctionType> {
+        Ok(FunctionType {
+            params: self.read_value_types()?,
+            results: self.read_value_types()?,
+        })
+    }
+
+    fn read_limit(&mut self) -> io::Result<Limit> {
+        let limit = match self.read_u8()? {
+            0 => Limit::Range(self.read_u64()?, self.read_u64()?),
+            1 => Limit::AtLeast(self.read_u64()?),
+            _ => return Err(invalid_data("invalid limit")),
+        };
+        Ok(limit)
+    }
+
+    fn read_table_type(&mut self) -> io::Result<TableType> {
+        Ok(TableType {
+            element_type: self.read_ref_type()?,
+            limit: self.read_limit()?,
+        })
+    }
+
+    fn read_memory_type(&mut self) -> io::Result<MemoryType> {
+        Ok(MemoryType {
+            limit: self.read_limit()?,
+            shared: self.read_bool()?,
+            memory64: self.read_bool()?,
+        })
+    }
+
+    fn read_global_type(&mut self) -> io::Result<GlobalType> {
+        Ok(GlobalType {
+            value_type: self.read_value_type()?,
+            mutable: self.read_bool()?,
+        })
+    }
+
+    fn read_import_item(&mut self) -> io::Result<ImportItem> {
+        let module_name = self.read_string()?;
+        let item_name = self.read_string()?;
+        let import_descriptor = match self.read_u8()? {
+            0 => ImportDescriptor::FunctionTypeIndex(self.read_u32()?),
+            1 => ImportDescriptor::TableType(self.read_table_type()?),
+            2 => ImportDescriptor::MemoryType(self.read_memory_type()?),
+            3 => ImportDescriptor::GlobalType(self.read_global_type()?),
+            4 => ImportDescriptor::TagType(TagType {
+                type_index: self.read_u32()?,
+            }),
+            _ => return Err(invalid_data("invalid import descriptor")),
+        };
+        Ok(ImportItem {
+            module_name,
+            item_name,
+            import_descriptor,
+        })
+    }
+
+    fn read_export_item(&mut self) -> io::Result<ExportItem> {
+        let name = self.read_string()?;
+        let export_descriptor = match self.read_u8()? {
+            0 => ExportDescriptor::FunctionIndex(self.read_u32()?),
+            1 => ExportDescriptor::TableIndex(self.read_u32()?),
+            2 => ExportDescriptor::MemoryBlockIndex(self.read_u32()?),
+            3 => ExportDescriptor::GlobalItemIndex(self.read_u32()?),
+            4 => ExportDescriptor::TagIndex(self.read_u32()?),
+            _ => return Err(invalid_data("invalid export descriptor")),
+        };
+        Ok(ExportItem {
+            name,
+            export_descriptor,
+        })
+    }
+
+    fn read_element_item(&mut self) -> io::Result<ElementItem> {
+        let mode = match self.read_u8()? {
+            0 => ElementMode::Active {
+                table_index: self.read_u32()?,
+                offset_instruction_items: self.read_instructions()?,
+            },
+            1 => ElementMode::Passive,
+            2 => ElementMode::Declarative,
+            _ => return Err(invalid_data("invalid element mode")),
+        };
+        let element_type = self.read_ref_type()?;
+        let items = match self.read_u8()? {
+            0 => ElementItems::FunctionIndices(self.read_u32_list()?),
+            1 => ElementItems::Expressions(self.read_list(Self::read_instructions)?),
+            _ => return Err(invalid_data("invalid element items")),
+        };
+        Ok(ElementItem {
+            mode,
+            element_type,
+            items,
+        })
+    }
+
+    fn read_data_item(&mut self) -> io::Result<DataItem> {
+        let mode = match self.read_u8()? {
+            0 => DataMode::Active {
+                memory_block_index: self.read_u32()?,
+                offset_instruction_items: self.read_instructions()?,
+            },
+            1 => DataMode::Passive,
+            _ => return Err(invalid_data("invalid data mode")),
+        };
+        Ok(DataItem {
+            mode,
+            data: self.read_byte_list()?,
+        })
+    }
+
+    fn read_memory_argument(&mut self) -> io::Result<MemoryArgument> {
+        Ok(MemoryArgument {
+            align: self.read_u32()?,
+            offset: self.read_u64()?,
+            memory_block_index: self.read_u32()?,
+        })
+    }
+
+    fn read_catch_clause(&mut self) -> io::Result<CatchClause> {
+        let catch_clause = match self.read_u8()? {
+            0 => CatchClause::Catch(self.read_u32()?, self.read_u32()?),
+            1 => CatchClause::CatchRef(self.read_u32()?, self.read_u32()?),
+            2 => CatchClause::CatchAll(self.read_u32()?),
+            3 => CatchClause::CatchAllRef(self.read_u32()?),
+            _ => return Err(invalid_data("invalid catch clause")),
+        };
+        Ok(catch_clause)
+    }
+
+    fn read_instructions(&mut self) -> io::Result<Vec<Instruction>> {
+        self.read_list(Self::read_instruction)
+    }
+
+    fn read_instruction(&mut self) -> io::Result<Instruction> {
+        let instruction = match self.read_u16()? {
+            0 => Instruction::Unreachable,
+            1 => Instruction::Nop,
+            2 => Instruction::Block(self.read_block_type()?, self.read_u32()?),
+            3 => Instruction::Loop(self.read_block_type()?, self.read_u32()?),
+            4 => Instruction::If(self.read_block_type()?, self.read_u32()?),
+            5 => Instruction::Else,
+            6 => Instruction::End,
+            7 => Instruction::Br(self.read_u32()?),
+            8 => Instruction::BrIf(self.read_u32()?),
+            9 => Instruction::BrTable(self.read_u32_list()?, self.read_u32()?),
+            10 => Instruction::Return,
+            11 => Instruction::Call(self.read_u32()?),
+            12 => Instruction::CallIndirect(self.read_u32()?, self.read_u32()?),
+            13 => Instruction::ReturnCall(self.read_u32()?),
+            14 => Instruction::ReturnCallIndirect(self.read_u32()?, self.read_u32()?),
+            15 => Instruction::Throw(self.read_u32()?),
+            16 => Instruction::ThrowRef,
+            17 => Instruction::TryTable(
+                self.read_block_type()?,
+                {
+                    let length = self.read_length()?;
+                    (0..length)
+                        .map(|_| self.read_catch_clause())
+                        .collect::<io::Result<Vec<CatchClause>>>()?
+                },
+                self.read_u32()?,
+            ),
+            18 => Instruction::Drop,
+            19 => Instruction::Select,
+            20 => Instruction::SelectTyped(self.read_value_types()?),
+            21 => Instruction::LocalGet(self.read_u32()?),
+            22 => Instruction::LocalSet(self.read_u32()?),
+            23 => Instruction::LocalTee(self.read_u32()?),
+            24 => Instruction::GlobalGet(self.read_u32()?),
+            25 => Instruction::GlobalSet(self.read_u32()?),
+            26 => Instruction::I32Load(self.read_memory_argument()?),
+            27 => Instruction::I64Load(self.read_memory_argument()?),
+            28 => Instruction::F32Load(self.read_memory_argument()?),
+            29 => Instruction::F64Load(self.read_memory_argument()?),
+            30 => Instruction::I32Load8S(self.read_memory_argument()?),
+            31 => Instruction::I32Load8U(self.read_memory_argument()?),
+            32 => Instruction::I32Load16S(self.read_memory_argument()?),
+            33 => Instruction::I32Load16U(self.read_memory_argument()?),
+            34 => Instruction::I64Load8S(self.read_memory_argument()?),
+            35 => Instruction::I64Load8U(self.read_memory_argument()?),
+            36 => Instruction::I64Load16S(self.read_memory_argument()?),
+            37 => Instruction::I64Load16U(self.read_memory_argument()?),
+            38 => Instruction::I64Load32S(self.read_memory_argument()?),
+            39 => Instruction::I64Load32U(self.read_memory_argument()?),
+            40 => Instruction::I32Store(self.read_memory_argument()?),
+            41 => Instruction::I64Store(self.read_memory_argument()?),
+            42 => Instruction::F32Store(self.read_memory_argument()?),
+            43 => Instruction::F64Store(self.read_memory_argument()?),
+            44 => Instruction::I32Store8(self.read_memory_argument()?),
+            45 => Instruction::I32Store16(self.read_memory_argument()?),
+            46 => Instruction::I64Store8(self.read_memory_argument()?),
+            47 => Instruction::I64Store16(self.read_memory_argument()?),
+            48 => Instruction::I64Store32(self.read_memory_argument()?),
+            49 => Instruction::MemorySize(self.read_u32()?),
+            50 => Instruction::MemoryGrow(self.read_u32()?),
+            51 => Instruction::MemoryInit(self.read_u32()?, self.read_u32()?),
+            52 => Instruction::DataDrop(self.read_u32()?),
+            53 => Instruction::MemoryCopy(self.read_u32()?, self.read_u32()?),
+            54 => Instruction::MemoryFill(self.read_u32()?),
+            55 => Instruction::TableGet(self.read_u32()?),
+            56 => Instruction::TableSet(self.read_u32()?),
+            57 => Instruction::TableInit(self.read_u32()?, self.read_u32()?),
+            58 => Instruction::ElementDrop(self.read_u32()?),
+            59 => Instruction::TableCopy(self.read_u32()?, self.read_u32()?),
+            60 => Instruction::TableGrow(self.read_u32()?),
+            61 => Instruction::TableSize(self.read_u32()?),
+            62 => Instruction::TableFill(self.read_u32()?),
+            63 => Instruction::RefNull(self.read_ref_type()?),
+            64 => Instruction::RefIsNull,
+            65 => Instruction::RefFunc(self.read_u32()?),
+            66 => Instruction::I32Const(self.read_u32()? as i32),
+            67 => Instruction::I64Const(self.read_u64()? as i64),
+            68 => Instruction::F32Const(f32::from_bits(self.read_u32()?)),
+            69 => Instruction::F64Const(f64::from_bits(self.read_u64()?)),
+            70 => Instruction::I32Eqz,
+            71 => Instruction::I32Eq,
+            72 => Instruction::I32Ne,
+            73 => Instruction::I32LtS,
+            74 => Instruction::I32LtU,
+            75 => Instruction::I32GtS,
+            76 => Instruction::I32GtU,
+            77 => Instruction::I32LeS,
+            78 => Instruction::I32LeU,
+            79 => Instruction::I32GeS,
+            80 => Instruction::I32GeU,
+            81 => Instruction::I64Eqz,
+            82 => Instruction::I64Eq,
+            83 => Instruction::I64Ne,
+            84 => Instruction::I64LtS,
+            85 => Instruction::I64LtU,
+            86 => Instruction::I64GtS,
+            87 => Instruction::I64GtU,
+            88 => Instruction::I64LeS,
+            89 => Instruction::I64LeU,
+            90 => Instruction::I64GeS,
+            91 => Instruction::I64GeU,
+            92 => Instruction::F32Eq,
+            93 => Instruction::F32Ne,
+            94 => Instruction::F32Lt,
+            95 => Instruction::F32Gt,
+            96 => Instruction::F32Le,
+            97 => Instruction::F32Ge,
+            98 => Instruction::F64Eq,
+            99 => Instruction::F64Ne,
+            100 => Instruction::F64Lt,
+            101 => Instruction::F64Gt,
+            102 => Instruction::F64Le,
+            103 => Instruction::F64Ge,
+            104 => Instruction::I32Clz,
+            105 => Instruction::I32Ctz,
+            106 => Instruction::I32PopCnt,
+            107 => Instruction::I32Add,
+            108 => Instruction::I32Sub,
+            109 => Instruction::I32Mul,
+            110 => Instruction::I32DivS,
+            111 => Instruction::I32DivU,
+            112 => Instruction::I32RemS,
+            113 => Instruction::I32RemU,
+            114 => Instruction::I32And,
+            115 => Instruction::I32Or,
+            116 => Instruction::I32Xor,
+            117 => Instruction::I32Shl,
+            118 => Instruction::I32ShrS,
+            119 => Instruction::I32ShrU,
+            120 => Instruction::I32Rotl,
+            121 => Instruction::I32Rotr,
+            122 => Instruction::I64Clz,
+            123 => Instruction::I64Ctz,
+            124 => Instruction::I64PopCnt,
+            125 => Instruction::I64Add,
+            126 => Instruction::I64Sub,
+            127 => Instruction::I64Mul,
+            128 => Instruction::I64DivS,
+            129 => Instruction::I64DivU,
+            130 => Instruction::I64RemS,
+            131 => Instruction::I64RemU,
+            132 => Instruction::I64And,
+            133 => Instruction::I64Or,
+            134 => Instruction::I64Xor,
+            135 => Instruction::I64Shl,
+            136 => Instruction::I64ShrS,
+            137 => Instruction::I64ShrU,
+            138 => Instruction::I64Rotl,
+            139 => Instruction::I64Rotr,
+            140 => Instruction::F32Abs,
+            141 => Instruction::F32Neg,
+            142 => Instruction::F32Ceil,
+            143 => Instruction::F32Floor,
+            144 => Instruction::F32Trunc,
+            145 => Instruction::F32Nearest,
+            146 => Instruction::F32Sqrt,
+            147 => Instruction::F32Add,
+            148 => Instruction::F32Sub,
+            149 => Instruction::F32Mul,
+            150 => Instruction::F32Div,
+            151 => Instruction::F32Min,
+            152 => Instruction::F32Max,
+            153 => Instruction::F32CopySign,
+            154 => Instruction::F64Abs,
+            155 => Instruction::F64Neg,
+            156 => Instruction::F64Ceil,
+            157 => Instruction::F64Floor,
+            158 => Instruction::F64Trunc,
+            159 => Instruction::F64Nearest,
+            160 => Instruction::F64Sqrt,
+            161 => Instruction::F64Add,
+            162 => Instruction::F64Sub,
+            163 => Instruction::F64Mul,
+            164 => Instruction::F64Div,
+            165 => Instruction::F64Min,
+            166 => Instruction::F64Max,
+            167 => Instruction::F64CopySign,
+            168 => Instruction::I32WrapI64,
+            169 => Instruction::I32TruncF32S,
+            170 => Instruction::I32TruncF32U,
+            171 => Instruction::I32TruncF64S,
+            172 => Instruction::I32TruncF64U,
+            173 => Instruction::I64ExtendI32S,
+            174 => Instruction::I64ExtendI32U,
+            175 => Instruction::I64TruncF32S,
+            176 => Instruction::I64TruncF32U,
+            177 => Instruction::I64TruncF64S,
+            178 => Instruction::I64TruncF64U,
+            179 => Instruction::F32ConvertI32S,
+            180 => Instruction::F32ConvertI32U,
+            181 => Instruction::F32ConvertI64S,
+            182 => Instruction::F32ConvertI64U,
+            183 => Instruction::F32DemoteF64,
+            184 => Instruction::F64ConvertI32S,
+            185 => Instruction::F64ConvertI32U,
+            186 => Instruction::F64ConvertI64S,
+            187 => Instruction::F64ConvertI64U,
+            188 => Instruction::F64PromoteF32,
+            189 => Instruction::I32ReinterpretF32,
+            190 => Instruction::I64ReinterpretF64,
+            191 => Instruction::F32ReinterpretI32,
+            192 => Instruction::F64ReinterpretI64,
+            193 => Instruction::I32Extend8S,
+            194 => Instruction::I32Extend16S,
+            195 => Instruction::I64Extend8S,
+            196 => Instruction::I64Extend16S,
+            197 => Instruction::I64Extend32S,
+            198 => Instruction::I32TruncSatF32S,
+            199 => Instruction::I32TruncSatF32U,
+            200 => Instruction::I32TruncSatF64S,
+            201 => Instruction::I32TruncSatF64U,
+            202 => Instruction::I64TruncSatF32S,
+            203 => Instruction::I64TruncSatF32U,
+            204 => Instruction::I64TruncSatF64S,
+            205 => Instruction::I64TruncSatF64U,
+            206 => Instruction::V128Load(self.read_memory_argument()?),
+            207 => Instruction::V128Load8x8S(self.read_memory_argument()?),
+            208 => Instruction::V128Load8x8U(self.read_memory_argument()?),
+            209 => Instruction::V128Load16x4S(self.read_memory_argument()?),
+            210 => Instruction::V128Load16x4U(self.read_memory_argument()?),
+            211 => Instruction::V128Load32x2S(self.read_memory_argument()?),
+            212 => Instruction::V128Load32x2U(self.read_memory_argument()?),
+            213 => Instruction::V128Load8Splat(self.read_memory_argument()?),
+            214 => Instruction::V128Load16Splat(self.read_memory_argument()?),
+            215 => Instruction::V128Load32Splat(self.read_memory_argument()?),
+            216 => Instruction::V128Load64Splat(self.read_memory_argument()?),
+            217 => Instruction::V128Store(self.read_memory_argument()?),
+            218 => Instruction::V128Const(u128::from_le_bytes(
+                self.read_bytes(16)?.try_into().unwrap(),
+            )),
+            219 => Instruction::I8x16Shuffle(self.read_bytes(16)?.try_into().unwrap()),
+            220 => Instruction::I8x16Swizzle,
+            221 => Instruction::I8x16Splat,
+            222 => Instruction::I16x8Splat,
+            223 => Instruction::I32x4Splat,
+            224 => Instruction::I64x2Splat,
+            225 => Instruction::F32x4Splat,
+            226 => Instruction::F64x2Splat,
+            227 => Instruction::I8x16ExtractLaneS(self.read_u8()?),
+            228 => Instruction::I8x16ExtractLaneU(self.read_u8()?),
+            229 => Instruction::I8x16ReplaceLane(self.read_u8()?),
+            230 => Instruction::I16x8ExtractLaneS(self.read_u8()?),
+            231 => Instruction::I16x8ExtractLaneU(self.read_u8()?),
+            232 => Instruction::I16x8ReplaceLane(self.read_u8()?),
+            233 => Instruction::I32x4ExtractLane(self.read_u8()?),
+            234 => Instruction::I32x4ReplaceLane(self.read_u8()?),
+            235 => Instruction::I64x2ExtractLane(self.read_u8()?),
+            236 => Instruction::I64x2ReplaceLane(self.read_u8()?),
+            237 => Instruction::F32x4ExtractLane(self.read_u8()?),
+            238 => Instruction::F32x4ReplaceLane(self.read_u8()?),
+            239 => Instruction::F64x2ExtractLane(self.read_u8()?),
+            240 => Instruction::F64x2ReplaceLane(self.read_u8()?),
+            241 => Instruction::I8x16Eq,
+            242 => Instruction::I8x16Ne,
+            243 => Instruction::I8x16LtS,
+            244 => Instruction::I8x16LtU,
+            245 => Instruction::I8x16GtS,
+            246 => Instruction::I8x16GtU,
+            247 => Instruction::I8x16LeS,
+            248 => Instruction::I8x16LeU,
+            249 => Instruction::I8x16GeS,
+            250 => Instruction::I8x16GeU,
+            251 => Instruction::I16x8Eq,
+            252 => Instruction::I16x8Ne,
+            253 => Instruction::I16x8LtS,
+            254 => Instruction::I16x8LtU,
+            255 => Instruction::I16x8GtS,
+            256 => Instruction::I16x8GtU,
+            257 => Instruction::I16x8LeS,
+            258 => Instruction::I16x8LeU,
+            259 => Instruction::I16x8GeS,
+            260 => Instruction::I16x8GeU,
+            261 => Instruction::I32x4Eq,
+            262 => Instruction::I32x4Ne,
+            263 => Instruction::I32x4LtS,
+            264 => Instruction::I32x4LtU,
+            265 => Instruction::I32x4GtS,
+            266 => Instruction::I32x4GtU,
+            267 => Instruction::I32x4LeS,
+            268 => Instruction::I32x4LeU,
+            269 => Instruction::I32x4GeS,
+            270 => Instruction::I32x4GeU,
+            271 => Instruction::F32x4Eq,
+            272 => Instruction::F32x4Ne,
+            273 => Instruction::F32x4Lt,
+            274 => Instruction::F32x4Gt,
+            275 => Instruction::F32x4Le,
+            276 => Instruction::F32x4Ge,
+            277 => Instruction::F64x2Eq,
+            278 => Instruction::F64x2Ne,
+            279 => Instruction::F64x2Lt,
+            280 => Instruction::F64x2Gt,
+            281 => Instruction::F64x2Le,
+            282 => Instruction::F64x2Ge,
+            283 => Instruction::V128Not,
+            284 => Instruction::V128And,
+            285 => Instruction::V128Andnot,
+            286 => Instruction::V128Or,
+            287 => Instruction::V128Xor,
+            288 => Instruction::V128Bitselect,
+            289 => Instruction::V128AnyTrue,
+            290 => Instruction::V128Load8Lane(self.read_memory_argument()?, self.read_u8()?),
+            291 => Instruction::V128Load16Lane(self.read_memory_argument()?, self.read_u8()?),
+            292 => Instruction::V128Load32Lane(self.read_memory_argument()?, self.read_u8()?),
+            293 => Instruction::V128Load64Lane(self.read_memory_argument()?, self.read_u8()?),
+            294 => Instruction::V128Store8Lane(self.read_memory_argument()?, self.read_u8()?),
+            295 => Instruction::V128Store16Lane(self.read_memory_argument()?, self.read_u8()?),
+            296 => Instruction::V128Store32Lane(self.read_memory_argument()?, self.read_u8()?),
+            297 => Instruction::V128Store64Lane(self.read_memory_argument()?, self.read_u8()?),
+            298 => Instruction::V128Load32Zero(self.read_memory_argument()?),
+            299 => Instruction::V128Load64Zero(self.read_memory_argument()?),
+            300 => Instruction::F32x4DemoteF64x2Zero,
+            301 => Instruction::F64x2PromoteLowF32x4,
+            302 => Instruction::I8x16Abs,
+            303 => Instruction::I8x16Neg,
+            304 => Instruction::I8x16Popcnt,
+            305 => Instruction::I8x16AllTrue,
+            306 => Instruction::I8x16Bitmask,
+            307 => Instruction::I8x16NarrowI16x8S,
+            308 => Instruction::I8x16NarrowI16x8U,
+            309 => Instruction::F32x4Ceil,
+            310 => Instruction::F32x4Floor,
+            311 => Instruction::F32x4Trunc,
+            312 => Instruction::F32x4Nearest,
+            313 => Instruction::I8x16Shl,
+            314 => Instruction::I8x16ShrS,
+            315 => Instruction::I8x16ShrU,
+            316 => Instruction::I8x16Add,
+            317 => Instruction::I8x16AddSatS,
+            318 => Instruction::I8x16AddSatU,
+            319 => Instruction::I8x16Sub,
+            320 => Instruction::I8x16SubSatS,
+            321 => Instruction::I8x16SubSatU,
+            322 => Instruction::F64x2Ceil,
+            323 => Instruction::F64x2Floor,
+            324 => Instruction::I8x16MinS,
+            325 => Instruction::I8x16MinU,
+            326 => Instruction::I8x16MaxS,
+            327 => Instruction::I8x16MaxU,
+            328 => Instruction::F64x2Trunc,
+            329 => Instruction::I8x16AvgrU,
+            330 => Instruction::I16x8ExtaddPairwiseI8x16S,
+            331 => Instruction::I16x8ExtaddPairwiseI8x16U,
+            332 => Instruction::I32x4ExtaddPairwiseI16x8S,
+            333 => Instruction::I32x4ExtaddPairwiseI16x8U,
+            334 => Instruction::I16x8Abs,
+            335 => Instruction::I16x8Neg,
+            336 => Instruction::I16x8Q15mulrSatS,
+            337 => Instruction::I16x8AllTrue,
+            338 => Instruction::I16x8Bitmask,
+            339 => Instruction::I16x8NarrowI32x4S,
+            340 => Instruction::I16x8NarrowI32x4U,
+            341 => Instruction::I16x8ExtendLowI8x16S,
+            342 => Instruction::I16x8ExtendHighI8x16S,
+            343 => Instruction::I16x8ExtendLowI8x16U,
+            344 => Instruction::I16x8ExtendHighI8x16U,
+            345 => Instruction::I16x8Shl,
+            346 => Instruction::I16x8ShrS,
+            347 => Instruction::I16x8ShrU,
+            348 => Instruction::I16x8Add,
+            349 => Instruction::I16x8AddSatS,
+            350 => Instruction::I16x8AddSatU,
+            351 => Instruction::I16x8Sub,
+            352 => Instruction::I16x8SubSatS,
+            353 => Instruction::I16x8SubSatU,
+            354 => Instruction::F64x2Nearest,
+            355 => Instruction::I16x8Mul,
+            356 => Instruction::I16x8MinS,
+            357 => Instruction::I16x8MinU,
+            358 => Instruction::I16x8MaxS,
+            359 => Instruction::I16x8MaxU,
+            360 => Instruction::I16x8AvgrU,
+            361 => Instruction::I16x8ExtmulLowI8x16S,
+            362 => Instruction::I16x8ExtmulHighI8x16S,
+            363 => Instruction::I16x8ExtmulLowI8x16U,
+            364 => Instruction::I16x8ExtmulHighI8x16U,
+            365 => Instruction::I32x4Abs,
+            366 => Instruction::I32x4Neg,
+            367 => Instruction::I32x4AllTrue,
+            368 => Instruction::I32x4Bitmask,
+            369 => Instruction::I32x4ExtendLowI16x8S,
+            370 => Instruction::I32x4ExtendHighI16x8S,
+            371 => Instruction::I32x4ExtendLowI16x8U,
+            372 => Instruction::I32x4ExtendHighI16x8U,
+            373 => Instruction::I32x4Shl,
+            374 => Instruction::I32x4ShrS,
+            375 => Instruction::I32x4ShrU,
+            376 => Instruction::I32x4Add,
+            377 => Instruction::I32x4Sub,
+            378 => Instruction::I32x4Mul,
+            379 => Instruction::I32x4MinS,
+            380 => Instruction::I32x4MinU,
+            381 => Instruction::I32x4MaxS,
+            382 => Instruction::I32x4MaxU,
+            383 => Instruction::I32x4DotI16x8S,
+            384 => Instruction::I32x4ExtmulLowI16x8S,
+            385 => Instruction::I32x4ExtmulHighI16x8S,
+            386 => Instruction::I32x4ExtmulLowI16x8U,
+            387 => Instruction::I32x4ExtmulHighI16x8U,
+            388 => Instruction::I64x2Abs,
+            389 => Instruction::I64x2Neg,
+            390 => Instruction::I64x2AllTrue,
+            391 => Instruction::I64x2Bitmask,
+            392 => Instruction::I64x2ExtendLowI32x4S,
+            393 => Instruction::I64x2ExtendHighI32x4S,
+            394 => Instruction::I64x2ExtendLowI32x4U,
+            395 => Instruction::I64x2ExtendHighI32x4U,
+            396 => Instruction::I64x2Shl,
+            397 => Instruction::I64x2ShrS,
+            398 => Instruction::I64x2ShrU,
+            399 => Instruction::I64x2Add,
+            400 => Instruction::I64x2Sub,
+            401 => Instruction::I64x2Mul,
+            402 => Instruction::I64x2Eq,
+            403 => Instruction::I64x2Ne,
+            404 => Instruction::I64x2LtS,
+            405 => Instruction::I64x2GtS,
+            406 => Instruction::I64x2LeS,
+            407 => Instruction::I64x2GeS,
+            408 => Instruction::I64x2ExtmulLowI32x4S,
+            409 => Instruction::I64x2ExtmulHighI32x4S,
+            410 => Instruction::I64x2ExtmulLowI32x4U,
+            411 => Instruction::I64x2ExtmulHighI32x4U,
+            412 => Instruction::F32x4Abs,
+            413 => Instruction::F32x4Neg,
+            414 => Instruction::F32x4Sqrt,
+            415 => Instruction::F32x4Add,
+            416 => Instruction::F32x4Sub,
+            417 => Instruction::F32x4Mul,
+            418 => Instruction::F32x4Div,
+            419 => Instruction::F32x4Min,
+            420 => Instruction::F32x4Max,
+            421 => Instruction::F32x4Pmin,
+            422 => Instruction::F32x4Pmax,
+            423 => Instruction::F64x2Abs,
+            424 => Instruction::F64x2Neg,
+            425 => Instruction::F64x2Sqrt,
+            426 => Instruction::F64x2Add,
+            427 => Instruction::F64x2Sub,
+            428 => Instruction::F64x2Mul,
+            429 => Instruction::F64x2Div,
+            430 => Instruction::F64x2Min,
+            431 => Instruction::F64x2Max,
+            432 => Instruction::F64x2Pmin,
+            433 => Instruction::F64x2Pmax,
+            434 => Instruction::I32x4TruncSatF32x4S,
+            435 => Instruction::I32x4TruncSatF32x4U,
+            436 => Instruction::F32x4ConvertI32x4S,
+            437 => Instruction::F32x4ConvertI32x4U,
+            438 => Instruction::I32x4TruncSatF64x2SZero,
+            439 => Instruction::I32x4TruncSatF64x2UZero,
+            440 => Instruction::F64x2ConvertLowI32x4S,
+            441 => Instruction::F64x2ConvertLowI32x4U,
+            442 => Instruction::MemoryAtomicNotify(self.read_memory_argument()?),
+            443 => Instruction::MemoryAtomicWait32(self.read_memory_argument()?),
+            444 => Instruction::MemoryAtomicWait64(self.read_memory_argument()?),
+            445 => Instruction::AtomicFence,
+            446 => Instruction::I32AtomicLoad(self.read_memory_argument()?),
+            447 => Instruction::I64AtomicLoad(self.read_memory_argument()?),
+            448 => Instruction::I32AtomicLoad8U(self.read_memory_argument()?),
+            449 => Instruction::I32AtomicLoad16U(self.read_memory_argument()?),
+            450 => Instruction::I64AtomicLoad8U(self.read_memory_argument()?),
+            451 => Instruction::I64AtomicLoad16U(self.read_memory_argument()?),
+            452 => Instruction::I64AtomicLoad32U(self.read_memory_argument()?),
+            453 => Instruction::I32AtomicStore(self.read_memory_argument()?),
+            454 => Instruction::I64AtomicStore(self.read_memory_argument()?),
+            455 => Instruction::I32AtomicStore8(self.read_memory_argument()?),
+            456 => Instruction::I32AtomicStore16(self.read_memory_argument()?),
+            457 => Instruction::I64AtomicStore8(self.read_memory_argument()?),
+            458 => Instruction::I64AtomicStore16(self.read_memory_argument()?),
+            459 => Instruction::I64AtomicStore32(self.read_memory_argument()?),
+            460 => Instruction::I32AtomicRmwAdd(self.read_memory_argument()?),
+            461 => Instruction::I64AtomicRmwAdd(self.read_memory_argument()?),
+            462 => Instruction::I32AtomicRmw8AddU(self.read_memory_argument()?),
+            463 => Instruction::I32AtomicRmw16AddU(self.read_memory_argument()?),
+            464 => Instruction::I64AtomicRmw8AddU(self.read_memory_argument()?),
+            465 => Instruction::I64AtomicRmw16AddU(self.read_memory_argument()?),
+            466 => Instruction::I64AtomicRmw32AddU(self.read_memory_argument()?),
+            467 => Instruction::I32AtomicRmwSub(self.read_memory_argument()?),
+            468 => Instruction::I64AtomicRmwSub(self.read_memory_argument()?),
+            469 => Instruction::I32AtomicRmw8SubU(self.read_memory_argument()?),
+            470 => Instruction::I32AtomicRmw16SubU(self.read_memory_argument()?),
+            471 => Instruction::I64AtomicRmw8SubU(self.read_memory_argument()?),
+            472 => Instruction::I64AtomicRmw16SubU(self.read_memory_argument()?),
+            473 => Instruction::I64AtomicRmw32SubU(self.read_memory_argument()?),
+            474 => Instruction::I32AtomicRmwAnd(self.read_memory_argument()?),
+            475 => Instruction::I64AtomicRmwAnd(self.read_memory_argument()?),
+            476 => Instruction::I32AtomicRmw8AndU(self.read_memory_argument()?),
+            477 => Instruction::I32AtomicRmw16AndU(self.read_memory_argument()?),
+            478 => Instruction::I64AtomicRmw8AndU(self.read_memory_argument()?),
+            479 => Instruction::I64AtomicRmw16AndU(self.read_memory_argument()?),
+            480 => Instruction::I64AtomicRmw32AndU(self.read_memory_argument()?),
+            481 => Instruction::I32AtomicRmwOr(self.read_memory_argument()?),
+            482 => Instruction::I64AtomicRmwOr(self.read_memory_argument()?),
+            483 => Instruction::I32AtomicRmw8OrU(self.read_memory_argument()?),
+            484 => Instruction::I32AtomicRmw16OrU(self.read_memory_argument()?),
+            485 => Instruction::I64AtomicRmw8OrU(self.read_memory_argument()?),
+            486 => Instruction::I64AtomicRmw16OrU(self.read_memory_argument()?),
+            487 => Instruction::I64AtomicRmw32OrU(self.read_memory_argument()?),
+            488 => Instruction::I32AtomicRmwXor(self.read_memory_argument()?),
+            489 => Instruction::I64AtomicRmwXor(self.read_memory_argument()?),
+            490 => Instruction::I32AtomicRmw8XorU(self.read_memory_argument()?),
+            491 => Instruction::I32AtomicRmw16XorU(self.read_memory_argument()?),
+            492 => Instruction::I64AtomicRmw8XorU(self.read_memory_argument()?),
+            493 => Instruction::I64AtomicRmw16XorU(self.read_memory_argument()?),
+            494 => Instruction::I64AtomicRmw32XorU(self.read_memory_argument()?),
+            495 => Instruction::I32AtomicRmwXchg(self.read_memory_argument()?),
+            496 => Instruction::I64AtomicRmwXchg(self.read_memory_argument()?),
+            497 => Instruction::I32AtomicRmw8XchgU(self.read_memory_argument()?),
+            498 => Instruction::I32AtomicRmw16XchgU(self.read_memory_argument()?),
+            499 => Instruction::I64AtomicRmw8XchgU(self.read_memory_argument()?),
+            500 => Instruction::I64AtomicRmw16XchgU(self.read_memory_argument()?),
+            501 => Instruction::I64AtomicRmw32XchgU(self.read_memory_argument()?),
+            502 => Instruction::I32AtomicRmwCmpxchg(self.read_memory_argument()?),
+            503 => Instruction::I64AtomicRmwCmpxchg(self.read_memory_argument()?),
+            504 => Instruction::I32AtomicRmw8CmpxchgU(self.read_memory_argument()?),
+            505 => Instruction::I32AtomicRmw16CmpxchgU(self.read_memory_argument()?),
+            506 => Instruction::I64AtomicRmw8CmpxchgU(self.read_memory_argument()?),
+            507 => Instruction::I64AtomicRmw16CmpxchgU(self.read_memory_argument()?),
+            508 => Instruction::I64AtomicRmw32CmpxchgU(self.read_memory_argument()?),
+            _ => return Err(invalid_data("invalid instruction")),
+        };
+        Ok(instruction)
+    }
+
+    // 链接和解码的结果
+
+    fn read_block_type(&mut self) -> io::Result<BlockType> {
+        let block_type = match self.read_u8()? {
+            0 => BlockType::ResultI32,
+            1 => BlockType::ResultI64,
+            2 => BlockType::ResultF32,
+            3 => BlockType::ResultF64,
+            4 => BlockType::ResultV128,
+            5 => BlockType::ResultFuncRef,
+            6 => BlockType::ResultExternRef,
+            7 => BlockType::ResultExnRef,
+            8 => BlockType::ResultEmpty,
+            9 => BlockType::TypeIndex(self.read_u32()?),
+            _ => return Err(invalid_data("invalid block type")),
+        };
+        Ok(block_type)
+    }
+
+    pub fn read_function_item(&mut self) -> io::Result<FunctionItem> {
+        let function_item = match self.read_u8()? {
+            0 => FunctionItem::Native {
+                native_module_index: self.read_usize()?,
+                type_index: self.read_usize()?,
+                function_index: self.read_usize()?,
+            },
+            1 => FunctionItem::Normal {
+                vm_module_index: self.read_usize()?,
+                type_index: self.read_usize()?,
+                function_index: self.read_usize()?,
+                internal_function_index: self.read_usize()?,
+                start_address: self.read_usize()?,
+                end_address: self.read_usize()?,
+                block_items: {
+                    let length = self.read_length()?;
+                    (0..length)
+                        .map(|_| self.read_block_item())
+                        .collect::<io::Result<Vec<BlockItem>>>()?
+                },
+            },
+            _ => return Err(invalid_data("invalid function item")),
+        };
+        Ok(function_item)
+    }
+
+    fn read_block_item(&mut self) -> io::Result<BlockItem> {
+        let block_item = match self.read_u8()? {
+            0 => BlockItem::Block {
+                block_type: self.read_block_type()?,
+                start_address: self.read_usize()?,
+                end_address: self.read_usize()?,
+            },
+            1 => BlockItem::Loop {
+                block_type: self.read_block_type()?,
+                start_address: self.read_usize()?,
+                end_address: self.read_usize()?,
+            },
+            2 => BlockItem::If {
+                block_type: self.read_block_type()?,
+                start_address: self.read_usize()?,
+                end_address: self.read_usize()?,
+                alternate_address: self.read_option_usize()?,
+            },
+            _ => return Err(invalid_data("invalid block item")),
+        };
+        Ok(block_item)
+    }
+
+    fn read_branch_target(&mut self) -> io::Result<BranchTarget> {
+        Ok(BranchTarget {
+            relative_depth: self.read_usize()?,
+            stack_height: self.read_usize()?,
+            arity: self.read_usize()?,
+            address: self.read_usize()?,
+        })
+    }
+
+    pub fn read_control(&mut self) -> io::Result<Control> {
+        let control = match self.read_u8()? {
+            0 => Control::Block {
+                block_type: self.read_block_type()?,
+                block_index: self.read_usize()?,
+                end_address: self.read_usize()?,
+            },
+            1 => Control::BlockAndJumpWhenEqZero {
+                block_type: self.read_block_type()?,
+                block_index: self.read_usize()?,
+                option_alternate_address: self.read_option_usize()?,
+                end_address: self.read_usize()?,
+            },
+            2 => Control::TryTable {
+                block_type: self.read_block_type()?,
+                block_index: self.read_usize()?,
+                end_address: self.read_usize()?,
+                catch_items: {
+                    let length = self.read_length()?;
+                    (0..length)
+                        .map(|_| {
+                            Ok(CatchItem {
+                                option_tag_index: self.read_option_usize()?,
+                                is_ref: self.read_bool()?,
+                                branch_target: self.read_branch_target()?,
+                            })
+                        })
+                        .collect::<io::Result<Vec<CatchItem>>>()?
+                },
+            },
+            3 => Control::JumpWithinBlock(self.read_usize()?),
+            4 => Control::Break {
+                option_block_index: self.read_option_usize()?,
+                branch_target: self.read_branch_target()?,
+            },
+            5 => Control::BreakWhenNotEqZero {
+                option_block_index: self.read_option_usize()?,
+                branch_target: self.read_branch_target()?,
+            },
+            6 => Control::Branch {
+                option_block_index: self.read_option_usize()?,
+                branch_targets: {
+                    let length = self.read_length()?;
+                    (0..length)
+                        .map(|_| self.read_branch_target())
+                        .collect::<io::Result<Vec<BranchTarget>>>()?
+                },
+                default_branch_target: self.read_branch_target()?,
+            },
+            7 => Control::Call {
+                vm_module_index: self.read_usize()?,
+                type_index: self.read_usize()?,
+                function_index: self.read_usize()?,
+                internal_function_index: self.read_usize()?,
+                address: self.read_usize()?,
+            },
+            8 => Control::CallNative {
+                native_module_index: self.read_usize()?,
+                type_index: self.read_usize()?,
+                function_index: self.read_usize()?,
+            },
+            9 => Control::CallIndirect {
+                type_index: self.read_usize()?,
+                table_index: self.read_usize()?,
+            },
+            10 => Control::ReturnCall {
+                vm_module_index: self.read_usize()?,
+                type_index: self.read_usize()?,
+                function_index: self.read_usize()?,
+                internal_function_index: self.read_usize()?,
+                address: self.read_usize()?,
+            },
+            11 => Control::ReturnCallNative {
+                native_module_index: self.read_usize()?,
+                type_index: self.read_usize()?,
+                function_index: self.read_usize()?,
+            },
+            12 => Control::ReturnCallIndirect {
+                type_index: self.read_usize()?,
+                table_index: self.read_usize()?,
+            },
+            13 => Control::Throw(self.read_usize()?),
+            14 => Control::ThrowRef,
+            15 => Control::End(self.read_option_usize()?),
+            16 => Control::Unreachable,
+            17 => Control::Nop,
+            _ => return Err(invalid_data("invalid control instruction")),
+        };
+        Ok(control)
+    }
+}
+
+pub fn value_type_to_u8(value_type: &ValueType) -> u8 {
+    match value_type {
+        ValueType::I32 => 0,
+        ValueType::I64 => 1,
+        ValueType::F32 => 2,
+        ValueType::F64 => 3,
+        ValueType::V128 => 4,
+        ValueType::FuncRef => 5,
+        ValueType::ExternRef => 6,
+        ValueType::ExnRef => 7,
+    }
+}
+
+/// AST 指令的标记，即指令在 `instruction::Instruction` 里的定义顺序
+fn get_instruction_tag(instruction: &Instruction) -> u16 {
+    match instruction {
+        Instruction::Unreachable => 0,
+        Instruction::Nop => 1,
+        Instruction::Block(..) => 2,
+        Instruction::Loop(..) => 3,
+        Instruction::If(..) => 4,
+        Instruction::Else => 5,
+        Instruction::End => 6,
+        Instruction::Br(..) => 7,
+        Instruction::BrIf(..) => 8,
+        Instruction::BrTable(..) => 9,
+        Instruction::Return => 10,
+        Instruction::Call(..) => 11,
+        Instruction::CallIndirect(..) => 12,
+        Instruction::ReturnCall(..) => 13,
+        Instruction::ReturnCallIndirect(..) => 14,
+        Instruction::Throw(..) => 15,
+        Instruction::ThrowRef => 16,
+        Instruction::TryTable(..) => 17,
+        Instruction::Drop => 18,
+        Instruction::Select => 19,
+        Instruction::SelectTyped(..) => 20,
+        Instruction::LocalGet(..) => 21,
+        Instruction::LocalSet(..) => 22,
+        Instruction::LocalTee(..) => 23,
+        Instruction::GlobalGet(..) => 24,
+        Instruction::GlobalSet(..) => 25,
+        Instruction::I32Load(..) => 26,
+        Instruction::I64Load(..) => 27,
+        Instruction::F32Load(..) => 28,
+        Instruction::F64Load(..) => 29,
+        Instruction::I32Load8S(..) => 30,
+        Instruction::I32Load8U(..) => 31,
+        Instruction::I32Load16S(..) => 32,
+        Instruction::I32Load16U(..) => 33,
+        Instruction::I64Load8S(..) => 34,
+        Instruction::I64Load8U(..) => 35,
+        Instruction::I64Load16S(..) => 36,
+        Instruction::I64Load16U(..) => 37,
+        Instruction::I64Load32S(..) => 38,
+        Instruction::I64Load32U(..) => 39,
+        Instruction::I32Store(..) => 40,
+        Instruction::I64Store(..) => 41,
+        Instruction::F32Store(..) => 42,
+        Instruction::F64Store(..) => 43,
+        Instruction::I32Store8(..) => 44,
+        Instruction::I32Store16(..) => 45,
+        Instruction::I64Store8(..) => 46,
+        Instruction::I64Store16(..) => 47,
+        Instruction::I64Store32(..) => 48,
+        Instruction::MemorySize(..) => 49,
+        Instruction::MemoryGrow(..) => 50,
+        Instruction::MemoryInit(..) => 51,
+        Instruction::DataDrop(..) => 52,
+        Instruction::MemoryCopy(..) => 53,
+        Instruction::MemoryFill(..) => 54,
+        Instruction::TableGet(..) => 55,
+        Instruction::TableSet(..) => 56,
+        Instruction::TableInit(..) => 57,
+        Instruction::ElementDrop(..) => 58,
+        Instruction::TableCopy(..) => 59,
+        Instruction::TableGrow(..) => 60,
+        Instruction::TableSize(..) => 61,
+        Instruction::TableFill(..) => 62,
+        Instruction::RefNull(..) => 63,
+        Instruction::RefIsNull => 64,
+        Instruction::RefFunc(..) => 65,
+        Instruction::I32Const(..) => 66,
+        Instruction::I64Const(..) => 67,
+        Instruction::F32Const(..) => 68,
+        Instruction::F64Const(..) => 69,
+        Instruction::I32Eqz => 70,
+        Instruction::I32Eq => 71,
+        Instruction::I32Ne => 72,
+        Instruction::I32LtS => 73,
+        Instruction::I32LtU => 74,
+        Instruction::I32GtS => 75,
+        Instruction::I32GtU => 76,
+        Instruction::I32LeS => 77,
+        Instruction::I32LeU => 78,
+        Instruction::I32GeS => 79,
+        Instruction::I32GeU => 80,
+        Instruction::I64Eqz => 81,
+        Instruction::I64Eq => 82,
+        Instruction::I64Ne => 83,
+        Instruction::I64LtS => 84,
+        Instruction::I64LtU => 85,
+        Instruction::I64GtS => 86,
+        Instruction::I64GtU => 87,
+        Instruction::I64LeS => 88,
+        Instruction::I64LeU => 89,
+        Instruction::I64GeS => 90,
+        Instruction::I64GeU => 91,
+        Instruction::F32Eq => 92,
+        Instruction::F32Ne => 93,
+        Instruction::F32Lt => 94,
+        Instruction::F32Gt => 95,
+        Instruction::F32Le => 96,
+        Instruction::F32Ge => 97,
+        Instruction::F64Eq => 98,
+        Instruction::F64Ne => 99,
+        Instruction::F64Lt => 100,
+        Instruction::F64Gt => 101,
+        Instruction::F64Le => 102,
+        Instruction::F64Ge => 103,
+        Instruction::I32Clz => 104,
+        Instruction::I32Ctz => 105,
+        Instruction::I32PopCnt => 106,
+        Instruction::I32Add => 107,
+        Instruction::I32Sub => 108,
+        Instruction::I32Mul => 109,
+        Instruction::I32DivS => 110,
+        Instruction::I32DivU => 111,
+        Instruction::I32RemS => 112,
+        Instruction::I32RemU => 113,
+        Instruction::I32And => 114,
+        Instruction::I32Or => 115,
+        Instruction::I32Xor => 116,
+        Instruction::I32Shl => 117,
+        Instruction::I32ShrS => 118,
+        Instruction::I32ShrU => 119,
+        Instruction::I32Rotl => 120,
+        Instruction::I32Rotr => 121,
+        Instruction::I64Clz => 122,
+        Instruction::I64Ctz => 123,
+        Instruction::I64PopCnt => 124,
+        Instruction::I64Add => 125,
+        Instruction::I64Sub => 126,
+        Instruction::I64Mul => 127,
+        Instruction::I64DivS => 128,
+        Instruction::I64DivU => 129,
+        Instruction::I64RemS => 130,
+        Instruction::I64RemU => 131,
+        Instruction::I64And => 132,
+        Instruction::I64Or => 133,
+        Instruction::I64Xor => 134,
+        Instruction::I64Shl => 135,
+        Instruction::I64ShrS => 136,
+        Instruction::I64ShrU => 137,
+        Instruction::I64Rotl => 138,
+        Instruction::I64Rotr => 139,
+        Instruction::F32Abs => 140,
+        Instruction::F32Neg => 141,
+        Instruction::F32Ceil => 142,
+        Instruction::F32Floor => 143,
+        Instruction::F32Trunc => 144,
+        Instruction::F32Nearest => 145,
+        Instruction::F32Sqrt => 146,
+        Instruction::F32Add => 147,
+        Instruction::F32Sub => 148,
+        Instruction::F32Mul => 149,
+        Instruction::F32Div => 150,
+        Instruction::F32Min => 151,
+        Instruction::F32Max => 152,
+        Instruction::F32CopySign => 153,
+        Instruction::F64Abs => 154,
+        Instruction::F64Neg => 155,
+        Instruction::F64Ceil => 156,
+        Instruction::F64Floor => 157,
+        Instruction::F64Trunc => 158,
+        Instruction::F64Nearest => 159,
+        Instruction::F64Sqrt => 160,
+        Instruction::F64Add => 161,
+        Instruction::F64Sub => 162,
+        Instruction::F64Mul => 163,
+        Instruction::F64Div => 164,
+        Instruction::F64Min => 165,
+        Instruction::F64Max => 166,
+        Instruction::F64CopySign => 167,
+        Instruction::I32WrapI64 => 168,
+        Instruction::I32TruncF32S => 169,
+        Instruction::I32TruncF32U => 170,
+        Instruction::I32TruncF64S => 171,
+        Instruction::I32TruncF64U => 172,
+        Instruction::I64ExtendI32S => 173,
+        Instruction::I64ExtendI32U => 174,
+        Instruction::I64TruncF32S => 175,
+        Instruction::I64TruncF32U => 176,
+        Instruction::I64TruncF64S => 177,
+        Instruction::I64TruncF64U => 178,
+        Instruction::F32ConvertI32S => 179,
+        Instruction::F32ConvertI32U => 180,
+        Instruction::F32ConvertI64S => 181,
+        Instruction::F32ConvertI64U => 182,
+        Instruction::F32DemoteF64 => 183,
+        Instruction::F64ConvertI32S => 184,
+        Instruction::F64ConvertI32U => 185,
+        Instruction::F64ConvertI64S => 186,
+        Instruction::F64ConvertI64U => 187,
+        Instruction::F64PromoteF32 => 188,
+        Instruction::I32ReinterpretF32 => 189,
+        Instruction::I64ReinterpretF64 => 190,
+        Instruction::F32ReinterpretI32 => 191,
+        Instruction::F64ReinterpretI64 => 192,
+        Instruction::I32Extend8S => 193,
+        Instruction::I32Extend16S => 194,
+        Instruction::I64Extend8S => 195,
+        Instruction::I64Extend16S => 196,
+        Instruction::I64Extend32S => 197,
+        Instruction::I32TruncSatF32S => 198,
+        Instruction::I32TruncSatF32U => 199,
+        Instruction::I32TruncSatF64S => 200,
+        Instruction::I32TruncSatF64U => 201,
+        Instruction::I64TruncSatF32S => 202,
+        Instruction::I64TruncSatF32U => 203,
+        Instruction::I64TruncSatF64S => 204,
+        Instruction::I64TruncSatF64U => 205,
+        Instruction::V128Load(..) => 206,
+        Instruction::V128Load8x8S(..) => 207,
+        Instruction::V128Load8x8U(..) => 208,
+        Instruction::V128Load16x4S(..) => 209,
+        Instruction::V128Load16x4U(..) => 210,
+        Instruction::V128Load32x2S(..) => 211,
+        Instruction::V128Load32x2U(..) => 212,
+        Instruction::V128Load8Splat(..) => 213,
+        Instruction::V128Load16Splat(..) => 214,
+        Instruction::V128Load32Splat(..) => 215,
+        Instruction::V128Load64Splat(..) => 216,
+        Instruction::V128Store(..) => 217,
+        Instruction::V128Const(..) => 218,
+        Instruction::I8x16Shuffle(..) => 219,
+        Instruction::I8x16Swizzle => 220,
+        Instruction::I8x16Splat => 221,
+        Instruction::I16x8Splat => 222,
+        Instruction::I32x4Splat => 223,
+        Instruction::I64x2Splat => 224,
+        Instruction::F32x4Splat => 225,
+        Instruction::F64x2Splat => 226,
+        Instruction::I8x16ExtractLaneS(..) => 227,
+        Instruction::I8x16ExtractLaneU(..) => 228,
+        Instruction::I8x16ReplaceLane(..) => 229,
+        Instruction::I16x8ExtractLaneS(..) => 230,
+        Instruction::I16x8ExtractLaneU(..) => 231,
+        Instruction::I16x8ReplaceLane(..) => 232,
+        Instruction::I32x4ExtractLane(..) => 233,
+        Instruction::I32x4ReplaceLane(..) => 234,
+        Instruction::I64x2ExtractLane(..) => 235,
+        Instruction::I64x2ReplaceLane(..) => 236,
+        Instruction::F32x4ExtractLane(..) => 237,
+        Instruction::F32x4ReplaceLane(..) => 238,
+        Instruction::F64x2ExtractLane(..) => 239,
+        Instruction::F64x2ReplaceLane(..) => 240,
+        Instruction::I8x16Eq => 241,
+        Instruction::I8x16Ne => 242,
+        Instruction::I8x16LtS => 243,
+        Instruction::I8x16LtU => 244,
+        Instruction::I8x16GtS => 245,
+        Instruction::I8x16GtU => 246,
+        Instruction::I8x16LeS => 247,
+        Instruction::I8x16LeU => 248,
+        Instruction::I8x16GeS => 249,
+        Instruction::I8x16GeU => 250,
+        Instruction::I16x8Eq => 251,
+        Instruction::I16x8Ne => 252,
+        Instruction::I16x8LtS => 253,
+        Instruction::I16x8LtU => 254,
+        Instruction::I16x8GtS => 255,
+        Instruction::I16x8GtU => 256,
+        Instruction::I16x8LeS => 257,
+        Instruction::I16x8LeU => 258,
+        Instruction::I16x8GeS => 259,
+        Instruction::I16x8GeU => 260,
+        Instruction::I32x4Eq => 261,
+        Instruction::I32x4Ne => 262,
+        Instruction::I32x4LtS => 263,
+        Instruction::I32x4LtU => 264,
+        Instruction::I32x4GtS => 265,
+        Instruction::I32x4GtU => 266,
+        Instruction::I32x4LeS => 267,
+        Instruction::I32x4LeU => 268,
+        Instruction::I32x4GeS => 269,
+        Instruction::I32x4GeU => 270,
+        Instruction::F32x4Eq => 271,
+        Instruction::F32x4Ne => 272,
+        Instruction::F32x4Lt => 273,
+        Instruction::F32x4Gt => 274,
+        Instruction::F32x4Le => 275,
+        Instruction::F32x4Ge => 276,
+        Instruction::F64x2Eq => 277,
+        Instruction::F64x2Ne => 278,
+        Instruction::F64x2Lt => 279,
+        Instruction::F64x2Gt => 280,
+        Instruction::F64x2Le => 281,
+        Instruction::F64x2Ge => 282,
+        Instruction::V128Not => 283,
+        Instruction::V128And => 284,
+        Instruction::V128Andnot => 285,
+        Instruction::V128Or => 286,
+        Instruction::V128Xor => 287,
+        Instruction::V128Bitselect => 288,
+        Instruction::V128AnyTrue => 289,
+        Instruction::V128Load8Lane(..) => 290,
+        Instruction::V128Load16Lane(..) => 291,
+        Instruction::V128Load32Lane(..) => 292,
+        Instruction::V128Load64Lane(..) => 293,
+        Instruction::V128Store8Lane(..) => 294,
+        Instruction::V128Store16Lane(..) => 295,
+        Instruction::V128Store32Lane(..) => 296,
+        Instruction::V128Store64Lane(..) => 297,
+        Instruction::V128Load32Zero(..) => 298,
+        Instruction::V128Load64Zero(..) => 299,
+        Instruction::F32x4DemoteF64x2Zero => 300,
+        Instruction::F64x2PromoteLowF32x4 => 301,
+        Instruction::I8x16Abs => 302,
+        Instruction::I8x16Neg => 303,
+        Instruction::I8x16Popcnt => 304,
+        Instruction::I8x16AllTrue => 305,
+        Instruction::I8x16Bitmask => 306,
+        Instruction::I8x16NarrowI16x8S => 307,
+        Instruction::I8x16NarrowI16x8U => 308,
+        Instruction::F32x4Ceil => 309,
+        Instruction::F32x4Floor => 310,
+        Instruction::F32x4Trunc => 311,
+        Instruction::F32x4Nearest => 312,
+        Instruction::I8x16Shl => 313,
+        Instruction::I8x16ShrS => 314,
+        Instruction::I8x16ShrU => 315,
+        Instruction::I8x16Add => 316,
+        Instruction::I8x16AddSatS => 317,
+        Instruction::I8x16AddSatU => 318,
+        Instruction::I8x16Sub => 319,
+        Instruction::I8x16SubSatS => 320,
+        Instruction::I8x16SubSatU => 321,
+        Instruction::F64x2Ceil => 322,
+        Instruction::F64x2Floor => 323,
+        Instruction::I8x16MinS => 324,
+        Instruction::I8x16MinU => 325,
+        Instruction::I8x16MaxS => 326,
+        Instruction::I8x16MaxU => 327,
+        Instruction::F64x2Trunc => 328,
+        Instruction::I8x16AvgrU => 329,
+        Instruction::I16x8ExtaddPairwiseI8x16S => 330,
+        Instruction::I16x8ExtaddPairwiseI8x16U => 331,
+        Instruction::I32x4ExtaddPairwiseI16x8S => 332,
+        Instruction::I32x4ExtaddPairwiseI16x8U => 333,
+        Instruction::I16x8Abs => 334,
+        Instruction::I16x8Neg => 335,
+        Instruction::I16x8Q15mulrSatS => 336,
+        Instruction::I16x8AllTrue => 337,
+        Instruction::I16x8Bitmask => 338,
+        Instruction::I16x8NarrowI32x4S => 339,
+        Instruction::I16x8NarrowI32x4U => 340,
+        Instruction::I16x8ExtendLowI8x16S => 341,
+        Instruction::I16x8ExtendHighI8x16S => 342,
+        Instruction::I16x8ExtendLowI8x16U => 343,
+        Instruction::I16x8ExtendHighI8x16U => 344,
+        Instruction::I16x8Shl => 345,
+        Instruction::I16x8ShrS => 346,
+        Instruction::I16x8ShrU => 347,
+        Instruction::I16x8Add => 348,
+        Instruction::I16x8AddSatS => 349,
+        Instruction::I16x8AddSatU => 350,
+        Instruction::I16x8Sub => 351,
+        Instruction::I16x8SubSatS => 352,
+        Instruction::I16x8SubSatU => 353,
+        Instruction::F64x2Nearest => 354,
+        Instruction::I16x8Mul => 355,
+        Instruction::I16x8MinS => 356,
+        Instruction::I16x8MinU => 357,
+        Instruction::I16x8MaxS => 358,
+        Instruction::I16x8MaxU => 359,
+        Instruction::I16x8AvgrU => 360,
+        Instruction::I16x8ExtmulLowI8x16S => 361,
+        Instruction::I16x8ExtmulHighI8x16S => 362,
+        Instruction::I16x8ExtmulLowI8x16U => 363,
+        Instruction::I16x8ExtmulHighI8x16U => 364,
+        Instruction::I32x4Abs => 365,
+        Instruction::I32x4Neg => 366,
+        Instruction::I32x4AllTrue => 367,
+        Instruction::I32x4Bitmask => 368,
+        Instruction::I32x4ExtendLowI16x8S => 369,
+        Instruction::I32x4ExtendHighI16x8S => 370,
+        Instruction::I32x4ExtendLowI16x8U => 371,
+        Instruction::I32x4ExtendHighI16x8U => 372,
+        Instruction::I32x4Shl => 373,
+        Instruction::I32x4ShrS => 374,
+        Instruction::I32x4ShrU => 375,
+        Instruction::I32x4Add => 376,
+        Instruction::I32x4Sub => 377,
+        Instruction::I32x4Mul => 378,
+        Instruction::I32x4MinS => 379,
+        Instruction::I32x4MinU => 380,
+        Instruction::I32x4MaxS => 381,
+        Instruction::I32x4MaxU => 382,
+        Instruction::I32x4DotI16x8S => 383,
+        Instruction::I32x4ExtmulLowI16x8S => 384,
+        Instruction::I32x4ExtmulHighI16x8S => 385,
+        Instruction::I32x4ExtmulLowI16x8U => 386,
+        Instruction::I32x4ExtmulHighI16x8U => 387,
+        Instruction::I64x2Abs => 388,
+        Instruction::I64x2Neg => 389,
+        Instruction::I64x2AllTrue => 390,
+        Instruction::I64x2Bitmask => 391,
+        Instruction::I64x2ExtendLowI32x4S => 392,
+        Instruction::I64x2ExtendHighI32x4S => 393,
+        Instruction::I64x2ExtendLowI32x4U => 394,
+        Instruction::I64x2ExtendHighI32x4U => 395,
+        Instruction::I64x2Shl => 396,
+        Instruction::I64x2ShrS => 397,
+        Instruction::I64x2ShrU => 398,
+        Instruction::I64x2Add => 399,
+        Instruction::I64x2Sub => 400,
+        Instruction::I64x2Mul => 401,
+        Instruction::I64x2Eq => 402,
+        Instruction::I64x2Ne => 403,
+        Instruction::I64x2LtS => 404,
+        Instruction::I64x2GtS => 405,
+        Instruction::I64x2LeS => 406,
+        Instruction::I64x2GeS => 407,
+        Instruction::I64x2ExtmulLowI32x4S => 408,
+        Instruction::I64x2ExtmulHighI32x4S => 409,
+        Instruction::I64x2ExtmulLowI32x4U => 410,
+        Instruction::I64x2ExtmulHighI32x4U => 411,
+        Instruction::F32x4Abs => 412,
+        Instruction::F32x4Neg => 413,
+        Instruction::F32x4Sqrt => 414,
+        Instruction::F32x4Add => 415,
+        Instruction::F32x4Sub => 416,
+        Instruction::F32x4Mul => 417,
+        Instruction::F32x4Div => 418,
+        Instruction::F32x4Min => 419,
+        Instruction::F32x4Max => 420,
+        Instruction::F32x4Pmin => 421,
+        Instruction::F32x4Pmax => 422,
+        Instruction::F64x2Abs => 423,
+        Instruction::F64x2Neg => 424,
+        Instruction::F64x2Sqrt => 425,
+        Instruction::F64x2Add => 426,
+        Instruction::F64x2Sub => 427,
+        Instruction::F64x2Mul => 428,
+        Instruction::F64x2Div => 429,
+        Instruction::F64x2Min => 430,
+        Instruction::F64x2Max => 431,
+        Instruction::F64x2Pmin => 432,
+        Instruction::F64x2Pmax => 433,
+        Instruction::I32x4TruncSatF32x4S => 434,
+        Instruction::I32x4TruncSatF32x4U => 435,
+        Instruction::F32x4ConvertI32x4S => 436,
+        Instruction::F32x4ConvertI32x4U => 437,
+        Instruction::I32x4TruncSatF64x2SZero => 438,
+        Instruction::I32x4TruncSatF64x2UZero => 439,
+        Instruction::F64x2ConvertLowI32x4S => 440,
+        Instruction::F64x2ConvertLowI32x4U => 441,
+        Instruction::MemoryAtomicNotify(..) => 442,
+        Instruction::MemoryAtomicWait32(..) => 443,
+        Instruction::MemoryAtomicWait64(..) => 444,
+        Instruction::AtomicFence => 445,
+        Instruction::I32AtomicLoad(..) => 446,
+        Instruction::I64AtomicLoad(..) => 447,
+        Instruction::I32AtomicLoad8U(..) => 448,
+        Instruction::I32AtomicLoad16U(..) => 449,
+        Instruction::I64AtomicLoad8U(..) => 450,
+        Instruction::I64AtomicLoad16U(..) => 451,
+        Instruction::I64AtomicLoad32U(..) => 452,
+        Instruction::I32AtomicStore(..) => 453,
+        Instruction::I64AtomicStore(..) => 454,
+        Instruction::I32AtomicStore8(..) => 455,
+        Instruction::I32AtomicStore16(..) => 456,
+        Instruction::I64AtomicStore8(..) => 457,
+        Instruction::I64AtomicStore16(..) => 458,
+        Instruction::I64AtomicStore32(..) => 459,
+        Instruction::I32AtomicRmwAdd(..) => 460,
+        Instruction::I64AtomicRmwAdd(..) => 461,
+        Instruction::I32AtomicRmw8AddU(..) => 462,
+        Instruction::I32AtomicRmw16AddU(..) => 463,
+        Instruction::I64AtomicRmw8AddU(..) => 464,
+        Instruction::I64AtomicRmw16AddU(..) => 465,
+        Instruction::I64AtomicRmw32AddU(..) => 466,
+        Instruction::I32AtomicRmwSub(..) => 467,
+        Instruction::I64AtomicRmwSub(..) => 468,
+        Instruction::I32AtomicRmw8SubU(..) => 469,
+        Instruction::I32AtomicRmw16SubU(..) => 470,
+        Instruction::I64AtomicRmw8SubU(..) => 471,
+        Instruction::I64AtomicRmw16SubU(..) => 472,
+        Instruction::I64AtomicRmw32SubU(..) => 473,
+        Instruction::I32AtomicRmwAnd(..) => 474,
+        Instruction::I64AtomicRmwAnd(..) => 475,
+        Instruction::I32AtomicRmw8AndU(..) => 476,
+        Instruction::I32AtomicRmw16AndU(..) => 477,
+        Instruction::I64AtomicRmw8AndU(..) => 478,
+        Instruction::I64AtomicRmw16AndU(..) => 479,
+        Instruction::I64AtomicRmw32AndU(..) => 480,
+        Instruction::I32AtomicRmwOr(..) => 481,
+        Instruction::I64AtomicRmwOr(..) => 482,
+        Instruction::I32AtomicRmw8OrU(..) => 483,
+        Instruction::I32AtomicRmw16OrU(..) => 484,
+        Instruction::I64AtomicRmw8OrU(..) => 485,
+        Instruction::I64AtomicRmw16OrU(..) => 486,
+        Instruction::I64AtomicRmw32OrU(..) => 487,
+        Instruction::I32AtomicRmwXor(..) => 488,
+        Instruction::I64AtomicRmwXor(..) => 489,
+        Instruction::I32AtomicRmw8XorU(..) => 490,
+        Instruction::I32AtomicRmw16XorU(..) => 491,
+        Instruction::I64AtomicRmw8XorU(..) => 492,
+        Instruction::I64AtomicRmw16XorU(..) => 493,
+        Instruction::I64AtomicRmw32XorU(..) => 494,
+        Instruction::I32AtomicRmwXchg(..) => 495,
+        Instruction::I64AtomicRmwXchg(..) => 496,
+        Instruction::I32AtomicRmw8XchgU(..) => 497,
+        Instruction::I32AtomicRmw16XchgU(..) => 498,
+        Instruction::I64AtomicRmw8XchgU(..) => 499,
+        Instruction::I64AtomicRmw16XchgU(..) => 500,
+        Instruction::I64AtomicRmw32XchgU(..) => 501,
+        Instruction::I32AtomicRmwCmpxchg(..) => 502,
+        Instruction::I64AtomicRmwCmpxchg(..) => 503,
+        Instruction::I32AtomicRmw8CmpxchgU(..) => 504,
+        Instruction::I32AtomicRmw16CmpxchgU(..) => 505,
+        Instruction::I64AtomicRmw8CmpxchgU(..) => 506,
+        Instruction::I64AtomicRmw16CmpxchgU(..) => 507,
+        Instruction::I64AtomicRmw32CmpxchgU(..) => 508,
+    }
+}
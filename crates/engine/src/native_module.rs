@@ -25,6 +25,7 @@ pub type NativeFunction =
 /// - 函数列表
 /// - 函数的名称
 /// - 局部变量（即型参）名称
+/// - 标签（tag）的类型以及名称
 ///
 /// 都分开存放。
 pub struct NativeModule {
@@ -40,6 +41,11 @@ pub struct NativeModule {
     pub function_names: Vec<String>,
     pub local_variable_names: Vec<Vec<String>>,
 
+    // 标签列表
+    // 本地函数可以抛出这些标签的异常，WebAssembly 模块也可以导入这些标签
+    pub tag_to_type_index_list: Vec<usize>,
+    pub tag_names: Vec<String>,
+
     pub module_context: Box<dyn ModuleContext>,
 }
 
@@ -58,6 +64,9 @@ impl NativeModule {
             function_names: vec![],
             local_variable_names: vec![],
 
+            tag_to_type_index_list: vec![],
+            tag_names: vec![],
+
             module_context: module_context,
         }
     }
@@ -83,6 +92,21 @@ impl NativeModule {
         );
     }
 
+    /// 添加标签
+    ///
+    /// 标签的类型是一个没有返回值的函数类型，参数 params 即异常携带的数值的类型。
+    pub fn add_tag(&mut self, name: &str, params: Vec<ValueType>) {
+        let function_type_index = self.add_function_type(params, vec![]);
+
+        self.tag_to_type_index_list.push(function_type_index);
+        self.tag_names.push(name.to_string());
+    }
+
+    /// 根据标签名称获得标签的索引值
+    pub fn find_tag_index_by_exported_name(&self, name: &str) -> Option<usize> {
+        self.tag_names.iter().position(|tag_name| tag_name == name)
+    }
+
     /// 根据函数名称获得函数的索引值
     ///
     /// 注意：对于本地模块来说
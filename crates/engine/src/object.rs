@@ -7,10 +7,17 @@
 use anvm_ast::ast;
 use anvm_ast::instruction::{self, BlockType};
 
+use crate::module_cache::ContentHash;
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct NamedAstModule {
     pub name: String,
     pub module: ast::Module,
+
+    /// 模块二进制内容的哈希值，用于查找编译模块缓存（见 `module_cache` 模块）
+    ///
+    /// 为 None 时不使用缓存。
+    pub option_content_hash: Option<ContentHash>,
 }
 
 impl NamedAstModule {
@@ -18,8 +25,15 @@ impl NamedAstModule {
         Self {
             name: name.to_string(),
             module,
+            option_content_hash: None,
         }
     }
+
+    /// 设置模块二进制内容的哈希值，哈希值一般由 `module_cache::hash_bytes` 函数计算
+    pub fn with_content_hash(mut self, content_hash: ContentHash) -> Self {
+        self.option_content_hash = Some(content_hash);
+        self
+    }
 }
 
 /// 一个模块里的所有类型函数的信息
@@ -68,32 +82,74 @@ pub enum BlockItem {
     },
 }
 
+/// 跳转目标
+///
+/// 结构块不再创建栈帧，结构块内的操作数跟函数的操作数位于同一个栈帧的运算操作数段，
+/// 所以跳转只需：
+///
+/// 1. 保留栈顶 arity 个操作数；
+/// 2. 把操作数栈截断到目标结构块的栈高度；
+/// 3. 压入第 1 步保留的操作数，然后跳到目标地址。
+///
+/// 其中栈高度在解码时已经通过静态分析预先计算好了。
+#[derive(Debug, PartialEq, Clone)]
+pub struct BranchTarget {
+    /// 指令所在位置到目标结构块的相对深度，仅用于调试和出错信息
+    pub relative_depth: usize,
+
+    /// 进入目标结构块时（不包括结构块的参数）运算操作数的数量，
+    /// 即相对于当前调用帧的运算操作数段开始位置的栈高度。
+    /// 跳到函数本层时其值为 0。
+    pub stack_height: usize,
+
+    /// 跳转时需要带到目标的操作数的数量，
+    /// 对于 block/if/try_table 结构块以及函数本层，是返回值的数量；
+    /// 对于 loop 结构块，是参数的数量。
+    pub arity: usize,
+
+    /// 跳转的目标地址
+    ///
+    /// - 对于 block/if/try_table 结构块，是结构块 `end 指令` 的下一个指令的位置；
+    /// - 对于 loop 结构块，是 `loop 指令` 的下一个指令的位置；
+    /// - 对于函数本层，是函数 `end 指令` 所在的位置。
+    pub address: usize,
+}
+
+/// `try_table` 结构块的 catch 子句
 #[derive(Debug, PartialEq, Clone)]
-pub enum BranchTarget {
-    /// 参数 address 是结构块的 `end 指令` 所在的位置。
-    Break(/* relative_depth */ usize, /* address */ usize),
+pub struct CatchItem {
+    /// 捕获的异常的标签在当前模块里的索引，
+    /// 对于 `catch_all` 和 `catch_all_ref` 子句，其值为 None。
+    pub option_tag_index: Option<usize>,
+
+    /// 是否把异常的引用（`exnref`）也带到目标结构块，
+    /// 对于 `catch_ref` 和 `catch_all_ref` 子句，其值为 true。
+    pub is_ref: bool,
 
-    /// 参数 address 是 `loop 指令` 所在的位置。
-    Recur(/* relative_depth */ usize, /* address */ usize),
+    /// 捕获异常之后的跳转目标
+    ///
+    /// 注意跳转的相对深度是从 `try_table` 结构块外部开始计算的，
+    /// 即跟 catch 子句里的相对深度一样。
+    pub branch_target: BranchTarget,
 }
 
 /// 控制指令
 #[derive(Debug, PartialEq, Clone)]
 pub enum Control {
-    /// 进入一个新的栈帧
+    /// 进入一个结构块
     ///
     /// 原 `block/loop 指令`
     ///
-    /// block 和 loop 结构块是相似的，仅当执行到结构块当中的
-    /// br 指令时，br 指令的行为有所不同，但两种结构块本身的行为是一样的，
-    /// 所以可以使用相同的方法来解析。
+    /// 结构块不创建栈帧，进入结构块时不需要任何操作，跳转目标的栈高度和地址
+    /// 已经在解码时计算好并保存在各个跳转指令里，这里保留结构块的信息主要
+    /// 是为了让指令序列跟原始指令一一对应，以便于追踪和调试。
     Block {
         block_type: BlockType,
         block_index: usize,
         end_address: usize,
     },
 
-    /// 进入一个新的栈帧，并当原栈顶的数值等于 0 时，跳转到指定的地址 alternate_address,
+    /// 进入一个结构块，并当原栈顶的数值等于 0 时，跳转到指定的地址 alternate_address,
     ///
     /// 原 `if 指令`
     ///
@@ -105,83 +161,48 @@ pub enum Control {
         end_address: usize,
     },
 
+    /// 进入一个结构块，跟 `Block` 一样，但在结构块内抛出的异常
+    /// 会按顺序跟 catch_items 匹配。
+    ///
+    /// 原 `try_table 指令`
+    TryTable {
+        block_type: BlockType,
+        block_index: usize,
+        end_address: usize,
+        catch_items: Vec<CatchItem>,
+    },
+
     /// 无条件跳到同一个 block 层的指定位置
     ///
-    /// 原 `else 指令`
+    /// 原 `else 指令`，参数 address 是 if 结构块 `end 指令` 的下一个指令的位置。
     JumpWithinBlock(/* address */ usize),
 
     /// 跳转到指定的地址
-    /// 其中 relative_depth 为当前栈帧距离目标栈帧的层次数量，当数量为 0 时，表示
-    /// 跳转到当前栈帧层的其他地址，当数量 >0 时，表示需要弹出相应的栈帧数量。
     ///
     /// 跳转可以理解为提前结束当前函数或者当前一系列的结构块，即类似 Rust 语言的
     /// `break 参数` 或者 `return 参数` 语句，它能将指定的数据带出结构块。
-    ///
-    /// 例如：
-    /// 当前栈帧处于一系列结构块的内部，当遇到 br 跳到函数本层（相当于 `return 指令`），则
-    /// 需要把当前栈帧的操作数作为函数的返回值。
+    /// 对于目标是 loop 结构块的情况，则相当于 `continue 参数` 语句。
     ///
     /// 原 `br/return 指令`
     ///
-    /// 参数 address 是结构块的 `end 指令` 所在的位置。
     /// 参数 option_block_index 指令所在的结构块索引。
     ///
     /// 结构块索引主要用于调式程序时方便定位出错的结构位置信息，即供用户看的，
     /// 索引本身不参与指令的解析和执行。
     Break {
         option_block_index: Option<usize>,
-        relative_depth: usize,
-        address: usize,
+        branch_target: BranchTarget,
     },
 
     /// 跳转到指定的地址
-    /// 跟 Jump 指令类似，但仅当原栈顶的数值不等于 0 时才跳转，否则什么事都不做
+    /// 跟 Break 指令类似，但仅当原栈顶的数值不等于 0 时才跳转，否则什么事都不做
     ///
     /// 原 `br_if 指令`
     ///
-    /// 参数 address 是结构块的 `end 指令` 所在的位置。
     /// 参数 option_block_index 指令所在的结构块索引。
-    ///
-    /// 结构块索引主要用于调式程序时方便定位出错的结构位置信息，即供用户看的，
-    /// 索引本身不参与指令的解析和执行。
     BreakWhenNotEqZero {
         option_block_index: Option<usize>,
-        relative_depth: usize,
-        address: usize,
-    },
-
-    /// 重复执行当前结构块
-    ///
-    /// 原 `br 指令`，对应于跳转到 loop 结构块的情况
-    /// - 如果 relative_depth 为 0，只需简单地跳到 loop 指令所在地位置即可，
-    ///   不需要弹出/压入参数，也不需要弹出/压入栈帧
-    /// - 如果 relative_depth 大于 0，则需要弹出目标 loop 结构块所需要的参数，
-    ///   然后弹出跟 relative_depth 的值一样数量的栈帧，再压入实参，然而还是不需要创建新的栈帧
-    ///
-    /// 参数 address 是 `loop 指令` 所在的位置。
-    /// 参数 block_index 指令所在的结构块索引。
-    ///
-    /// 结构块索引主要用于调式程序时方便定位出错的结构位置信息，即供用户看的，
-    /// 索引本身不参与指令的解析和执行。
-    Recur {
-        block_index: usize,
-        relative_depth: usize,
-        address: usize,
-    },
-
-    /// 重复执行当前结构块
-    ///
-    /// 原 `br_if 指令`，对应于跳转到 loop 结构块的情况
-    ///
-    /// 参数 address 是 `loop 指令` 所在的位置。
-    /// 参数 block_index 指令所在的结构块索引。
-    ///
-    /// 结构块索引主要用于调式程序时方便定位出错的结构位置信息，即供用户看的，
-    /// 索引本身不参与指令的解析和执行。
-    RecurWhenNotEqZero {
-        block_index: usize,
-        relative_depth: usize,
-        address: usize,
+        branch_target: BranchTarget,
     },
 
     /// 原 `br_table 指令`
@@ -239,6 +260,46 @@ pub enum Control {
         table_index: usize,
     },
 
+    /// 尾调用（普通）函数
+    ///
+    /// 原 `return_call 指令`，对应于调用普通函数（非本地函数）的情况。
+    /// 尾调用会复用当前函数的调用帧，而不是压入一个新的调用帧。
+    ReturnCall {
+        vm_module_index: usize,
+        type_index: usize,
+        function_index: usize,
+        internal_function_index: usize,
+        address: usize,
+    },
+
+    /// 尾调用本地函数
+    ///
+    /// 原 `return_call 指令`，对应于调用本地函数的情况。
+    /// 本地函数的调用不需要调用帧，所以相当于调用本地函数之后紧接着执行 `return 指令`。
+    ReturnCallNative {
+        native_module_index: usize,
+        type_index: usize,
+        function_index: usize,
+    },
+
+    /// 函数间接尾调用
+    ///
+    /// 原 `return_call_indirect 指令`
+    ReturnCallIndirect {
+        type_index: usize,
+        table_index: usize,
+    },
+
+    /// 抛出异常
+    ///
+    /// 原 `throw 指令`，参数是标签在当前模块里的索引
+    Throw(/* tag_index */ usize),
+
+    /// 重新抛出异常引用所指向的异常
+    ///
+    /// 原 `throw_ref 指令`
+    ThrowRef,
+
     /// 函数或者结构块结束
     ///
     /// 原 `end 指令`
@@ -268,3 +329,42 @@ pub enum Instruction {
     /// 会控制或者会改变程序执行顺序的指令
     Control(Control),
 }
+
+/// 超级指令
+///
+/// 由解码器把若干条连续的常见指令融合而成，执行一个超级指令相当于依次执行被融合的
+/// 各条原指令，但只需分派一次。
+#[derive(Debug, PartialEq, Clone)]
+pub enum FusedInstruction {
+    /// `local.get a; local.get b; i32.add`
+    LocalGetLocalGetI32Add { left_index: u32, right_index: u32 },
+
+    /// `local.get a; i32.const c; i32.add; local.set b`
+    LocalGetI32ConstI32AddLocalSet {
+        source_index: u32,
+        value: i32,
+        dest_index: u32,
+    },
+
+    /// `i32.const c; i32.lt_s; br_if`
+    I32ConstI32LtSBreakWhenNotEqZero {
+        value: i32,
+        option_block_index: Option<usize>,
+        branch_target: BranchTarget,
+    },
+}
+
+/// 超级指令项
+///
+/// 超级指令并不替换指令列表里的原指令（指令列表仍然跟原始指令一一对应），
+/// 而是记录被融合的原指令的位置，即从 `address` 开始的 `length` 条指令。
+#[derive(Debug, PartialEq, Clone)]
+pub struct FusedItem {
+    /// 第一条被融合的原指令的地址
+    pub address: usize,
+
+    /// 被融合的原指令的数量
+    pub length: usize,
+
+    pub fused_instruction: FusedInstruction,
+}
@@ -0,0 +1,1321 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 寄存器中间表示
+//!
+//! 栈式解释器的每一个运算指令都需要从操作数栈弹出操作数，然后把结果压入栈，
+//! 而像 `local.get`、`i32.const` 这类指令仅仅是为了把操作数搬到栈顶。
+//!
+//! 本模块把函数的指令序列降低（lower）为基于寄存器的中间表示：局部变量以及
+//! 操作数栈上的临时数值都看作调用帧里的寄存器，运算指令则转换为三地址形式，
+//! 比如
+//!
+//! ```text
+//! local.get 0
+//! local.get 1
+//! i32.add
+//! local.set 2
+//! ```
+//!
+//! 会被转换为一个操作 `i32.add r2, r0, r1`。
+//!
+//! 寄存器的编号即栈槽相对于调用帧开始位置（fp）的偏移值，所以寄存器的布局
+//! 跟栈式解释器的调用帧完全相同：
+//!
+//! ```text
+//! r0 .. r(L-1)    局部变量（包括参数），L 为局部变量的数量
+//! rL .. r(L+7)    信息段
+//! r(L+8+h)        栈高度为 h 的操作数（称为临时寄存器）
+//! ```
+//!
+//! 因此两种解释器可以在任意一个 `出口` 处交接：函数调用、函数返回等指令
+//! 仍然交由栈式解释器执行，执行完毕之后再回到寄存器中间表示继续执行。
+//! 其余暂不支持的顺序指令（比如内存读写、SIMD 指令）也以 `Stack` 操作的形式
+//! 交由栈式解释器逐条执行。
+//!
+//! 包含 `try_table` 结构块或者 v128 类型局部变量的函数不会被降低，这些函数
+//! 始终由栈式解释器执行。
+
+use std::mem;
+
+use anvm_ast::{
+    instruction::{self, BlockType},
+    types::ValueType,
+};
+
+use crate::{
+    object::{BranchTarget, Control, FunctionItem, Instruction},
+    stack_effect::get_stack_effect,
+    vm::Resource,
+    vm_stack::INFO_SEGMENT_ITEM_COUNT,
+};
+
+/// 寄存器的编号，即栈槽相对于调用帧开始位置的偏移值
+pub type Register = u32;
+
+/// 二元运算的操作数
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct BinaryOperands {
+    pub dest: Register,
+    pub left: Register,
+    pub right: Register,
+}
+
+/// 一元运算以及复制操作的操作数
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct UnaryOperands {
+    pub dest: Register,
+    pub source: Register,
+}
+
+/// 寄存器中间表示的操作
+///
+/// 运算操作的语义跟同名的原指令一致，只是操作数从寄存器读取，结果写入寄存器。
+/// 跳转操作的目标是操作在操作列表里的索引。
+#[derive(Debug, PartialEq, Clone)]
+pub enum RegisterOp {
+    /// 把常量写入寄存器，常量已经编码为栈槽的格式
+    Const {
+        dest: Register,
+        value: u64,
+        value_type: ValueType,
+    },
+
+    /// 复制寄存器，不能用于 v128 数值
+    Copy(UnaryOperands),
+
+    I32Eqz(UnaryOperands),
+    I64Eqz(UnaryOperands),
+
+    I32Eq(BinaryOperands),
+    I32Ne(BinaryOperands),
+    I32LtS(BinaryOperands),
+    I32LtU(BinaryOperands),
+    I32GtS(BinaryOperands),
+    I32GtU(BinaryOperands),
+    I32LeS(BinaryOperands),
+    I32LeU(BinaryOperands),
+    I32GeS(BinaryOperands),
+    I32GeU(BinaryOperands),
+
+    I64Eq(BinaryOperands),
+    I64Ne(BinaryOperands),
+    I64LtS(BinaryOperands),
+    I64LtU(BinaryOperands),
+    I64GtS(BinaryOperands),
+    I64GtU(BinaryOperands),
+    I64LeS(BinaryOperands),
+    I64LeU(BinaryOperands),
+    I64GeS(BinaryOperands),
+    I64GeU(BinaryOperands),
+
+    F32Eq(BinaryOperands),
+    F32Ne(BinaryOperands),
+    F32Lt(BinaryOperands),
+    F32Gt(BinaryOperands),
+    F32Le(BinaryOperands),
+    F32Ge(BinaryOperands),
+
+    F64Eq(BinaryOperands),
+    F64Ne(BinaryOperands),
+    F64Lt(BinaryOperands),
+    F64Gt(BinaryOperands),
+    F64Le(BinaryOperands),
+    F64Ge(BinaryOperands),
+
+    I32Add(BinaryOperands),
+    I32Sub(BinaryOperands),
+    I32Mul(BinaryOperands),
+    I32DivS(BinaryOperands),
+    I32DivU(BinaryOperands),
+    I32RemS(BinaryOperands),
+    I32RemU(BinaryOperands),
+    I32And(BinaryOperands),
+    I32Or(BinaryOperands),
+    I32Xor(BinaryOperands),
+    I32Shl(BinaryOperands),
+    I32ShrS(BinaryOperands),
+    I32ShrU(BinaryOperands),
+    I32Rotl(BinaryOperands),
+    I32Rotr(BinaryOperands),
+
+    I64Add(BinaryOperands),
+    I64Sub(BinaryOperands),
+    I64Mul(BinaryOperands),
+    I64DivS(BinaryOperands),
+    I64DivU(BinaryOperands),
+    I64RemS(BinaryOperands),
+    I64RemU(BinaryOperands),
+    I64And(BinaryOperands),
+    I64Or(BinaryOperands),
+    I64Xor(BinaryOperands),
+    I64Shl(BinaryOperands),
+    I64ShrS(BinaryOperands),
+    I64ShrU(BinaryOperands),
+    I64Rotl(BinaryOperands),
+    I64Rotr(BinaryOperands),
+
+    F32Add(BinaryOperands),
+    F32Sub(BinaryOperands),
+    F32Mul(BinaryOperands),
+    F32Div(BinaryOperands),
+
+    F64Add(BinaryOperands),
+    F64Sub(BinaryOperands),
+    F64Mul(BinaryOperands),
+    F64Div(BinaryOperands),
+
+    /// 无条件跳转
+    Jump(/* target */ u32),
+
+    /// 当寄存器的值（i32）等于 0 时跳转
+    JumpWhenEqZero {
+        condition: Register,
+        target: u32,
+    },
+
+    /// 当寄存器的值（i32）不等于 0 时跳转
+    JumpWhenNotEqZero {
+        condition: Register,
+        target: u32,
+    },
+
+    /// 复制一段连续的寄存器，用于跳转时把操作数带到目标结构块，可以用于 v128 数值
+    Move {
+        dest: Register,
+        source: Register,
+        count: u32,
+    },
+
+    /// 原 `br_table 指令`
+    ///
+    /// 参数 table 是跳转表在 `RegisterCode::branch_tables` 里的索引。
+    BranchTable {
+        index: Register,
+        table: u32,
+    },
+
+    /// 由栈式解释器执行指定地址的顺序指令
+    ///
+    /// height 为执行该指令之前的栈高度，即操作数栈（运算操作数段）的大小。
+    Stack {
+        address: u32,
+        height: u32,
+    },
+
+    /// 离开寄存器中间表示，由栈式解释器从指定地址继续执行
+    ///
+    /// 用于函数调用、函数返回以及 `unreachable` 等指令。
+    Exit {
+        address: u32,
+        height: u32,
+    },
+}
+
+impl RegisterOp {
+    /// 获取运算操作的目标寄存器
+    fn get_dest_mut(&mut self) -> Option<&mut Register> {
+        match self {
+            RegisterOp::Const { dest, .. } => Some(dest),
+            RegisterOp::Copy(operands)
+            | RegisterOp::I32Eqz(operands)
+            | RegisterOp::I64Eqz(operands) => Some(&mut operands.dest),
+            RegisterOp::Jump(_)
+            | RegisterOp::JumpWhenEqZero { .. }
+            | RegisterOp::JumpWhenNotEqZero { .. }
+            | RegisterOp::Move { .. }
+            | RegisterOp::BranchTable { .. }
+            | RegisterOp::Stack { .. }
+            | RegisterOp::Exit { .. } => None,
+            RegisterOp::I32Eq(operands)
+            | RegisterOp::I32Ne(operands)
+            | RegisterOp::I32LtS(operands)
+            | RegisterOp::I32LtU(operands)
+            | RegisterOp::I32GtS(operands)
+            | RegisterOp::I32GtU(operands)
+            | RegisterOp::I32LeS(operands)
+            | RegisterOp::I32LeU(operands)
+            | RegisterOp::I32GeS(operands)
+            | RegisterOp::I32GeU(operands)
+            | RegisterOp::I64Eq(operands)
+            | RegisterOp::I64Ne(operands)
+            | RegisterOp::I64LtS(operands)
+            | RegisterOp::I64LtU(operands)
+            | RegisterOp::I64GtS(operands)
+            | RegisterOp::I64GtU(operands)
+            | RegisterOp::I64LeS(operands)
+            | RegisterOp::I64LeU(operands)
+            | RegisterOp::I64GeS(operands)
+            | RegisterOp::I64GeU(operands)
+            | RegisterOp::F32Eq(operands)
+            | RegisterOp::F32Ne(operands)
+            | RegisterOp::F32Lt(operands)
+            | RegisterOp::F32Gt(operands)
+            | RegisterOp::F32Le(operands)
+            | RegisterOp::F32Ge(operands)
+            | RegisterOp::F64Eq(operands)
+            | RegisterOp::F64Ne(operands)
+            | RegisterOp::F64Lt(operands)
+            | RegisterOp::F64Gt(operands)
+            | RegisterOp::F64Le(operands)
+            | RegisterOp::F64Ge(operands)
+            | RegisterOp::I32Add(operands)
+            | RegisterOp::I32Sub(operands)
+            | RegisterOp::I32Mul(operands)
+            | RegisterOp::I32DivS(operands)
+            | RegisterOp::I32DivU(operands)
+            | RegisterOp::I32RemS(operands)
+            | RegisterOp::I32RemU(operands)
+            | RegisterOp::I32And(operands)
+            | RegisterOp::I32Or(operands)
+            | RegisterOp::I32Xor(operands)
+            | RegisterOp::I32Shl(operands)
+            | RegisterOp::I32ShrS(operands)
+            | RegisterOp::I32ShrU(operands)
+            | RegisterOp::I32Rotl(operands)
+            | RegisterOp::I32Rotr(operands)
+            | RegisterOp::I64Add(operands)
+            | RegisterOp::I64Sub(operands)
+            | RegisterOp::I64Mul(operands)
+            | RegisterOp::I64DivS(operands)
+            | RegisterOp::I64DivU(operands)
+            | RegisterOp::I64RemS(operands)
+            | RegisterOp::I64RemU(operands)
+            | RegisterOp::I64And(operands)
+            | RegisterOp::I64Or(operands)
+            | RegisterOp::I64Xor(operands)
+            | RegisterOp::I64Shl(operands)
+            | RegisterOp::I64ShrS(operands)
+            | RegisterOp::I64ShrU(operands)
+            | RegisterOp::I64Rotl(operands)
+            | RegisterOp::I64Rotr(operands)
+            | RegisterOp::F32Add(operands)
+            | RegisterOp::F32Sub(operands)
+            | RegisterOp::F32Mul(operands)
+            | RegisterOp::F32Div(operands)
+            | RegisterOp::F64Add(operands)
+            | RegisterOp::F64Sub(operands)
+            | RegisterOp::F64Mul(operands)
+            | RegisterOp::F64Div(operands) => Some(&mut operands.dest),
+        }
+    }
+
+    /// 设置跳转操作的目标
+    fn set_jump_target(&mut self, op_index: u32) {
+        match self {
+            RegisterOp::Jump(target)
+            | RegisterOp::JumpWhenEqZero { target, .. }
+            | RegisterOp::JumpWhenNotEqZero { target, .. } => *target = op_index,
+            _ => unreachable!("not a jump op"),
+        }
+    }
+}
+
+/// 入口
+///
+/// 栈式解释器执行到入口所在的地址时，转为执行寄存器中间表示。
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct EntryItem {
+    /// 入口对应的操作的索引
+    pub op_index: u32,
+
+    /// 函数的最大栈高度，进入时需要预先为所有临时寄存器分配栈槽
+    pub max_stack_height: u32,
+}
+
+/// 跳转表的一项
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct BranchTableItem {
+    /// 需要带到目标结构块的操作数的位置，count 为 0 表示无需复制
+    pub dest: Register,
+    pub source: Register,
+    pub count: u32,
+
+    /// 目标操作的索引
+    pub target: u32,
+}
+
+/// 一个模块的寄存器中间表示
+#[derive(Debug, PartialEq, Clone)]
+pub struct RegisterCode {
+    /// 模块所有已降低的函数的操作列表
+    pub ops: Vec<RegisterOp>,
+
+    /// 跳转表列表，每个跳转表的最后一项是默认目标
+    pub branch_tables: Vec<Vec<BranchTableItem>>,
+
+    /// 入口列表，跟模块的指令列表一一对应
+    ///
+    /// 入口包括函数的第一条指令以及函数调用指令的下一条指令，其余位置为 None。
+    pub entries: Vec<Option<EntryItem>>,
+}
+
+impl RegisterCode {
+    /// 把指定模块的内部函数降低为寄存器中间表示
+    ///
+    /// 需要读取其它模块（包括本地模块）的函数类型以确定函数调用指令的栈效应，
+    /// 所以传入整个 Resource。
+    pub fn lower(resource: &Resource, vm_module_index: usize) -> Self {
+        let vm_module = &resource.vm_modules[vm_module_index];
+        let local_variable_types_list = &vm_module.internal_function_local_variable_types_list;
+
+        let mut register_code = RegisterCode {
+            ops: vec![],
+            branch_tables: vec![],
+            entries: vec![None; vm_module.instructions.len()],
+        };
+
+        // 内部函数位于函数列表的末尾，跟局部变量数据类型列表一一对应
+        let internal_function_offset =
+            vm_module.function_items.len() - local_variable_types_list.len();
+
+        for (function_item, local_variable_types) in vm_module.function_items
+            [internal_function_offset..]
+            .iter()
+            .zip(local_variable_types_list)
+        {
+            if let FunctionItem::Normal {
+                type_index,
+                start_address,
+                end_address,
+                ..
+            } = function_item
+            {
+                let function_type = &vm_module.function_types[*type_index];
+                let instructions = &vm_module.instructions[*start_address..=*end_address];
+
+                if !is_lowerable(instructions, &function_type.params, local_variable_types) {
+                    continue;
+                }
+
+                let function_lowering = FunctionLowering::new(
+                    resource,
+                    vm_module_index,
+                    &mut register_code,
+                    function_type.params.len() + local_variable_types.len(),
+                    function_type.results.len(),
+                    *start_address,
+                    instructions,
+                );
+                function_lowering.lower();
+            }
+        }
+
+        register_code
+    }
+}
+
+/// 判断函数能否降低为寄存器中间表示
+///
+/// 寄存器之间的复制不处理 v128 数值的高 64 位，所以不降低含有 v128 类型局部变量的函数；
+/// 异常被捕获之后会跳到 catch 子句的目标地址，该地址不是入口，所以也不降低
+/// 含有 `try_table` 结构块的函数。
+///
+/// 局部变量的寄存器直接对应调用帧里的栈槽，JIT 编译的机器码读写栈槽时并不检查边界，
+/// 所以虽然模块在实例化时已经通过验证，这里仍然拒绝局部变量索引超出范围的函数，
+/// 以免越界读写栈槽之外的内存。
+fn is_lowerable(
+    instructions: &[Instruction],
+    parameter_types: &[ValueType],
+    local_variable_types: &[ValueType],
+) -> bool {
+    let locals_count = parameter_types.len() + local_variable_types.len();
+
+    parameter_types
+        .iter()
+        .chain(local_variable_types)
+        .all(|value_type| *value_type != ValueType::V128)
+        && instructions.iter().all(|instruction| match instruction {
+            Instruction::Control(Control::TryTable { .. }) => false,
+            Instruction::Sequence(
+                instruction::Instruction::LocalGet(index)
+                | instruction::Instruction::LocalSet(index)
+                | instruction::Instruction::LocalTee(index),
+            ) => (*index as usize) < locals_count,
+            _ => true,
+        })
+}
+
+/// 降低过程中的操作数
+///
+/// 为了减少复制，`local.get` 以及 `t.const` 指令并不立即生成操作，而是把
+/// 局部变量或者常量记录在操作数栈上，直到被运算操作使用时直接读取局部变量的寄存器，
+/// 或者在需要时（比如进入结构块、跳转以及调用函数之前）才写入临时寄存器。
+#[derive(Debug, PartialEq, Clone)]
+enum Operand {
+    /// 已经存放在临时寄存器里
+    Temp,
+
+    /// 尚未复制的局部变量
+    Local(Register),
+
+    /// 尚未写入的常量
+    Const(u64, ValueType),
+}
+
+struct BlockContext {
+    /// 进入结构块时（不包括结构块的参数）的栈高度
+    base_height: usize,
+    params_count: usize,
+    results_count: usize,
+
+    /// 进入结构块时是否可达
+    is_entered_reachable: bool,
+}
+
+struct FunctionLowering<'a> {
+    resource: &'a Resource,
+    vm_module_index: usize,
+    register_code: &'a mut RegisterCode,
+
+    locals_count: usize,
+    results_count: usize,
+    start_address: usize,
+    instructions: &'a [Instruction],
+
+    operands: Vec<Operand>,
+    max_stack_height: usize,
+    block_contexts: Vec<BlockContext>,
+
+    /// 是否可达，跳转、返回等指令之后直到结构块结束的指令是不可达的，不需要降低
+    is_reachable: bool,
+
+    /// 每个地址的指令所对应的第一个操作的索引
+    labels: Vec<u32>,
+
+    /// 是否为跳转的目标，索引为指令相对于函数开始位置的偏移值
+    branch_target_flags: Vec<bool>,
+
+    /// 待回填的跳转，(操作索引, 目标地址)
+    jump_patches: Vec<(usize, usize)>,
+
+    /// 待回填的跳转表，(跳转表索引, 项索引, 目标地址)
+    table_patches: Vec<(usize, usize, usize)>,
+
+    /// 入口，(地址, 操作索引)
+    entries: Vec<(usize, usize)>,
+
+    /// 最后一个运算操作的索引
+    ///
+    /// `local.set` 指令可以直接修改该操作的目标寄存器，从而省去一次复制。
+    last_value_op_index: Option<usize>,
+}
+
+impl<'a> FunctionLowering<'a> {
+    fn new(
+        resource: &'a Resource,
+        vm_module_index: usize,
+        register_code: &'a mut RegisterCode,
+        locals_count: usize,
+        results_count: usize,
+        start_address: usize,
+        instructions: &'a [Instruction],
+    ) -> Self {
+        let mut branch_target_flags = vec![false; instructions.len() + 1];
+        let mut set_flag = |address: usize| branch_target_flags[address - start_address] = true;
+
+        for instruction in instructions {
+            match instruction {
+                Instruction::Control(Control::BlockAndJumpWhenEqZero {
+                    option_alternate_address,
+                    end_address,
+                    ..
+                }) => set_flag(option_alternate_address.map_or(*end_address, |a| a) + 1),
+                Instruction::Control(Control::JumpWithinBlock(address)) => set_flag(*address),
+                Instruction::Control(Control::Break { branch_target, .. })
+                | Instruction::Control(Control::BreakWhenNotEqZero { branch_target, .. }) => {
+                    set_flag(branch_target.address)
+                }
+                Instruction::Control(Control::Branch {
+                    branch_targets,
+                    default_branch_target,
+                    ..
+                }) => {
+                    for branch_target in branch_targets {
+                        set_flag(branch_target.address);
+                    }
+                    set_flag(default_branch_target.address);
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            resource,
+            vm_module_index,
+            register_code,
+            locals_count,
+            results_count,
+            start_address,
+            instructions,
+            operands: vec![],
+            max_stack_height: 0,
+            block_contexts: vec![],
+            is_reachable: true,
+            labels: vec![u32::MAX; instructions.len()],
+            branch_target_flags,
+            jump_patches: vec![],
+            table_patches: vec![],
+            entries: vec![],
+            last_value_op_index: None,
+        }
+    }
+
+    fn lower(mut self) {
+        self.entries
+            .push((self.start_address, self.register_code.ops.len()));
+
+        for (offset, instruction) in self.instructions.iter().enumerate() {
+            let address = self.start_address + offset;
+
+            match instruction {
+                Instruction::Control(Control::End(None)) => {
+                    // 函数的结束指令，返回值需要先写入临时寄存器并移到运算操作数段的开始位置，
+                    // 跟跳到函数本层的情况一致，然后才是跳转的目标
+                    if self.is_reachable {
+                        self.materialize_all();
+
+                        let height = self.operands.len();
+                        if self.results_count > 0 && height > self.results_count {
+                            self.emit(RegisterOp::Move {
+                                dest: self.get_temp(0),
+                                source: self.get_temp(height - self.results_count),
+                                count: self.results_count as u32,
+                            });
+                        }
+                    }
+                    self.bind_label(address);
+                    self.emit(RegisterOp::Exit {
+                        address: address as u32,
+                        height: self.results_count as u32,
+                    });
+                }
+                Instruction::Sequence(sequence_instruction) => {
+                    self.bind_label(address);
+                    if self.is_reachable {
+                        self.lower_sequence(address, sequence_instruction);
+                    }
+                }
+                Instruction::Control(control) => {
+                    self.bind_label(address);
+                    self.lower_control(address, control);
+                }
+            }
+        }
+
+        self.patch();
+    }
+
+    fn lower_sequence(&mut self, address: usize, instruction: &instruction::Instruction) {
+        match instruction {
+            instruction::Instruction::I32Const(value) => {
+                self.push_operand(Operand::Const(*value as u32 as u64, ValueType::I32))
+            }
+            instruction::Instruction::I64Const(value) => {
+                self.push_operand(Operand::Const(*value as u64, ValueType::I64))
+            }
+            instruction::Instruction::F32Const(value) => {
+                self.push_operand(Operand::Const(value.to_bits() as u64, ValueType::F32))
+            }
+            instruction::Instruction::F64Const(value) => {
+                self.push_operand(Operand::Const(value.to_bits(), ValueType::F64))
+            }
+            instruction::Instruction::LocalGet(index) => {
+                self.push_operand(Operand::Local(*index as Register))
+            }
+            instruction::Instruction::LocalSet(index) => self.set_local(*index as Register, false),
+            instruction::Instruction::LocalTee(index) => self.set_local(*index as Register, true),
+            instruction::Instruction::Drop => {
+                self.operands.pop();
+            }
+            instruction::Instruction::I32Eqz => self.lower_unary(RegisterOp::I32Eqz),
+            instruction::Instruction::I64Eqz => self.lower_unary(RegisterOp::I64Eqz),
+            _ => {
+                if let Some(make_op) = get_binary_op(instruction) {
+                    self.lower_binary(make_op);
+                } else {
+                    self.lower_stack(address, instruction);
+                }
+            }
+        }
+    }
+
+    fn lower_control(&mut self, address: usize, control: &Control) {
+        match control {
+            Control::Block { block_type, .. } => {
+                if self.is_reachable {
+                    self.materialize_all();
+                }
+                self.enter_block(block_type);
+            }
+            Control::BlockAndJumpWhenEqZero {
+                block_type,
+                option_alternate_address,
+                end_address,
+                ..
+            } => {
+                if self.is_reachable {
+                    let condition = self.pop_register();
+                    self.materialize_all();
+
+                    let target_address = option_alternate_address.map_or(*end_address, |a| a) + 1;
+                    self.emit_jump(
+                        RegisterOp::JumpWhenEqZero {
+                            condition,
+                            target: 0,
+                        },
+                        target_address,
+                    );
+                }
+                self.enter_block(block_type);
+            }
+            Control::TryTable { .. } => {
+                unreachable!("function with try_table block should not be lowered")
+            }
+            Control::JumpWithinBlock(target_address) => {
+                if self.is_reachable {
+                    self.materialize_all();
+                    self.emit_jump(RegisterOp::Jump(0), *target_address);
+                }
+
+                // 进入 else 分支
+                let block_context = self.block_contexts.last().unwrap();
+                let height = block_context.base_height + block_context.params_count;
+                self.is_reachable = block_context.is_entered_reachable;
+                self.reset_operands(block_context.base_height, height);
+            }
+            Control::End(_) => {
+                if self.is_reachable {
+                    self.materialize_all();
+                }
+
+                let block_context = self.block_contexts.pop().unwrap();
+                let height = block_context.base_height + block_context.results_count;
+                self.is_reachable = block_context.is_entered_reachable;
+                self.reset_operands(block_context.base_height, height);
+            }
+            _ if !self.is_reachable => {
+                // 跳过不可达的指令
+            }
+            Control::Break { branch_target, .. } => {
+                self.materialize_all();
+                self.emit_branch(branch_target);
+                self.is_reachable = false;
+            }
+            Control::BreakWhenNotEqZero { branch_target, .. } => {
+                let condition = self.pop_register();
+                self.materialize_all();
+
+                if self.is_move_required(branch_target) {
+                    let skip_op_index = self.emit(RegisterOp::JumpWhenEqZero {
+                        condition,
+                        target: 0,
+                    });
+                    self.emit_branch(branch_target);
+
+                    let next_op_index = self.register_code.ops.len() as u32;
+                    self.register_code.ops[skip_op_index].set_jump_target(next_op_index);
+                } else {
+                    self.emit_jump(
+                        RegisterOp::JumpWhenNotEqZero {
+                            condition,
+                            target: 0,
+                        },
+                        branch_target.address,
+                    );
+                }
+            }
+            Control::Branch {
+                branch_targets,
+                default_branch_target,
+                ..
+            } => {
+                let index = self.pop_register();
+                self.materialize_all();
+
+                let table_index = self.register_code.branch_tables.len();
+                let mut branch_table_items: Vec<BranchTableItem> = vec![];
+
+                for (item_index, branch_target) in branch_targets
+                    .iter()
+                    .chain([default_branch_target])
+                    .enumerate()
+                {
+                    let (dest, source, count) = if self.is_move_required(branch_target) {
+                        self.get_move_registers(branch_target)
+                    } else {
+                        (0, 0, 0)
+                    };
+
+                    branch_table_items.push(BranchTableItem {
+                        dest,
+                        source,
+                        count,
+                        target: 0,
+                    });
+                    self.table_patches
+                        .push((table_index, item_index, branch_target.address));
+                }
+
+                self.register_code.branch_tables.push(branch_table_items);
+                self.emit(RegisterOp::BranchTable {
+                    index,
+                    table: table_index as u32,
+                });
+                self.is_reachable = false;
+            }
+            Control::Call {
+                vm_module_index,
+                type_index,
+                ..
+            } => {
+                let function_type =
+                    &self.resource.vm_modules[*vm_module_index].function_types[*type_index];
+                self.lower_call(
+                    address,
+                    function_type.params.len(),
+                    function_type.results.len(),
+                );
+            }
+            Control::CallNative {
+                native_module_index,
+                type_index,
+                ..
+            } => {
+                let function_type =
+                    &self.resource.native_modules[*native_module_index].function_types[*type_index];
+                self.lower_call(
+                    address,
+                    function_type.params.len(),
+                    function_type.results.len(),
+                );
+            }
+            Control::CallIndirect { type_index, .. } => {
+                let function_type =
+                    &self.resource.vm_modules[self.vm_module_index].function_types[*type_index];
+
+                // 除了实参，还需要弹出表元素的索引
+                self.lower_call(
+                    address,
+                    function_type.params.len() + 1,
+                    function_type.results.len(),
+                );
+            }
+            Control::ReturnCall { .. }
+            | Control::ReturnCallNative { .. }
+            | Control::ReturnCallIndirect { .. }
+            | Control::Throw(_)
+            | Control::ThrowRef
+            | Control::Unreachable => {
+                self.materialize_all();
+                self.emit(RegisterOp::Exit {
+                    address: address as u32,
+                    height: self.operands.len() as u32,
+                });
+                self.last_value_op_index = None;
+                self.is_reachable = false;
+            }
+            Control::Nop => {
+                // pass
+            }
+        }
+    }
+
+    /// 由栈式解释器执行指令
+    fn lower_stack(&mut self, address: usize, instruction: &instruction::Instruction) {
+        let (pops, pushes) = get_stack_effect(instruction);
+
+        // 只有被指令弹出的操作数需要写入临时寄存器，
+        // 顺序指令不会修改局部变量，所以其余的操作数可以保持原样。
+        self.materialize_top(pops);
+
+        let height = self.operands.len();
+        self.emit(RegisterOp::Stack {
+            address: address as u32,
+            height: height as u32,
+        });
+
+        self.operands.truncate(height - pops);
+        self.push_temps(pushes);
+        self.last_value_op_index = None;
+    }
+
+    fn lower_unary(&mut self, make_op: fn(UnaryOperands) -> RegisterOp) {
+        let source = self.pop_register();
+        let dest = self.get_temp(self.operands.len());
+        let op_index = self.emit(make_op(UnaryOperands { dest, source }));
+
+        self.push_operand(Operand::Temp);
+        self.last_value_op_index = Some(op_index);
+    }
+
+    fn lower_binary(&mut self, make_op: fn(BinaryOperands) -> RegisterOp) {
+        let right = self.pop_register();
+        let left = self.pop_register();
+        let dest = self.get_temp(self.operands.len());
+        let op_index = self.emit(make_op(BinaryOperands { dest, left, right }));
+
+        self.push_operand(Operand::Temp);
+        self.last_value_op_index = Some(op_index);
+    }
+
+    /// 降低 `local.set` 以及 `local.tee` 指令
+    fn set_local(&mut self, register: Register, is_tee: bool) {
+        let height = self.operands.len() - 1;
+
+        // 操作数栈上尚未复制的该局部变量需要先复制，
+        // 以保留局部变量被修改之前的值。
+        for index in 0..height {
+            if self.operands[index] == Operand::Local(register) {
+                self.materialize(index);
+            }
+        }
+
+        match self.operands.pop().unwrap() {
+            Operand::Temp => {
+                let source = self.get_temp(height);
+                if !self.retarget_last_value_op(source, register) {
+                    self.emit(RegisterOp::Copy(UnaryOperands {
+                        dest: register,
+                        source,
+                    }));
+                }
+            }
+            Operand::Local(source) => {
+                if source != register {
+                    self.emit(RegisterOp::Copy(UnaryOperands {
+                        dest: register,
+                        source,
+                    }));
+                }
+            }
+            Operand::Const(value, value_type) => {
+                self.emit(RegisterOp::Const {
+                    dest: register,
+                    value,
+                    value_type,
+                });
+            }
+        }
+
+        if is_tee {
+            self.push_operand(Operand::Local(register));
+        }
+
+        self.last_value_op_index = None;
+    }
+
+    /// 如果最后一个操作是把结果写入指定的临时寄存器的运算操作，则把它的
+    /// 目标寄存器修改为局部变量的寄存器
+    fn retarget_last_value_op(&mut self, source: Register, register: Register) -> bool {
+        let op_index = match self.last_value_op_index {
+            Some(op_index) if op_index + 1 == self.register_code.ops.len() => op_index,
+            _ => return false,
+        };
+
+        match self.register_code.ops[op_index].get_dest_mut() {
+            Some(dest) if *dest == source => {
+                *dest = register;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn lower_call(&mut self, address: usize, params_count: usize, results_count: usize) {
+        self.materialize_all();
+
+        let height = self.operands.len();
+        self.emit(RegisterOp::Exit {
+            address: address as u32,
+            height: height as u32,
+        });
+
+        self.operands.truncate(height - params_count);
+        self.push_temps(results_count);
+
+        // 被调用的函数返回之后，从下一条指令的入口回到寄存器中间表示
+        self.entries
+            .push((address + 1, self.register_code.ops.len()));
+        self.last_value_op_index = None;
+    }
+
+    fn enter_block(&mut self, block_type: &BlockType) {
+        let (params_count, results_count) = match block_type {
+            BlockType::ResultEmpty => (0, 0),
+            BlockType::TypeIndex(type_index) => {
+                let function_type = &self.resource.vm_modules[self.vm_module_index].function_types
+                    [*type_index as usize];
+                (function_type.params.len(), function_type.results.len())
+            }
+            _ => (0, 1),
+        };
+
+        self.block_contexts.push(BlockContext {
+            base_height: self.operands.len().saturating_sub(params_count),
+            params_count,
+            results_count,
+            is_entered_reachable: self.is_reachable,
+        });
+    }
+
+    /// 把操作数栈截断到 base_height，然后用临时寄存器填充到 height
+    fn reset_operands(&mut self, base_height: usize, height: usize) {
+        self.operands.truncate(base_height);
+        self.operands.resize(height, Operand::Temp);
+        self.max_stack_height = self.max_stack_height.max(height);
+    }
+
+    fn is_move_required(&self, branch_target: &BranchTarget) -> bool {
+        branch_target.arity > 0
+            && self.operands.len() - branch_target.arity != branch_target.stack_height
+    }
+
+    fn get_move_registers(&self, branch_target: &BranchTarget) -> (Register, Register, u32) {
+        let source_height = self.operands.len() - branch_target.arity;
+        (
+            self.get_temp(branch_target.stack_height),
+            self.get_temp(source_height),
+            branch_target.arity as u32,
+        )
+    }
+
+    fn emit_branch(&mut self, branch_target: &BranchTarget) {
+        if self.is_move_required(branch_target) {
+            let (dest, source, count) = self.get_move_registers(branch_target);
+            self.emit(RegisterOp::Move {
+                dest,
+                source,
+                count,
+            });
+        }
+        self.emit_jump(RegisterOp::Jump(0), branch_target.address);
+    }
+
+    fn emit_jump(&mut self, op: RegisterOp, target_address: usize) {
+        let op_index = self.emit(op);
+        self.jump_patches.push((op_index, target_address));
+        self.last_value_op_index = None;
+    }
+
+    fn emit(&mut self, op: RegisterOp) -> usize {
+        self.register_code.ops.push(op);
+        self.register_code.ops.len() - 1
+    }
+
+    fn bind_label(&mut self, address: usize) {
+        let offset = address - self.start_address;
+        self.labels[offset] = self.register_code.ops.len() as u32;
+
+        // 跳转的目标处不能修改之前的操作的目标寄存器
+        if self.branch_target_flags[offset] {
+            self.last_value_op_index = None;
+        }
+    }
+
+    fn get_temp(&self, height: usize) -> Register {
+        (self.locals_count + INFO_SEGMENT_ITEM_COUNT + height) as Register
+    }
+
+    fn push_operand(&mut self, operand: Operand) {
+        self.operands.push(operand);
+        self.max_stack_height = self.max_stack_height.max(self.operands.len());
+    }
+
+    fn push_temps(&mut self, count: usize) {
+        for _ in 0..count {
+            self.push_operand(Operand::Temp);
+        }
+    }
+
+    /// 弹出栈顶的操作数，返回存放该操作数的寄存器
+    fn pop_register(&mut self) -> Register {
+        let height = self.operands.len() - 1;
+
+        if let Operand::Local(register) = self.operands[height] {
+            self.operands.pop();
+            return register;
+        }
+
+        self.materialize(height);
+        self.operands.pop();
+        self.get_temp(height)
+    }
+
+    /// 把指定位置的操作数写入临时寄存器
+    fn materialize(&mut self, height: usize) {
+        let dest = self.get_temp(height);
+
+        match mem::replace(&mut self.operands[height], Operand::Temp) {
+            Operand::Temp => {}
+            Operand::Local(source) => {
+                self.emit(RegisterOp::Copy(UnaryOperands { dest, source }));
+            }
+            Operand::Const(value, value_type) => {
+                self.emit(RegisterOp::Const {
+                    dest,
+                    value,
+                    value_type,
+                });
+            }
+        }
+    }
+
+    fn materialize_top(&mut self, count: usize) {
+        let length = self.operands.len();
+        for height in (length - count)..length {
+            self.materialize(height);
+        }
+    }
+
+    fn materialize_all(&mut self) {
+        self.materialize_top(self.operands.len());
+    }
+
+    /// 回填跳转目标以及入口
+    fn patch(self) {
+        let start_address = self.start_address;
+        let labels = &self.labels;
+
+        for (op_index, address) in self.jump_patches {
+            self.register_code.ops[op_index].set_jump_target(labels[address - start_address]);
+        }
+
+        for (table_index, item_index, address) in self.table_patches {
+            self.register_code.branch_tables[table_index][item_index].target =
+                labels[address - start_address];
+        }
+
+        for (address, op_index) in self.entries {
+            self.register_code.entries[address] = Some(EntryItem {
+                op_index: op_index as u32,
+                max_stack_height: self.max_stack_height as u32,
+            });
+        }
+    }
+}
+
+/// 获取可以转换为三地址形式的二元运算指令所对应的操作
+fn get_binary_op(
+    instruction: &instruction::Instruction,
+) -> Option<fn(BinaryOperands) -> RegisterOp> {
+    let make_op: fn(BinaryOperands) -> RegisterOp = match instruction {
+        instruction::Instruction::I32Eq => RegisterOp::I32Eq,
+        instruction::Instruction::I32Ne => RegisterOp::I32Ne,
+        instruction::Instruction::I32LtS => RegisterOp::I32LtS,
+        instruction::Instruction::I32LtU => RegisterOp::I32LtU,
+        instruction::Instruction::I32GtS => RegisterOp::I32GtS,
+        instruction::Instruction::I32GtU => RegisterOp::I32GtU,
+        instruction::Instruction::I32LeS => RegisterOp::I32LeS,
+        instruction::Instruction::I32LeU => RegisterOp::I32LeU,
+        instruction::Instruction::I32GeS => RegisterOp::I32GeS,
+        instruction::Instruction::I32GeU => RegisterOp::I32GeU,
+
+        instruction::Instruction::I64Eq => RegisterOp::I64Eq,
+        instruction::Instruction::I64Ne => RegisterOp::I64Ne,
+        instruction::Instruction::I64LtS => RegisterOp::I64LtS,
+        instruction::Instruction::I64LtU => RegisterOp::I64LtU,
+        instruction::Instruction::I64GtS => RegisterOp::I64GtS,
+        instruction::Instruction::I64GtU => RegisterOp::I64GtU,
+        instruction::Instruction::I64LeS => RegisterOp::I64LeS,
+        instruction::Instruction::I64LeU => RegisterOp::I64LeU,
+        instruction::Instruction::I64GeS => RegisterOp::I64GeS,
+        instruction::Instruction::I64GeU => RegisterOp::I64GeU,
+
+        instruction::Instruction::F32Eq => RegisterOp::F32Eq,
+        instruction::Instruction::F32Ne => RegisterOp::F32Ne,
+        instruction::Instruction::F32Lt => RegisterOp::F32Lt,
+        instruction::Instruction::F32Gt => RegisterOp::F32Gt,
+        instruction::Instruction::F32Le => RegisterOp::F32Le,
+        instruction::Instruction::F32Ge => RegisterOp::F32Ge,
+
+        instruction::Instruction::F64Eq => RegisterOp::F64Eq,
+        instruction::Instruction::F64Ne => RegisterOp::F64Ne,
+        instruction::Instruction::F64Lt => RegisterOp::F64Lt,
+        instruction::Instruction::F64Gt => RegisterOp::F64Gt,
+        instruction::Instruction::F64Le => RegisterOp::F64Le,
+        instruction::Instruction::F64Ge => RegisterOp::F64Ge,
+
+        instruction::Instruction::I32Add => RegisterOp::I32Add,
+        instruction::Instruction::I32Sub => RegisterOp::I32Sub,
+        instruction::Instruction::I32Mul => RegisterOp::I32Mul,
+        instruction::Instruction::I32DivS => RegisterOp::I32DivS,
+        instruction::Instruction::I32DivU => RegisterOp::I32DivU,
+        instruction::Instruction::I32RemS => RegisterOp::I32RemS,
+        instruction::Instruction::I32RemU => RegisterOp::I32RemU,
+        instruction::Instruction::I32And => RegisterOp::I32And,
+        instruction::Instruction::I32Or => RegisterOp::I32Or,
+        instruction::Instruction::I32Xor => RegisterOp::I32Xor,
+        instruction::Instruction::I32Shl => RegisterOp::I32Shl,
+        instruction::Instruction::I32ShrS => RegisterOp::I32ShrS,
+        instruction::Instruction::I32ShrU => RegisterOp::I32ShrU,
+        instruction::Instruction::I32Rotl => RegisterOp::I32Rotl,
+        instruction::Instruction::I32Rotr => RegisterOp::I32Rotr,
+
+        instruction::Instruction::I64Add => RegisterOp::I64Add,
+        instruction::Instruction::I64Sub => RegisterOp::I64Sub,
+        instruction::Instruction::I64Mul => RegisterOp::I64Mul,
+        instruction::Instruction::I64DivS => RegisterOp::I64DivS,
+        instruction::Instruction::I64DivU => RegisterOp::I64DivU,
+        instruction::Instruction::I64RemS => RegisterOp::I64RemS,
+        instruction::Instruction::I64RemU => RegisterOp::I64RemU,
+        instruction::Instruction::I64And => RegisterOp::I64And,
+        instruction::Instruction::I64Or => RegisterOp::I64Or,
+        instruction::Instruction::I64Xor => RegisterOp::I64Xor,
+        instruction::Instruction::I64Shl => RegisterOp::I64Shl,
+        instruction::Instruction::I64ShrS => RegisterOp::I64ShrS,
+        instruction::Instruction::I64ShrU => RegisterOp::I64ShrU,
+        instruction::Instruction::I64Rotl => RegisterOp::I64Rotl,
+        instruction::Instruction::I64Rotr => RegisterOp::I64Rotr,
+
+        instruction::Instruction::F32Add => RegisterOp::F32Add,
+        instruction::Instruction::F32Sub => RegisterOp::F32Sub,
+        instruction::Instruction::F32Mul => RegisterOp::F32Mul,
+        instruction::Instruction::F32Div => RegisterOp::F32Div,
+
+        instruction::Instruction::F64Add => RegisterOp::F64Add,
+        instruction::Instruction::F64Sub => RegisterOp::F64Sub,
+        instruction::Instruction::F64Mul => RegisterOp::F64Mul,
+        instruction::Instruction::F64Div => RegisterOp::F64Div,
+
+        _ => return None,
+    };
+
+    Some(make_op)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use anvm_ast::{ast, instruction, types::ValueType};
+    use anvm_binary_parser::parser;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        instance::create_instance_with_interpreter,
+        object::{FunctionItem, Instruction, NamedAstModule},
+        vm::InterpreterKind,
+    };
+
+    use super::{is_lowerable, BinaryOperands, EntryItem, RegisterOp, UnaryOperands};
+
+    fn get_test_ast_module(filename: &str) -> ast::Module {
+        let mut path_buf = env::current_dir().unwrap();
+        if !path_buf.ends_with("engine") {
+            path_buf.push("crates");
+            path_buf.push("engine");
+        }
+        let fullname_buf = path_buf.join("resources").join(filename);
+        let bytes = fs::read(fullname_buf).unwrap();
+        parser::parse(&bytes).unwrap()
+    }
+
+    #[test]
+    fn test_lower() {
+        let named_ast_module =
+            NamedAstModule::new("test", get_test_ast_module("test-block-loop.wasm"));
+        let vm = create_instance_with_interpreter(
+            vec![],
+            &[named_ast_module],
+            InterpreterKind::Register,
+        )
+        .unwrap();
+
+        let vm_module = &vm.resource.vm_modules[0];
+        let register_code = vm_module.register_code.as_ref().unwrap();
+
+        let (start_address, end_address) = match &vm_module.function_items[0] {
+            FunctionItem::Normal {
+                start_address,
+                end_address,
+                ..
+            } => (*start_address, *end_address),
+            _ => unreachable!(),
+        };
+
+        // 函数 $sum 有 1 个参数和 2 个局部变量，所以临时寄存器从 r11 开始（3 + 8）
+        //
+        // ```wat
+        // (loop $next
+        //     (local.set $i (i32.add (local.get $i) (i32.const 1)))
+        //     (local.set $acc (i32.add (local.get $acc) (local.get $i)))
+        //     (br_if $next (i32.lt_u (local.get $i) (local.get $n)))
+        // )
+        // (local.get $acc)
+        // ```
+        assert_eq!(
+            register_code.ops[..7],
+            vec![
+                RegisterOp::Const {
+                    dest: 12,
+                    value: 1,
+                    value_type: ValueType::I32
+                },
+                RegisterOp::I32Add(BinaryOperands {
+                    dest: 1,
+                    left: 1,
+                    right: 12
+                }),
+                RegisterOp::I32Add(BinaryOperands {
+                    dest: 2,
+                    left: 2,
+                    right: 1
+                }),
+                RegisterOp::I32LtU(BinaryOperands {
+                    dest: 11,
+                    left: 1,
+                    right: 0
+                }),
+                RegisterOp::JumpWhenNotEqZero {
+                    condition: 11,
+                    target: 0
+                },
+                RegisterOp::Copy(UnaryOperands {
+                    dest: 11,
+                    source: 2
+                }),
+                RegisterOp::Exit {
+                    address: end_address as u32,
+                    height: 1
+                },
+            ]
+        );
+
+        assert_eq!(
+            register_code.entries[start_address],
+            Some(EntryItem {
+                op_index: 0,
+                max_stack_height: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_is_lowerable() {
+        let local_set = |index: u32| {
+            vec![
+                Instruction::Sequence(instruction::Instruction::I32Const(0)),
+                Instruction::Sequence(instruction::Instruction::LocalSet(index)),
+            ]
+        };
+
+        assert!(is_lowerable(
+            &local_set(1),
+            &[ValueType::I32],
+            &[ValueType::I32]
+        ));
+
+        // 局部变量的索引超出范围的函数不会被降低，也就不会被 JIT 编译
+        assert!(!is_lowerable(
+            &local_set(2),
+            &[ValueType::I32],
+            &[ValueType::I32]
+        ));
+        assert!(!is_lowerable(&local_set(100000), &[], &[]));
+    }
+}
@@ -0,0 +1,492 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 寄存器解释器
+//!
+//! 执行由 `register` 模块降低而成的寄存器中间表示。
+//!
+//! 寄存器解释器跟栈式解释器共用同一个栈和同一套调用帧，当栈式解释器执行到
+//! 一个入口（函数的第一条指令，或者函数调用指令的下一条指令）时，转为执行
+//! 寄存器中间表示，直到遇到 `Exit` 操作再交回栈式解释器。
+//!
+//! 执行寄存器中间表示时，栈的大小固定为 `bp + 信息段大小 + 最大栈高度`，
+//! 即预先为所有临时寄存器分配好栈槽，运算操作只读写栈槽而不改变栈的大小。
+
+use std::rc::Rc;
+
+use anvm_ast::types::ValueType;
+
+use crate::{
+    error::EngineError,
+    ins_numeric_binary::{
+        checked_i32_div_s, checked_i32_div_u, checked_i32_rem_s, checked_i32_rem_u,
+        checked_i64_div_s, checked_i64_div_u, checked_i64_rem_s, checked_i64_rem_u,
+    },
+    interpreter,
+    register::{BinaryOperands, EntryItem, Register, RegisterCode, RegisterOp, UnaryOperands},
+    vm::VM,
+    vm_stack::{VMStack, INFO_SEGMENT_ITEM_COUNT},
+};
+
+/// 执行剩余的指令，无视断点
+///
+/// 遇到入口时执行寄存器中间表示，其余指令由栈式解释器执行。
+pub fn recur_without_break(vm: &mut VM) -> Result<(), EngineError> {
+    loop {
+        let option_entry = {
+            let vm_module = &vm.resource.vm_modules[vm.status.vm_module_index];
+            vm_module.register_code.as_ref().and_then(|register_code| {
+                register_code.entries[vm.status.address]
+                    .map(|entry_item| (Rc::clone(register_code), entry_item))
+            })
+        };
+
+        if let Some((register_code, entry_item)) = option_entry {
+            exec_register_code(vm, &register_code, entry_item)?;
+        }
+
+        let is_program_end = vm.step()?;
+        if is_program_end {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// 从指定入口开始执行寄存器中间表示，直到遇到 `Exit` 操作
+fn exec_register_code(
+    vm: &mut VM,
+    register_code: &RegisterCode,
+    entry_item: EntryItem,
+) -> Result<(), EngineError> {
+    let frame_pointer = vm.status.frame_pointer;
+    let operand_pointer = vm.status.base_pointer + INFO_SEGMENT_ITEM_COUNT;
+    let full_size = operand_pointer + entry_item.max_stack_height as usize;
+
+    vm.stack.set_size(full_size);
+
+    let mut op_index = entry_item.op_index as usize;
+
+    while let Some(next_op_index) = exec_register_op(
+        vm,
+        register_code,
+        frame_pointer,
+        operand_pointer,
+        full_size,
+        op_index,
+    )? {
+        op_index = next_op_index;
+    }
+
+    Ok(())
+}
+
+/// 执行一个操作，返回下一个操作的索引，遇到 `Exit` 操作时返回 None
+///
+/// 参数 full_size 是执行寄存器中间表示期间栈的大小。
+#[inline(always)]
+pub fn exec_register_op(
+    vm: &mut VM,
+    register_code: &RegisterCode,
+    frame_pointer: usize,
+    operand_pointer: usize,
+    full_size: usize,
+    op_index: usize,
+) -> Result<Option<usize>, EngineError> {
+    let stack = &mut vm.stack;
+
+    match &register_code.ops[op_index] {
+        RegisterOp::Const {
+            dest,
+            value,
+            value_type,
+        } => stack.set_slot(frame_pointer + *dest as usize, *value, value_type.clone()),
+        RegisterOp::Copy(operands) => stack.copy_slot(
+            frame_pointer + operands.source as usize,
+            frame_pointer + operands.dest as usize,
+        ),
+
+        RegisterOp::I32Eqz(o) => i32_test(stack, frame_pointer, o, |v| v == 0),
+        RegisterOp::I64Eqz(o) => i64_test(stack, frame_pointer, o, |v| v == 0),
+
+        RegisterOp::I32Eq(o) => i32_compare(stack, frame_pointer, o, |l, r| l == r),
+        RegisterOp::I32Ne(o) => i32_compare(stack, frame_pointer, o, |l, r| l != r),
+        RegisterOp::I32LtS(o) => i32_compare(stack, frame_pointer, o, |l, r| l < r),
+        RegisterOp::I32LtU(o) => {
+            i32_compare(stack, frame_pointer, o, |l, r| (l as u32) < (r as u32))
+        }
+        RegisterOp::I32GtS(o) => i32_compare(stack, frame_pointer, o, |l, r| l > r),
+        RegisterOp::I32GtU(o) => {
+            i32_compare(stack, frame_pointer, o, |l, r| (l as u32) > (r as u32))
+        }
+        RegisterOp::I32LeS(o) => i32_compare(stack, frame_pointer, o, |l, r| l <= r),
+        RegisterOp::I32LeU(o) => {
+            i32_compare(stack, frame_pointer, o, |l, r| (l as u32) <= (r as u32))
+        }
+        RegisterOp::I32GeS(o) => i32_compare(stack, frame_pointer, o, |l, r| l >= r),
+        RegisterOp::I32GeU(o) => {
+            i32_compare(stack, frame_pointer, o, |l, r| (l as u32) >= (r as u32))
+        }
+
+        RegisterOp::I64Eq(o) => i64_compare(stack, frame_pointer, o, |l, r| l == r),
+        RegisterOp::I64Ne(o) => i64_compare(stack, frame_pointer, o, |l, r| l != r),
+        RegisterOp::I64LtS(o) => i64_compare(stack, frame_pointer, o, |l, r| l < r),
+        RegisterOp::I64LtU(o) => {
+            i64_compare(stack, frame_pointer, o, |l, r| (l as u64) < (r as u64))
+        }
+        RegisterOp::I64GtS(o) => i64_compare(stack, frame_pointer, o, |l, r| l > r),
+        RegisterOp::I64GtU(o) => {
+            i64_compare(stack, frame_pointer, o, |l, r| (l as u64) > (r as u64))
+        }
+        RegisterOp::I64LeS(o) => i64_compare(stack, frame_pointer, o, |l, r| l <= r),
+        RegisterOp::I64LeU(o) => {
+            i64_compare(stack, frame_pointer, o, |l, r| (l as u64) <= (r as u64))
+        }
+        RegisterOp::I64GeS(o) => i64_compare(stack, frame_pointer, o, |l, r| l >= r),
+        RegisterOp::I64GeU(o) => {
+            i64_compare(stack, frame_pointer, o, |l, r| (l as u64) >= (r as u64))
+        }
+
+        RegisterOp::F32Eq(o) => f32_compare(stack, frame_pointer, o, |l, r| l == r),
+        RegisterOp::F32Ne(o) => f32_compare(stack, frame_pointer, o, |l, r| l != r),
+        RegisterOp::F32Lt(o) => f32_compare(stack, frame_pointer, o, |l, r| l < r),
+        RegisterOp::F32Gt(o) => f32_compare(stack, frame_pointer, o, |l, r| l > r),
+        RegisterOp::F32Le(o) => f32_compare(stack, frame_pointer, o, |l, r| l <= r),
+        RegisterOp::F32Ge(o) => f32_compare(stack, frame_pointer, o, |l, r| l >= r),
+
+        RegisterOp::F64Eq(o) => f64_compare(stack, frame_pointer, o, |l, r| l == r),
+        RegisterOp::F64Ne(o) => f64_compare(stack, frame_pointer, o, |l, r| l != r),
+        RegisterOp::F64Lt(o) => f64_compare(stack, frame_pointer, o, |l, r| l < r),
+        RegisterOp::F64Gt(o) => f64_compare(stack, frame_pointer, o, |l, r| l > r),
+        RegisterOp::F64Le(o) => f64_compare(stack, frame_pointer, o, |l, r| l <= r),
+        RegisterOp::F64Ge(o) => f64_compare(stack, frame_pointer, o, |l, r| l >= r),
+
+        RegisterOp::I32Add(o) => i32_binary(stack, frame_pointer, o, |l, r| l.wrapping_add(r)),
+        RegisterOp::I32Sub(o) => i32_binary(stack, frame_pointer, o, |l, r| l.wrapping_sub(r)),
+        RegisterOp::I32Mul(o) => i32_binary(stack, frame_pointer, o, |l, r| l.wrapping_mul(r)),
+        RegisterOp::I32DivS(o) => i32_binary_checked(stack, frame_pointer, o, checked_i32_div_s)?,
+        RegisterOp::I32DivU(o) => i32_binary_checked(stack, frame_pointer, o, checked_i32_div_u)?,
+        RegisterOp::I32RemS(o) => i32_binary_checked(stack, frame_pointer, o, checked_i32_rem_s)?,
+        RegisterOp::I32RemU(o) => i32_binary_checked(stack, frame_pointer, o, checked_i32_rem_u)?,
+        RegisterOp::I32And(o) => i32_binary(stack, frame_pointer, o, |l, r| l & r),
+        RegisterOp::I32Or(o) => i32_binary(stack, frame_pointer, o, |l, r| l | r),
+        RegisterOp::I32Xor(o) => i32_binary(stack, frame_pointer, o, |l, r| l ^ r),
+        RegisterOp::I32Shl(o) => {
+            i32_binary(stack, frame_pointer, o, |l, r| l.wrapping_shl(r as u32))
+        }
+        RegisterOp::I32ShrS(o) => {
+            i32_binary(stack, frame_pointer, o, |l, r| l.wrapping_shr(r as u32))
+        }
+        RegisterOp::I32ShrU(o) => i32_binary(stack, frame_pointer, o, |l, r| {
+            (l as u32).wrapping_shr(r as u32) as i32
+        }),
+        RegisterOp::I32Rotl(o) => i32_binary(stack, frame_pointer, o, |l, r| {
+            i32::rotate_left(l, r as u32)
+        }),
+        RegisterOp::I32Rotr(o) => i32_binary(stack, frame_pointer, o, |l, r| {
+            i32::rotate_right(l, r as u32)
+        }),
+
+        RegisterOp::I64Add(o) => i64_binary(stack, frame_pointer, o, |l, r| l.wrapping_add(r)),
+        RegisterOp::I64Sub(o) => i64_binary(stack, frame_pointer, o, |l, r| l.wrapping_sub(r)),
+        RegisterOp::I64Mul(o) => i64_binary(stack, frame_pointer, o, |l, r| l.wrapping_mul(r)),
+        RegisterOp::I64DivS(o) => i64_binary_checked(stack, frame_pointer, o, checked_i64_div_s)?,
+        RegisterOp::I64DivU(o) => i64_binary_checked(stack, frame_pointer, o, checked_i64_div_u)?,
+        RegisterOp::I64RemS(o) => i64_binary_checked(stack, frame_pointer, o, checked_i64_rem_s)?,
+        RegisterOp::I64RemU(o) => i64_binary_checked(stack, frame_pointer, o, checked_i64_rem_u)?,
+        RegisterOp::I64And(o) => i64_binary(stack, frame_pointer, o, |l, r| l & r),
+        RegisterOp::I64Or(o) => i64_binary(stack, frame_pointer, o, |l, r| l | r),
+        RegisterOp::I64Xor(o) => i64_binary(stack, frame_pointer, o, |l, r| l ^ r),
+
+        // 移位以及旋转的 RHS 跟栈式解释器一样按 i32 读取
+        RegisterOp::I64Shl(o) => {
+            i64_binary(stack, frame_pointer, o, |l, r| l.wrapping_shl(r as u32))
+        }
+        RegisterOp::I64ShrS(o) => {
+            i64_binary(stack, frame_pointer, o, |l, r| l.wrapping_shr(r as u32))
+        }
+        RegisterOp::I64ShrU(o) => i64_binary(stack, frame_pointer, o, |l, r| {
+            (l as u64).wrapping_shr(r as u32) as i64
+        }),
+        RegisterOp::I64Rotl(o) => i64_binary(stack, frame_pointer, o, |l, r| {
+            i64::rotate_left(l, r as i32 as u32)
+        }),
+        RegisterOp::I64Rotr(o) => i64_binary(stack, frame_pointer, o, |l, r| {
+            i64::rotate_right(l, r as i32 as u32)
+        }),
+
+        RegisterOp::F32Add(o) => f32_binary(stack, frame_pointer, o, |l, r| l + r),
+        RegisterOp::F32Sub(o) => f32_binary(stack, frame_pointer, o, |l, r| l - r),
+        RegisterOp::F32Mul(o) => f32_binary(stack, frame_pointer, o, |l, r| l * r),
+        RegisterOp::F32Div(o) => f32_binary(stack, frame_pointer, o, |l, r| l / r),
+
+        RegisterOp::F64Add(o) => f64_binary(stack, frame_pointer, o, |l, r| l + r),
+        RegisterOp::F64Sub(o) => f64_binary(stack, frame_pointer, o, |l, r| l - r),
+        RegisterOp::F64Mul(o) => f64_binary(stack, frame_pointer, o, |l, r| l * r),
+        RegisterOp::F64Div(o) => f64_binary(stack, frame_pointer, o, |l, r| l / r),
+
+        RegisterOp::Jump(target) => return Ok(Some(*target as usize)),
+        RegisterOp::JumpWhenEqZero { condition, target } => {
+            if stack.get_slot(frame_pointer + *condition as usize) as u32 == 0 {
+                return Ok(Some(*target as usize));
+            }
+        }
+        RegisterOp::JumpWhenNotEqZero { condition, target } => {
+            if stack.get_slot(frame_pointer + *condition as usize) as u32 != 0 {
+                return Ok(Some(*target as usize));
+            }
+        }
+        RegisterOp::Move {
+            dest,
+            source,
+            count,
+        } => stack.move_slots(
+            frame_pointer + *source as usize,
+            frame_pointer + *dest as usize,
+            *count as usize,
+        ),
+        RegisterOp::BranchTable { index, table } => {
+            let branch_index = stack.get_slot(frame_pointer + *index as usize) as u32 as i32;
+            let (default_item, items) = register_code.branch_tables[*table as usize]
+                .split_last()
+                .unwrap();
+            let item = items.get(branch_index as usize).unwrap_or(default_item);
+
+            if item.count > 0 {
+                stack.move_slots(
+                    frame_pointer + item.source as usize,
+                    frame_pointer + item.dest as usize,
+                    item.count as usize,
+                );
+            }
+
+            return Ok(Some(item.target as usize));
+        }
+        RegisterOp::Stack { address, height } => {
+            let address = *address as usize;
+
+            stack.set_size(operand_pointer + *height as usize);
+            vm.status.address = address;
+
+            let opcode = vm.resource.vm_modules[vm.status.vm_module_index]
+                .bytecode
+                .opcodes[address];
+            interpreter::exec_opcode(vm, opcode)?;
+
+            vm.stack.set_size(full_size);
+        }
+        RegisterOp::Exit { address, height } => {
+            stack.set_size(operand_pointer + *height as usize);
+            vm.status.address = *address as usize;
+            return Ok(None);
+        }
+    }
+
+    Ok(Some(op_index + 1))
+}
+
+// 运算操作的辅助函数
+//
+// 栈槽的编码方式跟栈式解释器的一致：i32 和 f32 存放在低 32 位，比较的结果为 i32 的 1 或者 0。
+
+#[inline(always)]
+fn read_i32(stack: &VMStack, frame_pointer: usize, register: Register) -> i32 {
+    stack.get_slot(frame_pointer + register as usize) as u32 as i32
+}
+
+#[inline(always)]
+fn read_i64(stack: &VMStack, frame_pointer: usize, register: Register) -> i64 {
+    stack.get_slot(frame_pointer + register as usize) as i64
+}
+
+#[inline(always)]
+fn read_f32(stack: &VMStack, frame_pointer: usize, register: Register) -> f32 {
+    f32::from_bits(stack.get_slot(frame_pointer + register as usize) as u32)
+}
+
+#[inline(always)]
+fn read_f64(stack: &VMStack, frame_pointer: usize, register: Register) -> f64 {
+    f64::from_bits(stack.get_slot(frame_pointer + register as usize))
+}
+
+#[inline(always)]
+fn write_bool(stack: &mut VMStack, frame_pointer: usize, register: Register, value: bool) {
+    stack.set_slot(
+        frame_pointer + register as usize,
+        value as u64,
+        ValueType::I32,
+    );
+}
+
+#[inline(always)]
+fn i32_test(
+    stack: &mut VMStack,
+    frame_pointer: usize,
+    operands: &UnaryOperands,
+    f: impl FnOnce(i32) -> bool,
+) {
+    let value = read_i32(stack, frame_pointer, operands.source);
+    write_bool(stack, frame_pointer, operands.dest, f(value));
+}
+
+#[inline(always)]
+fn i64_test(
+    stack: &mut VMStack,
+    frame_pointer: usize,
+    operands: &UnaryOperands,
+    f: impl FnOnce(i64) -> bool,
+) {
+    let value = read_i64(stack, frame_pointer, operands.source);
+    write_bool(stack, frame_pointer, operands.dest, f(value));
+}
+
+#[inline(always)]
+fn i32_compare(
+    stack: &mut VMStack,
+    frame_pointer: usize,
+    operands: &BinaryOperands,
+    f: impl FnOnce(i32, i32) -> bool,
+) {
+    let left = read_i32(stack, frame_pointer, operands.left);
+    let right = read_i32(stack, frame_pointer, operands.right);
+    write_bool(stack, frame_pointer, operands.dest, f(left, right));
+}
+
+#[inline(always)]
+fn i64_compare(
+    stack: &mut VMStack,
+    frame_pointer: usize,
+    operands: &BinaryOperands,
+    f: impl FnOnce(i64, i64) -> bool,
+) {
+    let left = read_i64(stack, frame_pointer, operands.left);
+    let right = read_i64(stack, frame_pointer, operands.right);
+    write_bool(stack, frame_pointer, operands.dest, f(left, right));
+}
+
+#[inline(always)]
+fn f32_compare(
+    stack: &mut VMStack,
+    frame_pointer: usize,
+    operands: &BinaryOperands,
+    f: impl FnOnce(f32, f32) -> bool,
+) {
+    let left = read_f32(stack, frame_pointer, operands.left);
+    let right = read_f32(stack, frame_pointer, operands.right);
+    write_bool(stack, frame_pointer, operands.dest, f(left, right));
+}
+
+#[inline(always)]
+fn f64_compare(
+    stack: &mut VMStack,
+    frame_pointer: usize,
+    operands: &BinaryOperands,
+    f: impl FnOnce(f64, f64) -> bool,
+) {
+    let left = read_f64(stack, frame_pointer, operands.left);
+    let right = read_f64(stack, frame_pointer, operands.right);
+    write_bool(stack, frame_pointer, operands.dest, f(left, right));
+}
+
+#[inline(always)]
+fn i32_binary(
+    stack: &mut VMStack,
+    frame_pointer: usize,
+    operands: &BinaryOperands,
+    f: impl FnOnce(i32, i32) -> i32,
+) {
+    let left = read_i32(stack, frame_pointer, operands.left);
+    let right = read_i32(stack, frame_pointer, operands.right);
+    stack.set_slot(
+        frame_pointer + operands.dest as usize,
+        f(left, right) as u32 as u64,
+        ValueType::I32,
+    );
+}
+
+#[inline(always)]
+fn i64_binary(
+    stack: &mut VMStack,
+    frame_pointer: usize,
+    operands: &BinaryOperands,
+    f: impl FnOnce(i64, i64) -> i64,
+) {
+    let left = read_i64(stack, frame_pointer, operands.left);
+    let right = read_i64(stack, frame_pointer, operands.right);
+    stack.set_slot(
+        frame_pointer + operands.dest as usize,
+        f(left, right) as u64,
+        ValueType::I64,
+    );
+}
+
+/// 可能陷入的二元运算，比如整数除法和求余
+#[inline(always)]
+fn i32_binary_checked(
+    stack: &mut VMStack,
+    frame_pointer: usize,
+    operands: &BinaryOperands,
+    f: impl FnOnce(i32, i32) -> Result<i32, EngineError>,
+) -> Result<(), EngineError> {
+    let left = read_i32(stack, frame_pointer, operands.left);
+    let right = read_i32(stack, frame_pointer, operands.right);
+    stack.set_slot(
+        frame_pointer + operands.dest as usize,
+        f(left, right)? as u32 as u64,
+        ValueType::I32,
+    );
+    Ok(())
+}
+
+/// 可能陷入的二元运算，比如整数除法和求余
+#[inline(always)]
+fn i64_binary_checked(
+    stack: &mut VMStack,
+    frame_pointer: usize,
+    operands: &BinaryOperands,
+    f: impl FnOnce(i64, i64) -> Result<i64, EngineError>,
+) -> Result<(), EngineError> {
+    let left = read_i64(stack, frame_pointer, operands.left);
+    let right = read_i64(stack, frame_pointer, operands.right);
+    stack.set_slot(
+        frame_pointer + operands.dest as usize,
+        f(left, right)? as u64,
+        ValueType::I64,
+    );
+    Ok(())
+}
+
+#[inline(always)]
+fn f32_binary(
+    stack: &mut VMStack,
+    frame_pointer: usize,
+    operands: &BinaryOperands,
+    f: impl FnOnce(f32, f32) -> f32,
+) {
+    let left = read_f32(stack, frame_pointer, operands.left);
+    let right = read_f32(stack, frame_pointer, operands.right);
+    stack.set_slot(
+        frame_pointer + operands.dest as usize,
+        f(left, right).to_bits() as u64,
+        ValueType::F32,
+    );
+}
+
+#[inline(always)]
+fn f64_binary(
+    stack: &mut VMStack,
+    frame_pointer: usize,
+    operands: &BinaryOperands,
+    f: impl FnOnce(f64, f64) -> f64,
+) {
+    let left = read_f64(stack, frame_pointer, operands.left);
+    let right = read_f64(stack, frame_pointer, operands.right);
+    stack.set_slot(
+        frame_pointer + operands.dest as usize,
+        f(left, right).to_bits(),
+        ValueType::F64,
+    );
+}
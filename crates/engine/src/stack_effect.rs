@@ -0,0 +1,553 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 指令的栈效应
+//!
+//! 解码器需要静态地计算每个结构块的栈高度，以便把 `br`、`br_if` 和 `br_table` 等
+//! 跳转指令转换为 "截断操作数栈并跳转"，从而不需要在进入结构块时创建栈帧。
+//!
+//! 大部分指令弹出和压入的操作数的数量是固定的，本模块列出这些指令的栈效应；
+//! 流程控制和函数调用等指令的栈效应需要结合结构块类型和函数类型才能确定，
+//! 由解码器单独处理。
+
+use anvm_ast::instruction::Instruction;
+
+/// 获取指令的栈效应
+///
+/// 返回值为 (弹出的操作数的数量, 压入的操作数的数量)。
+///
+/// 注意流程控制指令、函数调用指令以及异常处理指令的栈效应跟类型有关，
+/// 这些指令不应该传入此函数。
+pub fn get_stack_effect(instruction: &Instruction) -> (usize, usize) {
+    match instruction {
+        // 不改变操作数栈的指令
+        Instruction::DataDrop(..) | Instruction::ElementDrop(..) | Instruction::AtomicFence => {
+            (0, 0)
+        }
+
+        // 压入一个操作数的指令，比如常量指令、`local.get` 和 `global.get`
+        Instruction::LocalGet(..)
+        | Instruction::GlobalGet(..)
+        | Instruction::MemorySize(..)
+        | Instruction::TableSize(..)
+        | Instruction::RefNull(..)
+        | Instruction::RefFunc(..)
+        | Instruction::I32Const(..)
+        | Instruction::I64Const(..)
+        | Instruction::F32Const(..)
+        | Instruction::F64Const(..)
+        | Instruction::V128Const(..) => (0, 1),
+
+        // 弹出一个操作数的指令
+        Instruction::Drop | Instruction::LocalSet(..) | Instruction::GlobalSet(..) => (1, 0),
+
+        // 一元运算，包括零值测试、数值转换、SIMD 的一元运算，以及内存加载指令（地址 -> 数值）
+        Instruction::LocalTee(..)
+        | Instruction::I32Load(..)
+        | Instruction::I64Load(..)
+        | Instruction::F32Load(..)
+        | Instruction::F64Load(..)
+        | Instruction::I32Load8S(..)
+        | Instruction::I32Load8U(..)
+        | Instruction::I32Load16S(..)
+        | Instruction::I32Load16U(..)
+        | Instruction::I64Load8S(..)
+        | Instruction::I64Load8U(..)
+        | Instruction::I64Load16S(..)
+        | Instruction::I64Load16U(..)
+        | Instruction::I64Load32S(..)
+        | Instruction::I64Load32U(..)
+        | Instruction::MemoryGrow(..)
+        | Instruction::TableGet(..)
+        | Instruction::RefIsNull
+        | Instruction::I32Eqz
+        | Instruction::I64Eqz
+        | Instruction::I32Clz
+        | Instruction::I32Ctz
+        | Instruction::I32PopCnt
+        | Instruction::I64Clz
+        | Instruction::I64Ctz
+        | Instruction::I64PopCnt
+        | Instruction::F32Abs
+        | Instruction::F32Neg
+        | Instruction::F32Ceil
+        | Instruction::F32Floor
+        | Instruction::F32Trunc
+        | Instruction::F32Nearest
+        | Instruction::F32Sqrt
+        | Instruction::F64Abs
+        | Instruction::F64Neg
+        | Instruction::F64Ceil
+        | Instruction::F64Floor
+        | Instruction::F64Trunc
+        | Instruction::F64Nearest
+        | Instruction::F64Sqrt
+        | Instruction::I32WrapI64
+        | Instruction::I32TruncF32S
+        | Instruction::I32TruncF32U
+        | Instruction::I32TruncF64S
+        | Instruction::I32TruncF64U
+        | Instruction::I64ExtendI32S
+        | Instruction::I64ExtendI32U
+        | Instruction::I64TruncF32S
+        | Instruction::I64TruncF32U
+        | Instruction::I64TruncF64S
+        | Instruction::I64TruncF64U
+        | Instruction::F32ConvertI32S
+        | Instruction::F32ConvertI32U
+        | Instruction::F32ConvertI64S
+        | Instruction::F32ConvertI64U
+        | Instruction::F32DemoteF64
+        | Instruction::F64ConvertI32S
+        | Instruction::F64ConvertI32U
+        | Instruction::F64ConvertI64S
+        | Instruction::F64ConvertI64U
+        | Instruction::F64PromoteF32
+        | Instruction::I32ReinterpretF32
+        | Instruction::I64ReinterpretF64
+        | Instruction::F32ReinterpretI32
+        | Instruction::F64ReinterpretI64
+        | Instruction::I32Extend8S
+        | Instruction::I32Extend16S
+        | Instruction::I64Extend8S
+        | Instruction::I64Extend16S
+        | Instruction::I64Extend32S
+        | Instruction::I32TruncSatF32S
+        | Instruction::I32TruncSatF32U
+        | Instruction::I32TruncSatF64S
+        | Instruction::I32TruncSatF64U
+        | Instruction::I64TruncSatF32S
+        | Instruction::I64TruncSatF32U
+        | Instruction::I64TruncSatF64S
+        | Instruction::I64TruncSatF64U
+        | Instruction::V128Load(..)
+        | Instruction::V128Load8x8S(..)
+        | Instruction::V128Load8x8U(..)
+        | Instruction::V128Load16x4S(..)
+        | Instruction::V128Load16x4U(..)
+        | Instruction::V128Load32x2S(..)
+        | Instruction::V128Load32x2U(..)
+        | Instruction::V128Load8Splat(..)
+        | Instruction::V128Load16Splat(..)
+        | Instruction::V128Load32Splat(..)
+        | Instruction::V128Load64Splat(..)
+        | Instruction::I8x16Splat
+        | Instruction::I16x8Splat
+        | Instruction::I32x4Splat
+        | Instruction::I64x2Splat
+        | Instruction::F32x4Splat
+        | Instruction::F64x2Splat
+        | Instruction::I8x16ExtractLaneS(..)
+        | Instruction::I8x16ExtractLaneU(..)
+        | Instruction::I16x8ExtractLaneS(..)
+        | Instruction::I16x8ExtractLaneU(..)
+        | Instruction::I32x4ExtractLane(..)
+        | Instruction::I64x2ExtractLane(..)
+        | Instruction::F32x4ExtractLane(..)
+        | Instruction::F64x2ExtractLane(..)
+        | Instruction::V128Not
+        | Instruction::V128AnyTrue
+        | Instruction::V128Load32Zero(..)
+        | Instruction::V128Load64Zero(..)
+        | Instruction::F32x4DemoteF64x2Zero
+        | Instruction::F64x2PromoteLowF32x4
+        | Instruction::I8x16Abs
+        | Instruction::I8x16Neg
+        | Instruction::I8x16Popcnt
+        | Instruction::I8x16AllTrue
+        | Instruction::I8x16Bitmask
+        | Instruction::F32x4Ceil
+        | Instruction::F32x4Floor
+        | Instruction::F32x4Trunc
+        | Instruction::F32x4Nearest
+        | Instruction::F64x2Ceil
+        | Instruction::F64x2Floor
+        | Instruction::F64x2Trunc
+        | Instruction::I16x8ExtaddPairwiseI8x16S
+        | Instruction::I16x8ExtaddPairwiseI8x16U
+        | Instruction::I32x4ExtaddPairwiseI16x8S
+        | Instruction::I32x4ExtaddPairwiseI16x8U
+        | Instruction::I16x8Abs
+        | Instruction::I16x8Neg
+        | Instruction::I16x8AllTrue
+        | Instruction::I16x8Bitmask
+        | Instruction::I16x8ExtendLowI8x16S
+        | Instruction::I16x8ExtendHighI8x16S
+        | Instruction::I16x8ExtendLowI8x16U
+        | Instruction::I16x8ExtendHighI8x16U
+        | Instruction::F64x2Nearest
+        | Instruction::I32x4Abs
+        | Instruction::I32x4Neg
+        | Instruction::I32x4AllTrue
+        | Instruction::I32x4Bitmask
+        | Instruction::I32x4ExtendLowI16x8S
+        | Instruction::I32x4ExtendHighI16x8S
+        | Instruction::I32x4ExtendLowI16x8U
+        | Instruction::I32x4ExtendHighI16x8U
+        | Instruction::I64x2Abs
+        | Instruction::I64x2Neg
+        | Instruction::I64x2AllTrue
+        | Instruction::I64x2Bitmask
+        | Instruction::I64x2ExtendLowI32x4S
+        | Instruction::I64x2ExtendHighI32x4S
+        | Instruction::I64x2ExtendLowI32x4U
+        | Instruction::I64x2ExtendHighI32x4U
+        | Instruction::F32x4Abs
+        | Instruction::F32x4Neg
+        | Instruction::F32x4Sqrt
+        | Instruction::F64x2Abs
+        | Instruction::F64x2Neg
+        | Instruction::F64x2Sqrt
+        | Instruction::I32x4TruncSatF32x4S
+        | Instruction::I32x4TruncSatF32x4U
+        | Instruction::F32x4ConvertI32x4S
+        | Instruction::F32x4ConvertI32x4U
+        | Instruction::I32x4TruncSatF64x2SZero
+        | Instruction::I32x4TruncSatF64x2UZero
+        | Instruction::F64x2ConvertLowI32x4S
+        | Instruction::F64x2ConvertLowI32x4U
+        | Instruction::I32AtomicLoad(..)
+        | Instruction::I64AtomicLoad(..)
+        | Instruction::I32AtomicLoad8U(..)
+        | Instruction::I32AtomicLoad16U(..)
+        | Instruction::I64AtomicLoad8U(..)
+        | Instruction::I64AtomicLoad16U(..)
+        | Instruction::I64AtomicLoad32U(..) => (1, 1),
+
+        // 内存存储指令（地址、数值 -> 空）以及 `table.set`
+        Instruction::I32Store(..)
+        | Instruction::I64Store(..)
+        | Instruction::F32Store(..)
+        | Instruction::F64Store(..)
+        | Instruction::I32Store8(..)
+        | Instruction::I32Store16(..)
+        | Instruction::I64Store8(..)
+        | Instruction::I64Store16(..)
+        | Instruction::I64Store32(..)
+        | Instruction::TableSet(..)
+        | Instruction::V128Store(..)
+        | Instruction::V128Store8Lane(..)
+        | Instruction::V128Store16Lane(..)
+        | Instruction::V128Store32Lane(..)
+        | Instruction::V128Store64Lane(..)
+        | Instruction::I32AtomicStore(..)
+        | Instruction::I64AtomicStore(..)
+        | Instruction::I32AtomicStore8(..)
+        | Instruction::I32AtomicStore16(..)
+        | Instruction::I64AtomicStore8(..)
+        | Instruction::I64AtomicStore16(..)
+        | Instruction::I64AtomicStore32(..) => (2, 0),
+
+        // 二元运算，包括数值比较、SIMD 的二元运算、原子读改写，以及 lane 加载指令
+        Instruction::TableGrow(..)
+        | Instruction::I32Eq
+        | Instruction::I32Ne
+        | Instruction::I32LtS
+        | Instruction::I32LtU
+        | Instruction::I32GtS
+        | Instruction::I32GtU
+        | Instruction::I32LeS
+        | Instruction::I32LeU
+        | Instruction::I32GeS
+        | Instruction::I32GeU
+        | Instruction::I64Eq
+        | Instruction::I64Ne
+        | Instruction::I64LtS
+        | Instruction::I64LtU
+        | Instruction::I64GtS
+        | Instruction::I64GtU
+        | Instruction::I64LeS
+        | Instruction::I64LeU
+        | Instruction::I64GeS
+        | Instruction::I64GeU
+        | Instruction::F32Eq
+        | Instruction::F32Ne
+        | Instruction::F32Lt
+        | Instruction::F32Gt
+        | Instruction::F32Le
+        | Instruction::F32Ge
+        | Instruction::F64Eq
+        | Instruction::F64Ne
+        | Instruction::F64Lt
+        | Instruction::F64Gt
+        | Instruction::F64Le
+        | Instruction::F64Ge
+        | Instruction::I32Add
+        | Instruction::I32Sub
+        | Instruction::I32Mul
+        | Instruction::I32DivS
+        | Instruction::I32DivU
+        | Instruction::I32RemS
+        | Instruction::I32RemU
+        | Instruction::I32And
+        | Instruction::I32Or
+        | Instruction::I32Xor
+        | Instruction::I32Shl
+        | Instruction::I32ShrS
+        | Instruction::I32ShrU
+        | Instruction::I32Rotl
+        | Instruction::I32Rotr
+        | Instruction::I64Add
+        | Instruction::I64Sub
+        | Instruction::I64Mul
+        | Instruction::I64DivS
+        | Instruction::I64DivU
+        | Instruction::I64RemS
+        | Instruction::I64RemU
+        | Instruction::I64And
+        | Instruction::I64Or
+        | Instruction::I64Xor
+        | Instruction::I64Shl
+        | Instruction::I64ShrS
+        | Instruction::I64ShrU
+        | Instruction::I64Rotl
+        | Instruction::I64Rotr
+        | Instruction::F32Add
+        | Instruction::F32Sub
+        | Instruction::F32Mul
+        | Instruction::F32Div
+        | Instruction::F32Min
+        | Instruction::F32Max
+        | Instruction::F32CopySign
+        | Instruction::F64Add
+        | Instruction::F64Sub
+        | Instruction::F64Mul
+        | Instruction::F64Div
+        | Instruction::F64Min
+        | Instruction::F64Max
+        | Instruction::F64CopySign
+        | Instruction::I8x16Shuffle(..)
+        | Instruction::I8x16Swizzle
+        | Instruction::I8x16ReplaceLane(..)
+        | Instruction::I16x8ReplaceLane(..)
+        | Instruction::I32x4ReplaceLane(..)
+        | Instruction::I64x2ReplaceLane(..)
+        | Instruction::F32x4ReplaceLane(..)
+        | Instruction::F64x2ReplaceLane(..)
+        | Instruction::I8x16Eq
+        | Instruction::I8x16Ne
+        | Instruction::I8x16LtS
+        | Instruction::I8x16LtU
+        | Instruction::I8x16GtS
+        | Instruction::I8x16GtU
+        | Instruction::I8x16LeS
+        | Instruction::I8x16LeU
+        | Instruction::I8x16GeS
+        | Instruction::I8x16GeU
+        | Instruction::I16x8Eq
+        | Instruction::I16x8Ne
+        | Instruction::I16x8LtS
+        | Instruction::I16x8LtU
+        | Instruction::I16x8GtS
+        | Instruction::I16x8GtU
+        | Instruction::I16x8LeS
+        | Instruction::I16x8LeU
+        | Instruction::I16x8GeS
+        | Instruction::I16x8GeU
+        | Instruction::I32x4Eq
+        | Instruction::I32x4Ne
+        | Instruction::I32x4LtS
+        | Instruction::I32x4LtU
+        | Instruction::I32x4GtS
+        | Instruction::I32x4GtU
+        | Instruction::I32x4LeS
+        | Instruction::I32x4LeU
+        | Instruction::I32x4GeS
+        | Instruction::I32x4GeU
+        | Instruction::F32x4Eq
+        | Instruction::F32x4Ne
+        | Instruction::F32x4Lt
+        | Instruction::F32x4Gt
+        | Instruction::F32x4Le
+        | Instruction::F32x4Ge
+        | Instruction::F64x2Eq
+        | Instruction::F64x2Ne
+        | Instruction::F64x2Lt
+        | Instruction::F64x2Gt
+        | Instruction::F64x2Le
+        | Instruction::F64x2Ge
+        | Instruction::V128And
+        | Instruction::V128Andnot
+        | Instruction::V128Or
+        | Instruction::V128Xor
+        | Instruction::V128Load8Lane(..)
+        | Instruction::V128Load16Lane(..)
+        | Instruction::V128Load32Lane(..)
+        | Instruction::V128Load64Lane(..)
+        | Instruction::I8x16NarrowI16x8S
+        | Instruction::I8x16NarrowI16x8U
+        | Instruction::I8x16Shl
+        | Instruction::I8x16ShrS
+        | Instruction::I8x16ShrU
+        | Instruction::I8x16Add
+        | Instruction::I8x16AddSatS
+        | Instruction::I8x16AddSatU
+        | Instruction::I8x16Sub
+        | Instruction::I8x16SubSatS
+        | Instruction::I8x16SubSatU
+        | Instruction::I8x16MinS
+        | Instruction::I8x16MinU
+        | Instruction::I8x16MaxS
+        | Instruction::I8x16MaxU
+        | Instruction::I8x16AvgrU
+        | Instruction::I16x8Q15mulrSatS
+        | Instruction::I16x8NarrowI32x4S
+        | Instruction::I16x8NarrowI32x4U
+        | Instruction::I16x8Shl
+        | Instruction::I16x8ShrS
+        | Instruction::I16x8ShrU
+        | Instruction::I16x8Add
+        | Instruction::I16x8AddSatS
+        | Instruction::I16x8AddSatU
+        | Instruction::I16x8Sub
+        | Instruction::I16x8SubSatS
+        | Instruction::I16x8SubSatU
+        | Instruction::I16x8Mul
+        | Instruction::I16x8MinS
+        | Instruction::I16x8MinU
+        | Instruction::I16x8MaxS
+        | Instruction::I16x8MaxU
+        | Instruction::I16x8AvgrU
+        | Instruction::I16x8ExtmulLowI8x16S
+        | Instruction::I16x8ExtmulHighI8x16S
+        | Instruction::I16x8ExtmulLowI8x16U
+        | Instruction::I16x8ExtmulHighI8x16U
+        | Instruction::I32x4Shl
+        | Instruction::I32x4ShrS
+        | Instruction::I32x4ShrU
+        | Instruction::I32x4Add
+        | Instruction::I32x4Sub
+        | Instruction::I32x4Mul
+        | Instruction::I32x4MinS
+        | Instruction::I32x4MinU
+        | Instruction::I32x4MaxS
+        | Instruction::I32x4MaxU
+        | Instruction::I32x4DotI16x8S
+        | Instruction::I32x4ExtmulLowI16x8S
+        | Instruction::I32x4ExtmulHighI16x8S
+        | Instruction::I32x4ExtmulLowI16x8U
+        | Instruction::I32x4ExtmulHighI16x8U
+        | Instruction::I64x2Shl
+        | Instruction::I64x2ShrS
+        | Instruction::I64x2ShrU
+        | Instruction::I64x2Add
+        | Instruction::I64x2Sub
+        | Instruction::I64x2Mul
+        | Instruction::I64x2Eq
+        | Instruction::I64x2Ne
+        | Instruction::I64x2LtS
+        | Instruction::I64x2GtS
+        | Instruction::I64x2LeS
+        | Instruction::I64x2GeS
+        | Instruction::I64x2ExtmulLowI32x4S
+        | Instruction::I64x2ExtmulHighI32x4S
+        | Instruction::I64x2ExtmulLowI32x4U
+        | Instruction::I64x2ExtmulHighI32x4U
+        | Instruction::F32x4Add
+        | Instruction::F32x4Sub
+        | Instruction::F32x4Mul
+        | Instruction::F32x4Div
+        | Instruction::F32x4Min
+        | Instruction::F32x4Max
+        | Instruction::F32x4Pmin
+        | Instruction::F32x4Pmax
+        | Instruction::F64x2Add
+        | Instruction::F64x2Sub
+        | Instruction::F64x2Mul
+        | Instruction::F64x2Div
+        | Instruction::F64x2Min
+        | Instruction::F64x2Max
+        | Instruction::F64x2Pmin
+        | Instruction::F64x2Pmax
+        | Instruction::MemoryAtomicNotify(..)
+        | Instruction::I32AtomicRmwAdd(..)
+        | Instruction::I64AtomicRmwAdd(..)
+        | Instruction::I32AtomicRmw8AddU(..)
+        | Instruction::I32AtomicRmw16AddU(..)
+        | Instruction::I64AtomicRmw8AddU(..)
+        | Instruction::I64AtomicRmw16AddU(..)
+        | Instruction::I64AtomicRmw32AddU(..)
+        | Instruction::I32AtomicRmwSub(..)
+        | Instruction::I64AtomicRmwSub(..)
+        | Instruction::I32AtomicRmw8SubU(..)
+        | Instruction::I32AtomicRmw16SubU(..)
+        | Instruction::I64AtomicRmw8SubU(..)
+        | Instruction::I64AtomicRmw16SubU(..)
+        | Instruction::I64AtomicRmw32SubU(..)
+        | Instruction::I32AtomicRmwAnd(..)
+        | Instruction::I64AtomicRmwAnd(..)
+        | Instruction::I32AtomicRmw8AndU(..)
+        | Instruction::I32AtomicRmw16AndU(..)
+        | Instruction::I64AtomicRmw8AndU(..)
+        | Instruction::I64AtomicRmw16AndU(..)
+        | Instruction::I64AtomicRmw32AndU(..)
+        | Instruction::I32AtomicRmwOr(..)
+        | Instruction::I64AtomicRmwOr(..)
+        | Instruction::I32AtomicRmw8OrU(..)
+        | Instruction::I32AtomicRmw16OrU(..)
+        | Instruction::I64AtomicRmw8OrU(..)
+        | Instruction::I64AtomicRmw16OrU(..)
+        | Instruction::I64AtomicRmw32OrU(..)
+        | Instruction::I32AtomicRmwXor(..)
+        | Instruction::I64AtomicRmwXor(..)
+        | Instruction::I32AtomicRmw8XorU(..)
+        | Instruction::I32AtomicRmw16XorU(..)
+        | Instruction::I64AtomicRmw8XorU(..)
+        | Instruction::I64AtomicRmw16XorU(..)
+        | Instruction::I64AtomicRmw32XorU(..)
+        | Instruction::I32AtomicRmwXchg(..)
+        | Instruction::I64AtomicRmwXchg(..)
+        | Instruction::I32AtomicRmw8XchgU(..)
+        | Instruction::I32AtomicRmw16XchgU(..)
+        | Instruction::I64AtomicRmw8XchgU(..)
+        | Instruction::I64AtomicRmw16XchgU(..)
+        | Instruction::I64AtomicRmw32XchgU(..) => (2, 1),
+
+        // 批量内存和表格操作指令
+        Instruction::MemoryInit(..)
+        | Instruction::MemoryCopy(..)
+        | Instruction::MemoryFill(..)
+        | Instruction::TableInit(..)
+        | Instruction::TableCopy(..)
+        | Instruction::TableFill(..) => (3, 0),
+
+        // 三元运算，包括 `select`、`v128.bitselect`、原子比较交换以及原子等待指令
+        Instruction::Select
+        | Instruction::SelectTyped(..)
+        | Instruction::V128Bitselect
+        | Instruction::MemoryAtomicWait32(..)
+        | Instruction::MemoryAtomicWait64(..)
+        | Instruction::I32AtomicRmwCmpxchg(..)
+        | Instruction::I64AtomicRmwCmpxchg(..)
+        | Instruction::I32AtomicRmw8CmpxchgU(..)
+        | Instruction::I32AtomicRmw16CmpxchgU(..)
+        | Instruction::I64AtomicRmw8CmpxchgU(..)
+        | Instruction::I64AtomicRmw16CmpxchgU(..)
+        | Instruction::I64AtomicRmw32CmpxchgU(..) => (3, 1),
+
+        // 流程控制、函数调用和异常处理指令由解码器单独处理
+        Instruction::Unreachable
+        | Instruction::Nop
+        | Instruction::Block(..)
+        | Instruction::Loop(..)
+        | Instruction::If(..)
+        | Instruction::Else
+        | Instruction::End
+        | Instruction::Br(..)
+        | Instruction::BrIf(..)
+        | Instruction::BrTable(..)
+        | Instruction::Return
+        | Instruction::Call(..)
+        | Instruction::CallIndirect(..)
+        | Instruction::ReturnCall(..)
+        | Instruction::ReturnCallIndirect(..)
+        | Instruction::Throw(..)
+        | Instruction::ThrowRef
+        | Instruction::TryTable(..) => {
+            unreachable!("the stack effect of control instruction depends on types")
+        }
+    }
+}
@@ -0,0 +1,224 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 线程
+//!
+//! 线程提案只定义了共享内存块以及原子指令，并没有定义如何创建线程，创建线程的方法由宿主提供，
+//! 比如 [wasi-threads](https://github.com/WebAssembly/wasi-threads) 的 `thread-spawn` 函数。
+//!
+//! 每一个线程都运行在各自的宿主线程以及各自的 VM 实例上，VM 实例之间只共享内存块
+//! （仅限共享内存块），而操作数栈、表、全局变量以及本地模块等都是各个实例独有的。
+//!
+//! 因为本地模块（比如 WASI 模块）的上下文不一定能跨线程传递，所以 `ThreadSpawner`
+//! 持有一个创建本地模块的工厂函数，在新线程里为新的 VM 实例创建本地模块。
+
+use std::{
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+};
+
+use anvm_ast::types::Value;
+
+use crate::{
+    error::{EngineError, ObjectNotFound},
+    instance::{create_instance, create_thread_instance, find_ast_module_export_function},
+    native_module::NativeModule,
+    object::NamedAstModule,
+    vm::VM,
+    vm_memory::VMMemory,
+};
+
+/// 创建本地模块列表的工厂函数
+///
+/// 参数是 ThreadSpawner 本身，以便本地模块（比如 `thread-spawn` 函数所在的模块）
+/// 可以在新线程里继续创建线程。
+pub type NativeModulesFactory = Arc<dyn Fn(&Arc<ThreadSpawner>) -> Vec<NativeModule> + Send + Sync>;
+
+pub struct ThreadSpawner {
+    named_ast_modules: Arc<Vec<NamedAstModule>>,
+    native_modules_factory: NativeModulesFactory,
+
+    /// 下一个线程的 id，id 0 保留给主线程
+    next_thread_id: AtomicU32,
+
+    /// 线程的运行结果为 Err 时，其内容是错误信息
+    join_handles: Mutex<Vec<JoinHandle<Result<(), String>>>>,
+}
+
+impl ThreadSpawner {
+    pub fn new(
+        named_ast_modules: Vec<NamedAstModule>,
+        native_modules_factory: NativeModulesFactory,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            named_ast_modules: Arc::new(named_ast_modules),
+            native_modules_factory,
+            next_thread_id: AtomicU32::new(1),
+            join_handles: Mutex::new(vec![]),
+        })
+    }
+
+    /// 创建主线程的 VM 实例
+    pub fn create_instance(self: &Arc<Self>) -> Result<VM, EngineError> {
+        let native_modules = (self.native_modules_factory)(self);
+        create_instance(native_modules, &self.named_ast_modules)
+    }
+
+    /// 分配一个新的线程 id
+    pub fn allocate_thread_id(&self) -> u32 {
+        self.next_thread_id.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// 在新线程里创建 VM 实例，并调用指定名称的导出函数
+    ///
+    /// 新的 VM 实例跟 vm 共享所有共享内存块。导出函数从最后一个模块开始查找，
+    /// 找不到时返回错误，而实例化以及函数运行时的错误则由 `join` 返回。
+    pub fn spawn(
+        self: &Arc<Self>,
+        vm: &VM,
+        export_function_name: &str,
+        arguments: Vec<Value>,
+    ) -> Result<(), EngineError> {
+        let (vm_module_index, function_index) = self
+            .named_ast_modules
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(module_index, named_ast_module)| {
+                find_ast_module_export_function(&named_ast_module.module, export_function_name)
+                    .map(|function_index| (module_index, function_index as usize))
+            })
+            .ok_or_else(|| {
+                let module_name = self
+                    .named_ast_modules
+                    .last()
+                    .map(|named_ast_module| named_ast_module.name.clone())
+                    .unwrap_or_default();
+                EngineError::ObjectNotFound(ObjectNotFound::FunctionNotFound(
+                    module_name,
+                    export_function_name.to_string(),
+                ))
+            })?;
+
+        let shared_memory_blocks = vm
+            .resource
+            .memory_blocks
+            .iter()
+            .map(|memory_block| memory_block.share())
+            .collect::<Vec<Option<VMMemory>>>();
+
+        let thread_spawner = Arc::clone(self);
+
+        let join_handle = thread::spawn(move || {
+            let native_modules = (thread_spawner.native_modules_factory)(&thread_spawner);
+            let mut thread_vm = create_thread_instance(
+                native_modules,
+                &thread_spawner.named_ast_modules,
+                shared_memory_blocks,
+            )
+            .map_err(|e| e.to_string())?;
+
+            thread_vm
+                .eval_function_by_index(vm_module_index, function_index, &arguments)
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        });
+
+        self.join_handles.lock().unwrap().push(join_handle);
+        Ok(())
+    }
+
+    /// 等待所有线程（包括线程运行期间创建的线程）结束
+    ///
+    /// 返回第一个出错的线程的错误信息。
+    pub fn join(&self) -> Result<(), String> {
+        let mut result = Ok(());
+
+        loop {
+            let option_join_handle = self.join_handles.lock().unwrap().pop();
+            let join_handle = match option_join_handle {
+                Some(join_handle) => join_handle,
+                None => break,
+            };
+
+            let thread_result = join_handle
+                .join()
+                .unwrap_or_else(|_| Err("thread panicked".to_string()));
+
+            if result.is_ok() {
+                result = thread_result;
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, sync::Arc};
+
+    use anvm_ast::{ast, types::Value};
+    use anvm_binary_parser::parser;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{instance::find_ast_module_export_function, object::NamedAstModule};
+
+    use super::ThreadSpawner;
+
+    fn get_test_ast_module(filename: &str) -> ast::Module {
+        let mut path_buf = env::current_dir().unwrap();
+        if !path_buf.ends_with("engine") {
+            path_buf.push("crates");
+            path_buf.push("engine");
+        }
+        let fullname_buf = path_buf.join("resources").join(filename);
+        let bytes = fs::read(fullname_buf).unwrap();
+        parser::parse(&bytes).unwrap()
+    }
+
+    #[test]
+    fn test_spawn() {
+        let ast_module = get_test_ast_module("test-thread.wasm");
+        let wait_for_function_index =
+            find_ast_module_export_function(&ast_module, "wait_for").unwrap() as usize;
+
+        let named_ast_module = NamedAstModule::new("test", ast_module);
+        let thread_spawner = ThreadSpawner::new(vec![named_ast_module], Arc::new(|_| vec![]));
+        let mut vm = thread_spawner.create_instance().unwrap();
+
+        for start_arg in 1..=8 {
+            let thread_id = thread_spawner.allocate_thread_id();
+            assert_eq!(thread_id, start_arg);
+
+            thread_spawner
+                .spawn(
+                    &vm,
+                    "wasi_thread_start",
+                    vec![Value::I32(thread_id as i32), Value::I32(start_arg as i32)],
+                )
+                .unwrap();
+        }
+
+        // 主线程等待所有线程累加完毕
+        assert_eq!(
+            vm.eval_function_by_index(0, wait_for_function_index, &vec![Value::I32(8)])
+                .unwrap(),
+            vec![Value::I32(36)]
+        );
+
+        assert_eq!(thread_spawner.join(), Ok(()));
+
+        // 找不到入口函数
+        assert!(thread_spawner
+            .spawn(&vm, "no_such_function", vec![])
+            .is_err());
+    }
+}
@@ -10,14 +10,19 @@ use anvm_ast::{
 };
 
 use crate::{
-    error::{EngineError, InvalidOperation, TypeMismatch, Unsupported},
+    error::{
+        make_operand_data_types_mismatch_engine_error, EngineError, InvalidOperation, TypeMismatch,
+        Unsupported,
+    },
     interpreter,
     native_module::NativeModule,
     object::FunctionItem,
+    register_interpreter,
+    vm_exception::{VMException, VMTag},
     vm_global_variable::VMGlobalVariable,
     vm_memory::VMMemory,
     vm_module::VMModule,
-    vm_stack::VMStack,
+    vm_stack::{VMStack, INFO_SEGMENT_ITEM_COUNT},
     vm_table::VMTable,
 };
 
@@ -39,16 +44,9 @@ pub struct Status {
 
     /// 局部变量段的开始地址
     ///
-    /// 也就是当前调用帧的地址
-    /// 因为 WebAssembly 的流程控制结构块（比如 block/loop/if）跟函数调用
-    /// 的工作方式非常类似，所以无论是函数调用调用还是进入一个结构块，
-    /// 当前的解析器都会创建一个新的栈帧。
-    ///
-    /// 但结构块没有自己的局部变量，它的局部变量来自最近一次函数调用所创建的帧，
-    /// - 当新的栈帧是因为函数调用而创建时，local_pointer 和 frame_pointer
-    ///   的值是相等的，
-    /// - 当新的栈帧是由进入结构块而创建时，则 local_pointer
-    ///   保持跟上一个栈帧的 local_pointer 相同。
+    /// 也就是当前调用帧的地址。
+    /// 流程控制结构块（比如 block/loop/if）不会创建栈帧，只有函数调用才会创建栈帧，
+    /// 所以 local_pointer 和 frame_pointer 的值总是相等的。
     pub local_pointer: usize,
 
     /// 信息段开始的地址
@@ -89,10 +87,10 @@ pub struct Status {
     /// 当前指令所在的函数，指令执行完毕之后，则是下一个待执行指令所在的函数
     pub function_index: usize,
 
-    /// 当前函数或者结构块的（签名）类型
+    /// 当前函数的（签名）类型
     ///
-    /// 一般函数的（签名）类型可以通过 function_index 间接找到，不过由于当前 VM 使用了跟执行 `一般函数`
-    /// 的方式执行结构块，所以当前栈帧还有可能是结构块，所以需要额外一个字段用于记录当前栈帧的 "函数" 类型。
+    /// 函数的（签名）类型也可以通过 function_index 间接找到，这里冗余记录一份，
+    /// 用于省去函数返回时的一次查询过程。
     pub frame_type: BlockType,
 
     /// 当前指令在指令序列里所处的位置，指令执行完毕之后，则是下一个待执行指令的位置
@@ -121,6 +119,17 @@ pub struct Resource {
     pub tables: Vec<VMTable>,
     pub global_variables: Vec<VMGlobalVariable>,
 
+    /// 标签实例列表
+    ///
+    /// 本地模块的标签排在最前面，然后才是各个模块内部定义的标签。
+    pub tags: Vec<VMTag>,
+
+    /// 异常对象列表，`exnref` 的值是异常对象在该列表里的索引
+    pub exceptions: Vec<VMException>,
+
+    /// 已被释放的异常对象在异常列表里的位置，供新的异常对象重用
+    pub free_exception_indexes: Vec<usize>,
+
     pub native_modules: Vec<NativeModule>,
     pub vm_modules: Vec<VMModule>,
 }
@@ -130,6 +139,7 @@ impl Resource {
         memory_blocks: Vec<VMMemory>,
         tables: Vec<VMTable>,
         global_variables: Vec<VMGlobalVariable>,
+        tags: Vec<VMTag>,
         native_modules: Vec<NativeModule>,
         vm_modules: Vec<VMModule>,
     ) -> Self {
@@ -137,16 +147,42 @@ impl Resource {
             memory_blocks,
             tables,
             global_variables,
+            tags,
+            exceptions: vec![],
+            free_exception_indexes: vec![],
             native_modules,
             vm_modules,
         }
     }
 }
 
+/// 解释器的类型
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum InterpreterKind {
+    /// 栈式解释器，逐条执行由指令列表编译而成的字节码
+    Stack,
+
+    /// 寄存器解释器
+    ///
+    /// 实例化时先把各个模块的内部函数降低为寄存器中间表示（见 `register` 模块），
+    /// 执行时优先执行寄存器中间表示，不支持的指令仍由栈式解释器执行。
+    Register,
+
+    /// JIT 编译器
+    ///
+    /// 实例化时把寄存器中间表示编译为 x86-64 机器码（见 `jit` 模块），
+    /// 不支持编译的操作由寄存器解释器执行。
+    #[cfg(feature = "jit")]
+    Jit,
+}
+
 pub struct VM {
     pub stack: VMStack,
     pub status: Status,
     pub resource: Resource,
+
+    /// 执行函数时使用的解释器
+    pub interpreter_kind: InterpreterKind,
 }
 
 pub enum CallFunctionResult {
@@ -186,6 +222,29 @@ impl VM {
         }
     }
 
+    /// 从本地函数内部（即重入）调用函数，并进行求值
+    ///
+    /// 本地函数被调用时 vm 正处于某个函数的执行过程当中，
+    /// 为了让被调用的函数执行完毕时能够正常结束（而不是返回到本地函数的调用者），
+    /// 先暂时把 vm 的状态设置为"尚未开始任何函数调用"，求值之后再恢复原先的状态。
+    pub fn eval_function_reentrant(
+        &mut self,
+        vm_module_index: usize,
+        function_index: usize,
+        arguments: &[Value],
+    ) -> Result<Vec<Value>, EngineError> {
+        let status = self.status.clone();
+
+        self.status.frame_pointer = INITIAL_FRAME_POINTER;
+        self.status.local_pointer = INITIAL_FRAME_POINTER;
+        self.status.base_pointer = INITIAL_FRAME_POINTER;
+
+        let result = self.eval_function_by_index(vm_module_index, function_index, arguments);
+
+        self.status = status;
+        result
+    }
+
     /// 将指定实参压入栈，并将 pc 的值指向函数的
     /// 第一个指令，但并不会开始执行指令。
     ///
@@ -385,15 +444,62 @@ impl VM {
         }
     }
 
+    /// 获取本地模块的标签在 VM 标签实例列表里的索引
+    ///
+    /// 链接时本地模块的标签按照本地模块的顺序排在标签实例列表的最前面。
+    pub fn get_native_tag_index(&self, native_module_index: usize, tag_index: usize) -> usize {
+        let offset: usize = self.resource.native_modules[..native_module_index]
+            .iter()
+            .map(|native_module| native_module.tag_names.len())
+            .sum();
+        offset + tag_index
+    }
+
+    /// 获取函数引用所指向的函数所属的模块的索引，以及函数在该模块里的索引
+    ///
+    /// 函数引用的值是函数在 VM 实例里的全局索引，见 `VMModule::function_reference_base`。
+    /// 函数引用有可能由宿主提供，所以当它不指向任何函数时返回 None。
+    pub fn resolve_function_reference(&self, function_reference: u32) -> Option<(usize, usize)> {
+        let vm_modules = &self.resource.vm_modules;
+
+        // 不含函数的模块的起始值跟下一个模块的相同，所以查找最后一个起始值
+        // 不大于函数引用值的模块
+        let vm_module_index = vm_modules
+            .partition_point(|vm_module| vm_module.function_reference_base <= function_reference)
+            .checked_sub(1)?;
+        let vm_module = &vm_modules[vm_module_index];
+        let function_index = (function_reference - vm_module.function_reference_base) as usize;
+
+        if function_index < vm_module.function_items.len() {
+            Some((vm_module_index, function_index))
+        } else {
+            None
+        }
+    }
+
     /// 执行剩余的指令，遇到断点时中断
     pub fn recur(&mut self) -> Result<bool, EngineError> {
         todo!()
     }
 
     /// 执行剩余的指令，无视断点
+    ///
+    /// 执行的是包含超级字节码的字节码列表，即被融合的原指令会一次执行完毕。
     pub fn recur_without_break(&mut self) -> Result<(), EngineError> {
+        if self.interpreter_kind == InterpreterKind::Register {
+            return register_interpreter::recur_without_break(self);
+        }
+
+        #[cfg(feature = "jit")]
+        if self.interpreter_kind == InterpreterKind::Jit {
+            return crate::jit::recur_without_break(self);
+        }
+
         loop {
-            let is_program_end = self.step()?;
+            let opcode = self.resource.vm_modules[self.status.vm_module_index]
+                .bytecode
+                .fused_opcodes[self.status.address];
+            let is_program_end = interpreter::exec_opcode(self, opcode)?;
             if is_program_end {
                 break;
             }
@@ -407,13 +513,18 @@ impl VM {
     ///
     /// 当程序（或者说第一个被调用的函数）的最后一条指令（即 `end 指令`）执行
     /// 之后，函数返回 true，否则返回 false。
+    ///
+    /// 执行的是由原指令编译而成的字节码，字节码实现了 Copy，所以这里直接复制，
+    /// 而不需要克隆原指令。
+    ///
+    /// 单步执行不使用超级字节码，所以每次只执行一条原指令。
+    #[inline]
     pub fn step(&mut self) -> Result<bool, EngineError> {
         let vm_module_index = self.status.vm_module_index;
         let address = self.status.address;
 
-        let instruction =
-            self.resource.vm_modules[vm_module_index].instructions[address].to_owned();
-        let is_program_end = interpreter::exec_instruction(self, &instruction)?;
+        let opcode = self.resource.vm_modules[vm_module_index].bytecode.opcodes[address];
+        let is_program_end = interpreter::exec_opcode(self, opcode)?;
 
         Ok(is_program_end)
     }
@@ -477,26 +588,22 @@ impl VM {
         let base_pointer = previous_stack_pointer + local_variable_types.len();
 
         // 分配局部变量空槽
+        // 数值类型的初始值为 0，引用类型的初始值为 null
         for variable_type in local_variable_types {
-            match variable_type {
-                ValueType::I32 => stack.push(Value::I32(0)),
-                ValueType::I64 => stack.push(Value::I64(0)),
-                ValueType::F32 => stack.push(Value::F32(0.0)),
-                ValueType::F64 => stack.push(Value::F64(0.0)),
-            }
+            stack.push(Value::default_of(variable_type));
         }
 
         let (frame_type_class, frame_type_value) = convert_from_frame_type(&previous_frame_type);
 
         // 写信息段
-        stack.push(previous_frame_pointer.into());
-        stack.push(previous_local_pointer.into());
-        stack.push(previous_base_pointer.into());
-        stack.push(return_vm_module_index.into());
-        stack.push(return_function_index.into());
-        stack.push(frame_type_class.into()); // 使用两个 Value::I64 来记录 frame_type
-        stack.push(frame_type_value.into());
-        stack.push(return_address.into());
+        stack.push_slot(previous_frame_pointer as u64);
+        stack.push_slot(previous_local_pointer as u64);
+        stack.push_slot(previous_base_pointer as u64);
+        stack.push_slot(return_vm_module_index as u64);
+        stack.push_slot(return_function_index as u64);
+        stack.push_slot(frame_type_class as u64); // 使用两个栈槽来记录 frame_type
+        stack.push_slot(frame_type_value as u64);
+        stack.push_slot(return_address as u64);
 
         // 更新跟栈帧位置信息相关的部分 status
         status.frame_pointer = frame_pointer;
@@ -504,55 +611,49 @@ impl VM {
         status.base_pointer = base_pointer;
     }
 
-    /// 压入控制块帧
+    /// 替换当前的函数调用帧，用于尾调用
     ///
-    /// 额外操作：
+    /// 栈顶的 parameters_count 个操作数是目标函数的实参，
+    /// 当前调用帧里的其他操作数（包括结构块内的操作数）都会被丢弃。
+    ///
+    /// 新的调用帧沿用原调用帧的信息段，即目标函数执行完毕之后，
+    /// 会直接返回到原函数的调用者。
+    ///
+    /// 额外操作
+    /// - 分配局部变量空槽
     /// - 更新跟栈帧部分相关的 status
-    pub fn push_control_frame(&mut self, return_address: usize) {
+    pub fn replace_call_frame(
+        &mut self,
+        parameters_count: usize,
+        local_variable_types: &[ValueType],
+    ) {
         let status = &mut self.status;
-
-        // 读取旧的 status
-        let previous_frame_pointer = status.frame_pointer;
-        let previous_local_pointer = status.local_pointer;
-        let previous_base_pointer = status.base_pointer;
-        let return_vm_module_index = status.vm_module_index;
-        let return_function_index = status.function_index;
-        let previous_frame_type = status.frame_type.clone();
-
         let stack = &mut self.stack;
-        let previous_stack_pointer = stack.get_size();
 
-        // 因为控制帧没有自己的局部变量段，所以
-        // 栈帧的起始位置为当前栈顶。
-        let frame_pointer = previous_stack_pointer;
+        // 读取当前的 status
+        let frame_pointer = status.frame_pointer;
+        let base_pointer = status.base_pointer;
 
-        // 对于 control frame，local pointer 的值跟上一次的 local pointer 一致
-        let local_pointer = previous_local_pointer;
+        // 先保存一份信息段
+        let info_segment = (0..INFO_SEGMENT_ITEM_COUNT)
+            .map(|offset| stack.get_slot(base_pointer + offset))
+            .collect::<Vec<u64>>();
 
-        // 信息段的开始位置为：
-        //
-        // - 原栈顶（sp）
-        // - 当前栈帧的开始位置（fp）+ 参数数量（结构块没有自己的局部变量）
-        //
-        // 两个数值是一样的
-        let base_pointer = previous_stack_pointer;
+        // 删除当前栈帧，但保留栈顶的实参
+        stack.drop_values_keep_top(frame_pointer, parameters_count);
 
-        let (frame_type_class, frame_type_value) = convert_from_frame_type(&previous_frame_type);
+        // 分配局部变量空槽
+        for variable_type in local_variable_types {
+            stack.push(Value::default_of(variable_type));
+        }
 
-        // 写信息段
-        stack.push(previous_frame_pointer.into());
-        stack.push(previous_local_pointer.into());
-        stack.push(previous_base_pointer.into());
-        stack.push(return_vm_module_index.into());
-        stack.push(return_function_index.into());
-        stack.push(frame_type_class.into()); // 使用两个 Value::I64 来记录 frame_type
-        stack.push(frame_type_value.into());
-        stack.push(return_address.into());
+        // 重新写信息段
+        for slot in info_segment {
+            stack.push_slot(slot);
+        }
 
-        // 更新跟栈帧位置信息相关的部分 status
-        status.frame_pointer = frame_pointer;
-        status.local_pointer = local_pointer;
-        status.base_pointer = base_pointer;
+        // 栈帧的起始位置（fp）以及 local pointer 保持不变
+        status.base_pointer = frame_pointer + parameters_count + local_variable_types.len();
     }
 
     /// 弹出栈帧
@@ -561,7 +662,7 @@ impl VM {
     /// 待更新的 pc 值返回，然后让调用者决定如何更新 pc 值。
     ///
     /// 额外操作：
-    /// - 函数或结构块的返回值将会暂存，并在弹出栈帧后重新压入栈
+    /// - 函数的返回值将会暂存，并在弹出栈帧后重新压入栈
     /// - 更新但跟栈帧部分相关的 status
     pub fn pop_frame(
         &mut self,
@@ -580,23 +681,17 @@ impl VM {
         let base_pointer = status.base_pointer;
 
         // 读取信息段
-        let previous_frame_pointer: usize = stack.get_value(base_pointer).into();
-        let previous_local_pointer: usize = stack.get_value(base_pointer + 1).into();
-        let previous_base_pointer: usize = stack.get_value(base_pointer + 2).into();
-        let return_vm_module_index: usize = stack.get_value(base_pointer + 3).into();
-        let return_function_index: usize = stack.get_value(base_pointer + 4).into();
-        let frame_type_class: usize = stack.get_value(base_pointer + 5).into(); // 使用两个 Value::I64 来还原 frame_type
-        let frame_type_value: usize = stack.get_value(base_pointer + 6).into();
-        let return_address: usize = stack.get_value(base_pointer + 7).into();
-
-        // 先保存一份返回值
-        let results = stack.pop_values(results_count);
-
-        // 删除当前栈帧
-        stack.drop_values_at(frame_pointer);
-
-        // 重新压入返回值
-        stack.push_values(&results);
+        let previous_frame_pointer = stack.get_slot(base_pointer) as usize;
+        let previous_local_pointer = stack.get_slot(base_pointer + 1) as usize;
+        let previous_base_pointer = stack.get_slot(base_pointer + 2) as usize;
+        let return_vm_module_index = stack.get_slot(base_pointer + 3) as usize;
+        let return_function_index = stack.get_slot(base_pointer + 4) as usize;
+        let frame_type_class = stack.get_slot(base_pointer + 5) as usize; // 使用两个栈槽来还原 frame_type
+        let frame_type_value = stack.get_slot(base_pointer + 6) as usize;
+        let return_address = stack.get_slot(base_pointer + 7) as usize;
+
+        // 删除当前栈帧，但保留栈顶的返回值
+        stack.drop_values_keep_top(frame_pointer, results_count);
 
         // 更新跟栈帧位置信息相关的部分 status
         status.frame_pointer = previous_frame_pointer;
@@ -613,156 +708,137 @@ impl VM {
         )
     }
 
-    /// 弹出指定层数的栈帧
+    /// 在指定模块的环境里对一个常量表达式求值
     ///
-    /// 注意该方法不会更新任何 status 数据，因为一般在调用这个方法之前
-    /// 调用者会先调用 get_status_by_relative_depth() 方法获取目标
-    /// 栈帧的资料，所以这里就不再重复获取并维护这些资料。
-    pub fn pop_frames(
-        &mut self,
-        relative_depth: usize,
-        results_count: usize,
-    ) -> (
-        /* vm_module_index */ usize,
-        /* function_index */ usize,
-        /* frame_type */ BlockType,
-        /* address */ usize,
-    ) {
-        if relative_depth == 0 {
-            // 没有弹出任何栈帧，直接返回当前栈帧信息
-            let status = &self.status;
-            (
-                status.vm_module_index,
-                status.function_index,
-                status.frame_type.clone(),
-                status.address,
-            )
-        } else {
-            let status = &mut self.status;
-            let stack = &mut self.stack;
-
-            // 读取当前的 status
-            let mut frame_pointer = status.frame_pointer; // frame_pointer 为将要丢弃的栈帧的位置
-            let mut base_pointer = status.base_pointer;
-
-            // 从栈回溯，找到指定层级的状态
-            for _ in 0..(relative_depth - 1) {
-                let previous_frame_pointer: usize = stack.get_value(base_pointer).into();
-                let previous_base_pointer: usize = stack.get_value(base_pointer + 2).into();
-                frame_pointer = previous_frame_pointer;
-                base_pointer = previous_base_pointer;
-            }
-
-            let previous_frame_pointer: usize = stack.get_value(base_pointer).into();
-            let previous_local_pointer: usize = stack.get_value(base_pointer + 1).into();
-            let previous_base_pointer: usize = stack.get_value(base_pointer + 2).into();
-            let return_vm_module_index: usize = stack.get_value(base_pointer + 3).into();
-            let return_function_index: usize = stack.get_value(base_pointer + 4).into();
-            // 使用两个 Value::I64 来还原 frame_type
-            let frame_type_class: usize = stack.get_value(base_pointer + 5).into();
-            let frame_type_value: usize = stack.get_value(base_pointer + 6).into();
-            let return_address: usize = stack.get_value(base_pointer + 7).into();
-
-            // 先保存一份返回值
-            let results = stack.pop_values(results_count);
-
-            // 丢弃自 frame_pointer 之后的所有数据
-            stack.drop_values_at(frame_pointer);
-
-            // 重新压入返回值
-            stack.push_values(&results);
-
-            // 更新跟栈帧位置信息相关的部分 status
-            status.frame_pointer = previous_frame_pointer;
-            status.local_pointer = previous_local_pointer;
-            status.base_pointer = previous_base_pointer;
-
-            let frame_type = convert_to_frame_type(frame_type_class, frame_type_value);
-
-            (
-                return_vm_module_index,
-                return_function_index,
-                frame_type,
-                return_address,
-            )
-        }
-    }
-
-    pub fn get_status_by_relative_depth(&self, relative_depth: usize) -> Status {
-        if relative_depth == 0 {
-            // 当相对层级为 0 时，
-            // 克隆一份当前的虚拟机状态即可。
-            self.status.clone()
-        } else {
-            let status = &self.status;
-            let stack = &self.stack;
-
-            let mut base_pointer = status.base_pointer;
-
-            // 从栈回溯，找到指定层级的状态
-            for _ in 0..(relative_depth - 1) {
-                let previous_base_pointer: usize = stack.get_value(base_pointer + 2).into();
-                base_pointer = previous_base_pointer;
-            }
-
-            // 读取信息段
-            let previous_frame_pointer: usize = stack.get_value(base_pointer).into();
-            let previous_local_pointer: usize = stack.get_value(base_pointer + 1).into();
-            let previous_base_pointer: usize = stack.get_value(base_pointer + 2).into();
-            let return_vm_module_index: usize = stack.get_value(base_pointer + 3).into();
-            let return_function_index: usize = stack.get_value(base_pointer + 4).into();
-            // 使用两个 Value::I64 来还原 frame_type
-            let frame_type_class: usize = stack.get_value(base_pointer + 5).into();
-            let frame_type_value: usize = stack.get_value(base_pointer + 6).into();
-            let return_address: usize = stack.get_value(base_pointer + 7).into();
-
-            let frame_type = convert_to_frame_type(frame_type_class, frame_type_value);
-
-            Status {
-                frame_pointer: previous_frame_pointer,
-                local_pointer: previous_local_pointer,
-                base_pointer: previous_base_pointer,
-
-                vm_module_index: return_vm_module_index,
-                function_index: return_function_index,
-                frame_type: frame_type,
-                address: return_address,
-            }
-        }
-    }
-
-    /// 对一个常量表达式求值
-    /// 目前只支持一个 `t.const 指令` 和一个 `end 指令` 这种常量表达式
+    /// 常量表达式里的 `global.get` 指令读取的是该模块的（导入的）全局变量。
     pub fn eval_constant_expression(
         &mut self,
+        vm_module_index: usize,
         instructions: &[instruction::Instruction],
     ) -> Result<Value, EngineError> {
-        VM::get_constant_instruction_value(instructions)
+        let vm_module = &self.resource.vm_modules[vm_module_index];
+        let global_variable_indexes = &vm_module.global_variable_indexes;
+        let global_variables = &self.resource.global_variables;
+
+        VM::get_constant_instruction_value(
+            instructions,
+            vm_module.function_reference_base,
+            |global_variable_index| {
+                global_variables[global_variable_indexes[global_variable_index as usize]]
+                    .get_value()
+            },
+        )
     }
 
-    pub fn get_constant_instruction_value(
+    /// 对一个常量表达式求值
+    ///
+    /// 常量表达式使用一个临时的操作数栈求值，结果必须是有且只有一个数值。
+    /// `function_reference_base` 是所在模块的函数引用值的起始值，
+    /// `get_global_value` 用于读取 `global.get` 指令所引用的全局变量的值。
+    pub fn get_constant_instruction_value<F>(
         instructions: &[instruction::Instruction],
-    ) -> Result<Value, EngineError> {
-        if let Some(first) = instructions.first() {
-            let value = match first {
+        function_reference_base: u32,
+        get_global_value: F,
+    ) -> Result<Value, EngineError>
+    where
+        F: Fn(u32) -> Value,
+    {
+        let mut values: Vec<Value> = vec![];
+
+        for inst in instructions {
+            let value = match inst {
                 instruction::Instruction::I32Const(v) => Value::I32(*v),
                 instruction::Instruction::I64Const(v) => Value::I64(*v),
                 instruction::Instruction::F32Const(v) => Value::F32(*v),
                 instruction::Instruction::F64Const(v) => Value::F64(*v),
+                instruction::Instruction::V128Const(v) => Value::V128(*v),
+                instruction::Instruction::RefNull(ref_type) => Value::new_null_ref(*ref_type),
+
+                // 函数引用的值是函数在 VM 实例里的全局索引
+                instruction::Instruction::RefFunc(function_index) => {
+                    Value::FuncRef(Some(function_reference_base + *function_index))
+                }
+                instruction::Instruction::GlobalGet(global_variable_index) => {
+                    get_global_value(*global_variable_index)
+                }
+                instruction::Instruction::I32Add
+                | instruction::Instruction::I32Sub
+                | instruction::Instruction::I32Mul
+                | instruction::Instruction::I64Add
+                | instruction::Instruction::I64Sub
+                | instruction::Instruction::I64Mul => {
+                    let right = values.pop();
+                    let left = values.pop();
+                    eval_constant_binary_instruction(inst, left, right)?
+                }
+                instruction::Instruction::End => break,
                 _ => {
                     return Err(EngineError::Unsupported(
-                        Unsupported::UnsupportedConstantExpressionInstruction(first.to_owned()),
+                        Unsupported::UnsupportedConstantExpressionInstruction(inst.to_owned()),
                     ))
                 }
             };
-            Ok(value)
-        } else {
-            // parser 应该事先检查常量表示式不为空
-            unreachable!("empty constant expression")
+
+            values.push(value);
+        }
+
+        match values.as_slice() {
+            [value] => Ok(*value),
+            _ => Err(EngineError::InvalidOperation(
+                InvalidOperation::IncorrectConstantExpressionResultCount(values.len()),
+            )),
         }
     }
 }
 
+/// 常量表达式里的整数算术指令
+///
+/// 跟普通的算术指令一样，运算结果溢出时回绕（wrapping）。
+fn eval_constant_binary_instruction(
+    inst: &instruction::Instruction,
+    left: Option<Value>,
+    right: Option<Value>,
+) -> Result<Value, EngineError> {
+    let value = match (inst, left, right) {
+        (instruction::Instruction::I32Add, Some(Value::I32(l)), Some(Value::I32(r))) => {
+            Value::I32(l.wrapping_add(r))
+        }
+        (instruction::Instruction::I32Sub, Some(Value::I32(l)), Some(Value::I32(r))) => {
+            Value::I32(l.wrapping_sub(r))
+        }
+        (instruction::Instruction::I32Mul, Some(Value::I32(l)), Some(Value::I32(r))) => {
+            Value::I32(l.wrapping_mul(r))
+        }
+        (instruction::Instruction::I64Add, Some(Value::I64(l)), Some(Value::I64(r))) => {
+            Value::I64(l.wrapping_add(r))
+        }
+        (instruction::Instruction::I64Sub, Some(Value::I64(l)), Some(Value::I64(r))) => {
+            Value::I64(l.wrapping_sub(r))
+        }
+        (instruction::Instruction::I64Mul, Some(Value::I64(l)), Some(Value::I64(r))) => {
+            Value::I64(l.wrapping_mul(r))
+        }
+        _ => {
+            let (instruction_name, operand_type) = match inst {
+                instruction::Instruction::I32Add => ("i32.add", ValueType::I32),
+                instruction::Instruction::I32Sub => ("i32.sub", ValueType::I32),
+                instruction::Instruction::I32Mul => ("i32.mul", ValueType::I32),
+                instruction::Instruction::I64Add => ("i64.add", ValueType::I64),
+                instruction::Instruction::I64Sub => ("i64.sub", ValueType::I64),
+                _ => ("i64.mul", ValueType::I64),
+            };
+
+            return Err(make_operand_data_types_mismatch_engine_error(
+                instruction_name,
+                vec![operand_type.clone(), operand_type],
+                left.iter().chain(right.iter()).collect::<Vec<&Value>>(),
+            ));
+        }
+    };
+
+    Ok(value)
+}
+
 fn convert_from_frame_type(
     block_type: &BlockType,
 ) -> (
@@ -785,6 +861,18 @@ fn convert_from_frame_type(
         BlockType::ResultF64 => {
             (1, 4) // class 1
         }
+        BlockType::ResultFuncRef => {
+            (1, 5) // class 1
+        }
+        BlockType::ResultExternRef => {
+            (1, 6) // class 1
+        }
+        BlockType::ResultV128 => {
+            (1, 7) // class 1
+        }
+        BlockType::ResultExnRef => {
+            (1, 8) // class 1
+        }
         BlockType::TypeIndex(value) => {
             (0, *value as usize) // class 0
         }
@@ -800,6 +888,10 @@ fn convert_to_frame_type(frame_type_class: usize, frame_type_value: usize) -> Bl
             2 => BlockType::ResultI64,
             3 => BlockType::ResultF32,
             4 => BlockType::ResultF64,
+            5 => BlockType::ResultFuncRef,
+            6 => BlockType::ResultExternRef,
+            7 => BlockType::ResultV128,
+            8 => BlockType::ResultExnRef,
             _ => unreachable!("no this frame type value"),
         },
         // class 0
@@ -0,0 +1,45 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 异常
+//!
+//! 异常处理提案添加了标签（tag）以及异常引用类型 `exnref`。
+//!
+//! - 标签用于区分异常的种类，标签的类型（即参数列表）决定了异常携带的数值的类型；
+//! - 异常由 `throw` 指令创建，或者由本地函数抛出，异常对象保存在 VM 的异常列表里，
+//!   `exnref` 的值是异常对象在该列表里的索引。
+//!
+//! 注意被异常引用所引用过的异常对象不会被释放，直到 VM 实例被销毁，
+//! 详细见 `ins_exception` 模块。
+
+use anvm_ast::types::{Value, ValueType};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct VMTag {
+    /// 异常携带的数值的类型
+    pub params: Vec<ValueType>,
+}
+
+impl VMTag {
+    pub fn new(params: Vec<ValueType>) -> Self {
+        Self { params }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct VMException {
+    /// 标签在 VM 标签实例列表里的索引
+    pub tag_index: usize,
+
+    /// 异常携带的数值
+    pub values: Vec<Value>,
+}
+
+impl VMException {
+    pub fn new(tag_index: usize, values: Vec<Value>) -> Self {
+        Self { tag_index, values }
+    }
+}
@@ -6,6 +6,7 @@
 
 use anvm_ast::{ast::GlobalType, types::Value};
 
+#[derive(Clone)]
 pub struct VMGlobalVariable {
     /// GlobalType 记录变量的 `数据类型` 以及 `可变性`
     global_type: GlobalType,
@@ -4,43 +4,162 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+use std::{
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Arc, Condvar, Mutex, RwLock,
+    },
+    time::{Duration, Instant},
+};
+
 use anvm_ast::ast::{Limit, MemoryType};
 
 use crate::error::{EngineError, Overflow};
+#[cfg(target_os = "linux")]
+use crate::vm_memory_mapped::{MappedBytes, MappedImage};
 
 /// 内存的容量单位是 `页`（`page`）
 /// 一页内存为 65536 个字节
-const PAGE_SIZE: u32 = 65536;
+const PAGE_SIZE: u64 = 65536;
+
+/// WebAssembly 约定 32 位内存块最大只能有 65536 个页面
+const MAX_PAGES: u64 = 65536;
 
-/// WebAssembly 约定内存块最大只能有 65536 个页面
-const MAX_PAGES: u32 = 65536;
+/// 64 位内存块（由 memory64 提案引入）最大可以有 2^48 个页面，即 2^64 个字节
+///
+/// 实际能分配的容量受限于宿主的内存。
+const MAX_PAGES_64: u64 = 1 << 48;
 
 /// 当前使用偷懒的方法 -- 数组来实现内存块，让 Rust 底层库
 /// 自动管理内存块的分配和容量。
+///
+/// 在 Linux 系统里，普通的 32 位内存块使用 mmap 保留地址空间，增长时无需复制数据
+/// （见 `vm_memory_mapped` 模块），无法保留地址空间时仍然使用数组。
+/// 跟数组一样，读写内存之前由调用者检查边界。
+///
+/// 共享内存块（由线程提案引入）的数据可以被多个线程的 VM 实例同时访问，
+/// 每个 VM 实例各自持有一个 `VMMemory`，它们指向同一份数据，见 `VMMemory::share`。
 pub struct VMMemory {
     memory_type: MemoryType,
-    data: Vec<u8>,
+    data: MemoryData,
+}
+
+/// 内存块的镜像，即内存块在某个时刻的容量以及内容
+///
+/// 用于快速地创建以及重置内存块（见 `instance_pool` 模块）。在 Linux 系统里，
+/// 普通的 32 位内存块的镜像保存在内存文件里，从镜像创建的内存块以写时复制的方式
+/// 映射镜像，重置时无需复制数据。
+pub struct MemoryImage {
+    memory_type: MemoryType,
+    bytes: Vec<u8>,
+
+    #[cfg(target_os = "linux")]
+    option_mapped_image: Option<MappedImage>,
+}
+
+/// 内存块的数据
+enum MemoryData {
+    /// 普通内存块的数据，只属于一个 VM 实例
+    Local(Vec<u8>),
+
+    /// 基于 mmap 的普通内存块的数据
+    #[cfg(target_os = "linux")]
+    Mapped(MappedBytes),
+
+    /// 共享内存块的数据
+    Shared(Arc<SharedMemoryData>),
+}
+
+/// 共享内存块的数据
+///
+/// - 字节使用 `AtomicU8` 储存，普通的（非原子）内存访问逐个字节以 `Relaxed` 顺序读写，
+///   所以多个线程同时访问同一个地址时不会产生 Rust 层面的数据竞争，不过跟 WebAssembly
+///   的规范一样，并发的非原子访问有可能读到 “撕裂” 的数值；
+/// - 所有原子指令都需要先获得互斥锁 `waiter_list`，所以原子指令之间是顺序一致的，
+///   `memory.atomic.wait` 的 “比较然后进入等待” 也因此不会错过其他线程的唤醒；
+/// - 内存块增长时需要获得 `bytes` 的写锁，以免其他线程访问期间数据被移动。
+struct SharedMemoryData {
+    bytes: RwLock<Vec<AtomicU8>>,
+    waiter_list: Mutex<WaiterList>,
+    condvar: Condvar,
+}
+
+/// 正在等待（`memory.atomic.wait`）的线程列表
+#[derive(Default)]
+struct WaiterList {
+    next_waiter_id: u64,
+
+    /// 按进入等待的先后排列，`memory.atomic.notify` 按照这个顺序唤醒线程
+    waiters: Vec<Waiter>,
+}
+
+struct Waiter {
+    id: u64,
+    address: usize,
+    notified: bool,
+}
+
+/// `memory.atomic.wait` 的结果，数值即指令的返回值
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum WaitResult {
+    /// 被 `memory.atomic.notify` 唤醒
+    Ok = 0,
+
+    /// 内存的数值跟期望值不相等，没有进入等待
+    NotEqual = 1,
+
+    /// 等待超时
+    TimedOut = 2,
 }
 
 impl VMMemory {
     pub fn new(memory_type: MemoryType) -> Self {
-        let min_page = memory_type.limit.get_min();
-        let bytes_count = min_page * PAGE_SIZE;
+        #[cfg(target_os = "linux")]
+        if !memory_type.shared && !memory_type.memory64 {
+            let bytes_count = (memory_type.limit.get_min() * PAGE_SIZE) as usize;
+            if let Some(mapped_bytes) = MappedBytes::new(bytes_count) {
+                return VMMemory {
+                    memory_type,
+                    data: MemoryData::Mapped(mapped_bytes),
+                };
+            }
+        }
 
-        VMMemory {
-            memory_type: memory_type,
+        VMMemory::new_portable(memory_type)
+    }
 
-            // 预先分配好空槽，因为访问者会随机访问指定的地址，所以不能仅仅
-            // 分配 Vec 的容量，而应该分配空槽。
-            // 空槽的初始值都是 0u8
-            data: vec![0; bytes_count as usize],
-        }
+    /// 创建使用数组储存数据的内存块
+    ///
+    /// 不使用 mmap 以及信号处理函数，所有平台的行为都一样。
+    pub fn new_portable(memory_type: MemoryType) -> Self {
+        let min_page = memory_type.limit.get_min();
+        let bytes_count = (min_page * PAGE_SIZE) as usize;
+
+        // 预先分配好空槽，因为访问者会随机访问指定的地址，所以不能仅仅
+        // 分配 Vec 的容量，而应该分配空槽。
+        // 空槽的初始值都是 0u8
+        let data = if memory_type.shared {
+            let mut bytes = Vec::with_capacity(bytes_count);
+            bytes.resize_with(bytes_count, || AtomicU8::new(0));
+
+            MemoryData::Shared(Arc::new(SharedMemoryData {
+                bytes: RwLock::new(bytes),
+                waiter_list: Mutex::new(WaiterList::default()),
+                condvar: Condvar::new(),
+            }))
+        } else {
+            MemoryData::Local(vec![0; bytes_count])
+        };
+
+        VMMemory { memory_type, data }
     }
 
     /// 创建指定页面数（且不限最大值的）内存块
     pub fn new_by_min_page(min_page: u32) -> Self {
         let memory_type = MemoryType {
-            limit: Limit::AtLeast(min_page),
+            limit: Limit::AtLeast(min_page as u64),
+            shared: false,
+            memory64: false,
         };
 
         VMMemory::new(memory_type)
@@ -49,87 +168,268 @@ impl VMMemory {
     /// min_page 和 max_page 的值都是 `包括的`（`included`）
     pub fn new_by_page_range(min_page: u32, max_page: u32) -> Self {
         let memory_type = MemoryType {
-            limit: Limit::Range(min_page, max_page),
+            limit: Limit::Range(min_page as u64, max_page as u64),
+            shared: false,
+            memory64: false,
         };
 
         VMMemory::new(memory_type)
     }
 
-    pub fn get_page_count(&self) -> u32 {
+    /// 创建内存块当前的镜像
+    pub fn create_image(&self) -> MemoryImage {
+        let bytes = self.read_bytes(0, self.get_size_in_bytes());
+
+        MemoryImage {
+            memory_type: self.memory_type.clone(),
+
+            #[cfg(target_os = "linux")]
+            option_mapped_image: if self.memory_type.shared || self.memory_type.memory64 {
+                None
+            } else {
+                MappedImage::new(&bytes)
+            },
+
+            bytes,
+        }
+    }
+
+    /// 从镜像创建内存块，内存块的容量以及内容跟镜像的相同
+    pub fn new_from_image(image: &MemoryImage) -> Self {
+        #[cfg(target_os = "linux")]
+        if let Some(mapped_image) = &image.option_mapped_image {
+            if let Some(mapped_bytes) = MappedBytes::new_from_image(image.bytes.len(), mapped_image)
+            {
+                return VMMemory {
+                    memory_type: image.memory_type.clone(),
+                    data: MemoryData::Mapped(mapped_bytes),
+                };
+            }
+        }
+
+        let mut memory_block = VMMemory::new(image.memory_type.clone());
+        memory_block.reset(image);
+        memory_block
+    }
+
+    /// 把内存块的容量以及内容重置为镜像的容量以及内容
+    ///
+    /// 对于从该镜像创建的（基于 mmap 的）内存块，只需丢弃写入过的页面，
+    /// 对于其他内存块，则需复制镜像的内容。
+    pub fn reset(&mut self, image: &MemoryImage) {
+        let length = image.bytes.len();
+
+        match &mut self.data {
+            MemoryData::Local(bytes) => {
+                bytes.resize(length, 0);
+                bytes.copy_from_slice(&image.bytes);
+            }
+            #[cfg(target_os = "linux")]
+            MemoryData::Mapped(mapped_bytes) => {
+                let image_id = mapped_bytes.get_image_id();
+                let is_image_mapped = image
+                    .option_mapped_image
+                    .as_ref()
+                    .is_some_and(|mapped_image| mapped_image.get_id() == image_id);
+
+                if mapped_bytes.reset(length) {
+                    if !is_image_mapped {
+                        mapped_bytes.as_mut_slice().copy_from_slice(&image.bytes);
+                    }
+                } else {
+                    // 无法提交页面时改用数组
+                    self.data = MemoryData::Local(image.bytes.clone());
+                }
+            }
+            MemoryData::Shared(shared_data) => {
+                let mut bytes = shared_data.bytes.write().unwrap();
+                bytes.truncate(length);
+                bytes.resize_with(length, || AtomicU8::new(0));
+                for (byte, value) in bytes.iter().zip(&image.bytes) {
+                    byte.store(*value, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
+    /// 是否共享内存块
+    pub fn is_shared(&self) -> bool {
+        matches!(self.data, MemoryData::Shared(_))
+    }
+
+    /// 创建一个指向同一份数据的内存块，用于让另一个线程的 VM 实例访问当前内存块
+    ///
+    /// 只有共享内存块才能被共享，对于普通内存块则返回 None。
+    pub fn share(&self) -> Option<VMMemory> {
+        match &self.data {
+            MemoryData::Shared(shared_data) => Some(VMMemory {
+                memory_type: self.memory_type.clone(),
+                data: MemoryData::Shared(Arc::clone(shared_data)),
+            }),
+            _ => None,
+        }
+    }
+
+    /// 是否 64 位内存块
+    pub fn is_memory64(&self) -> bool {
+        self.memory_type.memory64
+    }
+
+    /// 普通内存块的数据，供 JIT 编译的机器码直接读写，对于共享内存块则返回 None
+    #[cfg(feature = "jit")]
+    pub(crate) fn get_local_bytes_mut(&mut self) -> Option<&mut [u8]> {
+        match &mut self.data {
+            MemoryData::Local(bytes) => Some(bytes),
+            #[cfg(target_os = "linux")]
+            MemoryData::Mapped(mapped_bytes) => Some(mapped_bytes.as_mut_slice()),
+            MemoryData::Shared(_) => None,
+        }
+    }
+
+    pub fn get_page_count(&self) -> u64 {
         // 这里暂时不考虑空间大小不 PAGE_SIZE 对齐的情况
-        self.data.len() as u32 / PAGE_SIZE
+        self.get_size_in_bytes() as u64 / PAGE_SIZE
     }
 
     /// 返回原先的页面数
-    pub fn increase_page(&mut self, increase_page_number: u32) -> Result<u32, EngineError> {
-        let old_page_count = self.get_page_count();
-        let new_page_count = old_page_count + increase_page_number;
-
-        // 如果 MemoryType 的 limit 成员不指定 max 值，则可以
-        // 增长到 WebAssembly 内存块最大允许的页面数 MAX_PAGES
-        if let Limit::Range(_, max_page) = self.memory_type.limit {
-            if new_page_count > max_page {
-                return Err(EngineError::Overflow(Overflow::MemoryPageExceed(
-                    new_page_count,
-                    max_page,
-                )));
+    pub fn increase_page(&mut self, increase_page_number: u64) -> Result<u64, EngineError> {
+        let memory_type = &self.memory_type;
+
+        match &mut self.data {
+            MemoryData::Local(bytes) => {
+                let old_page_count = bytes.len() as u64 / PAGE_SIZE;
+                let new_page_count =
+                    check_page_count(memory_type, old_page_count, increase_page_number)?;
+                let new_length = get_byte_length(new_page_count)?;
+
+                // 容量超出宿主的内存时返回错误而不是中止进程
+                bytes
+                    .try_reserve_exact(new_length - bytes.len())
+                    .map_err(|_| make_allocation_failed_error(new_page_count))?;
+
+                // 新增加的空槽的初始值都是 0u8
+                bytes.resize(new_length, 0u8);
+                Ok(old_page_count)
+            }
+            #[cfg(target_os = "linux")]
+            MemoryData::Mapped(mapped_bytes) => {
+                let old_page_count = mapped_bytes.len() as u64 / PAGE_SIZE;
+                let new_page_count =
+                    check_page_count(memory_type, old_page_count, increase_page_number)?;
+                let new_length = get_byte_length(new_page_count)?;
+
+                // 新提交的页面由操作系统填充 0
+                if mapped_bytes.grow(new_length) {
+                    Ok(old_page_count)
+                } else {
+                    Err(make_allocation_failed_error(new_page_count))
+                }
+            }
+            MemoryData::Shared(shared_data) => {
+                // 共享内存块需要在持有写锁期间检查以及增长，以免多个线程同时增长
+                let mut bytes = shared_data.bytes.write().unwrap();
+                let old_page_count = bytes.len() as u64 / PAGE_SIZE;
+                let new_page_count =
+                    check_page_count(memory_type, old_page_count, increase_page_number)?;
+                let new_length = get_byte_length(new_page_count)?;
+
+                let old_length = bytes.len();
+                bytes
+                    .try_reserve_exact(new_length - old_length)
+                    .map_err(|_| make_allocation_failed_error(new_page_count))?;
+
+                bytes.resize_with(new_length, || AtomicU8::new(0));
+                Ok(old_page_count)
             }
         }
+    }
 
-        if new_page_count > MAX_PAGES {
-            return Err(EngineError::Overflow(Overflow::MemoryPageExceed(
-                new_page_count,
-                MAX_PAGES,
-            )));
+    /// 内存块当前的容量，单位为字节
+    pub fn get_size_in_bytes(&self) -> usize {
+        match &self.data {
+            MemoryData::Local(bytes) => bytes.len(),
+            #[cfg(target_os = "linux")]
+            MemoryData::Mapped(mapped_bytes) => mapped_bytes.len(),
+            MemoryData::Shared(shared_data) => shared_data.bytes.read().unwrap().len(),
         }
-
-        // 新增加的空槽的初始值都是 0u8
-        self.data.resize((new_page_count * PAGE_SIZE) as usize, 0u8);
-        Ok(old_page_count)
     }
 
     pub fn get_memory_type(&self) -> &MemoryType {
         &self.memory_type
     }
 
-    pub fn read_bytes(&self, address: usize, length: usize) -> &[u8] {
-        &self.data[address..(address + length)]
+    pub fn read_bytes(&self, address: usize, length: usize) -> Vec<u8> {
+        let mut data = vec![0u8; length];
+        self.read_to(address, &mut data);
+        data
     }
 
     pub fn write_bytes(&mut self, address: usize, data: &[u8]) {
-        for index in 0..data.len() {
-            self.data[address + index] = data[index]
+        match &mut self.data {
+            MemoryData::Local(bytes) => {
+                bytes[address..(address + data.len())].copy_from_slice(data);
+            }
+            #[cfg(target_os = "linux")]
+            MemoryData::Mapped(mapped_bytes) => {
+                mapped_bytes.as_mut_slice()[address..(address + data.len())].copy_from_slice(data);
+            }
+            MemoryData::Shared(shared_data) => {
+                let bytes = shared_data.bytes.read().unwrap();
+                for (byte, value) in bytes[address..(address + data.len())].iter().zip(data) {
+                    byte.store(*value, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
+    /// 读取数据到指定的缓冲区，读取的长度即缓冲区的长度
+    fn read_to(&self, address: usize, buffer: &mut [u8]) {
+        match &self.data {
+            MemoryData::Local(bytes) => {
+                buffer.copy_from_slice(&bytes[address..(address + buffer.len())]);
+            }
+            #[cfg(target_os = "linux")]
+            MemoryData::Mapped(mapped_bytes) => {
+                buffer.copy_from_slice(&mapped_bytes.as_slice()[address..(address + buffer.len())]);
+            }
+            MemoryData::Shared(shared_data) => {
+                let bytes = shared_data.bytes.read().unwrap();
+                let length = buffer.len();
+                for (value, byte) in buffer.iter_mut().zip(&bytes[address..(address + length)]) {
+                    *value = byte.load(Ordering::Relaxed);
+                }
+            }
         }
     }
 
+    fn read_array<const N: usize>(&self, address: usize) -> [u8; N] {
+        let mut buffer = [0u8; N];
+        self.read_to(address, &mut buffer);
+        buffer
+    }
+
     pub fn read_i8(&self, address: usize) -> i8 {
-        let bytes = self.read_bytes(address, 1);
-        bytes[0] as i8
+        i8::from_le_bytes(self.read_array(address))
     }
 
     pub fn read_i16(&self, address: usize) -> i16 {
-        let bytes = self.read_bytes(address, 2);
-        i16::from_le_bytes(bytes.try_into().unwrap())
+        i16::from_le_bytes(self.read_array(address))
     }
 
     pub fn read_i32(&self, address: usize) -> i32 {
-        let bytes = self.read_bytes(address, 4);
-        i32::from_le_bytes(bytes.try_into().unwrap())
+        i32::from_le_bytes(self.read_array(address))
     }
 
     pub fn read_i64(&self, address: usize) -> i64 {
-        let bytes = self.read_bytes(address, 8);
-        i64::from_le_bytes(bytes.try_into().unwrap())
+        i64::from_le_bytes(self.read_array(address))
     }
 
     pub fn read_f32(&self, address: usize) -> f32 {
-        let bytes = self.read_bytes(address, 4);
-        f32::from_le_bytes(bytes.try_into().unwrap())
+        f32::from_le_bytes(self.read_array(address))
     }
 
     pub fn read_f64(&self, address: usize) -> f64 {
-        let bytes = self.read_bytes(address, 8);
-        f64::from_le_bytes(bytes.try_into().unwrap())
+        f64::from_le_bytes(self.read_array(address))
     }
 
     pub fn write_i8(&mut self, address: usize, value: i8) {
@@ -161,13 +461,212 @@ impl VMMemory {
         let data = f64::to_le_bytes(value);
         self.write_bytes(address, &data);
     }
+
+    /// 原子地读取 length 个字节（1、2、4 或者 8）的无符号整数
+    pub fn atomic_load(&self, address: usize, length: usize) -> u64 {
+        let shared_data = self.get_shared_data();
+        let _guard = shared_data
+            .as_ref()
+            .map(|data| data.waiter_list.lock().unwrap());
+        self.read_unsigned(address, length)
+    }
+
+    /// 原子地写入 length 个字节的无符号整数，数值超出长度的高位会被截断
+    pub fn atomic_store(&mut self, address: usize, length: usize, value: u64) {
+        let shared_data = self.get_shared_data();
+        let _guard = shared_data
+            .as_ref()
+            .map(|data| data.waiter_list.lock().unwrap());
+        self.write_bytes(address, &value.to_le_bytes()[..length]);
+    }
+
+    /// 原子地 “读取-修改-写入”（read-modify-write）length 个字节的无符号整数
+    ///
+    /// 参数 op 用于根据旧值计算新值，新值超出长度的高位会被截断。
+    /// 返回旧值。
+    pub fn atomic_rmw(
+        &mut self,
+        address: usize,
+        length: usize,
+        op: impl FnOnce(u64) -> u64,
+    ) -> u64 {
+        let shared_data = self.get_shared_data();
+        let _guard = shared_data
+            .as_ref()
+            .map(|data| data.waiter_list.lock().unwrap());
+        let old_value = self.read_unsigned(address, length);
+        self.write_bytes(address, &op(old_value).to_le_bytes()[..length]);
+        old_value
+    }
+
+    /// 让当前线程等待直到被 `atomic_notify` 唤醒或者超时
+    ///
+    /// 仅当指定地址的数值（length 个字节的无符号整数）等于 expected 时才进入等待，
+    /// timeout 为 None 时表示不会超时。
+    ///
+    /// 只有共享内存块才能等待，对于普通内存块则返回 None。
+    pub fn atomic_wait(
+        &self,
+        address: usize,
+        length: usize,
+        expected: u64,
+        timeout: Option<Duration>,
+    ) -> Option<WaitResult> {
+        let shared_data = match &self.data {
+            MemoryData::Shared(shared_data) => shared_data,
+            _ => return None,
+        };
+
+        let mut waiter_list = shared_data.waiter_list.lock().unwrap();
+        if self.read_unsigned(address, length) != expected {
+            return Some(WaitResult::NotEqual);
+        }
+
+        let id = waiter_list.next_waiter_id;
+        waiter_list.next_waiter_id += 1;
+        waiter_list.waiters.push(Waiter {
+            id,
+            address,
+            notified: false,
+        });
+
+        let deadline = timeout.map(|duration| Instant::now() + duration);
+
+        loop {
+            let position = waiter_list
+                .waiters
+                .iter()
+                .position(|waiter| waiter.id == id)
+                .unwrap();
+
+            if waiter_list.waiters[position].notified {
+                waiter_list.waiters.remove(position);
+                return Some(WaitResult::Ok);
+            }
+
+            waiter_list = match deadline {
+                None => shared_data.condvar.wait(waiter_list).unwrap(),
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        waiter_list.waiters.remove(position);
+                        return Some(WaitResult::TimedOut);
+                    }
+
+                    shared_data
+                        .condvar
+                        .wait_timeout(waiter_list, deadline - now)
+                        .unwrap()
+                        .0
+                }
+            };
+        }
+    }
+
+    /// 唤醒最多 count 个正在等待指定地址的线程，返回被唤醒的线程数量
+    ///
+    /// 普通内存块不会有线程在等待，所以总是返回 0。
+    pub fn atomic_notify(&self, address: usize, count: u32) -> u32 {
+        let shared_data = match &self.data {
+            MemoryData::Shared(shared_data) => shared_data,
+            _ => return 0,
+        };
+
+        let mut waiter_list = shared_data.waiter_list.lock().unwrap();
+        let mut notified_count: u32 = 0;
+
+        for waiter in waiter_list
+            .waiters
+            .iter_mut()
+            .filter(|waiter| waiter.address == address && !waiter.notified)
+        {
+            if notified_count == count {
+                break;
+            }
+
+            waiter.notified = true;
+            notified_count += 1;
+        }
+
+        if notified_count > 0 {
+            shared_data.condvar.notify_all();
+        }
+
+        notified_count
+    }
+
+    /// 获取共享内存块的数据，原子指令在访问内存之前需要先获得它的互斥锁
+    fn get_shared_data(&self) -> Option<Arc<SharedMemoryData>> {
+        match &self.data {
+            MemoryData::Shared(shared_data) => Some(Arc::clone(shared_data)),
+            _ => None,
+        }
+    }
+
+    /// 读取 length 个字节的无符号整数（小端格式）
+    fn read_unsigned(&self, address: usize, length: usize) -> u64 {
+        let mut buffer = [0u8; 8];
+        self.read_to(address, &mut buffer[..length]);
+        u64::from_le_bytes(buffer)
+    }
+}
+
+/// 检查内存块增长之后的页面数是否超出限制，返回新的页面数
+fn check_page_count(
+    memory_type: &MemoryType,
+    old_page_count: u64,
+    increase_page_number: u64,
+) -> Result<u64, EngineError> {
+    let new_page_count = old_page_count.saturating_add(increase_page_number);
+
+    // 如果 MemoryType 的 limit 成员不指定 max 值，则可以
+    // 增长到 WebAssembly 内存块最大允许的页面数 MAX_PAGES（或者 MAX_PAGES_64）
+    if let Limit::Range(_, max_page) = memory_type.limit {
+        if new_page_count > max_page {
+            return Err(EngineError::Overflow(Overflow::MemoryPageExceed(
+                new_page_count,
+                max_page,
+            )));
+        }
+    }
+
+    let max_pages = if memory_type.memory64 {
+        MAX_PAGES_64
+    } else {
+        MAX_PAGES
+    };
+
+    if new_page_count > max_pages {
+        return Err(EngineError::Overflow(Overflow::MemoryPageExceed(
+            new_page_count,
+            max_pages,
+        )));
+    }
+
+    Ok(new_page_count)
+}
+
+/// 计算指定页面数的容量（字节数），超出宿主的地址范围时返回错误
+fn get_byte_length(page_count: u64) -> Result<usize, EngineError> {
+    page_count
+        .checked_mul(PAGE_SIZE)
+        .and_then(|length| usize::try_from(length).ok())
+        .ok_or_else(|| make_allocation_failed_error(page_count))
+}
+
+fn make_allocation_failed_error(page_count: u64) -> EngineError {
+    EngineError::Overflow(Overflow::MemoryAllocationFailed(page_count))
 }
 
 #[cfg(test)]
 mod tests {
+    use std::{thread, time::Duration};
+
+    use anvm_ast::ast::{Limit, MemoryType};
+
     use crate::error::{EngineError, Overflow};
 
-    use super::VMMemory;
+    use super::{VMMemory, WaitResult};
 
     #[test]
     fn test_increase_page() {
@@ -197,6 +696,24 @@ mod tests {
             m2.increase_page(1),
             Err(EngineError::Overflow(Overflow::MemoryPageExceed(_, _)))
         ));
+
+        // 64 位内存块增长的页面数在限制之内，但超出宿主的地址范围或者内存
+        let mut m3 = VMMemory::new(MemoryType {
+            limit: Limit::AtLeast(1),
+            shared: false,
+            memory64: true,
+        });
+
+        assert!(matches!(
+            m3.increase_page(1 << 47),
+            Err(EngineError::Overflow(Overflow::MemoryAllocationFailed(_)))
+        ));
+        assert!(matches!(
+            m3.increase_page(1 << 30),
+            Err(EngineError::Overflow(Overflow::MemoryAllocationFailed(_)))
+        ));
+        assert_eq!(m3.get_page_count(), 1);
+        assert_eq!(m3.increase_page(1).unwrap(), 1);
     }
 
     #[test]
@@ -254,4 +771,81 @@ mod tests {
         assert_eq!(m0.read_f32(8 * 8), 3.142);
         assert_eq!(m0.read_f64(8 * 9), 2.718);
     }
+
+    #[test]
+    fn test_shared_memory() {
+        let mut m0 = VMMemory::new(MemoryType {
+            limit: Limit::Range(1, 2),
+            shared: true,
+            memory64: false,
+        });
+        assert!(m0.is_shared());
+        assert!(VMMemory::new_by_min_page(1).share().is_none());
+
+        // 共享内存块的写入以及增长对其他句柄可见
+        let mut m1 = m0.share().unwrap();
+        m0.write_i32(0, 0x1122_3344);
+        assert_eq!(m1.read_i32(0), 0x1122_3344);
+
+        assert_eq!(m1.increase_page(1).unwrap(), 1);
+        assert_eq!(m0.get_page_count(), 2);
+
+        // 原子指令
+        assert_eq!(m0.atomic_rmw(0, 1, |old| old + 0xff), 0x44);
+        assert_eq!(m1.atomic_load(0, 4), 0x1122_3343);
+        m1.atomic_store(4, 2, 0x1_abcd);
+        assert_eq!(m0.atomic_load(4, 8), 0xabcd);
+
+        // 等待以及唤醒
+        assert_eq!(m0.atomic_wait(0, 4, 0, None), Some(WaitResult::NotEqual));
+        assert_eq!(
+            m0.atomic_wait(0, 4, 0x1122_3343, Some(Duration::from_millis(1))),
+            Some(WaitResult::TimedOut)
+        );
+
+        let m2 = m0.share().unwrap();
+        let handle = thread::spawn(move || m2.atomic_wait(8, 4, 0, None));
+
+        // 等待另一个线程进入等待状态
+        while m0.atomic_notify(8, 1) == 0 {
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        assert_eq!(handle.join().unwrap(), Some(WaitResult::Ok));
+
+        // 普通内存块不能等待
+        let m3 = VMMemory::new_by_min_page(1);
+        assert_eq!(m3.atomic_wait(0, 4, 0, None), None);
+        assert_eq!(m3.atomic_notify(0, 1), 0);
+    }
+
+    #[test]
+    fn test_mapped_memory() {
+        let memory_type = MemoryType {
+            limit: Limit::Range(1, 3),
+            shared: false,
+            memory64: false,
+        };
+
+        for mut m0 in [
+            VMMemory::new(memory_type.clone()),
+            VMMemory::new_portable(memory_type.clone()),
+        ] {
+            m0.write_i32(0, 0x1122_3344);
+            assert_eq!(m0.read_i32(0), 0x1122_3344);
+
+            // 增长之后原有的数据保持不变，新增加的页面的初始值都是 0
+            assert_eq!(m0.increase_page(1).unwrap(), 1);
+            assert_eq!(m0.get_size_in_bytes(), 65536 * 2);
+            assert_eq!(m0.read_i32(0), 0x1122_3344);
+            assert_eq!(m0.read_bytes(65536 * 2 - 8, 8), vec![0; 8]);
+
+            m0.write_i64(65536 * 2 - 8, -1);
+            assert_eq!(m0.read_i64(65536 * 2 - 8), -1);
+
+            // 超出最大页面数
+            assert!(m0.increase_page(2).is_err());
+            assert_eq!(m0.get_page_count(), 2);
+        }
+    }
 }
@@ -0,0 +1,258 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 基于 mmap 的内存块数据（仅 Linux）
+//!
+//! 创建内存块时通过 mmap 一次性保留（不可访问的）地址空间，然后通过 mprotect
+//! 提交（即改为可读写）最初的页面，增长时只需提交新增的页面，不用复制数据，
+//! 新页面由操作系统在第一次访问时才分配并填充 0。
+//!
+//! 保留的地址空间为 `RESERVED_LENGTH`，即 32 位内存块的最大容量（4GiB），
+//! 使用 `MAP_NORESERVE` 保留，所以并不占用物理内存。
+//!
+//! 读写内存之前跟数组实现的内存块一样由调用者检查边界，未提交的页面不会被访问，
+//! 所以这里不需要安装信号处理函数，也不会改变其他代码的内存错误的处理方式。
+//!
+//! 系统调用的常量均按照 Linux 通用的 ABI（比如 x86-64 以及 AArch64）。
+//!
+//! ## 写时复制
+//!
+//! 内存块的初始内容（即镜像，见 `MappedImage`）可以保存在一个内存文件（memfd）里，
+//! 从镜像创建的内存块以 `MAP_PRIVATE` 方式映射该文件，写入时由操作系统复制页面，
+//! 重置时只需丢弃（`MADV_DONTNEED`）所有页面，之后再读取时页面的内容又是镜像的内容。
+//! 所以从镜像创建以及重置内存块的开销跟镜像的大小无关，而只跟写入过的页面数量有关。
+
+use std::{
+    ffi::{c_char, c_void},
+    fs::File,
+    io::Write,
+    os::fd::{AsRawFd, FromRawFd},
+    ptr,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// 32 位内存块的最大容量
+const MAX_MEMORY_LENGTH: usize = 1 << 32;
+
+/// 保留的地址空间，即 32 位内存块的最大容量
+const RESERVED_LENGTH: usize = MAX_MEMORY_LENGTH;
+
+const PROT_NONE: i32 = 0x0;
+const PROT_READ: i32 = 0x1;
+const PROT_WRITE: i32 = 0x2;
+const MAP_PRIVATE: i32 = 0x02;
+const MAP_ANONYMOUS: i32 = 0x20;
+const MAP_FIXED: i32 = 0x10;
+const MAP_NORESERVE: i32 = 0x4000;
+const MAP_FAILED: *mut c_void = !0 as *mut c_void;
+const MADV_DONTNEED: i32 = 4;
+const MFD_CLOEXEC: u32 = 0x1;
+
+extern "C" {
+    fn mmap(
+        addr: *mut c_void,
+        length: usize,
+        prot: i32,
+        flags: i32,
+        fd: i32,
+        offset: i64,
+    ) -> *mut c_void;
+    fn mprotect(addr: *mut c_void, length: usize, prot: i32) -> i32;
+    fn munmap(addr: *mut c_void, length: usize) -> i32;
+    fn madvise(addr: *mut c_void, length: usize, advice: i32) -> i32;
+    fn memfd_create(name: *const c_char, flags: u32) -> i32;
+}
+
+/// 下一个镜像的编号，镜像的编号从 1 开始
+static NEXT_IMAGE_ID: AtomicUsize = AtomicUsize::new(1);
+
+/// 保存在内存文件里的内存块镜像
+pub struct MappedImage {
+    id: usize,
+    file: File,
+    length: usize,
+}
+
+impl MappedImage {
+    /// 创建内存文件并写入镜像的内容，长度需要是系统页面大小的整数倍
+    ///
+    /// 无法创建内存文件时返回 None。
+    pub fn new(bytes: &[u8]) -> Option<Self> {
+        let fd = unsafe { memfd_create(c"anvm-memory-image".as_ptr(), MFD_CLOEXEC) };
+        if fd < 0 {
+            return None;
+        }
+
+        let mut file = unsafe { File::from_raw_fd(fd) };
+        file.write_all(bytes).ok()?;
+
+        Some(Self {
+            id: NEXT_IMAGE_ID.fetch_add(1, Ordering::Relaxed),
+            file,
+            length: bytes.len(),
+        })
+    }
+
+    pub fn get_id(&self) -> usize {
+        self.id
+    }
+}
+
+/// 基于 mmap 的内存块数据
+pub struct MappedBytes {
+    base: *mut u8,
+
+    /// 已提交的长度，即内存块当前的容量
+    length: usize,
+
+    /// 以写时复制方式映射的镜像的编号，不是从镜像创建的内存块的值为 0
+    image_id: usize,
+}
+
+// 内存块数据只属于一个 VM 实例，VM 实例可以被移到其他线程
+unsafe impl Send for MappedBytes {}
+
+impl MappedBytes {
+    /// 保留地址空间并提交指定长度的页面
+    ///
+    /// 无法保留地址空间（比如进程的虚拟内存受到限制）时返回 None。
+    pub fn new(length: usize) -> Option<Self> {
+        let mut mapped_bytes = Self::reserve()?;
+
+        if mapped_bytes.grow(length) {
+            Some(mapped_bytes)
+        } else {
+            None
+        }
+    }
+
+    /// 保留地址空间，以写时复制的方式映射镜像，然后提交其余的页面直到指定的长度
+    pub fn new_from_image(length: usize, image: &MappedImage) -> Option<Self> {
+        if image.length > length {
+            return None;
+        }
+
+        let mut mapped_bytes = Self::reserve()?;
+
+        if image.length > 0 {
+            let address = unsafe {
+                mmap(
+                    mapped_bytes.base as *mut c_void,
+                    image.length,
+                    PROT_READ | PROT_WRITE,
+                    MAP_PRIVATE | MAP_FIXED,
+                    image.file.as_raw_fd(),
+                    0,
+                )
+            };
+            if address == MAP_FAILED {
+                return None;
+            }
+
+            mapped_bytes.length = image.length;
+        }
+
+        mapped_bytes.image_id = image.id;
+
+        if mapped_bytes.grow(length) {
+            Some(mapped_bytes)
+        } else {
+            None
+        }
+    }
+
+    /// 保留地址空间，此时所有页面均未提交
+    fn reserve() -> Option<Self> {
+        let address = unsafe {
+            mmap(
+                ptr::null_mut(),
+                RESERVED_LENGTH,
+                PROT_NONE,
+                MAP_PRIVATE | MAP_ANONYMOUS | MAP_NORESERVE,
+                -1,
+                0,
+            )
+        };
+        if address == MAP_FAILED {
+            return None;
+        }
+
+        Some(Self {
+            base: address as *mut u8,
+            length: 0,
+            image_id: 0,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// 提交页面直到指定的长度，失败时返回 false
+    pub fn grow(&mut self, new_length: usize) -> bool {
+        if new_length > MAX_MEMORY_LENGTH {
+            return false;
+        }
+
+        if new_length > self.length {
+            let result = unsafe {
+                mprotect(
+                    self.base.add(self.length) as *mut c_void,
+                    new_length - self.length,
+                    PROT_READ | PROT_WRITE,
+                )
+            };
+            if result != 0 {
+                return false;
+            }
+
+            self.length = new_length;
+        }
+
+        true
+    }
+
+    /// 丢弃所有页面并改变到指定的长度，失败时返回 false
+    ///
+    /// 之后镜像部分的内容恢复为镜像的内容，其余部分的内容为 0。
+    pub fn reset(&mut self, length: usize) -> bool {
+        unsafe {
+            madvise(self.base as *mut c_void, self.length, MADV_DONTNEED);
+
+            if self.length > length {
+                mprotect(
+                    self.base.add(length) as *mut c_void,
+                    self.length - length,
+                    PROT_NONE,
+                );
+                self.length = length;
+            }
+        }
+
+        self.grow(length)
+    }
+
+    /// 以写时复制方式映射的镜像的编号
+    pub fn get_image_id(&self) -> usize {
+        self.image_id
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.base, self.length) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(self.base, self.length) }
+    }
+}
+
+impl Drop for MappedBytes {
+    fn drop(&mut self) {
+        unsafe {
+            munmap(self.base as *mut c_void, RESERVED_LENGTH);
+        }
+    }
+}
@@ -4,25 +4,41 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use anvm_ast::{ast::FunctionType, types::ValueType};
+use std::rc::Rc;
 
-use crate::object::{FunctionItem, Instruction};
+use anvm_ast::{
+    ast::FunctionType,
+    types::{Value, ValueType},
+};
 
+use crate::{
+    bytecode::Bytecode,
+    object::{FunctionItem, FusedItem, Instruction},
+    register::RegisterCode,
+};
+
+#[cfg(feature = "jit")]
+use crate::jit::JitCode;
+
+#[derive(Clone)]
 pub struct VMModule {
     /// 模块的名称
     pub name: String,
 
-    /// 当前模块的表在 VM 表实例里的索引
-    /// 目前 WebAssembly 只允许定义一张表
-    pub table_index: usize,
+    /// 当前模块的表在 VM 表实例列表里的索引
+    /// 指令里的表索引需要通过这个列表转换为表实例的索引
+    pub table_indexes: Vec<usize>,
 
-    /// 当前模块的内存块在 VM 内存块实例里的索引
-    /// 目前 WebAssembly 只允许定义一块内存
-    pub memory_index: usize,
+    /// 当前模块的内存块在 VM 内存块实例列表里的索引
+    /// 指令里的内存块索引需要通过这个列表转换为内存块实例的索引
+    pub memory_indexes: Vec<usize>,
 
     /// 当前模块的全局变量在 VM 全局变量实例列表里的索引
     pub global_variable_indexes: Vec<usize>,
 
+    /// 当前模块的标签在 VM 标签实例列表里的索引
+    pub tag_indexes: Vec<usize>,
+
     /// 复制一份 `类型列表`
     /// 调用函数时，需要这个函数类型表来确定实参和返回值的数量
     pub function_types: Vec<FunctionType>,
@@ -40,29 +56,132 @@ pub struct VMModule {
     pub function_items: Vec<FunctionItem>,
 
     /// 指令列表
-    pub instructions: Vec<Instruction>,
+    ///
+    /// 执行指令时需要同时借用 VM 和指令，所以使用 Rc 共享指令列表，
+    /// 以避免每次执行指令时都复制一次指令。
+    pub instructions: Rc<[Instruction]>,
+
+    /// 由指令列表编译而成的字节码，跟指令列表一一对应
+    pub bytecode: Bytecode,
+
+    /// 超级指令项列表，按地址从小到大排列
+    pub fused_items: Vec<FusedItem>,
+
+    /// 元素段的引用值列表，供 `table.init` 指令使用
+    ///
+    /// 被 `elem.drop` 指令丢弃的元素段（包括主动模式以及声明模式的元素段）的
+    /// 内容为空列表。
+    pub element_segments: Vec<Vec<Value>>,
+
+    /// 本模块的函数引用值的起始值
+    ///
+    /// 函数引用的值是函数在 VM 实例里的全局索引，即这个起始值加上函数在本模块里的索引
+    /// （包括导入函数）。各个模块的函数按模块的顺序排列，所以函数引用可以在模块之间传递。
+    pub function_reference_base: u32,
+
+    /// 数据段的内容，供 `memory.init` 指令使用
+    ///
+    /// 被 `data.drop` 指令丢弃的数据段的内容为空列表。目前模块只有主动模式（active）
+    /// 的数据段，它们在实例化之后相当于被丢弃，所以内容都为空。
+    pub data_segments: Vec<Vec<u8>>,
+
+    /// 由内部函数降低而成的寄存器中间表示
+    ///
+    /// 仅当 VM 使用寄存器解释器时才会生成，执行时需要同时借用 VM，所以使用 Rc 共享。
+    pub register_code: Option<Rc<RegisterCode>>,
+
+    /// 由寄存器中间表示编译而成的机器码
+    ///
+    /// 仅当 VM 使用 JIT 编译器并且编译成功时才存在，否则由寄存器解释器执行。
+    #[cfg(feature = "jit")]
+    pub jit_code: Option<Rc<JitCode>>,
 }
 
 impl VMModule {
     pub fn new(
         name: String,
-        table_index: usize,
-        memory_index: usize,
+        table_indexes: Vec<usize>,
+        memory_indexes: Vec<usize>,
         global_variable_indexes: Vec<usize>,
+        tag_indexes: Vec<usize>,
         function_types: Vec<FunctionType>,
         internal_function_local_variable_types_list: Vec<Vec<ValueType>>,
         function_items: Vec<FunctionItem>,
         instructions: Vec<Instruction>,
+        fused_items: Vec<FusedItem>,
     ) -> Self {
+        // 内部函数位于函数列表的末尾，跟局部变量数据类型列表一一对应
+        let internal_function_offset =
+            function_items.len() - internal_function_local_variable_types_list.len();
+        let function_local_variable_types_list = function_items[internal_function_offset..]
+            .iter()
+            .zip(&internal_function_local_variable_types_list)
+            .filter_map(|(function_item, local_variable_types)| {
+                if let FunctionItem::Normal {
+                    type_index,
+                    start_address,
+                    ..
+                } = function_item
+                {
+                    let mut types = function_types[*type_index].params.clone();
+                    types.extend(local_variable_types.iter().cloned());
+                    Some((*start_address, types))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<(usize, Vec<ValueType>)>>();
+
+        let mut bytecode = Bytecode::compile(
+            &instructions,
+            &memory_indexes,
+            &global_variable_indexes,
+            &function_local_variable_types_list,
+        );
+        bytecode.compile_fused_items(&fused_items, &function_local_variable_types_list);
+
         Self {
             name,
-            table_index,
-            memory_index,
+            table_indexes,
+            memory_indexes,
             global_variable_indexes,
+            tag_indexes,
             function_types,
             internal_function_local_variable_types_list,
             function_items,
-            instructions,
+            instructions: instructions.into(),
+            bytecode,
+            fused_items,
+            element_segments: vec![],
+            function_reference_base: 0,
+            data_segments: vec![],
+            register_code: None,
+            #[cfg(feature = "jit")]
+            jit_code: None,
+        }
+    }
+
+    /// 获取指定地址的原指令
+    ///
+    /// 字节码跟指令列表一一对应，所以调试器可以通过 VM 的当前地址获取正在执行的原指令。
+    pub fn get_instruction(&self, address: usize) -> &Instruction {
+        &self.instructions[address]
+    }
+
+    /// 获取包含指定地址的超级指令项
+    ///
+    /// 调试器可以通过超级指令项获知某个地址的原指令是否被融合，以及被融合的原指令的
+    /// 地址范围。
+    pub fn get_fused_item(&self, address: usize) -> Option<&FusedItem> {
+        let index = self
+            .fused_items
+            .partition_point(|fused_item| fused_item.address <= address);
+        match index {
+            0 => None,
+            _ => {
+                let fused_item = &self.fused_items[index - 1];
+                (address < fused_item.address + fused_item.length).then_some(fused_item)
+            }
         }
     }
 }
@@ -63,49 +63,31 @@
 //! - 一般体系结构当中栈底一般是内存的最高位，然后栈往下（往低处）增长，
 //!   但虚拟机里使用数组来实现栈，所以这里的栈底的索引为 0，栈顶的索引比栈底的大。
 //!
-//! ## 控制帧
+//! ## 结构块
 //!
 //! 栈帧因函数调用而产生，所以栈帧信息段记录的自然是函数调用的信息，所以排除局部变量段
 //! 以及运算操作数段之后，栈也可以称为 `函数调用栈`（`call stack`），栈帧也可以称为
-//! `函数调用帧`（`call frame`），但因为 WebAssembly 的流程控制结构块的工作方式
-//! 跟函数调用非常相近，所以当前的 VM 也会因为进入一个流程控制块而创建一个新的栈帧，当离开
-//! 控制块时，也会弹出一个栈帧，因此当前的栈帧其实也包含了 `流程控制结构块的帧`（`flow control frame`），
-//! 下面简称为 `控制帧`（`control frame`）。
+//! `函数调用帧`（`call frame`）。
 //!
-//! `控制帧` 跟 `调用帧` 几乎是一样的，除了：
-//!
-//! 1. 控制帧没有局部变量段，控制帧共享最近一次函数调用所创建的帧的局部变量；
-//! 2. 返回地址是结构块的最后一条指令（即 `end` 指令）所处的位置，而并非调用指令的下一条指令的位置。
-//!
-//! 下面是进入一个流程控制结构块所创建的 `栈帧` 示意图：
+//! WebAssembly 的流程控制结构块（block/loop/if/try_table）不会创建栈帧，
+//! 结构块内的操作数跟函数的操作数一起位于调用帧的运算操作数段。
+//! 解码器预先计算好每个结构块的栈高度（相对于运算操作数段的开始位置），
+//! 跳出结构块时只需保留需要带出结构块的操作数，然后把操作数栈截断到目标结构块的栈高度：
 //!
 //! ```diagram
 //!
-//!                              | ------------------ | <-- sp (stack pointer)
-//!                              |                    |
-//!                              | 运算操作数段         |
-//!                              |                    |
-//!                              | .................. |
-//!                              |                    |
-//!                              | 旧帧的 fp, bp 等信息 | <-- 栈帧信息段
-//!                              |                    |
-//! | ------- 栈顶。------- |     | .................. | <-- 新 bp
-//! | 第 N 个参数值。        | --> | 第 N 个参数值。      |
-//! | 第 0 个参数值。        | --> | 第 0 个参数值。      |
-//! | ....... 原栈顶 ...... |     | ---- index 0 ----- | <-- 新 fp
-//! |                      |
-//! | 运算操作数段           |
-//! | -------------------- |
-//! |                      |
-//! | 栈帧信息段。           |
-//! |                      |
-//! | -------------------- | <-- 旧 bp
-//! |                      |
-//! | 局部变量段。           |
-//! | .................... |
-//! | 来自上一个栈帧的实参。。 |
-//! |                      |
-//! | ------- 栈底。 ------ | <-- 旧 fp, 新旧 lp 均在此
+//! | ------- 栈顶。------- |
+//! | 第 N 个返回值。        | ---
+//! | 第 0 个返回值。        | -- |
+//! | 结构块内多余的操作数。   |  | |
+//! | .................... |  | |     | ------- 栈顶。------- |
+//! |                      |  | ---> | 第 N 个返回值。        |
+//! |                      |  -----> | 第 0 个返回值。        |
+//! | 目标结构块的栈高度 ---- | ------> | ....... 原栈顶 ...... |
+//! | 运算操作数段           |         | 运算操作数段           |
+//! | -------------------- | <-- bp  | -------------------- |
+//!
+//! ```
 //!
 //! ## 信息段的数据
 //!
@@ -120,13 +102,22 @@
 //! 6. frame_type_value
 //! 7. return_address
 //!
-//! 其中第 5 和第 6 项合并起来表示上一帧的函数或者结构块的类型（即参数和返回值的情况）
+//! 其中第 5 和第 6 项合并起来表示上一帧的函数的类型（即参数和返回值的情况）
 
-use anvm_ast::types::Value;
+use anvm_ast::types::{Value, ValueType};
 
 /// 信息段的项目（数值）的数量
 pub const INFO_SEGMENT_ITEM_COUNT: usize = 8;
 
+/// 栈的最大槽位数量
+///
+/// 调用函数时如果栈的大小超过此值，则抛出 `Overflow::StackOverflow` 异常，
+/// 以防止无限递归耗尽宿主的内存。
+pub const MAX_STACK_SIZE: usize = 1024 * 1024;
+
+/// 空引用在栈槽里的值
+const NULL_REF_SLOT: u64 = u64::MAX;
+
 /// # VMStack 同时肩负作为运算栈（操作数栈）和记录栈帧信息的任务
 ///
 /// 也就是说，除了用于记录栈帧信息，同时供给运算指令（诸如 i32.add 等）
@@ -135,13 +126,52 @@ pub const INFO_SEGMENT_ITEM_COUNT: usize = 8;
 ///
 /// 当前使用偷懒的方法 -- 数组来实现操作数栈，让 Rust 底层库
 /// 自动管理栈的分配和容量。
+///
+/// ## 栈槽
+///
+/// 模块经过验证之后，每个指令的操作数的数据类型都是静态确定的，所以栈使用无类型的
+/// 64 位栈槽（`u64`）来存放数值，运算指令通过 `pop_i32`、`push_i32` 等带类型的方法
+/// 直接读写栈槽，而无需匹配和重新包装 `Value`。数值在栈槽里的编码方式：
+///
+/// - i32：零扩展为 u64；
+/// - i64：原样存放；
+/// - f32/f64：存放其二进制位；
+/// - funcref/externref/exnref：存放引用的索引，空引用为 `u64::MAX`；
+/// - v128：栈槽存放低 64 位，高 64 位存放在跟栈槽平行的 `v128_high_slots` 里，
+///   所以一个 v128 数值仍然只占用一个栈槽。
+///
+/// 信息段里的 fp、bp、返回地址等数据也直接存放在栈槽里。
+///
+/// 另外栈还为每个栈槽记录一份数据类型，称为栈的 `类型视图`。运算指令只写入而不读取
+/// 类型视图，它用于调试器查看栈的内容，以及函数调用、返回值等以 `Value` 为单位
+/// 读写数值的方法。
 pub struct VMStack {
-    slots: Vec<Value>,
+    /// 无类型的 64 位栈槽
+    slots: Vec<u64>,
+
+    /// 栈的类型视图，即每个栈槽的数据类型
+    types: Vec<ValueType>,
+
+    /// v128 数值的高 64 位
+    ///
+    /// 只有存放 v128 数值的栈槽才会用到，其长度按需增长，
+    /// 其余位置的内容是无意义的。
+    v128_high_slots: Vec<u64>,
 }
 
 impl VMStack {
     pub fn new() -> Self {
-        VMStack { slots: vec![] }
+        VMStack {
+            slots: vec![],
+            types: vec![],
+            v128_high_slots: vec![],
+        }
+    }
+
+    /// 清空栈，保留已分配的空间
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.types.clear();
     }
 
     /// 获取栈的总大小
@@ -151,58 +181,362 @@ impl VMStack {
         self.slots.len()
     }
 
+    // 带类型的栈槽读写方法
+
+    #[inline]
+    pub fn push_i32(&mut self, value: i32) {
+        self.slots.push(value as u32 as u64);
+        self.types.push(ValueType::I32);
+    }
+
+    #[inline]
+    pub fn pop_i32(&mut self) -> i32 {
+        self.pop_slot() as u32 as i32
+    }
+
+    #[inline]
+    pub fn push_i64(&mut self, value: i64) {
+        self.slots.push(value as u64);
+        self.types.push(ValueType::I64);
+    }
+
+    #[inline]
+    pub fn pop_i64(&mut self) -> i64 {
+        self.pop_slot() as i64
+    }
+
+    #[inline]
+    pub fn push_f32(&mut self, value: f32) {
+        self.slots.push(value.to_bits() as u64);
+        self.types.push(ValueType::F32);
+    }
+
+    #[inline]
+    pub fn pop_f32(&mut self) -> f32 {
+        f32::from_bits(self.pop_slot() as u32)
+    }
+
+    #[inline]
+    pub fn push_f64(&mut self, value: f64) {
+        self.slots.push(value.to_bits());
+        self.types.push(ValueType::F64);
+    }
+
+    #[inline]
+    pub fn pop_f64(&mut self) -> f64 {
+        f64::from_bits(self.pop_slot())
+    }
+
+    pub fn push_v128(&mut self, value: u128) {
+        let index = self.slots.len();
+        self.slots.push(value as u64);
+        self.types.push(ValueType::V128);
+        self.set_v128_high_slot(index, (value >> 64) as u64);
+    }
+
+    pub fn pop_v128(&mut self) -> u128 {
+        let low = self.pop_slot();
+        let high = self.v128_high_slots[self.slots.len()];
+        ((high as u128) << 64) | (low as u128)
+    }
+
+    // 原地读写栈顶操作数的方法
+    //
+    // 一元运算以及二元运算的结果替换掉栈顶的操作数，而无需先弹出再压入。
+    // `replace_top_*` 方法只写入栈槽，所以栈顶操作数的数据类型必须跟新数值的一致。
+
+    #[inline]
+    fn top_slot(&self) -> u64 {
+        *self.slots.last().expect("operand stack is empty")
+    }
+
+    #[inline]
+    fn top_slot_mut(&mut self) -> &mut u64 {
+        self.slots.last_mut().expect("operand stack is empty")
+    }
+
+    #[inline]
+    pub fn peek_i32(&self) -> i32 {
+        self.top_slot() as u32 as i32
+    }
+
+    #[inline]
+    pub fn replace_top_i32(&mut self, value: i32) {
+        *self.top_slot_mut() = value as u32 as u64;
+    }
+
+    #[inline]
+    pub fn peek_i64(&self) -> i64 {
+        self.top_slot() as i64
+    }
+
+    #[inline]
+    pub fn replace_top_i64(&mut self, value: i64) {
+        *self.top_slot_mut() = value as u64;
+    }
+
+    #[inline]
+    pub fn peek_f32(&self) -> f32 {
+        f32::from_bits(self.top_slot() as u32)
+    }
+
+    #[inline]
+    pub fn replace_top_f32(&mut self, value: f32) {
+        *self.top_slot_mut() = value.to_bits() as u64;
+    }
+
+    #[inline]
+    pub fn peek_f64(&self) -> f64 {
+        f64::from_bits(self.top_slot())
+    }
+
+    #[inline]
+    pub fn replace_top_f64(&mut self, value: f64) {
+        *self.top_slot_mut() = value.to_bits();
+    }
+
+    /// 将栈顶的操作数替换为 bool 值（即 i32 0 或者 1）
+    ///
+    /// 比较运算的操作数不一定是 i32，所以此方法会同时更新类型视图。
+    #[inline]
+    pub fn replace_top_bool(&mut self, value: bool) {
+        *self.top_slot_mut() = value as u64;
+        if let Some(value_type) = self.types.last_mut() {
+            *value_type = ValueType::I32;
+        }
+    }
+
+    /// 压入原始的栈槽数据，用于写信息段
+    ///
+    /// 在类型视图里显示为 i64。
+    pub fn push_slot(&mut self, slot: u64) {
+        self.slots.push(slot);
+        self.types.push(ValueType::I64);
+    }
+
+    /// 弹出原始的栈槽数据
+    #[inline]
+    pub fn pop_slot(&mut self) -> u64 {
+        self.types.pop();
+        self.slots.pop().expect("operand stack is empty")
+    }
+
+    /// 按索引读取原始的栈槽数据，用于读信息段
+    #[inline]
+    pub fn get_slot(&self, index: usize) -> u64 {
+        self.slots[index]
+    }
+
+    /// 栈槽以及类型视图的开始地址，供 JIT 编译的机器码直接读写栈槽
+    ///
+    /// 改变栈的大小会使地址失效。
+    #[cfg(feature = "jit")]
+    pub(crate) fn as_mut_ptrs(&mut self) -> (*mut u64, *mut ValueType) {
+        (self.slots.as_mut_ptr(), self.types.as_mut_ptr())
+    }
+
+    /// 读取指定索引的栈槽（即局部变量），并压入栈
+    ///
+    /// 用于 `local.get 指令`，直接复制栈槽，而无需知道数据类型。
+    ///
+    /// 注意，为了让局部变量指令尽可能快，此方法及 `pop_to_slot`、`copy_top_to_slot`
+    /// 均不复制 v128 数值的高 64 位，所以只能用于非 v128 类型的局部变量，v128 类型的
+    /// 局部变量需使用 `get_value` 和 `set_value` 方法来读写。
+    #[inline]
+    pub fn push_from_slot(&mut self, index: usize) {
+        let slot = self.slots[index];
+        let value_type = self.types[index].clone();
+        self.slots.push(slot);
+        self.types.push(value_type);
+    }
+
+    /// 弹出栈顶的操作数，并写入到指定索引的栈槽（即局部变量）
+    ///
+    /// 用于 `local.set 指令`，不能用于 v128 类型的局部变量。
+    #[inline]
+    pub fn pop_to_slot(&mut self, index: usize) {
+        self.copy_top_to_slot(index);
+        self.slots.pop();
+        self.types.pop();
+    }
+
+    /// 将栈顶的操作数写入到指定索引的栈槽（即局部变量）
+    ///
+    /// 用于 `local.tee 指令`，不能用于 v128 类型的局部变量。
+    #[inline]
+    pub fn copy_top_to_slot(&mut self, index: usize) {
+        let top_index = self.slots.len() - 1;
+        self.slots[index] = self.slots[top_index];
+        self.types[index] = self.types[top_index].clone();
+    }
+
+    // 供寄存器解释器使用的方法
+    //
+    // 寄存器解释器把局部变量以及操作数栈上的临时数值看作寄存器，
+    // 寄存器的读写不改变栈的大小。
+
+    /// 调整栈的大小
+    ///
+    /// 新增的栈槽的值为 0，在类型视图里显示为 i32。
+    #[inline]
+    pub fn set_size(&mut self, size: usize) {
+        self.slots.resize(size, 0);
+        self.types.resize(size, ValueType::I32);
+    }
+
+    /// 按索引写入栈槽以及它的数据类型，不能用于 v128 数值
+    #[inline]
+    pub fn set_slot(&mut self, index: usize, slot: u64, value_type: ValueType) {
+        self.slots[index] = slot;
+        self.types[index] = value_type;
+    }
+
+    /// 复制栈槽以及它的数据类型，不能用于 v128 数值
+    #[inline]
+    pub fn copy_slot(&mut self, source_index: usize, dest_index: usize) {
+        self.slots[dest_index] = self.slots[source_index];
+        self.types[dest_index] = self.types[source_index].clone();
+    }
+
+    /// 复制一段连续的栈槽，可以用于 v128 数值
+    ///
+    /// 用于跳转时把操作数带到目标结构块，目标位置总是低于源位置，
+    /// 所以按从低到高的顺序复制即可。
+    pub fn move_slots(&mut self, source_index: usize, dest_index: usize, count: usize) {
+        for offset in 0..count {
+            self.move_slot(source_index + offset, dest_index + offset);
+        }
+    }
+
+    /// 复制栈槽，包括类型视图以及 v128 数值的高 64 位
+    fn move_slot(&mut self, source_index: usize, dest_index: usize) {
+        let value_type = self.types[source_index].clone();
+
+        if value_type == ValueType::V128 {
+            let high = self.v128_high_slots[source_index];
+            self.set_v128_high_slot(dest_index, high);
+        }
+
+        self.slots[dest_index] = self.slots[source_index];
+        self.types[dest_index] = value_type;
+    }
+
+    fn set_v128_high_slot(&mut self, index: usize, high: u64) {
+        if index >= self.v128_high_slots.len() {
+            self.v128_high_slots.resize(index + 1, 0);
+        }
+        self.v128_high_slots[index] = high;
+    }
+
+    // 以 Value 为单位的读写方法，数据类型来自类型视图
+
+    fn encode_value(value: &Value) -> u64 {
+        match value {
+            Value::I32(v) => *v as u32 as u64,
+            Value::I64(v) => *v as u64,
+            Value::F32(v) => v.to_bits() as u64,
+            Value::F64(v) => v.to_bits(),
+            Value::V128(v) => *v as u64,
+            Value::FuncRef(option_index)
+            | Value::ExternRef(option_index)
+            | Value::ExnRef(option_index) => match option_index {
+                Some(index) => *index as u64,
+                None => NULL_REF_SLOT,
+            },
+        }
+    }
+
+    fn decode_value(&self, index: usize) -> Value {
+        let slot = self.slots[index];
+        let to_ref = |slot: u64| {
+            if slot == NULL_REF_SLOT {
+                None
+            } else {
+                Some(slot as u32)
+            }
+        };
+
+        match self.types[index] {
+            ValueType::I32 => Value::I32(slot as u32 as i32),
+            ValueType::I64 => Value::I64(slot as i64),
+            ValueType::F32 => Value::F32(f32::from_bits(slot as u32)),
+            ValueType::F64 => Value::F64(f64::from_bits(slot)),
+            ValueType::V128 => {
+                let high = self.v128_high_slots[index];
+                Value::V128(((high as u128) << 64) | (slot as u128))
+            }
+            ValueType::FuncRef => Value::FuncRef(to_ref(slot)),
+            ValueType::ExternRef => Value::ExternRef(to_ref(slot)),
+            ValueType::ExnRef => Value::ExnRef(to_ref(slot)),
+        }
+    }
+
     /// 压入
     pub fn push(&mut self, value: Value) {
-        self.slots.push(value);
+        if let Value::V128(v) = value {
+            self.push_v128(v);
+        } else {
+            self.slots.push(VMStack::encode_value(&value));
+            self.types.push(value.get_type());
+        }
     }
 
     /// 弹出
     pub fn pop(&mut self) -> Value {
-        self.slots.pop().expect("operand stack is empty")
+        let value = self.peek();
+        self.slots.pop();
+        self.types.pop();
+        value
     }
 
     /// 查看最后一个操作数
     pub fn peek(&self) -> Value {
-        *self.slots.last().expect("operand stack is empty")
+        let size = self.slots.len();
+        assert!(size > 0, "operand stack is empty");
+        self.decode_value(size - 1)
     }
 
     /// 对于压入 bool 值的约定：
     /// 使用 i32 0 表示 false，
     /// 使用 i32 1 表示 true。
+    #[inline]
     pub fn push_bool(&mut self, value: bool) {
-        if value {
-            self.slots.push(Value::I32(1));
-        } else {
-            self.slots.push(Value::I32(0));
-        }
+        self.push_i32(value as i32);
     }
 
     /// 对于弹出 bool 值的约定：
     /// 如果数值为 i32 0，则表示 false，
     /// 如果数值为 i32 非零，则表示 true。
+    #[inline]
     pub fn pop_bool(&mut self) -> Result<bool, Value> {
-        let value = self.pop();
-        match value {
-            Value::I32(0) => Ok(false),
-            Value::I64(_) | Value::F32(_) | Value::F64(_) => Err(value),
-            _ => Ok(true),
+        match self.types.last() {
+            Some(ValueType::I32) => Ok(self.pop_slot() != 0),
+            Some(ValueType::I64 | ValueType::F32 | ValueType::F64 | ValueType::V128) => {
+                Err(self.pop())
+            }
+            _ => {
+                self.pop_slot();
+                Ok(true)
+            }
         }
     }
 
     /// 按索引来获取栈的操作数
     ///
     /// 用于读写局部变量（局部变量包括函数调用的实参）
-    /// 以及栈信息
     pub fn get_value(&self, index: usize) -> Value {
-        self.slots[index]
+        self.decode_value(index)
     }
 
     /// 按索引来设置栈的操作数
     ///
     /// 用于读写局部变量（局部变量包括函数调用的实参）
-    /// 以及栈信息
     pub fn set_value(&mut self, index: usize, value: Value) {
-        self.slots[index] = value;
+        if let Value::V128(v) = value {
+            self.set_v128_high_slot(index, (v >> 64) as u64);
+        }
+        self.slots[index] = VMStack::encode_value(&value);
+        self.types[index] = value.get_type();
     }
 
     /// 将一组数值原样压入栈
@@ -219,7 +553,9 @@ impl VMStack {
     ///                  |栈底。|
     /// ```
     pub fn push_values(&mut self, values: &[Value]) {
-        self.slots.extend_from_slice(values)
+        for value in values {
+            self.push(*value);
+        }
     }
 
     /// 将一组数值原样弹出栈
@@ -236,22 +572,54 @@ impl VMStack {
     /// |栈底。|
     /// ```
     pub fn pop_values(&mut self, count: usize) -> Vec<Value> {
-        let index = self.slots.len() - count;
-        let values: Vec<Value> = self.slots.drain(index..).collect();
+        let values = self.peek_values(count);
+        self.drop_values_at(self.slots.len() - count);
         values
     }
 
     pub fn drop_values_at(&mut self, index: usize) {
-        self.slots.drain(index..);
+        self.slots.truncate(index);
+        self.types.truncate(index);
+    }
+
+    /// 丢弃从 index 开始的操作数，但保留栈顶 count 个操作数
+    ///
+    /// 此方法用于跳出结构块，即把操作数栈截断到目标结构块的栈高度，
+    /// 同时保留需要带出结构块的操作数。
+    ///
+    /// ```diagram
+    /// |栈顶。|
+    /// | 4   |              |栈顶。|
+    /// | 3   |              | 4   |
+    /// | 2   | --(2, 1)---> | 1   |
+    /// | 1   |              | 0   |
+    /// | 0   |              |栈底。|
+    /// |栈底。|
+    /// ```
+    pub fn drop_values_keep_top(&mut self, index: usize, count: usize) {
+        let top_index = self.slots.len() - count;
+        if top_index > index {
+            for offset in 0..count {
+                self.move_slot(top_index + offset, index + offset);
+            }
+            self.drop_values_at(index + count);
+        }
+    }
+
+    /// 读取栈槽区间 [start, end) 的数值，即栈的类型视图，供调试器使用
+    pub fn read_values(&self, start: usize, end: usize) -> Vec<Value> {
+        (start..end).map(|index| self.decode_value(index)).collect()
     }
 
-    pub fn read_slots(&self, start: usize, end: usize) -> &[Value] {
-        &self.slots[start..end]
+    pub fn peek_values(&self, count: usize) -> Vec<Value> {
+        let size = self.slots.len();
+        self.read_values(size - count, size)
     }
 
-    pub fn peek_values(&self, count: usize) -> &[Value] {
-        let index = self.slots.len() - count;
-        &self.slots[index..]
+    /// 查看栈顶 count 个操作数的数据类型
+    pub fn peek_types(&self, count: usize) -> &[ValueType] {
+        let index = self.types.len() - count;
+        &self.types[index..]
     }
 }
 
@@ -364,4 +732,83 @@ mod tests {
             vec![Value::I32(1), Value::I32(2), Value::F32(1.1),]
         );
     }
+
+    #[test]
+    fn test_drop_values_keep_top() {
+        let mut s0 = VMStack::new();
+
+        s0.push_values(&vec![
+            Value::I32(0),
+            Value::I32(1),
+            Value::I32(2),
+            Value::I32(3),
+            Value::I32(4),
+        ]);
+
+        // 截断到索引 2，保留栈顶 1 个操作数
+        s0.drop_values_keep_top(2, 1);
+        assert_eq!(s0.get_size(), 3);
+        assert_eq!(
+            s0.peek_values(3),
+            &[Value::I32(0), Value::I32(1), Value::I32(4)]
+        );
+
+        // 没有多余的操作数时，栈保持不变
+        s0.drop_values_keep_top(1, 2);
+        assert_eq!(
+            s0.peek_values(3),
+            &[Value::I32(0), Value::I32(1), Value::I32(4)]
+        );
+
+        // 不保留操作数
+        s0.drop_values_keep_top(1, 0);
+        assert_eq!(s0.peek_values(1), &[Value::I32(0)]);
+    }
+
+    #[test]
+    fn test_typed_slots() {
+        let mut s0 = VMStack::new();
+
+        // 带类型的方法跟 Value 方法读写的是同一份栈槽
+        s0.push_i32(-1);
+        s0.push_f64(2.5);
+        s0.push_v128(0x1111_2222_3333_4444_5555_6666_7777_8888);
+        s0.push(Value::FuncRef(None));
+        assert_eq!(
+            s0.peek_values(4),
+            &[
+                Value::I32(-1),
+                Value::F64(2.5),
+                Value::V128(0x1111_2222_3333_4444_5555_6666_7777_8888),
+                Value::FuncRef(None)
+            ]
+        );
+
+        // 原地替换栈顶的操作数
+        s0.push_i64(7);
+        s0.replace_top_i64(s0.peek_i64() * 6);
+        assert_eq!(s0.peek(), Value::I64(42));
+        s0.replace_top_bool(s0.peek_i64() == 42);
+        assert_eq!(s0.pop(), Value::I32(1));
+
+        // 直接复制栈槽来读写非 v128 类型的局部变量
+        s0.push_from_slot(1);
+        s0.pop_to_slot(0);
+        assert_eq!(s0.get_value(0), Value::F64(2.5));
+
+        s0.push_f32(1.5);
+        s0.copy_top_to_slot(1);
+        assert_eq!(s0.pop_f32(), 1.5);
+        assert_eq!(s0.get_value(1), Value::F32(1.5));
+
+        // v128 类型的局部变量使用 Value 来读写
+        let value = s0.get_value(2);
+        s0.set_value(0, value);
+
+        assert_eq!(s0.pop(), Value::FuncRef(None));
+        assert_eq!(s0.pop_v128(), 0x1111_2222_3333_4444_5555_6666_7777_8888);
+        assert_eq!(s0.pop_f32(), 1.5);
+        assert_eq!(s0.pop_v128(), 0x1111_2222_3333_4444_5555_6666_7777_8888);
+        assert_eq!(s0.get_size(), 0);
+    }
 }
@@ -4,16 +4,17 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-// `表段` 和 `元素段` 目前用于列出一组函数，然后在执行 `call_indirect` 指令时，根据栈顶
-// 的操作数获取该列表中的其中一个函数，从而实现 "动态" 选择被调用的函数。
+// `表段` 和 `元素段` 用于列出一组引用（函数引用 `funcref` 或者外部引用 `externref`），
+// 对于函数引用表，在执行 `call_indirect` 指令时，根据栈顶的操作数获取该列表中的其中
+// 一个函数，从而实现 "动态" 选择被调用的函数。
 // 相对应高级语言里的 `函数指针`（比如数据类型为 `函数` 类型的参数）
 //
-// `表段` 用于说明元素项目数量的范围，
-// `元素段` 用于存储 `表` 的初始化数据，也就是函数的索引。
+// `表段` 用于说明表的元素类型以及元素项目数量的范围，
+// `元素段` 用于存储 `表` 的初始化数据，也就是引用值的列表。
 //
 // 指令 call_indirect 的操作步骤：
 // 1. 从操作数栈弹出一个 u32 数，该数是表内项目的索引
-// 2. 从表里获取指定的项目，也就是目标函数的索引值
+// 2. 从表里获取指定的项目，也就是目标函数的引用（函数的索引值）
 // 3. 通过函数索引值获取目标函数
 // 4. 调用目标函数
 //
@@ -24,44 +25,55 @@
 // | ...         |       | #2 func#5   |     | #5 div     |
 // | -- 栈底。 -- |       | ...         |     | ...        |
 
-use anvm_ast::ast::{Limit, TableType};
+use anvm_ast::{
+    ast::{Limit, TableType},
+    types::{RefType, Value},
+};
 
-use crate::error::{EngineError, Overflow, OutOfRange};
+use crate::error::{EngineError, OutOfRange, Overflow};
 
+#[derive(Clone)]
 pub struct VMTable {
-    /// TableType 的信息包含表的类型（目前只有函数引用类型）以及限制值（范围值）
+    /// TableType 的信息包含表的元素类型以及限制值（范围值）
     table_type: TableType,
 
-    /// 函数索引列表
-    elements: Vec<Option<u32>>,
+    /// 引用值列表
+    ///
+    /// 函数引用的值是函数在 VM 实例里的全局索引（见 `VMModule::function_reference_base`），
+    /// 外部引用的值是宿主提供的句柄，空槽的值是对应类型的 null 引用。
+    elements: Vec<Value>,
 }
 
 impl VMTable {
     pub fn new(table_type: TableType) -> Self {
         let min = table_type.limit.get_min();
+        let null_value = Value::new_null_ref(table_type.element_type);
+
         VMTable {
             table_type: table_type,
 
             // 预先分配好空槽，因为访问者会随机访问指定的地址，所以不能仅仅
             // 分配 Vec 的容量，而应该分配空槽。
-            // 空槽的初始值都是 None
-            elements: vec![None; min as usize],
+            // 空槽的初始值都是 null
+            elements: vec![null_value; min as usize],
         }
     }
 
-    /// 创建指定项目数量（且不限最大值的）的表
+    /// 创建指定项目数量（且不限最大值的）的函数引用表
     pub fn new_by_min(min: u32) -> Self {
         let table_type = TableType {
-            limit: Limit::AtLeast(min),
+            element_type: RefType::FuncRef,
+            limit: Limit::AtLeast(min as u64),
         };
 
         VMTable::new(table_type)
     }
 
-    /// min 和 max 的值都是 `包括的`（`included`）
+    /// 创建函数引用表，min 和 max 的值都是 `包括的`（`included`）
     pub fn new_by_page_range(min_page: u32, max_page: u32) -> Self {
         let table_type = TableType {
-            limit: Limit::Range(min_page, max_page),
+            element_type: RefType::FuncRef,
+            limit: Limit::Range(min_page as u64, max_page as u64),
         };
 
         VMTable::new(table_type)
@@ -71,27 +83,42 @@ impl VMTable {
         self.elements.len() as u32
     }
 
-    /// 返回原先的大小
+    /// 增加空槽，返回原先的大小
     pub fn increase_size(&mut self, increase_number: u32) -> Result<u32, EngineError> {
+        let null_value = Value::new_null_ref(self.table_type.element_type);
+        self.increase_size_with_value(increase_number, null_value)
+    }
+
+    /// 增加指定初始值的槽，返回原先的大小
+    pub fn increase_size_with_value(
+        &mut self,
+        increase_number: u32,
+        value: Value,
+    ) -> Result<u32, EngineError> {
         let old_len = self.get_size();
-        let new_len = self.get_size() + increase_number;
 
         // 如果 TableType 的 limit 成员不指定 max 值，则可以
         // 增长到 u32 的最大值
-        if let Limit::Range(_, max) = self.table_type.limit {
-            if new_len > max {
+        let max = match self.table_type.limit {
+            Limit::Range(_, max) => max.min(u32::MAX as u64) as u32,
+            Limit::AtLeast(_) => u32::MAX,
+        };
+
+        let new_len = match old_len.checked_add(increase_number) {
+            Some(new_len) if new_len <= max => new_len,
+            _ => {
                 return Err(EngineError::Overflow(Overflow::TableSizeExceed(
-                    new_len, max,
-                )));
+                    old_len.saturating_add(increase_number),
+                    max,
+                )))
             }
-        }
+        };
 
-        // 新增加的空槽的初始值都是 None
-        self.elements.resize(new_len as usize, None);
+        self.elements.resize(new_len as usize, value);
         Ok(old_len)
     }
 
-    pub fn get_element(&self, index: usize) -> Result<Option<u32>, EngineError> {
+    pub fn get_element(&self, index: usize) -> Result<Value, EngineError> {
         if index >= self.elements.len() {
             return Err(EngineError::OutOfRange(
                 OutOfRange::ElementIndexOutOfRange(index, self.elements.len())
@@ -101,24 +128,80 @@ impl VMTable {
         Ok(self.elements[index])
     }
 
-    pub fn set_element(&mut self, index: usize, function_index: u32) -> Result<(), EngineError> {
+    pub fn set_element(&mut self, index: usize, value: Value) -> Result<(), EngineError> {
         if index >= self.elements.len() {
             return Err(EngineError::OutOfRange(
                 OutOfRange::ElementIndexOutOfRange(index, self.elements.len())
             ));
         }
 
-        self.elements[index] = Some(function_index);
+        self.elements[index] = value;
+        Ok(())
+    }
+
+    /// 读取从 offset 开始的 length 个槽的值
+    pub fn read_elements(&self, offset: usize, length: usize) -> Result<Vec<Value>, EngineError> {
+        self.check_range(offset, length)?;
+        Ok(self.elements[offset..(offset + length)].to_vec())
+    }
+
+    /// 把一组引用值写入到从 offset 开始的槽
+    ///
+    /// 如果区间 [offset, offset + values.len()) 超出了表的范围，则不作任何修改
+    pub fn write_elements(&mut self, offset: usize, values: &[Value]) -> Result<(), EngineError> {
+        self.check_range(offset, values.len())?;
+        self.elements[offset..(offset + values.len())].copy_from_slice(values);
+        Ok(())
+    }
+
+    /// 把从 offset 开始的 length 个槽设置为指定的值
+    pub fn fill_elements(
+        &mut self,
+        offset: usize,
+        length: usize,
+        value: Value,
+    ) -> Result<(), EngineError> {
+        self.check_range(offset, length)?;
+        self.elements[offset..(offset + length)].fill(value);
+        Ok(())
+    }
+
+    /// 在表内复制槽的值，源区间和目标区间允许重叠
+    pub fn copy_elements(
+        &mut self,
+        dest_offset: usize,
+        source_offset: usize,
+        length: usize,
+    ) -> Result<(), EngineError> {
+        self.check_range(source_offset, length)?;
+        self.check_range(dest_offset, length)?;
+        self.elements
+            .copy_within(source_offset..(source_offset + length), dest_offset);
         Ok(())
     }
 
     pub fn get_table_type(&self) -> &TableType {
         &self.table_type
     }
+
+    fn check_range(&self, offset: usize, length: usize) -> Result<(), EngineError> {
+        let table_size = self.elements.len();
+        match offset.checked_add(length) {
+            Some(end) if end <= table_size => Ok(()),
+            _ => Err(EngineError::OutOfRange(OutOfRange::TableAccessOutOfRange(
+                offset, length, table_size,
+            ))),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use anvm_ast::{
+        ast::{Limit, TableType},
+        types::{RefType, Value},
+    };
+
     use crate::{
         error::{EngineError, OutOfRange, Overflow},
         vm_table::VMTable,
@@ -158,18 +241,18 @@ mod tests {
     fn test_read_write_element() {
         let mut t0 = VMTable::new_by_min(10);
 
-        t0.set_element(0, 10).unwrap();
-        t0.set_element(1, 11).unwrap();
-        t0.set_element(2, 12).unwrap();
-        t0.set_element(3, 13).unwrap();
+        t0.set_element(0, Value::FuncRef(Some(10))).unwrap();
+        t0.set_element(1, Value::FuncRef(Some(11))).unwrap();
+        t0.set_element(2, Value::FuncRef(Some(12))).unwrap();
+        t0.set_element(3, Value::FuncRef(Some(13))).unwrap();
 
-        assert_eq!(t0.get_element(0).unwrap(), Some(10));
-        assert_eq!(t0.get_element(1).unwrap(), Some(11));
-        assert_eq!(t0.get_element(2).unwrap(), Some(12));
-        assert_eq!(t0.get_element(3).unwrap(), Some(13));
+        assert_eq!(t0.get_element(0).unwrap(), Value::FuncRef(Some(10)));
+        assert_eq!(t0.get_element(1).unwrap(), Value::FuncRef(Some(11)));
+        assert_eq!(t0.get_element(2).unwrap(), Value::FuncRef(Some(12)));
+        assert_eq!(t0.get_element(3).unwrap(), Value::FuncRef(Some(13)));
 
-        assert_eq!(t0.get_element(4).unwrap(), None);
-        assert_eq!(t0.get_element(9).unwrap(), None);
+        assert_eq!(t0.get_element(4).unwrap(), Value::FuncRef(None));
+        assert_eq!(t0.get_element(9).unwrap(), Value::FuncRef(None));
 
         assert!(matches!(
             t0.get_element(20),
@@ -179,4 +262,46 @@ mod tests {
             )))
         ));
     }
+
+    #[test]
+    fn test_bulk_elements() {
+        let mut t0 = VMTable::new(TableType {
+            element_type: RefType::ExternRef,
+            limit: Limit::AtLeast(4),
+        });
+
+        assert_eq!(t0.get_element(0).unwrap(), Value::ExternRef(None));
+
+        t0.write_elements(1, &[Value::ExternRef(Some(1)), Value::ExternRef(Some(2))])
+            .unwrap();
+        t0.copy_elements(2, 1, 2).unwrap();
+        assert_eq!(t0.get_element(2).unwrap(), Value::ExternRef(Some(1)));
+        assert_eq!(t0.get_element(3).unwrap(), Value::ExternRef(Some(2)));
+
+        t0.fill_elements(0, 2, Value::ExternRef(Some(7))).unwrap();
+        assert_eq!(t0.get_element(1).unwrap(), Value::ExternRef(Some(7)));
+
+        assert_eq!(
+            t0.increase_size_with_value(2, Value::ExternRef(Some(9)))
+                .unwrap(),
+            4
+        );
+        assert_eq!(t0.get_element(5).unwrap(), Value::ExternRef(Some(9)));
+
+        // 越界访问不会修改表的内容
+        assert!(matches!(
+            t0.write_elements(5, &[Value::ExternRef(None), Value::ExternRef(None)]),
+            Err(EngineError::OutOfRange(OutOfRange::TableAccessOutOfRange(
+                5, 2, 6
+            )))
+        ));
+        assert_eq!(t0.get_element(5).unwrap(), Value::ExternRef(Some(9)));
+
+        assert!(matches!(
+            t0.copy_elements(0, 5, 2),
+            Err(EngineError::OutOfRange(OutOfRange::TableAccessOutOfRange(
+                5, 2, 6
+            )))
+        ));
+    }
 }
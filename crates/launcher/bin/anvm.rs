@@ -64,6 +64,10 @@ e.g.
     $ anvm console.wasm -- help
     $ anvm console.wasm -- convert -d 123 --format hex
     $ anvm --disassembly input.wasm output.wat
+
+Set the environment variable ANVM_CACHE_DIR to cache the compiled modules, e.g.
+
+    $ ANVM_CACHE_DIR=~/.cache/anvm anvm app.wasm
 "
     );
 }
@@ -4,17 +4,23 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use std::fs;
 use std::path::Path;
+use std::{env, fs};
 
 use anvm_ast::types::Value;
 use anvm_binary_parser::parser;
 use anvm_disassembly::disassembler::module_to_text;
 use anvm_engine::error::{EngineError, NativeTerminate, NativeError};
 use anvm_engine::instance::{
-    create_instance, find_ast_module_export_function, get_entry_module_and_function_index,
+    create_instance, create_instance_with_cache, create_instance_with_compiled_modules,
+    find_ast_module_export_function, get_entry_module_and_function_index,
 };
+use anvm_engine::module_cache::{hash_bytes, CompiledModules, ContentHash, ModuleCache};
 use anvm_engine::object::NamedAstModule;
+use anvm_engine::vm::InterpreterKind;
+
+/// 指定编译模块缓存目录的环境变量，未设置时不使用缓存
+pub const CACHE_DIRECTORY_ENV_NAME: &str = "ANVM_CACHE_DIR";
 
 pub fn disassembly(input_filepath: &str, output_filepath: &str) {
     println!(
@@ -44,9 +50,13 @@ pub fn execute_function(
     function_arguments: &[Value],
     application_arguments: &[String],
 ) -> Result<(Vec<Value>, i32), String> {
-    let named_ast_modules = load_ast_modules(module_filepaths)?;
-    execute_function_by_modules(
+    let option_module_cache = env::var_os(CACHE_DIRECTORY_ENV_NAME)
+        .map(|directory| ModuleCache::new(Path::new(&directory)));
+    let (named_ast_modules, option_compiled_modules) =
+        load_ast_modules(module_filepaths, option_module_cache.as_ref())?;
+    execute(
         &named_ast_modules,
+        option_compiled_modules,
         entry_module_function_name,
         function_arguments,
         application_arguments,
@@ -59,6 +69,24 @@ pub fn execute_function_by_modules(
     entry_module_function_name: Option<(String, String)>,
     function_arguments: &[Value],
     application_arguments: &[String],
+) -> Result<(Vec<Value>, i32), String> {
+    execute(
+        named_ast_modules,
+        None,
+        entry_module_function_name,
+        function_arguments,
+        application_arguments,
+    )
+}
+
+/// option_compiled_modules 是从编译模块缓存读取的链接和解码的结果，
+/// 为 None 时如果设置了缓存目录，则在实例化时读写缓存。
+fn execute(
+    named_ast_modules: &[NamedAstModule],
+    option_compiled_modules: Option<CompiledModules>,
+    entry_module_function_name: Option<(String, String)>,
+    function_arguments: &[Value],
+    _application_arguments: &[String],
 ) -> Result<(Vec<Value>, i32), String> {
     let (vm_module_index, function_index) =
         // 用户指定了入口模块及函数
@@ -112,7 +140,25 @@ function index is also supported, e.g.
         named_ast_modules[vm_module_index].name, function_index
     );
 
-    let mut vm = create_instance(vec![], &named_ast_modules).map_err(|e| e.to_string())?;
+    let mut vm = match (
+        option_compiled_modules,
+        env::var_os(CACHE_DIRECTORY_ENV_NAME),
+    ) {
+        (Some(compiled_modules), _) => create_instance_with_compiled_modules(
+            vec![],
+            named_ast_modules,
+            compiled_modules,
+            InterpreterKind::Stack,
+        ),
+        (None, Some(cache_directory)) => create_instance_with_cache(
+            vec![],
+            named_ast_modules,
+            InterpreterKind::Stack,
+            &ModuleCache::new(Path::new(&cache_directory)),
+        ),
+        (None, None) => create_instance(vec![], named_ast_modules),
+    }
+    .map_err(|e| e.to_string())?;
     match vm.eval_function_by_index(vm_module_index, function_index, function_arguments) {
         Ok(results) => Ok((results, 0)),
         Err(e) => {
@@ -120,6 +166,7 @@ function index is also supported, e.g.
                 EngineError::NativeTerminate(ne) => match &ne.native_error {
                     NativeError::Exit(exit_code) => Ok((vec![], *exit_code)),
                     NativeError::Internal(internal_error) => Err(internal_error.to_string()),
+                    NativeError::Exception(_, _) => Err(ne.to_string()),
                 },
                 _ => {
                     // todo::
@@ -131,8 +178,15 @@ function index is also supported, e.g.
     }
 }
 
-fn load_ast_modules(module_filepaths: &[String]) -> Result<Vec<NamedAstModule>, String> {
-    let mut named_ast_modules: Vec<NamedAstModule> = vec![];
+/// 读取并解析模块（不验证）
+///
+/// 如果指定了编译模块缓存，则先根据模块文件的内容查找缓存，命中缓存时直接返回
+/// 缓存里的模块以及链接和解码的结果，无需解析和验证模块。
+fn load_ast_modules(
+    module_filepaths: &[String],
+    option_module_cache: Option<&ModuleCache>,
+) -> Result<(Vec<NamedAstModule>, Option<CompiledModules>), String> {
+    let mut module_names_and_bytes: Vec<(&str, Vec<u8>)> = vec![];
 
     for filepath in module_filepaths {
         let p = Path::new(filepath);
@@ -151,11 +205,32 @@ fn load_ast_modules(module_filepaths: &[String]) -> Result<Vec<NamedAstModule>,
         let bytes =
             fs::read(filepath).map_err(|_| format!("failed to open file \"{}\"", filepath))?;
 
-        let ast_module = parser::parse(&bytes).map_err(|e| e.to_string())?;
+        module_names_and_bytes.push((basename, bytes));
+    }
+
+    let named_content_hashes = module_names_and_bytes
+        .iter()
+        .map(|(name, bytes)| (*name, hash_bytes(bytes)))
+        .collect::<Vec<(&str, ContentHash)>>();
+
+    if let Some(module_cache) = option_module_cache {
+        let key = ModuleCache::compute_key_by_content_hashes(&[], &named_content_hashes);
+        if let Some((named_ast_modules, compiled_modules)) = module_cache.load(&key) {
+            return Ok((named_ast_modules, Some(compiled_modules)));
+        }
+    }
+
+    let mut named_ast_modules: Vec<NamedAstModule> = vec![];
+
+    for ((name, bytes), (_, content_hash)) in
+        module_names_and_bytes.iter().zip(named_content_hashes)
+    {
+        // 模块在实例化时验证（见 `anvm_engine::instance`），这里只解析
+        let ast_module = parser::parse(bytes).map_err(|e| e.to_string())?;
 
-        let named_ast_module = NamedAstModule::new(basename, ast_module);
-        named_ast_modules.push(named_ast_module);
+        named_ast_modules
+            .push(NamedAstModule::new(name, ast_module).with_content_hash(content_hash));
     }
 
-    Ok(named_ast_modules)
+    Ok((named_ast_modules, None))
 }
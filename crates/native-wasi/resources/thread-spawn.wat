@@ -0,0 +1,38 @@
+(module
+    (import "wasi" "thread-spawn" (func $thread_spawn (param i32) (result i32)))
+    (memory 1 1 shared)
+
+    ;; 新线程的入口函数
+    ;; 把 start_arg 累加到地址 0，然后累加地址 4 的完成计数并唤醒等待者
+    (func (export "wasi_thread_start") (param $tid i32) (param $start_arg i32)
+        (drop (i32.atomic.rmw.add (i32.const 0) (local.get $start_arg)))
+        (drop (i32.atomic.rmw.add (i32.const 4) (i32.const 1)))
+        (drop (memory.atomic.notify (i32.const 4) (i32.const -1)))
+    )
+
+    ;; 创建 $count 个线程（第 n 个线程的 start_arg 为 n），
+    ;; 等待所有线程结束之后返回累加的结果以及最后一个线程的 id
+    (func (export "spawn_and_wait") (param $count i32) (result i32 i32)
+        (local $index i32)
+        (local $tid i32)
+        (local $finished i32)
+        (block $spawned
+            (loop $spawn
+                (br_if $spawned (i32.ge_u (local.get $index) (local.get $count)))
+                (local.set $index (i32.add (local.get $index) (i32.const 1)))
+                (local.set $tid (call $thread_spawn (local.get $index)))
+                (br $spawn)
+            )
+        )
+        (block $done
+            (loop $again
+                (local.set $finished (i32.atomic.load (i32.const 4)))
+                (br_if $done (i32.ge_u (local.get $finished) (local.get $count)))
+                (drop (memory.atomic.wait32 (i32.const 4) (local.get $finished) (i64.const -1)))
+                (br $again)
+            )
+        )
+        (i32.atomic.load (i32.const 0))
+        (local.get $tid)
+    )
+)
@@ -11,3 +11,4 @@ pub mod native_fd;
 pub mod filesystem_context;
 pub mod wasi_module_context;
 pub mod wasi;
+pub mod wasi_threads;
@@ -145,7 +145,7 @@ pub fn fd_write(
                 for ciovec in ciovecs {
                     let data = memory_block
                         .read_bytes(ciovec.buf_offset as usize, ciovec.buf_len as usize);
-                    match file.write(data) {
+                    match file.write(&data) {
                         Ok(n) => {
                             wrote_bytes += n;
                         }
@@ -163,7 +163,7 @@ pub fn fd_write(
                 for ciovec in ciovecs {
                     let data = memory_block
                         .read_bytes(ciovec.buf_offset as usize, ciovec.buf_len as usize);
-                    match writer.write(data) {
+                    match writer.write(&data) {
                         Ok(n) => {
                             wrote_bytes += n;
                         }
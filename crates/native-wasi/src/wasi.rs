@@ -221,7 +221,7 @@ fn fd_write(
                 ciovec_data_size,
             );
 
-            let ciovec = CIOVec::deserialize(data);
+            let ciovec = CIOVec::deserialize(&data);
             ciovecs.push(ciovec);
         }
     }
@@ -0,0 +1,161 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # wasi-threads
+//!
+//! wasi-threads 提案只有一个 API：
+//!
+//! `(import "wasi" "thread-spawn" (func $thread-spawn (param $start_arg i32) (result i32)))`
+//!
+//! 它会创建一个新的线程，然后在新线程里（一个新的 VM 实例）调用应用程序导出的函数
+//!
+//! `(func (export "wasi_thread_start") (param $tid i32) (param $start_arg i32))`
+//!
+//! 新的 VM 实例跟调用者共享所有共享内存块。线程创建成功时返回新线程的 id（正数），
+//! 失败时返回负数。
+//!
+//! 注意应用程序需要导入（或者定义）共享内存块，否则各个线程之间将无法交换数据。
+//!
+//! https://github.com/WebAssembly/wasi-threads
+
+use std::sync::Arc;
+
+use anvm_ast::types::{Value, ValueType};
+use anvm_engine::{
+    error::NativeTerminate,
+    native_module::{ModuleContext, NativeModule},
+    thread::ThreadSpawner,
+    vm::VM,
+};
+
+/// wasi-threads 模块的名称
+pub const MODULE_NAME: &str = "wasi";
+
+/// 新线程的入口函数的导出名称
+pub const THREAD_START_FUNCTION_NAME: &str = "wasi_thread_start";
+
+pub struct WASIThreadsModuleContext {
+    pub thread_spawner: Arc<ThreadSpawner>,
+}
+
+impl ModuleContext for WASIThreadsModuleContext {
+    fn as_any(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl WASIThreadsModuleContext {
+    pub fn new(thread_spawner: Arc<ThreadSpawner>) -> Self {
+        Self { thread_spawner }
+    }
+}
+
+pub fn new_wasi_threads_module(module_context: WASIThreadsModuleContext) -> NativeModule {
+    let mut native_module = NativeModule::new(MODULE_NAME, Box::new(module_context));
+
+    native_module.add_native_function(
+        "thread-spawn",
+        vec![ValueType::I32],
+        vec!["start_arg"],
+        vec![ValueType::I32],
+        thread_spawn,
+    );
+
+    native_module
+}
+
+/// # thread-spawn
+///
+/// `(func $wasi.thread-spawn (param $start_arg i32) (result (;tid;) i32))`
+///
+/// - $start_arg：传给新线程入口函数 `wasi_thread_start` 的参数，通常是一个指向
+///   线程启动信息的内存地址
+fn thread_spawn(
+    vm: &mut VM,
+    native_module_index: usize,
+    args: &[Value],
+) -> Result<Vec<Value>, NativeTerminate> {
+    let start_arg = if let Value::I32(start_arg) = args[0] {
+        start_arg
+    } else {
+        unreachable!()
+    };
+
+    let thread_spawner = {
+        let module_context = vm.resource.native_modules[native_module_index]
+            .module_context
+            .as_any()
+            .downcast_mut::<WASIThreadsModuleContext>()
+            .unwrap();
+        Arc::clone(&module_context.thread_spawner)
+    };
+
+    let thread_id = thread_spawner.allocate_thread_id();
+
+    let result = thread_spawner.spawn(
+        vm,
+        THREAD_START_FUNCTION_NAME,
+        vec![Value::I32(thread_id as i32), Value::I32(start_arg)],
+    );
+
+    match result {
+        Ok(_) => Ok(vec![Value::I32(thread_id as i32)]),
+        Err(_) => Ok(vec![Value::I32(-1)]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, sync::Arc};
+
+    use anvm_ast::{ast, types::Value};
+    use anvm_binary_parser::parser;
+    use anvm_engine::{
+        instance::find_ast_module_export_function, object::NamedAstModule, thread::ThreadSpawner,
+    };
+
+    use pretty_assertions::assert_eq;
+
+    use super::{new_wasi_threads_module, WASIThreadsModuleContext};
+
+    fn get_test_ast_module(filename: &str) -> ast::Module {
+        let mut path_buf = env::current_dir().unwrap();
+        if !path_buf.ends_with("native-wasi") {
+            path_buf.push("crates");
+            path_buf.push("native-wasi");
+        }
+        let fullname_buf = path_buf.join("resources").join(filename);
+        let bytes = fs::read(fullname_buf).unwrap();
+        parser::parse(&bytes).unwrap()
+    }
+
+    #[test]
+    fn test_thread_spawn() {
+        let ast_module = get_test_ast_module("thread-spawn.wasm");
+        let function_index =
+            find_ast_module_export_function(&ast_module, "spawn_and_wait").unwrap() as usize;
+
+        let named_ast_module = NamedAstModule::new("test", ast_module);
+        let thread_spawner = ThreadSpawner::new(
+            vec![named_ast_module],
+            Arc::new(|thread_spawner| {
+                let module_context = WASIThreadsModuleContext::new(Arc::clone(thread_spawner));
+                vec![new_wasi_threads_module(module_context)]
+            }),
+        );
+
+        let mut vm = thread_spawner.create_instance().unwrap();
+
+        // 1 + 2 + 3 + 4 = 10，线程 id 从 1 开始
+        assert_eq!(
+            vm.eval_function_by_index(0, function_index, &vec![Value::I32(4)])
+                .unwrap(),
+            vec![Value::I32(10), Value::I32(4)]
+        );
+
+        assert_eq!(thread_spawner.join(), Ok(()));
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::{env, panic, path::Path, process};
+
+use anvm_spec_test::runner::run_test_suite;
+
+/// 执行 WebAssembly 规范测试集，并按提案输出测试结果，例如：
+///
+/// `$ cargo run --bin anvm-spec-test -- crates/spec-test/resources/testsuite`
+///
+/// 如果存在失败的断言或者无法解析的脚本，程序的退出码为 1。
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    if args.len() != 2 {
+        println!(
+            "\
+Usage:
+
+    $ anvm-spec-test testsuite_path
+
+e.g.
+
+    $ anvm-spec-test crates/spec-test/resources/testsuite
+"
+        );
+        return;
+    }
+
+    // VM 崩溃的信息会被记录到测试报告里，这里不再打印到终端
+    panic::set_hook(Box::new(|_| {}));
+
+    let reports = match run_test_suite(Path::new(&args[1])) {
+        Ok(reports) => reports,
+        Err(e) => {
+            println!("failed to read the test suite: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let mut is_all_passed = true;
+
+    for report in &reports {
+        for (file_name, message) in &report.script_errors {
+            println!("[{}] {}: {}", report.name, file_name, message);
+        }
+
+        for (file_name, line, reason) in &report.failures {
+            println!("[{}] {}:{}: {}", report.name, file_name, line, reason);
+        }
+
+        if report.failed > 0 || !report.script_errors.is_empty() {
+            is_all_passed = false;
+        }
+    }
+
+    println!();
+    println!(
+        "{:<40} {:>8} {:>8} {:>8} {:>8}",
+        "proposal", "passed", "failed", "skipped", "errors"
+    );
+
+    for report in &reports {
+        println!(
+            "{:<40} {:>8} {:>8} {:>8} {:>8}",
+            report.name,
+            report.passed,
+            report.failed,
+            report.skipped,
+            report.script_errors.len()
+        );
+    }
+
+    if !is_all_passed {
+        process::exit(1);
+    }
+}
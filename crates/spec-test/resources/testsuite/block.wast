@@ -0,0 +1,98 @@
+;; block, loop, if, br, br_if, br_table and return
+
+;; (module
+;;   (func (export "empty") (block) (block $l))
+;;   (func (export "singular") (result i32) (block (result i32) (i32.const 7)))
+;;   (func (export "nested") (result i32)
+;;     (block (result i32)
+;;       (block (result i32) (i32.const 8))
+;;       (drop)
+;;       (block (result i32) (i32.const 9))))
+;;   (func (export "br-value") (result i32) (block (result i32) (br 0 (i32.const 1)) (i32.const 2)))
+;;   (func (export "br-outer") (result i32)
+;;     (block (result i32) (block (br 1 (i32.const 3))) (i32.const 4)))
+;;   (func (export "br_if") (param $x i32) (result i32)
+;;     (block (result i32) (br_if 0 (i32.const 10) (local.get $x)) (drop) (i32.const 20)))
+;;   (func (export "if") (param $x i32) (result i32)
+;;     (if (result i32) (local.get $x) (then (i32.const 1)) (else (i32.const 2))))
+;;   (func (export "if-without-else") (param $x i32) (result i32)
+;;     (local $r i32)
+;;     (if (local.get $x) (then (local.set $r (i32.const 5))))
+;;     (local.get $r))
+;;   (func (export "br_table") (param $x i32) (result i32)
+;;     (block $default
+;;       (block $2
+;;         (block $1
+;;           (block $0 (br_table $0 $1 $2 $default (local.get $x)))
+;;           (return (i32.const 100)))
+;;         (return (i32.const 101)))
+;;       (return (i32.const 102)))
+;;     (i32.const 103))
+;;   (func (export "br_table-value") (param $x i32) (result i32)
+;;     (block $b (result i32)
+;;       (block $a (result i32)
+;;         (br_table $a $b (i32.const 7) (local.get $x)))
+;;       (i32.add (i32.const 10))))
+;;   (func (export "sum") (param $n i32) (result i32)
+;;     (local $s i32)
+;;     (block $done
+;;       (loop $continue
+;;         (br_if $done (i32.eqz (local.get $n)))
+;;         (local.set $s (i32.add (local.get $s) (local.get $n)))
+;;         (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+;;         (br $continue)))
+;;     (local.get $s))
+;;   (func (export "loop-value") (result i32) (loop (result i32) (i32.const 6)))
+;;   (func (export "return") (param $x i32) (result i32)
+;;     (block (if (local.get $x) (then (return (i32.const 1)))))
+;;     (i32.const 2))
+;;   (func (export "select") (param $c i32) (result i32)
+;;     (select (i32.const 10) (i32.const 20) (local.get $c)))
+;; )
+(module binary
+  "\00\61\73\6d\01\00\00\00\01\0d\03\60\00\00\60\00\01\7f\60\01\7f\01\7f\03"
+  "\0f\0e\00\01\01\01\01\02\02\02\02\02\02\01\02\02\07\93\01\0e\05\65\6d\70"
+  "\74\79\00\00\08\73\69\6e\67\75\6c\61\72\00\01\06\6e\65\73\74\65\64\00\02"
+  "\08\62\72\2d\76\61\6c\75\65\00\03\08\62\72\2d\6f\75\74\65\72\00\04\05\62"
+  "\72\5f\69\66\00\05\02\69\66\00\06\0f\69\66\2d\77\69\74\68\6f\75\74\2d\65"
+  "\6c\73\65\00\07\08\62\72\5f\74\61\62\6c\65\00\08\0e\62\72\5f\74\61\62\6c"
+  "\65\2d\76\61\6c\75\65\00\09\03\73\75\6d\00\0a\0a\6c\6f\6f\70\2d\76\61\6c"
+  "\75\65\00\0b\06\72\65\74\75\72\6e\00\0c\06\73\65\6c\65\63\74\00\0d\0a\e8"
+  "\01\0e\08\00\02\40\0b\02\40\0b\0b\07\00\02\7f\41\07\0b\0b\10\00\02\7f\02"
+  "\7f\41\08\0b\1a\02\7f\41\09\0b\0b\0b\0b\00\02\7f\41\01\0c\00\41\02\0b\0b"
+  "\0e\00\02\7f\02\40\41\03\0c\01\0b\41\04\0b\0b\0e\00\02\7f\41\0a\20\00\0d"
+  "\00\1a\41\14\0b\0b\0c\00\20\00\04\7f\41\01\05\41\02\0b\0b\0f\01\01\7f\20"
+  "\00\04\40\41\05\21\01\0b\20\01\0b\25\00\02\40\02\40\02\40\02\40\20\00\0e"
+  "\03\00\01\02\03\0b\41\e4\00\0f\0b\41\e5\00\0f\0b\41\e6\00\0f\0b\41\e7\00"
+  "\0b\13\00\02\7f\02\7f\41\07\20\00\0e\01\00\01\0b\41\0a\6a\0b\0b\21\01\01"
+  "\7f\02\40\03\40\20\00\45\0d\01\20\01\20\00\6a\21\01\20\00\41\01\6b\21\00"
+  "\0c\00\0b\0b\20\01\0b\07\00\03\7f\41\06\0b\0b\0f\00\02\40\20\00\04\40\41"
+  "\01\0f\0b\0b\41\02\0b\09\00\41\0a\41\14\20\00\1b\0b"
+)
+
+(assert_return (invoke "empty"))
+(assert_return (invoke "singular") (i32.const 7))
+(assert_return (invoke "nested") (i32.const 9))
+(assert_return (invoke "br-value") (i32.const 1))
+(assert_return (invoke "br-outer") (i32.const 3))
+(assert_return (invoke "br_if" (i32.const 0)) (i32.const 20))
+(assert_return (invoke "br_if" (i32.const 1)) (i32.const 10))
+(assert_return (invoke "if" (i32.const 0)) (i32.const 2))
+(assert_return (invoke "if" (i32.const -1)) (i32.const 1))
+(assert_return (invoke "if-without-else" (i32.const 0)) (i32.const 0))
+(assert_return (invoke "if-without-else" (i32.const 1)) (i32.const 5))
+(assert_return (invoke "br_table" (i32.const 0)) (i32.const 100))
+(assert_return (invoke "br_table" (i32.const 1)) (i32.const 101))
+(assert_return (invoke "br_table" (i32.const 2)) (i32.const 102))
+(assert_return (invoke "br_table" (i32.const 3)) (i32.const 103))
+(assert_return (invoke "br_table" (i32.const -1)) (i32.const 103))
+(assert_return (invoke "br_table-value" (i32.const 0)) (i32.const 17))
+(assert_return (invoke "br_table-value" (i32.const 1)) (i32.const 7))
+(assert_return (invoke "br_table-value" (i32.const 9)) (i32.const 7))
+(assert_return (invoke "sum" (i32.const 0)) (i32.const 0))
+(assert_return (invoke "sum" (i32.const 100)) (i32.const 5050))
+(assert_return (invoke "loop-value") (i32.const 6))
+(assert_return (invoke "return" (i32.const 1)) (i32.const 1))
+(assert_return (invoke "return" (i32.const 0)) (i32.const 2))
+(assert_return (invoke "select" (i32.const 1)) (i32.const 10))
+(assert_return (invoke "select" (i32.const 0)) (i32.const 20))
@@ -0,0 +1,76 @@
+;; call, call_indirect and call stack exhaustion
+
+;; (module
+;;   (type $i32_to_i32 (func (param i32) (result i32)))
+;;   (type $void (func))
+;;   (table 4 funcref)
+;;   (elem (i32.const 0) $inc $dec $fac)
+;;   (func $inc (type $i32_to_i32) (i32.add (local.get 0) (i32.const 1)))
+;;   (func $dec (type $i32_to_i32) (i32.sub (local.get 0) (i32.const 1)))
+;;   (func $fac (export "fac") (param $n i64) (result i64)
+;;     (if (result i64) (i64.eqz (local.get $n))
+;;       (then (i64.const 1))
+;;       (else (i64.mul (local.get $n) (call $fac (i64.sub (local.get $n) (i64.const 1)))))))
+;;   (func $fib (export "fib") (param $n i32) (result i32)
+;;     (if (result i32) (i32.lt_u (local.get $n) (i32.const 2))
+;;       (then (local.get $n))
+;;       (else
+;;         (i32.add
+;;           (call $fib (i32.sub (local.get $n) (i32.const 1)))
+;;           (call $fib (i32.sub (local.get $n) (i32.const 2)))))))
+;;   (func $swap (export "swap") (param i32 i32) (result i32 i32) (local.get 1) (local.get 0))
+;;   (func (export "swap-sub") (param i32 i32) (result i32)
+;;     (call $swap (local.get 0) (local.get 1))
+;;     (i32.sub))
+;;   (func (export "dispatch") (param $i i32) (param $x i32) (result i32)
+;;     (call_indirect (type $i32_to_i32) (local.get $x) (local.get $i)))
+;;   (func (export "dispatch-void") (param $i i32)
+;;     (call_indirect (type $void) (local.get $i)))
+;;   (func $runaway (export "runaway") (call $runaway))
+;;   (func $even (export "even") (param $n i32) (result i32)
+;;     (if (result i32) (i32.eqz (local.get $n))
+;;       (then (i32.const 1))
+;;       (else (call $odd (i32.sub (local.get $n) (i32.const 1))))))
+;;   (func $odd (param $n i32) (result i32)
+;;     (if (result i32) (i32.eqz (local.get $n))
+;;       (then (i32.const 0))
+;;       (else (call $even (i32.sub (local.get $n) (i32.const 1))))))
+;;   (func $mutual (export "mutual-runaway") (call $mutual2))
+;;   (func $mutual2 (call $mutual))
+;; )
+(module binary
+  "\00\61\73\6d\01\00\00\00\01\1f\06\60\01\7f\01\7f\60\00\00\60\01\7e\01\7e"
+  "\60\02\7f\7f\02\7f\7f\60\02\7f\7f\01\7f\60\01\7f\00\03\0e\0d\00\00\02\00"
+  "\03\04\04\05\01\00\00\01\01\04\04\01\70\00\04\07\5c\09\03\66\61\63\00\02"
+  "\03\66\69\62\00\03\04\73\77\61\70\00\04\08\73\77\61\70\2d\73\75\62\00\05"
+  "\08\64\69\73\70\61\74\63\68\00\06\0d\64\69\73\70\61\74\63\68\2d\76\6f\69"
+  "\64\00\07\07\72\75\6e\61\77\61\79\00\08\04\65\76\65\6e\00\09\0e\6d\75\74"
+  "\75\61\6c\2d\72\75\6e\61\77\61\79\00\0b\09\09\01\00\41\00\0b\03\00\01\02"
+  "\0a\9c\01\0d\07\00\20\00\41\01\6a\0b\07\00\20\00\41\01\6b\0b\15\00\20\00"
+  "\50\04\7e\42\01\05\20\00\20\00\42\01\7d\10\02\7e\0b\0b\1c\00\20\00\41\02"
+  "\49\04\7f\20\00\05\20\00\41\01\6b\10\03\20\00\41\02\6b\10\03\6a\0b\0b\06"
+  "\00\20\01\20\00\0b\09\00\20\00\20\01\10\04\6b\0b\09\00\20\01\20\00\11\00"
+  "\00\0b\07\00\20\00\11\01\00\0b\04\00\10\08\0b\12\00\20\00\45\04\7f\41\01"
+  "\05\20\00\41\01\6b\10\0a\0b\0b\12\00\20\00\45\04\7f\41\00\05\20\00\41\01"
+  "\6b\10\09\0b\0b\04\00\10\0c\0b\04\00\10\0b\0b"
+)
+
+(assert_return (invoke "fac" (i64.const 0)) (i64.const 1))
+(assert_return (invoke "fac" (i64.const 5)) (i64.const 120))
+(assert_return (invoke "fac" (i64.const 25)) (i64.const 7034535277573963776))
+(assert_return (invoke "fib" (i32.const 0)) (i32.const 0))
+(assert_return (invoke "fib" (i32.const 1)) (i32.const 1))
+(assert_return (invoke "fib" (i32.const 20)) (i32.const 6765))
+(assert_return (invoke "swap" (i32.const 1) (i32.const 2)) (i32.const 2) (i32.const 1))
+(assert_return (invoke "swap-sub" (i32.const 1) (i32.const 3)) (i32.const 2))
+(assert_return (invoke "dispatch" (i32.const 0) (i32.const 10)) (i32.const 11))
+(assert_return (invoke "dispatch" (i32.const 1) (i32.const 10)) (i32.const 9))
+(assert_trap (invoke "dispatch" (i32.const 2) (i32.const 10)) "indirect call type mismatch")
+(assert_trap (invoke "dispatch" (i32.const 3) (i32.const 10)) "uninitialized element")
+(assert_trap (invoke "dispatch" (i32.const 4) (i32.const 10)) "undefined element")
+(assert_trap (invoke "dispatch-void" (i32.const 0)) "indirect call type mismatch")
+(assert_return (invoke "even" (i32.const 100)) (i32.const 1))
+(assert_return (invoke "even" (i32.const 77)) (i32.const 0))
+(assert_exhaustion (invoke "runaway") "call stack exhausted")
+(assert_exhaustion (invoke "mutual-runaway") "call stack exhausted")
+(assert_return (invoke "fac" (i64.const 3)) (i64.const 6))
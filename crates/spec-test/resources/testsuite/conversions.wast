@@ -0,0 +1,98 @@
+;; numeric conversions
+
+;; (module
+;;   (func (export "i64.extend_i32_s") (param $x i32) (result i64) (i64.extend_i32_s (local.get $x)))
+;;   (func (export "i64.extend_i32_u") (param $x i32) (result i64) (i64.extend_i32_u (local.get $x)))
+;;   (func (export "i32.wrap_i64") (param $x i64) (result i32) (i32.wrap_i64 (local.get $x)))
+;;   (func (export "i32.trunc_f32_s") (param $x f32) (result i32) (i32.trunc_f32_s (local.get $x)))
+;;   (func (export "i32.trunc_f32_u") (param $x f32) (result i32) (i32.trunc_f32_u (local.get $x)))
+;;   (func (export "i32.trunc_f64_s") (param $x f64) (result i32) (i32.trunc_f64_s (local.get $x)))
+;;   (func (export "i64.trunc_f64_u") (param $x f64) (result i64) (i64.trunc_f64_u (local.get $x)))
+;;   (func (export "f32.convert_i32_s") (param $x i32) (result f32) (f32.convert_i32_s (local.get $x)))
+;;   (func (export "f32.convert_i32_u") (param $x i32) (result f32) (f32.convert_i32_u (local.get $x)))
+;;   (func (export "f64.convert_i64_s") (param $x i64) (result f64) (f64.convert_i64_s (local.get $x)))
+;;   (func (export "f64.convert_i64_u") (param $x i64) (result f64) (f64.convert_i64_u (local.get $x)))
+;;   (func (export "f32.demote_f64") (param $x f64) (result f32) (f32.demote_f64 (local.get $x)))
+;;   (func (export "f64.promote_f32") (param $x f32) (result f64) (f64.promote_f32 (local.get $x)))
+;;   (func (export "i32.reinterpret_f32") (param $x f32) (result i32) (i32.reinterpret_f32 (local.get $x)))
+;;   (func (export "f32.reinterpret_i32") (param $x i32) (result f32) (f32.reinterpret_i32 (local.get $x)))
+;;   (func (export "i64.reinterpret_f64") (param $x f64) (result i64) (i64.reinterpret_f64 (local.get $x)))
+;;   (func (export "f64.reinterpret_i64") (param $x i64) (result f64) (f64.reinterpret_i64 (local.get $x)))
+;; )
+(module binary
+  "\00\61\73\6d\01\00\00\00\01\2e\09\60\01\7f\01\7e\60\01\7e\01\7f\60\01\7d"
+  "\01\7f\60\01\7c\01\7f\60\01\7c\01\7e\60\01\7f\01\7d\60\01\7e\01\7c\60\01"
+  "\7c\01\7d\60\01\7d\01\7c\03\12\11\00\00\01\02\02\03\04\05\05\06\06\07\08"
+  "\02\05\04\06\07\c9\02\11\10\69\36\34\2e\65\78\74\65\6e\64\5f\69\33\32\5f"
+  "\73\00\00\10\69\36\34\2e\65\78\74\65\6e\64\5f\69\33\32\5f\75\00\01\0c\69"
+  "\33\32\2e\77\72\61\70\5f\69\36\34\00\02\0f\69\33\32\2e\74\72\75\6e\63\5f"
+  "\66\33\32\5f\73\00\03\0f\69\33\32\2e\74\72\75\6e\63\5f\66\33\32\5f\75\00"
+  "\04\0f\69\33\32\2e\74\72\75\6e\63\5f\66\36\34\5f\73\00\05\0f\69\36\34\2e"
+  "\74\72\75\6e\63\5f\66\36\34\5f\75\00\06\11\66\33\32\2e\63\6f\6e\76\65\72"
+  "\74\5f\69\33\32\5f\73\00\07\11\66\33\32\2e\63\6f\6e\76\65\72\74\5f\69\33"
+  "\32\5f\75\00\08\11\66\36\34\2e\63\6f\6e\76\65\72\74\5f\69\36\34\5f\73\00"
+  "\09\11\66\36\34\2e\63\6f\6e\76\65\72\74\5f\69\36\34\5f\75\00\0a\0e\66\33"
+  "\32\2e\64\65\6d\6f\74\65\5f\66\36\34\00\0b\0f\66\36\34\2e\70\72\6f\6d\6f"
+  "\74\65\5f\66\33\32\00\0c\13\69\33\32\2e\72\65\69\6e\74\65\72\70\72\65\74"
+  "\5f\66\33\32\00\0d\13\66\33\32\2e\72\65\69\6e\74\65\72\70\72\65\74\5f\69"
+  "\33\32\00\0e\13\69\36\34\2e\72\65\69\6e\74\65\72\70\72\65\74\5f\66\36\34"
+  "\00\0f\13\66\36\34\2e\72\65\69\6e\74\65\72\70\72\65\74\5f\69\36\34\00\10"
+  "\0a\67\11\05\00\20\00\ac\0b\05\00\20\00\ad\0b\05\00\20\00\a7\0b\05\00\20"
+  "\00\a8\0b\05\00\20\00\a9\0b\05\00\20\00\aa\0b\05\00\20\00\b1\0b\05\00\20"
+  "\00\b2\0b\05\00\20\00\b3\0b\05\00\20\00\b9\0b\05\00\20\00\ba\0b\05\00\20"
+  "\00\b6\0b\05\00\20\00\bb\0b\05\00\20\00\bc\0b\05\00\20\00\be\0b\05\00\20"
+  "\00\bd\0b\05\00\20\00\bf\0b"
+)
+
+(assert_return (invoke "i64.extend_i32_s" (i32.const -10000)) (i64.const -10000))
+(assert_return (invoke "i64.extend_i32_s" (i32.const 0x80000000)) (i64.const 0xffffffff80000000))
+(assert_return (invoke "i64.extend_i32_u" (i32.const -10000)) (i64.const 0x00000000ffffd8f0))
+(assert_return (invoke "i64.extend_i32_u" (i32.const 0x80000000)) (i64.const 0x0000000080000000))
+(assert_return (invoke "i32.wrap_i64" (i64.const -100000)) (i32.const -100000))
+(assert_return (invoke "i32.wrap_i64" (i64.const 0xffffffff00000000)) (i32.const 0))
+(assert_return (invoke "i32.wrap_i64" (i64.const 0x0000000100000001)) (i32.const 1))
+
+(assert_return (invoke "i32.trunc_f32_s" (f32.const -0x1.ccccccp-1)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f32_s" (f32.const -0x1.19999ap+0)) (i32.const -1))
+(assert_return (invoke "i32.trunc_f32_s" (f32.const 2147483520.0)) (i32.const 2147483520))
+(assert_return (invoke "i32.trunc_f32_s" (f32.const -2147483648.0)) (i32.const -2147483648))
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const 2147483648.0)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const -2147483904.0)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const inf)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const nan)) "invalid conversion to integer")
+(assert_return (invoke "i32.trunc_f32_u" (f32.const 4294967040.0)) (i32.const -256))
+(assert_return (invoke "i32.trunc_f32_u" (f32.const -0x1.ccccccp-1)) (i32.const 0))
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const 4294967296.0)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const -1.0)) "integer overflow")
+(assert_return (invoke "i32.trunc_f64_s" (f64.const -2147483648.9)) (i32.const -2147483648))
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const -2147483649.0)) "integer overflow")
+(assert_return (invoke "i64.trunc_f64_u" (f64.const 18446744073709549568.0)) (i64.const -2048))
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const 18446744073709551616.0)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const nan)) "invalid conversion to integer")
+
+(assert_return (invoke "f32.convert_i32_s" (i32.const 1)) (f32.const 1.0))
+(assert_return (invoke "f32.convert_i32_s" (i32.const -2147483648)) (f32.const -2147483648))
+(assert_return (invoke "f32.convert_i32_s" (i32.const 1234567890)) (f32.const 0x1.26580cp+30))
+(assert_return (invoke "f32.convert_i32_s" (i32.const 16777217)) (f32.const 16777216.0))
+(assert_return (invoke "f32.convert_i32_s" (i32.const 16777219)) (f32.const 16777220.0))
+(assert_return (invoke "f32.convert_i32_u" (i32.const 0x80000000)) (f32.const 2147483648))
+(assert_return (invoke "f32.convert_i32_u" (i32.const 0xffffffff)) (f32.const 4294967296.0))
+(assert_return (invoke "f64.convert_i64_s" (i64.const -9223372036854775808)) (f64.const -9223372036854775808))
+(assert_return (invoke "f64.convert_i64_s" (i64.const 9007199254740993)) (f64.const 9007199254740992))
+(assert_return (invoke "f64.convert_i64_u" (i64.const 0xffffffffffffffff)) (f64.const 18446744073709551616.0))
+
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.fffffe0000000p+127)) (f32.const 0x1.fffffep+127))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.fffffefffffffp+127)) (f32.const 0x1.fffffep+127))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.ffffffp+127)) (f32.const inf))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1p-150)) (f32.const 0x0p+0))
+(assert_return (invoke "f32.demote_f64" (f64.const nan)) (f32.const nan:canonical))
+(assert_return (invoke "f64.promote_f32" (f32.const 0x1p-149)) (f64.const 0x1p-149))
+(assert_return (invoke "f64.promote_f32" (f32.const -inf)) (f64.const -inf))
+(assert_return (invoke "f64.promote_f32" (f32.const nan)) (f64.const nan:canonical))
+
+(assert_return (invoke "i32.reinterpret_f32" (f32.const -0x0p+0)) (i32.const 0x80000000))
+(assert_return (invoke "i32.reinterpret_f32" (f32.const nan:0x200000)) (i32.const 0x7fa00000))
+(assert_return (invoke "f32.reinterpret_i32" (i32.const 0x7f800000)) (f32.const inf))
+(assert_return (invoke "f32.reinterpret_i32" (i32.const 0xffc00000)) (f32.const -nan))
+(assert_return (invoke "i64.reinterpret_f64" (f64.const -0x0p+0)) (i64.const 0x8000000000000000))
+(assert_return (invoke "f64.reinterpret_i64" (i64.const 0x7ff4000000000000)) (f64.const nan:0x4000000000000))
@@ -0,0 +1,109 @@
+;; f32 operations
+
+;; (module
+;;   (func (export "add") (param $x f32) (param $y f32) (result f32) (f32.add (local.get $x) (local.get $y)))
+;;   (func (export "sub") (param $x f32) (param $y f32) (result f32) (f32.sub (local.get $x) (local.get $y)))
+;;   (func (export "mul") (param $x f32) (param $y f32) (result f32) (f32.mul (local.get $x) (local.get $y)))
+;;   (func (export "div") (param $x f32) (param $y f32) (result f32) (f32.div (local.get $x) (local.get $y)))
+;;   (func (export "sqrt") (param $x f32) (result f32) (f32.sqrt (local.get $x)))
+;;   (func (export "min") (param $x f32) (param $y f32) (result f32) (f32.min (local.get $x) (local.get $y)))
+;;   (func (export "max") (param $x f32) (param $y f32) (result f32) (f32.max (local.get $x) (local.get $y)))
+;;   (func (export "ceil") (param $x f32) (result f32) (f32.ceil (local.get $x)))
+;;   (func (export "floor") (param $x f32) (result f32) (f32.floor (local.get $x)))
+;;   (func (export "trunc") (param $x f32) (result f32) (f32.trunc (local.get $x)))
+;;   (func (export "nearest") (param $x f32) (result f32) (f32.nearest (local.get $x)))
+;;   (func (export "abs") (param $x f32) (result f32) (f32.abs (local.get $x)))
+;;   (func (export "neg") (param $x f32) (result f32) (f32.neg (local.get $x)))
+;;   (func (export "copysign") (param $x f32) (param $y f32) (result f32) (f32.copysign (local.get $x) (local.get $y)))
+;;   (func (export "eq") (param $x f32) (param $y f32) (result i32) (f32.eq (local.get $x) (local.get $y)))
+;;   (func (export "ne") (param $x f32) (param $y f32) (result i32) (f32.ne (local.get $x) (local.get $y)))
+;;   (func (export "lt") (param $x f32) (param $y f32) (result i32) (f32.lt (local.get $x) (local.get $y)))
+;;   (func (export "ge") (param $x f32) (param $y f32) (result i32) (f32.ge (local.get $x) (local.get $y)))
+;; )
+(module binary
+  "\00\61\73\6d\01\00\00\00\01\12\03\60\02\7d\7d\01\7d\60\01\7d\01\7d\60\02"
+  "\7d\7d\01\7f\03\13\12\00\00\00\00\01\00\00\01\01\01\01\01\01\00\02\02\02"
+  "\02\07\78\12\03\61\64\64\00\00\03\73\75\62\00\01\03\6d\75\6c\00\02\03\64"
+  "\69\76\00\03\04\73\71\72\74\00\04\03\6d\69\6e\00\05\03\6d\61\78\00\06\04"
+  "\63\65\69\6c\00\07\05\66\6c\6f\6f\72\00\08\05\74\72\75\6e\63\00\09\07\6e"
+  "\65\61\72\65\73\74\00\0a\03\61\62\73\00\0b\03\6e\65\67\00\0c\08\63\6f\70"
+  "\79\73\69\67\6e\00\0d\02\65\71\00\0e\02\6e\65\00\0f\02\6c\74\00\10\02\67"
+  "\65\00\11\0a\83\01\12\07\00\20\00\20\01\92\0b\07\00\20\00\20\01\93\0b\07"
+  "\00\20\00\20\01\94\0b\07\00\20\00\20\01\95\0b\05\00\20\00\91\0b\07\00\20"
+  "\00\20\01\96\0b\07\00\20\00\20\01\97\0b\05\00\20\00\8d\0b\05\00\20\00\8e"
+  "\0b\05\00\20\00\8f\0b\05\00\20\00\90\0b\05\00\20\00\8b\0b\05\00\20\00\8c"
+  "\0b\07\00\20\00\20\01\98\0b\07\00\20\00\20\01\5b\0b\07\00\20\00\20\01\5c"
+  "\0b\07\00\20\00\20\01\5d\0b\07\00\20\00\20\01\60\0b"
+)
+
+(assert_return (invoke "add" (f32.const 0x1p+0) (f32.const 0x1p+0)) (f32.const 0x1p+1))
+(assert_return (invoke "add" (f32.const -0x0p+0) (f32.const -0x0p+0)) (f32.const -0x0p+0))
+(assert_return (invoke "add" (f32.const -0x0p+0) (f32.const 0x0p+0)) (f32.const 0x0p+0))
+(assert_return (invoke "add" (f32.const 0x1p-149) (f32.const 0x1p-149)) (f32.const 0x1p-148))
+(assert_return (invoke "add" (f32.const 0x1.fffffep+127) (f32.const 0x1.fffffep+127)) (f32.const inf))
+(assert_return (invoke "add" (f32.const inf) (f32.const -inf)) (f32.const nan:canonical))
+(assert_return (invoke "add" (f32.const nan) (f32.const 1.0)) (f32.const nan:canonical))
+(assert_return (invoke "add" (f32.const nan:0x200000) (f32.const 1.0)) (f32.const nan:arithmetic))
+(assert_return (invoke "add" (f32.const 1.1) (f32.const 2.2)) (f32.const 3.3000002))
+
+(assert_return (invoke "sub" (f32.const 0x1p+0) (f32.const 0x1p+0)) (f32.const 0x0p+0))
+(assert_return (invoke "sub" (f32.const -0x0p+0) (f32.const 0x0p+0)) (f32.const -0x0p+0))
+(assert_return (invoke "sub" (f32.const inf) (f32.const inf)) (f32.const nan:canonical))
+
+(assert_return (invoke "mul" (f32.const 0x1p+0) (f32.const -0x0p+0)) (f32.const -0x0p+0))
+(assert_return (invoke "mul" (f32.const -0x1p-1) (f32.const -0x1p-1)) (f32.const 0x1p-2))
+(assert_return (invoke "mul" (f32.const inf) (f32.const 0x0p+0)) (f32.const nan:canonical))
+(assert_return (invoke "mul" (f32.const 0x1p-126) (f32.const 0x1p-23)) (f32.const 0x1p-149))
+
+(assert_return (invoke "div" (f32.const 0x1p+0) (f32.const 0x0p+0)) (f32.const inf))
+(assert_return (invoke "div" (f32.const -0x1p+0) (f32.const 0x0p+0)) (f32.const -inf))
+(assert_return (invoke "div" (f32.const 0x0p+0) (f32.const 0x0p+0)) (f32.const nan:canonical))
+(assert_return (invoke "div" (f32.const 1.0) (f32.const 3.0)) (f32.const 0x1.555556p-2))
+
+(assert_return (invoke "sqrt" (f32.const 0x1p+2)) (f32.const 0x1p+1))
+(assert_return (invoke "sqrt" (f32.const -0x0p+0)) (f32.const -0x0p+0))
+(assert_return (invoke "sqrt" (f32.const -0x1p+0)) (f32.const nan:canonical))
+(assert_return (invoke "sqrt" (f32.const inf)) (f32.const inf))
+(assert_return (invoke "sqrt" (f32.const 2.0)) (f32.const 0x1.6a09e6p+0))
+
+(assert_return (invoke "min" (f32.const 0x0p+0) (f32.const -0x0p+0)) (f32.const -0x0p+0))
+(assert_return (invoke "min" (f32.const -0x0p+0) (f32.const 0x0p+0)) (f32.const -0x0p+0))
+(assert_return (invoke "min" (f32.const 1.0) (f32.const nan)) (f32.const nan:canonical))
+(assert_return (invoke "min" (f32.const -inf) (f32.const 1.0)) (f32.const -inf))
+(assert_return (invoke "max" (f32.const 0x0p+0) (f32.const -0x0p+0)) (f32.const 0x0p+0))
+(assert_return (invoke "max" (f32.const -0x0p+0) (f32.const 0x0p+0)) (f32.const 0x0p+0))
+(assert_return (invoke "max" (f32.const nan) (f32.const 1.0)) (f32.const nan:canonical))
+(assert_return (invoke "max" (f32.const inf) (f32.const 1.0)) (f32.const inf))
+
+(assert_return (invoke "ceil" (f32.const -0x1p-1)) (f32.const -0x0p+0))
+(assert_return (invoke "ceil" (f32.const 0x1p-1)) (f32.const 0x1p+0))
+(assert_return (invoke "ceil" (f32.const -inf)) (f32.const -inf))
+(assert_return (invoke "floor" (f32.const -0x1p-1)) (f32.const -0x1p+0))
+(assert_return (invoke "floor" (f32.const 0x1p-1)) (f32.const 0x0p+0))
+(assert_return (invoke "trunc" (f32.const -0x1.8p+0)) (f32.const -0x1p+0))
+(assert_return (invoke "trunc" (f32.const 0x1.8p+0)) (f32.const 0x1p+0))
+(assert_return (invoke "nearest" (f32.const 0x1p-1)) (f32.const 0x0p+0))
+(assert_return (invoke "nearest" (f32.const -0x1p-1)) (f32.const -0x0p+0))
+(assert_return (invoke "nearest" (f32.const 0x1.8p+0)) (f32.const 0x1p+1))
+(assert_return (invoke "nearest" (f32.const 0x1.4p+1)) (f32.const 0x1p+1))
+(assert_return (invoke "nearest" (f32.const -0x1.cp+1)) (f32.const -0x1p+2))
+(assert_return (invoke "nearest" (f32.const nan)) (f32.const nan:canonical))
+
+(assert_return (invoke "abs" (f32.const -0x0p+0)) (f32.const 0x0p+0))
+(assert_return (invoke "abs" (f32.const -inf)) (f32.const inf))
+(assert_return (invoke "abs" (f32.const -nan)) (f32.const nan))
+(assert_return (invoke "abs" (f32.const -nan:0x0f1e2)) (f32.const nan:0x0f1e2))
+(assert_return (invoke "neg" (f32.const 0x0p+0)) (f32.const -0x0p+0))
+(assert_return (invoke "neg" (f32.const nan)) (f32.const -nan))
+(assert_return (invoke "neg" (f32.const -nan:0x0f1e2)) (f32.const nan:0x0f1e2))
+(assert_return (invoke "copysign" (f32.const 0x1p+0) (f32.const -0x0p+0)) (f32.const -0x1p+0))
+(assert_return (invoke "copysign" (f32.const -inf) (f32.const 0x0p+0)) (f32.const inf))
+(assert_return (invoke "copysign" (f32.const nan) (f32.const -1.0)) (f32.const -nan))
+
+(assert_return (invoke "eq" (f32.const 0x0p+0) (f32.const -0x0p+0)) (i32.const 1))
+(assert_return (invoke "eq" (f32.const nan) (f32.const nan)) (i32.const 0))
+(assert_return (invoke "ne" (f32.const nan) (f32.const nan)) (i32.const 1))
+(assert_return (invoke "lt" (f32.const -0x0p+0) (f32.const 0x0p+0)) (i32.const 0))
+(assert_return (invoke "lt" (f32.const -inf) (f32.const 0x1p-149)) (i32.const 1))
+(assert_return (invoke "ge" (f32.const nan) (f32.const 0x0p+0)) (i32.const 0))
+(assert_return (invoke "ge" (f32.const inf) (f32.const inf)) (i32.const 1))
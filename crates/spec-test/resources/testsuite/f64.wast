@@ -0,0 +1,109 @@
+;; f64 operations
+
+;; (module
+;;   (func (export "add") (param $x f64) (param $y f64) (result f64) (f64.add (local.get $x) (local.get $y)))
+;;   (func (export "sub") (param $x f64) (param $y f64) (result f64) (f64.sub (local.get $x) (local.get $y)))
+;;   (func (export "mul") (param $x f64) (param $y f64) (result f64) (f64.mul (local.get $x) (local.get $y)))
+;;   (func (export "div") (param $x f64) (param $y f64) (result f64) (f64.div (local.get $x) (local.get $y)))
+;;   (func (export "sqrt") (param $x f64) (result f64) (f64.sqrt (local.get $x)))
+;;   (func (export "min") (param $x f64) (param $y f64) (result f64) (f64.min (local.get $x) (local.get $y)))
+;;   (func (export "max") (param $x f64) (param $y f64) (result f64) (f64.max (local.get $x) (local.get $y)))
+;;   (func (export "ceil") (param $x f64) (result f64) (f64.ceil (local.get $x)))
+;;   (func (export "floor") (param $x f64) (result f64) (f64.floor (local.get $x)))
+;;   (func (export "trunc") (param $x f64) (result f64) (f64.trunc (local.get $x)))
+;;   (func (export "nearest") (param $x f64) (result f64) (f64.nearest (local.get $x)))
+;;   (func (export "abs") (param $x f64) (result f64) (f64.abs (local.get $x)))
+;;   (func (export "neg") (param $x f64) (result f64) (f64.neg (local.get $x)))
+;;   (func (export "copysign") (param $x f64) (param $y f64) (result f64) (f64.copysign (local.get $x) (local.get $y)))
+;;   (func (export "eq") (param $x f64) (param $y f64) (result i32) (f64.eq (local.get $x) (local.get $y)))
+;;   (func (export "ne") (param $x f64) (param $y f64) (result i32) (f64.ne (local.get $x) (local.get $y)))
+;;   (func (export "lt") (param $x f64) (param $y f64) (result i32) (f64.lt (local.get $x) (local.get $y)))
+;;   (func (export "ge") (param $x f64) (param $y f64) (result i32) (f64.ge (local.get $x) (local.get $y)))
+;; )
+(module binary
+  "\00\61\73\6d\01\00\00\00\01\12\03\60\02\7c\7c\01\7c\60\01\7c\01\7c\60\02"
+  "\7c\7c\01\7f\03\13\12\00\00\00\00\01\00\00\01\01\01\01\01\01\00\02\02\02"
+  "\02\07\78\12\03\61\64\64\00\00\03\73\75\62\00\01\03\6d\75\6c\00\02\03\64"
+  "\69\76\00\03\04\73\71\72\74\00\04\03\6d\69\6e\00\05\03\6d\61\78\00\06\04"
+  "\63\65\69\6c\00\07\05\66\6c\6f\6f\72\00\08\05\74\72\75\6e\63\00\09\07\6e"
+  "\65\61\72\65\73\74\00\0a\03\61\62\73\00\0b\03\6e\65\67\00\0c\08\63\6f\70"
+  "\79\73\69\67\6e\00\0d\02\65\71\00\0e\02\6e\65\00\0f\02\6c\74\00\10\02\67"
+  "\65\00\11\0a\83\01\12\07\00\20\00\20\01\a0\0b\07\00\20\00\20\01\a1\0b\07"
+  "\00\20\00\20\01\a2\0b\07\00\20\00\20\01\a3\0b\05\00\20\00\9f\0b\07\00\20"
+  "\00\20\01\a4\0b\07\00\20\00\20\01\a5\0b\05\00\20\00\9b\0b\05\00\20\00\9c"
+  "\0b\05\00\20\00\9d\0b\05\00\20\00\9e\0b\05\00\20\00\99\0b\05\00\20\00\9a"
+  "\0b\07\00\20\00\20\01\a6\0b\07\00\20\00\20\01\61\0b\07\00\20\00\20\01\62"
+  "\0b\07\00\20\00\20\01\63\0b\07\00\20\00\20\01\66\0b"
+)
+
+(assert_return (invoke "add" (f64.const 0x1p+0) (f64.const 0x1p+0)) (f64.const 0x1p+1))
+(assert_return (invoke "add" (f64.const -0x0p+0) (f64.const -0x0p+0)) (f64.const -0x0p+0))
+(assert_return (invoke "add" (f64.const -0x0p+0) (f64.const 0x0p+0)) (f64.const 0x0p+0))
+(assert_return (invoke "add" (f64.const 0x1p-1074) (f64.const 0x1p-1074)) (f64.const 0x1p-1073))
+(assert_return (invoke "add" (f64.const 0x1.fffffffffffffp+1023) (f64.const 0x1.fffffffffffffp+1023)) (f64.const inf))
+(assert_return (invoke "add" (f64.const inf) (f64.const -inf)) (f64.const nan:canonical))
+(assert_return (invoke "add" (f64.const nan) (f64.const 1.0)) (f64.const nan:canonical))
+(assert_return (invoke "add" (f64.const nan:0x4000000000000) (f64.const 1.0)) (f64.const nan:arithmetic))
+(assert_return (invoke "add" (f64.const 1.1) (f64.const 2.2)) (f64.const 3.3000000000000003))
+
+(assert_return (invoke "sub" (f64.const 0x1p+0) (f64.const 0x1p+0)) (f64.const 0x0p+0))
+(assert_return (invoke "sub" (f64.const -0x0p+0) (f64.const 0x0p+0)) (f64.const -0x0p+0))
+(assert_return (invoke "sub" (f64.const inf) (f64.const inf)) (f64.const nan:canonical))
+
+(assert_return (invoke "mul" (f64.const 0x1p+0) (f64.const -0x0p+0)) (f64.const -0x0p+0))
+(assert_return (invoke "mul" (f64.const -0x1p-1) (f64.const -0x1p-1)) (f64.const 0x1p-2))
+(assert_return (invoke "mul" (f64.const inf) (f64.const 0x0p+0)) (f64.const nan:canonical))
+(assert_return (invoke "mul" (f64.const 0x1p-1022) (f64.const 0x1p-52)) (f64.const 0x1p-1074))
+
+(assert_return (invoke "div" (f64.const 0x1p+0) (f64.const 0x0p+0)) (f64.const inf))
+(assert_return (invoke "div" (f64.const -0x1p+0) (f64.const 0x0p+0)) (f64.const -inf))
+(assert_return (invoke "div" (f64.const 0x0p+0) (f64.const 0x0p+0)) (f64.const nan:canonical))
+(assert_return (invoke "div" (f64.const 1.0) (f64.const 3.0)) (f64.const 0x1.5555555555555p-2))
+
+(assert_return (invoke "sqrt" (f64.const 0x1p+2)) (f64.const 0x1p+1))
+(assert_return (invoke "sqrt" (f64.const -0x0p+0)) (f64.const -0x0p+0))
+(assert_return (invoke "sqrt" (f64.const -0x1p+0)) (f64.const nan:canonical))
+(assert_return (invoke "sqrt" (f64.const inf)) (f64.const inf))
+(assert_return (invoke "sqrt" (f64.const 2.0)) (f64.const 0x1.6a09e667f3bcdp+0))
+
+(assert_return (invoke "min" (f64.const 0x0p+0) (f64.const -0x0p+0)) (f64.const -0x0p+0))
+(assert_return (invoke "min" (f64.const -0x0p+0) (f64.const 0x0p+0)) (f64.const -0x0p+0))
+(assert_return (invoke "min" (f64.const 1.0) (f64.const nan)) (f64.const nan:canonical))
+(assert_return (invoke "min" (f64.const -inf) (f64.const 1.0)) (f64.const -inf))
+(assert_return (invoke "max" (f64.const 0x0p+0) (f64.const -0x0p+0)) (f64.const 0x0p+0))
+(assert_return (invoke "max" (f64.const -0x0p+0) (f64.const 0x0p+0)) (f64.const 0x0p+0))
+(assert_return (invoke "max" (f64.const nan) (f64.const 1.0)) (f64.const nan:canonical))
+(assert_return (invoke "max" (f64.const inf) (f64.const 1.0)) (f64.const inf))
+
+(assert_return (invoke "ceil" (f64.const -0x1p-1)) (f64.const -0x0p+0))
+(assert_return (invoke "ceil" (f64.const 0x1p-1)) (f64.const 0x1p+0))
+(assert_return (invoke "ceil" (f64.const -inf)) (f64.const -inf))
+(assert_return (invoke "floor" (f64.const -0x1p-1)) (f64.const -0x1p+0))
+(assert_return (invoke "floor" (f64.const 0x1p-1)) (f64.const 0x0p+0))
+(assert_return (invoke "trunc" (f64.const -0x1.8p+0)) (f64.const -0x1p+0))
+(assert_return (invoke "trunc" (f64.const 0x1.8p+0)) (f64.const 0x1p+0))
+(assert_return (invoke "nearest" (f64.const 0x1p-1)) (f64.const 0x0p+0))
+(assert_return (invoke "nearest" (f64.const -0x1p-1)) (f64.const -0x0p+0))
+(assert_return (invoke "nearest" (f64.const 0x1.8p+0)) (f64.const 0x1p+1))
+(assert_return (invoke "nearest" (f64.const 0x1.4p+1)) (f64.const 0x1p+1))
+(assert_return (invoke "nearest" (f64.const -0x1.cp+1)) (f64.const -0x1p+2))
+(assert_return (invoke "nearest" (f64.const nan)) (f64.const nan:canonical))
+
+(assert_return (invoke "abs" (f64.const -0x0p+0)) (f64.const 0x0p+0))
+(assert_return (invoke "abs" (f64.const -inf)) (f64.const inf))
+(assert_return (invoke "abs" (f64.const -nan)) (f64.const nan))
+(assert_return (invoke "abs" (f64.const -nan:0x0f1e2)) (f64.const nan:0x0f1e2))
+(assert_return (invoke "neg" (f64.const 0x0p+0)) (f64.const -0x0p+0))
+(assert_return (invoke "neg" (f64.const nan)) (f64.const -nan))
+(assert_return (invoke "neg" (f64.const -nan:0x0f1e2)) (f64.const nan:0x0f1e2))
+(assert_return (invoke "copysign" (f64.const 0x1p+0) (f64.const -0x0p+0)) (f64.const -0x1p+0))
+(assert_return (invoke "copysign" (f64.const -inf) (f64.const 0x0p+0)) (f64.const inf))
+(assert_return (invoke "copysign" (f64.const nan) (f64.const -1.0)) (f64.const -nan))
+
+(assert_return (invoke "eq" (f64.const 0x0p+0) (f64.const -0x0p+0)) (i32.const 1))
+(assert_return (invoke "eq" (f64.const nan) (f64.const nan)) (i32.const 0))
+(assert_return (invoke "ne" (f64.const nan) (f64.const nan)) (i32.const 1))
+(assert_return (invoke "lt" (f64.const -0x0p+0) (f64.const 0x0p+0)) (i32.const 0))
+(assert_return (invoke "lt" (f64.const -inf) (f64.const 0x1p-1074)) (i32.const 1))
+(assert_return (invoke "ge" (f64.const nan) (f64.const 0x0p+0)) (i32.const 0))
+(assert_return (invoke "ge" (f64.const inf) (f64.const inf)) (i32.const 1))
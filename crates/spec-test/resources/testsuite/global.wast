@@ -0,0 +1,90 @@
+;; global variables
+
+;; (module
+;;   (global $spectest_i32 (import "spectest" "global_i32") i32)
+;;   (global $spectest_f64 (import "spectest" "global_f64") f64)
+;;   (global $a i32 (i32.const -2))
+;;   (global $b (mut i64) (i64.const -5))
+;;   (global $c (mut f32) (f32.const 1.5))
+;;   (global $d i32 (global.get $spectest_i32))
+;;   (global (export "exported") i64 (i64.const 42))
+;;   (func (export "get-a") (result i32) (global.get $a))
+;;   (func (export "get-b") (result i64) (global.get $b))
+;;   (func (export "get-c") (result f32) (global.get $c))
+;;   (func (export "get-d") (result i32) (global.get $d))
+;;   (func (export "get-spectest-i32") (result i32) (global.get $spectest_i32))
+;;   (func (export "get-spectest-f64") (result f64) (global.get $spectest_f64))
+;;   (func (export "set-b") (param i64) (global.set $b (local.get 0)))
+;;   (func (export "set-c") (param f32) (global.set $c (local.get 0)))
+;; )
+(module binary
+  "\00\61\73\6d\01\00\00\00\01\19\06\60\00\01\7f\60\00\01\7e\60\00\01\7d\60"
+  "\00\01\7c\60\01\7e\00\60\01\7d\00\02\2f\02\08\73\70\65\63\74\65\73\74\0a"
+  "\67\6c\6f\62\61\6c\5f\69\33\32\03\7f\00\08\73\70\65\63\74\65\73\74\0a\67"
+  "\6c\6f\62\61\6c\5f\66\36\34\03\7c\00\03\09\08\00\01\02\00\00\03\04\05\06"
+  "\1d\05\7f\00\41\7e\0b\7e\01\42\7b\0b\7d\01\43\00\00\c0\3f\0b\7f\00\23\00"
+  "\0b\7e\00\42\2a\0b\07\62\09\08\65\78\70\6f\72\74\65\64\03\06\05\67\65\74"
+  "\2d\61\00\00\05\67\65\74\2d\62\00\01\05\67\65\74\2d\63\00\02\05\67\65\74"
+  "\2d\64\00\03\10\67\65\74\2d\73\70\65\63\74\65\73\74\2d\69\33\32\00\04\10"
+  "\67\65\74\2d\73\70\65\63\74\65\73\74\2d\66\36\34\00\05\05\73\65\74\2d\62"
+  "\00\06\05\73\65\74\2d\63\00\07\0a\2d\08\04\00\23\02\0b\04\00\23\03\0b\04"
+  "\00\23\04\0b\04\00\23\05\0b\04\00\23\00\0b\04\00\23\01\0b\06\00\20\00\24"
+  "\03\0b\06\00\20\00\24\04\0b"
+)
+
+(assert_return (invoke "get-a") (i32.const -2))
+(assert_return (invoke "get-b") (i64.const -5))
+(assert_return (invoke "get-c") (f32.const 1.5))
+(assert_return (invoke "get-d") (i32.const 666))
+(assert_return (invoke "get-spectest-i32") (i32.const 666))
+(assert_return (invoke "get-spectest-f64") (f64.const 666.6))
+(assert_return (get "exported") (i64.const 42))
+(assert_return (invoke "set-b" (i64.const 0xfffffffffffffff)))
+(assert_return (invoke "get-b") (i64.const 0xfffffffffffffff))
+(assert_return (invoke "set-c" (f32.const -inf)))
+(assert_return (invoke "get-c") (f32.const -inf))
+
+;; (module (global i32 (i32.const 0)) (func (global.set 0 (i32.const 1))))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\04\01\60\00\00\03\02\01\00\06\06\01\7f\00\41"
+    "\00\0b\0a\08\01\06\00\41\01\24\00\0b"
+  )
+  "global is immutable"
+)
+;; (module (global i32 (i64.const 0)))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\06\06\01\7f\00\42\00\0b"
+  )
+  "type mismatch"
+)
+;; (module (global i32 (i32.const 0) (i32.const 0)))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\06\08\01\7f\00\41\00\41\00\0b"
+  )
+  "type mismatch"
+)
+;; (module (global (mut i32) (i32.const 0)) (global i32 (global.get 0)))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\06\0b\02\7f\01\41\00\0b\7f\00\23\00\0b"
+  )
+  "unknown global"
+)
+;; (module (global i32 (i32.div_s (i32.const 1) (i32.const 2))))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\06\09\01\7f\00\41\01\41\02\6d\0b"
+  )
+  "constant expression required"
+)
+;; (module (func (result i32) (global.get 0)))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\05\01\60\00\01\7f\03\02\01\00\0a\06\01\04\00"
+    "\23\00\0b"
+  )
+  "unknown global"
+)
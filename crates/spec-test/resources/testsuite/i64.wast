@@ -0,0 +1,77 @@
+;; i64 operations
+
+;; (module
+;;   (func (export "add") (param $x i64) (param $y i64) (result i64) (i64.add (local.get $x) (local.get $y)))
+;;   (func (export "sub") (param $x i64) (param $y i64) (result i64) (i64.sub (local.get $x) (local.get $y)))
+;;   (func (export "mul") (param $x i64) (param $y i64) (result i64) (i64.mul (local.get $x) (local.get $y)))
+;;   (func (export "div_s") (param $x i64) (param $y i64) (result i64) (i64.div_s (local.get $x) (local.get $y)))
+;;   (func (export "div_u") (param $x i64) (param $y i64) (result i64) (i64.div_u (local.get $x) (local.get $y)))
+;;   (func (export "rem_s") (param $x i64) (param $y i64) (result i64) (i64.rem_s (local.get $x) (local.get $y)))
+;;   (func (export "rem_u") (param $x i64) (param $y i64) (result i64) (i64.rem_u (local.get $x) (local.get $y)))
+;;   (func (export "shl") (param $x i64) (param $y i64) (result i64) (i64.shl (local.get $x) (local.get $y)))
+;;   (func (export "shr_s") (param $x i64) (param $y i64) (result i64) (i64.shr_s (local.get $x) (local.get $y)))
+;;   (func (export "shr_u") (param $x i64) (param $y i64) (result i64) (i64.shr_u (local.get $x) (local.get $y)))
+;;   (func (export "rotl") (param $x i64) (param $y i64) (result i64) (i64.rotl (local.get $x) (local.get $y)))
+;;   (func (export "rotr") (param $x i64) (param $y i64) (result i64) (i64.rotr (local.get $x) (local.get $y)))
+;;   (func (export "clz") (param $x i64) (result i64) (i64.clz (local.get $x)))
+;;   (func (export "ctz") (param $x i64) (result i64) (i64.ctz (local.get $x)))
+;;   (func (export "popcnt") (param $x i64) (result i64) (i64.popcnt (local.get $x)))
+;;   (func (export "eqz") (param $x i64) (result i32) (i64.eqz (local.get $x)))
+;;   (func (export "lt_s") (param $x i64) (param $y i64) (result i32) (i64.lt_s (local.get $x) (local.get $y)))
+;;   (func (export "lt_u") (param $x i64) (param $y i64) (result i32) (i64.lt_u (local.get $x) (local.get $y)))
+;; )
+(module binary
+  "\00\61\73\6d\01\00\00\00\01\17\04\60\02\7e\7e\01\7e\60\01\7e\01\7e\60\01"
+  "\7e\01\7f\60\02\7e\7e\01\7f\03\13\12\00\00\00\00\00\00\00\00\00\00\00\00"
+  "\01\01\01\02\03\03\07\80\01\12\03\61\64\64\00\00\03\73\75\62\00\01\03\6d"
+  "\75\6c\00\02\05\64\69\76\5f\73\00\03\05\64\69\76\5f\75\00\04\05\72\65\6d"
+  "\5f\73\00\05\05\72\65\6d\5f\75\00\06\03\73\68\6c\00\07\05\73\68\72\5f\73"
+  "\00\08\05\73\68\72\5f\75\00\09\04\72\6f\74\6c\00\0a\04\72\6f\74\72\00\0b"
+  "\03\63\6c\7a\00\0c\03\63\74\7a\00\0d\06\70\6f\70\63\6e\74\00\0e\03\65\71"
+  "\7a\00\0f\04\6c\74\5f\73\00\10\04\6c\74\5f\75\00\11\0a\89\01\12\07\00\20"
+  "\00\20\01\7c\0b\07\00\20\00\20\01\7d\0b\07\00\20\00\20\01\7e\0b\07\00\20"
+  "\00\20\01\7f\0b\07\00\20\00\20\01\80\0b\07\00\20\00\20\01\81\0b\07\00\20"
+  "\00\20\01\82\0b\07\00\20\00\20\01\86\0b\07\00\20\00\20\01\87\0b\07\00\20"
+  "\00\20\01\88\0b\07\00\20\00\20\01\89\0b\07\00\20\00\20\01\8a\0b\05\00\20"
+  "\00\79\0b\05\00\20\00\7a\0b\05\00\20\00\7b\0b\05\00\20\00\50\0b\07\00\20"
+  "\00\20\01\53\0b\07\00\20\00\20\01\54\0b"
+)
+
+(assert_return (invoke "add" (i64.const 1) (i64.const 1)) (i64.const 2))
+(assert_return (invoke "add" (i64.const -1) (i64.const -1)) (i64.const -2))
+(assert_return (invoke "add" (i64.const 0x7fffffffffffffff) (i64.const 1)) (i64.const 0x8000000000000000))
+(assert_return (invoke "add" (i64.const 0x8000000000000000) (i64.const 0x8000000000000000)) (i64.const 0))
+(assert_return (invoke "sub" (i64.const 0x8000000000000000) (i64.const 1)) (i64.const 0x7fffffffffffffff))
+(assert_return (invoke "mul" (i64.const 0x0123456789abcdef) (i64.const 0xfedcba9876543210)) (i64.const 0x2236d88fe5618cf0))
+(assert_return (invoke "mul" (i64.const 0x8000000000000000) (i64.const -1)) (i64.const 0x8000000000000000))
+
+(assert_trap (invoke "div_s" (i64.const 1) (i64.const 0)) "integer divide by zero")
+(assert_trap (invoke "div_s" (i64.const 0x8000000000000000) (i64.const -1)) "integer overflow")
+(assert_return (invoke "div_s" (i64.const -5) (i64.const 2)) (i64.const -2))
+(assert_return (invoke "div_s" (i64.const 0x8000000000000000) (i64.const 2)) (i64.const 0xc000000000000000))
+(assert_trap (invoke "div_u" (i64.const 1) (i64.const 0)) "integer divide by zero")
+(assert_return (invoke "div_u" (i64.const -5) (i64.const 2)) (i64.const 0x7ffffffffffffffd))
+(assert_trap (invoke "rem_s" (i64.const 1) (i64.const 0)) "integer divide by zero")
+(assert_return (invoke "rem_s" (i64.const 0x8000000000000000) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "rem_s" (i64.const -7) (i64.const 3)) (i64.const -1))
+(assert_trap (invoke "rem_u" (i64.const 1) (i64.const 0)) "integer divide by zero")
+(assert_return (invoke "rem_u" (i64.const -5) (i64.const 2)) (i64.const 1))
+
+(assert_return (invoke "shl" (i64.const 1) (i64.const 63)) (i64.const 0x8000000000000000))
+(assert_return (invoke "shl" (i64.const 1) (i64.const 64)) (i64.const 1))
+(assert_return (invoke "shr_s" (i64.const 0x8000000000000000) (i64.const 63)) (i64.const -1))
+(assert_return (invoke "shr_u" (i64.const 0x8000000000000000) (i64.const 63)) (i64.const 1))
+(assert_return (invoke "rotl" (i64.const 0xabcd987602468ace) (i64.const 1)) (i64.const 0x579b30ec048d159d))
+(assert_return (invoke "rotr" (i64.const 1) (i64.const 1)) (i64.const 0x8000000000000000))
+
+(assert_return (invoke "clz" (i64.const 0)) (i64.const 64))
+(assert_return (invoke "clz" (i64.const 0x00008000)) (i64.const 48))
+(assert_return (invoke "ctz" (i64.const 0)) (i64.const 64))
+(assert_return (invoke "ctz" (i64.const 0x8000000000000000)) (i64.const 63))
+(assert_return (invoke "popcnt" (i64.const -1)) (i64.const 64))
+(assert_return (invoke "popcnt" (i64.const 0x8000800080008000)) (i64.const 4))
+
+(assert_return (invoke "eqz" (i64.const 0)) (i32.const 1))
+(assert_return (invoke "eqz" (i64.const 0xffffffffffffffff)) (i32.const 0))
+(assert_return (invoke "lt_s" (i64.const 0x8000000000000000) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "lt_u" (i64.const 0x8000000000000000) (i64.const 0)) (i32.const 0))
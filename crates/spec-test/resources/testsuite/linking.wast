@@ -0,0 +1,128 @@
+;; linking modules by registered names
+
+;; (module $Mf
+;;   (func (export "call") (result i32) (call $g))
+;;   (func $g (result i32) (i32.const 2))
+;; )
+(module $Mf binary
+  "\00\61\73\6d\01\00\00\00\01\05\01\60\00\01\7f\03\03\02\00\00\07\08\01\04"
+  "\63\61\6c\6c\00\00\0a\0b\02\04\00\10\01\0b\04\00\41\02\0b"
+)
+(register "Mf" $Mf)
+
+;; (module $Nf
+;;   (func $f (import "Mf" "call") (result i32))
+;;   (export "Mf.call" (func $f))
+;;   (func (export "call Mf.call") (result i32) (call $f))
+;;   (func (export "call") (result i32) (call $g))
+;;   (func $g (result i32) (i32.const 3))
+;; )
+(module $Nf binary
+  "\00\61\73\6d\01\00\00\00\01\05\01\60\00\01\7f\02\0b\01\02\4d\66\04\63\61"
+  "\6c\6c\00\00\03\04\03\00\00\00\07\21\03\07\4d\66\2e\63\61\6c\6c\00\00\0c"
+  "\63\61\6c\6c\20\4d\66\2e\63\61\6c\6c\00\01\04\63\61\6c\6c\00\02\0a\10\03"
+  "\04\00\10\00\0b\04\00\10\03\0b\04\00\41\03\0b"
+)
+
+(assert_return (invoke $Mf "call") (i32.const 2))
+(assert_return (invoke $Nf "Mf.call") (i32.const 2))
+(assert_return (invoke $Nf "call") (i32.const 3))
+(assert_return (invoke $Nf "call Mf.call") (i32.const 2))
+
+;; (module $Mg
+;;   (global $glob (export "glob") i32 (i32.const 42))
+;;   (func (export "get") (result i32) (global.get $glob))
+;; )
+(module $Mg binary
+  "\00\61\73\6d\01\00\00\00\01\05\01\60\00\01\7f\03\02\01\00\06\06\01\7f\00"
+  "\41\2a\0b\07\0e\02\04\67\6c\6f\62\03\00\03\67\65\74\00\00\0a\06\01\04\00"
+  "\23\00\0b"
+)
+(register "Mg" $Mg)
+
+;; (module $Ng
+;;   (global $x (import "Mg" "glob") i32)
+;;   (func $f (import "Mg" "get") (result i32))
+;;   (export "Mg.glob" (global $x))
+;;   (export "Mg.get" (func $f))
+;;   (func (export "get") (result i32) (global.get $x))
+;; )
+(module $Ng binary
+  "\00\61\73\6d\01\00\00\00\01\05\01\60\00\01\7f\02\15\02\02\4d\67\04\67\6c"
+  "\6f\62\03\7f\00\02\4d\67\03\67\65\74\00\00\03\02\01\00\07\1a\03\07\4d\67"
+  "\2e\67\6c\6f\62\03\00\06\4d\67\2e\67\65\74\00\00\03\67\65\74\00\01\0a\06"
+  "\01\04\00\23\00\0b"
+)
+
+(assert_return (get $Mg "glob") (i32.const 42))
+(assert_return (get $Ng "Mg.glob") (i32.const 42))
+(assert_return (invoke $Ng "Mg.get") (i32.const 42))
+(assert_return (invoke $Ng "get") (i32.const 42))
+
+;; (module $Mm
+;;   (memory (export "mem") 1 5)
+;;   (data (i32.const 10) "\00\01\02\03\04\05\06\07\08\09")
+;;   (func (export "load") (param $a i32) (result i32) (i32.load8_u (local.get $a)))
+;; )
+(module $Mm binary
+  "\00\61\73\6d\01\00\00\00\01\06\01\60\01\7f\01\7f\03\02\01\00\05\04\01\01"
+  "\01\05\07\0e\02\03\6d\65\6d\02\00\04\6c\6f\61\64\00\00\0a\09\01\07\00\20"
+  "\00\2d\00\00\0b\0b\10\01\00\41\0a\0b\0a\00\01\02\03\04\05\06\07\08\09"
+)
+(register "Mm" $Mm)
+
+;; (module $Nm
+;;   (import "Mm" "mem" (memory 1))
+;;   (func (export "load") (param $a i32) (result i32) (i32.load8_u (local.get $a)))
+;; )
+(module $Nm binary
+  "\00\61\73\6d\01\00\00\00\01\06\01\60\01\7f\01\7f\02\0b\01\02\4d\6d\03\6d"
+  "\65\6d\02\00\01\03\02\01\00\07\08\01\04\6c\6f\61\64\00\00\0a\09\01\07\00"
+  "\20\00\2d\00\00\0b"
+)
+
+(assert_return (invoke $Mm "load" (i32.const 12)) (i32.const 2))
+(assert_return (invoke $Nm "load" (i32.const 12)) (i32.const 2))
+
+;; (module
+;;   (func (import "spectest" "print_i32") (param i32))
+;;   (func (export "print") (call 0 (i32.const 1)))
+;; )
+(module binary
+  "\00\61\73\6d\01\00\00\00\01\08\02\60\01\7f\00\60\00\00\02\16\01\08\73\70"
+  "\65\63\74\65\73\74\09\70\72\69\6e\74\5f\69\33\32\00\00\03\02\01\01\07\09"
+  "\01\05\70\72\69\6e\74\00\01\0a\08\01\06\00\41\01\10\00\0b"
+)
+(assert_return (invoke "print"))
+
+;; (module (import "Mf" "missing" (func)))
+(assert_unlinkable
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\04\01\60\00\00\02\0e\01\02\4d\66\07\6d\69\73"
+    "\73\69\6e\67\00\00"
+  )
+  "unknown import"
+)
+;; (module (import "missing" "call" (func (result i32))))
+(assert_unlinkable
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\05\01\60\00\01\7f\02\10\01\07\6d\69\73\73\69"
+    "\6e\67\04\63\61\6c\6c\00\00"
+  )
+  "unknown import"
+)
+;; (module (import "Mf" "call" (func (result i64))))
+(assert_unlinkable
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\05\01\60\00\01\7e\02\0b\01\02\4d\66\04\63\61"
+    "\6c\6c\00\00"
+  )
+  "incompatible import type"
+)
+;; (module (import "Mg" "glob" (global i64)))
+(assert_unlinkable
+  (module binary
+    "\00\61\73\6d\01\00\00\00\02\0c\01\02\4d\67\04\67\6c\6f\62\03\7e\00"
+  )
+  "incompatible import type"
+)
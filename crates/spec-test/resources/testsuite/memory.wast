@@ -0,0 +1,104 @@
+;; memory load, store, size and grow
+
+;; (module
+;;   (memory 1 3)
+;;   (data (i32.const 0) "abcdefghijklmnopqrstuvwxyz")
+;;   (data (i32.const 100) "\ff\ff\ff\ff\00\00\00\80")
+;;   (func (export "load8_s") (param $a i32) (result i32) (i32.load8_s (local.get $a)))
+;;   (func (export "load8_u") (param $a i32) (result i32) (i32.load8_u (local.get $a)))
+;;   (func (export "load16_s") (param $a i32) (result i32) (i32.load16_s (local.get $a)))
+;;   (func (export "load16_u") (param $a i32) (result i32) (i32.load16_u (local.get $a)))
+;;   (func (export "i32.load") (param $a i32) (result i32) (i32.load (local.get $a)))
+;;   (func (export "i64.load") (param $a i32) (result i64) (i64.load (local.get $a)))
+;;   (func (export "i64.load32_s") (param $a i32) (result i64) (i64.load32_s (local.get $a)))
+;;   (func (export "i64.load32_u") (param $a i32) (result i64) (i64.load32_u (local.get $a)))
+;;   (func (export "load-offset") (param $a i32) (result i32) (i32.load8_u offset=2 (local.get $a)))
+;;   (func (export "i32.store") (param $a i32) (param $v i32) (i32.store (local.get $a) (local.get $v)))
+;;   (func (export "i32.store8") (param $a i32) (param $v i32) (i32.store8 (local.get $a) (local.get $v)))
+;;   (func (export "i64.store16") (param $a i32) (param $v i64) (i64.store16 (local.get $a) (local.get $v)))
+;;   (func (export "f32.store") (param $a i32) (param $v f32) (f32.store (local.get $a) (local.get $v)))
+;;   (func (export "f32.load") (param $a i32) (result f32) (f32.load (local.get $a)))
+;;   (func (export "f64.store") (param $a i32) (param $v f64) (f64.store (local.get $a) (local.get $v)))
+;;   (func (export "f64.load") (param $a i32) (result f64) (f64.load (local.get $a)))
+;;   (func (export "size") (result i32) (memory.size))
+;;   (func (export "grow") (param $n i32) (result i32) (memory.grow (local.get $n)))
+;; )
+(module binary
+  "\00\61\73\6d\01\00\00\00\01\2d\09\60\01\7f\01\7f\60\01\7f\01\7e\60\02\7f"
+  "\7f\00\60\02\7f\7e\00\60\02\7f\7d\00\60\01\7f\01\7d\60\02\7f\7c\00\60\01"
+  "\7f\01\7c\60\00\01\7f\03\13\12\00\00\00\00\00\01\01\01\00\02\02\03\04\05"
+  "\06\07\08\00\05\04\01\01\01\03\07\d0\01\12\07\6c\6f\61\64\38\5f\73\00\00"
+  "\07\6c\6f\61\64\38\5f\75\00\01\08\6c\6f\61\64\31\36\5f\73\00\02\08\6c\6f"
+  "\61\64\31\36\5f\75\00\03\08\69\33\32\2e\6c\6f\61\64\00\04\08\69\36\34\2e"
+  "\6c\6f\61\64\00\05\0c\69\36\34\2e\6c\6f\61\64\33\32\5f\73\00\06\0c\69\36"
+  "\34\2e\6c\6f\61\64\33\32\5f\75\00\07\0b\6c\6f\61\64\2d\6f\66\66\73\65\74"
+  "\00\08\09\69\33\32\2e\73\74\6f\72\65\00\09\0a\69\33\32\2e\73\74\6f\72\65"
+  "\38\00\0a\0b\69\36\34\2e\73\74\6f\72\65\31\36\00\0b\09\66\33\32\2e\73\74"
+  "\6f\72\65\00\0c\08\66\33\32\2e\6c\6f\61\64\00\0d\09\66\36\34\2e\73\74\6f"
+  "\72\65\00\0e\08\66\36\34\2e\6c\6f\61\64\00\0f\04\73\69\7a\65\00\10\04\67"
+  "\72\6f\77\00\11\0a\97\01\12\07\00\20\00\2c\00\00\0b\07\00\20\00\2d\00\00"
+  "\0b\07\00\20\00\2e\01\00\0b\07\00\20\00\2f\01\00\0b\07\00\20\00\28\02\00"
+  "\0b\07\00\20\00\29\03\00\0b\07\00\20\00\34\02\00\0b\07\00\20\00\35\02\00"
+  "\0b\07\00\20\00\2d\00\02\0b\09\00\20\00\20\01\36\02\00\0b\09\00\20\00\20"
+  "\01\3a\00\00\0b\09\00\20\00\20\01\3d\01\00\0b\09\00\20\00\20\01\38\02\00"
+  "\0b\07\00\20\00\2a\02\00\0b\09\00\20\00\20\01\39\03\00\0b\07\00\20\00\2b"
+  "\03\00\0b\04\00\3f\00\0b\06\00\20\00\40\00\0b\0b\2e\02\00\41\00\0b\1a\61"
+  "\62\63\64\65\66\67\68\69\6a\6b\6c\6d\6e\6f\70\71\72\73\74\75\76\77\78\79"
+  "\7a\00\41\e4\00\0b\08\ff\ff\ff\ff\00\00\00\80"
+)
+
+(assert_return (invoke "load8_u" (i32.const 0)) (i32.const 97))
+(assert_return (invoke "load8_u" (i32.const 25)) (i32.const 122))
+(assert_return (invoke "load8_u" (i32.const 26)) (i32.const 0))
+(assert_return (invoke "load8_s" (i32.const 100)) (i32.const -1))
+(assert_return (invoke "load8_u" (i32.const 100)) (i32.const 255))
+(assert_return (invoke "load16_s" (i32.const 100)) (i32.const -1))
+(assert_return (invoke "load16_u" (i32.const 100)) (i32.const 65535))
+(assert_return (invoke "i32.load" (i32.const 0)) (i32.const 0x64636261))
+(assert_return (invoke "i32.load" (i32.const 104)) (i32.const 0x80000000))
+(assert_return (invoke "i64.load" (i32.const 100)) (i64.const 0x80000000ffffffff))
+(assert_return (invoke "i64.load32_s" (i32.const 100)) (i64.const -1))
+(assert_return (invoke "i64.load32_u" (i32.const 100)) (i64.const 0xffffffff))
+(assert_return (invoke "load-offset" (i32.const 0)) (i32.const 99))
+
+(assert_return (invoke "i32.store" (i32.const 200) (i32.const 0x12345678)))
+(assert_return (invoke "i32.load" (i32.const 200)) (i32.const 0x12345678))
+(assert_return (invoke "load8_u" (i32.const 200)) (i32.const 0x78))
+(assert_return (invoke "i32.store8" (i32.const 200) (i32.const 0xffffff01)))
+(assert_return (invoke "i32.load" (i32.const 200)) (i32.const 0x12345601))
+(assert_return (invoke "i64.store16" (i32.const 200) (i64.const 0xabcd)))
+(assert_return (invoke "i32.load" (i32.const 200)) (i32.const 0x1234abcd))
+(assert_return (invoke "f32.store" (i32.const 300) (f32.const -0x1.8p+1)))
+(assert_return (invoke "f32.load" (i32.const 300)) (f32.const -0x1.8p+1))
+(assert_return (invoke "f32.store" (i32.const 300) (f32.const nan:0x200000)))
+(assert_return (invoke "f32.load" (i32.const 300)) (f32.const nan:0x200000))
+(assert_return (invoke "f64.store" (i32.const 301) (f64.const 0x1.23456789abcdep+100)))
+(assert_return (invoke "f64.load" (i32.const 301)) (f64.const 0x1.23456789abcdep+100))
+
+(assert_return (invoke "i32.load" (i32.const 65532)) (i32.const 0))
+(assert_trap (invoke "i32.load" (i32.const 65533)) "out of bounds memory access")
+(assert_trap (invoke "load8_u" (i32.const 65536)) "out of bounds memory access")
+(assert_trap (invoke "load8_u" (i32.const -1)) "out of bounds memory access")
+(assert_trap (invoke "load-offset" (i32.const 65534)) "out of bounds memory access")
+(assert_trap (invoke "i32.store" (i32.const 65535) (i32.const 0)) "out of bounds memory access")
+
+(assert_return (invoke "size") (i32.const 1))
+(assert_return (invoke "grow" (i32.const 1)) (i32.const 1))
+(assert_return (invoke "size") (i32.const 2))
+(assert_return (invoke "load8_u" (i32.const 65536)) (i32.const 0))
+(assert_return (invoke "grow" (i32.const 0)) (i32.const 2))
+(assert_return (invoke "grow" (i32.const 2)) (i32.const -1))
+(assert_return (invoke "grow" (i32.const 1)) (i32.const 2))
+(assert_return (invoke "size") (i32.const 3))
+
+;; (module
+;;     (memory 1)
+;;     (data (i32.const 65535) "ab")
+;;   )
+(assert_trap
+  (module binary
+    "\00\61\73\6d\01\00\00\00\05\03\01\00\01\0b\0a\01\00\41\ff\ff\03\0b\02\61"
+    "\62"
+  )
+  "out of bounds memory access"
+)
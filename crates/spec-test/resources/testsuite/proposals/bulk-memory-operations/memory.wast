@@ -0,0 +1,48 @@
+;; bulk memory operations
+
+(module
+  (memory 1)
+  (data "\aa\bb\cc\dd")
+  (func (export "fill") (param $d i32) (param $v i32) (param $n i32)
+    (memory.fill (local.get $d) (local.get $v) (local.get $n)))
+  (func (export "copy") (param $d i32) (param $s i32) (param $n i32)
+    (memory.copy (local.get $d) (local.get $s) (local.get $n)))
+  (func (export "init") (param $d i32) (param $s i32) (param $n i32)
+    (memory.init 0 (local.get $d) (local.get $s) (local.get $n)))
+  (func (export "drop") (data.drop 0))
+  (func (export "load8_u") (param $a i32) (result i32) (i32.load8_u (local.get $a)))
+)
+
+;; the same module in the binary format
+(module binary
+  "\00\61\73\6d\01\00\00\00\01\0f\03\60\03\7f\7f\7f\00\60\00\00\60\01\7f\01"
+  "\7f\03\06\05\00\00\00\01\02\05\03\01\00\01\07\27\05\04\66\69\6c\6c\00\00"
+  "\04\63\6f\70\79\00\01\04\69\6e\69\74\00\02\04\64\72\6f\70\00\03\07\6c\6f"
+  "\61\64\38\5f\75\00\04\0c\01\01\0a\35\05\0b\00\20\00\20\01\20\02\fc\0b\00"
+  "\0b\0c\00\20\00\20\01\20\02\fc\0a\00\00\0b\0c\00\20\00\20\01\20\02\fc\08"
+  "\00\00\0b\05\00\fc\09\00\0b\07\00\20\00\2d\00\00\0b\0b\07\01\01\04\aa\bb"
+  "\cc\dd"
+)
+
+(invoke "fill" (i32.const 1) (i32.const 0xff) (i32.const 3))
+(assert_return (invoke "load8_u" (i32.const 0)) (i32.const 0))
+(assert_return (invoke "load8_u" (i32.const 1)) (i32.const 0xff))
+(assert_return (invoke "load8_u" (i32.const 3)) (i32.const 0xff))
+(assert_return (invoke "load8_u" (i32.const 4)) (i32.const 0))
+(assert_trap (invoke "fill" (i32.const 65536) (i32.const 0) (i32.const 1)) "out of bounds memory access")
+(assert_return (invoke "fill" (i32.const 65536) (i32.const 0) (i32.const 0)))
+
+(invoke "init" (i32.const 10) (i32.const 1) (i32.const 3))
+(assert_return (invoke "load8_u" (i32.const 10)) (i32.const 0xbb))
+(assert_return (invoke "load8_u" (i32.const 12)) (i32.const 0xdd))
+(assert_trap (invoke "init" (i32.const 0) (i32.const 2) (i32.const 3)) "out of bounds memory access")
+
+(invoke "copy" (i32.const 11) (i32.const 10) (i32.const 3))
+(assert_return (invoke "load8_u" (i32.const 11)) (i32.const 0xbb))
+(assert_return (invoke "load8_u" (i32.const 12)) (i32.const 0xcc))
+(assert_return (invoke "load8_u" (i32.const 13)) (i32.const 0xdd))
+(assert_trap (invoke "copy" (i32.const 65535) (i32.const 0) (i32.const 2)) "out of bounds memory access")
+
+(invoke "drop")
+(assert_trap (invoke "init" (i32.const 0) (i32.const 0) (i32.const 1)) "out of bounds memory access")
+(assert_return (invoke "init" (i32.const 0) (i32.const 0) (i32.const 0)))
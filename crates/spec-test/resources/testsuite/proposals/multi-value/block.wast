@@ -0,0 +1,49 @@
+;; multiple values
+
+;; (module
+;;   (func $pair (export "pair") (result i32 i64) (i32.const 1) (i64.const 2))
+;;   (func (export "block-params") (result i32)
+;;     (i32.const 3) (i32.const 4)
+;;     (block (param i32 i32) (result i32) (i32.sub)))
+;;   (func (export "block-results") (result i32 i32)
+;;     (block (result i32 i32) (i32.const 5) (i32.const 6)))
+;;   (func (export "loop-params") (param $n i32) (result i32)
+;;     (i32.const 0) (local.get $n)
+;;     (loop $l (param i32 i32) (result i32)
+;;       (if (param i32 i32) (result i32) (i32.eqz (local.get $n))
+;;         (then (drop))
+;;         (else
+;;           (i32.add)
+;;           (local.tee $n (i32.sub (local.get $n) (i32.const 1)))
+;;           (br $l)))))
+;;   (func (export "if-params") (param $c i32) (result i32)
+;;     (i32.const 10) (i32.const 2)
+;;     (if (param i32 i32) (result i32) (local.get $c)
+;;       (then (i32.add))
+;;       (else (i32.sub))))
+;;   (func (export "br-multi") (result i32 i32)
+;;     (block (result i32 i32) (i32.const 7) (i32.const 8) (br 0)))
+;;   (func (export "call-multi") (result i64)
+;;     (call $pair) (drop) (i64.extend_i32_u))
+;; )
+(module binary
+  "\00\61\73\6d\01\00\00\00\01\1e\06\60\00\02\7f\7e\60\00\01\7f\60\02\7f\7f"
+  "\01\7f\60\00\02\7f\7f\60\01\7f\01\7f\60\00\01\7e\03\08\07\00\01\03\04\04"
+  "\03\05\07\59\07\04\70\61\69\72\00\00\0c\62\6c\6f\63\6b\2d\70\61\72\61\6d"
+  "\73\00\01\0d\62\6c\6f\63\6b\2d\72\65\73\75\6c\74\73\00\02\0b\6c\6f\6f\70"
+  "\2d\70\61\72\61\6d\73\00\03\09\69\66\2d\70\61\72\61\6d\73\00\04\08\62\72"
+  "\2d\6d\75\6c\74\69\00\05\0a\63\61\6c\6c\2d\6d\75\6c\74\69\00\06\0a\5b\07"
+  "\06\00\41\01\42\02\0b\0a\00\41\03\41\04\02\02\6b\0b\0b\09\00\02\03\41\05"
+  "\41\06\0b\0b\1b\00\41\00\20\00\03\02\20\00\45\04\02\1a\05\6a\20\00\41\01"
+  "\6b\22\00\0c\01\0b\0b\0b\0e\00\41\0a\41\02\20\00\04\02\6a\05\6b\0b\0b\0b"
+  "\00\02\03\41\07\41\08\0c\00\0b\0b\06\00\10\00\1a\ad\0b"
+)
+
+(assert_return (invoke "pair") (i32.const 1) (i64.const 2))
+(assert_return (invoke "block-params") (i32.const -1))
+(assert_return (invoke "block-results") (i32.const 5) (i32.const 6))
+(assert_return (invoke "loop-params" (i32.const 4)) (i32.const 10))
+(assert_return (invoke "if-params" (i32.const 1)) (i32.const 12))
+(assert_return (invoke "if-params" (i32.const 0)) (i32.const 8))
+(assert_return (invoke "br-multi") (i32.const 7) (i32.const 8))
+(assert_return (invoke "call-multi") (i64.const 1))
@@ -0,0 +1,31 @@
+;; non-trapping (saturating) float-to-int conversions
+
+;; (module
+;;   (func (export "i32.trunc_sat_f32_s") (param $x f32) (result i32) (i32.trunc_sat_f32_s (local.get $x)))
+;;   (func (export "i32.trunc_sat_f32_u") (param $x f32) (result i32) (i32.trunc_sat_f32_u (local.get $x)))
+;;   (func (export "i32.trunc_sat_f64_s") (param $x f64) (result i32) (i32.trunc_sat_f64_s (local.get $x)))
+;;   (func (export "i64.trunc_sat_f64_u") (param $x f64) (result i64) (i64.trunc_sat_f64_u (local.get $x)))
+;; )
+(module binary
+  "\00\61\73\6d\01\00\00\00\01\10\03\60\01\7d\01\7f\60\01\7c\01\7f\60\01\7c"
+  "\01\7e\03\05\04\00\00\01\02\07\59\04\13\69\33\32\2e\74\72\75\6e\63\5f\73"
+  "\61\74\5f\66\33\32\5f\73\00\00\13\69\33\32\2e\74\72\75\6e\63\5f\73\61\74"
+  "\5f\66\33\32\5f\75\00\01\13\69\33\32\2e\74\72\75\6e\63\5f\73\61\74\5f\66"
+  "\36\34\5f\73\00\02\13\69\36\34\2e\74\72\75\6e\63\5f\73\61\74\5f\66\36\34"
+  "\5f\75\00\03\0a\1d\04\06\00\20\00\fc\00\0b\06\00\20\00\fc\01\0b\06\00\20"
+  "\00\fc\02\0b\06\00\20\00\fc\07\0b"
+)
+
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const -0x1.19999ap+0)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const 2147483648.0)) (i32.const 0x7fffffff))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const -2147483904.0)) (i32.const 0x80000000))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const inf)) (i32.const 0x7fffffff))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const nan)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const 4294967296.0)) (i32.const 0xffffffff))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const -1.0)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const -nan)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const -2147483649.0)) (i32.const 0x80000000))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const 1e100)) (i32.const 0x7fffffff))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const 18446744073709551616.0)) (i64.const 0xffffffffffffffff))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const -inf)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const 4294967296)) (i64.const 4294967296))
@@ -0,0 +1,34 @@
+;; sign extension operators
+
+;; (module
+;;   (func (export "i32.extend8_s") (param $x i32) (result i32) (i32.extend8_s (local.get $x)))
+;;   (func (export "i32.extend16_s") (param $x i32) (result i32) (i32.extend16_s (local.get $x)))
+;;   (func (export "i64.extend8_s") (param $x i64) (result i64) (i64.extend8_s (local.get $x)))
+;;   (func (export "i64.extend16_s") (param $x i64) (result i64) (i64.extend16_s (local.get $x)))
+;;   (func (export "i64.extend32_s") (param $x i64) (result i64) (i64.extend32_s (local.get $x)))
+;; )
+(module binary
+  "\00\61\73\6d\01\00\00\00\01\0b\02\60\01\7f\01\7f\60\01\7e\01\7e\03\06\05"
+  "\00\00\01\01\01\07\54\05\0d\69\33\32\2e\65\78\74\65\6e\64\38\5f\73\00\00"
+  "\0e\69\33\32\2e\65\78\74\65\6e\64\31\36\5f\73\00\01\0d\69\36\34\2e\65\78"
+  "\74\65\6e\64\38\5f\73\00\02\0e\69\36\34\2e\65\78\74\65\6e\64\31\36\5f\73"
+  "\00\03\0e\69\36\34\2e\65\78\74\65\6e\64\33\32\5f\73\00\04\0a\1f\05\05\00"
+  "\20\00\c0\0b\05\00\20\00\c1\0b\05\00\20\00\c2\0b\05\00\20\00\c3\0b\05\00"
+  "\20\00\c4\0b"
+)
+
+(assert_return (invoke "i32.extend8_s" (i32.const 0)) (i32.const 0))
+(assert_return (invoke "i32.extend8_s" (i32.const 0x7f)) (i32.const 127))
+(assert_return (invoke "i32.extend8_s" (i32.const 0x80)) (i32.const -128))
+(assert_return (invoke "i32.extend8_s" (i32.const 0xff)) (i32.const -1))
+(assert_return (invoke "i32.extend8_s" (i32.const 0x012345_00)) (i32.const 0))
+(assert_return (invoke "i32.extend8_s" (i32.const 0xfedcba_80)) (i32.const -0x80))
+(assert_return (invoke "i32.extend16_s" (i32.const 0x7fff)) (i32.const 32767))
+(assert_return (invoke "i32.extend16_s" (i32.const 0x8000)) (i32.const -32768))
+(assert_return (invoke "i32.extend16_s" (i32.const 0x0123_0000)) (i32.const 0))
+(assert_return (invoke "i64.extend8_s" (i64.const 0x80)) (i64.const -128))
+(assert_return (invoke "i64.extend8_s" (i64.const 0x01234567_89abcd_00)) (i64.const 0))
+(assert_return (invoke "i64.extend16_s" (i64.const 0x8000)) (i64.const -32768))
+(assert_return (invoke "i64.extend32_s" (i64.const 0x7fffffff)) (i64.const 0x7fffffff))
+(assert_return (invoke "i64.extend32_s" (i64.const 0x80000000)) (i64.const -0x80000000))
+(assert_return (invoke "i64.extend32_s" (i64.const 0x01234567_00000000)) (i64.const 0))
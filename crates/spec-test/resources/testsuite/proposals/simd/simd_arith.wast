@@ -0,0 +1,82 @@
+;; SIMD 整数运算
+
+;; (module
+;;   (func (export "i8x16.add") (param v128 v128) (result v128) (i8x16.add (local.get 0) (local.get 1)))
+;;   (func (export "i8x16.add_sat_s") (param v128 v128) (result v128) (i8x16.add_sat_s (local.get 0) (local.get 1)))
+;;   (func (export "i8x16.sub_sat_u") (param v128 v128) (result v128) (i8x16.sub_sat_u (local.get 0) (local.get 1)))
+;;   (func (export "i16x8.mul") (param v128 v128) (result v128) (i16x8.mul (local.get 0) (local.get 1)))
+;;   (func (export "i16x8.q15mulr_sat_s") (param v128 v128) (result v128) (i16x8.q15mulr_sat_s (local.get 0) (local.get 1)))
+;;   (func (export "i32x4.add") (param v128 v128) (result v128) (i32x4.add (local.get 0) (local.get 1)))
+;;   (func (export "i32x4.dot_i16x8_s") (param v128 v128) (result v128) (i32x4.dot_i16x8_s (local.get 0) (local.get 1)))
+;;   (func (export "i32x4.min_u") (param v128 v128) (result v128) (i32x4.min_u (local.get 0) (local.get 1)))
+;;   (func (export "i64x2.mul") (param v128 v128) (result v128) (i64x2.mul (local.get 0) (local.get 1)))
+;;   (func (export "i64x2.neg") (param v128) (result v128) (i64x2.neg (local.get 0)))
+;;   (func (export "i8x16.abs") (param v128) (result v128) (i8x16.abs (local.get 0)))
+;;   (func (export "i8x16.popcnt") (param v128) (result v128) (i8x16.popcnt (local.get 0)))
+;;   (func (export "i8x16.avgr_u") (param v128 v128) (result v128) (i8x16.avgr_u (local.get 0) (local.get 1)))
+;;   (func (export "i32x4.shr_s") (param v128 i32) (result v128) (i32x4.shr_s (local.get 0) (local.get 1)))
+;;   (func (export "i64x2.shr_u") (param v128 i32) (result v128) (i64x2.shr_u (local.get 0) (local.get 1)))
+;;   (func (export "i8x16.narrow_i16x8_s") (param v128 v128) (result v128) (i8x16.narrow_i16x8_s (local.get 0) (local.get 1)))
+;;   (func (export "i16x8.extmul_high_i8x16_u") (param v128 v128) (result v128) (i16x8.extmul_high_i8x16_u (local.get 0) (local.get 1)))
+;;   (func (export "i32x4.extadd_pairwise_i16x8_s") (param v128) (result v128) (i32x4.extadd_pairwise_i16x8_s (local.get 0)))
+;; )
+(module binary
+  "\00\61\73\6d\01\00\00\00\01\12\03\60\02\7b\7b\01\7b\60\01\7b\01\7b\60\02"
+  "\7b\7f\01\7b\03\13\12\00\00\00\00\00\00\00\00\00\01\01\01\00\02\02\00\00"
+  "\01\07\b2\02\12\09\69\38\78\31\36\2e\61\64\64\00\00\0f\69\38\78\31\36\2e"
+  "\61\64\64\5f\73\61\74\5f\73\00\01\0f\69\38\78\31\36\2e\73\75\62\5f\73\61"
+  "\74\5f\75\00\02\09\69\31\36\78\38\2e\6d\75\6c\00\03\13\69\31\36\78\38\2e"
+  "\71\31\35\6d\75\6c\72\5f\73\61\74\5f\73\00\04\09\69\33\32\78\34\2e\61\64"
+  "\64\00\05\11\69\33\32\78\34\2e\64\6f\74\5f\69\31\36\78\38\5f\73\00\06\0b"
+  "\69\33\32\78\34\2e\6d\69\6e\5f\75\00\07\09\69\36\34\78\32\2e\6d\75\6c\00"
+  "\08\09\69\36\34\78\32\2e\6e\65\67\00\09\09\69\38\78\31\36\2e\61\62\73\00"
+  "\0a\0c\69\38\78\31\36\2e\70\6f\70\63\6e\74\00\0b\0c\69\38\78\31\36\2e\61"
+  "\76\67\72\5f\75\00\0c\0b\69\33\32\78\34\2e\73\68\72\5f\73\00\0d\0b\69\36"
+  "\34\78\32\2e\73\68\72\5f\75\00\0e\14\69\38\78\31\36\2e\6e\61\72\72\6f\77"
+  "\5f\69\31\36\78\38\5f\73\00\0f\19\69\31\36\78\38\2e\65\78\74\6d\75\6c\5f"
+  "\68\69\67\68\5f\69\38\78\31\36\5f\75\00\10\1d\69\33\32\78\34\2e\65\78\74"
+  "\61\64\64\5f\70\61\69\72\77\69\73\65\5f\69\31\36\78\38\5f\73\00\11\0a\a5"
+  "\01\12\08\00\20\00\20\01\fd\6e\0b\08\00\20\00\20\01\fd\6f\0b\08\00\20\00"
+  "\20\01\fd\73\0b\09\00\20\00\20\01\fd\95\01\0b\09\00\20\00\20\01\fd\82\01"
+  "\0b\09\00\20\00\20\01\fd\ae\01\0b\09\00\20\00\20\01\fd\ba\01\0b\09\00\20"
+  "\00\20\01\fd\b7\01\0b\09\00\20\00\20\01\fd\d5\01\0b\07\00\20\00\fd\c1\01"
+  "\0b\06\00\20\00\fd\60\0b\06\00\20\00\fd\62\0b\08\00\20\00\20\01\fd\7b\0b"
+  "\09\00\20\00\20\01\fd\ac\01\0b\09\00\20\00\20\01\fd\cd\01\0b\08\00\20\00"
+  "\20\01\fd\65\0b\09\00\20\00\20\01\fd\9f\01\0b\06\00\20\00\fd\7e\0b"
+)
+
+(assert_return (invoke "i8x16.add" (v128.const i8x16 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 255) (v128.const i8x16 1 1 1 1 1 1 1 1 1 1 1 1 1 1 1 1))
+                                   (v128.const i8x16 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 0))
+(assert_return (invoke "i8x16.add_sat_s" (v128.const i8x16 127 -128 1 -1 0 0 0 0 0 0 0 0 0 0 0 0) (v128.const i8x16 1 -1 -1 1 0 0 0 0 0 0 0 0 0 0 0 0))
+                                         (v128.const i8x16 127 -128 0 0 0 0 0 0 0 0 0 0 0 0 0 0))
+(assert_return (invoke "i8x16.sub_sat_u" (v128.const i8x16 0 1 255 128 0 0 0 0 0 0 0 0 0 0 0 0) (v128.const i8x16 1 1 1 129 0 0 0 0 0 0 0 0 0 0 0 0))
+                                         (v128.const i8x16 0 0 254 0 0 0 0 0 0 0 0 0 0 0 0 0))
+(assert_return (invoke "i16x8.mul" (v128.const i16x8 0x100 -1 3 0x4000 0 0 0 0) (v128.const i16x8 0x100 -1 -7 4 0 0 0 0))
+                                   (v128.const i16x8 0 1 -21 0 0 0 0 0))
+(assert_return (invoke "i16x8.q15mulr_sat_s" (v128.const i16x8 -32768 16384 -16384 1 0 0 0 0) (v128.const i16x8 -32768 16384 16384 1 0 0 0 0))
+                                             (v128.const i16x8 32767 8192 -8192 0 0 0 0 0))
+(assert_return (invoke "i32x4.add" (v128.const i32x4 1 2 3 0xffffffff) (v128.const i32x4 10 20 30 1))
+                                   (v128.const i32x4 11 22 33 0))
+(assert_return (invoke "i32x4.dot_i16x8_s" (v128.const i16x8 1 2 3 4 -32768 -32768 0 0) (v128.const i16x8 5 6 7 8 -32768 -32768 0 0))
+                                           (v128.const i32x4 17 53 -2147483648 0))
+(assert_return (invoke "i32x4.min_u" (v128.const i32x4 -1 0 1 0x80000000) (v128.const i32x4 1 -1 0 0x7fffffff))
+                                     (v128.const i32x4 1 0 0 0x7fffffff))
+(assert_return (invoke "i64x2.mul" (v128.const i64x2 0x100000000 -3) (v128.const i64x2 0x100000000 7))
+                                   (v128.const i64x2 0 -21))
+(assert_return (invoke "i64x2.neg" (v128.const i64x2 1 -9223372036854775808)) (v128.const i64x2 -1 -9223372036854775808))
+(assert_return (invoke "i8x16.abs" (v128.const i8x16 -128 -1 1 127 0 0 0 0 0 0 0 0 0 0 0 0))
+                                   (v128.const i8x16 -128 1 1 127 0 0 0 0 0 0 0 0 0 0 0 0))
+(assert_return (invoke "i8x16.popcnt" (v128.const i8x16 0 1 3 7 15 31 63 127 255 0x55 0xaa 0x80 0 0 0 0))
+                                      (v128.const i8x16 0 1 2 3 4 5 6 7 8 4 4 1 0 0 0 0))
+(assert_return (invoke "i8x16.avgr_u" (v128.const i8x16 0 1 255 254 0 0 0 0 0 0 0 0 0 0 0 0) (v128.const i8x16 0 2 255 255 0 0 0 0 0 0 0 0 0 0 0 0))
+                                      (v128.const i8x16 0 2 255 255 0 0 0 0 0 0 0 0 0 0 0 0))
+(assert_return (invoke "i32x4.shr_s" (v128.const i32x4 -8 8 -1 0x40000000) (i32.const 33))
+                                     (v128.const i32x4 -4 4 -1 0x20000000))
+(assert_return (invoke "i64x2.shr_u" (v128.const i64x2 -1 0x100) (i32.const 64))
+                                     (v128.const i64x2 -1 0x100))
+(assert_return (invoke "i8x16.narrow_i16x8_s" (v128.const i16x8 128 -129 1 -1 0 0 0 0) (v128.const i16x8 0 0 0 0 0 0 0 300))
+                                              (v128.const i8x16 127 -128 1 -1 0 0 0 0 0 0 0 0 0 0 0 127))
+(assert_return (invoke "i16x8.extmul_high_i8x16_u" (v128.const i8x16 0 0 0 0 0 0 0 0 255 2 0 0 0 0 0 0) (v128.const i8x16 0 0 0 0 0 0 0 0 255 3 0 0 0 0 0 0))
+                                                   (v128.const i16x8 65025 6 0 0 0 0 0 0))
+(assert_return (invoke "i32x4.extadd_pairwise_i16x8_s" (v128.const i16x8 -32768 -32768 1 2 32767 32767 -1 1))
+                                                       (v128.const i32x4 -65536 3 65534 0))
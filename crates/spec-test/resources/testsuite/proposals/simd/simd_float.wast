@@ -0,0 +1,77 @@
+;; SIMD 浮点数运算以及转换
+
+;; (module
+;;   (func (export "f32x4.add") (param v128 v128) (result v128) (f32x4.add (local.get 0) (local.get 1)))
+;;   (func (export "f32x4.min") (param v128 v128) (result v128) (f32x4.min (local.get 0) (local.get 1)))
+;;   (func (export "f32x4.max") (param v128 v128) (result v128) (f32x4.max (local.get 0) (local.get 1)))
+;;   (func (export "f32x4.pmin") (param v128 v128) (result v128) (f32x4.pmin (local.get 0) (local.get 1)))
+;;   (func (export "f32x4.sqrt") (param v128) (result v128) (f32x4.sqrt (local.get 0)))
+;;   (func (export "f32x4.nearest") (param v128) (result v128) (f32x4.nearest (local.get 0)))
+;;   (func (export "f64x2.div") (param v128 v128) (result v128) (f64x2.div (local.get 0) (local.get 1)))
+;;   (func (export "f64x2.abs") (param v128) (result v128) (f64x2.abs (local.get 0)))
+;;   (func (export "f64x2.floor") (param v128) (result v128) (f64x2.floor (local.get 0)))
+;;   (func (export "f32x4.eq") (param v128 v128) (result v128) (f32x4.eq (local.get 0) (local.get 1)))
+;;   (func (export "f64x2.lt") (param v128 v128) (result v128) (f64x2.lt (local.get 0) (local.get 1)))
+;;   (func (export "i32x4.trunc_sat_f32x4_s") (param v128) (result v128) (i32x4.trunc_sat_f32x4_s (local.get 0)))
+;;   (func (export "i32x4.trunc_sat_f64x2_u_zero") (param v128) (result v128) (i32x4.trunc_sat_f64x2_u_zero (local.get 0)))
+;;   (func (export "f32x4.convert_i32x4_u") (param v128) (result v128) (f32x4.convert_i32x4_u (local.get 0)))
+;;   (func (export "f64x2.convert_low_i32x4_s") (param v128) (result v128) (f64x2.convert_low_i32x4_s (local.get 0)))
+;;   (func (export "f32x4.demote_f64x2_zero") (param v128) (result v128) (f32x4.demote_f64x2_zero (local.get 0)))
+;;   (func (export "f64x2.promote_low_f32x4") (param v128) (result v128) (f64x2.promote_low_f32x4 (local.get 0)))
+;; )
+(module binary
+  "\00\61\73\6d\01\00\00\00\01\0c\02\60\02\7b\7b\01\7b\60\01\7b\01\7b\03\12"
+  "\11\00\00\00\00\01\01\00\01\01\00\00\01\01\01\01\01\01\07\ac\02\11\09\66"
+  "\33\32\78\34\2e\61\64\64\00\00\09\66\33\32\78\34\2e\6d\69\6e\00\01\09\66"
+  "\33\32\78\34\2e\6d\61\78\00\02\0a\66\33\32\78\34\2e\70\6d\69\6e\00\03\0a"
+  "\66\33\32\78\34\2e\73\71\72\74\00\04\0d\66\33\32\78\34\2e\6e\65\61\72\65"
+  "\73\74\00\05\09\66\36\34\78\32\2e\64\69\76\00\06\09\66\36\34\78\32\2e\61"
+  "\62\73\00\07\0b\66\36\34\78\32\2e\66\6c\6f\6f\72\00\08\08\66\33\32\78\34"
+  "\2e\65\71\00\09\08\66\36\34\78\32\2e\6c\74\00\0a\17\69\33\32\78\34\2e\74"
+  "\72\75\6e\63\5f\73\61\74\5f\66\33\32\78\34\5f\73\00\0b\1c\69\33\32\78\34"
+  "\2e\74\72\75\6e\63\5f\73\61\74\5f\66\36\34\78\32\5f\75\5f\7a\65\72\6f\00"
+  "\0c\15\66\33\32\78\34\2e\63\6f\6e\76\65\72\74\5f\69\33\32\78\34\5f\75\00"
+  "\0d\19\66\36\34\78\32\2e\63\6f\6e\76\65\72\74\5f\6c\6f\77\5f\69\33\32\78"
+  "\34\5f\73\00\0e\17\66\33\32\78\34\2e\64\65\6d\6f\74\65\5f\66\36\34\78\32"
+  "\5f\7a\65\72\6f\00\0f\17\66\36\34\78\32\2e\70\72\6f\6d\6f\74\65\5f\6c\6f"
+  "\77\5f\66\33\32\78\34\00\10\0a\91\01\11\09\00\20\00\20\01\fd\e4\01\0b\09"
+  "\00\20\00\20\01\fd\e8\01\0b\09\00\20\00\20\01\fd\e9\01\0b\09\00\20\00\20"
+  "\01\fd\ea\01\0b\07\00\20\00\fd\e3\01\0b\06\00\20\00\fd\6a\0b\09\00\20\00"
+  "\20\01\fd\f3\01\0b\07\00\20\00\fd\ec\01\0b\06\00\20\00\fd\75\0b\08\00\20"
+  "\00\20\01\fd\41\0b\08\00\20\00\20\01\fd\49\0b\07\00\20\00\fd\f8\01\0b\07"
+  "\00\20\00\fd\fd\01\0b\07\00\20\00\fd\fb\01\0b\07\00\20\00\fd\fe\01\0b\06"
+  "\00\20\00\fd\5e\0b\06\00\20\00\fd\5f\0b"
+)
+
+(assert_return (invoke "f32x4.add" (v128.const f32x4 1.5 -2.0 inf 0x1p-149) (v128.const f32x4 1.5 2.0 1.0 0x1p-149))
+                                   (v128.const f32x4 3.0 0.0 inf 0x1p-148))
+(assert_return (invoke "f32x4.add" (v128.const f32x4 inf nan 0 0) (v128.const f32x4 -inf 1.0 0 0))
+                                   (v128.const f32x4 nan:canonical nan:canonical 0 0))
+(assert_return (invoke "f32x4.min" (v128.const f32x4 0.0 -0.0 nan 1.0) (v128.const f32x4 -0.0 0.0 1.0 nan))
+                                   (v128.const f32x4 -0.0 -0.0 nan:canonical nan:canonical))
+(assert_return (invoke "f32x4.max" (v128.const f32x4 0.0 -0.0 -inf 1.0) (v128.const f32x4 -0.0 0.0 -1.0 2.0))
+                                   (v128.const f32x4 0.0 0.0 -1.0 2.0))
+(assert_return (invoke "f32x4.max" (v128.const f32x4 nan:0x200000 0 0 0) (v128.const f32x4 1.0 0 0 0))
+                                   (v128.const f32x4 nan:arithmetic 0 0 0))
+(assert_return (invoke "f32x4.pmin" (v128.const f32x4 0.0 -0.0 nan 1.0) (v128.const f32x4 -0.0 0.0 1.0 nan))
+                                    (v128.const f32x4 0.0 -0.0 nan 1.0))
+(assert_return (invoke "f32x4.sqrt" (v128.const f32x4 4.0 -0.0 -1.0 inf)) (v128.const f32x4 2.0 -0.0 nan:canonical inf))
+(assert_return (invoke "f32x4.nearest" (v128.const f32x4 0.5 1.5 -2.5 -0.4)) (v128.const f32x4 0.0 2.0 -2.0 -0.0))
+(assert_return (invoke "f64x2.div" (v128.const f64x2 1.0 0.0) (v128.const f64x2 0.0 0.0)) (v128.const f64x2 inf nan:canonical))
+(assert_return (invoke "f64x2.abs" (v128.const f64x2 -nan -0.0)) (v128.const f64x2 nan 0.0))
+(assert_return (invoke "f64x2.floor" (v128.const f64x2 -0.5 1.5)) (v128.const f64x2 -1.0 1.0))
+(assert_return (invoke "f32x4.eq" (v128.const f32x4 0.0 nan 1.0 2.0) (v128.const f32x4 -0.0 nan 1.0 3.0))
+                                  (v128.const i32x4 -1 0 -1 0))
+(assert_return (invoke "f64x2.lt" (v128.const f64x2 -inf nan) (v128.const f64x2 0.0 1.0)) (v128.const i64x2 -1 0))
+(assert_return (invoke "i32x4.trunc_sat_f32x4_s" (v128.const f32x4 -1.9 nan 3e9 -inf))
+                                                 (v128.const i32x4 -1 0 2147483647 -2147483648))
+(assert_return (invoke "i32x4.trunc_sat_f64x2_u_zero" (v128.const f64x2 -1.0 4294967296.0))
+                                                      (v128.const i32x4 0 4294967295 0 0))
+(assert_return (invoke "f32x4.convert_i32x4_u" (v128.const i32x4 -1 0 1 0x80000000))
+                                               (v128.const f32x4 4294967296.0 0.0 1.0 2147483648.0))
+(assert_return (invoke "f64x2.convert_low_i32x4_s" (v128.const i32x4 -1 2147483647 5 6))
+                                                   (v128.const f64x2 -1.0 2147483647.0))
+(assert_return (invoke "f32x4.demote_f64x2_zero" (v128.const f64x2 1e300 0.1))
+                                                 (v128.const f32x4 inf 0x1.99999ap-4 0.0 0.0))
+(assert_return (invoke "f64x2.promote_low_f32x4" (v128.const f32x4 0.1 -inf 1.0 1.0))
+                                                 (v128.const f64x2 0x1.99999ap-4 -inf))
@@ -0,0 +1,65 @@
+;; SIMD 通道的读写、洗牌以及按位运算
+
+;; (module
+;;   (func (export "i8x16.extract_lane_s") (param v128) (result i32) (i8x16.extract_lane_s 15 (local.get 0)))
+;;   (func (export "i8x16.extract_lane_u") (param v128) (result i32) (i8x16.extract_lane_u 15 (local.get 0)))
+;;   (func (export "i64x2.replace_lane") (param v128 i64) (result v128) (i64x2.replace_lane 1 (local.get 0) (local.get 1)))
+;;   (func (export "f32x4.extract_lane") (param v128) (result f32) (f32x4.extract_lane 2 (local.get 0)))
+;;   (func (export "f64x2.splat") (param f64) (result v128) (f64x2.splat (local.get 0)))
+;;   (func (export "i8x16.swizzle") (param v128 v128) (result v128) (i8x16.swizzle (local.get 0) (local.get 1)))
+;;   (func (export "i8x16.shuffle") (param v128 v128) (result v128)
+;;     (i8x16.shuffle 31 0 30 1 29 2 28 3 27 4 26 5 25 6 24 7 (local.get 0) (local.get 1)))
+;;   (func (export "v128.bitselect") (param v128 v128 v128) (result v128) (v128.bitselect (local.get 0) (local.get 1) (local.get 2)))
+;;   (func (export "v128.andnot") (param v128 v128) (result v128) (v128.andnot (local.get 0) (local.get 1)))
+;;   (func (export "i16x8.all_true") (param v128) (result i32) (i16x8.all_true (local.get 0)))
+;;   (func (export "i32x4.bitmask") (param v128) (result i32) (i32x4.bitmask (local.get 0)))
+;;   (func (export "i64x2.ge_s") (param v128 v128) (result v128) (i64x2.ge_s (local.get 0) (local.get 1)))
+;; )
+(module binary
+  "\00\61\73\6d\01\00\00\00\01\23\06\60\01\7b\01\7f\60\02\7b\7e\01\7b\60\01"
+  "\7b\01\7d\60\01\7c\01\7b\60\02\7b\7b\01\7b\60\03\7b\7b\7b\01\7b\03\0d\0c"
+  "\00\00\01\02\03\04\04\05\04\00\00\04\07\d4\01\0c\14\69\38\78\31\36\2e\65"
+  "\78\74\72\61\63\74\5f\6c\61\6e\65\5f\73\00\00\14\69\38\78\31\36\2e\65\78"
+  "\74\72\61\63\74\5f\6c\61\6e\65\5f\75\00\01\12\69\36\34\78\32\2e\72\65\70"
+  "\6c\61\63\65\5f\6c\61\6e\65\00\02\12\66\33\32\78\34\2e\65\78\74\72\61\63"
+  "\74\5f\6c\61\6e\65\00\03\0b\66\36\34\78\32\2e\73\70\6c\61\74\00\04\0d\69"
+  "\38\78\31\36\2e\73\77\69\7a\7a\6c\65\00\05\0d\69\38\78\31\36\2e\73\68\75"
+  "\66\66\6c\65\00\06\0e\76\31\32\38\2e\62\69\74\73\65\6c\65\63\74\00\07\0b"
+  "\76\31\32\38\2e\61\6e\64\6e\6f\74\00\08\0e\69\31\36\78\38\2e\61\6c\6c\5f"
+  "\74\72\75\65\00\09\0d\69\33\32\78\34\2e\62\69\74\6d\61\73\6b\00\0a\0a\69"
+  "\36\34\78\32\2e\67\65\5f\73\00\0b\0a\7a\0c\07\00\20\00\fd\15\0f\0b\07\00"
+  "\20\00\fd\16\0f\0b\09\00\20\00\20\01\fd\1e\01\0b\07\00\20\00\fd\1f\02\0b"
+  "\06\00\20\00\fd\14\0b\08\00\20\00\20\01\fd\0e\0b\18\00\20\00\20\01\fd\0d"
+  "\1f\00\1e\01\1d\02\1c\03\1b\04\1a\05\19\06\18\07\0b\0a\00\20\00\20\01\20"
+  "\02\fd\52\0b\08\00\20\00\20\01\fd\4f\0b\07\00\20\00\fd\83\01\0b\07\00\20"
+  "\00\fd\a4\01\0b\09\00\20\00\20\01\fd\db\01\0b"
+)
+
+(assert_return (invoke "i8x16.extract_lane_s" (v128.const i8x16 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 -1)) (i32.const -1))
+(assert_return (invoke "i8x16.extract_lane_u" (v128.const i8x16 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 -1)) (i32.const 255))
+(assert_return (invoke "i64x2.replace_lane" (v128.const i64x2 1 2) (i64.const -1)) (v128.const i64x2 1 -1))
+(assert_return (invoke "f32x4.extract_lane" (v128.const f32x4 0 0 nan:0x1 0)) (f32.const nan:0x1))
+(assert_return (invoke "f64x2.splat" (f64.const -0x1.8p+1)) (v128.const f64x2 -3.0 -3.0))
+(assert_return (invoke "i8x16.swizzle" (v128.const i8x16 0xa0 0xa1 0xa2 0xa3 0xa4 0xa5 0xa6 0xa7 0xa8 0xa9 0xaa 0xab 0xac 0xad 0xae 0xaf)
+                                       (v128.const i8x16 15 0 16 255 1 1 1 1 1 1 1 1 1 1 1 14))
+                                       (v128.const i8x16 0xaf 0xa0 0 0 0xa1 0xa1 0xa1 0xa1 0xa1 0xa1 0xa1 0xa1 0xa1 0xa1 0xa1 0xae))
+(assert_return (invoke "i8x16.shuffle" (v128.const i8x16 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15)
+                                       (v128.const i8x16 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31))
+                                       (v128.const i8x16 31 0 30 1 29 2 28 3 27 4 26 5 25 6 24 7))
+(assert_return (invoke "v128.bitselect" (v128.const i32x4 0x12345678 0 -1 0) (v128.const i32x4 0 -1 0 0x55555555) (v128.const i32x4 0xffff0000 0 -1 0xf0f0f0f0))
+                                        (v128.const i32x4 0x12340000 -1 -1 0x05050505))
+(assert_return (invoke "v128.andnot" (v128.const i32x4 0xff 0xff 0 -1) (v128.const i32x4 0x0f 0 -1 0x80000000))
+                                     (v128.const i32x4 0xf0 0xff 0 0x7fffffff))
+(assert_return (invoke "i16x8.all_true" (v128.const i16x8 1 1 1 1 1 1 1 1)) (i32.const 1))
+(assert_return (invoke "i16x8.all_true" (v128.const i16x8 1 1 1 1 1 1 1 0)) (i32.const 0))
+(assert_return (invoke "i32x4.bitmask" (v128.const i32x4 -1 0 0x80000000 1)) (i32.const 5))
+(assert_return (invoke "i64x2.ge_s" (v128.const i64x2 -1 1) (v128.const i64x2 0 1)) (v128.const i64x2 0 -1))
+
+;; 通道索引超出范围
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\06\01\60\01\7b\01\7f\03\02\01\00\0a\09\01\07"
+    "\00\20\00\fd\1b\04\0b"
+  )
+  "invalid lane index"
+)
@@ -0,0 +1,53 @@
+;; SIMD 内存访问
+
+;; (module
+;;   (memory 1)
+;;   (data (i32.const 0) "\00\01\02\03\04\05\06\07\08\09\0a\0b\0c\0d\0e\0f\80\81\82\83\84\85\86\87")
+;;   (func (export "v128.load") (param i32) (result v128) (v128.load (local.get 0)))
+;;   (func (export "v128.load_offset") (param i32) (result v128) (v128.load offset=8 align=1 (local.get 0)))
+;;   (func (export "v128.load8x8_s") (param i32) (result v128) (v128.load8x8_s offset=16 (local.get 0)))
+;;   (func (export "v128.load16x4_u") (param i32) (result v128) (v128.load16x4_u offset=16 (local.get 0)))
+;;   (func (export "v128.load32_zero") (param i32) (result v128) (v128.load32_zero (local.get 0)))
+;;   (func (export "v128.load64_splat") (param i32) (result v128) (v128.load64_splat (local.get 0)))
+;;   (func (export "v128.store") (param i32 v128) (result v128)
+;;     (v128.store offset=32 (local.get 0) (local.get 1))
+;;     (v128.load offset=32 (local.get 0))
+;;   )
+;;   (func (export "v128.load16_lane") (param i32 v128) (result v128) (v128.load16_lane 7 (local.get 0) (local.get 1)))
+;;   (func (export "v128.store8_lane") (param i32 v128) (result i32)
+;;     (v128.store8_lane offset=64 1 (local.get 0) (local.get 1))
+;;     (i32.load8_u offset=64 (local.get 0))
+;;   )
+;; )
+(module binary
+  "\00\61\73\6d\01\00\00\00\01\12\03\60\01\7f\01\7b\60\02\7f\7b\01\7b\60\02"
+  "\7f\7b\01\7f\03\0a\09\00\00\00\00\00\00\01\01\02\05\03\01\00\01\07\9d\01"
+  "\09\09\76\31\32\38\2e\6c\6f\61\64\00\00\10\76\31\32\38\2e\6c\6f\61\64\5f"
+  "\6f\66\66\73\65\74\00\01\0e\76\31\32\38\2e\6c\6f\61\64\38\78\38\5f\73\00"
+  "\02\0f\76\31\32\38\2e\6c\6f\61\64\31\36\78\34\5f\75\00\03\10\76\31\32\38"
+  "\2e\6c\6f\61\64\33\32\5f\7a\65\72\6f\00\04\11\76\31\32\38\2e\6c\6f\61\64"
+  "\36\34\5f\73\70\6c\61\74\00\05\0a\76\31\32\38\2e\73\74\6f\72\65\00\06\10"
+  "\76\31\32\38\2e\6c\6f\61\64\31\36\5f\6c\61\6e\65\00\07\10\76\31\32\38\2e"
+  "\73\74\6f\72\65\38\5f\6c\61\6e\65\00\08\0a\65\09\08\00\20\00\fd\00\04\00"
+  "\0b\08\00\20\00\fd\00\00\08\0b\08\00\20\00\fd\01\03\10\0b\08\00\20\00\fd"
+  "\04\03\10\0b\08\00\20\00\fd\5c\02\00\0b\08\00\20\00\fd\0a\03\00\0b\10\00"
+  "\20\00\20\01\fd\0b\04\20\20\00\fd\00\04\20\0b\0b\00\20\00\20\01\fd\55\01"
+  "\00\07\0b\10\00\20\00\20\01\fd\58\00\40\01\20\00\2d\00\40\0b\0b\1e\01\00"
+  "\41\00\0b\18\00\01\02\03\04\05\06\07\08\09\0a\0b\0c\0d\0e\0f\80\81\82\83"
+  "\84\85\86\87"
+)
+
+(assert_return (invoke "v128.load" (i32.const 0)) (v128.const i8x16 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15))
+(assert_return (invoke "v128.load_offset" (i32.const 1)) (v128.const i8x16 9 10 11 12 13 14 15 0x80 0x81 0x82 0x83 0x84 0x85 0x86 0x87 0))
+(assert_return (invoke "v128.load8x8_s" (i32.const 0)) (v128.const i16x8 -128 -127 -126 -125 -124 -123 -122 -121))
+(assert_return (invoke "v128.load16x4_u" (i32.const 0)) (v128.const i32x4 0x8180 0x8382 0x8584 0x8786))
+(assert_return (invoke "v128.load32_zero" (i32.const 4)) (v128.const i32x4 0x07060504 0 0 0))
+(assert_return (invoke "v128.load64_splat" (i32.const 0)) (v128.const i64x2 0x0706050403020100 0x0706050403020100))
+(assert_return (invoke "v128.store" (i32.const 1) (v128.const i64x2 -1 2)) (v128.const i64x2 -1 2))
+(assert_return (invoke "v128.load16_lane" (i32.const 2) (v128.const i64x2 0 0)) (v128.const i16x8 0 0 0 0 0 0 0 0x0302))
+(assert_return (invoke "v128.store8_lane" (i32.const 0) (v128.const i8x16 0 99 0 0 0 0 0 0 0 0 0 0 0 0 0 0)) (i32.const 99))
+
+(assert_trap (invoke "v128.load" (i32.const 65521)) "out of bounds memory access")
+(assert_trap (invoke "v128.load_offset" (i32.const -1)) "out of bounds memory access")
+(assert_trap (invoke "v128.load64_splat" (i32.const 65529)) "out of bounds memory access")
+(assert_trap (invoke "v128.store8_lane" (i32.const 65472) (v128.const i64x2 0 0)) "out of bounds memory access")
@@ -0,0 +1,60 @@
+;; start function
+
+;; (module
+;;   (memory (data "A"))
+;;   (func $inc
+;;     (i32.store8 (i32.const 0) (i32.add (i32.load8_u (i32.const 0)) (i32.const 1))))
+;;   (func (export "get") (result i32) (i32.load8_u (i32.const 0)))
+;;   (func $main (call $inc) (call $inc))
+;;   (start $main)
+;; )
+(module binary
+  "\00\61\73\6d\01\00\00\00\01\08\02\60\00\00\60\00\01\7f\03\04\03\00\01\00"
+  "\05\04\01\01\01\01\07\07\01\03\67\65\74\00\01\08\01\02\0a\20\03\0f\00\41"
+  "\00\41\00\2d\00\00\41\01\6a\3a\00\00\0b\07\00\41\00\2d\00\00\0b\06\00\10"
+  "\00\10\00\0b\0b\07\01\00\41\00\0b\01\41"
+)
+(assert_return (invoke "get") (i32.const 67))
+
+;; (module
+;;   (func $print (import "spectest" "print"))
+;;   (start $print)
+;; )
+(module binary
+  "\00\61\73\6d\01\00\00\00\01\04\01\60\00\00\02\12\01\08\73\70\65\63\74\65"
+  "\73\74\05\70\72\69\6e\74\00\00\08\01\00"
+)
+
+;; (module (func $main (unreachable)) (start $main))
+(assert_trap
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\04\01\60\00\00\03\02\01\00\08\01\00\0a\05\01"
+    "\03\00\00\0b"
+  )
+  "unreachable"
+)
+
+;; (module (func $main (result i32) (i32.const 0)) (start $main))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\05\01\60\00\01\7f\03\02\01\00\08\01\00\0a\06"
+    "\01\04\00\41\00\0b"
+  )
+  "start function"
+)
+;; (module (func $main (param i32)) (start $main))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\05\01\60\01\7f\00\03\02\01\00\08\01\00\0a\04"
+    "\01\02\00\0b"
+  )
+  "start function"
+)
+;; (module (func) (start 1))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\04\01\60\00\00\03\02\01\00\08\01\01\0a\04\01"
+    "\02\00\0b"
+  )
+  "unknown function"
+)
@@ -0,0 +1,22 @@
+;; unreachable
+
+;; (module
+;;   (func (export "unreachable") (unreachable))
+;;   (func (export "in-block") (result i32) (block (result i32) (i32.const 1) (unreachable)))
+;;   (func (export "in-if") (param i32) (result i32)
+;;     (if (result i32) (local.get 0) (then (unreachable)) (else (i32.const 7))))
+;;   (func (export "after-return") (result i32) (return (i32.const 3)) (unreachable))
+;; )
+(module binary
+  "\00\61\73\6d\01\00\00\00\01\0d\03\60\00\00\60\00\01\7f\60\01\7f\01\7f\03"
+  "\05\04\00\01\02\01\07\31\04\0b\75\6e\72\65\61\63\68\61\62\6c\65\00\00\08"
+  "\69\6e\2d\62\6c\6f\63\6b\00\01\05\69\6e\2d\69\66\00\02\0c\61\66\74\65\72"
+  "\2d\72\65\74\75\72\6e\00\03\0a\21\04\03\00\00\0b\08\00\02\7f\41\01\00\0b"
+  "\0b\0b\00\20\00\04\7f\00\05\41\07\0b\0b\06\00\41\03\0f\00\0b"
+)
+
+(assert_trap (invoke "unreachable") "unreachable")
+(assert_trap (invoke "in-block") "unreachable")
+(assert_trap (invoke "in-if" (i32.const 1)) "unreachable")
+(assert_return (invoke "in-if" (i32.const 0)) (i32.const 7))
+(assert_return (invoke "after-return") (i32.const 3))
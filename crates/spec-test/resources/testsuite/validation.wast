@@ -0,0 +1,179 @@
+;; module validation and binary format
+
+(assert_malformed (module binary "") "unexpected end")
+(assert_malformed (module binary "\00asm") "unexpected end")
+(assert_malformed (module binary "asm\00") "magic header not detected")
+(assert_malformed (module binary "\00asm\00\00\00\00") "unknown binary version")
+(assert_malformed (module binary "\00asm\02\00\00\00") "unknown binary version")
+(assert_malformed (module binary "\00asm\01\00\00\00\0d\00") "malformed section id")
+(assert_malformed (module binary "\00asm\01\00\00\00\01\05\01\60\00") "unexpected end")
+(module binary "\00asm\01\00\00\00")
+
+;; (module (func (result i32) (i64.const 0)))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\05\01\60\00\01\7f\03\02\01\00\0a\06\01\04\00"
+    "\42\00\0b"
+  )
+  "type mismatch"
+)
+;; (module (func (result i32)))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\05\01\60\00\01\7f\03\02\01\00\0a\04\01\02\00"
+    "\0b"
+  )
+  "type mismatch"
+)
+;; (module (func (i32.const 0)))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\04\01\60\00\00\03\02\01\00\0a\06\01\04\00\41"
+    "\00\0b"
+  )
+  "type mismatch"
+)
+;; (module (func (result i32) (i32.add (i32.const 0))))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\05\01\60\00\01\7f\03\02\01\00\0a\07\01\05\00"
+    "\41\00\6a\0b"
+  )
+  "type mismatch"
+)
+;; (module (func (param f32) (result i32) (i32.eqz (local.get 0))))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\06\01\60\01\7d\01\7f\03\02\01\00\0a\07\01\05"
+    "\00\20\00\45\0b"
+  )
+  "type mismatch"
+)
+;; (module (func (local.get 0) (drop)))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\04\01\60\00\00\03\02\01\00\0a\07\01\05\00\20"
+    "\00\1a\0b"
+  )
+  "unknown local"
+)
+;; (module (func (call 1)))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\04\01\60\00\00\03\02\01\00\0a\06\01\04\00\10"
+    "\01\0b"
+  )
+  "unknown function"
+)
+;; (module (func (br 1)))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\04\01\60\00\00\03\02\01\00\0a\06\01\04\00\0c"
+    "\01\0b"
+  )
+  "unknown label"
+)
+;; (module (func (result i32) (block (result i32) (br 0)) ))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\05\01\60\00\01\7f\03\02\01\00\0a\09\01\07\00"
+    "\02\7f\0c\00\0b\0b"
+  )
+  "type mismatch"
+)
+;; (module (func (result i32) (if (result i32) (i32.const 1) (then (i32.const 1)))))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\05\01\60\00\01\7f\03\02\01\00\0a\0b\01\09\00"
+    "\41\01\04\7f\41\01\0b\0b"
+  )
+  "type mismatch"
+)
+;; (module (func (block (result i32) (block (result i64) (br_table 0 1 (i32.const 0) (i32.const 0)))) (drop)))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\04\01\60\00\00\03\02\01\00\0a\13\01\11\00\02"
+    "\7f\02\7e\41\00\41\00\0e\01\00\01\0b\0b\1a\0b"
+  )
+  "type mismatch"
+)
+;; (module (func (i32.load (i32.const 0)) (drop)))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\04\01\60\00\00\03\02\01\00\0a\0a\01\08\00\41"
+    "\00\28\02\00\1a\0b"
+  )
+  "unknown memory"
+)
+;; (module (memory 1) (func (i32.load align=8 (i32.const 0)) (drop)))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\04\01\60\00\00\03\02\01\00\05\03\01\00\01\0a"
+    "\0a\01\08\00\41\00\28\03\00\1a\0b"
+  )
+  "alignment must not be larger than natural"
+)
+;; (module (func (call_indirect (type 0) (i32.const 0))) (type (func)))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\04\01\60\00\00\03\02\01\00\0a\09\01\07\00\41"
+    "\00\11\00\00\0b"
+  )
+  "unknown table"
+)
+;; (module (memory 1 0))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\05\04\01\01\01\00"
+  )
+  "size minimum must not be greater than maximum"
+)
+;; (module (memory 65537))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\05\05\01\00\81\80\04"
+  )
+  "memory size must be at most 65536 pages (4GiB)"
+)
+;; (module (func) (export "a" (func 0)) (export "a" (func 0)))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\01\04\01\60\00\00\03\02\01\00\07\09\02\01\61\00"
+    "\00\01\61\00\00\0a\04\01\02\00\0b"
+  )
+  "duplicate export name"
+)
+;; (module (export "a" (func 0)))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\07\05\01\01\61\00\00"
+  )
+  "unknown function"
+)
+;; (module (data (i32.const 0) ""))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\0b\06\01\00\41\00\0b\00"
+  )
+  "unknown memory"
+)
+;; (module (table 1 funcref) (elem (i32.const 0) 0))
+(assert_invalid
+  (module binary
+    "\00\61\73\6d\01\00\00\00\04\04\01\70\00\01\09\07\01\00\41\00\0b\01\00"
+  )
+  "unknown function"
+)
+
+;; (module
+;;   (func (export "unreachable-typed") (result i32) (unreachable) (i32.add))
+;;   (func (export "br-polymorphic") (result i32) (block (result i32) (i32.const 1) (br 0) (i32.add)))
+;; )
+(module binary
+  "\00\61\73\6d\01\00\00\00\01\05\01\60\00\01\7f\03\03\02\00\00\07\26\02\11"
+  "\75\6e\72\65\61\63\68\61\62\6c\65\2d\74\79\70\65\64\00\00\0e\62\72\2d\70"
+  "\6f\6c\79\6d\6f\72\70\68\69\63\00\01\0a\11\02\04\00\00\6a\0b\0a\00\02\7f"
+  "\41\01\0c\00\6a\0b\0b"
+)
+(assert_trap (invoke "unreachable-typed") "unreachable")
+(assert_return (invoke "br-polymorphic") (i32.const 1))
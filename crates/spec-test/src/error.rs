@@ -0,0 +1,46 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::fmt::Display;
+
+use anvm_assembly::error::AssemblyError;
+
+/// 测试脚本（`.wast`）的错误
+///
+/// 注意断言失败不属于脚本错误，断言的结果记录在测试报告里。
+#[derive(Debug, PartialEq, Clone)]
+pub enum ScriptError {
+    /// 词法错误，比如字符串缺少结束的引号、括号不配对等
+    LexicalError(/* line */ usize, /* message */ String),
+
+    /// 语法错误，比如未知的命令、数值格式不正确等
+    SyntaxError(/* line */ usize, /* message */ String),
+}
+
+impl Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScriptError::LexicalError(line, message) => {
+                write!(f, "lexical error at line {}: {}", line, message)
+            }
+            ScriptError::SyntaxError(line, message) => {
+                write!(f, "syntax error at line {}: {}", line, message)
+            }
+        }
+    }
+}
+
+impl From<AssemblyError> for ScriptError {
+    fn from(e: AssemblyError) -> Self {
+        match e {
+            AssemblyError::LexicalError(line, message) => ScriptError::LexicalError(line, message),
+            AssemblyError::SyntaxError(line, message)
+            | AssemblyError::UnsupportedError(line, message) => {
+                ScriptError::SyntaxError(line, message)
+            }
+        }
+    }
+}
@@ -0,0 +1,850 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 测试脚本的运行器
+//!
+//! 运行器逐个执行测试脚本里的命令，并记录每个命令（断言）的结果：
+//!
+//! - `Passed`，断言成立；
+//! - `Failed`，断言不成立，或者 VM 在执行过程中崩溃（panic）；
+//! - `Skipped`，命令含有 VM 尚不支持的内容，比如被动的数据段、引用类型的数值等。
+//!
+//! 文本格式的模块（`(module ...)` 以及 `(module quote ...)`）由 `anvm_assembly` 汇编器
+//! 转换为语法树，汇编失败的模块视为格式错误（malformed）。
+//!
+//! 每个 `module` 命令都会创建一个新的 VM 实例，实例里包含 `spectest` 模块、
+//! 所有已注册（`register`）的模块以及当前模块。
+//! 需注意已注册的模块会在新的 VM 实例里重新实例化，所以模块在注册之后的状态（比如全局变量、
+//! 内存的内容）并不会在不同的实例之间共享。
+//!
+//! 断言的判断规则：
+//!
+//! - `assert_invalid` 和 `assert_malformed` 只要模块被解析器或者验证器拒绝即视为通过，
+//!   并不检查错误的具体原因；
+//! - `assert_trap` 只要 VM 返回错误即视为通过，同样不检查错误信息；
+//! - `assert_exhaustion` 要求 VM 返回栈溢出错误；
+//! - `assert_unlinkable` 要求模块能通过验证，但在实例化时返回错误；
+//! - `assert_return` 的浮点数按二进制位比较，`nan:canonical` 和 `nan:arithmetic`
+//!   则按 NaN 的类别比较。
+//!
+//! 启用 `jit` 特性时，VM 实例使用 JIT 编译器执行函数。
+
+use std::{
+    fs, io,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+use anvm_assembly::{assembler, error::AssemblyError};
+use anvm_ast::{
+    ast::{ExportDescriptor, Module},
+    types::{RefType, Value, ValueType},
+};
+use anvm_binary_parser::parser;
+use anvm_engine::{
+    error::{EngineError, Overflow},
+    instance::{create_instance_with_interpreter, find_ast_module_export_function},
+    object::NamedAstModule,
+    vm::{InterpreterKind, Status, VM},
+    vm_stack::VMStack,
+};
+use anvm_validator::validator::validate;
+
+use crate::{
+    error::ScriptError,
+    script::{self, Action, ActionOrModule, Command, ExpectedValue, ModuleSource},
+    spectest_module::{create_spectest_module, SPECTEST_MODULE_NAME},
+};
+
+/// 测试脚本里的模块（即非注册的模块）在 VM 实例里的名称
+const SCRIPT_MODULE_NAME: &str = "$script";
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Outcome {
+    Passed,
+    Failed(/* reason */ String),
+    Skipped(/* reason */ String),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CommandResult {
+    pub line: usize,
+    pub outcome: Outcome,
+}
+
+/// 一个提案（proposal）的测试结果
+///
+/// 测试集根目录下的脚本属于核心规范（`core`），`proposals/<name>` 目录下的脚本
+/// 属于名为 `<name>` 的提案。
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ProposalReport {
+    pub name: String,
+    pub passed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+
+    /// (脚本文件名, 行号, 原因)
+    pub failures: Vec<(String, usize, String)>,
+
+    /// (脚本文件名, 错误信息)
+    pub script_errors: Vec<(String, String)>,
+}
+
+/// 执行测试脚本
+pub fn run_script(source: &str) -> Result<Vec<CommandResult>, ScriptError> {
+    let script_commands = script::parse(source)?;
+    let mut runner = Runner::new();
+
+    let results = script_commands
+        .iter()
+        .map(|script_command| CommandResult {
+            line: script_command.line,
+            outcome: runner.run_command(&script_command.command),
+        })
+        .collect::<Vec<CommandResult>>();
+
+    Ok(results)
+}
+
+/// 执行测试集目录里的所有脚本，并按提案汇总结果
+///
+/// 目录结构：
+///
+/// ```text
+/// testsuite/
+/// |-- *.wast
+/// \-- proposals/
+///     \-- <name>/
+///         \-- *.wast
+/// ```
+pub fn run_test_suite(testsuite_path: &Path) -> io::Result<Vec<ProposalReport>> {
+    let mut reports: Vec<ProposalReport> = vec![];
+
+    reports.push(run_scripts("core", &list_script_files(testsuite_path)?)?);
+
+    let proposals_path = testsuite_path.join("proposals");
+    if proposals_path.is_dir() {
+        let mut proposal_paths = fs::read_dir(&proposals_path)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<io::Result<Vec<PathBuf>>>()?;
+        proposal_paths.sort();
+
+        for proposal_path in proposal_paths.iter().filter(|p| p.is_dir()) {
+            let name = proposal_path
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .to_string();
+            reports.push(run_scripts(&name, &list_script_files(proposal_path)?)?);
+        }
+    }
+
+    Ok(reports)
+}
+
+fn list_script_files(directory: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut script_files = fs::read_dir(directory)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<Vec<PathBuf>>>()?
+        .into_iter()
+        .filter(|path| path.is_file() && path.extension().is_some_and(|e| e == "wast"))
+        .collect::<Vec<PathBuf>>();
+    script_files.sort();
+    Ok(script_files)
+}
+
+fn run_scripts(name: &str, script_files: &[PathBuf]) -> io::Result<ProposalReport> {
+    let mut report = ProposalReport {
+        name: name.to_string(),
+        ..ProposalReport::default()
+    };
+
+    for script_file in script_files {
+        let file_name = script_file
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .to_string();
+        let source = fs::read_to_string(script_file)?;
+
+        match run_script(&source) {
+            Ok(results) => {
+                for result in results {
+                    match result.outcome {
+                        Outcome::Passed => report.passed += 1,
+                        Outcome::Skipped(_) => report.skipped += 1,
+                        Outcome::Failed(reason) => {
+                            report.failed += 1;
+                            report
+                                .failures
+                                .push((file_name.clone(), result.line, reason));
+                        }
+                    }
+                }
+            }
+            Err(e) => report.script_errors.push((file_name, e.to_string())),
+        }
+    }
+
+    Ok(report)
+}
+
+struct Instance {
+    /// 实例里的所有模块，最后一个是测试脚本的模块
+    named_ast_modules: Vec<NamedAstModule>,
+    vm: VM,
+}
+
+impl Instance {
+    fn get_script_module_index(&self) -> usize {
+        self.named_ast_modules.len() - 1
+    }
+
+    fn get_script_module(&self) -> &Module {
+        &self.named_ast_modules[self.get_script_module_index()].module
+    }
+}
+
+/// 模块在解析、验证或者实例化过程中产生的错误
+enum ModuleError {
+    Unsupported(String),
+    Malformed(String),
+    Invalid(String),
+    Uninstantiable(Termination),
+
+    /// 解析器或者验证器崩溃（panic）
+    Crashed(String),
+}
+
+/// VM 执行过程中止的原因
+enum Termination {
+    Error(EngineError),
+    Panic(String),
+}
+
+impl Termination {
+    fn to_reason(&self) -> String {
+        match self {
+            Termination::Error(e) => format!("engine error: {}", e),
+            Termination::Panic(message) => format!("engine panicked: {}", message),
+        }
+    }
+}
+
+enum ActionError {
+    /// 模块不存在或者模块被跳过
+    Unavailable(String),
+
+    /// 导出项不存在
+    ExportNotFound(String),
+
+    Terminated(Termination),
+}
+
+struct Runner {
+    /// 已注册的模块，名称为注册名称
+    registered_modules: Vec<NamedAstModule>,
+
+    /// 所有 `module` 命令所创建的实例，如果模块被跳过或者实例化失败，则为 None
+    instances: Vec<Option<Instance>>,
+
+    /// (模块 id, 实例的索引)
+    instance_ids: Vec<(String, usize)>,
+}
+
+impl Runner {
+    fn new() -> Self {
+        Self {
+            registered_modules: vec![],
+            instances: vec![],
+            instance_ids: vec![],
+        }
+    }
+
+    fn run_command(&mut self, command: &Command) -> Outcome {
+        match command {
+            Command::Module(option_id, module_source) => {
+                let (outcome, option_instance) = match self.instantiate(module_source) {
+                    Ok(instance) => (Outcome::Passed, Some(instance)),
+                    Err(ModuleError::Unsupported(reason)) => (Outcome::Skipped(reason), None),
+                    Err(ModuleError::Malformed(reason))
+                    | Err(ModuleError::Invalid(reason))
+                    | Err(ModuleError::Crashed(reason)) => (Outcome::Failed(reason), None),
+                    Err(ModuleError::Uninstantiable(termination)) => {
+                        (Outcome::Failed(termination.to_reason()), None)
+                    }
+                };
+
+                self.instances.push(option_instance);
+
+                if let Some(id) = option_id {
+                    let index = self.instances.len() - 1;
+                    self.instance_ids.retain(|(i, _)| i != id);
+                    self.instance_ids.push((id.clone(), index));
+                }
+
+                outcome
+            }
+            Command::Register(name, option_id) => match self.get_instance(option_id) {
+                Ok(instance) => {
+                    let mut named_ast_module =
+                        instance.named_ast_modules[instance.get_script_module_index()].clone();
+                    named_ast_module.name = name.clone();
+
+                    self.registered_modules.retain(|m| &m.name != name);
+                    self.registered_modules.push(named_ast_module);
+                    Outcome::Passed
+                }
+                Err(ActionError::Unavailable(reason)) => Outcome::Skipped(reason),
+                Err(ActionError::ExportNotFound(reason)) => Outcome::Failed(reason),
+                Err(ActionError::Terminated(termination)) => {
+                    Outcome::Failed(termination.to_reason())
+                }
+            },
+            Command::Action(action) => match self.perform_action(action) {
+                Ok(_) => Outcome::Passed,
+                Err(ActionError::Unavailable(reason)) => Outcome::Skipped(reason),
+                Err(ActionError::ExportNotFound(reason)) => Outcome::Failed(reason),
+                Err(ActionError::Terminated(termination)) => {
+                    Outcome::Failed(termination.to_reason())
+                }
+            },
+            Command::AssertReturn(action, expected_values) => match self.perform_action(action) {
+                Ok(values) => check_values(&values, expected_values),
+                Err(ActionError::Unavailable(reason)) => Outcome::Skipped(reason),
+                Err(ActionError::ExportNotFound(reason)) => Outcome::Failed(reason),
+                Err(ActionError::Terminated(termination)) => {
+                    Outcome::Failed(termination.to_reason())
+                }
+            },
+            Command::AssertTrap(ActionOrModule::Action(action), message) => {
+                match self.perform_action(action) {
+                    Ok(values) => Outcome::Failed(format!(
+                        "expected trap \"{}\", actual results: {:?}",
+                        message, values
+                    )),
+                    Err(ActionError::Unavailable(reason)) => Outcome::Skipped(reason),
+                    Err(ActionError::ExportNotFound(reason)) => Outcome::Failed(reason),
+                    Err(ActionError::Terminated(Termination::Error(_))) => Outcome::Passed,
+                    Err(ActionError::Terminated(termination)) => {
+                        Outcome::Failed(termination.to_reason())
+                    }
+                }
+            }
+            Command::AssertTrap(ActionOrModule::Module(module_source), message) => {
+                match self.instantiate(module_source) {
+                    Ok(_) => Outcome::Failed(format!(
+                        "expected trap \"{}\", but the module was instantiated successfully",
+                        message
+                    )),
+                    Err(ModuleError::Unsupported(reason)) => Outcome::Skipped(reason),
+                    Err(ModuleError::Uninstantiable(Termination::Error(_))) => Outcome::Passed,
+                    Err(ModuleError::Uninstantiable(termination)) => {
+                        Outcome::Failed(termination.to_reason())
+                    }
+                    Err(ModuleError::Malformed(reason))
+                    | Err(ModuleError::Invalid(reason))
+                    | Err(ModuleError::Crashed(reason)) => Outcome::Failed(reason),
+                }
+            }
+            Command::AssertExhaustion(action, message) => match self.perform_action(action) {
+                Ok(values) => Outcome::Failed(format!(
+                    "expected exhaustion \"{}\", actual results: {:?}",
+                    message, values
+                )),
+                Err(ActionError::Unavailable(reason)) => Outcome::Skipped(reason),
+                Err(ActionError::ExportNotFound(reason)) => Outcome::Failed(reason),
+                Err(ActionError::Terminated(Termination::Error(EngineError::Overflow(
+                    Overflow::StackOverflow(_),
+                )))) => Outcome::Passed,
+                Err(ActionError::Terminated(termination)) => Outcome::Failed(format!(
+                    "expected exhaustion \"{}\", actual {}",
+                    message,
+                    termination.to_reason()
+                )),
+            },
+            Command::AssertInvalid(module_source, message)
+            | Command::AssertMalformed(module_source, message) => {
+                match load_module(module_source) {
+                    Ok(_) => Outcome::Failed(format!(
+                        "expected module rejection \"{}\", but the module was accepted",
+                        message
+                    )),
+                    Err(ModuleError::Unsupported(reason)) => Outcome::Skipped(reason),
+                    Err(ModuleError::Crashed(reason)) => Outcome::Failed(reason),
+                    Err(_) => Outcome::Passed,
+                }
+            }
+            Command::AssertUnlinkable(module_source, message) => {
+                match self.instantiate(module_source) {
+                    Ok(_) => Outcome::Failed(format!(
+                        "expected link error \"{}\", but the module was instantiated successfully",
+                        message
+                    )),
+                    Err(ModuleError::Unsupported(reason)) => Outcome::Skipped(reason),
+                    Err(ModuleError::Uninstantiable(Termination::Error(_))) => Outcome::Passed,
+                    Err(ModuleError::Uninstantiable(termination)) => {
+                        Outcome::Failed(termination.to_reason())
+                    }
+                    Err(ModuleError::Malformed(reason))
+                    | Err(ModuleError::Invalid(reason))
+                    | Err(ModuleError::Crashed(reason)) => Outcome::Failed(reason),
+                }
+            }
+            Command::Unsupported(description) => {
+                Outcome::Skipped(format!("unsupported {}", description))
+            }
+        }
+    }
+
+    fn instantiate(&self, module_source: &ModuleSource) -> Result<Instance, ModuleError> {
+        let module = load_module(module_source)?;
+
+        let mut named_ast_modules: Vec<NamedAstModule> = vec![NamedAstModule::new(
+            SPECTEST_MODULE_NAME,
+            create_spectest_module(),
+        )];
+        named_ast_modules.extend(self.registered_modules.iter().cloned());
+        named_ast_modules.push(NamedAstModule::new(SCRIPT_MODULE_NAME, module));
+
+        let option_start_function_index = named_ast_modules
+            .last()
+            .unwrap()
+            .module
+            .start_function_index;
+        let script_module_index = named_ast_modules.len() - 1;
+
+        let vm = catch_termination(|| {
+            let mut vm = create_vm(&named_ast_modules)?;
+            if let Some(function_index) = option_start_function_index {
+                vm.eval_function_by_index(script_module_index, function_index as usize, &[])?;
+            }
+            Ok(vm)
+        })
+        .map_err(ModuleError::Uninstantiable)?;
+
+        Ok(Instance {
+            named_ast_modules,
+            vm,
+        })
+    }
+
+    fn get_instance(&self, option_id: &Option<String>) -> Result<&Instance, ActionError> {
+        let option_index = match option_id {
+            Some(id) => self
+                .instance_ids
+                .iter()
+                .find(|(i, _)| i == id)
+                .map(|(_, index)| *index),
+            None => self.instances.len().checked_sub(1),
+        };
+
+        option_index
+            .and_then(|index| self.instances[index].as_ref())
+            .ok_or_else(|| ActionError::Unavailable("the module is not available".to_string()))
+    }
+
+    fn perform_action(&mut self, action: &Action) -> Result<Vec<Value>, ActionError> {
+        let option_id = match action {
+            Action::Invoke(option_id, _, _) => option_id,
+            Action::Get(option_id, _) => option_id,
+        };
+
+        // 先确认实例存在，再获取可变引用
+        self.get_instance(option_id)?;
+        let index = match option_id {
+            Some(id) => self
+                .instance_ids
+                .iter()
+                .find(|(i, _)| i == id)
+                .map(|(_, index)| *index)
+                .unwrap(),
+            None => self.instances.len() - 1,
+        };
+        let instance = self.instances[index].as_mut().unwrap();
+
+        match action {
+            Action::Invoke(_, name, arguments) => {
+                let function_index =
+                    find_ast_module_export_function(instance.get_script_module(), name)
+                        .ok_or_else(|| {
+                            ActionError::ExportNotFound(format!(
+                                "export function \"{}\" not found",
+                                name
+                            ))
+                        })?;
+
+                let vm_module_index = instance.get_script_module_index();
+                let vm = &mut instance.vm;
+
+                let result = catch_termination(|| {
+                    vm.eval_function_by_index(vm_module_index, function_index as usize, arguments)
+                });
+
+                if result.is_err() {
+                    // 执行中止之后，栈和状态可能残留有未完成的栈帧，这里将其重置
+                    vm.stack = VMStack::new();
+                    vm.status = Status::new();
+                }
+
+                result.map_err(ActionError::Terminated)
+            }
+            Action::Get(_, name) => {
+                let global_item_index = instance
+                    .get_script_module()
+                    .export_items
+                    .iter()
+                    .find_map(|item| match item.export_descriptor {
+                        ExportDescriptor::GlobalItemIndex(i) if &item.name == name => Some(i),
+                        _ => None,
+                    })
+                    .ok_or_else(|| {
+                        ActionError::ExportNotFound(format!(
+                            "export global variable \"{}\" not found",
+                            name
+                        ))
+                    })?;
+
+                let resource = &instance.vm.resource;
+                let global_variable_index = resource.vm_modules[instance.get_script_module_index()]
+                    .global_variable_indexes[global_item_index as usize];
+
+                Ok(vec![
+                    resource.global_variables[global_variable_index].get_value()
+                ])
+            }
+        }
+    }
+}
+
+/// 解析（或者汇编）并验证模块
+fn load_module(module_source: &ModuleSource) -> Result<Module, ModuleError> {
+    let module = match module_source {
+        ModuleSource::Binary(bytes) => catch_panic(|| parser::parse(bytes))
+            .map_err(|message| ModuleError::Crashed(format!("parser panicked: {}", message)))?
+            .map_err(|e| ModuleError::Malformed(e.to_string()))?,
+        ModuleSource::Quote(source) => assemble_module_source(|| assembler::assemble(source))?,
+        ModuleSource::Text(expression) => {
+            assemble_module_source(|| assembler::assemble_module(expression))?
+        }
+    };
+
+    catch_panic(|| validate(&module))
+        .map_err(|message| ModuleError::Crashed(format!("validator panicked: {}", message)))?
+        .map_err(|e| ModuleError::Invalid(e.to_string()))?;
+    Ok(module)
+}
+
+/// 汇编文本格式的模块
+///
+/// 含有汇编器尚不支持的内容的模块会被跳过，其余的汇编错误均视为模块格式错误（malformed）。
+fn assemble_module_source<F>(f: F) -> Result<Module, ModuleError>
+where
+    F: FnOnce() -> Result<Module, AssemblyError>,
+{
+    match catch_panic(f) {
+        Ok(Ok(module)) => Ok(module),
+        Ok(Err(AssemblyError::UnsupportedError(_, message))) => {
+            Err(ModuleError::Unsupported(message))
+        }
+        Ok(Err(e)) => Err(ModuleError::Malformed(e.to_string())),
+        Err(message) => Err(ModuleError::Crashed(format!(
+            "assembler panicked: {}",
+            message
+        ))),
+    }
+}
+
+/// 创建 VM 实例，启用 `jit` 特性时使用 JIT 编译器
+fn create_vm(named_ast_modules: &[NamedAstModule]) -> Result<VM, EngineError> {
+    #[cfg(feature = "jit")]
+    let interpreter_kind = InterpreterKind::Jit;
+    #[cfg(not(feature = "jit"))]
+    let interpreter_kind = InterpreterKind::Stack;
+
+    create_instance_with_interpreter(vec![], named_ast_modules, interpreter_kind)
+}
+
+/// 执行一个可能会令 VM 崩溃（panic）的过程
+///
+/// 目前 VM 在某些情况下（比如整数除以 0、内存访问越界）会直接崩溃而不是返回错误，
+/// 为了让测试脚本能继续执行，这里捕获崩溃并将其视为执行中止。
+fn catch_termination<T, F>(f: F) -> Result<T, Termination>
+where
+    F: FnOnce() -> Result<T, EngineError>,
+{
+    match catch_panic(f) {
+        Ok(result) => result.map_err(Termination::Error),
+        Err(message) => Err(Termination::Panic(message)),
+    }
+}
+
+/// 捕获崩溃，返回崩溃的信息
+fn catch_panic<T, F>(f: F) -> Result<T, String>
+where
+    F: FnOnce() -> T,
+{
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        if let Some(s) = payload.downcast_ref::<&str>() {
+            s.to_string()
+        } else if let Some(s) = payload.downcast_ref::<String>() {
+            s.clone()
+        } else {
+            "unknown".to_string()
+        }
+    })
+}
+
+fn check_values(actual_values: &[Value], expected_values: &[ExpectedValue]) -> Outcome {
+    let is_matched = actual_values.len() == expected_values.len()
+        && actual_values
+            .iter()
+            .zip(expected_values.iter())
+            .all(|(actual, expected)| is_value_matched(actual, expected));
+
+    if is_matched {
+        Outcome::Passed
+    } else {
+        Outcome::Failed(format!(
+            "result mismatch, expected: {:?}, actual: {:?}",
+            expected_values, actual_values
+        ))
+    }
+}
+
+fn is_value_matched(actual: &Value, expected: &ExpectedValue) -> bool {
+    match (actual, expected) {
+        (Value::I32(a), ExpectedValue::Value(Value::I32(e))) => a == e,
+        (Value::I64(a), ExpectedValue::Value(Value::I64(e))) => a == e,
+        (Value::F32(a), ExpectedValue::Value(Value::F32(e))) => a.to_bits() == e.to_bits(),
+        (Value::F64(a), ExpectedValue::Value(Value::F64(e))) => a.to_bits() == e.to_bits(),
+        (Value::FuncRef(a), ExpectedValue::Value(Value::FuncRef(e))) => a == e,
+        (Value::ExternRef(a), ExpectedValue::Value(Value::ExternRef(e))) => a == e,
+        (Value::V128(a), ExpectedValue::Value(Value::V128(e))) => a == e,
+        (Value::FuncRef(a), ExpectedValue::NonNullRef(RefType::FuncRef)) => a.is_some(),
+        (Value::ExternRef(a), ExpectedValue::NonNullRef(RefType::ExternRef)) => a.is_some(),
+
+        // canonical NaN：指数全为 1，尾数只有最高位为 1
+        (Value::F32(a), ExpectedValue::CanonicalNan(ValueType::F32)) => {
+            a.to_bits() & 0x7fff_ffff == 0x7fc0_0000
+        }
+        (Value::F64(a), ExpectedValue::CanonicalNan(ValueType::F64)) => {
+            a.to_bits() & 0x7fff_ffff_ffff_ffff == 0x7ff8_0000_0000_0000
+        }
+
+        // arithmetic NaN：指数全为 1，尾数最高位为 1
+        (Value::F32(a), ExpectedValue::ArithmeticNan(ValueType::F32)) => {
+            a.to_bits() & 0x7fc0_0000 == 0x7fc0_0000
+        }
+        (Value::F64(a), ExpectedValue::ArithmeticNan(ValueType::F64)) => {
+            a.to_bits() & 0x7ff8_0000_0000_0000 == 0x7ff8_0000_0000_0000
+        }
+
+        // 逐个通道比较 f32x4 或者 f64x2 向量
+        (Value::V128(a), ExpectedValue::V128Lanes(lanes)) => {
+            let bytes = a.to_le_bytes();
+            let lane_size = bytes.len() / lanes.len();
+            bytes
+                .chunks(lane_size)
+                .zip(lanes)
+                .all(|(lane_bytes, expected)| {
+                    let lane_value = if lane_size == 4 {
+                        Value::F32(f32::from_le_bytes(lane_bytes.try_into().unwrap()))
+                    } else {
+                        Value::F64(f64::from_le_bytes(lane_bytes.try_into().unwrap()))
+                    };
+                    is_value_matched(&lane_value, expected)
+                })
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, path::PathBuf};
+
+    use pretty_assertions::assert_eq;
+
+    use super::{run_script, run_test_suite, CommandResult, Outcome};
+
+    // 以下模块的文本格式：
+    //
+    // (module
+    //   (global $g (export "g") (mut i32) (i32.const 10))
+    //   (func (export "add") (param i32 i32) (result i32)
+    //     (i32.add (local.get 0) (local.get 1)))
+    //   (func (export "inc")
+    //     (global.set $g (i32.add (global.get $g) (i32.const 1))))
+    //   (func (export "nan") (result f32) (f32.const nan))
+    //   (func (export "trap") (unreachable))
+    //   (func $f (export "loop") (call $f))
+    // )
+    const TEST_MODULE: &str = r#"(module binary
+        "\00\61\73\6d\01\00\00\00\01\0e\03\60\02\7f\7f\01\7f\60\00\00\60\00\01\7d"
+        "\03\06\05\00\01\02\01\01\06\06\01\7f\01\41\0a\0b\07\25\06\01\67\03\00\03"
+        "\61\64\64\00\00\03\69\6e\63\00\01\03\6e\61\6e\00\02\04\74\72\61\70\00\03"
+        "\04\6c\6f\6f\70\00\04\0a\24\05\07\00\20\00\20\01\6a\0b\09\00\23\00\41\01"
+        "\6a\24\00\0b\07\00\43\00\00\c0\7f\0b\03\00\00\0b\04\00\10\04\0b\00\11\04"
+        "\6e\61\6d\65\01\04\01\04\01\66\07\04\01\00\01\67"
+    )"#;
+
+    fn get_outcomes(source: &str) -> Vec<Outcome> {
+        run_script(source)
+            .unwrap()
+            .into_iter()
+            .map(|CommandResult { outcome, .. }| outcome)
+            .collect::<Vec<Outcome>>()
+    }
+
+    #[test]
+    fn test_run_script() {
+        let source = format!(
+            r#"
+            {}
+            (assert_return (invoke "add" (i32.const 1) (i32.const 2)) (i32.const 3))
+            (assert_return (invoke "add" (i32.const 1) (i32.const 2)) (i32.const 4))
+            (invoke "inc")
+            (assert_return (get "g") (i32.const 11))
+            (assert_return (invoke "nan") (f32.const nan:canonical))
+            (assert_trap (invoke "trap") "unreachable")
+            (assert_exhaustion (invoke "loop") "call stack exhausted")
+            (assert_return (invoke "add" (i32.const 5) (i32.const 6)) (i32.const 11))
+            (assert_malformed (module binary "\00asm") "unexpected end")
+            (assert_invalid (module binary "\00asm\01\00\00\00\08\01\00") "unknown function")
+            (assert_return (invoke "nan") (either (f32.const 0) (f32.const 1)))
+            (module (func))
+            (invoke "foo")
+            "#,
+            TEST_MODULE
+        );
+
+        let outcomes = get_outcomes(&source);
+        assert_eq!(outcomes.len(), 14);
+        assert_eq!(outcomes[0], Outcome::Passed);
+        assert_eq!(outcomes[1], Outcome::Passed);
+        assert!(matches!(outcomes[2], Outcome::Failed(_)));
+        assert_eq!(outcomes[3..11], vec![Outcome::Passed; 8]);
+        assert!(matches!(outcomes[11], Outcome::Skipped(_)));
+        assert_eq!(outcomes[12], Outcome::Passed);
+        assert!(matches!(outcomes[13], Outcome::Failed(_)));
+    }
+
+    #[test]
+    fn test_run_script_text_module() {
+        let source = r#"
+            (module $m
+              (func (export "add") (param i32 i32) (result i32)
+                (i32.add (local.get 0) (local.get 1))))
+            (assert_return (invoke $m "add" (i32.const 1) (i32.const 2)) (i32.const 3))
+            (module quote "(func (export \"neg\") (param i32) (result i32)"
+                          "  (i32.sub (i32.const 0) (local.get 0)))")
+            (assert_return (invoke "neg" (i32.const 5)) (i32.const -5))
+            (assert_malformed (module quote "(func (i32.foo))") "unknown operator")
+            (assert_invalid (module (func (result i32) (i64.const 0))) "type mismatch")
+            (module
+              (memory 1)
+              (data "abc")
+              (func (export "init") (memory.init 0 (i32.const 0) (i32.const 0) (i32.const 3)))
+              (func (export "load") (result i32) (i32.load8_u (i32.const 2))))
+            (invoke "init")
+            (assert_return (invoke "load") (i32.const 0x63))
+            "#;
+
+        let outcomes = get_outcomes(source);
+        assert_eq!(outcomes, vec![Outcome::Passed; 9]);
+    }
+
+    #[test]
+    fn test_run_script_register() {
+        // 第二个模块的文本格式：
+        //
+        // (module
+        //   (import "lib" "add" (func $add (param i32 i32) (result i32)))
+        //   (func (export "add3") (param i32) (result i32)
+        //     (call $add (local.get 0) (i32.const 3)))
+        // )
+        let source = format!(
+            r#"
+            {}
+            (register "lib")
+            (module $app binary
+                "\00\61\73\6d\01\00\00\00\01\0c\02\60\02\7f\7f\01\7f\60\01\7f\01\7f\02\0b"
+                "\01\03\6c\69\62\03\61\64\64\00\00\03\02\01\01\07\08\01\04\61\64\64\33\00"
+                "\01\0a\0a\01\08\00\20\00\41\03\10\00\0b\00\0d\04\6e\61\6d\65\01\06\01\00"
+                "\03\61\64\64"
+            )
+            (assert_return (invoke $app "add3" (i32.const 4)) (i32.const 7))
+            (assert_unlinkable
+                (module binary
+                    "\00\61\73\6d\01\00\00\00\01\07\01\60\02\7f\7f\01\7f\02\0b\01\03\6e\6f\6e"
+                    "\03\61\64\64\00\00\00\0d\04\6e\61\6d\65\01\06\01\00\03\61\64\64"
+                )
+                "unknown import"
+            )
+            "#,
+            TEST_MODULE
+        );
+
+        assert_eq!(get_outcomes(&source), vec![Outcome::Passed; 5]);
+    }
+
+    fn get_testsuite_path() -> PathBuf {
+        // 使用 `cargo test` 测试时，`env::current_dir()` 函数获取的当前目录为
+        // `./xiaoxuan-vm/crates/spec-test`；
+        // 使用 VSCode 的代码编辑器里头的 `debug` 按钮测试时，当前目录为 `./xiaoxuan-vm`。
+        let mut path_buf = env::current_dir().expect("failed to get current directory");
+
+        if !path_buf.ends_with("spec-test") {
+            path_buf.push("crates");
+            path_buf.push("spec-test");
+        }
+
+        path_buf.push("resources");
+        path_buf.push("testsuite");
+        path_buf
+    }
+
+    /// 已知的失败断言，(提案名称, 脚本文件名, 行号列表)
+    ///
+    /// 修正了 VM 的缺陷之后需要同步更新这个列表。
+    const EXPECTED_FAILURES: &[(&str, &str, &[usize])] = &[];
+
+    #[test]
+    fn test_run_test_suite() {
+        let reports = run_test_suite(&get_testsuite_path()).unwrap();
+
+        assert_eq!(reports[0].name, "core");
+
+        for report in &reports {
+            // 测试集里的脚本都应该能被正确解析
+            assert_eq!(report.script_errors, vec![]);
+            assert!(report.passed + report.failed + report.skipped > 0);
+        }
+
+        // 除了已知的失败之外，其余的断言均应通过
+        let failures = reports
+            .iter()
+            .flat_map(|report| {
+                report
+                    .failures
+                    .iter()
+                    .map(|(file_name, line, _)| (report.name.clone(), file_name.clone(), *line))
+            })
+            .collect::<Vec<(String, String, usize)>>();
+
+        let expected_failures = EXPECTED_FAILURES
+            .iter()
+            .flat_map(|(name, file_name, lines)| {
+                lines
+                    .iter()
+                    .map(|line| (name.to_string(), file_name.to_string(), *line))
+            })
+            .collect::<Vec<(String, String, usize)>>();
+
+        assert_eq!(failures, expected_failures);
+    }
+}
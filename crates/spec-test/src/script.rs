@@ -0,0 +1,883 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 测试脚本（`.wast`）的命令
+//!
+//! <https://github.com/WebAssembly/spec/tree/main/interpreter#scripts>
+//!
+//! 支持的命令：
+//!
+//! - `(module $id? ...)`，包括 `(module $id? binary "...")` 和 `(module $id? quote "...")`
+//! - `(register "name" $id?)`
+//! - `(invoke $id? "name" const*)` 和 `(get $id? "name")`
+//! - `(assert_return action result*)`
+//! - `(assert_trap action|module "message")`
+//! - `(assert_exhaustion action "message")`
+//! - `(assert_invalid module "message")`
+//! - `(assert_malformed module "message")`
+//! - `(assert_unlinkable module "message")`
+//!
+//! 常量除了数值之外，还支持引用类型的 `(ref.null func|extern)` 和 `(ref.extern N)`，
+//! 以及向量 `(v128.const shape lane*)`，
+//! 期望值还可以是 `(ref.func)` 和 `(ref.extern)`，表示任意的非 null 引用，
+//! 向量的期望值的浮点数通道可以是 `nan:canonical` 或者 `nan:arithmetic`。
+//!
+//! 其他命令（比如 `assert_uninstantiable`、`(module definition ...)` 等）以及
+//! 含有目前 VM 尚不支持的数值的命令，会被解析为 `Command::Unsupported`。
+
+use anvm_assembly::s_expression::{self, SExpression};
+use anvm_ast::types::{RefType, Value, ValueType};
+
+use crate::error::ScriptError;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ScriptCommand {
+    pub line: usize,
+    pub command: Command,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Command {
+    Module(/* id */ Option<String>, ModuleSource),
+    Register(
+        /* register name */ String,
+        /* module id */ Option<String>,
+    ),
+    Action(Action),
+    AssertReturn(Action, Vec<ExpectedValue>),
+    AssertTrap(ActionOrModule, /* message */ String),
+    AssertExhaustion(Action, /* message */ String),
+    AssertInvalid(ModuleSource, /* message */ String),
+    AssertMalformed(ModuleSource, /* message */ String),
+    AssertUnlinkable(ModuleSource, /* message */ String),
+    Unsupported(/* description */ String),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ModuleSource {
+    Binary(Vec<u8>),
+    Quote(String),
+
+    /// 文本格式的模块，参数为整个 `(module ...)` 表达式
+    Text(SExpression),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ActionOrModule {
+    Action(Action),
+    Module(ModuleSource),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Action {
+    Invoke(
+        /* module id */ Option<String>,
+        /* export name */ String,
+        /* arguments */ Vec<Value>,
+    ),
+    Get(
+        /* module id */ Option<String>,
+        /* export name */ String,
+    ),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ExpectedValue {
+    Value(Value),
+    CanonicalNan(ValueType),
+    ArithmeticNan(ValueType),
+
+    /// 任意的非 null 引用，比如 `(ref.func)`
+    NonNullRef(RefType),
+
+    /// 逐个通道比较的向量，用于含有 NaN 模式的 f32x4 以及 f64x2 向量，
+    /// 比如 `(v128.const f32x4 nan:canonical 1.0 2.0 nan:arithmetic)`
+    V128Lanes(Vec<ExpectedValue>),
+}
+
+/// 命令里含有 VM 尚不支持的内容
+struct UnsupportedContent(String);
+
+enum ParseResult<T> {
+    Ok(T),
+    Unsupported(UnsupportedContent),
+}
+
+macro_rules! try_parse {
+    ($e:expr) => {
+        match $e? {
+            ParseResult::Ok(v) => v,
+            ParseResult::Unsupported(u) => return Ok(ParseResult::Unsupported(u)),
+        }
+    };
+}
+
+pub fn parse(source: &str) -> Result<Vec<ScriptCommand>, ScriptError> {
+    let expressions = s_expression::parse(source)?;
+
+    expressions
+        .iter()
+        .map(|expression| {
+            let line = expression.get_line();
+            let command = match parse_command(expression)? {
+                ParseResult::Ok(command) => command,
+                ParseResult::Unsupported(UnsupportedContent(description)) => {
+                    Command::Unsupported(description)
+                }
+            };
+            Ok(ScriptCommand { line, command })
+        })
+        .collect::<Result<Vec<ScriptCommand>, ScriptError>>()
+}
+
+fn syntax_error(expression: &SExpression, message: &str) -> ScriptError {
+    ScriptError::SyntaxError(expression.get_line(), message.to_string())
+}
+
+fn parse_command(expression: &SExpression) -> Result<ParseResult<Command>, ScriptError> {
+    let items = expression
+        .as_list()
+        .ok_or_else(|| syntax_error(expression, "expected a command"))?;
+
+    let keyword = expression
+        .get_keyword()
+        .ok_or_else(|| syntax_error(expression, "expected a command name"))?;
+
+    let command = match keyword {
+        "module" => {
+            let (id, module_source) = try_parse!(parse_module(expression));
+            Command::Module(id, module_source)
+        }
+        "register" => {
+            let name = get_text_item(expression, items, 1)?;
+            let id = items
+                .get(2)
+                .and_then(|item| item.as_symbol())
+                .map(|s| s.to_string());
+            Command::Register(name, id)
+        }
+        "invoke" | "get" => Command::Action(try_parse!(parse_action(expression))),
+        "assert_return" => {
+            let action = try_parse!(parse_action(get_item(expression, items, 1)?));
+            let mut expected_values: Vec<ExpectedValue> = vec![];
+            for item in &items[2..] {
+                expected_values.push(try_parse!(parse_expected_value(item)));
+            }
+            Command::AssertReturn(action, expected_values)
+        }
+        "assert_trap" => {
+            let target = get_item(expression, items, 1)?;
+            let message = get_text_item(expression, items, 2)?;
+            let action_or_module = if target.get_keyword() == Some("module") {
+                let (_, module_source) = try_parse!(parse_module(target));
+                ActionOrModule::Module(module_source)
+            } else {
+                ActionOrModule::Action(try_parse!(parse_action(target)))
+            };
+            Command::AssertTrap(action_or_module, message)
+        }
+        "assert_exhaustion" => {
+            let action = try_parse!(parse_action(get_item(expression, items, 1)?));
+            let message = get_text_item(expression, items, 2)?;
+            Command::AssertExhaustion(action, message)
+        }
+        "assert_invalid" | "assert_malformed" | "assert_unlinkable" => {
+            let (_, module_source) = try_parse!(parse_module(get_item(expression, items, 1)?));
+            let message = get_text_item(expression, items, 2)?;
+            match keyword {
+                "assert_invalid" => Command::AssertInvalid(module_source, message),
+                "assert_malformed" => Command::AssertMalformed(module_source, message),
+                _ => Command::AssertUnlinkable(module_source, message),
+            }
+        }
+        _ => {
+            return Ok(ParseResult::Unsupported(UnsupportedContent(format!(
+                "command \"{}\"",
+                keyword
+            ))))
+        }
+    };
+
+    Ok(ParseResult::Ok(command))
+}
+
+fn get_item<'a>(
+    expression: &SExpression,
+    items: &'a [SExpression],
+    index: usize,
+) -> Result<&'a SExpression, ScriptError> {
+    items
+        .get(index)
+        .ok_or_else(|| syntax_error(expression, "missing argument"))
+}
+
+fn get_text_item(
+    expression: &SExpression,
+    items: &[SExpression],
+    index: usize,
+) -> Result<String, ScriptError> {
+    let bytes = get_item(expression, items, index)?
+        .as_text()
+        .ok_or_else(|| syntax_error(expression, "expected a string"))?;
+    Ok(String::from_utf8_lossy(bytes).to_string())
+}
+
+fn parse_module(
+    expression: &SExpression,
+) -> Result<ParseResult<(Option<String>, ModuleSource)>, ScriptError> {
+    let items = expression
+        .as_list()
+        .ok_or_else(|| syntax_error(expression, "expected a module"))?;
+
+    if expression.get_keyword() != Some("module") {
+        return Err(syntax_error(expression, "expected a module"));
+    }
+
+    let mut index = 1;
+
+    let id = match items.get(index).and_then(|item| item.as_symbol()) {
+        Some(symbol) if symbol.starts_with('$') => {
+            index += 1;
+            Some(symbol.to_string())
+        }
+        _ => None,
+    };
+
+    let module_source = match items.get(index).and_then(|item| item.as_symbol()) {
+        Some("binary") => {
+            let mut bytes: Vec<u8> = vec![];
+            for item in &items[(index + 1)..] {
+                let text = item
+                    .as_text()
+                    .ok_or_else(|| syntax_error(item, "expected a string"))?;
+                bytes.extend_from_slice(text);
+            }
+            ModuleSource::Binary(bytes)
+        }
+        Some("quote") => {
+            let mut source = String::new();
+            for item in &items[(index + 1)..] {
+                let text = item
+                    .as_text()
+                    .ok_or_else(|| syntax_error(item, "expected a string"))?;
+                source.push_str(&String::from_utf8_lossy(text));
+                source.push(' ');
+            }
+            ModuleSource::Quote(source)
+        }
+        Some("definition") | Some("instance") => {
+            return Ok(ParseResult::Unsupported(UnsupportedContent(
+                "module definition and instance".to_string(),
+            )))
+        }
+        _ => ModuleSource::Text(expression.clone()),
+    };
+
+    Ok(ParseResult::Ok((id, module_source)))
+}
+
+fn parse_action(expression: &SExpression) -> Result<ParseResult<Action>, ScriptError> {
+    let items = expression
+        .as_list()
+        .ok_or_else(|| syntax_error(expression, "expected an action"))?;
+
+    let keyword = expression
+        .get_keyword()
+        .ok_or_else(|| syntax_error(expression, "expected an action"))?;
+
+    let mut index = 1;
+    let id = match items.get(index).and_then(|item| item.as_symbol()) {
+        Some(symbol) if symbol.starts_with('$') => {
+            index += 1;
+            Some(symbol.to_string())
+        }
+        _ => None,
+    };
+
+    let name = get_text_item(expression, items, index)?;
+
+    let action = match keyword {
+        "invoke" => {
+            let mut arguments: Vec<Value> = vec![];
+            for item in &items[(index + 1)..] {
+                arguments.push(try_parse!(parse_const(item)));
+            }
+            Action::Invoke(id, name, arguments)
+        }
+        "get" => Action::Get(id, name),
+        _ => return Err(syntax_error(expression, "expected an action")),
+    };
+
+    Ok(ParseResult::Ok(action))
+}
+
+/// 解析诸如 `(i32.const 1)` 的常量
+fn parse_const(expression: &SExpression) -> Result<ParseResult<Value>, ScriptError> {
+    if expression.get_keyword() == Some("v128.const") {
+        return parse_v128_const(expression).map(ParseResult::Ok);
+    }
+
+    let (keyword, literal) = get_const_parts(expression)?;
+
+    let option_value = match keyword {
+        "i32.const" => parse_i32(literal).map(Value::I32),
+        "i64.const" => parse_i64(literal).map(Value::I64),
+        "f32.const" => parse_f32(literal).map(|bits| Value::F32(f32::from_bits(bits))),
+        "f64.const" => parse_f64(literal).map(|bits| Value::F64(f64::from_bits(bits))),
+        "ref.null" => match literal {
+            "func" => Some(Value::FuncRef(None)),
+            "extern" => Some(Value::ExternRef(None)),
+            _ => return Err(syntax_error(expression, "expected a heap type")),
+        },
+        // 宿主提供的外部引用，数值由测试脚本指定
+        "ref.extern" => literal.parse::<u32>().ok().map(|index| Value::ExternRef(Some(index))),
+        _ => {
+            return Ok(ParseResult::Unsupported(UnsupportedContent(format!(
+                "value \"{}\"",
+                keyword
+            ))))
+        }
+    };
+
+    option_value
+        .map(ParseResult::Ok)
+        .ok_or_else(|| syntax_error(expression, &format!("invalid number \"{}\"", literal)))
+}
+
+fn parse_expected_value(
+    expression: &SExpression,
+) -> Result<ParseResult<ExpectedValue>, ScriptError> {
+    if let Some([SExpression::Symbol(keyword, _)]) = expression.as_list() {
+        match keyword.as_str() {
+            "ref.func" => return Ok(ParseResult::Ok(ExpectedValue::NonNullRef(RefType::FuncRef))),
+            "ref.extern" => {
+                return Ok(ParseResult::Ok(ExpectedValue::NonNullRef(
+                    RefType::ExternRef,
+                )))
+            }
+            _ => {}
+        }
+    }
+
+    if expression.get_keyword() == Some("v128.const") {
+        return parse_expected_v128(expression).map(ParseResult::Ok);
+    }
+
+    let (keyword, literal) = match get_const_parts(expression) {
+        Ok(parts) => parts,
+        Err(_) => {
+            // 比如 `(either ...)` 等
+            return Ok(ParseResult::Unsupported(UnsupportedContent(format!(
+                "result \"{}\"",
+                expression.get_keyword().unwrap_or("")
+            ))));
+        }
+    };
+
+    let value_type = match keyword {
+        "f32.const" => Some(ValueType::F32),
+        "f64.const" => Some(ValueType::F64),
+        _ => None,
+    };
+
+    let expected_value = match (literal, value_type) {
+        ("nan:canonical", Some(value_type)) => ExpectedValue::CanonicalNan(value_type),
+        ("nan:arithmetic", Some(value_type)) => ExpectedValue::ArithmeticNan(value_type),
+        _ => ExpectedValue::Value(try_parse!(parse_const(expression))),
+    };
+
+    Ok(ParseResult::Ok(expected_value))
+}
+
+/// 解析 `(v128.const shape lane*)` 常量
+fn parse_v128_const(expression: &SExpression) -> Result<Value, ScriptError> {
+    let (shape, literals) = get_v128_const_parts(expression)?;
+
+    let mut bytes: Vec<u8> = Vec::with_capacity(16);
+    for literal in literals {
+        let option_lane_bytes = match shape {
+            "i8x16" => parse_narrow_integer(literal, 8).map(|v| vec![v as u8]),
+            "i16x8" => parse_narrow_integer(literal, 16).map(|v| (v as u16).to_le_bytes().to_vec()),
+            "i32x4" => parse_i32(literal).map(|v| v.to_le_bytes().to_vec()),
+            "i64x2" => parse_i64(literal).map(|v| v.to_le_bytes().to_vec()),
+            "f32x4" => parse_f32(literal).map(|bits| bits.to_le_bytes().to_vec()),
+            "f64x2" => parse_f64(literal).map(|bits| bits.to_le_bytes().to_vec()),
+            _ => unreachable!(),
+        };
+
+        let lane_bytes = option_lane_bytes
+            .ok_or_else(|| syntax_error(expression, &format!("invalid number \"{}\"", literal)))?;
+        bytes.extend(lane_bytes);
+    }
+
+    Ok(Value::V128(u128::from_le_bytes(bytes.try_into().unwrap())))
+}
+
+/// 解析向量的期望值
+///
+/// 如果浮点数通道里含有 NaN 模式，则需要逐个通道比较，
+/// 否则直接比较整个向量的二进制位。
+fn parse_expected_v128(expression: &SExpression) -> Result<ExpectedValue, ScriptError> {
+    let (shape, literals) = get_v128_const_parts(expression)?;
+
+    let option_value_type = match shape {
+        "f32x4" => Some(ValueType::F32),
+        "f64x2" => Some(ValueType::F64),
+        _ => None,
+    };
+
+    let value_type = match option_value_type {
+        Some(value_type) if literals.iter().any(|literal| literal.starts_with("nan:")) => {
+            value_type
+        }
+        _ => return Ok(ExpectedValue::Value(parse_v128_const(expression)?)),
+    };
+
+    let mut lanes: Vec<ExpectedValue> = vec![];
+    for literal in literals {
+        let lane = match (literal, &value_type) {
+            ("nan:canonical", _) => ExpectedValue::CanonicalNan(value_type.clone()),
+            ("nan:arithmetic", _) => ExpectedValue::ArithmeticNan(value_type.clone()),
+            (_, ValueType::F32) => parse_f32(literal)
+                .map(|bits| ExpectedValue::Value(Value::F32(f32::from_bits(bits))))
+                .ok_or_else(|| syntax_error(expression, "invalid number"))?,
+            _ => parse_f64(literal)
+                .map(|bits| ExpectedValue::Value(Value::F64(f64::from_bits(bits))))
+                .ok_or_else(|| syntax_error(expression, "invalid number"))?,
+        };
+        lanes.push(lane);
+    }
+
+    Ok(ExpectedValue::V128Lanes(lanes))
+}
+
+/// 获取 `(v128.const shape lane*)` 的形状以及各个通道的字面量，并检查通道的数量
+fn get_v128_const_parts(expression: &SExpression) -> Result<(&str, Vec<&str>), ScriptError> {
+    let items = expression.as_list().unwrap();
+
+    let shape = items
+        .get(1)
+        .and_then(|item| item.as_symbol())
+        .ok_or_else(|| syntax_error(expression, "expected a vector shape"))?;
+
+    let lane_count = match shape {
+        "i8x16" => 16,
+        "i16x8" => 8,
+        "i32x4" | "f32x4" => 4,
+        "i64x2" | "f64x2" => 2,
+        _ => return Err(syntax_error(expression, "expected a vector shape")),
+    };
+
+    let literals = items[2..]
+        .iter()
+        .map(|item| item.as_symbol())
+        .collect::<Option<Vec<&str>>>()
+        .filter(|literals| literals.len() == lane_count)
+        .ok_or_else(|| syntax_error(expression, "wrong number of vector lanes"))?;
+
+    Ok((shape, literals))
+}
+
+fn get_const_parts(expression: &SExpression) -> Result<(&str, &str), ScriptError> {
+    let items = expression
+        .as_list()
+        .ok_or_else(|| syntax_error(expression, "expected a constant"))?;
+
+    match items {
+        [SExpression::Symbol(keyword, _), SExpression::Symbol(literal, _)] => {
+            Ok((keyword.as_str(), literal.as_str()))
+        }
+        _ => Err(syntax_error(expression, "expected a constant")),
+    }
+}
+
+/// 解析整数字面量
+///
+/// 支持十进制和十六进制（`0x` 前缀）、正负号以及数字之间的下划线，
+/// 返回值为 (是否负数, 绝对值)
+fn parse_integer(literal: &str) -> Option<(bool, u128)> {
+    let (negative, unsigned) = match literal.as_bytes().first() {
+        Some(b'-') => (true, &literal[1..]),
+        Some(b'+') => (false, &literal[1..]),
+        _ => (false, literal),
+    };
+
+    let (radix, digits) = match unsigned.strip_prefix("0x") {
+        Some(hex) => (16, hex),
+        None => (10, unsigned),
+    };
+
+    if digits.is_empty() || digits.starts_with('_') || digits.ends_with('_') {
+        return None;
+    }
+
+    let digits = digits.replace('_', "");
+    u128::from_str_radix(&digits, radix)
+        .ok()
+        .map(|value| (negative, value))
+}
+
+/// 解析 8 位或者 16 位（即 bits）的整数字面量，数值的范围是 [-2^(bits-1), 2^bits)，
+/// 返回数值的补码
+fn parse_narrow_integer(literal: &str, bits: u32) -> Option<u32> {
+    let (negative, value) = parse_integer(literal)?;
+    if negative {
+        if value > 1 << (bits - 1) {
+            None
+        } else {
+            Some((value as u32).wrapping_neg())
+        }
+    } else if value >= 1 << bits {
+        None
+    } else {
+        Some(value as u32)
+    }
+}
+
+/// i32 字面量可以是有符号数也可以是无符号数，比如 `-1` 和 `0xffffffff` 是同一个数
+pub fn parse_i32(literal: &str) -> Option<i32> {
+    let (negative, value) = parse_integer(literal)?;
+    if negative {
+        if value > 0x8000_0000 {
+            None
+        } else {
+            Some((value as i64).wrapping_neg() as i32)
+        }
+    } else if value > 0xffff_ffff {
+        None
+    } else {
+        Some(value as u32 as i32)
+    }
+}
+
+pub fn parse_i64(literal: &str) -> Option<i64> {
+    let (negative, value) = parse_integer(literal)?;
+    if negative {
+        if value > 0x8000_0000_0000_0000 {
+            None
+        } else {
+            Some((value as i128).wrapping_neg() as i64)
+        }
+    } else if value > 0xffff_ffff_ffff_ffff {
+        None
+    } else {
+        Some(value as u64 as i64)
+    }
+}
+
+/// 解析 f32 字面量，返回数值的二进制位
+pub fn parse_f32(literal: &str) -> Option<u32> {
+    parse_float(literal, 23, 8).map(|bits| bits as u32)
+}
+
+/// 解析 f64 字面量，返回数值的二进制位
+pub fn parse_f64(literal: &str) -> Option<u64> {
+    parse_float(literal, 52, 11)
+}
+
+/// 解析浮点数字面量
+///
+/// 支持：
+/// - 十进制数，比如 `1.5`、`-0.25e10`、`1_000.0`
+/// - 十六进制数，比如 `0x1.8p3`、`-0x1p-149`
+/// - `inf`、`nan` 以及带载荷的 `nan:0x200000`
+fn parse_float(literal: &str, mantissa_bits: u32, exponent_bits: u32) -> Option<u64> {
+    let (negative, unsigned) = match literal.as_bytes().first() {
+        Some(b'-') => (true, &literal[1..]),
+        Some(b'+') => (false, &literal[1..]),
+        _ => (false, literal),
+    };
+
+    let sign_bit = if negative {
+        1u64 << (mantissa_bits + exponent_bits)
+    } else {
+        0
+    };
+    let exponent_mask = ((1u64 << exponent_bits) - 1) << mantissa_bits;
+
+    let bits = if unsigned == "inf" {
+        exponent_mask
+    } else if unsigned == "nan" {
+        exponent_mask | (1u64 << (mantissa_bits - 1))
+    } else if let Some(payload) = unsigned.strip_prefix("nan:0x") {
+        let payload = u64::from_str_radix(&payload.replace('_', ""), 16).ok()?;
+        if payload == 0 || payload >= (1u64 << mantissa_bits) {
+            return None;
+        }
+        exponent_mask | payload
+    } else if let Some(hex) = unsigned.strip_prefix("0x") {
+        parse_hex_float(hex, mantissa_bits, exponent_bits)?
+    } else {
+        if !unsigned.starts_with(|c: char| c.is_ascii_digit()) {
+            return None;
+        }
+        let decimal = unsigned.replace('_', "");
+        if mantissa_bits == 23 {
+            decimal.parse::<f32>().ok()?.to_bits() as u64
+        } else {
+            decimal.parse::<f64>().ok()?.to_bits()
+        }
+    };
+
+    Some(sign_bit | bits)
+}
+
+/// 解析十六进制浮点数（不含 `0x` 前缀以及符号），按照 "就近舍入，平局取偶" 的规则舍入
+fn parse_hex_float(literal: &str, mantissa_bits: u32, exponent_bits: u32) -> Option<u64> {
+    let literal = literal.replace('_', "");
+
+    let (significand, exponent) = match literal.find(['p', 'P']) {
+        Some(position) => {
+            let exponent = literal[(position + 1)..].parse::<i64>().ok()?;
+            (&literal[..position], exponent)
+        }
+        None => (&literal[..], 0),
+    };
+
+    let (integer_digits, fraction_digits) = match significand.find('.') {
+        Some(position) => (&significand[..position], &significand[(position + 1)..]),
+        None => (significand, ""),
+    };
+
+    if integer_digits.is_empty() {
+        return None;
+    }
+
+    // 把有效数字读入一个整数 m，数值 = m * 2^e
+    let mut m: u128 = 0;
+    let mut e: i64 = exponent;
+    let mut sticky = false;
+
+    for (index, c) in integer_digits
+        .chars()
+        .chain(fraction_digits.chars())
+        .enumerate()
+    {
+        let digit = c.to_digit(16)? as u128;
+        let is_fraction = index >= integer_digits.len();
+
+        if m >> 120 == 0 {
+            m = (m << 4) | digit;
+            if is_fraction {
+                e -= 4;
+            }
+        } else {
+            // 有效数字过多，多余的位只影响舍入
+            sticky |= digit != 0;
+            if !is_fraction {
+                e += 4;
+            }
+        }
+    }
+
+    if m == 0 {
+        return Some(0);
+    }
+
+    let bias = (1i64 << (exponent_bits - 1)) - 1;
+    let min_exponent = 1 - bias;
+    let max_biased_exponent = (1i64 << exponent_bits) - 1;
+
+    // 最高位的位置
+    let msb = 127 - m.leading_zeros() as i64;
+    let unbiased_exponent = msb + e;
+
+    // 结果的最小单位（quantum）的指数
+    let quantum =
+        (unbiased_exponent - mantissa_bits as i64).max(min_exponent - mantissa_bits as i64);
+
+    let shift = quantum - e;
+    let mut n: u128 = if shift <= 0 {
+        if -shift >= 128 - msb {
+            // 溢出
+            return Some((max_biased_exponent as u64) << mantissa_bits);
+        }
+        m << (-shift)
+    } else if shift >= 128 {
+        // 非常小的数，舍入为 0
+        0
+    } else {
+        let n = m >> shift;
+        let remainder = m & ((1u128 << shift) - 1);
+        let half = 1u128 << (shift - 1);
+        let round_up = remainder > half || (remainder == half && (sticky || n & 1 == 1));
+        if round_up {
+            n + 1
+        } else {
+            n
+        }
+    };
+
+    let mut quantum = quantum;
+    if n >> (mantissa_bits + 1) != 0 {
+        n >>= 1;
+        quantum += 1;
+    }
+
+    if n >> mantissa_bits == 0 {
+        // 次正规数（subnormal）
+        return Some(n as u64);
+    }
+
+    let biased_exponent = quantum + mantissa_bits as i64 + bias;
+    if biased_exponent >= max_biased_exponent {
+        return Some((max_biased_exponent as u64) << mantissa_bits);
+    }
+
+    let fraction = (n as u64) & ((1u64 << mantissa_bits) - 1);
+    Some(((biased_exponent as u64) << mantissa_bits) | fraction)
+}
+
+#[cfg(test)]
+mod tests {
+    use anvm_ast::types::{RefType, Value, ValueType};
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        parse, parse_f32, parse_f64, parse_i32, parse_i64, Action, ActionOrModule, Command,
+        ExpectedValue, ModuleSource, ScriptCommand,
+    };
+
+    #[test]
+    fn test_parse_integer() {
+        assert_eq!(parse_i32("0"), Some(0));
+        assert_eq!(parse_i32("-1"), Some(-1));
+        assert_eq!(parse_i32("0xffffffff"), Some(-1));
+        assert_eq!(parse_i32("-0x80000000"), Some(i32::MIN));
+        assert_eq!(parse_i32("1_000"), Some(1000));
+        assert_eq!(parse_i32("0x100000000"), None);
+        assert_eq!(parse_i64("0xffffffffffffffff"), Some(-1));
+        assert_eq!(parse_i64("-9223372036854775808"), Some(i64::MIN));
+    }
+
+    #[test]
+    fn test_parse_float() {
+        assert_eq!(parse_f32("1.5"), Some(1.5f32.to_bits()));
+        assert_eq!(parse_f32("-0"), Some((-0.0f32).to_bits()));
+        assert_eq!(parse_f32("inf"), Some(f32::INFINITY.to_bits()));
+        assert_eq!(parse_f32("-inf"), Some(f32::NEG_INFINITY.to_bits()));
+        assert_eq!(parse_f32("nan"), Some(0x7fc0_0000));
+        assert_eq!(parse_f32("-nan:0x200000"), Some(0xffa0_0000));
+        assert_eq!(parse_f32("0x1.8p3"), Some(12.0f32.to_bits()));
+        assert_eq!(parse_f32("0x1p-149"), Some(1));
+        assert_eq!(parse_f32("0x1.fffffep+127"), Some(f32::MAX.to_bits()));
+        assert_eq!(parse_f32("0x1.ffffffp+127"), Some(f32::INFINITY.to_bits()));
+        assert_eq!(parse_f32("0x1.000001p0"), Some(1.0f32.to_bits()));
+        assert_eq!(parse_f32("0x1.000003p0"), Some(1.0f32.to_bits() + 2));
+        assert_eq!(parse_f64("0x1p-1074"), Some(1));
+        assert_eq!(
+            parse_f64("0x1.fffffffffffffp+1023"),
+            Some(f64::MAX.to_bits())
+        );
+        assert_eq!(parse_f64("-0x0.8p1"), Some((-1.0f64).to_bits()));
+        assert_eq!(parse_f64("1e308"), Some(1e308f64.to_bits()));
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        let source = r#"
+            (module $m binary "\00asm" "\01\00\00\00")
+            (register "lib" $m)
+            (assert_return (invoke $m "add" (i32.const 1) (i64.const 2)) (f32.const nan:canonical))
+            (assert_trap (invoke "div" (i32.const 0)) "integer divide by zero")
+            (assert_invalid (module quote "(func)") "type mismatch")
+            (assert_return (invoke "ref") (ref.null func))
+            (assert_return (invoke "id" (ref.extern 1) (ref.null extern)) (ref.extern))
+            (assert_return (invoke "v" (v128.const i32x4 -1 2 3 4))
+                (v128.const f32x4 nan:canonical 1.0 -0.0 nan:arithmetic))
+            (assert_return (invoke "w") (v128.const i8x16 -128 255 0 0 0 0 0 0 0 0 0 0 0 1 2 3))
+        "#;
+
+        assert_eq!(
+            parse(source).unwrap(),
+            vec![
+                ScriptCommand {
+                    line: 2,
+                    command: Command::Module(
+                        Some("$m".to_string()),
+                        ModuleSource::Binary(vec![0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00])
+                    )
+                },
+                ScriptCommand {
+                    line: 3,
+                    command: Command::Register("lib".to_string(), Some("$m".to_string()))
+                },
+                ScriptCommand {
+                    line: 4,
+                    command: Command::AssertReturn(
+                        Action::Invoke(
+                            Some("$m".to_string()),
+                            "add".to_string(),
+                            vec![Value::I32(1), Value::I64(2)]
+                        ),
+                        vec![ExpectedValue::CanonicalNan(ValueType::F32)]
+                    )
+                },
+                ScriptCommand {
+                    line: 5,
+                    command: Command::AssertTrap(
+                        ActionOrModule::Action(Action::Invoke(
+                            None,
+                            "div".to_string(),
+                            vec![Value::I32(0)]
+                        )),
+                        "integer divide by zero".to_string()
+                    )
+                },
+                ScriptCommand {
+                    line: 6,
+                    command: Command::AssertInvalid(
+                        ModuleSource::Quote("(func) ".to_string()),
+                        "type mismatch".to_string()
+                    )
+                },
+                ScriptCommand {
+                    line: 7,
+                    command: Command::AssertReturn(
+                        Action::Invoke(None, "ref".to_string(), vec![]),
+                        vec![ExpectedValue::Value(Value::FuncRef(None))]
+                    )
+                },
+                ScriptCommand {
+                    line: 8,
+                    command: Command::AssertReturn(
+                        Action::Invoke(
+                            None,
+                            "id".to_string(),
+                            vec![Value::ExternRef(Some(1)), Value::ExternRef(None)]
+                        ),
+                        vec![ExpectedValue::NonNullRef(RefType::ExternRef)]
+                    )
+                },
+                ScriptCommand {
+                    line: 9,
+                    command: Command::AssertReturn(
+                        Action::Invoke(
+                            None,
+                            "v".to_string(),
+                            vec![Value::V128(0x0000_0004_0000_0003_0000_0002_ffff_ffff)]
+                        ),
+                        vec![ExpectedValue::V128Lanes(vec![
+                            ExpectedValue::CanonicalNan(ValueType::F32),
+                            ExpectedValue::Value(Value::F32(1.0)),
+                            ExpectedValue::Value(Value::F32(-0.0)),
+                            ExpectedValue::ArithmeticNan(ValueType::F32),
+                        ])]
+                    )
+                },
+                ScriptCommand {
+                    line: 11,
+                    command: Command::AssertReturn(
+                        Action::Invoke(None, "w".to_string(), vec![]),
+                        vec![ExpectedValue::Value(Value::V128(
+                            0x0302_0100_0000_0000_0000_0000_0000_ff80
+                        ))]
+                    )
+                },
+            ]
+        );
+    }
+}
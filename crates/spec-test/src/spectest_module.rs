@@ -0,0 +1,123 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # `spectest` 模块
+//!
+//! 测试脚本里的模块可以从名为 `spectest` 的宿主模块导入函数、全局变量、表和内存块，
+//! 详细见：
+//! <https://github.com/WebAssembly/spec/tree/main/interpreter#spectest-host-module>
+//!
+//! 因为 VM 的本地模块（`NativeModule`）只能提供函数，所以这里用一个普通的 AST 模块
+//! 来模拟 `spectest` 模块，其中的 `print*` 函数的函数体为空（即不输出任何内容）。
+
+use anvm_ast::{
+    ast::{
+        CodeItem, ExportDescriptor, ExportItem, FunctionType, GlobalItem, GlobalType, Limit,
+        MemoryType, Module, TableType, TypeItem,
+    },
+    instruction::Instruction,
+    types::{RefType, ValueType},
+};
+
+pub const SPECTEST_MODULE_NAME: &str = "spectest";
+
+pub fn create_spectest_module() -> Module {
+    let function_signatures: Vec<(&str, Vec<ValueType>)> = vec![
+        ("print", vec![]),
+        ("print_i32", vec![ValueType::I32]),
+        ("print_i64", vec![ValueType::I64]),
+        ("print_f32", vec![ValueType::F32]),
+        ("print_f64", vec![ValueType::F64]),
+        ("print_i32_f32", vec![ValueType::I32, ValueType::F32]),
+        ("print_f64_f64", vec![ValueType::F64, ValueType::F64]),
+    ];
+
+    let global_items = vec![
+        create_global_item(ValueType::I32, Instruction::I32Const(666)),
+        create_global_item(ValueType::I64, Instruction::I64Const(666)),
+        create_global_item(ValueType::F32, Instruction::F32Const(666.6)),
+        create_global_item(ValueType::F64, Instruction::F64Const(666.6)),
+    ];
+
+    let global_names = ["global_i32", "global_i64", "global_f32", "global_f64"];
+
+    let mut export_items = function_signatures
+        .iter()
+        .enumerate()
+        .map(|(index, (name, _))| ExportItem {
+            name: name.to_string(),
+            export_descriptor: ExportDescriptor::FunctionIndex(index as u32),
+        })
+        .collect::<Vec<ExportItem>>();
+
+    export_items.extend(
+        global_names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| ExportItem {
+                name: name.to_string(),
+                export_descriptor: ExportDescriptor::GlobalItemIndex(index as u32),
+            }),
+    );
+
+    export_items.push(ExportItem {
+        name: "table".to_string(),
+        export_descriptor: ExportDescriptor::TableIndex(0),
+    });
+
+    export_items.push(ExportItem {
+        name: "memory".to_string(),
+        export_descriptor: ExportDescriptor::MemoryBlockIndex(0),
+    });
+
+    Module {
+        custom_items: vec![],
+        type_items: function_signatures
+            .iter()
+            .map(|(_, params)| {
+                TypeItem::FunctionType(FunctionType {
+                    params: params.clone(),
+                    results: vec![],
+                })
+            })
+            .collect::<Vec<TypeItem>>(),
+        import_items: vec![],
+        internal_function_to_type_index_list: (0..function_signatures.len() as u32).collect(),
+        tables: vec![TableType {
+            element_type: RefType::FuncRef,
+            limit: Limit::Range(10, 20),
+        }],
+        memory_blocks: vec![MemoryType {
+            limit: Limit::Range(1, 2),
+            shared: false,
+            memory64: false,
+        }],
+        tag_items: vec![],
+        global_items,
+        export_items,
+        start_function_index: None,
+        element_items: vec![],
+        code_items: function_signatures
+            .iter()
+            .map(|_| CodeItem {
+                local_groups: vec![],
+                instruction_items: vec![Instruction::End],
+            })
+            .collect::<Vec<CodeItem>>(),
+        data_items: vec![],
+        data_count: None,
+    }
+}
+
+fn create_global_item(value_type: ValueType, instruction: Instruction) -> GlobalItem {
+    GlobalItem {
+        global_type: GlobalType {
+            value_type,
+            mutable: false,
+        },
+        initialize_instruction_items: vec![instruction, Instruction::End],
+    }
+}
@@ -0,0 +1,71 @@
+(module
+    (type $bin_op (func (param i32 i32) (result i32)))
+    (import "env" "base" (global $base i32))
+    (import "env" "add" (func $add (type $bin_op)))
+    (table 2 funcref)
+    (memory 1 2)
+    (global $counter (mut i64) (i64.const 0))
+    (elem (global.get $base) $sub $add)
+    (data (i32.const 8) "hello")
+
+    (func $sub (type $bin_op)
+        (i32.sub (local.get 0) (local.get 1))
+    )
+
+    ;; 结构块的参数和返回值
+    (func $block_params (param i32) (result i32)
+        (local.get 0)
+        (block (param i32) (result i32)
+            (i32.const 1)
+            (i32.add)
+        )
+        (if (param i32) (result i32) (i32.eqz (local.get 0))
+            (then (i32.const 10) (i32.mul))
+            (else (i32.const 20) (i32.sub))
+        )
+    )
+
+    ;; br_table 以及不可到达的代码
+    (func $branch (param i32) (result i32)
+        (block (result i32)
+            (block (result i32)
+                (i32.const 55)
+                (local.get 0)
+                (br_table 0 1 1)
+            )
+            (i32.const 1)
+            (i32.add)
+        )
+        (return)
+        (i32.add)
+        (drop)
+        (unreachable)
+    )
+
+    ;; loop、select、全局变量以及内存指令
+    (func $loop (param $n i32) (result i64)
+        (local $i i32)
+        (loop $next
+            (global.set $counter
+                (i64.add (global.get $counter)
+                    (i64.extend_i32_u (i32.load8_u (local.get $i)))))
+            (i32.store16 offset=100 (local.get $i) (local.get $i))
+            (local.tee $i (i32.add (local.get $i) (i32.const 1)))
+            (local.get $n)
+            (i32.lt_u)
+            (br_if $next)
+        )
+        (select (global.get $counter) (i64.const 0) (i32.const 1))
+        (drop (memory.grow (memory.size)))
+    )
+
+    (func $indirect (param i32) (result i32)
+        (call_indirect (type $bin_op) (i32.const 7) (global.get $base) (local.get 0))
+    )
+
+    (start $start)
+    (func $start)
+    (export "sub" (func $sub))
+    (export "counter" (global $counter))
+    (export "memory" (memory 0))
+)
@@ -0,0 +1,428 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::fmt::Display;
+
+use anvm_ast::{
+    instruction::Instruction,
+    types::{RefType, ValueType},
+};
+
+/// 模块验证错误
+///
+/// 验证错误分为两大类：
+/// - 模块结构的错误，比如索引值超出范围、导出名称重复、常量表达式不正确等；
+/// - 函数体的指令序列的错误，比如操作数的类型不匹配、结构块的参数和返回值不匹配等，
+///   此类错误会附带函数的索引以及出错指令在函数体指令列表当中的位置。
+#[derive(Debug, PartialEq, Clone)]
+pub enum ValidationError {
+    ModuleError(ModuleError),
+    FunctionError(
+        /* function index */ u32,
+        /* instruction offset */ usize,
+        FunctionError,
+    ),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ModuleError {
+    TypeIndexOutOfRange(/* type index */ u32),
+    FunctionIndexOutOfRange(/* function index */ u32),
+    TableIndexOutOfRange(/* table index */ u32),
+    MemoryBlockIndexOutOfRange(/* memory block index */ u32),
+    GlobalIndexOutOfRange(/* global variable index */ u32),
+    TagIndexOutOfRange(/* tag index */ u32),
+
+    /// 函数段（`Function Section`）和代码段（`Code Section`）的项目数量不一致
+    FunctionAndCodeCountMismatch(/* function count */ usize, /* code count */ usize),
+
+    /// Limit 的最小值大于最大值
+    InvalidLimit(/* min */ u64, /* max */ u64),
+    MemoryPageExceed(/* pages */ u64, /* max allowed */ u64),
+
+    /// 共享内存块必须指定最大页面数
+    SharedMemoryWithoutMax,
+
+    /// 标签（tag）的类型必须是没有返回值的函数类型
+    InvalidTagType(/* tag index */ u32),
+
+    DuplicateExportName(/* name */ String),
+
+    /// start 函数的类型必须是 `() -> ()`
+    InvalidStartFunctionType(/* function index */ u32),
+
+    InvalidConstantExpression(ConstantExpressionLocation, ConstantExpressionError),
+
+    /// 主动模式元素段的元素类型必须跟目标表的元素类型一致，
+    /// 函数索引列表形式的元素段的元素类型必须是 `funcref`
+    ElementTypeMismatch(
+        /* element item index */ u32,
+        /* expected */ RefType,
+        /* actual */ RefType,
+    ),
+}
+
+/// 常量表达式所在的位置
+#[derive(Debug, PartialEq, Clone)]
+pub enum ConstantExpressionLocation {
+    GlobalItem(/* global item index */ u32),
+    ElementItem(/* element item index */ u32),
+    DataItem(/* data item index */ u32),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ConstantExpressionError {
+    /// 常量表达式只允许 `t.const`、`ref.null`、`ref.func`、`global.get` 指令，
+    /// 以及整数的 `add`、`sub` 和 `mul` 指令
+    NonConstantInstruction(Instruction),
+
+    /// 常量表达式里的 `global.get` 只能引用导入的且不可变的全局变量
+    GlobalIndexOutOfRange(/* global variable index */ u32),
+    MutableGlobal(/* global variable index */ u32),
+
+    FunctionIndexOutOfRange(/* function index */ u32),
+
+    /// 常量表达式里的算术指令缺少操作数，或者操作数的类型不匹配
+    OperandTypeMismatch(
+        /* instruction */ Instruction,
+        /* expected */ ValueType,
+    ),
+
+    /// 常量表达式的结果必须是有且只有一个指定类型的数值
+    ResultTypeMismatch(
+        /* expected */ ValueType,
+        /* actual */ Vec<ValueType>,
+    ),
+
+    MissingEnd,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum FunctionError {
+    /// 操作数栈里没有足够的操作数
+    OperandStackUnderflow,
+
+    OperandTypeMismatch(/* expected */ ValueType, /* actual */ ValueType),
+
+    /// 结构块或者函数结束时，操作数栈里残留有多余的操作数
+    UnexpectedOperands(/* count */ usize),
+
+    /// 不带 `else` 分支的 `if` 结构块，其参数类型和返回值类型必须一致
+    IfWithoutElseTypeMismatch,
+
+    /// `br_table` 指令的各个目标结构块的参数数量不一致
+    BranchTableArityMismatch,
+
+    ElseWithoutIf,
+
+    /// 函数体的 `end` 指令之后仍有指令
+    UnexpectedInstructionAfterEnd,
+    MissingEnd,
+
+    /// 函数声明的局部变量的数量超出了限制（见 `validator::MAX_FUNCTION_LOCALS`）
+    TooManyLocals(/* max */ usize),
+
+    /// 对齐值不能大于数据类型的自然对齐值
+    InvalidAlignment(/* align */ u32, /* natural align */ u32),
+
+    /// 32 位内存块的内存参数的偏移值不能超出 u32 的范围
+    MemoryOffsetOutOfRange(/* offset */ u64),
+
+    /// 原子内存访问指令的对齐值必须等于数据类型的自然对齐值
+    InvalidAtomicAlignment(/* align */ u32, /* natural align */ u32),
+
+    /// SIMD 指令的通道索引超出了向量的通道数
+    InvalidLaneIndex(/* lane index */ u8, /* lane count */ u8),
+
+    ImmutableGlobal(/* global variable index */ u32),
+
+    BlockRelativeDepthOutOfRange(/* relative depth */ u32),
+    LocalIndexOutOfRange(/* local variable index */ u32),
+    GlobalIndexOutOfRange(/* global variable index */ u32),
+    FunctionIndexOutOfRange(/* function index */ u32),
+    TypeIndexOutOfRange(/* type index */ u32),
+    TableIndexOutOfRange(/* table index */ u32),
+    MemoryBlockIndexOutOfRange(/* memory block index */ u32),
+    ElementIndexOutOfRange(/* element index */ u32),
+    DataIndexOutOfRange(/* data index */ u32),
+    TagIndexOutOfRange(/* tag index */ u32),
+
+    /// 表或者元素段的元素类型不匹配，比如 `call_indirect` 指令的目标表必须是 `funcref` 表
+    TableElementTypeMismatch(/* expected */ RefType, /* actual */ RefType),
+
+    /// 不带类型的 `select` 指令只能用于数值类型的操作数
+    UntypedSelectOnReference(ValueType),
+
+    /// 带类型的 `select` 指令有且只有一个类型
+    InvalidSelectTypeCount(/* count */ usize),
+
+    /// `ref.is_null` 指令的操作数必须是引用类型
+    ReferenceOperandExpected(ValueType),
+
+    /// 函数体里的 `ref.func` 指令只能引用在元素段、全局变量或者导出项里出现过的函数
+    UndeclaredFunctionReference(/* function index */ u32),
+
+    /// 尾调用的目标函数的返回值类型必须跟当前函数的返回值类型一致
+    TailCallResultTypeMismatch(
+        /* expected */ Vec<ValueType>,
+        /* actual */ Vec<ValueType>,
+    ),
+
+    /// `try_table` 指令的 catch 子句携带的数值类型必须跟目标结构块的类型一致
+    CatchLabelTypeMismatch(
+        /* expected */ Vec<ValueType>,
+        /* actual */ Vec<ValueType>,
+    ),
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationError::ModuleError(e) => write!(f, "{}", e),
+            ValidationError::FunctionError(function_index, instruction_offset, e) => write!(
+                f,
+                "{} (function #{}, instruction #{})",
+                e, function_index, instruction_offset
+            ),
+        }
+    }
+}
+
+impl Display for ModuleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModuleError::TypeIndexOutOfRange(index) => {
+                write!(f, "the type index {} is out of range", index)
+            }
+            ModuleError::FunctionIndexOutOfRange(index) => {
+                write!(f, "the function index {} is out of range", index)
+            }
+            ModuleError::TableIndexOutOfRange(index) => {
+                write!(f, "the table index {} is out of range", index)
+            }
+            ModuleError::MemoryBlockIndexOutOfRange(index) => {
+                write!(f, "the memory block index {} is out of range", index)
+            }
+            ModuleError::GlobalIndexOutOfRange(index) => {
+                write!(f, "the global variable index {} is out of range", index)
+            }
+            ModuleError::TagIndexOutOfRange(index) => {
+                write!(f, "the tag index {} is out of range", index)
+            }
+            ModuleError::FunctionAndCodeCountMismatch(function_count, code_count) => write!(
+                f,
+                "the number of functions {} does not match the number of code items {}",
+                function_count, code_count
+            ),
+            ModuleError::InvalidLimit(min, max) => write!(
+                f,
+                "the minimum value {} of the limit is greater than the maximum value {}",
+                min, max
+            ),
+            ModuleError::MemoryPageExceed(pages, max) => write!(
+                f,
+                "memory pages {} exceeds the limit, maximum allowed {}",
+                pages, max
+            ),
+            ModuleError::SharedMemoryWithoutMax => {
+                write!(f, "the shared memory must have a maximum size")
+            }
+            ModuleError::InvalidTagType(index) => {
+                write!(f, "the type of the tag {} should have no results", index)
+            }
+            ModuleError::DuplicateExportName(name) => {
+                write!(f, "duplicate export name \"{}\"", name)
+            }
+            ModuleError::InvalidStartFunctionType(index) => write!(
+                f,
+                "the type of the start function {} should be \"() -> ()\"",
+                index
+            ),
+            ModuleError::InvalidConstantExpression(location, e) => {
+                write!(f, "{} in the constant expression of {}", e, location)
+            }
+            ModuleError::ElementTypeMismatch(index, expected, actual) => write!(
+                f,
+                "the element type of the element item #{} mismatch, expected: {}, actual: {}",
+                index, expected, actual
+            ),
+        }
+    }
+}
+
+impl Display for ConstantExpressionLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConstantExpressionLocation::GlobalItem(index) => {
+                write!(f, "the global item #{}", index)
+            }
+            ConstantExpressionLocation::ElementItem(index) => {
+                write!(f, "the element item #{}", index)
+            }
+            ConstantExpressionLocation::DataItem(index) => {
+                write!(f, "the data item #{}", index)
+            }
+        }
+    }
+}
+
+impl Display for ConstantExpressionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConstantExpressionError::NonConstantInstruction(instruction) => {
+                write!(f, "non-constant instruction \"{:?}\"", instruction)
+            }
+            ConstantExpressionError::GlobalIndexOutOfRange(index) => write!(
+                f,
+                "the global variable index {} is out of range or is not imported",
+                index
+            ),
+            ConstantExpressionError::MutableGlobal(index) => {
+                write!(f, "the global variable {} is mutable", index)
+            }
+            ConstantExpressionError::FunctionIndexOutOfRange(index) => {
+                write!(f, "the function index {} is out of range", index)
+            }
+            ConstantExpressionError::OperandTypeMismatch(instruction, expected) => write!(
+                f,
+                "instruction \"{:?}\" expects two operands of type {:?}",
+                instruction, expected
+            ),
+            ConstantExpressionError::ResultTypeMismatch(expected, actual) => write!(
+                f,
+                "result type mismatch, expected: {:?}, actual: {:?}",
+                expected, actual
+            ),
+            ConstantExpressionError::MissingEnd => {
+                write!(f, "missing instruction \"end\"")
+            }
+        }
+    }
+}
+
+impl Display for FunctionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FunctionError::OperandStackUnderflow => write!(f, "operand stack underflow"),
+            FunctionError::OperandTypeMismatch(expected, actual) => write!(
+                f,
+                "operand type mismatch, expected: {:?}, actual: {:?}",
+                expected, actual
+            ),
+            FunctionError::UnexpectedOperands(count) => write!(
+                f,
+                "{} unexpected operand(s) left on the operand stack at the end of the block",
+                count
+            ),
+            FunctionError::IfWithoutElseTypeMismatch => write!(
+                f,
+                "the params and results of the \"if\" block without \"else\" should be the same"
+            ),
+            FunctionError::BranchTableArityMismatch => write!(
+                f,
+                "the target blocks of the \"br_table\" have different arities"
+            ),
+            FunctionError::ElseWithoutIf => {
+                write!(f, "instruction \"else\" does not match any \"if\"")
+            }
+            FunctionError::UnexpectedInstructionAfterEnd => {
+                write!(f, "unexpected instruction after the end of the function")
+            }
+            FunctionError::MissingEnd => write!(f, "missing instruction \"end\""),
+            FunctionError::TooManyLocals(max) => {
+                write!(f, "the number of local variables exceeds the limit {}", max)
+            }
+            FunctionError::InvalidAlignment(align, natural_align) => write!(
+                f,
+                "the alignment 2^{} is larger than the natural alignment 2^{}",
+                align, natural_align
+            ),
+            FunctionError::MemoryOffsetOutOfRange(offset) => write!(
+                f,
+                "the memory offset {} is out of range of the 32-bit memory",
+                offset
+            ),
+            FunctionError::InvalidAtomicAlignment(align, natural_align) => write!(
+                f,
+                "the atomic alignment 2^{} must equal the natural alignment 2^{}",
+                align, natural_align
+            ),
+            FunctionError::InvalidLaneIndex(lane, lane_count) => write!(
+                f,
+                "invalid lane index {}, the vector has {} lanes",
+                lane, lane_count
+            ),
+            FunctionError::ImmutableGlobal(index) => {
+                write!(f, "the global variable {} is immutable", index)
+            }
+            FunctionError::BlockRelativeDepthOutOfRange(relative_depth) => write!(
+                f,
+                "the relative depth of the block {} is out of range",
+                relative_depth
+            ),
+            FunctionError::LocalIndexOutOfRange(index) => {
+                write!(f, "the local variable index {} is out of range", index)
+            }
+            FunctionError::GlobalIndexOutOfRange(index) => {
+                write!(f, "the global variable index {} is out of range", index)
+            }
+            FunctionError::FunctionIndexOutOfRange(index) => {
+                write!(f, "the function index {} is out of range", index)
+            }
+            FunctionError::TypeIndexOutOfRange(index) => {
+                write!(f, "the type index {} is out of range", index)
+            }
+            FunctionError::TableIndexOutOfRange(index) => {
+                write!(f, "the table index {} is out of range", index)
+            }
+            FunctionError::MemoryBlockIndexOutOfRange(index) => {
+                write!(f, "the memory block index {} is out of range", index)
+            }
+            FunctionError::ElementIndexOutOfRange(index) => {
+                write!(f, "the element index {} is out of range", index)
+            }
+            FunctionError::DataIndexOutOfRange(index) => {
+                write!(f, "the data index {} is out of range", index)
+            }
+            FunctionError::TagIndexOutOfRange(index) => {
+                write!(f, "the tag index {} is out of range", index)
+            }
+            FunctionError::TableElementTypeMismatch(expected, actual) => write!(
+                f,
+                "table element type mismatch, expected: {}, actual: {}",
+                expected, actual
+            ),
+            FunctionError::UntypedSelectOnReference(value_type) => write!(
+                f,
+                "the operands of the untyped \"select\" should be numeric, actual: {}",
+                value_type
+            ),
+            FunctionError::InvalidSelectTypeCount(count) => write!(
+                f,
+                "the typed \"select\" should have exactly one type, actual: {}",
+                count
+            ),
+            FunctionError::ReferenceOperandExpected(value_type) => write!(
+                f,
+                "the operand should be a reference, actual: {}",
+                value_type
+            ),
+            FunctionError::UndeclaredFunctionReference(index) => {
+                write!(f, "undeclared function reference {}", index)
+            }
+            FunctionError::TailCallResultTypeMismatch(expected, actual) => write!(
+                f,
+                "the results of the tail call target mismatch, expected: {:?}, actual: {:?}",
+                expected, actual
+            ),
+            FunctionError::CatchLabelTypeMismatch(expected, actual) => write!(
+                f,
+                "the catch clause type mismatch, expected: {:?}, actual: {:?}",
+                expected, actual
+            ),
+        }
+    }
+}
@@ -0,0 +1,3202 @@
+// Copyright (c) 2022 Hemashushu <hippospark@gmail.com>, All rights reserved.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! # 模块验证
+//!
+//! <https://webassembly.github.io/spec/core/valid/index.html>
+//!
+//! 函数体的验证采用规范附录里的验证算法：
+//! <https://webassembly.github.io/spec/core/appendix/algorithm.html>
+//!
+//! 验证器在一次线性扫描里模拟操作数栈和控制栈（结构块栈），操作数栈里
+//! 存放的不是数值，而是数值的类型。在 `unreachable`、`br`、`return` 等
+//! 指令之后，操作数栈是 "多态" 的，此时弹出的操作数的类型为 `Unknown`，
+//! 它能匹配任何类型。
+//!
+//! 通过验证的模块，在运行时不会出现操作数类型不匹配、索引值超出范围等错误。
+
+use std::collections::HashSet;
+
+use anvm_ast::{
+    ast::{
+        CodeItem, DataMode, ElementItems, ElementMode, ExportDescriptor, FunctionType, GlobalType,
+        ImportDescriptor, Limit, LocalGroup, MemoryType, Module, TableType, TypeItem,
+    },
+    instruction::{BlockType, CatchClause, Instruction, MemoryArgument},
+    types::{RefType, ValueType},
+};
+
+use crate::error::{
+    ConstantExpressionError, ConstantExpressionLocation, FunctionError, ModuleError,
+    ValidationError,
+};
+
+/// WebAssembly 约定 32 位内存块最大只能有 65536 个页面
+const MAX_MEMORY_PAGES: u64 = 65536;
+
+/// 64 位内存块（由 memory64 提案引入）最大可以有 2^48 个页面
+const MAX_MEMORY64_PAGES: u64 = 1 << 48;
+
+/// 一个函数最多可以声明的局部变量（不包括参数）的数量
+///
+/// 规范只要求总数不超过 u32 的范围，但实例化时需要为每个局部变量分配空间，
+/// 所以跟 V8 等引擎一样限制为 50000，以免一个很小的模块就能令引擎耗尽内存。
+pub const MAX_FUNCTION_LOCALS: usize = 50_000;
+
+pub fn validate(module: &Module) -> Result<(), ValidationError> {
+    let context = Context::new(module).map_err(ValidationError::ModuleError)?;
+
+    validate_module_items(module, &context).map_err(ValidationError::ModuleError)?;
+
+    for (internal_function_index, code_item) in module.code_items.iter().enumerate() {
+        let function_index = (context.imported_function_count + internal_function_index) as u32;
+        validate_function(&context, function_index, code_item)?;
+    }
+
+    Ok(())
+}
+
+/// 验证过程中需要用到的模块信息
+///
+/// 函数、表、内存块、全局变量的索引空间都是先导入的项目，然后才是模块内部定义的项目。
+struct Context<'a> {
+    function_types: Vec<&'a FunctionType>,
+    function_type_indices: Vec<u32>,
+    imported_function_count: usize,
+    table_types: Vec<&'a TableType>,
+    memory_types: Vec<&'a MemoryType>,
+    global_types: Vec<&'a GlobalType>,
+    imported_global_count: usize,
+    element_types: Vec<RefType>,
+    data_count: usize,
+
+    /// 标签（tag）的参数类型，即异常携带的数值的类型
+    tag_params: Vec<Vec<ValueType>>,
+
+    /// 在函数体之外（元素段、全局变量的初始值以及导出项）被引用的函数，
+    /// 函数体里的 `ref.func` 指令只能引用这些函数。
+    declared_function_indices: HashSet<u32>,
+}
+
+impl<'a> Context<'a> {
+    fn new(module: &'a Module) -> Result<Self, ModuleError> {
+        let function_types = module
+            .type_items
+            .iter()
+            .map(|item| match item {
+                TypeItem::FunctionType(function_type) => function_type,
+            })
+            .collect::<Vec<&FunctionType>>();
+
+        let mut function_type_indices: Vec<u32> = vec![];
+        let mut table_types: Vec<&TableType> = vec![];
+        let mut memory_types: Vec<&MemoryType> = vec![];
+        let mut global_types: Vec<&GlobalType> = vec![];
+        let mut tag_type_indices: Vec<u32> = vec![];
+
+        for import_item in &module.import_items {
+            match &import_item.import_descriptor {
+                ImportDescriptor::FunctionTypeIndex(type_index) => {
+                    function_type_indices.push(*type_index);
+                }
+                ImportDescriptor::TableType(table_type) => {
+                    validate_limit(&table_type.limit, None)?;
+                    table_types.push(table_type);
+                }
+                ImportDescriptor::MemoryType(memory_type) => {
+                    validate_memory_type(memory_type)?;
+                    memory_types.push(memory_type);
+                }
+                ImportDescriptor::GlobalType(global_type) => {
+                    global_types.push(global_type);
+                }
+                ImportDescriptor::TagType(tag_type) => {
+                    tag_type_indices.push(tag_type.type_index);
+                }
+            }
+        }
+
+        let imported_function_count = function_type_indices.len();
+        let imported_global_count = global_types.len();
+
+        function_type_indices.extend(module.internal_function_to_type_index_list.iter());
+
+        for table_type in &module.tables {
+            validate_limit(&table_type.limit, None)?;
+            table_types.push(table_type);
+        }
+
+        for memory_type in &module.memory_blocks {
+            validate_memory_type(memory_type)?;
+            memory_types.push(memory_type);
+        }
+
+        global_types.extend(module.global_items.iter().map(|item| &item.global_type));
+        tag_type_indices.extend(module.tag_items.iter().map(|item| item.type_index));
+
+        if let Some(type_index) = function_type_indices
+            .iter()
+            .find(|type_index| **type_index as usize >= function_types.len())
+        {
+            return Err(ModuleError::TypeIndexOutOfRange(*type_index));
+        }
+
+        let mut tag_params: Vec<Vec<ValueType>> = vec![];
+        for (tag_index, type_index) in tag_type_indices.iter().enumerate() {
+            let function_type = function_types
+                .get(*type_index as usize)
+                .ok_or(ModuleError::TypeIndexOutOfRange(*type_index))?;
+
+            if !function_type.results.is_empty() {
+                return Err(ModuleError::InvalidTagType(tag_index as u32));
+            }
+
+            tag_params.push(function_type.params.clone());
+        }
+
+        Ok(Context {
+            function_types,
+            function_type_indices,
+            imported_function_count,
+            table_types,
+            memory_types,
+            global_types,
+            imported_global_count,
+            element_types: module
+                .element_items
+                .iter()
+                .map(|item| item.element_type)
+                .collect::<Vec<RefType>>(),
+            data_count: module.data_items.len(),
+            tag_params,
+            declared_function_indices: get_declared_function_indices(module),
+        })
+    }
+
+    fn get_function_type(&self, function_index: u32) -> Option<&'a FunctionType> {
+        self.function_type_indices
+            .get(function_index as usize)
+            .map(|type_index| self.function_types[*type_index as usize])
+    }
+}
+
+/// 获取在函数体之外被引用的函数的索引
+fn get_declared_function_indices(module: &Module) -> HashSet<u32> {
+    let mut function_indices: HashSet<u32> = HashSet::new();
+
+    for global_item in &module.global_items {
+        collect_ref_func_indices(
+            &global_item.initialize_instruction_items,
+            &mut function_indices,
+        );
+    }
+
+    for element_item in &module.element_items {
+        match &element_item.items {
+            ElementItems::FunctionIndices(indices) => function_indices.extend(indices.iter()),
+            ElementItems::Expressions(expressions) => {
+                for expression in expressions {
+                    collect_ref_func_indices(expression, &mut function_indices);
+                }
+            }
+        }
+    }
+
+    for export_item in &module.export_items {
+        if let ExportDescriptor::FunctionIndex(function_index) = export_item.export_descriptor {
+            function_indices.insert(function_index);
+        }
+    }
+
+    function_indices
+}
+
+fn collect_ref_func_indices(instructions: &[Instruction], function_indices: &mut HashSet<u32>) {
+    for instruction in instructions {
+        if let Instruction::RefFunc(function_index) = instruction {
+            function_indices.insert(*function_index);
+        }
+    }
+}
+
+fn validate_limit(limit: &Limit, option_max_allowed: Option<u64>) -> Result<(), ModuleError> {
+    let (min, option_max) = match limit {
+        Limit::AtLeast(min) => (*min, None),
+        Limit::Range(min, max) => (*min, Some(*max)),
+    };
+
+    if let Some(max) = option_max {
+        if min > max {
+            return Err(ModuleError::InvalidLimit(min, max));
+        }
+    }
+
+    if let Some(max_allowed) = option_max_allowed {
+        let largest = option_max.unwrap_or(min);
+        if largest > max_allowed {
+            return Err(ModuleError::MemoryPageExceed(largest, max_allowed));
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_memory_type(memory_type: &MemoryType) -> Result<(), ModuleError> {
+    let max_allowed = if memory_type.memory64 {
+        MAX_MEMORY64_PAGES
+    } else {
+        MAX_MEMORY_PAGES
+    };
+
+    validate_limit(&memory_type.limit, Some(max_allowed))?;
+
+    if memory_type.shared && matches!(memory_type.limit, Limit::AtLeast(_)) {
+        return Err(ModuleError::SharedMemoryWithoutMax);
+    }
+
+    Ok(())
+}
+
+/// 验证函数体之外的模块项目：
+/// 全局变量、元素、数据、start 函数以及导出项
+fn validate_module_items(module: &Module, context: &Context) -> Result<(), ModuleError> {
+    if module.internal_function_to_type_index_list.len() != module.code_items.len() {
+        return Err(ModuleError::FunctionAndCodeCountMismatch(
+            module.internal_function_to_type_index_list.len(),
+            module.code_items.len(),
+        ));
+    }
+
+    for (index, global_item) in module.global_items.iter().enumerate() {
+        validate_constant_expression(
+            context,
+            &global_item.initialize_instruction_items,
+            &global_item.global_type.value_type,
+        )
+        .map_err(|e| {
+            ModuleError::InvalidConstantExpression(
+                ConstantExpressionLocation::GlobalItem(index as u32),
+                e,
+            )
+        })?;
+    }
+
+    for (index, element_item) in module.element_items.iter().enumerate() {
+        let to_module_error = |e| {
+            ModuleError::InvalidConstantExpression(
+                ConstantExpressionLocation::ElementItem(index as u32),
+                e,
+            )
+        };
+
+        if let ElementMode::Active {
+            table_index,
+            offset_instruction_items,
+        } = &element_item.mode
+        {
+            let table_type = context
+                .table_types
+                .get(*table_index as usize)
+                .ok_or(ModuleError::TableIndexOutOfRange(*table_index))?;
+
+            if table_type.element_type != element_item.element_type {
+                return Err(ModuleError::ElementTypeMismatch(
+                    index as u32,
+                    table_type.element_type,
+                    element_item.element_type,
+                ));
+            }
+
+            validate_constant_expression(context, offset_instruction_items, &ValueType::I32)
+                .map_err(to_module_error)?;
+        }
+
+        match &element_item.items {
+            ElementItems::FunctionIndices(function_indices) => {
+                if element_item.element_type != RefType::FuncRef {
+                    return Err(ModuleError::ElementTypeMismatch(
+                        index as u32,
+                        RefType::FuncRef,
+                        element_item.element_type,
+                    ));
+                }
+
+                if let Some(function_index) = function_indices
+                    .iter()
+                    .find(|function_index| context.get_function_type(**function_index).is_none())
+                {
+                    return Err(ModuleError::FunctionIndexOutOfRange(*function_index));
+                }
+            }
+            ElementItems::Expressions(expressions) => {
+                let element_type = ValueType::from(element_item.element_type);
+                for expression in expressions {
+                    validate_constant_expression(context, expression, &element_type)
+                        .map_err(to_module_error)?;
+                }
+            }
+        }
+    }
+
+    for (index, data_item) in module.data_items.iter().enumerate() {
+        if let DataMode::Active {
+            memory_block_index,
+            offset_instruction_items,
+        } = &data_item.mode
+        {
+            let memory_type = context
+                .memory_types
+                .get(*memory_block_index as usize)
+                .ok_or(ModuleError::MemoryBlockIndexOutOfRange(*memory_block_index))?;
+
+            // 64 位内存块的数据段的偏移值是 i64
+            validate_constant_expression(
+                context,
+                offset_instruction_items,
+                &memory_type.get_address_type(),
+            )
+            .map_err(|e| {
+                ModuleError::InvalidConstantExpression(
+                    ConstantExpressionLocation::DataItem(index as u32),
+                    e,
+                )
+            })?;
+        }
+    }
+
+    if let Some(function_index) = module.start_function_index {
+        match context.get_function_type(function_index) {
+            Some(function_type) => {
+                if !function_type.params.is_empty() || !function_type.results.is_empty() {
+                    return Err(ModuleError::InvalidStartFunctionType(function_index));
+                }
+            }
+            None => return Err(ModuleError::FunctionIndexOutOfRange(function_index)),
+        }
+    }
+
+    let mut export_names: HashSet<&str> = HashSet::new();
+
+    for export_item in &module.export_items {
+        if !export_names.insert(&export_item.name) {
+            return Err(ModuleError::DuplicateExportName(export_item.name.clone()));
+        }
+
+        match export_item.export_descriptor {
+            ExportDescriptor::FunctionIndex(index) => {
+                if context.get_function_type(index).is_none() {
+                    return Err(ModuleError::FunctionIndexOutOfRange(index));
+                }
+            }
+            ExportDescriptor::TableIndex(index) => {
+                if index as usize >= context.table_types.len() {
+                    return Err(ModuleError::TableIndexOutOfRange(index));
+                }
+            }
+            ExportDescriptor::MemoryBlockIndex(index) => {
+                if index as usize >= context.memory_types.len() {
+                    return Err(ModuleError::MemoryBlockIndexOutOfRange(index));
+                }
+            }
+            ExportDescriptor::GlobalItemIndex(index) => {
+                if index as usize >= context.global_types.len() {
+                    return Err(ModuleError::GlobalIndexOutOfRange(index));
+                }
+            }
+            ExportDescriptor::TagIndex(index) => {
+                if index as usize >= context.tag_params.len() {
+                    return Err(ModuleError::TagIndexOutOfRange(index));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// 验证常量表达式
+///
+/// 常量表达式只允许 `t.const`、`ref.null`、`ref.func` 指令，引用导入的不可变全局变量
+/// 的 `global.get` 指令，以及 extended-const 提案引入的 `i32`、`i64` 的 `add`、`sub`
+/// 和 `mul` 指令，且表达式的结果必须是有且只有一个指定类型的数值。
+/// <https://webassembly.github.io/spec/core/valid/instructions.html#constant-expressions>
+/// <https://github.com/WebAssembly/extended-const/blob/main/proposals/extended-const/Overview.md>
+fn validate_constant_expression(
+    context: &Context,
+    instructions: &[Instruction],
+    expected_type: &ValueType,
+) -> Result<(), ConstantExpressionError> {
+    let body = match instructions.split_last() {
+        Some((Instruction::End, body)) => body,
+        _ => return Err(ConstantExpressionError::MissingEnd),
+    };
+
+    let mut result_types: Vec<ValueType> = vec![];
+
+    for instruction in body {
+        match instruction {
+            Instruction::I32Const(_) => result_types.push(ValueType::I32),
+            Instruction::I64Const(_) => result_types.push(ValueType::I64),
+            Instruction::F32Const(_) => result_types.push(ValueType::F32),
+            Instruction::F64Const(_) => result_types.push(ValueType::F64),
+            Instruction::V128Const(_) => result_types.push(ValueType::V128),
+            Instruction::RefNull(ref_type) => result_types.push(ValueType::from(*ref_type)),
+            Instruction::RefFunc(function_index) => {
+                if context.get_function_type(*function_index).is_none() {
+                    return Err(ConstantExpressionError::FunctionIndexOutOfRange(
+                        *function_index,
+                    ));
+                }
+
+                result_types.push(ValueType::FuncRef);
+            }
+            Instruction::GlobalGet(global_index) => {
+                if *global_index as usize >= context.imported_global_count {
+                    return Err(ConstantExpressionError::GlobalIndexOutOfRange(
+                        *global_index,
+                    ));
+                }
+
+                let global_type = context.global_types[*global_index as usize];
+                if global_type.mutable {
+                    return Err(ConstantExpressionError::MutableGlobal(*global_index));
+                }
+
+                result_types.push(global_type.value_type.clone());
+            }
+            Instruction::I32Add | Instruction::I32Sub | Instruction::I32Mul => {
+                pop_constant_operands(&mut result_types, instruction, ValueType::I32)?;
+                result_types.push(ValueType::I32);
+            }
+            Instruction::I64Add | Instruction::I64Sub | Instruction::I64Mul => {
+                pop_constant_operands(&mut result_types, instruction, ValueType::I64)?;
+                result_types.push(ValueType::I64);
+            }
+            _ => {
+                return Err(ConstantExpressionError::NonConstantInstruction(
+                    instruction.clone(),
+                ))
+            }
+        }
+    }
+
+    if result_types.len() != 1 || &result_types[0] != expected_type {
+        return Err(ConstantExpressionError::ResultTypeMismatch(
+            expected_type.clone(),
+            result_types,
+        ));
+    }
+
+    Ok(())
+}
+
+/// 弹出常量表达式里的算术指令的两个操作数
+fn pop_constant_operands(
+    operand_types: &mut Vec<ValueType>,
+    instruction: &Instruction,
+    expected_type: ValueType,
+) -> Result<(), ConstantExpressionError> {
+    match operand_types.as_slice() {
+        [.., left, right] if *left == expected_type && *right == expected_type => {
+            operand_types.truncate(operand_types.len() - 2);
+            Ok(())
+        }
+        _ => Err(ConstantExpressionError::OperandTypeMismatch(
+            instruction.clone(),
+            expected_type,
+        )),
+    }
+}
+
+/// 计算函数声明的局部变量（不包括参数）的总数
+///
+/// 各个局部变量组的数量之和有可能超出 u32 甚至 usize 的范围，所以使用检查溢出的加法，
+/// 总数超出 `MAX_FUNCTION_LOCALS` 时返回错误。
+pub fn count_local_variables(local_groups: &[LocalGroup]) -> Result<usize, FunctionError> {
+    let mut count: usize = 0;
+    for local_group in local_groups {
+        count = count
+            .checked_add(local_group.variable_count as usize)
+            .filter(|count| *count <= MAX_FUNCTION_LOCALS)
+            .ok_or(FunctionError::TooManyLocals(MAX_FUNCTION_LOCALS))?;
+    }
+    Ok(count)
+}
+
+fn validate_function(
+    context: &Context,
+    function_index: u32,
+    code_item: &CodeItem,
+) -> Result<(), ValidationError> {
+    // 函数的类型索引已经在创建 Context 时验证过
+    let function_type = context.get_function_type(function_index).unwrap();
+
+    // 先检查局部变量的数量，然后再分配空间
+    let local_count = count_local_variables(&code_item.local_groups)
+        .map_err(|e| ValidationError::FunctionError(function_index, 0, e))?;
+
+    let mut local_types = Vec::with_capacity(function_type.params.len() + local_count);
+    local_types.extend_from_slice(&function_type.params);
+    for local_group in &code_item.local_groups {
+        local_types.extend(vec![
+            local_group.value_type.clone();
+            local_group.variable_count as usize
+        ]);
+    }
+
+    let mut function_validator = FunctionValidator::new(context, local_types);
+    function_validator.push_frame(FrameKind::Function, vec![], function_type.results.clone());
+
+    for (offset, instruction) in code_item.instruction_items.iter().enumerate() {
+        if function_validator.frames.is_empty() {
+            return Err(ValidationError::FunctionError(
+                function_index,
+                offset,
+                FunctionError::UnexpectedInstructionAfterEnd,
+            ));
+        }
+
+        function_validator
+            .validate_instruction(instruction)
+            .map_err(|e| ValidationError::FunctionError(function_index, offset, e))?;
+    }
+
+    if !function_validator.frames.is_empty() {
+        return Err(ValidationError::FunctionError(
+            function_index,
+            code_item.instruction_items.len(),
+            FunctionError::MissingEnd,
+        ));
+    }
+
+    Ok(())
+}
+
+/// 操作数栈里的操作数类型
+///
+/// `Unknown` 仅出现在 "多态" 栈（即不可到达的代码）里。
+#[derive(Debug, PartialEq, Clone)]
+enum OperandType {
+    Known(ValueType),
+    Unknown,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum FrameKind {
+    Function,
+    Block,
+    Loop,
+    If,
+    Else,
+}
+
+struct ControlFrame {
+    kind: FrameKind,
+    start_types: Vec<ValueType>,
+    end_types: Vec<ValueType>,
+
+    // 进入结构块时操作数栈的高度
+    height: usize,
+    unreachable: bool,
+}
+
+/// (参数类型列表, 返回值类型列表)
+type InstructionType = (&'static [ValueType], &'static [ValueType]);
+
+const I32_UNARY: InstructionType = (&[ValueType::I32], &[ValueType::I32]);
+const I32_BINARY: InstructionType = (&[ValueType::I32, ValueType::I32], &[ValueType::I32]);
+const I64_UNARY: InstructionType = (&[ValueType::I64], &[ValueType::I64]);
+const I64_BINARY: InstructionType = (&[ValueType::I64, ValueType::I64], &[ValueType::I64]);
+const I64_TEST: InstructionType = (&[ValueType::I64], &[ValueType::I32]);
+const I64_COMPARE: InstructionType = (&[ValueType::I64, ValueType::I64], &[ValueType::I32]);
+const F32_UNARY: InstructionType = (&[ValueType::F32], &[ValueType::F32]);
+const F32_BINARY: InstructionType = (&[ValueType::F32, ValueType::F32], &[ValueType::F32]);
+const F32_COMPARE: InstructionType = (&[ValueType::F32, ValueType::F32], &[ValueType::I32]);
+const F64_UNARY: InstructionType = (&[ValueType::F64], &[ValueType::F64]);
+const F64_BINARY: InstructionType = (&[ValueType::F64, ValueType::F64], &[ValueType::F64]);
+const F64_COMPARE: InstructionType = (&[ValueType::F64, ValueType::F64], &[ValueType::I32]);
+
+const V128_UNARY: InstructionType = (&[ValueType::V128], &[ValueType::V128]);
+const V128_BINARY: InstructionType = (&[ValueType::V128, ValueType::V128], &[ValueType::V128]);
+const V128_TERNARY: InstructionType = (
+    &[ValueType::V128, ValueType::V128, ValueType::V128],
+    &[ValueType::V128],
+);
+const V128_TEST: InstructionType = (&[ValueType::V128], &[ValueType::I32]);
+const V128_SHIFT: InstructionType = (&[ValueType::V128, ValueType::I32], &[ValueType::V128]);
+
+const I32_FROM_I64: InstructionType = (&[ValueType::I64], &[ValueType::I32]);
+const I32_FROM_F32: InstructionType = (&[ValueType::F32], &[ValueType::I32]);
+const I32_FROM_F64: InstructionType = (&[ValueType::F64], &[ValueType::I32]);
+const I64_FROM_I32: InstructionType = (&[ValueType::I32], &[ValueType::I64]);
+const I64_FROM_F32: InstructionType = (&[ValueType::F32], &[ValueType::I64]);
+const I64_FROM_F64: InstructionType = (&[ValueType::F64], &[ValueType::I64]);
+const F32_FROM_I32: InstructionType = (&[ValueType::I32], &[ValueType::F32]);
+const F32_FROM_I64: InstructionType = (&[ValueType::I64], &[ValueType::F32]);
+const F32_FROM_F64: InstructionType = (&[ValueType::F64], &[ValueType::F32]);
+const F64_FROM_I32: InstructionType = (&[ValueType::I32], &[ValueType::F64]);
+const F64_FROM_I64: InstructionType = (&[ValueType::I64], &[ValueType::F64]);
+const F64_FROM_F32: InstructionType = (&[ValueType::F32], &[ValueType::F64]);
+const V128_FROM_I32: InstructionType = (&[ValueType::I32], &[ValueType::V128]);
+const V128_FROM_I64: InstructionType = (&[ValueType::I64], &[ValueType::V128]);
+const V128_FROM_F32: InstructionType = (&[ValueType::F32], &[ValueType::V128]);
+const V128_FROM_F64: InstructionType = (&[ValueType::F64], &[ValueType::V128]);
+
+/// 获取数值类指令的参数和返回值类型
+fn get_numeric_instruction_type(instruction: &Instruction) -> Option<InstructionType> {
+    let instruction_type = match instruction {
+        Instruction::I32Eqz
+        | Instruction::I32Clz
+        | Instruction::I32Ctz
+        | Instruction::I32PopCnt
+        | Instruction::I32Extend8S
+        | Instruction::I32Extend16S => I32_UNARY,
+
+        Instruction::I32Eq
+        | Instruction::I32Ne
+        | Instruction::I32LtS
+        | Instruction::I32LtU
+        | Instruction::I32GtS
+        | Instruction::I32GtU
+        | Instruction::I32LeS
+        | Instruction::I32LeU
+        | Instruction::I32GeS
+        | Instruction::I32GeU
+        | Instruction::I32Add
+        | Instruction::I32Sub
+        | Instruction::I32Mul
+        | Instruction::I32DivS
+        | Instruction::I32DivU
+        | Instruction::I32RemS
+        | Instruction::I32RemU
+        | Instruction::I32And
+        | Instruction::I32Or
+        | Instruction::I32Xor
+        | Instruction::I32Shl
+        | Instruction::I32ShrS
+        | Instruction::I32ShrU
+        | Instruction::I32Rotl
+        | Instruction::I32Rotr => I32_BINARY,
+
+        Instruction::I64Eqz => I64_TEST,
+
+        Instruction::I64Clz
+        | Instruction::I64Ctz
+        | Instruction::I64PopCnt
+        | Instruction::I64Extend8S
+        | Instruction::I64Extend16S
+        | Instruction::I64Extend32S => I64_UNARY,
+
+        Instruction::I64Eq
+        | Instruction::I64Ne
+        | Instruction::I64LtS
+        | Instruction::I64LtU
+        | Instruction::I64GtS
+        | Instruction::I64GtU
+        | Instruction::I64LeS
+        | Instruction::I64LeU
+        | Instruction::I64GeS
+        | Instruction::I64GeU => I64_COMPARE,
+
+        Instruction::I64Add
+        | Instruction::I64Sub
+        | Instruction::I64Mul
+        | Instruction::I64DivS
+        | Instruction::I64DivU
+        | Instruction::I64RemS
+        | Instruction::I64RemU
+        | Instruction::I64And
+        | Instruction::I64Or
+        | Instruction::I64Xor
+        | Instruction::I64Shl
+        | Instruction::I64ShrS
+        | Instruction::I64ShrU
+        | Instruction::I64Rotl
+        | Instruction::I64Rotr => I64_BINARY,
+
+        Instruction::F32Eq
+        | Instruction::F32Ne
+        | Instruction::F32Lt
+        | Instruction::F32Gt
+        | Instruction::F32Le
+        | Instruction::F32Ge => F32_COMPARE,
+
+        Instruction::F32Abs
+        | Instruction::F32Neg
+        | Instruction::F32Ceil
+        | Instruction::F32Floor
+        | Instruction::F32Trunc
+        | Instruction::F32Nearest
+        | Instruction::F32Sqrt => F32_UNARY,
+
+        Instruction::F32Add
+        | Instruction::F32Sub
+        | Instruction::F32Mul
+        | Instruction::F32Div
+        | Instruction::F32Min
+        | Instruction::F32Max
+        | Instruction::F32CopySign => F32_BINARY,
+
+        Instruction::F64Eq
+        | Instruction::F64Ne
+        | Instruction::F64Lt
+        | Instruction::F64Gt
+        | Instruction::F64Le
+        | Instruction::F64Ge => F64_COMPARE,
+
+        Instruction::F64Abs
+        | Instruction::F64Neg
+        | Instruction::F64Ceil
+        | Instruction::F64Floor
+        | Instruction::F64Trunc
+        | Instruction::F64Nearest
+        | Instruction::F64Sqrt => F64_UNARY,
+
+        Instruction::F64Add
+        | Instruction::F64Sub
+        | Instruction::F64Mul
+        | Instruction::F64Div
+        | Instruction::F64Min
+        | Instruction::F64Max
+        | Instruction::F64CopySign => F64_BINARY,
+
+        Instruction::I32WrapI64 => I32_FROM_I64,
+        Instruction::I32TruncF32S
+        | Instruction::I32TruncF32U
+        | Instruction::I32TruncSatF32S
+        | Instruction::I32TruncSatF32U
+        | Instruction::I32ReinterpretF32 => I32_FROM_F32,
+        Instruction::I32TruncF64S
+        | Instruction::I32TruncF64U
+        | Instruction::I32TruncSatF64S
+        | Instruction::I32TruncSatF64U => I32_FROM_F64,
+
+        Instruction::I64ExtendI32S | Instruction::I64ExtendI32U => I64_FROM_I32,
+        Instruction::I64TruncF32S
+        | Instruction::I64TruncF32U
+        | Instruction::I64TruncSatF32S
+        | Instruction::I64TruncSatF32U => I64_FROM_F32,
+        Instruction::I64TruncF64S
+        | Instruction::I64TruncF64U
+        | Instruction::I64TruncSatF64S
+        | Instruction::I64TruncSatF64U
+        | Instruction::I64ReinterpretF64 => I64_FROM_F64,
+
+        Instruction::F32ConvertI32S
+        | Instruction::F32ConvertI32U
+        | Instruction::F32ReinterpretI32 => F32_FROM_I32,
+        Instruction::F32ConvertI64S | Instruction::F32ConvertI64U => F32_FROM_I64,
+        Instruction::F32DemoteF64 => F32_FROM_F64,
+
+        Instruction::F64ConvertI32S | Instruction::F64ConvertI32U => F64_FROM_I32,
+        Instruction::F64ConvertI64S
+        | Instruction::F64ConvertI64U
+        | Instruction::F64ReinterpretI64 => F64_FROM_I64,
+        Instruction::F64PromoteF32 => F64_FROM_F32,
+
+        Instruction::V128Not
+        | Instruction::F32x4DemoteF64x2Zero
+        | Instruction::F64x2PromoteLowF32x4
+        | Instruction::I8x16Abs
+        | Instruction::I8x16Neg
+        | Instruction::I8x16Popcnt
+        | Instruction::F32x4Ceil
+        | Instruction::F32x4Floor
+        | Instruction::F32x4Trunc
+        | Instruction::F32x4Nearest
+        | Instruction::F64x2Ceil
+        | Instruction::F64x2Floor
+        | Instruction::F64x2Trunc
+        | Instruction::I16x8ExtaddPairwiseI8x16S
+        | Instruction::I16x8ExtaddPairwiseI8x16U
+        | Instruction::I32x4ExtaddPairwiseI16x8S
+        | Instruction::I32x4ExtaddPairwiseI16x8U
+        | Instruction::I16x8Abs
+        | Instruction::I16x8Neg
+        | Instruction::I16x8ExtendLowI8x16S
+        | Instruction::I16x8ExtendHighI8x16S
+        | Instruction::I16x8ExtendLowI8x16U
+        | Instruction::I16x8ExtendHighI8x16U
+        | Instruction::F64x2Nearest
+        | Instruction::I32x4Abs
+        | Instruction::I32x4Neg
+        | Instruction::I32x4ExtendLowI16x8S
+        | Instruction::I32x4ExtendHighI16x8S
+        | Instruction::I32x4ExtendLowI16x8U
+        | Instruction::I32x4ExtendHighI16x8U
+        | Instruction::I64x2Abs
+        | Instruction::I64x2Neg
+        | Instruction::I64x2ExtendLowI32x4S
+        | Instruction::I64x2ExtendHighI32x4S
+        | Instruction::I64x2ExtendLowI32x4U
+        | Instruction::I64x2ExtendHighI32x4U
+        | Instruction::F32x4Abs
+        | Instruction::F32x4Neg
+        | Instruction::F32x4Sqrt
+        | Instruction::F64x2Abs
+        | Instruction::F64x2Neg
+        | Instruction::F64x2Sqrt
+        | Instruction::I32x4TruncSatF32x4S
+        | Instruction::I32x4TruncSatF32x4U
+        | Instruction::F32x4ConvertI32x4S
+        | Instruction::F32x4ConvertI32x4U
+        | Instruction::I32x4TruncSatF64x2SZero
+        | Instruction::I32x4TruncSatF64x2UZero
+        | Instruction::F64x2ConvertLowI32x4S
+        | Instruction::F64x2ConvertLowI32x4U => V128_UNARY,
+        Instruction::I8x16Swizzle
+        | Instruction::I8x16Eq
+        | Instruction::I8x16Ne
+        | Instruction::I8x16LtS
+        | Instruction::I8x16LtU
+        | Instruction::I8x16GtS
+        | Instruction::I8x16GtU
+        | Instruction::I8x16LeS
+        | Instruction::I8x16LeU
+        | Instruction::I8x16GeS
+        | Instruction::I8x16GeU
+        | Instruction::I16x8Eq
+        | Instruction::I16x8Ne
+        | Instruction::I16x8LtS
+        | Instruction::I16x8LtU
+        | Instruction::I16x8GtS
+        | Instruction::I16x8GtU
+        | Instruction::I16x8LeS
+        | Instruction::I16x8LeU
+        | Instruction::I16x8GeS
+        | Instruction::I16x8GeU
+        | Instruction::I32x4Eq
+        | Instruction::I32x4Ne
+        | Instruction::I32x4LtS
+        | Instruction::I32x4LtU
+        | Instruction::I32x4GtS
+        | Instruction::I32x4GtU
+        | Instruction::I32x4LeS
+        | Instruction::I32x4LeU
+        | Instruction::I32x4GeS
+        | Instruction::I32x4GeU
+        | Instruction::F32x4Eq
+        | Instruction::F32x4Ne
+        | Instruction::F32x4Lt
+        | Instruction::F32x4Gt
+        | Instruction::F32x4Le
+        | Instruction::F32x4Ge
+        | Instruction::F64x2Eq
+        | Instruction::F64x2Ne
+        | Instruction::F64x2Lt
+        | Instruction::F64x2Gt
+        | Instruction::F64x2Le
+        | Instruction::F64x2Ge
+        | Instruction::V128And
+        | Instruction::V128Andnot
+        | Instruction::V128Or
+        | Instruction::V128Xor
+        | Instruction::I8x16NarrowI16x8S
+        | Instruction::I8x16NarrowI16x8U
+        | Instruction::I8x16Add
+        | Instruction::I8x16AddSatS
+        | Instruction::I8x16AddSatU
+        | Instruction::I8x16Sub
+        | Instruction::I8x16SubSatS
+        | Instruction::I8x16SubSatU
+        | Instruction::I8x16MinS
+        | Instruction::I8x16MinU
+        | Instruction::I8x16MaxS
+        | Instruction::I8x16MaxU
+        | Instruction::I8x16AvgrU
+        | Instruction::I16x8Q15mulrSatS
+        | Instruction::I16x8NarrowI32x4S
+        | Instruction::I16x8NarrowI32x4U
+        | Instruction::I16x8Add
+        | Instruction::I16x8AddSatS
+        | Instruction::I16x8AddSatU
+        | Instruction::I16x8Sub
+        | Instruction::I16x8SubSatS
+        | Instruction::I16x8SubSatU
+        | Instruction::I16x8Mul
+        | Instruction::I16x8MinS
+        | Instruction::I16x8MinU
+        | Instruction::I16x8MaxS
+        | Instruction::I16x8MaxU
+        | Instruction::I16x8AvgrU
+        | Instruction::I16x8ExtmulLowI8x16S
+        | Instruction::I16x8ExtmulHighI8x16S
+        | Instruction::I16x8ExtmulLowI8x16U
+        | Instruction::I16x8ExtmulHighI8x16U
+        | Instruction::I32x4Add
+        | Instruction::I32x4Sub
+        | Instruction::I32x4Mul
+        | Instruction::I32x4MinS
+        | Instruction::I32x4MinU
+        | Instruction::I32x4MaxS
+        | Instruction::I32x4MaxU
+        | Instruction::I32x4DotI16x8S
+        | Instruction::I32x4ExtmulLowI16x8S
+        | Instruction::I32x4ExtmulHighI16x8S
+        | Instruction::I32x4ExtmulLowI16x8U
+        | Instruction::I32x4ExtmulHighI16x8U
+        | Instruction::I64x2Add
+        | Instruction::I64x2Sub
+        | Instruction::I64x2Mul
+        | Instruction::I64x2Eq
+        | Instruction::I64x2Ne
+        | Instruction::I64x2LtS
+        | Instruction::I64x2GtS
+        | Instruction::I64x2LeS
+        | Instruction::I64x2GeS
+        | Instruction::I64x2ExtmulLowI32x4S
+        | Instruction::I64x2ExtmulHighI32x4S
+        | Instruction::I64x2ExtmulLowI32x4U
+        | Instruction::I64x2ExtmulHighI32x4U
+        | Instruction::F32x4Add
+        | Instruction::F32x4Sub
+        | Instruction::F32x4Mul
+        | Instruction::F32x4Div
+        | Instruction::F32x4Min
+        | Instruction::F32x4Max
+        | Instruction::F32x4Pmin
+        | Instruction::F32x4Pmax
+        | Instruction::F64x2Add
+        | Instruction::F64x2Sub
+        | Instruction::F64x2Mul
+        | Instruction::F64x2Div
+        | Instruction::F64x2Min
+        | Instruction::F64x2Max
+        | Instruction::F64x2Pmin
+        | Instruction::F64x2Pmax => V128_BINARY,
+        Instruction::V128Bitselect => V128_TERNARY,
+        Instruction::V128AnyTrue
+        | Instruction::I8x16AllTrue
+        | Instruction::I8x16Bitmask
+        | Instruction::I16x8AllTrue
+        | Instruction::I16x8Bitmask
+        | Instruction::I32x4AllTrue
+        | Instruction::I32x4Bitmask
+        | Instruction::I64x2AllTrue
+        | Instruction::I64x2Bitmask => V128_TEST,
+        Instruction::I8x16Shl
+        | Instruction::I8x16ShrS
+        | Instruction::I8x16ShrU
+        | Instruction::I16x8Shl
+        | Instruction::I16x8ShrS
+        | Instruction::I16x8ShrU
+        | Instruction::I32x4Shl
+        | Instruction::I32x4ShrS
+        | Instruction::I32x4ShrU
+        | Instruction::I64x2Shl
+        | Instruction::I64x2ShrS
+        | Instruction::I64x2ShrU => V128_SHIFT,
+        Instruction::I8x16Splat | Instruction::I16x8Splat | Instruction::I32x4Splat => {
+            V128_FROM_I32
+        }
+        Instruction::I64x2Splat => V128_FROM_I64,
+        Instruction::F32x4Splat => V128_FROM_F32,
+        Instruction::F64x2Splat => V128_FROM_F64,
+
+        _ => return None,
+    };
+
+    Some(instruction_type)
+}
+
+struct FunctionValidator<'a> {
+    context: &'a Context<'a>,
+    local_types: Vec<ValueType>,
+    operands: Vec<OperandType>,
+    frames: Vec<ControlFrame>,
+}
+
+impl<'a> FunctionValidator<'a> {
+    fn new(context: &'a Context<'a>, local_types: Vec<ValueType>) -> Self {
+        FunctionValidator {
+            context,
+            local_types,
+            operands: vec![],
+            frames: vec![],
+        }
+    }
+
+    fn push_operand(&mut self, operand_type: OperandType) {
+        self.operands.push(operand_type);
+    }
+
+    fn push_values(&mut self, value_types: &[ValueType]) {
+        for value_type in value_types {
+            self.push_operand(OperandType::Known(value_type.clone()));
+        }
+    }
+
+    fn pop_operand(&mut self) -> Result<OperandType, FunctionError> {
+        let frame = self.frames.last().unwrap();
+
+        if self.operands.len() == frame.height {
+            // 在不可到达的代码里，操作数栈是多态的
+            return if frame.unreachable {
+                Ok(OperandType::Unknown)
+            } else {
+                Err(FunctionError::OperandStackUnderflow)
+            };
+        }
+
+        Ok(self.operands.pop().unwrap())
+    }
+
+    fn pop_value(&mut self, expected_type: &ValueType) -> Result<OperandType, FunctionError> {
+        let operand_type = self.pop_operand()?;
+
+        match &operand_type {
+            OperandType::Known(actual_type) if actual_type != expected_type => Err(
+                FunctionError::OperandTypeMismatch(expected_type.clone(), actual_type.clone()),
+            ),
+            _ => Ok(operand_type),
+        }
+    }
+
+    fn pop_values(&mut self, value_types: &[ValueType]) -> Result<Vec<OperandType>, FunctionError> {
+        let mut operand_types: Vec<OperandType> = vec![];
+        for value_type in value_types.iter().rev() {
+            operand_types.push(self.pop_value(value_type)?);
+        }
+        operand_types.reverse();
+        Ok(operand_types)
+    }
+
+    fn push_frame(
+        &mut self,
+        kind: FrameKind,
+        start_types: Vec<ValueType>,
+        end_types: Vec<ValueType>,
+    ) {
+        let frame = ControlFrame {
+            kind,
+            start_types,
+            end_types,
+            height: self.operands.len(),
+            unreachable: false,
+        };
+
+        let start_types = frame.start_types.clone();
+        self.frames.push(frame);
+        self.push_values(&start_types);
+    }
+
+    fn pop_frame(&mut self) -> Result<ControlFrame, FunctionError> {
+        let end_types = self.frames.last().unwrap().end_types.clone();
+        self.pop_values(&end_types)?;
+
+        let height = self.frames.last().unwrap().height;
+        if self.operands.len() != height {
+            return Err(FunctionError::UnexpectedOperands(
+                self.operands.len() - height,
+            ));
+        }
+
+        Ok(self.frames.pop().unwrap())
+    }
+
+    /// 跳转到目标结构块时需要携带的操作数的类型
+    ///
+    /// 跳转到 `loop` 结构块（的开头）时，携带的是结构块的参数，
+    /// 跳转到其他结构块（的末尾）时，携带的是结构块的返回值。
+    fn get_label_types(&self, relative_depth: u32) -> Result<Vec<ValueType>, FunctionError> {
+        let depth = relative_depth as usize;
+        if depth >= self.frames.len() {
+            return Err(FunctionError::BlockRelativeDepthOutOfRange(relative_depth));
+        }
+
+        let frame = &self.frames[self.frames.len() - 1 - depth];
+        if frame.kind == FrameKind::Loop {
+            Ok(frame.start_types.clone())
+        } else {
+            Ok(frame.end_types.clone())
+        }
+    }
+
+    fn set_unreachable(&mut self) {
+        let frame = self.frames.last_mut().unwrap();
+        self.operands.truncate(frame.height);
+        frame.unreachable = true;
+    }
+
+    fn get_block_type(
+        &self,
+        block_type: &BlockType,
+    ) -> Result<(Vec<ValueType>, Vec<ValueType>), FunctionError> {
+        let types = match block_type {
+            BlockType::ResultI32 => (vec![], vec![ValueType::I32]),
+            BlockType::ResultI64 => (vec![], vec![ValueType::I64]),
+            BlockType::ResultF32 => (vec![], vec![ValueType::F32]),
+            BlockType::ResultF64 => (vec![], vec![ValueType::F64]),
+            BlockType::ResultV128 => (vec![], vec![ValueType::V128]),
+            BlockType::ResultFuncRef => (vec![], vec![ValueType::FuncRef]),
+            BlockType::ResultExternRef => (vec![], vec![ValueType::ExternRef]),
+            BlockType::ResultExnRef => (vec![], vec![ValueType::ExnRef]),
+            BlockType::ResultEmpty => (vec![], vec![]),
+            BlockType::TypeIndex(type_index) => {
+                let function_type = self
+                    .context
+                    .function_types
+                    .get(*type_index as usize)
+                    .ok_or(FunctionError::TypeIndexOutOfRange(*type_index))?;
+                (function_type.params.clone(), function_type.results.clone())
+            }
+        };
+
+        Ok(types)
+    }
+
+    fn get_global_type(&self, global_index: u32) -> Result<&'a GlobalType, FunctionError> {
+        self.context
+            .global_types
+            .get(global_index as usize)
+            .copied()
+            .ok_or(FunctionError::GlobalIndexOutOfRange(global_index))
+    }
+
+    fn get_local_type(&self, local_index: u32) -> Result<ValueType, FunctionError> {
+        self.local_types
+            .get(local_index as usize)
+            .cloned()
+            .ok_or(FunctionError::LocalIndexOutOfRange(local_index))
+    }
+
+    /// 检查表索引，并返回表的元素类型
+    fn get_table_element_type(&self, table_index: u32) -> Result<RefType, FunctionError> {
+        self.context
+            .table_types
+            .get(table_index as usize)
+            .map(|table_type| table_type.element_type)
+            .ok_or(FunctionError::TableIndexOutOfRange(table_index))
+    }
+
+    /// 获取内存地址的数据类型，32 位内存块为 i32，64 位内存块为 i64
+    fn get_memory_address_type(&self, memory_block_index: u32) -> Result<ValueType, FunctionError> {
+        self.context
+            .memory_types
+            .get(memory_block_index as usize)
+            .map(|memory_type| memory_type.get_address_type())
+            .ok_or(FunctionError::MemoryBlockIndexOutOfRange(
+                memory_block_index,
+            ))
+    }
+
+    /// 检查元素段索引，并返回元素段的元素类型
+    fn get_element_type(&self, element_index: u32) -> Result<RefType, FunctionError> {
+        self.context
+            .element_types
+            .get(element_index as usize)
+            .copied()
+            .ok_or(FunctionError::ElementIndexOutOfRange(element_index))
+    }
+
+    fn check_data_index(&self, data_index: u32) -> Result<(), FunctionError> {
+        if data_index as usize >= self.context.data_count {
+            Err(FunctionError::DataIndexOutOfRange(data_index))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// 检查内存类指令的内存块以及对齐值
+    ///
+    /// natural_align 是数据类型的自然对齐值的对数，比如 i32 为 2（即 4 个字节）
+    /// 检查内存参数，返回内存地址的数据类型
+    ///
+    /// 32 位内存块的 offset 不能超出 u32 的范围。
+    fn check_memory_argument(
+        &self,
+        memory_argument: &MemoryArgument,
+        natural_align: u32,
+    ) -> Result<ValueType, FunctionError> {
+        let address_type = self.get_memory_address_type(memory_argument.memory_block_index)?;
+
+        if address_type == ValueType::I32 && memory_argument.offset > u32::MAX as u64 {
+            return Err(FunctionError::MemoryOffsetOutOfRange(
+                memory_argument.offset,
+            ));
+        }
+
+        if memory_argument.align > natural_align {
+            return Err(FunctionError::InvalidAlignment(
+                memory_argument.align,
+                natural_align,
+            ));
+        }
+
+        Ok(address_type)
+    }
+
+    fn validate_load(
+        &mut self,
+        memory_argument: &MemoryArgument,
+        natural_align: u32,
+        value_type: ValueType,
+    ) -> Result<(), FunctionError> {
+        let address_type = self.check_memory_argument(memory_argument, natural_align)?;
+        self.pop_value(&address_type)?;
+        self.push_operand(OperandType::Known(value_type));
+        Ok(())
+    }
+
+    fn validate_store(
+        &mut self,
+        memory_argument: &MemoryArgument,
+        natural_align: u32,
+        value_type: ValueType,
+    ) -> Result<(), FunctionError> {
+        let address_type = self.check_memory_argument(memory_argument, natural_align)?;
+        self.pop_value(&value_type)?;
+        self.pop_value(&address_type)?;
+        Ok(())
+    }
+
+    /// 验证原子内存访问指令
+    ///
+    /// 原子指令的对齐值必须等于自然对齐值，
+    /// params 是除了内存地址之外的其余参数的类型。
+    fn validate_atomic(
+        &mut self,
+        memory_argument: &MemoryArgument,
+        natural_align: u32,
+        params: &[ValueType],
+        results: &[ValueType],
+    ) -> Result<(), FunctionError> {
+        let address_type = self.check_memory_argument(memory_argument, natural_align)?;
+
+        if memory_argument.align != natural_align {
+            return Err(FunctionError::InvalidAtomicAlignment(
+                memory_argument.align,
+                natural_align,
+            ));
+        }
+
+        self.pop_values(params)?;
+        self.pop_value(&address_type)?;
+        self.push_values(results);
+        Ok(())
+    }
+
+    fn check_lane_index(&self, lane: u8, lane_count: u8) -> Result<(), FunctionError> {
+        if lane >= lane_count {
+            Err(FunctionError::InvalidLaneIndex(lane, lane_count))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// 验证 `v128.loadN_lane` 指令
+    ///
+    /// 通道数由通道的宽度（即自然对齐值）决定，比如 `v128.load16_lane` 有 8 个通道。
+    fn validate_load_lane(
+        &mut self,
+        memory_argument: &MemoryArgument,
+        natural_align: u32,
+        lane: u8,
+    ) -> Result<(), FunctionError> {
+        let address_type = self.check_memory_argument(memory_argument, natural_align)?;
+        self.check_lane_index(lane, 16 >> natural_align)?;
+        self.pop_values(&[address_type, ValueType::V128])?;
+        self.push_values(&[ValueType::V128]);
+        Ok(())
+    }
+
+    fn validate_store_lane(
+        &mut self,
+        memory_argument: &MemoryArgument,
+        natural_align: u32,
+        lane: u8,
+    ) -> Result<(), FunctionError> {
+        let address_type = self.check_memory_argument(memory_argument, natural_align)?;
+        self.check_lane_index(lane, 16 >> natural_align)?;
+        self.pop_values(&[address_type, ValueType::V128])?;
+        Ok(())
+    }
+
+    fn validate_extract_lane(
+        &mut self,
+        lane: u8,
+        lane_count: u8,
+        value_type: ValueType,
+    ) -> Result<(), FunctionError> {
+        self.check_lane_index(lane, lane_count)?;
+        self.pop_value(&ValueType::V128)?;
+        self.push_operand(OperandType::Known(value_type));
+        Ok(())
+    }
+
+    fn validate_replace_lane(
+        &mut self,
+        lane: u8,
+        lane_count: u8,
+        value_type: ValueType,
+    ) -> Result<(), FunctionError> {
+        self.check_lane_index(lane, lane_count)?;
+        self.pop_values(&[ValueType::V128, value_type])?;
+        self.push_values(&[ValueType::V128]);
+        Ok(())
+    }
+
+    fn get_tag_params(&self, tag_index: u32) -> Result<Vec<ValueType>, FunctionError> {
+        self.context
+            .tag_params
+            .get(tag_index as usize)
+            .cloned()
+            .ok_or(FunctionError::TagIndexOutOfRange(tag_index))
+    }
+
+    /// catch 子句捕获异常之后会跳转到目标结构块，携带的数值：
+    ///
+    /// - `catch`：异常的参数
+    /// - `catch_ref`：异常的参数以及 exnref
+    /// - `catch_all`：无
+    /// - `catch_all_ref`：exnref
+    ///
+    /// 注意 catch 子句的目标结构块的相对深度不包括 `try_table` 结构块本身。
+    fn validate_catch_clause(&self, catch_clause: &CatchClause) -> Result<(), FunctionError> {
+        let (catch_types, relative_depth) = match catch_clause {
+            CatchClause::Catch(tag_index, relative_depth) => {
+                (self.get_tag_params(*tag_index)?, relative_depth)
+            }
+            CatchClause::CatchRef(tag_index, relative_depth) => {
+                let mut types = self.get_tag_params(*tag_index)?;
+                types.push(ValueType::ExnRef);
+                (types, relative_depth)
+            }
+            CatchClause::CatchAll(relative_depth) => (vec![], relative_depth),
+            CatchClause::CatchAllRef(relative_depth) => (vec![ValueType::ExnRef], relative_depth),
+        };
+
+        let label_types = self.get_label_types(*relative_depth)?;
+        if label_types != catch_types {
+            return Err(FunctionError::CatchLabelTypeMismatch(
+                label_types,
+                catch_types,
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn validate_call(&mut self, function_type: &FunctionType) -> Result<(), FunctionError> {
+        self.pop_values(&function_type.params)?;
+        self.push_values(&function_type.results);
+        Ok(())
+    }
+
+    /// 尾调用相当于 `call` 指令紧接着 `return` 指令，所以目标函数的返回值类型
+    /// 必须跟当前函数的返回值类型一致
+    fn validate_return_call(&mut self, function_type: &FunctionType) -> Result<(), FunctionError> {
+        let results = &self.frames[0].end_types;
+        if &function_type.results != results {
+            return Err(FunctionError::TailCallResultTypeMismatch(
+                results.clone(),
+                function_type.results.clone(),
+            ));
+        }
+
+        self.pop_values(&function_type.params)?;
+        self.set_unreachable();
+        Ok(())
+    }
+
+    fn validate_instruction(&mut self, instruction: &Instruction) -> Result<(), FunctionError> {
+        match instruction {
+            Instruction::Unreachable => self.set_unreachable(),
+            Instruction::Nop => {}
+
+            Instruction::Block(block_type, _) => {
+                let (params, results) = self.get_block_type(block_type)?;
+                self.pop_values(&params)?;
+                self.push_frame(FrameKind::Block, params, results);
+            }
+            Instruction::Loop(block_type, _) => {
+                let (params, results) = self.get_block_type(block_type)?;
+                self.pop_values(&params)?;
+                self.push_frame(FrameKind::Loop, params, results);
+            }
+            Instruction::If(block_type, _) => {
+                let (params, results) = self.get_block_type(block_type)?;
+                self.pop_value(&ValueType::I32)?;
+                self.pop_values(&params)?;
+                self.push_frame(FrameKind::If, params, results);
+            }
+            Instruction::Else => {
+                if self.frames.last().unwrap().kind != FrameKind::If {
+                    return Err(FunctionError::ElseWithoutIf);
+                }
+
+                let frame = self.pop_frame()?;
+                self.push_frame(FrameKind::Else, frame.start_types, frame.end_types);
+            }
+            Instruction::End => {
+                let frame = self.pop_frame()?;
+                if frame.kind == FrameKind::If && frame.start_types != frame.end_types {
+                    return Err(FunctionError::IfWithoutElseTypeMismatch);
+                }
+                self.push_values(&frame.end_types);
+            }
+
+            Instruction::Br(relative_depth) => {
+                let label_types = self.get_label_types(*relative_depth)?;
+                self.pop_values(&label_types)?;
+                self.set_unreachable();
+            }
+            Instruction::BrIf(relative_depth) => {
+                self.pop_value(&ValueType::I32)?;
+                let label_types = self.get_label_types(*relative_depth)?;
+                self.pop_values(&label_types)?;
+                self.push_values(&label_types);
+            }
+            Instruction::BrTable(relative_depths, default_relative_depth) => {
+                self.pop_value(&ValueType::I32)?;
+
+                let default_label_types = self.get_label_types(*default_relative_depth)?;
+                let arity = default_label_types.len();
+
+                for relative_depth in relative_depths {
+                    let label_types = self.get_label_types(*relative_depth)?;
+                    if label_types.len() != arity {
+                        return Err(FunctionError::BranchTableArityMismatch);
+                    }
+
+                    let operand_types = self.pop_values(&label_types)?;
+                    for operand_type in operand_types {
+                        self.push_operand(operand_type);
+                    }
+                }
+
+                self.pop_values(&default_label_types)?;
+                self.set_unreachable();
+            }
+            Instruction::Return => {
+                let results = self.frames[0].end_types.clone();
+                self.pop_values(&results)?;
+                self.set_unreachable();
+            }
+            Instruction::Call(function_index) => {
+                let function_type = self
+                    .context
+                    .get_function_type(*function_index)
+                    .ok_or(FunctionError::FunctionIndexOutOfRange(*function_index))?;
+                self.validate_call(function_type)?;
+            }
+            Instruction::CallIndirect(type_index, table_index) => {
+                let element_type = self.get_table_element_type(*table_index)?;
+                if element_type != RefType::FuncRef {
+                    return Err(FunctionError::TableElementTypeMismatch(
+                        RefType::FuncRef,
+                        element_type,
+                    ));
+                }
+
+                let function_type = *self
+                    .context
+                    .function_types
+                    .get(*type_index as usize)
+                    .ok_or(FunctionError::TypeIndexOutOfRange(*type_index))?;
+
+                self.pop_value(&ValueType::I32)?;
+                self.validate_call(function_type)?;
+            }
+            Instruction::ReturnCall(function_index) => {
+                let function_type = self
+                    .context
+                    .get_function_type(*function_index)
+                    .ok_or(FunctionError::FunctionIndexOutOfRange(*function_index))?;
+                self.validate_return_call(function_type)?;
+            }
+            Instruction::ReturnCallIndirect(type_index, table_index) => {
+                let element_type = self.get_table_element_type(*table_index)?;
+                if element_type != RefType::FuncRef {
+                    return Err(FunctionError::TableElementTypeMismatch(
+                        RefType::FuncRef,
+                        element_type,
+                    ));
+                }
+
+                let function_type = *self
+                    .context
+                    .function_types
+                    .get(*type_index as usize)
+                    .ok_or(FunctionError::TypeIndexOutOfRange(*type_index))?;
+
+                self.pop_value(&ValueType::I32)?;
+                self.validate_return_call(function_type)?;
+            }
+
+            Instruction::Throw(tag_index) => {
+                let params = self.get_tag_params(*tag_index)?;
+                self.pop_values(&params)?;
+                self.set_unreachable();
+            }
+            Instruction::ThrowRef => {
+                self.pop_value(&ValueType::ExnRef)?;
+                self.set_unreachable();
+            }
+            Instruction::TryTable(block_type, catch_clauses, _) => {
+                let (params, results) = self.get_block_type(block_type)?;
+                for catch_clause in catch_clauses {
+                    self.validate_catch_clause(catch_clause)?;
+                }
+                self.pop_values(&params)?;
+                self.push_frame(FrameKind::Block, params, results);
+            }
+
+            Instruction::Drop => {
+                self.pop_operand()?;
+            }
+            Instruction::Select => {
+                self.pop_value(&ValueType::I32)?;
+                let first = self.pop_operand()?;
+                let second = self.pop_operand()?;
+
+                // 不带类型的 select 指令只能用于数值类型的操作数
+                for operand_type in [&first, &second] {
+                    if let OperandType::Known(value_type) = operand_type {
+                        if value_type.is_ref() {
+                            return Err(FunctionError::UntypedSelectOnReference(
+                                value_type.clone(),
+                            ));
+                        }
+                    }
+                }
+
+                match (&first, &second) {
+                    (OperandType::Known(t1), OperandType::Known(t2)) if t1 != t2 => {
+                        return Err(FunctionError::OperandTypeMismatch(t1.clone(), t2.clone()));
+                    }
+                    (OperandType::Unknown, _) => self.push_operand(second),
+                    _ => self.push_operand(first),
+                }
+            }
+            Instruction::SelectTyped(value_types) => {
+                if value_types.len() != 1 {
+                    return Err(FunctionError::InvalidSelectTypeCount(value_types.len()));
+                }
+
+                self.pop_value(&ValueType::I32)?;
+                self.pop_values(&[value_types[0].clone(), value_types[0].clone()])?;
+                self.push_values(value_types);
+            }
+
+            Instruction::LocalGet(local_index) => {
+                let value_type = self.get_local_type(*local_index)?;
+                self.push_operand(OperandType::Known(value_type));
+            }
+            Instruction::LocalSet(local_index) => {
+                let value_type = self.get_local_type(*local_index)?;
+                self.pop_value(&value_type)?;
+            }
+            Instruction::LocalTee(local_index) => {
+                let value_type = self.get_local_type(*local_index)?;
+                self.pop_value(&value_type)?;
+                self.push_operand(OperandType::Known(value_type));
+            }
+            Instruction::GlobalGet(global_index) => {
+                let global_type = self.get_global_type(*global_index)?;
+                self.push_operand(OperandType::Known(global_type.value_type.clone()));
+            }
+            Instruction::GlobalSet(global_index) => {
+                let global_type = self.get_global_type(*global_index)?;
+                if !global_type.mutable {
+                    return Err(FunctionError::ImmutableGlobal(*global_index));
+                }
+                self.pop_value(&global_type.value_type)?;
+            }
+
+            Instruction::I32Load(m) => self.validate_load(m, 2, ValueType::I32)?,
+            Instruction::I64Load(m) => self.validate_load(m, 3, ValueType::I64)?,
+            Instruction::F32Load(m) => self.validate_load(m, 2, ValueType::F32)?,
+            Instruction::F64Load(m) => self.validate_load(m, 3, ValueType::F64)?,
+            Instruction::I32Load8S(m) => self.validate_load(m, 0, ValueType::I32)?,
+            Instruction::I32Load8U(m) => self.validate_load(m, 0, ValueType::I32)?,
+            Instruction::I32Load16S(m) => self.validate_load(m, 1, ValueType::I32)?,
+            Instruction::I32Load16U(m) => self.validate_load(m, 1, ValueType::I32)?,
+            Instruction::I64Load8S(m) => self.validate_load(m, 0, ValueType::I64)?,
+            Instruction::I64Load8U(m) => self.validate_load(m, 0, ValueType::I64)?,
+            Instruction::I64Load16S(m) => self.validate_load(m, 1, ValueType::I64)?,
+            Instruction::I64Load16U(m) => self.validate_load(m, 1, ValueType::I64)?,
+            Instruction::I64Load32S(m) => self.validate_load(m, 2, ValueType::I64)?,
+            Instruction::I64Load32U(m) => self.validate_load(m, 2, ValueType::I64)?,
+            Instruction::I32Store(m) => self.validate_store(m, 2, ValueType::I32)?,
+            Instruction::I64Store(m) => self.validate_store(m, 3, ValueType::I64)?,
+            Instruction::F32Store(m) => self.validate_store(m, 2, ValueType::F32)?,
+            Instruction::F64Store(m) => self.validate_store(m, 3, ValueType::F64)?,
+            Instruction::I32Store8(m) => self.validate_store(m, 0, ValueType::I32)?,
+            Instruction::I32Store16(m) => self.validate_store(m, 1, ValueType::I32)?,
+            Instruction::I64Store8(m) => self.validate_store(m, 0, ValueType::I64)?,
+            Instruction::I64Store16(m) => self.validate_store(m, 1, ValueType::I64)?,
+            Instruction::I64Store32(m) => self.validate_store(m, 2, ValueType::I64)?,
+
+            // 64 位内存块的页面数以及地址都是 i64
+            Instruction::MemorySize(memory_block_index) => {
+                let address_type = self.get_memory_address_type(*memory_block_index)?;
+                self.push_values(&[address_type]);
+            }
+            Instruction::MemoryGrow(memory_block_index) => {
+                let address_type = self.get_memory_address_type(*memory_block_index)?;
+                self.pop_value(&address_type)?;
+                self.push_values(&[address_type]);
+            }
+            Instruction::MemoryInit(data_index, memory_block_index) => {
+                let address_type = self.get_memory_address_type(*memory_block_index)?;
+                self.check_data_index(*data_index)?;
+                self.pop_values(&[address_type, ValueType::I32, ValueType::I32])?;
+            }
+            Instruction::DataDrop(data_index) => {
+                self.check_data_index(*data_index)?;
+            }
+            Instruction::MemoryCopy(dest_memory_block_index, source_memory_block_index) => {
+                let dest_address_type = self.get_memory_address_type(*dest_memory_block_index)?;
+                let source_address_type =
+                    self.get_memory_address_type(*source_memory_block_index)?;
+
+                // 当两个内存块其中一个是 32 位内存块时，复制的长度是 i32
+                let length_type = if dest_address_type == ValueType::I64
+                    && source_address_type == ValueType::I64
+                {
+                    ValueType::I64
+                } else {
+                    ValueType::I32
+                };
+
+                self.pop_values(&[dest_address_type, source_address_type, length_type])?;
+            }
+            Instruction::MemoryFill(memory_block_index) => {
+                let address_type = self.get_memory_address_type(*memory_block_index)?;
+                self.pop_values(&[address_type.clone(), ValueType::I32, address_type])?;
+            }
+
+            Instruction::TableInit(element_index, table_index) => {
+                let table_element_type = self.get_table_element_type(*table_index)?;
+                let element_type = self.get_element_type(*element_index)?;
+                if table_element_type != element_type {
+                    return Err(FunctionError::TableElementTypeMismatch(
+                        table_element_type,
+                        element_type,
+                    ));
+                }
+                self.pop_values(&[ValueType::I32, ValueType::I32, ValueType::I32])?;
+            }
+            Instruction::ElementDrop(element_index) => {
+                self.get_element_type(*element_index)?;
+            }
+            Instruction::TableCopy(dest_table_index, source_table_index) => {
+                let dest_element_type = self.get_table_element_type(*dest_table_index)?;
+                let source_element_type = self.get_table_element_type(*source_table_index)?;
+                if dest_element_type != source_element_type {
+                    return Err(FunctionError::TableElementTypeMismatch(
+                        dest_element_type,
+                        source_element_type,
+                    ));
+                }
+                self.pop_values(&[ValueType::I32, ValueType::I32, ValueType::I32])?;
+            }
+            Instruction::TableSize(table_index) => {
+                self.get_table_element_type(*table_index)?;
+                self.push_values(&[ValueType::I32]);
+            }
+            Instruction::TableGet(table_index) => {
+                let element_type = self.get_table_element_type(*table_index)?;
+                self.pop_value(&ValueType::I32)?;
+                self.push_values(&[ValueType::from(element_type)]);
+            }
+            Instruction::TableSet(table_index) => {
+                let element_type = self.get_table_element_type(*table_index)?;
+                self.pop_values(&[ValueType::I32, ValueType::from(element_type)])?;
+            }
+            Instruction::TableGrow(table_index) => {
+                let element_type = self.get_table_element_type(*table_index)?;
+                self.pop_values(&[ValueType::from(element_type), ValueType::I32])?;
+                self.push_values(&[ValueType::I32]);
+            }
+            Instruction::TableFill(table_index) => {
+                let element_type = self.get_table_element_type(*table_index)?;
+                self.pop_values(&[
+                    ValueType::I32,
+                    ValueType::from(element_type),
+                    ValueType::I32,
+                ])?;
+            }
+
+            Instruction::RefNull(ref_type) => self.push_values(&[ValueType::from(*ref_type)]),
+            Instruction::RefIsNull => {
+                if let OperandType::Known(value_type) = self.pop_operand()? {
+                    if !value_type.is_ref() {
+                        return Err(FunctionError::ReferenceOperandExpected(value_type));
+                    }
+                }
+                self.push_values(&[ValueType::I32]);
+            }
+            Instruction::RefFunc(function_index) => {
+                if self.context.get_function_type(*function_index).is_none() {
+                    return Err(FunctionError::FunctionIndexOutOfRange(*function_index));
+                }
+
+                if !self
+                    .context
+                    .declared_function_indices
+                    .contains(function_index)
+                {
+                    return Err(FunctionError::UndeclaredFunctionReference(*function_index));
+                }
+
+                self.push_values(&[ValueType::FuncRef]);
+            }
+
+            Instruction::I32Const(_) => self.push_values(&[ValueType::I32]),
+            Instruction::I64Const(_) => self.push_values(&[ValueType::I64]),
+            Instruction::F32Const(_) => self.push_values(&[ValueType::F32]),
+            Instruction::F64Const(_) => self.push_values(&[ValueType::F64]),
+            Instruction::V128Const(_) => self.push_values(&[ValueType::V128]),
+
+            Instruction::V128Load(m) => self.validate_load(m, 4, ValueType::V128)?,
+            Instruction::V128Load8x8S(m) => self.validate_load(m, 3, ValueType::V128)?,
+            Instruction::V128Load8x8U(m) => self.validate_load(m, 3, ValueType::V128)?,
+            Instruction::V128Load16x4S(m) => self.validate_load(m, 3, ValueType::V128)?,
+            Instruction::V128Load16x4U(m) => self.validate_load(m, 3, ValueType::V128)?,
+            Instruction::V128Load32x2S(m) => self.validate_load(m, 3, ValueType::V128)?,
+            Instruction::V128Load32x2U(m) => self.validate_load(m, 3, ValueType::V128)?,
+            Instruction::V128Load8Splat(m) => self.validate_load(m, 0, ValueType::V128)?,
+            Instruction::V128Load16Splat(m) => self.validate_load(m, 1, ValueType::V128)?,
+            Instruction::V128Load32Splat(m) => self.validate_load(m, 2, ValueType::V128)?,
+            Instruction::V128Load64Splat(m) => self.validate_load(m, 3, ValueType::V128)?,
+            Instruction::V128Store(m) => self.validate_store(m, 4, ValueType::V128)?,
+            Instruction::I8x16ExtractLaneS(lane) => {
+                self.validate_extract_lane(*lane, 16, ValueType::I32)?
+            }
+            Instruction::I8x16ExtractLaneU(lane) => {
+                self.validate_extract_lane(*lane, 16, ValueType::I32)?
+            }
+            Instruction::I8x16ReplaceLane(lane) => {
+                self.validate_replace_lane(*lane, 16, ValueType::I32)?
+            }
+            Instruction::I16x8ExtractLaneS(lane) => {
+                self.validate_extract_lane(*lane, 8, ValueType::I32)?
+            }
+            Instruction::I16x8ExtractLaneU(lane) => {
+                self.validate_extract_lane(*lane, 8, ValueType::I32)?
+            }
+            Instruction::I16x8ReplaceLane(lane) => {
+                self.validate_replace_lane(*lane, 8, ValueType::I32)?
+            }
+            Instruction::I32x4ExtractLane(lane) => {
+                self.validate_extract_lane(*lane, 4, ValueType::I32)?
+            }
+            Instruction::I32x4ReplaceLane(lane) => {
+                self.validate_replace_lane(*lane, 4, ValueType::I32)?
+            }
+            Instruction::I64x2ExtractLane(lane) => {
+                self.validate_extract_lane(*lane, 2, ValueType::I64)?
+            }
+            Instruction::I64x2ReplaceLane(lane) => {
+                self.validate_replace_lane(*lane, 2, ValueType::I64)?
+            }
+            Instruction::F32x4ExtractLane(lane) => {
+                self.validate_extract_lane(*lane, 4, ValueType::F32)?
+            }
+            Instruction::F32x4ReplaceLane(lane) => {
+                self.validate_replace_lane(*lane, 4, ValueType::F32)?
+            }
+            Instruction::F64x2ExtractLane(lane) => {
+                self.validate_extract_lane(*lane, 2, ValueType::F64)?
+            }
+            Instruction::F64x2ReplaceLane(lane) => {
+                self.validate_replace_lane(*lane, 2, ValueType::F64)?
+            }
+            Instruction::V128Load8Lane(m, lane) => self.validate_load_lane(m, 0, *lane)?,
+            Instruction::V128Load16Lane(m, lane) => self.validate_load_lane(m, 1, *lane)?,
+            Instruction::V128Load32Lane(m, lane) => self.validate_load_lane(m, 2, *lane)?,
+            Instruction::V128Load64Lane(m, lane) => self.validate_load_lane(m, 3, *lane)?,
+            Instruction::V128Store8Lane(m, lane) => self.validate_store_lane(m, 0, *lane)?,
+            Instruction::V128Store16Lane(m, lane) => self.validate_store_lane(m, 1, *lane)?,
+            Instruction::V128Store32Lane(m, lane) => self.validate_store_lane(m, 2, *lane)?,
+            Instruction::V128Store64Lane(m, lane) => self.validate_store_lane(m, 3, *lane)?,
+            Instruction::V128Load32Zero(m) => self.validate_load(m, 2, ValueType::V128)?,
+            Instruction::V128Load64Zero(m) => self.validate_load(m, 3, ValueType::V128)?,
+            Instruction::I8x16Shuffle(lanes) => {
+                // 洗牌指令的索引范围是两个向量的通道数之和
+                for lane in lanes {
+                    self.check_lane_index(*lane, 32)?;
+                }
+                self.pop_values(&[ValueType::V128, ValueType::V128])?;
+                self.push_values(&[ValueType::V128]);
+            }
+
+            Instruction::MemoryAtomicNotify(m) => {
+                self.validate_atomic(m, 2, &[ValueType::I32], &[ValueType::I32])?
+            }
+            Instruction::MemoryAtomicWait32(m) => {
+                self.validate_atomic(m, 2, &[ValueType::I32, ValueType::I64], &[ValueType::I32])?
+            }
+            Instruction::MemoryAtomicWait64(m) => {
+                self.validate_atomic(m, 3, &[ValueType::I64, ValueType::I64], &[ValueType::I32])?
+            }
+            Instruction::AtomicFence => {}
+            Instruction::I32AtomicLoad(m) => self.validate_atomic(m, 2, &[], &[ValueType::I32])?,
+            Instruction::I64AtomicLoad(m) => self.validate_atomic(m, 3, &[], &[ValueType::I64])?,
+            Instruction::I32AtomicLoad8U(m) => {
+                self.validate_atomic(m, 0, &[], &[ValueType::I32])?
+            }
+            Instruction::I32AtomicLoad16U(m) => {
+                self.validate_atomic(m, 1, &[], &[ValueType::I32])?
+            }
+            Instruction::I64AtomicLoad8U(m) => {
+                self.validate_atomic(m, 0, &[], &[ValueType::I64])?
+            }
+            Instruction::I64AtomicLoad16U(m) => {
+                self.validate_atomic(m, 1, &[], &[ValueType::I64])?
+            }
+            Instruction::I64AtomicLoad32U(m) => {
+                self.validate_atomic(m, 2, &[], &[ValueType::I64])?
+            }
+            Instruction::I32AtomicStore(m) => self.validate_atomic(m, 2, &[ValueType::I32], &[])?,
+            Instruction::I64AtomicStore(m) => self.validate_atomic(m, 3, &[ValueType::I64], &[])?,
+            Instruction::I32AtomicStore8(m) => {
+                self.validate_atomic(m, 0, &[ValueType::I32], &[])?
+            }
+            Instruction::I32AtomicStore16(m) => {
+                self.validate_atomic(m, 1, &[ValueType::I32], &[])?
+            }
+            Instruction::I64AtomicStore8(m) => {
+                self.validate_atomic(m, 0, &[ValueType::I64], &[])?
+            }
+            Instruction::I64AtomicStore16(m) => {
+                self.validate_atomic(m, 1, &[ValueType::I64], &[])?
+            }
+            Instruction::I64AtomicStore32(m) => {
+                self.validate_atomic(m, 2, &[ValueType::I64], &[])?
+            }
+            Instruction::I32AtomicRmwAdd(m) => {
+                self.validate_atomic(m, 2, &[ValueType::I32], &[ValueType::I32])?
+            }
+            Instruction::I64AtomicRmwAdd(m) => {
+                self.validate_atomic(m, 3, &[ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I32AtomicRmw8AddU(m) => {
+                self.validate_atomic(m, 0, &[ValueType::I32], &[ValueType::I32])?
+            }
+            Instruction::I32AtomicRmw16AddU(m) => {
+                self.validate_atomic(m, 1, &[ValueType::I32], &[ValueType::I32])?
+            }
+            Instruction::I64AtomicRmw8AddU(m) => {
+                self.validate_atomic(m, 0, &[ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I64AtomicRmw16AddU(m) => {
+                self.validate_atomic(m, 1, &[ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I64AtomicRmw32AddU(m) => {
+                self.validate_atomic(m, 2, &[ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I32AtomicRmwSub(m) => {
+                self.validate_atomic(m, 2, &[ValueType::I32], &[ValueType::I32])?
+            }
+            Instruction::I64AtomicRmwSub(m) => {
+                self.validate_atomic(m, 3, &[ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I32AtomicRmw8SubU(m) => {
+                self.validate_atomic(m, 0, &[ValueType::I32], &[ValueType::I32])?
+            }
+            Instruction::I32AtomicRmw16SubU(m) => {
+                self.validate_atomic(m, 1, &[ValueType::I32], &[ValueType::I32])?
+            }
+            Instruction::I64AtomicRmw8SubU(m) => {
+                self.validate_atomic(m, 0, &[ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I64AtomicRmw16SubU(m) => {
+                self.validate_atomic(m, 1, &[ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I64AtomicRmw32SubU(m) => {
+                self.validate_atomic(m, 2, &[ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I32AtomicRmwAnd(m) => {
+                self.validate_atomic(m, 2, &[ValueType::I32], &[ValueType::I32])?
+            }
+            Instruction::I64AtomicRmwAnd(m) => {
+                self.validate_atomic(m, 3, &[ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I32AtomicRmw8AndU(m) => {
+                self.validate_atomic(m, 0, &[ValueType::I32], &[ValueType::I32])?
+            }
+            Instruction::I32AtomicRmw16AndU(m) => {
+                self.validate_atomic(m, 1, &[ValueType::I32], &[ValueType::I32])?
+            }
+            Instruction::I64AtomicRmw8AndU(m) => {
+                self.validate_atomic(m, 0, &[ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I64AtomicRmw16AndU(m) => {
+                self.validate_atomic(m, 1, &[ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I64AtomicRmw32AndU(m) => {
+                self.validate_atomic(m, 2, &[ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I32AtomicRmwOr(m) => {
+                self.validate_atomic(m, 2, &[ValueType::I32], &[ValueType::I32])?
+            }
+            Instruction::I64AtomicRmwOr(m) => {
+                self.validate_atomic(m, 3, &[ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I32AtomicRmw8OrU(m) => {
+                self.validate_atomic(m, 0, &[ValueType::I32], &[ValueType::I32])?
+            }
+            Instruction::I32AtomicRmw16OrU(m) => {
+                self.validate_atomic(m, 1, &[ValueType::I32], &[ValueType::I32])?
+            }
+            Instruction::I64AtomicRmw8OrU(m) => {
+                self.validate_atomic(m, 0, &[ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I64AtomicRmw16OrU(m) => {
+                self.validate_atomic(m, 1, &[ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I64AtomicRmw32OrU(m) => {
+                self.validate_atomic(m, 2, &[ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I32AtomicRmwXor(m) => {
+                self.validate_atomic(m, 2, &[ValueType::I32], &[ValueType::I32])?
+            }
+            Instruction::I64AtomicRmwXor(m) => {
+                self.validate_atomic(m, 3, &[ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I32AtomicRmw8XorU(m) => {
+                self.validate_atomic(m, 0, &[ValueType::I32], &[ValueType::I32])?
+            }
+            Instruction::I32AtomicRmw16XorU(m) => {
+                self.validate_atomic(m, 1, &[ValueType::I32], &[ValueType::I32])?
+            }
+            Instruction::I64AtomicRmw8XorU(m) => {
+                self.validate_atomic(m, 0, &[ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I64AtomicRmw16XorU(m) => {
+                self.validate_atomic(m, 1, &[ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I64AtomicRmw32XorU(m) => {
+                self.validate_atomic(m, 2, &[ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I32AtomicRmwXchg(m) => {
+                self.validate_atomic(m, 2, &[ValueType::I32], &[ValueType::I32])?
+            }
+            Instruction::I64AtomicRmwXchg(m) => {
+                self.validate_atomic(m, 3, &[ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I32AtomicRmw8XchgU(m) => {
+                self.validate_atomic(m, 0, &[ValueType::I32], &[ValueType::I32])?
+            }
+            Instruction::I32AtomicRmw16XchgU(m) => {
+                self.validate_atomic(m, 1, &[ValueType::I32], &[ValueType::I32])?
+            }
+            Instruction::I64AtomicRmw8XchgU(m) => {
+                self.validate_atomic(m, 0, &[ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I64AtomicRmw16XchgU(m) => {
+                self.validate_atomic(m, 1, &[ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I64AtomicRmw32XchgU(m) => {
+                self.validate_atomic(m, 2, &[ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I32AtomicRmwCmpxchg(m) => {
+                self.validate_atomic(m, 2, &[ValueType::I32, ValueType::I32], &[ValueType::I32])?
+            }
+            Instruction::I64AtomicRmwCmpxchg(m) => {
+                self.validate_atomic(m, 3, &[ValueType::I64, ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I32AtomicRmw8CmpxchgU(m) => {
+                self.validate_atomic(m, 0, &[ValueType::I32, ValueType::I32], &[ValueType::I32])?
+            }
+            Instruction::I32AtomicRmw16CmpxchgU(m) => {
+                self.validate_atomic(m, 1, &[ValueType::I32, ValueType::I32], &[ValueType::I32])?
+            }
+            Instruction::I64AtomicRmw8CmpxchgU(m) => {
+                self.validate_atomic(m, 0, &[ValueType::I64, ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I64AtomicRmw16CmpxchgU(m) => {
+                self.validate_atomic(m, 1, &[ValueType::I64, ValueType::I64], &[ValueType::I64])?
+            }
+            Instruction::I64AtomicRmw32CmpxchgU(m) => {
+                self.validate_atomic(m, 2, &[ValueType::I64, ValueType::I64], &[ValueType::I64])?
+            }
+
+            _ => {
+                // 剩下的都是数值类指令
+                let (params, results) = get_numeric_instruction_type(instruction).unwrap();
+                self.pop_values(params)?;
+                self.push_values(results);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use anvm_ast::{
+        ast::{
+            CodeItem, DataItem, DataMode, ElementItem, ElementItems, ElementMode, ExportDescriptor,
+            ExportItem, FunctionType, GlobalItem, GlobalType, ImportDescriptor, ImportItem, Limit,
+            LocalGroup, MemoryType, Module, TableType, TagType, TypeItem,
+        },
+        instruction::{BlockType, CatchClause, Instruction, MemoryArgument},
+        types::{RefType, ValueType},
+    };
+    use anvm_binary_parser::parser;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::error::{
+        ConstantExpressionError, ConstantExpressionLocation, FunctionError, ModuleError,
+        ValidationError,
+    };
+
+    use super::{validate, MAX_FUNCTION_LOCALS};
+
+    // 辅助方法
+    fn get_test_binary_resource(filename: &str) -> Vec<u8> {
+        let mut path_buf = env::current_dir().unwrap();
+
+        // 使用 `cargo test` 测试时，
+        // `env::current_dir()` 函数获得的当前目录为
+        // `./xiaoxuan-vm/crates/validator`；
+        //
+        // 但如果使用 vscode 的源码编辑框里面的 `debug` 按钮开始调试，
+        // `env::current_dir()` 函数获得的当前目录为
+        // `./xiaoxuan-vm`。
+        //
+        // 下面语句用于处理这种情况。
+
+        if !path_buf.ends_with("validator") {
+            path_buf.push("crates");
+            path_buf.push("validator");
+        }
+        let fullname_buf = path_buf.join("resources").join(filename);
+        let fullname = fullname_buf.to_str().unwrap();
+        fs::read(fullname).unwrap_or_else(|_| {
+            panic!("failed to read the specified binary file: {}", fullname)
+        })
+    }
+
+    /// 创建只有一个函数（以及一个内存块和一个不可变全局变量）的模块
+    fn create_test_module(
+        params: Vec<ValueType>,
+        results: Vec<ValueType>,
+        local_groups: Vec<LocalGroup>,
+        instruction_items: Vec<Instruction>,
+    ) -> Module {
+        Module {
+            custom_items: vec![],
+            type_items: vec![TypeItem::FunctionType(FunctionType { params, results })],
+            import_items: vec![],
+            internal_function_to_type_index_list: vec![0],
+            tables: vec![],
+            memory_blocks: vec![MemoryType {
+                limit: Limit::AtLeast(1),
+                shared: false,
+                memory64: false,
+            }],
+            tag_items: vec![],
+            global_items: vec![GlobalItem {
+                global_type: GlobalType {
+                    value_type: ValueType::I32,
+                    mutable: false,
+                },
+                initialize_instruction_items: vec![Instruction::I32Const(0), Instruction::End],
+            }],
+            export_items: vec![],
+            start_function_index: None,
+            element_items: vec![],
+            code_items: vec![CodeItem {
+                local_groups,
+                instruction_items,
+            }],
+            data_items: vec![],
+            data_count: None,
+        }
+    }
+
+    fn validate_function_body(
+        results: Vec<ValueType>,
+        instruction_items: Vec<Instruction>,
+    ) -> Result<(), ValidationError> {
+        validate(&create_test_module(
+            vec![],
+            results,
+            vec![],
+            instruction_items,
+        ))
+    }
+
+    #[test]
+    fn test_validate_valid_module() {
+        let bytes = get_test_binary_resource("test-validate.wasm");
+        let module = parser::parse(&bytes).unwrap();
+        assert_eq!(validate(&module), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_operand_types() {
+        assert_eq!(
+            validate_function_body(
+                vec![ValueType::I32],
+                vec![
+                    Instruction::I32Const(1),
+                    Instruction::I64Const(2),
+                    Instruction::I32Add,
+                    Instruction::End
+                ]
+            ),
+            Err(ValidationError::FunctionError(
+                0,
+                2,
+                FunctionError::OperandTypeMismatch(ValueType::I32, ValueType::I64)
+            ))
+        );
+
+        assert_eq!(
+            validate_function_body(
+                vec![ValueType::I32],
+                vec![
+                    Instruction::I32Const(1),
+                    Instruction::I32Add,
+                    Instruction::End
+                ]
+            ),
+            Err(ValidationError::FunctionError(
+                0,
+                1,
+                FunctionError::OperandStackUnderflow
+            ))
+        );
+
+        assert_eq!(
+            validate_function_body(
+                vec![ValueType::I32],
+                vec![
+                    Instruction::I32Const(1),
+                    Instruction::I32Const(2),
+                    Instruction::End
+                ]
+            ),
+            Err(ValidationError::FunctionError(
+                0,
+                2,
+                FunctionError::UnexpectedOperands(1)
+            ))
+        );
+
+        // 不可到达的代码里，操作数栈是多态的
+        assert_eq!(
+            validate_function_body(
+                vec![ValueType::I64],
+                vec![
+                    Instruction::Unreachable,
+                    Instruction::I32Add,
+                    Instruction::Drop,
+                    Instruction::End
+                ]
+            ),
+            Ok(())
+        );
+
+        assert_eq!(
+            validate_function_body(
+                vec![],
+                vec![
+                    Instruction::I64Const(1),
+                    Instruction::I32Const(2),
+                    Instruction::I32Const(1),
+                    Instruction::Select,
+                    Instruction::Drop,
+                    Instruction::End
+                ]
+            ),
+            Err(ValidationError::FunctionError(
+                0,
+                3,
+                FunctionError::OperandTypeMismatch(ValueType::I32, ValueType::I64)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_validate_control_frames() {
+        assert_eq!(
+            validate_function_body(
+                vec![],
+                vec![
+                    Instruction::Block(BlockType::ResultEmpty, 0),
+                    Instruction::Br(2),
+                    Instruction::End,
+                    Instruction::End
+                ]
+            ),
+            Err(ValidationError::FunctionError(
+                0,
+                1,
+                FunctionError::BlockRelativeDepthOutOfRange(2)
+            ))
+        );
+
+        assert_eq!(
+            validate_function_body(
+                vec![ValueType::I32],
+                vec![
+                    Instruction::I32Const(1),
+                    Instruction::If(BlockType::ResultI32, 0),
+                    Instruction::I32Const(2),
+                    Instruction::End,
+                    Instruction::End
+                ]
+            ),
+            Err(ValidationError::FunctionError(
+                0,
+                3,
+                FunctionError::IfWithoutElseTypeMismatch
+            ))
+        );
+
+        assert_eq!(
+            validate_function_body(
+                vec![],
+                vec![
+                    Instruction::Block(BlockType::ResultEmpty, 0),
+                    Instruction::Else,
+                    Instruction::End,
+                    Instruction::End
+                ]
+            ),
+            Err(ValidationError::FunctionError(
+                0,
+                1,
+                FunctionError::ElseWithoutIf
+            ))
+        );
+
+        assert_eq!(
+            validate_function_body(
+                vec![ValueType::I32],
+                vec![
+                    Instruction::Block(BlockType::ResultEmpty, 0),
+                    Instruction::I32Const(1),
+                    Instruction::I32Const(0),
+                    Instruction::BrTable(vec![0], 1),
+                    Instruction::End,
+                    Instruction::I32Const(2),
+                    Instruction::End
+                ]
+            ),
+            Err(ValidationError::FunctionError(
+                0,
+                3,
+                FunctionError::BranchTableArityMismatch
+            ))
+        );
+
+        assert_eq!(
+            validate_function_body(
+                vec![],
+                vec![
+                    Instruction::Block(BlockType::ResultEmpty, 0),
+                    Instruction::End
+                ]
+            ),
+            Err(ValidationError::FunctionError(
+                0,
+                2,
+                FunctionError::MissingEnd
+            ))
+        );
+    }
+
+    #[test]
+    fn test_validate_indices() {
+        assert_eq!(
+            validate(&create_test_module(
+                vec![ValueType::I32],
+                vec![],
+                vec![LocalGroup {
+                    variable_count: 2,
+                    value_type: ValueType::I64
+                }],
+                vec![
+                    Instruction::LocalGet(3),
+                    Instruction::Drop,
+                    Instruction::End
+                ]
+            )),
+            Err(ValidationError::FunctionError(
+                0,
+                0,
+                FunctionError::LocalIndexOutOfRange(3)
+            ))
+        );
+
+        assert_eq!(
+            validate_function_body(
+                vec![],
+                vec![
+                    Instruction::I32Const(1),
+                    Instruction::GlobalSet(0),
+                    Instruction::End
+                ]
+            ),
+            Err(ValidationError::FunctionError(
+                0,
+                1,
+                FunctionError::ImmutableGlobal(0)
+            ))
+        );
+
+        assert_eq!(
+            validate_function_body(vec![], vec![Instruction::Call(1), Instruction::End]),
+            Err(ValidationError::FunctionError(
+                0,
+                0,
+                FunctionError::FunctionIndexOutOfRange(1)
+            ))
+        );
+
+        assert_eq!(
+            validate_function_body(
+                vec![ValueType::I32],
+                vec![
+                    Instruction::I32Const(0),
+                    Instruction::I32Load(MemoryArgument {
+                        align: 3,
+                        offset: 0,
+                        memory_block_index: 0
+                    }),
+                    Instruction::End
+                ]
+            ),
+            Err(ValidationError::FunctionError(
+                0,
+                1,
+                FunctionError::InvalidAlignment(3, 2)
+            ))
+        );
+
+        // 多个内存块
+        let mut m0 = create_test_module(
+            vec![],
+            vec![ValueType::I32],
+            vec![],
+            vec![
+                Instruction::I32Const(0),
+                Instruction::I32Load(MemoryArgument {
+                    align: 2,
+                    offset: 0,
+                    memory_block_index: 1,
+                }),
+                Instruction::MemoryGrow(1),
+                Instruction::End,
+            ],
+        );
+        assert_eq!(
+            validate(&m0),
+            Err(ValidationError::FunctionError(
+                0,
+                1,
+                FunctionError::MemoryBlockIndexOutOfRange(1)
+            ))
+        );
+
+        m0.memory_blocks.push(MemoryType {
+            limit: Limit::AtLeast(1),
+            shared: false,
+            memory64: false,
+        });
+        assert_eq!(validate(&m0), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_local_count() {
+        let create_local_groups = |variable_counts: &[u32]| {
+            variable_counts
+                .iter()
+                .map(|variable_count| LocalGroup {
+                    variable_count: *variable_count,
+                    value_type: ValueType::I32,
+                })
+                .collect::<Vec<LocalGroup>>()
+        };
+
+        assert_eq!(
+            validate(&create_test_module(
+                vec![ValueType::I32],
+                vec![],
+                create_local_groups(&[MAX_FUNCTION_LOCALS as u32 - 1, 1]),
+                vec![Instruction::End]
+            )),
+            Ok(())
+        );
+
+        assert_eq!(
+            validate(&create_test_module(
+                vec![],
+                vec![],
+                create_local_groups(&[MAX_FUNCTION_LOCALS as u32, 1]),
+                vec![Instruction::End]
+            )),
+            Err(ValidationError::FunctionError(
+                0,
+                0,
+                FunctionError::TooManyLocals(MAX_FUNCTION_LOCALS)
+            ))
+        );
+
+        // 各组的数量之和超出 u32 的范围，不会溢出，也不会在验证时分配空间
+        assert_eq!(
+            validate(&create_test_module(
+                vec![],
+                vec![],
+                create_local_groups(&[u32::MAX, u32::MAX, 2]),
+                vec![Instruction::End]
+            )),
+            Err(ValidationError::FunctionError(
+                0,
+                0,
+                FunctionError::TooManyLocals(MAX_FUNCTION_LOCALS)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_validate_module_items() {
+        let mut m0 = create_test_module(vec![], vec![], vec![], vec![Instruction::End]);
+        m0.global_items[0].initialize_instruction_items = vec![
+            Instruction::I32Const(1),
+            Instruction::I32Const(2),
+            Instruction::I32And,
+            Instruction::End,
+        ];
+        assert_eq!(
+            validate(&m0),
+            Err(ValidationError::ModuleError(
+                ModuleError::InvalidConstantExpression(
+                    ConstantExpressionLocation::GlobalItem(0),
+                    ConstantExpressionError::NonConstantInstruction(Instruction::I32And)
+                )
+            ))
+        );
+
+        let mut m1 = create_test_module(vec![], vec![], vec![], vec![Instruction::End]);
+        m1.global_items[0].initialize_instruction_items =
+            vec![Instruction::I64Const(1), Instruction::End];
+        assert_eq!(
+            validate(&m1),
+            Err(ValidationError::ModuleError(
+                ModuleError::InvalidConstantExpression(
+                    ConstantExpressionLocation::GlobalItem(0),
+                    ConstantExpressionError::ResultTypeMismatch(
+                        ValueType::I32,
+                        vec![ValueType::I64]
+                    )
+                )
+            ))
+        );
+
+        let mut m2 = create_test_module(vec![], vec![], vec![], vec![Instruction::End]);
+        m2.export_items = vec![
+            ExportItem {
+                name: "f".to_string(),
+                export_descriptor: ExportDescriptor::FunctionIndex(0),
+            },
+            ExportItem {
+                name: "f".to_string(),
+                export_descriptor: ExportDescriptor::MemoryBlockIndex(0),
+            },
+        ];
+        assert_eq!(
+            validate(&m2),
+            Err(ValidationError::ModuleError(
+                ModuleError::DuplicateExportName("f".to_string())
+            ))
+        );
+
+        let mut m3 = create_test_module(
+            vec![],
+            vec![ValueType::I32],
+            vec![],
+            vec![Instruction::I32Const(0), Instruction::End],
+        );
+        m3.start_function_index = Some(0);
+        assert_eq!(
+            validate(&m3),
+            Err(ValidationError::ModuleError(
+                ModuleError::InvalidStartFunctionType(0)
+            ))
+        );
+
+        let mut m4 = create_test_module(vec![], vec![], vec![], vec![Instruction::End]);
+        m4.memory_blocks = vec![MemoryType {
+            limit: Limit::Range(2, 1),
+            shared: false,
+            memory64: false,
+        }];
+        assert_eq!(
+            validate(&m4),
+            Err(ValidationError::ModuleError(ModuleError::InvalidLimit(
+                2, 1
+            )))
+        );
+    }
+
+    #[test]
+    fn test_validate_extended_constant_expression() {
+        // 导入一个不可变的全局变量 `__memory_base`，然后在全局变量、数据段和元素段
+        // 的常量表达式里使用算术指令
+        let mut m0 = create_test_module(vec![], vec![], vec![], vec![Instruction::End]);
+        m0.import_items = vec![ImportItem {
+            module_name: "env".to_string(),
+            item_name: "__memory_base".to_string(),
+            import_descriptor: ImportDescriptor::GlobalType(GlobalType {
+                value_type: ValueType::I32,
+                mutable: false,
+            }),
+        }];
+        m0.global_items[0].initialize_instruction_items = vec![
+            Instruction::GlobalGet(0),
+            Instruction::I32Const(4),
+            Instruction::I32Const(2),
+            Instruction::I32Mul,
+            Instruction::I32Sub,
+            Instruction::End,
+        ];
+        m0.data_items = vec![DataItem {
+            mode: DataMode::Active {
+                memory_block_index: 0,
+                offset_instruction_items: vec![
+                    Instruction::GlobalGet(0),
+                    Instruction::I32Const(16),
+                    Instruction::I32Add,
+                    Instruction::End,
+                ],
+            },
+            data: vec![1, 2, 3],
+        }];
+        m0.tables = vec![TableType {
+            element_type: RefType::FuncRef,
+            limit: Limit::AtLeast(1),
+        }];
+        m0.element_items = vec![ElementItem {
+            mode: ElementMode::Active {
+                table_index: 0,
+                offset_instruction_items: vec![
+                    Instruction::GlobalGet(0),
+                    Instruction::I32Const(1),
+                    Instruction::I32Add,
+                    Instruction::End,
+                ],
+            },
+            element_type: RefType::FuncRef,
+            items: ElementItems::FunctionIndices(vec![0]),
+        }];
+        assert_eq!(validate(&m0), Ok(()));
+
+        // 操作数的类型不匹配
+        let mut m1 = m0.clone();
+        m1.data_items[0].mode = DataMode::Active {
+            memory_block_index: 0,
+            offset_instruction_items: vec![
+                Instruction::GlobalGet(0),
+                Instruction::I64Const(16),
+                Instruction::I32Add,
+                Instruction::End,
+            ],
+        };
+        assert_eq!(
+            validate(&m1),
+            Err(ValidationError::ModuleError(
+                ModuleError::InvalidConstantExpression(
+                    ConstantExpressionLocation::DataItem(0),
+                    ConstantExpressionError::OperandTypeMismatch(
+                        Instruction::I32Add,
+                        ValueType::I32
+                    )
+                )
+            ))
+        );
+
+        // 缺少操作数
+        let mut m2 = create_test_module(vec![], vec![], vec![], vec![Instruction::End]);
+        m2.global_items[0].global_type.value_type = ValueType::I64;
+        m2.global_items[0].initialize_instruction_items = vec![
+            Instruction::I64Const(1),
+            Instruction::I64Mul,
+            Instruction::End,
+        ];
+        assert_eq!(
+            validate(&m2),
+            Err(ValidationError::ModuleError(
+                ModuleError::InvalidConstantExpression(
+                    ConstantExpressionLocation::GlobalItem(0),
+                    ConstantExpressionError::OperandTypeMismatch(
+                        Instruction::I64Mul,
+                        ValueType::I64
+                    )
+                )
+            ))
+        );
+
+        // 表达式的结果多于一个
+        m2.global_items[0].initialize_instruction_items = vec![
+            Instruction::I64Const(1),
+            Instruction::I64Const(2),
+            Instruction::I64Const(3),
+            Instruction::I64Add,
+            Instruction::End,
+        ];
+        assert_eq!(
+            validate(&m2),
+            Err(ValidationError::ModuleError(
+                ModuleError::InvalidConstantExpression(
+                    ConstantExpressionLocation::GlobalItem(0),
+                    ConstantExpressionError::ResultTypeMismatch(
+                        ValueType::I64,
+                        vec![ValueType::I64, ValueType::I64]
+                    )
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn test_validate_reference_types() {
+        assert_eq!(
+            validate_function_body(
+                vec![ValueType::FuncRef],
+                vec![
+                    Instruction::RefNull(RefType::FuncRef),
+                    Instruction::RefNull(RefType::FuncRef),
+                    Instruction::I32Const(1),
+                    Instruction::SelectTyped(vec![ValueType::FuncRef]),
+                    Instruction::End
+                ]
+            ),
+            Ok(())
+        );
+
+        assert_eq!(
+            validate_function_body(
+                vec![ValueType::ExternRef],
+                vec![
+                    Instruction::RefNull(RefType::ExternRef),
+                    Instruction::RefNull(RefType::ExternRef),
+                    Instruction::I32Const(1),
+                    Instruction::Select,
+                    Instruction::End
+                ]
+            ),
+            Err(ValidationError::FunctionError(
+                0,
+                3,
+                FunctionError::UntypedSelectOnReference(ValueType::ExternRef)
+            ))
+        );
+
+        assert_eq!(
+            validate_function_body(
+                vec![ValueType::I32],
+                vec![
+                    Instruction::I32Const(1),
+                    Instruction::RefIsNull,
+                    Instruction::End
+                ]
+            ),
+            Err(ValidationError::FunctionError(
+                0,
+                1,
+                FunctionError::ReferenceOperandExpected(ValueType::I32)
+            ))
+        );
+
+        // ref.func 引用的函数必须在模块里（除了函数体之外）声明过
+        let mut m0 = create_test_module(
+            vec![],
+            vec![ValueType::FuncRef],
+            vec![],
+            vec![Instruction::RefFunc(0), Instruction::End],
+        );
+        assert_eq!(
+            validate(&m0),
+            Err(ValidationError::FunctionError(
+                0,
+                0,
+                FunctionError::UndeclaredFunctionReference(0)
+            ))
+        );
+
+        m0.element_items = vec![ElementItem {
+            mode: ElementMode::Declarative,
+            element_type: RefType::FuncRef,
+            items: ElementItems::FunctionIndices(vec![0]),
+        }];
+        assert_eq!(validate(&m0), Ok(()));
+
+        // 元素段的类型必须跟表的元素类型一致
+        let mut m1 = create_test_module(vec![], vec![], vec![], vec![Instruction::End]);
+        m1.tables = vec![TableType {
+            element_type: RefType::ExternRef,
+            limit: Limit::AtLeast(1),
+        }];
+        m1.element_items = vec![ElementItem {
+            mode: ElementMode::Active {
+                table_index: 0,
+                offset_instruction_items: vec![Instruction::I32Const(0), Instruction::End],
+            },
+            element_type: RefType::FuncRef,
+            items: ElementItems::FunctionIndices(vec![0]),
+        }];
+        assert_eq!(
+            validate(&m1),
+            Err(ValidationError::ModuleError(
+                ModuleError::ElementTypeMismatch(0, RefType::ExternRef, RefType::FuncRef)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_validate_simd() {
+        assert_eq!(
+            validate_function_body(
+                vec![ValueType::I32],
+                vec![
+                    Instruction::I32Const(0),
+                    Instruction::V128Load(MemoryArgument {
+                        align: 4,
+                        offset: 0,
+                        memory_block_index: 0
+                    }),
+                    Instruction::V128Const(1),
+                    Instruction::I32x4Add,
+                    Instruction::I32x4ExtractLane(3),
+                    Instruction::End
+                ]
+            ),
+            Ok(())
+        );
+
+        // i32x4 只有 4 个通道
+        assert_eq!(
+            validate_function_body(
+                vec![ValueType::I32],
+                vec![
+                    Instruction::V128Const(0),
+                    Instruction::I32x4ExtractLane(4),
+                    Instruction::End
+                ]
+            ),
+            Err(ValidationError::FunctionError(
+                0,
+                1,
+                FunctionError::InvalidLaneIndex(4, 4)
+            ))
+        );
+
+        // v128.load16_lane 的通道数由通道的宽度决定
+        assert_eq!(
+            validate_function_body(
+                vec![ValueType::V128],
+                vec![
+                    Instruction::I32Const(0),
+                    Instruction::V128Const(0),
+                    Instruction::V128Load16Lane(
+                        MemoryArgument {
+                            align: 1,
+                            offset: 0,
+                            memory_block_index: 0
+                        },
+                        8
+                    ),
+                    Instruction::End
+                ]
+            ),
+            Err(ValidationError::FunctionError(
+                0,
+                2,
+                FunctionError::InvalidLaneIndex(8, 8)
+            ))
+        );
+
+        // 对齐值不能大于自然对齐值
+        assert_eq!(
+            validate_function_body(
+                vec![ValueType::V128],
+                vec![
+                    Instruction::I32Const(0),
+                    Instruction::V128Load64Splat(MemoryArgument {
+                        align: 4,
+                        offset: 0,
+                        memory_block_index: 0
+                    }),
+                    Instruction::End
+                ]
+            ),
+            Err(ValidationError::FunctionError(
+                0,
+                1,
+                FunctionError::InvalidAlignment(4, 3)
+            ))
+        );
+
+        // 洗牌的通道索引必须小于 32
+        let mut lanes = [0u8; 16];
+        lanes[15] = 32;
+        assert_eq!(
+            validate_function_body(
+                vec![ValueType::V128],
+                vec![
+                    Instruction::V128Const(0),
+                    Instruction::V128Const(0),
+                    Instruction::I8x16Shuffle(lanes),
+                    Instruction::End
+                ]
+            ),
+            Err(ValidationError::FunctionError(
+                0,
+                2,
+                FunctionError::InvalidLaneIndex(32, 32)
+            ))
+        );
+
+        // 位移指令的位移数是 i32
+        assert_eq!(
+            validate_function_body(
+                vec![ValueType::V128],
+                vec![
+                    Instruction::V128Const(0),
+                    Instruction::I64Const(1),
+                    Instruction::I64x2Shl,
+                    Instruction::End
+                ]
+            ),
+            Err(ValidationError::FunctionError(
+                0,
+                2,
+                FunctionError::OperandTypeMismatch(ValueType::I32, ValueType::I64)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_validate_atomic() {
+        assert_eq!(
+            validate_function_body(
+                vec![ValueType::I64],
+                vec![
+                    Instruction::I32Const(0),
+                    Instruction::I64Const(1),
+                    Instruction::I64Const(2),
+                    Instruction::I64AtomicRmw16CmpxchgU(MemoryArgument {
+                        align: 1,
+                        offset: 0,
+                        memory_block_index: 0
+                    }),
+                    Instruction::AtomicFence,
+                    Instruction::End
+                ]
+            ),
+            Ok(())
+        );
+
+        assert_eq!(
+            validate_function_body(
+                vec![ValueType::I32],
+                vec![
+                    Instruction::I32Const(0),
+                    Instruction::I64Const(0),
+                    Instruction::I64Const(-1),
+                    Instruction::MemoryAtomicWait64(MemoryArgument {
+                        align: 3,
+                        offset: 0,
+                        memory_block_index: 0
+                    }),
+                    Instruction::End
+                ]
+            ),
+            Ok(())
+        );
+
+        // 原子指令的对齐值必须等于自然对齐值
+        assert_eq!(
+            validate_function_body(
+                vec![ValueType::I32],
+                vec![
+                    Instruction::I32Const(0),
+                    Instruction::I32AtomicLoad(MemoryArgument {
+                        align: 1,
+                        offset: 0,
+                        memory_block_index: 0
+                    }),
+                    Instruction::End
+                ]
+            ),
+            Err(ValidationError::FunctionError(
+                0,
+                1,
+                FunctionError::InvalidAtomicAlignment(1, 2)
+            ))
+        );
+
+        // 共享内存块必须指定最大页面数
+        let mut m0 = create_test_module(vec![], vec![], vec![], vec![Instruction::End]);
+        m0.memory_blocks = vec![MemoryType {
+            limit: Limit::AtLeast(1),
+            shared: true,
+            memory64: false,
+        }];
+        assert_eq!(
+            validate(&m0),
+            Err(ValidationError::ModuleError(
+                ModuleError::SharedMemoryWithoutMax
+            ))
+        );
+    }
+
+    #[test]
+    fn test_validate_memory64() {
+        let memory64 = MemoryType {
+            limit: Limit::AtLeast(1),
+            shared: false,
+            memory64: true,
+        };
+
+        // 64 位内存块的地址、页面数以及数据段的偏移值都是 i64
+        let mut m0 = create_test_module(
+            vec![],
+            vec![ValueType::I64],
+            vec![],
+            vec![
+                Instruction::I64Const(0),
+                Instruction::I64Load(MemoryArgument {
+                    align: 3,
+                    offset: 0x1_0000_0000,
+                    memory_block_index: 0,
+                }),
+                Instruction::I64Const(1),
+                Instruction::MemoryGrow(0),
+                Instruction::I64Add,
+                Instruction::End,
+            ],
+        );
+        m0.memory_blocks = vec![memory64.clone()];
+        m0.data_items = vec![DataItem {
+            mode: DataMode::Active {
+                memory_block_index: 0,
+                offset_instruction_items: vec![Instruction::I64Const(8), Instruction::End],
+            },
+            data: vec![1, 2, 3],
+        }];
+        assert_eq!(validate(&m0), Ok(()));
+
+        let mut m1 = create_test_module(
+            vec![],
+            vec![ValueType::I64],
+            vec![],
+            vec![
+                Instruction::I32Const(0),
+                Instruction::I64Load(MemoryArgument {
+                    align: 3,
+                    offset: 0,
+                    memory_block_index: 0,
+                }),
+                Instruction::End,
+            ],
+        );
+        m1.memory_blocks = vec![memory64.clone()];
+        assert_eq!(
+            validate(&m1),
+            Err(ValidationError::FunctionError(
+                0,
+                1,
+                FunctionError::OperandTypeMismatch(ValueType::I64, ValueType::I32)
+            ))
+        );
+
+        // 32 位内存块的偏移值不能超出 u32 的范围
+        assert_eq!(
+            validate_function_body(
+                vec![ValueType::I32],
+                vec![
+                    Instruction::I32Const(0),
+                    Instruction::I32Load(MemoryArgument {
+                        align: 2,
+                        offset: 0x1_0000_0000,
+                        memory_block_index: 0
+                    }),
+                    Instruction::End
+                ]
+            ),
+            Err(ValidationError::FunctionError(
+                0,
+                1,
+                FunctionError::MemoryOffsetOutOfRange(0x1_0000_0000)
+            ))
+        );
+
+        // 64 位内存块的页面数可以超过 65536
+        let mut m2 = create_test_module(vec![], vec![], vec![], vec![Instruction::End]);
+        m2.memory_blocks = vec![MemoryType {
+            limit: Limit::Range(1, 0x1_0000_0000),
+            ..memory64
+        }];
+        assert_eq!(validate(&m2), Ok(()));
+
+        m2.memory_blocks[0].memory64 = false;
+        assert_eq!(
+            validate(&m2),
+            Err(ValidationError::ModuleError(ModuleError::MemoryPageExceed(
+                0x1_0000_0000,
+                65536
+            )))
+        );
+    }
+
+    #[test]
+    fn test_validate_tail_call() {
+        assert_eq!(
+            validate(&create_test_module(
+                vec![ValueType::I32],
+                vec![ValueType::I32],
+                vec![],
+                vec![
+                    Instruction::LocalGet(0),
+                    Instruction::ReturnCall(0),
+                    // 尾调用之后的操作数栈是多态的
+                    Instruction::Drop,
+                    Instruction::End
+                ]
+            )),
+            Ok(())
+        );
+
+        // 目标函数的返回值类型必须跟当前函数的一致
+        let mut m0 = create_test_module(
+            vec![],
+            vec![ValueType::I32],
+            vec![],
+            vec![Instruction::ReturnCall(1), Instruction::End],
+        );
+        m0.type_items.push(TypeItem::FunctionType(FunctionType {
+            params: vec![],
+            results: vec![ValueType::I64],
+        }));
+        m0.internal_function_to_type_index_list.push(1);
+        m0.code_items.push(CodeItem {
+            local_groups: vec![],
+            instruction_items: vec![Instruction::I64Const(0), Instruction::End],
+        });
+        assert_eq!(
+            validate(&m0),
+            Err(ValidationError::FunctionError(
+                0,
+                0,
+                FunctionError::TailCallResultTypeMismatch(
+                    vec![ValueType::I32],
+                    vec![ValueType::I64]
+                )
+            ))
+        );
+
+        // 缺少目标函数的实参
+        assert_eq!(
+            validate(&create_test_module(
+                vec![ValueType::I32],
+                vec![],
+                vec![],
+                vec![Instruction::ReturnCall(0), Instruction::End]
+            )),
+            Err(ValidationError::FunctionError(
+                0,
+                0,
+                FunctionError::OperandStackUnderflow
+            ))
+        );
+    }
+
+    #[test]
+    fn test_validate_exception_handling() {
+        // 标签 0 的类型为 `(param i32)`
+        let create_module = |instruction_items: Vec<Instruction>| {
+            let mut module =
+                create_test_module(vec![], vec![ValueType::I32], vec![], instruction_items);
+            module.type_items.push(TypeItem::FunctionType(FunctionType {
+                params: vec![ValueType::I32],
+                results: vec![],
+            }));
+            module.tag_items.push(TagType { type_index: 1 });
+            module
+        };
+
+        assert_eq!(
+            validate(&create_module(vec![
+                Instruction::Block(BlockType::ResultI32, 0),
+                Instruction::TryTable(BlockType::ResultEmpty, vec![CatchClause::Catch(0, 0)], 1),
+                Instruction::I32Const(1),
+                Instruction::Throw(0),
+                Instruction::End,
+                Instruction::I32Const(0),
+                Instruction::End,
+                Instruction::End
+            ])),
+            Ok(())
+        );
+
+        // catch_ref 子句携带的数值还包括 exnref
+        assert_eq!(
+            validate(&create_module(vec![
+                Instruction::Block(BlockType::ResultI32, 0),
+                Instruction::TryTable(BlockType::ResultEmpty, vec![CatchClause::CatchRef(0, 0)], 1),
+                Instruction::End,
+                Instruction::I32Const(0),
+                Instruction::End,
+                Instruction::End
+            ])),
+            Err(ValidationError::FunctionError(
+                0,
+                1,
+                FunctionError::CatchLabelTypeMismatch(
+                    vec![ValueType::I32],
+                    vec![ValueType::I32, ValueType::ExnRef]
+                )
+            ))
+        );
+
+        assert_eq!(
+            validate(&create_module(vec![
+                Instruction::Throw(1),
+                Instruction::End
+            ])),
+            Err(ValidationError::FunctionError(
+                0,
+                0,
+                FunctionError::TagIndexOutOfRange(1)
+            ))
+        );
+
+        assert_eq!(
+            validate(&create_module(vec![
+                Instruction::I32Const(0),
+                Instruction::ThrowRef,
+                Instruction::End
+            ])),
+            Err(ValidationError::FunctionError(
+                0,
+                1,
+                FunctionError::OperandTypeMismatch(ValueType::ExnRef, ValueType::I32)
+            ))
+        );
+
+        // 标签的类型不能有返回值
+        let mut m0 = create_module(vec![Instruction::I32Const(0), Instruction::End]);
+        m0.tag_items.push(TagType { type_index: 0 });
+        assert_eq!(
+            validate(&m0),
+            Err(ValidationError::ModuleError(ModuleError::InvalidTagType(1)))
+        );
+    }
+}